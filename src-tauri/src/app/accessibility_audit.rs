@@ -0,0 +1,283 @@
+use std::collections::HashMap;
+
+use crate::app::adb::input::parse_bounds_rect;
+use crate::app::models::AccessibilityFinding;
+use crate::app::screenshot_diff::RgbaImage;
+use crate::app::ui_diff::UiNodeSnapshot;
+
+pub const FINDING_MISSING_LABEL: &str = "missing_label";
+pub const FINDING_SMALL_TOUCH_TARGET: &str = "small_touch_target";
+pub const FINDING_LOW_CONTRAST: &str = "low_contrast";
+
+pub const SEVERITY_ERROR: &str = "error";
+pub const SEVERITY_WARNING: &str = "warning";
+pub const SEVERITY_INFO: &str = "info";
+
+/// Android accessibility guidance: touch targets at least 48x48dp.
+pub const MIN_TOUCH_TARGET_DP: f64 = 48.0;
+/// WCAG AA: 4.5:1 for body text, 3:1 for large text. Text size is not in the dump, so
+/// ratios between the two are reported as info.
+pub const MIN_TEXT_CONTRAST: f64 = 4.5;
+pub const MIN_LARGE_TEXT_CONTRAST: f64 = 3.0;
+pub const DEFAULT_DENSITY_DPI: u32 = 160;
+
+/// Upper bound on sampled pixels per node, so full-screen text views stay cheap.
+const MAX_CONTRAST_SAMPLES: u64 = 20_000;
+/// Fewer foreground pixels than this fraction means no visible text to judge.
+const MIN_FOREGROUND_FRACTION: f64 = 0.005;
+
+pub fn px_to_dp(px: u32, density_dpi: u32) -> f64 {
+    f64::from(px) * 160.0 / f64::from(density_dpi.max(1))
+}
+
+fn finding(
+    node: &UiNodeSnapshot,
+    kind: &str,
+    severity: &str,
+    message: String,
+    contrast_ratio: Option<f64>,
+) -> AccessibilityFinding {
+    AccessibilityFinding {
+        kind: kind.to_string(),
+        severity: severity.to_string(),
+        path: node.path.clone(),
+        class_name: node.class_name.clone(),
+        resource_id: node.resource_id.clone(),
+        text: node.text.clone(),
+        bounds: node.bounds.clone(),
+        message,
+        contrast_ratio,
+    }
+}
+
+/// A clickable node is labelled by its own text, content-desc or hint, or by any
+/// descendant's text or content-desc (a row whose child TextView reads out).
+fn has_label(node: &UiNodeSnapshot, nodes: &[UiNodeSnapshot]) -> bool {
+    let prefix = format!("{}/", node.path);
+    let labelled = |candidate: &UiNodeSnapshot| {
+        !candidate.text.trim().is_empty() || !candidate.content_desc.trim().is_empty()
+    };
+    labelled(node)
+        || !node.hint.trim().is_empty()
+        || nodes
+            .iter()
+            .any(|candidate| candidate.path.starts_with(&prefix) && labelled(candidate))
+}
+
+/// Label and touch-target findings for enabled, on-screen clickable nodes.
+pub fn audit_nodes(nodes: &[UiNodeSnapshot], density_dpi: u32) -> Vec<AccessibilityFinding> {
+    let mut findings = Vec::new();
+    for node in nodes.iter().filter(|node| node.clickable && node.enabled) {
+        let Some((left, top, right, bottom)) = parse_bounds_rect(&node.bounds) else {
+            continue;
+        };
+        if right == left || bottom == top {
+            continue;
+        }
+        if !has_label(node, nodes) {
+            findings.push(finding(
+                node,
+                FINDING_MISSING_LABEL,
+                SEVERITY_ERROR,
+                "Clickable element has no text or content-desc for screen readers".to_string(),
+                None,
+            ));
+        }
+        let width_dp = px_to_dp(right - left, density_dpi);
+        let height_dp = px_to_dp(bottom - top, density_dpi);
+        if width_dp < MIN_TOUCH_TARGET_DP || height_dp < MIN_TOUCH_TARGET_DP {
+            findings.push(finding(
+                node,
+                FINDING_SMALL_TOUCH_TARGET,
+                SEVERITY_WARNING,
+                format!(
+                    "Touch target is {width_dp:.0}x{height_dp:.0}dp, below {MIN_TOUCH_TARGET_DP:.0}x{MIN_TOUCH_TARGET_DP:.0}dp"
+                ),
+                None,
+            ));
+        }
+    }
+    findings
+}
+
+/// WCAG relative luminance of an sRGB pixel.
+pub fn relative_luminance(pixel: &[u8]) -> f64 {
+    let channel = |value: u8| {
+        let value = f64::from(value) / 255.0;
+        if value <= 0.039_28 {
+            value / 12.92
+        } else {
+            ((value + 0.055) / 1.055).powf(2.4)
+        }
+    };
+    0.2126 * channel(pixel[0]) + 0.7152 * channel(pixel[1]) + 0.0722 * channel(pixel[2])
+}
+
+pub fn contrast_ratio(a: f64, b: f64) -> f64 {
+    let (light, dark) = if a >= b { (a, b) } else { (b, a) };
+    (light + 0.05) / (dark + 0.05)
+}
+
+/// Estimated text contrast inside `bounds`: the most common colour is taken as the
+/// background and the strongest-contrasting pixel as the text colour. Anti-aliasing only
+/// lowers contrast, so the maximum approximates the glyph colour. `None` when the area
+/// shows no foreground at all.
+pub fn estimate_text_contrast(image: &RgbaImage, bounds: (u32, u32, u32, u32)) -> Option<f64> {
+    let (left, top, right, bottom) = bounds;
+    let right = right.min(image.width);
+    let bottom = bottom.min(image.height);
+    if right <= left || bottom <= top {
+        return None;
+    }
+    let area = u64::from(right - left) * u64::from(bottom - top);
+    let stride = ((area as f64 / MAX_CONTRAST_SAMPLES as f64).sqrt().ceil() as u32).max(1);
+
+    let mut samples: Vec<[u8; 3]> = Vec::new();
+    let mut buckets: HashMap<[u8; 3], usize> = HashMap::new();
+    for y in (top..bottom).step_by(stride as usize) {
+        for x in (left..right).step_by(stride as usize) {
+            let Some(pixel) = image.pixel(x, y) else {
+                continue;
+            };
+            let rgb = [pixel[0], pixel[1], pixel[2]];
+            *buckets.entry(rgb.map(|value| value >> 4)).or_default() += 1;
+            samples.push(rgb);
+        }
+    }
+    let (background_bucket, _) = buckets.iter().max_by_key(|(_, count)| **count)?;
+    let background = samples
+        .iter()
+        .find(|rgb| rgb.map(|value| value >> 4) == *background_bucket)
+        .map(|rgb| relative_luminance(rgb))?;
+
+    let foreground: Vec<f64> = samples
+        .iter()
+        .filter(|rgb| rgb.map(|value| value >> 4) != *background_bucket)
+        .map(|rgb| contrast_ratio(background, relative_luminance(rgb)))
+        .collect();
+    if (foreground.len() as f64) < samples.len() as f64 * MIN_FOREGROUND_FRACTION {
+        return None;
+    }
+    foreground.into_iter().reduce(f64::max)
+}
+
+/// Low-contrast hints for nodes that show text, estimated from a screenshot taken with the
+/// hierarchy dump.
+pub fn audit_contrast(nodes: &[UiNodeSnapshot], image: &RgbaImage) -> Vec<AccessibilityFinding> {
+    let mut findings = Vec::new();
+    for node in nodes.iter().filter(|node| !node.text.trim().is_empty()) {
+        let Some(bounds) = parse_bounds_rect(&node.bounds) else {
+            continue;
+        };
+        let Some(ratio) = estimate_text_contrast(image, bounds) else {
+            continue;
+        };
+        let severity = if ratio < MIN_LARGE_TEXT_CONTRAST {
+            SEVERITY_WARNING
+        } else if ratio < MIN_TEXT_CONTRAST {
+            SEVERITY_INFO
+        } else {
+            continue;
+        };
+        let ratio = (ratio * 100.0).round() / 100.0;
+        findings.push(finding(
+            node,
+            FINDING_LOW_CONTRAST,
+            severity,
+            format!(
+                "Estimated text contrast {ratio:.2}:1 is below {MIN_TEXT_CONTRAST}:1 (verify manually)"
+            ),
+            Some(ratio),
+        ));
+    }
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::ui_diff::extract_ui_nodes;
+
+    const XML: &str = "<?xml version='1.0' ?><hierarchy rotation=\"0\">\
+<node index=\"0\" class=\"android.widget.FrameLayout\" clickable=\"false\" enabled=\"true\" bounds=\"[0,0][1080,2400]\">\
+<node index=\"0\" class=\"android.widget.ImageButton\" content-desc=\"\" clickable=\"true\" enabled=\"true\" bounds=\"[0,0][84,84]\" />\
+<node index=\"1\" class=\"android.widget.LinearLayout\" clickable=\"true\" enabled=\"true\" bounds=\"[0,200][1080,400]\">\
+<node index=\"0\" class=\"android.widget.TextView\" text=\"Settings\" clickable=\"false\" enabled=\"true\" bounds=\"[40,220][400,380]\" />\
+</node>\
+<node index=\"2\" class=\"android.widget.ImageButton\" content-desc=\"Close\" long-clickable=\"true\" enabled=\"true\" bounds=\"[900,0][1080,180]\" />\
+<node index=\"3\" class=\"android.widget.Button\" clickable=\"true\" enabled=\"false\" bounds=\"[0,500][10,510]\" />\
+</node></hierarchy>";
+
+    fn image(width: u32, height: u32, background: [u8; 4]) -> RgbaImage {
+        RgbaImage {
+            width,
+            height,
+            pixels: background.repeat((width * height) as usize),
+        }
+    }
+
+    fn paint(image: &mut RgbaImage, x: u32, y: u32, rgba: [u8; 4]) {
+        let offset = ((y * image.width + x) * 4) as usize;
+        image.pixels[offset..offset + 4].copy_from_slice(&rgba);
+    }
+
+    #[test]
+    fn flags_missing_labels_and_small_targets() {
+        let nodes = extract_ui_nodes(XML);
+        // 420dpi: 84px is 32dp, 180px is ~69dp.
+        let findings = audit_nodes(&nodes, 420);
+        let kinds: Vec<(&str, &str)> = findings
+            .iter()
+            .map(|finding| (finding.class_name.as_str(), finding.kind.as_str()))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ("android.widget.ImageButton", FINDING_MISSING_LABEL),
+                ("android.widget.ImageButton", FINDING_SMALL_TOUCH_TARGET),
+            ]
+        );
+        assert!(findings[1].message.contains("32x32dp"));
+        assert!(audit_nodes(&nodes, 160)
+            .iter()
+            .all(|finding| finding.kind != FINDING_SMALL_TOUCH_TARGET));
+    }
+
+    #[test]
+    fn computes_contrast_ratios() {
+        let white = relative_luminance(&[255, 255, 255]);
+        let black = relative_luminance(&[0, 0, 0]);
+        assert!((contrast_ratio(white, black) - 21.0).abs() < 1e-9);
+        assert!((contrast_ratio(black, black) - 1.0).abs() < 1e-9);
+        assert_eq!(px_to_dp(96, 320), 48.0);
+    }
+
+    #[test]
+    fn estimates_text_contrast_from_pixels() {
+        let mut light_gray = image(20, 10, [255, 255, 255, 255]);
+        for x in 2..10 {
+            paint(&mut light_gray, x, 5, [200, 200, 200, 255]);
+        }
+        let ratio = estimate_text_contrast(&light_gray, (0, 0, 20, 10)).unwrap();
+        assert!(ratio < MIN_LARGE_TEXT_CONTRAST, "{ratio}");
+
+        let mut dark = image(20, 10, [255, 255, 255, 255]);
+        for x in 2..10 {
+            paint(&mut dark, x, 5, [20, 20, 20, 255]);
+        }
+        assert!(estimate_text_contrast(&dark, (0, 0, 20, 10)).unwrap() > MIN_TEXT_CONTRAST);
+        assert_eq!(
+            estimate_text_contrast(&image(20, 10, [0, 0, 0, 255]), (0, 0, 20, 10)),
+            None
+        );
+        assert_eq!(estimate_text_contrast(&dark, (30, 30, 40, 40)), None);
+
+        let nodes = extract_ui_nodes(
+            "<node index=\"0\" class=\"android.widget.TextView\" text=\"Hint\" bounds=\"[0,0][20,10]\" />",
+        );
+        let findings = audit_contrast(&nodes, &light_gray);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].severity, SEVERITY_WARNING);
+        assert!(audit_contrast(&nodes, &dark).is_empty());
+    }
+}
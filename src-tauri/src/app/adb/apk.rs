@@ -5,7 +5,7 @@ use std::path::{Path, PathBuf};
 use tempfile::TempDir;
 use zip::ZipArchive;
 
-use crate::app::models::ApkInfo;
+use crate::app::models::{ApkInfo, NativeLibAbi};
 
 pub struct SplitApkBundle {
     pub apk_paths: Vec<String>,
@@ -109,6 +109,100 @@ pub fn get_apk_info(path: &str) -> ApkInfo {
     info
 }
 
+pub struct ApkBinaryContents {
+    pub dex_count: usize,
+    pub native_libs: Vec<NativeLibAbi>,
+}
+
+/// Count `classes*.dex` and group `lib/<abi>/*.so` entries by ABI across an app's base and
+/// split APKs; ABI splits (`split_config.arm64_v8a.apk`) carry the native libraries.
+pub fn inspect_apk_binaries<P: AsRef<Path>>(paths: &[P]) -> Result<ApkBinaryContents, String> {
+    let mut dex_count = 0usize;
+    let mut by_abi: std::collections::BTreeMap<String, Vec<String>> =
+        std::collections::BTreeMap::new();
+    for path in paths {
+        let path = path.as_ref();
+        let file = File::open(path)
+            .map_err(|err| format!("Failed to open APK {}: {err}", path.display()))?;
+        let archive = ZipArchive::new(file)
+            .map_err(|err| format!("Invalid APK {}: {err}", path.display()))?;
+        collect_apk_binaries(&archive, &mut dex_count, &mut by_abi);
+    }
+
+    let native_libs = by_abi
+        .into_iter()
+        .map(|(abi, mut libraries)| {
+            libraries.sort();
+            libraries.dedup();
+            NativeLibAbi { abi, libraries }
+        })
+        .collect();
+    Ok(ApkBinaryContents {
+        dex_count,
+        native_libs,
+    })
+}
+
+fn collect_apk_binaries(
+    archive: &ZipArchive<File>,
+    dex_count: &mut usize,
+    by_abi: &mut std::collections::BTreeMap<String, Vec<String>>,
+) {
+    for name in archive.file_names() {
+        if name.starts_with("classes") && name.ends_with(".dex") && !name.contains('/') {
+            *dex_count += 1;
+            continue;
+        }
+        let Some(rest) = name.strip_prefix("lib/") else {
+            continue;
+        };
+        let Some((abi, library)) = rest.split_once('/') else {
+            continue;
+        };
+        if abi.is_empty() || library.contains('/') || !library.ends_with(".so") {
+            continue;
+        }
+        by_abi
+            .entry(abi.to_string())
+            .or_default()
+            .push(library.to_string());
+    }
+}
+
+/// Explain the usual reasons an installed APK refuses to run on a device.
+pub fn binary_compatibility_issues(
+    native_libs: &[NativeLibAbi],
+    device_abis: &[String],
+    min_sdk: Option<i64>,
+    device_sdk: Option<i64>,
+) -> Vec<String> {
+    let mut issues = Vec::new();
+    if !native_libs.is_empty() && !device_abis.is_empty() {
+        let supported = native_libs
+            .iter()
+            .any(|entry| device_abis.iter().any(|abi| abi == &entry.abi));
+        if !supported {
+            let shipped = native_libs
+                .iter()
+                .map(|entry| entry.abi.as_str())
+                .collect::<Vec<_>>()
+                .join(", ");
+            issues.push(format!(
+                "No native libraries for device ABIs ({}); APK ships {shipped}",
+                device_abis.join(", ")
+            ));
+        }
+    }
+    if let (Some(min_sdk), Some(device_sdk)) = (min_sdk, device_sdk) {
+        if min_sdk > device_sdk {
+            issues.push(format!(
+                "minSdk {min_sdk} is above the device API level {device_sdk}"
+            ));
+        }
+    }
+    issues
+}
+
 pub fn normalize_apk_path(path: &str) -> PathBuf {
     if path.starts_with("~/") {
         if let Ok(home) = std::env::var("HOME") {
@@ -141,4 +235,69 @@ mod tests {
         assert_eq!(bundle.apk_paths.len(), 2);
         assert!(bundle.apk_paths[0].ends_with("base.apk"));
     }
+
+    #[test]
+    fn inspects_dex_and_native_libs() {
+        let tmp = TempDir::new().expect("tmp");
+        let write_apk = |file_name: &str, entries: &[&str]| {
+            let apk_path = tmp.path().join(file_name);
+            let file = File::create(&apk_path).expect("apk");
+            let mut zip = zip::ZipWriter::new(file);
+            for name in entries {
+                zip.start_file(*name, FileOptions::<()>::default()).unwrap();
+                zip.write_all(b"x").unwrap();
+            }
+            zip.finish().unwrap();
+            apk_path
+        };
+        let base = write_apk(
+            "base.apk",
+            &[
+                "classes.dex",
+                "classes2.dex",
+                "lib/x86_64/libfoo.so",
+                "lib/x86_64/notes.txt",
+                "assets/classes.dex",
+            ],
+        );
+        let abi_split = write_apk(
+            "split_config.arm64_v8a.apk",
+            &["lib/arm64-v8a/libfoo.so", "lib/arm64-v8a/libbar.so"],
+        );
+        let feature_split = write_apk(
+            "split_feature.apk",
+            &["classes.dex", "lib/arm64-v8a/libfoo.so"],
+        );
+
+        let contents = inspect_apk_binaries(&[base, abi_split, feature_split]).expect("inspect");
+        assert_eq!(contents.dex_count, 3);
+        assert_eq!(
+            contents.native_libs,
+            vec![
+                NativeLibAbi {
+                    abi: "arm64-v8a".to_string(),
+                    libraries: vec!["libbar.so".to_string(), "libfoo.so".to_string()],
+                },
+                NativeLibAbi {
+                    abi: "x86_64".to_string(),
+                    libraries: vec!["libfoo.so".to_string()],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_abi_and_sdk_mismatches() {
+        let libs = vec![NativeLibAbi {
+            abi: "x86_64".to_string(),
+            libraries: vec!["libfoo.so".to_string()],
+        }];
+        let device = vec!["arm64-v8a".to_string(), "armeabi-v7a".to_string()];
+        let issues = binary_compatibility_issues(&libs, &device, Some(30), Some(28));
+        assert_eq!(issues.len(), 2);
+        assert!(issues[0].contains("x86_64"));
+        assert!(issues[1].contains("minSdk 30"));
+
+        assert!(binary_compatibility_issues(&[], &device, Some(21), Some(28)).is_empty());
+    }
 }
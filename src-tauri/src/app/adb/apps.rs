@@ -1,4 +1,9 @@
-use crate::app::models::AppInfo;
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{self, AtomicUsize};
+use std::sync::mpsc;
+
+use crate::app::models::{AppInfo, AppListPage, AppListQuery, StartupTimeStats};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct PackageEntry {
@@ -287,6 +292,91 @@ pub fn parse_dumpsys_target_sdk(output: &str) -> Option<i64> {
         .or_else(|| parse_dumpsys_int(output, "targetSdkVersion:"))
 }
 
+pub fn parse_dumpsys_min_sdk(output: &str) -> Option<i64> {
+    parse_dumpsys_int(output, "minSdk=").or_else(|| parse_dumpsys_int(output, "minSdkVersion="))
+}
+
+/// `primaryCpuAbi=null` means the package ships no native code (or runs with the default ABI).
+pub fn parse_dumpsys_primary_cpu_abi(output: &str) -> Option<String> {
+    parse_dumpsys_value(output, "primaryCpuAbi=").filter(|value| value != "null")
+}
+
+/// Look for DEBUGGABLE in the `flags=[ ... ]` / `pkgFlags=[ ... ]` lists.
+pub fn parse_dumpsys_debuggable(output: &str) -> bool {
+    output.lines().any(|line| {
+        let trimmed = line.trim();
+        (trimmed.starts_with("flags=[") || trimmed.starts_with("pkgFlags=["))
+            && trimmed
+                .split(|ch: char| ch.is_whitespace() || ch == '[' || ch == ']')
+                .any(|flag| flag == "DEBUGGABLE")
+    })
+}
+
+/// Last line of `cmd package resolve-activity --brief` is the `pkg/.Activity` component.
+pub fn parse_resolve_activity_brief(output: &str) -> Option<String> {
+    output
+        .lines()
+        .map(str::trim)
+        .rev()
+        .find(|line| !line.is_empty())
+        .filter(|line| line.contains('/') && !line.contains(' '))
+        .map(str::to_string)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AmStartWait {
+    pub total_time_ms: Option<u32>,
+    pub wait_time_ms: Option<u32>,
+    pub launch_state: Option<String>,
+}
+
+/// Parse `am start -W`; fails when the status line or an `Error:` reports a failed launch.
+pub fn parse_am_start_wait(output: &str) -> Result<AmStartWait, String> {
+    let mut result = AmStartWait {
+        total_time_ms: None,
+        wait_time_ms: None,
+        launch_state: None,
+    };
+    for line in output.lines() {
+        let line = line.trim();
+        if let Some(error) = line.strip_prefix("Error:") {
+            return Err(error.trim().to_string());
+        }
+        if let Some(status) = line.strip_prefix("Status:") {
+            let status = status.trim();
+            if status != "ok" {
+                return Err(format!("Launch status: {status}"));
+            }
+        } else if let Some(value) = line.strip_prefix("TotalTime:") {
+            result.total_time_ms = value.trim().parse().ok();
+        } else if let Some(value) = line.strip_prefix("WaitTime:") {
+            result.wait_time_ms = value.trim().parse().ok();
+        } else if let Some(value) = line.strip_prefix("LaunchState:") {
+            result.launch_state = Some(value.trim().to_string());
+        }
+    }
+    if result.total_time_ms.is_none() && result.wait_time_ms.is_none() {
+        return Err("am start -W reported no timing".to_string());
+    }
+    Ok(result)
+}
+
+pub fn summarize_durations(values: &[u32]) -> Option<StartupTimeStats> {
+    let mut sorted = values.to_vec();
+    sorted.sort_unstable();
+    let mid = sorted.len() / 2;
+    let median_ms = if sorted.len().is_multiple_of(2) {
+        (sorted.get(mid.checked_sub(1)?)? + sorted[mid]) / 2
+    } else {
+        sorted[mid]
+    };
+    Some(StartupTimeStats {
+        min_ms: *sorted.first()?,
+        median_ms,
+        max_ms: *sorted.last()?,
+    })
+}
+
 pub fn parse_pm_path_output(output: &str) -> Vec<String> {
     let mut paths = Vec::new();
     for raw in output.lines() {
@@ -315,7 +405,315 @@ pub fn package_entry_to_app_info(
         version_code,
         is_system: entry.is_system,
         apk_path: entry.apk_path,
+        enabled: None,
+        target_sdk: None,
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AppVersion {
+    pub version_name: Option<String>,
+    pub version_code: Option<String>,
+    pub target_sdk: Option<i64>,
+}
+
+pub fn parse_dumpsys_version(output: &str) -> AppVersion {
+    AppVersion {
+        version_name: parse_dumpsys_version_name(output),
+        version_code: parse_dumpsys_version_code(output),
+        target_sdk: parse_dumpsys_target_sdk(output),
+    }
+}
+
+/// `disabled` is the `pm list packages -d` set, or `None` when it could not be read.
+pub fn build_app_info(
+    entry: &PackageEntry,
+    version: Option<&AppVersion>,
+    disabled: Option<&HashSet<String>>,
+) -> AppInfo {
+    let version = version.cloned().unwrap_or_default();
+    AppInfo {
+        enabled: disabled.map(|set| !set.contains(&entry.package_name)),
+        target_sdk: version.target_sdk,
+        ..package_entry_to_app_info(entry.clone(), version.version_name, version.version_code)
+    }
+}
+
+pub const APP_LIST_SORT_KEYS: [&str; 3] = ["package_name", "version_code", "target_sdk"];
+
+/// Page filters only; `offset`, `limit` and the sort order do not affect a match.
+pub fn app_matches_query(app: &AppInfo, query: &AppListQuery) -> bool {
+    if let Some(needle) = query
+        .name_contains
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+    {
+        if !app
+            .package_name
+            .to_lowercase()
+            .contains(&needle.to_lowercase())
+        {
+            return false;
+        }
+    }
+    if let Some(enabled) = query.enabled {
+        // Unknown state counts as enabled; `pm` lists disabled packages explicitly.
+        if app.enabled.unwrap_or(true) != enabled {
+            return false;
+        }
+    }
+    if query.system.is_some_and(|system| app.is_system != system) {
+        return false;
     }
+    if let Some(min) = query.min_target_sdk {
+        if app.target_sdk.is_none_or(|sdk| sdk < min) {
+            return false;
+        }
+    }
+    true
+}
+
+/// Filter, sort and page a full app list. Unknown sort keys fall back to `package_name`;
+/// callers validate against `APP_LIST_SORT_KEYS` first. Missing values sort last.
+pub fn query_app_list(apps: &[AppInfo], query: &AppListQuery) -> AppListPage {
+    let mut matched: Vec<&AppInfo> = apps
+        .iter()
+        .filter(|app| app_matches_query(app, query))
+        .collect();
+    let by_value = |a: Option<i64>, b: Option<i64>| match (a, b) {
+        (Some(a), Some(b)) if query.descending => b.cmp(&a),
+        (Some(a), Some(b)) => a.cmp(&b),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    };
+    let version_code = |app: &AppInfo| app.version_code.as_deref()?.parse::<i64>().ok();
+    matched.sort_by(|a, b| {
+        let by_name = a.package_name.cmp(&b.package_name);
+        match query.sort_by.as_deref() {
+            Some("version_code") => by_value(version_code(a), version_code(b)).then(by_name),
+            Some("target_sdk") => by_value(a.target_sdk, b.target_sdk).then(by_name),
+            _ if query.descending => by_name.reverse(),
+            _ => by_name,
+        }
+    });
+
+    let total = matched.len();
+    let offset = query.offset.unwrap_or(0).min(total);
+    let end = query
+        .limit
+        .map_or(total, |limit| offset.saturating_add(limit).min(total));
+    AppListPage {
+        apps: matched[offset..end]
+            .iter()
+            .map(|app| (*app).clone())
+            .collect(),
+        total,
+        has_more: end < total,
+        next_offset: end,
+    }
+}
+
+/// Run `fetch` for every package on at most `concurrency` threads. Results reach `on_batch` on
+/// the calling thread in groups of `batch_size` as they complete, and are also returned.
+pub fn fetch_versions_parallel(
+    packages: &[String],
+    concurrency: usize,
+    batch_size: usize,
+    fetch: impl Fn(&str) -> AppVersion + Sync,
+    on_batch: &mut dyn FnMut(Vec<(String, AppVersion)>),
+) -> HashMap<String, AppVersion> {
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+    let mut versions = HashMap::with_capacity(packages.len());
+    std::thread::scope(|scope| {
+        for _ in 0..concurrency.clamp(1, packages.len().max(1)) {
+            let sender = sender.clone();
+            let (next, fetch) = (&next, &fetch);
+            scope.spawn(move || {
+                while let Some(package) = packages.get(next.fetch_add(1, atomic::Ordering::Relaxed))
+                {
+                    if sender.send((package.clone(), fetch(package))).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        drop(sender);
+
+        let batch_size = batch_size.max(1);
+        let mut batch = Vec::with_capacity(batch_size);
+        for (package, version) in receiver {
+            versions.insert(package.clone(), version.clone());
+            batch.push((package, version));
+            if batch.len() >= batch_size {
+                on_batch(std::mem::take(&mut batch));
+            }
+        }
+        if !batch.is_empty() {
+            on_batch(batch);
+        }
+    });
+    versions
+}
+
+/// Split `dumpsys package packages` into `(package, block)` pairs. Updated system apps appear
+/// again under "Hidden system packages"; only the first (active) block is kept.
+pub fn split_dumpsys_package_blocks(output: &str) -> Vec<(String, String)> {
+    let mut blocks: Vec<(String, String)> = Vec::new();
+    let mut current: Option<(String, String)> = None;
+    for line in output.lines() {
+        let trimmed = line.trim();
+        if let Some(rest) = trimmed.strip_prefix("Package [") {
+            if let Some(done) = current.take() {
+                blocks.push(done);
+            }
+            if let Some((package, _)) = rest.split_once(']') {
+                current = Some((package.to_string(), String::new()));
+            }
+            continue;
+        }
+        if trimmed.ends_with(':') && !line.starts_with("    ") {
+            // Section headers such as "Hidden system packages:" end the current block.
+            if let Some(done) = current.take() {
+                blocks.push(done);
+            }
+            continue;
+        }
+        if let Some((_, block)) = current.as_mut() {
+            block.push_str(trimmed);
+            block.push('\n');
+        }
+    }
+    if let Some(done) = current {
+        blocks.push(done);
+    }
+    let mut seen = std::collections::HashSet::new();
+    blocks.retain(|(package, _)| seen.insert(package.clone()));
+    blocks
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AppDiskSizes {
+    pub app_bytes: Option<u64>,
+    pub data_bytes: Option<u64>,
+    pub cache_bytes: Option<u64>,
+}
+
+fn parse_diskstats_array(output: &str, key: &str) -> Option<Vec<String>> {
+    let line = output
+        .lines()
+        .map(str::trim)
+        .find(|line| line.starts_with(key))?;
+    let body = line[key.len()..]
+        .trim()
+        .strip_prefix('[')?
+        .strip_suffix(']')?;
+    Some(
+        body.split(',')
+            .map(|item| item.trim().trim_matches('"').to_string())
+            .collect(),
+    )
+}
+
+/// Parse the per-package size arrays from `dumpsys diskstats`.
+pub fn parse_diskstats_app_sizes(output: &str) -> HashMap<String, AppDiskSizes> {
+    let mut sizes = HashMap::new();
+    let Some(names) = parse_diskstats_array(output, "Package Names:") else {
+        return sizes;
+    };
+    let column = |key: &str| parse_diskstats_array(output, key).unwrap_or_default();
+    let app = column("App Sizes:");
+    let data = column("App Data Sizes:");
+    let cache = column("Cache Sizes:");
+    let value_at =
+        |values: &[String], index: usize| values.get(index).and_then(|v| v.parse::<u64>().ok());
+    for (index, name) in names.into_iter().enumerate() {
+        if name.is_empty() {
+            continue;
+        }
+        sizes.insert(
+            name,
+            AppDiskSizes {
+                app_bytes: value_at(&app, index),
+                data_bytes: value_at(&data, index),
+                cache_bytes: value_at(&cache, index),
+            },
+        );
+    }
+    sizes
+}
+
+pub fn is_valid_package_name(value: &str) -> bool {
+    value.contains('.')
+        && value.split('.').all(|segment| {
+            segment
+                .chars()
+                .next()
+                .is_some_and(|ch| ch.is_ascii_alphabetic())
+                && segment
+                    .chars()
+                    .all(|ch| ch.is_ascii_alphanumeric() || ch == '_')
+        })
+}
+
+/// Collect packages holding device admin, device owner or profile owner from
+/// `dumpsys device_policy`. Android refuses to uninstall these until the admin is removed.
+pub fn parse_device_admin_packages(output: &str) -> Vec<String> {
+    let mut packages: Vec<String> = Vec::new();
+    let mut push = |value: &str| {
+        let package = value.trim();
+        if is_valid_package_name(package) && !packages.iter().any(|item| item == package) {
+            packages.push(package.to_string());
+        }
+    };
+    let mut in_admin_list = false;
+    for raw in output.lines() {
+        let line = raw.trim();
+        if line.starts_with("Enabled Device Admins") {
+            in_admin_list = true;
+            continue;
+        }
+        if let Some(start) = line.find("ComponentInfo{") {
+            let rest = &line[start + "ComponentInfo{".len()..];
+            if let Some((package, _)) = rest.split_once('/') {
+                push(package);
+            }
+        }
+        if in_admin_list {
+            match line.strip_suffix(':') {
+                Some(component) if component.contains('/') && !component.contains(' ') => {
+                    if let Some((package, _)) = component.split_once('/') {
+                        push(package);
+                    }
+                }
+                _ if line.is_empty() || !raw.starts_with("    ") => in_admin_list = false,
+                _ => {}
+            }
+        }
+    }
+    packages
+}
+
+/// `adb uninstall` prints `Success` or `Failure [REASON]`, sometimes with exit code 0.
+pub fn parse_uninstall_output(stdout: &str, stderr: &str) -> Result<(), String> {
+    if stdout.lines().any(|line| line.trim() == "Success") {
+        return Ok(());
+    }
+    let combined = format!("{stdout}\n{stderr}");
+    let reason = combined
+        .lines()
+        .map(str::trim)
+        .find(|line| line.starts_with("Failure") || line.contains("DELETE_FAILED"))
+        .or_else(|| {
+            combined
+                .lines()
+                .map(str::trim)
+                .find(|line| !line.is_empty())
+        })
+        .unwrap_or("Unknown uninstall failure");
+    Err(reason.to_string())
 }
 
 fn is_system_path(path: &str) -> bool {
@@ -329,6 +727,173 @@ fn is_system_path(path: &str) -> bool {
 mod tests {
     use super::*;
 
+    #[test]
+    fn fetches_versions_in_bounded_parallel_batches() {
+        let packages: Vec<String> = (0..23)
+            .map(|index| format!("com.example.app{index}"))
+            .collect();
+        let running = AtomicUsize::new(0);
+        let peak = AtomicUsize::new(0);
+        let mut batches = Vec::new();
+        let versions = fetch_versions_parallel(
+            &packages,
+            4,
+            10,
+            |package| {
+                let now = running.fetch_add(1, atomic::Ordering::SeqCst) + 1;
+                peak.fetch_max(now, atomic::Ordering::SeqCst);
+                std::thread::sleep(std::time::Duration::from_millis(5));
+                running.fetch_sub(1, atomic::Ordering::SeqCst);
+                parse_dumpsys_version(&format!(
+                    "versionCode=7 minSdk=24 targetSdk=34\nversionName={}\n",
+                    package.len()
+                ))
+            },
+            &mut |batch| batches.push(batch.len()),
+        );
+        assert_eq!(versions.len(), 23);
+        assert_eq!(batches, vec![10, 10, 3]);
+        assert!(peak.load(atomic::Ordering::SeqCst) <= 4);
+        assert_eq!(
+            versions["com.example.app3"],
+            AppVersion {
+                version_name: Some("16".to_string()),
+                version_code: Some("7".to_string()),
+                target_sdk: Some(34),
+            }
+        );
+        assert!(
+            fetch_versions_parallel(&[], 4, 10, |_| AppVersion::default(), &mut |_| {}).is_empty()
+        );
+    }
+
+    #[test]
+    fn filters_sorts_and_pages_app_lists() {
+        let app =
+            |name: &str, system: bool, enabled: Option<bool>, code: Option<&str>, sdk| AppInfo {
+                package_name: name.to_string(),
+                version_name: None,
+                version_code: code.map(str::to_string),
+                is_system: system,
+                apk_path: None,
+                enabled,
+                target_sdk: sdk,
+            };
+        let apps = vec![
+            app(
+                "com.android.chrome",
+                true,
+                Some(true),
+                Some("600"),
+                Some(34),
+            ),
+            app("com.example.Maps", false, Some(false), Some("90"), Some(30)),
+            app("com.example.mail", false, Some(true), None, None),
+            app("com.example.music", false, None, Some("1200"), Some(33)),
+        ];
+        let names = |page: &AppListPage| -> Vec<String> {
+            page.apps
+                .iter()
+                .map(|app| app.package_name.clone())
+                .collect()
+        };
+
+        let page = query_app_list(
+            &apps,
+            &AppListQuery {
+                name_contains: Some(" EXAMPLE.M ".to_string()),
+                enabled: Some(true),
+                ..Default::default()
+            },
+        );
+        assert_eq!(names(&page), ["com.example.mail", "com.example.music"]);
+
+        let page = query_app_list(
+            &apps,
+            &AppListQuery {
+                system: Some(false),
+                min_target_sdk: Some(31),
+                ..Default::default()
+            },
+        );
+        assert_eq!(names(&page), ["com.example.music"]);
+
+        let by_code = AppListQuery {
+            sort_by: Some("version_code".to_string()),
+            descending: true,
+            offset: Some(1),
+            limit: Some(2),
+            ..Default::default()
+        };
+        let page = query_app_list(&apps, &by_code);
+        // music, chrome, Maps, then mail without a version code.
+        assert_eq!(names(&page), ["com.android.chrome", "com.example.Maps"]);
+        assert_eq!((page.total, page.has_more, page.next_offset), (4, true, 3));
+
+        let page = query_app_list(
+            &apps,
+            &AppListQuery {
+                offset: Some(10),
+                ..Default::default()
+            },
+        );
+        assert!(page.apps.is_empty());
+        assert_eq!((page.total, page.has_more, page.next_offset), (4, false, 4));
+    }
+
+    #[test]
+    fn splits_dumpsys_package_blocks() {
+        let output = "Packages:\n  Package [com.example] (a1b2):\n    userId=10123\n    versionCode=42 minSdk=24 targetSdk=33\n    versionName=1.2\n    firstInstallTime=2024-01-01 10:00:00\n  Package [com.android.chrome] (c3d4):\n    versionCode=6000 minSdk=29\n\nHidden system packages:\n  Package [com.android.chrome] (e5f6):\n    versionCode=5000\n";
+        let blocks = split_dumpsys_package_blocks(output);
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0].0, "com.example");
+        assert_eq!(
+            parse_dumpsys_version_code(&blocks[0].1).as_deref(),
+            Some("42")
+        );
+        assert_eq!(
+            parse_dumpsys_first_install_time(&blocks[0].1).as_deref(),
+            Some("2024-01-01 10:00:00")
+        );
+        assert_eq!(
+            parse_dumpsys_version_code(&blocks[1].1).as_deref(),
+            Some("6000")
+        );
+    }
+
+    #[test]
+    fn parses_diskstats_app_sizes() {
+        let output = "Latency: 2ms [512B Data Write]\nPackage Names: [\"com.a\",\"com.b\"]\nApp Sizes: [1000,2000]\nApp Data Sizes: [10,20]\nCache Sizes: [1,2]\n";
+        let sizes = parse_diskstats_app_sizes(output);
+        assert_eq!(sizes["com.b"].app_bytes, Some(2000));
+        assert_eq!(sizes["com.a"].data_bytes, Some(10));
+        assert_eq!(sizes["com.a"].cache_bytes, Some(1));
+        assert!(parse_diskstats_app_sizes("nothing").is_empty());
+    }
+
+    #[test]
+    fn parses_device_admin_packages() {
+        let output = "Current Device Policy Manager state:\n  Device Owner:\n    admin=ComponentInfo{com.example.mdm/com.example.mdm.AdminReceiver}\n    name=\n  Enabled Device Admins (User 0, provisioningState: 3):\n    com.example.mdm/.AdminReceiver:\n      uid=10123\n    com.google.android.gms/com.google.android.gms.mdm.receivers.MdmDeviceAdminReceiver:\n      uid=10050\n\n  Other:\n    com.not.admin/.Thing:\n";
+        assert_eq!(
+            parse_device_admin_packages(output),
+            vec![
+                "com.example.mdm".to_string(),
+                "com.google.android.gms".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_uninstall_output() {
+        assert!(parse_uninstall_output("Success\n", "").is_ok());
+        assert_eq!(
+            parse_uninstall_output("Failure [DELETE_FAILED_INTERNAL_ERROR]\n", "").unwrap_err(),
+            "Failure [DELETE_FAILED_INTERNAL_ERROR]"
+        );
+        assert!(is_valid_package_name("com.example_app.v2"));
+        assert!(!is_valid_package_name("com.example;rm"));
+    }
+
     #[test]
     fn parses_pm_list_packages_output() {
         let output = "package:/data/app/com.example/base.apk=com.example\npackage:/system/app/Sys.apk=com.android.sys\n";
@@ -385,6 +950,61 @@ mod tests {
         assert_eq!(paths[1], "/b.apk");
     }
 
+    #[test]
+    fn parses_dumpsys_min_sdk_abi_and_debuggable() {
+        let output = "\
+            versionCode=42 minSdk=24 targetSdk=33\n\
+            primaryCpuAbi=arm64-v8a\n\
+            flags=[ DEBUGGABLE HAS_CODE ALLOW_CLEAR_USER_DATA ]\n\
+        ";
+        assert_eq!(parse_dumpsys_min_sdk(output), Some(24));
+        assert_eq!(
+            parse_dumpsys_primary_cpu_abi(output).as_deref(),
+            Some("arm64-v8a")
+        );
+        assert!(parse_dumpsys_debuggable(output));
+
+        let release = "primaryCpuAbi=null\nflags=[ HAS_CODE ALLOW_CLEAR_USER_DATA ]\n";
+        assert_eq!(parse_dumpsys_primary_cpu_abi(release), None);
+        assert!(!parse_dumpsys_debuggable(release));
+    }
+
+    #[test]
+    fn parses_am_start_wait_and_resolve_activity() {
+        let output = "Starting: Intent { cmp=com.example/.MainActivity }\nStatus: ok\nLaunchState: COLD\nActivity: com.example/.MainActivity\nTotalTime: 512\nWaitTime: 530\nComplete\n";
+        let parsed = parse_am_start_wait(output).expect("parsed");
+        assert_eq!(parsed.total_time_ms, Some(512));
+        assert_eq!(parsed.wait_time_ms, Some(530));
+        assert_eq!(parsed.launch_state.as_deref(), Some("COLD"));
+        assert!(
+            parse_am_start_wait("Error: Activity not started, unable to resolve Intent").is_err()
+        );
+        assert!(parse_am_start_wait("Status: timeout\n").is_err());
+
+        assert_eq!(
+            parse_resolve_activity_brief(
+                "priority=0 preferredOrder=0\ncom.example/.MainActivity\n"
+            )
+            .as_deref(),
+            Some("com.example/.MainActivity")
+        );
+        assert_eq!(parse_resolve_activity_brief("No activity found\n"), None);
+    }
+
+    #[test]
+    fn summarizes_startup_durations() {
+        let stats = summarize_durations(&[700, 500, 600, 900]).expect("stats");
+        assert_eq!(
+            (stats.min_ms, stats.median_ms, stats.max_ms),
+            (500, 650, 900)
+        );
+        assert_eq!(
+            summarize_durations(&[300, 100, 200]).unwrap().median_ms,
+            200
+        );
+        assert!(summarize_durations(&[]).is_none());
+    }
+
     #[test]
     fn parses_dumpsys_installer_uid_data_dir_target_sdk() {
         let output = "\
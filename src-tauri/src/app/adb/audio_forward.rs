@@ -0,0 +1,123 @@
+use crate::app::adb::scrcpy::{unsupported_flag_issues, ScrcpyVersion};
+
+pub const AUDIO_CODECS: [&str; 4] = ["opus", "aac", "flac", "raw"];
+pub const AUDIO_SOURCES: [&str; 2] = ["output", "mic"];
+pub const DEFAULT_AUDIO_CODEC: &str = "opus";
+pub const DEFAULT_AUDIO_SOURCE: &str = "output";
+
+/// scrcpy captures device audio through the Android 11 playback capture API.
+pub const MIN_AUDIO_SDK: i64 = 30;
+
+pub const SNDCPY_NOTE: &str =
+    "Older scrcpy releases cannot forward audio on their own; install scrcpy 2.1+ or use sndcpy (https://github.com/rom1v/sndcpy).";
+
+const FLAC_SINCE: ScrcpyVersion = (2, 3);
+
+fn pick(
+    value: Option<&str>,
+    allowed: &[&str],
+    default: &str,
+    field: &str,
+) -> Result<String, String> {
+    let value = value
+        .map(|value| value.trim().to_ascii_lowercase())
+        .filter(|value| !value.is_empty())
+        .unwrap_or_else(|| default.to_string());
+    if allowed.contains(&value.as_str()) {
+        Ok(value)
+    } else {
+        Err(format!(
+            "Unsupported audio {field}: {value} (expected one of {})",
+            allowed.join(", ")
+        ))
+    }
+}
+
+pub fn normalize_audio_codec(codec: Option<&str>) -> Result<String, String> {
+    pick(codec, &AUDIO_CODECS, DEFAULT_AUDIO_CODEC, "codec")
+}
+
+pub fn normalize_audio_source(source: Option<&str>) -> Result<String, String> {
+    pick(source, &AUDIO_SOURCES, DEFAULT_AUDIO_SOURCE, "source")
+}
+
+/// scrcpy arguments (without the program) for an audio-only session.
+pub fn build_audio_forward_args(serial: &str, codec: &str, source: &str) -> Vec<String> {
+    vec![
+        "-s".to_string(),
+        serial.to_string(),
+        "--no-video".to_string(),
+        "--no-control".to_string(),
+        format!("--audio-codec={codec}"),
+        format!("--audio-source={source}"),
+    ]
+}
+
+/// Why the installed scrcpy or the device cannot forward audio, if anything.
+pub fn audio_forward_blocker(
+    args: &[String],
+    codec: &str,
+    version: ScrcpyVersion,
+    device_sdk: Option<i64>,
+) -> Option<String> {
+    let issues = unsupported_flag_issues(args, version);
+    if let Some(issue) = issues.first() {
+        return Some(format!("{} {SNDCPY_NOTE}", issue.message));
+    }
+    if codec == "flac" && version < FLAC_SINCE {
+        return Some(format!(
+            "The flac codec needs scrcpy {}.{} or newer; use opus or aac instead.",
+            FLAC_SINCE.0, FLAC_SINCE.1
+        ));
+    }
+    match device_sdk {
+        Some(sdk) if sdk < MIN_AUDIO_SDK => Some(format!(
+            "Audio forwarding needs Android 11 (API {MIN_AUDIO_SDK}) or newer; this device reports API {sdk}. {SNDCPY_NOTE}"
+        )),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes_codec_and_source() {
+        assert_eq!(normalize_audio_codec(None).unwrap(), "opus");
+        assert_eq!(normalize_audio_codec(Some(" AAC ")).unwrap(), "aac");
+        assert!(normalize_audio_codec(Some("mp3")).is_err());
+        assert_eq!(normalize_audio_source(Some("")).unwrap(), "output");
+        assert_eq!(normalize_audio_source(Some("mic")).unwrap(), "mic");
+        assert!(normalize_audio_source(Some("speaker")).is_err());
+    }
+
+    #[test]
+    fn builds_audio_only_args() {
+        assert_eq!(
+            build_audio_forward_args("R58M123", "opus", "output"),
+            vec![
+                "-s",
+                "R58M123",
+                "--no-video",
+                "--no-control",
+                "--audio-codec=opus",
+                "--audio-source=output"
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_blockers() {
+        let args = build_audio_forward_args("R58M123", "flac", "output");
+        let old = audio_forward_blocker(&args, "flac", (1, 25), Some(33)).unwrap();
+        assert!(old.contains("--no-video needs scrcpy 2.1"));
+        assert!(old.contains("sndcpy"));
+        let flac = audio_forward_blocker(&args, "flac", (2, 1), Some(33)).unwrap();
+        assert!(flac.contains("flac codec"));
+        let sdk = audio_forward_blocker(&args, "opus", (2, 4), Some(29)).unwrap();
+        assert!(sdk.contains("API 29"));
+        assert_eq!(audio_forward_blocker(&args, "opus", (2, 4), Some(30)), None);
+        assert_eq!(audio_forward_blocker(&args, "opus", (2, 4), None), None);
+    }
+}
@@ -0,0 +1,197 @@
+use crate::app::adb::apps::is_valid_package_name;
+use crate::app::models::{AccessibilityServiceInfo, InputMethodInfo};
+
+pub const ACCESSIBILITY_SERVICE_ACTION: &str = "android.accessibilityservice.AccessibilityService";
+pub const ENABLED_ACCESSIBILITY_SERVICES: &str = "enabled_accessibility_services";
+pub const DEFAULT_INPUT_METHOD: &str = "default_input_method";
+
+/// `package/class` or `package/.Class`, as accessibility services and IMEs are identified.
+pub fn is_valid_component(value: &str) -> bool {
+    let Some((package, class)) = value.split_once('/') else {
+        return false;
+    };
+    let class = class.strip_prefix('.').unwrap_or(class);
+    is_valid_package_name(package)
+        && !class.is_empty()
+        && class.split('.').all(|segment| {
+            segment
+                .chars()
+                .next()
+                .is_some_and(|ch| ch.is_ascii_alphabetic() || ch == '_')
+                && segment
+                    .chars()
+                    .all(|ch| ch.is_ascii_alphanumeric() || ch == '_' || ch == '$')
+        })
+}
+
+/// Expand the `package/.Class` shorthand so components compare equal however they were written.
+pub fn expand_component(component: &str) -> String {
+    match component.trim().split_once('/') {
+        Some((package, class)) if class.starts_with('.') => format!("{package}/{package}{class}"),
+        _ => component.trim().to_string(),
+    }
+}
+
+/// Components from `cmd package query-services` or `ime list -s`, in order, skipping the
+/// headers and `priority=...` detail lines around them.
+pub fn parse_component_lines(output: &str) -> Vec<String> {
+    let mut components: Vec<String> = Vec::new();
+    for line in output.lines() {
+        for token in line.split_whitespace() {
+            if is_valid_component(token) {
+                let component = expand_component(token);
+                if !components.contains(&component) {
+                    components.push(component);
+                }
+            }
+        }
+    }
+    components
+}
+
+/// The colon-separated `enabled_accessibility_services` setting; `null` when unset.
+pub fn parse_enabled_services(value: &str) -> Vec<String> {
+    let value = value.trim();
+    if value.is_empty() || value == "null" {
+        return Vec::new();
+    }
+    value
+        .split(':')
+        .map(str::trim)
+        .filter(|component| !component.is_empty())
+        .map(expand_component)
+        .collect()
+}
+
+/// Installed services plus any enabled service that is no longer installed, so stale entries
+/// can still be disabled.
+pub fn merge_accessibility_services(
+    installed: &[String],
+    enabled: &[String],
+) -> Vec<AccessibilityServiceInfo> {
+    let mut services: Vec<AccessibilityServiceInfo> = installed
+        .iter()
+        .map(|component| AccessibilityServiceInfo {
+            component: component.clone(),
+            package_name: package_of(component),
+            enabled: enabled.contains(component),
+            installed: true,
+        })
+        .collect();
+    for component in enabled {
+        if !installed.contains(component) {
+            services.push(AccessibilityServiceInfo {
+                component: component.clone(),
+                package_name: package_of(component),
+                enabled: true,
+                installed: false,
+            });
+        }
+    }
+    services
+}
+
+/// `enabled` is the setting value after turning `component` on or off.
+pub fn toggle_enabled_services(enabled: &[String], component: &str, enable: bool) -> Vec<String> {
+    let component = expand_component(component);
+    let mut next: Vec<String> = enabled
+        .iter()
+        .filter(|existing| **existing != component)
+        .cloned()
+        .collect();
+    if enable {
+        next.push(component);
+    }
+    next
+}
+
+pub fn merge_input_methods(
+    available: &[String],
+    enabled: &[String],
+    selected: Option<&str>,
+) -> Vec<InputMethodInfo> {
+    let selected = selected.map(expand_component);
+    available
+        .iter()
+        .map(|id| InputMethodInfo {
+            id: id.clone(),
+            package_name: package_of(id),
+            enabled: enabled.contains(id),
+            selected: selected.as_deref() == Some(id.as_str()),
+        })
+        .collect()
+}
+
+fn package_of(component: &str) -> String {
+    component
+        .split_once('/')
+        .map(|(package, _)| package)
+        .unwrap_or(component)
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merges_accessibility_services() {
+        let installed = parse_component_lines(concat!(
+            "2 services found:\n",
+            "  Service #0:\n",
+            "    priority=0 preferredOrder=0 match=0x108000 specificIndex=-1 isDefault=false\n",
+            "    com.google.android.marvin.talkback/.TalkBackService\n",
+            "  Service #1:\n",
+            "    com.example.test/com.example.test.a11y.DriverService\n",
+        ));
+        assert_eq!(
+            installed,
+            [
+                "com.google.android.marvin.talkback/com.google.android.marvin.talkback.TalkBackService",
+                "com.example.test/com.example.test.a11y.DriverService",
+            ]
+        );
+        let enabled = parse_enabled_services(
+            "com.example.test/.a11y.DriverService:com.gone.app/com.gone.app.Service",
+        );
+        let services = merge_accessibility_services(&installed, &enabled);
+        assert_eq!(services.len(), 3);
+        assert!(!services[0].enabled);
+        assert!(services[1].enabled && services[1].installed);
+        assert!(services[2].enabled && !services[2].installed);
+        assert_eq!(services[2].package_name, "com.gone.app");
+        assert!(parse_enabled_services("null").is_empty());
+
+        let next = toggle_enabled_services(&enabled, "com.gone.app/.Service", false);
+        assert_eq!(
+            next,
+            ["com.example.test/com.example.test.a11y.DriverService"]
+        );
+        let next = toggle_enabled_services(&next, "com.example.test/.a11y.DriverService", true);
+        assert_eq!(next.len(), 1);
+        assert!(!is_valid_component("com.example.test"));
+        assert!(!is_valid_component("com.example/.Svc;reboot"));
+    }
+
+    #[test]
+    fn merges_input_methods() {
+        let available = parse_component_lines(concat!(
+            "com.google.android.inputmethod.latin/com.android.inputmethod.latin.LatinIME\n",
+            "com.example.ime/.TestKeyboard\n",
+        ));
+        let enabled = parse_component_lines(
+            "com.google.android.inputmethod.latin/com.android.inputmethod.latin.LatinIME\n",
+        );
+        let methods = merge_input_methods(
+            &available,
+            &enabled,
+            Some("com.google.android.inputmethod.latin/com.android.inputmethod.latin.LatinIME"),
+        );
+        assert!(methods[0].enabled && methods[0].selected);
+        assert_eq!(
+            methods[1].id,
+            "com.example.ime/com.example.ime.TestKeyboard"
+        );
+        assert!(!methods[1].enabled && !methods[1].selected);
+    }
+}
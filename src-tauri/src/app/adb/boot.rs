@@ -0,0 +1,229 @@
+use std::collections::HashMap;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use tauri::AppHandle;
+use tracing::{info, warn};
+
+use crate::app::adb::runner::run_command_with_timeout;
+use crate::app::metrics::emit_tracked;
+use crate::app::models::{BootInfo, RebootProgress};
+use crate::app::timeouts::{command_timeout, TimeoutKind};
+
+pub const REBOOT_PROGRESS_EVENT: &str = "reboot-progress";
+
+pub const BOOT_STAGE_REBOOTING: &str = "rebooting";
+pub const BOOT_STAGE_WAITING: &str = "waiting";
+pub const BOOT_STAGE_BOOTED: &str = "booted";
+pub const BOOT_STAGE_TIMED_OUT: &str = "timed_out";
+
+pub const DEFAULT_BOOT_WAIT_SECS: u64 = 180;
+const BOOT_WAIT_BOUNDS: (u64, u64) = (10, 1_800);
+const BOOT_POLL_INTERVAL: Duration = Duration::from_secs(2);
+/// `adb reboot` returns before the device drops off, so a `sys.boot_completed=1` read right
+/// after it is still the old boot. It only counts once the device was seen going down, or
+/// after this long for devices that come back faster than one poll.
+const REBOOT_SETTLE: Duration = Duration::from_secs(20);
+
+pub fn boot_wait_timeout(secs: Option<u64>) -> Duration {
+    let (min, max) = BOOT_WAIT_BOUNDS;
+    Duration::from_secs(secs.unwrap_or(DEFAULT_BOOT_WAIT_SECS).clamp(min, max))
+}
+
+/// Decides when a rebooted device has finished booting from successive
+/// `getprop sys.boot_completed` reads (`None` when the device could not be reached).
+#[derive(Debug, Default)]
+pub struct BootWatch {
+    seen_down: bool,
+}
+
+impl BootWatch {
+    pub fn observe(&mut self, boot_completed: Option<bool>, elapsed: Duration) -> bool {
+        match boot_completed {
+            Some(true) => self.seen_down || elapsed >= REBOOT_SETTLE,
+            _ => {
+                self.seen_down = true;
+                false
+            }
+        }
+    }
+}
+
+pub fn parse_boot_completed(stdout: &str) -> bool {
+    stdout.trim() == "1"
+}
+
+/// Poll the device until it reports `sys.boot_completed=1` or `timeout` passes, emitting a
+/// [`REBOOT_PROGRESS_EVENT`] per poll. Returns how long the boot took, or `None` on timeout.
+pub fn wait_for_boot_completed(
+    app: &AppHandle,
+    adb_program: &str,
+    serial: &str,
+    timeout: Duration,
+    trace_id: &str,
+) -> Option<Duration> {
+    let started = Instant::now();
+    let emit = |stage: &str, error: Option<String>| {
+        let progress = RebootProgress {
+            serial: serial.to_string(),
+            stage: stage.to_string(),
+            elapsed_ms: started.elapsed().as_millis() as u64,
+            timeout_ms: timeout.as_millis() as u64,
+            error,
+            trace_id: trace_id.to_string(),
+        };
+        if let Err(err) = emit_tracked(app, REBOOT_PROGRESS_EVENT, progress) {
+            warn!(trace_id = %trace_id, error = %err, "failed to emit reboot progress");
+        }
+    };
+    emit(BOOT_STAGE_REBOOTING, None);
+
+    let args = ["-s", serial, "shell", "getprop", "sys.boot_completed"].map(str::to_string);
+    let mut watch = BootWatch::default();
+    let mut last_error = None;
+    while started.elapsed() < timeout {
+        thread::sleep(BOOT_POLL_INTERVAL);
+        let boot_completed = match run_command_with_timeout(
+            adb_program,
+            &args,
+            command_timeout(TimeoutKind::Probe, trace_id),
+            trace_id,
+        ) {
+            Ok(output) if output.exit_code == Some(0) => {
+                last_error = None;
+                Some(parse_boot_completed(&output.stdout))
+            }
+            // Expected while the device is down; kept for the timeout report.
+            Ok(output) => {
+                last_error = Some(output.stderr.trim().to_string()).filter(|err| !err.is_empty());
+                None
+            }
+            Err(err) => {
+                last_error = Some(err.error);
+                None
+            }
+        };
+        if watch.observe(boot_completed, started.elapsed()) {
+            let elapsed = started.elapsed();
+            info!(trace_id = %trace_id, serial = %serial, elapsed_ms = elapsed.as_millis() as u64, "device finished booting");
+            emit(BOOT_STAGE_BOOTED, None);
+            return Some(elapsed);
+        }
+        emit(BOOT_STAGE_WAITING, last_error.clone());
+    }
+
+    warn!(trace_id = %trace_id, serial = %serial, timeout_secs = timeout.as_secs(), "device did not finish booting in time");
+    emit(BOOT_STAGE_TIMED_OUT, last_error);
+    None
+}
+
+fn prop(props: &HashMap<String, String>, key: &str) -> Option<String> {
+    props
+        .get(key)
+        .map(|value| value.trim())
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+}
+
+fn prop_true(props: &HashMap<String, String>, key: &str) -> bool {
+    prop(props, key).is_some_and(|value| value == "true" || value == "1")
+}
+
+/// Slot, verified boot and update capabilities from a `getprop` dump.
+pub fn parse_boot_info(serial: &str, props: &HashMap<String, String>) -> BootInfo {
+    let current_slot = prop(props, "ro.boot.slot_suffix")
+        .or_else(|| prop(props, "ro.boot.slot"))
+        .map(|slot| slot.trim_start_matches('_').to_string());
+    let ab_update = prop_true(props, "ro.build.ab_update");
+    let virtual_ab = prop_true(props, "ro.virtual_ab.enabled");
+    // `flash.locked` is the older property; `vbmeta.device_state` replaced it with AVB 2.0.
+    let bootloader_locked = match prop(props, "ro.boot.flash.locked").as_deref() {
+        Some("1") => Some(true),
+        Some("0") => Some(false),
+        _ => match prop(props, "ro.boot.vbmeta.device_state").as_deref() {
+            Some("locked") => Some(true),
+            Some("unlocked") => Some(false),
+            _ => None,
+        },
+    };
+    BootInfo {
+        serial: serial.to_string(),
+        current_slot,
+        ab_update,
+        virtual_ab,
+        seamless_updates: ab_update || virtual_ab,
+        verified_boot_state: prop(props, "ro.boot.verifiedbootstate"),
+        verity_mode: prop(props, "ro.boot.veritymode"),
+        bootloader_locked,
+        bootloader_version: prop(props, "ro.bootloader")
+            .or_else(|| prop(props, "ro.boot.bootloader"))
+            .filter(|version| !version.eq_ignore_ascii_case("unknown")),
+        boot_reason: prop(props, "sys.boot.reason").or_else(|| prop(props, "ro.boot.bootreason")),
+        build_fingerprint: prop(props, "ro.build.fingerprint"),
+        security_patch: prop(props, "ro.build.version.security_patch"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn waits_for_the_new_boot() {
+        assert!(parse_boot_completed("1\n"));
+        assert!(!parse_boot_completed(""));
+        assert_eq!(boot_wait_timeout(None), Duration::from_secs(180));
+        assert_eq!(boot_wait_timeout(Some(1)), Duration::from_secs(10));
+
+        // The first read still sees the old boot.
+        let mut watch = BootWatch::default();
+        assert!(!watch.observe(Some(true), Duration::from_secs(2)));
+        assert!(!watch.observe(None, Duration::from_secs(4)));
+        assert!(!watch.observe(Some(false), Duration::from_secs(30)));
+        assert!(watch.observe(Some(true), Duration::from_secs(32)));
+
+        // A device that never looked down is trusted once the settle time has passed.
+        let mut fast = BootWatch::default();
+        assert!(fast.observe(Some(true), REBOOT_SETTLE));
+    }
+
+    #[test]
+    fn parses_boot_info() {
+        let props: HashMap<String, String> = [
+            ("ro.boot.slot_suffix", "_b"),
+            ("ro.build.ab_update", "true"),
+            ("ro.virtual_ab.enabled", "true"),
+            ("ro.boot.verifiedbootstate", "orange"),
+            ("ro.boot.vbmeta.device_state", "unlocked"),
+            ("ro.bootloader", "slider-1.2-9152140"),
+            ("sys.boot.reason", "reboot,ota"),
+            ("ro.build.version.security_patch", "2024-05-05"),
+        ]
+        .into_iter()
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect();
+        let info = parse_boot_info("R58M", &props);
+        assert_eq!(info.current_slot.as_deref(), Some("b"));
+        assert!(info.seamless_updates && info.virtual_ab);
+        assert_eq!(info.verified_boot_state.as_deref(), Some("orange"));
+        assert_eq!(info.bootloader_locked, Some(false));
+        assert_eq!(
+            info.bootloader_version.as_deref(),
+            Some("slider-1.2-9152140")
+        );
+        assert_eq!(info.boot_reason.as_deref(), Some("reboot,ota"));
+        assert_eq!(info.verity_mode, None);
+
+        let legacy = parse_boot_info(
+            "old",
+            &HashMap::from([
+                ("ro.boot.flash.locked".to_string(), "1".to_string()),
+                ("ro.bootloader".to_string(), "unknown".to_string()),
+            ]),
+        );
+        assert_eq!(legacy.current_slot, None);
+        assert!(!legacy.seamless_updates);
+        assert_eq!(legacy.bootloader_locked, Some(true));
+        assert_eq!(legacy.bootloader_version, None);
+    }
+}
@@ -0,0 +1,46 @@
+use std::collections::HashSet;
+
+pub const INPUT_BROADCAST_EVENT_NAME: &str = "input-broadcast";
+pub const MAX_BROADCAST_DEVICES: usize = 32;
+
+/// Trimmed, de-duplicated target serials, leaving out the leader that drives the broadcast.
+pub fn broadcast_targets(leader: Option<&str>, serials: &[String]) -> Result<Vec<String>, String> {
+    let leader = leader.map(str::trim);
+    let mut seen = HashSet::new();
+    let targets: Vec<String> = serials
+        .iter()
+        .map(|serial| serial.trim())
+        .filter(|serial| !serial.is_empty() && Some(*serial) != leader)
+        .filter(|serial| seen.insert(serial.to_string()))
+        .map(str::to_string)
+        .collect();
+    if targets.is_empty() {
+        return Err("At least one target device is required".to_string());
+    }
+    if targets.len() > MAX_BROADCAST_DEVICES {
+        return Err(format!(
+            "At most {MAX_BROADCAST_DEVICES} devices can receive a broadcast"
+        ));
+    }
+    Ok(targets)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dedupes_targets_and_skips_the_leader() {
+        let serials = ["R58M", " emulator-5554 ", "R58M", "", "leader"].map(String::from);
+        assert_eq!(
+            broadcast_targets(Some("leader"), &serials).unwrap(),
+            ["R58M", "emulator-5554"]
+        );
+        assert_eq!(broadcast_targets(None, &serials).unwrap().len(), 3);
+        assert!(broadcast_targets(Some("leader"), &["leader".to_string()]).is_err());
+        let many: Vec<String> = (0..=MAX_BROADCAST_DEVICES)
+            .map(|index| format!("device-{index}"))
+            .collect();
+        assert!(broadcast_targets(None, &many).is_err());
+    }
+}
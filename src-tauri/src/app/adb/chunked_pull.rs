@@ -0,0 +1,349 @@
+use std::fs::{self, OpenOptions};
+use std::io::{Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::app::adb::socket::{SocketError, SyncStat};
+use crate::app::adb::sync::{finish_partial, local_error, partial_path, TransferOutcome};
+use crate::app::shell_pipeline::quote_shell_value;
+
+/// Pulls at least this large (bugreports, recordings, ROM dumps) are fetched in chunks that
+/// survive a cable hiccup instead of restarting from zero.
+pub const CHUNKED_PULL_MIN_BYTES: u64 = 256 * 1024 * 1024;
+pub const CHUNK_BYTES: u64 = 8 * 1024 * 1024;
+/// `dd` block size; chunk starts stay multiples of it so `skip=` needs no byte-level flags,
+/// which older toolbox `dd` builds lack.
+const DD_BLOCK_BYTES: u64 = 1024 * 1024;
+pub const CHUNK_ATTEMPTS: u32 = 6;
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(16);
+pub const MANIFEST_SUFFIX: &str = ".part.json";
+
+/// Progress of a chunked pull, stored next to the `.part` file so a later attempt knows which
+/// bytes are already on disk.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChunkedPullManifest {
+    pub remote_path: String,
+    pub total_bytes: u64,
+    pub remote_mtime: i64,
+    pub chunk_bytes: u64,
+    /// Completed `[start, end)` byte ranges, sorted and merged.
+    pub completed: Vec<(u64, u64)>,
+}
+
+impl ChunkedPullManifest {
+    pub fn new(remote_path: &str, stat: &SyncStat) -> Self {
+        Self {
+            remote_path: remote_path.to_string(),
+            total_bytes: stat.size,
+            remote_mtime: stat.mtime,
+            chunk_bytes: CHUNK_BYTES,
+            completed: Vec::new(),
+        }
+    }
+
+    /// Whether the manifest was written for this exact device file; a changed size or mtime
+    /// means the bytes on disk may no longer match.
+    pub fn describes(&self, remote_path: &str, stat: &SyncStat) -> bool {
+        self.remote_path == remote_path
+            && self.total_bytes == stat.size
+            && self.remote_mtime == stat.mtime
+            && self.chunk_bytes > 0
+            && self.chunk_bytes.is_multiple_of(DD_BLOCK_BYTES)
+    }
+
+    pub fn mark_completed(&mut self, start: u64, end: u64) {
+        self.completed.push((start, end.min(self.total_bytes)));
+        self.completed.sort_unstable();
+        let mut merged: Vec<(u64, u64)> = Vec::with_capacity(self.completed.len());
+        for (start, end) in self.completed.drain(..) {
+            match merged.last_mut() {
+                Some(last) if start <= last.1 => last.1 = last.1.max(end),
+                _ => merged.push((start, end)),
+            }
+        }
+        self.completed = merged;
+    }
+
+    pub fn completed_bytes(&self) -> u64 {
+        self.completed.iter().map(|(start, end)| end - start).sum()
+    }
+
+    /// The next chunk to fetch: the first gap, capped at `chunk_bytes`.
+    pub fn next_missing(&self) -> Option<(u64, u64)> {
+        let mut cursor = 0u64;
+        for &(start, end) in &self.completed {
+            if start > cursor {
+                return Some((cursor, start.min(cursor + self.chunk_bytes)));
+            }
+            cursor = cursor.max(end);
+        }
+        (cursor < self.total_bytes)
+            .then(|| (cursor, self.total_bytes.min(cursor + self.chunk_bytes)))
+    }
+}
+
+pub fn manifest_path(local: &Path) -> PathBuf {
+    let mut name = local.as_os_str().to_owned();
+    name.push(MANIFEST_SUFFIX);
+    PathBuf::from(name)
+}
+
+pub fn load_manifest(path: &Path) -> Option<ChunkedPullManifest> {
+    let raw = fs::read_to_string(path).ok()?;
+    match serde_json::from_str(&raw) {
+        Ok(manifest) => Some(manifest),
+        Err(err) => {
+            warn!(path = %path.display(), error = %err, "chunked pull manifest is invalid, starting over");
+            None
+        }
+    }
+}
+
+fn save_manifest(path: &Path, manifest: &ChunkedPullManifest) -> Result<(), SocketError> {
+    let payload = serde_json::to_string(manifest)
+        .map_err(|err| SocketError::Io(format!("Failed to serialize pull manifest: {err}")))?;
+    let staging = path.with_extension("json.tmp");
+    fs::write(&staging, payload)
+        .and_then(|_| fs::rename(&staging, path))
+        .map_err(|err| SocketError::Io(format!("{}: {err}", path.display())))
+}
+
+/// `dd` reading `[offset, offset + len)` of `remote` to stdout; `offset` is block aligned and
+/// the last chunk may run past the end of the file.
+pub fn dd_chunk_command(remote: &str, offset: u64, len: u64) -> String {
+    format!(
+        "dd if={} bs={DD_BLOCK_BYTES} skip={} count={} 2>/dev/null",
+        quote_shell_value(remote),
+        offset / DD_BLOCK_BYTES,
+        len.div_ceil(DD_BLOCK_BYTES)
+    )
+}
+
+/// `stat -c '%f %s %Y'`: hex mode, size and mtime, for devices the sync stat cannot reach.
+pub fn parse_stat_mode_size_mtime(output: &str) -> Option<SyncStat> {
+    let line = output
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())?;
+    let mut parts = line.split_whitespace();
+    let mode = u32::from_str_radix(parts.next()?, 16).ok()?;
+    let size = parts.next()?.parse().ok()?;
+    let mtime = parts.next()?.parse().ok()?;
+    Some(SyncStat { mode, size, mtime })
+}
+
+pub fn retry_delay(attempt: u32) -> Duration {
+    Duration::from_secs(1u64 << attempt.saturating_sub(1).min(4)).min(MAX_RETRY_DELAY)
+}
+
+fn sleep_unless_cancelled(delay: Duration, cancel: &AtomicBool) -> Result<(), SocketError> {
+    let until = Instant::now() + delay;
+    while Instant::now() < until {
+        if cancel.load(Ordering::Relaxed) {
+            return Err(SocketError::Cancelled);
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+    Ok(())
+}
+
+fn fetch_with_retry(
+    offset: u64,
+    len: u64,
+    cancel: &AtomicBool,
+    fetch: &mut dyn FnMut(u64, u64) -> Result<Vec<u8>, SocketError>,
+    on_retry: &mut dyn FnMut(u32, &str),
+) -> Result<Vec<u8>, SocketError> {
+    let mut attempt = 1;
+    loop {
+        if cancel.load(Ordering::Relaxed) {
+            return Err(SocketError::Cancelled);
+        }
+        let error = match fetch(offset, len) {
+            Ok(data) if data.len() as u64 == len => return Ok(data),
+            Ok(data) => format!(
+                "chunk at byte {offset} returned {} of {len} bytes",
+                data.len()
+            ),
+            Err(SocketError::Cancelled) => return Err(SocketError::Cancelled),
+            Err(err) => err.to_string(),
+        };
+        if attempt >= CHUNK_ATTEMPTS {
+            return Err(SocketError::Io(format!(
+                "{error} (gave up after {attempt} attempts)"
+            )));
+        }
+        on_retry(attempt, &error);
+        sleep_unless_cancelled(retry_delay(attempt), cancel)?;
+        attempt += 1;
+    }
+}
+
+/// Pulls `remote` into `local` one chunk at a time through `<local>.part`, recording finished
+/// ranges in `<local>.part.json`. Failed chunks are retried with backoff so an unplugged cable
+/// only pauses the pull, and a later call continues where the last one stopped.
+pub fn pull_chunked(
+    remote: &str,
+    stat: &SyncStat,
+    local: &Path,
+    cancel: &AtomicBool,
+    fetch: &mut dyn FnMut(u64, u64) -> Result<Vec<u8>, SocketError>,
+    on_progress: &mut dyn FnMut(u64),
+    on_retry: &mut dyn FnMut(u32, &str),
+) -> Result<TransferOutcome, SocketError> {
+    let part = partial_path(local);
+    let manifest_file = manifest_path(local);
+    let mut manifest = match load_manifest(&manifest_file) {
+        Some(manifest) if manifest.describes(remote, stat) && part.exists() => manifest,
+        _ => ChunkedPullManifest::new(remote, stat),
+    };
+    let resumed_from = manifest.completed_bytes();
+
+    let mut file = OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(resumed_from == 0)
+        .open(&part)
+        .map_err(|err| local_error(&part, err))?;
+    save_manifest(&manifest_file, &manifest)?;
+    on_progress(resumed_from);
+
+    while let Some((start, end)) = manifest.next_missing() {
+        let data = fetch_with_retry(start, end - start, cancel, fetch, on_retry)?;
+        file.seek(SeekFrom::Start(start))
+            .and_then(|_| file.write_all(&data))
+            .and_then(|_| file.sync_data())
+            .map_err(|err| local_error(&part, err))?;
+        manifest.mark_completed(start, end);
+        save_manifest(&manifest_file, &manifest)?;
+        on_progress(manifest.completed_bytes());
+    }
+    drop(file);
+
+    finish_partial(&part, local)?;
+    if let Err(err) = fs::remove_file(&manifest_file) {
+        warn!(path = %manifest_file.display(), error = %err, "failed to remove chunked pull manifest");
+    }
+    Ok(TransferOutcome {
+        bytes: stat.size,
+        resumed_from,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stat(size: u64) -> SyncStat {
+        SyncStat {
+            mode: 0o100644,
+            size,
+            mtime: 1_700_000_000,
+        }
+    }
+
+    #[test]
+    fn tracks_completed_ranges_and_gaps() {
+        let mut manifest = ChunkedPullManifest::new("/sdcard/dump.img", &stat(20 * 1024 * 1024));
+        assert_eq!(manifest.next_missing(), Some((0, CHUNK_BYTES)));
+
+        manifest.mark_completed(CHUNK_BYTES, 2 * CHUNK_BYTES);
+        assert_eq!(manifest.next_missing(), Some((0, CHUNK_BYTES)));
+        manifest.mark_completed(0, CHUNK_BYTES);
+        assert_eq!(manifest.completed, vec![(0, 2 * CHUNK_BYTES)]);
+        assert_eq!(
+            manifest.next_missing(),
+            Some((2 * CHUNK_BYTES, 20 * 1024 * 1024))
+        );
+        manifest.mark_completed(2 * CHUNK_BYTES, 20 * 1024 * 1024);
+        assert_eq!(manifest.next_missing(), None);
+        assert_eq!(manifest.completed_bytes(), 20 * 1024 * 1024);
+
+        assert!(manifest.describes("/sdcard/dump.img", &stat(20 * 1024 * 1024)));
+        assert!(!manifest.describes("/sdcard/dump.img", &stat(21 * 1024 * 1024)));
+    }
+
+    #[test]
+    fn builds_dd_commands_and_parses_stat() {
+        assert_eq!(
+            dd_chunk_command("/sdcard/my dump.img", 16 * 1024 * 1024, 3 * 1024 * 1024 + 5),
+            "dd if='/sdcard/my dump.img' bs=1048576 skip=16 count=4 2>/dev/null"
+        );
+        assert_eq!(
+            parse_stat_mode_size_mtime("81a4 734003200 1715000000\n"),
+            Some(SyncStat {
+                mode: 0o100644,
+                size: 734_003_200,
+                mtime: 1_715_000_000,
+            })
+        );
+        assert_eq!(parse_stat_mode_size_mtime("stat: No such file"), None);
+        assert_eq!(retry_delay(1), Duration::from_secs(1));
+        assert_eq!(retry_delay(9), MAX_RETRY_DELAY);
+    }
+
+    #[test]
+    fn resumes_after_an_interrupted_pull() {
+        let dir = std::env::temp_dir().join(format!("chunked_pull_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let local = dir.join("dump.img");
+        let total = 2 * CHUNK_BYTES + 100;
+        let remote: Vec<u8> = (0..total).map(|index| (index % 251) as u8).collect();
+        let slice = |offset: u64, len: u64| {
+            let end = (offset + len).min(total) as usize;
+            remote[offset as usize..end].to_vec()
+        };
+
+        // The cable drops after the first chunk and the user cancels while it retries.
+        let cancel = AtomicBool::new(false);
+        let mut retries = Vec::new();
+        let first = pull_chunked(
+            "/sdcard/dump.img",
+            &stat(total),
+            &local,
+            &cancel,
+            &mut |offset, len| {
+                if offset >= CHUNK_BYTES {
+                    cancel.store(true, Ordering::Relaxed);
+                    return Err(SocketError::Unavailable(
+                        "device 'abc' not found".to_string(),
+                    ));
+                }
+                Ok(slice(offset, len))
+            },
+            &mut |_| {},
+            &mut |attempt, _| retries.push(attempt),
+        );
+        assert_eq!(first.unwrap_err(), SocketError::Cancelled);
+        assert_eq!(retries, vec![1]);
+        assert_eq!(
+            load_manifest(&manifest_path(&local)).unwrap().completed,
+            vec![(0, CHUNK_BYTES)]
+        );
+
+        let mut fetched = Vec::new();
+        let outcome = pull_chunked(
+            "/sdcard/dump.img",
+            &stat(total),
+            &local,
+            &AtomicBool::new(false),
+            &mut |offset, len| {
+                fetched.push(offset);
+                Ok(slice(offset, len))
+            },
+            &mut |_| {},
+            &mut |_, _| {},
+        )
+        .unwrap();
+        assert_eq!(outcome.resumed_from, CHUNK_BYTES);
+        assert_eq!(fetched, vec![CHUNK_BYTES, 2 * CHUNK_BYTES]);
+        assert_eq!(fs::read(&local).unwrap(), remote);
+        assert!(!manifest_path(&local).exists());
+        assert!(!partial_path(&local).exists());
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
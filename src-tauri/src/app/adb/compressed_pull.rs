@@ -0,0 +1,263 @@
+use std::fs::{self, File};
+use std::io::{ErrorKind, Read, Write};
+use std::path::Path;
+use std::process::{Child, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use flate2::read::GzDecoder;
+
+use crate::app::adb::locator::{
+    adb_command, route_adb_program, transport_for_serial, TRANSPORT_TCP,
+};
+use crate::app::adb::socket::{AdbSocketClient, SocketError};
+use crate::app::adb::sync::{
+    check_cancel, finish_partial, io_error, local_error, open_stream, partial_path, SYNC_DATA_MAX,
+    TRANSFER_IDLE_TIMEOUT,
+};
+use crate::app::shell_pipeline::quote_shell_value;
+
+/// Prints the path of the device's `gzip` (toybox ships one from Android 9), or nothing.
+pub const GZIP_PROBE_COMMAND: &str = "command -v gzip";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CompressedPullOutcome {
+    pub bytes: u64,
+    /// Compressed bytes that crossed the link.
+    pub wire_bytes: u64,
+}
+
+/// An explicit choice wins; otherwise TCP/IP devices are compressed when `auto_wireless` is on.
+pub fn should_compress(requested: Option<bool>, serial: &str, auto_wireless: bool) -> bool {
+    requested.unwrap_or_else(|| auto_wireless && transport_for_serial(serial) == TRANSPORT_TCP)
+}
+
+pub fn gzip_command(remote: &str) -> String {
+    format!("gzip -c {} 2>/dev/null", quote_shell_value(remote))
+}
+
+struct CountingReader<R> {
+    inner: R,
+    count: u64,
+}
+
+impl<R: Read> Read for CountingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let count = self.inner.read(buf)?;
+        self.count += count as u64;
+        Ok(count)
+    }
+}
+
+/// Inflates the gzip stream from `source` into `out`, reporting decompressed bytes; returns
+/// (decompressed, compressed) byte counts.
+pub fn inflate_into<R: Read, W: Write>(
+    source: R,
+    out: &mut W,
+    cancel: &AtomicBool,
+    on_bytes: &mut dyn FnMut(u64),
+) -> Result<(u64, u64), SocketError> {
+    let mut decoder = GzDecoder::new(CountingReader {
+        inner: source,
+        count: 0,
+    });
+    let mut buffer = vec![0u8; SYNC_DATA_MAX];
+    let mut total = 0u64;
+    loop {
+        check_cancel(cancel)?;
+        match decoder.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(count) => {
+                out.write_all(&buffer[..count])
+                    .map_err(|err| SocketError::Io(format!("Failed to write local file: {err}")))?;
+                total += count as u64;
+                on_bytes(total);
+            }
+            Err(err) if err.kind() == ErrorKind::Interrupted => continue,
+            Err(err) => return Err(io_error("gzip stream failed", err)),
+        }
+    }
+    Ok((total, decoder.get_ref().count))
+}
+
+/// Inflates into `<local>.part` and moves it over `local` once exactly `total` bytes arrived.
+fn write_inflated(
+    local: &Path,
+    total: u64,
+    inflate: impl FnOnce(&mut File) -> Result<(u64, u64), SocketError>,
+) -> Result<CompressedPullOutcome, SocketError> {
+    let part = partial_path(local);
+    let mut file = File::create(&part).map_err(|err| local_error(&part, err))?;
+    let result = inflate(&mut file).and_then(|(bytes, wire_bytes)| {
+        if bytes != total {
+            return Err(SocketError::Io(format!(
+                "Transfer incomplete: received {bytes} of {total} bytes"
+            )));
+        }
+        file.flush().map_err(|err| local_error(&part, err))?;
+        Ok(CompressedPullOutcome { bytes, wire_bytes })
+    });
+    drop(file);
+    match result {
+        Ok(outcome) => {
+            finish_partial(&part, local)?;
+            Ok(outcome)
+        }
+        Err(err) => {
+            let _ = fs::remove_file(&part);
+            Err(err)
+        }
+    }
+}
+
+/// `gzip -c` on the device through `exec:` on the adb server socket.
+pub fn pull_compressed_via_socket(
+    client: &AdbSocketClient,
+    serial: &str,
+    remote: &str,
+    total: u64,
+    local: &Path,
+    cancel: &AtomicBool,
+    on_progress: &mut dyn FnMut(u64),
+) -> Result<CompressedPullOutcome, SocketError> {
+    let stream = open_stream(client, serial, &format!("exec:{}", gzip_command(remote)))?;
+    write_inflated(local, total, |out| {
+        inflate_into(stream, out, cancel, on_progress)
+    })
+}
+
+/// Kills `child` once the pull is cancelled or no data arrived for `TRANSFER_IDLE_TIMEOUT`;
+/// a pipe has no read timeout of its own.
+fn watch_child(
+    child: &Mutex<Child>,
+    finished: &AtomicBool,
+    last_activity: &Mutex<Instant>,
+    cancel: &AtomicBool,
+    timed_out: &AtomicBool,
+) {
+    while !finished.load(Ordering::Relaxed) {
+        std::thread::sleep(Duration::from_millis(200));
+        let idle = last_activity
+            .lock()
+            .map(|at| at.elapsed() > TRANSFER_IDLE_TIMEOUT)
+            .unwrap_or(false);
+        if idle || cancel.load(Ordering::Relaxed) {
+            timed_out.store(idle, Ordering::Relaxed);
+            if let Ok(mut child) = child.lock() {
+                let _ = child.kill();
+            }
+            return;
+        }
+    }
+}
+
+/// `adb exec-out gzip -c`, for devices the socket client cannot serve.
+pub fn pull_compressed_via_adb(
+    program: &str,
+    serial: &str,
+    remote: &str,
+    total: u64,
+    local: &Path,
+    cancel: &AtomicBool,
+    on_progress: &mut dyn FnMut(u64),
+) -> Result<CompressedPullOutcome, SocketError> {
+    let args = vec![
+        "-s".to_string(),
+        serial.to_string(),
+        "exec-out".to_string(),
+        gzip_command(remote),
+    ];
+    let mut child = adb_command(&route_adb_program(program, &args))
+        .args(&args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|err| SocketError::Io(format!("Failed to spawn adb: {err}")))?;
+    let stdout = child
+        .stdout
+        .take()
+        .ok_or_else(|| SocketError::Io("Failed to capture adb stdout".to_string()))?;
+
+    let child = Mutex::new(child);
+    let finished = AtomicBool::new(false);
+    let timed_out = AtomicBool::new(false);
+    let last_activity = Mutex::new(Instant::now());
+    let result = std::thread::scope(|scope| {
+        scope.spawn(|| watch_child(&child, &finished, &last_activity, cancel, &timed_out));
+        let result = write_inflated(local, total, |out| {
+            inflate_into(stdout, out, cancel, &mut |bytes| {
+                if let Ok(mut at) = last_activity.lock() {
+                    *at = Instant::now();
+                }
+                on_progress(bytes);
+            })
+        });
+        finished.store(true, Ordering::Relaxed);
+        result
+    });
+    if let Ok(mut child) = child.lock() {
+        let _ = child.kill();
+        let _ = child.wait();
+    }
+
+    if cancel.load(Ordering::Relaxed) {
+        return Err(SocketError::Cancelled);
+    }
+    if timed_out.load(Ordering::Relaxed) {
+        return Err(SocketError::TimedOut);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+
+    #[test]
+    fn inflates_gzip_streams_and_counts_wire_bytes() {
+        let text = "04-01 10:00:00.000  1000  1000 I ActivityManager: Start proc\n".repeat(2000);
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(text.as_bytes()).unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        let mut out = Vec::new();
+        let mut last = 0;
+        let (bytes, wire_bytes) = inflate_into(
+            compressed.as_slice(),
+            &mut out,
+            &AtomicBool::new(false),
+            &mut |total| last = total,
+        )
+        .unwrap();
+        assert_eq!(out, text.as_bytes());
+        assert_eq!(bytes, text.len() as u64);
+        assert_eq!(last, bytes);
+        assert_eq!(wire_bytes, compressed.len() as u64);
+        assert!(wire_bytes * 10 < bytes);
+
+        // `gzip: not found` or a permission error arrives as plain text.
+        assert!(inflate_into(
+            b"/system/bin/sh: gzip: inaccessible or not found\n".as_slice(),
+            &mut Vec::new(),
+            &AtomicBool::new(false),
+            &mut |_| {},
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn compresses_wireless_devices_unless_told_otherwise() {
+        assert!(should_compress(None, "192.168.1.20:5555", true));
+        assert!(!should_compress(None, "192.168.1.20:5555", false));
+        assert!(!should_compress(None, "R58M12345", true));
+        assert!(should_compress(Some(true), "R58M12345", false));
+        assert!(!should_compress(Some(false), "192.168.1.20:5555", true));
+        assert_eq!(
+            gzip_command("/sdcard/bug report.txt"),
+            "gzip -c '/sdcard/bug report.txt' 2>/dev/null"
+        );
+    }
+}
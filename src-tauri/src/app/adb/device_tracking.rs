@@ -1,22 +1,61 @@
+use std::collections::{HashMap, HashSet};
 use std::io::BufRead;
-use std::process::{Child, Command, Stdio};
+use std::process::{Child, Stdio};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
 use std::thread::{self, JoinHandle};
 use std::time::{Duration, Instant};
 
-use tauri::{AppHandle, Emitter};
+use chrono::Utc;
+use tauri::AppHandle;
 use tracing::warn;
 
+use crate::app::adb::locator::{adb_command, transport_for_serial, TRANSPORT_USB};
+use crate::app::adb::reconnect::{ReconnectPolicy, ReconnectSupervisor};
+use crate::app::adb::socket::forget_device_capabilities;
 use crate::app::adb::track_devices::TrackDevicesStreamParser;
-use crate::app::models::DeviceInfo;
+use crate::app::device_history::{device_history_path, DeviceHistoryRecorder};
+use crate::app::metrics::emit_tracked;
+use crate::app::models::{DeviceInfo, DeviceSummary, DeviceTransition};
+use crate::app::notifications::{devices_gone_offline, notify_webhooks, NOTIFY_DEVICE_OFFLINE};
 
 pub const DEVICE_TRACKING_SNAPSHOT_EVENT: &str = "device-tracking-snapshot";
+pub const DEVICE_TRANSITION_EVENT: &str = "device-transition";
+pub const DEVICE_DETAIL_UPDATED_EVENT: &str = "device-detail-updated";
+
+pub const TRANSITION_CONNECTED: &str = "connected";
+pub const TRANSITION_DISCONNECTED: &str = "disconnected";
+pub const TRANSITION_AUTHORIZED: &str = "authorized";
+pub const TRANSITION_ONLINE: &str = "online";
+pub const TRANSITION_UNAUTHORIZED: &str = "unauthorized";
+pub const TRANSITION_OFFLINE: &str = "offline";
+pub const TRANSITION_STATE_CHANGED: &str = "state_changed";
+
+/// USB cables and hubs often drop a device for a moment; a ready USB device has to stay
+/// gone this long before the loss is reported.
+pub const USB_FLAP_DEBOUNCE: Duration = Duration::from_millis(1500);
+const DEBOUNCE_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Called for every transition into the `device` state; must not block the tracker.
+pub type DeviceOnlineHook = Arc<dyn Fn(&DeviceTransition) + Send + Sync>;
+
+/// Called with the ready devices whose details need a refresh; must not block the tracker.
+pub type DeviceDetailHook = Arc<dyn Fn(Vec<DeviceSummary>, &str) + Send + Sync>;
+
+struct TrackerHooks {
+    reconnects: ReconnectSupervisor,
+    on_online: DeviceOnlineHook,
+    on_changed: DeviceDetailHook,
+    history: DeviceHistoryRecorder,
+}
 
 pub struct DeviceTrackerHandle {
     stop_flag: Arc<AtomicBool>,
     child: Arc<Mutex<Option<Child>>>,
     join: JoinHandle<()>,
+    debounce_join: JoinHandle<()>,
+    hooks: Arc<TrackerHooks>,
+    trace_id: String,
 }
 
 impl DeviceTrackerHandle {
@@ -29,6 +68,244 @@ impl DeviceTrackerHandle {
             }
         }
         let _ = self.join.join();
+        let _ = self.debounce_join.join();
+        self.hooks.reconnects.stop_all();
+        self.hooks
+            .history
+            .close(Utc::now().timestamp_millis(), &self.trace_id);
+    }
+}
+
+pub fn classify_transition(previous: Option<&str>, current: Option<&str>) -> &'static str {
+    match (previous, current) {
+        (None, Some(_)) => TRANSITION_CONNECTED,
+        (Some(_), None) => TRANSITION_DISCONNECTED,
+        (Some("unauthorized"), Some("device")) => TRANSITION_AUTHORIZED,
+        (Some("offline"), Some("device")) => TRANSITION_ONLINE,
+        (_, Some("unauthorized")) => TRANSITION_UNAUTHORIZED,
+        (_, Some("offline")) => TRANSITION_OFFLINE,
+        _ => TRANSITION_STATE_CHANGED,
+    }
+}
+
+#[derive(Debug, Clone)]
+struct KnownDevice {
+    state: String,
+    model: Option<String>,
+}
+
+/// Turns track-devices snapshots into transitions. The first snapshot only seeds the
+/// known states so starting the tracker does not report every attached device.
+#[derive(Default)]
+pub struct DeviceTransitionTracker {
+    primed: bool,
+    confirmed: HashMap<String, KnownDevice>,
+    latest: HashMap<String, KnownDevice>,
+    pending: HashMap<String, Instant>,
+}
+
+impl DeviceTransitionTracker {
+    pub fn observe(&mut self, snapshot: &[DeviceSummary], now: Instant) -> Vec<DeviceTransition> {
+        self.latest = snapshot
+            .iter()
+            .map(|device| {
+                (
+                    device.serial.clone(),
+                    KnownDevice {
+                        state: device.state.clone(),
+                        model: device.model.clone(),
+                    },
+                )
+            })
+            .collect();
+        if !self.primed {
+            self.primed = true;
+            self.confirmed = self.latest.clone();
+            return Vec::new();
+        }
+
+        let mut serials: Vec<String> = self
+            .confirmed
+            .keys()
+            .chain(self.latest.keys())
+            .cloned()
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        serials.sort();
+
+        let mut transitions = Vec::new();
+        for serial in serials {
+            let previous = self
+                .confirmed
+                .get(&serial)
+                .map(|known| known.state.as_str());
+            let current = self.latest.get(&serial).map(|known| known.state.as_str());
+            if previous == current {
+                self.pending.remove(&serial);
+                continue;
+            }
+            // Only a ready USB device losing its connection is held back; connects and
+            // authorizations are reported right away.
+            if previous == Some("device") && transport_for_serial(&serial) == TRANSPORT_USB {
+                self.pending.entry(serial).or_insert(now);
+                continue;
+            }
+            transitions.extend(self.confirm(&serial));
+        }
+        transitions
+    }
+
+    /// Reports USB losses that outlived the debounce window.
+    pub fn expire(&mut self, now: Instant) -> Vec<DeviceTransition> {
+        let mut due: Vec<String> = self
+            .pending
+            .iter()
+            .filter(|(_, since)| now.saturating_duration_since(**since) >= USB_FLAP_DEBOUNCE)
+            .map(|(serial, _)| serial.clone())
+            .collect();
+        due.sort();
+        due.iter()
+            .filter_map(|serial| self.confirm(serial))
+            .collect()
+    }
+
+    fn confirm(&mut self, serial: &str) -> Option<DeviceTransition> {
+        self.pending.remove(serial);
+        let previous = self.confirmed.get(serial).cloned();
+        let current = self.latest.get(serial).cloned();
+        match &current {
+            Some(known) => {
+                self.confirmed.insert(serial.to_string(), known.clone());
+            }
+            None => {
+                self.confirmed.remove(serial);
+            }
+        }
+        let previous_state = previous.as_ref().map(|known| known.state.clone());
+        let state = current.as_ref().map(|known| known.state.clone());
+        if previous_state == state {
+            return None;
+        }
+        Some(DeviceTransition {
+            serial: serial.to_string(),
+            kind: classify_transition(previous_state.as_deref(), state.as_deref()).to_string(),
+            model: current
+                .and_then(|known| known.model)
+                .or_else(|| previous.and_then(|known| known.model)),
+            previous_state,
+            state,
+        })
+    }
+}
+
+fn emit_transitions(
+    app: &AppHandle,
+    hooks: &TrackerHooks,
+    transitions: Vec<DeviceTransition>,
+    trace_id: &str,
+) {
+    for transition in transitions {
+        forget_device_capabilities(&transition.serial);
+        hooks.reconnects.on_transition(&transition);
+        if transition.state.as_deref() == Some("device") {
+            (hooks.on_online)(&transition);
+        }
+        let payload = serde_json::json!({
+            "trace_id": trace_id,
+            "transition": transition,
+        });
+        if let Err(err) = emit_tracked(app, DEVICE_TRANSITION_EVENT, payload) {
+            warn!(trace_id = %trace_id, error = %err, "failed to emit device transition");
+        }
+    }
+}
+
+fn observe_snapshot(
+    app: &AppHandle,
+    hooks: &TrackerHooks,
+    transitions: &Mutex<DeviceTransitionTracker>,
+    snapshot: &[DeviceSummary],
+    trace_id: &str,
+) {
+    let observed = match transitions.lock() {
+        Ok(mut tracker) => tracker.observe(snapshot, Instant::now()),
+        Err(_) => {
+            warn!(trace_id = %trace_id, "device transition tracker poisoned");
+            return;
+        }
+    };
+    hooks
+        .history
+        .observe(snapshot, Utc::now().timestamp_millis(), trace_id);
+    emit_transitions(app, hooks, observed, trace_id);
+}
+
+/// Ready devices in `current` that are new or whose summary changed since `previous`;
+/// only these need their details recomputed.
+pub fn detail_refresh_targets(
+    previous: &[DeviceSummary],
+    current: &[DeviceSummary],
+) -> Vec<DeviceSummary> {
+    let previous: HashMap<&str, &DeviceSummary> = previous
+        .iter()
+        .map(|summary| (summary.serial.as_str(), summary))
+        .collect();
+    current
+        .iter()
+        .filter(|summary| summary.state == "device")
+        .filter(|summary| previous.get(summary.serial.as_str()) != Some(summary))
+        .cloned()
+        .collect()
+}
+
+/// Pushes the summaries and lists the serials whose details will follow as
+/// `device-detail-updated` events, so the UI does not re-query every device.
+fn emit_snapshot(
+    app: &AppHandle,
+    hooks: &TrackerHooks,
+    last_snapshot: &mut Vec<DeviceSummary>,
+    snapshot: Vec<DeviceSummary>,
+    trace_id: &str,
+) {
+    let targets = detail_refresh_targets(last_snapshot, &snapshot);
+    let changed: Vec<&str> = targets
+        .iter()
+        .map(|summary| summary.serial.as_str())
+        .collect();
+    let devices = snapshot
+        .iter()
+        .cloned()
+        .map(|summary| DeviceInfo {
+            summary,
+            detail: None,
+            notes: None,
+            reservation: None,
+        })
+        .collect::<Vec<_>>();
+    let payload = serde_json::json!({
+        "trace_id": trace_id,
+        "devices": devices,
+        "changed": changed,
+    });
+    if let Err(err) = emit_tracked(app, DEVICE_TRACKING_SNAPSHOT_EVENT, payload) {
+        warn!(trace_id = %trace_id, error = %err, "failed to emit device snapshot");
+    }
+    if !targets.is_empty() {
+        (hooks.on_changed)(targets, trace_id);
+    }
+    *last_snapshot = snapshot;
+}
+
+fn notify_offline(online: &mut HashSet<String>, snapshot: &[DeviceSummary], trace_id: &str) {
+    for serial in devices_gone_offline(online, snapshot) {
+        notify_webhooks(
+            NOTIFY_DEVICE_OFFLINE,
+            &serial,
+            false,
+            &format!("Device {serial} went offline"),
+            trace_id,
+        );
     }
 }
 
@@ -36,15 +313,53 @@ pub fn start_device_tracker(
     app: AppHandle,
     trace_id: String,
     adb_program: String,
+    reconnect: Option<ReconnectPolicy>,
+    on_online: DeviceOnlineHook,
+    on_changed: DeviceDetailHook,
 ) -> DeviceTrackerHandle {
     let stop_flag = Arc::new(AtomicBool::new(false));
     let child_slot: Arc<Mutex<Option<Child>>> = Arc::new(Mutex::new(None));
     let stop_thread = Arc::clone(&stop_flag);
     let child_thread = Arc::clone(&child_slot);
+    let transitions = Arc::new(Mutex::new(DeviceTransitionTracker::default()));
+    let hooks = Arc::new(TrackerHooks {
+        reconnects: ReconnectSupervisor::new(
+            app.clone(),
+            adb_program.clone(),
+            reconnect,
+            trace_id.clone(),
+        ),
+        on_online,
+        on_changed,
+        history: DeviceHistoryRecorder::open(device_history_path(), &trace_id),
+    });
+
+    let debounce_join = {
+        let app = app.clone();
+        let trace_id = trace_id.clone();
+        let stop_debounce = Arc::clone(&stop_flag);
+        let transitions = Arc::clone(&transitions);
+        let hooks = Arc::clone(&hooks);
+        thread::spawn(move || {
+            while !stop_debounce.load(Ordering::Relaxed) {
+                thread::sleep(DEBOUNCE_POLL_INTERVAL);
+                let expired = match transitions.lock() {
+                    Ok(mut tracker) => tracker.expire(Instant::now()),
+                    Err(_) => {
+                        warn!(trace_id = %trace_id, "device transition tracker poisoned");
+                        return;
+                    }
+                };
+                emit_transitions(&app, &hooks, expired, &trace_id);
+            }
+        })
+    };
 
+    let hooks_thread = Arc::clone(&hooks);
+    let trace_handle = trace_id.clone();
     let join = thread::spawn(move || {
         let try_spawn = |args: &[&str]| -> Option<Child> {
-            match Command::new(&adb_program)
+            match adb_command(&adb_program)
                 .args(args)
                 .stdout(Stdio::piped())
                 .stderr(Stdio::piped())
@@ -66,6 +381,9 @@ pub fn start_device_tracker(
         let args_fallback = ["track-devices"];
 
         let mut backoff_ms = 200u64;
+        let mut online = HashSet::new();
+        // Survives respawns so a restarted `track-devices` does not refetch every detail.
+        let mut last_snapshot: Vec<DeviceSummary> = Vec::new();
         let backoff_max_ms = 5_000u64;
 
         loop {
@@ -246,35 +564,17 @@ pub fn start_device_tracker(
 
                 let maybe_snapshot = parser.push_line(&line);
                 if let Some(snapshot) = maybe_snapshot {
-                    let devices = snapshot
-                        .into_iter()
-                        .map(|summary| DeviceInfo {
-                            summary,
-                            detail: None,
-                        })
-                        .collect::<Vec<_>>();
-                    let payload = serde_json::json!({
-                        "trace_id": trace_id,
-                        "devices": devices,
-                    });
-                    let _ = app.emit(DEVICE_TRACKING_SNAPSHOT_EVENT, payload);
+                    notify_offline(&mut online, &snapshot, &trace_id);
+                    observe_snapshot(&app, &hooks_thread, &transitions, &snapshot, &trace_id);
+                    emit_snapshot(&app, &hooks_thread, &mut last_snapshot, snapshot, &trace_id);
                 }
             }
 
             // Emit the last buffered snapshot (if any) before exiting.
             if let Some(snapshot) = parser.flush() {
-                let devices = snapshot
-                    .into_iter()
-                    .map(|summary| DeviceInfo {
-                        summary,
-                        detail: None,
-                    })
-                    .collect::<Vec<_>>();
-                let payload = serde_json::json!({
-                    "trace_id": trace_id,
-                    "devices": devices,
-                });
-                let _ = app.emit(DEVICE_TRACKING_SNAPSHOT_EVENT, payload);
+                notify_offline(&mut online, &snapshot, &trace_id);
+                observe_snapshot(&app, &hooks_thread, &transitions, &snapshot, &trace_id);
+                emit_snapshot(&app, &hooks_thread, &mut last_snapshot, snapshot, &trace_id);
             }
 
             if let Ok(mut guard) = child_thread.lock() {
@@ -301,5 +601,172 @@ pub fn start_device_tracker(
         stop_flag,
         child: child_slot,
         join,
+        debounce_join,
+        hooks,
+        trace_id: trace_handle,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn device(serial: &str, state: &str) -> DeviceSummary {
+        DeviceSummary {
+            serial: serial.to_string(),
+            state: state.to_string(),
+            model: Some("Pixel".to_string()),
+            product: None,
+            device: None,
+            transport_id: None,
+            connection_type: transport_for_serial(serial).to_string(),
+            usb_port: None,
+        }
+    }
+
+    fn kinds(transitions: &[DeviceTransition]) -> Vec<(&str, &str)> {
+        transitions
+            .iter()
+            .map(|transition| (transition.serial.as_str(), transition.kind.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn classifies_transitions() {
+        assert_eq!(
+            classify_transition(None, Some("device")),
+            TRANSITION_CONNECTED
+        );
+        assert_eq!(
+            classify_transition(Some("device"), None),
+            TRANSITION_DISCONNECTED
+        );
+        assert_eq!(
+            classify_transition(Some("unauthorized"), Some("device")),
+            TRANSITION_AUTHORIZED
+        );
+        assert_eq!(
+            classify_transition(Some("offline"), Some("device")),
+            TRANSITION_ONLINE
+        );
+        assert_eq!(
+            classify_transition(Some("device"), Some("offline")),
+            TRANSITION_OFFLINE
+        );
+        assert_eq!(
+            classify_transition(Some("recovery"), Some("sideload")),
+            TRANSITION_STATE_CHANGED
+        );
+    }
+
+    #[test]
+    fn first_snapshot_only_seeds_state() {
+        let mut tracker = DeviceTransitionTracker::default();
+        let now = Instant::now();
+        assert!(tracker.observe(&[device("A1", "device")], now).is_empty());
+        let transitions = tracker.observe(
+            &[
+                device("A1", "device"),
+                device("emulator-5554", "unauthorized"),
+            ],
+            now,
+        );
+        assert_eq!(
+            kinds(&transitions),
+            vec![("emulator-5554", TRANSITION_CONNECTED)]
+        );
+        let transitions = tracker.observe(
+            &[device("A1", "device"), device("emulator-5554", "device")],
+            now,
+        );
+        assert_eq!(
+            kinds(&transitions),
+            vec![("emulator-5554", TRANSITION_AUTHORIZED)]
+        );
+        assert_eq!(
+            transitions[0].previous_state.as_deref(),
+            Some("unauthorized")
+        );
+        assert_eq!(transitions[0].state.as_deref(), Some("device"));
+    }
+
+    #[test]
+    fn debounces_flapping_usb_devices() {
+        let mut tracker = DeviceTransitionTracker::default();
+        let start = Instant::now();
+        tracker.observe(&[device("A1", "device")], start);
+
+        assert!(tracker.observe(&[], start).is_empty());
+        assert!(tracker
+            .observe(
+                &[device("A1", "offline")],
+                start + Duration::from_millis(200)
+            )
+            .is_empty());
+        assert!(tracker
+            .observe(
+                &[device("A1", "device")],
+                start + Duration::from_millis(400)
+            )
+            .is_empty());
+        assert!(tracker.expire(start + USB_FLAP_DEBOUNCE * 2).is_empty());
+
+        let later = start + Duration::from_secs(10);
+        assert!(tracker.observe(&[], later).is_empty());
+        assert!(tracker
+            .expire(later + Duration::from_millis(500))
+            .is_empty());
+        let transitions = tracker.expire(later + USB_FLAP_DEBOUNCE);
+        assert_eq!(kinds(&transitions), vec![("A1", TRANSITION_DISCONNECTED)]);
+        assert_eq!(transitions[0].previous_state.as_deref(), Some("device"));
+        assert_eq!(transitions[0].model.as_deref(), Some("Pixel"));
+    }
+
+    #[test]
+    fn refreshes_details_only_for_changed_ready_devices() {
+        let previous = vec![device("A1", "device"), device("B2", "unauthorized")];
+        let mut moved = device("A1", "device");
+        moved.transport_id = Some("7".to_string());
+        let serials = |targets: Vec<DeviceSummary>| {
+            targets
+                .into_iter()
+                .map(|summary| summary.serial)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(serials(detail_refresh_targets(&[], &previous)), vec!["A1"]);
+        assert!(detail_refresh_targets(&previous, &previous).is_empty());
+        assert_eq!(
+            serials(detail_refresh_targets(
+                &previous,
+                &[
+                    device("A1", "device"),
+                    device("B2", "device"),
+                    device("C3", "offline")
+                ]
+            )),
+            vec!["B2"]
+        );
+        assert_eq!(
+            serials(detail_refresh_targets(&previous, &[moved])),
+            vec!["A1"]
+        );
+    }
+
+    #[test]
+    fn network_devices_report_losses_immediately() {
+        let mut tracker = DeviceTransitionTracker::default();
+        let now = Instant::now();
+        tracker.observe(&[device("192.168.1.5:5555", "device")], now);
+        let transitions = tracker.observe(&[device("192.168.1.5:5555", "offline")], now);
+        assert_eq!(
+            kinds(&transitions),
+            vec![("192.168.1.5:5555", TRANSITION_OFFLINE)]
+        );
+        let transitions = tracker.observe(&[device("192.168.1.5:5555", "device")], now);
+        assert_eq!(
+            kinds(&transitions),
+            vec![("192.168.1.5:5555", TRANSITION_ONLINE)]
+        );
     }
 }
@@ -0,0 +1,263 @@
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpStream};
+use std::time::Duration;
+
+use serde_json::Value;
+
+use crate::app::models::DevtoolsTarget;
+
+pub const CHROME_DEVTOOLS_SOCKET: &str = "chrome_devtools_remote";
+const WEBVIEW_SOCKET_PREFIX: &str = "webview_devtools_remote_";
+const DEVTOOLS_SOCKET_SUFFIX: &str = "_devtools_remote";
+const HTTP_RESPONSE_LIMIT: u64 = 4 * 1024 * 1024;
+
+/// Abstract DevTools sockets from `/proc/net/unix` (last column, `@`-prefixed), in order.
+pub fn parse_devtools_sockets(proc_net_unix: &str) -> Vec<String> {
+    let mut sockets: Vec<String> = Vec::new();
+    for line in proc_net_unix.lines() {
+        let Some(name) = line
+            .split_whitespace()
+            .last()
+            .and_then(|path| path.strip_prefix('@'))
+        else {
+            continue;
+        };
+        if name.contains("devtools_remote") && !sockets.iter().any(|known| known == name) {
+            sockets.push(name.to_string());
+        }
+    }
+    sockets
+}
+
+/// WebView sockets carry the owning process id: `webview_devtools_remote_<pid>`.
+pub fn socket_pid(socket: &str) -> Option<u32> {
+    socket.strip_prefix(WEBVIEW_SOCKET_PREFIX)?.parse().ok()
+}
+
+/// Package implied by the socket name alone; WebView sockets need a pid lookup instead.
+pub fn socket_package(socket: &str) -> Option<String> {
+    if socket == CHROME_DEVTOOLS_SOCKET {
+        return Some("com.android.chrome".to_string());
+    }
+    if socket.starts_with(WEBVIEW_SOCKET_PREFIX) {
+        return None;
+    }
+    socket
+        .strip_suffix(DEVTOOLS_SOCKET_SUFFIX)
+        .filter(|package| !package.is_empty())
+        .map(str::to_string)
+}
+
+/// `/proc/<pid>/cmdline` is NUL separated; the first entry is the process (package) name.
+pub fn parse_cmdline_process(cmdline: &str) -> Option<String> {
+    cmdline
+        .split('\0')
+        .next()
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(|name| name.split(':').next().unwrap_or(name).to_string())
+}
+
+pub fn build_devtools_forward_args(serial: &str, socket: &str) -> Vec<String> {
+    vec![
+        "-s".to_string(),
+        serial.to_string(),
+        "forward".to_string(),
+        "tcp:0".to_string(),
+        format!("localabstract:{socket}"),
+    ]
+}
+
+pub fn build_devtools_forward_remove_args(serial: &str, local_port: u16) -> Vec<String> {
+    vec![
+        "-s".to_string(),
+        serial.to_string(),
+        "forward".to_string(),
+        "--remove".to_string(),
+        format!("tcp:{local_port}"),
+    ]
+}
+
+/// `adb forward tcp:0 ...` prints the allocated port.
+pub fn parse_forward_port(stdout: &str) -> Option<u16> {
+    stdout.trim().parse().ok()
+}
+
+/// Existing `(local_port, socket)` DevTools forwards for `serial` from `adb forward --list`.
+pub fn devtools_forwards(list_output: &str, serial: &str) -> Vec<(u16, String)> {
+    list_output
+        .lines()
+        .filter_map(|line| {
+            let parts: Vec<&str> = line.split_whitespace().collect();
+            let [owner, local, remote] = parts.as_slice() else {
+                return None;
+            };
+            if *owner != serial {
+                return None;
+            }
+            let port = local.strip_prefix("tcp:")?.parse().ok()?;
+            let socket = remote.strip_prefix("localabstract:")?;
+            socket
+                .contains("devtools_remote")
+                .then(|| (port, socket.to_string()))
+        })
+        .collect()
+}
+
+/// Minimal HTTP GET against the forwarded loopback port; the DevTools endpoint speaks
+/// plain HTTP and closes the connection after the response.
+pub fn http_get_local(port: u16, path: &str, timeout: Duration) -> Result<String, String> {
+    let addr = SocketAddr::from(([127, 0, 0, 1], port));
+    let mut stream = TcpStream::connect_timeout(&addr, timeout)
+        .map_err(|err| format!("Failed to connect to 127.0.0.1:{port}: {err}"))?;
+    stream
+        .set_read_timeout(Some(timeout))
+        .and_then(|_| stream.set_write_timeout(Some(timeout)))
+        .map_err(|err| format!("Failed to configure socket: {err}"))?;
+    let request =
+        format!("GET {path} HTTP/1.0\r\nHost: 127.0.0.1:{port}\r\nConnection: close\r\n\r\n");
+    stream
+        .write_all(request.as_bytes())
+        .map_err(|err| format!("Failed to send request: {err}"))?;
+    let mut response = Vec::new();
+    stream
+        .take(HTTP_RESPONSE_LIMIT)
+        .read_to_end(&mut response)
+        .map_err(|err| format!("Failed to read response: {err}"))?;
+    parse_http_body(&String::from_utf8_lossy(&response))
+}
+
+pub fn parse_http_body(response: &str) -> Result<String, String> {
+    let (head, body) = response
+        .split_once("\r\n\r\n")
+        .ok_or_else(|| "Malformed HTTP response".to_string())?;
+    let status = head
+        .lines()
+        .next()
+        .and_then(|line| line.split_whitespace().nth(1))
+        .unwrap_or_default();
+    if status != "200" {
+        return Err(format!("DevTools endpoint returned HTTP {status}"));
+    }
+    Ok(body.to_string())
+}
+
+/// Targets from the `/json/list` endpoint, with a `devtools://` inspector URL pointing at
+/// the local forward.
+pub fn parse_devtools_targets(
+    json: &str,
+    socket: &str,
+    local_port: u16,
+) -> Result<Vec<DevtoolsTarget>, String> {
+    let value: Value =
+        serde_json::from_str(json).map_err(|err| format!("Invalid DevTools target list: {err}"))?;
+    let entries = value
+        .as_array()
+        .ok_or_else(|| "DevTools target list is not an array".to_string())?;
+    let text = |entry: &Value, key: &str| {
+        entry
+            .get(key)
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string()
+    };
+    Ok(entries
+        .iter()
+        .map(|entry| {
+            let id = text(entry, "id");
+            let websocket_url = Some(text(entry, "webSocketDebuggerUrl"))
+                .filter(|url| !url.is_empty());
+            // Absent while another debugger is attached to the page.
+            let devtools_url = websocket_url.as_ref().map(|_| {
+                format!(
+                    "devtools://devtools/bundled/inspector.html?ws=127.0.0.1:{local_port}/devtools/page/{id}"
+                )
+            });
+            DevtoolsTarget {
+                socket: socket.to_string(),
+                local_port,
+                id,
+                target_type: text(entry, "type"),
+                title: text(entry, "title"),
+                url: text(entry, "url"),
+                websocket_url,
+                devtools_url,
+            }
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_devtools_sockets() {
+        let output = "Num       RefCount Protocol Flags    Type St Inode Path\n\
+0000000000000000: 00000002 00000000 00010000 0001 01 12345 @chrome_devtools_remote\n\
+0000000000000000: 00000002 00000000 00010000 0001 01 12346 @webview_devtools_remote_4321\n\
+0000000000000000: 00000003 00000000 00000000 0001 03 12347 @webview_devtools_remote_4321\n\
+0000000000000000: 00000002 00000000 00010000 0001 01 12348 @jdwp-control\n\
+0000000000000000: 00000002 00000000 00010000 0001 01 12349 /dev/socket/zygote\n";
+        assert_eq!(
+            parse_devtools_sockets(output),
+            vec!["chrome_devtools_remote", "webview_devtools_remote_4321"]
+        );
+    }
+
+    #[test]
+    fn resolves_socket_owners() {
+        assert_eq!(socket_pid("webview_devtools_remote_4321"), Some(4321));
+        assert_eq!(socket_pid("chrome_devtools_remote"), None);
+        assert_eq!(
+            socket_package("chrome_devtools_remote").as_deref(),
+            Some("com.android.chrome")
+        );
+        assert_eq!(
+            socket_package("org.chromium.webview_shell_devtools_remote").as_deref(),
+            Some("org.chromium.webview_shell")
+        );
+        assert_eq!(socket_package("webview_devtools_remote_4321"), None);
+        assert_eq!(
+            parse_cmdline_process("com.example.app:web\0--flag\0").as_deref(),
+            Some("com.example.app")
+        );
+        assert_eq!(parse_cmdline_process(""), None);
+    }
+
+    #[test]
+    fn parses_forwards() {
+        assert_eq!(parse_forward_port("41233\n"), Some(41233));
+        assert_eq!(parse_forward_port("error: closed"), None);
+        let list = "abc tcp:41233 localabstract:chrome_devtools_remote\n\
+abc tcp:27042 tcp:27042\n\
+xyz tcp:41234 localabstract:webview_devtools_remote_1\n";
+        assert_eq!(
+            devtools_forwards(list, "abc"),
+            vec![(41233, "chrome_devtools_remote".to_string())]
+        );
+    }
+
+    #[test]
+    fn parses_http_and_targets() {
+        let body =
+            parse_http_body("HTTP/1.1 200 OK\r\nContent-Type: application/json\r\n\r\n[]").unwrap();
+        assert_eq!(body, "[]");
+        assert!(parse_http_body("HTTP/1.1 404 Not Found\r\n\r\n").is_err());
+
+        let json = r#"[
+            {"id": "A1", "type": "page", "title": "Home", "url": "https://example.com/",
+             "webSocketDebuggerUrl": "ws://127.0.0.1:41233/devtools/page/A1"},
+            {"id": "B2", "type": "page", "title": "Busy", "url": "about:blank"}
+        ]"#;
+        let targets = parse_devtools_targets(json, "chrome_devtools_remote", 41233).unwrap();
+        assert_eq!(targets.len(), 2);
+        assert_eq!(
+            targets[0].devtools_url.as_deref(),
+            Some("devtools://devtools/bundled/inspector.html?ws=127.0.0.1:41233/devtools/page/A1")
+        );
+        assert_eq!(targets[1].websocket_url, None);
+        assert_eq!(targets[1].devtools_url, None);
+        assert!(parse_devtools_targets("{}", "s", 1).is_err());
+    }
+}
@@ -0,0 +1,119 @@
+use crate::app::models::EmulatorSnapshot;
+
+pub fn validate_snapshot_name(value: &str) -> Result<String, String> {
+    let value = value.trim();
+    if value.is_empty() || value.len() > 64 {
+        return Err("Snapshot name must be 1-64 characters".to_string());
+    }
+    if !value
+        .chars()
+        .all(|ch| ch.is_ascii_alphanumeric() || ch == '_' || ch == '-' || ch == '.')
+    {
+        return Err("Snapshot name may only contain letters, digits, '.', '_' and '-'".to_string());
+    }
+    Ok(value.to_string())
+}
+
+/// The emulator console answers `OK` on success and `KO: <reason>` on failure, while
+/// `adb emu` itself still exits with 0.
+pub fn emulator_console_error(output: &str) -> Option<String> {
+    output.lines().map(str::trim).find_map(|line| {
+        line.strip_prefix("KO")
+            .map(|rest| rest.trim_start_matches(':').trim().to_string())
+    })
+}
+
+fn is_date(token: &str) -> bool {
+    let parts: Vec<&str> = token.split('-').collect();
+    parts.len() == 3
+        && parts[0].len() == 4
+        && parts
+            .iter()
+            .all(|part| !part.is_empty() && part.chars().all(|ch| ch.is_ascii_digit()))
+}
+
+/// Parse `adb emu avd snapshot list`:
+///
+/// ```text
+/// List of snapshots present on all disks:
+/// ID        TAG                 VM SIZE                DATE       VM CLOCK
+/// --        default_boot          180M 2024-05-10 10:12:33   00:02:31.123
+/// OK
+/// ```
+pub fn parse_snapshot_list(output: &str) -> Vec<EmulatorSnapshot> {
+    let mut snapshots = Vec::new();
+    let mut in_table = false;
+    for line in output.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("ID") && trimmed.contains("TAG") {
+            in_table = true;
+            continue;
+        }
+        if !in_table || trimmed.is_empty() || trimmed == "OK" {
+            continue;
+        }
+        let tokens: Vec<&str> = trimmed.split_whitespace().collect();
+        let date_index = match tokens.iter().position(|token| is_date(token)) {
+            Some(index) if index >= 2 => index,
+            _ => continue,
+        };
+        snapshots.push(EmulatorSnapshot {
+            name: tokens[1].to_string(),
+            size: tokens[2..date_index].join(" "),
+            created_at: tokens[date_index..]
+                .iter()
+                .take(2)
+                .copied()
+                .collect::<Vec<_>>()
+                .join(" "),
+            vm_clock: tokens.get(date_index + 2).map(|value| value.to_string()),
+        });
+    }
+    snapshots
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate_snapshot_name_rejects_spaces_and_shell_chars() {
+        assert_eq!(
+            validate_snapshot_name(" clean_state ").unwrap(),
+            "clean_state"
+        );
+        assert!(validate_snapshot_name("clean state").is_err());
+        assert!(validate_snapshot_name("a;b").is_err());
+        assert!(validate_snapshot_name("").is_err());
+    }
+
+    #[test]
+    fn emulator_console_error_reads_ko_lines() {
+        assert_eq!(
+            emulator_console_error("KO: snapshot not found\n"),
+            Some("snapshot not found".to_string())
+        );
+        assert_eq!(emulator_console_error("OK\n"), None);
+    }
+
+    #[test]
+    fn parse_snapshot_list_reads_rows() {
+        let output = "List of snapshots present on all disks:\n\
+ID        TAG                 VM SIZE                DATE       VM CLOCK\n\
+--        default_boot          180M 2024-05-10 10:12:33   00:02:31.123\n\
+--        logged_in            1.2 G 2024-05-11 08:00:01   00:10:00.000\n\
+OK\n";
+        let snapshots = parse_snapshot_list(output);
+        assert_eq!(snapshots.len(), 2);
+        assert_eq!(snapshots[0].name, "default_boot");
+        assert_eq!(snapshots[0].size, "180M");
+        assert_eq!(snapshots[0].created_at, "2024-05-10 10:12:33");
+        assert_eq!(snapshots[0].vm_clock.as_deref(), Some("00:02:31.123"));
+        assert_eq!(snapshots[1].size, "1.2 G");
+    }
+
+    #[test]
+    fn parse_snapshot_list_handles_empty_output() {
+        assert!(parse_snapshot_list("There is no snapshot available.\nOK\n").is_empty());
+    }
+}
@@ -0,0 +1,108 @@
+/// frida-server has to live somewhere executable; `/data/local/tmp` is the only location the
+/// shell user can write to and execute from on stock builds.
+pub const DEVICE_AGENT_DIR: &str = "/data/local/tmp/";
+
+pub fn validate_agent_remote_path(value: &str) -> Result<String, String> {
+    let value = value.trim();
+    let name = value.strip_prefix(DEVICE_AGENT_DIR).unwrap_or_default();
+    let valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '-' | '_' | '.'))
+        && !name.starts_with('.');
+    if valid {
+        Ok(value.to_string())
+    } else {
+        Err(format!(
+            "Agent path must be a file directly under {DEVICE_AGENT_DIR}: {value}"
+        ))
+    }
+}
+
+pub fn agent_process_name(remote_path: &str) -> &str {
+    remote_path.rsplit('/').next().unwrap_or(remote_path)
+}
+
+/// Command line run through `su -c`; the agent daemonizes and listens on the device loopback.
+pub fn build_agent_start_command(remote_path: &str, device_port: u16) -> String {
+    format!("{remote_path} -D -l 127.0.0.1:{device_port}")
+}
+
+pub fn build_forward_args(serial: &str, local_port: u16, device_port: u16) -> Vec<String> {
+    vec![
+        "-s".to_string(),
+        serial.to_string(),
+        "forward".to_string(),
+        format!("tcp:{local_port}"),
+        format!("tcp:{device_port}"),
+    ]
+}
+
+pub fn build_forward_remove_args(serial: &str, local_port: u16) -> Vec<String> {
+    vec![
+        "-s".to_string(),
+        serial.to_string(),
+        "forward".to_string(),
+        "--remove".to_string(),
+        format!("tcp:{local_port}"),
+    ]
+}
+
+pub fn parse_pidof(output: &str) -> Vec<u32> {
+    output
+        .split_whitespace()
+        .filter_map(|value| value.parse::<u32>().ok())
+        .collect()
+}
+
+/// Check `adb forward --list` (`<serial> tcp:<local> tcp:<remote>` per line) for a forward.
+pub fn has_forward(output: &str, serial: &str, local_port: u16, device_port: u16) -> bool {
+    let local = format!("tcp:{local_port}");
+    let remote = format!("tcp:{device_port}");
+    output.lines().any(|line| {
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        parts.len() == 3 && parts[0] == serial && parts[1] == local && parts[2] == remote
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate_agent_remote_path_requires_local_tmp_file() {
+        assert_eq!(
+            validate_agent_remote_path(" /data/local/tmp/frida-server-16.1.4 ").unwrap(),
+            "/data/local/tmp/frida-server-16.1.4"
+        );
+        assert!(validate_agent_remote_path("/system/bin/frida").is_err());
+        assert!(validate_agent_remote_path("/data/local/tmp/").is_err());
+        assert!(validate_agent_remote_path("/data/local/tmp/a;reboot").is_err());
+        assert!(validate_agent_remote_path("/data/local/tmp/sub/frida").is_err());
+    }
+
+    #[test]
+    fn builds_agent_commands() {
+        assert_eq!(
+            build_agent_start_command("/data/local/tmp/frida-server", 27042),
+            "/data/local/tmp/frida-server -D -l 127.0.0.1:27042"
+        );
+        assert_eq!(
+            agent_process_name("/data/local/tmp/frida-server"),
+            "frida-server"
+        );
+        assert_eq!(
+            &build_forward_args("abc", 27042, 27042)[2..],
+            ["forward", "tcp:27042", "tcp:27042"]
+        );
+    }
+
+    #[test]
+    fn parses_pidof_and_forward_list() {
+        assert_eq!(parse_pidof("1234 5678\n"), vec![1234, 5678]);
+        assert!(parse_pidof("").is_empty());
+        let list = "abc tcp:27042 tcp:27042\nxyz tcp:5000 tcp:6000\n";
+        assert!(has_forward(list, "abc", 27042, 27042));
+        assert!(!has_forward(list, "xyz", 27042, 27042));
+    }
+}
@@ -0,0 +1,439 @@
+use crate::app::adb::input::{scale_coordinate, ScaledGesture};
+use crate::app::models::{GesturePoint, GestureRecording, GestureStroke};
+
+pub const GESTURE_FILE_VERSION: u32 = 1;
+pub const MAX_REPLAY_STROKES: usize = 500;
+/// Pauses longer than this between strokes are shortened on replay.
+pub const MAX_REPLAY_GAP_MS: u64 = 10_000;
+
+const TAP_SLOP_PX: u32 = 24;
+const TAP_MAX_MS: u32 = 500;
+const MAX_STROKE_MS: u32 = 10_000;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TouchDevice {
+    pub path: String,
+    pub min_x: i64,
+    pub max_x: i64,
+    pub min_y: i64,
+    pub max_y: i64,
+}
+
+/// Find the first multi-touch device in `getevent -lp` output along with its axis ranges.
+pub fn parse_touch_device(output: &str) -> Option<TouchDevice> {
+    let mut path: Option<String> = None;
+    let mut x_range: Option<(i64, i64)> = None;
+    let mut y_range: Option<(i64, i64)> = None;
+
+    let finish =
+        |path: &Option<String>, x: Option<(i64, i64)>, y: Option<(i64, i64)>| match (path, x, y) {
+            (Some(path), Some((min_x, max_x)), Some((min_y, max_y)))
+                if max_x > min_x && max_y > min_y =>
+            {
+                Some(TouchDevice {
+                    path: path.clone(),
+                    min_x,
+                    max_x,
+                    min_y,
+                    max_y,
+                })
+            }
+            _ => None,
+        };
+
+    for line in output.lines() {
+        let trimmed = line.trim();
+        if let Some(rest) = trimmed.strip_prefix("add device") {
+            if let Some(device) = finish(&path, x_range, y_range) {
+                return Some(device);
+            }
+            path = rest
+                .split_once(':')
+                .map(|(_, value)| value.trim().to_string())
+                .filter(|value| !value.is_empty());
+            x_range = None;
+            y_range = None;
+            continue;
+        }
+        if trimmed.contains("ABS_MT_POSITION_X") {
+            x_range = parse_axis_range(trimmed);
+        } else if trimmed.contains("ABS_MT_POSITION_Y") {
+            y_range = parse_axis_range(trimmed);
+        }
+    }
+    finish(&path, x_range, y_range)
+}
+
+fn parse_axis_range(line: &str) -> Option<(i64, i64)> {
+    let field = |name: &str| {
+        line.split(',').find_map(|part| {
+            part.trim()
+                .rsplit_once(name)
+                .and_then(|(_, value)| value.trim().parse::<i64>().ok())
+        })
+    };
+    Some((field("min ")?, field("max ")?))
+}
+
+fn parse_event_value(value: &str) -> Option<i64> {
+    match value {
+        "DOWN" => Some(1),
+        "UP" => Some(0),
+        _ => u32::from_str_radix(value, 16)
+            .ok()
+            .map(|raw| raw as i32 as i64),
+    }
+}
+
+fn map_axis(raw: i64, min: i64, max: i64, size: u32) -> u32 {
+    let span = (max - min + 1).max(1);
+    let offset = (raw - min).clamp(0, span - 1);
+    ((offset * i64::from(size)) / span).min(i64::from(size.saturating_sub(1))) as u32
+}
+
+/// Convert `getevent -lt` output for one touch device into screen-space strokes.
+/// Only the first contact (slot 0) is tracked; additional fingers are ignored.
+pub fn parse_getevent_strokes(
+    output: &str,
+    device: &TouchDevice,
+    screen_width: u32,
+    screen_height: u32,
+) -> Vec<GestureStroke> {
+    let mut parser = GeteventStrokeParser::new(device.clone(), screen_width, screen_height);
+    let mut strokes: Vec<GestureStroke> = output
+        .lines()
+        .filter_map(|line| parser.push_line(line))
+        .collect();
+    strokes.extend(parser.finish());
+    strokes
+}
+
+/// Line-by-line form of `parse_getevent_strokes`, so a live `getevent` stream can act on
+/// each stroke as soon as the finger lifts.
+pub struct GeteventStrokeParser {
+    device: TouchDevice,
+    screen_width: u32,
+    screen_height: u32,
+    origin_us: Option<i64>,
+    slot: i64,
+    touching: bool,
+    x: Option<i64>,
+    y: Option<i64>,
+    current: Option<(i64, Vec<GesturePoint>)>,
+}
+
+impl GeteventStrokeParser {
+    pub fn new(device: TouchDevice, screen_width: u32, screen_height: u32) -> Self {
+        Self {
+            device,
+            screen_width,
+            screen_height,
+            origin_us: None,
+            slot: 0,
+            touching: false,
+            x: None,
+            y: None,
+            current: None,
+        }
+    }
+
+    /// Feed one `getevent -lt` line; returns the stroke it completes, if any.
+    pub fn push_line(&mut self, line: &str) -> Option<GestureStroke> {
+        let (stamp, rest) = line
+            .trim()
+            .strip_prefix('[')
+            .and_then(|v| v.split_once(']'))?;
+        let time_us = parse_timestamp_us(stamp)?;
+        let mut parts = rest.split_whitespace().filter(|part| !part.ends_with(':'));
+        let (Some(kind), Some(code), Some(value)) = (parts.next(), parts.next(), parts.next())
+        else {
+            return None;
+        };
+        let value = parse_event_value(value)?;
+
+        match (kind, code) {
+            ("EV_ABS", "ABS_MT_SLOT") => self.slot = value,
+            ("EV_ABS", "ABS_MT_TRACKING_ID") if self.slot == 0 => self.touching = value >= 0,
+            ("EV_ABS", "ABS_MT_POSITION_X") if self.slot == 0 => self.x = Some(value),
+            ("EV_ABS", "ABS_MT_POSITION_Y") if self.slot == 0 => self.y = Some(value),
+            ("EV_KEY", "BTN_TOUCH") => self.touching = value == 1,
+            ("EV_SYN", "SYN_REPORT") => {
+                if self.touching {
+                    if let (Some(raw_x), Some(raw_y)) = (self.x, self.y) {
+                        self.origin_us.get_or_insert(time_us);
+                        let (start_us, points) =
+                            self.current.get_or_insert_with(|| (time_us, Vec::new()));
+                        points.push(GesturePoint {
+                            t_ms: ((time_us - *start_us) / 1000).max(0) as u32,
+                            x: map_axis(
+                                raw_x,
+                                self.device.min_x,
+                                self.device.max_x,
+                                self.screen_width,
+                            ),
+                            y: map_axis(
+                                raw_y,
+                                self.device.min_y,
+                                self.device.max_y,
+                                self.screen_height,
+                            ),
+                        });
+                    }
+                } else {
+                    return self.finish_current();
+                }
+            }
+            _ => {}
+        }
+        None
+    }
+
+    /// The stroke still in progress when the stream ended, if any.
+    pub fn finish(mut self) -> Option<GestureStroke> {
+        self.finish_current()
+    }
+
+    fn finish_current(&mut self) -> Option<GestureStroke> {
+        let (start_us, points) = self.current.take()?;
+        let origin = self.origin_us.unwrap_or(start_us);
+        Some(GestureStroke {
+            start_ms: ((start_us - origin) / 1000).max(0) as u64,
+            points,
+        })
+    }
+}
+
+fn parse_timestamp_us(stamp: &str) -> Option<i64> {
+    let (secs, frac) = stamp.trim().split_once('.')?;
+    let secs: i64 = secs.trim().parse().ok()?;
+    let frac: String = frac.chars().chain(std::iter::repeat('0')).take(6).collect();
+    Some(secs * 1_000_000 + frac.parse::<i64>().ok()?)
+}
+
+pub fn validate_gesture_recording(recording: &GestureRecording) -> Result<(), String> {
+    if recording.version != GESTURE_FILE_VERSION {
+        return Err(format!(
+            "Unsupported gesture file version: {}",
+            recording.version
+        ));
+    }
+    if recording.source_width == 0 || recording.source_height == 0 {
+        return Err("source_width and source_height must be positive".to_string());
+    }
+    if recording.strokes.is_empty() {
+        return Err("Gesture file has no strokes".to_string());
+    }
+    if recording.strokes.len() > MAX_REPLAY_STROKES {
+        return Err(format!(
+            "Gesture file has more than {MAX_REPLAY_STROKES} strokes"
+        ));
+    }
+    let in_bounds = recording.strokes.iter().all(|stroke| {
+        !stroke.points.is_empty()
+            && stroke
+                .points
+                .iter()
+                .all(|p| p.x < recording.source_width && p.y < recording.source_height)
+    });
+    if !in_bounds {
+        return Err("Gesture file contains empty strokes or points outside the screen".to_string());
+    }
+    Ok(())
+}
+
+/// Approximate a recorded stroke with a single `input tap`/`swipe`: intermediate points are
+/// dropped, so curved paths replay as a straight line with the same start, end and duration.
+pub fn stroke_to_gesture(
+    stroke: &GestureStroke,
+    source_width: u32,
+    source_height: u32,
+    device_width: u32,
+    device_height: u32,
+) -> Option<ScaledGesture> {
+    let first = stroke.points.first()?;
+    let last = stroke.points.last()?;
+    let duration = last.t_ms.saturating_sub(first.t_ms);
+    let moved = first.x.abs_diff(last.x).max(first.y.abs_diff(last.y));
+    let x = scale_coordinate(first.x, source_width, device_width);
+    let y = scale_coordinate(first.y, source_height, device_height);
+
+    if moved <= TAP_SLOP_PX {
+        if duration < TAP_MAX_MS {
+            return Some(ScaledGesture {
+                kind: "tap".to_string(),
+                x,
+                y,
+                end_x: None,
+                end_y: None,
+                duration_ms: None,
+            });
+        }
+        return Some(ScaledGesture {
+            kind: "long_press".to_string(),
+            x,
+            y,
+            end_x: None,
+            end_y: None,
+            duration_ms: Some(duration.min(MAX_STROKE_MS)),
+        });
+    }
+    Some(ScaledGesture {
+        kind: "swipe".to_string(),
+        x,
+        y,
+        end_x: Some(scale_coordinate(last.x, source_width, device_width)),
+        end_y: Some(scale_coordinate(last.y, source_height, device_height)),
+        duration_ms: Some(duration.clamp(1, MAX_STROKE_MS)),
+    })
+}
+
+/// Idle time between the end of `previous` and the start of `next`, capped for replay.
+pub fn replay_gap_ms(previous: &GestureStroke, next: &GestureStroke) -> u64 {
+    let previous_end =
+        previous.start_ms + u64::from(previous.points.last().map(|p| p.t_ms).unwrap_or_default());
+    next.start_ms
+        .saturating_sub(previous_end)
+        .min(MAX_REPLAY_GAP_MS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GETEVENT_LP: &str = r#"add device 1: /dev/input/event4
+  name:     "gpio-keys"
+  events:
+    KEY (0001): KEY_VOLUMEDOWN        KEY_VOLUMEUP
+add device 2: /dev/input/event2
+  name:     "sec_touchscreen"
+  events:
+    ABS (0003): ABS_MT_SLOT           : value 0, min 0, max 9, fuzz 0, flat 0, resolution 0
+                ABS_MT_POSITION_X     : value 0, min 0, max 4095, fuzz 0, flat 0, resolution 0
+                ABS_MT_POSITION_Y     : value 0, min 0, max 8191, fuzz 0, flat 0, resolution 0
+                ABS_MT_TRACKING_ID    : value 0, min 0, max 65535, fuzz 0, flat 0, resolution 0
+"#;
+
+    #[test]
+    fn parses_touch_device_ranges() {
+        let device = parse_touch_device(GETEVENT_LP).expect("device");
+        assert_eq!(device.path, "/dev/input/event2");
+        assert_eq!((device.min_x, device.max_x), (0, 4095));
+        assert_eq!((device.min_y, device.max_y), (0, 8191));
+        assert!(parse_touch_device("add device 1: /dev/input/event0\n").is_none());
+    }
+
+    #[test]
+    fn parses_tap_and_swipe_strokes() {
+        let device = parse_touch_device(GETEVENT_LP).unwrap();
+        let output = "\
+[   100.000000] EV_ABS       ABS_MT_TRACKING_ID   00000001
+[   100.000000] EV_ABS       ABS_MT_POSITION_X    00000800
+[   100.000000] EV_ABS       ABS_MT_POSITION_Y    00001000
+[   100.000000] EV_KEY       BTN_TOUCH            DOWN
+[   100.000000] EV_SYN       SYN_REPORT           00000000
+[   100.050000] EV_ABS       ABS_MT_TRACKING_ID   ffffffff
+[   100.050000] EV_KEY       BTN_TOUCH            UP
+[   100.050000] EV_SYN       SYN_REPORT           00000000
+[   101.000000] /dev/input/event2: EV_ABS       ABS_MT_TRACKING_ID   00000002
+[   101.000000] /dev/input/event2: EV_ABS       ABS_MT_POSITION_Y    00001800
+[   101.000000] /dev/input/event2: EV_SYN       SYN_REPORT           00000000
+[   101.100000] /dev/input/event2: EV_ABS       ABS_MT_SLOT          00000001
+[   101.100000] /dev/input/event2: EV_ABS       ABS_MT_POSITION_Y    00000010
+[   101.100000] /dev/input/event2: EV_ABS       ABS_MT_SLOT          00000000
+[   101.100000] /dev/input/event2: EV_ABS       ABS_MT_POSITION_Y    00000400
+[   101.100000] /dev/input/event2: EV_SYN       SYN_REPORT           00000000
+[   101.200000] /dev/input/event2: EV_ABS       ABS_MT_TRACKING_ID   ffffffff
+[   101.200000] /dev/input/event2: EV_SYN       SYN_REPORT           00000000
+";
+        let strokes = parse_getevent_strokes(output, &device, 1080, 2400);
+        assert_eq!(strokes.len(), 2);
+        assert_eq!(strokes[0].start_ms, 0);
+        assert_eq!(
+            strokes[0].points,
+            vec![GesturePoint {
+                t_ms: 0,
+                x: 540,
+                y: 1200
+            }]
+        );
+        assert_eq!(strokes[1].start_ms, 1000);
+        assert_eq!(strokes[1].points.len(), 2);
+        assert_eq!(strokes[1].points[0].y, 1800);
+        assert_eq!(strokes[1].points[1].t_ms, 100);
+        assert_eq!(strokes[1].points[1].y, 300);
+    }
+
+    #[test]
+    fn streams_strokes_as_fingers_lift() {
+        let device = parse_touch_device(GETEVENT_LP).unwrap();
+        let mut parser = GeteventStrokeParser::new(device, 1080, 2400);
+        let lines = [
+            "[   5.000000] EV_ABS       ABS_MT_TRACKING_ID   00000001",
+            "[   5.000000] EV_ABS       ABS_MT_POSITION_X    00000400",
+            "[   5.000000] EV_ABS       ABS_MT_POSITION_Y    00000800",
+            "[   5.000000] EV_SYN       SYN_REPORT           00000000",
+            "[   5.040000] EV_ABS       ABS_MT_TRACKING_ID   ffffffff",
+        ];
+        assert!(lines.iter().all(|line| parser.push_line(line).is_none()));
+        let stroke = parser
+            .push_line("[   5.040000] EV_SYN       SYN_REPORT           00000000")
+            .expect("stroke ends on lift");
+        assert_eq!(stroke.points.len(), 1);
+        assert_eq!((stroke.points[0].x, stroke.points[0].y), (270, 600));
+        assert!(parser.finish().is_none());
+    }
+
+    #[test]
+    fn converts_strokes_to_scaled_gestures() {
+        let point = |t_ms, x, y| GesturePoint { t_ms, x, y };
+        let tap = GestureStroke {
+            start_ms: 0,
+            points: vec![point(0, 540, 1200), point(80, 545, 1204)],
+        };
+        let gesture = stroke_to_gesture(&tap, 1080, 2400, 720, 1600).unwrap();
+        assert_eq!(
+            (gesture.kind.as_str(), gesture.x, gesture.y),
+            ("tap", 360, 800)
+        );
+
+        let hold = GestureStroke {
+            start_ms: 0,
+            points: vec![point(0, 100, 100), point(900, 100, 100)],
+        };
+        let gesture = stroke_to_gesture(&hold, 1080, 2400, 1080, 2400).unwrap();
+        assert_eq!(gesture.kind, "long_press");
+        assert_eq!(gesture.duration_ms, Some(900));
+
+        let swipe = GestureStroke {
+            start_ms: 2000,
+            points: vec![point(0, 540, 1800), point(250, 540, 600)],
+        };
+        let gesture = stroke_to_gesture(&swipe, 1080, 2400, 720, 1600).unwrap();
+        assert_eq!(gesture.kind, "swipe");
+        assert_eq!((gesture.end_x, gesture.end_y), (Some(360), Some(400)));
+        assert_eq!(gesture.duration_ms, Some(250));
+        assert_eq!(replay_gap_ms(&tap, &swipe), 1920);
+    }
+
+    #[test]
+    fn validates_gesture_files() {
+        let mut recording = GestureRecording {
+            version: GESTURE_FILE_VERSION,
+            source_width: 1080,
+            source_height: 2400,
+            strokes: vec![GestureStroke {
+                start_ms: 0,
+                points: vec![GesturePoint {
+                    t_ms: 0,
+                    x: 10,
+                    y: 10,
+                }],
+            }],
+        };
+        assert!(validate_gesture_recording(&recording).is_ok());
+        recording.strokes[0].points[0].x = 1080;
+        assert!(validate_gesture_recording(&recording).is_err());
+        recording.version = 2;
+        assert!(validate_gesture_recording(&recording).is_err());
+    }
+}
@@ -0,0 +1,214 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::app::config::config_path;
+use crate::app::error::AppError;
+use crate::app::i18n::Message;
+use crate::app::on_connect::is_valid_locale;
+
+/// Accented English: longer, accented strings that expose truncation and hardcoded text.
+pub const PSEUDO_LOCALE_ACCENTED: &str = "en-XA";
+/// Bidi English: mirrored, RTL-wrapped strings that expose layout direction bugs.
+pub const PSEUDO_LOCALE_BIDI: &str = "ar-XB";
+pub const PSEUDO_LOCALES: [&str; 2] = [PSEUDO_LOCALE_ACCENTED, PSEUDO_LOCALE_BIDI];
+
+pub const LOCALE_PROPERTY: &str = "persist.sys.locale";
+pub const PRODUCT_LOCALE_PROPERTY: &str = "ro.product.locale";
+/// Developer options "Force RTL layout direction" writes both the global setting and the
+/// system property of the same name.
+pub const FORCE_RTL_KEY: &str = "debug.force_rtl";
+/// Restarting zygote reloads the system locale without a full reboot; needs root.
+pub const RESTART_UI_COMMAND: &str = "setprop ctl.restart zygote";
+
+const FALLBACK_LOCALE: &str = "en-US";
+
+/// Restores for devices toggled from separate threads read-modify-write the same file.
+static RESTORE_LOCK: Mutex<()> = Mutex::new(());
+
+/// Settings in place before the first toggle, so repeated toggles still restore the original.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct I18nRestorePoint {
+    pub locale: String,
+    pub force_rtl: bool,
+    pub saved_at: String,
+}
+
+pub fn i18n_restore_path() -> PathBuf {
+    config_path().with_file_name(".lazy_blacktea_i18n_restore.json")
+}
+
+/// Canonical spelling of a supported pseudo-locale (`en-xa` becomes `en-XA`).
+pub fn normalize_pseudo_locale(value: &str) -> Result<String, String> {
+    let value = value.trim();
+    PSEUDO_LOCALES
+        .iter()
+        .find(|locale| locale.eq_ignore_ascii_case(value))
+        .map(|locale| locale.to_string())
+        .ok_or_else(|| {
+            format!(
+                "Unsupported pseudo-locale: {value} (expected {})",
+                PSEUDO_LOCALES.join(" or ")
+            )
+        })
+}
+
+/// `persist.sys.locale` is empty until the locale is first changed; the build default then
+/// lives in `ro.product.locale`. Values are passed back to the shell, so only well-formed
+/// tags are kept.
+pub fn original_locale(persist_locale: &str, product_locale: &str) -> String {
+    [persist_locale.trim(), product_locale.trim()]
+        .into_iter()
+        .find(|locale| is_valid_locale(locale))
+        .unwrap_or(FALLBACK_LOCALE)
+        .to_string()
+}
+
+/// `settings get global debug.force_rtl` prints `1`, `0` or `null`.
+pub fn parse_force_rtl(output: &str) -> bool {
+    output.trim() == "1"
+}
+
+pub fn force_rtl_shell_commands(enable: bool) -> Vec<Vec<String>> {
+    let value = if enable { "1" } else { "0" };
+    vec![
+        vec![
+            "settings".to_string(),
+            "put".to_string(),
+            "global".to_string(),
+            FORCE_RTL_KEY.to_string(),
+            value.to_string(),
+        ],
+        vec![
+            "setprop".to_string(),
+            FORCE_RTL_KEY.to_string(),
+            value.to_string(),
+        ],
+    ]
+}
+
+pub fn load_restore_points(path: &Path) -> HashMap<String, I18nRestorePoint> {
+    let raw = match fs::read_to_string(path) {
+        Ok(raw) => raw,
+        Err(_) => return HashMap::new(),
+    };
+    match serde_json::from_str(&raw) {
+        Ok(points) => points,
+        Err(err) => {
+            warn!(path = %path.display(), error = %err, "i18n restore points are invalid, starting over");
+            HashMap::new()
+        }
+    }
+}
+
+fn write_restore_points(
+    path: &Path,
+    points: &HashMap<String, I18nRestorePoint>,
+    trace_id: &str,
+) -> Result<(), AppError> {
+    let payload = serde_json::to_string_pretty(points).map_err(|err| {
+        AppError::system(
+            Message::keyed("operation_failed")
+                .with("action", "serialize i18n restore points")
+                .with("detail", &err),
+            trace_id,
+        )
+    })?;
+    fs::write(path, payload).map_err(|err| {
+        AppError::system(
+            Message::keyed("operation_failed")
+                .with("action", "save i18n restore points")
+                .with("detail", &err),
+            trace_id,
+        )
+    })
+}
+
+/// Records `point` unless the device already has one; returns whether it was recorded.
+pub fn save_restore_point_if_absent(
+    path: &Path,
+    serial: &str,
+    point: &I18nRestorePoint,
+    trace_id: &str,
+) -> Result<bool, AppError> {
+    let _guard = RESTORE_LOCK
+        .lock()
+        .map_err(|_| AppError::system("i18n restore points locked", trace_id))?;
+    let mut points = load_restore_points(path);
+    if points.contains_key(serial) {
+        return Ok(false);
+    }
+    points.insert(serial.to_string(), point.clone());
+    write_restore_points(path, &points, trace_id)?;
+    Ok(true)
+}
+
+pub fn remove_restore_point(path: &Path, serial: &str, trace_id: &str) -> Result<(), AppError> {
+    let _guard = RESTORE_LOCK
+        .lock()
+        .map_err(|_| AppError::system("i18n restore points locked", trace_id))?;
+    let mut points = load_restore_points(path);
+    if points.remove(serial).is_some() {
+        write_restore_points(path, &points, trace_id)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(locale: &str, force_rtl: bool) -> I18nRestorePoint {
+        I18nRestorePoint {
+            locale: locale.to_string(),
+            force_rtl,
+            saved_at: "2024-01-01T00:00:00Z".to_string(),
+        }
+    }
+
+    #[test]
+    fn normalizes_pseudo_locales() {
+        assert_eq!(normalize_pseudo_locale(" en-xa ").unwrap(), "en-XA");
+        assert_eq!(normalize_pseudo_locale("AR-XB").unwrap(), "ar-XB");
+        assert!(normalize_pseudo_locale("fr-FR").is_err());
+        assert!(normalize_pseudo_locale("en-XA; reboot").is_err());
+    }
+
+    #[test]
+    fn reads_device_defaults() {
+        assert_eq!(original_locale("fr-FR\n", "en-US"), "fr-FR");
+        assert_eq!(original_locale("", "ja-JP\n"), "ja-JP");
+        assert_eq!(original_locale("", ""), "en-US");
+        assert_eq!(original_locale("fr-FR;reboot", ""), "en-US");
+        assert!(parse_force_rtl("1\n"));
+        assert!(!parse_force_rtl("null"));
+        assert_eq!(
+            force_rtl_shell_commands(true)[1],
+            vec!["setprop", "debug.force_rtl", "1"]
+        );
+    }
+
+    #[test]
+    fn keeps_first_restore_point() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("restore.json");
+        assert!(
+            save_restore_point_if_absent(&path, "A1", &point("fr-FR", false), "trace").unwrap()
+        );
+        assert!(
+            !save_restore_point_if_absent(&path, "A1", &point("en-XA", true), "trace").unwrap()
+        );
+        assert!(save_restore_point_if_absent(&path, "B2", &point("en-US", true), "trace").unwrap());
+
+        let points = load_restore_points(&path);
+        assert_eq!(points["A1"].locale, "fr-FR");
+        remove_restore_point(&path, "A1", "trace").unwrap();
+        let points = load_restore_points(&path);
+        assert!(!points.contains_key("A1"));
+        assert!(points["B2"].force_rtl);
+    }
+}
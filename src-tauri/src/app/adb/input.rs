@@ -0,0 +1,467 @@
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+
+use crate::app::models::{PointerGesture, UiSelector};
+use crate::app::ui_diff::UiNodeSnapshot;
+
+const DEFAULT_LONG_PRESS_MS: u32 = 800;
+const DEFAULT_SWIPE_MS: u32 = 300;
+const MAX_GESTURE_DURATION_MS: u32 = 10_000;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScaledGesture {
+    pub kind: String,
+    pub x: u32,
+    pub y: u32,
+    pub end_x: Option<u32>,
+    pub end_y: Option<u32>,
+    pub duration_ms: Option<u32>,
+}
+
+/// Returns the device size matching the orientation of the source image.
+/// `wm size` always reports the natural orientation, while screenshots follow the current rotation.
+pub fn orient_device_size(
+    device_width: u32,
+    device_height: u32,
+    source_width: u32,
+    source_height: u32,
+) -> (u32, u32) {
+    let source_landscape = source_width > source_height;
+    let device_landscape = device_width > device_height;
+    if source_landscape != device_landscape {
+        (device_height, device_width)
+    } else {
+        (device_width, device_height)
+    }
+}
+
+pub fn scale_coordinate(value: u32, source: u32, target: u32) -> u32 {
+    if source == 0 || target == 0 {
+        return 0;
+    }
+    let scaled = (u64::from(value) * u64::from(target) + u64::from(source) / 2) / u64::from(source);
+    scaled.min(u64::from(target - 1)) as u32
+}
+
+pub fn scale_pointer_gesture(
+    gesture: &PointerGesture,
+    device_width: u32,
+    device_height: u32,
+) -> Result<ScaledGesture, String> {
+    if gesture.source_width == 0 || gesture.source_height == 0 {
+        return Err("source_width and source_height must be positive".to_string());
+    }
+    if gesture.x >= gesture.source_width || gesture.y >= gesture.source_height {
+        return Err("Gesture start point is outside the source image".to_string());
+    }
+    let (target_w, target_h) = orient_device_size(
+        device_width,
+        device_height,
+        gesture.source_width,
+        gesture.source_height,
+    );
+    let x = scale_coordinate(gesture.x, gesture.source_width, target_w);
+    let y = scale_coordinate(gesture.y, gesture.source_height, target_h);
+    let kind = gesture.kind.trim().to_lowercase();
+
+    match kind.as_str() {
+        "tap" => Ok(ScaledGesture {
+            kind,
+            x,
+            y,
+            end_x: None,
+            end_y: None,
+            duration_ms: None,
+        }),
+        "long_press" => Ok(ScaledGesture {
+            kind,
+            x,
+            y,
+            end_x: None,
+            end_y: None,
+            duration_ms: Some(clamp_duration(gesture.duration_ms, DEFAULT_LONG_PRESS_MS)),
+        }),
+        "swipe" => {
+            let (end_x, end_y) = match (gesture.end_x, gesture.end_y) {
+                (Some(end_x), Some(end_y)) => (end_x, end_y),
+                _ => return Err("Swipe requires end_x and end_y".to_string()),
+            };
+            if end_x >= gesture.source_width || end_y >= gesture.source_height {
+                return Err("Gesture end point is outside the source image".to_string());
+            }
+            Ok(ScaledGesture {
+                kind,
+                x,
+                y,
+                end_x: Some(scale_coordinate(end_x, gesture.source_width, target_w)),
+                end_y: Some(scale_coordinate(end_y, gesture.source_height, target_h)),
+                duration_ms: Some(clamp_duration(gesture.duration_ms, DEFAULT_SWIPE_MS)),
+            })
+        }
+        _ => Err(format!("Unsupported gesture kind: {}", gesture.kind)),
+    }
+}
+
+pub fn build_gesture_input_args(gesture: &ScaledGesture) -> Vec<String> {
+    match gesture.kind.as_str() {
+        "tap" => vec![
+            "input".to_string(),
+            "tap".to_string(),
+            gesture.x.to_string(),
+            gesture.y.to_string(),
+        ],
+        _ => {
+            let end_x = gesture.end_x.unwrap_or(gesture.x);
+            let end_y = gesture.end_y.unwrap_or(gesture.y);
+            vec![
+                "input".to_string(),
+                "swipe".to_string(),
+                gesture.x.to_string(),
+                gesture.y.to_string(),
+                end_x.to_string(),
+                end_y.to_string(),
+                gesture.duration_ms.unwrap_or(DEFAULT_SWIPE_MS).to_string(),
+            ]
+        }
+    }
+}
+
+fn clamp_duration(value: Option<u32>, default: u32) -> u32 {
+    value.unwrap_or(default).clamp(1, MAX_GESTURE_DURATION_MS)
+}
+
+pub fn validate_unlock_pin(pin: &str) -> Result<String, String> {
+    let pin = pin.trim();
+    if pin.len() < 4 || pin.len() > 16 {
+        return Err("PIN must be 4-16 digits".to_string());
+    }
+    if !pin.chars().all(|ch| ch.is_ascii_digit()) {
+        return Err("PIN must contain digits only".to_string());
+    }
+    Ok(pin.to_string())
+}
+
+/// Escape a value for `adb shell input text`: the device shell re-parses the argument, and
+/// `input` reads `%s` as a space. Only printable ASCII can be typed this way.
+pub fn escape_input_text(value: &str) -> Result<String, String> {
+    if value.is_empty() {
+        return Err("Text value is empty".to_string());
+    }
+    let mut escaped = String::with_capacity(value.len() * 2);
+    for ch in value.chars() {
+        match ch {
+            ' ' => escaped.push_str("%s"),
+            '\\' | '"' | '\'' | '`' | '$' | '&' | '|' | ';' | '<' | '>' | '(' | ')' | '*' | '?'
+            | '~' | '!' | '#' | '[' | ']' | '{' | '}' => {
+                escaped.push('\\');
+                escaped.push(ch);
+            }
+            ch if ch.is_ascii_graphic() => escaped.push(ch),
+            _ => return Err("Only printable ASCII text can be typed".to_string()),
+        }
+    }
+    Ok(escaped)
+}
+
+pub const ADB_KEYBOARD_IME: &str = "com.android.adbkeyboard/.AdbIME";
+pub const ADB_KEYBOARD_B64_ACTION: &str = "ADB_INPUT_B64";
+
+/// Characters per `input text` call; longer arguments drop characters on slow devices.
+pub const INPUT_TEXT_CHUNK_CHARS: usize = 64;
+/// Raw bytes per ADBKeyboard broadcast, before base64.
+pub const ADB_KEYBOARD_CHUNK_BYTES: usize = 512;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TextInputStep {
+    /// Already escaped argument for `input text`.
+    Text(String),
+    KeyEvent(&'static str),
+}
+
+/// Whether `value` can only be typed through ADBKeyboard (anything beyond printable ASCII,
+/// spaces, tabs and newlines).
+pub fn needs_adb_keyboard(value: &str) -> bool {
+    value
+        .chars()
+        .any(|ch| !(ch.is_ascii_graphic() || matches!(ch, ' ' | '\t' | '\n' | '\r')))
+}
+
+/// Splits `value` into `input text` chunks of at most `chunk_chars` characters, with tabs and
+/// newlines sent as key events. A literal `%s` is split across two calls so `input` does not
+/// turn it into a space.
+pub fn plan_input_text(value: &str, chunk_chars: usize) -> Result<Vec<TextInputStep>, String> {
+    if value.is_empty() {
+        return Err("Text value is empty".to_string());
+    }
+    let chunk_chars = chunk_chars.max(2);
+    let mut steps = Vec::new();
+    let mut pending = String::new();
+    let flush = |pending: &mut String, steps: &mut Vec<TextInputStep>| -> Result<(), String> {
+        if !pending.is_empty() {
+            steps.push(TextInputStep::Text(escape_input_text(pending)?));
+            pending.clear();
+        }
+        Ok(())
+    };
+    for ch in value.chars() {
+        let key = match ch {
+            '\n' => Some("KEYCODE_ENTER"),
+            '\t' => Some("KEYCODE_TAB"),
+            '\r' => continue,
+            _ => None,
+        };
+        if let Some(key) = key {
+            flush(&mut pending, &mut steps)?;
+            steps.push(TextInputStep::KeyEvent(key));
+            continue;
+        }
+        if pending.chars().count() >= chunk_chars || (ch == 's' && pending.ends_with('%')) {
+            flush(&mut pending, &mut steps)?;
+        }
+        pending.push(ch);
+    }
+    flush(&mut pending, &mut steps)?;
+    Ok(steps)
+}
+
+/// Base64 payloads for `am broadcast -a ADB_INPUT_B64`, split on character boundaries.
+pub fn adb_keyboard_payloads(value: &str, chunk_bytes: usize) -> Vec<String> {
+    let chunk_bytes = chunk_bytes.max(4);
+    let mut payloads = Vec::new();
+    let mut start = 0;
+    let mut end = 0;
+    for (index, ch) in value.char_indices() {
+        let next = index + ch.len_utf8();
+        if next - start > chunk_bytes && end > start {
+            payloads.push(STANDARD.encode(&value[start..end]));
+            start = end;
+        }
+        end = next;
+    }
+    if end > start {
+        payloads.push(STANDARD.encode(&value[start..end]));
+    }
+    payloads
+}
+
+/// `(left, top, right, bottom)` of a uiautomator `bounds="[l,t][r,b]"` attribute.
+pub fn parse_bounds_rect(bounds: &str) -> Option<(u32, u32, u32, u32)> {
+    let numbers: Vec<u32> = bounds
+        .split(|ch: char| !ch.is_ascii_digit())
+        .filter(|part| !part.is_empty())
+        .map(|part| part.parse().ok())
+        .collect::<Option<Vec<_>>>()?;
+    match numbers.as_slice() {
+        [left, top, right, bottom] if right >= left && bottom >= top => {
+            Some((*left, *top, *right, *bottom))
+        }
+        _ => None,
+    }
+}
+
+/// Center of a uiautomator `bounds="[l,t][r,b]"` attribute.
+pub fn parse_bounds_center(bounds: &str) -> Option<(u32, u32)> {
+    let (left, top, right, bottom) = parse_bounds_rect(bounds)?;
+    Some(((left + right) / 2, (top + bottom) / 2))
+}
+
+/// First node matching every provided selector field; `resource_id` also matches the short id
+/// without the `package:id/` prefix.
+pub fn find_selector_center(nodes: &[UiNodeSnapshot], selector: &UiSelector) -> Option<(u32, u32)> {
+    fn wanted(value: &Option<String>) -> Option<&str> {
+        value.as_deref().map(str::trim).filter(|v| !v.is_empty())
+    }
+    let resource_id = wanted(&selector.resource_id);
+    let text = wanted(&selector.text);
+    let content_desc = wanted(&selector.content_desc);
+    if resource_id.is_none() && text.is_none() && content_desc.is_none() {
+        return None;
+    }
+    nodes
+        .iter()
+        .find(|node| {
+            resource_id.is_none_or(|id| {
+                node.resource_id == id
+                    || node
+                        .resource_id
+                        .rsplit_once(":id/")
+                        .is_some_and(|(_, short)| short == id)
+            }) && text.is_none_or(|text| node.text == text)
+                && content_desc.is_none_or(|desc| node.content_desc == desc)
+        })
+        .and_then(|node| parse_bounds_center(&node.bounds))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gesture(kind: &str, x: u32, y: u32) -> PointerGesture {
+        PointerGesture {
+            kind: kind.to_string(),
+            x,
+            y,
+            end_x: None,
+            end_y: None,
+            duration_ms: None,
+            source_width: 540,
+            source_height: 1200,
+        }
+    }
+
+    #[test]
+    fn scale_coordinate_maps_and_clamps() {
+        assert_eq!(scale_coordinate(270, 540, 1080), 540);
+        assert_eq!(scale_coordinate(539, 540, 1080), 1078);
+        assert_eq!(scale_coordinate(10, 0, 1080), 0);
+    }
+
+    #[test]
+    fn orient_device_size_swaps_for_rotated_source() {
+        assert_eq!(orient_device_size(1080, 2400, 1200, 540), (2400, 1080));
+        assert_eq!(orient_device_size(1080, 2400, 540, 1200), (1080, 2400));
+    }
+
+    #[test]
+    fn scale_pointer_gesture_tap() {
+        let scaled = scale_pointer_gesture(&gesture("tap", 270, 600), 1080, 2400).unwrap();
+        assert_eq!((scaled.x, scaled.y), (540, 1200));
+        assert_eq!(
+            build_gesture_input_args(&scaled),
+            vec!["input", "tap", "540", "1200"]
+        );
+    }
+
+    #[test]
+    fn scale_pointer_gesture_long_press_uses_swipe_in_place() {
+        let scaled = scale_pointer_gesture(&gesture("long_press", 100, 100), 1080, 2400).unwrap();
+        assert_eq!(scaled.duration_ms, Some(DEFAULT_LONG_PRESS_MS));
+        assert_eq!(
+            build_gesture_input_args(&scaled),
+            vec!["input", "swipe", "200", "200", "200", "200", "800"]
+        );
+    }
+
+    #[test]
+    fn scale_pointer_gesture_swipe_requires_end() {
+        assert!(scale_pointer_gesture(&gesture("swipe", 10, 10), 1080, 2400).is_err());
+        let mut swipe = gesture("swipe", 10, 10);
+        swipe.end_x = Some(20);
+        swipe.end_y = Some(1000);
+        swipe.duration_ms = Some(60_000);
+        let scaled = scale_pointer_gesture(&swipe, 1080, 2400).unwrap();
+        assert_eq!(scaled.end_x, Some(40));
+        assert_eq!(scaled.end_y, Some(2000));
+        assert_eq!(scaled.duration_ms, Some(MAX_GESTURE_DURATION_MS));
+    }
+
+    #[test]
+    fn scale_pointer_gesture_rejects_invalid_input() {
+        assert!(scale_pointer_gesture(&gesture("pinch", 10, 10), 1080, 2400).is_err());
+        assert!(scale_pointer_gesture(&gesture("tap", 540, 10), 1080, 2400).is_err());
+        let mut zero = gesture("tap", 0, 0);
+        zero.source_width = 0;
+        assert!(scale_pointer_gesture(&zero, 1080, 2400).is_err());
+    }
+
+    #[test]
+    fn validate_unlock_pin_requires_digits() {
+        assert_eq!(validate_unlock_pin(" 1234 ").unwrap(), "1234");
+        assert!(validate_unlock_pin("123").is_err());
+        assert!(validate_unlock_pin("12a4").is_err());
+        assert!(validate_unlock_pin("1234; reboot").is_err());
+    }
+
+    #[test]
+    fn escape_input_text_handles_shell_and_spaces() {
+        assert_eq!(escape_input_text("qa user").unwrap(), "qa%suser");
+        assert_eq!(escape_input_text("p@ss$w0rd!").unwrap(), "p@ss\\$w0rd\\!");
+        assert_eq!(escape_input_text("a'b\"c").unwrap(), "a\\'b\\\"c");
+        assert!(escape_input_text("").is_err());
+        assert!(escape_input_text("caf\u{e9}").is_err());
+    }
+
+    #[test]
+    fn plans_chunked_input_text() {
+        assert_eq!(
+            plan_input_text("hello world\nok", 64).unwrap(),
+            vec![
+                TextInputStep::Text("hello%sworld".to_string()),
+                TextInputStep::KeyEvent("KEYCODE_ENTER"),
+                TextInputStep::Text("ok".to_string()),
+            ]
+        );
+        assert_eq!(
+            plan_input_text("abcdefg", 3).unwrap(),
+            vec![
+                TextInputStep::Text("abc".to_string()),
+                TextInputStep::Text("def".to_string()),
+                TextInputStep::Text("g".to_string()),
+            ]
+        );
+        assert_eq!(
+            plan_input_text("100%s", 64).unwrap(),
+            vec![
+                TextInputStep::Text("100%".to_string()),
+                TextInputStep::Text("s".to_string()),
+            ]
+        );
+        assert!(plan_input_text("", 64).is_err());
+        assert!(plan_input_text("caf\u{e9}", 64).is_err());
+    }
+
+    #[test]
+    fn detects_text_that_needs_adb_keyboard() {
+        assert!(!needs_adb_keyboard("plain text\twith tab\n"));
+        assert!(needs_adb_keyboard("caf\u{e9}"));
+        assert!(needs_adb_keyboard("\u{4f60}\u{597d}"));
+    }
+
+    #[test]
+    fn splits_adb_keyboard_payloads_on_char_boundaries() {
+        let text = "\u{4f60}\u{597d}ab";
+        let payloads = adb_keyboard_payloads(text, 4);
+        let decoded: Vec<String> = payloads
+            .iter()
+            .map(|payload| String::from_utf8(STANDARD.decode(payload).unwrap()).unwrap())
+            .collect();
+        assert_eq!(decoded, vec!["\u{4f60}", "\u{597d}a", "b"]);
+        assert_eq!(decoded.concat(), text);
+        assert_eq!(adb_keyboard_payloads("", 16), Vec::<String>::new());
+    }
+
+    #[test]
+    fn finds_selector_center_from_nodes() {
+        let node = |resource_id: &str, text: &str, bounds: &str| UiNodeSnapshot {
+            path: String::new(),
+            class_name: "android.widget.EditText".to_string(),
+            resource_id: resource_id.to_string(),
+            text: text.to_string(),
+            content_desc: String::new(),
+            bounds: bounds.to_string(),
+            hint: String::new(),
+            clickable: true,
+            enabled: true,
+        };
+        let nodes = vec![
+            node("com.example:id/username", "", "[40,300][1040,420]"),
+            node("com.example:id/password", "", "[40,460][1040,580]"),
+            node("", "Sign in", "[40,700][1040,820]"),
+        ];
+        let selector = |resource_id: Option<&str>, text: Option<&str>| UiSelector {
+            resource_id: resource_id.map(str::to_string),
+            text: text.map(str::to_string),
+            content_desc: None,
+        };
+        assert_eq!(
+            find_selector_center(&nodes, &selector(Some("password"), None)),
+            Some((540, 520))
+        );
+        assert_eq!(
+            find_selector_center(&nodes, &selector(None, Some("Sign in"))),
+            Some((540, 760))
+        );
+        assert_eq!(find_selector_center(&nodes, &selector(None, None)), None);
+        assert_eq!(parse_bounds_center("[0,0][bad]"), None);
+    }
+}
@@ -0,0 +1,163 @@
+use crate::app::adb::apps::is_valid_package_name;
+
+/// Helper app used on physical devices. Appium Settings ships a foreground location service
+/// that feeds mock fixes once it holds the `android:mock_location` app op.
+pub const DEFAULT_MOCK_LOCATION_PACKAGE: &str = "io.appium.settings";
+const MOCK_LOCATION_SERVICE: &str = ".LocationService";
+const DEFAULT_ACCURACY_METERS: f64 = 5.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MockCoordinates {
+    pub latitude: f64,
+    pub longitude: f64,
+    pub accuracy: f64,
+}
+
+pub fn is_emulator_serial(serial: &str) -> bool {
+    serial
+        .strip_prefix("emulator-")
+        .is_some_and(|port| !port.is_empty() && port.chars().all(|ch| ch.is_ascii_digit()))
+}
+
+pub fn validate_mock_coordinates(
+    latitude: f64,
+    longitude: f64,
+    accuracy: Option<f64>,
+) -> Result<MockCoordinates, String> {
+    if !latitude.is_finite() || !(-90.0..=90.0).contains(&latitude) {
+        return Err("Latitude must be between -90 and 90".to_string());
+    }
+    if !longitude.is_finite() || !(-180.0..=180.0).contains(&longitude) {
+        return Err("Longitude must be between -180 and 180".to_string());
+    }
+    let accuracy = accuracy.unwrap_or(DEFAULT_ACCURACY_METERS);
+    if !accuracy.is_finite() || accuracy <= 0.0 || accuracy > 10_000.0 {
+        return Err("Accuracy must be between 0 and 10000 meters".to_string());
+    }
+    Ok(MockCoordinates {
+        latitude,
+        longitude,
+        accuracy,
+    })
+}
+
+pub fn validate_helper_package(value: &str) -> Result<String, String> {
+    let value = value.trim();
+    if is_valid_package_name(value) {
+        Ok(value.to_string())
+    } else {
+        Err(format!("Invalid helper package name: {value}"))
+    }
+}
+
+/// `adb emu geo fix` takes longitude before latitude.
+pub fn build_geo_fix_args(serial: &str, coords: &MockCoordinates) -> Vec<String> {
+    vec![
+        "-s".to_string(),
+        serial.to_string(),
+        "emu".to_string(),
+        "geo".to_string(),
+        "fix".to_string(),
+        format!("{:.6}", coords.longitude),
+        format!("{:.6}", coords.latitude),
+    ]
+}
+
+pub fn build_helper_location_args(
+    serial: &str,
+    package: &str,
+    coords: &MockCoordinates,
+) -> Vec<String> {
+    vec![
+        "-s".to_string(),
+        serial.to_string(),
+        "shell".to_string(),
+        "am".to_string(),
+        "start-foreground-service".to_string(),
+        "--user".to_string(),
+        "0".to_string(),
+        "-n".to_string(),
+        format!("{package}/{MOCK_LOCATION_SERVICE}"),
+        "--es".to_string(),
+        "latitude".to_string(),
+        format!("{:.6}", coords.latitude),
+        "--es".to_string(),
+        "longitude".to_string(),
+        format!("{:.6}", coords.longitude),
+        "--es".to_string(),
+        "accuracy".to_string(),
+        format!("{:.1}", coords.accuracy),
+    ]
+}
+
+pub fn build_helper_stop_args(serial: &str, package: &str) -> Vec<String> {
+    vec![
+        "-s".to_string(),
+        serial.to_string(),
+        "shell".to_string(),
+        "am".to_string(),
+        "stopservice".to_string(),
+        format!("{package}/{MOCK_LOCATION_SERVICE}"),
+    ]
+}
+
+pub fn build_mock_location_appops_args(serial: &str, package: &str, allow: bool) -> Vec<String> {
+    vec![
+        "-s".to_string(),
+        serial.to_string(),
+        "shell".to_string(),
+        "appops".to_string(),
+        "set".to_string(),
+        package.to_string(),
+        "android:mock_location".to_string(),
+        if allow { "allow" } else { "deny" }.to_string(),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn is_emulator_serial_matches_console_port() {
+        assert!(is_emulator_serial("emulator-5554"));
+        assert!(!is_emulator_serial("emulator-"));
+        assert!(!is_emulator_serial("192.168.1.2:5555"));
+        assert!(!is_emulator_serial("R58M123ABC"));
+    }
+
+    #[test]
+    fn validate_mock_coordinates_checks_ranges() {
+        let coords = validate_mock_coordinates(25.033, 121.5654, None).unwrap();
+        assert_eq!(coords.accuracy, 5.0);
+        assert!(validate_mock_coordinates(91.0, 0.0, None).is_err());
+        assert!(validate_mock_coordinates(0.0, -181.0, None).is_err());
+        assert!(validate_mock_coordinates(0.0, 0.0, Some(0.0)).is_err());
+        assert!(validate_mock_coordinates(f64::NAN, 0.0, None).is_err());
+    }
+
+    #[test]
+    fn validate_helper_package_rejects_shell_input() {
+        assert_eq!(
+            validate_helper_package(" io.appium.settings ").unwrap(),
+            "io.appium.settings"
+        );
+        assert!(validate_helper_package("io.appium;reboot").is_err());
+        assert!(validate_helper_package("settings").is_err());
+    }
+
+    #[test]
+    fn build_geo_fix_args_orders_longitude_first() {
+        let coords = validate_mock_coordinates(25.0, 121.5, Some(3.0)).unwrap();
+        let args = build_geo_fix_args("emulator-5554", &coords);
+        assert_eq!(&args[2..], ["emu", "geo", "fix", "121.500000", "25.000000"]);
+    }
+
+    #[test]
+    fn build_helper_location_args_targets_location_service() {
+        let coords = validate_mock_coordinates(25.0, 121.5, Some(3.0)).unwrap();
+        let args = build_helper_location_args("serial", DEFAULT_MOCK_LOCATION_PACKAGE, &coords);
+        assert!(args.contains(&"io.appium.settings/.LocationService".to_string()));
+        assert_eq!(args.last().map(String::as_str), Some("3.0"));
+    }
+}
@@ -1,4 +1,230 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::{OnceLock, RwLock};
+
+use crate::app::adb::location::is_emulator_serial;
+use crate::app::command_log::serial_from_args;
+use crate::app::config::{AdbPathOverride, AdbSettings};
+
+pub const ADB_SERVER_SOCKET_ENV: &str = "ADB_SERVER_SOCKET";
+pub const TRANSPORT_USB: &str = "usb";
+pub const TRANSPORT_TCP: &str = "tcp";
+pub const TRANSPORT_EMULATOR: &str = "emulator";
+pub const ADB_TRANSPORTS: [&str; 3] = [TRANSPORT_USB, TRANSPORT_TCP, TRANSPORT_EMULATOR];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AdbServerTarget {
+    pub host: String,
+    pub port: u16,
+}
+
+impl AdbServerTarget {
+    pub fn socket_spec(&self) -> String {
+        if self.host.contains(':') && !self.host.starts_with('[') {
+            format!("tcp:[{}]:{}", self.host, self.port)
+        } else {
+            format!("tcp:{}:{}", self.host, self.port)
+        }
+    }
+}
+
+fn active_server_slot() -> &'static RwLock<Option<AdbServerTarget>> {
+    static SLOT: OnceLock<RwLock<Option<AdbServerTarget>>> = OnceLock::new();
+    SLOT.get_or_init(|| RwLock::new(None))
+}
+
+/// Hosts reached through an SSH tunnel resolve to the forwarded local port.
+pub fn resolve_adb_server(settings: &AdbSettings) -> Option<AdbServerTarget> {
+    let active = settings.active_server.trim();
+    if active.is_empty() {
+        return None;
+    }
+    settings
+        .server_hosts
+        .iter()
+        .find(|entry| entry.name == active)
+        .map(|entry| match &entry.ssh_tunnel {
+            Some(tunnel) => AdbServerTarget {
+                host: "127.0.0.1".to_string(),
+                port: tunnel.local_port,
+            },
+            None => AdbServerTarget {
+                host: entry.host.trim().to_string(),
+                port: entry.port,
+            },
+        })
+}
+
+pub fn set_active_adb_server(target: Option<AdbServerTarget>) {
+    if let Ok(mut guard) = active_server_slot().write() {
+        *guard = target;
+    }
+}
+
+pub fn active_adb_server() -> Option<AdbServerTarget> {
+    active_server_slot()
+        .read()
+        .map(|guard| guard.clone())
+        .unwrap_or_default()
+}
+
+#[derive(Debug, Clone, Default)]
+struct AdbPathRouting {
+    default_program: String,
+    overrides: Vec<AdbPathOverride>,
+}
+
+fn path_routing_slot() -> &'static RwLock<AdbPathRouting> {
+    static SLOT: OnceLock<RwLock<AdbPathRouting>> = OnceLock::new();
+    SLOT.get_or_init(|| RwLock::new(AdbPathRouting::default()))
+}
+
+pub fn set_adb_path_routing(default_program: &str, overrides: &[AdbPathOverride]) {
+    if let Ok(mut guard) = path_routing_slot().write() {
+        *guard = AdbPathRouting {
+            default_program: default_program.to_string(),
+            overrides: overrides.to_vec(),
+        };
+    }
+}
+
+/// Program from the last successful `get_adb_program`, keyed by the config file it came from.
+/// Saving the config clears it; `reload_adb_program` refreshes it on demand.
+fn resolved_program_slot() -> &'static RwLock<Option<(PathBuf, String)>> {
+    static SLOT: OnceLock<RwLock<Option<(PathBuf, String)>>> = OnceLock::new();
+    SLOT.get_or_init(|| RwLock::new(None))
+}
+
+pub fn cached_adb_program(config_path: &Path) -> Option<String> {
+    let guard = resolved_program_slot().read().ok()?;
+    guard
+        .as_ref()
+        .filter(|(path, _)| path == config_path)
+        .map(|(_, program)| program.clone())
+}
+
+pub fn store_adb_program(config_path: &Path, program: &str) {
+    if let Ok(mut guard) = resolved_program_slot().write() {
+        *guard = Some((config_path.to_path_buf(), program.to_string()));
+    }
+}
+
+pub fn invalidate_adb_program_cache() {
+    if let Ok(mut guard) = resolved_program_slot().write() {
+        *guard = None;
+    }
+}
+
+/// Wireless serials are `host:port` or mDNS names such as `adb-XYZ._adb-tls-connect._tcp`.
+pub fn transport_for_serial(serial: &str) -> &'static str {
+    if is_emulator_serial(serial) {
+        TRANSPORT_EMULATOR
+    } else if serial.contains(':') || serial.contains("._tcp") {
+        TRANSPORT_TCP
+    } else {
+        TRANSPORT_USB
+    }
+}
+
+pub fn resolve_adb_program_for_serial(
+    default_program: &str,
+    overrides: &[AdbPathOverride],
+    serial: &str,
+) -> String {
+    let transport = transport_for_serial(serial);
+    overrides
+        .iter()
+        .find(|entry| entry.serial == serial)
+        .or_else(|| {
+            overrides
+                .iter()
+                .find(|entry| entry.serial.is_empty() && entry.transport == transport)
+        })
+        .map(|entry| entry.command_path.clone())
+        .unwrap_or_else(|| default_program.to_string())
+}
+
+/// adb binary to use for `serial`. Only the configured default program is rerouted, so tools
+/// that are not adb (or an explicitly chosen adb) are left alone.
+pub fn route_adb_program_for_serial(program: &str, serial: &str) -> String {
+    let Ok(routing) = path_routing_slot().read() else {
+        return program.to_string();
+    };
+    if routing.overrides.is_empty() || routing.default_program != program {
+        return program.to_string();
+    }
+    resolve_adb_program_for_serial(program, &routing.overrides, serial)
+}
+
+/// The server protocol version from `adb version` (`Android Debug Bridge version 1.0.41`).
+/// A client kills any server with a different protocol version, so every adb binary that
+/// talks to one server must agree on it.
+pub fn parse_adb_protocol_version(stdout: &str) -> Option<String> {
+    stdout.lines().find_map(|line| {
+        line.trim()
+            .strip_prefix("Android Debug Bridge version ")
+            .map(|version| version.trim().to_string())
+            .filter(|version| !version.is_empty())
+    })
+}
+
+/// Why `entry` cannot share the default adb server, given the protocol versions of the
+/// default binary and of the override (`None` when `adb version` could not be read).
+pub fn override_version_conflict(
+    entry: &AdbPathOverride,
+    default_version: Option<&str>,
+    override_version: Option<&str>,
+) -> Option<String> {
+    let scope = if entry.serial.is_empty() {
+        format!("{} devices", entry.transport)
+    } else {
+        entry.serial.clone()
+    };
+    match (default_version, override_version) {
+        (Some(default), Some(version)) if default == version => None,
+        (Some(default), Some(version)) => Some(format!(
+            "adb override for {scope} ({}) is protocol version {version}, but the default adb is {default}; they would keep restarting the shared adb server",
+            entry.command_path
+        )),
+        (None, _) => Some(format!(
+            "Could not read the default adb version to check the override for {scope}"
+        )),
+        (_, None) => Some(format!(
+            "Could not read the version of the adb override for {scope} ({})",
+            entry.command_path
+        )),
+    }
+}
+
+/// Reroute an adb invocation by the `-s <serial>` in its arguments.
+pub fn route_adb_program(program: &str, args: &[String]) -> String {
+    match serial_from_args(args) {
+        Some(serial) => route_adb_program_for_serial(program, &serial),
+        None => program.to_string(),
+    }
+}
+
+/// Point a spawned adb (or adb-driven tool such as scrcpy) at the selected adb server.
+///
+/// Remote hosts must run their server with `adb -a nodaemon server start` so it listens on all
+/// interfaces. Without a selection the inherited environment is left untouched.
+pub fn configure_adb_command(command: &mut Command) -> &mut Command {
+    if let Some(target) = active_adb_server() {
+        command.env(ADB_SERVER_SOCKET_ENV, target.socket_spec());
+    }
+    command
+}
+
+pub fn adb_command(program: &str) -> Command {
+    let mut command = Command::new(program);
+    configure_adb_command(&mut command);
+    command
+}
+
+/// `adb_command` for a device-scoped invocation, using that device's adb binary.
+pub fn adb_command_for_serial(program: &str, serial: &str) -> Command {
+    adb_command(&route_adb_program_for_serial(program, serial))
+}
 
 pub fn normalize_command_path(value: &str) -> String {
     let trimmed = value.trim();
@@ -47,6 +273,16 @@ pub fn validate_adb_program(program: &str) -> Result<(), String> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn caches_program_per_config_path() {
+        let config = Path::new("/tmp/lazy_blacktea_locator_test.json");
+        store_adb_program(config, "/opt/adb");
+        assert_eq!(cached_adb_program(config).as_deref(), Some("/opt/adb"));
+        assert_eq!(cached_adb_program(Path::new("/tmp/other.json")), None);
+        invalidate_adb_program_cache();
+        assert_eq!(cached_adb_program(config), None);
+    }
+
     #[test]
     fn strips_wrapping_double_quotes() {
         assert_eq!(
@@ -69,6 +305,103 @@ mod tests {
         assert_eq!(resolve_adb_program("   "), "adb");
     }
 
+    #[test]
+    fn resolves_active_adb_server_from_settings() {
+        let mut settings = AdbSettings::default();
+        assert_eq!(resolve_adb_server(&settings), None);
+        settings.server_hosts = vec![crate::app::config::AdbServerHost {
+            name: "lab".to_string(),
+            host: "10.0.0.5".to_string(),
+            port: 5038,
+            ssh_tunnel: None,
+        }];
+        settings.active_server = "lab".to_string();
+        let target = resolve_adb_server(&settings).expect("target");
+        assert_eq!(target.socket_spec(), "tcp:10.0.0.5:5038");
+
+        settings.server_hosts[0].ssh_tunnel = Some(crate::app::config::SshTunnelSettings {
+            user: "qa".to_string(),
+            key_path: String::new(),
+            ssh_port: 22,
+            remote_port: 5037,
+            local_port: 15037,
+        });
+        let target = resolve_adb_server(&settings).expect("tunnel target");
+        assert_eq!(target.socket_spec(), "tcp:127.0.0.1:15037");
+
+        let ipv6 = AdbServerTarget {
+            host: "fe80::1".to_string(),
+            port: 5037,
+        };
+        assert_eq!(ipv6.socket_spec(), "tcp:[fe80::1]:5037");
+    }
+
+    #[test]
+    fn classifies_transports() {
+        assert_eq!(transport_for_serial("emulator-5554"), TRANSPORT_EMULATOR);
+        assert_eq!(transport_for_serial("192.168.1.20:5555"), TRANSPORT_TCP);
+        assert_eq!(
+            transport_for_serial("adb-R58M123-abc._adb-tls-connect._tcp"),
+            TRANSPORT_TCP
+        );
+        assert_eq!(transport_for_serial("R58M123"), TRANSPORT_USB);
+    }
+
+    #[test]
+    fn resolves_adb_program_per_serial_then_transport() {
+        let overrides = vec![
+            AdbPathOverride {
+                serial: String::new(),
+                transport: TRANSPORT_USB.to_string(),
+                command_path: "/opt/usb/adb".to_string(),
+            },
+            AdbPathOverride {
+                serial: "R58M123".to_string(),
+                transport: String::new(),
+                command_path: "/opt/vendor/adb".to_string(),
+            },
+        ];
+        assert_eq!(
+            resolve_adb_program_for_serial("adb", &overrides, "R58M123"),
+            "/opt/vendor/adb"
+        );
+        assert_eq!(
+            resolve_adb_program_for_serial("adb", &overrides, "R58M999"),
+            "/opt/usb/adb"
+        );
+        assert_eq!(
+            resolve_adb_program_for_serial("adb", &overrides, "emulator-5554"),
+            "adb"
+        );
+        assert_eq!(resolve_adb_program_for_serial("adb", &[], "R58M123"), "adb");
+    }
+
+    #[test]
+    fn requires_matching_protocol_versions() {
+        assert_eq!(
+            parse_adb_protocol_version(
+                "Android Debug Bridge version 1.0.41\nVersion 35.0.1-11580240\n"
+            )
+            .as_deref(),
+            Some("1.0.41")
+        );
+        assert_eq!(parse_adb_protocol_version("adb: command not found"), None);
+
+        let entry = AdbPathOverride {
+            serial: "R58M123".to_string(),
+            transport: String::new(),
+            command_path: "/opt/vendor/adb".to_string(),
+        };
+        assert_eq!(
+            override_version_conflict(&entry, Some("1.0.41"), Some("1.0.41")),
+            None
+        );
+        let conflict = override_version_conflict(&entry, Some("1.0.41"), Some("1.0.39")).unwrap();
+        assert!(conflict.contains("R58M123") && conflict.contains("1.0.39"));
+        assert!(override_version_conflict(&entry, Some("1.0.41"), None).is_some());
+        assert!(override_version_conflict(&entry, None, Some("1.0.41")).is_some());
+    }
+
     #[test]
     fn validates_nonexistent_path() {
         let err = validate_adb_program("/this/path/should/not/exist/adb").unwrap_err();
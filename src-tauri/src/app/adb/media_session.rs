@@ -0,0 +1,166 @@
+use crate::app::models::MediaSessionInfo;
+
+/// Keys `send_media_key` accepts: the `cmd media_session dispatch` name and the matching
+/// keycode for `input keyevent`.
+pub const MEDIA_KEYS: [(&str, &str, &str); 8] = [
+    ("play", "play", "KEYCODE_MEDIA_PLAY"),
+    ("pause", "pause", "KEYCODE_MEDIA_PAUSE"),
+    ("play_pause", "play-pause", "KEYCODE_MEDIA_PLAY_PAUSE"),
+    ("stop", "stop", "KEYCODE_MEDIA_STOP"),
+    ("next", "next", "KEYCODE_MEDIA_NEXT"),
+    ("previous", "previous", "KEYCODE_MEDIA_PREVIOUS"),
+    ("fast_forward", "fast-forward", "KEYCODE_MEDIA_FAST_FORWARD"),
+    ("rewind", "rewind", "KEYCODE_MEDIA_REWIND"),
+];
+
+/// `(dispatch name, keycode)` for a media key.
+pub fn media_key(key: &str) -> Result<(&'static str, &'static str), String> {
+    let key = key.trim().to_ascii_lowercase().replace('-', "_");
+    MEDIA_KEYS
+        .iter()
+        .find(|(name, _, _)| *name == key)
+        .map(|(_, dispatch, keycode)| (*dispatch, *keycode))
+        .ok_or_else(|| {
+            format!(
+                "Unknown media key: {key} (expected one of {})",
+                MEDIA_KEYS
+                    .iter()
+                    .map(|(name, _, _)| *name)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        })
+}
+
+/// `PlaybackState.STATE_*` names.
+fn playback_state_name(code: i64) -> String {
+    match code {
+        0 => "none",
+        1 => "stopped",
+        2 => "paused",
+        3 => "playing",
+        4 => "fast_forwarding",
+        5 => "rewinding",
+        6 => "buffering",
+        7 => "error",
+        8 => "connecting",
+        9 => "skipping_to_previous",
+        10 => "skipping_to_next",
+        11 => "skipping_to_queue_item",
+        _ => return format!("unknown({code})"),
+    }
+    .to_string()
+}
+
+fn field<'a>(text: &'a str, key: &str) -> Option<&'a str> {
+    let start = text.find(key)? + key.len();
+    let rest = &text[start..];
+    let end = rest.find([',', '}']).unwrap_or(rest.len());
+    Some(rest[..end].trim())
+}
+
+/// Sessions from `dumpsys media_session`, most recently active first as the stack lists them.
+/// Each session is a header line (`YouTube com.google.android.youtube/YouTube (userId=0)`)
+/// followed by indented `key=value` lines, starting with `ownerPid=`.
+pub fn parse_media_sessions(output: &str) -> Vec<MediaSessionInfo> {
+    let lines: Vec<&str> = output.lines().collect();
+    let mut sessions: Vec<MediaSessionInfo> = Vec::new();
+    for (index, line) in lines.iter().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("ownerPid=") {
+            let tag = index
+                .checked_sub(1)
+                .and_then(|previous| lines.get(previous))
+                .map(|header| {
+                    let header = header.trim();
+                    header
+                        .split_once(" (userId=")
+                        .map(|(tag, _)| tag)
+                        .unwrap_or(header)
+                        .trim()
+                        .to_string()
+                })
+                .unwrap_or_default();
+            sessions.push(MediaSessionInfo {
+                tag,
+                package_name: None,
+                active: false,
+                state: None,
+                position_ms: None,
+                description: None,
+            });
+            continue;
+        }
+        let Some(session) = sessions.last_mut() else {
+            continue;
+        };
+        if let Some(package) = trimmed.strip_prefix("package=") {
+            session.package_name = Some(package.trim().to_string());
+        } else if let Some(active) = trimmed.strip_prefix("active=") {
+            session.active = active.trim() == "true";
+        } else if let Some(state) = trimmed.strip_prefix("state=PlaybackState {") {
+            session.state = field(state, "state=")
+                .and_then(|code| code.parse().ok())
+                .map(playback_state_name);
+            session.position_ms = field(state, "position=").and_then(|value| value.parse().ok());
+        } else if let Some(metadata) = trimmed.strip_prefix("metadata:") {
+            session.description = metadata
+                .split_once("description=")
+                .map(|(_, description)| description.trim().to_string())
+                .filter(|description| !description.is_empty() && description != "null");
+        }
+    }
+    sessions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_sessions_and_keys() {
+        let output = concat!(
+            "MEDIA SESSION SERVICE (dumpsys media_session)\n",
+            "\n",
+            "  Sessions Stack - have 2 sessions:\n",
+            "    YouTube Music com.google.android.apps.youtube.music/YouTube Music (userId=0)\n",
+            "      ownerPid=4321, ownerUid=10150, userId=0\n",
+            "      package=com.google.android.apps.youtube.music\n",
+            "      active=true\n",
+            "      state=PlaybackState {state=3, position=61234, buffered position=0, speed=1.0, updated=99, actions=2360143, custom actions=[], active item id=4, error=null}\n",
+            "      metadata: size=7, description=Song title, Artist, Album\n",
+            "    ExoPlayer com.example.player/ExoPlayer (userId=0)\n",
+            "      ownerPid=5555, ownerUid=10151, userId=0\n",
+            "      package=com.example.player\n",
+            "      active=false\n",
+            "      state=null\n",
+            "      metadata: size=0, description=null\n",
+        );
+        let sessions = parse_media_sessions(output);
+        assert_eq!(sessions.len(), 2);
+        assert_eq!(
+            sessions[0].tag,
+            "YouTube Music com.google.android.apps.youtube.music/YouTube Music"
+        );
+        assert_eq!(
+            sessions[0].package_name.as_deref(),
+            Some("com.google.android.apps.youtube.music")
+        );
+        assert!(sessions[0].active);
+        assert_eq!(sessions[0].state.as_deref(), Some("playing"));
+        assert_eq!(sessions[0].position_ms, Some(61234));
+        assert_eq!(
+            sessions[0].description.as_deref(),
+            Some("Song title, Artist, Album")
+        );
+        assert!(!sessions[1].active);
+        assert_eq!(sessions[1].state, None);
+        assert_eq!(sessions[1].description, None);
+
+        assert_eq!(
+            media_key("Play-Pause"),
+            Ok(("play-pause", "KEYCODE_MEDIA_PLAY_PAUSE"))
+        );
+        assert!(media_key("eject").is_err());
+    }
+}
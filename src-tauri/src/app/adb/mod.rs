@@ -1,11 +1,37 @@
 pub mod apk;
 pub mod apps;
+pub mod audio_forward;
+pub mod automation_services;
+pub mod boot;
+pub mod broadcast;
 pub mod bugreport;
+pub mod chunked_pull;
+pub mod compressed_pull;
 pub mod device_tracking;
+pub mod devtools;
+pub mod emulator;
+pub mod frida;
+pub mod getevent;
+pub mod i18n_toggles;
+pub mod input;
+pub mod location;
 pub mod locator;
+pub mod media_session;
+pub mod monkey;
+pub mod net_shaping;
+pub mod overlay;
 pub mod parse;
 pub mod paths;
+pub mod proxy;
+pub mod push_tokens;
+pub mod reconnect;
 pub mod runner;
 pub mod scrcpy;
+pub mod socket;
+pub mod sync;
 pub mod track_devices;
 pub mod transfer;
+pub mod tunnel;
+pub mod usb;
+pub mod volume;
+pub mod wireless;
@@ -0,0 +1,144 @@
+use std::time::Duration;
+
+pub const CRASH_KIND_CRASH: &str = "crash";
+pub const CRASH_KIND_ANR: &str = "anr";
+pub const CRASH_KIND_NATIVE: &str = "native_crash";
+
+pub const MAX_MONKEY_EVENTS: u32 = 100_000;
+pub const DEFAULT_MONKEY_THROTTLE_MS: u32 = 100;
+const MAX_MONKEY_THROTTLE_MS: u32 = 5_000;
+/// Per-event cost on top of the throttle when sizing the host timeout.
+const MONKEY_EVENT_OVERHEAD_MS: u64 = 20;
+const MONKEY_TIMEOUT_SLACK_SECS: u64 = 60;
+const MAX_MONKEY_TIMEOUT_SECS: u64 = 2 * 60 * 60;
+/// Output lines kept before a crash marker, and the cap on the whole triage excerpt.
+pub const CRASH_CONTEXT_LINES: usize = 10;
+pub const CRASH_EXCERPT_LINES: usize = 200;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MonkeyOptions {
+    pub package_name: String,
+    pub events: u32,
+    pub throttle_ms: u32,
+    pub seed: Option<u64>,
+}
+
+pub fn validate_monkey_options(
+    events: u32,
+    throttle_ms: Option<u32>,
+) -> Result<(u32, u32), String> {
+    if events == 0 || events > MAX_MONKEY_EVENTS {
+        return Err(format!("events must be between 1 and {MAX_MONKEY_EVENTS}"));
+    }
+    let throttle_ms = throttle_ms.unwrap_or(DEFAULT_MONKEY_THROTTLE_MS);
+    if throttle_ms > MAX_MONKEY_THROTTLE_MS {
+        return Err(format!(
+            "throttle_ms must be at most {MAX_MONKEY_THROTTLE_MS}"
+        ));
+    }
+    Ok((events, throttle_ms))
+}
+
+/// Monkey stops at the first crash or ANR (no `--ignore-*` flags), so the device is still
+/// showing the failure when triage runs.
+pub fn build_monkey_args(serial: &str, options: &MonkeyOptions) -> Vec<String> {
+    let mut args = vec![
+        "-s".to_string(),
+        serial.to_string(),
+        "shell".to_string(),
+        "monkey".to_string(),
+        "-p".to_string(),
+        options.package_name.clone(),
+        "--throttle".to_string(),
+        options.throttle_ms.to_string(),
+        "--monitor-native-crashes".to_string(),
+        "-v".to_string(),
+    ];
+    if let Some(seed) = options.seed {
+        args.push("-s".to_string());
+        args.push(seed.to_string());
+    }
+    args.push(options.events.to_string());
+    args
+}
+
+pub fn monkey_timeout(options: &MonkeyOptions) -> Duration {
+    let per_event = u64::from(options.throttle_ms) + MONKEY_EVENT_OVERHEAD_MS;
+    let secs = u64::from(options.events) * per_event / 1000 + MONKEY_TIMEOUT_SLACK_SECS;
+    Duration::from_secs(secs.min(MAX_MONKEY_TIMEOUT_SECS))
+}
+
+/// Crash markers Monkey prints: `// CRASH: <pkg> (pid N)`, `// NOT RESPONDING: <pkg>` and
+/// `** New native crash detected.`; app-side `FATAL EXCEPTION` is also caught.
+pub fn detect_crash_keyword(line: &str) -> Option<&'static str> {
+    let line = line.trim();
+    if line.starts_with("// CRASH:") || line.contains("FATAL EXCEPTION") {
+        Some(CRASH_KIND_CRASH)
+    } else if line.starts_with("// NOT RESPONDING:") {
+        Some(CRASH_KIND_ANR)
+    } else if line.contains("New native crash detected") {
+        Some(CRASH_KIND_NATIVE)
+    } else {
+        None
+    }
+}
+
+/// `Events injected: 1234` from the end of a Monkey run.
+pub fn parse_events_injected(line: &str) -> Option<u64> {
+    line.trim()
+        .strip_prefix("Events injected:")
+        .and_then(|value| value.trim().parse().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(events: u32, throttle_ms: u32, seed: Option<u64>) -> MonkeyOptions {
+        MonkeyOptions {
+            package_name: "com.example.app".to_string(),
+            events,
+            throttle_ms,
+            seed,
+        }
+    }
+
+    #[test]
+    fn validates_and_builds_args() {
+        assert_eq!(validate_monkey_options(500, None).unwrap(), (500, 100));
+        assert!(validate_monkey_options(0, None).is_err());
+        assert!(validate_monkey_options(MAX_MONKEY_EVENTS + 1, None).is_err());
+        assert!(validate_monkey_options(10, Some(10_000)).is_err());
+        assert_eq!(
+            build_monkey_args("A1", &options(500, 50, Some(42))).join(" "),
+            "-s A1 shell monkey -p com.example.app --throttle 50 --monitor-native-crashes -v -s 42 500"
+        );
+        assert_eq!(
+            monkey_timeout(&options(1000, 80, None)),
+            Duration::from_secs(160)
+        );
+        assert_eq!(
+            monkey_timeout(&options(MAX_MONKEY_EVENTS, 5_000, None)),
+            Duration::from_secs(MAX_MONKEY_TIMEOUT_SECS)
+        );
+    }
+
+    #[test]
+    fn detects_crash_markers() {
+        assert_eq!(
+            detect_crash_keyword("// CRASH: com.example.app (pid 4242)"),
+            Some(CRASH_KIND_CRASH)
+        );
+        assert_eq!(
+            detect_crash_keyword("// NOT RESPONDING: com.example.app (pid 4242)"),
+            Some(CRASH_KIND_ANR)
+        );
+        assert_eq!(
+            detect_crash_keyword("** New native crash detected."),
+            Some(CRASH_KIND_NATIVE)
+        );
+        assert_eq!(detect_crash_keyword(":Sending Touch (ACTION_DOWN)"), None);
+        assert_eq!(parse_events_injected("Events injected: 500"), Some(500));
+        assert_eq!(parse_events_injected(":Dropped: keys=0"), None);
+    }
+}
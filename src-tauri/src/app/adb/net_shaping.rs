@@ -0,0 +1,170 @@
+pub const SHAPING_EMULATOR_CONSOLE: &str = "emulator_console";
+pub const SHAPING_TC: &str = "tc";
+
+pub const DEFAULT_SHAPING_INTERFACE: &str = "wlan0";
+
+const MAX_RATE_KBPS: u32 = 1_000_000;
+const MAX_LATENCY_MS: u32 = 10_000;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ShapingParams {
+    /// 0 leaves bandwidth unlimited.
+    pub rate_kbps: u32,
+    pub latency_ms: u32,
+    pub loss_pct: f64,
+}
+
+pub fn validate_shaping(params: ShapingParams) -> Result<ShapingParams, String> {
+    if params.rate_kbps > MAX_RATE_KBPS {
+        return Err(format!("rate_kbps must be at most {MAX_RATE_KBPS}"));
+    }
+    if params.latency_ms > MAX_LATENCY_MS {
+        return Err(format!("latency_ms must be at most {MAX_LATENCY_MS}"));
+    }
+    if !(0.0..=100.0).contains(&params.loss_pct) {
+        return Err("loss_pct must be between 0 and 100".to_string());
+    }
+    if params.rate_kbps == 0 && params.latency_ms == 0 && params.loss_pct == 0.0 {
+        return Err("Nothing to shape; use clear_network_shaping to remove shaping".to_string());
+    }
+    Ok(params)
+}
+
+/// The emulator console shapes speed and delay but cannot drop packets, so loss needs `tc`.
+pub fn use_emulator_console(is_emulator: bool, params: &ShapingParams) -> bool {
+    is_emulator && params.loss_pct == 0.0
+}
+
+/// Argument for `emu network speed`: `<up>:<down>` in kbps, or `full`.
+pub fn emulator_speed_arg(rate_kbps: u32) -> String {
+    if rate_kbps == 0 {
+        "full".to_string()
+    } else {
+        format!("{rate_kbps}:{rate_kbps}")
+    }
+}
+
+/// Argument for `emu network delay`: `<min>:<max>` in ms, or `none`.
+pub fn emulator_delay_arg(latency_ms: u32) -> String {
+    if latency_ms == 0 {
+        "none".to_string()
+    } else {
+        format!("{latency_ms}:{latency_ms}")
+    }
+}
+
+/// Default-route interface from `ip route get <addr>` (`... dev wlan0 src ...`).
+pub fn parse_route_interface(output: &str) -> Option<String> {
+    let mut tokens = output.split_whitespace();
+    while let Some(token) = tokens.next() {
+        if token == "dev" {
+            return tokens
+                .next()
+                .filter(|name| is_valid_interface(name))
+                .map(str::to_string);
+        }
+    }
+    None
+}
+
+/// Interface names end up inside a `su -c` string, so only plain names pass.
+pub fn is_valid_interface(name: &str) -> bool {
+    !name.is_empty()
+        && name.len() <= 15
+        && name
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '_' | '-' | '.'))
+}
+
+pub fn build_netem_command(interface: &str, params: &ShapingParams) -> String {
+    let mut command = format!("tc qdisc replace dev {interface} root netem");
+    if params.latency_ms > 0 {
+        command.push_str(&format!(" delay {}ms", params.latency_ms));
+    }
+    if params.loss_pct > 0.0 {
+        command.push_str(&format!(" loss {}%", params.loss_pct));
+    }
+    if params.rate_kbps > 0 {
+        command.push_str(&format!(" rate {}kbit", params.rate_kbps));
+    }
+    command
+}
+
+pub fn build_netem_clear_command(interface: &str) -> String {
+    format!("tc qdisc del dev {interface} root")
+}
+
+/// `tc qdisc del` fails when no root qdisc was installed; that is not an error for clearing.
+pub fn is_missing_qdisc_error(message: &str) -> bool {
+    let message = message.to_lowercase();
+    message.contains("handle of zero")
+        || message.contains("no such file or directory")
+        || message.contains("invalid argument")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params(rate_kbps: u32, latency_ms: u32, loss_pct: f64) -> ShapingParams {
+        ShapingParams {
+            rate_kbps,
+            latency_ms,
+            loss_pct,
+        }
+    }
+
+    #[test]
+    fn validates_params() {
+        assert!(validate_shaping(params(256, 300, 1.5)).is_ok());
+        assert!(validate_shaping(params(0, 0, 0.0)).is_err());
+        assert!(validate_shaping(params(MAX_RATE_KBPS + 1, 0, 0.0)).is_err());
+        assert!(validate_shaping(params(0, MAX_LATENCY_MS + 1, 0.0)).is_err());
+        assert!(validate_shaping(params(0, 0, 101.0)).is_err());
+        assert!(validate_shaping(params(0, 0, f64::NAN)).is_err());
+    }
+
+    #[test]
+    fn picks_emulator_console_without_loss() {
+        assert!(use_emulator_console(true, &params(256, 100, 0.0)));
+        assert!(!use_emulator_console(true, &params(256, 100, 2.0)));
+        assert!(!use_emulator_console(false, &params(256, 100, 0.0)));
+        assert_eq!(emulator_speed_arg(0), "full");
+        assert_eq!(emulator_speed_arg(128), "128:128");
+        assert_eq!(emulator_delay_arg(0), "none");
+        assert_eq!(emulator_delay_arg(250), "250:250");
+    }
+
+    #[test]
+    fn builds_netem_commands() {
+        assert_eq!(
+            build_netem_command("wlan0", &params(512, 200, 2.5)),
+            "tc qdisc replace dev wlan0 root netem delay 200ms loss 2.5% rate 512kbit"
+        );
+        assert_eq!(
+            build_netem_command("eth0", &params(0, 0, 10.0)),
+            "tc qdisc replace dev eth0 root netem loss 10%"
+        );
+        assert_eq!(
+            build_netem_clear_command("wlan0"),
+            "tc qdisc del dev wlan0 root"
+        );
+        assert!(is_missing_qdisc_error(
+            "RTNETLINK answers: No such file or directory"
+        ));
+        assert!(!is_missing_qdisc_error("su: not found"));
+    }
+
+    #[test]
+    fn parses_route_interface() {
+        assert_eq!(
+            parse_route_interface(
+                "1.1.1.1 via 192.168.1.1 dev wlan0 table 1021 src 192.168.1.20 uid 2000\n"
+            )
+            .as_deref(),
+            Some("wlan0")
+        );
+        assert_eq!(parse_route_interface("unreachable"), None);
+        assert_eq!(parse_route_interface("1.1.1.1 dev wl;rm"), None);
+    }
+}
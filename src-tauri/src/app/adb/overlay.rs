@@ -0,0 +1,92 @@
+use crate::app::models::OverlayPackage;
+
+pub const OVERLAY_ENABLED: &str = "enabled";
+pub const OVERLAY_DISABLED: &str = "disabled";
+/// `---`: the overlay cannot be toggled, e.g. its target is missing or it is immutable.
+pub const OVERLAY_UNAVAILABLE: &str = "unavailable";
+
+/// `cmd overlay list` prints each target package followed by its overlays:
+/// `[x]` enabled, `[ ]` disabled, `---` not toggleable.
+pub fn parse_overlay_list(output: &str) -> Vec<OverlayPackage> {
+    let mut overlays = Vec::new();
+    let mut target: Option<String> = None;
+    for line in output.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+        let (state, package) = if let Some(package) = trimmed.strip_prefix("[x]") {
+            (OVERLAY_ENABLED, package)
+        } else if let Some(package) = trimmed.strip_prefix("[ ]") {
+            (OVERLAY_DISABLED, package)
+        } else if let Some(package) = trimmed.strip_prefix("---") {
+            (OVERLAY_UNAVAILABLE, package)
+        } else {
+            if !trimmed.contains(char::is_whitespace) {
+                target = Some(trimmed.to_string());
+            }
+            continue;
+        };
+        let (Some(target), Some(package)) = (target.as_ref(), package.split_whitespace().next())
+        else {
+            continue;
+        };
+        overlays.push(OverlayPackage {
+            package_name: package.to_string(),
+            target_package: target.clone(),
+            state: state.to_string(),
+        });
+    }
+    overlays
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_overlays_grouped_by_target() {
+        let output = concat!(
+            "android\n",
+            "[x] com.android.theme.color.ocean\n",
+            "[ ] com.android.internal.display.cutout.emulation.corner\n",
+            "--- com.google.android.overlay.modules.ext\n",
+            "\n",
+            "com.android.systemui\n",
+            "[ ] com.android.systemui.navbar.gestural\n",
+        );
+        let overlays = parse_overlay_list(output);
+        let summary: Vec<(&str, &str, &str)> = overlays
+            .iter()
+            .map(|overlay| {
+                (
+                    overlay.package_name.as_str(),
+                    overlay.target_package.as_str(),
+                    overlay.state.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("com.android.theme.color.ocean", "android", OVERLAY_ENABLED),
+                (
+                    "com.android.internal.display.cutout.emulation.corner",
+                    "android",
+                    OVERLAY_DISABLED
+                ),
+                (
+                    "com.google.android.overlay.modules.ext",
+                    "android",
+                    OVERLAY_UNAVAILABLE
+                ),
+                (
+                    "com.android.systemui.navbar.gestural",
+                    "com.android.systemui",
+                    OVERLAY_DISABLED
+                ),
+            ]
+        );
+        assert!(parse_overlay_list("[x] orphan.overlay\n").is_empty());
+    }
+}
@@ -2,8 +2,12 @@ use std::collections::HashMap;
 
 use regex::Regex;
 
+use crate::app::adb::locator::{transport_for_serial, TRANSPORT_USB};
 use crate::app::models::{DeviceDetail, DeviceFileEntry, DeviceSummary};
 
+/// Links at or below this speed make large installs and file transfers noticeably slow.
+pub const USB_HIGH_SPEED_MBPS: u32 = 480;
+
 pub fn parse_adb_devices(output: &str) -> Vec<DeviceSummary> {
     output
         .lines()
@@ -21,8 +25,11 @@ pub fn parse_adb_devices(output: &str) -> Vec<DeviceSummary> {
             let mut product = None;
             let mut device = None;
             let mut transport_id = None;
+            let mut usb_port = None;
             for token in tokens.iter().skip(2) {
-                if let Some(value) = token.strip_prefix("model:") {
+                if let Some(value) = token.strip_prefix("usb:") {
+                    usb_port = Some(value.to_string());
+                } else if let Some(value) = token.strip_prefix("model:") {
                     model = Some(value.to_string());
                 } else if let Some(value) = token.strip_prefix("product:") {
                     product = Some(value.to_string());
@@ -32,13 +39,20 @@ pub fn parse_adb_devices(output: &str) -> Vec<DeviceSummary> {
                     transport_id = Some(value.to_string());
                 }
             }
+            let connection_type = if usb_port.is_some() {
+                TRANSPORT_USB
+            } else {
+                transport_for_serial(&serial)
+            };
             Some(DeviceSummary {
+                connection_type: connection_type.to_string(),
                 serial,
                 state,
                 model,
                 product,
                 device,
                 transport_id,
+                usb_port,
             })
         })
         .collect()
@@ -110,6 +124,11 @@ pub fn build_device_detail(serial: &str, getprop_map: &HashMap<String, String>)
         memory_total_bytes: None,
         audio_state: None,
         bluetooth_manager_state: None,
+        usb_speed_mbps: None,
+        usb_speed: None,
+        adb_features: Vec::new(),
+        lock_state: None,
+        telephony: None,
     }
 }
 
@@ -140,6 +159,70 @@ pub fn parse_settings_bool(output: &str) -> Option<bool> {
     }
 }
 
+/// `adb features` prints one feature per line; older servers print a comma separated list.
+pub fn parse_adb_features(output: &str) -> Vec<String> {
+    let mut features: Vec<String> = output
+        .split([',', '\n'])
+        .map(str::trim)
+        .filter(|feature| !feature.is_empty())
+        .map(str::to_string)
+        .collect();
+    features.sort();
+    features.dedup();
+    features
+}
+
+/// Mbps for values such as `high-speed`, `super-speed-plus`, `SUPERSPEED_10Gb`,
+/// `USB_DATA_TRANSFER_RATE_HIGH_SPEED` or a bare number.
+pub fn parse_usb_speed_value(value: &str) -> Option<u32> {
+    let normalized: String = value
+        .trim()
+        .to_lowercase()
+        .chars()
+        .filter(char::is_ascii_alphanumeric)
+        .collect();
+    if let Ok(mbps) = normalized.parse::<u32>() {
+        return (mbps > 0).then_some(mbps);
+    }
+    if normalized.contains("20gb") {
+        Some(20_000)
+    } else if normalized.contains("10gb") || normalized.contains("superspeedplus") {
+        Some(10_000)
+    } else if normalized.contains("superspeed") {
+        Some(5_000)
+    } else if normalized.contains("highspeed") {
+        Some(USB_HIGH_SPEED_MBPS)
+    } else if normalized.contains("fullspeed") {
+        Some(12)
+    } else {
+        None
+    }
+}
+
+/// The first `key=value` or `key: value` line in `dumpsys usb` whose key mentions the
+/// speed and whose value is a known USB speed.
+pub fn parse_dumpsys_usb_speed(output: &str) -> Option<u32> {
+    output.lines().find_map(|line| {
+        let line = line.trim();
+        let (key, value) = line.split_once('=').or_else(|| line.split_once(':'))?;
+        if !key.to_lowercase().contains("speed") {
+            return None;
+        }
+        parse_usb_speed_value(value)
+    })
+}
+
+pub fn usb_speed_label(mbps: u32) -> String {
+    let label = match mbps {
+        0..=12 => "USB 1.1 Full Speed (12 Mbps)",
+        13..=480 => "USB 2.0 High Speed (480 Mbps)",
+        481..=5_000 => "USB 3.2 Gen 1 (5 Gbps)",
+        5_001..=10_000 => "USB 3.2 Gen 2 (10 Gbps)",
+        _ => "USB 3.2 Gen 2x2 (20 Gbps)",
+    };
+    label.to_string()
+}
+
 pub fn parse_wm_size(output: &str) -> Option<String> {
     let mut physical: Option<String> = None;
     let mut override_size: Option<String> = None;
@@ -170,6 +253,33 @@ pub fn parse_wm_size(output: &str) -> Option<String> {
     override_size.or(physical)
 }
 
+/// `wm density` prints `Physical density: 420` and, when overridden, `Override density: 480`.
+pub fn parse_wm_density(output: &str) -> Option<u32> {
+    let value = |prefix: &str| {
+        output.lines().find_map(|line| {
+            let (label, value) = line.trim().split_once(':')?;
+            (label.trim().eq_ignore_ascii_case(prefix))
+                .then(|| value.trim().parse::<u32>().ok())
+                .flatten()
+                .filter(|density| *density > 0)
+        })
+    };
+    value("override density").or_else(|| value("physical density"))
+}
+
+pub fn parse_size_dimensions(value: &str) -> Option<(u32, u32)> {
+    let (w_str, h_str) = value
+        .trim()
+        .split_once('x')
+        .or_else(|| value.trim().split_once('X'))?;
+    let w = w_str.trim().parse::<u32>().ok()?;
+    let h = h_str.trim().parse::<u32>().ok()?;
+    if w == 0 || h == 0 {
+        return None;
+    }
+    Some((w, h))
+}
+
 fn parse_wm_size_value(value: &str) -> Option<String> {
     let value = value.trim();
     let (w_str, h_str) = value.split_once('x').or_else(|| value.split_once('X'))?;
@@ -179,6 +289,20 @@ fn parse_wm_size_value(value: &str) -> Option<String> {
 }
 
 pub fn parse_df_total_kb(output: &str) -> Result<u64, String> {
+    parse_df_column_kb(output, &["1k-blocks", "1024-blocks", "size"], "size")
+}
+
+pub fn parse_df_available_kb(output: &str) -> Result<u64, String> {
+    parse_df_column_kb(output, &["available", "avail"], "available")
+}
+
+pub fn parse_du_kb(output: &str) -> Option<u64> {
+    output
+        .lines()
+        .find_map(|line| line.split_whitespace().next()?.parse::<u64>().ok())
+}
+
+fn parse_df_column_kb(output: &str, names: &[&str], label: &str) -> Result<u64, String> {
     let lines: Vec<&str> = output
         .lines()
         .map(|line| line.trim())
@@ -193,22 +317,19 @@ pub fn parse_df_total_kb(output: &str) -> Result<u64, String> {
         return Err("Missing df header columns".to_string());
     }
 
-    let size_idx = header_cols
+    let column_idx = header_cols
         .iter()
-        .position(|col| {
-            let col = col.to_ascii_lowercase();
-            col == "1k-blocks" || col == "1024-blocks" || col == "size"
-        })
-        .ok_or_else(|| "Missing df size column".to_string())?;
+        .position(|col| names.contains(&col.to_ascii_lowercase().as_str()))
+        .ok_or_else(|| format!("Missing df {label} column"))?;
 
     let data_cols: Vec<&str> = lines[1].split_whitespace().collect();
-    if data_cols.len() <= size_idx {
-        return Err("df row missing size column".to_string());
+    if data_cols.len() <= column_idx {
+        return Err(format!("df row missing {label} column"));
     }
 
-    data_cols[size_idx]
+    data_cols[column_idx]
         .parse::<u64>()
-        .map_err(|_| "Invalid df size value".to_string())
+        .map_err(|_| format!("Invalid df {label} value"))
 }
 
 pub fn parse_audio_summary(output: &str) -> Option<String> {
@@ -401,6 +522,36 @@ mod tests {
         assert_eq!(parsed[0].state, "device");
         assert_eq!(parsed[0].model.as_deref(), Some("Pixel_7"));
         assert_eq!(parsed[1].state, "unauthorized");
+        assert_eq!(parsed[0].connection_type, "usb");
+        assert_eq!(parsed[1].connection_type, "emulator");
+
+        let transports = parse_adb_devices(
+            "List of devices attached\n2A1B3C device usb:1-4.2 product:panther model:Pixel_7 device:panther transport_id:5\n192.168.1.20:5555 device product:panther model:Pixel_7 device:panther transport_id:6\n",
+        );
+        assert_eq!(transports[0].usb_port.as_deref(), Some("1-4.2"));
+        assert_eq!(transports[0].transport_id.as_deref(), Some("5"));
+        assert_eq!(transports[1].connection_type, "tcp");
+        assert_eq!(transports[1].usb_port, None);
+    }
+
+    #[test]
+    fn parses_usb_speed_and_adb_features() {
+        let dumpsys = "USB MANAGER STATE (dumpsys usb):\n{\n  device_manager={\n    handler={\n      current_functions=mtp,adb\n      connected=true\n      usb_speed=high-speed\n    }\n  }\n}\n";
+        assert_eq!(parse_dumpsys_usb_speed(dumpsys), Some(480));
+        assert_eq!(
+            parse_dumpsys_usb_speed("  mUsbSpeed: SUPERSPEED_10Gb\n"),
+            Some(10_000)
+        );
+        assert_eq!(parse_dumpsys_usb_speed("connected=true\n"), None);
+        assert_eq!(parse_usb_speed_value("super-speed\n"), Some(5_000));
+        assert_eq!(parse_usb_speed_value("UNKNOWN"), None);
+        assert_eq!(usb_speed_label(480), "USB 2.0 High Speed (480 Mbps)");
+
+        assert_eq!(
+            parse_adb_features("shell_v2\ncmd\nstat_v2\ncmd\n"),
+            vec!["cmd", "shell_v2", "stat_v2"]
+        );
+        assert_eq!(parse_adb_features("shell_v2,cmd"), vec!["cmd", "shell_v2"]);
     }
 
     #[test]
@@ -437,12 +588,30 @@ mod tests {
         assert_eq!(parse_wm_size(output).as_deref(), Some("1080x2400"));
     }
 
+    #[test]
+    fn parses_wm_density() {
+        assert_eq!(parse_wm_density("Physical density: 420\n"), Some(420));
+        assert_eq!(
+            parse_wm_density("Physical density: 420\nOverride density: 480\n"),
+            Some(480)
+        );
+        assert_eq!(parse_wm_density("error"), None);
+    }
+
     #[test]
     fn parses_wm_size_override_preferred() {
         let output = "Physical size: 1080x2400\nOverride size: 720x1600\n";
         assert_eq!(parse_wm_size(output).as_deref(), Some("720x1600"));
     }
 
+    #[test]
+    fn parses_size_dimensions() {
+        assert_eq!(parse_size_dimensions("1080x2400"), Some((1080, 2400)));
+        assert_eq!(parse_size_dimensions(" 720X1600 "), Some((720, 1600)));
+        assert_eq!(parse_size_dimensions("0x1600"), None);
+        assert_eq!(parse_size_dimensions("abc"), None);
+    }
+
     #[test]
     fn parses_df_total_kb_standard() {
         let output = "Filesystem     1K-blocks    Used Available Use% Mounted on\n/dev/block/dm-0  11634528  12345  11622183   1% /data\n";
@@ -455,6 +624,19 @@ mod tests {
         assert_eq!(parse_df_total_kb(output).unwrap(), 2048);
     }
 
+    #[test]
+    fn parses_df_available_kb_from_posix_output() {
+        let output = "Filesystem     1024-blocks      Used Available Capacity Mounted on\n/dev/nvme0n1p2   488245288 300000000 163397548      65% /\n";
+        assert_eq!(parse_df_available_kb(output).unwrap(), 163397548);
+        assert!(parse_df_available_kb("Filesystem Size\n/dev/x 10\n").is_err());
+    }
+
+    #[test]
+    fn parses_du_kb_first_column() {
+        assert_eq!(parse_du_kb("20480\t/sdcard/record.mp4\n"), Some(20480));
+        assert_eq!(parse_du_kb("du: /sdcard/missing: No such file\n"), None);
+    }
+
     #[test]
     fn parses_battery_level() {
         let output = "AC powered: false\nlevel: 87\nstatus: 2\n";
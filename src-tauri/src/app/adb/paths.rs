@@ -42,6 +42,26 @@ pub fn device_parent_dir(device_path: &str) -> String {
     }
 }
 
+pub fn is_media_file_path(device_path: &str) -> bool {
+    let mime = mime_guess::from_path(device_path.trim());
+    match mime.first() {
+        Some(mime) => matches!(mime.type_().as_str(), "image" | "video" | "audio"),
+        None => false,
+    }
+}
+
+pub fn media_scan_file_uri(device_path: &str) -> String {
+    let mut uri = String::from("file://");
+    for byte in device_path.trim().bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'/' | b'-' | b'_' | b'.' | b'~') {
+            uri.push(byte as char);
+        } else {
+            uri.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    uri
+}
+
 pub fn sanitize_filename_component(value: &str) -> String {
     let trimmed = value.trim();
     if trimmed.is_empty() {
@@ -90,6 +110,27 @@ mod tests {
         assert_eq!(device_parent_dir(""), "/");
     }
 
+    #[test]
+    fn is_media_file_path_detects_media_extensions() {
+        assert!(is_media_file_path("/sdcard/DCIM/photo.JPG"));
+        assert!(is_media_file_path("/sdcard/Movies/clip.mp4"));
+        assert!(is_media_file_path("/sdcard/Music/track.mp3"));
+        assert!(!is_media_file_path("/sdcard/Download/notes.txt"));
+        assert!(!is_media_file_path("/sdcard/Download/archive"));
+    }
+
+    #[test]
+    fn media_scan_file_uri_escapes_special_chars() {
+        assert_eq!(
+            media_scan_file_uri("/sdcard/DCIM/photo.jpg"),
+            "file:///sdcard/DCIM/photo.jpg"
+        );
+        assert_eq!(
+            media_scan_file_uri("/sdcard/DCIM/my photo's.jpg"),
+            "file:///sdcard/DCIM/my%20photo%27s.jpg"
+        );
+    }
+
     #[test]
     fn sanitize_filename_component_replaces_invalid_chars() {
         assert_eq!(
@@ -0,0 +1,135 @@
+use std::path::Path;
+
+/// `settings put global http_proxy :0` is the value that reliably clears the proxy; deleting
+/// the key leaves some builds routing through the stale host until reboot.
+pub const CLEAR_PROXY_VALUE: &str = ":0";
+pub const DEVICE_CA_DIR: &str = "/sdcard/Download";
+
+const CA_EXTENSIONS: [&str; 4] = ["pem", "crt", "cer", "der"];
+const MAX_CA_BYTES: u64 = 64 * 1024;
+
+/// `host:port` where host is a hostname or IPv4 address.
+pub fn validate_proxy_address(value: &str) -> Result<String, String> {
+    let value = value.trim();
+    let (host, port) = value
+        .rsplit_once(':')
+        .ok_or_else(|| "Proxy must be host:port".to_string())?;
+    let host_ok = !host.is_empty()
+        && host.len() <= 253
+        && !host.starts_with(['-', '.'])
+        && host
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '-' | '.'));
+    if !host_ok {
+        return Err(format!("Invalid proxy host: {host}"));
+    }
+    match port.parse::<u16>() {
+        Ok(port) if port > 0 => Ok(format!("{host}:{port}")),
+        _ => Err(format!("Invalid proxy port: {port}")),
+    }
+}
+
+/// `settings get global http_proxy` prints `null` (never set) or `:0` (cleared) when no
+/// proxy is active.
+pub fn parse_proxy_setting(output: &str) -> Option<String> {
+    let value = output.trim();
+    (!value.is_empty() && value != "null" && value != CLEAR_PROXY_VALUE).then(|| value.to_string())
+}
+
+/// Checks a local CA certificate before it is pushed: a known extension, a small file, and
+/// PEM or DER content.
+pub fn validate_ca_certificate(path: &Path, size: u64, head: &[u8]) -> Result<(), String> {
+    let extension = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(str::to_ascii_lowercase)
+        .unwrap_or_default();
+    if !CA_EXTENSIONS.contains(&extension.as_str()) {
+        return Err(format!(
+            "CA certificate must be one of: {}",
+            CA_EXTENSIONS.join(", ")
+        ));
+    }
+    if size == 0 || size > MAX_CA_BYTES {
+        return Err("CA certificate must be between 1 byte and 64 KB".to_string());
+    }
+    let pem = String::from_utf8_lossy(head)
+        .trim_start()
+        .starts_with("-----BEGIN CERTIFICATE-----");
+    // DER certificates are an ASN.1 SEQUENCE.
+    let der = head.first() == Some(&0x30);
+    if !pem && !der {
+        return Err("File is not a PEM or DER certificate".to_string());
+    }
+    Ok(())
+}
+
+pub fn ca_device_path(file_name: &str) -> String {
+    let safe: String = file_name
+        .chars()
+        .map(|ch| {
+            if ch.is_ascii_alphanumeric() || matches!(ch, '-' | '_' | '.') {
+                ch
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("{DEVICE_CA_DIR}/{safe}")
+}
+
+/// Android 11 (API 30) and later only accept user CAs through Settings.
+pub fn ca_install_note(sdk: Option<i64>) -> String {
+    match sdk {
+        Some(sdk) if sdk >= 30 => format!(
+            "Android 11+ requires a manual install: Settings > Security > Encryption & credentials > Install a certificate > CA certificate, then pick the file from {DEVICE_CA_DIR}."
+        ),
+        _ => "Confirm the installer prompt on the device; a screen lock may be required.".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validates_proxy_addresses() {
+        assert_eq!(
+            validate_proxy_address(" 192.168.1.10:8888 ").unwrap(),
+            "192.168.1.10:8888"
+        );
+        assert_eq!(
+            validate_proxy_address("proxy.local:08080").unwrap(),
+            "proxy.local:8080"
+        );
+        assert!(validate_proxy_address("192.168.1.10").is_err());
+        assert!(validate_proxy_address(":8888").is_err());
+        assert!(validate_proxy_address("host:0").is_err());
+        assert!(validate_proxy_address("host:70000").is_err());
+        assert!(validate_proxy_address("host;reboot:8888").is_err());
+    }
+
+    #[test]
+    fn parses_proxy_setting() {
+        assert_eq!(
+            parse_proxy_setting("10.0.2.2:8888\n").as_deref(),
+            Some("10.0.2.2:8888")
+        );
+        assert_eq!(parse_proxy_setting("null\n"), None);
+        assert_eq!(parse_proxy_setting(":0"), None);
+        assert_eq!(parse_proxy_setting(""), None);
+    }
+
+    #[test]
+    fn validates_ca_certificates() {
+        let pem = b"-----BEGIN CERTIFICATE-----\nMIIB";
+        assert!(validate_ca_certificate(Path::new("mitm.pem"), 1200, pem).is_ok());
+        assert!(validate_ca_certificate(Path::new("charles.CER"), 900, &[0x30, 0x82]).is_ok());
+        assert!(validate_ca_certificate(Path::new("mitm.txt"), 1200, pem).is_err());
+        assert!(validate_ca_certificate(Path::new("mitm.pem"), 0, pem).is_err());
+        assert!(validate_ca_certificate(Path::new("mitm.pem"), 1200, b"hello").is_err());
+        assert_eq!(ca_device_path("my ca.pem"), "/sdcard/Download/my_ca.pem");
+        assert!(ca_install_note(Some(33)).contains("Settings"));
+        assert!(!ca_install_note(Some(28)).contains("Settings"));
+    }
+}
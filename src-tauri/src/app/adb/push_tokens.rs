@@ -0,0 +1,105 @@
+use regex::Regex;
+
+use crate::app::models::CapturedPushToken;
+
+/// FCM registration tokens: an instance id, a colon, then an `APA91b...` payload.
+pub const DEFAULT_PUSH_TOKEN_PATTERN: &str = r"\b([A-Za-z0-9_-]{11,}:APA91b[A-Za-z0-9_-]{100,})";
+const MAX_PATTERN_LEN: usize = 512;
+
+pub fn compile_push_token_pattern(pattern: Option<&str>) -> Result<Regex, String> {
+    let pattern = pattern
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .unwrap_or(DEFAULT_PUSH_TOKEN_PATTERN);
+    if pattern.len() > MAX_PATTERN_LEN {
+        return Err(format!(
+            "Token pattern is too long (max {MAX_PATTERN_LEN} characters)"
+        ));
+    }
+    Regex::new(pattern).map_err(|err| format!("Invalid token pattern: {err}"))
+}
+
+/// `logcat -v time` lines start with `MM-DD HH:MM:SS.mmm`.
+fn logcat_timestamp(line: &str) -> Option<String> {
+    let mut parts = line.split_whitespace();
+    let date = parts.next()?;
+    let time = parts.next()?;
+    let looks_like_time = date.len() == 5
+        && date.as_bytes().get(2) == Some(&b'-')
+        && time.len() >= 8
+        && time.as_bytes().get(2) == Some(&b':');
+    looks_like_time.then(|| format!("{date} {time}"))
+}
+
+/// Collect unique tokens in order of first appearance. When the pattern has a capture group,
+/// the first group is the token; otherwise the whole match is.
+pub fn extract_push_tokens(logcat: &str, pattern: &Regex) -> Vec<CapturedPushToken> {
+    let mut tokens: Vec<CapturedPushToken> = Vec::new();
+    for line in logcat.lines() {
+        for captures in pattern.captures_iter(line) {
+            let Some(token) = captures.get(1).or_else(|| captures.get(0)) else {
+                continue;
+            };
+            let token = token.as_str();
+            if token.is_empty() {
+                continue;
+            }
+            let seen_at = logcat_timestamp(line);
+            match tokens.iter_mut().find(|entry| entry.token == token) {
+                Some(entry) => {
+                    entry.occurrences += 1;
+                    if seen_at.is_some() {
+                        entry.last_seen = seen_at;
+                    }
+                }
+                None => tokens.push(CapturedPushToken {
+                    token: token.to_string(),
+                    first_seen: seen_at.clone(),
+                    last_seen: seen_at,
+                    occurrences: 1,
+                }),
+            }
+        }
+    }
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fake_token(prefix: &str) -> String {
+        format!("{prefix}:APA91b{}", "x".repeat(120))
+    }
+
+    #[test]
+    fn extracts_unique_fcm_tokens_with_timestamps() {
+        let first = fake_token("dGVzdC1pbnN0YW5jZQ");
+        let second = fake_token("b3RoZXItaW5zdGFuY2U");
+        let logcat = format!(
+            "05-01 10:00:00.100 D/FCM ( 1234): token={first}\n\
+             05-01 10:00:05.200 D/FCM ( 1234): refreshed {second}\n\
+             05-01 10:01:00.000 D/FCM ( 1234): token={first}\n\
+             05-01 10:01:01.000 D/Other ( 1234): no token here\n"
+        );
+        let pattern = compile_push_token_pattern(None).expect("pattern");
+        let tokens = extract_push_tokens(&logcat, &pattern);
+        assert_eq!(tokens.len(), 2);
+        assert_eq!(tokens[0].token, first);
+        assert_eq!(tokens[0].occurrences, 2);
+        assert_eq!(tokens[0].first_seen.as_deref(), Some("05-01 10:00:00.100"));
+        assert_eq!(tokens[0].last_seen.as_deref(), Some("05-01 10:01:00.000"));
+        assert_eq!(tokens[1].token, second);
+    }
+
+    #[test]
+    fn supports_custom_patterns() {
+        let pattern = compile_push_token_pattern(Some(r"pushToken=(\w+)")).expect("pattern");
+        let tokens = extract_push_tokens("I/App: pushToken=abc123 ok\n", &pattern);
+        assert_eq!(tokens[0].token, "abc123");
+        assert!(tokens[0].first_seen.is_none());
+
+        assert!(compile_push_token_pattern(Some("(unclosed")).is_err());
+        assert!(compile_push_token_pattern(Some(&"a".repeat(600))).is_err());
+    }
+}
@@ -0,0 +1,359 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use tauri::AppHandle;
+use tracing::{info, warn};
+
+use crate::app::adb::device_tracking::{TRANSITION_DISCONNECTED, TRANSITION_OFFLINE};
+use crate::app::adb::locator::{transport_for_serial, TRANSPORT_TCP};
+use crate::app::adb::runner::{run_command_with_timeout, CommandOutput};
+use crate::app::config::DeviceSettings;
+use crate::app::metrics::emit_tracked;
+use crate::app::models::{DeviceReconnectStatus, DeviceTransition};
+
+pub const DEVICE_RECONNECT_EVENT: &str = "device-reconnect";
+
+pub const RECONNECT_RETRYING: &str = "retrying";
+pub const RECONNECT_RECONNECTED: &str = "reconnected";
+pub const RECONNECT_GAVE_UP: &str = "gave_up";
+
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+const STOP_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReconnectPolicy {
+    /// How long after the device dropped off to keep retrying.
+    pub window: Duration,
+}
+
+pub fn reconnect_policy(settings: &DeviceSettings) -> Option<ReconnectPolicy> {
+    settings.auto_reconnect.then(|| ReconnectPolicy {
+        window: Duration::from_secs(settings.auto_reconnect_window_secs),
+    })
+}
+
+/// Delay after the given (1-based) failed attempt: 1s, 2s, 4s, ... capped at 30s.
+pub fn backoff_delay(attempt: u32) -> Duration {
+    let exponent = attempt.saturating_sub(1).min(16);
+    INITIAL_BACKOFF
+        .saturating_mul(1u32 << exponent)
+        .min(MAX_BACKOFF)
+}
+
+/// A ready `host:port` device that dropped off or went offline. mDNS serials are skipped
+/// because `adb connect` cannot resolve them.
+pub fn is_reconnect_candidate(transition: &DeviceTransition) -> bool {
+    transition.previous_state.as_deref() == Some("device")
+        && (transition.kind == TRANSITION_DISCONNECTED || transition.kind == TRANSITION_OFFLINE)
+        && transport_for_serial(&transition.serial) == TRANSPORT_TCP
+        && !transition.serial.contains("._tcp")
+}
+
+/// `adb connect` exits 0 even when it could not reach the device, so the output is checked.
+pub fn connect_failed(output: &CommandOutput) -> bool {
+    let combined = format!("{}{}", output.stdout, output.stderr).to_lowercase();
+    output.exit_code.unwrap_or_default() != 0
+        || combined.contains("failed")
+        || combined.contains("unable")
+        || combined.contains("cannot")
+}
+
+pub fn connect_failure_detail(output: &CommandOutput) -> String {
+    if output.stderr.trim().is_empty() {
+        output.stdout.trim().to_string()
+    } else {
+        output.stderr.trim().to_string()
+    }
+}
+
+struct ReconnectHandle {
+    stop_flag: Arc<AtomicBool>,
+    join: JoinHandle<()>,
+}
+
+/// Per-serial reconnect workers started from device tracker transitions.
+pub struct ReconnectSupervisor {
+    app: AppHandle,
+    adb_program: String,
+    policy: Option<ReconnectPolicy>,
+    trace_id: String,
+    workers: Mutex<HashMap<String, ReconnectHandle>>,
+}
+
+impl ReconnectSupervisor {
+    pub fn new(
+        app: AppHandle,
+        adb_program: String,
+        policy: Option<ReconnectPolicy>,
+        trace_id: String,
+    ) -> Self {
+        Self {
+            app,
+            adb_program,
+            policy,
+            trace_id,
+            workers: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn on_transition(&self, transition: &DeviceTransition) {
+        let Some(policy) = self.policy else {
+            return;
+        };
+        let mut workers = match self.workers.lock() {
+            Ok(guard) => guard,
+            Err(_) => {
+                warn!(trace_id = %self.trace_id, "reconnect registry poisoned");
+                return;
+            }
+        };
+        workers.retain(|_, handle| !handle.join.is_finished());
+
+        if transition.state.as_deref() == Some("device") {
+            // Back online (possibly reconnected by hand): let the worker wind down.
+            if let Some(handle) = workers.get(&transition.serial) {
+                handle.stop_flag.store(true, Ordering::Relaxed);
+            }
+            return;
+        }
+        if !is_reconnect_candidate(transition) {
+            return;
+        }
+        if let Some(handle) = workers.remove(&transition.serial) {
+            if !handle.stop_flag.load(Ordering::Relaxed) {
+                workers.insert(transition.serial.clone(), handle);
+                return;
+            }
+            let _ = handle.join.join();
+        }
+        let handle = start_reconnect(
+            self.app.clone(),
+            self.adb_program.clone(),
+            transition.serial.clone(),
+            transition.kind == TRANSITION_OFFLINE,
+            policy,
+            self.trace_id.clone(),
+        );
+        workers.insert(transition.serial.clone(), handle);
+    }
+
+    pub fn stop_all(&self) {
+        let workers = match self.workers.lock() {
+            Ok(mut guard) => std::mem::take(&mut *guard),
+            Err(_) => return,
+        };
+        for handle in workers.values() {
+            handle.stop_flag.store(true, Ordering::Relaxed);
+        }
+        for (_, handle) in workers {
+            let _ = handle.join.join();
+        }
+    }
+}
+
+fn emit_status(app: &AppHandle, status: DeviceReconnectStatus) {
+    let trace_id = status.trace_id.clone();
+    if let Err(err) = emit_tracked(app, DEVICE_RECONNECT_EVENT, status) {
+        warn!(trace_id = %trace_id, error = %err, "failed to emit device reconnect status");
+    }
+}
+
+/// Sleeps for `delay`, returning false as soon as a stop is requested.
+fn sleep_unless_stopped(stop_flag: &AtomicBool, delay: Duration) -> bool {
+    let deadline = Instant::now() + delay;
+    while Instant::now() < deadline {
+        if stop_flag.load(Ordering::Relaxed) {
+            return false;
+        }
+        thread::sleep(STOP_POLL_INTERVAL.min(deadline.saturating_duration_since(Instant::now())));
+    }
+    !stop_flag.load(Ordering::Relaxed)
+}
+
+fn start_reconnect(
+    app: AppHandle,
+    adb_program: String,
+    serial: String,
+    offline: bool,
+    policy: ReconnectPolicy,
+    trace_id: String,
+) -> ReconnectHandle {
+    let stop_flag = Arc::new(AtomicBool::new(false));
+    let stop_thread = Arc::clone(&stop_flag);
+    let join = thread::spawn(move || {
+        let started = Instant::now();
+        if offline {
+            // A stale offline transport makes `adb connect` answer "already connected".
+            let args = vec!["disconnect".to_string(), serial.clone()];
+            if let Err(err) =
+                run_command_with_timeout(&adb_program, &args, CONNECT_TIMEOUT, &trace_id)
+            {
+                warn!(trace_id = %trace_id, serial = %serial, error = %err, "failed to drop offline transport");
+            }
+        }
+        let args = vec!["connect".to_string(), serial.clone()];
+        let mut attempt = 0u32;
+        loop {
+            if stop_thread.load(Ordering::Relaxed) {
+                return;
+            }
+            attempt += 1;
+            let error =
+                match run_command_with_timeout(&adb_program, &args, CONNECT_TIMEOUT, &trace_id) {
+                    Ok(output) if !connect_failed(&output) => None,
+                    Ok(output) => Some(connect_failure_detail(&output)),
+                    Err(err) => Some(err.error),
+                };
+            let Some(error) = error else {
+                info!(trace_id = %trace_id, serial = %serial, attempt, "wireless device reconnected");
+                emit_status(
+                    &app,
+                    DeviceReconnectStatus {
+                        serial: serial.clone(),
+                        status: RECONNECT_RECONNECTED.to_string(),
+                        attempt,
+                        next_retry_ms: None,
+                        error: None,
+                        trace_id: trace_id.clone(),
+                    },
+                );
+                return;
+            };
+            if stop_thread.load(Ordering::Relaxed) {
+                return;
+            }
+            let delay = backoff_delay(attempt);
+            if started.elapsed() + delay > policy.window {
+                warn!(trace_id = %trace_id, serial = %serial, attempt, error = %error, "giving up wireless reconnect");
+                emit_status(
+                    &app,
+                    DeviceReconnectStatus {
+                        serial: serial.clone(),
+                        status: RECONNECT_GAVE_UP.to_string(),
+                        attempt,
+                        next_retry_ms: None,
+                        error: Some(error),
+                        trace_id: trace_id.clone(),
+                    },
+                );
+                return;
+            }
+            emit_status(
+                &app,
+                DeviceReconnectStatus {
+                    serial: serial.clone(),
+                    status: RECONNECT_RETRYING.to_string(),
+                    attempt,
+                    next_retry_ms: Some(delay.as_millis() as u64),
+                    error: Some(error),
+                    trace_id: trace_id.clone(),
+                },
+            );
+            if !sleep_unless_stopped(&stop_thread, delay) {
+                return;
+            }
+        }
+    });
+    ReconnectHandle { stop_flag, join }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transition(serial: &str, kind: &str, previous: Option<&str>) -> DeviceTransition {
+        DeviceTransition {
+            serial: serial.to_string(),
+            kind: kind.to_string(),
+            previous_state: previous.map(str::to_string),
+            state: None,
+            model: None,
+        }
+    }
+
+    fn output(stdout: &str, exit_code: Option<i32>) -> CommandOutput {
+        CommandOutput {
+            stdout: stdout.to_string(),
+            stderr: String::new(),
+            exit_code,
+        }
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_cap() {
+        assert_eq!(backoff_delay(1), Duration::from_secs(1));
+        assert_eq!(backoff_delay(2), Duration::from_secs(2));
+        assert_eq!(backoff_delay(4), Duration::from_secs(8));
+        assert_eq!(backoff_delay(6), MAX_BACKOFF);
+        assert_eq!(backoff_delay(u32::MAX), MAX_BACKOFF);
+    }
+
+    #[test]
+    fn only_dropped_wireless_devices_are_candidates() {
+        assert!(is_reconnect_candidate(&transition(
+            "192.168.1.5:5555",
+            TRANSITION_DISCONNECTED,
+            Some("device")
+        )));
+        assert!(is_reconnect_candidate(&transition(
+            "192.168.1.5:5555",
+            TRANSITION_OFFLINE,
+            Some("device")
+        )));
+        assert!(!is_reconnect_candidate(&transition(
+            "R58M123",
+            TRANSITION_DISCONNECTED,
+            Some("device")
+        )));
+        assert!(!is_reconnect_candidate(&transition(
+            "adb-R58M123-abc._adb-tls-connect._tcp",
+            TRANSITION_DISCONNECTED,
+            Some("device")
+        )));
+        assert!(!is_reconnect_candidate(&transition(
+            "192.168.1.5:5555",
+            TRANSITION_DISCONNECTED,
+            Some("unauthorized")
+        )));
+    }
+
+    #[test]
+    fn detects_connect_failures() {
+        assert!(!connect_failed(&output(
+            "connected to 10.0.0.2:5555\n",
+            Some(0)
+        )));
+        assert!(!connect_failed(&output(
+            "already connected to 10.0.0.2:5555\n",
+            Some(0)
+        )));
+        assert!(connect_failed(&output(
+            "failed to connect to 10.0.0.2:5555\n",
+            Some(0)
+        )));
+        assert!(connect_failed(&output(
+            "cannot connect to 10.0.0.2:5555: No route to host\n",
+            Some(0)
+        )));
+        assert!(connect_failed(&output("", Some(1))));
+    }
+
+    #[test]
+    fn policy_follows_settings() {
+        let mut settings = DeviceSettings::default();
+        assert_eq!(reconnect_policy(&settings), None);
+        settings.auto_reconnect = true;
+        settings.auto_reconnect_window_secs = 60;
+        assert_eq!(
+            reconnect_policy(&settings),
+            Some(ReconnectPolicy {
+                window: Duration::from_secs(60)
+            })
+        );
+    }
+}
@@ -1,8 +1,18 @@
 use std::io::Read;
-use std::process::{Command, Stdio};
+use std::process::Stdio;
 use std::time::{Duration, Instant};
 
+use chrono::Utc;
+use tracing::warn;
+
+use crate::app::adb::locator::{adb_command, route_adb_program, route_adb_program_for_serial};
+use crate::app::adb::socket::{
+    is_adb_program, socket_client_enabled, socket_shell_request, AdbSocketClient, SocketError,
+    SyncStat,
+};
+use crate::app::command_log::record_command;
 use crate::app::error::AppError;
+use crate::app::i18n::Message;
 
 #[derive(Debug, Clone)]
 pub struct CommandOutput {
@@ -11,6 +21,14 @@ pub struct CommandOutput {
     pub exit_code: Option<i32>,
 }
 
+/// `CommandOutput` with stdout kept as bytes, for binary payloads such as `adb exec-out`.
+#[derive(Debug, Clone)]
+pub struct BinaryCommandOutput {
+    pub stdout: Vec<u8>,
+    pub stderr: String,
+    pub exit_code: Option<i32>,
+}
+
 pub fn run_command(
     program: &str,
     args: &[String],
@@ -25,12 +43,132 @@ pub fn run_command_with_timeout(
     timeout: Duration,
     trace_id: &str,
 ) -> Result<CommandOutput, AppError> {
-    let mut child = Command::new(program)
+    let routed = route_adb_program(program, args);
+    let started_at = Utc::now();
+    let started = Instant::now();
+    let result = match socket_shell_request(program, &routed, args) {
+        Some((serial, command)) => {
+            execute_via_server(&routed, args, serial, &command, timeout, trace_id)
+        }
+        None => execute_with_timeout(&routed, args, timeout, trace_id),
+    };
+    record_command(&routed, args, trace_id, started_at, started, &result);
+    result
+}
+
+/// `adb shell` through the adb server socket, skipping a process spawn per call. Falls back
+/// to the adb binary only while nothing has reached the device, so commands never run twice,
+/// and for devices that cannot report an exit code over the socket.
+fn execute_via_server(
+    program: &str,
+    args: &[String],
+    serial: &str,
+    command: &str,
+    timeout: Duration,
+    trace_id: &str,
+) -> Result<CommandOutput, AppError> {
+    match AdbSocketClient::from_environment().shell(serial, command, timeout) {
+        Ok(output) => Ok(output),
+        Err(SocketError::TimedOut) => {
+            Err(AppError::system("Command timed out".to_string(), trace_id))
+        }
+        Err(SocketError::Io(message)) => Err(AppError::dependency(message, trace_id)),
+        Err(SocketError::Unsupported(_)) => execute_with_timeout(program, args, timeout, trace_id),
+        Err(err) => {
+            warn!(trace_id = %trace_id, serial = %serial, error = %err, "adb socket client unavailable, spawning adb");
+            execute_with_timeout(program, args, timeout, trace_id)
+        }
+    }
+}
+
+/// A client for the adb server behind `program`, or `None` when the socket client is off or
+/// `serial` is routed to a different adb binary.
+pub fn socket_client_for_serial(program: &str, serial: &str) -> Option<AdbSocketClient> {
+    if !socket_client_enabled()
+        || !is_adb_program(program)
+        || route_adb_program_for_serial(program, serial) != program
+    {
+        return None;
+    }
+    Some(AdbSocketClient::from_environment())
+}
+
+/// `stat` of a device path over the adb server's sync service, or `None` when the socket
+/// client cannot serve it and callers should use `adb shell stat`.
+pub fn stat_device_path(
+    program: &str,
+    serial: &str,
+    path: &str,
+    timeout: Duration,
+    trace_id: &str,
+) -> Option<SyncStat> {
+    let client = socket_client_for_serial(program, serial)?;
+    match client.stat(serial, path, timeout) {
+        Ok(stat) => Some(stat),
+        Err(SocketError::Unsupported(_)) => None,
+        Err(err) => {
+            warn!(trace_id = %trace_id, serial = %serial, error = %err, "sync stat failed, using adb shell");
+            None
+        }
+    }
+}
+
+/// Runs `args` with stdout captured as raw bytes; always spawns the adb binary.
+pub fn run_binary_command_with_timeout(
+    program: &str,
+    args: &[String],
+    timeout: Duration,
+    trace_id: &str,
+) -> Result<BinaryCommandOutput, AppError> {
+    let routed = route_adb_program(program, args);
+    let started_at = Utc::now();
+    let started = Instant::now();
+    let result = execute_binary_with_timeout(&routed, args, timeout, trace_id);
+    let logged = result
+        .as_ref()
+        .map_err(Clone::clone)
+        .map(|output| CommandOutput {
+            stdout: format!("<{} bytes>", output.stdout.len()),
+            stderr: output.stderr.clone(),
+            exit_code: output.exit_code,
+        });
+    record_command(&routed, args, trace_id, started_at, started, &logged);
+    result
+}
+
+fn execute_with_timeout(
+    program: &str,
+    args: &[String],
+    timeout: Duration,
+    trace_id: &str,
+) -> Result<CommandOutput, AppError> {
+    let output = execute_binary_with_timeout(program, args, timeout, trace_id)?;
+    Ok(CommandOutput {
+        stdout: String::from_utf8_lossy(&output.stdout).to_string(),
+        stderr: output.stderr,
+        exit_code: output.exit_code,
+    })
+}
+
+fn execute_binary_with_timeout(
+    program: &str,
+    args: &[String],
+    timeout: Duration,
+    trace_id: &str,
+) -> Result<BinaryCommandOutput, AppError> {
+    let mut child = adb_command(program)
         .args(args)
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
         .spawn()
-        .map_err(|err| AppError::system(format!("Failed to spawn command: {err}"), trace_id))?;
+        .map_err(|err| {
+            AppError::system(
+                Message::keyed("operation_failed")
+                    .with("action", "spawn command")
+                    .with("detail", &err),
+                trace_id,
+            )
+        })?;
 
     // Drain stdout/stderr in parallel; otherwise, a chatty child process can block once the pipe
     // buffer fills, and we will incorrectly hit the timeout.
@@ -89,7 +227,9 @@ pub fn run_command_with_timeout(
                 let _ = stdout_handle.join();
                 let _ = stderr_handle.join();
                 return Err(AppError::system(
-                    format!("Failed to poll command: {err}"),
+                    Message::keyed("operation_failed")
+                        .with("action", "poll command")
+                        .with("detail", &err),
                     trace_id,
                 ));
             }
@@ -99,8 +239,8 @@ pub fn run_command_with_timeout(
     let stdout_bytes = stdout_handle.join().unwrap_or_default();
     let stderr_bytes = stderr_handle.join().unwrap_or_default();
 
-    Ok(CommandOutput {
-        stdout: String::from_utf8_lossy(&stdout_bytes).to_string(),
+    Ok(BinaryCommandOutput {
+        stdout: stdout_bytes,
         stderr: String::from_utf8_lossy(&stderr_bytes).to_string(),
         exit_code,
     })
@@ -2,6 +2,40 @@ use std::path::Path;
 use std::process::Command;
 
 use crate::app::config::ScrcpySettings;
+use crate::app::models::ScrcpyOptionIssue;
+
+/// `(major, minor)`
+pub type ScrcpyVersion = (i32, i32);
+
+/// Flags whose support depends on the scrcpy version: (flag, first version accepting it,
+/// version that removed it).
+const FLAG_SUPPORT: &[(&str, ScrcpyVersion, Option<ScrcpyVersion>)] = &[
+    ("--bit-rate", (1, 0), Some((2, 0))),
+    ("--encoder", (1, 0), Some((2, 0))),
+    ("--codec-options", (1, 0), Some((2, 0))),
+    ("--otg", (1, 24), None),
+    ("--no-audio", (2, 0), None),
+    ("--audio-codec", (2, 0), None),
+    ("--audio-bit-rate", (2, 0), None),
+    ("--audio-buffer", (2, 0), None),
+    ("--audio-encoder", (2, 0), None),
+    ("--video-codec", (2, 0), None),
+    ("--video-bit-rate", (2, 0), None),
+    ("--video-encoder", (2, 0), None),
+    ("--video-codec-options", (2, 0), None),
+    ("--list-encoders", (2, 0), None),
+    ("--no-video", (2, 1), None),
+    ("--audio-source", (2, 1), None),
+    ("--video-buffer", (2, 1), None),
+    ("--video-source", (2, 2), None),
+    ("--camera-id", (2, 2), None),
+    ("--camera-size", (2, 2), None),
+    ("--camera-facing", (2, 2), None),
+    ("--list-cameras", (2, 2), None),
+    ("--keyboard", (2, 4), None),
+    ("--mouse", (2, 4), None),
+    ("--new-display", (3, 0), None),
+];
 
 pub struct ScrcpyAvailability {
     pub available: bool,
@@ -160,6 +194,111 @@ fn parse_scrcpy_major(output: &str) -> i32 {
     2
 }
 
+/// `(major, minor)` from `scrcpy --version` output such as `scrcpy 2.4 <https://...>`.
+pub fn parse_scrcpy_version(output: &str) -> Option<ScrcpyVersion> {
+    output.split_whitespace().find_map(|token| {
+        let token = token.trim_start_matches('v');
+        let mut parts = token.split('.');
+        let major = parts.next()?.parse::<i32>().ok()?;
+        let minor = parts
+            .next()
+            .and_then(|part| part.parse::<i32>().ok())
+            .unwrap_or(0);
+        Some((major, minor))
+    })
+}
+
+fn format_version((major, minor): ScrcpyVersion) -> String {
+    format!("{major}.{minor}")
+}
+
+fn valid_bitrate(value: &str) -> bool {
+    let digits = value.trim_end_matches(['K', 'k', 'M', 'm']);
+    value.len() - digits.len() <= 1
+        && !digits.is_empty()
+        && digits.parse::<f64>().is_ok_and(|rate| rate > 0.0)
+}
+
+/// Version-dependent flags in `args` that the given scrcpy version rejects.
+pub fn unsupported_flag_issues(args: &[String], version: ScrcpyVersion) -> Vec<ScrcpyOptionIssue> {
+    let mut issues = Vec::new();
+    for arg in args.iter().filter(|arg| arg.starts_with("--")) {
+        let flag = arg.split('=').next().unwrap_or(arg);
+        let Some((_, since, removed)) = FLAG_SUPPORT.iter().find(|(name, _, _)| *name == flag)
+        else {
+            continue;
+        };
+        if version < *since {
+            issues.push(ScrcpyOptionIssue {
+                flag: flag.to_string(),
+                message: format!(
+                    "{flag} needs scrcpy {} or newer (detected {})",
+                    format_version(*since),
+                    format_version(version)
+                ),
+            });
+        } else if let Some(removed) = removed.filter(|removed| version >= *removed) {
+            issues.push(ScrcpyOptionIssue {
+                flag: flag.to_string(),
+                message: format!(
+                    "{flag} was removed in scrcpy {} (detected {})",
+                    format_version(removed),
+                    format_version(version)
+                ),
+            });
+        }
+    }
+    issues
+}
+
+/// Problems with a dry-built scrcpy command line for the given scrcpy version.
+pub fn scrcpy_option_issues(
+    settings: &ScrcpySettings,
+    args: &[String],
+    version: ScrcpyVersion,
+) -> Vec<ScrcpyOptionIssue> {
+    let mut issues = Vec::new();
+    let bitrate = settings.bitrate.trim();
+    if !bitrate.is_empty() && !valid_bitrate(bitrate) {
+        issues.push(ScrcpyOptionIssue {
+            flag: "-b".to_string(),
+            message: format!(
+                "Invalid bitrate `{bitrate}`; use a number with an optional K or M suffix"
+            ),
+        });
+    }
+    if settings.max_size < 0 {
+        issues.push(ScrcpyOptionIssue {
+            flag: "--max-size".to_string(),
+            message: "Max size must be 0 (unlimited) or a positive number".to_string(),
+        });
+    }
+    issues.extend(unsupported_flag_issues(args, version));
+    issues
+}
+
+/// Settings for launching on `serial`: an explicit preset wins, then the device's assigned
+/// preset, then the base options.
+pub fn resolve_scrcpy_settings(
+    settings: &ScrcpySettings,
+    serial: &str,
+    preset: Option<&str>,
+) -> Result<ScrcpySettings, String> {
+    let name = match preset.map(str::trim).filter(|name| !name.is_empty()) {
+        Some(name) => name,
+        None => match settings.device_presets.get(serial) {
+            Some(name) => name.as_str(),
+            None => return Ok(settings.clone()),
+        },
+    };
+    settings
+        .presets
+        .iter()
+        .find(|preset| preset.name == name)
+        .map(|preset| preset.options.to_settings())
+        .ok_or_else(|| format!("Unknown scrcpy preset: {name}"))
+}
+
 fn expand_home(path: &str) -> String {
     if let Some(rest) = path.strip_prefix("~/") {
         if let Ok(home) = std::env::var("HOME") {
@@ -182,6 +321,7 @@ mod tests {
             bitrate: String::new(),
             max_size: 0,
             extra_args: String::new(),
+            ..Default::default()
         }
     }
 
@@ -242,4 +382,56 @@ mod tests {
         assert!(has_flag_with_value(&args, "-b", "16M"));
         assert!(!has_flag(&args, "--bit-rate"));
     }
+
+    #[test]
+    fn parses_scrcpy_version() {
+        assert_eq!(
+            parse_scrcpy_version("scrcpy 2.4 <https://github.com/Genymobile/scrcpy>"),
+            Some((2, 4))
+        );
+        assert_eq!(parse_scrcpy_version("scrcpy v1.25"), Some((1, 25)));
+        assert_eq!(parse_scrcpy_version("scrcpy"), None);
+    }
+
+    #[test]
+    fn reports_flags_unsupported_by_version() {
+        let mut settings = base_settings();
+        settings.extra_args = "--video-buffer=50 --bit-rate 2M --new-display".to_string();
+        let args = build_scrcpy_command("device", &settings, 2);
+        let issues = scrcpy_option_issues(&settings, &args, (2, 4));
+        let flags: Vec<&str> = issues.iter().map(|issue| issue.flag.as_str()).collect();
+        assert_eq!(flags, vec!["--bit-rate", "--new-display"]);
+        assert!(issues[1].message.contains("needs scrcpy 3.0"));
+
+        let issues = scrcpy_option_issues(&settings, &args, (1, 25));
+        let flags: Vec<&str> = issues.iter().map(|issue| issue.flag.as_str()).collect();
+        assert_eq!(flags, vec!["--video-buffer", "--new-display"]);
+
+        settings.extra_args = String::new();
+        settings.bitrate = "fast".to_string();
+        let args = build_scrcpy_command("device", &settings, 3);
+        let issues = scrcpy_option_issues(&settings, &args, (3, 0));
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].flag, "-b");
+        assert!(valid_bitrate("8M"));
+        assert!(valid_bitrate("1.5m"));
+        assert!(!valid_bitrate("8MM"));
+        assert!(!valid_bitrate("0"));
+    }
+
+    #[test]
+    fn resolves_presets_per_device() {
+        let mut settings = ScrcpySettings::default();
+        settings
+            .device_presets
+            .insert("A1".to_string(), "otg".to_string());
+
+        let base = resolve_scrcpy_settings(&settings, "B2", None).unwrap();
+        assert_eq!(base.bitrate, "8M");
+        let device = resolve_scrcpy_settings(&settings, "A1", None).unwrap();
+        assert_eq!(device.extra_args, "--otg");
+        let explicit = resolve_scrcpy_settings(&settings, "A1", Some("low-latency")).unwrap();
+        assert_eq!(explicit.max_size, 1024);
+        assert!(resolve_scrcpy_settings(&settings, "A1", Some("missing")).is_err());
+    }
 }
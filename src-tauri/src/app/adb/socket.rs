@@ -0,0 +1,713 @@
+use std::collections::HashMap;
+use std::io::{ErrorKind, Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use crate::app::adb::locator::{active_adb_server, ADB_SERVER_SOCKET_ENV};
+use crate::app::adb::parse::parse_adb_features;
+use crate::app::adb::runner::CommandOutput;
+
+pub const DEFAULT_ADB_SERVER_PORT: u16 = 5037;
+const ADB_SERVER_PORT_ENV: &str = "ANDROID_ADB_SERVER_PORT";
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(2);
+/// Requests carry a 4 hex digit length prefix.
+const MAX_REQUEST_BYTES: usize = 0xffff;
+
+/// Shell protocol v2 packet ids: `[id][u32 le length][payload]`.
+pub const SHELL_V2_STDIN: u8 = 0;
+pub const SHELL_V2_STDOUT: u8 = 1;
+pub const SHELL_V2_STDERR: u8 = 2;
+pub const SHELL_V2_EXIT: u8 = 3;
+pub const SHELL_V2_CLOSE_STDIN: u8 = 4;
+const STA2_RESPONSE_BYTES: usize = 72;
+
+static SOCKET_CLIENT_ENABLED: AtomicBool = AtomicBool::new(true);
+
+pub fn set_socket_client_enabled(enabled: bool) {
+    SOCKET_CLIENT_ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+pub fn socket_client_enabled() -> bool {
+    SOCKET_CLIENT_ENABLED.load(Ordering::Relaxed)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SocketError {
+    /// Nothing reached the device yet, so running the adb binary instead is safe.
+    Unavailable(String),
+    /// The device or feature does not support the request.
+    Unsupported(String),
+    /// The connection broke after the request was sent; the command may have run.
+    Io(String),
+    TimedOut,
+    Cancelled,
+}
+
+impl std::fmt::Display for SocketError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SocketError::Unavailable(message)
+            | SocketError::Unsupported(message)
+            | SocketError::Io(message) => f.write_str(message),
+            SocketError::TimedOut => f.write_str("Command timed out"),
+            SocketError::Cancelled => f.write_str("Cancelled"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct DeviceCapabilities {
+    shell_v2: bool,
+    stat_v2: bool,
+}
+
+/// Features rarely change while a device stays attached, so ask the server once per serial.
+fn capabilities_cache() -> &'static Mutex<HashMap<String, DeviceCapabilities>> {
+    static CACHE: OnceLock<Mutex<HashMap<String, DeviceCapabilities>>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Drop the cached features of `serial` on every server; called whenever the device changes
+/// state, since it may come back with a different build (flashed, rebooted into recovery).
+pub fn forget_device_capabilities(serial: &str) {
+    let suffix = format!("/{serial}");
+    if let Ok(mut cache) = capabilities_cache().lock() {
+        cache.retain(|key, _| !key.ends_with(&suffix));
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SyncStat {
+    pub mode: u32,
+    pub size: u64,
+    pub mtime: i64,
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct ShellV2Output {
+    pub stdout: Vec<u8>,
+    pub stderr: Vec<u8>,
+    pub exit_code: Option<i32>,
+}
+
+/// `adb -s <serial> shell <command...>` as the socket client can serve it: the default adb
+/// binary and a non-interactive command without `adb shell` options such as `-t`.
+pub fn socket_shell_request<'a>(
+    program: &str,
+    routed_program: &str,
+    args: &'a [String],
+) -> Option<(&'a str, String)> {
+    if !socket_client_enabled() || program != routed_program || !is_adb_program(program) {
+        return None;
+    }
+    let [flag, serial, shell, command @ ..] = args else {
+        return None;
+    };
+    if flag != "-s" || shell != "shell" || command.is_empty() || command[0].starts_with('-') {
+        return None;
+    }
+    // The adb client joins shell arguments with spaces without quoting them.
+    Some((serial.as_str(), command.join(" ")))
+}
+
+/// Checks the file name on either separator so Windows paths match on any host.
+pub fn is_adb_program(program: &str) -> bool {
+    let name = program.rsplit(['/', '\\']).next().unwrap_or(program);
+    name.eq_ignore_ascii_case("adb") || name.eq_ignore_ascii_case("adb.exe")
+}
+
+pub fn encode_request(payload: &str) -> Result<Vec<u8>, String> {
+    if payload.len() > MAX_REQUEST_BYTES {
+        return Err(format!("adb request is too long ({} bytes)", payload.len()));
+    }
+    Ok(format!("{:04x}{payload}", payload.len()).into_bytes())
+}
+
+/// `ADB_SERVER_SOCKET` values such as `tcp:5037`, `tcp:host:5037` or `tcp:[::1]:5037`.
+pub fn parse_server_socket_spec(spec: &str) -> Option<(String, u16)> {
+    let rest = spec.trim().strip_prefix("tcp:")?;
+    let (host, port) = match rest.rsplit_once(':') {
+        Some((host, port)) => (host.trim_matches(['[', ']']), port),
+        None => ("127.0.0.1", rest),
+    };
+    let port = port.parse::<u16>().ok()?;
+    if host.is_empty() {
+        return None;
+    }
+    Some((host.to_string(), port))
+}
+
+pub fn decode_shell_v2(data: &[u8]) -> Result<ShellV2Output, String> {
+    let mut output = ShellV2Output::default();
+    let mut rest = data;
+    while !rest.is_empty() {
+        let Some(header) = rest.get(..5) else {
+            return Err("Truncated shell packet header".to_string());
+        };
+        let length = u32::from_le_bytes([header[1], header[2], header[3], header[4]]) as usize;
+        let Some(body) = rest.get(5..5 + length) else {
+            return Err("Truncated shell packet".to_string());
+        };
+        match header[0] {
+            SHELL_V2_STDOUT => output.stdout.extend_from_slice(body),
+            SHELL_V2_STDERR => output.stderr.extend_from_slice(body),
+            SHELL_V2_EXIT => output.exit_code = body.first().map(|code| i32::from(*code)),
+            _ => {}
+        }
+        rest = &rest[5 + length..];
+    }
+    Ok(output)
+}
+
+pub fn decode_sta2(response: &[u8]) -> Result<SyncStat, String> {
+    if response.len() < STA2_RESPONSE_BYTES || &response[..4] != b"STA2" {
+        return Err("Unexpected sync stat response".to_string());
+    }
+    let u32_at = |offset: usize| {
+        u32::from_le_bytes([
+            response[offset],
+            response[offset + 1],
+            response[offset + 2],
+            response[offset + 3],
+        ])
+    };
+    let u64_at = |offset: usize| {
+        let mut bytes = [0u8; 8];
+        bytes.copy_from_slice(&response[offset..offset + 8]);
+        u64::from_le_bytes(bytes)
+    };
+    let error = u32_at(4);
+    if error != 0 {
+        return Err(std::io::Error::from_raw_os_error(error as i32).to_string());
+    }
+    Ok(SyncStat {
+        mode: u32_at(24),
+        size: u64_at(40),
+        mtime: u64_at(56) as i64,
+    })
+}
+
+/// Talks to the adb server directly ("smart sockets") instead of spawning the adb binary,
+/// which saves a process per call in monitors that sample every second.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AdbSocketClient {
+    host: String,
+    port: u16,
+}
+
+impl AdbSocketClient {
+    pub fn new(host: impl Into<String>, port: u16) -> Self {
+        Self {
+            host: host.into(),
+            port,
+        }
+    }
+
+    /// The server the adb binary would use: the selected remote server, then the
+    /// `ADB_SERVER_SOCKET`/`ANDROID_ADB_SERVER_PORT` overrides, then the local default.
+    pub fn from_environment() -> Self {
+        if let Some(target) = active_adb_server() {
+            return Self::new(target.host, target.port);
+        }
+        if let Some((host, port)) = std::env::var(ADB_SERVER_SOCKET_ENV)
+            .ok()
+            .and_then(|spec| parse_server_socket_spec(&spec))
+        {
+            return Self::new(host, port);
+        }
+        let port = std::env::var(ADB_SERVER_PORT_ENV)
+            .ok()
+            .and_then(|value| value.trim().parse::<u16>().ok())
+            .unwrap_or(DEFAULT_ADB_SERVER_PORT);
+        Self::new("127.0.0.1", port)
+    }
+
+    fn connect(&self, deadline: Instant) -> Result<TcpStream, SocketError> {
+        let unavailable = |err: String| {
+            SocketError::Unavailable(format!(
+                "adb server {}:{} unreachable: {err}",
+                self.host, self.port
+            ))
+        };
+        let addrs = (self.host.as_str(), self.port)
+            .to_socket_addrs()
+            .map_err(|err| unavailable(err.to_string()))?;
+        let timeout = remaining(deadline)?.min(CONNECT_TIMEOUT);
+        let mut last_error = "no address".to_string();
+        for addr in addrs {
+            match TcpStream::connect_timeout(&addr, timeout) {
+                Ok(stream) => {
+                    let _ = stream.set_nodelay(true);
+                    return Ok(stream);
+                }
+                Err(err) => last_error = err.to_string(),
+            }
+        }
+        Err(unavailable(last_error))
+    }
+
+    /// Sends one request and waits for `OKAY`; a `FAIL` (unknown device, unauthorized, ...)
+    /// counts as unavailable so the adb binary can report it in its usual words.
+    fn request(
+        &self,
+        stream: &mut TcpStream,
+        payload: &str,
+        deadline: Instant,
+    ) -> Result<(), SocketError> {
+        let request = encode_request(payload).map_err(SocketError::Unavailable)?;
+        set_deadline(stream, deadline)?;
+        stream.write_all(&request).map_err(|err| match err.kind() {
+            ErrorKind::WouldBlock | ErrorKind::TimedOut => SocketError::TimedOut,
+            _ => SocketError::Unavailable(format!("adb server write failed: {err}")),
+        })?;
+        let mut status = [0u8; 4];
+        read_exact_by(stream, &mut status, deadline)
+            .map_err(|err| SocketError::Unavailable(err.to_string()))?;
+        match &status {
+            b"OKAY" => Ok(()),
+            b"FAIL" => {
+                let message = read_length_prefixed(stream, deadline)
+                    .map_err(|err| SocketError::Unavailable(err.to_string()))?;
+                Err(SocketError::Unavailable(format!("adb server: {message}")))
+            }
+            other => Err(SocketError::Unavailable(format!(
+                "Unexpected adb server status {:?}",
+                String::from_utf8_lossy(other)
+            ))),
+        }
+    }
+
+    fn capabilities(
+        &self,
+        serial: &str,
+        deadline: Instant,
+    ) -> Result<DeviceCapabilities, SocketError> {
+        let key = format!("{}:{}/{serial}", self.host, self.port);
+        if let Some(cached) = capabilities_cache()
+            .lock()
+            .ok()
+            .and_then(|cache| cache.get(&key).copied())
+        {
+            return Ok(cached);
+        }
+        let features = self.features(serial, deadline)?;
+        let capabilities = DeviceCapabilities {
+            shell_v2: features.iter().any(|feature| feature == "shell_v2"),
+            stat_v2: features.iter().any(|feature| feature == "stat_v2"),
+        };
+        if let Ok(mut cache) = capabilities_cache().lock() {
+            cache.insert(key, capabilities);
+        }
+        Ok(capabilities)
+    }
+
+    pub fn features(&self, serial: &str, deadline: Instant) -> Result<Vec<String>, SocketError> {
+        let mut stream = self.connect(deadline)?;
+        self.request(
+            &mut stream,
+            &format!("host-serial:{serial}:features"),
+            deadline,
+        )?;
+        let features = read_length_prefixed(&mut stream, deadline)
+            .map_err(|err| SocketError::Unavailable(err.to_string()))?;
+        Ok(parse_adb_features(&features))
+    }
+
+    fn open_transport(&self, serial: &str, deadline: Instant) -> Result<TcpStream, SocketError> {
+        let mut stream = self.connect(deadline)?;
+        self.request(&mut stream, &format!("host:transport:{serial}"), deadline)?;
+        Ok(stream)
+    }
+
+    /// A stream to a device service such as `sync:` or `shell,v2,raw:<command>`, ready for
+    /// the service's own protocol.
+    pub fn open_service(
+        &self,
+        serial: &str,
+        service: &str,
+        deadline: Instant,
+    ) -> Result<TcpStream, SocketError> {
+        let mut stream = self.open_transport(serial, deadline)?;
+        self.request(&mut stream, service, deadline)?;
+        Ok(stream)
+    }
+
+    pub fn supports_shell_v2(&self, serial: &str, deadline: Instant) -> Result<bool, SocketError> {
+        Ok(self.capabilities(serial, deadline)?.shell_v2)
+    }
+
+    /// Runs `command` like `adb -s <serial> shell <command>`. Devices without `shell_v2`
+    /// (Android 6 and older) cannot report an exit code over the socket, so they are
+    /// `Unsupported` and left to the adb binary.
+    pub fn shell(
+        &self,
+        serial: &str,
+        command: &str,
+        timeout: Duration,
+    ) -> Result<CommandOutput, SocketError> {
+        let deadline = Instant::now() + timeout;
+        if !self.capabilities(serial, deadline)?.shell_v2 {
+            return Err(SocketError::Unsupported(format!(
+                "{serial} does not support shell_v2"
+            )));
+        }
+        let mut stream = self.open_transport(serial, deadline)?;
+        self.request(&mut stream, &format!("shell,v2,raw:{command}"), deadline)?;
+
+        let mut raw = Vec::new();
+        read_to_end_by(&mut stream, &mut raw, deadline)?;
+        let output = decode_shell_v2(&raw).map_err(SocketError::Io)?;
+        Ok(CommandOutput {
+            stdout: String::from_utf8_lossy(&output.stdout).to_string(),
+            stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+            exit_code: output.exit_code,
+        })
+    }
+
+    /// A long-running `shell:<command>` (such as `logcat`) whose merged output is read straight
+    /// off the returned stream. Only opening the service is bounded by `timeout`; shutting the
+    /// stream down ends the command on the device.
+    pub fn shell_stream(
+        &self,
+        serial: &str,
+        command: &str,
+        timeout: Duration,
+    ) -> Result<TcpStream, SocketError> {
+        let deadline = Instant::now() + timeout;
+        let stream = self.open_service(serial, &format!("shell:{command}"), deadline)?;
+        stream
+            .set_read_timeout(None)
+            .and_then(|_| stream.set_write_timeout(None))
+            .map_err(|err| SocketError::Io(format!("adb socket setup failed: {err}")))?;
+        Ok(stream)
+    }
+
+    /// Sends `request` to `tcp:<port>` on the device, as `adb forward` would reach it but
+    /// without a host port, and reads the reply until the device closes the connection.
+    pub fn device_tcp_exchange(
+        &self,
+        serial: &str,
+        port: u16,
+        request: &[u8],
+        timeout: Duration,
+    ) -> Result<Vec<u8>, SocketError> {
+        let deadline = Instant::now() + timeout;
+        let mut stream = self.open_service(serial, &format!("tcp:{port}"), deadline)?;
+        set_deadline(&stream, deadline)?;
+        stream
+            .write_all(request)
+            .map_err(|err| SocketError::Io(format!("device tcp:{port} write failed: {err}")))?;
+        let mut reply = Vec::new();
+        read_to_end_by(&mut stream, &mut reply, deadline)?;
+        Ok(reply)
+    }
+
+    /// `stat` over the sync service; needs the device's `stat_v2` feature for 64-bit sizes.
+    pub fn stat(
+        &self,
+        serial: &str,
+        path: &str,
+        timeout: Duration,
+    ) -> Result<SyncStat, SocketError> {
+        let deadline = Instant::now() + timeout;
+        if !self.capabilities(serial, deadline)?.stat_v2 {
+            return Err(SocketError::Unsupported(format!(
+                "{serial} does not support stat_v2"
+            )));
+        }
+        let mut stream = self.open_transport(serial, deadline)?;
+        self.request(&mut stream, "sync:", deadline)?;
+
+        let mut request = b"STA2".to_vec();
+        request.extend_from_slice(&(path.len() as u32).to_le_bytes());
+        request.extend_from_slice(path.as_bytes());
+        set_deadline(&stream, deadline)?;
+        stream
+            .write_all(&request)
+            .map_err(|err| SocketError::Io(format!("sync stat write failed: {err}")))?;
+        let mut response = [0u8; STA2_RESPONSE_BYTES];
+        read_exact_by(&mut stream, &mut response, deadline)?;
+        let _ = stream.write_all(b"QUIT\0\0\0\0");
+        decode_sta2(&response).map_err(SocketError::Io)
+    }
+}
+
+fn remaining(deadline: Instant) -> Result<Duration, SocketError> {
+    deadline
+        .checked_duration_since(Instant::now())
+        .filter(|left| !left.is_zero())
+        .ok_or(SocketError::TimedOut)
+}
+
+fn set_deadline(stream: &TcpStream, deadline: Instant) -> Result<(), SocketError> {
+    let left = remaining(deadline)?;
+    stream
+        .set_read_timeout(Some(left))
+        .and_then(|_| stream.set_write_timeout(Some(left)))
+        .map_err(|err| SocketError::Io(format!("adb socket setup failed: {err}")))
+}
+
+fn read_error(err: std::io::Error) -> SocketError {
+    match err.kind() {
+        ErrorKind::WouldBlock | ErrorKind::TimedOut => SocketError::TimedOut,
+        _ => SocketError::Io(format!("adb socket read failed: {err}")),
+    }
+}
+
+fn read_exact_by(
+    stream: &mut TcpStream,
+    buffer: &mut [u8],
+    deadline: Instant,
+) -> Result<(), SocketError> {
+    let mut filled = 0;
+    while filled < buffer.len() {
+        set_deadline(stream, deadline)?;
+        match stream.read(&mut buffer[filled..]) {
+            Ok(0) => {
+                return Err(SocketError::Io(
+                    "adb server closed the connection".to_string(),
+                ))
+            }
+            Ok(count) => filled += count,
+            Err(err) if err.kind() == ErrorKind::Interrupted => continue,
+            Err(err) => return Err(read_error(err)),
+        }
+    }
+    Ok(())
+}
+
+fn read_to_end_by(
+    stream: &mut TcpStream,
+    buffer: &mut Vec<u8>,
+    deadline: Instant,
+) -> Result<(), SocketError> {
+    let mut chunk = [0u8; 16 * 1024];
+    loop {
+        set_deadline(stream, deadline)?;
+        match stream.read(&mut chunk) {
+            Ok(0) => return Ok(()),
+            Ok(count) => buffer.extend_from_slice(&chunk[..count]),
+            Err(err) if err.kind() == ErrorKind::Interrupted => continue,
+            Err(err) => return Err(read_error(err)),
+        }
+    }
+}
+
+fn read_length_prefixed(stream: &mut TcpStream, deadline: Instant) -> Result<String, SocketError> {
+    let mut length = [0u8; 4];
+    read_exact_by(stream, &mut length, deadline)?;
+    let length = std::str::from_utf8(&length)
+        .ok()
+        .and_then(|value| usize::from_str_radix(value, 16).ok())
+        .ok_or_else(|| SocketError::Io("Invalid adb server length prefix".to_string()))?;
+    let mut payload = vec![0u8; length];
+    read_exact_by(stream, &mut payload, deadline)?;
+    Ok(String::from_utf8_lossy(&payload).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::TcpListener;
+
+    fn shell_packet(id: u8, body: &[u8]) -> Vec<u8> {
+        let mut packet = vec![id];
+        packet.extend_from_slice(&(body.len() as u32).to_le_bytes());
+        packet.extend_from_slice(body);
+        packet
+    }
+
+    fn read_request(stream: &mut TcpStream) -> String {
+        let mut length = [0u8; 4];
+        stream.read_exact(&mut length).unwrap();
+        let length = usize::from_str_radix(std::str::from_utf8(&length).unwrap(), 16).unwrap();
+        let mut payload = vec![0u8; length];
+        stream.read_exact(&mut payload).unwrap();
+        String::from_utf8(payload).unwrap()
+    }
+
+    #[test]
+    fn matches_only_plain_adb_shell_calls() {
+        let args = |values: &[&str]| values.iter().map(|v| v.to_string()).collect::<Vec<_>>();
+        let shell = args(&["-s", "A1", "shell", "cat", "/proc/stat"]);
+        assert_eq!(
+            socket_shell_request("/opt/sdk/adb", "/opt/sdk/adb", &shell),
+            Some(("A1", "cat /proc/stat".to_string()))
+        );
+        assert_eq!(
+            socket_shell_request("/opt/sdk/adb", "/opt/vendor/adb", &shell),
+            None
+        );
+        assert_eq!(socket_shell_request("sh", "sh", &shell), None);
+        for other in [
+            args(&["-s", "A1", "shell"]),
+            args(&["-s", "A1", "shell", "-t", "top"]),
+            args(&["-s", "A1", "pull", "/sdcard/a", "/tmp/a"]),
+        ] {
+            assert_eq!(socket_shell_request("adb", "adb", &other), None);
+        }
+        assert!(is_adb_program(r"C:\platform-tools\adb.exe"));
+    }
+
+    #[test]
+    fn encodes_requests_and_decodes_responses() {
+        assert_eq!(encode_request("host:version").unwrap(), b"000chost:version");
+        assert!(encode_request(&"x".repeat(MAX_REQUEST_BYTES + 1)).is_err());
+        assert_eq!(
+            parse_server_socket_spec("tcp:10.0.0.2:5038"),
+            Some(("10.0.0.2".to_string(), 5038))
+        );
+        assert_eq!(
+            parse_server_socket_spec("tcp:5039"),
+            Some(("127.0.0.1".to_string(), 5039))
+        );
+        assert_eq!(
+            parse_server_socket_spec("tcp:[::1]:5037"),
+            Some(("::1".to_string(), 5037))
+        );
+        assert_eq!(parse_server_socket_spec("localfilesystem:/tmp/adb"), None);
+
+        let mut data = shell_packet(SHELL_V2_STDOUT, b"hello ");
+        data.extend(shell_packet(SHELL_V2_STDERR, b"warn\n"));
+        data.extend(shell_packet(SHELL_V2_STDOUT, b"world\n"));
+        data.extend(shell_packet(SHELL_V2_EXIT, &[3]));
+        let output = decode_shell_v2(&data).unwrap();
+        assert_eq!(output.stdout, b"hello world\n");
+        assert_eq!(output.stderr, b"warn\n");
+        assert_eq!(output.exit_code, Some(3));
+        assert!(decode_shell_v2(&data[..data.len() - 1]).is_err());
+
+        let mut sta2 = vec![0u8; STA2_RESPONSE_BYTES];
+        sta2[..4].copy_from_slice(b"STA2");
+        sta2[24..28].copy_from_slice(&0o100644u32.to_le_bytes());
+        sta2[40..48].copy_from_slice(&(5u64 << 32).to_le_bytes());
+        let stat = decode_sta2(&sta2).unwrap();
+        assert_eq!(stat.mode, 0o100644);
+        assert_eq!(stat.size, 5u64 << 32);
+        sta2[4..8].copy_from_slice(&2u32.to_le_bytes());
+        assert!(decode_sta2(&sta2).is_err());
+    }
+
+    #[test]
+    fn runs_shell_v2_commands_against_a_server() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = std::thread::spawn(move || {
+            let (mut features, _) = listener.accept().unwrap();
+            assert_eq!(read_request(&mut features), "host-serial:A1:features");
+            features.write_all(b"OKAY0010shell_v2,cmd,abb").unwrap();
+            drop(features);
+
+            let (mut shell, _) = listener.accept().unwrap();
+            assert_eq!(read_request(&mut shell), "host:transport:A1");
+            shell.write_all(b"OKAY").unwrap();
+            assert_eq!(read_request(&mut shell), "shell,v2,raw:cat /proc/loadavg");
+            shell.write_all(b"OKAY").unwrap();
+            shell
+                .write_all(&shell_packet(SHELL_V2_STDOUT, b"0.50 0.40 0.30\n"))
+                .unwrap();
+            shell.write_all(&shell_packet(SHELL_V2_EXIT, &[0])).unwrap();
+            drop(shell);
+
+            let (mut missing, _) = listener.accept().unwrap();
+            assert_eq!(read_request(&mut missing), "host:transport:A1");
+            missing.write_all(b"FAIL000edevice offline").unwrap();
+        });
+
+        let client = AdbSocketClient::new("127.0.0.1", port);
+        let output = client
+            .shell("A1", "cat /proc/loadavg", Duration::from_secs(5))
+            .unwrap();
+        assert_eq!(output.stdout, "0.50 0.40 0.30\n");
+        assert_eq!(output.exit_code, Some(0));
+
+        let err = client
+            .shell("A1", "true", Duration::from_secs(5))
+            .unwrap_err();
+        assert_eq!(
+            err,
+            SocketError::Unavailable("adb server: device offline".to_string())
+        );
+        server.join().unwrap();
+
+        let cached = |port: u16| {
+            capabilities_cache()
+                .lock()
+                .unwrap()
+                .contains_key(&format!("127.0.0.1:{port}/A1"))
+        };
+        assert!(cached(port));
+        forget_device_capabilities("A1");
+        assert!(!cached(port));
+
+        // Legacy devices cannot report an exit code, so nothing is sent to them.
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let legacy_port = listener.local_addr().unwrap().port();
+        let server = std::thread::spawn(move || {
+            let (mut features, _) = listener.accept().unwrap();
+            assert_eq!(read_request(&mut features), "host-serial:A1:features");
+            features.write_all(b"OKAY0003cmd").unwrap();
+        });
+        let legacy = AdbSocketClient::new("127.0.0.1", legacy_port);
+        assert!(matches!(
+            legacy.shell("A1", "true", Duration::from_secs(5)),
+            Err(SocketError::Unsupported(_))
+        ));
+        server.join().unwrap();
+
+        let closed = AdbSocketClient::new("127.0.0.1", port);
+        assert!(matches!(
+            closed.features("A1", Instant::now() + Duration::from_secs(1)),
+            Err(SocketError::Unavailable(_))
+        ));
+    }
+
+    #[test]
+    fn streams_long_running_shell_output() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            assert_eq!(read_request(&mut stream), "host:transport:A1");
+            stream.write_all(b"OKAY").unwrap();
+            assert_eq!(read_request(&mut stream), "shell:logcat -v threadtime");
+            stream.write_all(b"OKAY").unwrap();
+            stream.write_all(b"line one\nline two\n").unwrap();
+        });
+
+        let client = AdbSocketClient::new("127.0.0.1", port);
+        let mut stream = client
+            .shell_stream("A1", "logcat -v threadtime", Duration::from_secs(5))
+            .unwrap();
+        server.join().unwrap();
+        let mut output = String::new();
+        stream.read_to_string(&mut output).unwrap();
+        assert_eq!(output, "line one\nline two\n");
+    }
+
+    #[test]
+    fn exchanges_with_a_device_tcp_port() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            assert_eq!(read_request(&mut stream), "host:transport:A1");
+            stream.write_all(b"OKAY").unwrap();
+            assert_eq!(read_request(&mut stream), "tcp:4939");
+            stream.write_all(b"OKAY").unwrap();
+            let mut request = [0u8; 8];
+            stream.read_exact(&mut request).unwrap();
+            assert_eq!(&request, b"DUMP -1\n");
+            stream.write_all(b"android.view.View@1\nDONE.\n").unwrap();
+        });
+
+        let client = AdbSocketClient::new("127.0.0.1", port);
+        let reply = client
+            .device_tcp_exchange("A1", 4939, b"DUMP -1\n", Duration::from_secs(5))
+            .unwrap();
+        server.join().unwrap();
+        assert_eq!(reply, b"android.view.View@1\nDONE.\n");
+    }
+}
@@ -0,0 +1,661 @@
+use std::fs::{self, File, Metadata, OpenOptions};
+use std::io::{ErrorKind, Read, Seek, SeekFrom, Write};
+use std::net::TcpStream;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant, UNIX_EPOCH};
+
+use crate::app::adb::socket::{
+    AdbSocketClient, SocketError, SHELL_V2_CLOSE_STDIN, SHELL_V2_EXIT, SHELL_V2_STDERR,
+    SHELL_V2_STDIN, SHELL_V2_STDOUT,
+};
+use crate::app::shell_pipeline::quote_shell_value;
+
+/// Largest `DATA` payload adbd accepts or sends.
+pub const SYNC_DATA_MAX: usize = 64 * 1024;
+/// Transfers fail when no byte moves for this long; a large file may take much longer overall.
+pub const TRANSFER_IDLE_TIMEOUT: Duration = Duration::from_secs(60);
+const OPEN_TIMEOUT: Duration = Duration::from_secs(10);
+/// adbd deletes the partial file of an aborted sync `SEND`, so pushes this large stream
+/// through `cat` into a `.part` file instead; an interrupted upload can then resume.
+pub const RESUMABLE_PUSH_MIN_BYTES: u64 = 64 * 1024 * 1024;
+pub const PARTIAL_SUFFIX: &str = ".part";
+
+const S_IFMT: u32 = 0o170000;
+const S_IFREG: u32 = 0o100000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TransferOutcome {
+    pub bytes: u64,
+    /// Bytes kept from an earlier attempt; 0 for a fresh transfer.
+    pub resumed_from: u64,
+}
+
+pub fn is_regular_file(mode: u32) -> bool {
+    mode & S_IFMT == S_IFREG
+}
+
+pub fn partial_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(PARTIAL_SUFFIX);
+    PathBuf::from(name)
+}
+
+pub fn io_error(context: &str, err: std::io::Error) -> SocketError {
+    match err.kind() {
+        ErrorKind::WouldBlock | ErrorKind::TimedOut => SocketError::TimedOut,
+        _ => SocketError::Io(format!("{context}: {err}")),
+    }
+}
+
+pub fn check_cancel(cancel: &AtomicBool) -> Result<(), SocketError> {
+    if cancel.load(Ordering::Relaxed) {
+        return Err(SocketError::Cancelled);
+    }
+    Ok(())
+}
+
+fn write_header<S: Write>(stream: &mut S, id: &[u8; 4], length: u32) -> Result<(), SocketError> {
+    let mut header = [0u8; 8];
+    header[..4].copy_from_slice(id);
+    header[4..].copy_from_slice(&length.to_le_bytes());
+    stream
+        .write_all(&header)
+        .map_err(|err| io_error("sync write failed", err))
+}
+
+fn read_header<S: Read>(stream: &mut S) -> Result<([u8; 4], u32), SocketError> {
+    let mut header = [0u8; 8];
+    stream
+        .read_exact(&mut header)
+        .map_err(|err| io_error("sync read failed", err))?;
+    Ok((
+        [header[0], header[1], header[2], header[3]],
+        u32::from_le_bytes([header[4], header[5], header[6], header[7]]),
+    ))
+}
+
+fn read_fail_message<S: Read>(stream: &mut S, length: u32) -> SocketError {
+    let mut message = vec![0u8; length as usize];
+    match stream.read_exact(&mut message) {
+        Ok(()) => SocketError::Io(format!("adb: {}", String::from_utf8_lossy(&message))),
+        Err(err) => io_error("sync read failed", err),
+    }
+}
+
+fn unexpected(id: &[u8; 4]) -> SocketError {
+    SocketError::Io(format!(
+        "Unexpected sync response {:?}",
+        String::from_utf8_lossy(id)
+    ))
+}
+
+fn read_local<R: Read>(input: &mut R, buffer: &mut [u8]) -> Result<usize, SocketError> {
+    loop {
+        match input.read(buffer) {
+            Ok(count) => return Ok(count),
+            Err(err) if err.kind() == ErrorKind::Interrupted => continue,
+            Err(err) => return Err(SocketError::Io(format!("Failed to read local file: {err}"))),
+        }
+    }
+}
+
+fn write_local<W: Write>(out: &mut W, data: &[u8]) -> Result<(), SocketError> {
+    out.write_all(data)
+        .map_err(|err| SocketError::Io(format!("Failed to write local file: {err}")))
+}
+
+/// Sync `RECV`: streams `remote` into `out`, reporting the running byte count.
+pub fn sync_recv<S: Read + Write, W: Write>(
+    stream: &mut S,
+    remote: &str,
+    out: &mut W,
+    cancel: &AtomicBool,
+    on_bytes: &mut dyn FnMut(u64),
+) -> Result<u64, SocketError> {
+    write_header(stream, b"RECV", remote.len() as u32)?;
+    stream
+        .write_all(remote.as_bytes())
+        .map_err(|err| io_error("sync write failed", err))?;
+    let mut buffer = vec![0u8; SYNC_DATA_MAX];
+    let mut total = 0u64;
+    loop {
+        check_cancel(cancel)?;
+        let (id, length) = read_header(stream)?;
+        match &id {
+            b"DATA" => {
+                let length = length as usize;
+                if length > SYNC_DATA_MAX {
+                    return Err(SocketError::Io(format!(
+                        "Sync packet too large ({length} bytes)"
+                    )));
+                }
+                stream
+                    .read_exact(&mut buffer[..length])
+                    .map_err(|err| io_error("sync read failed", err))?;
+                write_local(out, &buffer[..length])?;
+                total += length as u64;
+                on_bytes(total);
+            }
+            b"DONE" => break,
+            b"FAIL" => return Err(read_fail_message(stream, length)),
+            other => return Err(unexpected(other)),
+        }
+    }
+    let _ = write_header(stream, b"QUIT", 0);
+    Ok(total)
+}
+
+/// Sync `SEND`: uploads `input` to `remote`; the device confirms once `DONE` carries `mtime`.
+pub fn sync_send<S: Read + Write, R: Read>(
+    stream: &mut S,
+    remote: &str,
+    mode: u32,
+    mtime: u32,
+    input: &mut R,
+    cancel: &AtomicBool,
+    on_bytes: &mut dyn FnMut(u64),
+) -> Result<u64, SocketError> {
+    let spec = format!("{remote},{mode}");
+    write_header(stream, b"SEND", spec.len() as u32)?;
+    stream
+        .write_all(spec.as_bytes())
+        .map_err(|err| io_error("sync write failed", err))?;
+    let mut buffer = vec![0u8; SYNC_DATA_MAX];
+    let mut total = 0u64;
+    loop {
+        check_cancel(cancel)?;
+        let count = read_local(input, &mut buffer)?;
+        if count == 0 {
+            break;
+        }
+        write_header(stream, b"DATA", count as u32)?;
+        stream
+            .write_all(&buffer[..count])
+            .map_err(|err| io_error("sync write failed", err))?;
+        total += count as u64;
+        on_bytes(total);
+    }
+    write_header(stream, b"DONE", mtime)?;
+    let (id, length) = read_header(stream)?;
+    match &id {
+        b"OKAY" => {}
+        b"FAIL" => return Err(read_fail_message(stream, length)),
+        other => return Err(unexpected(other)),
+    }
+    let _ = write_header(stream, b"QUIT", 0);
+    Ok(total)
+}
+
+fn write_packet<S: Write>(stream: &mut S, id: u8, payload: &[u8]) -> Result<(), SocketError> {
+    let mut header = [0u8; 5];
+    header[0] = id;
+    header[1..].copy_from_slice(&(payload.len() as u32).to_le_bytes());
+    stream
+        .write_all(&header)
+        .and_then(|_| stream.write_all(payload))
+        .map_err(|err| io_error("shell write failed", err))
+}
+
+/// The next shell v2 packet, or `None` once the device closed the stream.
+fn read_packet<S: Read>(stream: &mut S) -> Result<Option<(u8, Vec<u8>)>, SocketError> {
+    let mut header = [0u8; 5];
+    match stream.read_exact(&mut header) {
+        Ok(()) => {}
+        Err(err) if err.kind() == ErrorKind::UnexpectedEof => return Ok(None),
+        Err(err) => return Err(io_error("shell read failed", err)),
+    }
+    let length = u32::from_le_bytes([header[1], header[2], header[3], header[4]]) as usize;
+    let mut payload = vec![0u8; length];
+    stream
+        .read_exact(&mut payload)
+        .map_err(|err| io_error("shell read failed", err))?;
+    Ok(Some((header[0], payload)))
+}
+
+fn finish_shell(exit_code: Option<i32>, stderr: &[u8]) -> Result<(), SocketError> {
+    match exit_code {
+        Some(0) => Ok(()),
+        code => Err(SocketError::Io(format!(
+            "Device command failed (exit {code:?}): {}",
+            String::from_utf8_lossy(stderr).trim()
+        ))),
+    }
+}
+
+/// Appends a shell v2 command's stdout to `out` (a resumed pull through `tail -c`).
+pub fn shell_v2_stdout_into<S: Read, W: Write>(
+    stream: &mut S,
+    out: &mut W,
+    cancel: &AtomicBool,
+    on_bytes: &mut dyn FnMut(u64),
+) -> Result<u64, SocketError> {
+    let mut total = 0u64;
+    let mut stderr = Vec::new();
+    let mut exit_code = None;
+    while let Some((id, payload)) = read_packet(stream)? {
+        check_cancel(cancel)?;
+        match id {
+            SHELL_V2_STDOUT => {
+                write_local(out, &payload)?;
+                total += payload.len() as u64;
+                on_bytes(total);
+            }
+            SHELL_V2_STDERR => stderr.extend_from_slice(&payload),
+            SHELL_V2_EXIT => exit_code = payload.first().map(|code| i32::from(*code)),
+            _ => {}
+        }
+    }
+    finish_shell(exit_code, &stderr)?;
+    Ok(total)
+}
+
+/// Streams `input` into a shell v2 command's stdin (a resumable push through `cat`).
+pub fn shell_v2_stdin_from<S: Read + Write, R: Read>(
+    stream: &mut S,
+    input: &mut R,
+    cancel: &AtomicBool,
+    on_bytes: &mut dyn FnMut(u64),
+) -> Result<u64, SocketError> {
+    let mut buffer = vec![0u8; SYNC_DATA_MAX];
+    let mut total = 0u64;
+    loop {
+        check_cancel(cancel)?;
+        let count = read_local(input, &mut buffer)?;
+        if count == 0 {
+            break;
+        }
+        write_packet(stream, SHELL_V2_STDIN, &buffer[..count])?;
+        total += count as u64;
+        on_bytes(total);
+    }
+    write_packet(stream, SHELL_V2_CLOSE_STDIN, &[])?;
+    let mut stderr = Vec::new();
+    let mut exit_code = None;
+    while let Some((id, payload)) = read_packet(stream)? {
+        match id {
+            SHELL_V2_STDERR => stderr.extend_from_slice(&payload),
+            SHELL_V2_EXIT => exit_code = payload.first().map(|code| i32::from(*code)),
+            _ => {}
+        }
+    }
+    finish_shell(exit_code, &stderr)?;
+    Ok(total)
+}
+
+/// A device service stream whose reads and writes fail after `TRANSFER_IDLE_TIMEOUT` idle.
+pub fn open_stream(
+    client: &AdbSocketClient,
+    serial: &str,
+    service: &str,
+) -> Result<TcpStream, SocketError> {
+    let stream = client.open_service(serial, service, Instant::now() + OPEN_TIMEOUT)?;
+    stream
+        .set_read_timeout(Some(TRANSFER_IDLE_TIMEOUT))
+        .and_then(|_| stream.set_write_timeout(Some(TRANSFER_IDLE_TIMEOUT)))
+        .map_err(|err| SocketError::Io(format!("adb socket setup failed: {err}")))?;
+    Ok(stream)
+}
+
+/// Raw stdout of `exec:<command>`, the service behind `adb exec-out`; no exit status is
+/// reported, so callers check the length of what came back.
+pub fn exec_out(
+    client: &AdbSocketClient,
+    serial: &str,
+    command: &str,
+    cancel: &AtomicBool,
+) -> Result<Vec<u8>, SocketError> {
+    let mut stream = open_stream(client, serial, &format!("exec:{command}"))?;
+    let mut buffer = vec![0u8; SYNC_DATA_MAX];
+    let mut out = Vec::new();
+    loop {
+        check_cancel(cancel)?;
+        match stream.read(&mut buffer) {
+            Ok(0) => return Ok(out),
+            Ok(count) => out.extend_from_slice(&buffer[..count]),
+            Err(err) if err.kind() == ErrorKind::Interrupted => continue,
+            Err(err) => return Err(io_error("exec read failed", err)),
+        }
+    }
+}
+
+pub fn local_error(path: &Path, err: std::io::Error) -> SocketError {
+    SocketError::Io(format!("{}: {err}", path.display()))
+}
+
+/// Moves a finished `.part` file over `local`, replacing an older copy.
+pub fn finish_partial(part: &Path, local: &Path) -> Result<(), SocketError> {
+    match fs::remove_file(local) {
+        Ok(()) => {}
+        Err(err) if err.kind() == ErrorKind::NotFound => {}
+        Err(err) => return Err(local_error(local, err)),
+    }
+    fs::rename(part, local).map_err(|err| local_error(local, err))
+}
+
+/// Pulls the regular file `remote` (`total` bytes) into `local` through `<local>.part`. With
+/// `resume`, a partial left by an earlier attempt is continued from its length; this assumes
+/// the device file did not change in between.
+#[allow(clippy::too_many_arguments)]
+pub fn pull_file(
+    client: &AdbSocketClient,
+    serial: &str,
+    remote: &str,
+    total: u64,
+    local: &Path,
+    resume: bool,
+    cancel: &AtomicBool,
+    on_progress: &mut dyn FnMut(u64),
+) -> Result<TransferOutcome, SocketError> {
+    let part = partial_path(local);
+    let existing = fs::metadata(&part).map(|meta| meta.len()).unwrap_or(0);
+    let offset = if resume
+        && existing > 0
+        && existing < total
+        && client.supports_shell_v2(serial, Instant::now() + OPEN_TIMEOUT)?
+    {
+        existing
+    } else {
+        0
+    };
+    let mut file = if offset > 0 {
+        OpenOptions::new().append(true).open(&part)
+    } else {
+        File::create(&part)
+    }
+    .map_err(|err| local_error(&part, err))?;
+
+    on_progress(offset);
+    let mut report = |bytes: u64| on_progress(offset + bytes);
+    let copied = if offset == 0 {
+        let mut stream = open_stream(client, serial, "sync:")?;
+        sync_recv(&mut stream, remote, &mut file, cancel, &mut report)?
+    } else {
+        let command = format!("tail -c +{} {}", offset + 1, quote_shell_value(remote));
+        let mut stream = open_stream(client, serial, &format!("shell,v2,raw:{command}"))?;
+        shell_v2_stdout_into(&mut stream, &mut file, cancel, &mut report)?
+    };
+    file.flush().map_err(|err| local_error(&part, err))?;
+    drop(file);
+
+    let received = offset + copied;
+    if received != total {
+        return Err(SocketError::Io(format!(
+            "Transfer incomplete: received {received} of {total} bytes"
+        )));
+    }
+    finish_partial(&part, local)?;
+    Ok(TransferOutcome {
+        bytes: received,
+        resumed_from: offset,
+    })
+}
+
+fn sync_mode(metadata: &Metadata) -> u32 {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        S_IFREG | (metadata.permissions().mode() & 0o777)
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = metadata;
+        S_IFREG | 0o644
+    }
+}
+
+/// Pushes `local` to `remote`. Large files on devices with shell v2 go through
+/// `<remote>.part` so `resume` can continue an interrupted upload; others use sync `SEND`.
+pub fn push_file(
+    client: &AdbSocketClient,
+    serial: &str,
+    local: &Path,
+    remote: &str,
+    resume: bool,
+    cancel: &AtomicBool,
+    on_progress: &mut dyn FnMut(u64),
+) -> Result<TransferOutcome, SocketError> {
+    let metadata = fs::metadata(local).map_err(|err| local_error(local, err))?;
+    let total = metadata.len();
+    let mut file = File::open(local).map_err(|err| local_error(local, err))?;
+    let resumable = total >= RESUMABLE_PUSH_MIN_BYTES
+        && client.supports_shell_v2(serial, Instant::now() + OPEN_TIMEOUT)?;
+
+    if !resumable {
+        let mtime = metadata
+            .modified()
+            .ok()
+            .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+            .map(|elapsed| elapsed.as_secs() as u32)
+            .unwrap_or_default();
+        let mut stream = open_stream(client, serial, "sync:")?;
+        let sent = sync_send(
+            &mut stream,
+            remote,
+            sync_mode(&metadata),
+            mtime,
+            &mut file,
+            cancel,
+            on_progress,
+        )?;
+        return Ok(TransferOutcome {
+            bytes: sent,
+            resumed_from: 0,
+        });
+    }
+
+    let part = format!("{remote}{PARTIAL_SUFFIX}");
+    let offset = if resume {
+        match client.stat(serial, &part, OPEN_TIMEOUT) {
+            Ok(stat) if stat.size < total => stat.size,
+            _ => 0,
+        }
+    } else {
+        0
+    };
+    file.seek(SeekFrom::Start(offset))
+        .map_err(|err| local_error(local, err))?;
+
+    on_progress(offset);
+    let redirect = if offset > 0 { ">>" } else { ">" };
+    let command = format!("cat {redirect} {}", quote_shell_value(&part));
+    let mut stream = open_stream(client, serial, &format!("shell,v2,raw:{command}"))?;
+    let sent = shell_v2_stdin_from(&mut stream, &mut file, cancel, &mut |bytes| {
+        on_progress(offset + bytes)
+    })?;
+    let pushed = offset + sent;
+    if pushed != total {
+        return Err(SocketError::Io(format!(
+            "Transfer incomplete: sent {pushed} of {total} bytes"
+        )));
+    }
+
+    let finish = format!(
+        "mv -f {} {}",
+        quote_shell_value(&part),
+        quote_shell_value(remote)
+    );
+    let output = client.shell(serial, &finish, OPEN_TIMEOUT)?;
+    if output.exit_code != Some(0) {
+        return Err(SocketError::Io(format!(
+            "Failed to move {part} into place: {}",
+            output.stderr.trim()
+        )));
+    }
+    Ok(TransferOutcome {
+        bytes: pushed,
+        resumed_from: offset,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    /// Replays scripted device responses and records what the client wrote.
+    struct ScriptedStream {
+        responses: Cursor<Vec<u8>>,
+        written: Vec<u8>,
+    }
+
+    impl ScriptedStream {
+        fn new(responses: Vec<u8>) -> Self {
+            Self {
+                responses: Cursor::new(responses),
+                written: Vec::new(),
+            }
+        }
+    }
+
+    impl Read for ScriptedStream {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            self.responses.read(buf)
+        }
+    }
+
+    impl Write for ScriptedStream {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.written.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn sync_packet(id: &[u8; 4], payload: &[u8]) -> Vec<u8> {
+        let mut packet = id.to_vec();
+        packet.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+        packet.extend_from_slice(payload);
+        packet
+    }
+
+    fn shell_packet(id: u8, payload: &[u8]) -> Vec<u8> {
+        let mut packet = vec![id];
+        packet.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+        packet.extend_from_slice(payload);
+        packet
+    }
+
+    #[test]
+    fn receives_files_with_byte_progress() {
+        let mut responses = sync_packet(b"DATA", b"hello");
+        responses.extend(sync_packet(b"DATA", b" world"));
+        responses.extend(sync_packet(b"DONE", b""));
+        let mut stream = ScriptedStream::new(responses);
+        let mut out = Vec::new();
+        let mut progress = Vec::new();
+        let total = sync_recv(
+            &mut stream,
+            "/sdcard/a.txt",
+            &mut out,
+            &AtomicBool::new(false),
+            &mut |bytes| progress.push(bytes),
+        )
+        .unwrap();
+
+        assert_eq!(total, 11);
+        assert_eq!(out, b"hello world");
+        assert_eq!(progress, vec![5, 11]);
+        assert!(stream
+            .written
+            .starts_with(&sync_packet(b"RECV", b"/sdcard/a.txt")));
+        assert!(stream.written.ends_with(b"QUIT\0\0\0\0"));
+
+        let mut failing = ScriptedStream::new(sync_packet(b"FAIL", b"No such file"));
+        let err = sync_recv(
+            &mut failing,
+            "/sdcard/missing",
+            &mut Vec::new(),
+            &AtomicBool::new(false),
+            &mut |_| {},
+        )
+        .unwrap_err();
+        assert_eq!(err, SocketError::Io("adb: No such file".to_string()));
+    }
+
+    #[test]
+    fn sends_files_and_stops_on_cancel() {
+        let data = vec![7u8; SYNC_DATA_MAX + 10];
+        let mut stream = ScriptedStream::new(sync_packet(b"OKAY", b""));
+        let mut progress = Vec::new();
+        let sent = sync_send(
+            &mut stream,
+            "/sdcard/b.bin",
+            S_IFREG | 0o644,
+            1_700_000_000,
+            &mut Cursor::new(data.clone()),
+            &AtomicBool::new(false),
+            &mut |bytes| progress.push(bytes),
+        )
+        .unwrap();
+        assert_eq!(sent, data.len() as u64);
+        assert_eq!(progress, vec![SYNC_DATA_MAX as u64, data.len() as u64]);
+        let spec = format!("/sdcard/b.bin,{}", S_IFREG | 0o644);
+        assert!(stream
+            .written
+            .starts_with(&sync_packet(b"SEND", spec.as_bytes())));
+
+        let mut cancelled = ScriptedStream::new(Vec::new());
+        let err = sync_send(
+            &mut cancelled,
+            "/sdcard/b.bin",
+            S_IFREG | 0o644,
+            0,
+            &mut Cursor::new(data),
+            &AtomicBool::new(true),
+            &mut |_| {},
+        )
+        .unwrap_err();
+        assert_eq!(err, SocketError::Cancelled);
+        assert!(!cancelled.written.windows(4).any(|window| window == b"DATA"));
+    }
+
+    #[test]
+    fn streams_resumed_transfers_over_shell_v2() {
+        let mut responses = shell_packet(SHELL_V2_STDOUT, b"tail");
+        responses.extend(shell_packet(SHELL_V2_EXIT, &[0]));
+        let mut out = b"head ".to_vec();
+        let copied = shell_v2_stdout_into(
+            &mut ScriptedStream::new(responses),
+            &mut out,
+            &AtomicBool::new(false),
+            &mut |_| {},
+        )
+        .unwrap();
+        assert_eq!(copied, 4);
+        assert_eq!(out, b"head tail");
+
+        let mut stream = ScriptedStream::new(shell_packet(SHELL_V2_EXIT, &[0]));
+        let sent = shell_v2_stdin_from(
+            &mut stream,
+            &mut Cursor::new(b"rest".to_vec()),
+            &AtomicBool::new(false),
+            &mut |_| {},
+        )
+        .unwrap();
+        assert_eq!(sent, 4);
+        let mut expected = shell_packet(SHELL_V2_STDIN, b"rest");
+        expected.extend(shell_packet(SHELL_V2_CLOSE_STDIN, b""));
+        assert_eq!(stream.written, expected);
+
+        let mut failed = shell_packet(SHELL_V2_STDERR, b"tail: No such file\n");
+        failed.extend(shell_packet(SHELL_V2_EXIT, &[1]));
+        assert!(shell_v2_stdout_into(
+            &mut ScriptedStream::new(failed),
+            &mut Vec::new(),
+            &AtomicBool::new(false),
+            &mut |_| {},
+        )
+        .is_err());
+
+        assert_eq!(
+            partial_path(Path::new("/tmp/out/video.mp4")),
+            PathBuf::from("/tmp/out/video.mp4.part")
+        );
+        assert!(is_regular_file(S_IFREG | 0o600));
+        assert!(!is_regular_file(0o040755));
+    }
+}
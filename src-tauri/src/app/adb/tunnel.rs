@@ -0,0 +1,280 @@
+use std::io::Read;
+use std::net::{SocketAddr, TcpStream};
+use std::process::{Child, Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use tauri::AppHandle;
+use tracing::{info, warn};
+
+use crate::app::config::SshTunnelSettings;
+use crate::app::error::AppError;
+use crate::app::i18n::Message;
+use crate::app::metrics::emit_tracked;
+use crate::app::models::AdbTunnelStatus;
+
+pub const ADB_TUNNEL_STATUS_EVENT: &str = "adb-tunnel-status";
+const TUNNEL_CONNECT_TIMEOUT: Duration = Duration::from_secs(15);
+const TUNNEL_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+pub struct SshTunnelHandle {
+    status: AdbTunnelStatus,
+    stop_flag: Arc<AtomicBool>,
+    child: Arc<Mutex<Option<Child>>>,
+    join: JoinHandle<()>,
+}
+
+impl SshTunnelHandle {
+    pub fn status(&self) -> AdbTunnelStatus {
+        let mut status = self.status.clone();
+        if self.join.is_finished() {
+            status.state = "down".to_string();
+        }
+        status
+    }
+
+    pub fn stop(self) {
+        self.stop_flag.store(true, Ordering::Relaxed);
+        if let Ok(mut guard) = self.child.lock() {
+            if let Some(mut child) = guard.take() {
+                let _ = child.kill();
+                let _ = child.wait();
+            }
+        }
+        let _ = self.join.join();
+    }
+}
+
+fn is_safe_ssh_token(value: &str) -> bool {
+    !value.is_empty()
+        && !value.starts_with('-')
+        && value
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '.' | '_' | '-' | ':' | '%'))
+}
+
+/// Build `ssh -N -L` arguments. Host and user are rejected if they could be read as options.
+pub fn build_ssh_tunnel_args(
+    host: &str,
+    settings: &SshTunnelSettings,
+) -> Result<Vec<String>, String> {
+    if !is_safe_ssh_token(host) {
+        return Err(format!("Invalid SSH host: {host}"));
+    }
+    if !is_safe_ssh_token(&settings.user) {
+        return Err(format!("Invalid SSH user: {}", settings.user));
+    }
+    let mut args = vec![
+        "-N".to_string(),
+        "-o".to_string(),
+        "BatchMode=yes".to_string(),
+        "-o".to_string(),
+        "ExitOnForwardFailure=yes".to_string(),
+        "-o".to_string(),
+        "ServerAliveInterval=15".to_string(),
+        "-o".to_string(),
+        "ServerAliveCountMax=3".to_string(),
+        "-p".to_string(),
+        settings.ssh_port.to_string(),
+    ];
+    if !settings.key_path.is_empty() {
+        args.push("-i".to_string());
+        args.push(settings.key_path.clone());
+    }
+    args.push("-L".to_string());
+    args.push(format!(
+        "127.0.0.1:{}:127.0.0.1:{}",
+        settings.local_port, settings.remote_port
+    ));
+    args.push(format!("{}@{}", settings.user, host));
+    Ok(args)
+}
+
+fn local_port_open(port: u16) -> bool {
+    let addr = SocketAddr::from(([127, 0, 0, 1], port));
+    TcpStream::connect_timeout(&addr, Duration::from_millis(300)).is_ok()
+}
+
+fn read_stderr(child: &mut Child) -> String {
+    let mut message = String::new();
+    if let Some(mut stderr) = child.stderr.take() {
+        let _ = stderr.read_to_string(&mut message);
+    }
+    message.trim().to_string()
+}
+
+/// Start `ssh -L` for `name` and wait until the forwarded port accepts connections.
+///
+/// A monitor thread emits `adb-tunnel-status` with state `down` when ssh exits on its own.
+pub fn start_ssh_tunnel(
+    app: AppHandle,
+    name: &str,
+    host: &str,
+    settings: &SshTunnelSettings,
+    trace_id: &str,
+) -> Result<SshTunnelHandle, AppError> {
+    let args = build_ssh_tunnel_args(host, settings)
+        .map_err(|message| AppError::validation(message, trace_id))?;
+    if local_port_open(settings.local_port) {
+        return Err(AppError::validation(
+            Message::keyed("port_in_use").with("port", settings.local_port),
+            trace_id,
+        ));
+    }
+
+    let mut child = Command::new("ssh")
+        .args(&args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|err| {
+            AppError::dependency(
+                Message::keyed("start_failed")
+                    .with("subject", "ssh")
+                    .with("detail", &err),
+                trace_id,
+            )
+        })?;
+
+    let started = Instant::now();
+    loop {
+        if local_port_open(settings.local_port) {
+            break;
+        }
+        match child.try_wait() {
+            Ok(Some(_)) => {
+                let message = read_stderr(&mut child);
+                return Err(AppError::dependency(
+                    Message::keyed("action_failed")
+                        .with("action", "SSH tunnel")
+                        .with("detail", &message),
+                    trace_id,
+                ));
+            }
+            Ok(None) => {}
+            Err(err) => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(AppError::system(
+                    Message::keyed("operation_failed")
+                        .with("action", "poll ssh")
+                        .with("detail", &err),
+                    trace_id,
+                ));
+            }
+        }
+        if started.elapsed() >= TUNNEL_CONNECT_TIMEOUT {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(AppError::dependency(
+                Message::keyed("tunnel_port_timeout"),
+                trace_id,
+            ));
+        }
+        thread::sleep(TUNNEL_POLL_INTERVAL);
+    }
+
+    let status = AdbTunnelStatus {
+        name: name.to_string(),
+        host: host.to_string(),
+        local_port: settings.local_port,
+        state: "up".to_string(),
+        message: None,
+        trace_id: trace_id.to_string(),
+    };
+    info!(trace_id = %trace_id, name = %name, local_port = settings.local_port, "ssh tunnel up");
+    let _ = emit_tracked(&app, ADB_TUNNEL_STATUS_EVENT, status.clone());
+
+    let stop_flag = Arc::new(AtomicBool::new(false));
+    let child_slot = Arc::new(Mutex::new(Some(child)));
+    let stop_thread = Arc::clone(&stop_flag);
+    let child_thread = Arc::clone(&child_slot);
+    let monitor_status = status.clone();
+    let join = thread::spawn(move || loop {
+        thread::sleep(TUNNEL_POLL_INTERVAL);
+        if stop_thread.load(Ordering::Relaxed) {
+            break;
+        }
+        let exited = match child_thread.lock() {
+            Ok(mut guard) => match guard.as_mut().map(|child| child.try_wait()) {
+                Some(Ok(Some(_))) => guard.as_mut().map(read_stderr),
+                Some(Ok(None)) => None,
+                Some(Err(err)) => Some(format!("Failed to poll ssh: {err}")),
+                None => break,
+            },
+            Err(_) => Some("Tunnel state poisoned".to_string()),
+        };
+        if let Some(message) = exited {
+            if stop_thread.load(Ordering::Relaxed) {
+                break;
+            }
+            warn!(
+                trace_id = %monitor_status.trace_id,
+                name = %monitor_status.name,
+                error = %message,
+                "ssh tunnel dropped"
+            );
+            let payload = AdbTunnelStatus {
+                state: "down".to_string(),
+                message: Some(if message.is_empty() {
+                    "ssh exited".to_string()
+                } else {
+                    message
+                }),
+                ..monitor_status.clone()
+            };
+            let _ = emit_tracked(&app, ADB_TUNNEL_STATUS_EVENT, payload);
+            break;
+        }
+    });
+
+    Ok(SshTunnelHandle {
+        status,
+        stop_flag,
+        child: child_slot,
+        join,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings(user: &str, key_path: &str) -> SshTunnelSettings {
+        SshTunnelSettings {
+            user: user.to_string(),
+            key_path: key_path.to_string(),
+            ssh_port: 2222,
+            remote_port: 5037,
+            local_port: 15037,
+        }
+    }
+
+    #[test]
+    fn build_ssh_tunnel_args_forwards_local_port() {
+        let args = build_ssh_tunnel_args("lab.local", &settings("qa", "/home/qa/.ssh/id_lab"))
+            .expect("args");
+        assert_eq!(args[0], "-N");
+        assert!(args.windows(2).any(|pair| pair == ["-p", "2222"]));
+        assert!(args
+            .windows(2)
+            .any(|pair| pair == ["-i", "/home/qa/.ssh/id_lab"]));
+        assert!(args
+            .windows(2)
+            .any(|pair| pair == ["-L", "127.0.0.1:15037:127.0.0.1:5037"]));
+        assert_eq!(args.last().map(String::as_str), Some("qa@lab.local"));
+
+        let without_key = build_ssh_tunnel_args("10.0.0.5", &settings("qa", "")).expect("args");
+        assert!(!without_key.contains(&"-i".to_string()));
+    }
+
+    #[test]
+    fn build_ssh_tunnel_args_rejects_option_injection() {
+        assert!(build_ssh_tunnel_args("-oProxyCommand=x", &settings("qa", "")).is_err());
+        assert!(build_ssh_tunnel_args("lab.local", &settings("qa x", "")).is_err());
+        assert!(build_ssh_tunnel_args("lab.local", &settings("", "")).is_err());
+    }
+}
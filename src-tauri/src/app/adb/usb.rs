@@ -0,0 +1,143 @@
+use crate::app::models::UsbFunctionState;
+
+/// Functions `svc usb setFunctions` accepts; `none` leaves the port charging only.
+pub const USB_FUNCTIONS: [&str; 6] = ["none", "mtp", "ptp", "rndis", "midi", "ncm"];
+
+/// Gadget functions that may appear in `sys.usb.config` / `sys.usb.state` alongside the ones
+/// the app can select.
+const KNOWN_USB_FUNCTIONS: [&str; 10] = [
+    "none",
+    "mtp",
+    "ptp",
+    "rndis",
+    "midi",
+    "ncm",
+    "adb",
+    "accessory",
+    "audio_source",
+    "uvc",
+];
+
+/// `svc usb getFunctions` (Android 9+) prints to stderr and older releases answer with usage
+/// text, so the USB gadget properties are read as well.
+pub const USB_FUNCTIONS_SCRIPT: &str = concat!(
+    "echo \"functions=$(svc usb getFunctions 2>&1 | head -n 1)\"; ",
+    "echo \"state=$(getprop sys.usb.state)\"; ",
+    "echo \"config=$(getprop sys.usb.config)\"",
+);
+
+pub fn normalize_usb_function(value: &str) -> Result<&'static str, String> {
+    let value = value.trim().to_ascii_lowercase();
+    let value = if value == "charging" { "none" } else { &value };
+    USB_FUNCTIONS
+        .into_iter()
+        .find(|function| *function == value)
+        .ok_or_else(|| {
+            format!(
+                "Unsupported USB function: {value} (expected one of {})",
+                USB_FUNCTIONS.join(", ")
+            )
+        })
+}
+
+/// `svc usb setFunctions` arguments; no argument resets the port to charging only.
+pub fn set_functions_args(function: &str) -> Vec<&str> {
+    let mut args = vec!["svc", "usb", "setFunctions"];
+    if function != "none" {
+        args.push(function);
+    }
+    args
+}
+
+fn parse_function_list(value: &str) -> Option<Vec<String>> {
+    let functions: Vec<String> = value
+        .split(',')
+        .map(|entry| entry.trim().to_ascii_lowercase())
+        .filter(|entry| !entry.is_empty())
+        .collect();
+    let known = !functions.is_empty()
+        && functions
+            .iter()
+            .all(|entry| KNOWN_USB_FUNCTIONS.contains(&entry.as_str()));
+    known.then_some(functions)
+}
+
+/// The first readable source wins: `svc usb getFunctions`, then the active gadget state, then
+/// the requested configuration. `adb` is managed by the debugging toggle rather than
+/// `setFunctions`, so it is reported separately.
+pub fn parse_usb_functions(serial: &str, output: &str) -> UsbFunctionState {
+    let lists: Vec<Vec<String>> = ["functions", "state", "config"]
+        .into_iter()
+        .filter_map(|key| {
+            output.lines().find_map(|line| {
+                line.trim()
+                    .strip_prefix(key)
+                    .and_then(|rest| rest.strip_prefix('='))
+            })
+        })
+        .filter_map(parse_function_list)
+        .collect();
+    let functions: Vec<String> = lists
+        .first()
+        .into_iter()
+        .flatten()
+        .filter(|function| *function != "none" && *function != "adb")
+        .cloned()
+        .collect();
+    let current = lists.first().map(|_| {
+        functions
+            .first()
+            .cloned()
+            .unwrap_or_else(|| "none".to_string())
+    });
+
+    UsbFunctionState {
+        serial: serial.to_string(),
+        current,
+        adb_enabled: lists.iter().flatten().any(|function| function == "adb"),
+        functions,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validates_usb_functions_and_builds_arguments() {
+        assert_eq!(normalize_usb_function(" MTP "), Ok("mtp"));
+        assert_eq!(normalize_usb_function("charging"), Ok("none"));
+        assert!(normalize_usb_function("adb").is_err());
+        assert!(normalize_usb_function("mtp;reboot").is_err());
+        assert_eq!(
+            set_functions_args("rndis"),
+            ["svc", "usb", "setFunctions", "rndis"]
+        );
+        assert_eq!(set_functions_args("none"), ["svc", "usb", "setFunctions"]);
+    }
+
+    #[test]
+    fn parses_current_functions_from_svc_or_properties() {
+        let modern = parse_usb_functions("R58M", "functions=mtp\nstate=mtp,adb\nconfig=mtp,adb\n");
+        assert_eq!(modern.current.as_deref(), Some("mtp"));
+        assert_eq!(modern.functions, ["mtp"]);
+        assert!(modern.adb_enabled);
+
+        let legacy = parse_usb_functions(
+            "old",
+            "functions=usage: svc usb [help|setFunction FUNCTION|getFunction]\nstate=rndis,adb\nconfig=mtp,adb\n",
+        );
+        assert_eq!(legacy.current.as_deref(), Some("rndis"));
+        assert_eq!(legacy.functions, ["rndis"]);
+        assert!(legacy.adb_enabled);
+
+        let charging = parse_usb_functions("R58M", "functions=none\nstate=adb\nconfig=adb\n");
+        assert_eq!(charging.current.as_deref(), Some("none"));
+        assert!(charging.functions.is_empty());
+        assert!(charging.adb_enabled);
+
+        let unreadable = parse_usb_functions("R58M", "");
+        assert_eq!(unreadable.current, None);
+        assert!(!unreadable.adb_enabled);
+    }
+}
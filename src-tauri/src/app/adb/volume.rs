@@ -0,0 +1,202 @@
+use crate::app::models::AudioStreamState;
+
+/// Streams `set_volume` accepts, with their `AudioManager.STREAM_*` ids.
+pub const VOLUME_STREAMS: [(&str, u32); 7] = [
+    ("voice_call", 0),
+    ("system", 1),
+    ("ring", 2),
+    ("music", 3),
+    ("alarm", 4),
+    ("notification", 5),
+    ("accessibility", 10),
+];
+
+pub fn stream_id(stream: &str) -> Result<u32, String> {
+    let stream = stream.trim().to_ascii_lowercase();
+    VOLUME_STREAMS
+        .iter()
+        .find(|(name, _)| *name == stream)
+        .map(|(_, id)| *id)
+        .ok_or_else(|| {
+            format!(
+                "Unknown audio stream: {stream} (expected one of {})",
+                VOLUME_STREAMS
+                    .iter()
+                    .map(|(name, _)| *name)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        })
+}
+
+/// `cmd media_session volume` on Android 8+, falling back to the older `media` shell tool.
+pub fn set_volume_commands(stream_id: u32, level: u32) -> [Vec<String>; 2] {
+    let args = |prefix: &[&str]| {
+        let mut args: Vec<String> = prefix.iter().map(|part| part.to_string()).collect();
+        args.extend([
+            "volume".to_string(),
+            "--stream".to_string(),
+            stream_id.to_string(),
+            "--set".to_string(),
+            level.to_string(),
+        ]);
+        args
+    };
+    [args(&["cmd", "media_session"]), args(&["media"])]
+}
+
+/// Per-stream blocks of `dumpsys audio`:
+///
+/// ```text
+/// - STREAM_MUSIC:
+///    Muted: false
+///    Min: 0
+///    Max: 15
+///    streamVolume:7
+///    Current: 2 (speaker): 7, 80 (bt_a2dp): 5, 40000000 (default): 7
+///    Devices: speaker
+/// ```
+///
+/// `streamVolume` is missing before Android 12; the volume of the first active device from
+/// `Current` is used instead.
+pub fn parse_audio_streams(output: &str) -> Vec<AudioStreamState> {
+    let mut streams: Vec<AudioStreamState> = Vec::new();
+    let mut current: Option<(AudioStreamState, Vec<(String, u32)>)> = None;
+    let finish = |entry: Option<(AudioStreamState, Vec<(String, u32)>)>,
+                  streams: &mut Vec<AudioStreamState>| {
+        if let Some((mut stream, per_device)) = entry {
+            if stream.volume.is_none() {
+                stream.volume = stream
+                    .devices
+                    .iter()
+                    .find_map(|device| {
+                        per_device
+                            .iter()
+                            .find(|(name, _)| name == device)
+                            .map(|(_, volume)| *volume)
+                    })
+                    .or_else(|| per_device.first().map(|(_, volume)| *volume));
+            }
+            streams.push(stream);
+        }
+    };
+
+    for line in output.lines() {
+        let trimmed = line.trim();
+        if let Some(name) = trimmed
+            .strip_prefix("- STREAM_")
+            .and_then(|rest| rest.strip_suffix(':'))
+        {
+            finish(current.take(), &mut streams);
+            current = Some((
+                AudioStreamState {
+                    stream: name.to_ascii_lowercase(),
+                    volume: None,
+                    min: None,
+                    max: None,
+                    muted: false,
+                    devices: Vec::new(),
+                },
+                Vec::new(),
+            ));
+            continue;
+        }
+        let Some((stream, per_device)) = current.as_mut() else {
+            continue;
+        };
+        if trimmed.is_empty() || trimmed.starts_with('-') || !line.starts_with(' ') {
+            finish(current.take(), &mut streams);
+            continue;
+        }
+        let Some((key, value)) = trimmed.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        match key.trim() {
+            "Muted" => stream.muted = value == "true",
+            "Min" => stream.min = value.parse().ok(),
+            "Max" => stream.max = value.parse().ok(),
+            "streamVolume" => stream.volume = value.parse().ok(),
+            "Devices" => {
+                stream.devices = value
+                    .split([',', ' '])
+                    .map(str::trim)
+                    .filter(|device| !device.is_empty())
+                    .map(str::to_string)
+                    .collect()
+            }
+            "Current" => {
+                // `2 (speaker): 7, 40000000 (default): 7`
+                for part in value.split(", ") {
+                    let Some((device, volume)) = part.split_once("):") else {
+                        continue;
+                    };
+                    let Some((_, name)) = device.split_once('(') else {
+                        continue;
+                    };
+                    if let Ok(volume) = volume.trim().parse() {
+                        per_device.push((name.trim().to_string(), volume));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    finish(current, &mut streams);
+    streams
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_stream_volumes_and_routes() {
+        let output = concat!(
+            "Stream volumes (device: index)\n",
+            "- STREAM_VOICE_CALL:\n",
+            "   Muted: false\n",
+            "   Min: 1\n",
+            "   Max: 5\n",
+            "   streamVolume:4\n",
+            "   Current: 1 (earpiece): 4, 2 (speaker): 4, 40000000 (default): 4\n",
+            "   Devices: earpiece\n",
+            "- STREAM_MUSIC:\n",
+            "   Muted: true\n",
+            "   Min: 0\n",
+            "   Max: 15\n",
+            "   Current: 2 (speaker): 11, 80 (bt_a2dp): 6, 40000000 (default): 11\n",
+            "   Devices: bt_a2dp\n",
+            "\n",
+            "- mute affected streams = 0x2e\n",
+        );
+        let streams = parse_audio_streams(output);
+        assert_eq!(streams.len(), 2);
+        assert_eq!(streams[0].stream, "voice_call");
+        assert_eq!(
+            (streams[0].min, streams[0].max, streams[0].volume),
+            (Some(1), Some(5), Some(4))
+        );
+        assert_eq!(streams[1].stream, "music");
+        assert!(streams[1].muted);
+        assert_eq!(streams[1].devices, ["bt_a2dp"]);
+        assert_eq!(streams[1].volume, Some(6));
+
+        assert_eq!(stream_id(" Music "), Ok(3));
+        assert!(stream_id("bogus").is_err());
+        let [primary, fallback] = set_volume_commands(3, 7);
+        assert_eq!(
+            primary,
+            [
+                "cmd",
+                "media_session",
+                "volume",
+                "--stream",
+                "3",
+                "--set",
+                "7"
+            ]
+        );
+        assert_eq!(fallback[0], "media");
+    }
+}
@@ -0,0 +1,96 @@
+use std::net::Ipv4Addr;
+
+use crate::app::adb::locator::{transport_for_serial, TRANSPORT_EMULATOR, TRANSPORT_TCP};
+
+/// Wireless debugging with pairing codes (`adb pair`) arrived in Android 11.
+pub const MIN_PAIRING_API_LEVEL: u32 = 30;
+pub const DEFAULT_TCPIP_PORT: u16 = 5555;
+
+pub const WIRELESS_FLOW_PAIRING: &str = "pairing";
+pub const WIRELESS_FLOW_TCPIP: &str = "tcpip";
+pub const WIRELESS_FLOW_CONNECTED: &str = "already_wireless";
+pub const WIRELESS_FLOW_UNSUPPORTED: &str = "unsupported";
+
+/// `already_wireless` and `unsupported` (emulators) come from the serial alone. Otherwise
+/// Android 11+ uses pairing codes and older devices need `adb tcpip` over USB first. An
+/// unknown API level falls back to tcpip, which works on every version.
+pub fn wireless_flow(serial: &str, api_level: Option<u32>) -> &'static str {
+    match transport_for_serial(serial) {
+        TRANSPORT_TCP => WIRELESS_FLOW_CONNECTED,
+        TRANSPORT_EMULATOR => WIRELESS_FLOW_UNSUPPORTED,
+        _ if api_level.is_some_and(|level| level >= MIN_PAIRING_API_LEVEL) => WIRELESS_FLOW_PAIRING,
+        _ => WIRELESS_FLOW_TCPIP,
+    }
+}
+
+/// First non-loopback IPv4 from `ip -f inet addr show wlan0` (`inet 192.168.1.23/24 ...`).
+pub fn parse_wlan_ipv4(output: &str) -> Option<Ipv4Addr> {
+    output.lines().find_map(|line| {
+        let address = line
+            .trim()
+            .strip_prefix("inet ")?
+            .split_whitespace()
+            .next()?;
+        let ip: Ipv4Addr = address.split('/').next()?.parse().ok()?;
+        (!ip.is_loopback() && !ip.is_unspecified()).then_some(ip)
+    })
+}
+
+/// `adb tcpip` prints `restarting in TCP mode port: 5555` on success; older adb versions
+/// print nothing and exit 0.
+pub fn tcpip_failed(stdout: &str, stderr: &str, exit_code: Option<i32>) -> bool {
+    let combined = format!("{stdout}{stderr}").to_lowercase();
+    exit_code.unwrap_or_default() != 0 || combined.contains("error") || combined.contains("failed")
+}
+
+pub fn tcpip_address(ip: Ipv4Addr, port: u16) -> String {
+    format!("{ip}:{port}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn picks_flow_from_serial_and_api_level() {
+        assert_eq!(wireless_flow("R58M123", Some(33)), WIRELESS_FLOW_PAIRING);
+        assert_eq!(wireless_flow("R58M123", Some(29)), WIRELESS_FLOW_TCPIP);
+        assert_eq!(wireless_flow("R58M123", None), WIRELESS_FLOW_TCPIP);
+        assert_eq!(
+            wireless_flow("192.168.1.5:5555", Some(29)),
+            WIRELESS_FLOW_CONNECTED
+        );
+        assert_eq!(
+            wireless_flow("emulator-5554", Some(34)),
+            WIRELESS_FLOW_UNSUPPORTED
+        );
+    }
+
+    #[test]
+    fn parses_wlan_address() {
+        let output = "30: wlan0: <BROADCAST,MULTICAST,UP,LOWER_UP> mtu 1500\n    inet 192.168.1.23/24 brd 192.168.1.255 scope global wlan0\n       valid_lft forever preferred_lft forever\n";
+        assert_eq!(
+            parse_wlan_ipv4(output),
+            Some(Ipv4Addr::new(192, 168, 1, 23))
+        );
+        assert_eq!(
+            parse_wlan_ipv4("    inet 127.0.0.1/8 scope host lo\n"),
+            None
+        );
+        assert_eq!(parse_wlan_ipv4("Device \"wlan0\" does not exist.\n"), None);
+        assert_eq!(
+            tcpip_address(Ipv4Addr::new(10, 0, 0, 2), DEFAULT_TCPIP_PORT),
+            "10.0.0.2:5555"
+        );
+        assert!(!tcpip_failed(
+            "restarting in TCP mode port: 5555\n",
+            "",
+            Some(0)
+        ));
+        assert!(tcpip_failed(
+            "",
+            "error: no devices/emulators found\n",
+            Some(1)
+        ));
+    }
+}
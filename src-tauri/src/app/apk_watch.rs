@@ -0,0 +1,120 @@
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+
+use notify::{Event, EventKind};
+
+/// Quiet period after the last filesystem event before the APK is treated as fully written.
+pub const APK_WATCH_DEBOUNCE: Duration = Duration::from_millis(1500);
+pub const APK_WATCH_POLL: Duration = Duration::from_millis(200);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FileStamp {
+    pub len: u64,
+    pub modified: Option<SystemTime>,
+}
+
+pub fn file_stamp(path: &Path) -> Option<FileStamp> {
+    let metadata = std::fs::metadata(path).ok()?;
+    if !metadata.is_file() || metadata.len() == 0 {
+        return None;
+    }
+    Some(FileStamp {
+        len: metadata.len(),
+        modified: metadata.modified().ok(),
+    })
+}
+
+/// Build tools often write the APK to a temp file and rename it into place, so the parent
+/// directory is watched and events are matched by file name.
+pub fn event_touches_apk(event: &Event, apk_path: &Path) -> bool {
+    if matches!(event.kind, EventKind::Access(_)) {
+        return false;
+    }
+    let Some(target) = apk_path.file_name() else {
+        return false;
+    };
+    event
+        .paths
+        .iter()
+        .any(|path| path.file_name() == Some(target))
+}
+
+/// Whether a pending change should trigger an install now: the quiet period has elapsed and
+/// the file differs from what was last installed.
+pub fn should_install(
+    quiet_for: Duration,
+    current: Option<FileStamp>,
+    last_installed: Option<FileStamp>,
+) -> bool {
+    quiet_for >= APK_WATCH_DEBOUNCE && current.is_some() && current != last_installed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use notify::event::{AccessKind, CreateKind, ModifyKind};
+    use std::path::PathBuf;
+
+    fn event(kind: EventKind, path: &str) -> Event {
+        Event::new(kind).add_path(PathBuf::from(path))
+    }
+
+    #[test]
+    fn matches_events_for_the_watched_apk() {
+        let apk = PathBuf::from("/build/outputs/app-debug.apk");
+        assert!(event_touches_apk(
+            &event(
+                EventKind::Create(CreateKind::File),
+                "/build/outputs/app-debug.apk"
+            ),
+            &apk
+        ));
+        assert!(event_touches_apk(
+            &event(
+                EventKind::Modify(ModifyKind::Any),
+                "/build/outputs/app-debug.apk"
+            ),
+            &apk
+        ));
+        assert!(!event_touches_apk(
+            &event(
+                EventKind::Modify(ModifyKind::Any),
+                "/build/outputs/output.json"
+            ),
+            &apk
+        ));
+        assert!(!event_touches_apk(
+            &event(
+                EventKind::Access(AccessKind::Any),
+                "/build/outputs/app-debug.apk"
+            ),
+            &apk
+        ));
+    }
+
+    #[test]
+    fn installs_only_after_quiet_period_and_on_change() {
+        let stamp = |len| {
+            Some(FileStamp {
+                len,
+                modified: None,
+            })
+        };
+        assert!(!should_install(Duration::from_millis(500), stamp(10), None));
+        assert!(should_install(APK_WATCH_DEBOUNCE, stamp(10), None));
+        assert!(!should_install(APK_WATCH_DEBOUNCE, stamp(10), stamp(10)));
+        assert!(should_install(APK_WATCH_DEBOUNCE, stamp(11), stamp(10)));
+        assert!(!should_install(APK_WATCH_DEBOUNCE, None, stamp(10)));
+    }
+
+    #[test]
+    fn ignores_missing_or_empty_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let apk = dir.path().join("app.apk");
+        assert_eq!(file_stamp(&apk), None);
+        std::fs::write(&apk, b"").unwrap();
+        assert_eq!(file_stamp(&apk), None);
+        std::fs::write(&apk, b"PK").unwrap();
+        assert_eq!(file_stamp(&apk).map(|stamp| stamp.len), Some(2));
+    }
+}
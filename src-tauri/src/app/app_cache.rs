@@ -0,0 +1,375 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use chrono::{SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::app::adb::paths::sanitize_filename_component;
+use crate::app::models::IconCacheStats;
+
+/// Shared cap for the per-device icon cache and the app metadata cache.
+pub const ICON_CACHE_MAX_BYTES: u64 = 64 * 1024 * 1024;
+
+/// Label and icon for one (package, versionCode) pair. A new versionCode gets a fresh entry, so
+/// app updates never serve stale metadata.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CachedAppMetadata {
+    pub package_name: String,
+    pub version_code: String,
+    pub label: Option<String>,
+    pub icon_file: Option<String>,
+    pub cached_at: String,
+}
+
+pub fn app_icon_cache_dir() -> PathBuf {
+    let base = dirs::cache_dir().unwrap_or_else(std::env::temp_dir);
+    base.join("lazy_blacktea").join("app_icons")
+}
+
+pub fn app_metadata_cache_dir() -> PathBuf {
+    let base = dirs::cache_dir().unwrap_or_else(std::env::temp_dir);
+    base.join("lazy_blacktea").join("app_metadata")
+}
+
+pub fn app_cache_key(package_name: &str, version_code: Option<&str>) -> String {
+    format!(
+        "{}@{}",
+        sanitize_filename_component(package_name),
+        sanitize_filename_component(version_code.unwrap_or("0"))
+    )
+}
+
+pub fn load_cached_metadata(dir: &Path, key: &str) -> Option<CachedAppMetadata> {
+    let raw = fs::read_to_string(dir.join(format!("{key}.json"))).ok()?;
+    let entry: CachedAppMetadata = serde_json::from_str(&raw).ok()?;
+    // Drop entries whose icon file went missing so the caller refetches them.
+    if let Some(icon) = &entry.icon_file {
+        let icon_path = dir.join(icon);
+        if !icon_path.is_file() {
+            return None;
+        }
+        touch_cache_file(&icon_path);
+    }
+    touch_cache_file(&dir.join(format!("{key}.json")));
+    Some(entry)
+}
+
+pub fn icon_cache_dirs() -> [PathBuf; 2] {
+    [app_icon_cache_dir(), app_metadata_cache_dir()]
+}
+
+/// Write the icon (if any) and the metadata record for `key`.
+pub fn store_cached_metadata(
+    dir: &Path,
+    key: &str,
+    package_name: &str,
+    version_code: Option<&str>,
+    label: Option<String>,
+    icon: Option<(&str, &[u8])>,
+) -> std::io::Result<CachedAppMetadata> {
+    fs::create_dir_all(dir)?;
+    let icon_file = match icon {
+        Some((ext, bytes)) => {
+            let name = format!("{key}.{ext}");
+            fs::write(dir.join(&name), bytes)?;
+            Some(name)
+        }
+        None => None,
+    };
+    let entry = CachedAppMetadata {
+        package_name: package_name.to_string(),
+        version_code: version_code.unwrap_or("0").to_string(),
+        label,
+        icon_file,
+        cached_at: Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true),
+    };
+    let json = serde_json::to_string(&entry).map_err(std::io::Error::other)?;
+    fs::write(dir.join(format!("{key}.json")), json)?;
+    remove_other_versions(dir, key);
+    Ok(entry)
+}
+
+/// Delete cached files for the same package under a different versionCode.
+pub fn remove_other_versions(dir: &Path, key: &str) {
+    let Some((package, _)) = key.split_once('@') else {
+        return;
+    };
+    let prefix = format!("{package}@");
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        let stem = name.rsplit_once('.').map(|(stem, _)| stem).unwrap_or(&name);
+        if name.starts_with(&prefix) && stem != key {
+            if let Err(err) = fs::remove_file(entry.path()) {
+                warn!(path = %entry.path().display(), error = %err, "failed to remove stale cache file");
+            }
+        }
+    }
+}
+
+/// Bump the modification time so LRU eviction keeps recently served entries.
+pub fn touch_cache_file(path: &Path) {
+    let result = fs::File::options()
+        .write(true)
+        .open(path)
+        .and_then(|file| file.set_modified(SystemTime::now()));
+    if let Err(err) = result {
+        warn!(path = %path.display(), error = %err, "failed to touch cache file");
+    }
+}
+
+fn collect_cache_files(dir: &Path, files: &mut Vec<(PathBuf, u64, SystemTime)>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        if metadata.is_dir() {
+            collect_cache_files(&path, files);
+        } else {
+            let modified = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+            files.push((path, metadata.len(), modified));
+        }
+    }
+}
+
+pub fn icon_cache_stats(dirs: &[PathBuf], max_bytes: u64) -> IconCacheStats {
+    let mut files = Vec::new();
+    for dir in dirs {
+        collect_cache_files(dir, &mut files);
+    }
+    IconCacheStats {
+        file_count: files.len(),
+        total_bytes: files.iter().map(|(_, len, _)| len).sum(),
+        max_bytes,
+    }
+}
+
+/// Remove least recently used files until the caches fit in `max_bytes`. Returns files removed.
+pub fn evict_icon_cache(dirs: &[PathBuf], max_bytes: u64) -> usize {
+    let mut files = Vec::new();
+    for dir in dirs {
+        collect_cache_files(dir, &mut files);
+    }
+    let mut total: u64 = files.iter().map(|(_, len, _)| len).sum();
+    if total <= max_bytes {
+        return 0;
+    }
+    files.sort_by_key(|(_, _, modified)| *modified);
+    let mut removed = 0;
+    for (path, len, _) in files {
+        if total <= max_bytes {
+            break;
+        }
+        match fs::remove_file(&path) {
+            Ok(()) => {
+                total = total.saturating_sub(len);
+                removed += 1;
+            }
+            Err(err) => warn!(path = %path.display(), error = %err, "failed to evict cache file"),
+        }
+    }
+    removed
+}
+
+/// Read `application-label` from `aapt dump badging` / `aapt2 dump badging` output.
+pub fn parse_badging_label(output: &str) -> Option<String> {
+    let mut localized = None;
+    for line in output.lines() {
+        let trimmed = line.trim();
+        if let Some(value) = trimmed.strip_prefix("application-label:") {
+            let value = value.trim().trim_matches('\'');
+            if !value.is_empty() {
+                return Some(value.to_string());
+            }
+        }
+        if localized.is_none() && trimmed.starts_with("application-label-") {
+            if let Some((_, value)) = trimmed.split_once(':') {
+                let value = value.trim().trim_matches('\'');
+                if !value.is_empty() {
+                    localized = Some(value.to_string());
+                }
+            }
+        }
+    }
+    localized
+}
+
+fn version_key(name: &str) -> Vec<u64> {
+    name.split(['.', '-'])
+        .map(|part| part.parse::<u64>().unwrap_or(0))
+        .collect()
+}
+
+/// Find `aapt2`/`aapt` in the newest SDK build-tools. The SDK root comes from
+/// `ANDROID_HOME`/`ANDROID_SDK_ROOT` or from the adb binary living in `<sdk>/platform-tools`.
+/// SDK roots from `ANDROID_HOME`/`ANDROID_SDK_ROOT`, plus the SDK that contains the configured
+/// adb when it lives in `platform-tools`.
+pub fn android_sdk_roots(adb_program: &str) -> Vec<PathBuf> {
+    let mut roots: Vec<PathBuf> = ["ANDROID_HOME", "ANDROID_SDK_ROOT"]
+        .iter()
+        .filter_map(|name| std::env::var_os(name).map(PathBuf::from))
+        .collect();
+    if let Some(sdk) = Path::new(adb_program)
+        .parent()
+        .filter(|dir| dir.file_name().is_some_and(|name| name == "platform-tools"))
+        .and_then(Path::parent)
+    {
+        roots.push(sdk.to_path_buf());
+    }
+    roots
+}
+
+pub fn find_aapt(adb_program: &str) -> Option<PathBuf> {
+    android_sdk_roots(adb_program)
+        .iter()
+        .find_map(|root| find_aapt_in_sdk(root))
+}
+
+pub fn find_aapt_in_sdk(sdk_root: &Path) -> Option<PathBuf> {
+    let mut versions: Vec<PathBuf> = fs::read_dir(sdk_root.join("build-tools"))
+        .ok()?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_dir())
+        .collect();
+    versions.sort_by_key(|path| {
+        version_key(
+            &path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default(),
+        )
+    });
+    let suffix = if cfg!(windows) { ".exe" } else { "" };
+    versions.iter().rev().find_map(|dir| {
+        ["aapt2", "aapt"]
+            .iter()
+            .map(|name| dir.join(format!("{name}{suffix}")))
+            .find(|path| path.is_file())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn app_cache_key_includes_version_code() {
+        assert_eq!(app_cache_key("com.example", Some("42")), "com.example@42");
+        assert_eq!(app_cache_key("com.example", None), "com.example@0");
+    }
+
+    #[test]
+    fn cached_metadata_round_trips_and_drops_missing_icons() {
+        let temp = TempDir::new().expect("temp");
+        let key = app_cache_key("com.example", Some("7"));
+        let stored = store_cached_metadata(
+            temp.path(),
+            &key,
+            "com.example",
+            Some("7"),
+            Some("Example".to_string()),
+            Some(("png", b"icon".as_slice())),
+        )
+        .expect("store");
+        assert_eq!(stored.icon_file.as_deref(), Some("com.example@7.png"));
+        assert_eq!(load_cached_metadata(temp.path(), &key), Some(stored));
+
+        fs::remove_file(temp.path().join("com.example@7.png")).expect("remove");
+        assert_eq!(load_cached_metadata(temp.path(), &key), None);
+        assert_eq!(load_cached_metadata(temp.path(), "com.example@8"), None);
+    }
+
+    #[test]
+    fn store_cached_metadata_drops_other_versions() {
+        let temp = TempDir::new().expect("temp");
+        let old_key = app_cache_key("com.example", Some("1"));
+        store_cached_metadata(
+            temp.path(),
+            &old_key,
+            "com.example",
+            Some("1"),
+            None,
+            Some(("png", b"old".as_slice())),
+        )
+        .expect("store old");
+        let other_key = app_cache_key("com.example.other", Some("1"));
+        store_cached_metadata(
+            temp.path(),
+            &other_key,
+            "com.example.other",
+            Some("1"),
+            None,
+            None,
+        )
+        .expect("store other");
+        let new_key = app_cache_key("com.example", Some("2"));
+        store_cached_metadata(temp.path(), &new_key, "com.example", Some("2"), None, None)
+            .expect("store new");
+
+        assert!(load_cached_metadata(temp.path(), &old_key).is_none());
+        assert!(!temp.path().join("com.example@1.png").exists());
+        assert!(load_cached_metadata(temp.path(), &other_key).is_some());
+        assert!(load_cached_metadata(temp.path(), &new_key).is_some());
+    }
+
+    #[test]
+    fn evict_icon_cache_removes_least_recently_used() {
+        let temp = TempDir::new().expect("temp");
+        let dir = temp.path().join("serial");
+        fs::create_dir_all(&dir).expect("mkdir");
+        for (name, age_secs) in [("old.png", 300), ("mid.png", 200), ("new.png", 100)] {
+            let path = dir.join(name);
+            fs::write(&path, [0u8; 100]).expect("write");
+            let modified = SystemTime::now() - std::time::Duration::from_secs(age_secs);
+            fs::File::options()
+                .write(true)
+                .open(&path)
+                .and_then(|file| file.set_modified(modified))
+                .expect("set mtime");
+        }
+        touch_cache_file(&dir.join("old.png"));
+
+        let dirs = [temp.path().to_path_buf()];
+        assert_eq!(icon_cache_stats(&dirs, 250).total_bytes, 300);
+        assert_eq!(evict_icon_cache(&dirs, 250), 1);
+        assert!(!dir.join("mid.png").exists());
+        assert!(dir.join("old.png").exists());
+        let stats = icon_cache_stats(&dirs, 250);
+        assert_eq!(stats.file_count, 2);
+        assert_eq!(stats.total_bytes, 200);
+    }
+
+    #[test]
+    fn parse_badging_label_prefers_default_label() {
+        let output = "package: name='com.example' versionCode='7'\napplication-label-de:'Beispiel'\napplication-label:'Example'\n";
+        assert_eq!(parse_badging_label(output).as_deref(), Some("Example"));
+        assert_eq!(
+            parse_badging_label("application-label-de:'Beispiel'\n").as_deref(),
+            Some("Beispiel")
+        );
+        assert_eq!(parse_badging_label("package: name='x'"), None);
+    }
+
+    #[test]
+    fn find_aapt_in_sdk_picks_newest_build_tools() {
+        let temp = TempDir::new().expect("temp");
+        let suffix = if cfg!(windows) { ".exe" } else { "" };
+        for version in ["9.0.0", "34.0.0", "30.0.3"] {
+            let dir = temp.path().join("build-tools").join(version);
+            fs::create_dir_all(&dir).expect("mkdir");
+            fs::write(dir.join(format!("aapt{suffix}")), b"").expect("write");
+        }
+        let found = find_aapt_in_sdk(temp.path()).expect("aapt");
+        assert!(found.to_string_lossy().contains("34.0.0"));
+        assert_eq!(find_aapt_in_sdk(&temp.path().join("missing")), None);
+    }
+}
@@ -4,10 +4,12 @@ use std::sync::Arc;
 use std::thread::{self, JoinHandle};
 use std::time::{Duration, Instant};
 
-use tauri::{AppHandle, Emitter};
+use tauri::AppHandle;
 use tracing::warn;
 
+use crate::app::adb::locator::adb_command_for_serial;
 use crate::app::adb::runner::run_command_with_timeout;
+use crate::app::metrics::emit_tracked;
 
 use super::models::{ParsedEvent, ParsedSnapshot, StateSummary};
 use super::parser::BluetoothParser;
@@ -116,7 +118,7 @@ pub fn start_bluetooth_monitor(
             "-b".to_string(),
             "all".to_string(),
         ];
-        let mut child = match std::process::Command::new(&adb_program_logcat)
+        let mut child = match adb_command_for_serial(&adb_program_logcat, &serial_logcat)
             .args(&args)
             .stdout(std::process::Stdio::piped())
             .stderr(std::process::Stdio::piped())
@@ -167,7 +169,7 @@ fn emit_snapshot(app: &AppHandle, snapshot: ParsedSnapshot, trace_id: &str) {
         "trace_id": trace_id,
         "snapshot": snapshot,
     });
-    let _ = app.emit("bluetooth-snapshot", payload);
+    let _ = emit_tracked(app, "bluetooth-snapshot", payload);
 }
 
 fn emit_event(app: &AppHandle, event: ParsedEvent, trace_id: &str) {
@@ -175,7 +177,7 @@ fn emit_event(app: &AppHandle, event: ParsedEvent, trace_id: &str) {
         "trace_id": trace_id,
         "event": event,
     });
-    let _ = app.emit("bluetooth-event", payload);
+    let _ = emit_tracked(app, "bluetooth-event", payload);
 }
 
 fn emit_state(app: &AppHandle, summary: StateSummary, trace_id: &str) {
@@ -183,7 +185,7 @@ fn emit_state(app: &AppHandle, summary: StateSummary, trace_id: &str) {
         "trace_id": trace_id,
         "state": summary,
     });
-    let _ = app.emit("bluetooth-state", payload);
+    let _ = emit_tracked(app, "bluetooth-state", payload);
 }
 
 fn adjust_interval(current: f64, last_activity: Option<Instant>, now: Instant) -> f64 {
@@ -2,6 +2,7 @@ use crate::app::models::{
     BugreportLogAroundPage, BugreportLogFilters, BugreportLogMatch, BugreportLogPage,
     BugreportLogRow, BugreportLogSearchResult, BugreportLogSummary,
 };
+use chrono::{Local, NaiveDateTime, TimeZone};
 use dirs::home_dir;
 use regex::{Regex, RegexBuilder};
 use rusqlite::functions::FunctionFlags;
@@ -9,7 +10,7 @@ use rusqlite::{params, Connection};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs::{self, File};
-use std::io::{BufRead, BufReader, Read};
+use std::io::{BufRead, BufReader, BufWriter, Read, Write};
 use std::path::{Path, PathBuf};
 use std::time::UNIX_EPOCH;
 use tracing::warn;
@@ -24,9 +25,15 @@ const DEFAULT_QUERY_LIMIT: usize = 200;
 const MAX_QUERY_LIMIT: usize = 500;
 const MAX_REGEX_FILTERS: usize = 20;
 const MAX_REGEX_PATTERN_LEN: usize = 512;
-const CACHE_SCHEMA_VERSION: u32 = 2;
+const CACHE_SCHEMA_VERSION: u32 = 3;
 const MAX_INDEX_LINE_BYTES: usize = 1_000_000;
 
+pub const LOGCAT_EXPORT_THREADTIME: &str = "threadtime";
+/// The JSON `.logcat` layout Android Studio writes from "Export to file" and re-opens.
+pub const LOGCAT_EXPORT_ANDROID_STUDIO: &str = "android_studio";
+pub const LOGCAT_EXPORT_FORMATS: [&str; 2] =
+    [LOGCAT_EXPORT_THREADTIME, LOGCAT_EXPORT_ANDROID_STUDIO];
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct LogcatCacheMeta {
     #[serde(default)]
@@ -244,6 +251,207 @@ pub fn query_bugreport_logcat_around(
     query_bugreport_logcat_around_connection(&connection, anchor_id, before, after, filters)
 }
 
+/// Index a saved `adb logcat` text file (threadtime, with or without color codes and the
+/// `year`/`usec`/`uid`/`zone` columns) so it can be browsed like a bugreport.
+pub fn import_logcat_file(
+    source_path: &Path,
+    trace_id: &str,
+) -> Result<BugreportLogSummary, String> {
+    if is_zip(source_path) {
+        return Err(validation_error(
+            "Zip files are bugreports; open them as a bugreport instead",
+        ));
+    }
+    let summary = prepare_bugreport_logcat(source_path, trace_id)?;
+    if summary.total_rows == 0 {
+        return Err(validation_error(
+            "No logcat lines found; expected `adb logcat -v threadtime` output",
+        ));
+    }
+    Ok(summary)
+}
+
+/// Write the rows matching `filters` to `output_path` as threadtime text or as an Android
+/// Studio `.logcat` file. Lines without a year are dated in `year`. Returns the row count.
+pub fn export_bugreport_logcat(
+    report_id: &str,
+    filters: BugreportLogFilters,
+    format: &str,
+    output_path: &Path,
+    year: i32,
+) -> Result<usize, String> {
+    if !LOGCAT_EXPORT_FORMATS.contains(&format) {
+        return Err(validation_error(format!(
+            "Export format must be one of {}",
+            LOGCAT_EXPORT_FORMATS.join(", ")
+        )));
+    }
+    let cache_dir = cache_dir_for_report(report_id)?;
+    let db_path = cache_dir.join("logcat.db");
+    if !db_path.exists() {
+        return Err("Bugreport log index not found. Load a bugreport first.".to_string());
+    }
+    let source_name = load_meta(&cache_dir.join("meta.json"))
+        .ok()
+        .flatten()
+        .and_then(|meta| {
+            Path::new(&meta.source_path)
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+        })
+        .unwrap_or_else(|| report_id.to_string());
+    let connection =
+        Connection::open(db_path).map_err(|err| format!("Failed to open logcat index: {err}"))?;
+
+    let part = output_path.with_file_name(format!(
+        "{}.part",
+        output_path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default()
+    ));
+    let file = File::create(&part).map_err(|err| format!("Failed to create export file: {err}"))?;
+    let mut writer = BufWriter::new(file);
+    let result = export_logcat_rows(
+        &connection,
+        filters,
+        format,
+        year,
+        &source_name,
+        &mut writer,
+    )
+    .and_then(|rows| {
+        writer
+            .flush()
+            .map_err(|err| format!("Failed to write export file: {err}"))?;
+        Ok(rows)
+    });
+    drop(writer);
+    match result {
+        Ok(rows) => {
+            fs::rename(&part, output_path)
+                .map_err(|err| format!("Failed to save export file: {err}"))?;
+            Ok(rows)
+        }
+        Err(err) => {
+            let _ = fs::remove_file(&part);
+            Err(err)
+        }
+    }
+}
+
+fn export_logcat_rows<W: Write>(
+    connection: &Connection,
+    filters: BugreportLogFilters,
+    format: &str,
+    year: i32,
+    source_name: &str,
+    out: &mut W,
+) -> Result<usize, String> {
+    let write_error = |err: std::io::Error| format!("Failed to write export file: {err}");
+    let (has_regex_include, has_regex_exclude) = attach_regex_filters(connection, &filters)?;
+    let (sql, params) = build_filtered_select_sql(filters, has_regex_include, has_regex_exclude);
+    let mut stmt = connection
+        .prepare(&sql)
+        .map_err(|err| format!("Failed to prepare logcat query: {err}"))?;
+    let rows_iter = stmt
+        .query_map(rusqlite::params_from_iter(params.iter()), |row| {
+            Ok(BugreportLogRow {
+                id: row.get(0)?,
+                ts: row.get(1)?,
+                level: row.get(2)?,
+                tag: row.get(3)?,
+                buffer: row.get(4)?,
+                pid: row.get(5)?,
+                tid: row.get(6)?,
+                msg: row.get(7)?,
+                raw_line: row.get(8)?,
+            })
+        })
+        .map_err(|err| format!("Failed to execute logcat query: {err}"))?;
+
+    let android_studio = format == LOGCAT_EXPORT_ANDROID_STUDIO;
+    if android_studio {
+        let metadata = serde_json::json!({
+            "device": { "deviceId": source_name, "name": source_name, "isOnline": false },
+            "filter": "",
+            "projectApplicationIds": [],
+        });
+        write!(out, "{{\"metadata\":{metadata},\"logcatMessages\":[").map_err(write_error)?;
+    }
+    let mut count = 0usize;
+    let mut last_buffer = String::new();
+    for row in rows_iter {
+        let row = row.map_err(|err| format!("Failed to read logcat row: {err}"))?;
+        if android_studio {
+            if count > 0 {
+                out.write_all(b",").map_err(write_error)?;
+            }
+            serde_json::to_writer(&mut *out, &android_studio_message(&row, year))
+                .map_err(|err| format!("Failed to write export file: {err}"))?;
+        } else {
+            if row.buffer != last_buffer && row.buffer != "unknown" {
+                writeln!(out, "--------- beginning of {}", row.buffer).map_err(write_error)?;
+                last_buffer = row.buffer.clone();
+            }
+            writeln!(out, "{}", threadtime_line(&row)).map_err(write_error)?;
+        }
+        count += 1;
+    }
+    if android_studio {
+        out.write_all(b"]}").map_err(write_error)?;
+    }
+    Ok(count)
+}
+
+/// `logcat -v threadtime` layout: tags are padded to 8 columns before the colon.
+fn threadtime_line(row: &BugreportLogRow) -> String {
+    format!(
+        "{} {:>5} {:>5} {} {:<8}: {}",
+        row.ts, row.pid, row.tid, row.level, row.tag, row.msg
+    )
+}
+
+/// `ts_raw` as a date, borrowing `year` when the line did not carry one.
+fn parse_row_datetime(ts_raw: &str, year: i32) -> Option<NaiveDateTime> {
+    let dated = if ts_without_year(ts_raw).len() == ts_raw.len() {
+        format!("{year:04}-{ts_raw}")
+    } else {
+        ts_raw.to_string()
+    };
+    NaiveDateTime::parse_from_str(&dated, "%Y-%m-%d %H:%M:%S%.f").ok()
+}
+
+fn android_studio_level(level: &str) -> &'static str {
+    match level {
+        "V" => "VERBOSE",
+        "D" => "DEBUG",
+        "I" => "INFO",
+        "W" => "WARN",
+        "E" => "ERROR",
+        _ => "ASSERT",
+    }
+}
+
+fn android_studio_message(row: &BugreportLogRow, year: i32) -> serde_json::Value {
+    let (seconds, nanos) = parse_row_datetime(&row.ts, year)
+        .and_then(|naive| Local.from_local_datetime(&naive).earliest())
+        .map(|local| (local.timestamp(), local.timestamp_subsec_nanos()))
+        .unwrap_or_default();
+    serde_json::json!({
+        "header": {
+            "logLevel": android_studio_level(&row.level),
+            "pid": row.pid,
+            "tid": row.tid,
+            "applicationId": "",
+            "processName": "",
+            "tag": row.tag,
+            "timestamp": { "seconds": seconds, "nanos": nanos },
+        },
+        "message": row.msg,
+    })
+}
+
 fn search_bugreport_logcat_connection(
     connection: &Connection,
     query: &str,
@@ -543,6 +751,30 @@ fn build_query_sql(
     limit: usize,
     has_regex_include: bool,
     has_regex_exclude: bool,
+) -> (String, Vec<rusqlite::types::Value>) {
+    let (mut sql, mut params) =
+        build_filtered_select_sql(filters, has_regex_include, has_regex_exclude);
+    sql.push_str(" LIMIT ? OFFSET ?");
+
+    let normalized_limit = if limit == 0 {
+        DEFAULT_QUERY_LIMIT
+    } else {
+        limit
+    };
+    let normalized_limit = normalized_limit.clamp(1, MAX_QUERY_LIMIT);
+    params.push(rusqlite::types::Value::Integer(
+        (normalized_limit + 1) as i64,
+    ));
+    params.push(rusqlite::types::Value::Integer(offset as i64));
+
+    (sql, params)
+}
+
+/// Every row matching `filters` in log order, without paging.
+fn build_filtered_select_sql(
+    filters: BugreportLogFilters,
+    has_regex_include: bool,
+    has_regex_exclude: bool,
 ) -> (String, Vec<rusqlite::types::Value>) {
     let mut params: Vec<rusqlite::types::Value> = Vec::new();
     let mut clauses: Vec<String> = Vec::new();
@@ -636,18 +868,6 @@ fn build_query_sql(
     sql.push_str(&format!(
         " ORDER BY {LOGCAT_TABLE}.ts_key ASC, {LOGCAT_TABLE}.id ASC"
     ));
-    sql.push_str(" LIMIT ? OFFSET ?");
-
-    let normalized_limit = if limit == 0 {
-        DEFAULT_QUERY_LIMIT
-    } else {
-        limit
-    };
-    let normalized_limit = normalized_limit.clamp(1, MAX_QUERY_LIMIT);
-    params.push(rusqlite::types::Value::Integer(
-        (normalized_limit + 1) as i64,
-    ));
-    params.push(rusqlite::types::Value::Integer(offset as i64));
 
     (sql, params)
 }
@@ -942,6 +1162,7 @@ fn stream_logcat_lines<R: Read>(
             continue;
         }
         let line = String::from_utf8_lossy(&buffer);
+        let line = strip_ansi_escapes(&line);
         let trimmed = line.trim_end_matches(&['\n', '\r'][..]);
         if trimmed.is_empty() {
             continue;
@@ -1051,7 +1272,8 @@ fn update_time_range(
 fn parse_logcat_line(line: &str, regex: &Regex) -> Option<ParsedLogcatLine> {
     let caps = regex.captures(line)?;
     let ts_raw = format!("{} {}", &caps["date"], &caps["time"]);
-    let ts_key = parse_ts_key(&ts_raw).unwrap_or(0);
+    // `-v year` lines carry a leading `YYYY-`; the sort key only uses month onwards.
+    let ts_key = parse_ts_key(ts_without_year(&ts_raw)).unwrap_or(0);
     let level = caps["level"].to_string();
     let tag = caps["tag"].to_string();
     let pid = caps["pid"].parse().unwrap_or(0);
@@ -1093,6 +1315,38 @@ fn parse_ts_key(raw: &str) -> Option<u64> {
     Some((((((month * 100 + day) * 100 + hour) * 100 + minute) * 100 + second) * 1000) + millis)
 }
 
+fn ts_without_year(raw: &str) -> &str {
+    match raw.as_bytes() {
+        [y1, y2, y3, y4, b'-', ..] if [y1, y2, y3, y4].iter().all(|b| b.is_ascii_digit()) => {
+            &raw[5..]
+        }
+        _ => raw,
+    }
+}
+
+/// Drop the SGR color codes `logcat -v color` (and tools like logcat-color) wrap fields in.
+fn strip_ansi_escapes(line: &str) -> std::borrow::Cow<'_, str> {
+    if !line.contains('\x1b') {
+        return std::borrow::Cow::Borrowed(line);
+    }
+    let mut stripped = String::with_capacity(line.len());
+    let mut chars = line.chars();
+    while let Some(ch) = chars.next() {
+        if ch != '\x1b' {
+            stripped.push(ch);
+            continue;
+        }
+        if chars.next() == Some('[') {
+            for code in chars.by_ref() {
+                if ('@'..='~').contains(&code) {
+                    break;
+                }
+            }
+        }
+    }
+    std::borrow::Cow::Owned(stripped)
+}
+
 fn parse_two_digits(bytes: &[u8]) -> Option<u64> {
     if bytes.len() != 2 || !bytes[0].is_ascii_digit() || !bytes[1].is_ascii_digit() {
         return None;
@@ -1112,7 +1366,9 @@ fn parse_three_digits(bytes: &[u8]) -> Option<u64> {
 
 fn logcat_regex() -> Regex {
     Regex::new(
-        r"^(?P<date>\d{2}-\d{2})\s+(?P<time>\d{2}:\d{2}:\d{2}\.\d{3})\s+(?:\S+\s+)?(?P<pid>\d+)\s+(?P<tid>\d+)\s+(?P<level>[VDIWEF])\s+(?P<tag>[^:]+):\s(?P<msg>.*)$",
+        // threadtime, optionally with `-v year`, `usec`, `uid` and `zone` columns. Tags are
+        // padded to 8 characters by logcat; the padding is not part of the tag.
+        r"^(?P<date>(?:\d{4}-)?\d{2}-\d{2})\s+(?P<time>\d{2}:\d{2}:\d{2}\.\d{3,6})\s+(?:\S+\s+){0,2}?(?P<pid>\d+)\s+(?P<tid>\d+)\s+(?P<level>[VDIWEF])\s+(?P<tag>[^:]*?)\s*:\s(?P<msg>.*)$",
     )
     .expect("logcat regex should compile")
 }
@@ -1307,6 +1563,102 @@ mod tests {
         assert_eq!(parsed.msg, "ANR in com.foo");
     }
 
+    #[test]
+    fn parse_logcat_line_accepts_saved_logcat_variants() {
+        let regex = logcat_regex();
+        let colored = strip_ansi_escapes(
+            "\x1b[38;5;196m08-24 14:22:33.123  1234  5678 E ActivityManager: ANR\x1b[0m",
+        );
+        let parsed = parse_logcat_line(&colored, &regex).unwrap();
+        assert_eq!((parsed.level.as_str(), parsed.msg.as_str()), ("E", "ANR"));
+        assert!(!parsed.raw_line.contains('\x1b'));
+
+        let year = "2025-08-24 14:22:33.123456 u0_a12 +0200  1234  5678 I Tag     : padded";
+        let parsed = parse_logcat_line(year, &regex).unwrap();
+        assert_eq!(parsed.ts_raw, "2025-08-24 14:22:33.123456");
+        assert_eq!(parsed.ts_key, parse_ts_key("08-24 14:22:33.123").unwrap());
+        assert_eq!(parsed.tag, "Tag");
+        assert_eq!((parsed.pid, parsed.tid), (1234, 5678));
+        assert_eq!(parsed.msg, "padded");
+    }
+
+    #[test]
+    fn export_writes_threadtime_and_android_studio_files() {
+        let dir = TempDir::new().expect("tmp");
+        let source = dir.path().join("saved.txt");
+        let db_path = dir.path().join("logcat.db");
+        fs::write(
+            &source,
+            concat!(
+                "--------- beginning of main\n",
+                "\x1b[32m08-24 14:22:33.123  1234  5678 I Tag     : Hello\x1b[0m\n",
+                "--------- beginning of crash\n",
+                "08-24 14:22:34.500  1234  1234 F libc    : Fatal signal 11\n",
+            ),
+        )
+        .expect("write");
+        build_logcat_index(&source, &db_path, "saved", 0, 0).expect("index");
+        let conn = Connection::open(&db_path).expect("open");
+
+        let mut text = Vec::new();
+        let rows = export_logcat_rows(
+            &conn,
+            BugreportLogFilters::default(),
+            LOGCAT_EXPORT_THREADTIME,
+            2025,
+            "saved",
+            &mut text,
+        )
+        .expect("export");
+        assert_eq!(rows, 2);
+        assert_eq!(
+            String::from_utf8(text.clone()).unwrap(),
+            concat!(
+                "--------- beginning of main\n",
+                "08-24 14:22:33.123  1234  5678 I Tag     : Hello\n",
+                "--------- beginning of crash\n",
+                "08-24 14:22:34.500  1234  1234 F libc    : Fatal signal 11\n",
+            )
+        );
+
+        // The threadtime export indexes back into the same rows.
+        let exported = dir.path().join("exported.txt");
+        fs::write(&exported, &text).expect("write");
+        let meta = build_logcat_index(&exported, &dir.path().join("again.db"), "again", 0, 0)
+            .expect("index");
+        assert_eq!(meta.total_rows, 2);
+        assert_eq!(meta.buffers.get("crash").copied(), Some(1));
+
+        let mut json = Vec::new();
+        let filters = BugreportLogFilters {
+            levels: vec!["F".to_string()],
+            ..BugreportLogFilters::default()
+        };
+        export_logcat_rows(
+            &conn,
+            filters,
+            LOGCAT_EXPORT_ANDROID_STUDIO,
+            2025,
+            "saved",
+            &mut json,
+        )
+        .expect("export");
+        let parsed: serde_json::Value = serde_json::from_slice(&json).expect("json");
+        assert_eq!(parsed["metadata"]["device"]["name"], "saved");
+        let messages = parsed["logcatMessages"].as_array().unwrap();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0]["header"]["logLevel"], "ASSERT");
+        assert_eq!(messages[0]["header"]["tag"], "libc");
+        assert_eq!(messages[0]["header"]["timestamp"]["nanos"], 500_000_000);
+        assert_eq!(messages[0]["message"], "Fatal signal 11");
+
+        assert_eq!(
+            parse_row_datetime("08-24 14:22:34.500", 2025),
+            parse_row_datetime("2025-08-24 14:22:34.500", 1999)
+        );
+        assert!(parse_row_datetime("garbage", 2025).is_none());
+    }
+
     #[test]
     fn build_logcat_index_tracks_buffers_and_skips_begin_markers() {
         let dir = TempDir::new().expect("tmp");
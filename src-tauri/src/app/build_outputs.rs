@@ -0,0 +1,238 @@
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use tracing::warn;
+
+use crate::app::models::BuildVariantApk;
+
+pub const OUTPUT_METADATA_FILE: &str = "output-metadata.json";
+/// `apk/<flavor...>/<buildType>`; deeper trees are not produced by the Android Gradle plugin.
+const MAX_SCAN_DEPTH: usize = 4;
+
+pub fn build_outputs_apk_dir(project_dir: &Path, module: &str) -> PathBuf {
+    project_dir
+        .join(module)
+        .join("build")
+        .join("outputs")
+        .join("apk")
+}
+
+fn variant_name(apk_root: &Path, dir: &Path) -> String {
+    dir.strip_prefix(apk_root)
+        .map(|relative| {
+            relative
+                .components()
+                .map(|part| part.as_os_str().to_string_lossy().to_string())
+                .collect::<Vec<_>>()
+                .join("/")
+        })
+        .unwrap_or_default()
+}
+
+fn file_details(path: &Path) -> (u64, Option<String>) {
+    match std::fs::metadata(path) {
+        Ok(metadata) => (
+            metadata.len(),
+            metadata
+                .modified()
+                .ok()
+                .map(|modified| DateTime::<Utc>::from(modified).to_rfc3339()),
+        ),
+        Err(_) => (0, None),
+    }
+}
+
+/// Entries described by an AGP `output-metadata.json`; elements whose file is missing are
+/// skipped.
+pub fn parse_output_metadata(contents: &str, dir: &Path, variant: &str) -> Vec<BuildVariantApk> {
+    let Ok(value) = serde_json::from_str::<Value>(contents) else {
+        return Vec::new();
+    };
+    let application_id = value
+        .get("applicationId")
+        .and_then(Value::as_str)
+        .map(str::to_string);
+    let variant = value
+        .get("variantName")
+        .and_then(Value::as_str)
+        .filter(|name| !name.is_empty())
+        .unwrap_or(variant)
+        .to_string();
+    let Some(elements) = value.get("elements").and_then(Value::as_array) else {
+        return Vec::new();
+    };
+
+    elements
+        .iter()
+        .filter_map(|element| {
+            let output_file = element.get("outputFile")?.as_str()?;
+            let path = dir.join(output_file);
+            if !path.is_file() {
+                return None;
+            }
+            let filters = element
+                .get("filters")
+                .and_then(Value::as_array)
+                .map(|filters| {
+                    filters
+                        .iter()
+                        .filter_map(|filter| {
+                            let kind = filter.get("filterType")?.as_str()?;
+                            let value = filter.get("value")?.as_str()?;
+                            Some(format!("{kind}={value}"))
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+            let (size_bytes, modified_at) = file_details(&path);
+            Some(BuildVariantApk {
+                variant: variant.clone(),
+                path: path.to_string_lossy().to_string(),
+                application_id: application_id.clone(),
+                version_code: element.get("versionCode").and_then(Value::as_i64),
+                version_name: element
+                    .get("versionName")
+                    .and_then(Value::as_str)
+                    .map(str::to_string),
+                output_type: element
+                    .get("type")
+                    .and_then(Value::as_str)
+                    .map(str::to_string),
+                filters,
+                size_bytes,
+                modified_at,
+                apk_info: None,
+            })
+        })
+        .collect()
+}
+
+fn scan_dir(apk_root: &Path, dir: &Path, depth: usize, found: &mut Vec<BuildVariantApk>) {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(err) => {
+            warn!(path = %dir.display(), error = %err, "failed to read build output directory");
+            return;
+        }
+    };
+    let mut subdirs = Vec::new();
+    let mut apks = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            subdirs.push(path);
+        } else if path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("apk"))
+        {
+            apks.push(path);
+        }
+    }
+
+    let variant = variant_name(apk_root, dir);
+    let metadata_path = dir.join(OUTPUT_METADATA_FILE);
+    let from_metadata = match std::fs::read_to_string(&metadata_path) {
+        Ok(contents) => parse_output_metadata(&contents, dir, &variant),
+        Err(_) => Vec::new(),
+    };
+    // Older plugins (or a bare `assemble` output) leave APKs without metadata.
+    let mut listed: Vec<String> = from_metadata.iter().map(|apk| apk.path.clone()).collect();
+    found.extend(from_metadata);
+    apks.sort();
+    for apk in apks {
+        let path = apk.to_string_lossy().to_string();
+        if listed.contains(&path) {
+            continue;
+        }
+        let (size_bytes, modified_at) = file_details(&apk);
+        listed.push(path.clone());
+        found.push(BuildVariantApk {
+            variant: variant.clone(),
+            path,
+            application_id: None,
+            version_code: None,
+            version_name: None,
+            output_type: None,
+            filters: Vec::new(),
+            size_bytes,
+            modified_at,
+            apk_info: None,
+        });
+    }
+
+    if depth < MAX_SCAN_DEPTH {
+        subdirs.sort();
+        for subdir in subdirs {
+            scan_dir(apk_root, &subdir, depth + 1, found);
+        }
+    }
+}
+
+/// List every APK under `apk_root`, newest first.
+pub fn scan_build_outputs(apk_root: &Path) -> Vec<BuildVariantApk> {
+    let mut found = Vec::new();
+    scan_dir(apk_root, apk_root, 0, &mut found);
+    found.sort_by(|a, b| b.modified_at.cmp(&a.modified_at));
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const METADATA: &str = r#"{
+      "version": 3,
+      "artifactType": { "type": "APK", "kind": "Directory" },
+      "applicationId": "com.example.app.debug",
+      "variantName": "freeDebug",
+      "elements": [
+        {
+          "type": "ONE_OF_MANY",
+          "filters": [{ "filterType": "ABI", "value": "arm64-v8a" }],
+          "versionCode": 42,
+          "versionName": "1.4.0-debug",
+          "outputFile": "app-free-arm64-v8a-debug.apk"
+        },
+        { "type": "SINGLE", "versionCode": 42, "outputFile": "missing.apk" }
+      ]
+    }"#;
+
+    #[test]
+    fn parses_output_metadata() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("app-free-arm64-v8a-debug.apk"), b"PK").unwrap();
+        let apks = parse_output_metadata(METADATA, dir.path(), "free/debug");
+        assert_eq!(apks.len(), 1);
+        let apk = &apks[0];
+        assert_eq!(apk.variant, "freeDebug");
+        assert_eq!(apk.application_id.as_deref(), Some("com.example.app.debug"));
+        assert_eq!(apk.version_code, Some(42));
+        assert_eq!(apk.version_name.as_deref(), Some("1.4.0-debug"));
+        assert_eq!(apk.output_type.as_deref(), Some("ONE_OF_MANY"));
+        assert_eq!(apk.filters, ["ABI=arm64-v8a"]);
+        assert_eq!(apk.size_bytes, 2);
+        assert!(parse_output_metadata("not json", dir.path(), "x").is_empty());
+    }
+
+    #[test]
+    fn scans_variants_with_and_without_metadata() {
+        let project = tempfile::tempdir().unwrap();
+        let root = build_outputs_apk_dir(project.path(), "app");
+        let debug = root.join("free").join("debug");
+        let release = root.join("release");
+        std::fs::create_dir_all(&debug).unwrap();
+        std::fs::create_dir_all(&release).unwrap();
+        std::fs::write(debug.join(OUTPUT_METADATA_FILE), METADATA).unwrap();
+        std::fs::write(debug.join("app-free-arm64-v8a-debug.apk"), b"PK").unwrap();
+        std::fs::write(release.join("app-release-unsigned.apk"), b"PK").unwrap();
+        std::fs::write(release.join("notes.txt"), b"x").unwrap();
+
+        let mut apks = scan_build_outputs(&root);
+        apks.sort_by(|a, b| a.variant.cmp(&b.variant));
+        let variants: Vec<&str> = apks.iter().map(|apk| apk.variant.as_str()).collect();
+        assert_eq!(variants, ["freeDebug", "release"]);
+        assert!(apks[1].path.ends_with("app-release-unsigned.apk"));
+        assert_eq!(apks[1].version_code, None);
+    }
+}
@@ -0,0 +1,117 @@
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use chrono::{DateTime, Local};
+
+use crate::app::adb::paths::sanitize_filename_component;
+use crate::app::models::CapturePerfSample;
+use crate::app::perf::parse::{
+    compute_cpu_percent_x100, parse_cpu_totals, parse_mem_totals, split_marked_sections, CpuTotals,
+    MARK_MEMINFO, MARK_PROC_STAT,
+};
+
+pub const CAPTURE_MAX_LOG_LINES: usize = 200_000;
+pub const CAPTURE_MAX_PERF_SAMPLES: usize = 3_600;
+pub const CAPTURE_PERF_INTERVAL: Duration = Duration::from_secs(2);
+pub const MAX_CAPTURE_LABEL_LEN: usize = 64;
+
+/// Bounded logcat buffer; the oldest lines are dropped first and counted.
+#[derive(Debug, Default)]
+pub struct CaptureLineBuffer {
+    pub lines: VecDeque<String>,
+    pub dropped: usize,
+}
+
+impl CaptureLineBuffer {
+    pub fn push(&mut self, line: String) {
+        self.lines.push_back(line);
+        while self.lines.len() > CAPTURE_MAX_LOG_LINES {
+            self.lines.pop_front();
+            self.dropped += 1;
+        }
+    }
+}
+
+/// Marker written into the captured logcat so install boundaries are easy to find.
+pub fn capture_marker(message: &str) -> String {
+    format!("----- [lazy-blacktea] {message} -----")
+}
+
+pub fn capture_dir_name(label: &str, serial: &str, started: DateTime<Local>) -> String {
+    format!(
+        "{}_{}_{}",
+        sanitize_filename_component(label),
+        sanitize_filename_component(serial),
+        started.format("%Y%m%d_%H%M%S")
+    )
+}
+
+/// Turn one perf script run into a sample; CPU usage needs the previous totals, so the first
+/// sample has none.
+pub fn capture_perf_sample(
+    output: &str,
+    previous_cpu: Option<CpuTotals>,
+    timestamp: String,
+) -> (CapturePerfSample, Option<CpuTotals>) {
+    let sections = split_marked_sections(output).unwrap_or_default();
+    let cpu = sections
+        .get(MARK_PROC_STAT)
+        .and_then(|section| parse_cpu_totals(section).ok());
+    let mem = sections
+        .get(MARK_MEMINFO)
+        .and_then(|section| parse_mem_totals(section).ok());
+    let cpu_percent_x100 = match (previous_cpu, cpu) {
+        (Some(prev), Some(curr)) => compute_cpu_percent_x100(prev, curr),
+        _ => None,
+    };
+    (
+        CapturePerfSample {
+            timestamp,
+            cpu_percent_x100,
+            mem_total_bytes: mem.map(|mem| mem.total_bytes),
+            mem_used_bytes: mem.map(|mem| mem.total_bytes.saturating_sub(mem.available_bytes)),
+        },
+        cpu.or(previous_cpu),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn buffer_drops_oldest_lines() {
+        let mut buffer = CaptureLineBuffer::default();
+        for index in 0..CAPTURE_MAX_LOG_LINES + 3 {
+            buffer.push(index.to_string());
+        }
+        assert_eq!(buffer.lines.len(), CAPTURE_MAX_LOG_LINES);
+        assert_eq!(buffer.dropped, 3);
+        assert_eq!(buffer.lines.front().map(String::as_str), Some("3"));
+    }
+
+    #[test]
+    fn names_capture_folder() {
+        let started = Local.with_ymd_and_hms(2024, 5, 1, 9, 30, 5).unwrap();
+        assert_eq!(
+            capture_dir_name("login flow/v2", "192.168.0.5:5555", started),
+            "login_flow_v2_192.168.0.5_5555_20240501_093005"
+        );
+    }
+
+    #[test]
+    fn builds_perf_samples_from_script_output() {
+        let output = |busy: u64, idle: u64| {
+            format!(
+                "{MARK_PROC_STAT}\ncpu  {busy} 0 0 {idle} 0 0 0 0 0 0\n{MARK_MEMINFO}\nMemTotal: 4000 kB\nMemAvailable: 1000 kB\n"
+            )
+        };
+        let (first, cpu) = capture_perf_sample(&output(0, 1000), None, "t0".to_string());
+        assert_eq!(first.cpu_percent_x100, None);
+        assert_eq!(first.mem_total_bytes, Some(4000 * 1024));
+        assert_eq!(first.mem_used_bytes, Some(3000 * 1024));
+        let (second, _) = capture_perf_sample(&output(1000, 2000), cpu, "t1".to_string());
+        assert_eq!(second.cpu_percent_x100, Some(5000));
+    }
+}
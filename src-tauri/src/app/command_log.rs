@@ -0,0 +1,436 @@
+use std::collections::VecDeque;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command};
+use std::sync::{Mutex, OnceLock};
+use std::time::Instant;
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use regex::Regex;
+use tracing::{info, warn};
+
+use crate::app::adb::runner::CommandOutput;
+use crate::app::config::config_path;
+use crate::app::error::AppError;
+use crate::app::metrics::{command_metric_key, record_command_metric};
+use crate::app::models::{CommandLogEntry, CommandLogQuery};
+
+pub const COMMAND_LOG_MEMORY_LIMIT: usize = 2000;
+const COMMAND_LOG_DEFAULT_QUERY_LIMIT: usize = 200;
+const COMMAND_LOG_DISK_MAX_BYTES: u64 = 5 * 1024 * 1024;
+const REDACTED: &str = "***";
+/// Outcome of a streaming process that was spawned but is not awaited.
+const OUTCOME_STARTED: &str = "started";
+const SECRET_FLAGS: [&str; 4] = ["--password", "--passphrase", "--token", "--secret"];
+
+struct CommandLogStore {
+    entries: VecDeque<CommandLogEntry>,
+    next_id: u64,
+    disk_path: Option<PathBuf>,
+}
+
+fn store() -> &'static Mutex<CommandLogStore> {
+    static STORE: OnceLock<Mutex<CommandLogStore>> = OnceLock::new();
+    STORE.get_or_init(|| {
+        Mutex::new(CommandLogStore {
+            entries: VecDeque::new(),
+            next_id: 1,
+            disk_path: None,
+        })
+    })
+}
+
+fn secret_assignment_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r"(?i)\b(password|passwd|passphrase|token|secret)=\S+")
+            .expect("valid secret regex")
+    })
+}
+
+pub fn command_log_path() -> PathBuf {
+    config_path().with_file_name(".lazy_blacktea_command_log.jsonl")
+}
+
+/// Mirror new command log entries to a JSONL file. Without this only the in-memory ring is kept.
+pub fn init_command_log_disk(path: PathBuf) {
+    match store().lock() {
+        Ok(mut guard) => guard.disk_path = Some(path),
+        Err(_) => warn!("command log store poisoned; disk log disabled"),
+    }
+}
+
+pub fn serial_from_args(args: &[String]) -> Option<String> {
+    args.iter()
+        .position(|arg| arg == "-s")
+        .and_then(|index| args.get(index + 1))
+        .cloned()
+}
+
+/// Mask secrets before a command line is logged: adb pairing codes, Wi-Fi passphrases,
+/// typed `input text`, `--password`-style flag values, and `password=`/`token=` assignments.
+pub fn redact_command_args(args: &[String]) -> Vec<String> {
+    let mut redacted: Vec<String> = args
+        .iter()
+        .map(|arg| {
+            secret_assignment_regex()
+                .replace_all(arg, |caps: &regex::Captures| {
+                    format!("{}={REDACTED}", &caps[1])
+                })
+                .to_string()
+        })
+        .collect();
+
+    let mut mask_at = Vec::new();
+    for (index, arg) in args.iter().enumerate() {
+        // `input text` carries typed content such as unlock PINs.
+        if arg == "input" && args.get(index + 1).map(String::as_str) == Some("text") {
+            mask_at.push(index + 2);
+        }
+        match arg.as_str() {
+            // ADBKeyboard broadcasts: `-a ADB_INPUT_B64 --es msg <payload>`.
+            "ADB_INPUT_B64" | "ADB_INPUT_TEXT" => mask_at.push(index + 3),
+            "pair" => mask_at.push(index + 2),
+            "connect-network" => mask_at.push(index + 3),
+            value if SECRET_FLAGS.contains(&value) => mask_at.push(index + 1),
+            _ => {}
+        }
+    }
+    for index in mask_at {
+        if let Some(value) = redacted.get_mut(index) {
+            *value = REDACTED.to_string();
+        }
+    }
+    redacted
+}
+
+fn program_name(program: &str) -> String {
+    Path::new(program)
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| program.to_string())
+}
+
+fn outcome_for(result: &Result<CommandOutput, AppError>) -> (&'static str, Option<i32>) {
+    match result {
+        Ok(output) if output.exit_code == Some(0) => ("ok", output.exit_code),
+        Ok(output) => ("failed", output.exit_code),
+        Err(err) if err.error == "Command timed out" => ("timeout", None),
+        Err(_) => ("error", None),
+    }
+}
+
+pub fn record_command(
+    program: &str,
+    args: &[String],
+    trace_id: &str,
+    started_at: DateTime<Utc>,
+    started: Instant,
+    result: &Result<CommandOutput, AppError>,
+) -> CommandLogEntry {
+    let (outcome, exit_code) = outcome_for(result);
+    record_command_metric(
+        &command_metric_key(&program_name(program), args),
+        started.elapsed(),
+        outcome == "ok",
+    );
+    record_entry(
+        program, args, trace_id, started_at, started, outcome, exit_code,
+    )
+}
+
+/// Log a streaming process (logcat, getevent, screenrecord...) when it is started. Its exit is
+/// not awaited, so the entry carries outcome `started`, or `error` when it could not start.
+pub fn record_spawn(
+    program: &str,
+    args: &[String],
+    trace_id: &str,
+    started_at: DateTime<Utc>,
+    started: Instant,
+    spawned: bool,
+) -> CommandLogEntry {
+    let outcome = if spawned { OUTCOME_STARTED } else { "error" };
+    record_entry(program, args, trace_id, started_at, started, outcome, None)
+}
+
+/// Spawn `command` and record it with `record_spawn`.
+pub fn spawn_logged(command: &mut Command, trace_id: &str) -> std::io::Result<Child> {
+    let started_at = Utc::now();
+    let started = Instant::now();
+    let result = command.spawn();
+    let program = command.get_program().to_string_lossy().to_string();
+    let args: Vec<String> = command
+        .get_args()
+        .map(|arg| arg.to_string_lossy().to_string())
+        .collect();
+    record_spawn(
+        &program,
+        &args,
+        trace_id,
+        started_at,
+        started,
+        result.is_ok(),
+    );
+    result
+}
+
+fn record_entry(
+    program: &str,
+    args: &[String],
+    trace_id: &str,
+    started_at: DateTime<Utc>,
+    started: Instant,
+    outcome: &str,
+    exit_code: Option<i32>,
+) -> CommandLogEntry {
+    let mut entry = CommandLogEntry {
+        id: 0,
+        trace_id: trace_id.to_string(),
+        serial: serial_from_args(args),
+        program: program_name(program),
+        args: redact_command_args(args),
+        started_at: started_at.to_rfc3339_opts(SecondsFormat::Millis, true),
+        duration_ms: started.elapsed().as_millis() as u64,
+        exit_code,
+        outcome: outcome.to_string(),
+    };
+
+    let disk_path = match store().lock() {
+        Ok(mut guard) => {
+            entry.id = guard.next_id;
+            guard.next_id += 1;
+            guard.entries.push_back(entry.clone());
+            while guard.entries.len() > COMMAND_LOG_MEMORY_LIMIT {
+                guard.entries.pop_front();
+            }
+            guard.disk_path.clone()
+        }
+        Err(_) => {
+            warn!(trace_id = %trace_id, "command log store poisoned");
+            None
+        }
+    };
+
+    info!(
+        trace_id = %trace_id,
+        serial = entry.serial.as_deref().unwrap_or(""),
+        program = %entry.program,
+        args = %entry.args.join(" "),
+        duration_ms = entry.duration_ms,
+        exit_code = ?entry.exit_code,
+        outcome = %entry.outcome,
+        "command finished"
+    );
+
+    if let Some(path) = disk_path {
+        if let Err(err) = append_to_disk(&path, &entry) {
+            warn!(trace_id = %trace_id, error = %err, "failed to write command log");
+        }
+    }
+    entry
+}
+
+fn append_to_disk(path: &Path, entry: &CommandLogEntry) -> std::io::Result<()> {
+    if let Ok(metadata) = fs::metadata(path) {
+        if metadata.len() > COMMAND_LOG_DISK_MAX_BYTES {
+            fs::rename(path, path.with_extension("jsonl.1"))?;
+        }
+    }
+    let line = serde_json::to_string(entry).map_err(std::io::Error::other)?;
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{line}")
+}
+
+pub fn filter_command_log<'a>(
+    entries: impl DoubleEndedIterator<Item = &'a CommandLogEntry>,
+    query: &CommandLogQuery,
+) -> Vec<CommandLogEntry> {
+    let limit = query
+        .limit
+        .unwrap_or(COMMAND_LOG_DEFAULT_QUERY_LIMIT)
+        .clamp(1, COMMAND_LOG_MEMORY_LIMIT);
+    let serial = query.serial.as_deref().map(str::trim).unwrap_or("");
+    let trace_id = query.trace_id.as_deref().map(str::trim).unwrap_or("");
+    let contains = query
+        .contains
+        .as_deref()
+        .map(|value| value.trim().to_lowercase())
+        .unwrap_or_default();
+
+    entries
+        .rev()
+        .filter(|entry| serial.is_empty() || entry.serial.as_deref() == Some(serial))
+        .filter(|entry| trace_id.is_empty() || entry.trace_id == trace_id)
+        .filter(|entry| {
+            !query.failed_only || !matches!(entry.outcome.as_str(), "ok" | OUTCOME_STARTED)
+        })
+        .filter(|entry| {
+            contains.is_empty() || entry.args.join(" ").to_lowercase().contains(&contains)
+        })
+        .take(limit)
+        .cloned()
+        .collect()
+}
+
+/// Return matching entries from the in-memory command log, newest first.
+pub fn query_command_log_entries(
+    query: &CommandLogQuery,
+    trace_id: &str,
+) -> Result<Vec<CommandLogEntry>, AppError> {
+    let guard = store()
+        .lock()
+        .map_err(|_| AppError::system("Command log locked", trace_id))?;
+    Ok(filter_command_log(guard.entries.iter(), query))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    fn entry(id: u64, serial: &str, outcome: &str, args: &[&str]) -> CommandLogEntry {
+        CommandLogEntry {
+            id,
+            trace_id: format!("trace-{id}"),
+            serial: Some(serial.to_string()),
+            program: "adb".to_string(),
+            args: args.iter().map(|value| value.to_string()).collect(),
+            started_at: "2024-01-01T00:00:00.000Z".to_string(),
+            duration_ms: 5,
+            exit_code: Some(0),
+            outcome: outcome.to_string(),
+        }
+    }
+
+    #[test]
+    fn redact_command_args_masks_known_secrets() {
+        let pair = redact_command_args(&args(&["pair", "192.168.1.2:37000", "123456"]));
+        assert_eq!(pair, args(&["pair", "192.168.1.2:37000", "***"]));
+
+        let wifi = redact_command_args(&args(&[
+            "-s",
+            "abc",
+            "shell",
+            "cmd",
+            "wifi",
+            "connect-network",
+            "Lab",
+            "wpa2",
+            "hunter2",
+        ]));
+        assert_eq!(wifi.last().map(String::as_str), Some("***"));
+        assert_eq!(wifi[6], "Lab");
+
+        let inline = redact_command_args(&args(&["shell", "am start --es token=abc PASSWORD=x"]));
+        assert_eq!(inline[1], "am start --es token=*** PASSWORD=***");
+
+        let typed = redact_command_args(&args(&["-s", "abc", "shell", "input", "text", "1234"]));
+        assert_eq!(typed[5], "***");
+
+        let broadcast = redact_command_args(&args(&[
+            "shell",
+            "am",
+            "broadcast",
+            "-a",
+            "ADB_INPUT_B64",
+            "--es",
+            "msg",
+            "MTIzNA==",
+        ]));
+        assert_eq!(broadcast[7], "***");
+
+        let flag = redact_command_args(&args(&["--token", "abc", "devices"]));
+        assert_eq!(flag, args(&["--token", "***", "devices"]));
+    }
+
+    #[test]
+    fn serial_from_args_reads_dash_s_value() {
+        assert_eq!(
+            serial_from_args(&args(&["-s", "emulator-5554", "shell"])),
+            Some("emulator-5554".to_string())
+        );
+        assert_eq!(serial_from_args(&args(&["devices", "-l"])), None);
+    }
+
+    #[test]
+    fn filter_command_log_returns_newest_matches_first() {
+        let entries = [
+            entry(1, "a", "ok", &["-s", "a", "shell", "getprop"]),
+            entry(2, "b", "failed", &["-s", "b", "shell", "getprop"]),
+            entry(3, "a", "timeout", &["-s", "a", "pull", "/sdcard/x"]),
+            entry(4, "a", "started", &["-s", "a", "logcat"]),
+        ];
+        let all = filter_command_log(entries.iter(), &CommandLogQuery::default());
+        assert_eq!(
+            all.iter().map(|e| e.id).collect::<Vec<_>>(),
+            vec![4, 3, 2, 1]
+        );
+
+        let query = CommandLogQuery {
+            serial: Some("a".to_string()),
+            failed_only: true,
+            ..CommandLogQuery::default()
+        };
+        let failed = filter_command_log(entries.iter(), &query);
+        assert_eq!(failed.len(), 1);
+        assert_eq!(failed[0].id, 3);
+
+        let query = CommandLogQuery {
+            contains: Some("GETPROP".to_string()),
+            limit: Some(1),
+            ..CommandLogQuery::default()
+        };
+        let limited = filter_command_log(entries.iter(), &query);
+        assert_eq!(limited.len(), 1);
+        assert_eq!(limited[0].id, 2);
+    }
+
+    #[test]
+    fn record_spawn_marks_streams_as_started() {
+        let trace_id = "trace-command-log-spawn";
+        let recorded = record_spawn(
+            "adb",
+            &args(&["-s", "serial-2", "logcat", "-v", "time"]),
+            trace_id,
+            Utc::now(),
+            Instant::now(),
+            true,
+        );
+        assert_eq!(recorded.outcome, "started");
+        assert_eq!(recorded.exit_code, None);
+        assert_eq!(recorded.serial.as_deref(), Some("serial-2"));
+    }
+
+    #[test]
+    fn record_command_is_queryable_by_trace_id() {
+        let trace_id = "trace-command-log-record";
+        let result = Ok(CommandOutput {
+            stdout: String::new(),
+            stderr: String::new(),
+            exit_code: Some(1),
+        });
+        let recorded = record_command(
+            "/opt/android/platform-tools/adb",
+            &args(&["-s", "serial-1", "pair", "host:1", "000000"]),
+            trace_id,
+            Utc::now(),
+            Instant::now(),
+            &result,
+        );
+        assert_eq!(recorded.program, "adb");
+        assert_eq!(recorded.outcome, "failed");
+
+        let query = CommandLogQuery {
+            trace_id: Some(trace_id.to_string()),
+            ..CommandLogQuery::default()
+        };
+        let found = query_command_log_entries(&query, trace_id).expect("query");
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].serial.as_deref(), Some("serial-1"));
+        assert_eq!(found[0].args.last().map(String::as_str), Some("***"));
+    }
+}
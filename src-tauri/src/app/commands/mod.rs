@@ -1,75 +1,398 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::fs;
 use std::io::{BufRead, BufReader, Read, Write};
-use std::path::PathBuf;
-use std::process::{Command, Stdio};
+use std::net::Ipv4Addr;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::{Arc, OnceLock, RwLock};
+use std::sync::{Arc, Mutex, OnceLock, RwLock};
 use std::time::{Duration, Instant};
 
-use chrono::Utc;
+use chrono::{Datelike, Local, Utc};
 use mime_guess::MimeGuess;
-use tauri::{AppHandle, Emitter, State};
+use notify::{RecursiveMode, Watcher};
+use tauri::{AppHandle, Manager, State};
 use tracing::{info, warn};
 use uuid::Uuid;
 use zip::ZipArchive;
 
-use crate::app::adb::apk::{extract_split_apks, get_apk_info, is_split_bundle, normalize_apk_path};
+use crate::app::accessibility_audit::{
+    audit_contrast, audit_nodes, DEFAULT_DENSITY_DPI, SEVERITY_ERROR, SEVERITY_WARNING,
+};
+use crate::app::adb::apk::{
+    binary_compatibility_issues, extract_split_apks, get_apk_info, inspect_apk_binaries,
+    is_split_bundle, normalize_apk_path,
+};
 use crate::app::adb::apps::{
-    package_entry_to_app_info, parse_dumpsys_components_summary, parse_dumpsys_data_dir,
+    app_matches_query, build_app_info, fetch_versions_parallel, is_valid_package_name,
+    parse_am_start_wait, parse_device_admin_packages, parse_diskstats_app_sizes,
+    parse_dumpsys_components_summary, parse_dumpsys_data_dir, parse_dumpsys_debuggable,
     parse_dumpsys_first_install_time, parse_dumpsys_granted_permissions,
     parse_dumpsys_initiating_package_name, parse_dumpsys_installer_package_name,
-    parse_dumpsys_installing_package_name, parse_dumpsys_last_update_time,
-    parse_dumpsys_originating_package_name, parse_dumpsys_requested_permissions,
-    parse_dumpsys_target_sdk, parse_dumpsys_user_id, parse_dumpsys_version_code,
-    parse_dumpsys_version_name, parse_pm_list_packages_output, parse_pm_path_output,
+    parse_dumpsys_installing_package_name, parse_dumpsys_last_update_time, parse_dumpsys_min_sdk,
+    parse_dumpsys_originating_package_name, parse_dumpsys_primary_cpu_abi,
+    parse_dumpsys_requested_permissions, parse_dumpsys_target_sdk, parse_dumpsys_user_id,
+    parse_dumpsys_version, parse_dumpsys_version_code, parse_dumpsys_version_name,
+    parse_pm_list_packages_output, parse_pm_path_output, parse_resolve_activity_brief,
+    parse_uninstall_output, query_app_list, split_dumpsys_package_blocks, summarize_durations,
+    AppVersion, PackageEntry, APP_LIST_SORT_KEYS,
+};
+use crate::app::adb::audio_forward::{
+    audio_forward_blocker, build_audio_forward_args, normalize_audio_codec, normalize_audio_source,
+};
+use crate::app::adb::automation_services::{
+    expand_component, is_valid_component, merge_accessibility_services, merge_input_methods,
+    parse_component_lines, parse_enabled_services, toggle_enabled_services,
+    ACCESSIBILITY_SERVICE_ACTION, DEFAULT_INPUT_METHOD, ENABLED_ACCESSIBILITY_SERVICES,
 };
+use crate::app::adb::boot::{boot_wait_timeout, parse_boot_info, wait_for_boot_completed};
+use crate::app::adb::broadcast::{broadcast_targets, INPUT_BROADCAST_EVENT_NAME};
 use crate::app::adb::bugreport::{parse_bugreportz_line, BugreportzPayload};
-use crate::app::adb::device_tracking::start_device_tracker;
-use crate::app::adb::locator::{normalize_command_path, resolve_adb_program, validate_adb_program};
+use crate::app::adb::chunked_pull::{
+    dd_chunk_command, parse_stat_mode_size_mtime, pull_chunked, CHUNKED_PULL_MIN_BYTES,
+    CHUNK_ATTEMPTS,
+};
+use crate::app::adb::compressed_pull::{
+    pull_compressed_via_adb, pull_compressed_via_socket, should_compress, GZIP_PROBE_COMMAND,
+};
+use crate::app::adb::device_tracking::{
+    start_device_tracker, DeviceDetailHook, DeviceOnlineHook, DEVICE_DETAIL_UPDATED_EVENT,
+};
+use crate::app::adb::devtools::{
+    build_devtools_forward_args, build_devtools_forward_remove_args, devtools_forwards,
+    http_get_local, parse_cmdline_process, parse_devtools_sockets, parse_devtools_targets,
+    parse_forward_port, socket_package, socket_pid,
+};
+use crate::app::adb::emulator::{
+    emulator_console_error, parse_snapshot_list, validate_snapshot_name,
+};
+use crate::app::adb::frida::{
+    agent_process_name, build_agent_start_command, build_forward_args, build_forward_remove_args,
+    has_forward, parse_pidof,
+};
+use crate::app::adb::getevent::{
+    parse_getevent_strokes, parse_touch_device, replay_gap_ms, stroke_to_gesture,
+    validate_gesture_recording, GeteventStrokeParser, GESTURE_FILE_VERSION,
+};
+use crate::app::adb::i18n_toggles::{
+    force_rtl_shell_commands, i18n_restore_path, load_restore_points, normalize_pseudo_locale,
+    original_locale, parse_force_rtl, remove_restore_point, save_restore_point_if_absent,
+    I18nRestorePoint, FORCE_RTL_KEY, LOCALE_PROPERTY, PRODUCT_LOCALE_PROPERTY, RESTART_UI_COMMAND,
+};
+use crate::app::adb::input::{
+    adb_keyboard_payloads, build_gesture_input_args, escape_input_text, find_selector_center,
+    needs_adb_keyboard, orient_device_size, plan_input_text, scale_pointer_gesture,
+    validate_unlock_pin, ScaledGesture, TextInputStep, ADB_KEYBOARD_B64_ACTION,
+    ADB_KEYBOARD_CHUNK_BYTES, ADB_KEYBOARD_IME, INPUT_TEXT_CHUNK_CHARS,
+};
+use crate::app::adb::location::{
+    build_geo_fix_args, build_helper_location_args, build_helper_stop_args,
+    build_mock_location_appops_args, is_emulator_serial, validate_helper_package,
+    validate_mock_coordinates, DEFAULT_MOCK_LOCATION_PACKAGE,
+};
+use crate::app::adb::locator::{
+    active_adb_server, adb_command, adb_command_for_serial, cached_adb_program,
+    invalidate_adb_program_cache, normalize_command_path, override_version_conflict,
+    parse_adb_protocol_version, resolve_adb_program, resolve_adb_program_for_serial,
+    resolve_adb_server, route_adb_program, set_active_adb_server, set_adb_path_routing,
+    store_adb_program, transport_for_serial, validate_adb_program, TRANSPORT_USB,
+};
+use crate::app::adb::media_session::{media_key, parse_media_sessions};
+use crate::app::adb::monkey::{
+    build_monkey_args, detect_crash_keyword, monkey_timeout, parse_events_injected,
+    validate_monkey_options, MonkeyOptions, CRASH_CONTEXT_LINES, CRASH_EXCERPT_LINES,
+};
+use crate::app::adb::net_shaping::{
+    build_netem_clear_command, build_netem_command, emulator_delay_arg, emulator_speed_arg,
+    is_missing_qdisc_error, parse_route_interface, use_emulator_console, validate_shaping,
+    ShapingParams, DEFAULT_SHAPING_INTERFACE, SHAPING_EMULATOR_CONSOLE, SHAPING_TC,
+};
+use crate::app::adb::overlay::{parse_overlay_list, OVERLAY_ENABLED, OVERLAY_UNAVAILABLE};
 use crate::app::adb::parse::{
-    build_device_detail, parse_adb_devices, parse_audio_summary, parse_battery_level,
-    parse_bluetooth_manager_state, parse_df_total_kb,
-    parse_dumpsys_version_name as parse_gms_version_name, parse_getprop_map, parse_ls_la,
-    parse_settings_bool, parse_wm_size,
+    build_device_detail, parse_adb_devices, parse_adb_features, parse_audio_summary,
+    parse_battery_level, parse_bluetooth_manager_state, parse_df_available_kb, parse_df_total_kb,
+    parse_du_kb, parse_dumpsys_usb_speed, parse_dumpsys_version_name as parse_gms_version_name,
+    parse_getprop_map, parse_ls_la, parse_settings_bool, parse_size_dimensions,
+    parse_usb_speed_value, parse_wm_density, parse_wm_size, usb_speed_label,
 };
 use crate::app::adb::paths::{
-    device_parent_dir, sanitize_filename_component, validate_device_path,
+    device_parent_dir, is_media_file_path, media_scan_file_uri, sanitize_filename_component,
+    validate_device_path,
+};
+use crate::app::adb::proxy::{
+    ca_device_path, ca_install_note, parse_proxy_setting, validate_ca_certificate,
+    validate_proxy_address, CLEAR_PROXY_VALUE,
+};
+use crate::app::adb::push_tokens::{compile_push_token_pattern, extract_push_tokens};
+use crate::app::adb::reconnect::{connect_failed, connect_failure_detail, reconnect_policy};
+use crate::app::adb::runner::{
+    run_adb, run_binary_command_with_timeout, run_command_with_timeout, socket_client_for_serial,
+    stat_device_path, CommandOutput,
+};
+use crate::app::adb::scrcpy::{
+    build_scrcpy_command, check_scrcpy_availability, parse_scrcpy_version, resolve_scrcpy_settings,
+    scrcpy_option_issues,
+};
+use crate::app::adb::socket::{set_socket_client_enabled, AdbSocketClient, SocketError, SyncStat};
+use crate::app::adb::sync::{
+    exec_out, is_regular_file, partial_path, pull_file, push_file, TransferOutcome,
+    TRANSFER_IDLE_TIMEOUT,
 };
-use crate::app::adb::runner::{run_adb, run_command_with_timeout};
-use crate::app::adb::scrcpy::{build_scrcpy_command, check_scrcpy_availability};
 use crate::app::adb::transfer::parse_progress_percent;
+use crate::app::adb::tunnel::start_ssh_tunnel;
+use crate::app::adb::usb::{
+    normalize_usb_function, parse_usb_functions, set_functions_args, USB_FUNCTIONS_SCRIPT,
+};
+use crate::app::adb::volume::{
+    parse_audio_streams, set_volume_commands, stream_id, VOLUME_STREAMS,
+};
+use crate::app::adb::wireless::{
+    parse_wlan_ipv4, tcpip_address, tcpip_failed, wireless_flow, DEFAULT_TCPIP_PORT,
+    WIRELESS_FLOW_TCPIP,
+};
+use crate::app::apk_watch::{
+    event_touches_apk, file_stamp, should_install, APK_WATCH_DEBOUNCE, APK_WATCH_POLL,
+};
+use crate::app::app_cache::{
+    android_sdk_roots, app_cache_key, app_icon_cache_dir, app_metadata_cache_dir, evict_icon_cache,
+    find_aapt, icon_cache_dirs, icon_cache_stats, load_cached_metadata, parse_badging_label,
+    remove_other_versions, store_cached_metadata, touch_cache_file, CachedAppMetadata,
+    ICON_CACHE_MAX_BYTES,
+};
 use crate::app::bluetooth::service::start_bluetooth_monitor as start_bluetooth_monitor_service;
 use crate::app::bugreport_logcat;
+use crate::app::build_outputs::{build_outputs_apk_dir, scan_build_outputs};
+use crate::app::capture_context::{
+    capture_dir_name, capture_marker, capture_perf_sample, CaptureLineBuffer,
+    CAPTURE_MAX_PERF_SAMPLES, CAPTURE_PERF_INTERVAL, MAX_CAPTURE_LABEL_LEN,
+};
+use crate::app::command_log::{
+    query_command_log_entries, record_command, record_spawn, spawn_logged, COMMAND_LOG_MEMORY_LIMIT,
+};
 use crate::app::config::{
-    clamp_terminal_buffer_lines, load_config, normalize_config_for_save, save_config, AppConfig,
+    clamp_terminal_buffer_lines, config_path, load_config, normalize_config_for_save, save_config,
+    AdbPathOverride, AppConfig, DebugAgentSettings, LoggingSettings, OnConnectAction,
+    ScrcpyOptions, SshTunnelSettings, UploadTarget,
+};
+use crate::app::custom_tools::{
+    build_invocation, resolve_inputs, run_result, tool_targets, tool_timeout, TOOL_KIND_ADB,
+};
+use crate::app::device_farm::{
+    build_farm_spec, build_gcloud_run_args, farm_exit_outcome, farm_host_timeout,
+    parse_farm_outcomes, parse_results_url, parse_storage_url, render_farm_argfile,
+    resolve_gcloud_program, FARM_APP_FILE, FARM_ARGFILE_NAME, FARM_ROBO_SCRIPT_FILE,
+    FARM_TEST_FILE,
+};
+use crate::app::device_history::{device_history_path, load_device_history, with_open_time};
+use crate::app::device_notes::{
+    build_device_notes, device_notes_path, load_device_notes, store_device_notes,
+};
+use crate::app::device_profiles::{
+    build_apply_report, commit_staged_profile, device_profiles_dir, list_profiles, load_profile,
+    parse_settings_value, parse_sha256sum, profile_step, save_profile, setting_shell_command,
+    sha256_file, staging_dir, stored_file_name, validate_profile_name, validate_setting_key,
+    PROFILE_APPS_DIR, PROFILE_FILES_DIR, STEP_APPLIED, STEP_FAILED, STEP_KIND_APP, STEP_KIND_FILE,
+    STEP_KIND_SETTING, STEP_SKIPPED,
+};
+use crate::app::device_reservations::{
+    current_reservation_user, device_reservations_path, is_reservation_active,
+    load_device_reservations, normalize_reservation_user, parse_reservation_until,
+    remove_device_reservation, reservation_conflicts, store_device_reservation,
+    DEVICE_RESERVATION_CONFLICT_EVENT,
 };
 use crate::app::diagnostics;
+use crate::app::dmesg::{
+    dmesg_probe_args, dmesg_probe_succeeded, dmesg_stream_args, parse_dmesg_line, DMESG_EVENT_NAME,
+    DMESG_MODE_DIRECT, DMESG_MODE_SU,
+};
+use crate::app::dumpsys::{
+    build_dumpsys_shell_args, find_dumpsys_parser, is_missing_service_output,
+    is_valid_dumpsys_service, validate_dumpsys_args, DUMPSYS_PARSERS,
+};
+use crate::app::environment::{
+    config_issues, config_status, environment_ready, find_emulator_in_sdk, missing_status,
+    probe_status, HINT_AAPT, HINT_ADB, HINT_ADB_SERVER_LOCAL, HINT_ADB_SERVER_REMOTE,
+    HINT_BUNDLETOOL, HINT_CONFIG, HINT_CURL, HINT_EMULATOR, HINT_FFMPEG, HINT_SCRCPY, STATUS_OK,
+};
 use crate::app::error::AppError;
+use crate::app::event_subscriptions::{
+    device_color, device_event_channels, normalize_channels, subscribe_device_events,
+    unsubscribe_device_events,
+};
+use crate::app::factory_reset::{
+    append_reset_audit, factory_reset_audit_path, issue_reset_token, load_reset_audit,
+    redeem_reset_token, wipe_started, WipeMethod, BROADCAST_WIPE_REBOOT_WAIT,
+    FACTORY_RESET_BOOT_TIMEOUT_SECS, FACTORY_RESET_TOKEN_TTL, RESET_STAGE_BOOT,
+    RESET_STAGE_PROVISION, RESET_STAGE_REQUESTED, RESET_STAGE_WIPE, WIPE_METHODS,
+};
+use crate::app::golden::{
+    append_golden_result, golden_check_paths, golden_image_path, golden_results_path,
+    golden_store_dir, latest_golden_results, list_golden_images, load_golden_results,
+    resolution_key, save_golden_meta, validate_screen_name, GOLDEN_FAILED, GOLDEN_MISSING,
+    GOLDEN_PASSED,
+};
+use crate::app::group_actions::{
+    command_result_to_group, install_result_to_group, order_group_results, parse_group_action,
+    reboot_result_to_group, resolve_group_serials, GroupAction,
+};
+use crate::app::hooks::{
+    hooks_for_event, log_hook_result, run_hook, run_hooks, validate_hooks, HookPayload,
+    HookRunResult, HOOK_BUGREPORT_FINISHED, HOOK_DEVICE_CONNECTED, HOOK_INSTALL_COMPLETE,
+    HOOK_TEST,
+};
+use crate::app::i18n::Message;
+use crate::app::input_devices::{parse_input_devices, INPUT_DEVICES_SCRIPT};
+use crate::app::issues::{
+    compose_issue_body, github_auth_config, github_issue_payload, jira_auth_config,
+    jira_issue_payload, parse_github_issue_response, parse_jira_issue_key, validate_github_repo,
+    IssueArtifactRef, MAX_ISSUE_ATTACHMENTS, MAX_ISSUE_TITLE_LEN,
+};
+use crate::app::last_boot_logs::{build_section, unavailable_reason, LAST_BOOT_SOURCES};
+use crate::app::leak_watch::{
+    clamp_interval_secs, clamp_window_samples, normalize_slope_kb_per_min, parse_total_pss_kb,
+    LeakTrend,
+};
+use crate::app::lifecycle::{
+    lifecycle_logcat_args, package_from_cmdline, LifecycleLineParser, FRAGMENT_LOG_PROPERTY,
+    LIFECYCLE_EVENT_NAME, LIFECYCLE_KIND_ACTIVITY,
+};
+use crate::app::lock_state::{parse_lock_state, LOCK_STATE_SCRIPT};
+use crate::app::logging::{
+    app_log_file_status, configure_app_log_file, current_log_levels, normalize_log_level,
+    recent_app_logs, set_log_level as apply_log_level,
+};
+use crate::app::media_capabilities::{parse_camera_dump, parse_codec_dump};
+use crate::app::media_convert::{
+    build_ffmpeg_convert_args, conversion_percent, converted_output_path, normalize_convert_format,
+    parse_ffmpeg_progress,
+};
+use crate::app::metrics::{emit_tracked, render_prometheus, snapshot_metrics};
 use crate::app::models::{
-    AdbInfo, ApkBatchInstallResult, ApkInstallErrorCode, ApkInstallResult, AppBasicInfo,
-    AppComponentsSummary, AppIcon, AppInfo, BugreportLogAroundPage, BugreportLogFilters,
-    BugreportLogPage, BugreportLogSearchResult, BugreportLogSummary, BugreportResult,
-    CommandResponse, CommandResult, DeviceDetail, DeviceFileEntry, DeviceInfo, FilePreview,
-    HostCommandResult, LogcatExportResult, NetProfilerSnapshot, PerfSnapshot, ScrcpyInfo,
-    TerminalEvent, TerminalSessionInfo, UiHierarchyCaptureResult, UiHierarchyExportResult,
+    AccessibilityAudit, AccessibilityServiceInfo, AdbInfo, AdbTunnelStatus, ApkBatchInstallResult,
+    ApkInstallErrorCode, ApkInstallResult, ApkWatchOptions, AppBasicInfo, AppBinaryInspection,
+    AppComponentsSummary, AppIcon, AppInfo, AppListEntry, AppListPage, AppListQuery, AppLogEntry,
+    AppLogStatus, AppStartupResult, AppUninstallResult, ArtifactUploadResult, AudioForwardSession,
+    AudioRouteInfo, BootInfo, BugreportLogAroundPage, BugreportLogFilters, BugreportLogPage,
+    BugreportLogSearchResult, BugreportLogSummary, BugreportResult, BuildVariantApk,
+    CaCertificateInstall, CaptureContextInfo, CaptureContextResult, CapturedPushToken,
+    ChecklistItemInput, CommandLogEntry, CommandLogQuery, CommandResponse, CommandResult,
+    CpuCluster, CreatedIssue, CustomToolResult, DebugAgentStatus, DependencyStatus, DeviceDetail,
+    DeviceFarmBundle, DeviceFarmRequest, DeviceFarmRunResult, DeviceFileEntry, DeviceHistory,
+    DeviceInfo, DeviceLockState, DeviceNotes, DeviceProfile, DeviceProfileApp,
+    DeviceProfileApplyReport, DeviceProfileFile, DeviceProfileSetting, DeviceProfileSettingKey,
+    DeviceProfileStep, DeviceReadinessResult, DeviceReservation, DeviceSummary, DeviceTransition,
+    DeviceUnlockResult, DevtoolsSocket, DmesgEvent, DmesgLine, DmesgStreamInfo, DumpsysParserInfo,
+    DumpsysResult, EmulatorSnapshot, EnvironmentReport, EventSubscriptionInfo,
+    FactoryResetAuditEntry, FactoryResetChallenge, FactoryResetReport, FilePreview,
+    GestureRecording, GestureRecordingSummary, GestureReplayResult, GestureStroke,
+    GoldenCheckResult, GoldenImage, GroupActionDeviceResult, GroupActionResult, HostCommandResult,
+    I18nToggleResult, IconCacheStats, InputBroadcastDeviceResult, InputBroadcastEvent,
+    InputBroadcastInfo, InputDeviceInventory, InputMethodInfo, InternalMetrics, JankScenarioResult,
+    LastBootLogSection, LastBootLogs, LifecycleStreamInfo, LogcatExportResult,
+    LogcatFileExportResult, MediaCapabilities, MediaSessionInfo, MockLocationResult,
+    MonkeyStressResult, NetProfilerSnapshot, NetworkShapingResult, OnConnectAuditEntry,
+    OverlayPackage, PerfMultiFrame, PerfSnapshot, PointerGesture, PointerGestureResult,
+    ProxyStatus, ReadinessThresholds, RebootResult, RecordingConversionStatus,
+    ReservationConflictEvent, ScrcpyInfo, ScrcpyValidation, ScreenRecordConversionProgress,
+    ScreenRecordConversionResult, ScreenshotComparison, SessionReportResult, SessionTimeRange,
+    SetupWizardState, ShellPipelineResult, ShellPipelineStep, ShellStreamEvent, ShellStreamInfo,
+    SoakProfile, SoakTestInfo, SoakTestReport, StartupSample, TcpipConnectResult, TelephonyInfo,
+    TerminalEvent, TerminalSessionInfo, TextFieldEntry, TextFieldFillResult, ThermalCrossing,
+    ThermalSnapshot, TimelineEvent, TimelineQuery, TriageBundle, TypeTextResult, UiCaptureRecord,
+    UiHierarchyCaptureResult, UiHierarchyDiff, UiHierarchyExportResult, UsbFunctionState,
+    WirelessFlowInfo,
 };
 use crate::app::net_profiler::parse::{
     parse_cmd_package_list_u, parse_dumpsys_netstats_app_uid_stats, parse_xt_qtaguid_stats,
 };
 use crate::app::net_profiler::snapshot::build_net_usage_rows;
+use crate::app::notifications::{
+    notify_webhooks, send_webhook, webhooks_for_event, NotificationContext,
+    NOTIFY_BUGREPORT_COMPLETED, NOTIFY_BUGREPORT_FAILED, NOTIFY_INSTALL_FINISHED,
+    NOTIFY_PERF_ALERT, NOTIFY_TEST,
+};
+use crate::app::on_connect::{
+    action_shell_commands, append_audit, load_audit, on_connect_audit_path, profile_actions,
+    ACTION_START_LOGCAT, ON_CONNECT_APPLIED_EVENT,
+};
+use crate::app::output::{prepare_artifact_dir, resolve_artifact_dir, ArtifactKind};
+use crate::app::perf::aggregate::{clamp_bucket_ms, PerfFrameAggregator};
 use crate::app::perf::parse::{
-    build_perf_script, compute_cpu_percent_x100, parse_battery_totals, parse_cpu_freq_khz,
-    parse_cpu_totals, parse_mem_totals, parse_net_totals, parse_per_core_cpu_totals,
-    split_marked_sections, BatteryTotals, CpuTotals, MemTotals, NetTotals, MARK_CPUFREQ,
+    build_perf_script, compute_cpu_percent_x100, compute_jank_percent_x100, parse_battery_totals,
+    parse_cpu_clusters, parse_cpu_freq_khz, parse_cpu_totals, parse_gfxinfo_summary,
+    parse_mem_totals, parse_net_totals, parse_per_core_cpu_totals, split_marked_sections,
+    BatteryTotals, CpuTotals, MemTotals, NetTotals, CPU_TOPOLOGY_SCRIPT, MARK_CPUFREQ,
     MARK_MEMINFO, MARK_NETDEV, MARK_PROC_STAT,
 };
+use crate::app::read_cache::{
+    bust_read_cache, cached_read, scope_ttl, store_read, CACHE_SCOPES, CACHE_SCOPE_APP_ICON,
+    CACHE_SCOPE_APP_INFO, CACHE_SCOPE_DEVICE_FILES,
+};
+use crate::app::readiness::{
+    count_anr_windows, evaluate_readiness, normalize_readiness_thresholds, parse_epoch_secs,
+    parse_keyguard_locked, parse_screen_on, parse_wifi_connected, ReadinessSignals,
+};
+use crate::app::restricted::{
+    apply_restricted_mode_change, ensure_action_allowed, preserve_restricted_mode,
+    redact_restricted_mode,
+};
+use crate::app::scheduler::TaskScheduler;
+use crate::app::screenshot_diff::{
+    default_diff_path, diff_images, normalize_similarity_threshold, read_png_rgba, write_png_rgba,
+    RgbaImage,
+};
+use crate::app::session_report::{
+    in_time_range, render_session_report_html, SessionCapture, SessionDeviceReport, SessionReport,
+};
+use crate::app::setup_wizard::{
+    apply_step_outcome, check_output_dir, default_wizard_state, device_authorization_outcome,
+    ensure_step_reachable, load_wizard_state, optional_tools_summary, save_wizard_state,
+    setup_wizard_path, skip_step, StepFailure, SKIPPABLE_STEPS, STEP_ADB_LOCATED,
+    STEP_DEVICE_AUTHORIZED, STEP_OPTIONAL_TOOLS_DETECTED, STEP_OUTPUT_DIR_CHOSEN,
+};
+use crate::app::shell_pipeline::{compile_pipeline, quote_shell_value, run_pipeline};
+use crate::app::shell_stream::{
+    clamp_stream_timeout_secs, ShellStream, ShellStreamEmitter, SHELL_STREAM_EVENT_NAME,
+};
+use crate::app::soak_test::{
+    logcat_snapshot_args, SoakPlan, SoakProgress, SoakReading, SOAK_MONITOR_NET, SOAK_MONITOR_PERF,
+    SOAK_MONITOR_THERMAL, SOAK_SAMPLES_FILE,
+};
 use crate::app::state::{
-    AppState, BugreportHandle, LogcatHandle, NetProfilerHandle, PerfMonitorHandle, RecordingHandle,
+    ApkWatchHandle, AppListSnapshot, AppState, AudioForwardHandle, BugreportHandle,
+    CaptureContextHandle, DeviceFarmHandle, DmesgHandle, FileTransferHandle, GestureRecorderHandle,
+    InputBroadcastHandle, LeakWatchHandle, LifecycleStreamHandle, LogcatHandle, LogcatSource,
+    MonkeyRunHandle, NetProfilerHandle, PerfMonitorHandle, RecordingConversionHandle,
+    RecordingHandle, SoakFeeds, SoakTestHandle, ThermalMonitorHandle,
 };
+use crate::app::storage::{ensure_host_storage, BUGREPORT_ESTIMATED_BYTES};
+use crate::app::telephony::{parse_telephony_info, TELEPHONY_SCRIPT};
 use crate::app::terminal::{TerminalSession, TERMINAL_EVENT_NAME};
+use crate::app::thermal::parse::{
+    build_thermal_script, detect_threshold_crossings, parse_thermal_service_temperatures,
+    parse_thermal_status, parse_thermal_zones, split_thermal_sections, throttle_status_label,
+};
+use crate::app::timeline::{
+    classify_logcat_line, parse_time_bound, query_timeline_events, record_timeline_event,
+    TIMELINE_CRASH, TIMELINE_INSTALL, TIMELINE_MEMORY_LIMIT, TIMELINE_PERF_ALERT, TIMELINE_REBOOT,
+    TIMELINE_SCREEN, TIMELINE_SCREEN_RECORD,
+};
+use crate::app::timeouts::{command_timeout, TimeoutKind};
+use crate::app::triage::{
+    render_device_state, triage_dir_name, TRIAGE_BUNDLE_READY_EVENT, TRIAGE_DEVICE_STATE_FILE,
+    TRIAGE_LOGCAT_FILE, TRIAGE_META_FILE, TRIAGE_OUTPUT_FILE, TRIAGE_SCREENSHOT_FILE,
+    TRIAGE_STATE_COMMANDS,
+};
 use crate::app::ui_capture::png_bytes_to_data_url;
+use crate::app::ui_diff::{build_ui_hierarchy_diff, extract_ui_nodes};
+use crate::app::ui_view_hierarchy::{
+    parse_accessibility_windows_to_xml, parse_view_hierarchy_to_xml, parse_view_server_dump_to_xml,
+};
 use crate::app::ui_xml::render_device_ui_html;
+use crate::app::upload::{
+    build_upload_request, post_json_with_curl, quote_config_value, resolve_curl_program, run_curl,
+    upload_timeout, MAX_UPLOAD_FILES,
+};
 
 #[cfg(test)]
 mod tests;
@@ -85,7 +408,7 @@ fn start_logcat_inner(
     registry: &std::sync::Mutex<std::collections::HashMap<String, LogcatHandle>>,
     emitter: LogcatEmitter,
     trace_id: &str,
-    spawn_logcat: impl FnOnce(&str, &str, Option<&str>, &str) -> Result<std::process::Child, AppError>,
+    spawn_logcat: impl FnOnce(&str, &str, Option<&str>, &str) -> Result<LogcatSource, AppError>,
 ) -> Result<bool, AppError> {
     ensure_non_empty(&serial, "serial", trace_id)?;
 
@@ -93,24 +416,31 @@ fn start_logcat_inner(
         .lock()
         .map_err(|_| AppError::system("Logcat registry locked", trace_id))?;
     if guard.contains_key(&serial) {
-        return Err(AppError::validation("Logcat already running", trace_id));
+        return Err(AppError::validation(
+            Message::keyed("already_running").with("subject", "Logcat"),
+            trace_id,
+        ));
     }
 
-    let mut child = spawn_logcat(
+    let mut source = spawn_logcat(
         adb_program,
         &serial,
         filter.as_deref().filter(|value| !value.trim().is_empty()),
         trace_id,
     )?;
 
-    let stdout = child
-        .stdout
-        .take()
-        .ok_or_else(|| AppError::system("Failed to capture logcat stdout", trace_id))?;
-    let stderr = child
-        .stderr
-        .take()
-        .ok_or_else(|| AppError::system("Failed to capture logcat stderr", trace_id))?;
+    let (stdout, stderr) = match source.take_output() {
+        Ok(output) => output,
+        Err(err) => {
+            source.stop();
+            return Err(AppError::system(
+                Message::keyed("operation_failed")
+                    .with("action", "capture logcat output")
+                    .with("detail", &err),
+                trace_id,
+            ));
+        }
+    };
 
     let stop_flag = Arc::new(AtomicBool::new(false));
     let stop_flag_stdout = Arc::clone(&stop_flag);
@@ -131,12 +461,16 @@ fn start_logcat_inner(
                 break;
             }
             let line = match line_result {
-                Ok(line) => line,
+                // Legacy `shell:` sessions may translate newlines to CRLF.
+                Ok(line) => line.trim_end_matches('\r').to_string(),
                 Err(err) => {
                     warn!(trace_id = %trace_stdout, error = %err, "failed to read logcat stdout");
                     break;
                 }
             };
+            if let Some((kind, summary)) = classify_logcat_line(&line) {
+                record_timeline_event(&serial_stdout, kind, &summary, &trace_stdout);
+            }
             pending.push(line);
             if pending.len() >= batch_limit || last_emit.elapsed() >= batch_delay {
                 let batch = std::mem::take(&mut pending);
@@ -199,10 +533,57 @@ fn start_logcat_inner(
         }
     });
 
-    guard.insert(serial, LogcatHandle { child, stop_flag });
+    guard.insert(serial, LogcatHandle { source, stop_flag });
     Ok(true)
 }
 
+/// `adb logcat` over the adb server socket when it serves this device, otherwise the adb
+/// binary. The filter is quoted token by token so it cannot run other device commands.
+fn spawn_logcat_source(
+    program: &str,
+    serial: &str,
+    filter: Option<&str>,
+    trace_id: &str,
+) -> Result<LogcatSource, AppError> {
+    if let Some(client) = socket_client_for_serial(program, serial) {
+        let mut command = "logcat".to_string();
+        for token in filter.unwrap_or_default().split_whitespace() {
+            command.push(' ');
+            command.push_str(&quote_shell_value(token));
+        }
+        match client.shell_stream(
+            serial,
+            &command,
+            command_timeout(TimeoutKind::Probe, trace_id),
+        ) {
+            Ok(stream) => {
+                let mut args = vec!["-s".to_string(), serial.to_string()];
+                args.extend(command.split(' ').map(str::to_string));
+                record_spawn(program, &args, trace_id, Utc::now(), Instant::now(), true);
+                return Ok(LogcatSource::Socket(stream));
+            }
+            Err(err) => {
+                warn!(trace_id = %trace_id, serial = %serial, error = %err, "logcat over adb socket unavailable, spawning adb");
+            }
+        }
+    }
+    let mut cmd = adb_command_for_serial(program, serial);
+    cmd.args(["-s", serial, "logcat"]);
+    if let Some(filter) = filter {
+        cmd.args(filter.split_whitespace());
+    }
+    spawn_logged(cmd.stdout(Stdio::piped()).stderr(Stdio::piped()), trace_id)
+        .map(LogcatSource::Process)
+        .map_err(|err| {
+            AppError::dependency(
+                Message::keyed("start_failed")
+                    .with("subject", "logcat")
+                    .with("detail", &err),
+                trace_id,
+            )
+        })
+}
+
 fn stop_logcat_inner(
     serial: String,
     registry: &std::sync::Mutex<std::collections::HashMap<String, LogcatHandle>>,
@@ -215,11 +596,15 @@ fn stop_logcat_inner(
         .map_err(|_| AppError::system("Logcat registry locked", trace_id))?;
     let mut handle = match guard.remove(&serial) {
         Some(handle) => handle,
-        None => return Err(AppError::validation("Logcat not running", trace_id)),
+        None => {
+            return Err(AppError::validation(
+                Message::keyed("not_running").with("subject", "Logcat"),
+                trace_id,
+            ))
+        }
     };
     handle.stop_flag.store(true, Ordering::Relaxed);
-    let _ = handle.child.kill();
-    let _ = handle.child.wait();
+    handle.source.stop();
     Ok(true)
 }
 
@@ -240,19 +625,7 @@ pub fn smoke_start_logcat_stream(
         registry,
         emitter,
         trace_id,
-        |program, serial, filter, trace_id| {
-            let mut cmd = Command::new(program);
-            cmd.args(["-s", serial, "logcat"]);
-            if let Some(filter) = filter {
-                cmd.args(filter.split_whitespace());
-            }
-            cmd.stdout(Stdio::piped())
-                .stderr(Stdio::piped())
-                .spawn()
-                .map_err(|err| {
-                    AppError::dependency(format!("Failed to start logcat: {err}"), trace_id)
-                })
-        },
+        spawn_logcat_source,
     )
 }
 
@@ -295,7 +668,7 @@ fn sanitize_net_profiler_pinned_uids(
     }
     if unique.len() > MAX_PINNED_UIDS {
         return Err(AppError::validation(
-            format!("Too many pinned UIDs (max {MAX_PINNED_UIDS})"),
+            Message::keyed("too_many_pinned_uids").with("max", MAX_PINNED_UIDS),
             trace_id,
         ));
     }
@@ -318,7 +691,7 @@ fn start_perf_monitor_inner(
         .map_err(|_| AppError::system("Perf monitor registry locked", trace_id))?;
     if guard.contains_key(&serial) {
         return Err(AppError::validation(
-            "Perf monitor already running",
+            Message::keyed("already_running").with("subject", "Perf monitor"),
             trace_id,
         ));
     }
@@ -342,7 +715,12 @@ fn stop_perf_monitor_inner(
             .map_err(|_| AppError::system("Perf monitor registry locked", trace_id))?;
         match guard.remove(&serial) {
             Some(handle) => handle,
-            None => return Err(AppError::validation("Perf monitor not running", trace_id)),
+            None => {
+                return Err(AppError::validation(
+                    Message::keyed("not_running").with("subject", "Perf monitor"),
+                    trace_id,
+                ))
+            }
         }
     };
 
@@ -354,6 +732,87 @@ fn stop_perf_monitor_inner(
     Ok(true)
 }
 
+fn clamp_thermal_interval_ms(input: Option<u64>) -> u64 {
+    let value = input.unwrap_or(5000);
+    value.clamp(1000, 30000)
+}
+
+fn clamp_thermal_threshold_decic(input: Option<i32>) -> i32 {
+    let value = input.unwrap_or(450);
+    value.clamp(200, 1200)
+}
+
+fn start_thermal_monitor_inner(
+    serial: String,
+    registry: &std::sync::Mutex<std::collections::HashMap<String, ThermalMonitorHandle>>,
+    trace_id: &str,
+    spawn: impl FnOnce(Arc<AtomicBool>) -> std::thread::JoinHandle<()>,
+) -> Result<bool, AppError> {
+    ensure_non_empty(&serial, "serial", trace_id)?;
+
+    let mut guard = registry
+        .lock()
+        .map_err(|_| AppError::system("Thermal monitor registry locked", trace_id))?;
+    if guard.contains_key(&serial) {
+        return Err(AppError::validation(
+            Message::keyed("already_running").with("subject", "Thermal monitor"),
+            trace_id,
+        ));
+    }
+
+    let stop_flag = Arc::new(AtomicBool::new(false));
+    let join = spawn(Arc::clone(&stop_flag));
+    guard.insert(serial, ThermalMonitorHandle { stop_flag, join });
+    Ok(true)
+}
+
+fn stop_thermal_monitor_inner(
+    serial: String,
+    registry: &std::sync::Mutex<std::collections::HashMap<String, ThermalMonitorHandle>>,
+    trace_id: &str,
+) -> Result<bool, AppError> {
+    ensure_non_empty(&serial, "serial", trace_id)?;
+
+    let handle = {
+        let mut guard = registry
+            .lock()
+            .map_err(|_| AppError::system("Thermal monitor registry locked", trace_id))?;
+        match guard.remove(&serial) {
+            Some(handle) => handle,
+            None => {
+                return Err(AppError::validation(
+                    Message::keyed("not_running").with("subject", "Thermal monitor"),
+                    trace_id,
+                ))
+            }
+        }
+    };
+
+    handle.stop_flag.store(true, Ordering::Relaxed);
+    handle
+        .join
+        .join()
+        .map_err(|_| AppError::system("Thermal monitor thread panicked", trace_id))?;
+    Ok(true)
+}
+
+/// Turn one thermal script run into a snapshot; HAL readings win over raw thermal zones.
+fn build_thermal_snapshot(output: &str, ts_ms: i64) -> ThermalSnapshot {
+    let (service, zones) = split_thermal_sections(output);
+    let mut readings = parse_thermal_service_temperatures(&service);
+    if readings.is_empty() {
+        readings = parse_thermal_zones(&zones);
+    }
+    let thermal_status = parse_thermal_status(&service);
+    ThermalSnapshot {
+        ts_ms,
+        thermal_status,
+        throttle_label: thermal_status.map(|status| throttle_status_label(status).to_string()),
+        max_temp_decic: readings.iter().map(|reading| reading.temp_decic).max(),
+        readings,
+    }
+}
+
 fn start_net_profiler_inner(
     serial: String,
     registry: &std::sync::Mutex<std::collections::HashMap<String, NetProfilerHandle>>,
@@ -368,7 +827,7 @@ fn start_net_profiler_inner(
         .map_err(|_| AppError::system("Net profiler registry locked", trace_id))?;
     if guard.contains_key(&serial) {
         return Err(AppError::validation(
-            "Net profiler already running",
+            Message::keyed("already_running").with("subject", "Net profiler"),
             trace_id,
         ));
     }
@@ -400,7 +859,12 @@ fn stop_net_profiler_inner(
             .map_err(|_| AppError::system("Net profiler registry locked", trace_id))?;
         match guard.remove(&serial) {
             Some(handle) => handle,
-            None => return Err(AppError::validation("Net profiler not running", trace_id)),
+            None => {
+                return Err(AppError::validation(
+                    Message::keyed("not_running").with("subject", "Net profiler"),
+                    trace_id,
+                ))
+            }
         }
     };
 
@@ -427,7 +891,12 @@ fn set_net_profiler_pinned_uids_inner(
             .map_err(|_| AppError::system("Net profiler registry locked", trace_id))?;
         match guard.get(&serial) {
             Some(handle) => Arc::clone(&handle.pinned_uids),
-            None => return Err(AppError::validation("Net profiler not running", trace_id)),
+            None => {
+                return Err(AppError::validation(
+                    Message::keyed("not_running").with("subject", "Net profiler"),
+                    trace_id,
+                ))
+            }
         }
     };
 
@@ -488,7 +957,7 @@ pub fn smoke_start_perf_monitor(
                 let output = match run_command_with_timeout(
                     &adb_program_spawn,
                     &args,
-                    Duration::from_secs(5),
+                    command_timeout(TimeoutKind::Probe, &trace_spawn),
                     &trace_spawn,
                 ) {
                     Ok(out) => out,
@@ -594,6 +1063,7 @@ pub fn smoke_start_perf_monitor(
                     },
                     display_refresh_hz_x100: None,
                     missed_frames_per_sec_x100: None,
+                    cpu_clusters: Vec::new(),
                 });
 
                 cpu_prev = Some(cpu_curr);
@@ -661,7 +1131,10 @@ pub fn smoke_launch_app(
 ) -> Result<Vec<CommandResult>, AppError> {
     ensure_non_empty(&package_name, "package_name", trace_id)?;
     if serials.is_empty() {
-        return Err(AppError::validation("serials is required", trace_id));
+        return Err(AppError::validation(
+            Message::keyed("required").with("field", "serials"),
+            trace_id,
+        ));
     }
 
     let adb_program = get_adb_program(trace_id)?;
@@ -699,7 +1172,7 @@ pub fn smoke_launch_app(
             let output = run_command_with_timeout(
                 &adb_program_clone,
                 &args,
-                Duration::from_secs(10),
+                command_timeout(TimeoutKind::DeviceCommand, &trace_clone),
                 &trace_clone,
             )?;
             Ok((
@@ -727,14 +1200,54 @@ pub fn smoke_launch_app(
 
 fn emit_perf_event(app: &AppHandle, event: PerfEvent) {
     let trace_id = event.trace_id.clone();
-    if let Err(err) = app.emit("perf-snapshot", event) {
+    if let Err(err) = emit_tracked(app, "perf-snapshot", event) {
         warn!(trace_id = %trace_id, error = %err, "failed to emit perf snapshot");
     }
 }
 
+fn emit_perf_frames(app: &AppHandle, frames: Vec<PerfMultiFrame>, trace_id: &str) {
+    for frame in frames {
+        let event = PerfFrameEvent {
+            frame,
+            trace_id: trace_id.to_string(),
+        };
+        if let Err(err) = emit_tracked(app, "perf-multi-frame", event) {
+            warn!(trace_id = %trace_id, error = %err, "failed to emit perf multi-device frame");
+        }
+    }
+}
+
+/// Feed a snapshot to the multi-device aggregator, if aggregation covers this serial.
+fn publish_to_perf_aggregator(
+    app: &AppHandle,
+    aggregator: &std::sync::Mutex<Option<PerfFrameAggregator>>,
+    serial: &str,
+    snapshot: &PerfSnapshot,
+    trace_id: &str,
+) {
+    let frames = match aggregator.lock() {
+        Ok(mut guard) => match guard.as_mut() {
+            Some(aggregator) => aggregator.push(serial, snapshot.clone()),
+            None => return,
+        },
+        Err(_) => {
+            warn!(trace_id = %trace_id, "perf aggregator lock poisoned");
+            return;
+        }
+    };
+    emit_perf_frames(app, frames, trace_id);
+}
+
+fn emit_thermal_event(app: &AppHandle, event: ThermalEvent) {
+    let trace_id = event.trace_id.clone();
+    if let Err(err) = emit_tracked(app, "thermal-snapshot", event) {
+        warn!(trace_id = %trace_id, error = %err, "failed to emit thermal snapshot");
+    }
+}
+
 fn emit_net_profiler_event(app: &AppHandle, event: NetProfilerEvent) {
     let trace_id = event.trace_id.clone();
-    if let Err(err) = app.emit("net-profiler-snapshot", event) {
+    if let Err(err) = emit_tracked(app, "net-profiler-snapshot", event) {
         warn!(
             trace_id = %trace_id,
             error = %err,
@@ -756,6 +1269,7 @@ struct PerfSnapshotInput {
     battery: BatteryTotals,
     display_refresh_hz_x100: Option<u16>,
     missed_frames_per_sec_x100: Option<u16>,
+    cpu_clusters: Vec<CpuCluster>,
 }
 
 fn build_perf_snapshot(input: PerfSnapshotInput) -> PerfSnapshot {
@@ -772,6 +1286,7 @@ fn build_perf_snapshot(input: PerfSnapshotInput) -> PerfSnapshot {
         battery,
         display_refresh_hz_x100,
         missed_frames_per_sec_x100,
+        cpu_clusters,
     } = input;
     let cpu_total_percent_x100 = cpu_prev.and_then(|prev| compute_cpu_percent_x100(prev, cpu_curr));
 
@@ -802,6 +1317,7 @@ fn build_perf_snapshot(input: PerfSnapshotInput) -> PerfSnapshot {
         battery_temp_decic: battery.temperature_decic,
         display_refresh_hz_x100,
         missed_frames_per_sec_x100,
+        cpu_clusters,
     }
 }
 
@@ -820,7 +1336,10 @@ fn reserve_bugreport_handle(
         .lock()
         .map_err(|_| AppError::system("Bugreport registry locked", trace_id))?;
     if guard.contains_key(serial) {
-        return Err(AppError::validation("Bugreport already running", trace_id));
+        return Err(AppError::validation(
+            Message::keyed("already_running").with("subject", "Bugreport"),
+            trace_id,
+        ));
     }
     guard.insert(
         serial.to_string(),
@@ -854,69 +1373,243 @@ pub struct PerfEvent {
 }
 
 #[derive(Clone, serde::Serialize)]
-pub struct NetProfilerEvent {
+pub struct PerfFrameEvent {
+    pub frame: PerfMultiFrame,
+    pub trace_id: String,
+}
+
+#[derive(Clone, serde::Serialize)]
+pub struct ThermalEvent {
     pub serial: String,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub snapshot: Option<NetProfilerSnapshot>,
+    pub snapshot: Option<ThermalSnapshot>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub crossings: Vec<ThermalCrossing>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<String>,
     pub trace_id: String,
 }
 
 #[derive(Clone, serde::Serialize)]
-pub struct FileTransferProgressEvent {
+pub struct MemoryLeakEvent {
     pub serial: String,
-    pub direction: String,
-    pub progress: Option<u8>,
-    pub message: Option<String>,
+    pub package_name: String,
+    pub pss_kb: u64,
+    pub slope_kb_per_min: f64,
+    pub threshold_kb_per_min: f64,
+    pub window_secs: f64,
     pub trace_id: String,
 }
 
-const APK_INSTALL_EVENT_NAME: &str = "apk-install-event";
-const APK_INSTALL_OUTPUT_MAX_LEN: usize = 4096;
-
 #[derive(Clone, serde::Serialize)]
-pub struct ApkInstallEvent {
+pub struct NetProfilerEvent {
     pub serial: String,
-    pub event: String,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub success: Option<bool>,
+    pub snapshot: Option<NetProfilerSnapshot>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub trace_id: String,
+}
+
+#[derive(Clone, serde::Serialize)]
+pub struct FileTransferProgressEvent {
+    pub serial: String,
+    pub direction: String,
+    pub progress: Option<u8>,
     pub message: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub error_code: Option<String>,
+    pub bytes_transferred: Option<u64>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub raw_output: Option<String>,
+    pub total_bytes: Option<u64>,
     pub trace_id: String,
 }
 
-fn resolve_trace_id(input: Option<String>) -> String {
-    input
-        .filter(|value| !value.trim().is_empty())
-        .unwrap_or_else(|| Uuid::new_v4().to_string())
+const FILE_TRANSFER_PROGRESS_EVENT: &str = "file-transfer-progress";
+const FILE_TRANSFER_PROGRESS_INTERVAL: Duration = Duration::from_millis(200);
+const FILE_TRANSFER_CANCELLED: &str = "Cancelled by user";
+
+/// Progress callback for sync transfers; emits at most every
+/// `FILE_TRANSFER_PROGRESS_INTERVAL`, plus the final byte.
+fn file_transfer_progress_reporter(
+    app: AppHandle,
+    serial: &str,
+    direction: &str,
+    trace_id: &str,
+    total: u64,
+) -> impl FnMut(u64) {
+    let serial = serial.to_string();
+    let direction = direction.to_string();
+    let trace_id = trace_id.to_string();
+    let mut last_emit: Option<Instant> = None;
+    move |bytes: u64| {
+        let due = last_emit.is_none_or(|at| at.elapsed() >= FILE_TRANSFER_PROGRESS_INTERVAL);
+        if !due && bytes < total {
+            return;
+        }
+        last_emit = Some(Instant::now());
+        let percent = if total == 0 {
+            100
+        } else {
+            (bytes.min(total) * 100 / total) as u8
+        };
+        if let Err(err) = emit_tracked(
+            &app,
+            FILE_TRANSFER_PROGRESS_EVENT,
+            FileTransferProgressEvent {
+                serial: serial.clone(),
+                direction: direction.clone(),
+                progress: Some(percent),
+                message: Some(format!("{percent}%")),
+                bytes_transferred: Some(bytes),
+                total_bytes: Some(total),
+                trace_id: trace_id.clone(),
+            },
+        ) {
+            warn!(trace_id = %trace_id, error = %err, "failed to emit file transfer progress");
+        }
+    }
 }
 
-fn ensure_non_empty(value: &str, field: &str, trace_id: &str) -> Result<(), AppError> {
-    if value.trim().is_empty() {
+fn begin_file_transfer(
+    state: &AppState,
+    serial: &str,
+    direction: &str,
+    trace_id: &str,
+) -> Result<Arc<AtomicBool>, AppError> {
+    let mut guard = state
+        .file_transfers
+        .lock()
+        .map_err(|_| AppError::system("File transfer registry locked", trace_id))?;
+    if guard.contains_key(trace_id) {
         return Err(AppError::validation(
-            format!("{field} is required"),
+            Message::keyed("already_running").with("subject", "A transfer with this trace id is"),
             trace_id,
         ));
     }
-    Ok(())
+    let cancel_flag = Arc::new(AtomicBool::new(false));
+    guard.insert(
+        trace_id.to_string(),
+        FileTransferHandle {
+            serial: serial.to_string(),
+            direction: direction.to_string(),
+            cancel_flag: Arc::clone(&cancel_flag),
+        },
+    );
+    Ok(cancel_flag)
 }
 
-fn validate_generate_bugreport_inputs(
-    serial: &str,
-    output_dir: &str,
-    trace_id: &str,
-) -> Result<(), AppError> {
-    ensure_non_empty(serial, "serial", trace_id)?;
-    ensure_non_empty(output_dir, "output_dir", trace_id)?;
-    Ok(())
+fn end_file_transfer(state: &AppState, trace_id: &str) {
+    match state.file_transfers.lock() {
+        Ok(mut guard) => {
+            guard.remove(trace_id);
+        }
+        Err(_) => warn!(trace_id = %trace_id, "file transfer registry locked, entry not removed"),
+    }
 }
 
-fn append_limited(buffer: &mut String, chunk: &str, max_len: usize) {
+/// Maps a sync transfer failure; `None` means the socket client could not serve the
+/// transfer before it started and the adb binary should take over.
+fn sync_transfer_error(err: SocketError, action: &str, trace_id: &str) -> Option<AppError> {
+    match err {
+        SocketError::Unavailable(_) | SocketError::Unsupported(_) => None,
+        SocketError::Cancelled => Some(AppError::system(FILE_TRANSFER_CANCELLED, trace_id)),
+        SocketError::TimedOut => Some(AppError::system(
+            format!(
+                "{action} stalled: no data for {}s",
+                TRANSFER_IDLE_TIMEOUT.as_secs()
+            ),
+            trace_id,
+        )),
+        SocketError::Io(message) => Some(AppError::dependency(
+            Message::keyed("action_failed")
+                .with("action", action)
+                .with("detail", &message),
+            trace_id,
+        )),
+    }
+}
+
+/// Sync-protocol transfers skip `run_command_with_timeout`, so log them as the `adb push`/`adb
+/// pull` they stand in for. Attempts the socket could not serve are left to the adb fallback.
+fn record_sync_transfer(
+    program: &str,
+    args: &[String],
+    trace_id: &str,
+    started_at: chrono::DateTime<Utc>,
+    started: Instant,
+    result: &Result<TransferOutcome, SocketError>,
+) {
+    let logged = match result {
+        Ok(outcome) => Ok(CommandOutput {
+            stdout: format!("<{} bytes>", outcome.bytes),
+            stderr: String::new(),
+            exit_code: Some(0),
+        }),
+        Err(SocketError::Unavailable(_) | SocketError::Unsupported(_)) => return,
+        Err(SocketError::TimedOut) => Err(AppError::system("Command timed out", trace_id)),
+        Err(err) => Err(AppError::dependency(err.to_string(), trace_id)),
+    };
+    record_command(program, args, trace_id, started_at, started, &logged);
+}
+
+const APK_INSTALL_EVENT_NAME: &str = "apk-install-event";
+const APK_INSTALL_OUTPUT_MAX_LEN: usize = 4096;
+
+#[derive(Clone, serde::Serialize)]
+pub struct ApkWatchEvent {
+    pub path: String,
+    pub cycle: u32,
+    pub phase: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<ApkBatchInstallResult>,
+    pub relaunched: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub trace_id: String,
+}
+
+#[derive(Clone, serde::Serialize)]
+pub struct ApkInstallEvent {
+    pub serial: String,
+    pub event: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub success: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_code: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub raw_output: Option<String>,
+    pub trace_id: String,
+}
+
+fn resolve_trace_id(input: Option<String>) -> String {
+    input
+        .filter(|value| !value.trim().is_empty())
+        .unwrap_or_else(|| Uuid::new_v4().to_string())
+}
+
+fn ensure_non_empty(value: &str, field: &str, trace_id: &str) -> Result<(), AppError> {
+    if value.trim().is_empty() {
+        return Err(AppError::validation(
+            Message::keyed("required").with("field", field),
+            trace_id,
+        ));
+    }
+    Ok(())
+}
+
+fn validate_generate_bugreport_inputs(
+    serial: &str,
+    output_dir: &str,
+    trace_id: &str,
+) -> Result<(), AppError> {
+    ensure_non_empty(serial, "serial", trace_id)?;
+    ensure_non_empty(output_dir, "output_dir", trace_id)?;
+    Ok(())
+}
+
+fn append_limited(buffer: &mut String, chunk: &str, max_len: usize) {
     if max_len == 0 {
         return;
     }
@@ -931,6 +1624,24 @@ fn append_limited(buffer: &mut String, chunk: &str, max_len: usize) {
     }
 }
 
+const UPLOAD_PROGRESS_EVENT_NAME: &str = "upload-progress";
+
+#[derive(Clone, serde::Serialize)]
+pub struct UploadProgressEvent {
+    pub target: String,
+    pub path: String,
+    pub index: usize,
+    pub total: usize,
+    /// `started`, `completed` or `failed`.
+    pub status: String,
+    pub bytes: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub trace_id: String,
+}
+
 fn truncate_for_event(value: &str, max_len: usize) -> String {
     if max_len == 0 {
         return String::new();
@@ -938,25 +1649,54 @@ fn truncate_for_event(value: &str, max_len: usize) -> String {
     value.chars().take(max_len).collect()
 }
 
+#[allow(clippy::too_many_arguments)]
 fn run_adb_transfer_with_progress(
     program: &str,
     args: &[String],
     timeout: Duration,
     serial: &str,
     direction: &str,
+    cancel: &AtomicBool,
+    trace_id: &str,
+    app: AppHandle,
+) -> Result<CommandOutput, AppError> {
+    let started_at = Utc::now();
+    let started = Instant::now();
+    let result = execute_adb_transfer_with_progress(
+        program, args, timeout, serial, direction, cancel, trace_id, app,
+    );
+    record_command(program, args, trace_id, started_at, started, &result);
+    result
+}
+
+#[allow(clippy::too_many_arguments)]
+fn execute_adb_transfer_with_progress(
+    program: &str,
+    args: &[String],
+    timeout: Duration,
+    serial: &str,
+    direction: &str,
+    cancel: &AtomicBool,
     trace_id: &str,
     app: AppHandle,
-) -> Result<crate::app::adb::runner::CommandOutput, AppError> {
+) -> Result<CommandOutput, AppError> {
     use std::io::Read;
     use std::sync::Mutex;
     use std::time::Instant;
 
-    let mut child = Command::new(program)
+    let mut child = adb_command(&route_adb_program(program, args))
         .args(args)
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
         .spawn()
-        .map_err(|err| AppError::system(format!("Failed to spawn command: {err}"), trace_id))?;
+        .map_err(|err| {
+            AppError::system(
+                Message::keyed("operation_failed")
+                    .with("action", "spawn command")
+                    .with("detail", &err),
+                trace_id,
+            )
+        })?;
 
     let stdout = child
         .stdout
@@ -1007,13 +1747,16 @@ fn run_adb_transfer_with_progress(
                         if last_progress != Some(percent) {
                             last_progress = Some(percent);
                             let message = Some(format!("{percent}%"));
-                            if let Err(err) = app_stdout.emit(
-                                "file-transfer-progress",
+                            if let Err(err) = emit_tracked(
+                                &app_stdout,
+                                FILE_TRANSFER_PROGRESS_EVENT,
                                 FileTransferProgressEvent {
                                     serial: serial_string.clone(),
                                     direction: direction_string.clone(),
                                     progress: Some(percent),
                                     message,
+                                    bytes_transferred: None,
+                                    total_bytes: None,
                                     trace_id: trace_string.clone(),
                                 },
                             ) {
@@ -1049,12 +1792,18 @@ fn run_adb_transfer_with_progress(
         match child.try_wait() {
             Ok(Some(status)) => break status,
             Ok(None) => {
-                if start.elapsed() > timeout {
+                let cancelled = cancel.load(Ordering::Relaxed);
+                if cancelled || start.elapsed() > timeout {
                     let _ = child.kill();
                     let _ = child.wait();
                     let _ = stdout_handle.join();
                     let _ = stderr_handle.join();
-                    return Err(AppError::system("Command timed out".to_string(), trace_id));
+                    let message = if cancelled {
+                        FILE_TRANSFER_CANCELLED
+                    } else {
+                        "Command timed out"
+                    };
+                    return Err(AppError::system(message, trace_id));
                 }
                 std::thread::sleep(Duration::from_millis(50));
             }
@@ -1062,7 +1811,9 @@ fn run_adb_transfer_with_progress(
                 let _ = stdout_handle.join();
                 let _ = stderr_handle.join();
                 return Err(AppError::system(
-                    format!("Failed to poll command: {err}"),
+                    Message::keyed("operation_failed")
+                        .with("action", "poll command")
+                        .with("detail", &err),
                     trace_id,
                 ));
             }
@@ -1088,3516 +1839,15549 @@ fn run_adb_transfer_with_progress(
     })
 }
 
+/// Configured adb program, validated once and cached until the config is saved. Failures are
+/// not cached so fixing the path takes effect on the next command.
 fn get_adb_program(trace_id: &str) -> Result<String, AppError> {
+    if let Some(program) = cached_adb_program(&config_path()) {
+        return Ok(program);
+    }
+    resolve_and_cache_adb_program(trace_id)
+}
+
+fn adb_protocol_version(program: &str, trace_id: &str) -> Option<String> {
+    let args = vec!["version".to_string()];
+    match run_command_with_timeout(
+        program,
+        &args,
+        command_timeout(TimeoutKind::Probe, trace_id),
+        trace_id,
+    ) {
+        Ok(output) => parse_adb_protocol_version(&output.stdout),
+        Err(err) => {
+            warn!(trace_id = %trace_id, program = %program, error = %err.error, "failed to read adb version");
+            None
+        }
+    }
+}
+
+/// Overrides reuse the default adb server, so each must match the default binary's protocol
+/// version. Returns the conflicting overrides with the reason.
+fn adb_override_conflicts(
+    program: &str,
+    overrides: &[AdbPathOverride],
+    trace_id: &str,
+) -> Vec<(AdbPathOverride, String)> {
+    if overrides.is_empty() {
+        return Vec::new();
+    }
+    let default_version = adb_protocol_version(program, trace_id);
+    overrides
+        .iter()
+        .filter_map(|entry| {
+            let version = adb_protocol_version(&entry.command_path, trace_id);
+            override_version_conflict(entry, default_version.as_deref(), version.as_deref())
+                .map(|reason| (entry.clone(), reason))
+        })
+        .collect()
+}
+
+fn resolve_and_cache_adb_program(trace_id: &str) -> Result<String, AppError> {
+    let path = config_path();
     let config = load_config(trace_id)?;
     let program = resolve_adb_program(&config.adb.command_path);
     if let Err(message) = validate_adb_program(&program) {
         return Err(AppError::validation(message, trace_id));
     }
+    let conflicts = adb_override_conflicts(&program, &config.adb.path_overrides, trace_id);
+    for (_, reason) in &conflicts {
+        warn!(trace_id = %trace_id, reason = %reason, "ignoring adb path override");
+    }
+    let overrides: Vec<AdbPathOverride> = config
+        .adb
+        .path_overrides
+        .iter()
+        .filter(|entry| !conflicts.iter().any(|(conflict, _)| conflict == *entry))
+        .cloned()
+        .collect();
+    set_adb_path_routing(&program, &overrides);
+    set_socket_client_enabled(config.adb.socket_client);
+    store_adb_program(&path, &program);
     Ok(program)
 }
 
+/// Point adb commands at the configured server host. Applied when the config is loaded at
+/// startup and whenever it is saved, never while resolving the adb program.
+fn apply_active_adb_server(config: &AppConfig) {
+    set_active_adb_server(resolve_adb_server(&config.adb));
+}
+
+/// Apply the saved adb server host before the first command runs.
+pub fn init_active_adb_server() {
+    let trace_id = resolve_trace_id(None);
+    match load_config(&trace_id) {
+        Ok(config) => apply_active_adb_server(&config),
+        Err(err) => {
+            warn!(trace_id = %trace_id, error = %err.error, "failed to load config for adb server host")
+        }
+    }
+}
+
+/// Drop the cached adb program and resolve it again, e.g. after replacing the binary on disk
+/// or editing the config file by hand.
 #[tauri::command(async)]
-pub fn get_config(trace_id: Option<String>) -> Result<CommandResponse<AppConfig>, AppError> {
+pub fn reload_adb_program(trace_id: Option<String>) -> Result<CommandResponse<String>, AppError> {
     let trace_id = resolve_trace_id(trace_id);
-    let config = load_config(&trace_id)?;
-    Ok(CommandResponse {
-        trace_id,
-        data: config,
-    })
+    invalidate_adb_program_cache();
+    apply_active_adb_server(&load_config(&trace_id)?);
+    let data = resolve_and_cache_adb_program(&trace_id)?;
+    info!(trace_id = %trace_id, program = %data, "adb program reloaded");
+    Ok(CommandResponse { trace_id, data })
 }
 
 #[tauri::command(async)]
-pub fn save_app_config(
-    config: AppConfig,
+pub fn query_command_log(
+    query: Option<CommandLogQuery>,
     trace_id: Option<String>,
-) -> Result<CommandResponse<AppConfig>, AppError> {
+) -> Result<CommandResponse<Vec<CommandLogEntry>>, AppError> {
     let trace_id = resolve_trace_id(trace_id);
-    let config = normalize_config_for_save(config);
-    save_config(&config, &trace_id)?;
+    let entries = query_command_log_entries(&query.unwrap_or_default(), &trace_id)?;
     Ok(CommandResponse {
         trace_id,
-        data: config,
+        data: entries,
     })
 }
 
+fn app_log_status(settings: &LoggingSettings) -> AppLogStatus {
+    let levels = current_log_levels();
+    let (log_file, file_error) = app_log_file_status();
+    AppLogStatus {
+        default_level: levels
+            .as_ref()
+            .map(|levels| levels.default.clone())
+            .unwrap_or_else(|| settings.log_level.to_lowercase()),
+        module_levels: levels.map(|levels| levels.modules).unwrap_or_default(),
+        log_to_file: settings.log_to_file,
+        log_file: log_file.map(|path| path.to_string_lossy().to_string()),
+        max_log_files: settings.max_log_files,
+        log_file_size_mb: settings.log_file_size_mb,
+        file_error,
+    }
+}
+
 #[tauri::command(async)]
-pub fn reset_config(trace_id: Option<String>) -> Result<CommandResponse<AppConfig>, AppError> {
+pub fn get_app_log_status(
+    trace_id: Option<String>,
+) -> Result<CommandResponse<AppLogStatus>, AppError> {
     let trace_id = resolve_trace_id(trace_id);
-    let config = normalize_config_for_save(AppConfig::default());
-    save_config(&config, &trace_id)?;
+    let config = load_config(&trace_id)?;
     Ok(CommandResponse {
+        data: app_log_status(&config.logging),
         trace_id,
-        data: config,
     })
 }
 
+/// Change the backend log level at runtime. Without `module` the default level changes and is
+/// saved; module levels (`app::adb::runner`, `tauri`, ...) last until restart.
 #[tauri::command(async)]
-pub fn check_adb(
-    command_path: Option<String>,
+pub fn set_log_level(
+    module: Option<String>,
+    level: String,
     trace_id: Option<String>,
-) -> Result<CommandResponse<AdbInfo>, AppError> {
+) -> Result<CommandResponse<AppLogStatus>, AppError> {
     let trace_id = resolve_trace_id(trace_id);
-    info!(trace_id = %trace_id, "check_adb");
-
-    let config = load_config(&trace_id)?;
-    let program = command_path
+    ensure_non_empty(&level, "level", &trace_id)?;
+    let levels = apply_log_level(module.as_deref(), &level)
+        .map_err(|err| AppError::validation(err, &trace_id))?;
+    let mut config = load_config(&trace_id)?;
+    if module
         .as_deref()
-        .map(normalize_command_path)
-        .filter(|value| !value.is_empty())
-        .unwrap_or_else(|| resolve_adb_program(&config.adb.command_path));
-
-    if let Err(message) = validate_adb_program(&program) {
-        warn!(trace_id = %trace_id, error = %message, "adb validation failed");
-        return Ok(CommandResponse {
-            trace_id,
-            data: AdbInfo {
-                available: false,
-                version_output: String::new(),
-                command_path: program,
-                error: Some(message),
-            },
-        });
-    }
-
-    let args = vec!["version".to_string()];
-    let output = match run_command_with_timeout(&program, &args, Duration::from_secs(5), &trace_id)
+        .is_none_or(|module| module.trim().is_empty())
     {
-        Ok(output) => output,
-        Err(err) => {
-            warn!(trace_id = %trace_id, error = %err.error, "adb check failed");
-            return Ok(CommandResponse {
-                trace_id,
-                data: AdbInfo {
-                    available: false,
-                    version_output: String::new(),
-                    command_path: program,
-                    error: Some(err.error),
-                },
-            });
-        }
-    };
-
-    let mut version_output = output.stdout.trim().to_string();
-    let stderr = output.stderr.trim();
-    if !stderr.is_empty() {
-        if !version_output.is_empty() {
-            version_output.push('\n');
-        }
-        version_output.push_str(stderr);
+        config.logging.log_level = levels.default.to_uppercase();
+        config = normalize_config_for_save(config);
+        save_config(&config, &trace_id)?;
     }
-
-    let available = output.exit_code.unwrap_or_default() == 0;
+    info!(trace_id = %trace_id, directives = %levels.directives(), "log level changed");
     Ok(CommandResponse {
+        data: app_log_status(&config.logging),
         trace_id,
-        data: AdbInfo {
-            available,
-            version_output,
-            command_path: program,
-            error: if available {
-                None
-            } else if output.stderr.trim().is_empty() {
-                Some("ADB command returned a non-zero exit code".to_string())
-            } else {
-                Some(output.stderr.trim().to_string())
-            },
-        },
     })
 }
 
+/// Update and save the log file settings; the new limits apply to the next write.
 #[tauri::command(async)]
-pub fn export_diagnostics_bundle(
-    output_dir: Option<String>,
+pub fn set_log_rotation(
+    log_to_file: bool,
+    max_log_files: i32,
+    log_file_size_mb: i32,
     trace_id: Option<String>,
-) -> Result<CommandResponse<String>, AppError> {
+) -> Result<CommandResponse<AppLogStatus>, AppError> {
     let trace_id = resolve_trace_id(trace_id);
-    info!(trace_id = %trace_id, "export_diagnostics_bundle");
+    if max_log_files < 1 || log_file_size_mb < 1 {
+        return Err(AppError::validation(
+            Message::keyed("at_least")
+                .with("field", "max_log_files and log_file_size_mb")
+                .with("min", 1),
+            &trace_id,
+        ));
+    }
+    let mut config = load_config(&trace_id)?;
+    config.logging.log_to_file = log_to_file;
+    config.logging.max_log_files = max_log_files;
+    config.logging.log_file_size_mb = log_file_size_mb;
+    let config = normalize_config_for_save(config);
+    save_config(&config, &trace_id)?;
+    configure_app_log_file(&config.logging);
+    Ok(CommandResponse {
+        data: app_log_status(&config.logging),
+        trace_id,
+    })
+}
 
-    // Best-effort: diagnostics bundle should still be generated even if config is broken.
-    let adb_program = match load_config(&trace_id) {
-        Ok(config) => resolve_adb_program(&config.adb.command_path),
-        Err(err) => {
-            warn!(
-                trace_id = %trace_id,
-                error = %err,
-                "Failed to load config for diagnostics adb program, falling back to default"
-            );
-            "adb".to_string()
-        }
-    };
+/// Newest backend log entries (oldest first), optionally only `min_level` and above, for
+/// attaching to bug reports.
+#[tauri::command(async)]
+pub fn get_recent_app_logs(
+    limit: Option<usize>,
+    min_level: Option<String>,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<Vec<AppLogEntry>>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    let min_level = min_level
+        .filter(|level| !level.trim().is_empty())
+        .map(|level| normalize_log_level(&level))
+        .transpose()
+        .map_err(|err| AppError::validation(err, &trace_id))?;
+    Ok(CommandResponse {
+        data: recent_app_logs(limit, min_level.as_deref()),
+        trace_id,
+    })
+}
 
-    let bundle_path = diagnostics::export_diagnostics_bundle(&adb_program, output_dir, &trace_id)?;
+/// Chronological feed of installs, crashes, perf alerts, screen records and reboots for one device.
+#[tauri::command(async)]
+pub fn query_device_timeline(
+    query: TimelineQuery,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<Vec<TimelineEvent>>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    let events = query_timeline_events(&query, &trace_id)?;
     Ok(CommandResponse {
         trace_id,
-        data: bundle_path.to_string_lossy().to_string(),
+        data: events,
     })
 }
 
-fn load_device_detail(
-    serial: &str,
+fn upload_artifact(
+    curl_program: &str,
+    target: &UploadTarget,
+    path: &Path,
     trace_id: &str,
-    profile_devices: bool,
-    profile_slow_ms: u64,
-    mut run: impl FnMut(
-        &[String],
-        Duration,
-        &'static str,
-    ) -> Result<crate::app::adb::runner::CommandOutput, AppError>,
-) -> Option<DeviceDetail> {
-    let detail_started = Instant::now();
-    let serial_arg = serial.to_string();
+) -> Result<String, AppError> {
+    let request = build_upload_request(target, path, |name| std::env::var(name).ok())
+        .map_err(|message| AppError::validation(message, trace_id))?;
+    let bytes = fs::metadata(path)
+        .map(|meta| meta.len())
+        .unwrap_or_default();
+    run_curl(
+        curl_program,
+        &request.config_lines,
+        request.args,
+        upload_timeout(bytes),
+        "Upload",
+        trace_id,
+    )?;
+    Ok(request.url)
+}
 
-    let should_log = |elapsed_ms: u64| -> bool {
-        if !profile_devices {
-            return false;
+/// Upload finished artifacts (bugreports, recordings, logs) to a configured upload target,
+/// one file at a time, emitting `upload-progress` events.
+#[tauri::command(async)]
+pub fn upload_artifacts(
+    target: String,
+    paths: Vec<String>,
+    app: AppHandle,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<Vec<ArtifactUploadResult>>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&target, "target", &trace_id)?;
+    if paths.is_empty() {
+        return Err(AppError::validation(
+            Message::keyed("required").with("field", "paths"),
+            &trace_id,
+        ));
+    }
+    if paths.len() > MAX_UPLOAD_FILES {
+        return Err(AppError::validation(
+            Message::keyed("too_many_uploads").with("max", MAX_UPLOAD_FILES),
+            &trace_id,
+        ));
+    }
+
+    let config = load_config(&trace_id)?;
+    let upload_target = config
+        .upload
+        .targets
+        .iter()
+        .find(|entry| entry.name == target.trim())
+        .cloned()
+        .ok_or_else(|| {
+            AppError::validation(
+                Message::keyed("unknown_value")
+                    .with("subject", "upload target")
+                    .with("value", target.trim()),
+                &trace_id,
+            )
+        })?;
+    let curl_program = resolve_curl_program(&config);
+    info!(
+        trace_id = %trace_id,
+        target = %upload_target.name,
+        kind = %upload_target.kind,
+        files = paths.len(),
+        "uploading artifacts"
+    );
+
+    let total = paths.len();
+    let emit = |event: UploadProgressEvent| {
+        if let Err(err) = emit_tracked(&app, UPLOAD_PROGRESS_EVENT_NAME, event) {
+            warn!(trace_id = %trace_id, error = %err, "failed to emit upload progress");
         }
-        profile_slow_ms == 0 || elapsed_ms >= profile_slow_ms
     };
+    let mut results = Vec::with_capacity(total);
+    for (index, raw_path) in paths.into_iter().enumerate() {
+        let path = PathBuf::from(raw_path.trim());
+        let bytes = fs::metadata(&path)
+            .ok()
+            .filter(|meta| meta.is_file())
+            .map(|meta| meta.len());
+        let progress =
+            |status: &str, url: Option<String>, error: Option<String>| UploadProgressEvent {
+                target: upload_target.name.clone(),
+                path: raw_path.clone(),
+                index,
+                total,
+                status: status.to_string(),
+                bytes: bytes.unwrap_or_default(),
+                url,
+                error,
+                trace_id: trace_id.clone(),
+            };
 
-    let mut run_timed = |step: &'static str,
-                         args: Vec<String>,
-                         timeout: Duration|
-     -> (
-        u64,
-        Result<crate::app::adb::runner::CommandOutput, AppError>,
-    ) {
-        let started = Instant::now();
-        let result = run(&args, timeout, step);
-        let elapsed_ms = started.elapsed().as_millis() as u64;
-
-        if should_log(elapsed_ms) {
-            match &result {
-                Ok(output) => {
-                    info!(
-                        trace_id = %trace_id,
-                        serial = %serial,
-                        step,
-                        elapsed_ms,
-                        exit_code = ?output.exit_code,
-                        "adb device command timing"
-                    );
+        let outcome = match bytes {
+            Some(_) => {
+                emit(progress("started", None, None));
+                upload_artifact(&curl_program, &upload_target, &path, &trace_id)
+            }
+            None => Err(AppError::validation(
+                Message::keyed("not_found_value")
+                    .with("subject", "Artifact")
+                    .with("value", &raw_path),
+                &trace_id,
+            )),
+        };
+        let result = match outcome {
+            Ok(url) => {
+                emit(progress("completed", Some(url.clone()), None));
+                ArtifactUploadResult {
+                    path: raw_path.clone(),
+                    url: Some(url),
+                    bytes: bytes.unwrap_or_default(),
+                    success: true,
+                    error: None,
                 }
-                Err(err) => {
-                    warn!(
-                        trace_id = %trace_id,
-                        serial = %serial,
-                        step,
-                        elapsed_ms,
-                        error = %err,
-                        "adb device command failed (timing)"
-                    );
+            }
+            Err(err) => {
+                warn!(trace_id = %trace_id, path = %raw_path, error = %err.error, "artifact upload failed");
+                emit(progress("failed", None, Some(err.error.clone())));
+                ArtifactUploadResult {
+                    path: raw_path.clone(),
+                    url: None,
+                    bytes: bytes.unwrap_or_default(),
+                    success: false,
+                    error: Some(err.error),
                 }
             }
-        }
+        };
+        results.push(result);
+    }
 
-        (elapsed_ms, result)
-    };
+    Ok(CommandResponse {
+        trace_id,
+        data: results,
+    })
+}
 
-    // getprop is required to build base detail. If it fails, bail early to avoid spending up to
-    // 5s * N subcommands per device.
-    let getprop_args = vec![
-        "-s".to_string(),
-        serial_arg.clone(),
-        "shell".to_string(),
-        "getprop".to_string(),
-    ];
-    let (getprop_elapsed_ms, getprop) = run_timed("getprop", getprop_args, Duration::from_secs(5));
+const DEVICE_FARM_PROGRESS_EVENT_NAME: &str = "device-farm-progress";
+const DEVICE_FARM_STDOUT_LIMIT: u64 = 1024 * 1024;
+const DEVICE_FARM_ERROR_LINES: usize = 5;
 
-    let output = match getprop {
-        Ok(output) => output,
-        Err(err) => {
-            warn!(
-                trace_id = %trace_id,
-                serial = %serial,
-                step = "getprop",
-                elapsed_ms = getprop_elapsed_ms,
-                error = %err,
-                "failed to load device detail"
-            );
-            let detail_elapsed_ms = detail_started.elapsed().as_millis() as u64;
-            if should_log(detail_elapsed_ms) {
-                info!(
-                    trace_id = %trace_id,
-                    serial = %serial,
-                    step = "device_detail_total",
-                    elapsed_ms = detail_elapsed_ms,
-                    "device detail timing"
-                );
-            }
-            return None;
-        }
-    };
+#[derive(Clone, serde::Serialize)]
+pub struct DeviceFarmProgressEvent {
+    pub line: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub results_url: Option<String>,
+    pub trace_id: String,
+}
 
-    let mut detail = build_device_detail(serial, &parse_getprop_map(&output.stdout));
+struct GcloudRun {
+    exit_code: Option<i32>,
+    stdout: String,
+    results_url: Option<String>,
+    storage_url: Option<String>,
+    stderr_tail: VecDeque<String>,
+}
 
-    let battery_args = vec![
-        "-s".to_string(),
-        serial_arg.clone(),
-        "shell".to_string(),
-        "dumpsys".to_string(),
-        "battery".to_string(),
-    ];
-    let (_battery_elapsed_ms, battery) = run_timed("battery", battery_args, Duration::from_secs(5));
-    if let Ok(battery_output) = battery {
-        detail.battery_level = parse_battery_level(&battery_output.stdout);
+/// Copy the APK(s) and Robo script into a fresh bundle directory next to a gcloud arg file.
+fn prepare_device_farm_bundle(
+    request: &DeviceFarmRequest,
+    config: &AppConfig,
+    trace_id: &str,
+) -> Result<(DeviceFarmBundle, u32), AppError> {
+    let spec = build_farm_spec(request).map_err(|err| AppError::validation(err, trace_id))?;
+    let mut sources = vec![(&spec.app_apk, FARM_APP_FILE)];
+    if let Some(path) = &spec.test_apk {
+        sources.push((path, FARM_TEST_FILE));
     }
-
-    let wifi_args = vec![
-        "-s".to_string(),
-        serial_arg.clone(),
-        "shell".to_string(),
-        "settings".to_string(),
-        "get".to_string(),
-        "global".to_string(),
-        "wifi_on".to_string(),
-    ];
-    let (_wifi_elapsed_ms, wifi_output) = run_timed("wifi", wifi_args, Duration::from_secs(5));
-    if let Ok(wifi_output) = wifi_output {
-        detail.wifi_is_on = parse_settings_bool(&wifi_output.stdout);
+    if let Some(path) = &spec.robo_script {
+        sources.push((path, FARM_ROBO_SCRIPT_FILE));
     }
-
-    let bt_args = vec![
-        "-s".to_string(),
-        serial_arg.clone(),
-        "shell".to_string(),
-        "settings".to_string(),
-        "get".to_string(),
-        "global".to_string(),
-        "bluetooth_on".to_string(),
-    ];
-    let (_bt_elapsed_ms, bt_output) = run_timed("bluetooth", bt_args, Duration::from_secs(5));
-    if let Ok(bt_output) = bt_output {
-        detail.bt_is_on = parse_settings_bool(&bt_output.stdout);
+    let mut total_bytes = 0;
+    for (source, _) in &sources {
+        let meta = fs::metadata(source).ok().filter(|meta| meta.is_file());
+        let Some(meta) = meta else {
+            return Err(AppError::validation(
+                Message::keyed("not_found_value")
+                    .with("subject", "File")
+                    .with("value", source.display()),
+                trace_id,
+            ));
+        };
+        total_bytes += meta.len();
     }
 
-    let bt_state_args = vec![
-        "-s".to_string(),
-        serial_arg.clone(),
-        "shell".to_string(),
-        "cmd".to_string(),
-        "bluetooth_manager".to_string(),
-        "get-state".to_string(),
-    ];
-    let (_bt_state_elapsed_ms, bt_state_output) = run_timed(
-        "bluetooth_manager_state",
-        bt_state_args,
-        Duration::from_secs(5),
-    );
-    let bt_state = bt_state_output
-        .ok()
-        .and_then(|output| parse_bluetooth_manager_state(&output.stdout));
-    if detail.bt_is_on.is_none() {
-        if let Some(state) = bt_state.as_deref() {
-            detail.bt_is_on = Some(state.contains("ON"));
-        }
+    let requested = request
+        .output_dir
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty());
+    let output_dir = prepare_artifact_dir(config, ArtifactKind::Log, requested, trace_id)?;
+    ensure_host_storage(&output_dir, total_bytes, trace_id)?;
+    let bundle_dir = output_dir.join(format!(
+        "device_farm_{}",
+        Utc::now().format("%Y%m%d_%H%M%S")
+    ));
+    fs::create_dir_all(&bundle_dir).map_err(|err| {
+        AppError::system(
+            Message::keyed("operation_failed")
+                .with("action", "create bundle dir")
+                .with("detail", &err),
+            trace_id,
+        )
+    })?;
+    for (source, name) in sources {
+        fs::copy(source, bundle_dir.join(name)).map_err(|err| {
+            AppError::system(
+                Message::keyed("operation_failed")
+                    .with("action", format!("copy {} into bundle", source.display()))
+                    .with("detail", &err),
+                trace_id,
+            )
+        })?;
     }
-    detail.bluetooth_manager_state = bt_state;
+    let argfile_path = bundle_dir.join(FARM_ARGFILE_NAME);
+    fs::write(
+        &argfile_path,
+        render_farm_argfile(&spec, &config.device_farm.results_bucket),
+    )
+    .map_err(|err| {
+        AppError::system(
+            Message::keyed("operation_failed")
+                .with("action", "write gcloud arg file")
+                .with("detail", &err),
+            trace_id,
+        )
+    })?;
 
-    let audio_args = vec![
-        "-s".to_string(),
-        serial_arg.clone(),
-        "shell".to_string(),
-        "dumpsys".to_string(),
-        "audio".to_string(),
-    ];
-    let (_audio_elapsed_ms, audio_output) = run_timed("audio", audio_args, Duration::from_secs(5));
-    if let Ok(audio_output) = audio_output {
-        detail.audio_state = parse_audio_summary(&audio_output.stdout);
-    }
+    let mut command = vec![resolve_gcloud_program(config)];
+    command.extend(build_gcloud_run_args(&config.device_farm.project_id));
+    Ok((
+        DeviceFarmBundle {
+            bundle_dir: bundle_dir.to_string_lossy().to_string(),
+            argfile_path: argfile_path.to_string_lossy().to_string(),
+            command,
+        },
+        spec.timeout_minutes,
+    ))
+}
 
-    let gms_args = vec![
-        "-s".to_string(),
-        serial_arg.clone(),
-        "shell".to_string(),
-        "dumpsys".to_string(),
-        "package".to_string(),
-        "com.google.android.gms".to_string(),
-    ];
-    let (_gms_elapsed_ms, gms_output) = run_timed("gms", gms_args, Duration::from_secs(5));
-    if let Ok(gms_output) = gms_output {
-        detail.gms_version = parse_gms_version_name(&gms_output.stdout);
+fn kill_device_farm_process(child_holder: &Arc<std::sync::Mutex<Option<std::process::Child>>>) {
+    if let Ok(mut guard) = child_holder.lock() {
+        if let Some(child) = guard.as_mut() {
+            let _ = child.kill();
+            let _ = child.wait();
+        }
     }
+}
 
-    let wm_size_args = vec![
-        "-s".to_string(),
-        serial_arg.clone(),
-        "shell".to_string(),
-        "wm".to_string(),
-        "size".to_string(),
-    ];
-    let (wm_size_elapsed_ms, wm_size_output) =
-        run_timed("wm_size", wm_size_args, Duration::from_secs(5));
-    match wm_size_output {
-        Ok(out) => {
-            let parsed = parse_wm_size(&out.stdout);
-            if parsed.is_none() && !out.stdout.trim().is_empty() {
-                warn!(
-                    trace_id = %trace_id,
-                    serial = %serial,
-                    step = "wm_size",
-                    elapsed_ms = wm_size_elapsed_ms,
-                    output = %out.stdout.trim(),
-                    "failed to parse wm size"
-                );
+/// Forward each line of `stream` to `sender` from a helper thread, so the caller can wait
+/// with a timeout instead of blocking on the pipe.
+fn forward_lines<R: Read + Send + 'static>(stream: R, sender: std::sync::mpsc::Sender<String>) {
+    std::thread::spawn(move || {
+        for line in BufReader::new(stream).lines().map_while(Result::ok) {
+            if sender.send(line).is_err() {
+                break;
             }
-            detail.resolution = parsed;
-        }
-        Err(err) => {
-            warn!(
-                trace_id = %trace_id,
-                serial = %serial,
-                step = "wm_size",
-                elapsed_ms = wm_size_elapsed_ms,
-                error = %err,
-                "failed to load wm size"
-            );
         }
+    });
+}
+
+/// Run gcloud from the bundle directory, forwarding each stderr line as a progress event.
+/// stderr is read on a helper thread so the timeout and cancel flag are honoured while
+/// gcloud is quiet.
+fn run_gcloud_streaming(
+    bundle: &DeviceFarmBundle,
+    timeout: Duration,
+    app: &AppHandle,
+    trace_id: &str,
+    cancel_flag: &Arc<AtomicBool>,
+    child_holder: &Arc<std::sync::Mutex<Option<std::process::Child>>>,
+) -> Result<GcloudRun, String> {
+    let (program, args) = bundle
+        .command
+        .split_first()
+        .ok_or_else(|| "gcloud command is empty".to_string())?;
+    let mut child = adb_command(program)
+        .args(args)
+        .current_dir(&bundle.bundle_dir)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|err| format!("Failed to launch gcloud: {err}"))?;
+    let stdout = child.stdout.take();
+    let stderr = child.stderr.take();
+    {
+        let mut guard = child_holder
+            .lock()
+            .map_err(|_| "Device farm registry locked".to_string())?;
+        *guard = Some(child);
     }
 
-    let df_args = vec![
-        "-s".to_string(),
-        serial_arg.clone(),
-        "shell".to_string(),
-        "df".to_string(),
-        "-k".to_string(),
-        "/data".to_string(),
-    ];
-    let (df_elapsed_ms, df_output) = run_timed("df", df_args, Duration::from_secs(5));
-    match df_output {
-        Ok(out) => match parse_df_total_kb(&out.stdout) {
-            Ok(total_kb) => {
-                detail.storage_total_bytes = Some(total_kb.saturating_mul(1024));
-            }
-            Err(err) => {
-                warn!(
-                    trace_id = %trace_id,
-                    serial = %serial,
-                    step = "df",
-                    elapsed_ms = df_elapsed_ms,
-                    error = %err,
-                    "failed to parse df output"
-                );
-            }
-        },
-        Err(err) => {
-            warn!(
-                trace_id = %trace_id,
-                serial = %serial,
-                step = "df",
-                elapsed_ms = df_elapsed_ms,
-                error = %err,
-                "failed to load df output"
-            );
-        }
+    let stdout_join = stdout.map(|stdout| {
+        std::thread::spawn(move || {
+            let mut buffer = String::new();
+            let _ = stdout
+                .take(DEVICE_FARM_STDOUT_LIMIT)
+                .read_to_string(&mut buffer);
+            buffer
+        })
+    });
+    let (sender, receiver) = std::sync::mpsc::channel::<String>();
+    if let Some(stderr) = stderr {
+        forward_lines(stderr, sender);
     }
 
-    let meminfo_args = vec![
-        "-s".to_string(),
-        serial_arg,
-        "shell".to_string(),
-        "cat".to_string(),
-        "/proc/meminfo".to_string(),
-    ];
-    let (meminfo_elapsed_ms, meminfo_output) =
-        run_timed("meminfo", meminfo_args, Duration::from_secs(5));
-    match meminfo_output {
-        Ok(out) => match parse_mem_totals(&out.stdout) {
-            Ok(mem) => {
-                detail.memory_total_bytes = Some(mem.total_bytes);
-            }
-            Err(err) => {
-                warn!(
-                    trace_id = %trace_id,
-                    serial = %serial,
-                    step = "meminfo",
-                    elapsed_ms = meminfo_elapsed_ms,
-                    error = %err,
-                    "failed to parse /proc/meminfo"
-                );
-            }
-        },
-        Err(err) => {
-            warn!(
-                trace_id = %trace_id,
-                serial = %serial,
-                step = "meminfo",
-                elapsed_ms = meminfo_elapsed_ms,
-                error = %err,
-                "failed to load /proc/meminfo"
-            );
+    let deadline = Instant::now() + timeout;
+    let mut run = GcloudRun {
+        exit_code: None,
+        stdout: String::new(),
+        results_url: None,
+        storage_url: None,
+        stderr_tail: VecDeque::new(),
+    };
+    loop {
+        if cancel_flag.load(Ordering::Relaxed) {
+            kill_device_farm_process(child_holder);
+            return Err("Cancelled by user".to_string());
+        }
+        if Instant::now() > deadline {
+            kill_device_farm_process(child_holder);
+            return Err(format!(
+                "gcloud did not finish within {} minutes",
+                timeout.as_secs() / 60
+            ));
+        }
+        let line = match receiver.recv_timeout(Duration::from_millis(200)) {
+            Ok(line) => line,
+            Err(std::sync::mpsc::RecvTimeoutError::Timeout) => continue,
+            Err(std::sync::mpsc::RecvTimeoutError::Disconnected) => break,
+        };
+        let line = line.trim().to_string();
+        if line.is_empty() {
+            continue;
+        }
+        let results_url = parse_results_url(&line);
+        if results_url.is_some() {
+            run.results_url.clone_from(&results_url);
+        }
+        if let Some(url) = parse_storage_url(&line) {
+            run.storage_url = Some(url);
+        }
+        if run.stderr_tail.len() == DEVICE_FARM_ERROR_LINES {
+            run.stderr_tail.pop_front();
+        }
+        run.stderr_tail.push_back(line.clone());
+        let event = DeviceFarmProgressEvent {
+            line,
+            results_url,
+            trace_id: trace_id.to_string(),
+        };
+        if let Err(err) = emit_tracked(app, DEVICE_FARM_PROGRESS_EVENT_NAME, event) {
+            warn!(trace_id = %trace_id, error = %err, "failed to emit device farm progress");
         }
     }
 
-    let detail_elapsed_ms = detail_started.elapsed().as_millis() as u64;
-    if should_log(detail_elapsed_ms) {
-        info!(
-            trace_id = %trace_id,
-            serial = %serial,
-            step = "device_detail_total",
-            elapsed_ms = detail_elapsed_ms,
-            "device detail timing"
-        );
+    let child = child_holder.lock().ok().and_then(|mut guard| guard.take());
+    if let Some(mut child) = child {
+        run.exit_code = child
+            .wait()
+            .map_err(|err| format!("Failed to wait for gcloud: {err}"))?
+            .code();
+    }
+    if cancel_flag.load(Ordering::Relaxed) {
+        return Err("Cancelled by user".to_string());
     }
+    run.stdout = stdout_join
+        .and_then(|join| join.join().ok())
+        .unwrap_or_default();
+    Ok(run)
+}
 
-    Some(detail)
+/// Package an APK with its Robo script or instrumentation test APK into a bundle that
+/// `gcloud firebase test android run` accepts, without submitting it.
+#[tauri::command(async)]
+pub fn export_device_farm_bundle(
+    request: DeviceFarmRequest,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<DeviceFarmBundle>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    let config = load_config(&trace_id)?;
+    let (bundle, _) = prepare_device_farm_bundle(&request, &config, &trace_id)?;
+    info!(trace_id = %trace_id, bundle = %bundle.bundle_dir, "exported device farm bundle");
+    Ok(CommandResponse {
+        trace_id,
+        data: bundle,
+    })
 }
 
+/// Package and submit a run to Firebase Test Lab, emitting `device-farm-progress` events
+/// with gcloud's output and reporting the results URL. One run at a time.
 #[tauri::command(async)]
-pub fn list_devices(
-    detailed: Option<bool>,
+pub fn run_device_farm_test(
+    request: DeviceFarmRequest,
+    app: AppHandle,
     state: State<'_, AppState>,
     trace_id: Option<String>,
-) -> Result<CommandResponse<Vec<DeviceInfo>>, AppError> {
+) -> Result<CommandResponse<DeviceFarmRunResult>, AppError> {
     let trace_id = resolve_trace_id(trace_id);
-    info!(trace_id = %trace_id, "list_devices");
-
-    // When troubleshooting device refresh performance, enable:
-    //   LAZY_BLACKTEA_PROFILE_DEVICES=1
-    // Optionally adjust the slow threshold:
-    //   LAZY_BLACKTEA_PROFILE_DEVICES_SLOW_MS=500
-    let profile_devices = std::env::var_os("LAZY_BLACKTEA_PROFILE_DEVICES").is_some();
-    let profile_slow_ms = std::env::var("LAZY_BLACKTEA_PROFILE_DEVICES_SLOW_MS")
-        .ok()
-        .and_then(|value| value.trim().parse::<u64>().ok())
-        .unwrap_or(500);
-    let list_started = Instant::now();
+    let config = load_config(&trace_id)?;
+    let (bundle, timeout_minutes) = prepare_device_farm_bundle(&request, &config, &trace_id)?;
 
-    let adb_program = get_adb_program(&trace_id)?;
-    let args = vec!["devices".to_string(), "-l".to_string()];
-    let devices_cmd_started = Instant::now();
-    let output = run_adb(&adb_program, &args, &trace_id)?;
-    let devices_cmd_elapsed_ms = devices_cmd_started.elapsed().as_millis() as u64;
-    if profile_devices && (profile_slow_ms == 0 || devices_cmd_elapsed_ms >= profile_slow_ms) {
-        info!(
-            trace_id = %trace_id,
-            step = "devices",
-            elapsed_ms = devices_cmd_elapsed_ms,
-            exit_code = ?output.exit_code,
-            "adb host command timing"
-        );
-    }
-    if output.exit_code.unwrap_or_default() != 0 {
-        return Err(AppError::dependency(
-            format!("adb devices failed: {}", output.stderr),
-            &trace_id,
-        ));
+    let cancel_flag = Arc::new(AtomicBool::new(false));
+    let child_holder = Arc::new(std::sync::Mutex::new(None));
+    {
+        let mut guard = state
+            .device_farm_run
+            .lock()
+            .map_err(|_| AppError::system("Device farm registry locked", &trace_id))?;
+        if guard.is_some() {
+            return Err(AppError::validation(
+                Message::keyed("device_farm_running"),
+                &trace_id,
+            ));
+        }
+        *guard = Some(DeviceFarmHandle {
+            cancel_flag: Arc::clone(&cancel_flag),
+            child: Arc::clone(&child_holder),
+        });
     }
-    let summaries = parse_adb_devices(&output.stdout);
-    let need_detail = detailed.unwrap_or(true);
-    let mut devices = Vec::with_capacity(summaries.len());
-
-    if need_detail && summaries.iter().any(|summary| summary.state == "device") {
-        let scheduler = Arc::clone(&state.scheduler);
-        let detail_slots: Arc<Vec<OnceLock<Option<DeviceDetail>>>> = Arc::new(
-            (0..summaries.len())
-                .map(|_| OnceLock::new())
-                .collect::<Vec<_>>(),
-        );
-
-        let mut handles = Vec::new();
-        for (index, summary) in summaries.iter().enumerate() {
-            if summary.state != "device" {
-                continue;
-            }
-
-            let serial = summary.serial.clone();
-            let adb_program_spawn = adb_program.clone();
-            let trace_spawn = trace_id.clone();
-            let scheduler_spawn = Arc::clone(&scheduler);
-            let detail_slots = Arc::clone(&detail_slots);
-
-            handles.push(std::thread::spawn(move || {
-                let run_scheduled = |args: &[String],
-                                     timeout: Duration,
-                                     _step: &'static str|
-                 -> Result<
-                    crate::app::adb::runner::CommandOutput,
-                    AppError,
-                > {
-                    let _permit = scheduler_spawn.acquire_global();
-                    let device_lock = scheduler_spawn.device_lock(&serial);
-                    let _device_guard = device_lock.lock().map_err(|_| {
-                        warn!(trace_id = %trace_spawn, serial = %serial, "device lock poisoned");
-                        AppError::system(
-                            "Failed to access the device. Please try again.",
-                            &trace_spawn,
-                        )
-                    })?;
-                    run_command_with_timeout(&adb_program_spawn, args, timeout, &trace_spawn)
-                };
-
-                let detail = load_device_detail(
-                    &serial,
-                    &trace_spawn,
-                    profile_devices,
-                    profile_slow_ms,
-                    run_scheduled,
-                );
+    info!(
+        trace_id = %trace_id,
+        bundle = %bundle.bundle_dir,
+        test_type = %request.test_type,
+        devices = request.devices.len(),
+        "submitting device farm run"
+    );
 
-                let _ = detail_slots[index].set(detail);
-            }));
+    let outcome = run_gcloud_streaming(
+        &bundle,
+        farm_host_timeout(timeout_minutes),
+        &app,
+        &trace_id,
+        &cancel_flag,
+        &child_holder,
+    );
+    match state.device_farm_run.lock() {
+        Ok(mut guard) => *guard = None,
+        Err(_) => {
+            warn!(trace_id = %trace_id, "device farm registry locked, run handle not cleared")
         }
+    }
 
-        for handle in handles {
-            if handle.join().is_err() {
-                warn!(
-                    trace_id = %trace_id,
-                    "device detail thread panicked"
-                );
+    let result = match outcome {
+        Ok(run) => {
+            let outcome = farm_exit_outcome(run.exit_code);
+            let success = run.exit_code == Some(0);
+            let error = (!success).then(|| {
+                let detail = run
+                    .stderr_tail
+                    .iter()
+                    .cloned()
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                if detail.is_empty() {
+                    format!("gcloud finished with outcome {outcome}")
+                } else {
+                    detail
+                }
+            });
+            DeviceFarmRunResult {
+                bundle,
+                success,
+                outcome: outcome.to_string(),
+                exit_code: run.exit_code,
+                results_url: run.results_url,
+                storage_url: run.storage_url,
+                outcomes: parse_farm_outcomes(&run.stdout),
+                error,
             }
         }
-
-        for (index, summary) in summaries.into_iter().enumerate() {
-            let detail = detail_slots[index].get().cloned().unwrap_or(None);
-            devices.push(DeviceInfo { summary, detail });
-        }
-    } else {
-        for summary in summaries {
-            devices.push(DeviceInfo {
-                summary,
-                detail: None,
-            });
+        Err(err) => {
+            let cancelled = err.contains("Cancelled");
+            DeviceFarmRunResult {
+                bundle,
+                success: false,
+                outcome: if cancelled { "cancelled" } else { "error" }.to_string(),
+                exit_code: None,
+                results_url: None,
+                storage_url: None,
+                outcomes: Vec::new(),
+                error: Some(err),
+            }
         }
+    };
+    if let Some(error) = &result.error {
+        warn!(trace_id = %trace_id, outcome = %result.outcome, error = %error, "device farm run did not pass");
     }
-
-    let list_elapsed_ms = list_started.elapsed().as_millis() as u64;
-    if profile_devices && (profile_slow_ms == 0 || list_elapsed_ms >= profile_slow_ms) {
-        info!(
-            trace_id = %trace_id,
-            step = "list_devices_total",
-            elapsed_ms = list_elapsed_ms,
-            detailed = need_detail,
-            device_count = devices.len(),
-            "list_devices timing"
-        );
-    }
-
     Ok(CommandResponse {
         trace_id,
-        data: devices,
+        data: result,
     })
 }
 
 #[tauri::command(async)]
-pub fn start_device_tracking(
-    app: AppHandle,
+pub fn cancel_device_farm_test(
     state: State<'_, AppState>,
     trace_id: Option<String>,
 ) -> Result<CommandResponse<bool>, AppError> {
     let trace_id = resolve_trace_id(trace_id);
-    info!(trace_id = %trace_id, "start_device_tracking");
-
-    let adb_program = get_adb_program(&trace_id)?;
-    let mut guard = state
-        .device_tracker
+    let guard = state
+        .device_farm_run
         .lock()
-        .map_err(|_| AppError::system("Device tracker registry locked", &trace_id))?;
-    if let Some(handle) = guard.take() {
-        handle.stop();
-    }
-    *guard = Some(start_device_tracker(app, trace_id.clone(), adb_program));
-
+        .map_err(|_| AppError::system("Device farm registry locked", &trace_id))?;
+    let Some(handle) = guard.as_ref() else {
+        return Err(AppError::validation(
+            Message::keyed("no_device_farm_run"),
+            &trace_id,
+        ));
+    };
+    handle.cancel_flag.store(true, Ordering::Relaxed);
+    kill_device_farm_process(&handle.child);
     Ok(CommandResponse {
         trace_id,
         data: true,
     })
 }
 
+/// File an issue on GitHub or Jira and attach the selected artifacts. Jira receives the files
+/// as issue attachments; for GitHub they go to the configured upload target and are linked.
 #[tauri::command(async)]
-pub fn stop_device_tracking(
-    state: State<'_, AppState>,
+pub fn create_issue_from_artifacts(
+    provider: String,
+    title: String,
+    description: String,
+    artifact_paths: Vec<String>,
     trace_id: Option<String>,
-) -> Result<CommandResponse<bool>, AppError> {
+) -> Result<CommandResponse<CreatedIssue>, AppError> {
     let trace_id = resolve_trace_id(trace_id);
-    info!(trace_id = %trace_id, "stop_device_tracking");
-
-    let mut guard = state
-        .device_tracker
-        .lock()
-        .map_err(|_| AppError::system("Device tracker registry locked", &trace_id))?;
-    if let Some(handle) = guard.take() {
-        handle.stop();
+    ensure_non_empty(&title, "title", &trace_id)?;
+    let title = title.trim().to_string();
+    if title.chars().count() > MAX_ISSUE_TITLE_LEN {
+        return Err(AppError::validation(
+            Message::keyed("max_length")
+                .with("field", "Title")
+                .with("max", MAX_ISSUE_TITLE_LEN),
+            &trace_id,
+        ));
     }
+    if artifact_paths.len() > MAX_ISSUE_ATTACHMENTS {
+        return Err(AppError::validation(
+            Message::keyed("too_many_artifacts").with("max", MAX_ISSUE_ATTACHMENTS),
+            &trace_id,
+        ));
+    }
+    let mut artifacts = Vec::with_capacity(artifact_paths.len());
+    for raw in &artifact_paths {
+        let path = PathBuf::from(raw.trim());
+        if !path.is_file() {
+            return Err(AppError::validation(
+                Message::keyed("not_found_value")
+                    .with("subject", "Artifact")
+                    .with("value", raw),
+                &trace_id,
+            ));
+        }
+        artifacts.push(path);
+    }
+    let file_name = |path: &Path| {
+        path.file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default()
+    };
+
+    let config = load_config(&trace_id)?;
+    let curl_program = resolve_curl_program(&config);
+    let env = |name: &str| std::env::var(name).ok();
+    let provider = provider.trim().to_lowercase();
+    let mut attached = Vec::new();
+    let mut failed_attachments = Vec::new();
+
+    let (key, url) = match provider.as_str() {
+        "github" => {
+            let settings = &config.issues.github;
+            validate_github_repo(&settings.repo)
+                .map_err(|message| AppError::validation(message, &trace_id))?;
+            let auth = github_auth_config(settings, env)
+                .map_err(|message| AppError::validation(message, &trace_id))?;
+            let upload_target = config
+                .upload
+                .targets
+                .iter()
+                .find(|target| target.name == settings.attachment_upload_target);
+
+            let mut refs = Vec::with_capacity(artifacts.len());
+            for path in &artifacts {
+                let name = file_name(path);
+                let uploaded = match upload_target {
+                    Some(target) => match upload_artifact(&curl_program, target, path, &trace_id) {
+                        Ok(url) => Some(url),
+                        Err(err) => {
+                            warn!(trace_id = %trace_id, artifact = %name, error = %err.error, "failed to upload issue attachment");
+                            None
+                        }
+                    },
+                    None => None,
+                };
+                if uploaded.is_some() {
+                    attached.push(name.clone());
+                } else {
+                    failed_attachments.push(name.clone());
+                }
+                refs.push(IssueArtifactRef {
+                    name,
+                    url: uploaded,
+                });
+            }
+            if upload_target.is_none() && !artifacts.is_empty() {
+                warn!(trace_id = %trace_id, "no GitHub attachment upload target configured; artifacts are listed by name only");
+            }
+
+            let body = compose_issue_body(&description, &refs);
+            let response = post_json_with_curl(
+                &curl_program,
+                &auth,
+                &format!("{}/repos/{}/issues", settings.api_url, settings.repo),
+                &github_issue_payload(&title, &body),
+                "GitHub issue creation",
+                &trace_id,
+            )?;
+            let (url, key) = parse_github_issue_response(&response).ok_or_else(|| {
+                AppError::dependency(
+                    Message::keyed("unexpected_response").with("service", "GitHub"),
+                    &trace_id,
+                )
+            })?;
+            (key, url)
+        }
+        "jira" => {
+            let settings = &config.issues.jira;
+            if settings.base_url.is_empty() || settings.project_key.is_empty() {
+                return Err(AppError::validation(
+                    Message::keyed("jira_not_configured"),
+                    &trace_id,
+                ));
+            }
+            let auth = jira_auth_config(settings, env)
+                .map_err(|message| AppError::validation(message, &trace_id))?;
+            let response = post_json_with_curl(
+                &curl_program,
+                &auth,
+                &format!("{}/rest/api/2/issue", settings.base_url),
+                &jira_issue_payload(settings, &title, description.trim()),
+                "Jira issue creation",
+                &trace_id,
+            )?;
+            let key = parse_jira_issue_key(&response).ok_or_else(|| {
+                AppError::dependency(
+                    Message::keyed("unexpected_response").with("service", "Jira"),
+                    &trace_id,
+                )
+            })?;
+
+            for path in &artifacts {
+                let name = file_name(path);
+                let quoted = quote_config_value(&path.to_string_lossy())
+                    .map_err(|message| AppError::validation(message, &trace_id))?;
+                let bytes = fs::metadata(path)
+                    .map(|meta| meta.len())
+                    .unwrap_or_default();
+                let args = vec![
+                    "-X".to_string(),
+                    "POST".to_string(),
+                    "-H".to_string(),
+                    "X-Atlassian-Token: no-check".to_string(),
+                    "-F".to_string(),
+                    format!("file=@{quoted}"),
+                    format!("{}/rest/api/2/issue/{key}/attachments", settings.base_url),
+                ];
+                match run_curl(
+                    &curl_program,
+                    &auth,
+                    args,
+                    upload_timeout(bytes),
+                    "Jira attachment upload",
+                    &trace_id,
+                ) {
+                    Ok(_) => attached.push(name),
+                    Err(err) => {
+                        warn!(trace_id = %trace_id, artifact = %name, error = %err.error, "failed to attach artifact to Jira issue");
+                        failed_attachments.push(name);
+                    }
+                }
+            }
+            let url = format!("{}/browse/{key}", settings.base_url);
+            (key, url)
+        }
+        _ => {
+            return Err(AppError::validation(
+                Message::keyed("unsupported_value")
+                    .with("subject", "issue provider")
+                    .with("value", &provider),
+                &trace_id,
+            ))
+        }
+    };
+
+    info!(
+        trace_id = %trace_id,
+        provider = %provider,
+        issue = %key,
+        attached = attached.len(),
+        failed = failed_attachments.len(),
+        "issue created"
+    );
+    Ok(CommandResponse {
+        trace_id,
+        data: CreatedIssue {
+            provider,
+            key,
+            url,
+            attached,
+            failed_attachments,
+        },
+    })
+}
 
+/// Send a test payload to one configured webhook, synchronously, so setup mistakes surface.
+#[tauri::command(async)]
+pub fn send_test_notification(
+    webhook: String,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<bool>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&webhook, "webhook", &trace_id)?;
+    let config = load_config(&trace_id)?;
+    let target = webhooks_for_event(&config.notifications, NOTIFY_TEST)
+        .into_iter()
+        .find(|entry| entry.name == webhook.trim())
+        .ok_or_else(|| {
+            AppError::validation(
+                Message::keyed("unknown_webhook").with("name", &webhook),
+                &trace_id,
+            )
+        })?;
+    let context = NotificationContext::new(
+        NOTIFY_TEST,
+        "",
+        true,
+        "Test notification from Lazy Blacktea",
+    );
+    send_webhook(&resolve_curl_program(&config), target, &context, &trace_id)?;
+    info!(trace_id = %trace_id, webhook = %target.name, "test notification sent");
     Ok(CommandResponse {
         trace_id,
         data: true,
     })
 }
 
+/// Run one configured hook with a test payload, synchronously, so setup mistakes surface.
 #[tauri::command(async)]
-pub fn adb_pair(
-    address: String,
-    pairing_code: String,
+pub fn run_test_hook(
+    hook: String,
     trace_id: Option<String>,
-) -> Result<CommandResponse<HostCommandResult>, AppError> {
+) -> Result<CommandResponse<HookRunResult>, AppError> {
     let trace_id = resolve_trace_id(trace_id);
-    ensure_non_empty(&address, "address", &trace_id)?;
-    ensure_non_empty(&pairing_code, "pairing_code", &trace_id)?;
+    ensure_non_empty(&hook, "hook", &trace_id)?;
+    let config = load_config(&trace_id)?;
+    let target = hooks_for_event(&config.hooks, HOOK_TEST)
+        .into_iter()
+        .find(|entry| entry.name == hook.trim())
+        .ok_or_else(|| {
+            AppError::validation(
+                Message::keyed("unknown_value")
+                    .with("subject", "or disabled hook")
+                    .with("value", &hook),
+                &trace_id,
+            )
+        })?;
+    let payload = HookPayload::new(
+        HOOK_TEST,
+        "",
+        true,
+        "Test hook from Lazy Blacktea",
+        serde_json::Value::Null,
+        &trace_id,
+    );
+    let result = run_hook(target, &payload, &trace_id);
+    log_hook_result(&result, &target.name, HOOK_TEST, &trace_id);
+    Ok(CommandResponse {
+        trace_id,
+        data: result?,
+    })
+}
 
-    let adb_program = get_adb_program(&trace_id)?;
-    let args = vec!["pair".to_string(), address.clone(), pairing_code.clone()];
-    let output = run_command_with_timeout(&adb_program, &args, Duration::from_secs(10), &trace_id)?;
-    let combined = format!("{}{}", output.stdout, output.stderr).to_lowercase();
-    if output.exit_code.unwrap_or_default() != 0
-        || combined.contains("failed")
-        || combined.contains("unable")
-    {
-        let detail = if output.stderr.trim().is_empty() {
-            output.stdout.trim()
-        } else {
-            output.stderr.trim()
-        };
-        return Err(AppError::dependency(
-            format!("adb pair failed: {detail}"),
-            &trace_id,
-        ));
-    }
+/// Run the configured custom tool `name` on `serials` (or once on the host for host tools
+/// with no device selected) after checking `inputs` against its schema. Devices run in
+/// parallel; a failure on one is reported in its result and does not stop the others.
+#[tauri::command(async)]
+pub fn run_custom_tool(
+    name: String,
+    serials: Vec<String>,
+    inputs: HashMap<String, String>,
+    app: AppHandle,
+    state: State<'_, AppState>,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<CustomToolResult>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_action_allowed("Running custom tools", &trace_id)?;
+    ensure_non_empty(&name, "name", &trace_id)?;
+    let config = load_config(&trace_id)?;
+    let tool = config
+        .custom_tools
+        .tools
+        .into_iter()
+        .find(|tool| tool.name == name.trim())
+        .ok_or_else(|| {
+            AppError::validation(
+                Message::keyed("unknown_value")
+                    .with("subject", "custom tool")
+                    .with("value", &name),
+                &trace_id,
+            )
+        })?;
+    let values =
+        resolve_inputs(&tool, &inputs).map_err(|err| AppError::validation(err, &trace_id))?;
+    let targets =
+        tool_targets(&tool, &serials).map_err(|err| AppError::validation(err, &trace_id))?;
+    let invocations = targets
+        .into_iter()
+        .map(|serial| {
+            let args = build_invocation(&tool, serial.as_deref(), &values)
+                .map_err(|err| AppError::validation(err, &trace_id))?;
+            Ok((serial, args))
+        })
+        .collect::<Result<Vec<_>, AppError>>()?;
+    let device_serials: Vec<String> = invocations
+        .iter()
+        .filter_map(|(serial, _)| serial.clone())
+        .collect();
+    warn_on_reservation_conflicts(&app, &device_serials, &tool.name, &trace_id);
+
+    let program = if tool.kind == TOOL_KIND_ADB {
+        get_adb_program(&trace_id)?
+    } else {
+        tool.program.clone()
+    };
+    let timeout = tool_timeout(&tool);
+    let scheduler = &state.scheduler;
+    let results = std::thread::scope(|scope| {
+        let handles: Vec<_> = invocations
+            .into_iter()
+            .map(|(serial, args)| {
+                let (program, trace_id, tool_name) = (&program, &trace_id, &tool.name);
+                scope.spawn(move || {
+                    let _permit = scheduler.acquire_global();
+                    let device_lock = serial.as_deref().map(|serial| scheduler.device_lock(serial));
+                    let started = Instant::now();
+                    let output = device_lock
+                        .as_ref()
+                        .map(|lock| lock.lock())
+                        .transpose()
+                        .map_err(|_| {
+                            warn!(trace_id = %trace_id, serial = ?serial, "device lock poisoned");
+                            AppError::system("Failed to access the device. Please try again.", trace_id)
+                        })
+                        .and_then(|_device_guard| {
+                            run_command_with_timeout(program, &args, timeout, trace_id)
+                        });
+                    let result = run_result(
+                        serial.clone(),
+                        output,
+                        started.elapsed().as_millis() as u64,
+                    );
+                    if result.success {
+                        info!(trace_id = %trace_id, tool = %tool_name, serial = ?serial, "custom tool finished");
+                    } else {
+                        warn!(trace_id = %trace_id, tool = %tool_name, serial = ?serial, exit_code = ?result.exit_code, error = ?result.error, "custom tool failed");
+                    }
+                    result
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| handle.join())
+            .collect::<Result<Vec<_>, _>>()
+    })
+    .map_err(|_| AppError::system("Custom tool thread panicked", &trace_id))?;
 
     Ok(CommandResponse {
         trace_id,
-        data: HostCommandResult {
-            stdout: output.stdout,
-            stderr: output.stderr,
-            exit_code: output.exit_code,
+        data: CustomToolResult {
+            tool: tool.name,
+            results,
         },
     })
 }
 
+/// Write a self-contained HTML report of the session for `serials`: installs, crashes and ANRs,
+/// perf alerts, recordings, UI captures and command counts within `time_range`.
 #[tauri::command(async)]
-pub fn adb_connect(
-    address: String,
+pub fn export_session_report(
+    serials: Vec<String>,
+    time_range: Option<SessionTimeRange>,
+    path: String,
+    state: State<'_, AppState>,
     trace_id: Option<String>,
-) -> Result<CommandResponse<HostCommandResult>, AppError> {
+) -> Result<CommandResponse<SessionReportResult>, AppError> {
     let trace_id = resolve_trace_id(trace_id);
-    ensure_non_empty(&address, "address", &trace_id)?;
+    ensure_non_empty(&path, "path", &trace_id)?;
+    let path = PathBuf::from(path.trim());
+    let is_html = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("html") || ext.eq_ignore_ascii_case("htm"));
+    if !is_html {
+        return Err(AppError::validation(
+            Message::keyed("report_extension"),
+            &trace_id,
+        ));
+    }
 
-    let adb_program = get_adb_program(&trace_id)?;
-    let args = vec!["connect".to_string(), address.clone()];
-    let output = run_command_with_timeout(&adb_program, &args, Duration::from_secs(10), &trace_id)?;
-    let combined = format!("{}{}", output.stdout, output.stderr).to_lowercase();
-    if output.exit_code.unwrap_or_default() != 0
-        || combined.contains("failed")
-        || combined.contains("unable")
-    {
-        let detail = if output.stderr.trim().is_empty() {
-            output.stdout.trim()
-        } else {
-            output.stderr.trim()
-        };
-        return Err(AppError::dependency(
-            format!("adb connect failed: {detail}"),
+    let mut unique_serials: Vec<String> = Vec::new();
+    for serial in serials {
+        ensure_non_empty(&serial, "serial", &trace_id)?;
+        let serial = serial.trim().to_string();
+        if !unique_serials.contains(&serial) {
+            unique_serials.push(serial);
+        }
+    }
+    if unique_serials.is_empty() {
+        return Err(AppError::validation(
+            Message::keyed("required").with("field", "serials"),
             &trace_id,
         ));
     }
 
+    let time_range = time_range.unwrap_or_default();
+    let since = parse_time_bound(time_range.since.as_deref(), "since")
+        .map_err(|message| AppError::validation(message, &trace_id))?;
+    let until = parse_time_bound(time_range.until.as_deref(), "until")
+        .map_err(|message| AppError::validation(message, &trace_id))?;
+
+    let mut devices = Vec::new();
+    for serial in unique_serials {
+        let events = query_timeline_events(
+            &TimelineQuery {
+                serial: serial.clone(),
+                since: time_range.since.clone(),
+                until: time_range.until.clone(),
+                kinds: Vec::new(),
+                limit: Some(TIMELINE_MEMORY_LIMIT),
+            },
+            &trace_id,
+        )?;
+        let captures = state
+            .ui_captures
+            .lock()
+            .map_err(|_| AppError::system("UI capture registry locked", &trace_id))?
+            .get(&serial)
+            .map(|records| {
+                records
+                    .iter()
+                    .filter(|record| in_time_range(&record.captured_at, since, until))
+                    .map(SessionCapture::from)
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        let commands = query_command_log_entries(
+            &CommandLogQuery {
+                serial: Some(serial.clone()),
+                limit: Some(COMMAND_LOG_MEMORY_LIMIT),
+                ..CommandLogQuery::default()
+            },
+            &trace_id,
+        )?;
+        let commands: Vec<&CommandLogEntry> = commands
+            .iter()
+            .filter(|entry| in_time_range(&entry.started_at, since, until))
+            .collect();
+        devices.push(SessionDeviceReport {
+            serial,
+            events,
+            captures,
+            commands_run: commands.len(),
+            commands_failed: commands
+                .iter()
+                .filter(|entry| entry.outcome != "ok")
+                .count(),
+        });
+    }
+
+    let report = SessionReport {
+        generated_at: Utc::now().to_rfc3339(),
+        since: time_range.since,
+        until: time_range.until,
+        devices,
+    };
+    let html = render_session_report_html(&report);
+    if let Some(parent) = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        fs::create_dir_all(parent).map_err(|err| {
+            AppError::system(
+                Message::keyed("operation_failed")
+                    .with("action", "create output dir")
+                    .with("detail", &err),
+                &trace_id,
+            )
+        })?;
+    }
+    fs::write(&path, html).map_err(|err| {
+        AppError::system(
+            Message::keyed("operation_failed")
+                .with("action", "write report")
+                .with("detail", &err),
+            &trace_id,
+        )
+    })?;
+
     Ok(CommandResponse {
         trace_id,
-        data: HostCommandResult {
-            stdout: output.stdout,
-            stderr: output.stderr,
-            exit_code: output.exit_code,
+        data: SessionReportResult {
+            path: path.to_string_lossy().to_string(),
+            device_count: report.devices.len(),
+            event_count: report.devices.iter().map(|d| d.events.len()).sum(),
+            capture_count: report.devices.iter().map(|d| d.captures.len()).sum(),
         },
     })
 }
 
 #[tauri::command(async)]
-pub fn run_shell(
-    serials: Vec<String>,
-    command: String,
-    parallel: Option<bool>,
+pub fn get_internal_metrics(
     state: State<'_, AppState>,
     trace_id: Option<String>,
-) -> Result<CommandResponse<Vec<CommandResult>>, AppError> {
+) -> Result<CommandResponse<InternalMetrics>, AppError> {
     let trace_id = resolve_trace_id(trace_id);
-    ensure_non_empty(&command, "command", &trace_id)?;
-    if serials.is_empty() {
-        return Err(AppError::validation("serials is required", &trace_id));
-    }
-
-    let adb_program = get_adb_program(&trace_id)?;
-    let mut config = load_config(&trace_id)?;
-    let timeout = Duration::from_secs(config.command.command_timeout.max(1) as u64);
-    let use_parallel = parallel.unwrap_or(config.command.parallel_execution);
-    let scheduler = Arc::clone(&state.scheduler);
+    let metrics = snapshot_metrics(
+        state.scheduler.global_limit(),
+        state.scheduler.global_in_use(),
+    );
+    Ok(CommandResponse {
+        trace_id,
+        data: metrics,
+    })
+}
 
-    let mut results = Vec::with_capacity(serials.len());
-    if use_parallel {
-        let mut handles = Vec::new();
-        for (index, serial) in serials.into_iter().enumerate() {
-            ensure_non_empty(&serial, "serial", &trace_id)?;
-            let trace_id_clone = trace_id.clone();
-            let command_clone = command.clone();
-            let adb_program_clone = adb_program.clone();
-            let scheduler_clone = Arc::clone(&scheduler);
-            handles.push(std::thread::spawn(move || -> Result<_, AppError> {
-                let _permit = scheduler_clone.acquire_global();
-                let device_lock = scheduler_clone.device_lock(&serial);
-                let _device_guard = device_lock.lock().map_err(|_| {
-                    warn!(trace_id = %trace_id_clone, serial = %serial, "device lock poisoned");
-                    AppError::system(
-                        "Failed to access the device. Please try again.",
-                        &trace_id_clone,
-                    )
-                })?;
-                let args = vec![
-                    "-s".to_string(),
-                    serial.clone(),
-                    "shell".to_string(),
-                    "sh".to_string(),
-                    "-c".to_string(),
-                    command_clone,
-                ];
-                let output =
-                    run_command_with_timeout(&adb_program_clone, &args, timeout, &trace_id_clone)?;
-                Ok((index, serial, output))
-            }));
-        }
-
-        let mut collected = Vec::new();
-        for handle in handles {
-            let (index, serial, output) = handle
-                .join()
-                .map_err(|_| AppError::system("Shell command thread panicked", &trace_id))??;
-            collected.push((
-                index,
-                CommandResult {
-                    serial,
-                    stdout: output.stdout,
-                    stderr: output.stderr,
-                    exit_code: output.exit_code,
-                },
-            ));
-        }
-        collected.sort_by_key(|item| item.0);
-        results = collected.into_iter().map(|item| item.1).collect();
-    } else {
-        for serial in serials {
-            ensure_non_empty(&serial, "serial", &trace_id)?;
-            let _permit = scheduler.acquire_global();
-            let device_lock = scheduler.device_lock(&serial);
-            let _device_guard = device_lock.lock().map_err(|_| {
-                warn!(trace_id = %trace_id, serial = %serial, "device lock poisoned");
-                AppError::system("Failed to access the device. Please try again.", &trace_id)
-            })?;
-            let args = vec![
-                "-s".to_string(),
-                serial.clone(),
-                "shell".to_string(),
-                "sh".to_string(),
-                "-c".to_string(),
-                command.clone(),
-            ];
-            let output = run_command_with_timeout(&adb_program, &args, timeout, &trace_id)?;
-            results.push(CommandResult {
-                serial,
-                stdout: output.stdout,
-                stderr: output.stderr,
-                exit_code: output.exit_code,
-            });
-        }
-    }
-
-    if config.command.auto_save_history && !command.trim().is_empty() {
-        if config
-            .command_history
-            .last()
-            .map(|last| last == &command)
-            .unwrap_or(false)
-        {
-            // skip duplicate trailing entry
-        } else {
-            config.command_history.push(command.clone());
-            if config.command_history.len() > config.command.max_history_size {
-                let start = config
-                    .command_history
-                    .len()
-                    .saturating_sub(config.command.max_history_size);
-                config.command_history = config.command_history.split_off(start);
-            }
-            if let Err(err) = save_config(&config, &trace_id) {
-                warn!(trace_id = %trace_id, error = %err, "Failed to save command history");
-            }
-        }
-    }
+#[tauri::command(async)]
+pub fn export_internal_metrics_prometheus(
+    state: State<'_, AppState>,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<String>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    let metrics = snapshot_metrics(
+        state.scheduler.global_limit(),
+        state.scheduler.global_in_use(),
+    );
+    Ok(CommandResponse {
+        trace_id,
+        data: render_prometheus(&metrics),
+    })
+}
 
+/// Only emit `channels` (logcat, perf, net, ...) for devices the UI has subscribed; a channel
+/// with no subscriptions at all keeps reaching every listener.
+#[tauri::command(async)]
+pub fn subscribe_events(
+    serial: String,
+    channels: Vec<String>,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<EventSubscriptionInfo>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+    let serial = serial.trim().to_string();
+    let channels = normalize_channels(&channels)
+        .map_err(|message| AppError::validation(message, &trace_id))?;
+    let channels = subscribe_device_events(&serial, &channels);
+    info!(trace_id = %trace_id, serial = %serial, channels = ?channels, "event channels subscribed");
     Ok(CommandResponse {
         trace_id,
-        data: results,
+        data: EventSubscriptionInfo {
+            color: device_color(&serial),
+            serial,
+            channels,
+        },
     })
 }
 
+/// Drop the device's subscription to `channels`, or to every channel when none are given.
 #[tauri::command(async)]
-pub fn persist_terminal_state(
-    restore_sessions: Vec<String>,
-    buffers: HashMap<String, Vec<String>>,
+pub fn unsubscribe_events(
+    serial: String,
+    channels: Option<Vec<String>>,
     trace_id: Option<String>,
-) -> Result<CommandResponse<bool>, AppError> {
+) -> Result<CommandResponse<EventSubscriptionInfo>, AppError> {
     let trace_id = resolve_trace_id(trace_id);
-
-    let mut config = load_config(&trace_id)?;
-
-    let mut unique_restore_sessions = Vec::new();
-    let mut seen = std::collections::HashSet::new();
-    for serial in restore_sessions {
-        let trimmed = serial.trim();
-        if trimmed.is_empty() {
-            continue;
-        }
-        if seen.insert(trimmed.to_string()) {
-            unique_restore_sessions.push(trimmed.to_string());
-        }
-    }
-
-    let mut next_buffers: HashMap<String, Vec<String>> = HashMap::new();
-    for serial in unique_restore_sessions.iter() {
-        if let Some(lines) = buffers.get(serial) {
-            let mut lines = lines.clone();
-            clamp_terminal_buffer_lines(&mut lines);
-            next_buffers.insert(serial.clone(), lines);
-        } else if let Some(existing) = config.terminal.buffers.get(serial) {
-            next_buffers.insert(serial.clone(), existing.clone());
-        }
-    }
-
-    config.terminal.restore_sessions = unique_restore_sessions;
-    config.terminal.buffers = next_buffers;
-
-    save_config(&config, &trace_id).map_err(|err| {
-        AppError::system(
-            format!("Failed to persist terminal state: {err}"),
-            &trace_id,
-        )
-    })?;
-
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+    let serial = serial.trim().to_string();
+    let channels = channels
+        .map(|channels| normalize_channels(&channels))
+        .transpose()
+        .map_err(|message| AppError::validation(message, &trace_id))?;
+    let channels = unsubscribe_device_events(&serial, channels.as_deref());
+    info!(trace_id = %trace_id, serial = %serial, channels = ?channels, "event channels unsubscribed");
     Ok(CommandResponse {
         trace_id,
-        data: true,
+        data: EventSubscriptionInfo {
+            color: device_color(&serial),
+            serial,
+            channels,
+        },
     })
 }
 
 #[tauri::command(async)]
-pub fn start_terminal_session(
+pub fn get_event_subscriptions(
     serial: String,
-    app: AppHandle,
-    state: State<'_, AppState>,
     trace_id: Option<String>,
-) -> Result<CommandResponse<TerminalSessionInfo>, AppError> {
+) -> Result<CommandResponse<EventSubscriptionInfo>, AppError> {
     let trace_id = resolve_trace_id(trace_id);
     ensure_non_empty(&serial, "serial", &trace_id)?;
+    let serial = serial.trim().to_string();
+    Ok(CommandResponse {
+        trace_id,
+        data: EventSubscriptionInfo {
+            color: device_color(&serial),
+            channels: device_event_channels(&serial),
+            serial,
+        },
+    })
+}
 
-    let adb_program = get_adb_program(&trace_id)?;
-    let mut guard = state
-        .terminal_sessions
-        .lock()
-        .map_err(|_| AppError::system("Terminal registry locked", &trace_id))?;
-    if let Some(existing) = guard.get(&serial) {
-        if existing.is_running() {
-            return Err(AppError::validation(
-                "Terminal session already running",
-                &trace_id,
-            ));
-        }
-        guard.remove(&serial);
-    }
-
-    let session_id = Uuid::new_v4().to_string();
-    let args = vec![
-        "-s".to_string(),
-        serial.clone(),
-        "shell".to_string(),
-        "-t".to_string(),
-    ];
-
-    let app_emit = app.clone();
-    let trace_emit = trace_id.clone();
-    let emitter: Arc<dyn Fn(TerminalEvent) + Send + Sync> = Arc::new(move |event| {
-        if let Err(err) = app_emit.emit(TERMINAL_EVENT_NAME, event) {
-            warn!(trace_id = %trace_emit, error = %err, "failed to emit terminal event");
-        }
-    });
-
-    let session = TerminalSession::spawn(
-        &adb_program,
-        &args,
-        serial.clone(),
-        session_id.clone(),
-        trace_id.clone(),
-        emitter,
-    )
-    .map_err(|err| {
-        AppError::dependency(
-            format!("Failed to start terminal session: {err}"),
-            &trace_id,
-        )
-    })?;
-
-    guard.insert(serial.clone(), session);
-
+#[tauri::command(async)]
+pub fn get_config(trace_id: Option<String>) -> Result<CommandResponse<AppConfig>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    let config = load_config(&trace_id)?;
     Ok(CommandResponse {
         trace_id,
-        data: TerminalSessionInfo { serial, session_id },
+        data: redact_restricted_mode(config),
     })
 }
 
+fn apply_logging_settings(settings: &LoggingSettings, trace_id: &str) {
+    configure_app_log_file(settings);
+    if let Err(err) = apply_log_level(None, &settings.log_level) {
+        warn!(trace_id = %trace_id, error = %err, "failed to apply configured log level");
+    }
+}
+
 #[tauri::command(async)]
-pub fn write_terminal_session(
-    serial: String,
-    data: String,
-    newline: bool,
-    state: State<'_, AppState>,
+pub fn save_app_config(
+    config: AppConfig,
     trace_id: Option<String>,
-) -> Result<CommandResponse<bool>, AppError> {
+) -> Result<CommandResponse<AppConfig>, AppError> {
     let trace_id = resolve_trace_id(trace_id);
-    ensure_non_empty(&serial, "serial", &trace_id)?;
-
-    let mut guard = state
-        .terminal_sessions
-        .lock()
-        .map_err(|_| AppError::system("Terminal registry locked", &trace_id))?;
-    let not_running = match guard.get(&serial) {
-        Some(session) => !session.is_running(),
-        None => true,
-    };
-    if not_running {
-        guard.remove(&serial);
-        return Err(AppError::validation(
-            "Terminal session not running",
-            &trace_id,
-        ));
+    let current = load_config(&trace_id)?;
+    let config = preserve_restricted_mode(&current, config);
+    validate_hooks(&config.hooks).map_err(|err| AppError::validation(err, &trace_id))?;
+    let config = normalize_config_for_save(config);
+    if config.adb.path_overrides != current.adb.path_overrides
+        || config.adb.command_path != current.adb.command_path
+    {
+        let program = resolve_adb_program(&config.adb.command_path);
+        if let Some((_, reason)) =
+            adb_override_conflicts(&program, &config.adb.path_overrides, &trace_id)
+                .into_iter()
+                .next()
+        {
+            return Err(AppError::validation(reason, &trace_id));
+        }
     }
-    let session = guard
-        .get(&serial)
-        .ok_or_else(|| AppError::validation("Terminal session not running", &trace_id))?;
-    session
-        .write(&data, newline)
-        .map_err(|err| AppError::dependency(format!("Terminal write failed: {err}"), &trace_id))?;
+    save_config(&config, &trace_id)?;
+    apply_logging_settings(&config.logging, &trace_id);
+    apply_active_adb_server(&config);
+    Ok(CommandResponse {
+        trace_id,
+        data: redact_restricted_mode(config),
+    })
+}
 
+#[tauri::command(async)]
+pub fn reset_config(trace_id: Option<String>) -> Result<CommandResponse<AppConfig>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_action_allowed("Resetting settings", &trace_id)?;
+    let config = normalize_config_for_save(AppConfig::default());
+    save_config(&config, &trace_id)?;
+    apply_logging_settings(&config.logging, &trace_id);
+    apply_active_adb_server(&config);
     Ok(CommandResponse {
         trace_id,
-        data: true,
+        data: config,
     })
 }
 
 #[tauri::command(async)]
-pub fn stop_terminal_session(
-    serial: String,
-    state: State<'_, AppState>,
+pub fn set_restricted_mode(
+    enabled: bool,
+    password: Option<String>,
+    new_password: Option<String>,
     trace_id: Option<String>,
-) -> Result<CommandResponse<bool>, AppError> {
+) -> Result<CommandResponse<AppConfig>, AppError> {
     let trace_id = resolve_trace_id(trace_id);
-    ensure_non_empty(&serial, "serial", &trace_id)?;
-
-    let mut guard = state
-        .terminal_sessions
-        .lock()
-        .map_err(|_| AppError::system("Terminal registry locked", &trace_id))?;
-    let session = guard
-        .remove(&serial)
-        .ok_or_else(|| AppError::validation("Terminal session not running", &trace_id))?;
-    session.stop();
-
+    let mut config = load_config(&trace_id)?;
+    config.restricted_mode = apply_restricted_mode_change(
+        &config.restricted_mode,
+        enabled,
+        password.as_deref(),
+        new_password.as_deref(),
+    )
+    .map_err(|message| AppError::restricted(message, &trace_id))?;
+    let config = normalize_config_for_save(config);
+    save_config(&config, &trace_id)?;
+    info!(trace_id = %trace_id, enabled, "restricted mode updated");
     Ok(CommandResponse {
         trace_id,
-        data: true,
+        data: redact_restricted_mode(config),
     })
 }
 
 #[tauri::command(async)]
-pub fn reboot_devices(
-    serials: Vec<String>,
-    mode: Option<String>,
-    state: State<'_, AppState>,
+pub fn check_adb(
+    command_path: Option<String>,
     trace_id: Option<String>,
-) -> Result<CommandResponse<Vec<CommandResult>>, AppError> {
+) -> Result<CommandResponse<AdbInfo>, AppError> {
     let trace_id = resolve_trace_id(trace_id);
-    if serials.is_empty() {
-        return Err(AppError::validation("serials is required", &trace_id));
-    }
+    info!(trace_id = %trace_id, "check_adb");
 
-    let adb_program = get_adb_program(&trace_id)?;
-    let mode = mode.unwrap_or_else(|| "system".to_string());
-    let scheduler = Arc::clone(&state.scheduler);
+    let config = load_config(&trace_id)?;
+    let program = command_path
+        .as_deref()
+        .map(normalize_command_path)
+        .filter(|value| !value.is_empty())
+        .unwrap_or_else(|| resolve_adb_program(&config.adb.command_path));
 
-    let mut handles = Vec::new();
-    for (index, serial) in serials.into_iter().enumerate() {
-        ensure_non_empty(&serial, "serial", &trace_id)?;
-        let scheduler_clone = Arc::clone(&scheduler);
-        let trace_clone = trace_id.clone();
-        let adb_program_clone = adb_program.clone();
-        let mode_clone = mode.clone();
-        handles.push(std::thread::spawn(move || -> Result<_, AppError> {
-            let _permit = scheduler_clone.acquire_global();
-            let device_lock = scheduler_clone.device_lock(&serial);
-            let _device_guard = device_lock.lock().map_err(|_| {
-                warn!(trace_id = %trace_clone, serial = %serial, "device lock poisoned");
-                AppError::system(
-                    "Failed to access the device. Please try again.",
-                    &trace_clone,
-                )
-            })?;
+    if let Err(message) = validate_adb_program(&program) {
+        warn!(trace_id = %trace_id, error = %message, "adb validation failed");
+        return Ok(CommandResponse {
+            trace_id,
+            data: AdbInfo {
+                available: false,
+                version_output: String::new(),
+                command_path: program,
+                error: Some(message),
+            },
+        });
+    }
 
-            let mut args = vec!["-s".to_string(), serial.clone(), "reboot".to_string()];
-            match mode_clone.as_str() {
-                "recovery" => args.push("recovery".to_string()),
-                "bootloader" => args.push("bootloader".to_string()),
-                _ => {}
-            }
-            let output = run_command_with_timeout(
-                &adb_program_clone,
-                &args,
-                Duration::from_secs(10),
-                &trace_clone,
-            )?;
-            Ok((
-                index,
-                CommandResult {
-                    serial,
-                    stdout: output.stdout,
-                    stderr: output.stderr,
-                    exit_code: output.exit_code,
+    let args = vec!["version".to_string()];
+    let output = match run_command_with_timeout(
+        &program,
+        &args,
+        command_timeout(TimeoutKind::Probe, &trace_id),
+        &trace_id,
+    ) {
+        Ok(output) => output,
+        Err(err) => {
+            warn!(trace_id = %trace_id, error = %err.error, "adb check failed");
+            return Ok(CommandResponse {
+                trace_id,
+                data: AdbInfo {
+                    available: false,
+                    version_output: String::new(),
+                    command_path: program,
+                    error: Some(err.error),
                 },
-            ))
-        }));
-    }
+            });
+        }
+    };
 
-    let mut collected = Vec::new();
-    for handle in handles {
-        let (index, result) = handle
-            .join()
-            .map_err(|_| AppError::system("Reboot thread panicked", &trace_id))??;
-        collected.push((index, result));
+    let mut version_output = output.stdout.trim().to_string();
+    let stderr = output.stderr.trim();
+    if !stderr.is_empty() {
+        if !version_output.is_empty() {
+            version_output.push('\n');
+        }
+        version_output.push_str(stderr);
     }
-    collected.sort_by_key(|item| item.0);
-    let results = collected.into_iter().map(|item| item.1).collect();
 
+    let available = output.exit_code.unwrap_or_default() == 0;
     Ok(CommandResponse {
         trace_id,
-        data: results,
+        data: AdbInfo {
+            available,
+            version_output,
+            command_path: program,
+            error: if available {
+                None
+            } else if output.stderr.trim().is_empty() {
+                Some("ADB command returned a non-zero exit code".to_string())
+            } else {
+                Some(output.stderr.trim().to_string())
+            },
+        },
     })
 }
 
+/// Check adb, the adb server, optional tools and the config in one pass, with a remediation
+/// hint for anything that needs attention.
 #[tauri::command(async)]
-pub fn set_wifi_state(
-    serials: Vec<String>,
-    enable: bool,
-    state: State<'_, AppState>,
+pub fn check_environment(
     trace_id: Option<String>,
-) -> Result<CommandResponse<Vec<CommandResult>>, AppError> {
+) -> Result<CommandResponse<EnvironmentReport>, AppError> {
     let trace_id = resolve_trace_id(trace_id);
-    if serials.is_empty() {
-        return Err(AppError::validation("serials is required", &trace_id));
-    }
+    info!(trace_id = %trace_id, "check_environment");
+    let report = environment_report(&trace_id);
+    info!(trace_id = %trace_id, ready = report.ready, "environment checked");
+    Ok(CommandResponse {
+        trace_id,
+        data: report,
+    })
+}
 
-    let adb_program = get_adb_program(&trace_id)?;
-    let scheduler = Arc::clone(&state.scheduler);
+fn environment_report(trace_id: &str) -> EnvironmentReport {
+    let (config, load_error) = match load_config(trace_id) {
+        Ok(config) => (config, None),
+        Err(err) => {
+            warn!(trace_id = %trace_id, error = %err.error, "failed to load config for environment check, using defaults");
+            (AppConfig::default(), Some(err.error))
+        }
+    };
+    let probe = |program: &str, args: &[&str]| {
+        let args: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+        run_command_with_timeout(
+            program,
+            &args,
+            command_timeout(TimeoutKind::DeviceCommand, trace_id),
+            trace_id,
+        )
+    };
+    let mut items = Vec::new();
 
-    let mut handles = Vec::new();
-    for (index, serial) in serials.into_iter().enumerate() {
-        ensure_non_empty(&serial, "serial", &trace_id)?;
-        let scheduler_clone = Arc::clone(&scheduler);
-        let trace_clone = trace_id.clone();
-        let adb_program_clone = adb_program.clone();
-        handles.push(std::thread::spawn(move || -> Result<_, AppError> {
-            let _permit = scheduler_clone.acquire_global();
-            let device_lock = scheduler_clone.device_lock(&serial);
-            let _device_guard = device_lock.lock().map_err(|_| {
-                warn!(trace_id = %trace_clone, serial = %serial, "device lock poisoned");
-                AppError::system(
-                    "Failed to access the device. Please try again.",
-                    &trace_clone,
-                )
-            })?;
+    let adb_program = resolve_adb_program(&config.adb.command_path);
+    let adb = match validate_adb_program(&adb_program) {
+        Ok(()) => probe_status(
+            "adb",
+            true,
+            &adb_program,
+            probe(&adb_program, &["version"]),
+            HINT_ADB,
+        ),
+        Err(message) => DependencyStatus {
+            path: Some(adb_program.clone()),
+            ..missing_status("adb", true, &message, HINT_ADB)
+        },
+    };
+    let adb_ok = adb.status == STATUS_OK;
+    items.push(adb);
 
-            let args = vec![
-                "-s".to_string(),
-                serial.clone(),
-                "shell".to_string(),
-                "svc".to_string(),
-                "wifi".to_string(),
-                if enable { "enable" } else { "disable" }.to_string(),
-            ];
+    let remote = active_adb_server();
+    let server_hint = if remote.is_some() {
+        HINT_ADB_SERVER_REMOTE
+    } else {
+        HINT_ADB_SERVER_LOCAL
+    };
+    let mut server = if adb_ok {
+        probe_status(
+            "adb_server",
+            true,
+            &adb_program,
+            probe(&adb_program, &["start-server"]),
+            server_hint,
+        )
+    } else {
+        missing_status("adb_server", true, "adb is not available", server_hint)
+    };
+    server.path = Some(
+        remote
+            .map(|target| target.socket_spec())
+            .unwrap_or_else(|| "local".to_string()),
+    );
+    server.version = None;
+    items.push(server);
+
+    let scrcpy = check_scrcpy_availability();
+    items.push(if scrcpy.available {
+        DependencyStatus {
+            name: "scrcpy".to_string(),
+            status: STATUS_OK.to_string(),
+            required: false,
+            path: Some(scrcpy.command_path),
+            version: scrcpy.version_output.lines().next().map(str::to_string),
+            detail: None,
+            hint: None,
+        }
+    } else {
+        missing_status("scrcpy", false, "scrcpy was not found", HINT_SCRCPY)
+    });
+
+    let ffmpeg_path = normalize_command_path(&config.screen_record.ffmpeg_path);
+    let ffmpeg_program = if ffmpeg_path.is_empty() {
+        "ffmpeg".to_string()
+    } else {
+        ffmpeg_path
+    };
+    items.push(probe_status(
+        "ffmpeg",
+        false,
+        &ffmpeg_program,
+        probe(&ffmpeg_program, &["-version"]),
+        HINT_FFMPEG,
+    ));
+
+    items.push(match find_aapt(&adb_program) {
+        Some(aapt) => {
+            let aapt = aapt.to_string_lossy().to_string();
+            probe_status("aapt", false, &aapt, probe(&aapt, &["version"]), HINT_AAPT)
+        }
+        None => missing_status(
+            "aapt",
+            false,
+            "No build-tools found in the Android SDK",
+            HINT_AAPT,
+        ),
+    });
+
+    let emulator = android_sdk_roots(&adb_program)
+        .iter()
+        .find_map(|root| find_emulator_in_sdk(root))
+        .map(|path| path.to_string_lossy().to_string())
+        .unwrap_or_else(|| "emulator".to_string());
+    items.push(probe_status(
+        "emulator",
+        false,
+        &emulator,
+        probe(&emulator, &["-version"]),
+        HINT_EMULATOR,
+    ));
+    items.push(probe_status(
+        "bundletool",
+        false,
+        "bundletool",
+        probe("bundletool", &["version"]),
+        HINT_BUNDLETOOL,
+    ));
+
+    let curl = resolve_curl_program(&config);
+    items.push(probe_status(
+        "curl",
+        false,
+        &curl,
+        probe(&curl, &["--version"]),
+        HINT_CURL,
+    ));
+
+    let issues = if load_error.is_none() {
+        config_issues(&config, |name| std::env::var_os(name).is_some())
+    } else {
+        Vec::new()
+    };
+    items.push(config_status(load_error, issues));
+
+    let ready = environment_ready(&items);
+    EnvironmentReport { ready, items }
+}
+
+fn run_setup_step(
+    step: &str,
+    output_dir: Option<&str>,
+    trace_id: &str,
+) -> Result<String, StepFailure> {
+    match step {
+        STEP_ADB_LOCATED => {
+            let config =
+                load_config(trace_id).map_err(|err| StepFailure::new(err.error, HINT_CONFIG))?;
+            let program = resolve_adb_program(&config.adb.command_path);
+            validate_adb_program(&program)
+                .map_err(|message| StepFailure::new(message, HINT_ADB))?;
+            let args = vec!["version".to_string()];
+            let status = probe_status(
+                "adb",
+                true,
+                &program,
+                run_command_with_timeout(
+                    &program,
+                    &args,
+                    command_timeout(TimeoutKind::DeviceCommand, trace_id),
+                    trace_id,
+                ),
+                HINT_ADB,
+            );
+            if status.status != STATUS_OK {
+                return Err(StepFailure {
+                    error: status
+                        .detail
+                        .unwrap_or_else(|| "adb did not run".to_string()),
+                    hint: status.hint,
+                });
+            }
+            Ok(match status.version {
+                Some(version) => format!("{program} ({version})"),
+                None => program,
+            })
+        }
+        STEP_DEVICE_AUTHORIZED => {
+            let program =
+                get_adb_program(trace_id).map_err(|err| StepFailure::new(err.error, HINT_ADB))?;
+            let server_hint = if active_adb_server().is_some() {
+                HINT_ADB_SERVER_REMOTE
+            } else {
+                HINT_ADB_SERVER_LOCAL
+            };
+            let args = vec!["devices".to_string(), "-l".to_string()];
             let output = run_command_with_timeout(
-                &adb_program_clone,
+                &program,
                 &args,
-                Duration::from_secs(10),
-                &trace_clone,
-            )?;
-            Ok((
-                index,
-                CommandResult {
-                    serial,
-                    stdout: output.stdout,
-                    stderr: output.stderr,
-                    exit_code: output.exit_code,
-                },
-            ))
-        }));
-    }
-
-    let mut collected = Vec::new();
-    for handle in handles {
-        let (index, result) = handle
-            .join()
-            .map_err(|_| AppError::system("WiFi toggle thread panicked", &trace_id))??;
-        collected.push((index, result));
+                command_timeout(TimeoutKind::DeviceCommand, trace_id),
+                trace_id,
+            )
+            .map_err(|err| StepFailure::new(err.error, server_hint))?;
+            if output.exit_code != Some(0) {
+                return Err(StepFailure::new(
+                    format!("adb devices failed: {}", output.stderr.trim()),
+                    server_hint,
+                ));
+            }
+            device_authorization_outcome(&parse_adb_devices(&output.stdout))
+        }
+        STEP_OUTPUT_DIR_CHOSEN => {
+            let mut config =
+                load_config(trace_id).map_err(|err| StepFailure::new(err.error, HINT_CONFIG))?;
+            let dir = output_dir
+                .map(str::trim)
+                .filter(|dir| !dir.is_empty())
+                .unwrap_or(config.output_path.trim())
+                .to_string();
+            if dir.is_empty() {
+                return Err(StepFailure::new(
+                    "No output folder chosen",
+                    "Pick a folder for screenshots, recordings and logs.",
+                ));
+            }
+            check_output_dir(Path::new(&dir))?;
+            if config.output_path != dir {
+                config.output_path = dir.clone();
+                save_config(&config, trace_id)
+                    .map_err(|err| StepFailure::new(err.error, HINT_CONFIG))?;
+            }
+            Ok(dir)
+        }
+        STEP_OPTIONAL_TOOLS_DETECTED => {
+            Ok(optional_tools_summary(&environment_report(trace_id).items))
+        }
+        _ => Err(StepFailure {
+            error: format!("Unknown setup step: {step}"),
+            hint: None,
+        }),
     }
-    collected.sort_by_key(|item| item.0);
-    let results = collected.into_iter().map(|item| item.1).collect();
+}
 
+#[tauri::command(async)]
+pub fn get_setup_wizard_state(
+    trace_id: Option<String>,
+) -> Result<CommandResponse<SetupWizardState>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
     Ok(CommandResponse {
         trace_id,
-        data: results,
+        data: load_wizard_state(&setup_wizard_path()),
     })
 }
 
+/// Validate one onboarding step and persist the result. A failed check is recorded in the
+/// returned state (with a hint) rather than returned as an error.
 #[tauri::command(async)]
-pub fn set_bluetooth_state(
-    serials: Vec<String>,
-    enable: bool,
-    state: State<'_, AppState>,
+pub fn run_setup_wizard_step(
+    step: String,
+    output_dir: Option<String>,
     trace_id: Option<String>,
-) -> Result<CommandResponse<Vec<CommandResult>>, AppError> {
+) -> Result<CommandResponse<SetupWizardState>, AppError> {
     let trace_id = resolve_trace_id(trace_id);
-    if serials.is_empty() {
-        return Err(AppError::validation("serials is required", &trace_id));
+    ensure_non_empty(&step, "step", &trace_id)?;
+    let step = step.trim();
+    info!(trace_id = %trace_id, step = %step, "run_setup_wizard_step");
+
+    let path = setup_wizard_path();
+    let state = load_wizard_state(&path);
+    ensure_step_reachable(&state, step)
+        .map_err(|message| AppError::validation(message, &trace_id))?;
+    let outcome = run_setup_step(step, output_dir.as_deref(), &trace_id);
+    if let Err(failure) = &outcome {
+        warn!(trace_id = %trace_id, step = %step, error = %failure.error, "setup step failed");
     }
+    let state = apply_step_outcome(state, step, outcome, Utc::now().to_rfc3339());
+    save_wizard_state(&path, &state, &trace_id)?;
+    Ok(CommandResponse {
+        trace_id,
+        data: state,
+    })
+}
 
-    let adb_program = get_adb_program(&trace_id)?;
-    let scheduler = Arc::clone(&state.scheduler);
+#[tauri::command(async)]
+pub fn skip_setup_wizard_step(
+    step: String,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<SetupWizardState>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&step, "step", &trace_id)?;
+    let step = step.trim();
+    if !SKIPPABLE_STEPS.contains(&step) {
+        return Err(AppError::validation(
+            Message::keyed("step_not_skippable").with("step", step),
+            &trace_id,
+        ));
+    }
+    let path = setup_wizard_path();
+    let state = load_wizard_state(&path);
+    ensure_step_reachable(&state, step)
+        .map_err(|message| AppError::validation(message, &trace_id))?;
+    let state = skip_step(state, step, Utc::now().to_rfc3339());
+    save_wizard_state(&path, &state, &trace_id)?;
+    Ok(CommandResponse {
+        trace_id,
+        data: state,
+    })
+}
 
-    let mut handles = Vec::new();
-    for (index, serial) in serials.into_iter().enumerate() {
-        ensure_non_empty(&serial, "serial", &trace_id)?;
-        let scheduler_clone = Arc::clone(&scheduler);
-        let trace_clone = trace_id.clone();
-        let adb_program_clone = adb_program.clone();
-        handles.push(std::thread::spawn(move || -> Result<_, AppError> {
-            let _permit = scheduler_clone.acquire_global();
-            let device_lock = scheduler_clone.device_lock(&serial);
-            let _device_guard = device_lock.lock().map_err(|_| {
-                warn!(trace_id = %trace_clone, serial = %serial, "device lock poisoned");
-                AppError::system(
-                    "Failed to access the device. Please try again.",
-                    &trace_clone,
-                )
-            })?;
+#[tauri::command(async)]
+pub fn reset_setup_wizard(
+    trace_id: Option<String>,
+) -> Result<CommandResponse<SetupWizardState>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    let state = default_wizard_state();
+    save_wizard_state(&setup_wizard_path(), &state, &trace_id)?;
+    info!(trace_id = %trace_id, "setup wizard reset");
+    Ok(CommandResponse {
+        trace_id,
+        data: state,
+    })
+}
 
-            let mut args = vec![
-                "-s".to_string(),
-                serial.clone(),
-                "shell".to_string(),
-                "svc".to_string(),
-                "bluetooth".to_string(),
-                if enable { "enable" } else { "disable" }.to_string(),
-            ];
-            let mut output = run_command_with_timeout(
-                &adb_program_clone,
-                &args,
-                Duration::from_secs(10),
-                &trace_clone,
-            );
-            if output
-                .as_ref()
-                .ok()
-                .and_then(|out| out.exit_code)
-                .unwrap_or(1)
-                != 0
-            {
-                args = vec![
-                    "-s".to_string(),
-                    serial.clone(),
-                    "shell".to_string(),
-                    "service".to_string(),
-                    "call".to_string(),
-                    "bluetooth_manager".to_string(),
-                    if enable { "8" } else { "9" }.to_string(),
-                ];
-                output = run_command_with_timeout(
-                    &adb_program_clone,
-                    &args,
-                    Duration::from_secs(10),
-                    &trace_clone,
-                );
-            }
-            let output = output?;
-            Ok((
-                index,
-                CommandResult {
-                    serial,
-                    stdout: output.stdout,
-                    stderr: output.stderr,
-                    exit_code: output.exit_code,
-                },
-            ))
-        }));
-    }
+#[tauri::command(async)]
+pub fn export_diagnostics_bundle(
+    output_dir: Option<String>,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<String>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    info!(trace_id = %trace_id, "export_diagnostics_bundle");
 
-    let mut collected = Vec::new();
-    for handle in handles {
-        let (index, result) = handle
-            .join()
-            .map_err(|_| AppError::system("Bluetooth toggle thread panicked", &trace_id))??;
-        collected.push((index, result));
-    }
-    collected.sort_by_key(|item| item.0);
-    let results = collected.into_iter().map(|item| item.1).collect();
+    // Best-effort: diagnostics bundle should still be generated even if config is broken.
+    let adb_program = match load_config(&trace_id) {
+        Ok(config) => resolve_adb_program(&config.adb.command_path),
+        Err(err) => {
+            warn!(
+                trace_id = %trace_id,
+                error = %err,
+                "Failed to load config for diagnostics adb program, falling back to default"
+            );
+            "adb".to_string()
+        }
+    };
 
+    let bundle_path = diagnostics::export_diagnostics_bundle(&adb_program, output_dir, &trace_id)?;
     Ok(CommandResponse {
         trace_id,
-        data: results,
+        data: bundle_path.to_string_lossy().to_string(),
     })
 }
 
-#[allow(clippy::too_many_arguments)]
-fn install_apk_batch_inner(
-    serials: Vec<String>,
-    apk_path: String,
-    replace: bool,
-    allow_downgrade: bool,
-    grant: bool,
-    allow_test_packages: bool,
-    extra_args: Option<String>,
-    state: &AppState,
+fn load_device_detail(
+    serial: &str,
     trace_id: &str,
-    app: Option<AppHandle>,
-) -> Result<ApkBatchInstallResult, AppError> {
-    let trace_id = trace_id.to_string();
-    ensure_non_empty(&apk_path, "apk_path", &trace_id)?;
-    if serials.is_empty() {
-        return Err(AppError::validation("serials is required", &trace_id));
-    }
+    profile_devices: bool,
+    profile_slow_ms: u64,
+    mut run: impl FnMut(&[String], Duration, &'static str) -> Result<CommandOutput, AppError>,
+) -> Option<DeviceDetail> {
+    let detail_started = Instant::now();
+    let serial_arg = serial.to_string();
+    let probe_timeout = command_timeout(TimeoutKind::Probe, trace_id);
+    let device_command_timeout = command_timeout(TimeoutKind::DeviceCommand, trace_id);
 
-    let adb_program = get_adb_program(&trace_id)?;
-    let normalized = normalize_apk_path(&apk_path);
-    let apk_path = normalized.to_string_lossy().to_string();
-    let mut result = ApkBatchInstallResult {
-        apk_path: apk_path.clone(),
-        apk_info: None,
-        results: HashMap::new(),
-        total_duration_seconds: 0.0,
+    let should_log = |elapsed_ms: u64| -> bool {
+        if !profile_devices {
+            return false;
+        }
+        profile_slow_ms == 0 || elapsed_ms >= profile_slow_ms
     };
 
-    let start = std::time::Instant::now();
+    let mut run_timed = |step: &'static str,
+                         args: Vec<String>,
+                         timeout: Duration|
+     -> (u64, Result<CommandOutput, AppError>) {
+        let started = Instant::now();
+        let result = run(&args, timeout, step);
+        let elapsed_ms = started.elapsed().as_millis() as u64;
 
-    let mut split_bundle = None;
-    if is_split_bundle(&apk_path) {
-        let bundle =
-            extract_split_apks(&apk_path).map_err(|err| AppError::dependency(err, &trace_id))?;
-        if bundle.apk_paths.is_empty() {
-            for serial in &serials {
-                result.results.insert(
-                    serial.clone(),
-                    ApkInstallResult {
-                        serial: serial.clone(),
-                        success: false,
-                        error_code: ApkInstallErrorCode::InstallFailedInvalidApk,
-                        raw_output: "Failed to extract split APKs".to_string(),
-                        duration_seconds: 0.0,
-                        device_model: None,
-                    },
-                );
+        if should_log(elapsed_ms) {
+            match &result {
+                Ok(output) => {
+                    info!(
+                        trace_id = %trace_id,
+                        serial = %serial,
+                        step,
+                        elapsed_ms,
+                        exit_code = ?output.exit_code,
+                        "adb device command timing"
+                    );
+                }
+                Err(err) => {
+                    warn!(
+                        trace_id = %trace_id,
+                        serial = %serial,
+                        step,
+                        elapsed_ms,
+                        error = %err,
+                        "adb device command failed (timing)"
+                    );
+                }
             }
-            result.total_duration_seconds = start.elapsed().as_secs_f64();
-            return Ok(result);
         }
-        split_bundle = Some(bundle);
-    } else {
-        let apk_info = get_apk_info(&apk_path);
-        result.apk_info = Some(apk_info.clone());
-        if !apk_info.is_valid() {
-            for serial in &serials {
-                result.results.insert(
-                    serial.clone(),
-                    ApkInstallResult {
-                        serial: serial.clone(),
-                        success: false,
-                        error_code: ApkInstallErrorCode::InstallFailedInvalidApk,
-                        raw_output: apk_info.error.clone().unwrap_or_default(),
-                        duration_seconds: 0.0,
-                        device_model: None,
-                    },
+
+        (elapsed_ms, result)
+    };
+
+    // getprop is required to build base detail. If it fails, bail early to avoid spending up to
+    // the probe timeout * N subcommands per device.
+    let getprop_args = vec![
+        "-s".to_string(),
+        serial_arg.clone(),
+        "shell".to_string(),
+        "getprop".to_string(),
+    ];
+    let (getprop_elapsed_ms, getprop) = run_timed("getprop", getprop_args, probe_timeout);
+
+    let output = match getprop {
+        Ok(output) => output,
+        Err(err) => {
+            warn!(
+                trace_id = %trace_id,
+                serial = %serial,
+                step = "getprop",
+                elapsed_ms = getprop_elapsed_ms,
+                error = %err,
+                "failed to load device detail"
+            );
+            let detail_elapsed_ms = detail_started.elapsed().as_millis() as u64;
+            if should_log(detail_elapsed_ms) {
+                info!(
+                    trace_id = %trace_id,
+                    serial = %serial,
+                    step = "device_detail_total",
+                    elapsed_ms = detail_elapsed_ms,
+                    "device detail timing"
                 );
             }
-            result.total_duration_seconds = start.elapsed().as_secs_f64();
-            return Ok(result);
+            return None;
         }
+    };
+
+    let mut detail = build_device_detail(serial, &parse_getprop_map(&output.stdout));
+
+    let battery_args = vec![
+        "-s".to_string(),
+        serial_arg.clone(),
+        "shell".to_string(),
+        "dumpsys".to_string(),
+        "battery".to_string(),
+    ];
+    let (_battery_elapsed_ms, battery) = run_timed("battery", battery_args, probe_timeout);
+    if let Ok(battery_output) = battery {
+        detail.battery_level = parse_battery_level(&battery_output.stdout);
     }
 
-    let extra_args_list = extra_args
-        .unwrap_or_default()
-        .split_whitespace()
-        .map(|item| item.to_string())
-        .collect::<Vec<_>>();
+    let wifi_args = vec![
+        "-s".to_string(),
+        serial_arg.clone(),
+        "shell".to_string(),
+        "settings".to_string(),
+        "get".to_string(),
+        "global".to_string(),
+        "wifi_on".to_string(),
+    ];
+    let (_wifi_elapsed_ms, wifi_output) = run_timed("wifi", wifi_args, probe_timeout);
+    if let Ok(wifi_output) = wifi_output {
+        detail.wifi_is_on = parse_settings_bool(&wifi_output.stdout);
+    }
 
-    let use_parallel = load_config(&trace_id)?.command.parallel_execution;
-    let scheduler = Arc::clone(&state.scheduler);
+    let bt_args = vec![
+        "-s".to_string(),
+        serial_arg.clone(),
+        "shell".to_string(),
+        "settings".to_string(),
+        "get".to_string(),
+        "global".to_string(),
+        "bluetooth_on".to_string(),
+    ];
+    let (_bt_elapsed_ms, bt_output) = run_timed("bluetooth", bt_args, probe_timeout);
+    if let Ok(bt_output) = bt_output {
+        detail.bt_is_on = parse_settings_bool(&bt_output.stdout);
+    }
 
-    let mut handles = Vec::new();
+    let bt_state_args = vec![
+        "-s".to_string(),
+        serial_arg.clone(),
+        "shell".to_string(),
+        "cmd".to_string(),
+        "bluetooth_manager".to_string(),
+        "get-state".to_string(),
+    ];
+    let (_bt_state_elapsed_ms, bt_state_output) =
+        run_timed("bluetooth_manager_state", bt_state_args, probe_timeout);
+    let bt_state = bt_state_output
+        .ok()
+        .and_then(|output| parse_bluetooth_manager_state(&output.stdout));
+    if detail.bt_is_on.is_none() {
+        if let Some(state) = bt_state.as_deref() {
+            detail.bt_is_on = Some(state.contains("ON"));
+        }
+    }
+    detail.bluetooth_manager_state = bt_state;
+
+    let audio_args = vec![
+        "-s".to_string(),
+        serial_arg.clone(),
+        "shell".to_string(),
+        "dumpsys".to_string(),
+        "audio".to_string(),
+    ];
+    let (_audio_elapsed_ms, audio_output) = run_timed("audio", audio_args, probe_timeout);
+    if let Ok(audio_output) = audio_output {
+        detail.audio_state = parse_audio_summary(&audio_output.stdout);
+    }
+
+    let gms_args = vec![
+        "-s".to_string(),
+        serial_arg.clone(),
+        "shell".to_string(),
+        "dumpsys".to_string(),
+        "package".to_string(),
+        "com.google.android.gms".to_string(),
+    ];
+    let (_gms_elapsed_ms, gms_output) = run_timed("gms", gms_args, probe_timeout);
+    if let Ok(gms_output) = gms_output {
+        detail.gms_version = parse_gms_version_name(&gms_output.stdout);
+    }
+
+    let wm_size_args = vec![
+        "-s".to_string(),
+        serial_arg.clone(),
+        "shell".to_string(),
+        "wm".to_string(),
+        "size".to_string(),
+    ];
+    let (wm_size_elapsed_ms, wm_size_output) = run_timed("wm_size", wm_size_args, probe_timeout);
+    match wm_size_output {
+        Ok(out) => {
+            let parsed = parse_wm_size(&out.stdout);
+            if parsed.is_none() && !out.stdout.trim().is_empty() {
+                warn!(
+                    trace_id = %trace_id,
+                    serial = %serial,
+                    step = "wm_size",
+                    elapsed_ms = wm_size_elapsed_ms,
+                    output = %out.stdout.trim(),
+                    "failed to parse wm size"
+                );
+            }
+            detail.resolution = parsed;
+        }
+        Err(err) => {
+            warn!(
+                trace_id = %trace_id,
+                serial = %serial,
+                step = "wm_size",
+                elapsed_ms = wm_size_elapsed_ms,
+                error = %err,
+                "failed to load wm size"
+            );
+        }
+    }
+
+    let df_args = vec![
+        "-s".to_string(),
+        serial_arg.clone(),
+        "shell".to_string(),
+        "df".to_string(),
+        "-k".to_string(),
+        "/data".to_string(),
+    ];
+    let (df_elapsed_ms, df_output) = run_timed("df", df_args, probe_timeout);
+    match df_output {
+        Ok(out) => match parse_df_total_kb(&out.stdout) {
+            Ok(total_kb) => {
+                detail.storage_total_bytes = Some(total_kb.saturating_mul(1024));
+            }
+            Err(err) => {
+                warn!(
+                    trace_id = %trace_id,
+                    serial = %serial,
+                    step = "df",
+                    elapsed_ms = df_elapsed_ms,
+                    error = %err,
+                    "failed to parse df output"
+                );
+            }
+        },
+        Err(err) => {
+            warn!(
+                trace_id = %trace_id,
+                serial = %serial,
+                step = "df",
+                elapsed_ms = df_elapsed_ms,
+                error = %err,
+                "failed to load df output"
+            );
+        }
+    }
+
+    let features_args = vec!["-s".to_string(), serial_arg.clone(), "features".to_string()];
+    let (_features_elapsed_ms, features_output) =
+        run_timed("features", features_args, probe_timeout);
+    match features_output {
+        Ok(out) if out.exit_code == Some(0) => {
+            detail.adb_features = parse_adb_features(&out.stdout);
+        }
+        Ok(out) => {
+            warn!(trace_id = %trace_id, serial = %serial, stderr = %out.stderr.trim(), "adb features failed");
+        }
+        Err(err) => {
+            warn!(trace_id = %trace_id, serial = %serial, error = %err, "failed to load adb features");
+        }
+    }
+
+    // Only USB links have a negotiated speed; older builds lack it in `dumpsys usb`, so fall
+    // back to the gadget controller's sysfs entry.
+    if transport_for_serial(serial) == TRANSPORT_USB {
+        let usb_args = vec![
+            "-s".to_string(),
+            serial_arg.clone(),
+            "shell".to_string(),
+            "dumpsys".to_string(),
+            "usb".to_string(),
+        ];
+        let (_usb_elapsed_ms, usb_output) = run_timed("usb", usb_args, probe_timeout);
+        let mut speed = usb_output
+            .ok()
+            .and_then(|out| parse_dumpsys_usb_speed(&out.stdout));
+        if speed.is_none() {
+            let udc_args = vec![
+                "-s".to_string(),
+                serial_arg.clone(),
+                "shell".to_string(),
+                "cat".to_string(),
+                "/sys/class/udc/*/current_speed".to_string(),
+            ];
+            let (_udc_elapsed_ms, udc_output) = run_timed("udc_speed", udc_args, probe_timeout);
+            speed = udc_output
+                .ok()
+                .filter(|out| out.exit_code == Some(0))
+                .and_then(|out| out.stdout.lines().find_map(parse_usb_speed_value));
+        }
+        detail.usb_speed_mbps = speed;
+        detail.usb_speed = speed.map(usb_speed_label);
+    }
+
+    let meminfo_args = vec![
+        "-s".to_string(),
+        serial_arg.clone(),
+        "shell".to_string(),
+        "cat".to_string(),
+        "/proc/meminfo".to_string(),
+    ];
+    let (meminfo_elapsed_ms, meminfo_output) = run_timed("meminfo", meminfo_args, probe_timeout);
+    match meminfo_output {
+        Ok(out) => match parse_mem_totals(&out.stdout) {
+            Ok(mem) => {
+                detail.memory_total_bytes = Some(mem.total_bytes);
+            }
+            Err(err) => {
+                warn!(
+                    trace_id = %trace_id,
+                    serial = %serial,
+                    step = "meminfo",
+                    elapsed_ms = meminfo_elapsed_ms,
+                    error = %err,
+                    "failed to parse /proc/meminfo"
+                );
+            }
+        },
+        Err(err) => {
+            warn!(
+                trace_id = %trace_id,
+                serial = %serial,
+                step = "meminfo",
+                elapsed_ms = meminfo_elapsed_ms,
+                error = %err,
+                "failed to load /proc/meminfo"
+            );
+        }
+    }
+
+    let lock_args = vec![
+        "-s".to_string(),
+        serial_arg.clone(),
+        "shell".to_string(),
+        LOCK_STATE_SCRIPT.to_string(),
+    ];
+    let (lock_elapsed_ms, lock_output) = run_timed("lock_state", lock_args, device_command_timeout);
+    match lock_output {
+        Ok(out) => detail.lock_state = Some(parse_lock_state(serial, &out.stdout)),
+        Err(err) => {
+            warn!(
+                trace_id = %trace_id,
+                serial = %serial,
+                step = "lock_state",
+                elapsed_ms = lock_elapsed_ms,
+                error = %err,
+                "failed to load lock state"
+            );
+        }
+    }
+
+    let telephony_args = vec![
+        "-s".to_string(),
+        serial_arg,
+        "shell".to_string(),
+        TELEPHONY_SCRIPT.to_string(),
+    ];
+    let (telephony_elapsed_ms, telephony_output) =
+        run_timed("telephony", telephony_args, device_command_timeout);
+    match telephony_output {
+        Ok(out) => detail.telephony = Some(parse_telephony_info(serial, &out.stdout)),
+        Err(err) => {
+            warn!(
+                trace_id = %trace_id,
+                serial = %serial,
+                step = "telephony",
+                elapsed_ms = telephony_elapsed_ms,
+                error = %err,
+                "failed to load telephony info"
+            );
+        }
+    }
+
+    let detail_elapsed_ms = detail_started.elapsed().as_millis() as u64;
+    if should_log(detail_elapsed_ms) {
+        info!(
+            trace_id = %trace_id,
+            serial = %serial,
+            step = "device_detail_total",
+            elapsed_ms = detail_elapsed_ms,
+            "device detail timing"
+        );
+    }
+
+    Some(detail)
+}
+
+/// `load_device_detail` with each adb call throttled by the global and per-device scheduler.
+fn fetch_device_detail(
+    scheduler: &TaskScheduler,
+    adb_program: &str,
+    serial: &str,
+    trace_id: &str,
+    profile_devices: bool,
+    profile_slow_ms: u64,
+) -> Option<DeviceDetail> {
+    let run_scheduled = |args: &[String],
+                         timeout: Duration,
+                         _step: &'static str|
+     -> Result<CommandOutput, AppError> {
+        let _permit = scheduler.acquire_global();
+        let device_lock = scheduler.device_lock(serial);
+        let _device_guard = device_lock.lock().map_err(|_| {
+            warn!(trace_id = %trace_id, serial = %serial, "device lock poisoned");
+            AppError::system("Failed to access the device. Please try again.", trace_id)
+        })?;
+        run_command_with_timeout(adb_program, args, timeout, trace_id)
+    };
+    load_device_detail(
+        serial,
+        trace_id,
+        profile_devices,
+        profile_slow_ms,
+        run_scheduled,
+    )
+}
+
+/// Summary plus detail, notes and active reservation for one device.
+fn build_device_info(summary: DeviceSummary, detail: Option<DeviceDetail>) -> DeviceInfo {
+    let notes = load_device_notes(&device_notes_path()).remove(&summary.serial);
+    let reservation = load_device_reservations(&device_reservations_path())
+        .remove(&summary.serial)
+        .filter(|reservation| is_reservation_active(reservation, Utc::now()));
+    DeviceInfo {
+        summary,
+        detail,
+        notes,
+        reservation,
+    }
+}
+
+/// Recomputes details for the devices the tracker reported as changed and pushes each one
+/// as a `device-detail-updated` event.
+fn detail_refresh_hook(
+    app: AppHandle,
+    adb_program: String,
+    scheduler: Arc<TaskScheduler>,
+) -> DeviceDetailHook {
+    Arc::new(move |targets: Vec<DeviceSummary>, trace_id: &str| {
+        for summary in targets {
+            let app = app.clone();
+            let adb_program = adb_program.clone();
+            let scheduler = Arc::clone(&scheduler);
+            let trace_id = trace_id.to_string();
+            std::thread::spawn(move || {
+                let detail = fetch_device_detail(
+                    &scheduler,
+                    &adb_program,
+                    &summary.serial,
+                    &trace_id,
+                    false,
+                    0,
+                );
+                let payload = serde_json::json!({
+                    "trace_id": trace_id,
+                    "device": build_device_info(summary, detail),
+                });
+                if let Err(err) = emit_tracked(&app, DEVICE_DETAIL_UPDATED_EVENT, payload) {
+                    warn!(trace_id = %trace_id, error = %err, "failed to emit device detail");
+                }
+            });
+        }
+    })
+}
+
+/// One device's summary and details without re-querying every attached device.
+#[tauri::command(async)]
+pub fn get_device_detail(
+    serial: String,
+    state: State<'_, AppState>,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<DeviceInfo>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+    info!(trace_id = %trace_id, serial = %serial, "get_device_detail");
+
+    let adb_program = get_adb_program(&trace_id)?;
+    let args = vec!["devices".to_string(), "-l".to_string()];
+    let output = run_adb(&adb_program, &args, &trace_id)?;
+    if output.exit_code.unwrap_or_default() != 0 {
+        return Err(AppError::dependency(
+            Message::keyed("action_failed")
+                .with("action", "adb devices")
+                .with("detail", &output.stderr),
+            &trace_id,
+        ));
+    }
+    let summary = parse_adb_devices(&output.stdout)
+        .into_iter()
+        .find(|summary| summary.serial == serial)
+        .ok_or_else(|| {
+            AppError::validation(
+                Message::keyed("device_not_connected").with("serial", &serial),
+                &trace_id,
+            )
+        })?;
+    let detail = if summary.state == "device" {
+        fetch_device_detail(&state.scheduler, &adb_program, &serial, &trace_id, false, 0)
+    } else {
+        None
+    };
+
+    Ok(CommandResponse {
+        trace_id,
+        data: build_device_info(summary, detail),
+    })
+}
+
+#[tauri::command(async)]
+pub fn list_devices(
+    detailed: Option<bool>,
+    state: State<'_, AppState>,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<Vec<DeviceInfo>>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    info!(trace_id = %trace_id, "list_devices");
+
+    // When troubleshooting device refresh performance, enable:
+    //   LAZY_BLACKTEA_PROFILE_DEVICES=1
+    // Optionally adjust the slow threshold:
+    //   LAZY_BLACKTEA_PROFILE_DEVICES_SLOW_MS=500
+    let profile_devices = std::env::var_os("LAZY_BLACKTEA_PROFILE_DEVICES").is_some();
+    let profile_slow_ms = std::env::var("LAZY_BLACKTEA_PROFILE_DEVICES_SLOW_MS")
+        .ok()
+        .and_then(|value| value.trim().parse::<u64>().ok())
+        .unwrap_or(500);
+    let list_started = Instant::now();
+
+    let adb_program = get_adb_program(&trace_id)?;
+    let args = vec!["devices".to_string(), "-l".to_string()];
+    let devices_cmd_started = Instant::now();
+    let output = run_adb(&adb_program, &args, &trace_id)?;
+    let devices_cmd_elapsed_ms = devices_cmd_started.elapsed().as_millis() as u64;
+    if profile_devices && (profile_slow_ms == 0 || devices_cmd_elapsed_ms >= profile_slow_ms) {
+        info!(
+            trace_id = %trace_id,
+            step = "devices",
+            elapsed_ms = devices_cmd_elapsed_ms,
+            exit_code = ?output.exit_code,
+            "adb host command timing"
+        );
+    }
+    if output.exit_code.unwrap_or_default() != 0 {
+        return Err(AppError::dependency(
+            Message::keyed("action_failed")
+                .with("action", "adb devices")
+                .with("detail", &output.stderr),
+            &trace_id,
+        ));
+    }
+    let summaries = parse_adb_devices(&output.stdout);
+    let need_detail = detailed.unwrap_or(true);
+    let mut devices = Vec::with_capacity(summaries.len());
+    let mut notes = load_device_notes(&device_notes_path());
+    let mut reservations = load_device_reservations(&device_reservations_path());
+    let now = Utc::now();
+
+    if need_detail && summaries.iter().any(|summary| summary.state == "device") {
+        let scheduler = Arc::clone(&state.scheduler);
+        let detail_slots: Arc<Vec<OnceLock<Option<DeviceDetail>>>> = Arc::new(
+            (0..summaries.len())
+                .map(|_| OnceLock::new())
+                .collect::<Vec<_>>(),
+        );
+
+        let mut handles = Vec::new();
+        for (index, summary) in summaries.iter().enumerate() {
+            if summary.state != "device" {
+                continue;
+            }
+
+            let serial = summary.serial.clone();
+            let adb_program_spawn = adb_program.clone();
+            let trace_spawn = trace_id.clone();
+            let scheduler_spawn = Arc::clone(&scheduler);
+            let detail_slots = Arc::clone(&detail_slots);
+
+            handles.push(std::thread::spawn(move || {
+                let detail = fetch_device_detail(
+                    &scheduler_spawn,
+                    &adb_program_spawn,
+                    &serial,
+                    &trace_spawn,
+                    profile_devices,
+                    profile_slow_ms,
+                );
+
+                let _ = detail_slots[index].set(detail);
+            }));
+        }
+
+        for handle in handles {
+            if handle.join().is_err() {
+                warn!(
+                    trace_id = %trace_id,
+                    "device detail thread panicked"
+                );
+            }
+        }
+
+        for (index, summary) in summaries.into_iter().enumerate() {
+            let detail = detail_slots[index].get().cloned().unwrap_or(None);
+            let notes = notes.remove(&summary.serial);
+            let reservation = reservations
+                .remove(&summary.serial)
+                .filter(|reservation| is_reservation_active(reservation, now));
+            devices.push(DeviceInfo {
+                summary,
+                detail,
+                notes,
+                reservation,
+            });
+        }
+    } else {
+        for summary in summaries {
+            let notes = notes.remove(&summary.serial);
+            let reservation = reservations
+                .remove(&summary.serial)
+                .filter(|reservation| is_reservation_active(reservation, now));
+            devices.push(DeviceInfo {
+                summary,
+                detail: None,
+                notes,
+                reservation,
+            });
+        }
+    }
+
+    let list_elapsed_ms = list_started.elapsed().as_millis() as u64;
+    if profile_devices && (profile_slow_ms == 0 || list_elapsed_ms >= profile_slow_ms) {
+        info!(
+            trace_id = %trace_id,
+            step = "list_devices_total",
+            elapsed_ms = list_elapsed_ms,
+            detailed = need_detail,
+            device_count = devices.len(),
+            "list_devices timing"
+        );
+    }
+
+    Ok(CommandResponse {
+        trace_id,
+        data: devices,
+    })
+}
+
+#[tauri::command(async)]
+pub fn start_device_tracking(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<bool>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    info!(trace_id = %trace_id, "start_device_tracking");
+
+    let adb_program = get_adb_program(&trace_id)?;
+    let reconnect = reconnect_policy(&load_config(&trace_id)?.device);
+    let mut guard = state
+        .device_tracker
+        .lock()
+        .map_err(|_| AppError::system("Device tracker registry locked", &trace_id))?;
+    if let Some(handle) = guard.take() {
+        handle.stop();
+    }
+    let on_online = on_connect_hook(app.clone(), adb_program.clone());
+    let on_changed = detail_refresh_hook(
+        app.clone(),
+        adb_program.clone(),
+        Arc::clone(&state.scheduler),
+    );
+    *guard = Some(start_device_tracker(
+        app,
+        trace_id.clone(),
+        adb_program,
+        reconnect,
+        on_online,
+        on_changed,
+    ));
+
+    Ok(CommandResponse {
+        trace_id,
+        data: true,
+    })
+}
+
+/// Notes for one device, or every device with notes when `serial` is empty.
+#[tauri::command(async)]
+pub fn get_device_notes(
+    serial: Option<String>,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<Vec<DeviceNotes>>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    let serial = serial.filter(|value| !value.trim().is_empty());
+    let data = load_device_notes(&device_notes_path())
+        .into_values()
+        .filter(|notes| serial.as_ref().is_none_or(|serial| notes.serial == *serial))
+        .collect();
+    Ok(CommandResponse { trace_id, data })
+}
+
+/// Replace a device's notes and checklist. Saving empty text and no items deletes them and
+/// returns `None`.
+#[tauri::command(async)]
+pub fn save_device_notes(
+    serial: String,
+    markdown: String,
+    checklist: Vec<ChecklistItemInput>,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<Option<DeviceNotes>>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+    let notes = build_device_notes(&serial, &markdown, checklist, &Utc::now().to_rfc3339())
+        .map_err(|err| AppError::validation(err, &trace_id))?;
+    store_device_notes(&device_notes_path(), &serial, notes.clone(), &trace_id)?;
+    Ok(CommandResponse {
+        trace_id,
+        data: notes,
+    })
+}
+
+#[tauri::command(async)]
+pub fn delete_device_notes(
+    serial: String,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<bool>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+    store_device_notes(&device_notes_path(), &serial, None, &trace_id)?;
+    Ok(CommandResponse {
+        trace_id,
+        data: true,
+    })
+}
+
+fn device_profile_name(value: &str, trace_id: &str) -> Result<String, AppError> {
+    validate_profile_name(value).map_err(|message| AppError::validation(message, trace_id))
+}
+
+fn adb_copy(
+    adb_program: &str,
+    serial: &str,
+    verb: &str,
+    from: &str,
+    to: &str,
+    trace_id: &str,
+) -> Result<(), AppError> {
+    let args = vec![
+        "-s".to_string(),
+        serial.to_string(),
+        verb.to_string(),
+        from.to_string(),
+        to.to_string(),
+    ];
+    let output = run_command_with_timeout(
+        adb_program,
+        &args,
+        command_timeout(TimeoutKind::Transfer, trace_id),
+        trace_id,
+    )?;
+    if output.exit_code.unwrap_or_default() != 0 {
+        let detail = if output.stderr.trim().is_empty() {
+            output.stdout
+        } else {
+            output.stderr
+        };
+        return Err(AppError::dependency(
+            Message::keyed("action_failed")
+                .with("action", format!("adb {verb} {from}"))
+                .with("detail", detail.trim()),
+            trace_id,
+        ));
+    }
+    Ok(())
+}
+
+/// Installed version of `package` on the device, `None` when it is not installed.
+fn installed_app_version(
+    adb_program: &str,
+    serial: &str,
+    package: &str,
+    trace_id: &str,
+) -> Result<Option<AppVersion>, AppError> {
+    let output = run_device_shell(
+        adb_program,
+        serial,
+        &["dumpsys", "package", package],
+        command_timeout(TimeoutKind::DeviceCommand, trace_id),
+        trace_id,
+    )?;
+    Ok(split_dumpsys_package_blocks(&output.stdout)
+        .into_iter()
+        .find(|(name, _)| name == package)
+        .map(|(_, block)| parse_dumpsys_version(&block)))
+}
+
+type CapturedProfileParts = (
+    Vec<DeviceProfileApp>,
+    Vec<DeviceProfileSetting>,
+    Vec<DeviceProfileFile>,
+);
+
+fn capture_device_profile_into(
+    adb_program: &str,
+    serial: &str,
+    dir: &Path,
+    packages: &[String],
+    settings: &[DeviceProfileSettingKey],
+    files: &[String],
+    trace_id: &str,
+) -> Result<CapturedProfileParts, AppError> {
+    let mut apps = Vec::with_capacity(packages.len());
+    for package in packages {
+        let output = run_device_shell(
+            adb_program,
+            serial,
+            &["pm", "path", package],
+            command_timeout(TimeoutKind::DeviceCommand, trace_id),
+            trace_id,
+        )?;
+        let apk_paths = parse_pm_path_output(&output.stdout);
+        if apk_paths.is_empty() {
+            return Err(AppError::validation(
+                Message::keyed("package_not_installed")
+                    .with("package", package)
+                    .with("serial", serial),
+                trace_id,
+            ));
+        }
+        let app_dir = dir.join(PROFILE_APPS_DIR).join(package);
+        fs::create_dir_all(&app_dir).map_err(|err| {
+            AppError::system(
+                Message::keyed("operation_failed")
+                    .with("action", "create profile dir")
+                    .with("detail", &err),
+                trace_id,
+            )
+        })?;
+        let mut apk_files = Vec::with_capacity(apk_paths.len());
+        for apk_path in &apk_paths {
+            let file_name =
+                sanitize_filename_component(apk_path.rsplit('/').next().unwrap_or("base.apk"));
+            let local = app_dir.join(&file_name);
+            adb_copy(
+                adb_program,
+                serial,
+                "pull",
+                apk_path,
+                &local.to_string_lossy(),
+                trace_id,
+            )?;
+            apk_files.push(file_name);
+        }
+        let version =
+            installed_app_version(adb_program, serial, package, trace_id)?.unwrap_or_default();
+        apps.push(DeviceProfileApp {
+            package_name: package.clone(),
+            version_name: version.version_name,
+            version_code: version.version_code,
+            apk_files,
+        });
+    }
+
+    let mut captured_settings = Vec::with_capacity(settings.len());
+    for setting in settings {
+        let output = run_device_shell(
+            adb_program,
+            serial,
+            &["settings", "get", &setting.namespace, &setting.key],
+            command_timeout(TimeoutKind::DeviceCommand, trace_id),
+            trace_id,
+        )?;
+        captured_settings.push(DeviceProfileSetting {
+            namespace: setting.namespace.clone(),
+            key: setting.key.clone(),
+            value: parse_settings_value(&output.stdout),
+        });
+    }
+
+    let files_dir = dir.join(PROFILE_FILES_DIR);
+    let mut captured_files = Vec::with_capacity(files.len());
+    for (index, device_path) in files.iter().enumerate() {
+        fs::create_dir_all(&files_dir).map_err(|err| {
+            AppError::system(
+                Message::keyed("operation_failed")
+                    .with("action", "create profile dir")
+                    .with("detail", &err),
+                trace_id,
+            )
+        })?;
+        let stored_file = stored_file_name(index, device_path);
+        let local = files_dir.join(&stored_file);
+        adb_copy(
+            adb_program,
+            serial,
+            "pull",
+            device_path,
+            &local.to_string_lossy(),
+            trace_id,
+        )?;
+        if !local.is_file() {
+            return Err(AppError::validation(
+                Message::keyed("not_regular_file").with("path", device_path),
+                trace_id,
+            ));
+        }
+        let size_bytes = fs::metadata(&local).map(|meta| meta.len()).unwrap_or(0);
+        let sha256 = sha256_file(&local).map_err(|err| {
+            AppError::system(
+                Message::keyed("operation_failed")
+                    .with("action", format!("hash {device_path}"))
+                    .with("detail", &err),
+                trace_id,
+            )
+        })?;
+        captured_files.push(DeviceProfileFile {
+            device_path: device_path.clone(),
+            stored_file,
+            size_bytes,
+            sha256,
+        });
+    }
+    Ok((apps, captured_settings, captured_files))
+}
+
+/// Save the given apps (APKs and versions), settings and files of `serial` as profile `name`,
+/// replacing a profile of the same name once the capture succeeded.
+#[tauri::command(async)]
+pub fn capture_device_profile(
+    serial: String,
+    name: String,
+    packages: Vec<String>,
+    settings: Vec<DeviceProfileSettingKey>,
+    files: Vec<String>,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<DeviceProfile>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+    let name = device_profile_name(&name, &trace_id)?;
+    let mut seen = HashSet::new();
+    let packages: Vec<String> = packages
+        .iter()
+        .map(|package| package.trim().to_string())
+        .filter(|package| seen.insert(package.clone()))
+        .collect();
+    if let Some(invalid) = packages
+        .iter()
+        .find(|package| !is_valid_package_name(package))
+    {
+        return Err(AppError::validation(
+            Message::keyed("invalid_value")
+                .with("subject", "package name")
+                .with("value", invalid),
+            &trace_id,
+        ));
+    }
+    for setting in &settings {
+        validate_setting_key(&setting.namespace, &setting.key)
+            .map_err(|message| AppError::validation(message, &trace_id))?;
+    }
+    let files: Vec<String> = files.iter().map(|path| path.trim().to_string()).collect();
+    if let Some(invalid) = files.iter().find(|path| !path.starts_with('/')) {
+        return Err(AppError::validation(
+            Message::keyed("device_path_not_absolute").with("path", invalid),
+            &trace_id,
+        ));
+    }
+    if packages.is_empty() && settings.is_empty() && files.is_empty() {
+        return Err(AppError::validation(
+            Message::keyed("profile_selection_empty"),
+            &trace_id,
+        ));
+    }
+
+    let adb_program = get_adb_program(&trace_id)?;
+    let store = device_profiles_dir();
+    let staging = staging_dir(&store, &name);
+    if staging.exists() {
+        fs::remove_dir_all(&staging).map_err(|err| {
+            AppError::system(
+                Message::keyed("operation_failed")
+                    .with("action", "clear profile staging dir")
+                    .with("detail", &err),
+                &trace_id,
+            )
+        })?;
+    }
+    fs::create_dir_all(&staging).map_err(|err| {
+        AppError::system(
+            Message::keyed("operation_failed")
+                .with("action", "create profile dir")
+                .with("detail", &err),
+            &trace_id,
+        )
+    })?;
+    let captured = capture_device_profile_into(
+        &adb_program,
+        &serial,
+        &staging,
+        &packages,
+        &settings,
+        &files,
+        &trace_id,
+    )
+    .and_then(|(apps, settings, files)| {
+        let profile = DeviceProfile {
+            name: name.clone(),
+            source_serial: serial.clone(),
+            created_at: Utc::now().to_rfc3339(),
+            apps,
+            settings,
+            files,
+        };
+        save_profile(&staging, &profile, &trace_id)?;
+        commit_staged_profile(&store, &name, &trace_id)?;
+        Ok(profile)
+    });
+    let profile = match captured {
+        Ok(profile) => profile,
+        Err(err) => {
+            if let Err(cleanup) = fs::remove_dir_all(&staging) {
+                warn!(trace_id = %trace_id, error = %cleanup, "failed to remove profile staging dir");
+            }
+            return Err(err);
+        }
+    };
+    info!(
+        trace_id = %trace_id,
+        serial = %serial,
+        profile = %profile.name,
+        apps = profile.apps.len(),
+        settings = profile.settings.len(),
+        files = profile.files.len(),
+        "captured device profile"
+    );
+    Ok(CommandResponse {
+        trace_id,
+        data: profile,
+    })
+}
+
+#[tauri::command(async)]
+pub fn list_device_profiles(
+    trace_id: Option<String>,
+) -> Result<CommandResponse<Vec<DeviceProfile>>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    Ok(CommandResponse {
+        trace_id,
+        data: list_profiles(&device_profiles_dir()),
+    })
+}
+
+#[tauri::command(async)]
+pub fn delete_device_profile(
+    name: String,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<bool>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    let name = device_profile_name(&name, &trace_id)?;
+    let dir = device_profiles_dir().join(&name);
+    if !dir.is_dir() {
+        return Ok(CommandResponse {
+            trace_id,
+            data: false,
+        });
+    }
+    fs::remove_dir_all(&dir).map_err(|err| {
+        AppError::system(
+            Message::keyed("operation_failed")
+                .with("action", "delete device profile")
+                .with("detail", &err),
+            &trace_id,
+        )
+    })?;
+    Ok(CommandResponse {
+        trace_id,
+        data: true,
+    })
+}
+
+fn apply_profile_app(
+    adb_program: &str,
+    serial: &str,
+    profile_dir: &Path,
+    app: &DeviceProfileApp,
+    trace_id: &str,
+) -> DeviceProfileStep {
+    let step = |status: &str, detail: String| {
+        profile_step(STEP_KIND_APP, &app.package_name, status, detail)
+    };
+    let installed = match installed_app_version(adb_program, serial, &app.package_name, trace_id) {
+        Ok(version) => version,
+        Err(err) => return step(STEP_FAILED, err.error),
+    };
+    let wanted = app.version_code.as_deref().unwrap_or("unknown");
+    if let Some(code) = installed
+        .as_ref()
+        .and_then(|version| version.version_code.as_deref())
+    {
+        if app.version_code.as_deref() == Some(code) {
+            return step(STEP_SKIPPED, format!("Already at versionCode {code}"));
+        }
+    }
+
+    let app_dir = profile_dir.join(PROFILE_APPS_DIR).join(&app.package_name);
+    let apks: Vec<String> = app
+        .apk_files
+        .iter()
+        .map(|file| app_dir.join(file).to_string_lossy().to_string())
+        .collect();
+    if apks.is_empty() || apks.iter().any(|apk| !Path::new(apk).is_file()) {
+        return step(
+            STEP_FAILED,
+            "APK files are missing from the profile".to_string(),
+        );
+    }
+    let verb = if apks.len() > 1 {
+        "install-multiple"
+    } else {
+        "install"
+    };
+    let mut args = vec![
+        "-s".to_string(),
+        serial.to_string(),
+        verb.to_string(),
+        "-r".to_string(),
+        "-d".to_string(),
+    ];
+    args.extend(apks);
+    let raw = match run_command_with_timeout(
+        adb_program,
+        &args,
+        command_timeout(TimeoutKind::Install, trace_id),
+        trace_id,
+    ) {
+        Ok(output) if output.stdout.trim().is_empty() => output.stderr,
+        Ok(output) => output.stdout,
+        Err(err) => return step(STEP_FAILED, err.error),
+    };
+    if ApkInstallErrorCode::from_output(&raw) == ApkInstallErrorCode::Success {
+        step(STEP_APPLIED, format!("Installed versionCode {wanted}"))
+    } else {
+        step(
+            STEP_FAILED,
+            truncate_for_event(raw.trim(), APK_INSTALL_OUTPUT_MAX_LEN),
+        )
+    }
+}
+
+fn apply_profile_setting(
+    adb_program: &str,
+    serial: &str,
+    setting: &DeviceProfileSetting,
+    trace_id: &str,
+) -> DeviceProfileStep {
+    let target = format!("{}/{}", setting.namespace, setting.key);
+    let step =
+        |status: &str, detail: String| profile_step(STEP_KIND_SETTING, &target, status, detail);
+    let current = match run_device_shell(
+        adb_program,
+        serial,
+        &["settings", "get", &setting.namespace, &setting.key],
+        command_timeout(TimeoutKind::DeviceCommand, trace_id),
+        trace_id,
+    ) {
+        Ok(output) => parse_settings_value(&output.stdout),
+        Err(err) => return step(STEP_FAILED, err.error),
+    };
+    let shown = setting.value.as_deref().unwrap_or("(unset)").to_string();
+    if current == setting.value {
+        return step(STEP_SKIPPED, format!("Already {shown}"));
+    }
+    match run_device_shell(
+        adb_program,
+        serial,
+        &[&setting_shell_command(setting)],
+        command_timeout(TimeoutKind::DeviceCommand, trace_id),
+        trace_id,
+    ) {
+        Ok(output) if output.exit_code.unwrap_or_default() == 0 => {
+            step(STEP_APPLIED, format!("Set to {shown}"))
+        }
+        Ok(output) => step(STEP_FAILED, output.stderr.trim().to_string()),
+        Err(err) => step(STEP_FAILED, err.error),
+    }
+}
+
+fn apply_profile_file(
+    adb_program: &str,
+    serial: &str,
+    profile_dir: &Path,
+    file: &DeviceProfileFile,
+    trace_id: &str,
+) -> DeviceProfileStep {
+    let step = |status: &str, detail: String| {
+        profile_step(STEP_KIND_FILE, &file.device_path, status, detail)
+    };
+    let local = profile_dir.join(PROFILE_FILES_DIR).join(&file.stored_file);
+    if !local.is_file() {
+        return step(STEP_FAILED, "File is missing from the profile".to_string());
+    }
+    let hash_command = format!("sha256sum {}", quote_shell_value(&file.device_path));
+    match run_device_shell(
+        adb_program,
+        serial,
+        &[&hash_command],
+        command_timeout(TimeoutKind::Transfer, trace_id),
+        trace_id,
+    ) {
+        Ok(output) if parse_sha256sum(&output.stdout).as_deref() == Some(&file.sha256) => {
+            return step(
+                STEP_SKIPPED,
+                "Identical file already on the device".to_string(),
+            );
+        }
+        Ok(_) => {}
+        Err(err) => {
+            warn!(trace_id = %trace_id, error = %err, "sha256sum failed; pushing the file anyway");
+        }
+    }
+    match adb_copy(
+        adb_program,
+        serial,
+        "push",
+        &local.to_string_lossy(),
+        &file.device_path,
+        trace_id,
+    ) {
+        Ok(()) => step(STEP_APPLIED, format!("Pushed {} bytes", file.size_bytes)),
+        Err(err) => step(STEP_FAILED, err.error),
+    }
+}
+
+/// Re-apply profile `name` onto `serial`: install apps whose versionCode differs (downgrades
+/// allowed), set differing settings and push files whose hash differs. Every step is
+/// reported as applied, skipped or failed; one failure does not stop the rest.
+#[tauri::command(async)]
+pub fn apply_device_profile(
+    serial: String,
+    name: String,
+    app: AppHandle,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<DeviceProfileApplyReport>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+    let name = device_profile_name(&name, &trace_id)?;
+    let store = device_profiles_dir();
+    let profile = load_profile(&store, &name, &trace_id)?;
+    ensure_action_allowed("Applying device profiles", &trace_id)?;
+    warn_on_reservation_conflicts(
+        &app,
+        std::slice::from_ref(&serial),
+        "Applying a device profile",
+        &trace_id,
+    );
+
+    let adb_program = get_adb_program(&trace_id)?;
+    let profile_dir = store.join(&name);
+    let mut steps = Vec::new();
+    for item in &profile.apps {
+        steps.push(apply_profile_app(
+            &adb_program,
+            &serial,
+            &profile_dir,
+            item,
+            &trace_id,
+        ));
+    }
+    for setting in &profile.settings {
+        steps.push(apply_profile_setting(
+            &adb_program,
+            &serial,
+            setting,
+            &trace_id,
+        ));
+    }
+    for file in &profile.files {
+        steps.push(apply_profile_file(
+            &adb_program,
+            &serial,
+            &profile_dir,
+            file,
+            &trace_id,
+        ));
+    }
+    let report = build_apply_report(&name, &serial, steps);
+    info!(
+        trace_id = %trace_id,
+        serial = %serial,
+        profile = %name,
+        applied = report.applied,
+        skipped = report.skipped,
+        failed = report.failed,
+        "applied device profile"
+    );
+    Ok(CommandResponse {
+        trace_id,
+        data: report,
+    })
+}
+
+/// Book `serial` for `user` until the RFC 3339 time `until`. Another user's active
+/// reservation is only replaced with `force`.
+#[tauri::command(async)]
+pub fn reserve_device(
+    serial: String,
+    user: String,
+    until: String,
+    force: Option<bool>,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<DeviceReservation>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+    let user =
+        normalize_reservation_user(&user).map_err(|err| AppError::validation(err, &trace_id))?;
+    let now = Utc::now();
+    let until =
+        parse_reservation_until(&until, now).map_err(|err| AppError::validation(err, &trace_id))?;
+    let reservation = DeviceReservation {
+        serial: serial.trim().to_string(),
+        user,
+        until: until.to_rfc3339(),
+        reserved_at: now.to_rfc3339(),
+    };
+    let data = store_device_reservation(
+        &device_reservations_path(),
+        reservation,
+        force.unwrap_or(false),
+        now,
+        &trace_id,
+    )?;
+    info!(trace_id = %trace_id, serial = %data.serial, until = %data.until, "device reserved");
+    Ok(CommandResponse { trace_id, data })
+}
+
+/// Release the reservation on `serial`. `user` defaults to this workstation's reservation
+/// user; releasing someone else's reservation requires `force`.
+#[tauri::command(async)]
+pub fn release_device(
+    serial: String,
+    user: Option<String>,
+    force: Option<bool>,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<bool>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+    let user = match user.filter(|value| !value.trim().is_empty()) {
+        Some(user) => {
+            normalize_reservation_user(&user).map_err(|err| AppError::validation(err, &trace_id))?
+        }
+        None => current_reservation_user(&load_config(&trace_id)?.device.reservation_user),
+    };
+    let data = remove_device_reservation(
+        &device_reservations_path(),
+        serial.trim(),
+        &user,
+        force.unwrap_or(false),
+        Utc::now(),
+        &trace_id,
+    )?;
+    Ok(CommandResponse { trace_id, data })
+}
+
+/// Warn (log and `device-reservation-conflict` event) when `action` touches devices someone
+/// else has reserved. The action still runs; reservations are advisory.
+fn warn_on_reservation_conflicts(
+    app: &AppHandle,
+    serials: &[String],
+    action: &str,
+    trace_id: &str,
+) {
+    let configured = match load_config(trace_id) {
+        Ok(config) => config.device.reservation_user,
+        Err(err) => {
+            warn!(trace_id = %trace_id, error = %err.error, "failed to load config for reservation check");
+            String::new()
+        }
+    };
+    let user = current_reservation_user(&configured);
+    let store = load_device_reservations(&device_reservations_path());
+    let conflicts = reservation_conflicts(&store, serials, &user, Utc::now());
+    if conflicts.is_empty() {
+        return;
+    }
+    for reservation in &conflicts {
+        warn!(
+            trace_id = %trace_id,
+            serial = %reservation.serial,
+            holder = %reservation.user,
+            until = %reservation.until,
+            action = %action,
+            "action on a device reserved by another user"
+        );
+    }
+    let _ = emit_tracked(
+        app,
+        DEVICE_RESERVATION_CONFLICT_EVENT,
+        ReservationConflictEvent {
+            action: action.to_string(),
+            user,
+            conflicts,
+            trace_id: trace_id.to_string(),
+        },
+    );
+}
+
+/// Connection sessions per device (all devices when `serial` is empty), most recently seen
+/// first.
+#[tauri::command(async)]
+pub fn get_device_history(
+    serial: Option<String>,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<Vec<DeviceHistory>>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    let serial = serial
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty());
+    let now_ms = Utc::now().timestamp_millis();
+    let mut histories: Vec<DeviceHistory> = load_device_history(&device_history_path())
+        .into_values()
+        .filter(|history| {
+            serial
+                .as_ref()
+                .is_none_or(|serial| &history.serial == serial)
+        })
+        .map(|history| with_open_time(history, now_ms))
+        .collect();
+    histories.sort_by_key(|history| std::cmp::Reverse(history.last_seen_ms));
+
+    Ok(CommandResponse {
+        trace_id,
+        data: histories,
+    })
+}
+
+#[tauri::command(async)]
+pub fn switch_adb_server(
+    name: Option<String>,
+    app: AppHandle,
+    state: State<'_, AppState>,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<AppConfig>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    let name = name
+        .map(|value| value.trim().to_string())
+        .unwrap_or_default();
+
+    let mut config = load_config(&trace_id)?;
+    if !name.is_empty()
+        && !config
+            .adb
+            .server_hosts
+            .iter()
+            .any(|entry| entry.name == name)
+    {
+        return Err(AppError::validation(
+            Message::keyed("unknown_value")
+                .with("subject", "adb server host")
+                .with("value", &name),
+            &trace_id,
+        ));
+    }
+    replace_adb_tunnel(&app, state.inner(), &config, &name, &trace_id)?;
+
+    config.adb.active_server = name;
+    let config = normalize_config_for_save(config);
+    save_config(&config, &trace_id)?;
+    apply_active_adb_server(&config);
+    info!(trace_id = %trace_id, active_server = %config.adb.active_server, "switched adb server");
+
+    // The device tracker holds a connection to the previous server; reconnect it.
+    let adb_program = get_adb_program(&trace_id)?;
+    let mut guard = state
+        .device_tracker
+        .lock()
+        .map_err(|_| AppError::system("Device tracker registry locked", &trace_id))?;
+    if let Some(handle) = guard.take() {
+        handle.stop();
+        let on_online = on_connect_hook(app.clone(), adb_program.clone());
+        let on_changed = detail_refresh_hook(
+            app.clone(),
+            adb_program.clone(),
+            Arc::clone(&state.scheduler),
+        );
+        *guard = Some(start_device_tracker(
+            app,
+            trace_id.clone(),
+            adb_program,
+            reconnect_policy(&config.device),
+            on_online,
+            on_changed,
+        ));
+    }
+
+    Ok(CommandResponse {
+        trace_id,
+        data: redact_restricted_mode(config),
+    })
+}
+
+/// `(host, tunnel)` of the server host `name` when it is reached over SSH.
+fn server_tunnel(config: &AppConfig, name: &str) -> Option<(String, SshTunnelSettings)> {
+    config
+        .adb
+        .server_hosts
+        .iter()
+        .find(|entry| !name.is_empty() && entry.name == name)
+        .and_then(|entry| {
+            entry
+                .ssh_tunnel
+                .clone()
+                .map(|tunnel| (entry.host.clone(), tunnel))
+        })
+}
+
+/// Bring up the tunnel for `name` (if it has one) before tearing down the current one, so a
+/// failed switch leaves the working tunnel in place. The registry stays unlocked while ssh
+/// connects. Tunnels on the same local port cannot overlap; then the old one goes first and
+/// is restarted if the new one fails.
+fn replace_adb_tunnel(
+    app: &AppHandle,
+    state: &AppState,
+    config: &AppConfig,
+    name: &str,
+    trace_id: &str,
+) -> Result<(), AppError> {
+    let locked = || AppError::system("Tunnel registry locked", trace_id);
+    let next = server_tunnel(config, name);
+    let mut stopped_current = false;
+    if let Some((_, settings)) = &next {
+        let mut guard = state.adb_tunnel.lock().map_err(|_| locked())?;
+        let same_port = guard
+            .as_ref()
+            .is_some_and(|handle| handle.status().local_port == settings.local_port);
+        let current = if same_port { guard.take() } else { None };
+        drop(guard);
+        if let Some(handle) = current {
+            handle.stop();
+            stopped_current = true;
+        }
+    }
+
+    let started = match &next {
+        Some((host, settings)) => {
+            match start_ssh_tunnel(app.clone(), name, host, settings, trace_id) {
+                Ok(handle) => Some(handle),
+                Err(err) => {
+                    if stopped_current {
+                        restart_saved_tunnel(app, state, config, trace_id);
+                    }
+                    return Err(err);
+                }
+            }
+        }
+        None => None,
+    };
+    let previous = {
+        let mut guard = state.adb_tunnel.lock().map_err(|_| locked())?;
+        std::mem::replace(&mut *guard, started)
+    };
+    if let Some(handle) = previous {
+        handle.stop();
+    }
+    Ok(())
+}
+
+/// Start the tunnel of the server saved in `config` unless one is already up; failures are
+/// logged since adb commands report the unreachable server on their own.
+fn restart_saved_tunnel(app: &AppHandle, state: &AppState, config: &AppConfig, trace_id: &str) {
+    let name = &config.adb.active_server;
+    let Some((host, settings)) = server_tunnel(config, name) else {
+        return;
+    };
+    let handle = match start_ssh_tunnel(app.clone(), name, &host, &settings, trace_id) {
+        Ok(handle) => handle,
+        Err(err) => {
+            warn!(trace_id = %trace_id, name = %name, error = %err.error, "failed to restart ssh tunnel");
+            return;
+        }
+    };
+    match state.adb_tunnel.lock() {
+        Ok(mut guard) if guard.is_none() => *guard = Some(handle),
+        // Another switch won the race; its tunnel stays.
+        Ok(_) => handle.stop(),
+        Err(_) => {
+            warn!(trace_id = %trace_id, "tunnel registry lock poisoned");
+            handle.stop();
+        }
+    }
+}
+
+/// Re-establish the saved server's SSH tunnel when the app starts. ssh may take a while to
+/// connect, so this runs in the background.
+pub fn restore_adb_tunnel(app: AppHandle) {
+    std::thread::spawn(move || {
+        let trace_id = resolve_trace_id(None);
+        let config = match load_config(&trace_id) {
+            Ok(config) => config,
+            Err(err) => {
+                warn!(trace_id = %trace_id, error = %err.error, "failed to load config for ssh tunnel");
+                return;
+            }
+        };
+        let state = app.state::<AppState>();
+        restart_saved_tunnel(&app, state.inner(), &config, &trace_id);
+    });
+}
+
+#[tauri::command(async)]
+pub fn get_adb_tunnel_status(
+    state: State<'_, AppState>,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<Option<AdbTunnelStatus>>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    let guard = state
+        .adb_tunnel
+        .lock()
+        .map_err(|_| AppError::system("Tunnel registry locked", &trace_id))?;
+    Ok(CommandResponse {
+        trace_id,
+        data: guard.as_ref().map(|handle| handle.status()),
+    })
+}
+
+#[tauri::command(async)]
+pub fn stop_device_tracking(
+    state: State<'_, AppState>,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<bool>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    info!(trace_id = %trace_id, "stop_device_tracking");
+
+    let mut guard = state
+        .device_tracker
+        .lock()
+        .map_err(|_| AppError::system("Device tracker registry locked", &trace_id))?;
+    if let Some(handle) = guard.take() {
+        handle.stop();
+    }
+
+    Ok(CommandResponse {
+        trace_id,
+        data: true,
+    })
+}
+
+#[tauri::command(async)]
+pub fn adb_pair(
+    address: String,
+    pairing_code: String,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<HostCommandResult>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&address, "address", &trace_id)?;
+    ensure_non_empty(&pairing_code, "pairing_code", &trace_id)?;
+
+    let adb_program = get_adb_program(&trace_id)?;
+    let args = vec!["pair".to_string(), address.clone(), pairing_code.clone()];
+    let output = run_command_with_timeout(
+        &adb_program,
+        &args,
+        command_timeout(TimeoutKind::DeviceCommand, &trace_id),
+        &trace_id,
+    )?;
+    let combined = format!("{}{}", output.stdout, output.stderr).to_lowercase();
+    if output.exit_code.unwrap_or_default() != 0
+        || combined.contains("failed")
+        || combined.contains("unable")
+    {
+        let detail = if output.stderr.trim().is_empty() {
+            output.stdout.trim()
+        } else {
+            output.stderr.trim()
+        };
+        return Err(AppError::dependency(
+            Message::keyed("action_failed")
+                .with("action", "adb pair")
+                .with("detail", detail),
+            &trace_id,
+        ));
+    }
+
+    Ok(CommandResponse {
+        trace_id,
+        data: HostCommandResult {
+            stdout: output.stdout,
+            stderr: output.stderr,
+            exit_code: output.exit_code,
+        },
+    })
+}
+
+#[tauri::command(async)]
+pub fn adb_connect(
+    address: String,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<HostCommandResult>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&address, "address", &trace_id)?;
+
+    let adb_program = get_adb_program(&trace_id)?;
+    let args = vec!["connect".to_string(), address.clone()];
+    let output = run_command_with_timeout(
+        &adb_program,
+        &args,
+        command_timeout(TimeoutKind::DeviceCommand, &trace_id),
+        &trace_id,
+    )?;
+    if connect_failed(&output) {
+        return Err(AppError::dependency(
+            Message::keyed("action_failed")
+                .with("action", "adb connect")
+                .with("detail", connect_failure_detail(&output)),
+            &trace_id,
+        ));
+    }
+
+    Ok(CommandResponse {
+        trace_id,
+        data: HostCommandResult {
+            stdout: output.stdout,
+            stderr: output.stderr,
+            exit_code: output.exit_code,
+        },
+    })
+}
+
+fn read_api_level(adb_program: &str, serial: &str, trace_id: &str) -> Option<u32> {
+    match run_device_shell(
+        adb_program,
+        serial,
+        &["getprop", "ro.build.version.sdk"],
+        command_timeout(TimeoutKind::Probe, trace_id),
+        trace_id,
+    ) {
+        Ok(output) => output.stdout.trim().parse::<u32>().ok(),
+        Err(err) => {
+            warn!(trace_id = %trace_id, serial = %serial, error = %err, "failed to read device SDK level");
+            None
+        }
+    }
+}
+
+fn read_wlan_ipv4(adb_program: &str, serial: &str, trace_id: &str) -> Option<Ipv4Addr> {
+    match run_device_shell(
+        adb_program,
+        serial,
+        &["ip", "-f", "inet", "addr", "show", "wlan0"],
+        command_timeout(TimeoutKind::Probe, trace_id),
+        trace_id,
+    ) {
+        Ok(output) => parse_wlan_ipv4(&output.stdout),
+        Err(err) => {
+            warn!(trace_id = %trace_id, serial = %serial, error = %err, "failed to read Wi-Fi address");
+            None
+        }
+    }
+}
+
+/// Which wireless setup a USB-connected device needs, so the UI can offer pairing codes on
+/// Android 11+ and the `adb tcpip` flow on older versions.
+#[tauri::command(async)]
+pub fn detect_wireless_flow(
+    serial: String,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<WirelessFlowInfo>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+    let adb_program = get_adb_program(&trace_id)?;
+
+    let api_level = read_api_level(&adb_program, &serial, &trace_id);
+    let flow = wireless_flow(&serial, api_level);
+    let wlan_ip = if flow == WIRELESS_FLOW_TCPIP {
+        read_wlan_ipv4(&adb_program, &serial, &trace_id).map(|ip| ip.to_string())
+    } else {
+        None
+    };
+
+    Ok(CommandResponse {
+        trace_id,
+        data: WirelessFlowInfo {
+            serial,
+            api_level,
+            flow: flow.to_string(),
+            wlan_ip,
+        },
+    })
+}
+
+/// Legacy wireless setup for devices without pairing support: read the Wi-Fi address over
+/// USB, switch adbd to TCP with `adb tcpip`, then `adb connect` once it has restarted.
+#[tauri::command(async)]
+pub fn enable_tcpip_and_connect(
+    serial: String,
+    port: Option<u16>,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<TcpipConnectResult>, AppError> {
+    const CONNECT_ATTEMPTS: u32 = 5;
+
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+    let port = port.unwrap_or(DEFAULT_TCPIP_PORT);
+    if port < 1024 {
+        return Err(AppError::validation(
+            Message::keyed("range")
+                .with("field", "port")
+                .with("min", "1024")
+                .with("max", "65535"),
+            &trace_id,
+        ));
+    }
+    if wireless_flow(&serial, None) != WIRELESS_FLOW_TCPIP {
+        return Err(AppError::validation(
+            Message::keyed("tcpip_needs_usb"),
+            &trace_id,
+        ));
+    }
+    let adb_program = get_adb_program(&trace_id)?;
+
+    // Read the address first: adbd restarts on tcpip and the USB transport drops briefly.
+    let ip = read_wlan_ipv4(&adb_program, &serial, &trace_id)
+        .ok_or_else(|| AppError::validation(Message::keyed("no_wifi_address"), &trace_id))?;
+    let args = vec![
+        "-s".to_string(),
+        serial.clone(),
+        "tcpip".to_string(),
+        port.to_string(),
+    ];
+    let output = run_command_with_timeout(
+        &adb_program,
+        &args,
+        command_timeout(TimeoutKind::DeviceCommand, &trace_id),
+        &trace_id,
+    )?;
+    if tcpip_failed(&output.stdout, &output.stderr, output.exit_code) {
+        return Err(AppError::dependency(
+            Message::keyed("action_failed")
+                .with("action", "adb tcpip")
+                .with("detail", connect_failure_detail(&output)),
+            &trace_id,
+        ));
+    }
+
+    let address = tcpip_address(ip, port);
+    let connect_args = vec!["connect".to_string(), address.clone()];
+    let mut attempts = 0;
+    let output = loop {
+        attempts += 1;
+        std::thread::sleep(Duration::from_secs(1));
+        let output = run_command_with_timeout(
+            &adb_program,
+            &connect_args,
+            command_timeout(TimeoutKind::DeviceCommand, &trace_id),
+            &trace_id,
+        )?;
+        if !connect_failed(&output) {
+            break output;
+        }
+        if attempts >= CONNECT_ATTEMPTS {
+            return Err(AppError::dependency(
+                Message::keyed("action_failed")
+                    .with("action", "adb connect")
+                    .with("detail", connect_failure_detail(&output)),
+                &trace_id,
+            ));
+        }
+        warn!(trace_id = %trace_id, address = %address, attempt = attempts, "adb connect not ready after tcpip, retrying");
+    };
+    info!(trace_id = %trace_id, serial = %serial, address = %address, "switched device to wireless adb");
+
+    Ok(CommandResponse {
+        trace_id,
+        data: TcpipConnectResult {
+            serial,
+            address,
+            attempts,
+            stdout: output.stdout,
+        },
+    })
+}
+
+#[tauri::command(async)]
+pub fn run_shell(
+    serials: Vec<String>,
+    command: String,
+    parallel: Option<bool>,
+    state: State<'_, AppState>,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<Vec<CommandResult>>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_action_allowed("Running shell commands", &trace_id)?;
+    ensure_non_empty(&command, "command", &trace_id)?;
+    if serials.is_empty() {
+        return Err(AppError::validation(
+            Message::keyed("required").with("field", "serials"),
+            &trace_id,
+        ));
+    }
+
+    let adb_program = get_adb_program(&trace_id)?;
+    let mut config = load_config(&trace_id)?;
+    let timeout = Duration::from_secs(config.command.command_timeout.max(1) as u64);
+    let use_parallel = parallel.unwrap_or(config.command.parallel_execution);
+    let scheduler = Arc::clone(&state.scheduler);
+
+    let mut results = Vec::with_capacity(serials.len());
+    if use_parallel {
+        let mut handles = Vec::new();
+        for (index, serial) in serials.into_iter().enumerate() {
+            ensure_non_empty(&serial, "serial", &trace_id)?;
+            let trace_id_clone = trace_id.clone();
+            let command_clone = command.clone();
+            let adb_program_clone = adb_program.clone();
+            let scheduler_clone = Arc::clone(&scheduler);
+            handles.push(std::thread::spawn(move || -> Result<_, AppError> {
+                let _permit = scheduler_clone.acquire_global();
+                let device_lock = scheduler_clone.device_lock(&serial);
+                let _device_guard = device_lock.lock().map_err(|_| {
+                    warn!(trace_id = %trace_id_clone, serial = %serial, "device lock poisoned");
+                    AppError::system(
+                        "Failed to access the device. Please try again.",
+                        &trace_id_clone,
+                    )
+                })?;
+                let args = vec![
+                    "-s".to_string(),
+                    serial.clone(),
+                    "shell".to_string(),
+                    "sh".to_string(),
+                    "-c".to_string(),
+                    command_clone,
+                ];
+                let output =
+                    run_command_with_timeout(&adb_program_clone, &args, timeout, &trace_id_clone)?;
+                Ok((index, serial, output))
+            }));
+        }
+
+        let mut collected = Vec::new();
+        for handle in handles {
+            let (index, serial, output) = handle
+                .join()
+                .map_err(|_| AppError::system("Shell command thread panicked", &trace_id))??;
+            collected.push((
+                index,
+                CommandResult {
+                    serial,
+                    stdout: output.stdout,
+                    stderr: output.stderr,
+                    exit_code: output.exit_code,
+                },
+            ));
+        }
+        collected.sort_by_key(|item| item.0);
+        results = collected.into_iter().map(|item| item.1).collect();
+    } else {
+        for serial in serials {
+            ensure_non_empty(&serial, "serial", &trace_id)?;
+            let _permit = scheduler.acquire_global();
+            let device_lock = scheduler.device_lock(&serial);
+            let _device_guard = device_lock.lock().map_err(|_| {
+                warn!(trace_id = %trace_id, serial = %serial, "device lock poisoned");
+                AppError::system("Failed to access the device. Please try again.", &trace_id)
+            })?;
+            let args = vec![
+                "-s".to_string(),
+                serial.clone(),
+                "shell".to_string(),
+                "sh".to_string(),
+                "-c".to_string(),
+                command.clone(),
+            ];
+            let output = run_command_with_timeout(&adb_program, &args, timeout, &trace_id)?;
+            results.push(CommandResult {
+                serial,
+                stdout: output.stdout,
+                stderr: output.stderr,
+                exit_code: output.exit_code,
+            });
+        }
+    }
+
+    if config.command.auto_save_history && !command.trim().is_empty() {
+        if config
+            .command_history
+            .last()
+            .map(|last| last == &command)
+            .unwrap_or(false)
+        {
+            // skip duplicate trailing entry
+        } else {
+            config.command_history.push(command.clone());
+            if config.command_history.len() > config.command.max_history_size {
+                let start = config
+                    .command_history
+                    .len()
+                    .saturating_sub(config.command.max_history_size);
+                config.command_history = config.command_history.split_off(start);
+            }
+            if let Err(err) = save_config(&config, &trace_id) {
+                warn!(trace_id = %trace_id, error = %err, "Failed to save command history");
+            }
+        }
+    }
+
+    Ok(CommandResponse {
+        trace_id,
+        data: results,
+    })
+}
+
+/// Start `command` on the device and emit its stdout and stderr as `shell-stream-event`
+/// chunks while it runs, for output that `run_shell` would only return at the end.
+#[tauri::command(async)]
+pub fn run_shell_streaming(
+    serial: String,
+    command: String,
+    timeout_secs: Option<u64>,
+    app: AppHandle,
+    state: State<'_, AppState>,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<ShellStreamInfo>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_action_allowed("Running shell commands", &trace_id)?;
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+    ensure_non_empty(&command, "command", &trace_id)?;
+    let timeout_secs = clamp_stream_timeout_secs(timeout_secs);
+    let adb_program = get_adb_program(&trace_id)?;
+
+    let stream_id = Uuid::new_v4().to_string();
+    let args = vec![
+        "-s".to_string(),
+        serial.clone(),
+        "shell".to_string(),
+        "sh".to_string(),
+        "-c".to_string(),
+        command,
+    ];
+    let app_emit = app.clone();
+    let trace_emit = trace_id.clone();
+    let emitter: ShellStreamEmitter = Arc::new(move |event: ShellStreamEvent| {
+        if event.event == "exit" {
+            match app_emit.state::<AppState>().shell_streams.lock() {
+                Ok(mut guard) => {
+                    guard.remove(&event.stream_id);
+                }
+                Err(_) => {
+                    warn!(trace_id = %trace_emit, stream_id = %event.stream_id, "shell stream registry locked, handle not cleared")
+                }
+            }
+        }
+        if let Err(err) = emit_tracked(&app_emit, SHELL_STREAM_EVENT_NAME, event) {
+            warn!(trace_id = %trace_emit, error = %err, "failed to emit shell stream event");
+        }
+    });
+
+    // Hold the registry while spawning so a fast exit cannot remove the handle before it is
+    // inserted.
+    let mut guard = state
+        .shell_streams
+        .lock()
+        .map_err(|_| AppError::system("Shell stream registry locked", &trace_id))?;
+    let stream = ShellStream::spawn(
+        &adb_program,
+        &args,
+        serial.clone(),
+        stream_id.clone(),
+        trace_id.clone(),
+        Duration::from_secs(timeout_secs),
+        emitter,
+    )
+    .map_err(|err| {
+        AppError::dependency(
+            Message::keyed("start_failed")
+                .with("subject", "shell stream")
+                .with("detail", &err),
+            &trace_id,
+        )
+    })?;
+    guard.insert(stream_id.clone(), stream);
+    info!(trace_id = %trace_id, serial = %serial, stream_id = %stream_id, "shell stream started");
+
+    Ok(CommandResponse {
+        trace_id,
+        data: ShellStreamInfo {
+            serial,
+            stream_id,
+            timeout_secs,
+        },
+    })
+}
+
+#[tauri::command(async)]
+pub fn cancel_shell_streaming(
+    stream_id: String,
+    state: State<'_, AppState>,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<bool>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&stream_id, "stream_id", &trace_id)?;
+    let guard = state
+        .shell_streams
+        .lock()
+        .map_err(|_| AppError::system("Shell stream registry locked", &trace_id))?;
+    let stream = guard.get(&stream_id).ok_or_else(|| {
+        AppError::validation(
+            Message::keyed("not_running").with("subject", "Shell stream"),
+            &trace_id,
+        )
+    })?;
+    stream.cancel();
+
+    Ok(CommandResponse {
+        trace_id,
+        data: true,
+    })
+}
+
+/// Run `steps` in order through `sh -c` on the device, threading regex captures from earlier
+/// stdout into later commands.
+#[tauri::command(async)]
+pub fn run_shell_pipeline(
+    serial: String,
+    steps: Vec<ShellPipelineStep>,
+    state: State<'_, AppState>,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<ShellPipelineResult>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_action_allowed("Running shell pipelines", &trace_id)?;
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+    let steps = compile_pipeline(&steps).map_err(|err| AppError::validation(err, &trace_id))?;
+    let adb_program = get_adb_program(&trace_id)?;
+
+    let scheduler = Arc::clone(&state.scheduler);
+    let _permit = scheduler.acquire_global();
+    let device_lock = scheduler.device_lock(&serial);
+    let _device_guard = device_lock.lock().map_err(|_| {
+        warn!(trace_id = %trace_id, serial = %serial, "device lock poisoned");
+        AppError::system("Failed to access the device. Please try again.", &trace_id)
+    })?;
+
+    let result = run_pipeline(&serial, &steps, |command, timeout| {
+        let args = vec![
+            "-s".to_string(),
+            serial.clone(),
+            "shell".to_string(),
+            "sh".to_string(),
+            "-c".to_string(),
+            command.to_string(),
+        ];
+        run_command_with_timeout(&adb_program, &args, timeout, &trace_id).map_err(|err| err.error)
+    });
+    if let Some(index) = result.stopped_at {
+        warn!(trace_id = %trace_id, serial = %serial, step = index, "shell pipeline stopped at a failed step");
+    }
+
+    Ok(CommandResponse {
+        trace_id,
+        data: result,
+    })
+}
+
+/// Run `dumpsys <service> [args]` and return structured data when a parser is registered for
+/// the service, raw text otherwise.
+#[tauri::command(async)]
+pub fn run_dumpsys(
+    serial: String,
+    service: String,
+    args: Option<Vec<String>>,
+    include_raw: Option<bool>,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<DumpsysResult>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+    let service = service.trim().to_string();
+    if !is_valid_dumpsys_service(&service) {
+        return Err(AppError::validation(
+            Message::keyed("invalid").with("subject", "dumpsys service"),
+            &trace_id,
+        ));
+    }
+    let args = validate_dumpsys_args(&args.unwrap_or_default())
+        .map_err(|err| AppError::validation(err, &trace_id))?;
+    let adb_program = get_adb_program(&trace_id)?;
+
+    let started = Instant::now();
+    let shell_args = build_dumpsys_shell_args(&service, &args);
+    let shell_args: Vec<&str> = shell_args.iter().map(String::as_str).collect();
+    let output = run_device_shell(
+        &adb_program,
+        &serial,
+        &shell_args,
+        command_timeout(TimeoutKind::DeviceCommand, &trace_id),
+        &trace_id,
+    )?;
+    if is_missing_service_output(&output.stdout) {
+        return Err(AppError::validation(
+            Message::keyed("service_not_found").with("service", &service),
+            &trace_id,
+        ));
+    }
+
+    let parser = find_dumpsys_parser(&service);
+    let parsed = parser.and_then(|parser| (parser.parse)(&output.stdout));
+    if parser.is_some() && parsed.is_none() {
+        warn!(trace_id = %trace_id, serial = %serial, service = %service, "dumpsys parser found no data, returning raw output");
+    }
+    let raw = (parsed.is_none() || include_raw.unwrap_or(false)).then_some(output.stdout);
+
+    Ok(CommandResponse {
+        trace_id,
+        data: DumpsysResult {
+            serial,
+            service,
+            args,
+            parser: parsed
+                .as_ref()
+                .and(parser)
+                .map(|parser| parser.service.to_string()),
+            parsed,
+            raw,
+            duration_ms: started.elapsed().as_millis() as u64,
+        },
+    })
+}
+
+#[tauri::command(async)]
+pub fn list_dumpsys_parsers(
+    trace_id: Option<String>,
+) -> Result<CommandResponse<Vec<DumpsysParserInfo>>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    let data = DUMPSYS_PARSERS
+        .iter()
+        .map(|parser| DumpsysParserInfo {
+            service: parser.service.to_string(),
+            description: parser.description.to_string(),
+        })
+        .collect();
+    Ok(CommandResponse { trace_id, data })
+}
+
+/// Logs that survive a reboot: the previous boot's logcat, the pre-reboot kernel log
+/// (`last_kmsg` or pstore) and kernel panics kept by dropbox. Missing sources are reported
+/// per section instead of failing the whole call.
+#[tauri::command(async)]
+pub fn get_last_boot_logs(
+    serial: String,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<LastBootLogs>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+    let adb_program = get_adb_program(&trace_id)?;
+
+    let sections: Vec<LastBootLogSection> = LAST_BOOT_SOURCES
+        .iter()
+        .map(|source| {
+            let mut outputs = Vec::new();
+            for candidate in source.candidates {
+                let mut args = vec!["-s".to_string(), serial.clone(), "shell".to_string()];
+                args.extend(candidate.iter().map(|value| value.to_string()));
+                let output = run_command_with_timeout(
+                    &adb_program,
+                    &args,
+                    Duration::from_secs(source.timeout_secs),
+                    &trace_id,
+                )
+                .map_err(|err| err.error);
+                let usable = output
+                    .as_ref()
+                    .is_ok_and(|output| unavailable_reason(output).is_none());
+                outputs.push(output);
+                if usable {
+                    break;
+                }
+            }
+            let section = build_section(source, outputs);
+            if let Some(err) = &section.error {
+                info!(trace_id = %trace_id, serial = %serial, source = %source.id, reason = %err, "last boot log source unavailable");
+            }
+            section
+        })
+        .collect();
+
+    let panic_detected = sections
+        .iter()
+        .any(|section| !section.panic_lines.is_empty());
+    Ok(CommandResponse {
+        trace_id,
+        data: LastBootLogs {
+            serial,
+            sections,
+            panic_detected,
+        },
+    })
+}
+
+#[tauri::command(async)]
+pub fn persist_terminal_state(
+    restore_sessions: Vec<String>,
+    buffers: HashMap<String, Vec<String>>,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<bool>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+
+    let mut config = load_config(&trace_id)?;
+
+    let mut unique_restore_sessions = Vec::new();
+    let mut seen = std::collections::HashSet::new();
+    for serial in restore_sessions {
+        let trimmed = serial.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+        if seen.insert(trimmed.to_string()) {
+            unique_restore_sessions.push(trimmed.to_string());
+        }
+    }
+
+    let mut next_buffers: HashMap<String, Vec<String>> = HashMap::new();
+    for serial in unique_restore_sessions.iter() {
+        if let Some(lines) = buffers.get(serial) {
+            let mut lines = lines.clone();
+            clamp_terminal_buffer_lines(&mut lines);
+            next_buffers.insert(serial.clone(), lines);
+        } else if let Some(existing) = config.terminal.buffers.get(serial) {
+            next_buffers.insert(serial.clone(), existing.clone());
+        }
+    }
+
+    config.terminal.restore_sessions = unique_restore_sessions;
+    config.terminal.buffers = next_buffers;
+
+    save_config(&config, &trace_id).map_err(|err| {
+        AppError::system(
+            Message::keyed("operation_failed")
+                .with("action", "persist terminal state")
+                .with("detail", &err),
+            &trace_id,
+        )
+    })?;
+
+    Ok(CommandResponse {
+        trace_id,
+        data: true,
+    })
+}
+
+#[tauri::command(async)]
+pub fn start_terminal_session(
+    serial: String,
+    app: AppHandle,
+    state: State<'_, AppState>,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<TerminalSessionInfo>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_action_allowed("Opening a device terminal", &trace_id)?;
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+
+    let adb_program = get_adb_program(&trace_id)?;
+    let mut guard = state
+        .terminal_sessions
+        .lock()
+        .map_err(|_| AppError::system("Terminal registry locked", &trace_id))?;
+    if let Some(existing) = guard.get(&serial) {
+        if existing.is_running() {
+            return Err(AppError::validation(
+                Message::keyed("already_running").with("subject", "Terminal session"),
+                &trace_id,
+            ));
+        }
+        guard.remove(&serial);
+    }
+
+    let session_id = Uuid::new_v4().to_string();
+    let args = vec![
+        "-s".to_string(),
+        serial.clone(),
+        "shell".to_string(),
+        "-t".to_string(),
+    ];
+
+    let app_emit = app.clone();
+    let trace_emit = trace_id.clone();
+    let emitter: Arc<dyn Fn(TerminalEvent) + Send + Sync> = Arc::new(move |event| {
+        if let Err(err) = emit_tracked(&app_emit, TERMINAL_EVENT_NAME, event) {
+            warn!(trace_id = %trace_emit, error = %err, "failed to emit terminal event");
+        }
+    });
+
+    let session = TerminalSession::spawn(
+        &adb_program,
+        &args,
+        serial.clone(),
+        session_id.clone(),
+        trace_id.clone(),
+        emitter,
+    )
+    .map_err(|err| {
+        AppError::dependency(
+            Message::keyed("start_failed")
+                .with("subject", "terminal session")
+                .with("detail", &err),
+            &trace_id,
+        )
+    })?;
+
+    guard.insert(serial.clone(), session);
+
+    Ok(CommandResponse {
+        trace_id,
+        data: TerminalSessionInfo { serial, session_id },
+    })
+}
+
+#[tauri::command(async)]
+pub fn write_terminal_session(
+    serial: String,
+    data: String,
+    newline: bool,
+    state: State<'_, AppState>,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<bool>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+
+    let mut guard = state
+        .terminal_sessions
+        .lock()
+        .map_err(|_| AppError::system("Terminal registry locked", &trace_id))?;
+    let not_running = match guard.get(&serial) {
+        Some(session) => !session.is_running(),
+        None => true,
+    };
+    if not_running {
+        guard.remove(&serial);
+        return Err(AppError::validation(
+            Message::keyed("not_running").with("subject", "Terminal session"),
+            &trace_id,
+        ));
+    }
+    let session = guard.get(&serial).ok_or_else(|| {
+        AppError::validation(
+            Message::keyed("not_running").with("subject", "Terminal session"),
+            &trace_id,
+        )
+    })?;
+    session.write(&data, newline).map_err(|err| {
+        AppError::dependency(
+            Message::keyed("action_failed")
+                .with("action", "Terminal write")
+                .with("detail", &err),
+            &trace_id,
+        )
+    })?;
+
+    Ok(CommandResponse {
+        trace_id,
+        data: true,
+    })
+}
+
+#[tauri::command(async)]
+pub fn stop_terminal_session(
+    serial: String,
+    state: State<'_, AppState>,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<bool>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+
+    let mut guard = state
+        .terminal_sessions
+        .lock()
+        .map_err(|_| AppError::system("Terminal registry locked", &trace_id))?;
+    let session = guard.remove(&serial).ok_or_else(|| {
+        AppError::validation(
+            Message::keyed("not_running").with("subject", "Terminal session"),
+            &trace_id,
+        )
+    })?;
+    session.stop();
+
+    Ok(CommandResponse {
+        trace_id,
+        data: true,
+    })
+}
+
+#[tauri::command(async)]
+pub fn reboot_devices(
+    serials: Vec<String>,
+    mode: Option<String>,
+    wait_for_boot: Option<bool>,
+    boot_timeout_secs: Option<u64>,
+    app: AppHandle,
+    state: State<'_, AppState>,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<Vec<RebootResult>>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    if serials.is_empty() {
+        return Err(AppError::validation(
+            Message::keyed("required").with("field", "serials"),
+            &trace_id,
+        ));
+    }
+    warn_on_reservation_conflicts(&app, &serials, "Rebooting devices", &trace_id);
+
+    let mode = mode.unwrap_or_else(|| "system".to_string());
+    if matches!(mode.as_str(), "bootloader" | "recovery") {
+        ensure_action_allowed(&format!("Rebooting to {mode}"), &trace_id)?;
+    }
+    let wait_for_boot = wait_for_boot.unwrap_or(false);
+    if wait_for_boot && matches!(mode.as_str(), "bootloader" | "recovery") {
+        return Err(AppError::validation(
+            Message::keyed("boot_wait_unsupported").with("mode", mode),
+            &trace_id,
+        ));
+    }
+    let boot_timeout = boot_wait_timeout(boot_timeout_secs);
+    let adb_program = get_adb_program(&trace_id)?;
+    let scheduler = Arc::clone(&state.scheduler);
+
+    let mut handles = Vec::new();
+    for (index, serial) in serials.into_iter().enumerate() {
+        ensure_non_empty(&serial, "serial", &trace_id)?;
+        let scheduler_clone = Arc::clone(&scheduler);
+        let trace_clone = trace_id.clone();
+        let adb_program_clone = adb_program.clone();
+        let mode_clone = mode.clone();
+        let app_clone = app.clone();
+        handles.push(std::thread::spawn(move || -> Result<_, AppError> {
+            let permit = scheduler_clone.acquire_global();
+            let device_lock = scheduler_clone.device_lock(&serial);
+            let _device_guard = device_lock.lock().map_err(|_| {
+                warn!(trace_id = %trace_clone, serial = %serial, "device lock poisoned");
+                AppError::system(
+                    "Failed to access the device. Please try again.",
+                    &trace_clone,
+                )
+            })?;
+
+            let mut args = vec!["-s".to_string(), serial.clone(), "reboot".to_string()];
+            match mode_clone.as_str() {
+                "recovery" => args.push("recovery".to_string()),
+                "bootloader" => args.push("bootloader".to_string()),
+                _ => {}
+            }
+            let output = run_command_with_timeout(
+                &adb_program_clone,
+                &args,
+                command_timeout(TimeoutKind::DeviceCommand, &trace_clone),
+                &trace_clone,
+            )?;
+            let rebooted = output.exit_code == Some(0);
+            if rebooted {
+                record_timeline_event(
+                    &serial,
+                    TIMELINE_REBOOT,
+                    &format!("Rebooted ({mode_clone})"),
+                    &trace_clone,
+                );
+            }
+            // Waiting frees the global slot for other devices but keeps the device lock, so
+            // actions queued for this device run only once it has booted.
+            drop(permit);
+            let boot_elapsed = (wait_for_boot && rebooted).then(|| {
+                wait_for_boot_completed(
+                    &app_clone,
+                    &adb_program_clone,
+                    &serial,
+                    boot_timeout,
+                    &trace_clone,
+                )
+            });
+            if let Some(Some(elapsed)) = boot_elapsed {
+                record_timeline_event(
+                    &serial,
+                    TIMELINE_REBOOT,
+                    &format!("Boot completed in {}s", elapsed.as_secs()),
+                    &trace_clone,
+                );
+            }
+            Ok((
+                index,
+                RebootResult {
+                    result: CommandResult {
+                        serial,
+                        stdout: output.stdout,
+                        stderr: output.stderr,
+                        exit_code: output.exit_code,
+                    },
+                    boot_completed: boot_elapsed.map(|elapsed| elapsed.is_some()),
+                    boot_elapsed_ms: boot_elapsed
+                        .flatten()
+                        .map(|elapsed| elapsed.as_millis() as u64),
+                },
+            ))
+        }));
+    }
+
+    let mut collected = Vec::new();
+    for handle in handles {
+        let (index, result) = handle
+            .join()
+            .map_err(|_| AppError::system("Reboot thread panicked", &trace_id))??;
+        collected.push((index, result));
+    }
+    collected.sort_by_key(|item| item.0);
+    let results = collected.into_iter().map(|item| item.1).collect();
+
+    Ok(CommandResponse {
+        trace_id,
+        data: results,
+    })
+}
+
+fn collect_readiness_signals(adb_program: &str, serial: &str, trace_id: &str) -> ReadinessSignals {
+    let shell = |command: &str| -> Option<String> {
+        let args = vec![
+            "-s".to_string(),
+            serial.to_string(),
+            "shell".to_string(),
+            command.to_string(),
+        ];
+        match run_command_with_timeout(
+            adb_program,
+            &args,
+            command_timeout(TimeoutKind::DeviceCommand, trace_id),
+            trace_id,
+        ) {
+            Ok(output) if output.exit_code.unwrap_or_default() == 0 => Some(output.stdout),
+            Ok(output) => {
+                warn!(trace_id = %trace_id, serial = %serial, command, stderr = %output.stderr.trim(), "readiness probe failed");
+                None
+            }
+            Err(err) => {
+                warn!(trace_id = %trace_id, serial = %serial, command, error = %err, "readiness probe failed");
+                None
+            }
+        }
+    };
+
+    let window = shell("dumpsys window");
+    ReadinessSignals {
+        screen_on: shell("dumpsys power").and_then(|output| parse_screen_on(&output)),
+        keyguard_locked: window.as_deref().and_then(parse_keyguard_locked),
+        battery_level: shell("dumpsys battery").and_then(|output| parse_battery_level(&output)),
+        free_storage_kb: shell("df -k /data")
+            .and_then(|output| parse_df_available_kb(&output).ok()),
+        anr_windows: window.as_deref().map(count_anr_windows),
+        wifi_connected: shell("cmd wifi status 2>/dev/null || dumpsys wifi")
+            .and_then(|output| parse_wifi_connected(&output)),
+        device_epoch_secs: shell("date +%s").and_then(|output| parse_epoch_secs(&output)),
+    }
+}
+
+#[tauri::command(async)]
+pub fn run_device_readiness_check(
+    serials: Vec<String>,
+    thresholds: Option<ReadinessThresholds>,
+    state: State<'_, AppState>,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<Vec<DeviceReadinessResult>>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    if serials.is_empty() {
+        return Err(AppError::validation(
+            Message::keyed("required").with("field", "serials"),
+            &trace_id,
+        ));
+    }
+    // Checked up front so a bad entry cannot leave threads for earlier serials running.
+    for serial in &serials {
+        ensure_non_empty(serial, "serial", &trace_id)?;
+    }
+
+    let adb_program = get_adb_program(&trace_id)?;
+    let thresholds = normalize_readiness_thresholds(thresholds);
+    let scheduler = Arc::clone(&state.scheduler);
+
+    let mut handles = Vec::new();
+    for (index, serial) in serials.into_iter().enumerate() {
+        let scheduler_clone = Arc::clone(&scheduler);
+        let trace_clone = trace_id.clone();
+        let adb_program_clone = adb_program.clone();
+        let thresholds_clone = thresholds.clone();
+        handles.push(std::thread::spawn(move || -> Result<_, AppError> {
+            let _permit = scheduler_clone.acquire_global();
+            let device_lock = scheduler_clone.device_lock(&serial);
+            let _device_guard = device_lock.lock().map_err(|_| {
+                warn!(trace_id = %trace_clone, serial = %serial, "device lock poisoned");
+                AppError::system(
+                    "Failed to access the device. Please try again.",
+                    &trace_clone,
+                )
+            })?;
+
+            let signals = collect_readiness_signals(&adb_program_clone, &serial, &trace_clone);
+            let result =
+                evaluate_readiness(&serial, &signals, &thresholds_clone, Utc::now().timestamp());
+            Ok((index, result))
+        }));
+    }
+
+    let mut collected = Vec::new();
+    for handle in handles {
+        let (index, result) = handle
+            .join()
+            .map_err(|_| AppError::system("Readiness check thread panicked", &trace_id))??;
+        collected.push((index, result));
+    }
+    collected.sort_by_key(|item| item.0);
+    let results = collected.into_iter().map(|item| item.1).collect();
+
+    Ok(CommandResponse {
+        trace_id,
+        data: results,
+    })
+}
+
+fn run_device_shell(
+    adb_program: &str,
+    serial: &str,
+    shell_args: &[&str],
+    timeout: Duration,
+    trace_id: &str,
+) -> Result<CommandOutput, AppError> {
+    let mut args = vec!["-s".to_string(), serial.to_string(), "shell".to_string()];
+    args.extend(shell_args.iter().map(|value| value.to_string()));
+    let output = run_command_with_timeout(adb_program, &args, timeout, trace_id)?;
+    if output.exit_code.unwrap_or_default() != 0 {
+        return Err(AppError::dependency(
+            // Only name the subcommand; trailing arguments may carry typed input such as PINs.
+            format!(
+                "{} failed: {}",
+                shell_args
+                    .iter()
+                    .take(2)
+                    .copied()
+                    .collect::<Vec<_>>()
+                    .join(" "),
+                output.stderr.trim()
+            ),
+            trace_id,
+        ));
+    }
+    Ok(output)
+}
+
+#[tauri::command(async)]
+pub fn wake_device(
+    serial: String,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<bool>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+
+    let adb_program = get_adb_program(&trace_id)?;
+    run_device_shell(
+        &adb_program,
+        &serial,
+        &["input", "keyevent", "KEYCODE_WAKEUP"],
+        command_timeout(TimeoutKind::DeviceCommand, &trace_id),
+        &trace_id,
+    )?;
+
+    Ok(CommandResponse {
+        trace_id,
+        data: true,
+    })
+}
+
+/// Keyguard, encryption and setup-wizard state: the usual reasons batch operations fail on a
+/// device that looks connected.
+#[tauri::command(async)]
+pub fn get_lock_state(
+    serial: String,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<DeviceLockState>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+
+    let adb_program = get_adb_program(&trace_id)?;
+    let args = vec![
+        "-s".to_string(),
+        serial.clone(),
+        "shell".to_string(),
+        LOCK_STATE_SCRIPT.to_string(),
+    ];
+    let output = run_command_with_timeout(
+        &adb_program,
+        &args,
+        command_timeout(TimeoutKind::DeviceCommand, &trace_id),
+        &trace_id,
+    )?;
+    if output.stdout.trim().is_empty() {
+        return Err(AppError::dependency(
+            Message::keyed("operation_failed")
+                .with("action", "read lock state")
+                .with("detail", output.stderr.trim()),
+            &trace_id,
+        ));
+    }
+
+    Ok(CommandResponse {
+        trace_id,
+        data: parse_lock_state(&serial, &output.stdout),
+    })
+}
+
+/// Camera ids, facing, hardware level and JPEG sizes from `dumpsys media.camera`, plus the
+/// codec list from `dumpsys media.player` with hardware/software classification.
+#[tauri::command(async)]
+pub fn get_media_capabilities(
+    serial: String,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<MediaCapabilities>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+
+    let adb_program = get_adb_program(&trace_id)?;
+    let dump = |service: &str| {
+        run_device_shell(
+            &adb_program,
+            &serial,
+            &["dumpsys", service],
+            command_timeout(TimeoutKind::DeviceCommand, &trace_id),
+            &trace_id,
+        )
+        .map(|output| output.stdout)
+    };
+    let (cameras, codecs) = match (dump("media.camera"), dump("media.player")) {
+        (Err(err), Err(_)) => return Err(err),
+        (cameras, codecs) => {
+            for (service, result) in [("media.camera", &cameras), ("media.player", &codecs)] {
+                if let Err(err) = result {
+                    warn!(trace_id = %trace_id, serial = %serial, service, error = %err.error, "media capability dump failed");
+                }
+            }
+            (
+                cameras
+                    .map(|output| parse_camera_dump(&output))
+                    .unwrap_or_default(),
+                codecs
+                    .map(|output| parse_codec_dump(&output))
+                    .unwrap_or_default(),
+            )
+        }
+    };
+
+    Ok(CommandResponse {
+        trace_id,
+        data: MediaCapabilities {
+            serial,
+            cameras,
+            codecs,
+        },
+    })
+}
+
+/// Connected input devices with InputReader's classification, key layout files and the
+/// kernel's event capabilities, for "controller not recognized" reports.
+#[tauri::command(async)]
+pub fn list_input_devices(
+    serial: String,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<InputDeviceInventory>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+
+    let adb_program = get_adb_program(&trace_id)?;
+    let args = vec![
+        "-s".to_string(),
+        serial.clone(),
+        "shell".to_string(),
+        INPUT_DEVICES_SCRIPT.to_string(),
+    ];
+    let output = run_command_with_timeout(
+        &adb_program,
+        &args,
+        command_timeout(TimeoutKind::DeviceCommand, &trace_id),
+        &trace_id,
+    )?;
+    let devices = parse_input_devices(&output.stdout);
+    if devices.is_empty() && output.exit_code != Some(0) {
+        return Err(AppError::dependency(
+            Message::keyed("operation_failed")
+                .with("action", "list input devices")
+                .with("detail", output.stderr.trim()),
+            &trace_id,
+        ));
+    }
+
+    Ok(CommandResponse {
+        trace_id,
+        data: InputDeviceInventory { serial, devices },
+    })
+}
+
+/// Per-SIM operator, network type, roaming, service, data and signal state.
+#[tauri::command(async)]
+pub fn get_telephony_info(
+    serial: String,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<TelephonyInfo>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+
+    let adb_program = get_adb_program(&trace_id)?;
+    let args = vec![
+        "-s".to_string(),
+        serial.clone(),
+        "shell".to_string(),
+        TELEPHONY_SCRIPT.to_string(),
+    ];
+    let output = run_command_with_timeout(
+        &adb_program,
+        &args,
+        command_timeout(TimeoutKind::DeviceCommand, &trace_id),
+        &trace_id,
+    )?;
+    if output.stdout.trim().is_empty() {
+        return Err(AppError::dependency(
+            Message::keyed("operation_failed")
+                .with("action", "read telephony info")
+                .with("detail", output.stderr.trim()),
+            &trace_id,
+        ));
+    }
+
+    Ok(CommandResponse {
+        trace_id,
+        data: parse_telephony_info(&serial, &output.stdout),
+    })
+}
+
+#[tauri::command(async)]
+pub fn get_boot_info(
+    serial: String,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<BootInfo>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+
+    let adb_program = get_adb_program(&trace_id)?;
+    let output = run_device_shell(
+        &adb_program,
+        &serial,
+        &["getprop"],
+        command_timeout(TimeoutKind::Probe, &trace_id),
+        &trace_id,
+    )?;
+    let props = parse_getprop_map(&output.stdout);
+    if props.is_empty() {
+        return Err(AppError::dependency(
+            Message::keyed("operation_failed")
+                .with("action", "read device properties")
+                .with("detail", output.stderr.trim()),
+            &trace_id,
+        ));
+    }
+
+    Ok(CommandResponse {
+        trace_id,
+        data: parse_boot_info(&serial, &props),
+    })
+}
+
+#[tauri::command(async)]
+pub fn unlock_device(
+    serial: String,
+    pin: Option<String>,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<DeviceUnlockResult>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+    let pin = match pin.filter(|value| !value.trim().is_empty()) {
+        Some(value) => Some(
+            validate_unlock_pin(&value)
+                .map_err(|message| AppError::validation(message, &trace_id))?,
+        ),
+        None => None,
+    };
+
+    let adb_program = get_adb_program(&trace_id)?;
+    let timeout = command_timeout(TimeoutKind::DeviceCommand, &trace_id);
+    run_device_shell(
+        &adb_program,
+        &serial,
+        &["input", "keyevent", "KEYCODE_WAKEUP"],
+        timeout,
+        &trace_id,
+    )?;
+    // `wm dismiss-keyguard` unlocks insecure keyguards and brings up the bouncer for secure ones.
+    if let Err(err) = run_device_shell(
+        &adb_program,
+        &serial,
+        &["wm", "dismiss-keyguard"],
+        timeout,
+        &trace_id,
+    ) {
+        warn!(trace_id = %trace_id, serial = %serial, error = %err, "dismiss-keyguard failed, falling back to menu key");
+        run_device_shell(
+            &adb_program,
+            &serial,
+            &["input", "keyevent", "KEYCODE_MENU"],
+            timeout,
+            &trace_id,
+        )?;
+    }
+    if let Some(pin) = pin.as_deref() {
+        std::thread::sleep(Duration::from_millis(500));
+        run_device_shell(
+            &adb_program,
+            &serial,
+            &["input", "text", pin],
+            timeout,
+            &trace_id,
+        )?;
+        run_device_shell(
+            &adb_program,
+            &serial,
+            &["input", "keyevent", "KEYCODE_ENTER"],
+            timeout,
+            &trace_id,
+        )?;
+        std::thread::sleep(Duration::from_millis(500));
+    }
+
+    let keyguard_locked = match run_device_shell(
+        &adb_program,
+        &serial,
+        &["dumpsys", "window"],
+        command_timeout(TimeoutKind::DeviceCommand, &trace_id),
+        &trace_id,
+    ) {
+        Ok(output) => parse_keyguard_locked(&output.stdout),
+        Err(err) => {
+            warn!(trace_id = %trace_id, serial = %serial, error = %err, "failed to read keyguard state");
+            None
+        }
+    };
+
+    Ok(CommandResponse {
+        trace_id,
+        data: DeviceUnlockResult {
+            serial,
+            pin_entered: pin.is_some(),
+            keyguard_locked,
+        },
+    })
+}
+
+#[tauri::command(async)]
+pub fn set_stay_awake(
+    serial: String,
+    enable: bool,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<bool>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+
+    let adb_program = get_adb_program(&trace_id)?;
+    // `svc power stayon true` keeps the screen on for AC, USB and wireless charging.
+    run_device_shell(
+        &adb_program,
+        &serial,
+        &[
+            "svc",
+            "power",
+            "stayon",
+            if enable { "true" } else { "false" },
+        ],
+        command_timeout(TimeoutKind::DeviceCommand, &trace_id),
+        &trace_id,
+    )?;
+
+    Ok(CommandResponse {
+        trace_id,
+        data: enable,
+    })
+}
+
+fn start_on_connect_logcat(
+    app: &AppHandle,
+    serial: &str,
+    filter: &str,
+    trace_id: &str,
+) -> Result<(), AppError> {
+    let state = app.state::<AppState>();
+    let running = state
+        .logcat_processes
+        .lock()
+        .map_err(|_| AppError::system("Logcat registry locked", trace_id))?
+        .contains_key(serial);
+    if running {
+        return Ok(());
+    }
+    let filter = Some(filter.to_string()).filter(|value| !value.is_empty());
+    start_logcat(
+        serial.to_string(),
+        filter,
+        app.clone(),
+        state,
+        Some(trace_id.to_string()),
+    )
+    .map(|_| ())
+}
+
+fn apply_on_connect_actions(
+    app: &AppHandle,
+    adb_program: &str,
+    serial: &str,
+    actions: &[OnConnectAction],
+    trace_id: &str,
+) -> Vec<OnConnectAuditEntry> {
+    actions
+        .iter()
+        .map(|action| {
+            let result = if action.kind == ACTION_START_LOGCAT {
+                start_on_connect_logcat(app, serial, &action.value, trace_id)
+            } else {
+                action_shell_commands(action).iter().try_for_each(|command| {
+                    let parts: Vec<&str> = command.iter().map(String::as_str).collect();
+                    run_device_shell(
+                        adb_program,
+                        serial,
+                        &parts,
+                        command_timeout(TimeoutKind::DeviceCommand, trace_id),
+                        trace_id,
+                    )
+                    .map(|_| ())
+                })
+            };
+            let error = result.err().map(|err| err.error);
+            if let Some(error) = &error {
+                warn!(trace_id = %trace_id, serial = %serial, action = %action.kind, error = %error, "on-connect action failed");
+            }
+            OnConnectAuditEntry {
+                serial: serial.to_string(),
+                action: action.kind.clone(),
+                value: action.value.clone(),
+                success: error.is_none(),
+                error,
+                applied_at: Utc::now().to_rfc3339(),
+                trace_id: trace_id.to_string(),
+            }
+        })
+        .collect()
+}
+
+fn record_on_connect(
+    app: &AppHandle,
+    serial: &str,
+    entries: &[OnConnectAuditEntry],
+    trace_id: &str,
+) {
+    if let Err(err) = append_audit(&on_connect_audit_path(), entries, trace_id) {
+        warn!(trace_id = %trace_id, error = %err, "failed to save on-connect audit");
+    }
+    let payload = serde_json::json!({
+        "trace_id": trace_id,
+        "serial": serial,
+        "entries": entries,
+    });
+    if let Err(err) = emit_tracked(app, ON_CONNECT_APPLIED_EVENT, payload) {
+        warn!(trace_id = %trace_id, error = %err, "failed to emit on-connect result");
+    }
+}
+
+/// Applies the device's on-connect profile in the background whenever it comes online.
+fn on_connect_hook(app: AppHandle, adb_program: String) -> DeviceOnlineHook {
+    Arc::new(move |transition: &DeviceTransition| {
+        let app = app.clone();
+        let adb_program = adb_program.clone();
+        let serial = transition.serial.clone();
+        let model = transition.model.clone();
+        std::thread::spawn(move || {
+            let trace_id = resolve_trace_id(None);
+            run_hooks(
+                HOOK_DEVICE_CONNECTED,
+                &serial,
+                true,
+                &format!("Device {serial} connected"),
+                serde_json::json!({ "model": model }),
+                &trace_id,
+            );
+            let config = match load_config(&trace_id) {
+                Ok(config) => config,
+                Err(err) => {
+                    warn!(trace_id = %trace_id, error = %err, "skipping on-connect actions; config unavailable");
+                    return;
+                }
+            };
+            let Some(actions) = profile_actions(&config.on_connect, &serial) else {
+                return;
+            };
+            info!(trace_id = %trace_id, serial = %serial, count = actions.len(), "applying on-connect actions");
+            let entries = apply_on_connect_actions(&app, &adb_program, &serial, actions, &trace_id);
+            record_on_connect(&app, &serial, &entries, &trace_id);
+        });
+    })
+}
+
+#[tauri::command(async)]
+pub fn apply_on_connect_profile(
+    serial: String,
+    app: AppHandle,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<Vec<OnConnectAuditEntry>>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+
+    let config = load_config(&trace_id)?;
+    let actions = profile_actions(&config.on_connect, &serial).ok_or_else(|| {
+        AppError::validation(
+            Message::keyed("no_on_connect_profile").with("serial", &serial),
+            &trace_id,
+        )
+    })?;
+    let adb_program = get_adb_program(&trace_id)?;
+    let entries = apply_on_connect_actions(&app, &adb_program, &serial, actions, &trace_id);
+    record_on_connect(&app, &serial, &entries, &trace_id);
+
+    Ok(CommandResponse {
+        trace_id,
+        data: entries,
+    })
+}
+
+/// Audit of applied on-connect actions, newest first.
+#[tauri::command(async)]
+pub fn get_on_connect_audit(
+    serial: Option<String>,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<Vec<OnConnectAuditEntry>>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    let serial = serial
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty());
+    let entries = load_audit(&on_connect_audit_path())
+        .into_iter()
+        .rev()
+        .filter(|entry| serial.as_ref().is_none_or(|serial| &entry.serial == serial))
+        .collect();
+
+    Ok(CommandResponse {
+        trace_id,
+        data: entries,
+    })
+}
+
+fn audit_factory_reset(
+    serial: &str,
+    stage: &str,
+    method: Option<&str>,
+    result: Result<(), &str>,
+    trace_id: &str,
+) {
+    let entry = FactoryResetAuditEntry {
+        serial: serial.to_string(),
+        stage: stage.to_string(),
+        method: method.map(str::to_string),
+        success: result.is_ok(),
+        detail: result.err().map(str::to_string),
+        at: Utc::now().to_rfc3339(),
+        trace_id: trace_id.to_string(),
+    };
+    if let Err(err) = append_reset_audit(&factory_reset_audit_path(), entry, trace_id) {
+        warn!(trace_id = %trace_id, serial = %serial, error = %err, "failed to save factory reset audit");
+    }
+}
+
+/// First half of a factory reset: hands out the single-use token `factory_reset_device`
+/// requires, along with the model so the UI can name the device being wiped.
+#[tauri::command(async)]
+pub fn prepare_factory_reset(
+    serial: String,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<FactoryResetChallenge>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+    let serial = serial.trim().to_string();
+    ensure_action_allowed("Factory resetting devices", &trace_id)?;
+
+    let adb_program = get_adb_program(&trace_id)?;
+    let model = match run_device_shell(
+        &adb_program,
+        &serial,
+        &["getprop", "ro.product.model"],
+        command_timeout(TimeoutKind::Probe, &trace_id),
+        &trace_id,
+    ) {
+        Ok(output) => Some(output.stdout.trim().to_string()).filter(|model| !model.is_empty()),
+        Err(err) => {
+            warn!(trace_id = %trace_id, serial = %serial, error = %err, "failed to read model for factory reset");
+            None
+        }
+    };
+    let token = issue_reset_token(&serial, &trace_id)?;
+    audit_factory_reset(&serial, RESET_STAGE_REQUESTED, None, Ok(()), &trace_id);
+    info!(trace_id = %trace_id, serial = %serial, "factory reset prepared");
+
+    Ok(CommandResponse {
+        trace_id,
+        data: FactoryResetChallenge {
+            serial,
+            model,
+            token,
+            expires_in_ms: FACTORY_RESET_TOKEN_TTL.as_millis() as u64,
+        },
+    })
+}
+
+/// Wipe user data through recovery for lab device recycling. With `provisioning_profile`, waits
+/// for the device to boot back up and applies that saved device profile; this only works on
+/// images that keep adb enabled across a wipe (emulators, lab userdebug builds).
+#[tauri::command(async)]
+pub fn factory_reset_device(
+    serial: String,
+    confirm_token: String,
+    provisioning_profile: Option<String>,
+    app: AppHandle,
+    state: State<'_, AppState>,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<FactoryResetReport>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+    ensure_non_empty(&confirm_token, "confirm_token", &trace_id)?;
+    let serial = serial.trim().to_string();
+    ensure_action_allowed("Factory resetting devices", &trace_id)?;
+    // Check the profile before wiping so a typo cannot leave a blank device behind.
+    let provisioning_profile = provisioning_profile
+        .filter(|name| !name.trim().is_empty())
+        .map(|name| -> Result<String, AppError> {
+            let name = device_profile_name(&name, &trace_id)?;
+            load_profile(&device_profiles_dir(), &name, &trace_id)?;
+            Ok(name)
+        })
+        .transpose()?;
+    redeem_reset_token(&serial, &confirm_token, &trace_id)?;
+    warn_on_reservation_conflicts(
+        &app,
+        std::slice::from_ref(&serial),
+        "Factory resetting a device",
+        &trace_id,
+    );
+
+    let adb_program = get_adb_program(&trace_id)?;
+    let device_lock = state.scheduler.device_lock(&serial);
+    let device_guard = device_lock.lock().map_err(|_| {
+        warn!(trace_id = %trace_id, serial = %serial, "device lock poisoned");
+        AppError::system("Failed to access the device. Please try again.", &trace_id)
+    })?;
+    warn!(trace_id = %trace_id, serial = %serial, "factory resetting device");
+    let mut failures = Vec::new();
+    let mut wipe_method = None;
+    for method in &WIPE_METHODS {
+        match start_wipe(&adb_program, &serial, method, &trace_id) {
+            Ok(()) => {
+                audit_factory_reset(
+                    &serial,
+                    RESET_STAGE_WIPE,
+                    Some(method.name),
+                    Ok(()),
+                    &trace_id,
+                );
+                wipe_method = Some(method.name);
+                break;
+            }
+            Err(message) => {
+                warn!(trace_id = %trace_id, serial = %serial, method = method.name, error = %message, "wipe method failed");
+                audit_factory_reset(
+                    &serial,
+                    RESET_STAGE_WIPE,
+                    Some(method.name),
+                    Err(&message),
+                    &trace_id,
+                );
+                failures.push(format!("{}: {message}", method.name));
+            }
+        }
+    }
+    let Some(wipe_method) = wipe_method else {
+        return Err(AppError::dependency(failures.join("; "), &trace_id));
+    };
+    info!(trace_id = %trace_id, serial = %serial, method = wipe_method, "factory reset started");
+    record_timeline_event(&serial, TIMELINE_REBOOT, "Factory reset started", &trace_id);
+    bust_read_cache(None, Some(&serial));
+    match state.app_lists.lock() {
+        Ok(mut snapshots) => {
+            snapshots.remove(&serial);
+        }
+        Err(_) => warn!(trace_id = %trace_id, "app list cache lock poisoned; entry not cleared"),
+    }
+
+    let mut report = FactoryResetReport {
+        serial: serial.clone(),
+        wipe_started: true,
+        wipe_method: wipe_method.to_string(),
+        boot_completed: None,
+        provisioning: None,
+    };
+    if let Some(profile) = provisioning_profile {
+        let booted = wait_for_boot_completed(
+            &app,
+            &adb_program,
+            &serial,
+            boot_wait_timeout(Some(FACTORY_RESET_BOOT_TIMEOUT_SECS)),
+            &trace_id,
+        )
+        .is_some();
+        drop(device_guard);
+        report.boot_completed = Some(booted);
+        if !booted {
+            audit_factory_reset(
+                &serial,
+                RESET_STAGE_BOOT,
+                None,
+                Err("Device did not come back with adb enabled"),
+                &trace_id,
+            );
+        } else {
+            audit_factory_reset(&serial, RESET_STAGE_BOOT, None, Ok(()), &trace_id);
+            match apply_device_profile(serial.clone(), profile, app, Some(trace_id.clone())) {
+                Ok(response) => {
+                    let applied = response.data;
+                    let outcome = if applied.failed == 0 {
+                        Ok(())
+                    } else {
+                        Err("Some provisioning steps failed")
+                    };
+                    audit_factory_reset(&serial, RESET_STAGE_PROVISION, None, outcome, &trace_id);
+                    report.provisioning = Some(applied);
+                }
+                Err(err) => {
+                    audit_factory_reset(
+                        &serial,
+                        RESET_STAGE_PROVISION,
+                        None,
+                        Err(&err.error),
+                        &trace_id,
+                    );
+                    return Err(err);
+                }
+            }
+        }
+    }
+
+    Ok(CommandResponse {
+        trace_id,
+        data: report,
+    })
+}
+
+/// Run one wipe method. A broadcast returns before anything happens and is dropped silently
+/// when the shell lacks `MASTER_CLEAR`, so it only counts once the device leaves adb.
+fn start_wipe(
+    adb_program: &str,
+    serial: &str,
+    method: &WipeMethod,
+    trace_id: &str,
+) -> Result<(), String> {
+    let mut args = vec!["-s".to_string(), serial.to_string(), "shell".to_string()];
+    args.extend(method.command.iter().map(|part| part.to_string()));
+    let output = run_command_with_timeout(
+        adb_program,
+        &args,
+        command_timeout(TimeoutKind::DeviceCommand, trace_id),
+        trace_id,
+    )
+    .map_err(|err| err.error)?;
+    wipe_started(method, &output)?;
+    if !method.is_broadcast() {
+        return Ok(());
+    }
+    let args = vec![
+        "-s".to_string(),
+        serial.to_string(),
+        "get-state".to_string(),
+    ];
+    let started = Instant::now();
+    loop {
+        match run_command_with_timeout(
+            adb_program,
+            &args,
+            command_timeout(TimeoutKind::Probe, trace_id),
+            trace_id,
+        ) {
+            Ok(output) if output.exit_code == Some(0) && output.stdout.trim() == "device" => {}
+            _ => return Ok(()),
+        }
+        if started.elapsed() >= BROADCAST_WIPE_REBOOT_WAIT {
+            return Err(format!(
+                "Broadcast sent but the device did not reboot within {}s",
+                BROADCAST_WIPE_REBOOT_WAIT.as_secs()
+            ));
+        }
+        std::thread::sleep(Duration::from_secs(1));
+    }
+}
+
+/// Factory reset audit trail, newest first.
+#[tauri::command(async)]
+pub fn get_factory_reset_audit(
+    serial: Option<String>,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<Vec<FactoryResetAuditEntry>>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    let serial = serial
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty());
+    let entries = load_reset_audit(&factory_reset_audit_path())
+        .into_iter()
+        .rev()
+        .filter(|entry| serial.as_ref().is_none_or(|serial| &entry.serial == serial))
+        .collect();
+
+    Ok(CommandResponse {
+        trace_id,
+        data: entries,
+    })
+}
+
+fn resolve_mock_location_package(
+    helper_package: Option<String>,
+    trace_id: &str,
+) -> Result<String, AppError> {
+    let value = helper_package
+        .filter(|value| !value.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_MOCK_LOCATION_PACKAGE.to_string());
+    validate_helper_package(&value).map_err(|message| AppError::validation(message, trace_id))
+}
+
+#[tauri::command(async)]
+pub fn set_mock_location(
+    serial: String,
+    latitude: f64,
+    longitude: f64,
+    accuracy: Option<f64>,
+    helper_package: Option<String>,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<MockLocationResult>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+    let coords = validate_mock_coordinates(latitude, longitude, accuracy)
+        .map_err(|message| AppError::validation(message, &trace_id))?;
+
+    let adb_program = get_adb_program(&trace_id)?;
+    if is_emulator_serial(&serial) {
+        let args = build_geo_fix_args(&serial, &coords);
+        let output = run_command_with_timeout(
+            &adb_program,
+            &args,
+            command_timeout(TimeoutKind::DeviceCommand, &trace_id),
+            &trace_id,
+        )?;
+        if output.exit_code.unwrap_or_default() != 0 || output.stdout.contains("KO") {
+            return Err(AppError::dependency(
+                Message::keyed("action_failed")
+                    .with("action", "geo fix")
+                    .with(
+                        "detail",
+                        format!("{}{}", output.stdout.trim(), output.stderr.trim()),
+                    ),
+                &trace_id,
+            ));
+        }
+        return Ok(CommandResponse {
+            trace_id,
+            data: MockLocationResult {
+                serial,
+                method: "emulator_geo_fix".to_string(),
+                helper_package: None,
+            },
+        });
+    }
+
+    let package = resolve_mock_location_package(helper_package, &trace_id)?;
+    let appops = build_mock_location_appops_args(&serial, &package, true);
+    let output = run_command_with_timeout(
+        &adb_program,
+        &appops,
+        command_timeout(TimeoutKind::DeviceCommand, &trace_id),
+        &trace_id,
+    )?;
+    if output.exit_code.unwrap_or_default() != 0 {
+        return Err(AppError::dependency(
+            Message::keyed("operation_failed")
+                .with(
+                    "action",
+                    format!("grant mock location to {package} (is the helper app installed?)"),
+                )
+                .with("detail", output.stderr.trim()),
+            &trace_id,
+        ));
+    }
+    let args = build_helper_location_args(&serial, &package, &coords);
+    let output = run_command_with_timeout(
+        &adb_program,
+        &args,
+        command_timeout(TimeoutKind::DeviceCommand, &trace_id),
+        &trace_id,
+    )?;
+    if output.exit_code.unwrap_or_default() != 0 || output.stdout.contains("Error:") {
+        return Err(AppError::dependency(
+            Message::keyed("start_failed")
+                .with("subject", "mock location service")
+                .with(
+                    "detail",
+                    format!("{}{}", output.stdout.trim(), output.stderr.trim()),
+                ),
+            &trace_id,
+        ));
+    }
+
+    Ok(CommandResponse {
+        trace_id,
+        data: MockLocationResult {
+            serial,
+            method: "helper_app".to_string(),
+            helper_package: Some(package),
+        },
+    })
+}
+
+#[tauri::command(async)]
+pub fn clear_mock_location(
+    serial: String,
+    helper_package: Option<String>,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<bool>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+
+    if is_emulator_serial(&serial) {
+        // The emulator keeps the last `geo fix`; there is no console command to revert it.
+        warn!(trace_id = %trace_id, serial = %serial, "emulator mock location cannot be cleared");
+        return Ok(CommandResponse {
+            trace_id,
+            data: false,
+        });
+    }
+
+    let adb_program = get_adb_program(&trace_id)?;
+    let package = resolve_mock_location_package(helper_package, &trace_id)?;
+    let stop = build_helper_stop_args(&serial, &package);
+    let output = run_command_with_timeout(
+        &adb_program,
+        &stop,
+        command_timeout(TimeoutKind::DeviceCommand, &trace_id),
+        &trace_id,
+    )?;
+    if output.exit_code.unwrap_or_default() != 0 {
+        warn!(trace_id = %trace_id, serial = %serial, stderr = %output.stderr.trim(), "failed to stop mock location service");
+    }
+    let appops = build_mock_location_appops_args(&serial, &package, false);
+    let output = run_command_with_timeout(
+        &adb_program,
+        &appops,
+        command_timeout(TimeoutKind::DeviceCommand, &trace_id),
+        &trace_id,
+    )?;
+    if output.exit_code.unwrap_or_default() != 0 {
+        return Err(AppError::dependency(
+            Message::keyed("operation_failed")
+                .with("action", "revoke mock location")
+                .with("detail", output.stderr.trim()),
+            &trace_id,
+        ));
+    }
+
+    Ok(CommandResponse {
+        trace_id,
+        data: true,
+    })
+}
+
+fn run_emulator_console(
+    adb_program: &str,
+    serial: &str,
+    console_args: &[&str],
+    timeout: Duration,
+    trace_id: &str,
+) -> Result<String, AppError> {
+    if !is_emulator_serial(serial) {
+        return Err(AppError::validation(
+            Message::keyed("snapshots_emulator_only"),
+            trace_id,
+        ));
+    }
+    let mut args = vec!["-s".to_string(), serial.to_string(), "emu".to_string()];
+    args.extend(console_args.iter().map(|value| value.to_string()));
+    let output = run_command_with_timeout(adb_program, &args, timeout, trace_id)?;
+    if output.exit_code.unwrap_or_default() != 0 {
+        return Err(AppError::dependency(
+            Message::keyed("action_failed")
+                .with("action", "Emulator console")
+                .with("detail", output.stderr.trim()),
+            trace_id,
+        ));
+    }
+    if let Some(reason) = emulator_console_error(&output.stdout) {
+        return Err(AppError::dependency(
+            Message::keyed("action_failed")
+                .with("action", "Emulator console")
+                .with("detail", &reason),
+            trace_id,
+        ));
+    }
+    Ok(output.stdout)
+}
+
+fn list_emulator_snapshots_inner(
+    adb_program: &str,
+    serial: &str,
+    trace_id: &str,
+) -> Result<Vec<EmulatorSnapshot>, AppError> {
+    let output = run_emulator_console(
+        adb_program,
+        serial,
+        &["avd", "snapshot", "list"],
+        command_timeout(TimeoutKind::DeviceCommand, trace_id),
+        trace_id,
+    )?;
+    Ok(parse_snapshot_list(&output))
+}
+
+fn run_device_root_shell(
+    adb_program: &str,
+    serial: &str,
+    command: &str,
+    timeout: Duration,
+    trace_id: &str,
+) -> Result<CommandOutput, AppError> {
+    // adb joins shell arguments with spaces, so the su payload must travel as one quoted word.
+    let args = vec![
+        "-s".to_string(),
+        serial.to_string(),
+        "shell".to_string(),
+        format!("su -c '{command}'"),
+    ];
+    let output = run_command_with_timeout(adb_program, &args, timeout, trace_id)?;
+    if output.exit_code.unwrap_or_default() != 0 {
+        return Err(AppError::dependency(
+            Message::keyed("root_shell_failed").with("detail", output.stderr.trim()),
+            trace_id,
+        ));
+    }
+    Ok(output)
+}
+
+/// Pids of processes named `name`. `pidof` exits 1 with no output when nothing matches; any
+/// other failure (device gone, timeout, no `pidof`) is an error rather than "not running".
+fn device_pidof(
+    adb_program: &str,
+    serial: &str,
+    name: &str,
+    trace_id: &str,
+) -> Result<Vec<u32>, AppError> {
+    let args = vec![
+        "-s".to_string(),
+        serial.to_string(),
+        "shell".to_string(),
+        "pidof".to_string(),
+        name.to_string(),
+    ];
+    let output = run_command_with_timeout(
+        adb_program,
+        &args,
+        command_timeout(TimeoutKind::Probe, trace_id),
+        trace_id,
+    )?;
+    match output.exit_code {
+        Some(0) => Ok(parse_pidof(&output.stdout)),
+        Some(1) if output.stdout.trim().is_empty() && output.stderr.trim().is_empty() => {
+            Ok(Vec::new())
+        }
+        exit_code => Err(AppError::dependency(
+            Message::keyed("action_failed")
+                .with("action", "pidof")
+                .with(
+                    "detail",
+                    format!("exit {exit_code:?}: {}", output.stderr.trim()),
+                ),
+            trace_id,
+        )),
+    }
+}
+
+fn debug_agent_status(
+    adb_program: &str,
+    serial: &str,
+    settings: &DebugAgentSettings,
+    trace_id: &str,
+) -> Result<DebugAgentStatus, AppError> {
+    let pids = device_pidof(
+        adb_program,
+        serial,
+        agent_process_name(&settings.remote_path),
+        trace_id,
+    )?;
+    let forward_args = vec!["forward".to_string(), "--list".to_string()];
+    let forward_output = run_command_with_timeout(
+        adb_program,
+        &forward_args,
+        command_timeout(TimeoutKind::DeviceCommand, trace_id),
+        trace_id,
+    )?;
+    Ok(DebugAgentStatus {
+        serial: serial.to_string(),
+        remote_path: settings.remote_path.clone(),
+        running: !pids.is_empty(),
+        pids,
+        device_port: settings.device_port,
+        local_port: settings.local_port,
+        forwarded: has_forward(
+            &forward_output.stdout,
+            serial,
+            settings.local_port,
+            settings.device_port,
+        ),
+    })
+}
+
+/// Push the configured debug agent binary (e.g. frida-server) and mark it executable.
+#[tauri::command(async)]
+pub fn push_debug_agent(
+    serial: String,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<DebugAgentStatus>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+    let settings = load_config(&trace_id)?.debug_agent;
+    if settings.local_path.is_empty() {
+        return Err(AppError::validation(
+            Message::keyed("debug_agent_path_missing"),
+            &trace_id,
+        ));
+    }
+    if !Path::new(&settings.local_path).is_file() {
+        return Err(AppError::validation(
+            Message::keyed("does_not_exist").with("subject", "Debug agent binary"),
+            &trace_id,
+        ));
+    }
+
+    let adb_program = get_adb_program(&trace_id)?;
+    let push_args = vec![
+        "-s".to_string(),
+        serial.clone(),
+        "push".to_string(),
+        settings.local_path.clone(),
+        settings.remote_path.clone(),
+    ];
+    let output = run_command_with_timeout(
+        &adb_program,
+        &push_args,
+        command_timeout(TimeoutKind::Transfer, &trace_id),
+        &trace_id,
+    )?;
+    if output.exit_code.unwrap_or_default() != 0 {
+        return Err(AppError::dependency(
+            Message::keyed("action_failed")
+                .with("action", "Push debug agent")
+                .with("detail", output.stderr.trim()),
+            &trace_id,
+        ));
+    }
+    run_device_shell(
+        &adb_program,
+        &serial,
+        &["chmod", "755", &settings.remote_path],
+        command_timeout(TimeoutKind::DeviceCommand, &trace_id),
+        &trace_id,
+    )?;
+    info!(trace_id = %trace_id, serial = %serial, remote_path = %settings.remote_path, "debug agent pushed");
+
+    let status = debug_agent_status(&adb_program, &serial, &settings, &trace_id)?;
+    Ok(CommandResponse {
+        trace_id,
+        data: status,
+    })
+}
+
+/// Start the debug agent as root and forward its port to the host.
+#[tauri::command(async)]
+pub fn start_debug_agent(
+    serial: String,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<DebugAgentStatus>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+    let settings = load_config(&trace_id)?.debug_agent;
+    let adb_program = get_adb_program(&trace_id)?;
+
+    if run_device_shell(
+        &adb_program,
+        &serial,
+        &["ls", &settings.remote_path],
+        command_timeout(TimeoutKind::DeviceCommand, &trace_id),
+        &trace_id,
+    )
+    .is_err()
+    {
+        return Err(AppError::validation(
+            Message::keyed("debug_agent_not_pushed"),
+            &trace_id,
+        ));
+    }
+
+    let status = debug_agent_status(&adb_program, &serial, &settings, &trace_id)?;
+    if !status.running {
+        run_device_root_shell(
+            &adb_program,
+            &serial,
+            &build_agent_start_command(&settings.remote_path, settings.device_port),
+            command_timeout(TimeoutKind::DeviceCommand, &trace_id),
+            &trace_id,
+        )?;
+        std::thread::sleep(Duration::from_millis(500));
+    }
+
+    let forward_output = run_command_with_timeout(
+        &adb_program,
+        &build_forward_args(&serial, settings.local_port, settings.device_port),
+        command_timeout(TimeoutKind::DeviceCommand, &trace_id),
+        &trace_id,
+    )?;
+    if forward_output.exit_code.unwrap_or_default() != 0 {
+        return Err(AppError::dependency(
+            Message::keyed("action_failed")
+                .with("action", "adb forward")
+                .with("detail", forward_output.stderr.trim()),
+            &trace_id,
+        ));
+    }
+
+    let status = debug_agent_status(&adb_program, &serial, &settings, &trace_id)?;
+    if !status.running {
+        return Err(AppError::dependency(
+            Message::keyed("debug_agent_exited"),
+            &trace_id,
+        ));
+    }
+    info!(trace_id = %trace_id, serial = %serial, pids = ?status.pids, "debug agent started");
+    Ok(CommandResponse {
+        trace_id,
+        data: status,
+    })
+}
+
+#[tauri::command(async)]
+pub fn stop_debug_agent(
+    serial: String,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<DebugAgentStatus>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+    let settings = load_config(&trace_id)?.debug_agent;
+    let adb_program = get_adb_program(&trace_id)?;
+
+    let status = debug_agent_status(&adb_program, &serial, &settings, &trace_id)?;
+    if status.running {
+        let pids = status
+            .pids
+            .iter()
+            .map(u32::to_string)
+            .collect::<Vec<_>>()
+            .join(" ");
+        run_device_root_shell(
+            &adb_program,
+            &serial,
+            &format!("kill {pids}"),
+            command_timeout(TimeoutKind::DeviceCommand, &trace_id),
+            &trace_id,
+        )?;
+    }
+    if status.forwarded {
+        let output = run_command_with_timeout(
+            &adb_program,
+            &build_forward_remove_args(&serial, settings.local_port),
+            command_timeout(TimeoutKind::DeviceCommand, &trace_id),
+            &trace_id,
+        )?;
+        if output.exit_code.unwrap_or_default() != 0 {
+            warn!(trace_id = %trace_id, serial = %serial, error = %output.stderr.trim(), "failed to remove debug agent forward");
+        }
+    }
+
+    let status = debug_agent_status(&adb_program, &serial, &settings, &trace_id)?;
+    info!(trace_id = %trace_id, serial = %serial, running = status.running, "debug agent stopped");
+    Ok(CommandResponse {
+        trace_id,
+        data: status,
+    })
+}
+
+#[tauri::command(async)]
+pub fn get_debug_agent_status(
+    serial: String,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<DebugAgentStatus>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+    let settings = load_config(&trace_id)?.debug_agent;
+    let adb_program = get_adb_program(&trace_id)?;
+    let status = debug_agent_status(&adb_program, &serial, &settings, &trace_id)?;
+    Ok(CommandResponse {
+        trace_id,
+        data: status,
+    })
+}
+
+fn list_adb_forwards(adb_program: &str, trace_id: &str) -> Result<String, AppError> {
+    let args = vec!["forward".to_string(), "--list".to_string()];
+    Ok(run_command_with_timeout(
+        adb_program,
+        &args,
+        command_timeout(TimeoutKind::DeviceCommand, trace_id),
+        trace_id,
+    )?
+    .stdout)
+}
+
+fn forward_devtools_socket(
+    adb_program: &str,
+    serial: &str,
+    socket: &str,
+    trace_id: &str,
+) -> Result<u16, String> {
+    let output = run_command_with_timeout(
+        adb_program,
+        &build_devtools_forward_args(serial, socket),
+        command_timeout(TimeoutKind::DeviceCommand, trace_id),
+        trace_id,
+    )
+    .map_err(|err| err.error)?;
+    if output.exit_code.unwrap_or_default() != 0 {
+        return Err(format!("adb forward failed: {}", output.stderr.trim()));
+    }
+    parse_forward_port(&output.stdout)
+        .ok_or_else(|| format!("adb forward returned no port: {}", output.stdout.trim()))
+}
+
+fn remove_devtools_forward(adb_program: &str, serial: &str, local_port: u16, trace_id: &str) {
+    let result = run_command_with_timeout(
+        adb_program,
+        &build_devtools_forward_remove_args(serial, local_port),
+        command_timeout(TimeoutKind::DeviceCommand, trace_id),
+        trace_id,
+    );
+    match result {
+        Ok(output) if output.exit_code.unwrap_or_default() == 0 => {}
+        Ok(output) => {
+            warn!(trace_id = %trace_id, serial = %serial, local_port, error = %output.stderr.trim(), "failed to remove devtools forward");
+        }
+        Err(err) => {
+            warn!(trace_id = %trace_id, serial = %serial, local_port, error = %err, "failed to remove devtools forward");
+        }
+    }
+}
+
+fn devtools_socket_package(
+    adb_program: &str,
+    serial: &str,
+    socket: &str,
+    pid: Option<u32>,
+    trace_id: &str,
+) -> Option<String> {
+    if let Some(package) = socket_package(socket) {
+        return Some(package);
+    }
+    let pid = pid?;
+    let cmdline = format!("/proc/{pid}/cmdline");
+    match run_device_shell(
+        adb_program,
+        serial,
+        &["cat", &cmdline],
+        command_timeout(TimeoutKind::Probe, trace_id),
+        trace_id,
+    ) {
+        Ok(output) => parse_cmdline_process(&output.stdout),
+        Err(err) => {
+            warn!(trace_id = %trace_id, serial = %serial, pid, error = %err, "failed to resolve webview process");
+            None
+        }
+    }
+}
+
+/// Lists debuggable Chrome tabs and WebViews. Each DevTools socket gets a local forward
+/// (reused when one exists); forwards to sockets that are gone are removed.
+#[tauri::command(async)]
+pub fn list_devtools_targets(
+    serial: String,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<Vec<DevtoolsSocket>>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+    let adb_program = get_adb_program(&trace_id)?;
+
+    let unix = run_device_shell(
+        &adb_program,
+        &serial,
+        &["cat", "/proc/net/unix"],
+        command_timeout(TimeoutKind::DeviceCommand, &trace_id),
+        &trace_id,
+    )?;
+    let sockets = parse_devtools_sockets(&unix.stdout);
+    let existing = devtools_forwards(&list_adb_forwards(&adb_program, &trace_id)?, &serial);
+    for (port, socket) in &existing {
+        if !sockets.contains(socket) {
+            remove_devtools_forward(&adb_program, &serial, *port, &trace_id);
+        }
+    }
+
+    let mut results = Vec::with_capacity(sockets.len());
+    for socket in sockets {
+        let pid = socket_pid(&socket);
+        let package_name = devtools_socket_package(&adb_program, &serial, &socket, pid, &trace_id);
+        let port = match existing.iter().find(|(_, known)| *known == socket) {
+            Some((port, _)) => Ok(*port),
+            None => forward_devtools_socket(&adb_program, &serial, &socket, &trace_id),
+        };
+        let targets = port.and_then(|port| {
+            let body = http_get_local(
+                port,
+                "/json/list",
+                command_timeout(TimeoutKind::Probe, &trace_id),
+            )?;
+            parse_devtools_targets(&body, &socket, port).map(|targets| (port, targets))
+        });
+        let entry = match targets {
+            Ok((port, targets)) => DevtoolsSocket {
+                socket,
+                package_name,
+                pid,
+                local_port: Some(port),
+                targets,
+                error: None,
+            },
+            Err(error) => {
+                warn!(trace_id = %trace_id, serial = %serial, socket = %socket, error = %error, "failed to query devtools socket");
+                DevtoolsSocket {
+                    socket,
+                    package_name,
+                    pid,
+                    local_port: None,
+                    targets: Vec::new(),
+                    error: Some(error),
+                }
+            }
+        };
+        results.push(entry);
+    }
+
+    Ok(CommandResponse {
+        trace_id,
+        data: results,
+    })
+}
+
+/// Removes every DevTools forward for `serial`; returns how many were removed.
+#[tauri::command(async)]
+pub fn remove_devtools_forwards(
+    serial: String,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<usize>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+    let adb_program = get_adb_program(&trace_id)?;
+
+    let forwards = devtools_forwards(&list_adb_forwards(&adb_program, &trace_id)?, &serial);
+    for (port, _) in &forwards {
+        remove_devtools_forward(&adb_program, &serial, *port, &trace_id);
+    }
+
+    Ok(CommandResponse {
+        trace_id,
+        data: forwards.len(),
+    })
+}
+
+fn shaping_interface(adb_program: &str, serial: &str, trace_id: &str) -> String {
+    match run_device_shell(
+        adb_program,
+        serial,
+        &["ip", "route", "get", "1.1.1.1"],
+        command_timeout(TimeoutKind::Probe, trace_id),
+        trace_id,
+    ) {
+        Ok(output) => parse_route_interface(&output.stdout)
+            .unwrap_or_else(|| DEFAULT_SHAPING_INTERFACE.to_string()),
+        Err(err) => {
+            warn!(trace_id = %trace_id, serial = %serial, error = %err, "failed to detect the default route interface");
+            DEFAULT_SHAPING_INTERFACE.to_string()
+        }
+    }
+}
+
+/// Throttles the device network: the emulator console for emulators (speed and delay),
+/// otherwise `tc netem` on the default-route interface, which needs root. `rate_kbps` 0
+/// leaves bandwidth unlimited.
+#[tauri::command(async)]
+pub fn set_network_shaping(
+    serial: String,
+    rate_kbps: u32,
+    latency_ms: u32,
+    loss_pct: f64,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<NetworkShapingResult>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+    let params = validate_shaping(ShapingParams {
+        rate_kbps,
+        latency_ms,
+        loss_pct,
+    })
+    .map_err(|message| AppError::validation(message, &trace_id))?;
+    let adb_program = get_adb_program(&trace_id)?;
+
+    let (method, interface) = if use_emulator_console(is_emulator_serial(&serial), &params) {
+        let speed = emulator_speed_arg(params.rate_kbps);
+        let delay = emulator_delay_arg(params.latency_ms);
+        run_emulator_console(
+            &adb_program,
+            &serial,
+            &["network", "speed", &speed],
+            command_timeout(TimeoutKind::DeviceCommand, &trace_id),
+            &trace_id,
+        )?;
+        run_emulator_console(
+            &adb_program,
+            &serial,
+            &["network", "delay", &delay],
+            command_timeout(TimeoutKind::DeviceCommand, &trace_id),
+            &trace_id,
+        )?;
+        (SHAPING_EMULATOR_CONSOLE, None)
+    } else {
+        let interface = shaping_interface(&adb_program, &serial, &trace_id);
+        run_device_root_shell(
+            &adb_program,
+            &serial,
+            &build_netem_command(&interface, &params),
+            command_timeout(TimeoutKind::DeviceCommand, &trace_id),
+            &trace_id,
+        )?;
+        (SHAPING_TC, Some(interface))
+    };
+    info!(trace_id = %trace_id, serial = %serial, method, rate_kbps, latency_ms, loss_pct, "network shaping applied");
+
+    Ok(CommandResponse {
+        trace_id,
+        data: NetworkShapingResult {
+            serial,
+            method: method.to_string(),
+            interface,
+            rate_kbps: params.rate_kbps,
+            latency_ms: params.latency_ms,
+            loss_pct: params.loss_pct,
+            active: true,
+        },
+    })
+}
+
+/// Removes shaping applied by `set_network_shaping`. Emulators get their console speed and
+/// delay reset; any `tc` qdisc on the default-route interface is deleted where root allows.
+#[tauri::command(async)]
+pub fn clear_network_shaping(
+    serial: String,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<NetworkShapingResult>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+    let adb_program = get_adb_program(&trace_id)?;
+    let is_emulator = is_emulator_serial(&serial);
+
+    if is_emulator {
+        run_emulator_console(
+            &adb_program,
+            &serial,
+            &["network", "speed", &emulator_speed_arg(0)],
+            command_timeout(TimeoutKind::DeviceCommand, &trace_id),
+            &trace_id,
+        )?;
+        run_emulator_console(
+            &adb_program,
+            &serial,
+            &["network", "delay", &emulator_delay_arg(0)],
+            command_timeout(TimeoutKind::DeviceCommand, &trace_id),
+            &trace_id,
+        )?;
+    }
+    let interface = shaping_interface(&adb_program, &serial, &trace_id);
+    match run_device_root_shell(
+        &adb_program,
+        &serial,
+        &build_netem_clear_command(&interface),
+        command_timeout(TimeoutKind::DeviceCommand, &trace_id),
+        &trace_id,
+    ) {
+        Ok(_) => {}
+        Err(err) if is_missing_qdisc_error(&err.error) => {}
+        // Console shaping is already reset; unrooted emulator images cannot run tc.
+        Err(err) if is_emulator => {
+            warn!(trace_id = %trace_id, serial = %serial, error = %err, "failed to clear tc shaping on emulator");
+        }
+        Err(err) => return Err(err),
+    }
+    info!(trace_id = %trace_id, serial = %serial, "network shaping cleared");
+
+    Ok(CommandResponse {
+        trace_id,
+        data: NetworkShapingResult {
+            serial,
+            method: if is_emulator {
+                SHAPING_EMULATOR_CONSOLE
+            } else {
+                SHAPING_TC
+            }
+            .to_string(),
+            interface: Some(interface),
+            rate_kbps: 0,
+            latency_ms: 0,
+            loss_pct: 0.0,
+            active: false,
+        },
+    })
+}
+
+#[tauri::command(async)]
+pub fn list_emulator_snapshots(
+    serial: String,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<Vec<EmulatorSnapshot>>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+
+    let adb_program = get_adb_program(&trace_id)?;
+    let snapshots = list_emulator_snapshots_inner(&adb_program, &serial, &trace_id)?;
+
+    Ok(CommandResponse {
+        trace_id,
+        data: snapshots,
+    })
+}
+
+#[tauri::command(async)]
+pub fn save_emulator_snapshot(
+    serial: String,
+    name: String,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<Vec<EmulatorSnapshot>>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+    let name = validate_snapshot_name(&name)
+        .map_err(|message| AppError::validation(message, &trace_id))?;
+
+    let adb_program = get_adb_program(&trace_id)?;
+    run_emulator_console(
+        &adb_program,
+        &serial,
+        &["avd", "snapshot", "save", &name],
+        command_timeout(TimeoutKind::Install, &trace_id),
+        &trace_id,
+    )?;
+    let snapshots = list_emulator_snapshots_inner(&adb_program, &serial, &trace_id)?;
+
+    Ok(CommandResponse {
+        trace_id,
+        data: snapshots,
+    })
+}
+
+#[tauri::command(async)]
+pub fn load_emulator_snapshot(
+    serial: String,
+    name: String,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<bool>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+    let name = validate_snapshot_name(&name)
+        .map_err(|message| AppError::validation(message, &trace_id))?;
+
+    let adb_program = get_adb_program(&trace_id)?;
+    run_emulator_console(
+        &adb_program,
+        &serial,
+        &["avd", "snapshot", "load", &name],
+        command_timeout(TimeoutKind::Install, &trace_id),
+        &trace_id,
+    )?;
+
+    Ok(CommandResponse {
+        trace_id,
+        data: true,
+    })
+}
+
+#[tauri::command(async)]
+pub fn set_wifi_state(
+    serials: Vec<String>,
+    enable: bool,
+    app: AppHandle,
+    state: State<'_, AppState>,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<Vec<CommandResult>>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    if serials.is_empty() {
+        return Err(AppError::validation(
+            Message::keyed("required").with("field", "serials"),
+            &trace_id,
+        ));
+    }
+    warn_on_reservation_conflicts(&app, &serials, "Changing Wi-Fi state", &trace_id);
+
+    let adb_program = get_adb_program(&trace_id)?;
+    let scheduler = Arc::clone(&state.scheduler);
+
+    let mut handles = Vec::new();
+    for (index, serial) in serials.into_iter().enumerate() {
+        ensure_non_empty(&serial, "serial", &trace_id)?;
+        let scheduler_clone = Arc::clone(&scheduler);
+        let trace_clone = trace_id.clone();
+        let adb_program_clone = adb_program.clone();
+        handles.push(std::thread::spawn(move || -> Result<_, AppError> {
+            let _permit = scheduler_clone.acquire_global();
+            let device_lock = scheduler_clone.device_lock(&serial);
+            let _device_guard = device_lock.lock().map_err(|_| {
+                warn!(trace_id = %trace_clone, serial = %serial, "device lock poisoned");
+                AppError::system(
+                    "Failed to access the device. Please try again.",
+                    &trace_clone,
+                )
+            })?;
+
+            let args = vec![
+                "-s".to_string(),
+                serial.clone(),
+                "shell".to_string(),
+                "svc".to_string(),
+                "wifi".to_string(),
+                if enable { "enable" } else { "disable" }.to_string(),
+            ];
+            let output = run_command_with_timeout(
+                &adb_program_clone,
+                &args,
+                command_timeout(TimeoutKind::DeviceCommand, &trace_clone),
+                &trace_clone,
+            )?;
+            Ok((
+                index,
+                CommandResult {
+                    serial,
+                    stdout: output.stdout,
+                    stderr: output.stderr,
+                    exit_code: output.exit_code,
+                },
+            ))
+        }));
+    }
+
+    let mut collected = Vec::new();
+    for handle in handles {
+        let (index, result) = handle
+            .join()
+            .map_err(|_| AppError::system("WiFi toggle thread panicked", &trace_id))??;
+        collected.push((index, result));
+    }
+    collected.sort_by_key(|item| item.0);
+    let results = collected.into_iter().map(|item| item.1).collect();
+
+    Ok(CommandResponse {
+        trace_id,
+        data: results,
+    })
+}
+
+/// Pseudo-locale and/or force-RTL change requested for a device wall sweep.
+#[derive(Clone)]
+struct I18nChange {
+    locale: Option<String>,
+    force_rtl: Option<bool>,
+    restart_ui: bool,
+}
+
+fn read_device_property(adb_program: &str, serial: &str, key: &str, trace_id: &str) -> String {
+    match run_device_shell(
+        adb_program,
+        serial,
+        &["getprop", key],
+        command_timeout(TimeoutKind::DeviceCommand, trace_id),
+        trace_id,
+    ) {
+        Ok(output) => output.stdout.trim().to_string(),
+        Err(err) => {
+            warn!(trace_id = %trace_id, serial = %serial, key = %key, error = %err.error, "failed to read device property");
+            String::new()
+        }
+    }
+}
+
+fn read_i18n_settings(
+    adb_program: &str,
+    serial: &str,
+    trace_id: &str,
+) -> Result<I18nRestorePoint, AppError> {
+    let persist_locale = read_device_property(adb_program, serial, LOCALE_PROPERTY, trace_id);
+    let product_locale =
+        read_device_property(adb_program, serial, PRODUCT_LOCALE_PROPERTY, trace_id);
+    let force_rtl = run_device_shell(
+        adb_program,
+        serial,
+        &["settings", "get", "global", FORCE_RTL_KEY],
+        command_timeout(TimeoutKind::DeviceCommand, trace_id),
+        trace_id,
+    )?;
+    Ok(I18nRestorePoint {
+        locale: original_locale(&persist_locale, &product_locale),
+        force_rtl: parse_force_rtl(&force_rtl.stdout),
+        saved_at: Utc::now().to_rfc3339(),
+    })
+}
+
+/// Applies `change` and verifies the locale took. Shell can only set `persist.sys.locale` on
+/// emulators and userdebug builds, and running apps only see it after a UI restart.
+fn apply_i18n_change(
+    adb_program: &str,
+    serial: &str,
+    change: &I18nChange,
+    trace_id: &str,
+) -> Result<I18nToggleResult, AppError> {
+    if let Some(locale) = &change.locale {
+        run_device_shell(
+            adb_program,
+            serial,
+            &["setprop", LOCALE_PROPERTY, locale],
+            command_timeout(TimeoutKind::DeviceCommand, trace_id),
+            trace_id,
+        )?;
+        let applied = read_device_property(adb_program, serial, LOCALE_PROPERTY, trace_id);
+        if &applied != locale {
+            return Err(AppError::dependency(
+                Message::keyed("locale_change_rejected"),
+                trace_id,
+            ));
+        }
+    }
+    if let Some(enable) = change.force_rtl {
+        for command in force_rtl_shell_commands(enable) {
+            let parts: Vec<&str> = command.iter().map(String::as_str).collect();
+            run_device_shell(
+                adb_program,
+                serial,
+                &parts,
+                command_timeout(TimeoutKind::DeviceCommand, trace_id),
+                trace_id,
+            )?;
+        }
+    }
+
+    let mut restart_required = change.locale.is_some() || change.force_rtl.is_some();
+    if restart_required && change.restart_ui {
+        match run_device_root_shell(
+            adb_program,
+            serial,
+            RESTART_UI_COMMAND,
+            command_timeout(TimeoutKind::DeviceCommand, trace_id),
+            trace_id,
+        ) {
+            Ok(_) => restart_required = false,
+            Err(err) => {
+                warn!(trace_id = %trace_id, serial = %serial, error = %err.error, "failed to restart the device UI after an i18n toggle");
+            }
+        }
+    }
+    Ok(I18nToggleResult {
+        serial: serial.to_string(),
+        success: true,
+        locale: change.locale.clone(),
+        force_rtl: change.force_rtl,
+        restart_required,
+        error: None,
+    })
+}
+
+fn toggle_i18n_on_device(
+    adb_program: &str,
+    serial: &str,
+    change: Option<&I18nChange>,
+    restart_ui: bool,
+    trace_id: &str,
+) -> I18nToggleResult {
+    let store = i18n_restore_path();
+    let outcome = match change {
+        Some(change) => read_i18n_settings(adb_program, serial, trace_id)
+            .and_then(|original| save_restore_point_if_absent(&store, serial, &original, trace_id))
+            .and_then(|_| apply_i18n_change(adb_program, serial, change, trace_id)),
+        None => match load_restore_points(&store).remove(serial) {
+            Some(original) => apply_i18n_change(
+                adb_program,
+                serial,
+                &I18nChange {
+                    locale: Some(original.locale),
+                    force_rtl: Some(original.force_rtl),
+                    restart_ui,
+                },
+                trace_id,
+            )
+            .and_then(|result| {
+                remove_restore_point(&store, serial, trace_id)?;
+                Ok(result)
+            }),
+            None => Err(AppError::validation(
+                Message::keyed("no_saved_i18n_settings"),
+                trace_id,
+            )),
+        },
+    };
+    outcome.unwrap_or_else(|err| {
+        warn!(trace_id = %trace_id, serial = %serial, error = %err.error, "i18n toggle failed");
+        I18nToggleResult {
+            serial: serial.to_string(),
+            success: false,
+            locale: None,
+            force_rtl: None,
+            restart_required: false,
+            error: Some(err.error),
+        }
+    })
+}
+
+fn run_i18n_toggles(
+    serials: Vec<String>,
+    change: Option<I18nChange>,
+    restart_ui: bool,
+    state: &AppState,
+    trace_id: &str,
+) -> Result<Vec<I18nToggleResult>, AppError> {
+    if serials.is_empty() {
+        return Err(AppError::validation(
+            Message::keyed("required").with("field", "serials"),
+            trace_id,
+        ));
+    }
+    let adb_program = get_adb_program(trace_id)?;
+    let scheduler = Arc::clone(&state.scheduler);
+
+    let mut handles = Vec::new();
+    for (index, serial) in serials.into_iter().enumerate() {
+        ensure_non_empty(&serial, "serial", trace_id)?;
+        let scheduler_clone = Arc::clone(&scheduler);
+        let trace_clone = trace_id.to_string();
+        let adb_program_clone = adb_program.clone();
+        let change_clone = change.clone();
+        handles.push(std::thread::spawn(move || -> Result<_, AppError> {
+            let _permit = scheduler_clone.acquire_global();
+            let device_lock = scheduler_clone.device_lock(&serial);
+            let _device_guard = device_lock.lock().map_err(|_| {
+                warn!(trace_id = %trace_clone, serial = %serial, "device lock poisoned");
+                AppError::system(
+                    "Failed to access the device. Please try again.",
+                    &trace_clone,
+                )
+            })?;
+
+            let result = toggle_i18n_on_device(
+                &adb_program_clone,
+                &serial,
+                change_clone.as_ref(),
+                restart_ui,
+                &trace_clone,
+            );
+            Ok((index, result))
+        }));
+    }
+
+    let mut collected = Vec::new();
+    for handle in handles {
+        let (index, result) = handle
+            .join()
+            .map_err(|_| AppError::system("i18n toggle thread panicked", trace_id))??;
+        collected.push((index, result));
+    }
+    collected.sort_by_key(|item| item.0);
+    Ok(collected.into_iter().map(|item| item.1).collect())
+}
+
+/// Switch selected devices to a pseudo-locale (`en-XA`, `ar-XB`) and/or force RTL layout.
+/// The settings in place before the first toggle are kept for `restore_i18n_toggles`.
+/// `restart_ui` restarts zygote through root so the change shows without a reboot.
+#[tauri::command(async)]
+pub fn set_i18n_toggles(
+    serials: Vec<String>,
+    pseudo_locale: Option<String>,
+    force_rtl: Option<bool>,
+    restart_ui: Option<bool>,
+    state: State<'_, AppState>,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<Vec<I18nToggleResult>>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    let locale = pseudo_locale
+        .filter(|value| !value.trim().is_empty())
+        .map(|value| normalize_pseudo_locale(&value))
+        .transpose()
+        .map_err(|err| AppError::validation(err, &trace_id))?;
+    if locale.is_none() && force_rtl.is_none() {
+        return Err(AppError::validation(
+            Message::keyed("required").with("field", "pseudo_locale or force_rtl"),
+            &trace_id,
+        ));
+    }
+    let restart_ui = restart_ui.unwrap_or(false);
+    let change = I18nChange {
+        locale,
+        force_rtl,
+        restart_ui,
+    };
+    let results = run_i18n_toggles(serials, Some(change), restart_ui, &state, &trace_id)?;
+    info!(
+        trace_id = %trace_id,
+        total = results.len(),
+        succeeded = results.iter().filter(|item| item.success).count(),
+        "i18n toggles applied"
+    );
+
+    Ok(CommandResponse {
+        trace_id,
+        data: results,
+    })
+}
+
+/// Put back the locale and force-RTL setting saved before the first `set_i18n_toggles`.
+#[tauri::command(async)]
+pub fn restore_i18n_toggles(
+    serials: Vec<String>,
+    restart_ui: Option<bool>,
+    state: State<'_, AppState>,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<Vec<I18nToggleResult>>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    let results = run_i18n_toggles(
+        serials,
+        None,
+        restart_ui.unwrap_or(false),
+        &state,
+        &trace_id,
+    )?;
+    info!(
+        trace_id = %trace_id,
+        total = results.len(),
+        succeeded = results.iter().filter(|item| item.success).count(),
+        "i18n toggles restored"
+    );
+
+    Ok(CommandResponse {
+        trace_id,
+        data: results,
+    })
+}
+
+fn read_global_proxy(
+    adb_program: &str,
+    serial: &str,
+    trace_id: &str,
+) -> Result<ProxyStatus, AppError> {
+    let output = run_device_shell(
+        adb_program,
+        serial,
+        &["settings", "get", "global", "http_proxy"],
+        command_timeout(TimeoutKind::DeviceCommand, trace_id),
+        trace_id,
+    )?;
+    Ok(ProxyStatus {
+        serial: serial.to_string(),
+        proxy: parse_proxy_setting(&output.stdout),
+    })
+}
+
+#[tauri::command(async)]
+pub fn get_global_proxy(
+    serial: String,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<ProxyStatus>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+    let adb_program = get_adb_program(&trace_id)?;
+    let status = read_global_proxy(&adb_program, &serial, &trace_id)?;
+    Ok(CommandResponse {
+        trace_id,
+        data: status,
+    })
+}
+
+fn read_usb_functions(
+    adb_program: &str,
+    serial: &str,
+    prefix: &[&str],
+    trace_id: &str,
+) -> Result<UsbFunctionState, AppError> {
+    let mut args = vec!["-s".to_string(), serial.to_string()];
+    args.extend(prefix.iter().map(|arg| arg.to_string()));
+    args.extend(["shell".to_string(), USB_FUNCTIONS_SCRIPT.to_string()]);
+    let output = run_command_with_timeout(
+        adb_program,
+        &args,
+        command_timeout(TimeoutKind::DeviceCommand, trace_id),
+        trace_id,
+    )?;
+    if output.exit_code != Some(0) {
+        return Err(AppError::dependency(
+            Message::keyed("operation_failed")
+                .with("action", "read USB functions")
+                .with("detail", output.stderr.trim()),
+            trace_id,
+        ));
+    }
+    Ok(parse_usb_functions(serial, &output.stdout))
+}
+
+#[tauri::command(async)]
+pub fn get_usb_functions(
+    serial: String,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<UsbFunctionState>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+    let adb_program = get_adb_program(&trace_id)?;
+    let status = read_usb_functions(&adb_program, &serial, &[], &trace_id)?;
+    Ok(CommandResponse {
+        trace_id,
+        data: status,
+    })
+}
+
+/// Switches the USB port to file transfer (`mtp`), photo transfer (`ptp`), tethering
+/// (`rndis`/`ncm`), MIDI or charging only (`none`). The port re-enumerates, so a USB adb
+/// connection drops briefly; the new state is read once the device is back.
+#[tauri::command(async)]
+pub fn set_usb_function(
+    serial: String,
+    function: String,
+    app: AppHandle,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<UsbFunctionState>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+    let function = normalize_usb_function(&function)
+        .map_err(|message| AppError::validation(message, &trace_id))?;
+    ensure_action_allowed("Changing the USB function", &trace_id)?;
+    warn_on_reservation_conflicts(
+        &app,
+        std::slice::from_ref(&serial),
+        "Changing the USB function",
+        &trace_id,
+    );
+    let adb_program = get_adb_program(&trace_id)?;
+
+    run_device_shell(
+        &adb_program,
+        &serial,
+        &set_functions_args(function),
+        command_timeout(TimeoutKind::DeviceCommand, &trace_id),
+        &trace_id,
+    )?;
+    let status = read_usb_functions(&adb_program, &serial, &["wait-for-device"], &trace_id)?;
+    if status.current.as_deref() != Some(function) {
+        warn!(trace_id = %trace_id, serial = %serial, requested = function, current = ?status.current, "USB function not applied yet");
+    }
+    info!(trace_id = %trace_id, serial = %serial, function, "USB function set");
+    Ok(CommandResponse {
+        trace_id,
+        data: status,
+    })
+}
+
+/// Routes the device's HTTP traffic through `address` (`host:port`), e.g. Charles or
+/// mitmproxy on the host. Apps that ignore the system proxy are unaffected.
+#[tauri::command(async)]
+pub fn set_global_proxy(
+    serial: String,
+    address: String,
+    app: AppHandle,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<ProxyStatus>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+    let address = validate_proxy_address(&address)
+        .map_err(|message| AppError::validation(message, &trace_id))?;
+    ensure_action_allowed("Changing the device proxy", &trace_id)?;
+    warn_on_reservation_conflicts(
+        &app,
+        std::slice::from_ref(&serial),
+        "Changing the device proxy",
+        &trace_id,
+    );
+    let adb_program = get_adb_program(&trace_id)?;
+
+    run_device_shell(
+        &adb_program,
+        &serial,
+        &["settings", "put", "global", "http_proxy", &address],
+        command_timeout(TimeoutKind::DeviceCommand, &trace_id),
+        &trace_id,
+    )?;
+    let status = read_global_proxy(&adb_program, &serial, &trace_id)?;
+    info!(trace_id = %trace_id, serial = %serial, proxy = ?status.proxy, "global proxy set");
+    Ok(CommandResponse {
+        trace_id,
+        data: status,
+    })
+}
+
+#[tauri::command(async)]
+pub fn clear_global_proxy(
+    serial: String,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<ProxyStatus>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+    let adb_program = get_adb_program(&trace_id)?;
+
+    run_device_shell(
+        &adb_program,
+        &serial,
+        &["settings", "put", "global", "http_proxy", CLEAR_PROXY_VALUE],
+        command_timeout(TimeoutKind::DeviceCommand, &trace_id),
+        &trace_id,
+    )?;
+    let status = read_global_proxy(&adb_program, &serial, &trace_id)?;
+    info!(trace_id = %trace_id, serial = %serial, "global proxy cleared");
+    Ok(CommandResponse {
+        trace_id,
+        data: status,
+    })
+}
+
+/// Pushes a CA certificate to the device's Download folder and, where the platform still
+/// allows it (before Android 11), opens the certificate installer. The install itself is
+/// always confirmed on the device.
+#[tauri::command(async)]
+pub fn install_ca_certificate(
+    serial: String,
+    cert_path: String,
+    app: AppHandle,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<CaCertificateInstall>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+    ensure_non_empty(&cert_path, "cert_path", &trace_id)?;
+    let path = PathBuf::from(cert_path.trim());
+    let mut head = Vec::with_capacity(64);
+    let size = fs::File::open(&path)
+        .and_then(|file| {
+            let size = file.metadata()?.len();
+            file.take(64).read_to_end(&mut head)?;
+            Ok(size)
+        })
+        .map_err(|err| {
+            AppError::validation(
+                Message::keyed("operation_failed")
+                    .with("action", "read CA certificate")
+                    .with("detail", &err),
+                &trace_id,
+            )
+        })?;
+    validate_ca_certificate(&path, size, &head)
+        .map_err(|message| AppError::validation(message, &trace_id))?;
+    ensure_action_allowed("Installing CA certificates", &trace_id)?;
+    warn_on_reservation_conflicts(
+        &app,
+        std::slice::from_ref(&serial),
+        "Installing CA certificates",
+        &trace_id,
+    );
+    let adb_program = get_adb_program(&trace_id)?;
+
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| "ca.pem".to_string());
+    let device_path = ca_device_path(&file_name);
+    let push_args = vec![
+        "-s".to_string(),
+        serial.clone(),
+        "push".to_string(),
+        path.to_string_lossy().to_string(),
+        device_path.clone(),
+    ];
+    let output = run_command_with_timeout(
+        &adb_program,
+        &push_args,
+        command_timeout(TimeoutKind::Transfer, &trace_id),
+        &trace_id,
+    )?;
+    if output.exit_code.unwrap_or_default() != 0 {
+        return Err(AppError::dependency(
+            Message::keyed("action_failed")
+                .with("action", "Push CA certificate")
+                .with("detail", output.stderr.trim()),
+            &trace_id,
+        ));
+    }
+
+    let sdk = match run_device_shell(
+        &adb_program,
+        &serial,
+        &["getprop", "ro.build.version.sdk"],
+        command_timeout(TimeoutKind::Probe, &trace_id),
+        &trace_id,
+    ) {
+        Ok(output) => output.stdout.trim().parse::<i64>().ok(),
+        Err(err) => {
+            warn!(trace_id = %trace_id, serial = %serial, error = %err, "failed to read device SDK for CA install");
+            None
+        }
+    };
+    let installer_launched = if sdk.is_some_and(|sdk| sdk < 30) {
+        let uri = format!("file://{device_path}");
+        match run_device_shell(
+            &adb_program,
+            &serial,
+            &[
+                "am",
+                "start",
+                "-n",
+                "com.android.certinstaller/.CertInstallerMain",
+                "-a",
+                "android.intent.action.VIEW",
+                "-t",
+                "application/x-x509-ca-cert",
+                "-d",
+                &uri,
+            ],
+            command_timeout(TimeoutKind::DeviceCommand, &trace_id),
+            &trace_id,
+        ) {
+            Ok(_) => true,
+            Err(err) => {
+                warn!(trace_id = %trace_id, serial = %serial, error = %err, "failed to open certificate installer");
+                false
+            }
+        }
+    } else {
+        false
+    };
+    info!(trace_id = %trace_id, serial = %serial, device_path = %device_path, installer_launched, "CA certificate pushed");
+
+    Ok(CommandResponse {
+        trace_id,
+        data: CaCertificateInstall {
+            serial,
+            device_path,
+            installer_launched,
+            note: ca_install_note(sdk),
+        },
+    })
+}
+
+#[tauri::command(async)]
+pub fn set_bluetooth_state(
+    serials: Vec<String>,
+    enable: bool,
+    app: AppHandle,
+    state: State<'_, AppState>,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<Vec<CommandResult>>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    if serials.is_empty() {
+        return Err(AppError::validation(
+            Message::keyed("required").with("field", "serials"),
+            &trace_id,
+        ));
+    }
+    warn_on_reservation_conflicts(&app, &serials, "Changing Bluetooth state", &trace_id);
+
+    let adb_program = get_adb_program(&trace_id)?;
+    let scheduler = Arc::clone(&state.scheduler);
+
+    let mut handles = Vec::new();
+    for (index, serial) in serials.into_iter().enumerate() {
+        ensure_non_empty(&serial, "serial", &trace_id)?;
+        let scheduler_clone = Arc::clone(&scheduler);
+        let trace_clone = trace_id.clone();
+        let adb_program_clone = adb_program.clone();
+        handles.push(std::thread::spawn(move || -> Result<_, AppError> {
+            let _permit = scheduler_clone.acquire_global();
+            let device_lock = scheduler_clone.device_lock(&serial);
+            let _device_guard = device_lock.lock().map_err(|_| {
+                warn!(trace_id = %trace_clone, serial = %serial, "device lock poisoned");
+                AppError::system(
+                    "Failed to access the device. Please try again.",
+                    &trace_clone,
+                )
+            })?;
+
+            let mut args = vec![
+                "-s".to_string(),
+                serial.clone(),
+                "shell".to_string(),
+                "svc".to_string(),
+                "bluetooth".to_string(),
+                if enable { "enable" } else { "disable" }.to_string(),
+            ];
+            let mut output = run_command_with_timeout(
+                &adb_program_clone,
+                &args,
+                command_timeout(TimeoutKind::DeviceCommand, &trace_clone),
+                &trace_clone,
+            );
+            if output
+                .as_ref()
+                .ok()
+                .and_then(|out| out.exit_code)
+                .unwrap_or(1)
+                != 0
+            {
+                args = vec![
+                    "-s".to_string(),
+                    serial.clone(),
+                    "shell".to_string(),
+                    "service".to_string(),
+                    "call".to_string(),
+                    "bluetooth_manager".to_string(),
+                    if enable { "8" } else { "9" }.to_string(),
+                ];
+                output = run_command_with_timeout(
+                    &adb_program_clone,
+                    &args,
+                    command_timeout(TimeoutKind::DeviceCommand, &trace_clone),
+                    &trace_clone,
+                );
+            }
+            let output = output?;
+            Ok((
+                index,
+                CommandResult {
+                    serial,
+                    stdout: output.stdout,
+                    stderr: output.stderr,
+                    exit_code: output.exit_code,
+                },
+            ))
+        }));
+    }
+
+    let mut collected = Vec::new();
+    for handle in handles {
+        let (index, result) = handle
+            .join()
+            .map_err(|_| AppError::system("Bluetooth toggle thread panicked", &trace_id))??;
+        collected.push((index, result));
+    }
+    collected.sort_by_key(|item| item.0);
+    let results = collected.into_iter().map(|item| item.1).collect();
+
+    Ok(CommandResponse {
+        trace_id,
+        data: results,
+    })
+}
+
+#[allow(clippy::too_many_arguments)]
+fn install_apk_batch_inner(
+    serials: Vec<String>,
+    apk_path: String,
+    replace: bool,
+    allow_downgrade: bool,
+    grant: bool,
+    allow_test_packages: bool,
+    extra_args: Option<String>,
+    state: &AppState,
+    trace_id: &str,
+    app: Option<AppHandle>,
+) -> Result<ApkBatchInstallResult, AppError> {
+    let trace_id = trace_id.to_string();
+    ensure_non_empty(&apk_path, "apk_path", &trace_id)?;
+    if serials.is_empty() {
+        return Err(AppError::validation(
+            Message::keyed("required").with("field", "serials"),
+            &trace_id,
+        ));
+    }
+
+    let adb_program = get_adb_program(&trace_id)?;
+    let normalized = normalize_apk_path(&apk_path);
+    let apk_path = normalized.to_string_lossy().to_string();
+    let mut result = ApkBatchInstallResult {
+        apk_path: apk_path.clone(),
+        apk_info: None,
+        results: HashMap::new(),
+        total_duration_seconds: 0.0,
+        capture_paths: HashMap::new(),
+    };
+
+    let start = std::time::Instant::now();
+
+    let mut split_bundle = None;
+    if is_split_bundle(&apk_path) {
+        let bundle =
+            extract_split_apks(&apk_path).map_err(|err| AppError::dependency(err, &trace_id))?;
+        if bundle.apk_paths.is_empty() {
+            for serial in &serials {
+                result.results.insert(
+                    serial.clone(),
+                    ApkInstallResult {
+                        serial: serial.clone(),
+                        success: false,
+                        error_code: ApkInstallErrorCode::InstallFailedInvalidApk,
+                        raw_output: "Failed to extract split APKs".to_string(),
+                        duration_seconds: 0.0,
+                        device_model: None,
+                    },
+                );
+            }
+            result.total_duration_seconds = start.elapsed().as_secs_f64();
+            return Ok(result);
+        }
+        split_bundle = Some(bundle);
+    } else {
+        let apk_info = get_apk_info(&apk_path);
+        result.apk_info = Some(apk_info.clone());
+        if !apk_info.is_valid() {
+            for serial in &serials {
+                result.results.insert(
+                    serial.clone(),
+                    ApkInstallResult {
+                        serial: serial.clone(),
+                        success: false,
+                        error_code: ApkInstallErrorCode::InstallFailedInvalidApk,
+                        raw_output: apk_info.error.clone().unwrap_or_default(),
+                        duration_seconds: 0.0,
+                        device_model: None,
+                    },
+                );
+            }
+            result.total_duration_seconds = start.elapsed().as_secs_f64();
+            return Ok(result);
+        }
+    }
+
+    let extra_args_list = extra_args
+        .unwrap_or_default()
+        .split_whitespace()
+        .map(|item| item.to_string())
+        .collect::<Vec<_>>();
+
+    let use_parallel = load_config(&trace_id)?.command.parallel_execution;
+    let scheduler = Arc::clone(&state.scheduler);
+
+    let mut handles = Vec::new();
     for serial in serials {
         let trace_clone = trace_id.clone();
-        let extra_args_list = extra_args_list.clone();
-        let split_paths = split_bundle.as_ref().map(|bundle| bundle.apk_paths.clone());
-        let apk_path_clone = apk_path.clone();
+        let extra_args_list = extra_args_list.clone();
+        let split_paths = split_bundle.as_ref().map(|bundle| bundle.apk_paths.clone());
+        let apk_path_clone = apk_path.clone();
+        let adb_program_clone = adb_program.clone();
+        let scheduler_clone = Arc::clone(&scheduler);
+        let app_clone = app.clone();
+        handles.push(std::thread::spawn(move || {
+            let start_device = std::time::Instant::now();
+            if let Some(app_emit) = &app_clone {
+                if let Err(err) = emit_tracked(
+                    app_emit,
+                    APK_INSTALL_EVENT_NAME,
+                    ApkInstallEvent {
+                        serial: serial.clone(),
+                        event: "start".to_string(),
+                        success: None,
+                        message: Some("Installing...".to_string()),
+                        error_code: None,
+                        raw_output: None,
+                        trace_id: trace_clone.clone(),
+                    },
+                ) {
+                    warn!(trace_id = %trace_clone, error = %err, "failed to emit apk install event");
+                }
+            }
+            let _permit = scheduler_clone.acquire_global();
+            let device_lock = scheduler_clone.device_lock(&serial);
+            let _device_guard = match device_lock.lock() {
+                Ok(guard) => guard,
+                Err(_) => {
+                    warn!(trace_id = %trace_clone, serial = %serial, "device lock poisoned");
+                    let result_item = ApkInstallResult {
+                        serial,
+                        success: false,
+                        error_code: ApkInstallErrorCode::UnknownError,
+                        raw_output: "Failed to access the device. Please try again.".to_string(),
+                        duration_seconds: start_device.elapsed().as_secs_f64(),
+                        device_model: None,
+                    };
+                    if let Some(app_emit) = &app_clone {
+                        if let Err(err) = emit_tracked(
+                            app_emit,
+                            APK_INSTALL_EVENT_NAME,
+                            ApkInstallEvent {
+                                serial: result_item.serial.clone(),
+                                event: "complete".to_string(),
+                                success: Some(false),
+                                message: Some("Failed to access the device. Please try again.".to_string()),
+                                error_code: Some(result_item.error_code.code().to_string()),
+                                raw_output: Some(truncate_for_event(
+                                    result_item.raw_output.trim(),
+                                    APK_INSTALL_OUTPUT_MAX_LEN,
+                                )),
+                                trace_id: trace_clone.clone(),
+                            },
+                        ) {
+                            warn!(trace_id = %trace_clone, error = %err, "failed to emit apk install event");
+                        }
+                    }
+                    return result_item;
+                }
+            };
+            let mut args = vec!["-s".to_string(), serial.clone()];
+            if let Some(paths) = split_paths {
+                args.push("install-multiple".to_string());
+                if replace {
+                    args.push("-r".to_string());
+                }
+                if allow_downgrade {
+                    args.push("-d".to_string());
+                }
+                if grant {
+                    args.push("-g".to_string());
+                }
+                if allow_test_packages {
+                    args.push("-t".to_string());
+                }
+                args.extend(extra_args_list.clone());
+                args.extend(paths);
+            } else {
+                args.push("install".to_string());
+                if replace {
+                    args.push("-r".to_string());
+                }
+                if allow_downgrade {
+                    args.push("-d".to_string());
+                }
+                if grant {
+                    args.push("-g".to_string());
+                }
+                if allow_test_packages {
+                    args.push("-t".to_string());
+                }
+                args.extend(extra_args_list.clone());
+                args.push(apk_path_clone);
+            }
+            let output = run_command_with_timeout(
+                &adb_program_clone,
+                &args,
+                command_timeout(TimeoutKind::Install, &trace_clone),
+                &trace_clone,
+            );
+            let elapsed = start_device.elapsed().as_secs_f64();
+            let result_item = match output {
+                Ok(output) => {
+                    let raw = if output.stdout.trim().is_empty() {
+                        output.stderr.clone()
+                    } else {
+                        output.stdout.clone()
+                    };
+                    let error_code = ApkInstallErrorCode::from_output(&raw);
+                    let success = error_code == ApkInstallErrorCode::Success;
+                    ApkInstallResult {
+                        serial,
+                        success,
+                        error_code,
+                        raw_output: raw,
+                        duration_seconds: elapsed,
+                        device_model: None,
+                    }
+                }
+                Err(err) => ApkInstallResult {
+                    serial,
+                    success: false,
+                    error_code: ApkInstallErrorCode::UnknownError,
+                    raw_output: err.error,
+                    duration_seconds: elapsed,
+                    device_model: None,
+                },
+            };
+
+            if let Some(app_emit) = &app_clone {
+                let raw_trimmed = result_item.raw_output.trim();
+                let raw_output = if raw_trimmed.is_empty() {
+                    None
+                } else {
+                    Some(truncate_for_event(raw_trimmed, APK_INSTALL_OUTPUT_MAX_LEN))
+                };
+                let message = if result_item.success {
+                    Some("Installed.".to_string())
+                } else if let Some(raw) = raw_output.clone() {
+                    Some(raw)
+                } else {
+                    Some(result_item.error_code.code().to_string())
+                };
+                if let Err(err) = emit_tracked(
+                    app_emit,
+                    APK_INSTALL_EVENT_NAME,
+                    ApkInstallEvent {
+                        serial: result_item.serial.clone(),
+                        event: "complete".to_string(),
+                        success: Some(result_item.success),
+                        message,
+                        error_code: Some(result_item.error_code.code().to_string()),
+                        raw_output,
+                        trace_id: trace_clone.clone(),
+                    },
+                ) {
+                    warn!(trace_id = %trace_clone, error = %err, "failed to emit apk install event");
+                }
+            }
+
+            result_item
+        }));
+        if !use_parallel {
+            if let Some(handle) = handles.pop() {
+                let result_item = handle
+                    .join()
+                    .map_err(|_| AppError::system("Install thread panicked", &trace_id))?;
+                result
+                    .results
+                    .insert(result_item.serial.clone(), result_item);
+            }
+        }
+    }
+
+    for handle in handles {
+        let result_item = handle
+            .join()
+            .map_err(|_| AppError::system("Install thread panicked", &trace_id))?;
+        result
+            .results
+            .insert(result_item.serial.clone(), result_item);
+    }
+
+    result.total_duration_seconds = start.elapsed().as_secs_f64();
+
+    let package = result
+        .apk_info
+        .as_ref()
+        .and_then(|info| info.package_name.clone())
+        .unwrap_or_else(|| result.apk_path.clone());
+    for item in result.results.values() {
+        let summary = if item.success {
+            format!("Installed {package}")
+        } else {
+            format!("Install of {package} failed: {}", item.error_code.code())
+        };
+        record_timeline_event(&item.serial, TIMELINE_INSTALL, &summary, &trace_id);
+    }
+    if !result.results.is_empty() {
+        let installed = result.results.values().filter(|item| item.success).count();
+        let total = result.results.len();
+        let mut serials: Vec<&str> = result.results.keys().map(String::as_str).collect();
+        serials.sort_unstable();
+        notify_webhooks(
+            NOTIFY_INSTALL_FINISHED,
+            &serials.join(","),
+            installed == total,
+            &format!(
+                "Installed {package} on {installed}/{total} devices in {:.0}s",
+                result.total_duration_seconds
+            ),
+            &trace_id,
+        );
+        let devices: Vec<serde_json::Value> = serials
+            .iter()
+            .filter_map(|serial| result.results.get(*serial))
+            .map(|item| {
+                serde_json::json!({
+                    "serial": item.serial,
+                    "success": item.success,
+                    "error_code": item.error_code.code(),
+                })
+            })
+            .collect();
+        run_hooks(
+            HOOK_INSTALL_COMPLETE,
+            &serials.join(","),
+            installed == total,
+            &format!("Installed {package} on {installed}/{total} devices"),
+            serde_json::json!({
+                "package_name": package,
+                "apk_path": result.apk_path,
+                "devices": devices,
+            }),
+            &trace_id,
+        );
+    }
+
+    Ok(result)
+}
+
+fn active_capture_context(state: &AppState, serial: &str) -> Option<String> {
+    let guard = state.capture_contexts.lock().ok()?;
+    guard
+        .iter()
+        .find(|(_, handle)| handle.serial == serial)
+        .map(|(context_id, _)| context_id.clone())
+}
+
+fn mark_capture_contexts(state: &AppState, serials: &[String], message: &str, trace_id: &str) {
+    let Ok(guard) = state.capture_contexts.lock() else {
+        warn!(trace_id = %trace_id, "capture context registry poisoned");
+        return;
+    };
+    for handle in guard.values() {
+        if serials.contains(&handle.serial) {
+            if let Ok(mut buffer) = handle.log_buffer.lock() {
+                buffer.push(capture_marker(message));
+            }
+        }
+    }
+}
+
+fn record_install_in_capture_contexts(
+    state: &AppState,
+    result: &ApkBatchInstallResult,
+    trace_id: &str,
+) {
+    let Ok(mut guard) = state.capture_contexts.lock() else {
+        warn!(trace_id = %trace_id, "capture context registry poisoned");
+        return;
+    };
+    for handle in guard.values_mut() {
+        let Some(install) = result.results.get(&handle.serial) else {
+            continue;
+        };
+        let outcome = if install.success {
+            "succeeded".to_string()
+        } else {
+            format!("failed ({})", install.error_code.code())
+        };
+        if let Ok(mut buffer) = handle.log_buffer.lock() {
+            buffer.push(capture_marker(&format!(
+                "install of {} {outcome}",
+                result.apk_path
+            )));
+        }
+        handle.installs.push(install.clone());
+    }
+}
+
+fn begin_capture_context_inner(
+    serial: &str,
+    label: &str,
+    state: &AppState,
+    trace_id: &str,
+) -> Result<CaptureContextInfo, AppError> {
+    ensure_non_empty(serial, "serial", trace_id)?;
+    ensure_non_empty(label, "label", trace_id)?;
+    let serial = serial.trim().to_string();
+    let label = label.trim().to_string();
+    if label.chars().count() > MAX_CAPTURE_LABEL_LEN {
+        return Err(AppError::validation(
+            Message::keyed("max_length")
+                .with("field", "Label")
+                .with("max", MAX_CAPTURE_LABEL_LEN),
+            trace_id,
+        ));
+    }
+
+    let mut guard = state
+        .capture_contexts
+        .lock()
+        .map_err(|_| AppError::system("Capture context registry locked", trace_id))?;
+    if guard.values().any(|handle| handle.serial == serial) {
+        return Err(AppError::validation(
+            Message::keyed("capture_context_active"),
+            trace_id,
+        ));
+    }
+
+    let adb_program = get_adb_program(trace_id)?;
+    // `-T 1` starts at the newest entry so the capture holds only what happens from now on.
+    let mut logcat = spawn_logged(
+        adb_command_for_serial(&adb_program, &serial)
+            .args(["-s", &serial, "logcat", "-v", "threadtime", "-T", "1"])
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null()),
+        trace_id,
+    )
+    .map_err(|err| {
+        AppError::dependency(
+            Message::keyed("start_failed")
+                .with("subject", "logcat")
+                .with("detail", &err),
+            trace_id,
+        )
+    })?;
+    let stdout = logcat
+        .stdout
+        .take()
+        .ok_or_else(|| AppError::system("Failed to capture logcat stdout", trace_id))?;
+
+    let log_buffer = Arc::new(Mutex::new(CaptureLineBuffer::default()));
+    let perf_samples = Arc::new(Mutex::new(Vec::new()));
+    let stop_flag = Arc::new(AtomicBool::new(false));
+
+    let buffer_reader = Arc::clone(&log_buffer);
+    let trace_reader = trace_id.to_string();
+    let reader_join = std::thread::spawn(move || {
+        for line in BufReader::new(stdout).lines() {
+            let line = match line {
+                Ok(line) => line,
+                Err(err) => {
+                    warn!(trace_id = %trace_reader, error = %err, "failed to read capture logcat");
+                    break;
+                }
+            };
+            match buffer_reader.lock() {
+                Ok(mut buffer) => buffer.push(line),
+                Err(_) => break,
+            }
+        }
+    });
+
+    let stop_perf = Arc::clone(&stop_flag);
+    let samples_perf = Arc::clone(&perf_samples);
+    let scheduler = Arc::clone(&state.scheduler);
+    let serial_perf = serial.clone();
+    let trace_perf = trace_id.to_string();
+    let perf_join = std::thread::spawn(move || {
+        let args = vec![
+            "-s".to_string(),
+            serial_perf.clone(),
+            "shell".to_string(),
+            build_perf_script(),
+        ];
+        let mut previous_cpu = None;
+        while !stop_perf.load(Ordering::Relaxed) {
+            let output = {
+                let _permit = scheduler.acquire_global();
+                let device_lock = scheduler.device_lock(&serial_perf);
+                let device_guard = device_lock.lock().ok();
+                device_guard.map(|_guard| {
+                    run_command_with_timeout(
+                        &adb_program,
+                        &args,
+                        command_timeout(TimeoutKind::Probe, &trace_perf),
+                        &trace_perf,
+                    )
+                })
+            };
+            match output {
+                Some(Ok(output)) if output.exit_code == Some(0) => {
+                    let (sample, cpu) =
+                        capture_perf_sample(&output.stdout, previous_cpu, Utc::now().to_rfc3339());
+                    previous_cpu = cpu;
+                    if let Ok(mut samples) = samples_perf.lock() {
+                        if samples.len() >= CAPTURE_MAX_PERF_SAMPLES {
+                            samples.remove(0);
+                        }
+                        samples.push(sample);
+                    }
+                }
+                Some(Ok(output)) => {
+                    warn!(trace_id = %trace_perf, exit_code = ?output.exit_code, "capture perf sample returned non-zero exit code");
+                }
+                Some(Err(err)) => {
+                    warn!(trace_id = %trace_perf, error = %err, "failed to sample capture perf");
+                }
+                None => {
+                    warn!(trace_id = %trace_perf, "device lock poisoned");
+                }
+            }
+            sleep_with_stop(CAPTURE_PERF_INTERVAL, &stop_perf);
+        }
+    });
+
+    let started_at = Local::now();
+    let info = CaptureContextInfo {
+        context_id: Uuid::new_v4().to_string(),
+        serial: serial.clone(),
+        label: label.clone(),
+        started_at: started_at.to_rfc3339(),
+    };
+    guard.insert(
+        info.context_id.clone(),
+        CaptureContextHandle {
+            serial,
+            label,
+            started_at,
+            stop_flag,
+            logcat,
+            log_buffer,
+            perf_samples,
+            installs: Vec::new(),
+            joins: vec![reader_join, perf_join],
+        },
+    );
+    info!(trace_id = %trace_id, context_id = %info.context_id, serial = %info.serial, label = %info.label, "capture context started");
+    Ok(info)
+}
+
+fn end_capture_context_inner(
+    context_id: &str,
+    output_dir: Option<&str>,
+    state: &AppState,
+    trace_id: &str,
+) -> Result<CaptureContextResult, AppError> {
+    ensure_non_empty(context_id, "context_id", trace_id)?;
+    let context_id = context_id.trim();
+    let handle = state
+        .capture_contexts
+        .lock()
+        .map_err(|_| AppError::system("Capture context registry locked", trace_id))?
+        .remove(context_id);
+    let Some(mut handle) = handle else {
+        return Err(AppError::validation(
+            Message::keyed("not_found").with("subject", "Capture context"),
+            trace_id,
+        ));
+    };
+
+    handle.stop_flag.store(true, Ordering::Relaxed);
+    if let Err(err) = handle.logcat.kill() {
+        warn!(trace_id = %trace_id, error = %err, "failed to stop capture logcat");
+    }
+    let _ = handle.logcat.wait();
+    for join in handle.joins.drain(..) {
+        if join.join().is_err() {
+            warn!(trace_id = %trace_id, "capture context thread panicked");
+        }
+    }
+
+    let config = load_config(trace_id)?;
+    let base_dir = prepare_artifact_dir(&config, ArtifactKind::Log, output_dir, trace_id)?;
+    let dir = base_dir.join(capture_dir_name(
+        &handle.label,
+        &handle.serial,
+        handle.started_at,
+    ));
+    fs::create_dir_all(&dir).map_err(|err| {
+        AppError::system(
+            Message::keyed("operation_failed")
+                .with("action", "create capture folder")
+                .with("detail", &err),
+            trace_id,
+        )
+    })?;
+
+    let (log_text, log_lines, dropped_log_lines) = {
+        let buffer = handle
+            .log_buffer
+            .lock()
+            .map_err(|_| AppError::system("Capture log buffer locked", trace_id))?;
+        let mut text = buffer.lines.iter().cloned().collect::<Vec<_>>().join("\n");
+        text.push('\n');
+        (text, buffer.lines.len(), buffer.dropped)
+    };
+    let samples = handle
+        .perf_samples
+        .lock()
+        .map_err(|_| AppError::system("Capture perf buffer locked", trace_id))?
+        .clone();
+    let write = |name: &str, contents: String| {
+        fs::write(dir.join(name), contents).map_err(|err| {
+            AppError::system(
+                Message::keyed("operation_failed")
+                    .with("action", format!("write {name}"))
+                    .with("detail", &err),
+                trace_id,
+            )
+        })
+    };
+    let to_json =
+        |value: serde_json::Value| serde_json::to_string_pretty(&value).unwrap_or_default();
+    write("logcat.txt", log_text)?;
+    write("perf.json", to_json(serde_json::json!(samples)))?;
+    write("installs.json", to_json(serde_json::json!(handle.installs)))?;
+    write(
+        "context.json",
+        to_json(serde_json::json!({
+            "context_id": context_id,
+            "serial": handle.serial,
+            "label": handle.label,
+            "started_at": handle.started_at.to_rfc3339(),
+            "ended_at": Local::now().to_rfc3339(),
+            "log_lines": log_lines,
+            "dropped_log_lines": dropped_log_lines,
+            "perf_samples": samples.len(),
+        })),
+    )?;
+
+    let path = dir.to_string_lossy().to_string();
+    info!(trace_id = %trace_id, context_id = %context_id, path = %path, log_lines, "capture context written");
+    Ok(CaptureContextResult {
+        context_id: context_id.to_string(),
+        serial: handle.serial,
+        label: handle.label,
+        path,
+        log_lines,
+        dropped_log_lines,
+        perf_samples: samples.len(),
+        installs: handle.installs,
+    })
+}
+
+/// Start buffering logcat and periodic perf samples for `serial` under `label`.
+#[tauri::command(async)]
+pub fn begin_capture_context(
+    serial: String,
+    label: String,
+    state: State<'_, AppState>,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<CaptureContextInfo>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    let info = begin_capture_context_inner(&serial, &label, state.inner(), &trace_id)?;
+    Ok(CommandResponse {
+        trace_id,
+        data: info,
+    })
+}
+
+/// Stop a capture context and write its logcat, perf samples and install results to a
+/// labeled folder.
+#[tauri::command(async)]
+pub fn end_capture_context(
+    context_id: String,
+    output_dir: Option<String>,
+    state: State<'_, AppState>,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<CaptureContextResult>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    let result =
+        end_capture_context_inner(&context_id, output_dir.as_deref(), state.inner(), &trace_id)?;
+    Ok(CommandResponse {
+        trace_id,
+        data: result,
+    })
+}
+
+/// Turn on the perf, net and thermal monitors for `serial` and feed their readings into the
+/// soak test, which checks them against the profile's thresholds and streams them to
+/// `samples.jsonl`, while a thread periodically saves screenshots and logcat snapshots into
+/// the soak test folder.
+#[tauri::command(async)]
+pub fn start_soak_test(
+    serial: String,
+    profile: Option<SoakProfile>,
+    output_dir: Option<String>,
+    app: AppHandle,
+    state: State<'_, AppState>,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<SoakTestInfo>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+    let serial = serial.trim().to_string();
+    let plan = SoakPlan::from_profile(&profile.unwrap_or_default())
+        .map_err(|message| AppError::validation(message, &trace_id))?;
+    let adb_program = get_adb_program(&trace_id)?;
+    let config = load_config(&trace_id)?;
+
+    let mut guard = state
+        .soak_tests
+        .lock()
+        .map_err(|_| AppError::system("Soak test registry locked", &trace_id))?;
+    if guard.contains_key(&serial) {
+        return Err(AppError::validation(
+            Message::keyed("soak_test_running"),
+            &trace_id,
+        ));
+    }
+
+    let started_at = Local::now();
+    let base_dir =
+        prepare_artifact_dir(&config, ArtifactKind::Log, output_dir.as_deref(), &trace_id)?;
+    let dir = base_dir.join(capture_dir_name(&plan.label, &serial, started_at));
+    for sub_dir in [dir.join("screenshots"), dir.join("logcat")] {
+        fs::create_dir_all(&sub_dir).map_err(|err| {
+            AppError::system(
+                Message::keyed("operation_failed")
+                    .with("action", "create soak test folder")
+                    .with("detail", &err),
+                &trace_id,
+            )
+        })?;
+    }
+
+    let samples_path = dir.join(SOAK_SAMPLES_FILE);
+    let samples_out = fs::File::create(&samples_path).map_err(|err| {
+        AppError::system(
+            Message::keyed("operation_failed")
+                .with("action", format!("create {SOAK_SAMPLES_FILE}"))
+                .with("detail", &err),
+            &trace_id,
+        )
+    })?;
+    let progress = Arc::new(Mutex::new(SoakProgress::new(
+        plan.clone(),
+        Box::new(std::io::BufWriter::new(samples_out)),
+    )));
+    state
+        .soak_feeds
+        .lock()
+        .map_err(|_| AppError::system("Soak test registry locked", &trace_id))?
+        .insert(serial.clone(), Arc::clone(&progress));
+
+    // Monitors that are already running belong to someone else and stay on after the soak;
+    // the soak reads whatever they sample at their own interval.
+    let sample_interval_ms = Some(plan.sample_interval.as_millis() as u64);
+    let already_running = (
+        state
+            .perf_monitors
+            .lock()
+            .map(|monitors| monitors.contains_key(&serial))
+            .unwrap_or(false),
+        state
+            .net_profilers
+            .lock()
+            .map(|profilers| profilers.contains_key(&serial))
+            .unwrap_or(false),
+        state
+            .thermal_monitors
+            .lock()
+            .map(|monitors| monitors.contains_key(&serial))
+            .unwrap_or(false),
+    );
+    let mut started_monitors: Vec<String> = Vec::new();
+    let started = (|| -> Result<(), AppError> {
+        if !already_running.0 {
+            start_perf_monitor(
+                serial.clone(),
+                sample_interval_ms,
+                app.clone(),
+                state.clone(),
+                Some(trace_id.clone()),
+            )?;
+            started_monitors.push(SOAK_MONITOR_PERF.to_string());
+        }
+        if !already_running.1 {
+            start_net_profiler(
+                serial.clone(),
+                sample_interval_ms,
+                None,
+                None,
+                app.clone(),
+                state.clone(),
+                Some(trace_id.clone()),
+            )?;
+            started_monitors.push(SOAK_MONITOR_NET.to_string());
+        }
+        if !already_running.2 {
+            start_thermal_monitor(
+                serial.clone(),
+                sample_interval_ms,
+                plan.temp_threshold_decic,
+                app.clone(),
+                state.clone(),
+                Some(trace_id.clone()),
+            )?;
+            started_monitors.push(SOAK_MONITOR_THERMAL.to_string());
+        }
+        Ok(())
+    })();
+    if let Err(err) = started {
+        stop_soak_monitors(&serial, &started_monitors, state.inner(), &trace_id);
+        remove_soak_feed(&serial, &state.soak_feeds, &trace_id);
+        return Err(err);
+    }
+
+    let stop_flag = Arc::new(AtomicBool::new(false));
+    let join = {
+        let stop_flag = Arc::clone(&stop_flag);
+        let progress = Arc::clone(&progress);
+        let scheduler = Arc::clone(&state.scheduler);
+        let plan = plan.clone();
+        let dir = dir.clone();
+        let serial = serial.clone();
+        let trace_id = trace_id.clone();
+        std::thread::spawn(move || {
+            let run_locked = |args: &[String], timeout: Duration| {
+                let _permit = scheduler.acquire_global();
+                let device_lock = scheduler.device_lock(&serial);
+                let device_guard = device_lock.lock().ok();
+                device_guard
+                    .map(|_guard| run_command_with_timeout(&adb_program, args, timeout, &trace_id))
+            };
+            let logcat_args = logcat_snapshot_args(&serial, plan.logcat_lines);
+            let mut last_screenshot: Option<Instant> = None;
+            let mut last_logcat: Option<Instant> = None;
+
+            while !stop_flag.load(Ordering::Relaxed) {
+                let loop_started = Instant::now();
+
+                let due = |last: Option<Instant>, interval: Option<Duration>| {
+                    interval
+                        .is_some_and(|interval| last.is_none_or(|last| last.elapsed() >= interval))
+                };
+                if due(last_screenshot, plan.screenshot_interval) {
+                    last_screenshot = Some(Instant::now());
+                    let path = dir.join("screenshots").join(format!(
+                        "screenshot_{}.png",
+                        Local::now().format("%Y%m%d_%H%M%S")
+                    ));
+                    let result = {
+                        let _permit = scheduler.acquire_global();
+                        capture_screenshot_to(&adb_program, &serial, &config, &path, &trace_id)
+                    };
+                    if let Ok(mut progress) = progress.lock() {
+                        match result {
+                            Ok(()) => progress.screenshots += 1,
+                            Err(err) => {
+                                warn!(trace_id = %trace_id, error = %err, "soak screenshot failed");
+                                progress.capture_errors += 1;
+                            }
+                        }
+                    }
+                }
+                if due(last_logcat, plan.logcat_interval) {
+                    last_logcat = Some(Instant::now());
+                    let path = dir.join("logcat").join(format!(
+                        "logcat_{}.txt",
+                        Local::now().format("%Y%m%d_%H%M%S")
+                    ));
+                    let result = match run_locked(
+                        &logcat_args,
+                        command_timeout(TimeoutKind::DeviceCommand, &trace_id),
+                    ) {
+                        Some(Ok(output)) if output.exit_code == Some(0) => {
+                            fs::write(&path, output.stdout).map_err(|err| err.to_string())
+                        }
+                        Some(Ok(output)) => Err(format!("exit code {:?}", output.exit_code)),
+                        Some(Err(err)) => Err(err.to_string()),
+                        None => Err("device lock poisoned".to_string()),
+                    };
+                    if let Ok(mut progress) = progress.lock() {
+                        match result {
+                            Ok(()) => progress.logcat_snapshots += 1,
+                            Err(err) => {
+                                warn!(trace_id = %trace_id, error = %err, "soak logcat snapshot failed");
+                                progress.capture_errors += 1;
+                            }
+                        }
+                    }
+                }
+
+                let elapsed = loop_started.elapsed();
+                if elapsed < plan.sample_interval {
+                    sleep_with_stop(plan.sample_interval - elapsed, &stop_flag);
+                }
+            }
+        })
+    };
+
+    let info = SoakTestInfo {
+        serial: serial.clone(),
+        label: plan.label.clone(),
+        started_at: started_at.to_rfc3339(),
+        path: dir.to_string_lossy().to_string(),
+        profile: plan.profile(),
+        started_monitors: started_monitors.clone(),
+    };
+    guard.insert(
+        serial,
+        SoakTestHandle {
+            plan,
+            started_at,
+            dir,
+            stop_flag,
+            progress,
+            join,
+            started_monitors,
+        },
+    );
+    info!(trace_id = %trace_id, serial = %info.serial, label = %info.label, path = %info.path, "soak test started");
+    Ok(CommandResponse {
+        trace_id,
+        data: info,
+    })
+}
+
+fn remove_soak_feed(serial: &str, feeds: &SoakFeeds, trace_id: &str) {
+    match feeds.lock() {
+        Ok(mut guard) => {
+            guard.remove(serial);
+        }
+        Err(_) => warn!(trace_id = %trace_id, "soak feed registry lock poisoned"),
+    }
+}
+
+/// Feed a monitor reading to the soak test running on `serial`, if there is one.
+fn publish_to_soak_test(feeds: &SoakFeeds, serial: &str, reading: SoakReading<'_>, trace_id: &str) {
+    let progress = match feeds.lock() {
+        Ok(guard) => match guard.get(serial) {
+            Some(progress) => Arc::clone(progress),
+            None => return,
+        },
+        Err(_) => {
+            warn!(trace_id = %trace_id, "soak feed registry lock poisoned");
+            return;
+        }
+    };
+    let (label, update) = match progress.lock() {
+        Ok(mut progress) => (progress.label().to_string(), progress.record(reading)),
+        Err(_) => {
+            warn!(trace_id = %trace_id, "soak test progress lock poisoned");
+            return;
+        }
+    };
+    if let Some(err) = update.write_error {
+        warn!(trace_id = %trace_id, serial = %serial, error = %err, "failed to write soak sample; later samples only count toward the summary");
+    }
+    for alert in &update.alerts {
+        let summary = format!("Soak {label}: {}", alert.summary);
+        record_timeline_event(serial, TIMELINE_PERF_ALERT, &summary, trace_id);
+        notify_webhooks(NOTIFY_PERF_ALERT, serial, false, &summary, trace_id);
+        warn!(trace_id = %trace_id, serial = %serial, metric = %alert.metric, value = alert.value, threshold = alert.threshold, "soak threshold crossed");
+    }
+}
+
+/// Stop the monitors a soak test turned on; failures are logged so the rest still stop.
+fn stop_soak_monitors(serial: &str, monitors: &[String], state: &AppState, trace_id: &str) {
+    for monitor in monitors {
+        let result = match monitor.as_str() {
+            SOAK_MONITOR_PERF => {
+                stop_perf_monitor_inner(serial.to_string(), &state.perf_monitors, trace_id)
+            }
+            SOAK_MONITOR_NET => {
+                stop_net_profiler_inner(serial.to_string(), &state.net_profilers, trace_id)
+            }
+            SOAK_MONITOR_THERMAL => {
+                stop_thermal_monitor_inner(serial.to_string(), &state.thermal_monitors, trace_id)
+            }
+            _ => Ok(true),
+        };
+        if let Err(err) = result {
+            warn!(trace_id = %trace_id, monitor = %monitor, error = %err, "failed to stop soak monitor");
+        }
+    }
+}
+
+/// Stop a soak test and write `report.json` and `alerts.json` next to its `samples.jsonl`,
+/// screenshots and logcat snapshots.
+#[tauri::command(async)]
+pub fn stop_soak_test(
+    serial: String,
+    state: State<'_, AppState>,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<SoakTestReport>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+    let serial = serial.trim().to_string();
+    let handle = state
+        .soak_tests
+        .lock()
+        .map_err(|_| AppError::system("Soak test registry locked", &trace_id))?
+        .remove(&serial);
+    let Some(handle) = handle else {
+        return Err(AppError::validation(
+            Message::keyed("not_running").with("subject", "Soak test"),
+            &trace_id,
+        ));
+    };
+
+    handle.stop_flag.store(true, Ordering::Relaxed);
+    if handle.join.join().is_err() {
+        warn!(trace_id = %trace_id, "soak test thread panicked");
+    }
+    remove_soak_feed(&serial, &state.soak_feeds, &trace_id);
+    stop_soak_monitors(&serial, &handle.started_monitors, state.inner(), &trace_id);
+
+    let mut progress = handle
+        .progress
+        .lock()
+        .map_err(|_| AppError::system("Soak test progress locked", &trace_id))?;
+    if let Err(err) = progress.finish() {
+        warn!(trace_id = %trace_id, error = %err, "failed to flush soak samples");
+        progress.capture_errors += 1;
+    }
+    let stats = progress.stats.clone();
+    let ended_at = Local::now();
+    let report = SoakTestReport {
+        serial,
+        label: handle.plan.label.clone(),
+        started_at: handle.started_at.to_rfc3339(),
+        ended_at: ended_at.to_rfc3339(),
+        duration_secs: (ended_at - handle.started_at).num_seconds(),
+        path: handle.dir.to_string_lossy().to_string(),
+        samples: stats.samples,
+        screenshots: progress.screenshots,
+        logcat_snapshots: progress.logcat_snapshots,
+        capture_errors: progress.capture_errors,
+        alerts: progress.alerts.clone(),
+        cpu_avg_percent_x100: stats.cpu_avg_percent_x100(),
+        cpu_max_percent_x100: stats.cpu_max_percent_x100,
+        mem_max_used_bytes: stats.mem_max_used_bytes,
+        temp_max_decic: stats.temp_max_decic,
+        net_usage: progress.net.usage(),
+    };
+
+    let write = |name: &str, value: serde_json::Value| {
+        let contents = serde_json::to_string_pretty(&value).map_err(|err| {
+            AppError::system(
+                Message::keyed("operation_failed")
+                    .with("action", format!("serialize {name}"))
+                    .with("detail", &err),
+                &trace_id,
+            )
+        })?;
+        fs::write(handle.dir.join(name), contents).map_err(|err| {
+            AppError::system(
+                Message::keyed("operation_failed")
+                    .with("action", format!("write {name}"))
+                    .with("detail", &err),
+                &trace_id,
+            )
+        })
+    };
+    write("alerts.json", serde_json::json!(report.alerts))?;
+    write(
+        "report.json",
+        serde_json::json!({
+            "report": report,
+            "profile": handle.plan.profile(),
+            "started_monitors": handle.started_monitors,
+        }),
+    )?;
+
+    info!(trace_id = %trace_id, serial = %report.serial, path = %report.path, samples = report.samples, alerts = report.alerts.len(), "soak test report written");
+    Ok(CommandResponse {
+        trace_id,
+        data: report,
+    })
+}
+
+#[tauri::command(async)]
+#[allow(clippy::too_many_arguments)]
+pub fn install_apk_batch(
+    serials: Vec<String>,
+    apk_path: String,
+    replace: bool,
+    allow_downgrade: bool,
+    grant: bool,
+    allow_test_packages: bool,
+    extra_args: Option<String>,
+    capture_label: Option<String>,
+    app: AppHandle,
+    state: State<'_, AppState>,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<ApkBatchInstallResult>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    let capture_label = capture_label
+        .map(|label| label.trim().to_string())
+        .filter(|label| !label.is_empty());
+    let mut auto_contexts = Vec::new();
+    if let Some(label) = &capture_label {
+        for serial in &serials {
+            if active_capture_context(state.inner(), serial).is_some() {
+                continue;
+            }
+            match begin_capture_context_inner(serial, label, state.inner(), &trace_id) {
+                Ok(info) => auto_contexts.push(info.context_id),
+                Err(err) => {
+                    warn!(trace_id = %trace_id, serial = %serial, error = %err.error, "failed to start install capture context");
+                }
+            }
+        }
+    }
+    warn_on_reservation_conflicts(&app, &serials, "Installing apps", &trace_id);
+    mark_capture_contexts(
+        state.inner(),
+        &serials,
+        &format!("install of {} started", apk_path.trim()),
+        &trace_id,
+    );
+
+    let result = install_apk_batch_inner(
+        serials,
+        apk_path,
+        replace,
+        allow_downgrade,
+        grant,
+        allow_test_packages,
+        extra_args,
+        state.inner(),
+        &trace_id,
+        Some(app),
+    );
+    if let Ok(result) = &result {
+        record_install_in_capture_contexts(state.inner(), result, &trace_id);
+    }
+    let mut capture_paths = HashMap::new();
+    for context_id in auto_contexts {
+        match end_capture_context_inner(&context_id, None, state.inner(), &trace_id) {
+            Ok(captured) => {
+                capture_paths.insert(captured.serial, captured.path);
+            }
+            Err(err) => {
+                warn!(trace_id = %trace_id, context_id = %context_id, error = %err.error, "failed to write install capture context");
+            }
+        }
+    }
+    let mut result = result?;
+    result.capture_paths = capture_paths;
+    for serial in result.results.keys() {
+        invalidate_app_reads(serial);
+    }
+
+    Ok(CommandResponse {
+        trace_id,
+        data: result,
+    })
+}
+
+#[tauri::command(async)]
+pub fn capture_screenshot(
+    serial: String,
+    output_dir: Option<String>,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<String>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+
+    let adb_program = get_adb_program(&trace_id)?;
+    let config = load_config(&trace_id)?;
+    let timestamp = Utc::now().format("%Y%m%d_%H%M%S");
+    let safe_serial = sanitize_filename_component(&serial);
+    let filename = format!("screenshot_{}_{}.png", safe_serial, timestamp);
+    let mut output_path = prepare_artifact_dir(
+        &config,
+        ArtifactKind::Screenshot,
+        output_dir.as_deref(),
+        &trace_id,
+    )?;
+    output_path.push(&filename);
+    capture_screenshot_to(&adb_program, &serial, &config, &output_path, &trace_id)?;
+
+    Ok(CommandResponse {
+        trace_id,
+        data: output_path.to_string_lossy().to_string(),
+    })
+}
+
+/// Run one of the multi-serial tools against a saved device group, resolving the group to
+/// serials here so automations can name a group instead of listing devices.
+#[tauri::command(async)]
+pub fn run_group_action(
+    group: String,
+    action: String,
+    params: Option<serde_json::Value>,
+    app: AppHandle,
+    state: State<'_, AppState>,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<GroupActionResult>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&group, "group", &trace_id)?;
+    ensure_non_empty(&action, "action", &trace_id)?;
+    let parsed = parse_group_action(&action, &params.unwrap_or_default())
+        .map_err(|err| AppError::validation(err, &trace_id))?;
+    let config = load_config(&trace_id)?;
+    let serials = resolve_group_serials(&config.device_groups, &group)
+        .map_err(|err| AppError::validation(err, &trace_id))?;
+    info!(trace_id = %trace_id, group = %group, action = %action, devices = serials.len(), "running group action");
+
+    let by_serial: HashMap<String, GroupActionDeviceResult> = match parsed {
+        GroupAction::Reboot {
+            mode,
+            wait_for_boot,
+            boot_timeout_secs,
+        } => reboot_devices(
+            serials.clone(),
+            mode,
+            Some(wait_for_boot),
+            boot_timeout_secs,
+            app,
+            state,
+            Some(trace_id.clone()),
+        )?
+        .data
+        .into_iter()
+        .map(|result| (result.result.serial.clone(), reboot_result_to_group(result)))
+        .collect(),
+        GroupAction::Wifi { enable } => {
+            set_wifi_state(serials.clone(), enable, app, state, Some(trace_id.clone()))?
+                .data
+                .into_iter()
+                .map(|result| (result.serial.clone(), command_result_to_group(result)))
+                .collect()
+        }
+        GroupAction::Install {
+            apk_path,
+            replace,
+            allow_downgrade,
+            grant,
+            allow_test_packages,
+            extra_args,
+        } => install_apk_batch(
+            serials.clone(),
+            apk_path,
+            replace,
+            allow_downgrade,
+            grant,
+            allow_test_packages,
+            extra_args,
+            None,
+            app,
+            state,
+            Some(trace_id.clone()),
+        )?
+        .data
+        .results
+        .into_values()
+        .map(|result| (result.serial.clone(), install_result_to_group(result)))
+        .collect(),
+        GroupAction::Screenshot { output_dir } => serials
+            .iter()
+            .map(|serial| {
+                let result = match capture_screenshot(
+                    serial.clone(),
+                    output_dir.clone(),
+                    Some(trace_id.clone()),
+                ) {
+                    Ok(response) => GroupActionDeviceResult {
+                        serial: serial.clone(),
+                        success: true,
+                        output: Some(response.data),
+                        error: None,
+                    },
+                    Err(err) => {
+                        warn!(trace_id = %trace_id, serial = %serial, error = %err.error, "group screenshot failed");
+                        GroupActionDeviceResult {
+                            serial: serial.clone(),
+                            success: false,
+                            output: None,
+                            error: Some(err.error),
+                        }
+                    }
+                };
+                (serial.clone(), result)
+            })
+            .collect(),
+    };
+
+    Ok(CommandResponse {
+        trace_id,
+        data: GroupActionResult {
+            group,
+            action: action.trim().to_string(),
+            results: order_group_results(&serials, by_serial),
+            serials,
+        },
+    })
+}
+
+/// `exec-out screencap`, falling back to capturing on the device and pulling the file.
+fn capture_screenshot_to(
+    adb_program: &str,
+    serial: &str,
+    config: &AppConfig,
+    output_path: &Path,
+    trace_id: &str,
+) -> Result<(), AppError> {
+    let filename = output_path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| "screenshot.png".to_string());
+
+    let mut args = vec![
+        "-s".to_string(),
+        serial.to_string(),
+        "exec-out".to_string(),
+        "screencap".to_string(),
+        "-p".to_string(),
+    ];
+    if config.screenshot.display_id >= 0 {
+        args.push("-d".to_string());
+        args.push(config.screenshot.display_id.to_string());
+    }
+    if !config.screenshot.extra_args.trim().is_empty() {
+        args.extend(
+            config
+                .screenshot
+                .extra_args
+                .split_whitespace()
+                .map(|item| item.to_string()),
+        );
+    }
+
+    let output = adb_command(&route_adb_program(adb_program, &args))
+        .args(&args)
+        .output()
+        .map_err(|err| {
+            AppError::dependency(
+                Message::keyed("operation_failed")
+                    .with("action", "run adb")
+                    .with("detail", &err),
+                trace_id,
+            )
+        })?;
+
+    if output.status.success() {
+        return fs::write(output_path, &output.stdout).map_err(|err| {
+            AppError::system(
+                Message::keyed("operation_failed")
+                    .with("action", "write screenshot")
+                    .with("detail", &err),
+                trace_id,
+            )
+        });
+    }
+
+    let exec_error_raw = String::from_utf8_lossy(&output.stderr).trim().to_string();
+    let exec_error = if exec_error_raw.is_empty() {
+        "unknown error".to_string()
+    } else {
+        exec_error_raw
+    };
+    warn!(
+        trace_id = %trace_id,
+        error = %exec_error,
+        "exec-out screencap failed; falling back to pull"
+    );
+
+    let fallback_result = (|| -> Result<(), AppError> {
+        let remote_path = format!("/sdcard/{filename}");
+        let capture_args = vec![
+            "-s".to_string(),
+            serial.to_string(),
+            "shell".to_string(),
+            "screencap".to_string(),
+            "-p".to_string(),
+            remote_path.clone(),
+        ];
+        let capture_output = run_command_with_timeout(
+            adb_program,
+            &capture_args,
+            command_timeout(TimeoutKind::DeviceCommand, trace_id),
+            trace_id,
+        )?;
+        if capture_output.exit_code.unwrap_or(1) != 0 {
+            return Err(AppError::dependency(
+                Message::keyed("action_failed")
+                    .with("action", "Fallback screencap")
+                    .with("detail", capture_output.stderr.trim()),
+                trace_id,
+            ));
+        }
+        let pull_args = vec![
+            "-s".to_string(),
+            serial.to_string(),
+            "pull".to_string(),
+            remote_path.clone(),
+            output_path.to_string_lossy().to_string(),
+        ];
+        let pull_output = run_command_with_timeout(
+            adb_program,
+            &pull_args,
+            command_timeout(TimeoutKind::Transfer, trace_id),
+            trace_id,
+        )?;
+        if pull_output.exit_code.unwrap_or(1) != 0 {
+            return Err(AppError::dependency(
+                Message::keyed("action_failed")
+                    .with("action", "Fallback pull")
+                    .with("detail", pull_output.stderr.trim()),
+                trace_id,
+            ));
+        }
+        let cleanup_args = vec![
+            "-s".to_string(),
+            serial.to_string(),
+            "shell".to_string(),
+            "rm".to_string(),
+            "-f".to_string(),
+            remote_path,
+        ];
+        if let Err(err) = run_command_with_timeout(
+            adb_program,
+            &cleanup_args,
+            command_timeout(TimeoutKind::DeviceCommand, trace_id),
+            trace_id,
+        ) {
+            warn!(
+                trace_id = %trace_id,
+                error = %err.error,
+                "failed to remove fallback screenshot"
+            );
+        }
+        Ok(())
+    })();
+
+    fallback_result.map_err(|err| {
+        AppError::dependency(
+            Message::keyed("screenshot_failed")
+                .with("detail", &exec_error)
+                .with("fallback", &err.error),
+            trace_id,
+        )
+    })
+}
+
+fn compare_screenshot_files(
+    path_a: &Path,
+    path_b: &Path,
+    threshold: f64,
+    diff_path: &Path,
+    trace_id: &str,
+) -> Result<ScreenshotComparison, AppError> {
+    let image_a =
+        read_png_rgba(path_a).map_err(|message| AppError::validation(message, trace_id))?;
+    let image_b =
+        read_png_rgba(path_b).map_err(|message| AppError::validation(message, trace_id))?;
+    let diff = diff_images(&image_a, &image_b);
+    write_png_rgba(diff_path, &diff.diff_image)
+        .map_err(|message| AppError::system(message, trace_id))?;
+    Ok(ScreenshotComparison {
+        path_a: path_a.to_string_lossy().to_string(),
+        path_b: path_b.to_string_lossy().to_string(),
+        width: diff.width,
+        height: diff.height,
+        diff_pixels: diff.diff_pixels,
+        total_pixels: diff.total_pixels,
+        similarity: diff.similarity,
+        threshold,
+        passed: diff.similarity >= threshold,
+        size_mismatch: diff.size_mismatch,
+        diff_path: diff_path.to_string_lossy().to_string(),
+    })
+}
+
+/// Pixel-diffs two PNG screenshots and writes the diff image next to `path_a`.
+#[tauri::command(async)]
+pub fn compare_screenshots(
+    path_a: String,
+    path_b: String,
+    threshold: Option<f64>,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<ScreenshotComparison>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&path_a, "path_a", &trace_id)?;
+    ensure_non_empty(&path_b, "path_b", &trace_id)?;
+    let threshold = normalize_similarity_threshold(threshold)
+        .map_err(|message| AppError::validation(message, &trace_id))?;
+
+    let path_a = PathBuf::from(path_a.trim());
+    let path_b = PathBuf::from(path_b.trim());
+    let diff_path = default_diff_path(&path_a, &path_b);
+    let comparison = compare_screenshot_files(&path_a, &path_b, threshold, &diff_path, &trace_id)?;
+    info!(
+        trace_id = %trace_id,
+        similarity = comparison.similarity,
+        passed = comparison.passed,
+        "compared screenshots"
+    );
+
+    Ok(CommandResponse {
+        trace_id,
+        data: comparison,
+    })
+}
+
+fn golden_screen_name(value: &str, trace_id: &str) -> Result<String, AppError> {
+    validate_screen_name(value).map_err(|message| AppError::validation(message, trace_id))
+}
+
+/// Captures `serial` into `path` and returns the capture's resolution key.
+fn capture_golden_candidate(serial: &str, path: &Path, trace_id: &str) -> Result<String, AppError> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|err| {
+            AppError::system(
+                Message::keyed("operation_failed")
+                    .with("action", "create golden dir")
+                    .with("detail", &err),
+                trace_id,
+            )
+        })?;
+    }
+    let adb_program = get_adb_program(trace_id)?;
+    let config = load_config(trace_id)?;
+    capture_screenshot_to(&adb_program, serial, &config, path, trace_id)?;
+    let image = read_png_rgba(path).map_err(|message| AppError::dependency(message, trace_id))?;
+    Ok(resolution_key(image.width, image.height))
+}
+
+/// Captures the device screen as the golden for `screen_name` at the device's current
+/// resolution, replacing any previous golden for that resolution.
+#[tauri::command(async)]
+pub fn save_golden(
+    serial: String,
+    screen_name: String,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<GoldenImage>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+    let screen_name = golden_screen_name(&screen_name, &trace_id)?;
+
+    let store = golden_store_dir();
+    let candidate = store.join(&screen_name).join(format!(
+        ".capture_{}.png",
+        sanitize_filename_component(&serial)
+    ));
+    let resolution = capture_golden_candidate(&serial, &candidate, &trace_id)?;
+    let path = golden_image_path(&store, &screen_name, &resolution);
+    fs::rename(&candidate, &path).map_err(|err| {
+        AppError::system(
+            Message::keyed("operation_failed")
+                .with("action", "store golden")
+                .with("detail", &err),
+            &trace_id,
+        )
+    })?;
+    let image = GoldenImage {
+        screen_name,
+        resolution,
+        path: path.to_string_lossy().to_string(),
+        serial,
+        saved_at: Utc::now().to_rfc3339(),
+    };
+    save_golden_meta(&image, &trace_id)?;
+    info!(trace_id = %trace_id, serial = %image.serial, screen_name = %image.screen_name, resolution = %image.resolution, "saved golden screenshot");
+
+    Ok(CommandResponse {
+        trace_id,
+        data: image,
+    })
+}
+
+/// Captures the device screen and diffs it against the golden for the same resolution.
+/// Every check is appended to the golden results report.
+#[tauri::command(async)]
+pub fn check_against_golden(
+    serial: String,
+    screen_name: String,
+    threshold: Option<f64>,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<GoldenCheckResult>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+    let screen_name = golden_screen_name(&screen_name, &trace_id)?;
+    let threshold = normalize_similarity_threshold(threshold)
+        .map_err(|message| AppError::validation(message, &trace_id))?;
+
+    let store = golden_store_dir();
+    let (capture_path, diff_path) =
+        golden_check_paths(&store, &screen_name, &sanitize_filename_component(&serial));
+    let resolution = capture_golden_candidate(&serial, &capture_path, &trace_id)?;
+    let golden_path = golden_image_path(&store, &screen_name, &resolution);
+    let comparison = if golden_path.is_file() {
+        Some(compare_screenshot_files(
+            &golden_path,
+            &capture_path,
+            threshold,
+            &diff_path,
+            &trace_id,
+        )?)
+    } else {
+        None
+    };
+    let status = match &comparison {
+        Some(comparison) if comparison.passed => GOLDEN_PASSED,
+        Some(_) => GOLDEN_FAILED,
+        None => GOLDEN_MISSING,
+    };
+    let result = GoldenCheckResult {
+        serial,
+        screen_name,
+        resolution,
+        status: status.to_string(),
+        golden_path: comparison
+            .is_some()
+            .then(|| golden_path.to_string_lossy().to_string()),
+        capture_path: capture_path.to_string_lossy().to_string(),
+        comparison,
+        checked_at: Utc::now().to_rfc3339(),
+        trace_id: trace_id.clone(),
+    };
+    if let Err(err) = append_golden_result(&golden_results_path(), &result, &trace_id) {
+        warn!(trace_id = %trace_id, error = %err, "failed to record golden check");
+    }
+    info!(trace_id = %trace_id, serial = %result.serial, screen_name = %result.screen_name, status = %result.status, "checked screen against golden");
+
+    Ok(CommandResponse {
+        trace_id,
+        data: result,
+    })
+}
+
+#[tauri::command(async)]
+pub fn list_goldens(
+    screen_name: Option<String>,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<Vec<GoldenImage>>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    let screen_name = screen_name
+        .filter(|value| !value.trim().is_empty())
+        .map(|value| golden_screen_name(&value, &trace_id))
+        .transpose()?;
+    let images = list_golden_images(&golden_store_dir(), screen_name.as_deref());
+
+    Ok(CommandResponse {
+        trace_id,
+        data: images,
+    })
+}
+
+/// Latest check per (screen, device), newest first: the device-matrix report.
+#[tauri::command(async)]
+pub fn get_golden_results(
+    screen_name: Option<String>,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<Vec<GoldenCheckResult>>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    let screen_name = screen_name
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty());
+    let results = latest_golden_results(
+        load_golden_results(&golden_results_path()),
+        screen_name.as_deref(),
+    );
+
+    Ok(CommandResponse {
+        trace_id,
+        data: results,
+    })
+}
+
+#[tauri::command(async)]
+pub fn start_screen_record(
+    serial: String,
+    state: State<'_, AppState>,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<String>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+
+    let adb_program = get_adb_program(&trace_id)?;
+    let mut guard = state
+        .recording_processes
+        .lock()
+        .map_err(|_| AppError::system("Recording registry locked", &trace_id))?;
+    if guard.contains_key(&serial) {
+        return Err(AppError::validation(
+            Message::keyed("already_active").with("subject", "Recording"),
+            &trace_id,
+        ));
+    }
+
+    let config = load_config(&trace_id)?;
+    let timestamp = Utc::now().format("%Y%m%d_%H%M%S");
+    let remote_path = format!("/sdcard/screenrecord_{}_{}.mp4", serial, timestamp);
+
+    let mut args = vec![
+        "-s".to_string(),
+        serial.clone(),
+        "shell".to_string(),
+        "screenrecord".to_string(),
+    ];
+    if !config.screen_record.bit_rate.trim().is_empty() {
+        args.push("--bit-rate".to_string());
+        args.push(config.screen_record.bit_rate.trim().to_string());
+    }
+    if config.screen_record.time_limit_sec > 0 {
+        args.push("--time-limit".to_string());
+        args.push(config.screen_record.time_limit_sec.to_string());
+    }
+    if !config.screen_record.size.trim().is_empty() {
+        args.push("--size".to_string());
+        args.push(config.screen_record.size.trim().to_string());
+    }
+    if config.screen_record.use_hevc {
+        args.push("--codec".to_string());
+        args.push("hevc".to_string());
+    }
+    if config.screen_record.bugreport {
+        args.push("--bugreport".to_string());
+    }
+    if config.screen_record.verbose {
+        args.push("--verbose".to_string());
+    }
+    if config.screen_record.display_id >= 0 {
+        args.push("--display-id".to_string());
+        args.push(config.screen_record.display_id.to_string());
+    }
+    if !config.screen_record.extra_args.trim().is_empty() {
+        args.extend(
+            config
+                .screen_record
+                .extra_args
+                .split_whitespace()
+                .map(|item| item.to_string()),
+        );
+    }
+    args.push(remote_path.clone());
+
+    let child = spawn_logged(
+        adb_command(&route_adb_program(&adb_program, &args))
+            .args(&args)
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped()),
+        &trace_id,
+    )
+    .map_err(|err| {
+        AppError::dependency(
+            Message::keyed("start_failed")
+                .with("subject", "screenrecord")
+                .with("detail", &err),
+            &trace_id,
+        )
+    })?;
+
+    record_timeline_event(
+        &serial,
+        TIMELINE_SCREEN_RECORD,
+        "Screen recording started",
+        &trace_id,
+    );
+    guard.insert(
+        serial,
+        RecordingHandle {
+            child,
+            remote_path: remote_path.clone(),
+            started_at: Instant::now(),
+        },
+    );
+
+    Ok(CommandResponse {
+        trace_id,
+        data: remote_path,
+    })
+}
+
+#[tauri::command(async)]
+pub fn stop_screen_record(
+    serial: String,
+    output_dir: Option<String>,
+    convert_format: Option<String>,
+    app: AppHandle,
+    state: State<'_, AppState>,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<String>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+
+    let adb_program = get_adb_program(&trace_id)?;
+    let mut guard = state
+        .recording_processes
+        .lock()
+        .map_err(|_| AppError::system("Recording registry locked", &trace_id))?;
+
+    let handle = match guard.remove(&serial) {
+        Some(handle) => handle,
+        None => {
+            return Err(AppError::validation(
+                Message::keyed("no_recording"),
+                &trace_id,
+            ))
+        }
+    };
+    let mut child = handle.child;
+    let recorded_for = handle.started_at.elapsed();
+
+    let _ = adb_command_for_serial(&adb_program, &serial)
+        .args(["-s", &serial, "shell", "pkill", "-SIGINT", "screenrecord"])
+        .output();
+
+    let timeout = Duration::from_secs(5);
+    let start = std::time::Instant::now();
+    loop {
+        match child.try_wait() {
+            Ok(Some(_)) => break,
+            Ok(None) => {
+                if start.elapsed() >= timeout {
+                    let _ = child.kill();
+                    let _ = child.wait();
+                    return Err(AppError::system(
+                        "Timeout waiting for screenrecord",
+                        &trace_id,
+                    ));
+                }
+                std::thread::sleep(Duration::from_millis(100));
+            }
+            Err(err) => {
+                return Err(AppError::system(
+                    Message::keyed("operation_failed")
+                        .with("action", "stop screenrecord")
+                        .with("detail", &err),
+                    &trace_id,
+                ));
+            }
+        }
+    }
+
+    let config = load_config(&trace_id)?;
+    let output_dir = match resolve_artifact_dir(
+        &config,
+        ArtifactKind::Recording,
+        output_dir.as_deref(),
+        Local::now().date_naive(),
+    ) {
+        Some(dir) => dir,
+        None => {
+            return Ok(CommandResponse {
+                trace_id,
+                data: String::new(),
+            })
+        }
+    };
+
+    fs::create_dir_all(&output_dir).map_err(|err| {
+        AppError::system(
+            Message::keyed("operation_failed")
+                .with("action", "create output dir")
+                .with("detail", &err),
+            &trace_id,
+        )
+    })?;
+    if let Some(estimated) =
+        remote_artifact_bytes(&adb_program, &serial, &handle.remote_path, &trace_id)
+    {
+        ensure_host_storage(&output_dir, estimated, &trace_id)?;
+    }
+
+    let filename = PathBuf::from(&handle.remote_path)
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| format!("screenrecord_{}.mp4", serial));
+    let local_path = output_dir.join(filename);
+
+    let args = vec![
+        "-s".to_string(),
+        serial.clone(),
+        "pull".to_string(),
+        handle.remote_path.clone(),
+        local_path.to_string_lossy().to_string(),
+    ];
+    let output = run_adb(&adb_program, &args, &trace_id)?;
+    if output.exit_code.unwrap_or_default() != 0 {
+        return Err(AppError::dependency(
+            Message::keyed("action_failed")
+                .with("action", "Pull")
+                .with("detail", &output.stderr),
+            &trace_id,
+        ));
+    }
+
+    let convert_format = convert_format
+        .filter(|value| !value.trim().is_empty())
+        .unwrap_or_else(|| config.screen_record.convert_format.clone());
+    let local_path = local_path.to_string_lossy().to_string();
+    if !convert_format.trim().is_empty() {
+        start_recording_conversion(
+            app,
+            state.inner(),
+            serial.clone(),
+            local_path.clone(),
+            convert_format,
+            config,
+            recorded_for,
+            trace_id.clone(),
+        );
+    }
+
+    record_timeline_event(
+        &serial,
+        TIMELINE_SCREEN_RECORD,
+        &format!("Screen recording saved to {local_path}"),
+        &trace_id,
+    );
+    Ok(CommandResponse {
+        trace_id,
+        data: local_path,
+    })
+}
+
+const SCREEN_RECORD_CONVERSION_PROGRESS_EVENT: &str = "screen-record-conversion-progress";
+/// How long app exit waits for background conversions before leaving them half written.
+const RECORDING_CONVERSION_EXIT_WAIT: Duration = Duration::from_secs(30);
+
+/// Running background conversions, with finished ones dropped from the registry.
+fn active_recording_conversions(
+    state: &AppState,
+) -> Option<std::sync::MutexGuard<'_, HashMap<String, RecordingConversionHandle>>> {
+    match state.recording_conversions.lock() {
+        Ok(mut guard) => {
+            guard.retain(|_, handle| !handle.join.is_finished());
+            Some(guard)
+        }
+        Err(_) => {
+            warn!("recording conversion registry poisoned");
+            None
+        }
+    }
+}
+
+/// Convert a pulled recording on a background thread so `stop_screen_record` returns once the
+/// MP4 is saved. Emits progress while ffmpeg runs and `screen-record-converted` at the end.
+#[allow(clippy::too_many_arguments)]
+fn start_recording_conversion(
+    app: AppHandle,
+    state: &AppState,
+    serial: String,
+    source_path: String,
+    format: String,
+    config: AppConfig,
+    recorded_for: Duration,
+    trace_id: String,
+) {
+    let Some(mut conversions) = active_recording_conversions(state) else {
+        return;
+    };
+    if conversions.contains_key(&source_path) {
+        warn!(trace_id = %trace_id, source_path = %source_path, "recording is already being converted");
+        return;
+    }
+    let key = source_path.clone();
+    let handle_serial = serial.clone();
+    let handle_format = format.trim().to_lowercase();
+    let join = std::thread::spawn(move || {
+        let mut last_emit: Option<Instant> = None;
+        let mut report = |converted: Duration| {
+            if last_emit.is_some_and(|at| at.elapsed() < Duration::from_millis(500)) {
+                return;
+            }
+            last_emit = Some(Instant::now());
+            let progress = ScreenRecordConversionProgress {
+                serial: serial.clone(),
+                source_path: source_path.clone(),
+                format: format.trim().to_lowercase(),
+                converted_ms: converted.as_millis() as u64,
+                percent: conversion_percent(converted, recorded_for),
+            };
+            if let Err(err) = emit_tracked(
+                &app,
+                SCREEN_RECORD_CONVERSION_PROGRESS_EVENT,
+                serde_json::json!({ "trace_id": trace_id, "progress": progress }),
+            ) {
+                warn!(trace_id = %trace_id, error = %err, "failed to emit screen record conversion progress");
+            }
+        };
+        let result = match convert_screen_recording_inner(
+            &source_path,
+            &format,
+            &config,
+            &mut report,
+            &trace_id,
+        ) {
+            Ok(result) => result,
+            Err(err) => {
+                warn!(
+                    trace_id = %trace_id,
+                    serial = %serial,
+                    error = %err,
+                    "screen record conversion failed"
+                );
+                ScreenRecordConversionResult {
+                    source_path: source_path.clone(),
+                    output_path: None,
+                    format: format.trim().to_lowercase(),
+                    size_bytes: None,
+                    error: Some(err.error),
+                }
+            }
+        };
+        if let Err(err) = emit_tracked(
+            &app,
+            "screen-record-converted",
+            serde_json::json!({
+                "trace_id": trace_id,
+                "serial": serial,
+                "result": result,
+            }),
+        ) {
+            warn!(trace_id = %trace_id, error = %err, "failed to emit screen record conversion");
+        }
+    });
+    conversions.insert(
+        key,
+        RecordingConversionHandle {
+            serial: handle_serial,
+            format: handle_format,
+            started_at: Instant::now(),
+            join,
+        },
+    );
+}
+
+/// Recording conversions still running in the background.
+#[tauri::command(async)]
+pub fn list_recording_conversions(
+    state: State<'_, AppState>,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<Vec<RecordingConversionStatus>>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    let conversions = active_recording_conversions(state.inner())
+        .ok_or_else(|| AppError::system("Recording conversion registry locked", &trace_id))?;
+    let mut data: Vec<RecordingConversionStatus> = conversions
+        .iter()
+        .map(|(source_path, handle)| RecordingConversionStatus {
+            serial: handle.serial.clone(),
+            source_path: source_path.clone(),
+            format: handle.format.clone(),
+            elapsed_ms: handle.started_at.elapsed().as_millis() as u64,
+        })
+        .collect();
+    data.sort_by(|a, b| a.source_path.cmp(&b.source_path));
+    Ok(CommandResponse { trace_id, data })
+}
+
+/// Give background conversions a bounded chance to finish when the app exits.
+pub fn join_recording_conversions(state: &AppState) {
+    let handles: Vec<(String, RecordingConversionHandle)> = match state.recording_conversions.lock()
+    {
+        Ok(mut guard) => guard.drain().collect(),
+        Err(_) => {
+            warn!("recording conversion registry poisoned; not waiting for conversions");
+            return;
+        }
+    };
+    let deadline = Instant::now() + RECORDING_CONVERSION_EXIT_WAIT;
+    for (source_path, handle) in handles {
+        while !handle.join.is_finished() && Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(100));
+        }
+        if handle.join.is_finished() {
+            if handle.join.join().is_err() {
+                warn!(source_path = %source_path, "recording conversion thread panicked");
+            }
+        } else {
+            warn!(source_path = %source_path, "recording conversion still running at exit");
+        }
+    }
+}
+
+/// Run ffmpeg, passing the media time converted so far from its `-progress` output to
+/// `on_progress`.
+fn run_ffmpeg_with_progress(
+    program: &str,
+    args: &[String],
+    timeout: Duration,
+    on_progress: &mut dyn FnMut(Duration),
+    trace_id: &str,
+) -> Result<CommandOutput, AppError> {
+    let mut child = std::process::Command::new(program)
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|err| {
+            AppError::dependency(
+                Message::keyed("operation_failed")
+                    .with("action", "run ffmpeg")
+                    .with("detail", &err),
+                trace_id,
+            )
+        })?;
+    let stdout = child
+        .stdout
+        .take()
+        .ok_or_else(|| AppError::system("Failed to capture ffmpeg stdout", trace_id))?;
+    let mut stderr = child
+        .stderr
+        .take()
+        .ok_or_else(|| AppError::system("Failed to capture ffmpeg stderr", trace_id))?;
+    let (sender, receiver) = std::sync::mpsc::channel::<Duration>();
+    std::thread::spawn(move || {
+        for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+            if let Some(converted) = parse_ffmpeg_progress(&line) {
+                if sender.send(converted).is_err() {
+                    break;
+                }
+            }
+        }
+    });
+    let stderr_reader = std::thread::spawn(move || {
+        let mut text = String::new();
+        let _ = stderr.read_to_string(&mut text);
+        text
+    });
+
+    let started = Instant::now();
+    let status = loop {
+        while let Ok(converted) = receiver.try_recv() {
+            on_progress(converted);
+        }
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if started.elapsed() >= timeout => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(AppError::system("Command timed out", trace_id));
+            }
+            Ok(None) => {
+                if let Ok(converted) = receiver.recv_timeout(Duration::from_millis(200)) {
+                    on_progress(converted);
+                }
+            }
+            Err(err) => {
+                return Err(AppError::system(
+                    Message::keyed("operation_failed")
+                        .with("action", "wait for ffmpeg")
+                        .with("detail", &err),
+                    trace_id,
+                ))
+            }
+        }
+    };
+    let stderr = stderr_reader.join().unwrap_or_default();
+    Ok(CommandOutput {
+        stdout: String::new(),
+        stderr,
+        exit_code: status.code(),
+    })
+}
+
+fn convert_screen_recording_inner(
+    input_path: &str,
+    format: &str,
+    config: &AppConfig,
+    on_progress: &mut dyn FnMut(Duration),
+    trace_id: &str,
+) -> Result<ScreenRecordConversionResult, AppError> {
+    ensure_non_empty(input_path, "input_path", trace_id)?;
+    let format =
+        normalize_convert_format(format).map_err(|err| AppError::validation(err, trace_id))?;
+    let input = PathBuf::from(input_path);
+    if !input.is_file() {
+        return Err(AppError::validation(
+            Message::keyed("does_not_exist").with("subject", "Recording file"),
+            trace_id,
+        ));
+    }
+    let output = converted_output_path(&input, &format);
+    let output_path = output.to_string_lossy().to_string();
+    let args = build_ffmpeg_convert_args(
+        input_path,
+        &output_path,
+        &format,
+        config.screen_record.convert_max_width,
+        config.screen_record.convert_fps,
+    )
+    .map_err(|err| AppError::validation(err, trace_id))?;
+
+    let ffmpeg_path = normalize_command_path(&config.screen_record.ffmpeg_path);
+    let ffmpeg_program = if ffmpeg_path.is_empty() {
+        "ffmpeg".to_string()
+    } else {
+        ffmpeg_path
+    };
+    let result = run_ffmpeg_with_progress(
+        &ffmpeg_program,
+        &args,
+        command_timeout(TimeoutKind::Transfer, trace_id),
+        on_progress,
+        trace_id,
+    )
+    .map_err(|err| {
+        AppError::dependency(
+            Message::keyed("operation_failed")
+                .with("action", "run ffmpeg")
+                .with("detail", &err.error),
+            trace_id,
+        )
+    })?;
+    if result.exit_code.unwrap_or_default() != 0 {
+        return Err(AppError::dependency(
+            Message::keyed("action_failed")
+                .with("action", "Recording conversion")
+                .with("detail", result.stderr.trim()),
+            trace_id,
+        ));
+    }
+    let size_bytes = fs::metadata(&output).ok().map(|meta| meta.len());
+
+    Ok(ScreenRecordConversionResult {
+        source_path: input_path.to_string(),
+        output_path: Some(output_path),
+        format,
+        size_bytes,
+        error: None,
+    })
+}
+
+#[tauri::command(async)]
+pub fn convert_screen_recording(
+    input_path: String,
+    format: String,
+    state: State<'_, AppState>,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<ScreenRecordConversionResult>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    let config = load_config(&trace_id)?;
+    if active_recording_conversions(state.inner())
+        .is_some_and(|conversions| conversions.contains_key(input_path.trim()))
+    {
+        return Err(AppError::validation(
+            Message::keyed("already_active").with("subject", "Recording conversion"),
+            &trace_id,
+        ));
+    }
+    let result =
+        convert_screen_recording_inner(&input_path, &format, &config, &mut |_| {}, &trace_id)?;
+
+    Ok(CommandResponse {
+        trace_id,
+        data: result,
+    })
+}
+
+#[tauri::command(async)]
+pub fn list_device_files(
+    serial: String,
+    path: String,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<Vec<DeviceFileEntry>>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+    ensure_non_empty(&path, "path", &trace_id)?;
+
+    let normalized = path.trim().to_string();
+    if let Some(entries) = cached_read::<Vec<DeviceFileEntry>>(
+        CACHE_SCOPE_DEVICE_FILES,
+        &serial,
+        &normalized,
+        &trace_id,
+    ) {
+        return Ok(CommandResponse {
+            trace_id,
+            data: entries,
+        });
+    }
+    let adb_program = get_adb_program(&trace_id)?;
+    let dir_hint = if normalized == "/" {
+        "/".to_string()
+    } else {
+        format!("{}/", normalized.trim_end_matches('/'))
+    };
+    let dir_args = vec![
+        "-s".to_string(),
+        serial.clone(),
+        "shell".to_string(),
+        "ls".to_string(),
+        "-la".to_string(),
+        dir_hint,
+    ];
+    let mut output = run_command_with_timeout(
+        &adb_program,
+        &dir_args,
+        command_timeout(TimeoutKind::Transfer, &trace_id),
+        &trace_id,
+    )?;
+    if output.exit_code.unwrap_or_default() != 0 {
+        let fallback_args = vec![
+            "-s".to_string(),
+            serial.clone(),
+            "shell".to_string(),
+            "ls".to_string(),
+            "-la".to_string(),
+            normalized.clone(),
+        ];
+        output = run_command_with_timeout(
+            &adb_program,
+            &fallback_args,
+            command_timeout(TimeoutKind::Transfer, &trace_id),
+            &trace_id,
+        )?;
+    }
+    let entries = parse_ls_la(&normalized, &output.stdout);
+    store_read(
+        CACHE_SCOPE_DEVICE_FILES,
+        &serial,
+        &normalized,
+        &entries,
+        &trace_id,
+    );
+
+    Ok(CommandResponse {
+        trace_id,
+        data: entries,
+    })
+}
+
+fn remote_artifact_bytes(
+    adb_program: &str,
+    serial: &str,
+    device_path: &str,
+    trace_id: &str,
+) -> Option<u64> {
+    let args = vec![
+        "-s".to_string(),
+        serial.to_string(),
+        "shell".to_string(),
+        "du".to_string(),
+        "-sk".to_string(),
+        device_path.to_string(),
+    ];
+    match run_command_with_timeout(
+        adb_program,
+        &args,
+        command_timeout(TimeoutKind::DeviceCommand, trace_id),
+        trace_id,
+    ) {
+        Ok(output) if output.exit_code.unwrap_or_default() == 0 => {
+            parse_du_kb(&output.stdout).map(|kb| kb.saturating_mul(1024))
+        }
+        Ok(output) => {
+            warn!(trace_id = %trace_id, stderr = %output.stderr.trim(), "remote size lookup failed");
+            None
+        }
+        Err(err) => {
+            warn!(trace_id = %trace_id, error = %err, "remote size lookup failed");
+            None
+        }
+    }
+}
+
+#[tauri::command(async)]
+#[allow(clippy::too_many_arguments)]
+pub fn pull_device_file(
+    serial: String,
+    device_path: String,
+    output_dir: String,
+    resume: Option<bool>,
+    compress: Option<bool>,
+    app: AppHandle,
+    state: State<'_, AppState>,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<String>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+    ensure_non_empty(&device_path, "device_path", &trace_id)?;
+    ensure_non_empty(&output_dir, "output_dir", &trace_id)?;
+
+    let cancel = begin_file_transfer(&state, &serial, "pull", &trace_id)?;
+    let result = pull_device_file_inner(
+        &serial,
+        &device_path,
+        &output_dir,
+        resume.unwrap_or(false),
+        compress,
+        &cancel,
+        &app,
+        &trace_id,
+    );
+    end_file_transfer(&state, &trace_id);
+    Ok(CommandResponse {
+        trace_id,
+        data: result?,
+    })
+}
+
+fn device_has_gzip(adb_program: &str, serial: &str, trace_id: &str) -> bool {
+    let args = vec![
+        "-s".to_string(),
+        serial.to_string(),
+        "shell".to_string(),
+        GZIP_PROBE_COMMAND.to_string(),
+    ];
+    match run_command_with_timeout(
+        adb_program,
+        &args,
+        command_timeout(TimeoutKind::DeviceCommand, trace_id),
+        trace_id,
+    ) {
+        Ok(output) if output.exit_code.unwrap_or_default() == 0 => {
+            let found = !output.stdout.trim().is_empty();
+            if !found {
+                warn!(trace_id = %trace_id, serial = %serial, "device has no gzip, pulling uncompressed");
+            }
+            found
+        }
+        Ok(_) => {
+            warn!(trace_id = %trace_id, serial = %serial, "device has no gzip, pulling uncompressed");
+            false
+        }
+        Err(err) => {
+            warn!(trace_id = %trace_id, serial = %serial, error = %err, "gzip probe failed, pulling uncompressed");
+            false
+        }
+    }
+}
+
+/// Sync `stat` when the socket client can serve it, otherwise `stat -c` through adb shell.
+fn device_file_stat(
+    adb_program: &str,
+    serial: &str,
+    device_path: &str,
+    trace_id: &str,
+) -> Option<SyncStat> {
+    if let Some(stat) = stat_device_path(
+        adb_program,
+        serial,
+        device_path,
+        command_timeout(TimeoutKind::DeviceCommand, trace_id),
+        trace_id,
+    ) {
+        return Some(stat);
+    }
+    let args = vec![
+        "-s".to_string(),
+        serial.to_string(),
+        "shell".to_string(),
+        "stat".to_string(),
+        "-c".to_string(),
+        "'%f %s %Y'".to_string(),
+        quote_shell_value(device_path),
+    ];
+    match run_command_with_timeout(
+        adb_program,
+        &args,
+        command_timeout(TimeoutKind::DeviceCommand, trace_id),
+        trace_id,
+    ) {
+        Ok(output) if output.exit_code.unwrap_or_default() == 0 => {
+            parse_stat_mode_size_mtime(&output.stdout)
+        }
+        Ok(output) => {
+            warn!(trace_id = %trace_id, stderr = %output.stderr.trim(), "device file stat failed");
+            None
+        }
+        Err(err) => {
+            warn!(trace_id = %trace_id, error = %err, "device file stat failed");
+            None
+        }
+    }
+}
+
+/// Chunked pull for large files: each `dd` chunk comes over `exec:` on the adb server socket,
+/// or `adb exec-out` when the socket client cannot serve this device.
+#[allow(clippy::too_many_arguments)]
+fn pull_device_file_chunked(
+    adb_program: &str,
+    client: Option<&AdbSocketClient>,
+    serial: &str,
+    device_path: &str,
+    stat: &SyncStat,
+    local_path: &Path,
+    cancel: &AtomicBool,
+    app: &AppHandle,
+    trace_id: &str,
+) -> Result<(), AppError> {
+    let mut fetch = |offset: u64, len: u64| -> Result<Vec<u8>, SocketError> {
+        let command = dd_chunk_command(device_path, offset, len);
+        let args = vec![
+            "-s".to_string(),
+            serial.to_string(),
+            "exec-out".to_string(),
+            command,
+        ];
+        if let Some(client) = client {
+            let started_at = Utc::now();
+            let started = Instant::now();
+            let result = exec_out(client, serial, &args[3], cancel);
+            let outcome = result
+                .as_ref()
+                .map_err(Clone::clone)
+                .map(|bytes| TransferOutcome {
+                    bytes: bytes.len() as u64,
+                    resumed_from: 0,
+                });
+            record_sync_transfer(adb_program, &args, trace_id, started_at, started, &outcome);
+            return result;
+        }
+        let output =
+            run_binary_command_with_timeout(adb_program, &args, TRANSFER_IDLE_TIMEOUT, trace_id)
+                .map_err(|err| SocketError::Io(err.error))?;
+        if output.exit_code.unwrap_or_default() != 0 {
+            return Err(SocketError::Io(output.stderr.trim().to_string()));
+        }
+        Ok(output.stdout)
+    };
+    let mut report =
+        file_transfer_progress_reporter(app.clone(), serial, "pull", trace_id, stat.size);
+    let mut on_retry = |attempt: u32, error: &str| {
+        warn!(trace_id = %trace_id, serial = %serial, attempt, error = %error, "chunked pull chunk failed, retrying");
+        if let Err(err) = emit_tracked(
+            app,
+            FILE_TRANSFER_PROGRESS_EVENT,
+            FileTransferProgressEvent {
+                serial: serial.to_string(),
+                direction: "pull".to_string(),
+                progress: None,
+                message: Some(format!(
+                    "Waiting for device (retry {attempt}/{})",
+                    CHUNK_ATTEMPTS - 1
+                )),
+                bytes_transferred: None,
+                total_bytes: None,
+                trace_id: trace_id.to_string(),
+            },
+        ) {
+            warn!(trace_id = %trace_id, error = %err, "failed to emit file transfer progress");
+        }
+    };
+
+    match pull_chunked(
+        device_path,
+        stat,
+        local_path,
+        cancel,
+        &mut fetch,
+        &mut report,
+        &mut on_retry,
+    ) {
+        Ok(outcome) => {
+            info!(
+                trace_id = %trace_id,
+                serial = %serial,
+                bytes = outcome.bytes,
+                resumed_from = outcome.resumed_from,
+                "pulled file in chunks"
+            );
+            Ok(())
+        }
+        Err(err) => Err(
+            sync_transfer_error(err.clone(), "Pull", trace_id).unwrap_or_else(|| {
+                AppError::dependency(
+                    Message::keyed("action_failed")
+                        .with("action", "Pull")
+                        .with("detail", &err),
+                    trace_id,
+                )
+            }),
+        ),
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn pull_device_file_inner(
+    serial: &str,
+    device_path: &str,
+    output_dir: &str,
+    resume: bool,
+    compress: Option<bool>,
+    cancel: &AtomicBool,
+    app: &AppHandle,
+    trace_id: &str,
+) -> Result<String, AppError> {
+    let adb_program = get_adb_program(trace_id)?;
+    fs::create_dir_all(output_dir).map_err(|err| {
+        AppError::system(
+            Message::keyed("operation_failed")
+                .with("action", "create output dir")
+                .with("detail", &err),
+            trace_id,
+        )
+    })?;
+
+    let filename = PathBuf::from(device_path)
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| "output".to_string());
+    let local_path = PathBuf::from(output_dir).join(filename);
+
+    let client = socket_client_for_serial(&adb_program, serial);
+    let remote_stat = device_file_stat(&adb_program, serial, device_path, trace_id)
+        .filter(|stat| is_regular_file(stat.mode));
+    if let Some(stat) = remote_stat {
+        ensure_host_storage(Path::new(output_dir), stat.size, trace_id)?;
+    } else if let Some(estimated) =
+        remote_artifact_bytes(&adb_program, serial, device_path, trace_id)
+    {
+        ensure_host_storage(Path::new(output_dir), estimated, trace_id)?;
+    }
+
+    let compress_pull = remote_stat.is_some()
+        && {
+            let auto_wireless = compress.is_none()
+                && match load_config(trace_id) {
+                    Ok(config) => config.adb.compress_wireless_pulls,
+                    Err(err) => {
+                        warn!(trace_id = %trace_id, error = %err, "failed to load config, pulling uncompressed");
+                        false
+                    }
+                };
+            should_compress(compress, serial, auto_wireless)
+                && device_has_gzip(&adb_program, serial, trace_id)
+        };
+    if let Some(stat) = remote_stat.filter(|_| compress_pull) {
+        let mut report =
+            file_transfer_progress_reporter(app.clone(), serial, "pull", trace_id, stat.size);
+        let result = match client.as_ref() {
+            Some(client) => pull_compressed_via_socket(
+                client,
+                serial,
+                device_path,
+                stat.size,
+                &local_path,
+                cancel,
+                &mut report,
+            ),
+            None => pull_compressed_via_adb(
+                &adb_program,
+                serial,
+                device_path,
+                stat.size,
+                &local_path,
+                cancel,
+                &mut report,
+            ),
+        };
+        match result {
+            Ok(outcome) => {
+                info!(
+                    trace_id = %trace_id,
+                    serial = %serial,
+                    bytes = outcome.bytes,
+                    wire_bytes = outcome.wire_bytes,
+                    "pulled file with gzip"
+                );
+                return Ok(local_path.to_string_lossy().to_string());
+            }
+            Err(err) => {
+                if let Some(err) = sync_transfer_error(err.clone(), "Pull", trace_id) {
+                    return Err(err);
+                }
+                warn!(trace_id = %trace_id, serial = %serial, error = %err, "compressed pull unavailable, pulling uncompressed");
+            }
+        }
+    }
+
+    if let Some(stat) = remote_stat.filter(|stat| stat.size >= CHUNKED_PULL_MIN_BYTES) {
+        pull_device_file_chunked(
+            &adb_program,
+            client.as_ref(),
+            serial,
+            device_path,
+            &stat,
+            &local_path,
+            cancel,
+            app,
+            trace_id,
+        )?;
+        return Ok(local_path.to_string_lossy().to_string());
+    }
+
+    // Regular files go over the sync protocol for byte-level progress and resume; directories
+    // and servers the socket client cannot reach use `adb pull -p`.
+    if let (Some(client), Some(stat)) = (client, remote_stat) {
+        let mut report =
+            file_transfer_progress_reporter(app.clone(), serial, "pull", trace_id, stat.size);
+        let started_at = Utc::now();
+        let started = Instant::now();
+        let result = pull_file(
+            &client,
+            serial,
+            device_path,
+            stat.size,
+            &local_path,
+            resume,
+            cancel,
+            &mut report,
+        );
+        let args = [
+            "-s",
+            serial,
+            "pull",
+            device_path,
+            &local_path.to_string_lossy(),
+        ]
+        .map(str::to_string);
+        record_sync_transfer(&adb_program, &args, trace_id, started_at, started, &result);
+        match result {
+            Ok(outcome) => {
+                info!(
+                    trace_id = %trace_id,
+                    serial = %serial,
+                    bytes = outcome.bytes,
+                    resumed_from = outcome.resumed_from,
+                    "pulled file over adb sync"
+                );
+                return Ok(local_path.to_string_lossy().to_string());
+            }
+            Err(err) => {
+                if let Some(err) = sync_transfer_error(err.clone(), "Pull", trace_id) {
+                    return Err(err);
+                }
+                warn!(trace_id = %trace_id, serial = %serial, error = %err, "adb sync pull unavailable, spawning adb");
+                let part = partial_path(&local_path);
+                if fs::metadata(&part).is_ok_and(|meta| meta.len() == 0) {
+                    let _ = fs::remove_file(part);
+                }
+            }
+        }
+    }
+
+    let args = vec![
+        "-s".to_string(),
+        serial.to_string(),
+        "pull".to_string(),
+        "-p".to_string(),
+        device_path.to_string(),
+        output_dir.to_string(),
+    ];
+    let mut output = run_adb_transfer_with_progress(
+        &adb_program,
+        &args,
+        command_timeout(TimeoutKind::Transfer, trace_id),
+        serial,
+        "pull",
+        cancel,
+        trace_id,
+        app.clone(),
+    )?;
+    if output.exit_code.unwrap_or_default() != 0 {
+        let combined = format!("{}\n{}", output.stdout, output.stderr).to_lowercase();
+        if combined.contains("unknown option") && combined.contains("-p") {
+            let fallback_args: Vec<String> = args
+                .iter()
+                .filter(|value| value.as_str() != "-p")
+                .cloned()
+                .collect();
+            output = run_adb_transfer_with_progress(
+                &adb_program,
+                &fallback_args,
+                command_timeout(TimeoutKind::Transfer, trace_id),
+                serial,
+                "pull",
+                cancel,
+                trace_id,
+                app.clone(),
+            )?;
+        }
+    }
+    if output.exit_code.unwrap_or_default() != 0 {
+        return Err(AppError::dependency(
+            Message::keyed("action_failed")
+                .with("action", "Pull")
+                .with("detail", &output.stderr),
+            trace_id,
+        ));
+    }
+
+    Ok(local_path.to_string_lossy().to_string())
+}
+
+#[allow(clippy::too_many_arguments)]
+fn push_device_file_inner(
+    adb_program: &str,
+    serial: &str,
+    local_path: &Path,
+    device_path: &str,
+    resume: bool,
+    cancel: &AtomicBool,
+    app: &AppHandle,
+    trace_id: &str,
+) -> Result<(), AppError> {
+    let device_dir = device_parent_dir(device_path);
+    if device_dir != "/" {
+        let mkdir_args = vec![
+            "-s".to_string(),
+            serial.to_string(),
+            "shell".to_string(),
+            "mkdir".to_string(),
+            "-p".to_string(),
+            device_dir,
+        ];
+        let mkdir_output = run_command_with_timeout(
+            adb_program,
+            &mkdir_args,
+            command_timeout(TimeoutKind::DeviceCommand, trace_id),
+            trace_id,
+        )?;
+        if mkdir_output.exit_code.unwrap_or_default() != 0 {
+            return Err(AppError::dependency(
+                Message::keyed("operation_failed")
+                    .with("action", "create device directory")
+                    .with("detail", &mkdir_output.stderr),
+                trace_id,
+            ));
+        }
+    }
+
+    if let Some(client) = socket_client_for_serial(adb_program, serial) {
+        let total = fs::metadata(local_path)
+            .map(|meta| meta.len())
+            .map_err(|err| {
+                AppError::system(
+                    Message::keyed("operation_failed")
+                        .with("action", "read local file")
+                        .with("detail", &err),
+                    trace_id,
+                )
+            })?;
+        let mut report =
+            file_transfer_progress_reporter(app.clone(), serial, "push", trace_id, total);
+        let started_at = Utc::now();
+        let started = Instant::now();
+        let result = push_file(
+            &client,
+            serial,
+            local_path,
+            device_path,
+            resume,
+            cancel,
+            &mut report,
+        );
+        let args = [
+            "-s",
+            serial,
+            "push",
+            &local_path.to_string_lossy(),
+            device_path,
+        ]
+        .map(str::to_string);
+        record_sync_transfer(adb_program, &args, trace_id, started_at, started, &result);
+        match result {
+            Ok(outcome) => {
+                info!(
+                    trace_id = %trace_id,
+                    serial = %serial,
+                    bytes = outcome.bytes,
+                    resumed_from = outcome.resumed_from,
+                    "pushed file over adb sync"
+                );
+                return Ok(());
+            }
+            Err(err) => {
+                if let Some(err) = sync_transfer_error(err.clone(), "Push", trace_id) {
+                    return Err(err);
+                }
+                warn!(trace_id = %trace_id, serial = %serial, error = %err, "adb sync push unavailable, spawning adb");
+            }
+        }
+    }
+
+    let args = vec![
+        "-s".to_string(),
+        serial.to_string(),
+        "push".to_string(),
+        "-p".to_string(),
+        local_path.to_string_lossy().to_string(),
+        device_path.to_string(),
+    ];
+    let mut output = run_adb_transfer_with_progress(
+        adb_program,
+        &args,
+        command_timeout(TimeoutKind::Transfer, trace_id),
+        serial,
+        "push",
+        cancel,
+        trace_id,
+        app.clone(),
+    )?;
+    if output.exit_code.unwrap_or_default() != 0 {
+        let combined = format!("{}\n{}", output.stdout, output.stderr).to_lowercase();
+        if combined.contains("unknown option") && combined.contains("-p") {
+            let fallback_args: Vec<String> = args
+                .iter()
+                .filter(|value| value.as_str() != "-p")
+                .cloned()
+                .collect();
+            output = run_adb_transfer_with_progress(
+                adb_program,
+                &fallback_args,
+                command_timeout(TimeoutKind::Transfer, trace_id),
+                serial,
+                "push",
+                cancel,
+                trace_id,
+                app.clone(),
+            )?;
+        }
+    }
+    if output.exit_code.unwrap_or_default() != 0 {
+        return Err(AppError::dependency(
+            Message::keyed("action_failed")
+                .with("action", "Push")
+                .with("detail", &output.stderr),
+            trace_id,
+        ));
+    }
+
+    Ok(())
+}
+
+#[tauri::command(async)]
+#[allow(clippy::too_many_arguments)]
+pub fn push_device_file(
+    serial: String,
+    local_path: String,
+    device_path: String,
+    media_scan: Option<bool>,
+    resume: Option<bool>,
+    app: AppHandle,
+    state: State<'_, AppState>,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<String>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+    ensure_non_empty(&local_path, "local_path", &trace_id)?;
+    ensure_non_empty(&device_path, "device_path", &trace_id)?;
+
+    if let Err(message) = validate_device_path(&device_path) {
+        return Err(AppError::validation(message, &trace_id));
+    }
+
+    let host_path = PathBuf::from(&local_path);
+    if !host_path.exists() {
+        return Err(AppError::validation(
+            Message::keyed("does_not_exist").with("subject", "Local file"),
+            &trace_id,
+        ));
+    }
+    if !host_path.is_file() {
+        return Err(AppError::validation(
+            Message::keyed("local_path_not_file"),
+            &trace_id,
+        ));
+    }
+
+    let adb_program = get_adb_program(&trace_id)?;
+    let cancel = begin_file_transfer(&state, &serial, "push", &trace_id)?;
+    let result = push_device_file_inner(
+        &adb_program,
+        &serial,
+        &host_path,
+        &device_path,
+        resume.unwrap_or(false),
+        &cancel,
+        &app,
+        &trace_id,
+    );
+    end_file_transfer(&state, &trace_id);
+    result?;
+    bust_read_cache(Some(CACHE_SCOPE_DEVICE_FILES), Some(&serial));
+
+    if media_scan.unwrap_or_else(|| is_media_file_path(&device_path)) {
+        if let Err(err) = trigger_media_scan(&adb_program, &serial, &device_path, &trace_id) {
+            warn!(
+                trace_id = %trace_id,
+                serial = %serial,
+                error = %err,
+                "media scan after push failed"
+            );
+        }
+    }
+
+    Ok(CommandResponse {
+        trace_id,
+        data: device_path,
+    })
+}
+
+#[tauri::command(async)]
+pub fn cancel_file_transfer(
+    transfer_id: String,
+    state: State<'_, AppState>,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<bool>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&transfer_id, "transfer_id", &trace_id)?;
+
+    let guard = state
+        .file_transfers
+        .lock()
+        .map_err(|_| AppError::system("File transfer registry locked", &trace_id))?;
+    let Some(handle) = guard.get(&transfer_id) else {
+        return Err(AppError::validation(
+            Message::keyed("not_running").with("subject", "File transfer"),
+            &trace_id,
+        ));
+    };
+    handle.cancel_flag.store(true, Ordering::Relaxed);
+    info!(
+        trace_id = %trace_id,
+        transfer_id = %transfer_id,
+        serial = %handle.serial,
+        direction = %handle.direction,
+        "file transfer cancel requested"
+    );
+
+    Ok(CommandResponse {
+        trace_id,
+        data: true,
+    })
+}
+
+fn trigger_media_scan(
+    adb_program: &str,
+    serial: &str,
+    device_path: &str,
+    trace_id: &str,
+) -> Result<(), AppError> {
+    let args = vec![
+        "-s".to_string(),
+        serial.to_string(),
+        "shell".to_string(),
+        "am".to_string(),
+        "broadcast".to_string(),
+        "-a".to_string(),
+        "android.intent.action.MEDIA_SCANNER_SCAN_FILE".to_string(),
+        "-d".to_string(),
+        media_scan_file_uri(device_path),
+    ];
+    let output = run_command_with_timeout(
+        adb_program,
+        &args,
+        command_timeout(TimeoutKind::DeviceCommand, trace_id),
+        trace_id,
+    )?;
+    if output.exit_code.unwrap_or_default() != 0 {
+        return Err(AppError::dependency(
+            Message::keyed("action_failed")
+                .with("action", "Media scan broadcast")
+                .with("detail", &output.stderr),
+            trace_id,
+        ));
+    }
+    Ok(())
+}
+
+#[tauri::command(async)]
+pub fn mkdir_device_dir(
+    serial: String,
+    device_path: String,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<String>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+    ensure_non_empty(&device_path, "device_path", &trace_id)?;
+
+    if let Err(message) = validate_device_path(&device_path) {
+        return Err(AppError::validation(message, &trace_id));
+    }
+
+    let adb_program = get_adb_program(&trace_id)?;
+    let args = vec![
+        "-s".to_string(),
+        serial.clone(),
+        "shell".to_string(),
+        "mkdir".to_string(),
+        "-p".to_string(),
+        device_path.clone(),
+    ];
+    let output = run_command_with_timeout(
+        &adb_program,
+        &args,
+        command_timeout(TimeoutKind::DeviceCommand, &trace_id),
+        &trace_id,
+    )?;
+    if output.exit_code.unwrap_or_default() != 0 {
+        return Err(AppError::dependency(
+            Message::keyed("action_failed")
+                .with("action", "mkdir")
+                .with("detail", &output.stderr),
+            &trace_id,
+        ));
+    }
+    bust_read_cache(Some(CACHE_SCOPE_DEVICE_FILES), Some(&serial));
+
+    Ok(CommandResponse {
+        trace_id,
+        data: device_path,
+    })
+}
+
+#[tauri::command(async)]
+pub fn rename_device_path(
+    serial: String,
+    from_path: String,
+    to_path: String,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<String>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+    ensure_non_empty(&from_path, "from_path", &trace_id)?;
+    ensure_non_empty(&to_path, "to_path", &trace_id)?;
+
+    if let Err(message) = validate_device_path(&from_path) {
+        return Err(AppError::validation(message, &trace_id));
+    }
+    if let Err(message) = validate_device_path(&to_path) {
+        return Err(AppError::validation(message, &trace_id));
+    }
+    if from_path.trim() == to_path.trim() {
+        return Err(AppError::validation(
+            Message::keyed("paths_must_differ"),
+            &trace_id,
+        ));
+    }
+
+    let adb_program = get_adb_program(&trace_id)?;
+    let args = vec![
+        "-s".to_string(),
+        serial.clone(),
+        "shell".to_string(),
+        "mv".to_string(),
+        from_path,
+        to_path.clone(),
+    ];
+    let output = run_command_with_timeout(
+        &adb_program,
+        &args,
+        command_timeout(TimeoutKind::DeviceCommand, &trace_id),
+        &trace_id,
+    )?;
+    if output.exit_code.unwrap_or_default() != 0 {
+        return Err(AppError::dependency(
+            Message::keyed("action_failed")
+                .with("action", "rename")
+                .with("detail", &output.stderr),
+            &trace_id,
+        ));
+    }
+    bust_read_cache(Some(CACHE_SCOPE_DEVICE_FILES), Some(&serial));
+
+    Ok(CommandResponse {
+        trace_id,
+        data: to_path,
+    })
+}
+
+#[tauri::command(async)]
+pub fn delete_device_path(
+    serial: String,
+    device_path: String,
+    recursive: bool,
+    app: AppHandle,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<String>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+    warn_on_reservation_conflicts(
+        &app,
+        std::slice::from_ref(&serial),
+        "Deleting device files",
+        &trace_id,
+    );
+    let data = delete_device_path_inner(serial, device_path, recursive, &trace_id)?;
+    Ok(CommandResponse { trace_id, data })
+}
+
+pub fn smoke_delete_device_path(
+    serial: String,
+    device_path: String,
+    recursive: bool,
+    trace_id: &str,
+) -> Result<String, AppError> {
+    delete_device_path_inner(serial, device_path, recursive, trace_id)
+}
+
+fn delete_device_path_inner(
+    serial: String,
+    device_path: String,
+    recursive: bool,
+    trace_id: &str,
+) -> Result<String, AppError> {
+    ensure_non_empty(&serial, "serial", trace_id)?;
+    ensure_non_empty(&device_path, "device_path", trace_id)?;
+
+    if let Err(message) = validate_device_path(&device_path) {
+        return Err(AppError::validation(message, trace_id));
+    }
+    ensure_action_allowed("Deleting device files", trace_id)?;
+
+    let adb_program = get_adb_program(trace_id)?;
+    let mut args = vec![
+        "-s".to_string(),
+        serial.clone(),
+        "shell".to_string(),
+        "rm".to_string(),
+    ];
+    if recursive {
+        args.push("-rf".to_string());
+    } else {
+        args.push("-f".to_string());
+    }
+    args.push(device_path.clone());
+
+    let output = run_command_with_timeout(
+        &adb_program,
+        &args,
+        command_timeout(TimeoutKind::DeviceCommand, trace_id),
+        trace_id,
+    )?;
+    if output.exit_code.unwrap_or_default() != 0 {
+        return Err(AppError::dependency(
+            Message::keyed("action_failed")
+                .with("action", "delete")
+                .with("detail", &output.stderr),
+            trace_id,
+        ));
+    }
+    bust_read_cache(Some(CACHE_SCOPE_DEVICE_FILES), Some(&serial));
+
+    Ok(device_path)
+}
+
+#[tauri::command(async)]
+pub fn preview_local_file(
+    local_path: String,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<FilePreview>, AppError> {
+    use base64::Engine as _;
+
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&local_path, "local_path", &trace_id)?;
+
+    let path = PathBuf::from(&local_path);
+    if !path.exists() {
+        return Err(AppError::validation(
+            Message::keyed("does_not_exist").with("subject", "File"),
+            &trace_id,
+        ));
+    }
+
+    let mime = MimeGuess::from_path(&path).first_or_octet_stream();
+    let mime_type = mime.essence_str().to_string();
+
+    const MAX_TEXT_PREVIEW_BYTES: usize = 200_000;
+    const MAX_IMAGE_PREVIEW_BYTES: usize = 6_000_000;
+    let mut file = fs::File::open(&path).map_err(|err| {
+        AppError::system(
+            Message::keyed("operation_failed")
+                .with("action", "open file")
+                .with("detail", &err),
+            &trace_id,
+        )
+    })?;
+    let mut buffer = Vec::new();
+    let is_image = mime_type.starts_with("image/");
+    let max_preview_bytes = if is_image {
+        MAX_IMAGE_PREVIEW_BYTES
+    } else {
+        MAX_TEXT_PREVIEW_BYTES
+    };
+    std::io::Read::by_ref(&mut file)
+        .take((max_preview_bytes + 1) as u64)
+        .read_to_end(&mut buffer)
+        .map_err(|err| {
+            AppError::system(
+                Message::keyed("operation_failed")
+                    .with("action", "read file")
+                    .with("detail", &err),
+                &trace_id,
+            )
+        })?;
+
+    let mut preview_text = None;
+    let mut is_text = false;
+    let mut preview_data_url = None;
+
+    if is_image {
+        // For images we only return a data URL if we captured the full file within the cap.
+        if buffer.len() <= max_preview_bytes {
+            let encoded = base64::engine::general_purpose::STANDARD.encode(&buffer);
+            preview_data_url = Some(format!("data:{mime_type};base64,{encoded}"));
+        }
+    } else if let Ok(text) = std::str::from_utf8(&buffer) {
+        if !contains_binary_control_chars(text) {
+            is_text = true;
+            let mut content = text.to_string();
+            if buffer.len() > MAX_TEXT_PREVIEW_BYTES {
+                content.truncate(MAX_TEXT_PREVIEW_BYTES);
+                content.push_str("\n… (truncated)");
+            }
+            preview_text = Some(content);
+        }
+    }
+
+    let is_text_flag = is_text || mime_type.starts_with("text/");
+
+    Ok(CommandResponse {
+        trace_id,
+        data: FilePreview {
+            local_path,
+            mime_type,
+            is_text: is_text_flag,
+            preview_text,
+            preview_data_url,
+        },
+    })
+}
+
+fn contains_binary_control_chars(text: &str) -> bool {
+    for ch in text.chars() {
+        if ch == '\n' || ch == '\r' || ch == '\t' {
+            continue;
+        }
+        if ch == '\u{0}' || ch < '\u{20}' {
+            return true;
+        }
+    }
+    false
+}
+
+fn density_rank(path: &str) -> i32 {
+    let lower = path.to_lowercase();
+    if lower.contains("xxxhdpi") {
+        return 6;
+    }
+    if lower.contains("xxhdpi") {
+        return 5;
+    }
+    if lower.contains("xhdpi") {
+        return 4;
+    }
+    if lower.contains("hdpi") {
+        return 3;
+    }
+    if lower.contains("mdpi") {
+        return 2;
+    }
+    if lower.contains("ldpi") {
+        return 1;
+    }
+    if lower.contains("nodpi") {
+        return 0;
+    }
+    0
+}
+
+fn icon_name_rank(path: &str) -> i32 {
+    let lower = path.to_lowercase();
+    if lower.contains("ic_launcher") {
+        return 6;
+    }
+    if lower.contains("launcher") {
+        return 5;
+    }
+    if lower.contains("appicon") {
+        return 4;
+    }
+    if lower.contains("app_icon") {
+        return 4;
+    }
+    if lower.contains("icon") {
+        return 2;
+    }
+    0
+}
+
+fn extract_best_icon_from_apk(
+    path: &std::path::Path,
+    trace_id: &str,
+) -> Result<Option<(String, Vec<u8>)>, AppError> {
+    let file = fs::File::open(path).map_err(|err| {
+        AppError::system(
+            Message::keyed("operation_failed")
+                .with("action", "open APK")
+                .with("detail", &err),
+            trace_id,
+        )
+    })?;
+    let mut zip = ZipArchive::new(file).map_err(|err| {
+        AppError::system(
+            Message::keyed("operation_failed")
+                .with("action", "read APK zip")
+                .with("detail", &err),
+            trace_id,
+        )
+    })?;
+
+    let mut best: Option<(i32, i32, u64, usize, String)> = None;
+    for index in 0..zip.len() {
+        let entry = match zip.by_index(index) {
+            Ok(value) => value,
+            Err(_) => continue,
+        };
+        let name = entry.name().to_string();
+        if !name.starts_with("res/") {
+            continue;
+        }
+        let lower = name.to_lowercase();
+        if !(lower.ends_with(".png") || lower.ends_with(".webp")) {
+            continue;
+        }
+        if !(lower.contains("/mipmap") || lower.contains("/drawable")) {
+            continue;
+        }
+        let name_score = icon_name_rank(&name);
+        let density_score = density_rank(&name);
+        let size = entry.size();
+        let candidate = (name_score, density_score, size, index, name);
+        if let Some(current) = &best {
+            if candidate.0 > current.0
+                || (candidate.0 == current.0 && candidate.1 > current.1)
+                || (candidate.0 == current.0 && candidate.1 == current.1 && candidate.2 > current.2)
+            {
+                best = Some(candidate);
+            }
+        } else {
+            best = Some(candidate);
+        }
+    }
+
+    let Some((_name_score, _density_score, _size, index, entry_name)) = best else {
+        return Ok(None);
+    };
+
+    let mut entry = zip.by_index(index).map_err(|err| {
+        AppError::system(
+            Message::keyed("operation_failed")
+                .with("action", "read icon entry")
+                .with("detail", &err),
+            trace_id,
+        )
+    })?;
+    let mut bytes = Vec::new();
+    entry.read_to_end(&mut bytes).map_err(|err| {
+        AppError::system(
+            Message::keyed("operation_failed")
+                .with("action", "read icon bytes")
+                .with("detail", &err),
+            trace_id,
+        )
+    })?;
+    Ok(Some((entry_name, bytes)))
+}
+
+#[derive(Clone, serde::Serialize)]
+pub struct AppListProgressEvent {
+    pub serial: String,
+    /// Apps whose versions changed since the previous event; the first event lists every app.
+    pub apps: Vec<AppInfo>,
+    pub resolved: usize,
+    pub total: usize,
+    pub trace_id: String,
+}
+
+const APP_LIST_PROGRESS_EVENT: &str = "app-list-progress";
+const APP_VERSION_FETCH_CONCURRENCY: usize = 4;
+const APP_VERSION_BATCH_SIZE: usize = 25;
+const APP_LIST_CACHE_TTL: Duration = Duration::from_secs(300);
+
+type AppListEmitter<'a> = dyn Fn(&HashMap<String, AppVersion>, &[&PackageEntry]) + 'a;
+
+/// Versions for every listed package from one `dumpsys package packages`. Packages missing from
+/// it fall back to a per-package `dumpsys package` on a few scheduler-gated threads. `emit`
+/// receives partial results along the way: first every package, then each resolved batch.
+fn fetch_app_versions(
+    scheduler: &TaskScheduler,
+    adb_program: &str,
+    serial: &str,
+    entries: &[PackageEntry],
+    trace_id: &str,
+    emit: &AppListEmitter,
+) -> HashMap<String, AppVersion> {
+    let all: Vec<&PackageEntry> = entries.iter().collect();
+    emit(&HashMap::new(), &all);
+
+    let blocks: HashMap<String, String> = match run_device_shell(
+        adb_program,
+        serial,
+        &["dumpsys", "package", "packages"],
+        command_timeout(TimeoutKind::DeviceCommand, trace_id),
+        trace_id,
+    ) {
+        Ok(output) => split_dumpsys_package_blocks(&output.stdout)
+            .into_iter()
+            .collect(),
+        Err(err) => {
+            warn!(trace_id = %trace_id, serial = %serial, error = %err, "dumpsys package failed");
+            HashMap::new()
+        }
+    };
+    let mut versions: HashMap<String, AppVersion> = entries
+        .iter()
+        .filter_map(|entry| {
+            let block = blocks.get(&entry.package_name)?;
+            Some((entry.package_name.clone(), parse_dumpsys_version(block)))
+        })
+        .collect();
+    let (found, missing): (Vec<&PackageEntry>, Vec<&PackageEntry>) = all
+        .into_iter()
+        .partition(|entry| versions.contains_key(&entry.package_name));
+    if !found.is_empty() {
+        emit(&versions, &found);
+    }
+    if missing.is_empty() {
+        return versions;
+    }
+
+    let by_name: HashMap<&str, &PackageEntry> = missing
+        .iter()
+        .map(|entry| (entry.package_name.as_str(), *entry))
+        .collect();
+    let packages: Vec<String> = missing
+        .iter()
+        .map(|entry| entry.package_name.clone())
+        .collect();
+    fetch_versions_parallel(
+        &packages,
+        APP_VERSION_FETCH_CONCURRENCY,
+        APP_VERSION_BATCH_SIZE,
+        |package| {
+            let _permit = scheduler.acquire_global();
+            match run_device_shell(
+                adb_program,
+                serial,
+                &["dumpsys", "package", package],
+                command_timeout(TimeoutKind::DeviceCommand, trace_id),
+                trace_id,
+            ) {
+                Ok(output) => parse_dumpsys_version(&output.stdout),
+                Err(err) => {
+                    warn!(
+                        trace_id = %trace_id,
+                        package_name = %package,
+                        error = %err,
+                        "dumpsys package failed while listing apps"
+                    );
+                    AppVersion::default()
+                }
+            }
+        },
+        &mut |batch| {
+            let updated: Vec<&PackageEntry> = batch
+                .iter()
+                .filter_map(|(package, _)| by_name.get(package.as_str()).copied())
+                .collect();
+            versions.extend(batch);
+            emit(&versions, &updated);
+        },
+    );
+    versions
+}
+
+fn pm_list_packages(
+    adb_program: &str,
+    serial: &str,
+    flags: &[&str],
+    trace_id: &str,
+) -> Result<Vec<PackageEntry>, AppError> {
+    let mut args = vec!["pm", "list", "packages", "-f"];
+    args.extend_from_slice(flags);
+    let output = run_device_shell(
+        adb_program,
+        serial,
+        &args,
+        command_timeout(TimeoutKind::DeviceCommand, trace_id),
+        trace_id,
+    )?;
+    if output.exit_code.unwrap_or_default() != 0 {
+        return Err(AppError::dependency(
+            Message::keyed("action_failed")
+                .with("action", "List apps")
+                .with("detail", &output.stderr),
+            trace_id,
+        ));
+    }
+    Ok(parse_pm_list_packages_output(&output.stdout))
+}
+
+#[allow(clippy::too_many_arguments)]
+fn load_app_list(
+    app: &AppHandle,
+    scheduler: &TaskScheduler,
+    adb_program: &str,
+    serial: &str,
+    third_party_only: Option<bool>,
+    with_versions: bool,
+    query: &AppListQuery,
+    trace_id: &str,
+) -> Result<Vec<AppInfo>, AppError> {
+    let flags: &[&str] = match third_party_only {
+        Some(true) => &["-3"],
+        Some(false) => &["-s"],
+        None => &[],
+    };
+    let entries = pm_list_packages(adb_program, serial, flags, trace_id)?;
+    let disabled: Option<HashSet<String>> = match pm_list_packages(
+        adb_program,
+        serial,
+        &["-d"],
+        trace_id,
+    ) {
+        Ok(entries) => Some(
+            entries
+                .into_iter()
+                .map(|entry| entry.package_name)
+                .collect(),
+        ),
+        Err(err) => {
+            warn!(trace_id = %trace_id, serial = %serial, error = %err, "pm list packages -d failed");
+            None
+        }
+    };
+
+    let versions = if with_versions {
+        let total = entries.len();
+        let emit = |versions: &HashMap<String, AppVersion>, packages: &[&PackageEntry]| {
+            // Streamed rows honour the filters; paging only applies to the final result.
+            let apps = packages
+                .iter()
+                .map(|entry| {
+                    build_app_info(entry, versions.get(&entry.package_name), disabled.as_ref())
+                })
+                .filter(|app| app_matches_query(app, query))
+                .collect();
+            if let Err(err) = emit_tracked(
+                app,
+                APP_LIST_PROGRESS_EVENT,
+                AppListProgressEvent {
+                    serial: serial.to_string(),
+                    apps,
+                    resolved: versions.len(),
+                    total,
+                    trace_id: trace_id.to_string(),
+                },
+            ) {
+                warn!(trace_id = %trace_id, error = %err, "failed to emit app list progress");
+            }
+        };
+        fetch_app_versions(scheduler, adb_program, serial, &entries, trace_id, &emit)
+    } else {
+        HashMap::new()
+    };
+    Ok(entries
+        .iter()
+        .map(|entry| build_app_info(entry, versions.get(&entry.package_name), disabled.as_ref()))
+        .collect())
+}
+
+/// List installed apps, filtered, sorted and paged by `query` on the backend.
+///
+/// The full list is kept per device for `APP_LIST_CACHE_TTL`; queries against it skip adb unless
+/// `refresh` is set. Filtering or sorting by target SDK or version code implies
+/// `include_versions`.
+#[allow(clippy::too_many_arguments)]
+#[tauri::command(async)]
+pub fn list_apps(
+    serial: String,
+    third_party_only: Option<bool>,
+    include_versions: Option<bool>,
+    query: Option<AppListQuery>,
+    refresh: Option<bool>,
+    app: AppHandle,
+    state: State<'_, AppState>,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<AppListPage>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+    let query = query.unwrap_or_default();
+    if let Some(sort_by) = query.sort_by.as_deref() {
+        if !APP_LIST_SORT_KEYS.contains(&sort_by) {
+            return Err(AppError::validation(
+                Message::keyed("one_of")
+                    .with("field", "sort_by")
+                    .with("values", APP_LIST_SORT_KEYS.join(", ")),
+                &trace_id,
+            ));
+        }
+    }
+    if query.limit == Some(0) {
+        return Err(AppError::validation(
+            Message::keyed("at_least")
+                .with("field", "limit")
+                .with("min", 1),
+            &trace_id,
+        ));
+    }
+    let with_versions = include_versions.unwrap_or(false)
+        || query.min_target_sdk.is_some()
+        || matches!(
+            query.sort_by.as_deref(),
+            Some("version_code" | "target_sdk")
+        );
+
+    let cached = if refresh.unwrap_or(false) {
+        None
+    } else {
+        let snapshots = state
+            .app_lists
+            .lock()
+            .map_err(|_| AppError::system("App list cache lock poisoned", &trace_id))?;
+        snapshots
+            .get(&serial)
+            .filter(|snapshot| {
+                snapshot.third_party_only == third_party_only
+                    && (snapshot.with_versions || !with_versions)
+                    && snapshot.fetched_at.elapsed() < APP_LIST_CACHE_TTL
+            })
+            .map(|snapshot| query_app_list(&snapshot.apps, &query))
+    };
+    if let Some(page) = cached {
+        return Ok(CommandResponse {
+            trace_id,
+            data: page,
+        });
+    }
+
+    let adb_program = get_adb_program(&trace_id)?;
+    let apps = load_app_list(
+        &app,
+        &state.scheduler,
+        &adb_program,
+        &serial,
+        third_party_only,
+        with_versions,
+        &query,
+        &trace_id,
+    )?;
+    let page = query_app_list(&apps, &query);
+    match state.app_lists.lock() {
+        Ok(mut snapshots) => {
+            snapshots.insert(
+                serial,
+                AppListSnapshot {
+                    third_party_only,
+                    with_versions,
+                    apps,
+                    fetched_at: Instant::now(),
+                },
+            );
+        }
+        Err(_) => warn!(trace_id = %trace_id, "app list cache lock poisoned; result not cached"),
+    }
+
+    Ok(CommandResponse {
+        trace_id,
+        data: page,
+    })
+}
+
+const ENRICHED_APP_METADATA_FETCH_LIMIT: usize = 16;
+
+#[allow(clippy::too_many_arguments)]
+fn resolve_app_metadata(
+    adb_program: &str,
+    aapt: Option<&std::path::Path>,
+    serial: &str,
+    package_name: &str,
+    apk_path: &str,
+    version_code: Option<&str>,
+    cache_dir: &std::path::Path,
+    trace_id: &str,
+) -> Result<CachedAppMetadata, AppError> {
+    if !apk_path.starts_with('/') {
+        return Err(AppError::validation(
+            Message::keyed("invalid").with("subject", "APK path"),
+            trace_id,
+        ));
+    }
+    let temp_dir = tempfile::tempdir().map_err(|err| {
+        AppError::system(
+            Message::keyed("operation_failed")
+                .with("action", "create temp dir")
+                .with("detail", &err),
+            trace_id,
+        )
+    })?;
+    let local_apk_path = temp_dir.path().join("base.apk");
+    let pull_args = vec![
+        "-s".to_string(),
+        serial.to_string(),
+        "pull".to_string(),
+        apk_path.to_string(),
+        local_apk_path.to_string_lossy().to_string(),
+    ];
+    let pull_output = run_command_with_timeout(
+        adb_program,
+        &pull_args,
+        command_timeout(TimeoutKind::Transfer, trace_id),
+        trace_id,
+    )?;
+    if pull_output.exit_code.unwrap_or_default() != 0 {
+        return Err(AppError::dependency(
+            Message::keyed("action_failed")
+                .with("action", "Pull APK")
+                .with("detail", pull_output.stderr.trim()),
+            trace_id,
+        ));
+    }
+
+    let icon = match extract_best_icon_from_apk(&local_apk_path, trace_id) {
+        Ok(Some((entry_name, bytes))) if bytes.len() <= 1_000_000 => {
+            let ext = if entry_name.to_lowercase().ends_with(".webp") {
+                "webp"
+            } else {
+                "png"
+            };
+            Some((ext, bytes))
+        }
+        Ok(_) => None,
+        Err(err) => {
+            warn!(trace_id = %trace_id, package_name = %package_name, error = %err, "icon extraction failed");
+            None
+        }
+    };
+
+    let label = aapt.and_then(|aapt| {
+        let args = vec![
+            "dump".to_string(),
+            "badging".to_string(),
+            local_apk_path.to_string_lossy().to_string(),
+        ];
+        match run_command_with_timeout(
+            &aapt.to_string_lossy(),
+            &args,
+            command_timeout(TimeoutKind::DeviceCommand, trace_id),
+            trace_id,
+        ) {
+            Ok(output) => parse_badging_label(&output.stdout),
+            Err(err) => {
+                warn!(trace_id = %trace_id, package_name = %package_name, error = %err, "aapt badging failed");
+                None
+            }
+        }
+    });
+
+    let key = app_cache_key(package_name, version_code);
+    store_cached_metadata(
+        cache_dir,
+        &key,
+        package_name,
+        version_code,
+        label,
+        icon.as_ref().map(|(ext, bytes)| (*ext, bytes.as_slice())),
+    )
+    .map_err(|err| {
+        AppError::system(
+            Message::keyed("operation_failed")
+                .with("action", "write app metadata cache")
+                .with("detail", &err),
+            trace_id,
+        )
+    })
+}
+
+/// List apps with label, icon, size and install time in one call.
+///
+/// Versions and install times come from a single `dumpsys package packages`, sizes from
+/// `dumpsys diskstats`. Labels and icons are cached on disk per (package, versionCode); at most
+/// `max_metadata_fetch` cache misses are resolved per call because each one pulls the APK.
+/// Labels need `aapt`/`aapt2` from the Android SDK build-tools and stay empty without it.
+#[tauri::command(async)]
+pub fn list_apps_enriched(
+    serial: String,
+    third_party_only: Option<bool>,
+    max_metadata_fetch: Option<usize>,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<Vec<AppListEntry>>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+
+    let adb_program = get_adb_program(&trace_id)?;
+    let mut list_args = vec!["pm", "list", "packages", "-f"];
+    match third_party_only {
+        Some(true) => list_args.push("-3"),
+        Some(false) => list_args.push("-s"),
+        None => {}
+    }
+    let list_output = run_device_shell(
+        &adb_program,
+        &serial,
+        &list_args,
+        command_timeout(TimeoutKind::DeviceCommand, &trace_id),
+        &trace_id,
+    )?;
+    let entries = parse_pm_list_packages_output(&list_output.stdout);
+
+    let package_blocks: HashMap<String, String> = match run_device_shell(
+        &adb_program,
+        &serial,
+        &["dumpsys", "package", "packages"],
+        command_timeout(TimeoutKind::DeviceCommand, &trace_id),
+        &trace_id,
+    ) {
+        Ok(output) => split_dumpsys_package_blocks(&output.stdout)
+            .into_iter()
+            .collect(),
+        Err(err) => {
+            warn!(trace_id = %trace_id, serial = %serial, error = %err, "dumpsys package failed");
+            HashMap::new()
+        }
+    };
+    let sizes = match run_device_shell(
+        &adb_program,
+        &serial,
+        &["dumpsys", "diskstats"],
+        command_timeout(TimeoutKind::DeviceCommand, &trace_id),
+        &trace_id,
+    ) {
+        Ok(output) => parse_diskstats_app_sizes(&output.stdout),
+        Err(err) => {
+            warn!(trace_id = %trace_id, serial = %serial, error = %err, "dumpsys diskstats failed");
+            HashMap::new()
+        }
+    };
+
+    let cache_dir = app_metadata_cache_dir();
+    let aapt = find_aapt(&adb_program);
+    let mut fetch_budget = max_metadata_fetch.unwrap_or(ENRICHED_APP_METADATA_FETCH_LIMIT);
+    let mut apps = Vec::with_capacity(entries.len());
+    for entry in entries {
+        let block = package_blocks
+            .get(&entry.package_name)
+            .map(String::as_str)
+            .unwrap_or_default();
+        let version_code = parse_dumpsys_version_code(block);
+        let key = app_cache_key(&entry.package_name, version_code.as_deref());
+        let mut cached = load_cached_metadata(&cache_dir, &key);
+        if cached.is_none() && fetch_budget > 0 {
+            if let Some(apk_path) = entry.apk_path.as_deref() {
+                fetch_budget -= 1;
+                match resolve_app_metadata(
+                    &adb_program,
+                    aapt.as_deref(),
+                    &serial,
+                    &entry.package_name,
+                    apk_path,
+                    version_code.as_deref(),
+                    &cache_dir,
+                    &trace_id,
+                ) {
+                    Ok(value) => cached = Some(value),
+                    Err(err) => warn!(
+                        trace_id = %trace_id,
+                        package_name = %entry.package_name,
+                        error = %err,
+                        "failed to resolve app metadata"
+                    ),
+                }
+            }
+        }
+
+        let size = sizes.get(&entry.package_name).cloned().unwrap_or_default();
+        apps.push(AppListEntry {
+            label: cached.as_ref().and_then(|item| item.label.clone()),
+            icon_path: cached
+                .as_ref()
+                .and_then(|item| item.icon_file.as_ref())
+                .map(|file| cache_dir.join(file).to_string_lossy().to_string()),
+            metadata_pending: cached.is_none(),
+            version_name: parse_dumpsys_version_name(block),
+            version_code,
+            first_install_time: parse_dumpsys_first_install_time(block),
+            last_update_time: parse_dumpsys_last_update_time(block),
+            app_size_bytes: size.app_bytes,
+            data_size_bytes: size.data_bytes,
+            cache_size_bytes: size.cache_bytes,
+            package_name: entry.package_name,
+            is_system: entry.is_system,
+            apk_path: entry.apk_path,
+        });
+    }
+    apps.sort_by(|a, b| a.package_name.cmp(&b.package_name));
+    evict_icon_cache(&icon_cache_dirs(), ICON_CACHE_MAX_BYTES);
+
+    Ok(CommandResponse {
+        trace_id,
+        data: apps,
+    })
+}
+
+/// Drop cached app icons. With a serial only that device's icon cache is removed; without
+/// one the shared app metadata cache is cleared as well.
+#[tauri::command(async)]
+pub fn clear_icon_cache(
+    serial: Option<String>,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<IconCacheStats>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    let targets = match serial
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+    {
+        Some(serial) => vec![app_icon_cache_dir().join(sanitize_filename_component(serial))],
+        None => icon_cache_dirs().to_vec(),
+    };
+    for dir in &targets {
+        if !dir.exists() {
+            continue;
+        }
+        fs::remove_dir_all(dir).map_err(|err| {
+            AppError::system(
+                Message::keyed("operation_failed")
+                    .with("action", "clear icon cache")
+                    .with("detail", &err),
+                &trace_id,
+            )
+        })?;
+    }
+    let stats = icon_cache_stats(&icon_cache_dirs(), ICON_CACHE_MAX_BYTES);
+    info!(trace_id = %trace_id, remaining_bytes = stats.total_bytes, "icon cache cleared");
+
+    Ok(CommandResponse {
+        trace_id,
+        data: stats,
+    })
+}
+
+/// Every APK `pm path` lists for the package: `base.apk` plus any splits.
+fn package_apk_paths(
+    adb_program: &str,
+    serial: &str,
+    package_name: &str,
+    trace_id: &str,
+) -> Result<Vec<String>, AppError> {
+    let pm_path_args = vec![
+        "-s".to_string(),
+        serial.to_string(),
+        "shell".to_string(),
+        "pm".to_string(),
+        "path".to_string(),
+        package_name.to_string(),
+    ];
+    let output = run_command_with_timeout(
+        adb_program,
+        &pm_path_args,
+        command_timeout(TimeoutKind::DeviceCommand, trace_id),
+        trace_id,
+    )?;
+    if output.exit_code.unwrap_or_default() != 0 {
+        return Err(AppError::dependency(
+            Message::keyed("action_failed")
+                .with("action", "pm path")
+                .with("detail", output.stderr.trim()),
+            trace_id,
+        ));
+    }
+    Ok(parse_pm_path_output(&output.stdout))
+}
+
+/// Pull an installed package's base APK into a temp dir; the `TempDir` must outlive the path.
+fn pull_package_apk(
+    adb_program: &str,
+    serial: &str,
+    package_name: &str,
+    apk_path: Option<String>,
+    trace_id: &str,
+) -> Result<(tempfile::TempDir, PathBuf, String), AppError> {
+    let resolved_apk_path = if let Some(path) = apk_path {
+        path
+    } else {
+        let paths = package_apk_paths(adb_program, serial, package_name, trace_id)?;
+        paths
+            .iter()
+            .find(|item| item.ends_with("base.apk"))
+            .or_else(|| paths.first())
+            .cloned()
+            .ok_or_else(|| AppError::dependency(Message::keyed("no_apk_path"), trace_id))?
+    };
+
+    if !resolved_apk_path.starts_with('/') {
+        return Err(AppError::validation(
+            Message::keyed("invalid").with("subject", "APK path"),
+            trace_id,
+        ));
+    }
+
+    let temp_dir = tempfile::tempdir().map_err(|err| {
+        AppError::system(
+            Message::keyed("operation_failed")
+                .with("action", "create temp dir")
+                .with("detail", &err),
+            trace_id,
+        )
+    })?;
+    let local_apk_path = temp_dir.path().join("base.apk");
+    let pull_args = vec![
+        "-s".to_string(),
+        serial.to_string(),
+        "pull".to_string(),
+        resolved_apk_path.clone(),
+        local_apk_path.to_string_lossy().to_string(),
+    ];
+    let pull_output = run_command_with_timeout(
+        adb_program,
+        &pull_args,
+        command_timeout(TimeoutKind::Transfer, trace_id),
+        trace_id,
+    )?;
+    if pull_output.exit_code.unwrap_or_default() != 0 {
+        return Err(AppError::dependency(
+            Message::keyed("action_failed")
+                .with("action", "Pull APK")
+                .with("detail", pull_output.stderr.trim()),
+            trace_id,
+        ));
+    }
+
+    Ok((temp_dir, local_apk_path, resolved_apk_path))
+}
+
+/// versionCode for the icon cache key. Cached reads (the icon's own entry, then a cached
+/// `get_app_basic_info`) are checked before falling back to `dumpsys package`.
+fn icon_version_code(
+    adb_program: &str,
+    serial: &str,
+    package_name: &str,
+    trace_id: &str,
+) -> Option<String> {
+    if let Some(value) = cached_read::<String>(CACHE_SCOPE_APP_ICON, serial, package_name, trace_id)
+    {
+        return Some(value);
+    }
+    let cached_info =
+        cached_read::<AppBasicInfo>(CACHE_SCOPE_APP_INFO, serial, package_name, trace_id);
+    let version_code = match cached_info.and_then(|info| info.version_code) {
+        Some(value) => Some(value),
+        None => match run_device_shell(
+            adb_program,
+            serial,
+            &["dumpsys", "package", package_name],
+            command_timeout(TimeoutKind::DeviceCommand, trace_id),
+            trace_id,
+        ) {
+            Ok(output) => parse_dumpsys_version_code(&output.stdout),
+            Err(err) => {
+                warn!(trace_id = %trace_id, package_name = %package_name, error = %err, "failed to read versionCode for icon cache");
+                None
+            }
+        },
+    };
+    if let Some(value) = &version_code {
+        store_read(CACHE_SCOPE_APP_ICON, serial, package_name, value, trace_id);
+    }
+    version_code
+}
+
+#[tauri::command(async)]
+pub fn get_app_icon(
+    serial: String,
+    package_name: String,
+    apk_path: Option<String>,
+    version_code: Option<String>,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<AppIcon>, AppError> {
+    use base64::Engine as _;
+
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+    ensure_non_empty(&package_name, "package_name", &trace_id)?;
+
+    let adb_program = get_adb_program(&trace_id)?;
+    // Key the cache by versionCode so an app update never serves the previous icon.
+    let version_code = match version_code.filter(|value| !value.trim().is_empty()) {
+        Some(value) => Some(value),
+        None => icon_version_code(&adb_program, &serial, &package_name, &trace_id),
+    };
+    let cache_key = app_cache_key(&package_name, version_code.as_deref());
+
+    let cache_dir = app_icon_cache_dir().join(sanitize_filename_component(&serial));
+    let cache_png = cache_dir.join(format!("{cache_key}.png"));
+    let cache_webp = cache_dir.join(format!("{cache_key}.webp"));
+    let existing_cache = if cache_png.exists() {
+        Some(cache_png.clone())
+    } else if cache_webp.exists() {
+        Some(cache_webp.clone())
+    } else {
+        None
+    };
+
+    if let Some(cache_path) = existing_cache {
+        touch_cache_file(&cache_path);
+        let bytes = fs::read(&cache_path).map_err(|err| {
+            AppError::system(
+                Message::keyed("operation_failed")
+                    .with("action", "read cached icon")
+                    .with("detail", &err),
+                &trace_id,
+            )
+        })?;
+        let mime_type = MimeGuess::from_path(&cache_path)
+            .first_or_octet_stream()
+            .essence_str()
+            .to_string();
+        let encoded = base64::engine::general_purpose::STANDARD.encode(&bytes);
+        return Ok(CommandResponse {
+            trace_id,
+            data: AppIcon {
+                package_name,
+                mime_type: mime_type.clone(),
+                data_url: format!("data:{mime_type};base64,{encoded}"),
+                from_cache: true,
+            },
+        });
+    }
+
+    fs::create_dir_all(&cache_dir).map_err(|err| {
+        AppError::system(
+            Message::keyed("operation_failed")
+                .with("action", "create cache dir")
+                .with("detail", &err),
+            &trace_id,
+        )
+    })?;
+
+    let (_temp_dir, local_apk_path, _) =
+        pull_package_apk(&adb_program, &serial, &package_name, apk_path, &trace_id)?;
+
+    let Some((entry_name, icon_bytes)) = extract_best_icon_from_apk(&local_apk_path, &trace_id)?
+    else {
+        return Err(AppError::dependency(
+            Message::keyed("apk_icon_missing"),
+            &trace_id,
+        ));
+    };
+
+    const MAX_ICON_BYTES: usize = 1_000_000;
+    if icon_bytes.len() > MAX_ICON_BYTES {
+        return Err(AppError::dependency(
+            Message::keyed("icon_too_large"),
+            &trace_id,
+        ));
+    }
+
+    let mime_type = MimeGuess::from_path(&entry_name)
+        .first_or_octet_stream()
+        .essence_str()
+        .to_string();
+    let ext = if mime_type == "image/webp" {
+        "webp"
+    } else {
+        "png"
+    };
+    let cache_path = cache_dir.join(format!("{cache_key}.{ext}"));
+    fs::write(&cache_path, &icon_bytes).map_err(|err| {
+        AppError::system(
+            Message::keyed("operation_failed")
+                .with("action", "write cached icon")
+                .with("detail", &err),
+            &trace_id,
+        )
+    })?;
+    remove_other_versions(&cache_dir, &cache_key);
+    evict_icon_cache(&icon_cache_dirs(), ICON_CACHE_MAX_BYTES);
+
+    let encoded = base64::engine::general_purpose::STANDARD.encode(&icon_bytes);
+    Ok(CommandResponse {
+        trace_id,
+        data: AppIcon {
+            package_name,
+            mime_type: mime_type.clone(),
+            data_url: format!("data:{mime_type};base64,{encoded}"),
+            from_cache: false,
+        },
+    })
+}
+
+/// Summarize dex/native code and SDK/debug flags of an installed app to explain why it may
+/// refuse to run on a device.
+#[tauri::command(async)]
+pub fn inspect_app_binaries(
+    serial: String,
+    package_name: String,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<AppBinaryInspection>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+    ensure_non_empty(&package_name, "package_name", &trace_id)?;
+    if !is_valid_package_name(&package_name) {
+        return Err(AppError::validation(
+            Message::keyed("invalid").with("subject", "package name"),
+            &trace_id,
+        ));
+    }
+
+    let adb_program = get_adb_program(&trace_id)?;
+    let dumpsys = run_device_shell(
+        &adb_program,
+        &serial,
+        &["dumpsys", "package", &package_name],
+        command_timeout(TimeoutKind::DeviceCommand, &trace_id),
+        &trace_id,
+    )?;
+    let device_abis = match run_device_shell(
+        &adb_program,
+        &serial,
+        &["getprop", "ro.product.cpu.abilist"],
+        command_timeout(TimeoutKind::Probe, &trace_id),
+        &trace_id,
+    ) {
+        Ok(output) => output
+            .stdout
+            .trim()
+            .split(',')
+            .map(str::trim)
+            .filter(|abi| !abi.is_empty())
+            .map(str::to_string)
+            .collect::<Vec<_>>(),
+        Err(err) => {
+            warn!(trace_id = %trace_id, serial = %serial, error = %err, "failed to read device ABI list");
+            Vec::new()
+        }
+    };
+    let device_sdk = match run_device_shell(
+        &adb_program,
+        &serial,
+        &["getprop", "ro.build.version.sdk"],
+        command_timeout(TimeoutKind::Probe, &trace_id),
+        &trace_id,
+    ) {
+        Ok(output) => output.stdout.trim().parse::<i64>().ok(),
+        Err(err) => {
+            warn!(trace_id = %trace_id, serial = %serial, error = %err, "failed to read device SDK level");
+            None
+        }
+    };
+
+    // Split installs keep native libraries in ABI splits, so every APK is inspected.
+    let mut apk_paths = package_apk_paths(&adb_program, &serial, &package_name, &trace_id)?;
+    if apk_paths.is_empty() {
+        return Err(AppError::dependency(
+            Message::keyed("no_apk_path"),
+            &trace_id,
+        ));
+    }
+    apk_paths.sort_by_key(|path| !path.ends_with("base.apk"));
+    let mut pulled = Vec::with_capacity(apk_paths.len());
+    for remote_path in &apk_paths {
+        pulled.push(pull_package_apk(
+            &adb_program,
+            &serial,
+            &package_name,
+            Some(remote_path.clone()),
+            &trace_id,
+        )?);
+    }
+    let local_paths: Vec<&PathBuf> = pulled.iter().map(|(_, local, _)| local).collect();
+    let apk_size_bytes = local_paths
+        .iter()
+        .map(|path| match fs::metadata(path) {
+            Ok(meta) => meta.len(),
+            Err(err) => {
+                warn!(trace_id = %trace_id, path = %path.display(), error = %err, "failed to read pulled APK size");
+                0
+            }
+        })
+        .sum();
+    let contents = inspect_apk_binaries(&local_paths)
+        .map_err(|message| AppError::dependency(message, &trace_id))?;
+
+    let min_sdk = parse_dumpsys_min_sdk(&dumpsys.stdout);
+    let issues =
+        binary_compatibility_issues(&contents.native_libs, &device_abis, min_sdk, device_sdk);
+    info!(trace_id = %trace_id, serial = %serial, package_name = %package_name, issues = issues.len(), "inspected app binaries");
+
+    Ok(CommandResponse {
+        trace_id,
+        data: AppBinaryInspection {
+            serial,
+            package_name,
+            apk_path: apk_paths[0].clone(),
+            apk_paths,
+            apk_size_bytes,
+            dex_count: contents.dex_count,
+            native_libs: contents.native_libs,
+            min_sdk,
+            target_sdk: parse_dumpsys_target_sdk(&dumpsys.stdout),
+            debuggable: parse_dumpsys_debuggable(&dumpsys.stdout),
+            primary_cpu_abi: parse_dumpsys_primary_cpu_abi(&dumpsys.stdout),
+            device_abis,
+            device_sdk,
+            issues,
+        },
+    })
+}
+
+/// Launch an app `iterations` times with `am start -W` and report TotalTime/WaitTime stats.
+/// `cold` force-stops the app before every launch; `warm` keeps the process and only sends
+/// the app to the background between launches.
+#[tauri::command(async)]
+pub fn measure_app_startup(
+    serial: String,
+    package_name: String,
+    iterations: Option<u32>,
+    mode: Option<String>,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<AppStartupResult>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+    ensure_non_empty(&package_name, "package_name", &trace_id)?;
+    if !is_valid_package_name(&package_name) {
+        return Err(AppError::validation(
+            Message::keyed("invalid").with("subject", "package name"),
+            &trace_id,
+        ));
+    }
+    let mode = mode
+        .map(|value| value.trim().to_lowercase())
+        .filter(|value| !value.is_empty())
+        .unwrap_or_else(|| "cold".to_string());
+    if mode != "cold" && mode != "warm" {
+        return Err(AppError::validation(
+            Message::keyed("launch_mode"),
+            &trace_id,
+        ));
+    }
+    let iterations = iterations.unwrap_or(5).clamp(1, 20);
+
+    let adb_program = get_adb_program(&trace_id)?;
+    let resolved = run_device_shell(
+        &adb_program,
+        &serial,
+        &[
+            "cmd",
+            "package",
+            "resolve-activity",
+            "--brief",
+            "-c",
+            "android.intent.category.LAUNCHER",
+            &package_name,
+        ],
+        command_timeout(TimeoutKind::DeviceCommand, &trace_id),
+        &trace_id,
+    )?;
+    let activity = parse_resolve_activity_brief(&resolved.stdout)
+        .ok_or_else(|| AppError::validation(Message::keyed("no_launcher_activity"), &trace_id))?;
+
+    let force_stop = || {
+        run_device_shell(
+            &adb_program,
+            &serial,
+            &["am", "force-stop", &package_name],
+            command_timeout(TimeoutKind::DeviceCommand, &trace_id),
+            &trace_id,
+        )
+    };
+    if mode == "warm" {
+        // Bring the process up once so every measured launch finds it alive.
+        force_stop()?;
+        run_device_shell(
+            &adb_program,
+            &serial,
+            &["am", "start", "-W", "-n", &activity],
+            command_timeout(TimeoutKind::DeviceCommand, &trace_id),
+            &trace_id,
+        )?;
+    }
+
+    let mut samples = Vec::new();
+    for iteration in 0..iterations {
+        if mode == "cold" {
+            force_stop()?;
+        } else {
+            run_device_shell(
+                &adb_program,
+                &serial,
+                &["input", "keyevent", "KEYCODE_HOME"],
+                command_timeout(TimeoutKind::Probe, &trace_id),
+                &trace_id,
+            )?;
+        }
+        // Let the previous launch settle so runs do not overlap.
+        std::thread::sleep(Duration::from_millis(500));
+        let output = run_device_shell(
+            &adb_program,
+            &serial,
+            &["am", "start", "-W", "-n", &activity],
+            command_timeout(TimeoutKind::DeviceCommand, &trace_id),
+            &trace_id,
+        )?;
+        let parsed = parse_am_start_wait(&output.stdout).map_err(|message| {
+            AppError::dependency(
+                Message::keyed("action_failed")
+                    .with("action", format!("Launch {}", iteration + 1))
+                    .with("detail", &message),
+                &trace_id,
+            )
+        })?;
+        samples.push(StartupSample {
+            total_time_ms: parsed.total_time_ms,
+            wait_time_ms: parsed.wait_time_ms,
+            launch_state: parsed.launch_state,
+        });
+    }
+
+    let totals: Vec<u32> = samples.iter().filter_map(|s| s.total_time_ms).collect();
+    let waits: Vec<u32> = samples.iter().filter_map(|s| s.wait_time_ms).collect();
+    let total_time = summarize_durations(&totals);
+    info!(
+        trace_id = %trace_id,
+        serial = %serial,
+        package_name = %package_name,
+        mode = %mode,
+        iterations,
+        median_ms = ?total_time.as_ref().map(|stats| stats.median_ms),
+        "measured app startup"
+    );
+
+    Ok(CommandResponse {
+        trace_id,
+        data: AppStartupResult {
+            serial,
+            package_name,
+            activity,
+            mode,
+            samples,
+            total_time,
+            wait_time: summarize_durations(&waits),
+        },
+    })
+}
+
+/// Scan the device log buffer for push registration tokens logged by `package_name`. Lines are
+/// limited to the app's process while it runs; otherwise to every process of the package's uid.
+#[tauri::command(async)]
+pub fn get_captured_push_tokens(
+    serial: String,
+    package_name: String,
+    pattern: Option<String>,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<Vec<CapturedPushToken>>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+    ensure_non_empty(&package_name, "package_name", &trace_id)?;
+    if !is_valid_package_name(&package_name) {
+        return Err(AppError::validation(
+            Message::keyed("invalid").with("subject", "package name"),
+            &trace_id,
+        ));
+    }
+    let regex = compile_push_token_pattern(pattern.as_deref())
+        .map_err(|message| AppError::validation(message, &trace_id))?;
+
+    let adb_program = get_adb_program(&trace_id)?;
+    let pid = match device_pidof(&adb_program, &serial, &package_name, &trace_id) {
+        Ok(pids) => pids.into_iter().next(),
+        Err(err) => {
+            warn!(trace_id = %trace_id, serial = %serial, error = %err, "pidof failed, filtering push tokens by uid");
+            None
+        }
+    };
+    let uid = match pid {
+        Some(_) => None,
+        None => Some(package_uid(
+            &adb_program,
+            &serial,
+            &package_name,
+            &trace_id,
+        )?),
+    };
+
+    let mut args = vec![
+        "-s".to_string(),
+        serial.clone(),
+        "logcat".to_string(),
+        "-d".to_string(),
+        "-v".to_string(),
+        "time".to_string(),
+    ];
+    match (pid, uid) {
+        (Some(pid), _) => args.push(format!("--pid={pid}")),
+        (None, Some(uid)) => args.push(format!("--uid={uid}")),
+        (None, None) => {}
+    }
+    let output = run_command_with_timeout(
+        &adb_program,
+        &args,
+        command_timeout(TimeoutKind::DeviceCommand, &trace_id),
+        &trace_id,
+    )?;
+    if output.exit_code.unwrap_or_default() != 0 {
+        return Err(AppError::dependency(
+            Message::keyed("action_failed")
+                .with("action", "logcat")
+                .with("detail", output.stderr.trim()),
+            &trace_id,
+        ));
+    }
+
+    let tokens = extract_push_tokens(&output.stdout, &regex);
+    // Tokens are credentials for sending pushes; only the count is logged.
+    info!(trace_id = %trace_id, serial = %serial, package_name = %package_name, pid = ?pid, uid = ?uid, count = tokens.len(), "captured push tokens");
+    Ok(CommandResponse {
+        trace_id,
+        data: tokens,
+    })
+}
+
+/// Look up the Linux uid `package_name` runs as, so its log lines can be selected while no
+/// process is alive.
+fn package_uid(
+    adb_program: &str,
+    serial: &str,
+    package_name: &str,
+    trace_id: &str,
+) -> Result<u32, AppError> {
+    let output = run_device_shell(
+        adb_program,
+        serial,
+        &["pm", "list", "packages", "-U", package_name],
+        command_timeout(TimeoutKind::Probe, trace_id),
+        trace_id,
+    )?;
+    // The filter argument is a substring match, so pick the exact package.
+    parse_cmd_package_list_u(&output.stdout)
+        .into_iter()
+        .find(|(_, packages)| packages.iter().any(|name| name == package_name))
+        .map(|(uid, _)| uid)
+        .ok_or_else(|| {
+            AppError::validation(
+                Message::keyed("not_found").with("subject", package_name),
+                trace_id,
+            )
+        })
+}
+
+/// Package installs and removals change what `get_app_basic_info` and the icon versionCode
+/// report, so drop both for the device.
+fn invalidate_app_reads(serial: &str) {
+    bust_read_cache(Some(CACHE_SCOPE_APP_INFO), Some(serial));
+    bust_read_cache(Some(CACHE_SCOPE_APP_ICON), Some(serial));
+}
+
+const CACHE_SCOPE_APP_LIST: &str = "app_list";
+
+#[tauri::command]
+pub fn bust_cache(
+    scope: Option<String>,
+    serial: Option<String>,
+    state: State<'_, AppState>,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<usize>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    let scope = scope
+        .map(|scope| scope.trim().to_ascii_lowercase())
+        .filter(|scope| !scope.is_empty() && scope != "all");
+    if let Some(scope) = scope.as_deref() {
+        if scope != CACHE_SCOPE_APP_LIST && scope_ttl(scope).is_none() {
+            let known: Vec<&str> = CACHE_SCOPES
+                .iter()
+                .map(|(name, _)| *name)
+                .chain([CACHE_SCOPE_APP_LIST, "all"])
+                .collect();
+            return Err(AppError::validation(
+                Message::keyed("unknown_value")
+                    .with("subject", "cache scope")
+                    .with(
+                        "value",
+                        format!("{scope} (expected one of {})", known.join(", ")),
+                    ),
+                &trace_id,
+            ));
+        }
+    }
+    let serial = serial
+        .map(|serial| serial.trim().to_string())
+        .filter(|serial| !serial.is_empty());
+
+    let mut removed = 0;
+    if scope.as_deref() != Some(CACHE_SCOPE_APP_LIST) {
+        removed += bust_read_cache(scope.as_deref(), serial.as_deref());
+    }
+    if scope.is_none() || scope.as_deref() == Some(CACHE_SCOPE_APP_LIST) {
+        let mut snapshots = state
+            .app_lists
+            .lock()
+            .map_err(|_| AppError::system("App list cache lock poisoned", &trace_id))?;
+        match serial.as_deref() {
+            Some(serial) => removed += usize::from(snapshots.remove(serial).is_some()),
+            None => {
+                removed += snapshots.len();
+                snapshots.clear();
+            }
+        }
+    }
+    info!(
+        trace_id = %trace_id,
+        scope = scope.as_deref().unwrap_or("all"),
+        removed,
+        "read cache busted"
+    );
+
+    Ok(CommandResponse {
+        trace_id,
+        data: removed,
+    })
+}
+
+#[tauri::command(async)]
+pub fn get_app_basic_info(
+    serial: String,
+    package_name: String,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<AppBasicInfo>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+    ensure_non_empty(&package_name, "package_name", &trace_id)?;
+    if let Some(info) =
+        cached_read::<AppBasicInfo>(CACHE_SCOPE_APP_INFO, &serial, &package_name, &trace_id)
+    {
+        return Ok(CommandResponse {
+            trace_id,
+            data: info,
+        });
+    }
+
+    let adb_program = get_adb_program(&trace_id)?;
+
+    let dump_args = vec![
+        "-s".to_string(),
+        serial.clone(),
+        "shell".to_string(),
+        "dumpsys".to_string(),
+        "package".to_string(),
+        package_name.clone(),
+    ];
+    let output = run_command_with_timeout(
+        &adb_program,
+        &dump_args,
+        command_timeout(TimeoutKind::DeviceCommand, &trace_id),
+        &trace_id,
+    )?;
+    if output.exit_code.unwrap_or_default() != 0 {
+        return Err(AppError::dependency(
+            Message::keyed("action_failed")
+                .with("action", "Get app info")
+                .with("detail", &output.stderr),
+            &trace_id,
+        ));
+    }
+
+    let version_name = parse_dumpsys_version_name(&output.stdout);
+    let version_code = parse_dumpsys_version_code(&output.stdout);
+    let first_install_time = parse_dumpsys_first_install_time(&output.stdout);
+    let last_update_time = parse_dumpsys_last_update_time(&output.stdout);
+    let installer_package_name = parse_dumpsys_installer_package_name(&output.stdout);
+    let installing_package_name = parse_dumpsys_installing_package_name(&output.stdout);
+    let originating_package_name = parse_dumpsys_originating_package_name(&output.stdout);
+    let initiating_package_name = parse_dumpsys_initiating_package_name(&output.stdout);
+    let uid = parse_dumpsys_user_id(&output.stdout);
+    let data_dir = parse_dumpsys_data_dir(&output.stdout);
+    let target_sdk = parse_dumpsys_target_sdk(&output.stdout);
+    let requested_permissions = parse_dumpsys_requested_permissions(&output.stdout);
+    let granted_permissions = parse_dumpsys_granted_permissions(&output.stdout);
+    let (activities, services, receivers, providers) =
+        parse_dumpsys_components_summary(&output.stdout);
+    let components_summary = if activities + services + receivers + providers > 0 {
+        Some(AppComponentsSummary {
+            activities,
+            services,
+            receivers,
+            providers,
+        })
+    } else {
+        None
+    };
+
+    let pm_path_args = vec![
+        "-s".to_string(),
+        serial.clone(),
+        "shell".to_string(),
+        "pm".to_string(),
+        "path".to_string(),
+        package_name.clone(),
+    ];
+    let mut apk_paths = Vec::new();
+    match run_command_with_timeout(
+        &adb_program,
+        &pm_path_args,
+        command_timeout(TimeoutKind::DeviceCommand, &trace_id),
+        &trace_id,
+    ) {
+        Ok(out) => {
+            if out.exit_code.unwrap_or_default() != 0 {
+                warn!(
+                    trace_id = %trace_id,
+                    package_name = %package_name,
+                    stderr = %out.stderr.trim(),
+                    "pm path returned non-zero exit code"
+                );
+            } else {
+                apk_paths = parse_pm_path_output(&out.stdout);
+            }
+        }
+        Err(err) => {
+            warn!(
+                trace_id = %trace_id,
+                package_name = %package_name,
+                error = %err,
+                "pm path failed"
+            );
+        }
+    }
+
+    apk_paths.sort();
+    apk_paths.dedup();
+
+    const MAX_APK_PATHS: usize = 20;
+    let mut apk_size_bytes_total = None;
+    if !apk_paths.is_empty() {
+        if apk_paths.len() > MAX_APK_PATHS {
+            warn!(
+                trace_id = %trace_id,
+                package_name = %package_name,
+                apk_paths_len = apk_paths.len(),
+                max_apk_paths = MAX_APK_PATHS,
+                "Too many APK paths; skipping size calculation"
+            );
+        } else {
+            let mut total = 0u64;
+            let mut has_error = false;
+            for apk_path in &apk_paths {
+                match try_get_device_file_size_bytes(&adb_program, &serial, apk_path, &trace_id) {
+                    Some(size) => total = total.saturating_add(size),
+                    None => {
+                        has_error = true;
+                        warn!(
+                            trace_id = %trace_id,
+                            package_name = %package_name,
+                            apk_path = %apk_path,
+                            "Failed to resolve APK size"
+                        );
+                    }
+                }
+            }
+            if !has_error {
+                apk_size_bytes_total = Some(total);
+            }
+        }
+    }
+
+    let info = AppBasicInfo {
+        package_name,
+        version_name,
+        version_code,
+        first_install_time,
+        last_update_time,
+        installer_package_name,
+        installing_package_name,
+        originating_package_name,
+        initiating_package_name,
+        uid,
+        data_dir,
+        target_sdk,
+        requested_permissions,
+        granted_permissions,
+        components_summary,
+        apk_paths,
+        apk_size_bytes_total,
+    };
+    store_read(
+        CACHE_SCOPE_APP_INFO,
+        &serial,
+        &info.package_name,
+        &info,
+        &trace_id,
+    );
+
+    Ok(CommandResponse {
+        trace_id,
+        data: info,
+    })
+}
+
+#[tauri::command(async)]
+pub fn uninstall_app(
+    serial: String,
+    package_name: String,
+    keep_data: bool,
+    app: AppHandle,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<bool>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+    ensure_non_empty(&package_name, "package_name", &trace_id)?;
+    ensure_action_allowed("Uninstalling apps", &trace_id)?;
+    warn_on_reservation_conflicts(
+        &app,
+        std::slice::from_ref(&serial),
+        "Uninstalling apps",
+        &trace_id,
+    );
+
+    let adb_program = get_adb_program(&trace_id)?;
+    let mut args = vec!["-s".to_string(), serial.clone(), "uninstall".to_string()];
+    if keep_data {
+        args.push("-k".to_string());
+    }
+    args.push(package_name);
+    let output = run_command_with_timeout(
+        &adb_program,
+        &args,
+        command_timeout(TimeoutKind::Install, &trace_id),
+        &trace_id,
+    )?;
+    let success = output.stdout.contains("Success") || output.exit_code.unwrap_or_default() == 0;
+    invalidate_app_reads(&serial);
+
+    Ok(CommandResponse {
+        trace_id,
+        data: success,
+    })
+}
+
+fn try_get_device_file_size_bytes(
+    adb_program: &str,
+    serial: &str,
+    path: &str,
+    trace_id: &str,
+) -> Option<u64> {
+    if let Some(stat) = stat_device_path(
+        adb_program,
+        serial,
+        path,
+        command_timeout(TimeoutKind::Probe, trace_id),
+        trace_id,
+    ) {
+        return Some(stat.size);
+    }
+
+    let stat_args = vec![
+        "-s".to_string(),
+        serial.to_string(),
+        "shell".to_string(),
+        "stat".to_string(),
+        "-c".to_string(),
+        "%s".to_string(),
+        path.to_string(),
+    ];
+    if let Ok(out) = run_command_with_timeout(
+        adb_program,
+        &stat_args,
+        command_timeout(TimeoutKind::Probe, trace_id),
+        trace_id,
+    ) {
+        if out.exit_code.unwrap_or_default() == 0 {
+            if let Some(size) = parse_stat_size_output(&out.stdout) {
+                return Some(size);
+            }
+        }
+    }
+
+    let ls_args = vec![
+        "-s".to_string(),
+        serial.to_string(),
+        "shell".to_string(),
+        "ls".to_string(),
+        "-la".to_string(),
+        path.to_string(),
+    ];
+    let out = run_command_with_timeout(
+        adb_program,
+        &ls_args,
+        command_timeout(TimeoutKind::Probe, trace_id),
+        trace_id,
+    )
+    .ok()?;
+    if out.exit_code.unwrap_or_default() != 0 {
+        return None;
+    }
+
+    let (dir, base) = split_device_path(path);
+    let parsed = parse_ls_la(&dir, &out.stdout);
+    if parsed.len() == 1 {
+        return parsed[0].size_bytes;
+    }
+    parsed
+        .into_iter()
+        .find(|entry| entry.name == base || entry.path.ends_with(&format!("/{}", base)))
+        .and_then(|entry| entry.size_bytes)
+}
+
+fn parse_stat_size_output(output: &str) -> Option<u64> {
+    output
+        .lines()
+        .map(|line| line.trim())
+        .find(|line| !line.is_empty())
+        .and_then(|line| line.parse::<u64>().ok())
+}
+
+fn split_device_path(path: &str) -> (String, String) {
+    let trimmed = path.trim_end_matches('/');
+    if let Some((dir, base)) = trimmed.rsplit_once('/') {
+        let dir = if dir.is_empty() {
+            "/".to_string()
+        } else {
+            dir.to_string()
+        };
+        return (dir, base.to_string());
+    }
+    (".".to_string(), trimmed.to_string())
+}
+
+#[tauri::command(async)]
+pub fn force_stop_app(
+    serial: String,
+    package_name: String,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<bool>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+    ensure_non_empty(&package_name, "package_name", &trace_id)?;
+
+    let adb_program = get_adb_program(&trace_id)?;
+    let args = vec![
+        "-s".to_string(),
+        serial.clone(),
+        "shell".to_string(),
+        "am".to_string(),
+        "force-stop".to_string(),
+        package_name,
+    ];
+    let output = run_command_with_timeout(
+        &adb_program,
+        &args,
+        command_timeout(TimeoutKind::DeviceCommand, &trace_id),
+        &trace_id,
+    )?;
+
+    Ok(CommandResponse {
+        trace_id,
+        data: output.exit_code.unwrap_or_default() == 0,
+    })
+}
+
+/// `(system packages, active device admins)` on `serial`, which batch uninstall must skip.
+fn protected_packages(
+    adb_program: &str,
+    serial: &str,
+    trace_id: &str,
+) -> Result<(Vec<String>, Vec<String>), AppError> {
+    let system_packages = run_device_shell(
+        adb_program,
+        serial,
+        &["pm", "list", "packages", "-s", "-f"],
+        command_timeout(TimeoutKind::DeviceCommand, trace_id),
+        trace_id,
+    )?;
+    let admin_packages = run_device_shell(
+        adb_program,
+        serial,
+        &["dumpsys", "device_policy"],
+        command_timeout(TimeoutKind::DeviceCommand, trace_id),
+        trace_id,
+    )?;
+    Ok((
+        parse_pm_list_packages_output(&system_packages.stdout)
+            .into_iter()
+            .map(|entry| entry.package_name)
+            .collect(),
+        parse_device_admin_packages(&admin_packages.stdout),
+    ))
+}
+
+fn uninstall_packages_on_device(
+    adb_program: &str,
+    serial: &str,
+    packages: &[String],
+    keep_data: bool,
+    trace_id: &str,
+) -> Vec<AppUninstallResult> {
+    let result = |package: &String, success: bool, skipped: bool, reason: Option<String>| {
+        AppUninstallResult {
+            serial: serial.to_string(),
+            package_name: package.clone(),
+            success,
+            skipped,
+            reason,
+        }
+    };
+    // Without both lists a system or admin package could be removed, so nothing is attempted.
+    let (system_packages, admin_packages) = match protected_packages(adb_program, serial, trace_id)
+    {
+        Ok(lists) => lists,
+        Err(err) => {
+            warn!(trace_id = %trace_id, serial = %serial, error = %err, "failed to check protected packages");
+            let reason = format!(
+                "Could not check system and device-admin packages: {}",
+                err.error
+            );
+            return packages
+                .iter()
+                .map(|package| result(package, false, false, Some(reason.clone())))
+                .collect();
+        }
+    };
+
+    packages
+        .iter()
+        .map(|package| {
+            let result = |success: bool, skipped: bool, reason: Option<String>| {
+                result(package, success, skipped, reason)
+            };
+            if system_packages.contains(package) {
+                return result(false, true, Some("System package".to_string()));
+            }
+            if admin_packages.contains(package) {
+                return result(
+                    false,
+                    true,
+                    Some("Active device admin; remove it in Settings first".to_string()),
+                );
+            }
+            let mut args = vec![
+                "-s".to_string(),
+                serial.to_string(),
+                "uninstall".to_string(),
+            ];
+            if keep_data {
+                args.push("-k".to_string());
+            }
+            args.push(package.clone());
+            match run_command_with_timeout(
+                adb_program,
+                &args,
+                command_timeout(TimeoutKind::Install, trace_id),
+                trace_id,
+            ) {
+                Ok(output) => match parse_uninstall_output(&output.stdout, &output.stderr) {
+                    Ok(()) => result(true, false, None),
+                    Err(reason) => result(false, false, Some(reason)),
+                },
+                Err(err) => result(false, false, Some(err.error)),
+            }
+        })
+        .collect()
+}
+
+/// Uninstall many packages from many devices. Devices run in parallel through the scheduler;
+/// packages on one device run in order. System and device-admin packages are skipped.
+#[tauri::command(async)]
+pub fn uninstall_apps_batch(
+    serials: Vec<String>,
+    packages: Vec<String>,
+    keep_data: bool,
+    app: AppHandle,
+    state: State<'_, AppState>,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<Vec<AppUninstallResult>>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    if serials.is_empty() {
+        return Err(AppError::validation(
+            Message::keyed("required").with("field", "serials"),
+            &trace_id,
+        ));
+    }
+    // Checked up front so a bad entry cannot leave threads for earlier serials running.
+    for serial in &serials {
+        ensure_non_empty(serial, "serial", &trace_id)?;
+    }
+    let mut unique_packages: Vec<String> = Vec::new();
+    for package in packages {
+        let package = package.trim().to_string();
+        if !is_valid_package_name(&package) {
+            return Err(AppError::validation(
+                Message::keyed("invalid_value")
+                    .with("subject", "package name")
+                    .with("value", &package),
+                &trace_id,
+            ));
+        }
+        if !unique_packages.contains(&package) {
+            unique_packages.push(package);
+        }
+    }
+    if unique_packages.is_empty() {
+        return Err(AppError::validation(
+            Message::keyed("required").with("field", "packages"),
+            &trace_id,
+        ));
+    }
+    ensure_action_allowed("Uninstalling apps", &trace_id)?;
+    warn_on_reservation_conflicts(&app, &serials, "Uninstalling apps", &trace_id);
+
+    let adb_program = get_adb_program(&trace_id)?;
+    let packages = Arc::new(unique_packages);
+    let scheduler = Arc::clone(&state.scheduler);
+
+    let mut handles = Vec::new();
+    for (index, serial) in serials.into_iter().enumerate() {
+        let scheduler_clone = Arc::clone(&scheduler);
+        let trace_clone = trace_id.clone();
         let adb_program_clone = adb_program.clone();
-        let scheduler_clone = Arc::clone(&scheduler);
-        let app_clone = app.clone();
-        handles.push(std::thread::spawn(move || {
-            let start_device = std::time::Instant::now();
-            if let Some(app_emit) = &app_clone {
-                if let Err(err) = app_emit.emit(
-                    APK_INSTALL_EVENT_NAME,
-                    ApkInstallEvent {
-                        serial: serial.clone(),
-                        event: "start".to_string(),
-                        success: None,
-                        message: Some("Installing...".to_string()),
-                        error_code: None,
-                        raw_output: None,
-                        trace_id: trace_clone.clone(),
-                    },
-                ) {
-                    warn!(trace_id = %trace_clone, error = %err, "failed to emit apk install event");
-                }
-            }
+        let packages_clone = Arc::clone(&packages);
+        handles.push(std::thread::spawn(move || -> Result<_, AppError> {
             let _permit = scheduler_clone.acquire_global();
             let device_lock = scheduler_clone.device_lock(&serial);
-            let _device_guard = match device_lock.lock() {
-                Ok(guard) => guard,
-                Err(_) => {
-                    warn!(trace_id = %trace_clone, serial = %serial, "device lock poisoned");
-                    let result_item = ApkInstallResult {
-                        serial,
-                        success: false,
-                        error_code: ApkInstallErrorCode::UnknownError,
-                        raw_output: "Failed to access the device. Please try again.".to_string(),
-                        duration_seconds: start_device.elapsed().as_secs_f64(),
-                        device_model: None,
-                    };
-                    if let Some(app_emit) = &app_clone {
-                        if let Err(err) = app_emit.emit(
-                            APK_INSTALL_EVENT_NAME,
-                            ApkInstallEvent {
-                                serial: result_item.serial.clone(),
-                                event: "complete".to_string(),
-                                success: Some(false),
-                                message: Some("Failed to access the device. Please try again.".to_string()),
-                                error_code: Some(result_item.error_code.code().to_string()),
-                                raw_output: Some(truncate_for_event(
-                                    result_item.raw_output.trim(),
-                                    APK_INSTALL_OUTPUT_MAX_LEN,
-                                )),
-                                trace_id: trace_clone.clone(),
-                            },
-                        ) {
-                            warn!(trace_id = %trace_clone, error = %err, "failed to emit apk install event");
-                        }
-                    }
-                    return result_item;
-                }
-            };
-            let mut args = vec!["-s".to_string(), serial.clone()];
-            if let Some(paths) = split_paths {
-                args.push("install-multiple".to_string());
-                if replace {
-                    args.push("-r".to_string());
-                }
-                if allow_downgrade {
-                    args.push("-d".to_string());
-                }
-                if grant {
-                    args.push("-g".to_string());
-                }
-                if allow_test_packages {
-                    args.push("-t".to_string());
-                }
-                args.extend(extra_args_list.clone());
-                args.extend(paths);
-            } else {
-                args.push("install".to_string());
-                if replace {
-                    args.push("-r".to_string());
-                }
-                if allow_downgrade {
-                    args.push("-d".to_string());
-                }
-                if grant {
-                    args.push("-g".to_string());
-                }
-                if allow_test_packages {
-                    args.push("-t".to_string());
-                }
-                args.extend(extra_args_list.clone());
-                args.push(apk_path_clone);
-            }
-            let output = run_command_with_timeout(
+            let _device_guard = device_lock.lock().map_err(|_| {
+                warn!(trace_id = %trace_clone, serial = %serial, "device lock poisoned");
+                AppError::system(
+                    "Failed to access the device. Please try again.",
+                    &trace_clone,
+                )
+            })?;
+
+            let results = uninstall_packages_on_device(
                 &adb_program_clone,
-                &args,
-                Duration::from_secs(180),
+                &serial,
+                &packages_clone,
+                keep_data,
                 &trace_clone,
             );
-            let elapsed = start_device.elapsed().as_secs_f64();
-            let result_item = match output {
-                Ok(output) => {
-                    let raw = if output.stdout.trim().is_empty() {
-                        output.stderr.clone()
-                    } else {
-                        output.stdout.clone()
-                    };
-                    let error_code = ApkInstallErrorCode::from_output(&raw);
-                    let success = error_code == ApkInstallErrorCode::Success;
-                    ApkInstallResult {
-                        serial,
-                        success,
-                        error_code,
-                        raw_output: raw,
-                        duration_seconds: elapsed,
-                        device_model: None,
-                    }
-                }
-                Err(err) => ApkInstallResult {
-                    serial,
-                    success: false,
-                    error_code: ApkInstallErrorCode::UnknownError,
-                    raw_output: err.error,
-                    duration_seconds: elapsed,
-                    device_model: None,
-                },
-            };
-
-            if let Some(app_emit) = &app_clone {
-                let raw_trimmed = result_item.raw_output.trim();
-                let raw_output = if raw_trimmed.is_empty() {
-                    None
-                } else {
-                    Some(truncate_for_event(raw_trimmed, APK_INSTALL_OUTPUT_MAX_LEN))
-                };
-                let message = if result_item.success {
-                    Some("Installed.".to_string())
-                } else if let Some(raw) = raw_output.clone() {
-                    Some(raw)
-                } else {
-                    Some(result_item.error_code.code().to_string())
-                };
-                if let Err(err) = app_emit.emit(
-                    APK_INSTALL_EVENT_NAME,
-                    ApkInstallEvent {
-                        serial: result_item.serial.clone(),
-                        event: "complete".to_string(),
-                        success: Some(result_item.success),
-                        message,
-                        error_code: Some(result_item.error_code.code().to_string()),
-                        raw_output,
-                        trace_id: trace_clone.clone(),
-                    },
-                ) {
-                    warn!(trace_id = %trace_clone, error = %err, "failed to emit apk install event");
-                }
-            }
-
-            result_item
+            Ok((index, results))
         }));
-        if !use_parallel {
-            if let Some(handle) = handles.pop() {
-                let result_item = handle
-                    .join()
-                    .map_err(|_| AppError::system("Install thread panicked", &trace_id))?;
-                result
-                    .results
-                    .insert(result_item.serial.clone(), result_item);
-            }
-        }
     }
 
+    let mut collected = Vec::new();
     for handle in handles {
-        let result_item = handle
+        let (index, results) = handle
             .join()
-            .map_err(|_| AppError::system("Install thread panicked", &trace_id))?;
-        result
-            .results
-            .insert(result_item.serial.clone(), result_item);
+            .map_err(|_| AppError::system("Uninstall thread panicked", &trace_id))??;
+        collected.push((index, results));
+    }
+    collected.sort_by_key(|item| item.0);
+    let results: Vec<AppUninstallResult> = collected.into_iter().flat_map(|item| item.1).collect();
+    for result in &results {
+        invalidate_app_reads(&result.serial);
+    }
+    info!(
+        trace_id = %trace_id,
+        total = results.len(),
+        succeeded = results.iter().filter(|item| item.success).count(),
+        skipped = results.iter().filter(|item| item.skipped).count(),
+        "batch uninstall finished"
+    );
+
+    Ok(CommandResponse {
+        trace_id,
+        data: results,
+    })
+}
+
+#[tauri::command(async)]
+pub fn clear_app_data(
+    serial: String,
+    package_name: String,
+    app: AppHandle,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<bool>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+    ensure_non_empty(&package_name, "package_name", &trace_id)?;
+    ensure_action_allowed("Clearing app data", &trace_id)?;
+    warn_on_reservation_conflicts(
+        &app,
+        std::slice::from_ref(&serial),
+        "Clearing app data",
+        &trace_id,
+    );
+
+    let adb_program = get_adb_program(&trace_id)?;
+    let args = vec![
+        "-s".to_string(),
+        serial.clone(),
+        "shell".to_string(),
+        "pm".to_string(),
+        "clear".to_string(),
+        package_name,
+    ];
+    let output = run_command_with_timeout(
+        &adb_program,
+        &args,
+        command_timeout(TimeoutKind::DeviceCommand, &trace_id),
+        &trace_id,
+    )?;
+    let success = output.stdout.to_lowercase().contains("success")
+        || output.exit_code.unwrap_or_default() == 0;
+    invalidate_app_reads(&serial);
+
+    Ok(CommandResponse {
+        trace_id,
+        data: success,
+    })
+}
+
+#[tauri::command(async)]
+pub fn set_app_enabled(
+    serial: String,
+    package_name: String,
+    enable: bool,
+    user_id: Option<i32>,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<bool>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+    ensure_non_empty(&package_name, "package_name", &trace_id)?;
+
+    let adb_program = get_adb_program(&trace_id)?;
+    let mut args = vec![
+        "-s".to_string(),
+        serial.clone(),
+        "shell".to_string(),
+        "pm".to_string(),
+    ];
+    if enable {
+        args.push("enable".to_string());
+    } else {
+        args.push("disable-user".to_string());
+        if let Some(id) = user_id {
+            args.push("--user".to_string());
+            args.push(id.to_string());
+        }
     }
+    args.push(package_name);
+    let output = run_command_with_timeout(
+        &adb_program,
+        &args,
+        command_timeout(TimeoutKind::DeviceCommand, &trace_id),
+        &trace_id,
+    )?;
+    let normalized = format!(
+        "{} {}",
+        output.stdout.to_lowercase(),
+        output.stderr.to_lowercase()
+    );
+    let success = if enable {
+        normalized.contains("enabled") || normalized.trim().is_empty()
+    } else {
+        normalized.contains("disabled") || normalized.trim().is_empty()
+    };
 
-    result.total_duration_seconds = start.elapsed().as_secs_f64();
+    Ok(CommandResponse {
+        trace_id,
+        data: success,
+    })
+}
 
-    Ok(result)
+fn read_overlays(
+    adb_program: &str,
+    serial: &str,
+    trace_id: &str,
+) -> Result<Vec<OverlayPackage>, AppError> {
+    let output = run_device_shell(
+        adb_program,
+        serial,
+        &["cmd", "overlay", "list"],
+        command_timeout(TimeoutKind::DeviceCommand, trace_id),
+        trace_id,
+    )?;
+    Ok(parse_overlay_list(&output.stdout))
 }
 
+/// Installed runtime resource overlays (theming and feature overlays) grouped by target.
 #[tauri::command(async)]
-#[allow(clippy::too_many_arguments)]
-pub fn install_apk_batch(
-    serials: Vec<String>,
-    apk_path: String,
-    replace: bool,
-    allow_downgrade: bool,
-    grant: bool,
-    allow_test_packages: bool,
-    extra_args: Option<String>,
-    app: AppHandle,
-    state: State<'_, AppState>,
+pub fn list_overlays(
+    serial: String,
     trace_id: Option<String>,
-) -> Result<CommandResponse<ApkBatchInstallResult>, AppError> {
+) -> Result<CommandResponse<Vec<OverlayPackage>>, AppError> {
     let trace_id = resolve_trace_id(trace_id);
-    let result = install_apk_batch_inner(
-        serials,
-        apk_path,
-        replace,
-        allow_downgrade,
-        grant,
-        allow_test_packages,
-        extra_args,
-        state.inner(),
-        &trace_id,
-        Some(app),
-    )?;
-
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+    let adb_program = get_adb_program(&trace_id)?;
+    let overlays = read_overlays(&adb_program, &serial, &trace_id)?;
     Ok(CommandResponse {
         trace_id,
-        data: result,
+        data: overlays,
     })
 }
 
 #[tauri::command(async)]
-pub fn capture_screenshot(
+pub fn set_overlay_state(
     serial: String,
-    output_dir: String,
+    overlay_package: String,
+    enable: bool,
+    app: AppHandle,
     trace_id: Option<String>,
-) -> Result<CommandResponse<String>, AppError> {
+) -> Result<CommandResponse<OverlayPackage>, AppError> {
     let trace_id = resolve_trace_id(trace_id);
+    ensure_action_allowed("Changing device overlays", &trace_id)?;
     ensure_non_empty(&serial, "serial", &trace_id)?;
-    ensure_non_empty(&output_dir, "output_dir", &trace_id)?;
-
+    let overlay_package = overlay_package.trim().to_string();
+    if !is_valid_package_name(&overlay_package) {
+        return Err(AppError::validation(
+            Message::keyed("invalid_value")
+                .with("subject", "overlay package")
+                .with("value", &overlay_package),
+            &trace_id,
+        ));
+    }
+    warn_on_reservation_conflicts(
+        &app,
+        std::slice::from_ref(&serial),
+        "Toggling overlays",
+        &trace_id,
+    );
     let adb_program = get_adb_program(&trace_id)?;
-    let config = load_config(&trace_id)?;
-    let timestamp = Utc::now().format("%Y%m%d_%H%M%S");
-    let safe_serial = sanitize_filename_component(&serial);
-    let filename = format!("screenshot_{}_{}.png", safe_serial, timestamp);
-    let mut output_path = PathBuf::from(output_dir);
-    fs::create_dir_all(&output_path).map_err(|err| {
-        AppError::system(format!("Failed to create output dir: {err}"), &trace_id)
-    })?;
-    output_path.push(&filename);
-    let output_path_string = output_path.to_string_lossy().to_string();
 
-    let mut args = vec![
-        "-s".to_string(),
-        serial.clone(),
-        "exec-out".to_string(),
-        "screencap".to_string(),
-        "-p".to_string(),
-    ];
-    if config.screenshot.display_id >= 0 {
-        args.push("-d".to_string());
-        args.push(config.screenshot.display_id.to_string());
+    let find = |overlays: Vec<OverlayPackage>| {
+        overlays
+            .into_iter()
+            .find(|overlay| overlay.package_name == overlay_package)
+            .ok_or_else(|| {
+                AppError::validation(
+                    Message::keyed("not_installed")
+                        .with("subject", "Overlay")
+                        .with("name", &overlay_package),
+                    &trace_id,
+                )
+            })
+    };
+    let overlay = find(read_overlays(&adb_program, &serial, &trace_id)?)?;
+    if overlay.state == OVERLAY_UNAVAILABLE {
+        return Err(AppError::validation(
+            Message::keyed("overlay_not_mutable").with("package", &overlay_package),
+            &trace_id,
+        ));
     }
-    if !config.screenshot.extra_args.trim().is_empty() {
-        args.extend(
-            config
-                .screenshot
-                .extra_args
-                .split_whitespace()
-                .map(|item| item.to_string()),
-        );
+    let output = run_device_shell(
+        &adb_program,
+        &serial,
+        &[
+            "cmd",
+            "overlay",
+            if enable { "enable" } else { "disable" },
+            &overlay_package,
+        ],
+        command_timeout(TimeoutKind::DeviceCommand, &trace_id),
+        &trace_id,
+    )?;
+    // `cmd overlay` reports refusals on stderr while still exiting 0 on some releases.
+    let message = format!("{}{}", output.stdout, output.stderr);
+    if message.contains("Error") {
+        return Err(AppError::dependency(
+            Message::keyed("operation_failed")
+                .with("action", format!("toggle overlay {overlay_package}"))
+                .with("detail", message.trim()),
+            &trace_id,
+        ));
+    }
+    let overlay = find(read_overlays(&adb_program, &serial, &trace_id)?)?;
+    if (overlay.state == OVERLAY_ENABLED) != enable {
+        warn!(trace_id = %trace_id, serial = %serial, overlay = %overlay_package, state = %overlay.state, "overlay state did not change");
     }
+    info!(trace_id = %trace_id, serial = %serial, overlay = %overlay_package, enable, "overlay toggled");
+    Ok(CommandResponse {
+        trace_id,
+        data: overlay,
+    })
+}
 
-    let output = Command::new(&adb_program)
-        .args(&args)
-        .output()
-        .map_err(|err| AppError::dependency(format!("Failed to run adb: {err}"), &trace_id))?;
+fn read_audio_route(
+    adb_program: &str,
+    serial: &str,
+    trace_id: &str,
+) -> Result<AudioRouteInfo, AppError> {
+    let output = run_device_shell(
+        adb_program,
+        serial,
+        &["dumpsys", "audio"],
+        command_timeout(TimeoutKind::DeviceCommand, trace_id),
+        trace_id,
+    )?;
+    let streams = parse_audio_streams(&output.stdout);
+    Ok(AudioRouteInfo {
+        serial: serial.to_string(),
+        summary: parse_audio_summary(&output.stdout),
+        media_route: streams
+            .iter()
+            .find(|stream| stream.stream == "music")
+            .map(|stream| stream.devices.clone())
+            .unwrap_or_default(),
+        streams,
+    })
+}
 
-    if output.status.success() {
-        fs::write(&output_path, &output.stdout).map_err(|err| {
-            AppError::system(format!("Failed to write screenshot: {err}"), &trace_id)
-        })?;
-        return Ok(CommandResponse {
-            trace_id,
-            data: output_path_string,
-        });
+fn apply_stream_volume(
+    adb_program: &str,
+    serial: &str,
+    stream_id: u32,
+    level: u32,
+    trace_id: &str,
+) -> Result<(), AppError> {
+    let timeout = command_timeout(TimeoutKind::DeviceCommand, trace_id);
+    let [primary, fallback] = set_volume_commands(stream_id, level);
+    let primary: Vec<&str> = primary.iter().map(String::as_str).collect();
+    let fallback: Vec<&str> = fallback.iter().map(String::as_str).collect();
+    match run_device_shell(adb_program, serial, &primary, timeout, trace_id) {
+        Ok(_) => Ok(()),
+        Err(err) => {
+            warn!(trace_id = %trace_id, serial = %serial, error = %err, "cmd media_session volume failed, trying media");
+            run_device_shell(adb_program, serial, &fallback, timeout, trace_id).map(|_| ())
+        }
     }
+}
 
-    let exec_error_raw = String::from_utf8_lossy(&output.stderr).trim().to_string();
-    let exec_error = if exec_error_raw.is_empty() {
-        "unknown error".to_string()
-    } else {
-        exec_error_raw
-    };
-    warn!(
-        trace_id = %trace_id,
-        error = %exec_error,
-        "exec-out screencap failed; falling back to pull"
-    );
+/// Volumes, mute state and output devices per stream, plus where media is routed.
+#[tauri::command(async)]
+pub fn get_audio_route(
+    serial: String,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<AudioRouteInfo>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+    let adb_program = get_adb_program(&trace_id)?;
+    let route = read_audio_route(&adb_program, &serial, &trace_id)?;
+    Ok(CommandResponse {
+        trace_id,
+        data: route,
+    })
+}
 
-    let fallback_result = (|| -> Result<(), AppError> {
-        let remote_path = format!("/sdcard/{filename}");
-        let capture_args = vec![
-            "-s".to_string(),
-            serial.clone(),
-            "shell".to_string(),
-            "screencap".to_string(),
-            "-p".to_string(),
-            remote_path.clone(),
-        ];
-        let capture_output = run_command_with_timeout(
-            &adb_program,
-            &capture_args,
-            Duration::from_secs(10),
-            &trace_id,
-        )?;
-        if capture_output.exit_code.unwrap_or(1) != 0 {
-            return Err(AppError::dependency(
-                format!(
-                    "Fallback screencap failed: {}",
-                    capture_output.stderr.trim()
-                ),
+#[tauri::command(async)]
+pub fn set_volume(
+    serial: String,
+    stream: String,
+    level: u32,
+    app: AppHandle,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<AudioRouteInfo>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+    let stream_id =
+        stream_id(&stream).map_err(|message| AppError::validation(message, &trace_id))?;
+    let stream = stream.trim().to_ascii_lowercase();
+    warn_on_reservation_conflicts(
+        &app,
+        std::slice::from_ref(&serial),
+        "Changing the volume",
+        &trace_id,
+    );
+    let adb_program = get_adb_program(&trace_id)?;
+    let route = read_audio_route(&adb_program, &serial, &trace_id)?;
+    if let Some(state) = route.streams.iter().find(|state| state.stream == stream) {
+        let (min, max) = (state.min.unwrap_or(0), state.max.unwrap_or(u32::MAX));
+        if !(min..=max).contains(&level) {
+            return Err(AppError::validation(
+                Message::keyed("range")
+                    .with("field", format!("Volume for {stream}"))
+                    .with("min", min)
+                    .with("max", max),
                 &trace_id,
             ));
         }
-        let pull_args = vec![
-            "-s".to_string(),
-            serial.clone(),
-            "pull".to_string(),
-            remote_path.clone(),
-            output_path_string.clone(),
-        ];
-        let pull_output =
-            run_command_with_timeout(&adb_program, &pull_args, Duration::from_secs(20), &trace_id)?;
-        if pull_output.exit_code.unwrap_or(1) != 0 {
-            return Err(AppError::dependency(
-                format!("Fallback pull failed: {}", pull_output.stderr.trim()),
-                &trace_id,
-            ));
+    }
+    apply_stream_volume(&adb_program, &serial, stream_id, level, &trace_id)?;
+    info!(trace_id = %trace_id, serial = %serial, stream = %stream, level, "volume set");
+
+    let route = read_audio_route(&adb_program, &serial, &trace_id)?;
+    Ok(CommandResponse {
+        trace_id,
+        data: route,
+    })
+}
+
+/// Turn every adjustable stream down to its minimum. Voice call cannot go below 1 on most
+/// devices, so each stream uses the minimum `dumpsys audio` reports.
+#[tauri::command(async)]
+pub fn mute_all(
+    serial: String,
+    app: AppHandle,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<AudioRouteInfo>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+    warn_on_reservation_conflicts(
+        &app,
+        std::slice::from_ref(&serial),
+        "Muting audio",
+        &trace_id,
+    );
+    let adb_program = get_adb_program(&trace_id)?;
+    let route = read_audio_route(&adb_program, &serial, &trace_id)?;
+
+    let mut failures = Vec::new();
+    for (stream, stream_id) in VOLUME_STREAMS {
+        let min = route
+            .streams
+            .iter()
+            .find(|state| state.stream == stream)
+            .and_then(|state| state.min)
+            .unwrap_or(0);
+        if let Err(err) = apply_stream_volume(&adb_program, &serial, stream_id, min, &trace_id) {
+            warn!(trace_id = %trace_id, serial = %serial, stream, error = %err, "failed to mute stream");
+            failures.push(stream);
         }
-        let cleanup_args = vec![
-            "-s".to_string(),
-            serial.clone(),
-            "shell".to_string(),
-            "rm".to_string(),
-            "-f".to_string(),
-            remote_path,
-        ];
-        if let Err(err) = run_command_with_timeout(
-            &adb_program,
-            &cleanup_args,
-            Duration::from_secs(10),
+    }
+    if failures.len() == VOLUME_STREAMS.len() {
+        return Err(AppError::dependency(
+            Message::keyed("volume_change_failed"),
             &trace_id,
-        ) {
-            warn!(
-                trace_id = %trace_id,
-                error = %err.error,
-                "failed to remove fallback screenshot"
-            );
-        }
-        Ok(())
-    })();
+        ));
+    }
+    info!(trace_id = %trace_id, serial = %serial, failed = failures.len(), "muted all streams");
 
-    match fallback_result {
-        Ok(()) => Ok(CommandResponse {
-            trace_id,
-            data: output_path_string,
-        }),
-        Err(err) => Err(AppError::dependency(
-            format!(
-                "Screenshot failed (exec-out): {}. Fallback failed: {}",
-                exec_error, err.error
-            ),
+    let route = read_audio_route(&adb_program, &serial, &trace_id)?;
+    Ok(CommandResponse {
+        trace_id,
+        data: route,
+    })
+}
+
+/// Send a media key to the active media session: `cmd media_session dispatch`, falling back to
+/// `input keyevent` where `dispatch` is unavailable.
+#[tauri::command(async)]
+pub fn send_media_key(
+    serial: String,
+    key: String,
+    app: AppHandle,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<bool>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+    let (dispatch, keycode) =
+        media_key(&key).map_err(|message| AppError::validation(message, &trace_id))?;
+    warn_on_reservation_conflicts(
+        &app,
+        std::slice::from_ref(&serial),
+        "Sending media keys",
+        &trace_id,
+    );
+    let adb_program = get_adb_program(&trace_id)?;
+    let timeout = command_timeout(TimeoutKind::DeviceCommand, &trace_id);
+    if let Err(err) = run_device_shell(
+        &adb_program,
+        &serial,
+        &["cmd", "media_session", "dispatch", dispatch],
+        timeout,
+        &trace_id,
+    ) {
+        warn!(trace_id = %trace_id, serial = %serial, error = %err, "media_session dispatch failed, using input keyevent");
+        run_device_shell(
+            &adb_program,
+            &serial,
+            &["input", "keyevent", keycode],
+            timeout,
             &trace_id,
-        )),
+        )?;
     }
+    info!(trace_id = %trace_id, serial = %serial, key = dispatch, "media key sent");
+    Ok(CommandResponse {
+        trace_id,
+        data: true,
+    })
 }
 
 #[tauri::command(async)]
-pub fn start_screen_record(
+pub fn get_media_sessions(
     serial: String,
-    state: State<'_, AppState>,
     trace_id: Option<String>,
-) -> Result<CommandResponse<String>, AppError> {
+) -> Result<CommandResponse<Vec<MediaSessionInfo>>, AppError> {
     let trace_id = resolve_trace_id(trace_id);
     ensure_non_empty(&serial, "serial", &trace_id)?;
-
     let adb_program = get_adb_program(&trace_id)?;
-    let mut guard = state
-        .recording_processes
-        .lock()
-        .map_err(|_| AppError::system("Recording registry locked", &trace_id))?;
-    if guard.contains_key(&serial) {
-        return Err(AppError::validation("Recording already active", &trace_id));
-    }
-
-    let config = load_config(&trace_id)?;
-    let timestamp = Utc::now().format("%Y%m%d_%H%M%S");
-    let remote_path = format!("/sdcard/screenrecord_{}_{}.mp4", serial, timestamp);
-
-    let mut args = vec![
-        "-s".to_string(),
-        serial.clone(),
-        "shell".to_string(),
-        "screenrecord".to_string(),
-    ];
-    if !config.screen_record.bit_rate.trim().is_empty() {
-        args.push("--bit-rate".to_string());
-        args.push(config.screen_record.bit_rate.trim().to_string());
-    }
-    if config.screen_record.time_limit_sec > 0 {
-        args.push("--time-limit".to_string());
-        args.push(config.screen_record.time_limit_sec.to_string());
-    }
-    if !config.screen_record.size.trim().is_empty() {
-        args.push("--size".to_string());
-        args.push(config.screen_record.size.trim().to_string());
-    }
-    if config.screen_record.use_hevc {
-        args.push("--codec".to_string());
-        args.push("hevc".to_string());
-    }
-    if config.screen_record.bugreport {
-        args.push("--bugreport".to_string());
-    }
-    if config.screen_record.verbose {
-        args.push("--verbose".to_string());
-    }
-    if config.screen_record.display_id >= 0 {
-        args.push("--display-id".to_string());
-        args.push(config.screen_record.display_id.to_string());
-    }
-    if !config.screen_record.extra_args.trim().is_empty() {
-        args.extend(
-            config
-                .screen_record
-                .extra_args
-                .split_whitespace()
-                .map(|item| item.to_string()),
-        );
-    }
-    args.push(remote_path.clone());
-
-    let child = Command::new(&adb_program)
-        .args(&args)
-        .stdout(Stdio::null())
-        .stderr(Stdio::piped())
-        .spawn()
-        .map_err(|err| {
-            AppError::dependency(format!("Failed to start screenrecord: {err}"), &trace_id)
-        })?;
+    let output = run_device_shell(
+        &adb_program,
+        &serial,
+        &["dumpsys", "media_session"],
+        command_timeout(TimeoutKind::DeviceCommand, &trace_id),
+        &trace_id,
+    )?;
+    Ok(CommandResponse {
+        trace_id,
+        data: parse_media_sessions(&output.stdout),
+    })
+}
 
-    guard.insert(
+/// Installed and enabled accessibility services, both as expanded components.
+fn read_accessibility_services(
+    adb_program: &str,
+    serial: &str,
+    trace_id: &str,
+) -> Result<(Vec<String>, Vec<String>), AppError> {
+    let timeout = command_timeout(TimeoutKind::DeviceCommand, trace_id);
+    let installed = run_device_shell(
+        adb_program,
         serial,
-        RecordingHandle {
-            child,
-            remote_path: remote_path.clone(),
-        },
-    );
+        &[
+            "cmd",
+            "package",
+            "query-services",
+            "-a",
+            ACCESSIBILITY_SERVICE_ACTION,
+        ],
+        timeout,
+        trace_id,
+    )?;
+    let enabled = run_device_shell(
+        adb_program,
+        serial,
+        &["settings", "get", "secure", ENABLED_ACCESSIBILITY_SERVICES],
+        timeout,
+        trace_id,
+    )?;
+    Ok((
+        parse_component_lines(&installed.stdout),
+        parse_enabled_services(&enabled.stdout),
+    ))
+}
 
+#[tauri::command(async)]
+pub fn list_accessibility_services(
+    serial: String,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<Vec<AccessibilityServiceInfo>>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+    let adb_program = get_adb_program(&trace_id)?;
+    let (installed, enabled) = read_accessibility_services(&adb_program, &serial, &trace_id)?;
     Ok(CommandResponse {
         trace_id,
-        data: remote_path,
+        data: merge_accessibility_services(&installed, &enabled),
     })
 }
 
 #[tauri::command(async)]
-pub fn stop_screen_record(
+pub fn set_accessibility_service(
     serial: String,
-    output_dir: Option<String>,
-    state: State<'_, AppState>,
+    component: String,
+    enable: bool,
+    app: AppHandle,
     trace_id: Option<String>,
-) -> Result<CommandResponse<String>, AppError> {
+) -> Result<CommandResponse<Vec<AccessibilityServiceInfo>>, AppError> {
     let trace_id = resolve_trace_id(trace_id);
     ensure_non_empty(&serial, "serial", &trace_id)?;
-
-    let adb_program = get_adb_program(&trace_id)?;
-    let mut guard = state
-        .recording_processes
-        .lock()
-        .map_err(|_| AppError::system("Recording registry locked", &trace_id))?;
-
-    let handle = match guard.remove(&serial) {
-        Some(handle) => handle,
-        None => return Err(AppError::validation("No recording in progress", &trace_id)),
-    };
-    let mut child = handle.child;
-
-    let _ = Command::new(&adb_program)
-        .args(["-s", &serial, "shell", "pkill", "-SIGINT", "screenrecord"])
-        .output();
-
-    let timeout = Duration::from_secs(5);
-    let start = std::time::Instant::now();
-    loop {
-        match child.try_wait() {
-            Ok(Some(_)) => break,
-            Ok(None) => {
-                if start.elapsed() >= timeout {
-                    let _ = child.kill();
-                    let _ = child.wait();
-                    return Err(AppError::system(
-                        "Timeout waiting for screenrecord",
-                        &trace_id,
-                    ));
-                }
-                std::thread::sleep(Duration::from_millis(100));
-            }
-            Err(err) => {
-                return Err(AppError::system(
-                    format!("Failed to stop screenrecord: {err}"),
-                    &trace_id,
-                ));
-            }
-        }
+    let component = component.trim().to_string();
+    if !is_valid_component(&component) {
+        return Err(AppError::validation(
+            Message::keyed("invalid_value")
+                .with("subject", "accessibility service component")
+                .with("value", &component),
+            &trace_id,
+        ));
     }
-
-    let config = load_config(&trace_id)?;
-    let output_dir = output_dir
-        .filter(|value| !value.trim().is_empty())
-        .unwrap_or_else(|| config.output_path.clone());
-    if output_dir.trim().is_empty() {
-        return Ok(CommandResponse {
-            trace_id,
-            data: String::new(),
-        });
+    warn_on_reservation_conflicts(
+        &app,
+        std::slice::from_ref(&serial),
+        "Toggling accessibility services",
+        &trace_id,
+    );
+    let adb_program = get_adb_program(&trace_id)?;
+    let (installed, enabled) = read_accessibility_services(&adb_program, &serial, &trace_id)?;
+    if enable && !installed.contains(&expand_component(&component)) {
+        return Err(AppError::validation(
+            Message::keyed("not_installed")
+                .with("subject", "Accessibility service")
+                .with("name", &component),
+            &trace_id,
+        ));
     }
 
-    fs::create_dir_all(&output_dir).map_err(|err| {
-        AppError::system(format!("Failed to create output dir: {err}"), &trace_id)
-    })?;
-
-    let filename = PathBuf::from(&handle.remote_path)
-        .file_name()
-        .map(|name| name.to_string_lossy().to_string())
-        .unwrap_or_else(|| format!("screenrecord_{}.mp4", serial));
-    let local_path = PathBuf::from(&output_dir).join(filename);
-
-    let args = vec![
-        "-s".to_string(),
-        serial.clone(),
-        "pull".to_string(),
-        handle.remote_path.clone(),
-        local_path.to_string_lossy().to_string(),
-    ];
-    let output = run_adb(&adb_program, &args, &trace_id)?;
-    if output.exit_code.unwrap_or_default() != 0 {
-        return Err(AppError::dependency(
-            format!("Pull failed: {}", output.stderr),
+    let next = toggle_enabled_services(&enabled, &component, enable);
+    let timeout = command_timeout(TimeoutKind::DeviceCommand, &trace_id);
+    if next.is_empty() {
+        // An empty argument does not survive `adb shell`, so clear the setting instead.
+        run_device_shell(
+            &adb_program,
+            &serial,
+            &[
+                "settings",
+                "delete",
+                "secure",
+                ENABLED_ACCESSIBILITY_SERVICES,
+            ],
+            timeout,
             &trace_id,
-        ));
+        )?;
+    } else {
+        run_device_shell(
+            &adb_program,
+            &serial,
+            &[
+                "settings",
+                "put",
+                "secure",
+                ENABLED_ACCESSIBILITY_SERVICES,
+                &quote_shell_value(&next.join(":")),
+            ],
+            timeout,
+            &trace_id,
+        )?;
     }
+    run_device_shell(
+        &adb_program,
+        &serial,
+        &[
+            "settings",
+            "put",
+            "secure",
+            "accessibility_enabled",
+            if next.is_empty() { "0" } else { "1" },
+        ],
+        timeout,
+        &trace_id,
+    )?;
+    info!(trace_id = %trace_id, serial = %serial, component = %component, enable, "accessibility service toggled");
 
+    let (installed, enabled) = read_accessibility_services(&adb_program, &serial, &trace_id)?;
     Ok(CommandResponse {
         trace_id,
-        data: local_path.to_string_lossy().to_string(),
+        data: merge_accessibility_services(&installed, &enabled),
     })
 }
 
+fn read_input_methods(
+    adb_program: &str,
+    serial: &str,
+    trace_id: &str,
+) -> Result<Vec<InputMethodInfo>, AppError> {
+    let timeout = command_timeout(TimeoutKind::DeviceCommand, trace_id);
+    let available = run_device_shell(
+        adb_program,
+        serial,
+        &["ime", "list", "-s", "-a"],
+        timeout,
+        trace_id,
+    )?;
+    let enabled = run_device_shell(
+        adb_program,
+        serial,
+        &["ime", "list", "-s"],
+        timeout,
+        trace_id,
+    )?;
+    let selected = run_device_shell(
+        adb_program,
+        serial,
+        &["settings", "get", "secure", DEFAULT_INPUT_METHOD],
+        timeout,
+        trace_id,
+    )?;
+    Ok(merge_input_methods(
+        &parse_component_lines(&available.stdout),
+        &parse_component_lines(&enabled.stdout),
+        Some(selected.stdout.trim()).filter(|id| !id.is_empty() && *id != "null"),
+    ))
+}
+
 #[tauri::command(async)]
-pub fn list_device_files(
+pub fn list_input_methods(
     serial: String,
-    path: String,
     trace_id: Option<String>,
-) -> Result<CommandResponse<Vec<DeviceFileEntry>>, AppError> {
+) -> Result<CommandResponse<Vec<InputMethodInfo>>, AppError> {
     let trace_id = resolve_trace_id(trace_id);
     ensure_non_empty(&serial, "serial", &trace_id)?;
-    ensure_non_empty(&path, "path", &trace_id)?;
+    let adb_program = get_adb_program(&trace_id)?;
+    let methods = read_input_methods(&adb_program, &serial, &trace_id)?;
+    Ok(CommandResponse {
+        trace_id,
+        data: methods,
+    })
+}
 
+/// Enable `ime_id` if needed and make it the active keyboard.
+#[tauri::command(async)]
+pub fn set_input_method(
+    serial: String,
+    ime_id: String,
+    app: AppHandle,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<Vec<InputMethodInfo>>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+    let ime_id = ime_id.trim().to_string();
+    if !is_valid_component(&ime_id) {
+        return Err(AppError::validation(
+            Message::keyed("invalid_value")
+                .with("subject", "input method id")
+                .with("value", &ime_id),
+            &trace_id,
+        ));
+    }
+    warn_on_reservation_conflicts(
+        &app,
+        std::slice::from_ref(&serial),
+        "Switching the input method",
+        &trace_id,
+    );
     let adb_program = get_adb_program(&trace_id)?;
-    let normalized = path.trim().to_string();
-    let dir_hint = if normalized == "/" {
-        "/".to_string()
-    } else {
-        format!("{}/", normalized.trim_end_matches('/'))
-    };
-    let dir_args = vec![
-        "-s".to_string(),
-        serial.clone(),
-        "shell".to_string(),
-        "ls".to_string(),
-        "-la".to_string(),
-        dir_hint,
-    ];
-    let mut output =
-        run_command_with_timeout(&adb_program, &dir_args, Duration::from_secs(300), &trace_id)?;
-    if output.exit_code.unwrap_or_default() != 0 {
-        let fallback_args = vec![
-            "-s".to_string(),
-            serial.clone(),
-            "shell".to_string(),
-            "ls".to_string(),
-            "-la".to_string(),
-            normalized.clone(),
-        ];
-        output = run_command_with_timeout(
+    let expanded = expand_component(&ime_id);
+    let methods = read_input_methods(&adb_program, &serial, &trace_id)?;
+    let method = methods
+        .iter()
+        .find(|method| method.id == expanded)
+        .ok_or_else(|| {
+            AppError::validation(
+                Message::keyed("not_installed")
+                    .with("subject", "Input method")
+                    .with("name", &ime_id),
+                &trace_id,
+            )
+        })?;
+
+    let timeout = command_timeout(TimeoutKind::DeviceCommand, &trace_id);
+    let quoted = quote_shell_value(&expanded);
+    if !method.enabled {
+        run_device_shell(
             &adb_program,
-            &fallback_args,
-            Duration::from_secs(300),
+            &serial,
+            &["ime", "enable", &quoted],
+            timeout,
             &trace_id,
         )?;
     }
-    let entries = parse_ls_la(&normalized, &output.stdout);
+    run_device_shell(
+        &adb_program,
+        &serial,
+        &["ime", "set", &quoted],
+        timeout,
+        &trace_id,
+    )?;
 
+    let methods = read_input_methods(&adb_program, &serial, &trace_id)?;
+    if !methods
+        .iter()
+        .any(|method| method.id == expanded && method.selected)
+    {
+        warn!(trace_id = %trace_id, serial = %serial, ime = %expanded, "input method did not become active");
+    }
+    info!(trace_id = %trace_id, serial = %serial, ime = %expanded, "input method set");
     Ok(CommandResponse {
         trace_id,
-        data: entries,
+        data: methods,
     })
 }
 
 #[tauri::command(async)]
-pub fn pull_device_file(
+pub fn open_app_info(
     serial: String,
-    device_path: String,
-    output_dir: String,
-    app: AppHandle,
+    package_name: String,
     trace_id: Option<String>,
-) -> Result<CommandResponse<String>, AppError> {
+) -> Result<CommandResponse<bool>, AppError> {
     let trace_id = resolve_trace_id(trace_id);
     ensure_non_empty(&serial, "serial", &trace_id)?;
-    ensure_non_empty(&device_path, "device_path", &trace_id)?;
-    ensure_non_empty(&output_dir, "output_dir", &trace_id)?;
+    ensure_non_empty(&package_name, "package_name", &trace_id)?;
 
     let adb_program = get_adb_program(&trace_id)?;
-    fs::create_dir_all(&output_dir).map_err(|err| {
-        AppError::system(format!("Failed to create output dir: {err}"), &trace_id)
-    })?;
-
-    let args = vec![
+    let primary_args = vec![
         "-s".to_string(),
         serial.clone(),
-        "pull".to_string(),
-        "-p".to_string(),
-        device_path.clone(),
-        output_dir.clone(),
+        "shell".to_string(),
+        "am".to_string(),
+        "start".to_string(),
+        "-a".to_string(),
+        "android.settings.APPLICATION_DETAILS_SETTINGS".to_string(),
+        "-d".to_string(),
+        format!("package:{package_name}"),
     ];
-    let app_progress = app.clone();
-    let mut output = run_adb_transfer_with_progress(
+    let output = run_command_with_timeout(
         &adb_program,
-        &args,
-        Duration::from_secs(600),
-        &serial,
-        "pull",
+        &primary_args,
+        command_timeout(TimeoutKind::DeviceCommand, &trace_id),
         &trace_id,
-        app_progress,
     )?;
-    if output.exit_code.unwrap_or_default() != 0 {
-        let combined = format!("{}\n{}", output.stdout, output.stderr).to_lowercase();
-        if combined.contains("unknown option") && combined.contains("-p") {
-            let fallback_args: Vec<String> = args
-                .iter()
-                .filter(|value| value.as_str() != "-p")
-                .cloned()
-                .collect();
-            output = run_adb_transfer_with_progress(
-                &adb_program,
-                &fallback_args,
-                Duration::from_secs(600),
-                &serial,
-                "pull",
-                &trace_id,
-                app.clone(),
-            )?;
-        }
-    }
-    if output.exit_code.unwrap_or_default() != 0 {
-        return Err(AppError::dependency(
-            format!("Pull failed: {}", output.stderr),
+    let combined = format!(
+        "{}{}",
+        output.stdout.to_lowercase(),
+        output.stderr.to_lowercase()
+    );
+    if output.exit_code.unwrap_or_default() != 0
+        || combined.contains("error")
+        || combined.contains("exception")
+        || combined.contains("unable")
+    {
+        let legacy_args = vec![
+            "-s".to_string(),
+            serial.clone(),
+            "shell".to_string(),
+            "am".to_string(),
+            "start".to_string(),
+            "-n".to_string(),
+            "com.android.settings/.applications.InstalledAppDetails".to_string(),
+            "-e".to_string(),
+            "package".to_string(),
+            package_name,
+        ];
+        let _ = run_command_with_timeout(
+            &adb_program,
+            &legacy_args,
+            command_timeout(TimeoutKind::DeviceCommand, &trace_id),
             &trace_id,
-        ));
+        )?;
     }
 
-    let filename = PathBuf::from(&device_path)
-        .file_name()
-        .map(|name| name.to_string_lossy().to_string())
-        .unwrap_or_else(|| "output".to_string());
-    let local_path = PathBuf::from(output_dir)
-        .join(filename)
-        .to_string_lossy()
-        .to_string();
-
     Ok(CommandResponse {
         trace_id,
-        data: local_path,
+        data: true,
     })
 }
 
 #[tauri::command(async)]
-pub fn push_device_file(
-    serial: String,
-    local_path: String,
-    device_path: String,
-    app: AppHandle,
+pub fn launch_app(
+    serials: Vec<String>,
+    package_name: String,
+    state: State<'_, AppState>,
     trace_id: Option<String>,
-) -> Result<CommandResponse<String>, AppError> {
+) -> Result<CommandResponse<Vec<CommandResult>>, AppError> {
     let trace_id = resolve_trace_id(trace_id);
-    ensure_non_empty(&serial, "serial", &trace_id)?;
-    ensure_non_empty(&local_path, "local_path", &trace_id)?;
-    ensure_non_empty(&device_path, "device_path", &trace_id)?;
-
-    if let Err(message) = validate_device_path(&device_path) {
-        return Err(AppError::validation(message, &trace_id));
-    }
-
-    let host_path = PathBuf::from(&local_path);
-    if !host_path.exists() {
-        return Err(AppError::validation("Local file does not exist", &trace_id));
-    }
-    if !host_path.is_file() {
-        return Err(AppError::validation("Local path must be a file", &trace_id));
+    ensure_non_empty(&package_name, "package_name", &trace_id)?;
+    if serials.is_empty() {
+        return Err(AppError::validation(
+            Message::keyed("required").with("field", "serials"),
+            &trace_id,
+        ));
     }
 
     let adb_program = get_adb_program(&trace_id)?;
+    let scheduler = Arc::clone(&state.scheduler);
 
-    let device_dir = device_parent_dir(&device_path);
-    if device_dir != "/" {
-        let mkdir_args = vec![
-            "-s".to_string(),
-            serial.clone(),
-            "shell".to_string(),
-            "mkdir".to_string(),
-            "-p".to_string(),
-            device_dir,
-        ];
-        let mkdir_output = run_command_with_timeout(
-            &adb_program,
-            &mkdir_args,
-            Duration::from_secs(10),
-            &trace_id,
-        )?;
-        if mkdir_output.exit_code.unwrap_or_default() != 0 {
-            return Err(AppError::dependency(
-                format!("Failed to create device directory: {}", mkdir_output.stderr),
-                &trace_id,
-            ));
-        }
-    }
+    let mut handles = Vec::new();
+    for (index, serial) in serials.into_iter().enumerate() {
+        ensure_non_empty(&serial, "serial", &trace_id)?;
+        let scheduler_clone = Arc::clone(&scheduler);
+        let trace_clone = trace_id.clone();
+        let adb_program_clone = adb_program.clone();
+        let package_clone = package_name.clone();
+        handles.push(std::thread::spawn(move || -> Result<_, AppError> {
+            let _permit = scheduler_clone.acquire_global();
+            let device_lock = scheduler_clone.device_lock(&serial);
+            let _device_guard = device_lock.lock().map_err(|_| {
+                warn!(trace_id = %trace_clone, serial = %serial, "device lock poisoned");
+                AppError::system(
+                    "Failed to access the device. Please try again.",
+                    &trace_clone,
+                )
+            })?;
 
-    let args = vec![
-        "-s".to_string(),
-        serial.clone(),
-        "push".to_string(),
-        "-p".to_string(),
-        local_path.clone(),
-        device_path.clone(),
-    ];
-    let app_progress = app.clone();
-    let mut output = run_adb_transfer_with_progress(
-        &adb_program,
-        &args,
-        Duration::from_secs(600),
-        &serial,
-        "push",
-        &trace_id,
-        app_progress,
-    )?;
-    if output.exit_code.unwrap_or_default() != 0 {
-        let combined = format!("{}\n{}", output.stdout, output.stderr).to_lowercase();
-        if combined.contains("unknown option") && combined.contains("-p") {
-            let fallback_args: Vec<String> = args
-                .iter()
-                .filter(|value| value.as_str() != "-p")
-                .cloned()
-                .collect();
-            output = run_adb_transfer_with_progress(
-                &adb_program,
-                &fallback_args,
-                Duration::from_secs(600),
-                &serial,
-                "push",
-                &trace_id,
-                app.clone(),
+            let args = vec![
+                "-s".to_string(),
+                serial.clone(),
+                "shell".to_string(),
+                "monkey".to_string(),
+                "-p".to_string(),
+                package_clone,
+                "-c".to_string(),
+                "android.intent.category.LAUNCHER".to_string(),
+                "1".to_string(),
+            ];
+            let output = run_command_with_timeout(
+                &adb_program_clone,
+                &args,
+                command_timeout(TimeoutKind::DeviceCommand, &trace_clone),
+                &trace_clone,
             )?;
-        }
+            Ok((
+                index,
+                CommandResult {
+                    serial,
+                    stdout: output.stdout,
+                    stderr: output.stderr,
+                    exit_code: output.exit_code,
+                },
+            ))
+        }));
     }
-    if output.exit_code.unwrap_or_default() != 0 {
-        return Err(AppError::dependency(
-            format!("Push failed: {}", output.stderr),
-            &trace_id,
-        ));
+
+    let mut collected = Vec::new();
+    for handle in handles {
+        let (index, result) = handle
+            .join()
+            .map_err(|_| AppError::system("Launch app thread panicked", &trace_id))??;
+        collected.push((index, result));
+    }
+    collected.sort_by_key(|item| item.0);
+    let results = collected.into_iter().map(|item| item.1).collect();
+
+    Ok(CommandResponse {
+        trace_id,
+        data: results,
+    })
+}
+
+#[tauri::command(async)]
+pub fn check_scrcpy(trace_id: Option<String>) -> Result<CommandResponse<ScrcpyInfo>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    let availability = check_scrcpy_availability();
+    Ok(CommandResponse {
+        trace_id,
+        data: ScrcpyInfo {
+            available: availability.available,
+            version_output: availability.version_output,
+            major_version: availability.major_version,
+            command_path: availability.command_path,
+        },
+    })
+}
+
+/// Dry-builds the scrcpy command for `options` and reports flags the installed scrcpy
+/// does not support.
+#[tauri::command(async)]
+pub fn validate_scrcpy_options(
+    options: ScrcpyOptions,
+    serial: Option<String>,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<ScrcpyValidation>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    let serial = serial
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+        .unwrap_or_else(|| "SERIAL".to_string());
+
+    let availability = check_scrcpy_availability();
+    let version = parse_scrcpy_version(&availability.version_output)
+        .unwrap_or((availability.major_version, 0));
+    let settings = options.to_settings();
+    let mut command = build_scrcpy_command(&serial, &settings, availability.major_version);
+    if !availability.command_path.trim().is_empty() {
+        command[0] = availability.command_path.clone();
     }
+    let issues = scrcpy_option_issues(&settings, &command, version);
 
     Ok(CommandResponse {
         trace_id,
-        data: device_path,
+        data: ScrcpyValidation {
+            available: availability.available,
+            version: availability
+                .available
+                .then(|| format!("{}.{}", version.0, version.1)),
+            valid: issues.is_empty(),
+            command,
+            issues,
+        },
     })
 }
 
 #[tauri::command(async)]
-pub fn mkdir_device_dir(
-    serial: String,
-    device_path: String,
+pub fn launch_scrcpy(
+    serials: Vec<String>,
+    preset: Option<String>,
+    state: State<'_, AppState>,
     trace_id: Option<String>,
-) -> Result<CommandResponse<String>, AppError> {
+) -> Result<CommandResponse<Vec<CommandResult>>, AppError> {
     let trace_id = resolve_trace_id(trace_id);
-    ensure_non_empty(&serial, "serial", &trace_id)?;
-    ensure_non_empty(&device_path, "device_path", &trace_id)?;
-
-    if let Err(message) = validate_device_path(&device_path) {
-        return Err(AppError::validation(message, &trace_id));
+    if serials.is_empty() {
+        return Err(AppError::validation(
+            Message::keyed("required").with("field", "serials"),
+            &trace_id,
+        ));
     }
 
-    let adb_program = get_adb_program(&trace_id)?;
-    let args = vec![
-        "-s".to_string(),
-        serial.clone(),
-        "shell".to_string(),
-        "mkdir".to_string(),
-        "-p".to_string(),
-        device_path.clone(),
-    ];
-    let output = run_command_with_timeout(&adb_program, &args, Duration::from_secs(10), &trace_id)?;
-    if output.exit_code.unwrap_or_default() != 0 {
+    let availability = check_scrcpy_availability();
+    if !availability.available {
         return Err(AppError::dependency(
-            format!("mkdir failed: {}", output.stderr),
+            Message::keyed("not_available").with("subject", "scrcpy"),
             &trace_id,
         ));
     }
+    let config = load_config(&trace_id)?;
+    let scheduler = Arc::clone(&state.scheduler);
+
+    let mut results = Vec::with_capacity(serials.len());
+    for serial in serials {
+        ensure_non_empty(&serial, "serial", &trace_id)?;
+        let _permit = scheduler.acquire_global();
+        let device_lock = scheduler.device_lock(&serial);
+        let _device_guard = match device_lock.lock() {
+            Ok(guard) => guard,
+            Err(_) => {
+                warn!(trace_id = %trace_id, serial = %serial, "device lock poisoned");
+                results.push(CommandResult {
+                    serial,
+                    stdout: String::new(),
+                    stderr: "Failed to access the device. Please try again.".to_string(),
+                    exit_code: Some(1),
+                });
+                continue;
+            }
+        };
+        let settings = resolve_scrcpy_settings(&config.scrcpy, &serial, preset.as_deref())
+            .map_err(|message| AppError::validation(message, &trace_id))?;
+        let mut args = build_scrcpy_command(&serial, &settings, availability.major_version);
+        if !availability.command_path.trim().is_empty() {
+            args[0] = availability.command_path.clone();
+        }
+        let mut iter = args.into_iter();
+        let command_path = iter.next().unwrap_or_else(|| "scrcpy".to_string());
+        let mut command = adb_command(&command_path);
+        command
+            .args(iter)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        // scrcpy picks its adb binary from `ADB`.
+        let default_adb = resolve_adb_program(&config.adb.command_path);
+        let device_adb =
+            resolve_adb_program_for_serial(&default_adb, &config.adb.path_overrides, &serial);
+        if device_adb != default_adb {
+            command.env("ADB", &device_adb);
+        }
+        let spawn_result = command.spawn();
+        match spawn_result {
+            Ok(mut child) => {
+                std::thread::sleep(Duration::from_millis(150));
+                match child.try_wait() {
+                    Ok(Some(_)) => match child.wait_with_output() {
+                        Ok(output) => {
+                            let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
+                            let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+                            let exit_code = output.status.code().unwrap_or(1);
+                            let (final_exit, final_stderr) = if output.status.success() {
+                                let detail = if !stderr.is_empty() {
+                                    stderr
+                                } else {
+                                    stdout.clone()
+                                };
+                                let message = if detail.is_empty() {
+                                    "scrcpy exited immediately".to_string()
+                                } else {
+                                    format!("scrcpy exited immediately: {detail}")
+                                };
+                                (1, message)
+                            } else {
+                                (exit_code, stderr)
+                            };
+                            if final_exit != 0 {
+                                warn!(
+                                    trace_id = %trace_id,
+                                    serial = %serial,
+                                    error = %final_stderr,
+                                    "scrcpy exited immediately"
+                                );
+                            }
+                            results.push(CommandResult {
+                                serial,
+                                stdout,
+                                stderr: final_stderr,
+                                exit_code: Some(final_exit),
+                            });
+                        }
+                        Err(err) => results.push(CommandResult {
+                            serial,
+                            stdout: String::new(),
+                            stderr: format!("Failed to capture scrcpy output: {err}"),
+                            exit_code: Some(1),
+                        }),
+                    },
+                    Ok(None) => results.push(CommandResult {
+                        serial,
+                        stdout: "scrcpy launched".to_string(),
+                        stderr: String::new(),
+                        exit_code: Some(0),
+                    }),
+                    Err(err) => results.push(CommandResult {
+                        serial,
+                        stdout: String::new(),
+                        stderr: format!("Failed to check scrcpy status: {err}"),
+                        exit_code: Some(1),
+                    }),
+                }
+            }
+            Err(err) => results.push(CommandResult {
+                serial,
+                stdout: String::new(),
+                stderr: format!("Failed to launch scrcpy: {err}"),
+                exit_code: Some(1),
+            }),
+        }
+    }
 
     Ok(CommandResponse {
         trace_id,
-        data: device_path,
+        data: results,
     })
 }
 
+const AUDIO_FORWARD_STDERR_LIMIT: usize = 4096;
+
+fn audio_forward_session(
+    serial: &str,
+    handle: &AudioForwardHandle,
+    running: bool,
+) -> AudioForwardSession {
+    let tail = handle
+        .stderr_tail
+        .lock()
+        .map(|tail| tail.trim().to_string())
+        .unwrap_or_default();
+    AudioForwardSession {
+        serial: serial.to_string(),
+        codec: handle.codec.clone(),
+        source: handle.source.clone(),
+        started_at: handle.started_at.to_rfc3339(),
+        running,
+        error: (!running && !tail.is_empty()).then_some(tail),
+    }
+}
+
+fn finish_audio_forward(mut handle: AudioForwardHandle) {
+    let _ = handle.child.kill();
+    let _ = handle.child.wait();
+    if let Some(join) = handle.stderr_join.take() {
+        let _ = join.join();
+    }
+}
+
+/// Starts an audio-only scrcpy session (no video, no control) for `serial`. Needs scrcpy
+/// 2.1+ and Android 11+; older setups get a pointer to sndcpy instead.
 #[tauri::command(async)]
-pub fn rename_device_path(
+pub fn start_audio_forward(
     serial: String,
-    from_path: String,
-    to_path: String,
+    codec: Option<String>,
+    source: Option<String>,
+    state: State<'_, AppState>,
     trace_id: Option<String>,
-) -> Result<CommandResponse<String>, AppError> {
+) -> Result<CommandResponse<AudioForwardSession>, AppError> {
     let trace_id = resolve_trace_id(trace_id);
     ensure_non_empty(&serial, "serial", &trace_id)?;
-    ensure_non_empty(&from_path, "from_path", &trace_id)?;
-    ensure_non_empty(&to_path, "to_path", &trace_id)?;
+    let codec = normalize_audio_codec(codec.as_deref())
+        .map_err(|message| AppError::validation(message, &trace_id))?;
+    let source = normalize_audio_source(source.as_deref())
+        .map_err(|message| AppError::validation(message, &trace_id))?;
 
-    if let Err(message) = validate_device_path(&from_path) {
-        return Err(AppError::validation(message, &trace_id));
-    }
-    if let Err(message) = validate_device_path(&to_path) {
-        return Err(AppError::validation(message, &trace_id));
+    {
+        let mut guard = state
+            .audio_forwards
+            .lock()
+            .map_err(|_| AppError::system("Audio forward registry locked", &trace_id))?;
+        if let Some(handle) = guard.get_mut(&serial) {
+            if matches!(handle.child.try_wait(), Ok(None)) {
+                return Err(AppError::validation(
+                    Message::keyed("already_active").with("subject", "Audio forwarding"),
+                    &trace_id,
+                ));
+            }
+            if let Some(handle) = guard.remove(&serial) {
+                finish_audio_forward(handle);
+            }
+        }
     }
-    if from_path.trim() == to_path.trim() {
-        return Err(AppError::validation(
-            "from_path and to_path must be different",
+
+    let availability = check_scrcpy_availability();
+    if !availability.available {
+        return Err(AppError::dependency(
+            Message::keyed("not_available").with("subject", "scrcpy"),
             &trace_id,
         ));
     }
+    let version = parse_scrcpy_version(&availability.version_output)
+        .unwrap_or((availability.major_version, 0));
 
     let adb_program = get_adb_program(&trace_id)?;
-    let args = vec![
-        "-s".to_string(),
-        serial.clone(),
-        "shell".to_string(),
-        "mv".to_string(),
-        from_path,
-        to_path.clone(),
-    ];
-    let output = run_command_with_timeout(&adb_program, &args, Duration::from_secs(10), &trace_id)?;
-    if output.exit_code.unwrap_or_default() != 0 {
-        return Err(AppError::dependency(
-            format!("rename failed: {}", output.stderr),
+    let device_sdk = match run_device_shell(
+        &adb_program,
+        &serial,
+        &["getprop", "ro.build.version.sdk"],
+        command_timeout(TimeoutKind::Probe, &trace_id),
+        &trace_id,
+    ) {
+        Ok(output) => output.stdout.trim().parse::<i64>().ok(),
+        Err(err) => {
+            warn!(trace_id = %trace_id, serial = %serial, error = %err, "failed to read device SDK for audio forwarding");
+            None
+        }
+    };
+
+    let args = build_audio_forward_args(&serial, &codec, &source);
+    if let Some(message) = audio_forward_blocker(&args, &codec, version, device_sdk) {
+        return Err(AppError::dependency(message, &trace_id));
+    }
+
+    let config = load_config(&trace_id)?;
+    let command_path = if availability.command_path.trim().is_empty() {
+        "scrcpy".to_string()
+    } else {
+        availability.command_path.clone()
+    };
+    let mut command = adb_command(&command_path);
+    command
+        .args(&args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped());
+    // scrcpy picks its adb binary from `ADB`.
+    let default_adb = resolve_adb_program(&config.adb.command_path);
+    let device_adb =
+        resolve_adb_program_for_serial(&default_adb, &config.adb.path_overrides, &serial);
+    if device_adb != default_adb {
+        command.env("ADB", &device_adb);
+    }
+    let mut child = command.spawn().map_err(|err| {
+        AppError::dependency(
+            Message::keyed("operation_failed")
+                .with("action", "launch scrcpy")
+                .with("detail", &err),
             &trace_id,
-        ));
+        )
+    })?;
+
+    let stderr_tail = Arc::new(Mutex::new(String::new()));
+    let stderr_join = child.stderr.take().map(|stderr| {
+        let tail = Arc::clone(&stderr_tail);
+        std::thread::spawn(move || {
+            let reader = BufReader::new(stderr);
+            for line in reader.lines().map_while(Result::ok) {
+                if let Ok(mut tail) = tail.lock() {
+                    append_limited(&mut tail, &format!("{line}\n"), AUDIO_FORWARD_STDERR_LIMIT);
+                }
+            }
+        })
+    });
+    let mut handle = AudioForwardHandle {
+        child,
+        codec,
+        source,
+        started_at: Local::now(),
+        stderr_tail,
+        stderr_join,
+    };
+
+    std::thread::sleep(Duration::from_millis(300));
+    match handle.child.try_wait() {
+        Ok(None) => {}
+        Ok(Some(status)) => {
+            if let Some(join) = handle.stderr_join.take() {
+                let _ = join.join();
+            }
+            let detail = handle
+                .stderr_tail
+                .lock()
+                .map(|tail| tail.trim().to_string())
+                .unwrap_or_default();
+            warn!(trace_id = %trace_id, serial = %serial, status = %status, error = %detail, "audio forwarding exited immediately");
+            let message = if detail.is_empty() {
+                "scrcpy exited immediately".to_string()
+            } else {
+                format!("scrcpy exited immediately: {detail}")
+            };
+            return Err(AppError::dependency(message, &trace_id));
+        }
+        Err(err) => {
+            finish_audio_forward(handle);
+            return Err(AppError::system(
+                Message::keyed("operation_failed")
+                    .with("action", "check scrcpy status")
+                    .with("detail", &err),
+                &trace_id,
+            ));
+        }
+    }
+
+    let session = audio_forward_session(&serial, &handle, true);
+    let mut guard = state
+        .audio_forwards
+        .lock()
+        .map_err(|_| AppError::system("Audio forward registry locked", &trace_id))?;
+    if let Some(previous) = guard.insert(serial.clone(), handle) {
+        finish_audio_forward(previous);
     }
+    info!(trace_id = %trace_id, serial = %serial, codec = %session.codec, source = %session.source, "audio forwarding started");
 
     Ok(CommandResponse {
         trace_id,
-        data: to_path,
+        data: session,
     })
 }
 
 #[tauri::command(async)]
-pub fn delete_device_path(
+pub fn stop_audio_forward(
     serial: String,
-    device_path: String,
-    recursive: bool,
+    state: State<'_, AppState>,
     trace_id: Option<String>,
-) -> Result<CommandResponse<String>, AppError> {
+) -> Result<CommandResponse<bool>, AppError> {
     let trace_id = resolve_trace_id(trace_id);
     ensure_non_empty(&serial, "serial", &trace_id)?;
-    ensure_non_empty(&device_path, "device_path", &trace_id)?;
-
-    if let Err(message) = validate_device_path(&device_path) {
-        return Err(AppError::validation(message, &trace_id));
-    }
-
-    let adb_program = get_adb_program(&trace_id)?;
-    let mut args = vec![
-        "-s".to_string(),
-        serial.clone(),
-        "shell".to_string(),
-        "rm".to_string(),
-    ];
-    if recursive {
-        args.push("-rf".to_string());
-    } else {
-        args.push("-f".to_string());
-    }
-    args.push(device_path.clone());
-
-    let output = run_command_with_timeout(&adb_program, &args, Duration::from_secs(10), &trace_id)?;
-    if output.exit_code.unwrap_or_default() != 0 {
-        return Err(AppError::dependency(
-            format!("delete failed: {}", output.stderr),
+
+    let handle = state
+        .audio_forwards
+        .lock()
+        .map_err(|_| AppError::system("Audio forward registry locked", &trace_id))?
+        .remove(&serial);
+    let Some(handle) = handle else {
+        return Err(AppError::validation(
+            Message::keyed("not_running").with("subject", "Audio forwarding"),
             &trace_id,
         ));
-    }
+    };
+    finish_audio_forward(handle);
 
     Ok(CommandResponse {
         trace_id,
-        data: device_path,
+        data: true,
     })
 }
 
+/// Active sessions plus any that exited on their own since the last call; the latter are
+/// reported once with their stderr and then dropped.
 #[tauri::command(async)]
-pub fn preview_local_file(
-    local_path: String,
+pub fn list_audio_forwards(
+    state: State<'_, AppState>,
     trace_id: Option<String>,
-) -> Result<CommandResponse<FilePreview>, AppError> {
-    use base64::Engine as _;
-
+) -> Result<CommandResponse<Vec<AudioForwardSession>>, AppError> {
     let trace_id = resolve_trace_id(trace_id);
-    ensure_non_empty(&local_path, "local_path", &trace_id)?;
-
-    let path = PathBuf::from(&local_path);
-    if !path.exists() {
-        return Err(AppError::validation("File does not exist", &trace_id));
+    let mut guard = state
+        .audio_forwards
+        .lock()
+        .map_err(|_| AppError::system("Audio forward registry locked", &trace_id))?;
+
+    let mut sessions = Vec::with_capacity(guard.len());
+    let mut exited = Vec::new();
+    for (serial, handle) in guard.iter_mut() {
+        let running = matches!(handle.child.try_wait(), Ok(None));
+        if !running {
+            exited.push(serial.clone());
+        }
+        sessions.push(audio_forward_session(serial, handle, running));
     }
+    for serial in exited {
+        warn!(trace_id = %trace_id, serial = %serial, "audio forwarding exited");
+        if let Some(handle) = guard.remove(&serial) {
+            finish_audio_forward(handle);
+        }
+    }
+    sessions.sort_by(|left, right| left.serial.cmp(&right.serial));
 
-    let mime = MimeGuess::from_path(&path).first_or_octet_stream();
-    let mime_type = mime.essence_str().to_string();
+    Ok(CommandResponse {
+        trace_id,
+        data: sessions,
+    })
+}
 
-    const MAX_TEXT_PREVIEW_BYTES: usize = 200_000;
-    const MAX_IMAGE_PREVIEW_BYTES: usize = 6_000_000;
-    let mut file = fs::File::open(&path)
-        .map_err(|err| AppError::system(format!("Failed to open file: {err}"), &trace_id))?;
-    let mut buffer = Vec::new();
-    let is_image = mime_type.starts_with("image/");
-    let max_preview_bytes = if is_image {
-        MAX_IMAGE_PREVIEW_BYTES
-    } else {
-        MAX_TEXT_PREVIEW_BYTES
-    };
-    std::io::Read::by_ref(&mut file)
-        .take((max_preview_bytes + 1) as u64)
-        .read_to_end(&mut buffer)
-        .map_err(|err| AppError::system(format!("Failed to read file: {err}"), &trace_id))?;
+#[tauri::command(async)]
+pub fn capture_ui_hierarchy(
+    serial: String,
+    backend: Option<String>,
+    state: State<'_, AppState>,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<UiHierarchyCaptureResult>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&serial, "serial", &trace_id)?;
 
-    let mut preview_text = None;
-    let mut is_text = false;
-    let mut preview_data_url = None;
+    let adb_program = get_adb_program(&trace_id)?;
+    let config = load_config(&trace_id)?;
+    let (xml, backend) = dump_ui_xml(&adb_program, &serial, backend.as_deref(), &trace_id)?;
+    let html = render_device_ui_html(&xml).map_err(|err| {
+        AppError::system(
+            Message::keyed("operation_failed")
+                .with("action", "render HTML")
+                .with("detail", &err),
+            &trace_id,
+        )
+    })?;
 
-    if is_image {
-        // For images we only return a data URL if we captured the full file within the cap.
-        if buffer.len() <= max_preview_bytes {
-            let encoded = base64::engine::general_purpose::STANDARD.encode(&buffer);
-            preview_data_url = Some(format!("data:{mime_type};base64,{encoded}"));
-        }
-    } else if let Ok(text) = std::str::from_utf8(&buffer) {
-        if !contains_binary_control_chars(text) {
-            is_text = true;
-            let mut content = text.to_string();
-            if buffer.len() > MAX_TEXT_PREVIEW_BYTES {
-                content.truncate(MAX_TEXT_PREVIEW_BYTES);
-                content.push_str("\n… (truncated)");
+    let mut screenshot_args = vec![
+        "-s".to_string(),
+        serial.clone(),
+        "exec-out".to_string(),
+        "screencap".to_string(),
+        "-p".to_string(),
+    ];
+    if config.screenshot.display_id >= 0 {
+        screenshot_args.push("-d".to_string());
+        screenshot_args.push(config.screenshot.display_id.to_string());
+    }
+    if !config.screenshot.extra_args.trim().is_empty() {
+        screenshot_args.extend(
+            config
+                .screenshot
+                .extra_args
+                .split_whitespace()
+                .map(|item| item.to_string()),
+        );
+    }
+
+    let (screenshot_data_url, screenshot_error) = match adb_command(&route_adb_program(
+        &adb_program,
+        &screenshot_args,
+    ))
+    .args(&screenshot_args)
+    .output()
+    {
+        Ok(screenshot_output) => {
+            if !screenshot_output.status.success() {
+                let message = format!(
+                    "Screenshot failed: {}",
+                    String::from_utf8_lossy(&screenshot_output.stderr)
+                );
+                warn!(trace_id = %trace_id, error = %message, "ui_inspector screenshot failed");
+                (None, Some(message))
+            } else {
+                match png_bytes_to_data_url(&screenshot_output.stdout) {
+                    Ok(url) => (Some(url), None),
+                    Err(message) => {
+                        warn!(trace_id = %trace_id, error = %message, "ui_inspector screenshot invalid");
+                        (None, Some(message))
+                    }
+                }
             }
-            preview_text = Some(content);
         }
-    }
+        Err(err) => {
+            let message = format!("Failed to capture screenshot: {err}");
+            warn!(trace_id = %trace_id, error = %message, "ui_inspector screenshot error");
+            (None, Some(message))
+        }
+    };
 
-    let is_text_flag = is_text || mime_type.starts_with("text/");
+    let capture_id = Uuid::new_v4().to_string();
+    let record = UiCaptureRecord {
+        capture_id: capture_id.clone(),
+        serial: serial.clone(),
+        captured_at: Utc::now().to_rfc3339(),
+        backend: backend.clone(),
+        node_count: extract_ui_nodes(&xml).len(),
+        xml: xml.clone(),
+        html: html.clone(),
+        diff: None,
+    };
+    let diff = record_ui_capture_inner(
+        &state.ui_captures,
+        record,
+        UI_CAPTURE_HISTORY_LIMIT,
+        &trace_id,
+    )?;
 
     Ok(CommandResponse {
         trace_id,
-        data: FilePreview {
-            local_path,
-            mime_type,
-            is_text: is_text_flag,
-            preview_text,
-            preview_data_url,
+        data: UiHierarchyCaptureResult {
+            html,
+            xml,
+            screenshot_data_url,
+            screenshot_error,
+            backend,
+            capture_id,
+            diff,
         },
     })
 }
 
-fn contains_binary_control_chars(text: &str) -> bool {
-    for ch in text.chars() {
-        if ch == '\n' || ch == '\r' || ch == '\t' {
-            continue;
+fn read_density_dpi(adb_program: &str, serial: &str, trace_id: &str) -> u32 {
+    let density = run_device_shell(
+        adb_program,
+        serial,
+        &["wm", "density"],
+        command_timeout(TimeoutKind::DeviceCommand, trace_id),
+        trace_id,
+    )
+    .map(|output| parse_wm_density(&output.stdout));
+    match density {
+        Ok(Some(density)) => density,
+        Ok(None) => {
+            warn!(trace_id = %trace_id, serial = %serial, "could not parse wm density, assuming mdpi");
+            DEFAULT_DENSITY_DPI
         }
-        if ch == '\u{0}' || ch < '\u{20}' {
-            return true;
+        Err(err) => {
+            warn!(trace_id = %trace_id, serial = %serial, error = %err.error, "failed to read wm density, assuming mdpi");
+            DEFAULT_DENSITY_DPI
         }
     }
-    false
-}
-
-fn cache_dir_for_app_icons() -> PathBuf {
-    let base = dirs::cache_dir().unwrap_or_else(std::env::temp_dir);
-    base.join("lazy_blacktea").join("app_icons")
-}
-
-fn density_rank(path: &str) -> i32 {
-    let lower = path.to_lowercase();
-    if lower.contains("xxxhdpi") {
-        return 6;
-    }
-    if lower.contains("xxhdpi") {
-        return 5;
-    }
-    if lower.contains("xhdpi") {
-        return 4;
-    }
-    if lower.contains("hdpi") {
-        return 3;
-    }
-    if lower.contains("mdpi") {
-        return 2;
-    }
-    if lower.contains("ldpi") {
-        return 1;
-    }
-    if lower.contains("nodpi") {
-        return 0;
-    }
-    0
 }
 
-fn icon_name_rank(path: &str) -> i32 {
-    let lower = path.to_lowercase();
-    if lower.contains("ic_launcher") {
-        return 6;
-    }
-    if lower.contains("launcher") {
-        return 5;
-    }
-    if lower.contains("appicon") {
-        return 4;
-    }
-    if lower.contains("app_icon") {
-        return 4;
-    }
-    if lower.contains("icon") {
-        return 2;
-    }
-    0
+fn capture_screenshot_rgba(
+    adb_program: &str,
+    serial: &str,
+    trace_id: &str,
+) -> Result<RgbaImage, AppError> {
+    let config = load_config(trace_id)?;
+    let file = tempfile::Builder::new()
+        .suffix(".png")
+        .tempfile()
+        .map_err(|err| {
+            AppError::system(
+                Message::keyed("operation_failed")
+                    .with("action", "create temp file")
+                    .with("detail", &err),
+                trace_id,
+            )
+        })?;
+    capture_screenshot_to(adb_program, serial, &config, file.path(), trace_id)?;
+    read_png_rgba(file.path()).map_err(|err| AppError::system(err, trace_id))
 }
 
-fn extract_best_icon_from_apk(
-    path: &std::path::Path,
-    trace_id: &str,
-) -> Result<Option<(String, Vec<u8>)>, AppError> {
-    let file = fs::File::open(path)
-        .map_err(|err| AppError::system(format!("Failed to open APK: {err}"), trace_id))?;
-    let mut zip = ZipArchive::new(file)
-        .map_err(|err| AppError::system(format!("Failed to read APK zip: {err}"), trace_id))?;
+/// Dump the UI hierarchy and flag common accessibility issues: clickable elements without a
+/// label, touch targets under 48dp and, unless `include_contrast` is false, low text
+/// contrast estimated from a screenshot.
+#[tauri::command(async)]
+pub fn run_accessibility_audit(
+    serial: String,
+    include_contrast: Option<bool>,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<AccessibilityAudit>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&serial, "serial", &trace_id)?;
 
-    let mut best: Option<(i32, i32, u64, usize, String)> = None;
-    for index in 0..zip.len() {
-        let entry = match zip.by_index(index) {
-            Ok(value) => value,
-            Err(_) => continue,
-        };
-        let name = entry.name().to_string();
-        if !name.starts_with("res/") {
-            continue;
-        }
-        let lower = name.to_lowercase();
-        if !(lower.ends_with(".png") || lower.ends_with(".webp")) {
-            continue;
-        }
-        if !(lower.contains("/mipmap") || lower.contains("/drawable")) {
-            continue;
-        }
-        let name_score = icon_name_rank(&name);
-        let density_score = density_rank(&name);
-        let size = entry.size();
-        let candidate = (name_score, density_score, size, index, name);
-        if let Some(current) = &best {
-            if candidate.0 > current.0
-                || (candidate.0 == current.0 && candidate.1 > current.1)
-                || (candidate.0 == current.0 && candidate.1 == current.1 && candidate.2 > current.2)
-            {
-                best = Some(candidate);
+    let adb_program = get_adb_program(&trace_id)?;
+    let (xml, _) = dump_ui_xml(&adb_program, &serial, None, &trace_id)?;
+    let nodes = extract_ui_nodes(&xml);
+    let density_dpi = read_density_dpi(&adb_program, &serial, &trace_id);
+    let mut findings = audit_nodes(&nodes, density_dpi);
+
+    let mut contrast_checked = false;
+    if include_contrast.unwrap_or(true) {
+        match capture_screenshot_rgba(&adb_program, &serial, &trace_id) {
+            Ok(image) => {
+                findings.extend(audit_contrast(&nodes, &image));
+                contrast_checked = true;
+            }
+            Err(err) => {
+                warn!(trace_id = %trace_id, serial = %serial, error = %err.error, "accessibility audit skipped contrast checks");
             }
-        } else {
-            best = Some(candidate);
         }
     }
-
-    let Some((_name_score, _density_score, _size, index, entry_name)) = best else {
-        return Ok(None);
+    let severity_rank = |severity: &str| match severity {
+        SEVERITY_ERROR => 0,
+        SEVERITY_WARNING => 1,
+        _ => 2,
     };
+    findings.sort_by_key(|finding| severity_rank(&finding.severity));
+    info!(
+        trace_id = %trace_id,
+        serial = %serial,
+        nodes = nodes.len(),
+        findings = findings.len(),
+        "accessibility audit finished"
+    );
+
+    Ok(CommandResponse {
+        trace_id,
+        data: AccessibilityAudit {
+            serial,
+            density_dpi,
+            node_count: nodes.len(),
+            clickable_count: nodes.iter().filter(|node| node.clickable).count(),
+            contrast_checked,
+            findings,
+            captured_at: Utc::now().to_rfc3339(),
+        },
+    })
+}
+
+const UI_CAPTURE_HISTORY_LIMIT: usize = 10;
+
+fn record_ui_capture_inner(
+    registry: &std::sync::Mutex<HashMap<String, VecDeque<UiCaptureRecord>>>,
+    mut record: UiCaptureRecord,
+    limit: usize,
+    trace_id: &str,
+) -> Result<Option<UiHierarchyDiff>, AppError> {
+    let mut guard = registry
+        .lock()
+        .map_err(|_| AppError::system("UI capture history locked", trace_id))?;
+    let history = guard.entry(record.serial.clone()).or_default();
+    let diff = history
+        .back()
+        .map(|previous| build_ui_hierarchy_diff(&previous.capture_id, &previous.xml, &record.xml));
+    record.diff = diff.clone();
+    history.push_back(record);
+    while history.len() > limit.max(1) {
+        history.pop_front();
+    }
+    Ok(diff)
+}
 
-    let mut entry = zip
-        .by_index(index)
-        .map_err(|err| AppError::system(format!("Failed to read icon entry: {err}"), trace_id))?;
-    let mut bytes = Vec::new();
-    entry
-        .read_to_end(&mut bytes)
-        .map_err(|err| AppError::system(format!("Failed to read icon bytes: {err}"), trace_id))?;
-    Ok(Some((entry_name, bytes)))
+fn list_ui_captures_inner(
+    serial: &str,
+    registry: &std::sync::Mutex<HashMap<String, VecDeque<UiCaptureRecord>>>,
+    trace_id: &str,
+) -> Result<Vec<UiCaptureRecord>, AppError> {
+    ensure_non_empty(serial, "serial", trace_id)?;
+    let guard = registry
+        .lock()
+        .map_err(|_| AppError::system("UI capture history locked", trace_id))?;
+    Ok(guard
+        .get(serial)
+        .map(|history| history.iter().rev().cloned().collect())
+        .unwrap_or_default())
 }
 
 #[tauri::command(async)]
-pub fn list_apps(
+pub fn list_ui_captures(
     serial: String,
-    third_party_only: Option<bool>,
-    include_versions: Option<bool>,
+    state: State<'_, AppState>,
     trace_id: Option<String>,
-) -> Result<CommandResponse<Vec<AppInfo>>, AppError> {
+) -> Result<CommandResponse<Vec<UiCaptureRecord>>, AppError> {
     let trace_id = resolve_trace_id(trace_id);
-    ensure_non_empty(&serial, "serial", &trace_id)?;
+    let captures = list_ui_captures_inner(&serial, &state.ui_captures, &trace_id)?;
 
-    let adb_program = get_adb_program(&trace_id)?;
-    let mut args = vec![
-        "-s".to_string(),
-        serial.clone(),
-        "shell".to_string(),
-        "pm".to_string(),
-        "list".to_string(),
-        "packages".to_string(),
-        "-f".to_string(),
-    ];
-    if let Some(only_third_party) = third_party_only {
-        if only_third_party {
-            args.push("-3".to_string());
-        } else {
-            args.push("-s".to_string());
-        }
-    }
+    Ok(CommandResponse {
+        trace_id,
+        data: captures,
+    })
+}
 
-    let output = run_command_with_timeout(&adb_program, &args, Duration::from_secs(30), &trace_id)?;
-    if output.exit_code.unwrap_or_default() != 0 {
-        return Err(AppError::dependency(
-            format!("List apps failed: {}", output.stderr),
-            &trace_id,
+/// `accessibility` reads the window list of `dumpsys accessibility`. The service does not dump
+/// view nodes, so on screens uiautomator cannot read it only yields window titles, types and
+/// bounds; per-view text and content-desc need the `view_hierarchy` ViewServer path.
+const UI_DUMP_BACKENDS: [&str; 5] = [
+    "auto",
+    "uiautomator",
+    "compressed",
+    "view_hierarchy",
+    "accessibility",
+];
+
+fn dump_ui_xml(
+    adb_program: &str,
+    serial: &str,
+    backend: Option<&str>,
+    trace_id: &str,
+) -> Result<(String, String), AppError> {
+    let backend = backend
+        .map(|value| value.trim().to_lowercase())
+        .filter(|value| !value.is_empty())
+        .unwrap_or_else(|| "uiautomator".to_string());
+    if !UI_DUMP_BACKENDS.contains(&backend.as_str()) {
+        return Err(AppError::validation(
+            Message::keyed("unsupported_value")
+                .with("subject", "UI dump backend")
+                .with("value", &backend),
+            trace_id,
         ));
     }
 
-    let include_versions = include_versions.unwrap_or(false);
-    let mut apps = Vec::new();
-    for entry in parse_pm_list_packages_output(&output.stdout) {
-        let (version_name, version_code) = if include_versions {
-            let dump_args = vec![
-                "-s".to_string(),
-                serial.clone(),
-                "shell".to_string(),
-                "dumpsys".to_string(),
-                "package".to_string(),
-                entry.package_name.clone(),
-            ];
-            match run_command_with_timeout(
-                &adb_program,
-                &dump_args,
-                Duration::from_secs(10),
-                &trace_id,
-            ) {
-                Ok(out) => (
-                    parse_dumpsys_version_name(&out.stdout),
-                    parse_dumpsys_version_code(&out.stdout),
-                ),
-                Err(err) => {
-                    warn!(
-                        trace_id = %trace_id,
-                        package_name = %entry.package_name,
-                        error = %err,
-                        "dumpsys package failed while listing apps"
-                    );
-                    (None, None)
-                }
+    if backend != "auto" {
+        let xml = dump_ui_xml_with_backend(adb_program, serial, &backend, trace_id)?;
+        return Ok((xml, backend));
+    }
+
+    let mut last_error = None;
+    for candidate in &UI_DUMP_BACKENDS[1..] {
+        match dump_ui_xml_with_backend(adb_program, serial, candidate, trace_id) {
+            Ok(xml) => return Ok((xml, candidate.to_string())),
+            Err(err) => {
+                warn!(
+                    trace_id = %trace_id,
+                    serial = %serial,
+                    backend = %candidate,
+                    error = %err,
+                    "ui dump backend failed, trying next"
+                );
+                last_error = Some(err);
             }
-        } else {
-            (None, None)
-        };
-        apps.push(package_entry_to_app_info(entry, version_name, version_code));
+        }
     }
+    Err(last_error.unwrap_or_else(|| {
+        AppError::dependency(
+            Message::keyed("action_failed_plain").with("action", "UI dump"),
+            trace_id,
+        )
+    }))
+}
 
-    apps.sort_by(|a, b| a.package_name.cmp(&b.package_name));
+/// Port `IWindowManager.startViewServer` listens on.
+const VIEW_SERVER_PORT: u16 = 4939;
+
+/// `DUMP -1` from the on-device ViewServer, which exports each view's text and content
+/// description. It only starts on builds with `ro.secure=0` (eng, userdebug and most
+/// emulators), so production devices fall back to the dumpsys hierarchy.
+fn dump_view_server(adb_program: &str, serial: &str, trace_id: &str) -> Result<String, String> {
+    let client = socket_client_for_serial(adb_program, serial)
+        .ok_or_else(|| "adb socket client is off for this device".to_string())?;
+    // `startViewServer(port)` (transaction 1); it returns false when already running.
+    run_device_shell(
+        adb_program,
+        serial,
+        &[
+            "service",
+            "call",
+            "window",
+            "1",
+            "i32",
+            &VIEW_SERVER_PORT.to_string(),
+        ],
+        command_timeout(TimeoutKind::Probe, trace_id),
+        trace_id,
+    )
+    .map_err(|err| err.error)?;
+    let reply = client
+        .device_tcp_exchange(
+            serial,
+            VIEW_SERVER_PORT,
+            b"DUMP -1\n",
+            command_timeout(TimeoutKind::DeviceCommand, trace_id),
+        )
+        .map_err(|err| err.to_string())?;
+    Ok(String::from_utf8_lossy(&reply).to_string())
+}
 
-    Ok(CommandResponse {
+fn dump_ui_xml_with_backend(
+    adb_program: &str,
+    serial: &str,
+    backend: &str,
+    trace_id: &str,
+) -> Result<String, AppError> {
+    if backend == "view_hierarchy" {
+        match dump_view_server(adb_program, serial, trace_id)
+            .and_then(|dump| parse_view_server_dump_to_xml(&dump))
+        {
+            Ok(xml) => return Ok(xml),
+            Err(err) => {
+                warn!(trace_id = %trace_id, serial = %serial, error = %err, "ViewServer dump unavailable, using the dumpsys view hierarchy without text");
+            }
+        }
+    }
+    let mut args = vec!["-s".to_string(), serial.to_string()];
+    match backend {
+        "view_hierarchy" => args.extend(
+            ["shell", "dumpsys", "activity", "top"]
+                .iter()
+                .map(|item| item.to_string()),
+        ),
+        "accessibility" => args.extend(
+            ["shell", "dumpsys", "accessibility"]
+                .iter()
+                .map(|item| item.to_string()),
+        ),
+        "compressed" => args.extend(
+            [
+                "exec-out",
+                "uiautomator",
+                "dump",
+                "--compressed",
+                "/dev/tty",
+            ]
+            .iter()
+            .map(|item| item.to_string()),
+        ),
+        _ => args.extend(
+            ["exec-out", "uiautomator", "dump", "/dev/tty"]
+                .iter()
+                .map(|item| item.to_string()),
+        ),
+    }
+    let output = run_command_with_timeout(
+        adb_program,
+        &args,
+        command_timeout(TimeoutKind::DeviceCommand, trace_id),
         trace_id,
-        data: apps,
-    })
+    )?;
+    if output.exit_code.unwrap_or_default() != 0 {
+        return Err(AppError::dependency(
+            Message::keyed("action_failed")
+                .with("action", "UI dump")
+                .with("detail", &output.stderr),
+            trace_id,
+        ));
+    }
+
+    let parsed = match backend {
+        "view_hierarchy" => Some(parse_view_hierarchy_to_xml(&output.stdout)),
+        "accessibility" => Some(parse_accessibility_windows_to_xml(&output.stdout)),
+        _ => None,
+    };
+    if let Some(parsed) = parsed {
+        return parsed.map_err(|err| {
+            AppError::dependency(
+                Message::keyed("action_failed")
+                    .with("action", "UI dump")
+                    .with("detail", &err),
+                trace_id,
+            )
+        });
+    }
+    if !output.stdout.contains("<hierarchy") {
+        let message = output
+            .stdout
+            .lines()
+            .map(|line| line.trim())
+            .find(|line| !line.is_empty())
+            .unwrap_or("empty output");
+        return Err(AppError::dependency(
+            Message::keyed("action_failed")
+                .with("action", "UI dump")
+                .with("detail", message),
+            trace_id,
+        ));
+    }
+    Ok(output.stdout)
 }
 
 #[tauri::command(async)]
-pub fn get_app_icon(
+pub fn export_ui_hierarchy(
     serial: String,
-    package_name: String,
-    apk_path: Option<String>,
+    output_dir: Option<String>,
+    backend: Option<String>,
     trace_id: Option<String>,
-) -> Result<CommandResponse<AppIcon>, AppError> {
-    use base64::Engine as _;
-
+) -> Result<CommandResponse<UiHierarchyExportResult>, AppError> {
     let trace_id = resolve_trace_id(trace_id);
     ensure_non_empty(&serial, "serial", &trace_id)?;
-    ensure_non_empty(&package_name, "package_name", &trace_id)?;
-
-    let safe_serial = sanitize_filename_component(&serial);
-    let safe_pkg = sanitize_filename_component(&package_name);
 
-    let cache_dir = cache_dir_for_app_icons().join(safe_serial);
-    let cache_png = cache_dir.join(format!("{safe_pkg}.png"));
-    let cache_webp = cache_dir.join(format!("{safe_pkg}.webp"));
-    let existing_cache = if cache_png.exists() {
-        Some(cache_png.clone())
-    } else if cache_webp.exists() {
-        Some(cache_webp.clone())
-    } else {
-        None
-    };
-
-    if let Some(cache_path) = existing_cache {
-        let bytes = fs::read(&cache_path).map_err(|err| {
-            AppError::system(format!("Failed to read cached icon: {err}"), &trace_id)
-        })?;
-        let mime_type = MimeGuess::from_path(&cache_path)
-            .first_or_octet_stream()
-            .essence_str()
-            .to_string();
-        let encoded = base64::engine::general_purpose::STANDARD.encode(&bytes);
-        return Ok(CommandResponse {
-            trace_id,
-            data: AppIcon {
-                package_name,
-                mime_type: mime_type.clone(),
-                data_url: format!("data:{mime_type};base64,{encoded}"),
-                from_cache: true,
-            },
-        });
-    }
+    let adb_program = get_adb_program(&trace_id)?;
+    let config = load_config(&trace_id)?;
+    let resolved_dir = prepare_artifact_dir(
+        &config,
+        ArtifactKind::UiDump,
+        output_dir.as_deref(),
+        &trace_id,
+    )?;
 
-    fs::create_dir_all(&cache_dir)
-        .map_err(|err| AppError::system(format!("Failed to create cache dir: {err}"), &trace_id))?;
+    let timestamp = Utc::now().format("%Y%m%d_%H%M%S");
+    let xml_path = resolved_dir.join(format!("ui_hierarchy_{}_{}.xml", serial, timestamp));
+    let html_path = resolved_dir.join(format!("ui_hierarchy_{}_{}.html", serial, timestamp));
+    let screenshot_path = resolved_dir.join(format!("ui_hierarchy_{}_{}.png", serial, timestamp));
 
-    let adb_program = get_adb_program(&trace_id)?;
-    let resolved_apk_path = if let Some(path) = apk_path {
-        path
-    } else {
-        let pm_path_args = vec![
-            "-s".to_string(),
-            serial.clone(),
-            "shell".to_string(),
-            "pm".to_string(),
-            "path".to_string(),
-            package_name.clone(),
-        ];
-        let output = run_command_with_timeout(
-            &adb_program,
-            &pm_path_args,
-            Duration::from_secs(10),
+    let (xml, _) = dump_ui_xml(&adb_program, &serial, backend.as_deref(), &trace_id)?;
+    let html = render_device_ui_html(&xml).map_err(|err| {
+        AppError::system(
+            Message::keyed("operation_failed")
+                .with("action", "render HTML")
+                .with("detail", &err),
             &trace_id,
-        )?;
-        if output.exit_code.unwrap_or_default() != 0 {
-            return Err(AppError::dependency(
-                format!("pm path failed: {}", output.stderr.trim()),
-                &trace_id,
-            ));
-        }
-        parse_pm_path_output(&output.stdout)
-            .into_iter()
-            .find(|item| item.ends_with("base.apk"))
-            .or_else(|| parse_pm_path_output(&output.stdout).into_iter().next())
-            .ok_or_else(|| AppError::dependency("pm path returned no APK path", &trace_id))?
-    };
-
-    if !resolved_apk_path.starts_with('/') {
-        return Err(AppError::validation("Invalid APK path", &trace_id));
-    }
+        )
+    })?;
 
-    let temp_dir = tempfile::tempdir()
-        .map_err(|err| AppError::system(format!("Failed to create temp dir: {err}"), &trace_id))?;
-    let local_apk_path = temp_dir.path().join("base.apk");
-    let local_apk_string = local_apk_path.to_string_lossy().to_string();
+    fs::write(&xml_path, xml).map_err(|err| {
+        AppError::system(
+            Message::keyed("operation_failed")
+                .with("action", "write XML")
+                .with("detail", &err),
+            &trace_id,
+        )
+    })?;
+    fs::write(&html_path, html).map_err(|err| {
+        AppError::system(
+            Message::keyed("operation_failed")
+                .with("action", "write HTML")
+                .with("detail", &err),
+            &trace_id,
+        )
+    })?;
 
-    let pull_args = vec![
+    let mut screenshot_args = vec![
         "-s".to_string(),
         serial.clone(),
-        "pull".to_string(),
-        resolved_apk_path.clone(),
-        local_apk_string.clone(),
+        "exec-out".to_string(),
+        "screencap".to_string(),
+        "-p".to_string(),
     ];
-    let pull_output =
-        run_command_with_timeout(&adb_program, &pull_args, Duration::from_secs(60), &trace_id)?;
-    if pull_output.exit_code.unwrap_or_default() != 0 {
+    if config.screenshot.display_id >= 0 {
+        screenshot_args.push("-d".to_string());
+        screenshot_args.push(config.screenshot.display_id.to_string());
+    }
+    if !config.screenshot.extra_args.trim().is_empty() {
+        screenshot_args.extend(
+            config
+                .screenshot
+                .extra_args
+                .split_whitespace()
+                .map(|item| item.to_string()),
+        );
+    }
+
+    let screenshot_output = adb_command(&route_adb_program(&adb_program, &screenshot_args))
+        .args(&screenshot_args)
+        .output()
+        .map_err(|err| {
+            AppError::dependency(
+                Message::keyed("operation_failed")
+                    .with("action", "capture screenshot")
+                    .with("detail", &err),
+                &trace_id,
+            )
+        })?;
+    if !screenshot_output.status.success() {
         return Err(AppError::dependency(
-            format!("Pull APK failed: {}", pull_output.stderr.trim()),
+            Message::keyed("operation_failed")
+                .with("action", "capture screenshot")
+                .with("detail", String::from_utf8_lossy(&screenshot_output.stderr)),
             &trace_id,
         ));
     }
+    let mut screenshot_file = fs::File::create(&screenshot_path).map_err(|err| {
+        AppError::system(
+            Message::keyed("operation_failed")
+                .with("action", "create screenshot")
+                .with("detail", &err),
+            &trace_id,
+        )
+    })?;
+    screenshot_file
+        .write_all(&screenshot_output.stdout)
+        .map_err(|err| {
+            AppError::system(
+                Message::keyed("operation_failed")
+                    .with("action", "write screenshot")
+                    .with("detail", &err),
+                &trace_id,
+            )
+        })?;
 
-    let Some((entry_name, icon_bytes)) = extract_best_icon_from_apk(&local_apk_path, &trace_id)?
-    else {
+    Ok(CommandResponse {
+        trace_id,
+        data: UiHierarchyExportResult {
+            serial,
+            xml_path: xml_path.to_string_lossy().to_string(),
+            html_path: html_path.to_string_lossy().to_string(),
+            screenshot_path: screenshot_path.to_string_lossy().to_string(),
+        },
+    })
+}
+
+fn read_device_screen_size(
+    adb_program: &str,
+    serial: &str,
+    trace_id: &str,
+) -> Result<(u32, u32), AppError> {
+    let wm_args = vec![
+        "-s".to_string(),
+        serial.to_string(),
+        "shell".to_string(),
+        "wm".to_string(),
+        "size".to_string(),
+    ];
+    let wm_output = run_command_with_timeout(
+        adb_program,
+        &wm_args,
+        command_timeout(TimeoutKind::Probe, trace_id),
+        trace_id,
+    )?;
+    if wm_output.exit_code.unwrap_or_default() != 0 {
         return Err(AppError::dependency(
-            "Failed to locate icon in APK",
-            &trace_id,
+            Message::keyed("action_failed")
+                .with("action", "wm size")
+                .with("detail", &wm_output.stderr),
+            trace_id,
         ));
-    };
+    }
+    parse_wm_size(&wm_output.stdout)
+        .and_then(|value| parse_size_dimensions(&value))
+        .ok_or_else(|| AppError::dependency(Message::keyed("screen_size_unreadable"), trace_id))
+}
 
-    const MAX_ICON_BYTES: usize = 1_000_000;
-    if icon_bytes.len() > MAX_ICON_BYTES {
+fn play_scaled_gesture(
+    adb_program: &str,
+    serial: &str,
+    scaled: &ScaledGesture,
+    trace_id: &str,
+) -> Result<(), AppError> {
+    let mut args = vec!["-s".to_string(), serial.to_string(), "shell".to_string()];
+    args.extend(build_gesture_input_args(scaled));
+    let timeout_ms = u64::from(scaled.duration_ms.unwrap_or(0)) + 10_000;
+    let output = run_command_with_timeout(
+        adb_program,
+        &args,
+        Duration::from_millis(timeout_ms),
+        trace_id,
+    )?;
+    if output.exit_code.unwrap_or_default() != 0 {
         return Err(AppError::dependency(
-            "Icon file too large to preview",
-            &trace_id,
+            Message::keyed("action_failed")
+                .with("action", "Input gesture")
+                .with("detail", &output.stderr),
+            trace_id,
         ));
     }
-
-    let mime_type = MimeGuess::from_path(&entry_name)
-        .first_or_octet_stream()
-        .essence_str()
-        .to_string();
-    let ext = if mime_type == "image/webp" {
-        "webp"
-    } else {
-        "png"
-    };
-    let cache_path = cache_dir.join(format!("{safe_pkg}.{ext}"));
-    fs::write(&cache_path, &icon_bytes).map_err(|err| {
-        AppError::system(format!("Failed to write cached icon: {err}"), &trace_id)
-    })?;
-
-    let encoded = base64::engine::general_purpose::STANDARD.encode(&icon_bytes);
-    Ok(CommandResponse {
-        trace_id,
-        data: AppIcon {
-            package_name,
-            mime_type: mime_type.clone(),
-            data_url: format!("data:{mime_type};base64,{encoded}"),
-            from_cache: false,
-        },
-    })
+    Ok(())
 }
 
+/// Reset `gfxinfo`, play `gestures` (`repeat` times, `pause_ms` apart) against the app in the
+/// foreground, then report jank and frame-time percentiles for just that scenario.
+#[allow(clippy::too_many_arguments)]
 #[tauri::command(async)]
-pub fn get_app_basic_info(
+pub fn run_jank_scenario(
     serial: String,
     package_name: String,
+    gestures: Vec<PointerGesture>,
+    repeat: Option<u32>,
+    pause_ms: Option<u64>,
+    state: State<'_, AppState>,
     trace_id: Option<String>,
-) -> Result<CommandResponse<AppBasicInfo>, AppError> {
+) -> Result<CommandResponse<JankScenarioResult>, AppError> {
+    const MAX_GESTURES: usize = 50;
     let trace_id = resolve_trace_id(trace_id);
     ensure_non_empty(&serial, "serial", &trace_id)?;
     ensure_non_empty(&package_name, "package_name", &trace_id)?;
-
-    let adb_program = get_adb_program(&trace_id)?;
-
-    let dump_args = vec![
-        "-s".to_string(),
-        serial.clone(),
-        "shell".to_string(),
-        "dumpsys".to_string(),
-        "package".to_string(),
-        package_name.clone(),
-    ];
-    let output =
-        run_command_with_timeout(&adb_program, &dump_args, Duration::from_secs(10), &trace_id)?;
-    if output.exit_code.unwrap_or_default() != 0 {
-        return Err(AppError::dependency(
-            format!("Get app info failed: {}", output.stderr),
+    if !is_valid_package_name(&package_name) {
+        return Err(AppError::validation(
+            Message::keyed("invalid").with("subject", "package name"),
+            &trace_id,
+        ));
+    }
+    if gestures.is_empty() || gestures.len() > MAX_GESTURES {
+        return Err(AppError::validation(
+            Message::keyed("gesture_count").with("max", MAX_GESTURES),
             &trace_id,
         ));
     }
+    let repeat = repeat.unwrap_or(1).clamp(1, 20);
+    let pause = Duration::from_millis(pause_ms.unwrap_or(300).min(5_000));
 
-    let version_name = parse_dumpsys_version_name(&output.stdout);
-    let version_code = parse_dumpsys_version_code(&output.stdout);
-    let first_install_time = parse_dumpsys_first_install_time(&output.stdout);
-    let last_update_time = parse_dumpsys_last_update_time(&output.stdout);
-    let installer_package_name = parse_dumpsys_installer_package_name(&output.stdout);
-    let installing_package_name = parse_dumpsys_installing_package_name(&output.stdout);
-    let originating_package_name = parse_dumpsys_originating_package_name(&output.stdout);
-    let initiating_package_name = parse_dumpsys_initiating_package_name(&output.stdout);
-    let uid = parse_dumpsys_user_id(&output.stdout);
-    let data_dir = parse_dumpsys_data_dir(&output.stdout);
-    let target_sdk = parse_dumpsys_target_sdk(&output.stdout);
-    let requested_permissions = parse_dumpsys_requested_permissions(&output.stdout);
-    let granted_permissions = parse_dumpsys_granted_permissions(&output.stdout);
-    let (activities, services, receivers, providers) =
-        parse_dumpsys_components_summary(&output.stdout);
-    let components_summary = if activities + services + receivers + providers > 0 {
-        Some(AppComponentsSummary {
-            activities,
-            services,
-            receivers,
-            providers,
-        })
-    } else {
-        None
-    };
+    let adb_program = get_adb_program(&trace_id)?;
+    let (device_width, device_height) = read_device_screen_size(&adb_program, &serial, &trace_id)?;
+    let scaled = gestures
+        .iter()
+        .map(|gesture| scale_pointer_gesture(gesture, device_width, device_height))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|message| AppError::validation(message, &trace_id))?;
+
+    let device_lock = state.scheduler.device_lock(&serial);
+    let _device_guard = device_lock.lock().map_err(|_| {
+        warn!(trace_id = %trace_id, serial = %serial, "device lock poisoned");
+        AppError::system("Failed to access the device. Please try again.", &trace_id)
+    })?;
 
-    let pm_path_args = vec![
-        "-s".to_string(),
-        serial.clone(),
-        "shell".to_string(),
-        "pm".to_string(),
-        "path".to_string(),
-        package_name.clone(),
-    ];
-    let mut apk_paths = Vec::new();
-    match run_command_with_timeout(
+    run_device_shell(
         &adb_program,
-        &pm_path_args,
-        Duration::from_secs(10),
+        &serial,
+        &["dumpsys", "gfxinfo", &package_name, "reset"],
+        command_timeout(TimeoutKind::DeviceCommand, &trace_id),
         &trace_id,
-    ) {
-        Ok(out) => {
-            if out.exit_code.unwrap_or_default() != 0 {
-                warn!(
-                    trace_id = %trace_id,
-                    package_name = %package_name,
-                    stderr = %out.stderr.trim(),
-                    "pm path returned non-zero exit code"
-                );
-            } else {
-                apk_paths = parse_pm_path_output(&out.stdout);
-            }
-        }
-        Err(err) => {
-            warn!(
-                trace_id = %trace_id,
-                package_name = %package_name,
-                error = %err,
-                "pm path failed"
-            );
+    )?;
+    let mut gestures_played = 0usize;
+    for _ in 0..repeat {
+        for gesture in &scaled {
+            play_scaled_gesture(&adb_program, &serial, gesture, &trace_id)?;
+            gestures_played += 1;
+            std::thread::sleep(pause);
         }
     }
-
-    apk_paths.sort();
-    apk_paths.dedup();
-
-    const MAX_APK_PATHS: usize = 20;
-    let mut apk_size_bytes_total = None;
-    if !apk_paths.is_empty() {
-        if apk_paths.len() > MAX_APK_PATHS {
-            warn!(
-                trace_id = %trace_id,
-                package_name = %package_name,
-                apk_paths_len = apk_paths.len(),
-                max_apk_paths = MAX_APK_PATHS,
-                "Too many APK paths; skipping size calculation"
-            );
-        } else {
-            let mut total = 0u64;
-            let mut has_error = false;
-            for apk_path in &apk_paths {
-                match try_get_device_file_size_bytes(&adb_program, &serial, apk_path, &trace_id) {
-                    Some(size) => total = total.saturating_add(size),
-                    None => {
-                        has_error = true;
-                        warn!(
-                            trace_id = %trace_id,
-                            package_name = %package_name,
-                            apk_path = %apk_path,
-                            "Failed to resolve APK size"
-                        );
-                    }
-                }
-            }
-            if !has_error {
-                apk_size_bytes_total = Some(total);
-            }
-        }
+    let output = run_device_shell(
+        &adb_program,
+        &serial,
+        &["dumpsys", "gfxinfo", &package_name],
+        command_timeout(TimeoutKind::DeviceCommand, &trace_id),
+        &trace_id,
+    )?;
+    let summary = parse_gfxinfo_summary(&output.stdout);
+    if summary.total_frames.is_none() {
+        return Err(AppError::dependency(
+            Message::keyed("no_gfxinfo_frames"),
+            &trace_id,
+        ));
     }
+    let jank_percent_x100 = compute_jank_percent_x100(&summary);
+    info!(
+        trace_id = %trace_id,
+        serial = %serial,
+        package_name = %package_name,
+        gestures_played,
+        jank_percent_x100 = ?jank_percent_x100,
+        "jank scenario finished"
+    );
 
     Ok(CommandResponse {
         trace_id,
-        data: AppBasicInfo {
+        data: JankScenarioResult {
+            serial,
             package_name,
-            version_name,
-            version_code,
-            first_install_time,
-            last_update_time,
-            installer_package_name,
-            installing_package_name,
-            originating_package_name,
-            initiating_package_name,
-            uid,
-            data_dir,
-            target_sdk,
-            requested_permissions,
-            granted_permissions,
-            components_summary,
-            apk_paths,
-            apk_size_bytes_total,
+            gestures_played,
+            total_frames: summary.total_frames,
+            janky_frames: summary.janky_frames,
+            jank_percent_x100,
+            p50_ms: summary.p50_ms,
+            p90_ms: summary.p90_ms,
+            p95_ms: summary.p95_ms,
+            p99_ms: summary.p99_ms,
+            missed_vsync: summary.missed_vsync,
+            slow_ui_thread: summary.slow_ui_thread,
+            frame_deadline_missed: summary.frame_deadline_missed,
         },
     })
 }
 
-#[tauri::command(async)]
-pub fn uninstall_app(
-    serial: String,
-    package_name: String,
-    keep_data: bool,
-    trace_id: Option<String>,
-) -> Result<CommandResponse<bool>, AppError> {
-    let trace_id = resolve_trace_id(trace_id);
-    ensure_non_empty(&serial, "serial", &trace_id)?;
-    ensure_non_empty(&package_name, "package_name", &trace_id)?;
-
-    let adb_program = get_adb_program(&trace_id)?;
-    let mut args = vec!["-s".to_string(), serial.clone(), "uninstall".to_string()];
-    if keep_data {
-        args.push("-k".to_string());
-    }
-    args.push(package_name);
-    let output = run_command_with_timeout(&adb_program, &args, Duration::from_secs(30), &trace_id)?;
-    let success = output.stdout.contains("Success") || output.exit_code.unwrap_or_default() == 0;
-
-    Ok(CommandResponse {
-        trace_id,
-        data: success,
-    })
+#[derive(Clone, serde::Serialize)]
+pub struct TriageBundleReadyEvent {
+    pub serial: String,
+    pub reason: String,
+    pub path: String,
+    pub trace_id: String,
 }
 
-fn try_get_device_file_size_bytes(
+/// Capture crash context into a fresh triage folder: screenshot first (the screen is the most
+/// transient), then `logcat -d`, a device state snapshot and the tool output excerpt. Pieces
+/// that fail are listed in `errors` instead of failing the bundle.
+fn collect_triage_bundle(
     adb_program: &str,
     serial: &str,
-    path: &str,
+    reason: &str,
+    detail: &str,
+    output_excerpt: &[String],
     trace_id: &str,
-) -> Option<u64> {
-    let stat_args = vec![
+) -> Result<TriageBundle, AppError> {
+    let config = load_config(trace_id)?;
+    let output_dir = prepare_artifact_dir(&config, ArtifactKind::Log, None, trace_id)?;
+    let dir = output_dir.join(triage_dir_name(serial, Local::now()));
+    fs::create_dir_all(&dir).map_err(|err| {
+        AppError::system(
+            Message::keyed("operation_failed")
+                .with("action", "create triage folder")
+                .with("detail", &err),
+            trace_id,
+        )
+    })?;
+    let mut files = Vec::new();
+    let mut errors = Vec::new();
+    let mut record = |file: &str, result: Result<(), String>| match result {
+        Ok(()) => files.push(file.to_string()),
+        Err(err) => {
+            warn!(trace_id = %trace_id, serial = %serial, file = %file, error = %err, "triage capture failed");
+            errors.push(format!("{file}: {err}"));
+        }
+    };
+
+    record(
+        TRIAGE_SCREENSHOT_FILE,
+        capture_screenshot_to(
+            adb_program,
+            serial,
+            &config,
+            &dir.join(TRIAGE_SCREENSHOT_FILE),
+            trace_id,
+        )
+        .map_err(|err| err.error),
+    );
+
+    let logcat_args = vec![
         "-s".to_string(),
         serial.to_string(),
-        "shell".to_string(),
-        "stat".to_string(),
-        "-c".to_string(),
-        "%s".to_string(),
-        path.to_string(),
+        "logcat".to_string(),
+        "-d".to_string(),
+        "-v".to_string(),
+        "threadtime".to_string(),
     ];
-    if let Ok(out) =
-        run_command_with_timeout(adb_program, &stat_args, Duration::from_secs(5), trace_id)
-    {
-        if out.exit_code.unwrap_or_default() == 0 {
-            if let Some(size) = parse_stat_size_output(&out.stdout) {
-                return Some(size);
-            }
+    let logcat = run_command_with_timeout(
+        adb_program,
+        &logcat_args,
+        command_timeout(TimeoutKind::DeviceCommand, trace_id),
+        trace_id,
+    )
+    .map_err(|err| err.error)
+    .and_then(|output| {
+        fs::write(dir.join(TRIAGE_LOGCAT_FILE), output.stdout).map_err(|err| err.to_string())
+    });
+    record(TRIAGE_LOGCAT_FILE, logcat);
+
+    let sections: Vec<(String, Result<String, String>)> = TRIAGE_STATE_COMMANDS
+        .iter()
+        .map(|(label, command)| {
+            let output = run_device_shell(
+                adb_program,
+                serial,
+                &[command],
+                command_timeout(TimeoutKind::DeviceCommand, trace_id),
+                trace_id,
+            )
+            .map(|output| output.stdout)
+            .map_err(|err| err.error);
+            (label.to_string(), output)
+        })
+        .collect();
+    record(
+        TRIAGE_DEVICE_STATE_FILE,
+        fs::write(
+            dir.join(TRIAGE_DEVICE_STATE_FILE),
+            render_device_state(&sections),
+        )
+        .map_err(|err| err.to_string()),
+    );
+    record(
+        TRIAGE_OUTPUT_FILE,
+        fs::write(dir.join(TRIAGE_OUTPUT_FILE), output_excerpt.join("\n"))
+            .map_err(|err| err.to_string()),
+    );
+
+    let bundle = TriageBundle {
+        serial: serial.to_string(),
+        reason: reason.to_string(),
+        detail: detail.to_string(),
+        path: dir.to_string_lossy().to_string(),
+        files,
+        errors,
+        created_at: Utc::now().to_rfc3339(),
+    };
+    let meta = serde_json::to_string_pretty(&bundle).map_err(|err| {
+        AppError::system(
+            Message::keyed("operation_failed")
+                .with("action", "serialize triage metadata")
+                .with("detail", &err),
+            trace_id,
+        )
+    })?;
+    fs::write(dir.join(TRIAGE_META_FILE), meta).map_err(|err| {
+        AppError::system(
+            Message::keyed("operation_failed")
+                .with("action", "write triage metadata")
+                .with("detail", &err),
+            trace_id,
+        )
+    })?;
+    Ok(bundle)
+}
+
+struct MonkeyRun {
+    exit_code: Option<i32>,
+    events_injected: Option<u64>,
+    crash: Option<(&'static str, String)>,
+    excerpt: Vec<String>,
+}
+
+fn kill_monkey_process(
+    adb_program: &str,
+    serial: &str,
+    child_holder: &Arc<std::sync::Mutex<Option<std::process::Child>>>,
+    trace_id: &str,
+) {
+    if let Ok(mut guard) = child_holder.lock() {
+        if let Some(child) = guard.as_mut() {
+            let _ = child.kill();
+            let _ = child.wait();
         }
     }
-
-    let ls_args = vec![
+    // Killing the local adb client does not always stop Monkey on the device.
+    let args = vec![
         "-s".to_string(),
         serial.to_string(),
         "shell".to_string(),
-        "ls".to_string(),
-        "-la".to_string(),
-        path.to_string(),
+        "pkill".to_string(),
+        "-f".to_string(),
+        "com.android.commands.monkey".to_string(),
     ];
-    let out =
-        run_command_with_timeout(adb_program, &ls_args, Duration::from_secs(5), trace_id).ok()?;
-    if out.exit_code.unwrap_or_default() != 0 {
-        return None;
-    }
-
-    let (dir, base) = split_device_path(path);
-    let parsed = parse_ls_la(&dir, &out.stdout);
-    if parsed.len() == 1 {
-        return parsed[0].size_bytes;
+    if let Err(err) = run_command_with_timeout(
+        adb_program,
+        &args,
+        command_timeout(TimeoutKind::DeviceCommand, trace_id),
+        trace_id,
+    ) {
+        warn!(trace_id = %trace_id, serial = %serial, error = %err.error, "failed to stop monkey on device");
     }
-    parsed
-        .into_iter()
-        .find(|entry| entry.name == base || entry.path.ends_with(&format!("/{}", base)))
-        .and_then(|entry| entry.size_bytes)
 }
 
-fn parse_stat_size_output(output: &str) -> Option<u64> {
-    output
-        .lines()
-        .map(|line| line.trim())
-        .find(|line| !line.is_empty())
-        .and_then(|line| line.parse::<u64>().ok())
-}
+/// Stream Monkey's stdout and stderr, noting the first crash marker and keeping the lines
+/// around it for triage.
+fn run_monkey_streaming(
+    adb_program: &str,
+    serial: &str,
+    options: &MonkeyOptions,
+    trace_id: &str,
+    cancel_flag: &Arc<AtomicBool>,
+    child_holder: &Arc<std::sync::Mutex<Option<std::process::Child>>>,
+) -> Result<MonkeyRun, String> {
+    let args = build_monkey_args(serial, options);
+    let mut child = spawn_logged(
+        adb_command_for_serial(adb_program, serial)
+            .args(&args)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped()),
+        trace_id,
+    )
+    .map_err(|err| format!("Failed to start monkey: {err}"))?;
+    let (sender, receiver) = std::sync::mpsc::channel::<String>();
+    if let Some(stdout) = child.stdout.take() {
+        forward_lines(stdout, sender.clone());
+    }
+    if let Some(stderr) = child.stderr.take() {
+        forward_lines(stderr, sender);
+    }
+    {
+        let mut guard = child_holder
+            .lock()
+            .map_err(|_| "Monkey registry locked".to_string())?;
+        *guard = Some(child);
+    }
 
-fn split_device_path(path: &str) -> (String, String) {
-    let trimmed = path.trim_end_matches('/');
-    if let Some((dir, base)) = trimmed.rsplit_once('/') {
-        let dir = if dir.is_empty() {
-            "/".to_string()
-        } else {
-            dir.to_string()
+    let deadline = Instant::now() + monkey_timeout(options);
+    let mut run = MonkeyRun {
+        exit_code: None,
+        events_injected: None,
+        crash: None,
+        excerpt: Vec::new(),
+    };
+    let mut context: VecDeque<String> = VecDeque::new();
+    loop {
+        if cancel_flag.load(Ordering::Relaxed) {
+            return Err("Cancelled by user".to_string());
+        }
+        if Instant::now() > deadline {
+            kill_monkey_process(adb_program, serial, child_holder, trace_id);
+            return Err("Monkey run timed out".to_string());
+        }
+        let line = match receiver.recv_timeout(Duration::from_millis(200)) {
+            Ok(line) => line,
+            Err(std::sync::mpsc::RecvTimeoutError::Timeout) => continue,
+            Err(std::sync::mpsc::RecvTimeoutError::Disconnected) => break,
         };
-        return (dir, base.to_string());
+        if let Some(events) = parse_events_injected(&line) {
+            run.events_injected = Some(events);
+        }
+        if run.crash.is_none() {
+            if let Some(kind) = detect_crash_keyword(&line) {
+                info!(trace_id = %trace_id, serial = %serial, kind = %kind, "monkey detected a crash");
+                run.crash = Some((kind, line.trim().to_string()));
+                run.excerpt.extend(context.drain(..));
+            } else {
+                if context.len() == CRASH_CONTEXT_LINES {
+                    context.pop_front();
+                }
+                context.push_back(line);
+                continue;
+            }
+        }
+        if run.excerpt.len() < CRASH_EXCERPT_LINES {
+            run.excerpt.push(line);
+        }
     }
-    (".".to_string(), trimmed.to_string())
+
+    let child = child_holder.lock().ok().and_then(|mut guard| guard.take());
+    if let Some(mut child) = child {
+        run.exit_code = child
+            .wait()
+            .map_err(|err| format!("Failed to wait for monkey: {err}"))?
+            .code();
+    }
+    if cancel_flag.load(Ordering::Relaxed) {
+        return Err("Cancelled by user".to_string());
+    }
+    Ok(run)
 }
 
+/// Run Monkey against `package_name` for `events` events. It stops at the first crash, ANR
+/// or native crash; a triage folder is then collected and announced with a single
+/// `triage-bundle-ready` event.
+#[allow(clippy::too_many_arguments)]
 #[tauri::command(async)]
-pub fn force_stop_app(
+pub fn run_monkey_stress(
     serial: String,
     package_name: String,
+    events: u32,
+    throttle_ms: Option<u32>,
+    seed: Option<u64>,
+    app: AppHandle,
+    state: State<'_, AppState>,
     trace_id: Option<String>,
-) -> Result<CommandResponse<bool>, AppError> {
+) -> Result<CommandResponse<MonkeyStressResult>, AppError> {
     let trace_id = resolve_trace_id(trace_id);
     ensure_non_empty(&serial, "serial", &trace_id)?;
-    ensure_non_empty(&package_name, "package_name", &trace_id)?;
-
+    let package_name = package_name.trim().to_string();
+    if !is_valid_package_name(&package_name) {
+        return Err(AppError::validation(
+            Message::keyed("invalid").with("subject", "package name"),
+            &trace_id,
+        ));
+    }
+    let (events, throttle_ms) = validate_monkey_options(events, throttle_ms)
+        .map_err(|err| AppError::validation(err, &trace_id))?;
+    let options = MonkeyOptions {
+        package_name: package_name.clone(),
+        events,
+        throttle_ms,
+        seed,
+    };
     let adb_program = get_adb_program(&trace_id)?;
-    let args = vec![
-        "-s".to_string(),
-        serial.clone(),
-        "shell".to_string(),
-        "am".to_string(),
-        "force-stop".to_string(),
+
+    let cancel_flag = Arc::new(AtomicBool::new(false));
+    let child_holder = Arc::new(std::sync::Mutex::new(None));
+    {
+        let mut guard = state
+            .monkey_runs
+            .lock()
+            .map_err(|_| AppError::system("Monkey registry locked", &trace_id))?;
+        if guard.contains_key(&serial) {
+            return Err(AppError::validation(
+                Message::keyed("monkey_running"),
+                &trace_id,
+            ));
+        }
+        guard.insert(
+            serial.clone(),
+            MonkeyRunHandle {
+                cancel_flag: Arc::clone(&cancel_flag),
+                child: Arc::clone(&child_holder),
+            },
+        );
+    }
+    info!(trace_id = %trace_id, serial = %serial, package = %package_name, events, "starting monkey stress run");
+
+    let started = Instant::now();
+    let outcome = run_monkey_streaming(
+        &adb_program,
+        &serial,
+        &options,
+        &trace_id,
+        &cancel_flag,
+        &child_holder,
+    );
+    match state.monkey_runs.lock() {
+        Ok(mut guard) => {
+            guard.remove(&serial);
+        }
+        Err(_) => {
+            warn!(trace_id = %trace_id, serial = %serial, "monkey registry locked, run handle not cleared")
+        }
+    }
+
+    let mut result = MonkeyStressResult {
+        serial: serial.clone(),
         package_name,
-    ];
-    let output = run_command_with_timeout(&adb_program, &args, Duration::from_secs(10), &trace_id)?;
+        events_requested: events,
+        events_injected: None,
+        seed,
+        crashed: false,
+        crash_kind: None,
+        triage: None,
+        exit_code: None,
+        cancelled: false,
+        duration_ms: 0,
+    };
+    match outcome {
+        Ok(run) => {
+            result.events_injected = run.events_injected;
+            result.exit_code = run.exit_code;
+            if let Some((kind, detail)) = run.crash {
+                result.crashed = true;
+                result.crash_kind = Some(kind.to_string());
+                match collect_triage_bundle(
+                    &adb_program,
+                    &serial,
+                    kind,
+                    &detail,
+                    &run.excerpt,
+                    &trace_id,
+                ) {
+                    Ok(bundle) => {
+                        let event = TriageBundleReadyEvent {
+                            serial: serial.clone(),
+                            reason: bundle.reason.clone(),
+                            path: bundle.path.clone(),
+                            trace_id: trace_id.clone(),
+                        };
+                        if let Err(err) = emit_tracked(&app, TRIAGE_BUNDLE_READY_EVENT, event) {
+                            warn!(trace_id = %trace_id, error = %err, "failed to emit triage bundle event");
+                        }
+                        record_timeline_event(
+                            &serial,
+                            TIMELINE_CRASH,
+                            &format!("Monkey {kind}: {detail}"),
+                            &trace_id,
+                        );
+                        result.triage = Some(bundle);
+                    }
+                    Err(err) => {
+                        warn!(trace_id = %trace_id, serial = %serial, error = %err.error, "failed to collect triage bundle");
+                    }
+                }
+            }
+        }
+        Err(err) if err.contains("Cancelled") => result.cancelled = true,
+        Err(err) => return Err(AppError::dependency(err, &trace_id)),
+    }
+    result.duration_ms = started.elapsed().as_millis() as u64;
 
     Ok(CommandResponse {
         trace_id,
-        data: output.exit_code.unwrap_or_default() == 0,
+        data: result,
     })
 }
 
 #[tauri::command(async)]
-pub fn clear_app_data(
+pub fn stop_monkey_stress(
     serial: String,
-    package_name: String,
+    state: State<'_, AppState>,
     trace_id: Option<String>,
 ) -> Result<CommandResponse<bool>, AppError> {
     let trace_id = resolve_trace_id(trace_id);
     ensure_non_empty(&serial, "serial", &trace_id)?;
-    ensure_non_empty(&package_name, "package_name", &trace_id)?;
-
+    let (cancel_flag, child) = {
+        let guard = state
+            .monkey_runs
+            .lock()
+            .map_err(|_| AppError::system("Monkey registry locked", &trace_id))?;
+        let Some(handle) = guard.get(&serial) else {
+            return Err(AppError::validation(
+                Message::keyed("not_active").with("subject", "Monkey run"),
+                &trace_id,
+            ));
+        };
+        (Arc::clone(&handle.cancel_flag), Arc::clone(&handle.child))
+    };
+    cancel_flag.store(true, Ordering::Relaxed);
     let adb_program = get_adb_program(&trace_id)?;
-    let args = vec![
-        "-s".to_string(),
-        serial.clone(),
-        "shell".to_string(),
-        "pm".to_string(),
-        "clear".to_string(),
-        package_name,
-    ];
-    let output = run_command_with_timeout(&adb_program, &args, Duration::from_secs(20), &trace_id)?;
-    let success = output.stdout.to_lowercase().contains("success")
-        || output.exit_code.unwrap_or_default() == 0;
-
+    kill_monkey_process(&adb_program, &serial, &child, &trace_id);
     Ok(CommandResponse {
         trace_id,
-        data: success,
+        data: true,
     })
 }
 
-#[tauri::command(async)]
-pub fn set_app_enabled(
-    serial: String,
-    package_name: String,
-    enable: bool,
-    user_id: Option<i32>,
-    trace_id: Option<String>,
-) -> Result<CommandResponse<bool>, AppError> {
-    let trace_id = resolve_trace_id(trace_id);
-    ensure_non_empty(&serial, "serial", &trace_id)?;
-    ensure_non_empty(&package_name, "package_name", &trace_id)?;
-
-    let adb_program = get_adb_program(&trace_id)?;
-    let mut args = vec![
-        "-s".to_string(),
-        serial.clone(),
-        "shell".to_string(),
-        "pm".to_string(),
-    ];
-    if enable {
-        args.push("enable".to_string());
-    } else {
-        args.push("disable-user".to_string());
-        if let Some(id) = user_id {
-            args.push("--user".to_string());
-            args.push(id.to_string());
+fn fill_text_field(
+    adb_program: &str,
+    serial: &str,
+    entry: &TextFieldEntry,
+    trace_id: &str,
+) -> Result<(), String> {
+    let text = escape_input_text(&entry.value)?;
+    let (x, y) = match (&entry.selector, entry.x, entry.y) {
+        (Some(selector), _, _) => {
+            let (xml, _) = dump_ui_xml(adb_program, serial, None, trace_id)
+                .map_err(|err| format!("UI dump failed: {}", err.error))?;
+            find_selector_center(&extract_ui_nodes(&xml), selector)
+                .ok_or_else(|| "No UI node matches the selector".to_string())?
         }
-    }
-    args.push(package_name);
-    let output = run_command_with_timeout(&adb_program, &args, Duration::from_secs(10), &trace_id)?;
-    let normalized = format!(
-        "{} {}",
-        output.stdout.to_lowercase(),
-        output.stderr.to_lowercase()
-    );
-    let success = if enable {
-        normalized.contains("enabled") || normalized.trim().is_empty()
-    } else {
-        normalized.contains("disabled") || normalized.trim().is_empty()
+        (None, Some(x), Some(y)) => (x, y),
+        _ => return Err("Entry needs x/y coordinates or a selector".to_string()),
     };
 
-    Ok(CommandResponse {
-        trace_id,
-        data: success,
-    })
+    let shell = |args: &[&str]| {
+        run_device_shell(
+            adb_program,
+            serial,
+            args,
+            command_timeout(TimeoutKind::DeviceCommand, trace_id),
+            trace_id,
+        )
+        .map(|_| ())
+        .map_err(|err| err.error)
+    };
+    shell(&["input", "tap", &x.to_string(), &y.to_string()])?;
+    // Give the IME a moment to attach to the focused field.
+    std::thread::sleep(Duration::from_millis(300));
+    if entry.clear {
+        shell(&["input", "keyevent", "KEYCODE_MOVE_END"])?;
+        let mut delete = vec!["input", "keyevent"];
+        delete.extend(std::iter::repeat_n("KEYCODE_DEL", 64));
+        shell(&delete)?;
+    }
+    // The command log already masks `input text` arguments.
+    shell(&["input", "text", &text])
 }
 
+/// Tap each field (by coordinates or UI selector) and type its value. Stops at the first
+/// failure. Values are often credentials, so only the field index is logged.
 #[tauri::command(async)]
-pub fn open_app_info(
+pub fn fill_text_fields(
     serial: String,
-    package_name: String,
+    entries: Vec<TextFieldEntry>,
     trace_id: Option<String>,
-) -> Result<CommandResponse<bool>, AppError> {
+) -> Result<CommandResponse<Vec<TextFieldFillResult>>, AppError> {
+    const MAX_ENTRIES: usize = 20;
     let trace_id = resolve_trace_id(trace_id);
-    ensure_non_empty(&serial, "serial", &trace_id)?;
-    ensure_non_empty(&package_name, "package_name", &trace_id)?;
-
-    let adb_program = get_adb_program(&trace_id)?;
-    let primary_args = vec![
-        "-s".to_string(),
-        serial.clone(),
-        "shell".to_string(),
-        "am".to_string(),
-        "start".to_string(),
-        "-a".to_string(),
-        "android.settings.APPLICATION_DETAILS_SETTINGS".to_string(),
-        "-d".to_string(),
-        format!("package:{package_name}"),
-    ];
-    let output = run_command_with_timeout(
-        &adb_program,
-        &primary_args,
-        Duration::from_secs(10),
-        &trace_id,
-    )?;
-    let combined = format!(
-        "{}{}",
-        output.stdout.to_lowercase(),
-        output.stderr.to_lowercase()
-    );
-    if output.exit_code.unwrap_or_default() != 0
-        || combined.contains("error")
-        || combined.contains("exception")
-        || combined.contains("unable")
-    {
-        let legacy_args = vec![
-            "-s".to_string(),
-            serial.clone(),
-            "shell".to_string(),
-            "am".to_string(),
-            "start".to_string(),
-            "-n".to_string(),
-            "com.android.settings/.applications.InstalledAppDetails".to_string(),
-            "-e".to_string(),
-            "package".to_string(),
-            package_name,
-        ];
-        let _ = run_command_with_timeout(
-            &adb_program,
-            &legacy_args,
-            Duration::from_secs(10),
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+    if entries.is_empty() || entries.len() > MAX_ENTRIES {
+        return Err(AppError::validation(
+            Message::keyed("field_count").with("max", MAX_ENTRIES),
             &trace_id,
-        )?;
+        ));
+    }
+    let adb_program = get_adb_program(&trace_id)?;
+
+    let mut results = Vec::with_capacity(entries.len());
+    for (index, entry) in entries.iter().enumerate() {
+        let outcome = fill_text_field(&adb_program, &serial, entry, &trace_id);
+        info!(trace_id = %trace_id, serial = %serial, index, success = outcome.is_ok(), "filled text field");
+        let failed = outcome.is_err();
+        results.push(TextFieldFillResult {
+            index,
+            success: !failed,
+            error: outcome.err(),
+        });
+        if failed {
+            break;
+        }
     }
 
     Ok(CommandResponse {
         trace_id,
-        data: true,
+        data: results,
     })
 }
 
-#[tauri::command(async)]
-pub fn launch_app(
-    serials: Vec<String>,
-    package_name: String,
-    state: State<'_, AppState>,
-    trace_id: Option<String>,
-) -> Result<CommandResponse<Vec<CommandResult>>, AppError> {
-    let trace_id = resolve_trace_id(trace_id);
-    ensure_non_empty(&package_name, "package_name", &trace_id)?;
-    if serials.is_empty() {
-        return Err(AppError::validation("serials is required", &trace_id));
-    }
+const TYPE_TEXT_MAX_CHARS: usize = 10_000;
 
-    let adb_program = get_adb_program(&trace_id)?;
-    let scheduler = Arc::clone(&state.scheduler);
+fn adb_keyboard_installed(adb_program: &str, serial: &str, trace_id: &str) -> bool {
+    match run_device_shell(
+        adb_program,
+        serial,
+        &["ime", "list", "-s", "-a"],
+        command_timeout(TimeoutKind::Probe, trace_id),
+        trace_id,
+    ) {
+        Ok(output) => output
+            .stdout
+            .lines()
+            .any(|line| line.trim() == ADB_KEYBOARD_IME),
+        Err(err) => {
+            warn!(trace_id = %trace_id, serial = %serial, error = %err, "failed to list input methods");
+            false
+        }
+    }
+}
 
-    let mut handles = Vec::new();
-    for (index, serial) in serials.into_iter().enumerate() {
-        ensure_non_empty(&serial, "serial", &trace_id)?;
-        let scheduler_clone = Arc::clone(&scheduler);
-        let trace_clone = trace_id.clone();
-        let adb_program_clone = adb_program.clone();
-        let package_clone = package_name.clone();
-        handles.push(std::thread::spawn(move || -> Result<_, AppError> {
-            let _permit = scheduler_clone.acquire_global();
-            let device_lock = scheduler_clone.device_lock(&serial);
-            let _device_guard = device_lock.lock().map_err(|_| {
-                warn!(trace_id = %trace_clone, serial = %serial, "device lock poisoned");
-                AppError::system(
-                    "Failed to access the device. Please try again.",
-                    &trace_clone,
-                )
-            })?;
+/// Sends `text` through ADBKeyboard, switching to it for the duration and restoring the
+/// previous input method afterwards. Returns the number of broadcasts.
+fn type_with_adb_keyboard(
+    adb_program: &str,
+    serial: &str,
+    text: &str,
+    trace_id: &str,
+) -> Result<usize, AppError> {
+    let shell = |args: &[&str]| {
+        run_device_shell(
+            adb_program,
+            serial,
+            args,
+            command_timeout(TimeoutKind::DeviceCommand, trace_id),
+            trace_id,
+        )
+    };
+    let previous = shell(&["settings", "get", "secure", "default_input_method"])?
+        .stdout
+        .trim()
+        .to_string();
+    let switched = previous != ADB_KEYBOARD_IME;
+    if switched {
+        shell(&["ime", "enable", ADB_KEYBOARD_IME])?;
+        shell(&["ime", "set", ADB_KEYBOARD_IME])?;
+        // Give the new IME a moment to bind to the focused field.
+        std::thread::sleep(Duration::from_millis(300));
+    }
+    let payloads = adb_keyboard_payloads(text, ADB_KEYBOARD_CHUNK_BYTES);
+    let mut result = Ok(payloads.len());
+    for payload in &payloads {
+        if let Err(err) = shell(&[
+            "am",
+            "broadcast",
+            "-a",
+            ADB_KEYBOARD_B64_ACTION,
+            "--es",
+            "msg",
+            payload,
+        ]) {
+            result = Err(err);
+            break;
+        }
+    }
+    if switched && !previous.is_empty() && previous != "null" {
+        if let Err(err) = shell(&["ime", "set", &previous]) {
+            warn!(trace_id = %trace_id, serial = %serial, error = %err, "failed to restore the previous input method");
+        }
+    }
+    result
+}
 
-            let args = vec![
-                "-s".to_string(),
-                serial.clone(),
-                "shell".to_string(),
-                "monkey".to_string(),
-                "-p".to_string(),
-                package_clone,
-                "-c".to_string(),
-                "android.intent.category.LAUNCHER".to_string(),
-                "1".to_string(),
-            ];
-            let output = run_command_with_timeout(
-                &adb_program_clone,
-                &args,
-                Duration::from_secs(10),
-                &trace_clone,
-            )?;
-            Ok((
-                index,
-                CommandResult {
-                    serial,
-                    stdout: output.stdout,
-                    stderr: output.stderr,
-                    exit_code: output.exit_code,
-                },
-            ))
-        }));
+fn ensure_type_text_length(text: &str, trace_id: &str) -> Result<usize, AppError> {
+    let characters = text.chars().count();
+    if characters == 0 || characters > TYPE_TEXT_MAX_CHARS {
+        return Err(AppError::validation(
+            Message::keyed("length_range")
+                .with("field", "text")
+                .with("min", 1)
+                .with("max", TYPE_TEXT_MAX_CHARS),
+            trace_id,
+        ));
     }
+    Ok(characters)
+}
 
-    let mut collected = Vec::new();
-    for handle in handles {
-        let (index, result) = handle
-            .join()
-            .map_err(|_| AppError::system("Launch app thread panicked", &trace_id))??;
-        collected.push((index, result));
+/// Returns the method used and the number of `input`/broadcast calls it took.
+fn type_text_on_device(
+    adb_program: &str,
+    serial: &str,
+    text: &str,
+    trace_id: &str,
+) -> Result<(&'static str, usize), AppError> {
+    if adb_keyboard_installed(adb_program, serial, trace_id) {
+        let chunks = type_with_adb_keyboard(adb_program, serial, text, trace_id)?;
+        return Ok(("adb_keyboard", chunks));
     }
-    collected.sort_by_key(|item| item.0);
-    let results = collected.into_iter().map(|item| item.1).collect();
+    if needs_adb_keyboard(text) {
+        return Err(AppError::validation(
+            Message::keyed("text_needs_adb_keyboard"),
+            trace_id,
+        ));
+    }
+    let steps = plan_input_text(text, INPUT_TEXT_CHUNK_CHARS)
+        .map_err(|message| AppError::validation(message, trace_id))?;
+    for step in &steps {
+        let args: Vec<&str> = match step {
+            TextInputStep::Text(chunk) => vec!["input", "text", chunk],
+            TextInputStep::KeyEvent(key) => vec!["input", "keyevent", key],
+        };
+        run_device_shell(
+            adb_program,
+            serial,
+            &args,
+            command_timeout(TimeoutKind::DeviceCommand, trace_id),
+            trace_id,
+        )?;
+    }
+    Ok(("input_text", steps.len()))
+}
+
+/// Types `text` into the focused field. Uses ADBKeyboard when it is installed (any Unicode,
+/// long strings); otherwise falls back to chunked `input text`, which is limited to ASCII.
+/// The typed text is never logged.
+#[tauri::command(async)]
+pub fn type_to_device(
+    serial: String,
+    text: String,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<TypeTextResult>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+    let characters = ensure_type_text_length(&text, &trace_id)?;
+    let adb_program = get_adb_program(&trace_id)?;
+
+    let (method, chunks) = type_text_on_device(&adb_program, &serial, &text, &trace_id)?;
+    info!(trace_id = %trace_id, serial = %serial, method, characters, chunks, "typed text to device");
 
     Ok(CommandResponse {
         trace_id,
-        data: results,
+        data: TypeTextResult {
+            serial,
+            method: method.to_string(),
+            characters,
+            chunks,
+        },
     })
 }
 
 #[tauri::command(async)]
-pub fn check_scrcpy(trace_id: Option<String>) -> Result<CommandResponse<ScrcpyInfo>, AppError> {
+pub fn send_pointer_gesture(
+    serial: String,
+    gesture: PointerGesture,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<PointerGestureResult>, AppError> {
     let trace_id = resolve_trace_id(trace_id);
-    let availability = check_scrcpy_availability();
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+    ensure_non_empty(&gesture.kind, "kind", &trace_id)?;
+
+    let adb_program = get_adb_program(&trace_id)?;
+    let (device_width, device_height) = read_device_screen_size(&adb_program, &serial, &trace_id)?;
+
+    let scaled = scale_pointer_gesture(&gesture, device_width, device_height)
+        .map_err(|message| AppError::validation(message, &trace_id))?;
+    play_scaled_gesture(&adb_program, &serial, &scaled, &trace_id)?;
+
+    let (oriented_width, oriented_height) = orient_device_size(
+        device_width,
+        device_height,
+        gesture.source_width,
+        gesture.source_height,
+    );
+
     Ok(CommandResponse {
         trace_id,
-        data: ScrcpyInfo {
-            available: availability.available,
-            version_output: availability.version_output,
-            major_version: availability.major_version,
-            command_path: availability.command_path,
+        data: PointerGestureResult {
+            serial,
+            kind: scaled.kind,
+            device_width: oriented_width,
+            device_height: oriented_height,
+            x: scaled.x,
+            y: scaled.y,
+            end_x: scaled.end_x,
+            end_y: scaled.end_y,
+            duration_ms: scaled.duration_ms,
         },
     })
 }
 
+/// Upper bound on buffered `getevent` output; roughly an hour of continuous touches.
+const MAX_GESTURE_CAPTURE_BYTES: usize = 16 * 1024 * 1024;
+
 #[tauri::command(async)]
-pub fn launch_scrcpy(
-    serials: Vec<String>,
+pub fn start_gesture_recording(
+    serial: String,
     state: State<'_, AppState>,
     trace_id: Option<String>,
-) -> Result<CommandResponse<Vec<CommandResult>>, AppError> {
+) -> Result<CommandResponse<bool>, AppError> {
     let trace_id = resolve_trace_id(trace_id);
-    if serials.is_empty() {
-        return Err(AppError::validation("serials is required", &trace_id));
-    }
+    ensure_non_empty(&serial, "serial", &trace_id)?;
 
-    let availability = check_scrcpy_availability();
-    if !availability.available {
-        return Err(AppError::dependency("scrcpy is not available", &trace_id));
-    }
-    let config = load_config(&trace_id)?;
-    let scheduler = Arc::clone(&state.scheduler);
+    let adb_program = get_adb_program(&trace_id)?;
+    let (screen_width, screen_height) = read_device_screen_size(&adb_program, &serial, &trace_id)?;
+    let devices = run_device_shell(
+        &adb_program,
+        &serial,
+        &["getevent", "-lp"],
+        command_timeout(TimeoutKind::DeviceCommand, &trace_id),
+        &trace_id,
+    )?;
+    let device = parse_touch_device(&devices.stdout)
+        .ok_or_else(|| AppError::dependency(Message::keyed("no_touchscreen"), &trace_id))?;
 
-    let mut results = Vec::with_capacity(serials.len());
-    for serial in serials {
-        ensure_non_empty(&serial, "serial", &trace_id)?;
-        let _permit = scheduler.acquire_global();
-        let device_lock = scheduler.device_lock(&serial);
-        let _device_guard = match device_lock.lock() {
-            Ok(guard) => guard,
-            Err(_) => {
-                warn!(trace_id = %trace_id, serial = %serial, "device lock poisoned");
-                results.push(CommandResult {
-                    serial,
-                    stdout: String::new(),
-                    stderr: "Failed to access the device. Please try again.".to_string(),
-                    exit_code: Some(1),
-                });
-                continue;
-            }
-        };
-        let mut args = build_scrcpy_command(&serial, &config.scrcpy, availability.major_version);
-        if !availability.command_path.trim().is_empty() {
-            args[0] = availability.command_path.clone();
-        }
-        let mut iter = args.into_iter();
-        let command_path = iter.next().unwrap_or_else(|| "scrcpy".to_string());
-        let mut command = Command::new(command_path);
-        command
-            .args(iter)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped());
-        let spawn_result = command.spawn();
-        match spawn_result {
-            Ok(mut child) => {
-                std::thread::sleep(Duration::from_millis(150));
-                match child.try_wait() {
-                    Ok(Some(_)) => match child.wait_with_output() {
-                        Ok(output) => {
-                            let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
-                            let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
-                            let exit_code = output.status.code().unwrap_or(1);
-                            let (final_exit, final_stderr) = if output.status.success() {
-                                let detail = if !stderr.is_empty() {
-                                    stderr
-                                } else {
-                                    stdout.clone()
-                                };
-                                let message = if detail.is_empty() {
-                                    "scrcpy exited immediately".to_string()
-                                } else {
-                                    format!("scrcpy exited immediately: {detail}")
-                                };
-                                (1, message)
-                            } else {
-                                (exit_code, stderr)
-                            };
-                            if final_exit != 0 {
-                                warn!(
-                                    trace_id = %trace_id,
-                                    serial = %serial,
-                                    error = %final_stderr,
-                                    "scrcpy exited immediately"
-                                );
-                            }
-                            results.push(CommandResult {
-                                serial,
-                                stdout,
-                                stderr: final_stderr,
-                                exit_code: Some(final_exit),
-                            });
-                        }
-                        Err(err) => results.push(CommandResult {
-                            serial,
-                            stdout: String::new(),
-                            stderr: format!("Failed to capture scrcpy output: {err}"),
-                            exit_code: Some(1),
-                        }),
-                    },
-                    Ok(None) => results.push(CommandResult {
-                        serial,
-                        stdout: "scrcpy launched".to_string(),
-                        stderr: String::new(),
-                        exit_code: Some(0),
-                    }),
-                    Err(err) => results.push(CommandResult {
-                        serial,
-                        stdout: String::new(),
-                        stderr: format!("Failed to check scrcpy status: {err}"),
-                        exit_code: Some(1),
-                    }),
+    let mut guard = state
+        .gesture_recorders
+        .lock()
+        .map_err(|_| AppError::system("Gesture recorder registry locked", &trace_id))?;
+    if guard.contains_key(&serial) {
+        return Err(AppError::validation(
+            Message::keyed("already_active").with("subject", "Gesture recording"),
+            &trace_id,
+        ));
+    }
+
+    let mut child = spawn_logged(
+        adb_command_for_serial(&adb_program, &serial)
+            .args(["-s", &serial, "shell", "getevent", "-lt", &device.path])
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null()),
+        &trace_id,
+    )
+    .map_err(|err| {
+        AppError::dependency(
+            Message::keyed("start_failed")
+                .with("subject", "getevent")
+                .with("detail", &err),
+            &trace_id,
+        )
+    })?;
+    let stdout = child
+        .stdout
+        .take()
+        .ok_or_else(|| AppError::system("Failed to capture getevent stdout", &trace_id))?;
+
+    let output = Arc::new(Mutex::new(String::new()));
+    let output_thread = Arc::clone(&output);
+    let trace_thread = trace_id.clone();
+    let join = std::thread::spawn(move || {
+        let reader = BufReader::new(stdout);
+        for line_result in reader.lines() {
+            let line = match line_result {
+                Ok(line) => line,
+                Err(err) => {
+                    warn!(trace_id = %trace_thread, error = %err, "failed to read getevent output");
+                    break;
                 }
+            };
+            let Ok(mut buffer) = output_thread.lock() else {
+                break;
+            };
+            if buffer.len() + line.len() >= MAX_GESTURE_CAPTURE_BYTES {
+                warn!(trace_id = %trace_thread, "gesture capture buffer full; ignoring further events");
+                break;
             }
-            Err(err) => results.push(CommandResult {
-                serial,
-                stdout: String::new(),
-                stderr: format!("Failed to launch scrcpy: {err}"),
-                exit_code: Some(1),
-            }),
+            buffer.push_str(&line);
+            buffer.push('\n');
         }
-    }
+    });
+
+    info!(trace_id = %trace_id, serial = %serial, device = %device.path, "gesture recording started");
+    guard.insert(
+        serial,
+        GestureRecorderHandle {
+            child,
+            output,
+            join,
+            device,
+            screen_width,
+            screen_height,
+        },
+    );
 
     Ok(CommandResponse {
         trace_id,
-        data: results,
+        data: true,
     })
 }
 
 #[tauri::command(async)]
-pub fn capture_ui_hierarchy(
+pub fn stop_gesture_recording(
     serial: String,
+    output_dir: Option<String>,
+    state: State<'_, AppState>,
     trace_id: Option<String>,
-) -> Result<CommandResponse<UiHierarchyCaptureResult>, AppError> {
+) -> Result<CommandResponse<GestureRecordingSummary>, AppError> {
     let trace_id = resolve_trace_id(trace_id);
     ensure_non_empty(&serial, "serial", &trace_id)?;
 
-    let adb_program = get_adb_program(&trace_id)?;
-    let config = load_config(&trace_id)?;
-    let output = Command::new(&adb_program)
-        .args(["-s", &serial, "exec-out", "uiautomator", "dump", "/dev/tty"])
-        .output()
-        .map_err(|err| {
-            AppError::dependency(format!("Failed to run uiautomator: {err}"), &trace_id)
-        })?;
+    let handle = state
+        .gesture_recorders
+        .lock()
+        .map_err(|_| AppError::system("Gesture recorder registry locked", &trace_id))?
+        .remove(&serial)
+        .ok_or_else(|| AppError::validation(Message::keyed("no_gesture_recording"), &trace_id))?;
+    let mut child = handle.child;
+    let _ = child.kill();
+    let _ = child.wait();
+    if handle.join.join().is_err() {
+        warn!(trace_id = %trace_id, serial = %serial, "gesture reader thread panicked");
+    }
 
-    if !output.status.success() {
-        return Err(AppError::dependency(
-            format!(
-                "UI dump failed: {}",
-                String::from_utf8_lossy(&output.stderr)
-            ),
+    let raw = handle
+        .output
+        .lock()
+        .map(|buffer| buffer.clone())
+        .map_err(|_| AppError::system("Gesture capture buffer locked", &trace_id))?;
+    let strokes = parse_getevent_strokes(
+        &raw,
+        &handle.device,
+        handle.screen_width,
+        handle.screen_height,
+    );
+    if strokes.is_empty() {
+        return Err(AppError::validation(
+            Message::keyed("no_touches"),
             &trace_id,
         ));
     }
-
-    let xml = String::from_utf8_lossy(&output.stdout).to_string();
-    let html = render_device_ui_html(&xml)
-        .map_err(|err| AppError::system(format!("Failed to render HTML: {err}"), &trace_id))?;
-
-    let mut screenshot_args = vec![
-        "-s".to_string(),
-        serial.clone(),
-        "exec-out".to_string(),
-        "screencap".to_string(),
-        "-p".to_string(),
-    ];
-    if config.screenshot.display_id >= 0 {
-        screenshot_args.push("-d".to_string());
-        screenshot_args.push(config.screenshot.display_id.to_string());
-    }
-    if !config.screenshot.extra_args.trim().is_empty() {
-        screenshot_args.extend(
-            config
-                .screenshot
-                .extra_args
-                .split_whitespace()
-                .map(|item| item.to_string()),
-        );
-    }
-
-    let (screenshot_data_url, screenshot_error) = match Command::new(&adb_program)
-        .args(&screenshot_args)
-        .output()
-    {
-        Ok(screenshot_output) => {
-            if !screenshot_output.status.success() {
-                let message = format!(
-                    "Screenshot failed: {}",
-                    String::from_utf8_lossy(&screenshot_output.stderr)
-                );
-                warn!(trace_id = %trace_id, error = %message, "ui_inspector screenshot failed");
-                (None, Some(message))
-            } else {
-                match png_bytes_to_data_url(&screenshot_output.stdout) {
-                    Ok(url) => (Some(url), None),
-                    Err(message) => {
-                        warn!(trace_id = %trace_id, error = %message, "ui_inspector screenshot invalid");
-                        (None, Some(message))
-                    }
-                }
-            }
-        }
-        Err(err) => {
-            let message = format!("Failed to capture screenshot: {err}");
-            warn!(trace_id = %trace_id, error = %message, "ui_inspector screenshot error");
-            (None, Some(message))
-        }
+    let duration_ms = strokes
+        .last()
+        .map(|stroke| {
+            stroke.start_ms + u64::from(stroke.points.last().map(|p| p.t_ms).unwrap_or_default())
+        })
+        .unwrap_or_default();
+    let recording = GestureRecording {
+        version: GESTURE_FILE_VERSION,
+        source_width: handle.screen_width,
+        source_height: handle.screen_height,
+        strokes,
     };
 
+    let config = load_config(&trace_id)?;
+    let dir = prepare_artifact_dir(
+        &config,
+        ArtifactKind::Recording,
+        output_dir.as_deref(),
+        &trace_id,
+    )?;
+    let filename = format!(
+        "gestures_{}_{}.json",
+        sanitize_filename_component(&serial),
+        Utc::now().format("%Y%m%d_%H%M%S")
+    );
+    let path = dir.join(filename);
+    let json = serde_json::to_vec_pretty(&recording).map_err(|err| {
+        AppError::system(
+            Message::keyed("operation_failed")
+                .with("action", "serialize gestures")
+                .with("detail", &err),
+            &trace_id,
+        )
+    })?;
+    fs::write(&path, json).map_err(|err| {
+        AppError::system(
+            Message::keyed("operation_failed")
+                .with("action", "write gesture file")
+                .with("detail", &err),
+            &trace_id,
+        )
+    })?;
+
     Ok(CommandResponse {
         trace_id,
-        data: UiHierarchyCaptureResult {
-            html,
-            xml,
-            screenshot_data_url,
-            screenshot_error,
+        data: GestureRecordingSummary {
+            serial,
+            path: path.to_string_lossy().to_string(),
+            stroke_count: recording.strokes.len(),
+            duration_ms,
+            source_width: recording.source_width,
+            source_height: recording.source_height,
         },
     })
 }
 
+/// Replay a gesture file stroke by stroke with `input`, scaled to this device's screen and
+/// keeping the recorded pauses (capped) between strokes.
 #[tauri::command(async)]
-pub fn export_ui_hierarchy(
+pub fn replay_gesture_file(
     serial: String,
-    output_dir: Option<String>,
+    path: String,
+    state: State<'_, AppState>,
     trace_id: Option<String>,
-) -> Result<CommandResponse<UiHierarchyExportResult>, AppError> {
+) -> Result<CommandResponse<GestureReplayResult>, AppError> {
     let trace_id = resolve_trace_id(trace_id);
     ensure_non_empty(&serial, "serial", &trace_id)?;
+    ensure_non_empty(&path, "path", &trace_id)?;
+
+    let content = fs::read_to_string(path.trim()).map_err(|err| {
+        AppError::validation(
+            Message::keyed("operation_failed")
+                .with("action", "read gesture file")
+                .with("detail", &err),
+            &trace_id,
+        )
+    })?;
+    let recording: GestureRecording = serde_json::from_str(&content).map_err(|err| {
+        AppError::validation(
+            Message::keyed("invalid_value")
+                .with("subject", "gesture file")
+                .with("value", &err),
+            &trace_id,
+        )
+    })?;
+    validate_gesture_recording(&recording)
+        .map_err(|message| AppError::validation(message, &trace_id))?;
 
     let adb_program = get_adb_program(&trace_id)?;
-    let config = load_config(&trace_id)?;
-    let resolved_dir = output_dir
-        .filter(|value| !value.trim().is_empty())
-        .unwrap_or_else(|| {
-            if !config.file_gen_output_path.trim().is_empty() {
-                config.file_gen_output_path.clone()
-            } else {
-                config.output_path.clone()
-            }
-        });
-    ensure_non_empty(&resolved_dir, "output_dir", &trace_id)?;
-    fs::create_dir_all(&resolved_dir).map_err(|err| {
-        AppError::system(format!("Failed to create output dir: {err}"), &trace_id)
+    let (device_width, device_height) = read_device_screen_size(&adb_program, &serial, &trace_id)?;
+
+    let device_lock = state.scheduler.device_lock(&serial);
+    let _device_guard = device_lock.lock().map_err(|_| {
+        warn!(trace_id = %trace_id, serial = %serial, "device lock poisoned");
+        AppError::system("Failed to access the device. Please try again.", &trace_id)
     })?;
 
-    let timestamp = Utc::now().format("%Y%m%d_%H%M%S");
-    let xml_path =
-        PathBuf::from(&resolved_dir).join(format!("ui_hierarchy_{}_{}.xml", serial, timestamp));
-    let html_path =
-        PathBuf::from(&resolved_dir).join(format!("ui_hierarchy_{}_{}.html", serial, timestamp));
-    let screenshot_path =
-        PathBuf::from(&resolved_dir).join(format!("ui_hierarchy_{}_{}.png", serial, timestamp));
-
-    let output = Command::new(&adb_program)
-        .args(["-s", &serial, "exec-out", "uiautomator", "dump", "/dev/tty"])
-        .output()
-        .map_err(|err| {
-            AppError::dependency(format!("Failed to run uiautomator: {err}"), &trace_id)
-        })?;
+    let mut previous: Option<&GestureStroke> = None;
+    let mut strokes_played = 0usize;
+    for stroke in &recording.strokes {
+        let Some(gesture) = stroke_to_gesture(
+            stroke,
+            recording.source_width,
+            recording.source_height,
+            device_width,
+            device_height,
+        ) else {
+            continue;
+        };
+        if let Some(previous) = previous {
+            std::thread::sleep(Duration::from_millis(replay_gap_ms(previous, stroke)));
+        }
+        play_scaled_gesture(&adb_program, &serial, &gesture, &trace_id)?;
+        previous = Some(stroke);
+        strokes_played += 1;
+    }
 
-    if !output.status.success() {
-        return Err(AppError::dependency(
-            format!(
-                "UI dump failed: {}",
-                String::from_utf8_lossy(&output.stderr)
-            ),
+    Ok(CommandResponse {
+        trace_id,
+        data: GestureReplayResult {
+            serial,
+            strokes_played,
+            device_width,
+            device_height,
+        },
+    })
+}
+
+/// Run `action(index, serial)` on every serial at once, each holding its device lock, and
+/// report per device.
+fn broadcast_to_devices(
+    serials: &[String],
+    scheduler: &TaskScheduler,
+    trace_id: &str,
+    action: impl Fn(usize, &str) -> Result<(), AppError> + Sync,
+) -> Vec<InputBroadcastDeviceResult> {
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = serials
+            .iter()
+            .enumerate()
+            .map(|(index, serial)| {
+                let action = &action;
+                scope.spawn(move || {
+                    let device_lock = scheduler.device_lock(serial);
+                    let result = match device_lock.lock() {
+                        Ok(_guard) => action(index, serial),
+                        Err(_) => Err(AppError::system(
+                            "Failed to access the device. Please try again.",
+                            trace_id,
+                        )),
+                    };
+                    if let Err(err) = &result {
+                        warn!(trace_id = %trace_id, serial = %serial, error = %err, "input broadcast failed on device");
+                    }
+                    InputBroadcastDeviceResult {
+                        serial: serial.clone(),
+                        success: result.is_ok(),
+                        error: result.err().map(|err| err.error),
+                    }
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .zip(serials)
+            .map(|(handle, serial)| {
+                handle
+                    .join()
+                    .unwrap_or_else(|_| InputBroadcastDeviceResult {
+                        serial: serial.clone(),
+                        success: false,
+                        error: Some("Input broadcast thread panicked".to_string()),
+                    })
+            })
+            .collect()
+    })
+}
+
+/// Send one pointer gesture, given in source-image pixels, to every device at the same time,
+/// scaled to each screen.
+#[tauri::command(async)]
+pub fn broadcast_pointer_gesture(
+    serials: Vec<String>,
+    gesture: PointerGesture,
+    app: AppHandle,
+    state: State<'_, AppState>,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<Vec<InputBroadcastDeviceResult>>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&gesture.kind, "kind", &trace_id)?;
+    let serials = broadcast_targets(None, &serials)
+        .map_err(|message| AppError::validation(message, &trace_id))?;
+    // Reject a malformed gesture once instead of failing on every device.
+    scale_pointer_gesture(&gesture, gesture.source_width, gesture.source_height)
+        .map_err(|message| AppError::validation(message, &trace_id))?;
+    warn_on_reservation_conflicts(&app, &serials, "Broadcasting input", &trace_id);
+
+    let adb_program = get_adb_program(&trace_id)?;
+    let results = broadcast_to_devices(&serials, &state.scheduler, &trace_id, |_, serial| {
+        let (device_width, device_height) =
+            read_device_screen_size(&adb_program, serial, &trace_id)?;
+        let scaled = scale_pointer_gesture(&gesture, device_width, device_height)
+            .map_err(|message| AppError::validation(message, &trace_id))?;
+        play_scaled_gesture(&adb_program, serial, &scaled, &trace_id)
+    });
+    info!(trace_id = %trace_id, kind = %gesture.kind, devices = serials.len(), "broadcast pointer gesture");
+
+    Ok(CommandResponse {
+        trace_id,
+        data: results,
+    })
+}
+
+/// Type the same text into the focused field of every device at once. The text is never
+/// logged.
+#[tauri::command(async)]
+pub fn broadcast_text(
+    serials: Vec<String>,
+    text: String,
+    app: AppHandle,
+    state: State<'_, AppState>,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<Vec<InputBroadcastDeviceResult>>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    let characters = ensure_type_text_length(&text, &trace_id)?;
+    let serials = broadcast_targets(None, &serials)
+        .map_err(|message| AppError::validation(message, &trace_id))?;
+    warn_on_reservation_conflicts(&app, &serials, "Broadcasting input", &trace_id);
+
+    let adb_program = get_adb_program(&trace_id)?;
+    let results = broadcast_to_devices(&serials, &state.scheduler, &trace_id, |_, serial| {
+        type_text_on_device(&adb_program, serial, &text, &trace_id).map(|_| ())
+    });
+    info!(trace_id = %trace_id, characters, devices = serials.len(), "broadcast text");
+
+    Ok(CommandResponse {
+        trace_id,
+        data: results,
+    })
+}
+
+/// Mirror touches made on `leader` to `followers`: every stroke read from the leader's
+/// touchscreen is replayed on all followers at once as soon as the finger lifts, scaled to
+/// each screen, and reported through `input-broadcast` events.
+#[tauri::command(async)]
+pub fn start_input_broadcast(
+    leader: String,
+    followers: Vec<String>,
+    app: AppHandle,
+    state: State<'_, AppState>,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<InputBroadcastInfo>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&leader, "leader", &trace_id)?;
+    let leader = leader.trim().to_string();
+    let followers = broadcast_targets(Some(&leader), &followers)
+        .map_err(|message| AppError::validation(message, &trace_id))?;
+    warn_on_reservation_conflicts(&app, &followers, "Broadcasting input", &trace_id);
+
+    let adb_program = get_adb_program(&trace_id)?;
+    let (leader_width, leader_height) = read_device_screen_size(&adb_program, &leader, &trace_id)?;
+    let follower_sizes = followers
+        .iter()
+        .map(|serial| read_device_screen_size(&adb_program, serial, &trace_id))
+        .collect::<Result<Vec<_>, AppError>>()?;
+    let devices = run_device_shell(
+        &adb_program,
+        &leader,
+        &["getevent", "-lp"],
+        command_timeout(TimeoutKind::DeviceCommand, &trace_id),
+        &trace_id,
+    )?;
+    let device = parse_touch_device(&devices.stdout)
+        .ok_or_else(|| AppError::dependency(Message::keyed("no_touchscreen"), &trace_id))?;
+
+    let mut guard = state
+        .input_broadcasts
+        .lock()
+        .map_err(|_| AppError::system("Input broadcast registry locked", &trace_id))?;
+    if guard.contains_key(&leader) {
+        return Err(AppError::validation(
+            Message::keyed("input_broadcast_running"),
             &trace_id,
         ));
     }
 
-    let xml = String::from_utf8_lossy(&output.stdout).to_string();
-    let html = render_device_ui_html(&xml)
-        .map_err(|err| AppError::system(format!("Failed to render HTML: {err}"), &trace_id))?;
+    let mut child = spawn_logged(
+        adb_command_for_serial(&adb_program, &leader)
+            .args(["-s", &leader, "shell", "getevent", "-lt", &device.path])
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null()),
+        &trace_id,
+    )
+    .map_err(|err| {
+        AppError::dependency(
+            Message::keyed("start_failed")
+                .with("subject", "getevent")
+                .with("detail", &err),
+            &trace_id,
+        )
+    })?;
+    let stdout = child
+        .stdout
+        .take()
+        .ok_or_else(|| AppError::system("Failed to capture getevent stdout", &trace_id))?;
+
+    let info = InputBroadcastInfo {
+        leader: leader.clone(),
+        followers: followers.clone(),
+        touch_device: device.path.clone(),
+    };
+    let scheduler = Arc::clone(&state.scheduler);
+    let leader_thread = leader.clone();
+    let followers_thread = followers.clone();
+    let trace_thread = trace_id.clone();
+    let join = std::thread::spawn(move || {
+        let mut parser = GeteventStrokeParser::new(device, leader_width, leader_height);
+        for line_result in BufReader::new(stdout).lines() {
+            let line = match line_result {
+                Ok(line) => line,
+                Err(err) => {
+                    warn!(trace_id = %trace_thread, error = %err, "failed to read getevent output");
+                    break;
+                }
+            };
+            let Some(stroke) = parser.push_line(&line) else {
+                continue;
+            };
+            let Some(kind) = stroke_to_gesture(
+                &stroke,
+                leader_width,
+                leader_height,
+                leader_width,
+                leader_height,
+            )
+            .map(|gesture| gesture.kind) else {
+                continue;
+            };
+            let results = broadcast_to_devices(
+                &followers_thread,
+                &scheduler,
+                &trace_thread,
+                |index, serial| {
+                    let (width, height) = follower_sizes[index];
+                    let gesture =
+                        stroke_to_gesture(&stroke, leader_width, leader_height, width, height)
+                            .ok_or_else(|| AppError::system("Empty stroke", &trace_thread))?;
+                    play_scaled_gesture(&adb_program, serial, &gesture, &trace_thread)
+                },
+            );
+            let event = InputBroadcastEvent {
+                leader: leader_thread.clone(),
+                kind,
+                results,
+                trace_id: trace_thread.clone(),
+            };
+            if let Err(err) = emit_tracked(&app, INPUT_BROADCAST_EVENT_NAME, event) {
+                warn!(trace_id = %trace_thread, error = %err, "failed to emit input broadcast event");
+            }
+        }
+    });
 
-    fs::write(&xml_path, xml)
-        .map_err(|err| AppError::system(format!("Failed to write XML: {err}"), &trace_id))?;
-    fs::write(&html_path, html)
-        .map_err(|err| AppError::system(format!("Failed to write HTML: {err}"), &trace_id))?;
+    info!(trace_id = %trace_id, leader = %leader, followers = followers.len(), device = %info.touch_device, "input broadcast started");
+    guard.insert(
+        leader,
+        InputBroadcastHandle {
+            child,
+            join,
+            followers,
+        },
+    );
+    Ok(CommandResponse {
+        trace_id,
+        data: info,
+    })
+}
 
-    let mut screenshot_args = vec![
-        "-s".to_string(),
-        serial.clone(),
-        "exec-out".to_string(),
-        "screencap".to_string(),
-        "-p".to_string(),
-    ];
-    if config.screenshot.display_id >= 0 {
-        screenshot_args.push("-d".to_string());
-        screenshot_args.push(config.screenshot.display_id.to_string());
-    }
-    if !config.screenshot.extra_args.trim().is_empty() {
-        screenshot_args.extend(
-            config
-                .screenshot
-                .extra_args
-                .split_whitespace()
-                .map(|item| item.to_string()),
-        );
-    }
+#[tauri::command(async)]
+pub fn stop_input_broadcast(
+    leader: String,
+    state: State<'_, AppState>,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<bool>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&leader, "leader", &trace_id)?;
 
-    let screenshot_output = Command::new(&adb_program)
-        .args(&screenshot_args)
-        .output()
-        .map_err(|err| {
-            AppError::dependency(format!("Failed to capture screenshot: {err}"), &trace_id)
+    let handle = state
+        .input_broadcasts
+        .lock()
+        .map_err(|_| AppError::system("Input broadcast registry locked", &trace_id))?
+        .remove(leader.trim())
+        .ok_or_else(|| {
+            AppError::validation(
+                Message::keyed("not_running").with("subject", "Input broadcast"),
+                &trace_id,
+            )
         })?;
-    if !screenshot_output.status.success() {
-        return Err(AppError::dependency(
-            format!(
-                "Failed to capture screenshot: {}",
-                String::from_utf8_lossy(&screenshot_output.stderr)
-            ),
-            &trace_id,
-        ));
+    let mut child = handle.child;
+    if let Err(err) = child.kill() {
+        warn!(trace_id = %trace_id, error = %err, "failed to stop input broadcast getevent");
     }
-    let mut screenshot_file = fs::File::create(&screenshot_path).map_err(|err| {
-        AppError::system(format!("Failed to create screenshot: {err}"), &trace_id)
-    })?;
-    screenshot_file
-        .write_all(&screenshot_output.stdout)
-        .map_err(|err| AppError::system(format!("Failed to write screenshot: {err}"), &trace_id))?;
+    let _ = child.wait();
+    if handle.join.join().is_err() {
+        warn!(trace_id = %trace_id, leader = %leader, "input broadcast thread panicked");
+    }
+    info!(trace_id = %trace_id, leader = %leader, followers = handle.followers.len(), "input broadcast stopped");
 
     Ok(CommandResponse {
         trace_id,
-        data: UiHierarchyExportResult {
-            serial,
-            xml_path: xml_path.to_string_lossy().to_string(),
-            html_path: html_path.to_string_lossy().to_string(),
-            screenshot_path: screenshot_path.to_string_lossy().to_string(),
-        },
+        data: true,
     })
 }
 
@@ -4617,6 +17401,8 @@ pub fn start_perf_monitor(
     let interval = Duration::from_millis(interval_ms);
     let perf_script = build_perf_script();
     let scheduler = Arc::clone(&state.scheduler);
+    let aggregator = Arc::clone(&state.perf_aggregator);
+    let soak_feeds = Arc::clone(&state.soak_feeds);
 
     let app_emit = app.clone();
     let serial_spawn = serial.clone();
@@ -4639,6 +17425,53 @@ pub fn start_perf_monitor(
                 temperature_decic: None,
             };
             let mut last_battery_check: Option<Instant> = None;
+            let mut cpu_clusters: Vec<CpuCluster> = Vec::new();
+
+            // Core topology does not change while the device is up, so read it once.
+            {
+                let args = vec![
+                    "-s".to_string(),
+                    serial_spawn.clone(),
+                    "shell".to_string(),
+                    CPU_TOPOLOGY_SCRIPT.to_string(),
+                ];
+                let output = {
+                    let _permit = scheduler.acquire_global();
+                    let device_lock = scheduler.device_lock(&serial_spawn);
+                    let device_guard = device_lock.lock().ok();
+                    device_guard.map(|_guard| {
+                        run_command_with_timeout(
+                            &adb_program_spawn,
+                            &args,
+                            command_timeout(TimeoutKind::Probe, &trace_spawn),
+                            &trace_spawn,
+                        )
+                    })
+                };
+
+                match output {
+                    Some(Ok(output)) if output.exit_code.unwrap_or_default() == 0 => {
+                        cpu_clusters = parse_cpu_clusters(&output.stdout);
+                    }
+                    Some(Ok(output)) => {
+                        warn!(
+                            trace_id = %trace_spawn,
+                            exit_code = ?output.exit_code,
+                            "cpu topology read returned non-zero exit code"
+                        );
+                    }
+                    Some(Err(err)) => {
+                        warn!(
+                            trace_id = %trace_spawn,
+                            error = %err,
+                            "failed to read cpu topology"
+                        );
+                    }
+                    None => {
+                        warn!(trace_id = %trace_spawn, "device lock poisoned");
+                    }
+                }
+            }
 
             {
                 let args = vec![
@@ -4655,7 +17488,7 @@ pub fn start_perf_monitor(
                         run_command_with_timeout(
                             &adb_program_spawn,
                             &args,
-                            Duration::from_secs(3),
+                            command_timeout(TimeoutKind::Probe, &trace_spawn),
                             &trace_spawn,
                         )
                     })
@@ -4705,7 +17538,7 @@ pub fn start_perf_monitor(
                         run_command_with_timeout(
                             &adb_program_spawn,
                             &args,
-                            Duration::from_secs(3),
+                            command_timeout(TimeoutKind::Probe, &trace_spawn),
                             &trace_spawn,
                         )
                     })
@@ -4903,7 +17736,7 @@ pub fn start_perf_monitor(
                             run_command_with_timeout(
                                 &adb_program_spawn,
                                 &args,
-                                Duration::from_secs(3),
+                                command_timeout(TimeoutKind::Probe, &trace_spawn),
                                 &trace_spawn,
                             )
                         })
@@ -4965,7 +17798,173 @@ pub fn start_perf_monitor(
                             run_command_with_timeout(
                                 &adb_program_spawn,
                                 &args,
-                                Duration::from_secs(3),
+                                command_timeout(TimeoutKind::Probe, &trace_spawn),
+                                &trace_spawn,
+                            )
+                        })
+                    };
+
+                    match output {
+                        Some(Ok(output)) => {
+                            if output.exit_code.unwrap_or_default() == 0 {
+                                let digits = output.stdout.trim();
+                                if let Ok(total) = digits.parse::<u64>() {
+                                    let now = Instant::now();
+                                    if let (Some(prev_total), Some(prev_instant)) =
+                                        (missed_frames_total_prev, missed_frames_instant_prev)
+                                    {
+                                        let delta = total.saturating_sub(prev_total) as u128;
+                                        let dt_ms = now.duration_since(prev_instant).as_millis();
+                                        if dt_ms > 0 {
+                                            let per_sec_x100 = ((delta * 100_000u128) / dt_ms)
+                                                .min(u16::MAX as u128)
+                                                as u16;
+                                            missed_frames_per_sec_x100 = Some(per_sec_x100);
+                                        }
+                                    }
+                                    missed_frames_total_prev = Some(total);
+                                    missed_frames_instant_prev = Some(now);
+                                }
+                            } else {
+                                warn!(
+                                    trace_id = %trace_spawn,
+                                    exit_code = ?output.exit_code,
+                                    stderr = %output.stderr,
+                                    "SurfaceFlinger dumpsys returned non-zero exit code"
+                                );
+                            }
+                        }
+                        Some(Err(err)) => {
+                            warn!(
+                                trace_id = %trace_spawn,
+                                error = %err,
+                                "failed to collect SurfaceFlinger missed frames"
+                            );
+                        }
+                        None => {
+                            warn!(trace_id = %trace_spawn, "device lock poisoned");
+                        }
+                    }
+                }
+
+                let snapshot = build_perf_snapshot(PerfSnapshotInput {
+                    ts_ms,
+                    cpu_prev,
+                    cpu_curr,
+                    core_percents_x100,
+                    core_freq_khz,
+                    mem,
+                    net_prev,
+                    net_curr,
+                    dt_ms,
+                    battery: battery_last,
+                    display_refresh_hz_x100,
+                    missed_frames_per_sec_x100,
+                    cpu_clusters: cpu_clusters.clone(),
+                });
+
+                cpu_prev = Some(cpu_curr);
+                net_prev = Some(net_curr);
+                net_prev_instant = Some(sample_instant);
+
+                publish_to_perf_aggregator(
+                    &app_emit,
+                    &aggregator,
+                    &serial_spawn,
+                    &snapshot,
+                    &trace_spawn,
+                );
+                publish_to_soak_test(
+                    &soak_feeds,
+                    &serial_spawn,
+                    SoakReading::Perf(&snapshot),
+                    &trace_spawn,
+                );
+                emit_perf_event(
+                    &app_emit,
+                    PerfEvent {
+                        serial: serial_spawn.clone(),
+                        snapshot: Some(snapshot),
+                        error: None,
+                        trace_id: trace_spawn.clone(),
+                    },
+                );
+
+                let elapsed = loop_started.elapsed();
+                if elapsed < interval {
+                    sleep_with_stop(interval - elapsed, &stop_flag);
+                }
+            }
+        })
+    })?;
+
+    Ok(CommandResponse {
+        trace_id,
+        data: true,
+    })
+}
+
+#[tauri::command(async)]
+pub fn start_net_profiler(
+    serial: String,
+    interval_ms: Option<u64>,
+    top_n: Option<u32>,
+    pinned_uids: Option<Vec<u32>>,
+    app: AppHandle,
+    state: State<'_, AppState>,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<bool>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+
+    let adb_program = get_adb_program(&trace_id)?;
+    let interval_ms = clamp_net_profiler_interval_ms(interval_ms);
+    let interval = Duration::from_millis(interval_ms);
+    let top_n = clamp_net_profiler_top_n(top_n);
+    let pinned_uids = sanitize_net_profiler_pinned_uids(pinned_uids, top_n, &trace_id)?;
+    let scheduler = Arc::clone(&state.scheduler);
+    let soak_feeds = Arc::clone(&state.soak_feeds);
+
+    let app_emit = app.clone();
+    let serial_spawn = serial.clone();
+    let trace_spawn = trace_id.clone();
+    let adb_program_spawn = adb_program.clone();
+
+    start_net_profiler_inner(
+        serial,
+        &state.net_profilers,
+        &trace_id,
+        pinned_uids,
+        move |stop_flag, pinned_uids| {
+            std::thread::spawn(move || {
+                #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+                enum NetStatsSource {
+                    ProcXtQtaguid,
+                    DumpsysNetstats,
+                }
+
+                let mut packages_by_uid: HashMap<u32, Vec<String>> = HashMap::new();
+                let mut prev_totals: Option<HashMap<u32, (u64, u64)>> = None;
+                let mut prev_instant: Option<Instant> = None;
+                let mut stats_source: Option<NetStatsSource> = None;
+                let mut unsupported = false;
+
+                {
+                    let args = vec![
+                        "-s".to_string(),
+                        serial_spawn.clone(),
+                        "shell".to_string(),
+                        "cmd package list packages -U".to_string(),
+                    ];
+                    let output = {
+                        let _permit = scheduler.acquire_global();
+                        let device_lock = scheduler.device_lock(&serial_spawn);
+                        let device_guard = device_lock.lock().ok();
+                        device_guard.map(|_guard| {
+                            run_command_with_timeout(
+                                &adb_program_spawn,
+                                &args,
+                                command_timeout(TimeoutKind::Probe, &trace_spawn),
                                 &trace_spawn,
                             )
                         })
@@ -4974,95 +17973,483 @@ pub fn start_perf_monitor(
                     match output {
                         Some(Ok(output)) => {
                             if output.exit_code.unwrap_or_default() == 0 {
-                                let digits = output.stdout.trim();
-                                if let Ok(total) = digits.parse::<u64>() {
-                                    let now = Instant::now();
-                                    if let (Some(prev_total), Some(prev_instant)) =
-                                        (missed_frames_total_prev, missed_frames_instant_prev)
-                                    {
-                                        let delta = total.saturating_sub(prev_total) as u128;
-                                        let dt_ms = now.duration_since(prev_instant).as_millis();
-                                        if dt_ms > 0 {
-                                            let per_sec_x100 = ((delta * 100_000u128) / dt_ms)
-                                                .min(u16::MAX as u128)
-                                                as u16;
-                                            missed_frames_per_sec_x100 = Some(per_sec_x100);
-                                        }
-                                    }
-                                    missed_frames_total_prev = Some(total);
-                                    missed_frames_instant_prev = Some(now);
-                                }
+                                packages_by_uid = parse_cmd_package_list_u(&output.stdout);
                             } else {
                                 warn!(
                                     trace_id = %trace_spawn,
                                     exit_code = ?output.exit_code,
                                     stderr = %output.stderr,
-                                    "SurfaceFlinger dumpsys returned non-zero exit code"
+                                    "cmd package list packages returned non-zero exit code"
                                 );
                             }
-                        }
-                        Some(Err(err)) => {
-                            warn!(
-                                trace_id = %trace_spawn,
-                                error = %err,
-                                "failed to collect SurfaceFlinger missed frames"
+                        }
+                        Some(Err(err)) => {
+                            warn!(
+                                trace_id = %trace_spawn,
+                                error = %err,
+                                "failed to list packages for net profiler"
+                            );
+                        }
+                        None => {
+                            warn!(trace_id = %trace_spawn, "device lock poisoned");
+                        }
+                    }
+                }
+
+                loop {
+                    if stop_flag.load(Ordering::Relaxed) {
+                        break;
+                    }
+
+                    if unsupported {
+                        std::thread::sleep(Duration::from_millis(200));
+                        continue;
+                    }
+
+                    let loop_started = Instant::now();
+
+                    let preferred = stats_source.unwrap_or(NetStatsSource::ProcXtQtaguid);
+                    let candidates = match preferred {
+                        NetStatsSource::ProcXtQtaguid => [
+                            NetStatsSource::ProcXtQtaguid,
+                            NetStatsSource::DumpsysNetstats,
+                        ],
+                        NetStatsSource::DumpsysNetstats => [
+                            NetStatsSource::DumpsysNetstats,
+                            NetStatsSource::ProcXtQtaguid,
+                        ],
+                    };
+                    let candidate_count = candidates.len();
+
+                    let mut totals: Option<HashMap<u32, (u64, u64)>> = None;
+                    let mut unsupported_sources = 0usize;
+                    let mut last_error: Option<String> = None;
+
+                    for source in candidates {
+                        if stop_flag.load(Ordering::Relaxed) {
+                            break;
+                        }
+
+                        match source {
+                            NetStatsSource::ProcXtQtaguid => {
+                                let args = vec![
+                                    "-s".to_string(),
+                                    serial_spawn.clone(),
+                                    "shell".to_string(),
+                                    "cat /proc/net/xt_qtaguid/stats".to_string(),
+                                ];
+
+                                let output = {
+                                    let _permit = scheduler.acquire_global();
+                                    let device_lock = scheduler.device_lock(&serial_spawn);
+                                    let device_guard = device_lock.lock().ok();
+                                    device_guard.map(|_guard| {
+                                        run_command_with_timeout(
+                                            &adb_program_spawn,
+                                            &args,
+                                            command_timeout(TimeoutKind::Probe, &trace_spawn),
+                                            &trace_spawn,
+                                        )
+                                    })
+                                };
+
+                                let output = match output {
+                                    Some(output) => output,
+                                    None => {
+                                        last_error = Some(
+                                            "Failed to access the device. Please try again."
+                                                .to_string(),
+                                        );
+                                        break;
+                                    }
+                                };
+
+                                let output = match output {
+                                    Ok(output) => output,
+                                    Err(err) => {
+                                        warn!(
+                                            trace_id = %trace_spawn,
+                                            error = %err,
+                                            "failed to collect net profiler output"
+                                        );
+                                        last_error = Some(
+	                                            "Failed to collect per-app network stats. Please try again."
+	                                                .to_string(),
+	                                        );
+                                        break;
+                                    }
+                                };
+
+                                if output.exit_code.unwrap_or_default() != 0 {
+                                    let stderr_lower = output.stderr.to_lowercase();
+                                    let stdout_lower = output.stdout.to_lowercase();
+                                    let is_unsupported = stderr_lower.contains("no such file")
+                                        || stderr_lower.contains("permission denied")
+                                        || stdout_lower.contains("no such file")
+                                        || stdout_lower.contains("permission denied");
+
+                                    if is_unsupported {
+                                        unsupported_sources += 1;
+                                        continue;
+                                    }
+
+                                    warn!(
+                                        trace_id = %trace_spawn,
+                                        exit_code = ?output.exit_code,
+                                        stderr = %output.stderr,
+                                        "net profiler adb shell returned non-zero exit code"
+                                    );
+                                    last_error =
+                                        Some("Failed to collect per-app network stats".to_string());
+                                    break;
+                                }
+
+                                match parse_xt_qtaguid_stats(&output.stdout) {
+                                    Ok(value) => {
+                                        totals = Some(value);
+                                        stats_source = Some(NetStatsSource::ProcXtQtaguid);
+                                        break;
+                                    }
+                                    Err(err) => {
+                                        warn!(
+                                            trace_id = %trace_spawn,
+                                            error = %err,
+                                            "failed to parse xt_qtaguid stats; falling back"
+                                        );
+                                        unsupported_sources += 1;
+                                        continue;
+                                    }
+                                }
+                            }
+                            NetStatsSource::DumpsysNetstats => {
+                                let args = vec![
+                                    "-s".to_string(),
+                                    serial_spawn.clone(),
+                                    "shell".to_string(),
+                                    "dumpsys netstats".to_string(),
+                                ];
+
+                                let output = {
+                                    let _permit = scheduler.acquire_global();
+                                    let device_lock = scheduler.device_lock(&serial_spawn);
+                                    let device_guard = device_lock.lock().ok();
+                                    device_guard.map(|_guard| {
+                                        run_command_with_timeout(
+                                            &adb_program_spawn,
+                                            &args,
+                                            command_timeout(TimeoutKind::Probe, &trace_spawn),
+                                            &trace_spawn,
+                                        )
+                                    })
+                                };
+
+                                let output = match output {
+                                    Some(output) => output,
+                                    None => {
+                                        last_error = Some(
+                                            "Failed to access the device. Please try again."
+                                                .to_string(),
+                                        );
+                                        break;
+                                    }
+                                };
+
+                                let output = match output {
+                                    Ok(output) => output,
+                                    Err(err) => {
+                                        warn!(
+                                            trace_id = %trace_spawn,
+                                            error = %err,
+                                            "failed to collect dumpsys netstats output"
+                                        );
+                                        last_error = Some(
+	                                            "Failed to collect per-app network stats. Please try again."
+	                                                .to_string(),
+	                                        );
+                                        break;
+                                    }
+                                };
+
+                                if output.exit_code.unwrap_or_default() != 0 {
+                                    let stderr_lower = output.stderr.to_lowercase();
+                                    let stdout_lower = output.stdout.to_lowercase();
+                                    let is_unsupported = stderr_lower
+                                        .contains("can't find service")
+                                        || stderr_lower.contains("not found")
+                                        || stderr_lower.contains("permission denied")
+                                        || stdout_lower.contains("can't find service")
+                                        || stdout_lower.contains("not found")
+                                        || stdout_lower.contains("permission denied");
+
+                                    if is_unsupported {
+                                        unsupported_sources += 1;
+                                        continue;
+                                    }
+
+                                    warn!(
+                                        trace_id = %trace_spawn,
+                                        exit_code = ?output.exit_code,
+                                        stderr = %output.stderr,
+                                        "dumpsys netstats returned non-zero exit code"
+                                    );
+                                    last_error =
+                                        Some("Failed to collect per-app network stats".to_string());
+                                    break;
+                                }
+
+                                match parse_dumpsys_netstats_app_uid_stats(&output.stdout) {
+                                    Ok(value) => {
+                                        totals = Some(value);
+                                        stats_source = Some(NetStatsSource::DumpsysNetstats);
+                                        break;
+                                    }
+                                    Err(err) => {
+                                        warn!(
+                                            trace_id = %trace_spawn,
+                                            error = %err,
+                                            "failed to parse dumpsys netstats uid map; falling back"
+                                        );
+                                        unsupported_sources += 1;
+                                        continue;
+                                    }
+                                }
+                            }
+                        }
+                    }
+
+                    let totals = match totals {
+                        Some(totals) => totals,
+                        None => {
+                            if unsupported_sources >= candidate_count {
+                                unsupported = true;
+                                let snapshot = NetProfilerSnapshot {
+                                    ts_ms: Utc::now().timestamp_millis(),
+                                    dt_ms: None,
+                                    rows: vec![],
+                                    unsupported: true,
+                                };
+                                emit_net_profiler_event(
+                                &app_emit,
+                                NetProfilerEvent {
+                                    serial: serial_spawn.clone(),
+                                    snapshot: Some(snapshot),
+                                    error: Some(
+                                        "Per-app network profiler is not supported on this device."
+                                            .to_string(),
+                                    ),
+                                    trace_id: trace_spawn.clone(),
+                                },
+                            );
+                                std::thread::sleep(Duration::from_millis(200));
+                                continue;
+                            }
+
+                            emit_net_profiler_event(
+                                &app_emit,
+                                NetProfilerEvent {
+                                    serial: serial_spawn.clone(),
+                                    snapshot: None,
+                                    error: Some(last_error.unwrap_or_else(|| {
+                                        "Failed to collect per-app network stats".to_string()
+                                    })),
+                                    trace_id: trace_spawn.clone(),
+                                },
                             );
+                            sleep_with_stop(interval, &stop_flag);
+                            continue;
                         }
-                        None => {
-                            warn!(trace_id = %trace_spawn, "device lock poisoned");
+                    };
+
+                    let sample_instant = Instant::now();
+                    let dt_ms_u128 = prev_instant
+                        .map(|prev| sample_instant.duration_since(prev).as_millis())
+                        .filter(|value| *value > 0);
+                    let dt_ms_u64 = dt_ms_u128.map(|value| value.min(u64::MAX as u128) as u64);
+
+                    let pinned = match pinned_uids.read() {
+                        Ok(guard) => guard.clone(),
+                        Err(_) => {
+                            warn!(trace_id = %trace_spawn, "net profiler pinned uids lock poisoned");
+                            vec![]
                         }
+                    };
+
+                    let rows = build_net_usage_rows(
+                        &totals,
+                        prev_totals.as_ref(),
+                        dt_ms_u128,
+                        &packages_by_uid,
+                        &pinned,
+                        top_n,
+                    );
+
+                    let snapshot = NetProfilerSnapshot {
+                        ts_ms: Utc::now().timestamp_millis(),
+                        dt_ms: dt_ms_u64,
+                        rows,
+                        unsupported: false,
+                    };
+                    publish_to_soak_test(
+                        &soak_feeds,
+                        &serial_spawn,
+                        SoakReading::Net(&snapshot),
+                        &trace_spawn,
+                    );
+                    emit_net_profiler_event(
+                        &app_emit,
+                        NetProfilerEvent {
+                            serial: serial_spawn.clone(),
+                            snapshot: Some(snapshot),
+                            error: None,
+                            trace_id: trace_spawn.clone(),
+                        },
+                    );
+
+                    prev_totals = Some(totals);
+                    prev_instant = Some(sample_instant);
+
+                    let elapsed = loop_started.elapsed();
+                    if elapsed < interval {
+                        sleep_with_stop(interval - elapsed, &stop_flag);
                     }
                 }
+            })
+        },
+    )?;
 
-                let snapshot = build_perf_snapshot(PerfSnapshotInput {
-                    ts_ms,
-                    cpu_prev,
-                    cpu_curr,
-                    core_percents_x100,
-                    core_freq_khz,
-                    mem,
-                    net_prev,
-                    net_curr,
-                    dt_ms,
-                    battery: battery_last,
-                    display_refresh_hz_x100,
-                    missed_frames_per_sec_x100,
-                });
+    Ok(CommandResponse {
+        trace_id,
+        data: true,
+    })
+}
 
-                cpu_prev = Some(cpu_curr);
-                net_prev = Some(net_curr);
-                net_prev_instant = Some(sample_instant);
+fn sleep_with_stop(duration: Duration, stop_flag: &Arc<AtomicBool>) {
+    let mut remaining = duration;
+    let chunk = Duration::from_millis(50);
+    while remaining > Duration::from_millis(0) {
+        if stop_flag.load(Ordering::Relaxed) {
+            break;
+        }
+        let step = if remaining > chunk { chunk } else { remaining };
+        std::thread::sleep(step);
+        remaining = remaining.saturating_sub(step);
+    }
+}
 
-                emit_perf_event(
-                    &app_emit,
-                    PerfEvent {
-                        serial: serial_spawn.clone(),
-                        snapshot: Some(snapshot),
-                        error: None,
-                        trace_id: trace_spawn.clone(),
-                    },
-                );
+fn parse_surfaceflinger_latency_ns(output: &str) -> Option<u64> {
+    output
+        .lines()
+        .next()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .and_then(|line| line.parse::<u64>().ok())
+        .filter(|value| *value > 0)
+}
 
-                let elapsed = loop_started.elapsed();
-                if elapsed < interval {
-                    sleep_with_stop(interval - elapsed, &stop_flag);
-                }
-            }
-        })
-    })?;
+fn compute_refresh_hz_x100(period_ns: u64) -> Option<u16> {
+    if period_ns == 0 {
+        return None;
+    }
+    let hz_x100 = (100_000_000_000u128 / (period_ns as u128)).min(u16::MAX as u128) as u16;
+    if hz_x100 == 0 {
+        return None;
+    }
+    Some(hz_x100)
+}
 
+// SurfaceFlinger output parsing is intentionally done via shell pipelines for missed frame totals.
+
+#[tauri::command(async)]
+pub fn stop_perf_monitor(
+    serial: String,
+    state: State<'_, AppState>,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<bool>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    stop_perf_monitor_inner(serial, &state.perf_monitors, &trace_id)?;
     Ok(CommandResponse {
         trace_id,
         data: true,
     })
 }
 
+/// Combine snapshots from running perf monitors into `perf-multi-frame` events, one per
+/// wall-clock bucket, for side-by-side comparisons.
 #[tauri::command(async)]
-pub fn start_net_profiler(
+pub fn start_perf_aggregation(
+    serials: Vec<String>,
+    bucket_ms: Option<u64>,
+    app: AppHandle,
+    state: State<'_, AppState>,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<bool>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    let mut unique: Vec<String> = Vec::new();
+    for serial in serials {
+        let serial = serial.trim().to_string();
+        if !serial.is_empty() && !unique.contains(&serial) {
+            unique.push(serial);
+        }
+    }
+    if unique.len() < 2 {
+        return Err(AppError::validation(
+            Message::keyed("perf_aggregation_devices"),
+            &trace_id,
+        ));
+    }
+
+    let bucket_ms = clamp_bucket_ms(bucket_ms);
+    let previous = {
+        let mut guard = state
+            .perf_aggregator
+            .lock()
+            .map_err(|_| AppError::system("Perf aggregator locked", &trace_id))?;
+        guard.replace(PerfFrameAggregator::new(unique.clone(), bucket_ms))
+    };
+    if let Some(mut previous) = previous {
+        emit_perf_frames(&app, previous.flush(), &trace_id);
+    }
+    info!(trace_id = %trace_id, serials = ?unique, bucket_ms, "perf aggregation started");
+
+    Ok(CommandResponse {
+        trace_id,
+        data: true,
+    })
+}
+
+#[tauri::command(async)]
+pub fn stop_perf_aggregation(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<bool>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    let previous = state
+        .perf_aggregator
+        .lock()
+        .map_err(|_| AppError::system("Perf aggregator locked", &trace_id))?
+        .take();
+    let Some(mut previous) = previous else {
+        return Err(AppError::validation(
+            Message::keyed("not_running").with("subject", "Perf aggregation"),
+            &trace_id,
+        ));
+    };
+    emit_perf_frames(&app, previous.flush(), &trace_id);
+
+    Ok(CommandResponse {
+        trace_id,
+        data: true,
+    })
+}
+
+/// Sample `dumpsys thermalservice` (falling back to thermal zone sysfs) and emit
+/// `thermal-snapshot` events, logging sensors that cross `threshold_decic`.
+#[tauri::command(async)]
+pub fn start_thermal_monitor(
     serial: String,
     interval_ms: Option<u64>,
-    top_n: Option<u32>,
-    pinned_uids: Option<Vec<u32>>,
+    threshold_decic: Option<i32>,
     app: AppHandle,
     state: State<'_, AppState>,
     trace_id: Option<String>,
@@ -5071,42 +18458,30 @@ pub fn start_net_profiler(
     ensure_non_empty(&serial, "serial", &trace_id)?;
 
     let adb_program = get_adb_program(&trace_id)?;
-    let interval_ms = clamp_net_profiler_interval_ms(interval_ms);
-    let interval = Duration::from_millis(interval_ms);
-    let top_n = clamp_net_profiler_top_n(top_n);
-    let pinned_uids = sanitize_net_profiler_pinned_uids(pinned_uids, top_n, &trace_id)?;
+    let interval = Duration::from_millis(clamp_thermal_interval_ms(interval_ms));
+    let threshold_decic = clamp_thermal_threshold_decic(threshold_decic);
+    let thermal_script = build_thermal_script();
     let scheduler = Arc::clone(&state.scheduler);
+    let soak_feeds = Arc::clone(&state.soak_feeds);
 
     let app_emit = app.clone();
     let serial_spawn = serial.clone();
     let trace_spawn = trace_id.clone();
-    let adb_program_spawn = adb_program.clone();
 
-    start_net_profiler_inner(
+    start_thermal_monitor_inner(
         serial,
-        &state.net_profilers,
+        &state.thermal_monitors,
         &trace_id,
-        pinned_uids,
-        move |stop_flag, pinned_uids| {
+        move |stop_flag| {
             std::thread::spawn(move || {
-                #[derive(Clone, Copy, Debug, PartialEq, Eq)]
-                enum NetStatsSource {
-                    ProcXtQtaguid,
-                    DumpsysNetstats,
-                }
-
-                let mut packages_by_uid: HashMap<u32, Vec<String>> = HashMap::new();
-                let mut prev_totals: Option<HashMap<u32, (u64, u64)>> = None;
-                let mut prev_instant: Option<Instant> = None;
-                let mut stats_source: Option<NetStatsSource> = None;
-                let mut unsupported = false;
-
-                {
+                let mut previous: Option<ThermalSnapshot> = None;
+                while !stop_flag.load(Ordering::Relaxed) {
+                    let loop_started = Instant::now();
                     let args = vec![
                         "-s".to_string(),
                         serial_spawn.clone(),
                         "shell".to_string(),
-                        "cmd package list packages -U".to_string(),
+                        thermal_script.clone(),
                     ];
                     let output = {
                         let _permit = scheduler.acquire_global();
@@ -5114,354 +18489,652 @@ pub fn start_net_profiler(
                         let device_guard = device_lock.lock().ok();
                         device_guard.map(|_guard| {
                             run_command_with_timeout(
-                                &adb_program_spawn,
+                                &adb_program,
                                 &args,
-                                Duration::from_secs(5),
+                                command_timeout(TimeoutKind::Probe, &trace_spawn),
                                 &trace_spawn,
                             )
                         })
                     };
 
-                    match output {
-                        Some(Ok(output)) => {
-                            if output.exit_code.unwrap_or_default() == 0 {
-                                packages_by_uid = parse_cmd_package_list_u(&output.stdout);
+                    let error = match output {
+                        Some(Ok(output)) if output.exit_code.unwrap_or_default() == 0 => {
+                            let snapshot = build_thermal_snapshot(
+                                &output.stdout,
+                                Utc::now().timestamp_millis(),
+                            );
+                            if snapshot.readings.is_empty() && snapshot.thermal_status.is_none() {
+                                Some("Device exposes no thermal data".to_string())
                             } else {
-                                warn!(
-                                    trace_id = %trace_spawn,
-                                    exit_code = ?output.exit_code,
-                                    stderr = %output.stderr,
-                                    "cmd package list packages returned non-zero exit code"
+                                let crossings = previous
+                                    .as_ref()
+                                    .map(|prev| {
+                                        detect_threshold_crossings(
+                                            &prev.readings,
+                                            &snapshot.readings,
+                                            threshold_decic,
+                                        )
+                                    })
+                                    .unwrap_or_default();
+                                for crossing in &crossings {
+                                    let summary = format!(
+                                        "{} {} {:.1} C",
+                                        crossing.name,
+                                        if crossing.rising {
+                                            "rose above"
+                                        } else {
+                                            "fell below"
+                                        },
+                                        f64::from(crossing.threshold_decic) / 10.0
+                                    );
+                                    record_timeline_event(
+                                        &serial_spawn,
+                                        TIMELINE_PERF_ALERT,
+                                        &summary,
+                                        &trace_spawn,
+                                    );
+                                    if crossing.rising {
+                                        notify_webhooks(
+                                            NOTIFY_PERF_ALERT,
+                                            &serial_spawn,
+                                            false,
+                                            &summary,
+                                            &trace_spawn,
+                                        );
+                                    }
+                                    warn!(
+                                        trace_id = %trace_spawn,
+                                        serial = %serial_spawn,
+                                        sensor = %crossing.name,
+                                        temp_decic = crossing.temp_decic,
+                                        threshold_decic,
+                                        rising = crossing.rising,
+                                        "thermal threshold crossed"
+                                    );
+                                }
+                                let previous_status =
+                                    previous.as_ref().and_then(|prev| prev.thermal_status);
+                                if previous.is_some() && previous_status != snapshot.thermal_status
+                                {
+                                    warn!(
+                                        trace_id = %trace_spawn,
+                                        serial = %serial_spawn,
+                                        from = ?previous_status,
+                                        to = ?snapshot.thermal_status,
+                                        "thermal throttle status changed"
+                                    );
+                                }
+                                publish_to_soak_test(
+                                    &soak_feeds,
+                                    &serial_spawn,
+                                    SoakReading::Thermal(&snapshot),
+                                    &trace_spawn,
+                                );
+                                emit_thermal_event(
+                                    &app_emit,
+                                    ThermalEvent {
+                                        serial: serial_spawn.clone(),
+                                        snapshot: Some(snapshot.clone()),
+                                        crossings,
+                                        error: None,
+                                        trace_id: trace_spawn.clone(),
+                                    },
                                 );
+                                previous = Some(snapshot);
+                                None
                             }
                         }
-                        Some(Err(err)) => {
+                        Some(Ok(output)) => {
                             warn!(
                                 trace_id = %trace_spawn,
-                                error = %err,
-                                "failed to list packages for net profiler"
+                                exit_code = ?output.exit_code,
+                                stderr = %output.stderr,
+                                "thermal adb shell returned non-zero exit code"
                             );
+                            Some("Failed to collect thermal data".to_string())
+                        }
+                        Some(Err(err)) => {
+                            warn!(trace_id = %trace_spawn, error = %err, "failed to collect thermal output");
+                            Some(format!("Failed to collect thermal data ({})", err.code))
                         }
                         None => {
                             warn!(trace_id = %trace_spawn, "device lock poisoned");
+                            Some("Failed to access the device. Please try again.".to_string())
                         }
-                    }
-                }
-
-                loop {
-                    if stop_flag.load(Ordering::Relaxed) {
-                        break;
+                    };
+                    if let Some(error) = error {
+                        emit_thermal_event(
+                            &app_emit,
+                            ThermalEvent {
+                                serial: serial_spawn.clone(),
+                                snapshot: None,
+                                crossings: Vec::new(),
+                                error: Some(error),
+                                trace_id: trace_spawn.clone(),
+                            },
+                        );
                     }
 
-                    if unsupported {
-                        std::thread::sleep(Duration::from_millis(200));
-                        continue;
+                    let elapsed = loop_started.elapsed();
+                    if elapsed < interval {
+                        sleep_with_stop(interval - elapsed, &stop_flag);
                     }
+                }
+            })
+        },
+    )?;
 
-                    let loop_started = Instant::now();
-
-                    let preferred = stats_source.unwrap_or(NetStatsSource::ProcXtQtaguid);
-                    let candidates = match preferred {
-                        NetStatsSource::ProcXtQtaguid => [
-                            NetStatsSource::ProcXtQtaguid,
-                            NetStatsSource::DumpsysNetstats,
-                        ],
-                        NetStatsSource::DumpsysNetstats => [
-                            NetStatsSource::DumpsysNetstats,
-                            NetStatsSource::ProcXtQtaguid,
-                        ],
-                    };
-                    let candidate_count = candidates.len();
-
-                    let mut totals: Option<HashMap<u32, (u64, u64)>> = None;
-                    let mut unsupported_sources = 0usize;
-                    let mut last_error: Option<String> = None;
-
-                    for source in candidates {
-                        if stop_flag.load(Ordering::Relaxed) {
-                            break;
-                        }
-
-                        match source {
-                            NetStatsSource::ProcXtQtaguid => {
-                                let args = vec![
-                                    "-s".to_string(),
-                                    serial_spawn.clone(),
-                                    "shell".to_string(),
-                                    "cat /proc/net/xt_qtaguid/stats".to_string(),
-                                ];
-
-                                let output = {
-                                    let _permit = scheduler.acquire_global();
-                                    let device_lock = scheduler.device_lock(&serial_spawn);
-                                    let device_guard = device_lock.lock().ok();
-                                    device_guard.map(|_guard| {
-                                        run_command_with_timeout(
-                                            &adb_program_spawn,
-                                            &args,
-                                            Duration::from_secs(5),
-                                            &trace_spawn,
-                                        )
-                                    })
-                                };
+    Ok(CommandResponse {
+        trace_id,
+        data: true,
+    })
+}
 
-                                let output = match output {
-                                    Some(output) => output,
-                                    None => {
-                                        last_error = Some(
-                                            "Failed to access the device. Please try again."
-                                                .to_string(),
-                                        );
-                                        break;
-                                    }
-                                };
+#[tauri::command(async)]
+pub fn stop_thermal_monitor(
+    serial: String,
+    state: State<'_, AppState>,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<bool>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    stop_thermal_monitor_inner(serial, &state.thermal_monitors, &trace_id)?;
+    Ok(CommandResponse {
+        trace_id,
+        data: true,
+    })
+}
 
-                                let output = match output {
-                                    Ok(output) => output,
-                                    Err(err) => {
-                                        warn!(
-                                            trace_id = %trace_spawn,
-                                            error = %err,
-                                            "failed to collect net profiler output"
-                                        );
-                                        last_error = Some(
-	                                            "Failed to collect per-app network stats. Please try again."
-	                                                .to_string(),
-	                                        );
-                                        break;
-                                    }
-                                };
+fn leak_watch_key(serial: &str, package_name: &str) -> String {
+    format!("{serial}:{package_name}")
+}
 
-                                if output.exit_code.unwrap_or_default() != 0 {
-                                    let stderr_lower = output.stderr.to_lowercase();
-                                    let stdout_lower = output.stdout.to_lowercase();
-                                    let is_unsupported = stderr_lower.contains("no such file")
-                                        || stderr_lower.contains("permission denied")
-                                        || stdout_lower.contains("no such file")
-                                        || stdout_lower.contains("permission denied");
+/// Sample a package's PSS every `interval_secs` and emit `memory-leak-suspected` when the
+/// least-squares slope over the last `window_samples` samples exceeds `slope_kb_per_min`.
+#[allow(clippy::too_many_arguments)]
+#[tauri::command(async)]
+pub fn start_memory_leak_watch(
+    serial: String,
+    package_name: String,
+    interval_secs: Option<u64>,
+    window_samples: Option<usize>,
+    slope_kb_per_min: Option<f64>,
+    app: AppHandle,
+    state: State<'_, AppState>,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<bool>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+    ensure_non_empty(&package_name, "package_name", &trace_id)?;
+    if !is_valid_package_name(&package_name) {
+        return Err(AppError::validation(
+            Message::keyed("invalid").with("subject", "package name"),
+            &trace_id,
+        ));
+    }
 
-                                    if is_unsupported {
-                                        unsupported_sources += 1;
-                                        continue;
-                                    }
+    let adb_program = get_adb_program(&trace_id)?;
+    let interval = Duration::from_secs(clamp_interval_secs(interval_secs));
+    let window = clamp_window_samples(window_samples);
+    let slope_limit = normalize_slope_kb_per_min(slope_kb_per_min);
+    let key = leak_watch_key(&serial, &package_name);
 
-                                    warn!(
-                                        trace_id = %trace_spawn,
-                                        exit_code = ?output.exit_code,
-                                        stderr = %output.stderr,
-                                        "net profiler adb shell returned non-zero exit code"
-                                    );
-                                    last_error =
-                                        Some("Failed to collect per-app network stats".to_string());
-                                    break;
-                                }
+    let mut guard = state
+        .leak_watches
+        .lock()
+        .map_err(|_| AppError::system("Leak watch registry locked", &trace_id))?;
+    if guard.contains_key(&key) {
+        return Err(AppError::validation(
+            Message::keyed("already_running").with("subject", "Memory leak watch"),
+            &trace_id,
+        ));
+    }
 
-                                match parse_xt_qtaguid_stats(&output.stdout) {
-                                    Ok(value) => {
-                                        totals = Some(value);
-                                        stats_source = Some(NetStatsSource::ProcXtQtaguid);
-                                        break;
-                                    }
-                                    Err(err) => {
-                                        warn!(
-                                            trace_id = %trace_spawn,
-                                            error = %err,
-                                            "failed to parse xt_qtaguid stats; falling back"
-                                        );
-                                        unsupported_sources += 1;
-                                        continue;
-                                    }
-                                }
+    let stop_flag = Arc::new(AtomicBool::new(false));
+    let stop_spawn = Arc::clone(&stop_flag);
+    let scheduler = Arc::clone(&state.scheduler);
+    let serial_spawn = serial.clone();
+    let package_spawn = package_name.clone();
+    let trace_spawn = trace_id.clone();
+    let join = std::thread::spawn(move || {
+        let started = Instant::now();
+        let mut trend = LeakTrend::new(window, slope_limit);
+        while !stop_spawn.load(Ordering::Relaxed) {
+            let loop_started = Instant::now();
+            let output = {
+                let _permit = scheduler.acquire_global();
+                let device_lock = scheduler.device_lock(&serial_spawn);
+                let device_guard = device_lock.lock().ok();
+                device_guard.map(|_guard| {
+                    run_device_shell(
+                        &adb_program,
+                        &serial_spawn,
+                        &["dumpsys", "meminfo", &package_spawn],
+                        command_timeout(TimeoutKind::DeviceCommand, &trace_spawn),
+                        &trace_spawn,
+                    )
+                })
+            };
+            match output {
+                Some(Ok(output)) => match parse_total_pss_kb(&output.stdout) {
+                    Some(pss_kb) => {
+                        let elapsed_secs = started.elapsed().as_secs_f64();
+                        if let Some(slope) = trend.push(elapsed_secs, pss_kb) {
+                            warn!(
+                                trace_id = %trace_spawn,
+                                serial = %serial_spawn,
+                                package_name = %package_spawn,
+                                pss_kb,
+                                slope_kb_per_min = slope,
+                                "memory leak suspected"
+                            );
+                            let summary = format!(
+                                "Memory leak suspected in {package_spawn} ({slope:.0} KB/min)"
+                            );
+                            record_timeline_event(
+                                &serial_spawn,
+                                TIMELINE_PERF_ALERT,
+                                &summary,
+                                &trace_spawn,
+                            );
+                            notify_webhooks(
+                                NOTIFY_PERF_ALERT,
+                                &serial_spawn,
+                                false,
+                                &summary,
+                                &trace_spawn,
+                            );
+                            let event = MemoryLeakEvent {
+                                serial: serial_spawn.clone(),
+                                package_name: package_spawn.clone(),
+                                pss_kb,
+                                slope_kb_per_min: slope,
+                                threshold_kb_per_min: slope_limit,
+                                window_secs: trend.window_secs(),
+                                trace_id: trace_spawn.clone(),
+                            };
+                            if let Err(err) = emit_tracked(&app, "memory-leak-suspected", event) {
+                                warn!(trace_id = %trace_spawn, error = %err, "failed to emit memory leak event");
                             }
-                            NetStatsSource::DumpsysNetstats => {
-                                let args = vec![
-                                    "-s".to_string(),
-                                    serial_spawn.clone(),
-                                    "shell".to_string(),
-                                    "dumpsys netstats".to_string(),
-                                ];
+                        }
+                    }
+                    None => {
+                        warn!(trace_id = %trace_spawn, package_name = %package_spawn, "no PSS total in meminfo; is the app running?");
+                    }
+                },
+                Some(Err(err)) => {
+                    warn!(trace_id = %trace_spawn, error = %err, "failed to sample meminfo");
+                }
+                None => {
+                    warn!(trace_id = %trace_spawn, "device lock poisoned");
+                }
+            }
 
-                                let output = {
-                                    let _permit = scheduler.acquire_global();
-                                    let device_lock = scheduler.device_lock(&serial_spawn);
-                                    let device_guard = device_lock.lock().ok();
-                                    device_guard.map(|_guard| {
-                                        run_command_with_timeout(
-                                            &adb_program_spawn,
-                                            &args,
-                                            Duration::from_secs(5),
-                                            &trace_spawn,
-                                        )
-                                    })
-                                };
+            let elapsed = loop_started.elapsed();
+            if elapsed < interval {
+                sleep_with_stop(interval - elapsed, &stop_spawn);
+            }
+        }
+    });
+    guard.insert(key, LeakWatchHandle { stop_flag, join });
+    info!(trace_id = %trace_id, serial = %serial, package_name = %package_name, window, slope_limit, "memory leak watch started");
 
-                                let output = match output {
-                                    Some(output) => output,
-                                    None => {
-                                        last_error = Some(
-                                            "Failed to access the device. Please try again."
-                                                .to_string(),
-                                        );
-                                        break;
-                                    }
-                                };
+    Ok(CommandResponse {
+        trace_id,
+        data: true,
+    })
+}
+
+#[tauri::command(async)]
+pub fn stop_memory_leak_watch(
+    serial: String,
+    package_name: String,
+    state: State<'_, AppState>,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<bool>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+    ensure_non_empty(&package_name, "package_name", &trace_id)?;
 
-                                let output = match output {
-                                    Ok(output) => output,
-                                    Err(err) => {
-                                        warn!(
-                                            trace_id = %trace_spawn,
-                                            error = %err,
-                                            "failed to collect dumpsys netstats output"
-                                        );
-                                        last_error = Some(
-	                                            "Failed to collect per-app network stats. Please try again."
-	                                                .to_string(),
-	                                        );
-                                        break;
-                                    }
-                                };
+    let handle = state
+        .leak_watches
+        .lock()
+        .map_err(|_| AppError::system("Leak watch registry locked", &trace_id))?
+        .remove(&leak_watch_key(&serial, &package_name));
+    let Some(handle) = handle else {
+        return Err(AppError::validation(
+            Message::keyed("not_running").with("subject", "Memory leak watch"),
+            &trace_id,
+        ));
+    };
+    handle.stop_flag.store(true, Ordering::Relaxed);
+    handle
+        .join
+        .join()
+        .map_err(|_| AppError::system("Leak watch thread panicked", &trace_id))?;
 
-                                if output.exit_code.unwrap_or_default() != 0 {
-                                    let stderr_lower = output.stderr.to_lowercase();
-                                    let stdout_lower = output.stdout.to_lowercase();
-                                    let is_unsupported = stderr_lower
-                                        .contains("can't find service")
-                                        || stderr_lower.contains("not found")
-                                        || stderr_lower.contains("permission denied")
-                                        || stdout_lower.contains("can't find service")
-                                        || stdout_lower.contains("not found")
-                                        || stdout_lower.contains("permission denied");
+    Ok(CommandResponse {
+        trace_id,
+        data: true,
+    })
+}
 
-                                    if is_unsupported {
-                                        unsupported_sources += 1;
-                                        continue;
-                                    }
+/// List APKs in the Gradle build outputs of `project_dir` (or the configured project), so a
+/// fresh build can be installed without browsing for the file.
+#[tauri::command(async)]
+pub fn list_build_variants(
+    project_dir: Option<String>,
+    module: Option<String>,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<Vec<BuildVariantApk>>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    let config = load_config(&trace_id)?;
+    let project_dir = project_dir
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+        .unwrap_or_else(|| config.apk_install.build_project_dir.clone());
+    if project_dir.is_empty() {
+        return Err(AppError::validation(
+            Message::keyed("build_project_missing"),
+            &trace_id,
+        ));
+    }
+    let module = module
+        .map(|value| value.trim().trim_matches(['/', '\\']).to_string())
+        .filter(|value| !value.is_empty())
+        .unwrap_or_else(|| config.apk_install.build_module.clone());
+    if module.split(['/', '\\']).any(|part| part == "..") {
+        return Err(AppError::validation(
+            Message::keyed("invalid").with("subject", "module name"),
+            &trace_id,
+        ));
+    }
+    let project_path = normalize_apk_path(&project_dir);
+    if !project_path.is_dir() {
+        return Err(AppError::validation(
+            Message::keyed("not_found_value")
+                .with("subject", "Project directory")
+                .with("value", &project_dir),
+            &trace_id,
+        ));
+    }
 
-                                    warn!(
-                                        trace_id = %trace_spawn,
-                                        exit_code = ?output.exit_code,
-                                        stderr = %output.stderr,
-                                        "dumpsys netstats returned non-zero exit code"
-                                    );
-                                    last_error =
-                                        Some("Failed to collect per-app network stats".to_string());
-                                    break;
-                                }
+    let apk_root = build_outputs_apk_dir(&project_path, &module);
+    if !apk_root.is_dir() {
+        info!(trace_id = %trace_id, path = %apk_root.display(), "no build outputs yet");
+        return Ok(CommandResponse {
+            trace_id,
+            data: Vec::new(),
+        });
+    }
+    let mut variants = scan_build_outputs(&apk_root);
+    for variant in &mut variants {
+        variant.apk_info = Some(get_apk_info(&variant.path));
+    }
+    info!(trace_id = %trace_id, path = %apk_root.display(), count = variants.len(), "listed build variants");
 
-                                match parse_dumpsys_netstats_app_uid_stats(&output.stdout) {
-                                    Ok(value) => {
-                                        totals = Some(value);
-                                        stats_source = Some(NetStatsSource::DumpsysNetstats);
-                                        break;
-                                    }
-                                    Err(err) => {
-                                        warn!(
-                                            trace_id = %trace_spawn,
-                                            error = %err,
-                                            "failed to parse dumpsys netstats uid map; falling back"
-                                        );
-                                        unsupported_sources += 1;
-                                        continue;
-                                    }
-                                }
-                            }
-                        }
-                    }
+    Ok(CommandResponse {
+        trace_id,
+        data: variants,
+    })
+}
 
-                    let totals = match totals {
-                        Some(totals) => totals,
-                        None => {
-                            if unsupported_sources >= candidate_count {
-                                unsupported = true;
-                                let snapshot = NetProfilerSnapshot {
-                                    ts_ms: Utc::now().timestamp_millis(),
-                                    dt_ms: None,
-                                    rows: vec![],
-                                    unsupported: true,
-                                };
-                                emit_net_profiler_event(
-                                &app_emit,
-                                NetProfilerEvent {
-                                    serial: serial_spawn.clone(),
-                                    snapshot: Some(snapshot),
-                                    error: Some(
-                                        "Per-app network profiler is not supported on this device."
-                                            .to_string(),
-                                    ),
-                                    trace_id: trace_spawn.clone(),
-                                },
-                            );
-                                std::thread::sleep(Duration::from_millis(200));
-                                continue;
-                            }
+fn emit_apk_watch_event(app: &AppHandle, event: ApkWatchEvent) {
+    let trace_id = event.trace_id.clone();
+    if let Err(err) = emit_tracked(app, "apk-watch", event) {
+        warn!(trace_id = %trace_id, error = %err, "failed to emit apk watch event");
+    }
+}
 
-                            emit_net_profiler_event(
-                                &app_emit,
-                                NetProfilerEvent {
-                                    serial: serial_spawn.clone(),
-                                    snapshot: None,
-                                    error: Some(last_error.unwrap_or_else(|| {
-                                        "Failed to collect per-app network stats".to_string()
-                                    })),
-                                    trace_id: trace_spawn.clone(),
-                                },
-                            );
-                            sleep_with_stop(interval, &stop_flag);
-                            continue;
-                        }
-                    };
+fn relaunch_after_install(
+    scheduler: &TaskScheduler,
+    adb_program: &str,
+    serial: &str,
+    package_name: &str,
+    trace_id: &str,
+) -> Result<(), AppError> {
+    let _permit = scheduler.acquire_global();
+    let device_lock = scheduler.device_lock(serial);
+    let _device_guard = device_lock.lock().map_err(|_| {
+        warn!(trace_id = %trace_id, serial = %serial, "device lock poisoned");
+        AppError::system("Failed to access the device. Please try again.", trace_id)
+    })?;
+    let output = run_device_shell(
+        adb_program,
+        serial,
+        &[
+            "monkey",
+            "-p",
+            package_name,
+            "-c",
+            "android.intent.category.LAUNCHER",
+            "1",
+        ],
+        command_timeout(TimeoutKind::DeviceCommand, trace_id),
+        trace_id,
+    )?;
+    if output.exit_code != Some(0) {
+        return Err(AppError::dependency(
+            Message::keyed("action_failed")
+                .with("action", "Launch")
+                .with("detail", output.stderr.trim()),
+            trace_id,
+        ));
+    }
+    Ok(())
+}
 
-                    let sample_instant = Instant::now();
-                    let dt_ms_u128 = prev_instant
-                        .map(|prev| sample_instant.duration_since(prev).as_millis())
-                        .filter(|value| *value > 0);
-                    let dt_ms_u64 = dt_ms_u128.map(|value| value.min(u64::MAX as u128) as u64);
+fn run_apk_watch_cycle(
+    app: &AppHandle,
+    apk_path: &Path,
+    serials: &[String],
+    options: &ApkWatchOptions,
+    cycle: u32,
+    trace_id: &str,
+) {
+    let path = apk_path.to_string_lossy().to_string();
+    let event = |phase: &str,
+                 result: Option<ApkBatchInstallResult>,
+                 relaunched: Vec<String>,
+                 error: Option<String>| ApkWatchEvent {
+        path: path.clone(),
+        cycle,
+        phase: phase.to_string(),
+        result,
+        relaunched,
+        error,
+        trace_id: trace_id.to_string(),
+    };
+    info!(trace_id = %trace_id, path = %path, cycle, "apk change detected; reinstalling");
+    emit_apk_watch_event(app, event("detected", None, Vec::new(), None));
+
+    let state = app.state::<AppState>();
+    let result = match install_apk_batch_inner(
+        serials.to_vec(),
+        path.clone(),
+        options.replace,
+        options.allow_downgrade,
+        options.grant,
+        options.allow_test_packages,
+        options.extra_args.clone(),
+        state.inner(),
+        trace_id,
+        Some(app.clone()),
+    ) {
+        Ok(result) => result,
+        Err(err) => {
+            warn!(trace_id = %trace_id, path = %path, cycle, error = %err.error, "watch-mode install failed");
+            emit_apk_watch_event(app, event("failed", None, Vec::new(), Some(err.error)));
+            return;
+        }
+    };
 
-                    let pinned = match pinned_uids.read() {
-                        Ok(guard) => guard.clone(),
-                        Err(_) => {
-                            warn!(trace_id = %trace_spawn, "net profiler pinned uids lock poisoned");
-                            vec![]
+    let mut relaunched = Vec::new();
+    let package = result
+        .apk_info
+        .as_ref()
+        .and_then(|info| info.package_name.clone());
+    if let (true, Some(package)) = (options.relaunch, package) {
+        match get_adb_program(trace_id) {
+            Ok(adb_program) => {
+                let mut installed: Vec<&String> = result
+                    .results
+                    .values()
+                    .filter(|item| item.success)
+                    .map(|item| &item.serial)
+                    .collect();
+                installed.sort();
+                for serial in installed {
+                    match relaunch_after_install(
+                        &state.scheduler,
+                        &adb_program,
+                        serial,
+                        &package,
+                        trace_id,
+                    ) {
+                        Ok(()) => relaunched.push(serial.clone()),
+                        Err(err) => {
+                            warn!(trace_id = %trace_id, serial = %serial, error = %err.error, "failed to relaunch app after install");
                         }
-                    };
+                    }
+                }
+            }
+            Err(err) => {
+                warn!(trace_id = %trace_id, error = %err.error, "adb unavailable; skipping relaunch");
+            }
+        }
+    }
 
-                    let rows = build_net_usage_rows(
-                        &totals,
-                        prev_totals.as_ref(),
-                        dt_ms_u128,
-                        &packages_by_uid,
-                        &pinned,
-                        top_n,
-                    );
+    let phase = if result.results.values().all(|item| item.success) {
+        "installed"
+    } else {
+        "failed"
+    };
+    emit_apk_watch_event(app, event(phase, Some(result), relaunched, None));
+}
 
-                    let snapshot = NetProfilerSnapshot {
-                        ts_ms: Utc::now().timestamp_millis(),
-                        dt_ms: dt_ms_u64,
-                        rows,
-                        unsupported: false,
-                    };
-                    emit_net_profiler_event(
-                        &app_emit,
-                        NetProfilerEvent {
-                            serial: serial_spawn.clone(),
-                            snapshot: Some(snapshot),
-                            error: None,
-                            trace_id: trace_spawn.clone(),
-                        },
-                    );
+/// Watch an APK and reinstall it on `serials` each time the build rewrites it.
+#[tauri::command(async)]
+pub fn watch_apk(
+    path: String,
+    serials: Vec<String>,
+    install_options: Option<ApkWatchOptions>,
+    app: AppHandle,
+    state: State<'_, AppState>,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<bool>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&path, "path", &trace_id)?;
+    if serials.is_empty() {
+        return Err(AppError::validation(
+            Message::keyed("required").with("field", "serials"),
+            &trace_id,
+        ));
+    }
+    for serial in &serials {
+        ensure_non_empty(serial, "serial", &trace_id)?;
+    }
+    let options = install_options.unwrap_or_default();
+    let apk_path = normalize_apk_path(path.trim());
+    let Some(parent) = apk_path
+        .parent()
+        .filter(|parent| parent.is_dir())
+        .map(Path::to_path_buf)
+    else {
+        return Err(AppError::validation(
+            Message::keyed("does_not_exist").with("subject", "APK directory"),
+            &trace_id,
+        ));
+    };
+    let key = apk_path.to_string_lossy().to_string();
 
-                    prev_totals = Some(totals);
-                    prev_instant = Some(sample_instant);
+    let mut guard = state
+        .apk_watchers
+        .lock()
+        .map_err(|_| AppError::system("APK watch registry locked", &trace_id))?;
+    if guard.contains_key(&key) {
+        return Err(AppError::validation(
+            Message::keyed("already_running").with("subject", "APK watch"),
+            &trace_id,
+        ));
+    }
+
+    let (sender, receiver) = std::sync::mpsc::channel();
+    let mut watcher = notify::recommended_watcher(sender).map_err(|err| {
+        AppError::system(
+            Message::keyed("start_failed")
+                .with("subject", "file watcher")
+                .with("detail", &err),
+            &trace_id,
+        )
+    })?;
+    watcher
+        .watch(&parent, RecursiveMode::NonRecursive)
+        .map_err(|err| {
+            AppError::system(
+                Message::keyed("operation_failed")
+                    .with("action", "watch APK directory")
+                    .with("detail", &err),
+                &trace_id,
+            )
+        })?;
 
-                    let elapsed = loop_started.elapsed();
-                    if elapsed < interval {
-                        sleep_with_stop(interval - elapsed, &stop_flag);
+    let stop_flag = Arc::new(AtomicBool::new(false));
+    let stop_spawn = Arc::clone(&stop_flag);
+    let trace_spawn = trace_id.clone();
+    let apk_spawn = apk_path.clone();
+    let join = std::thread::spawn(move || {
+        // Dropping the watcher ends the event stream, so it lives as long as the thread.
+        let _watcher = watcher;
+        let mut last_installed = file_stamp(&apk_spawn);
+        let mut pending: Option<Instant> = None;
+        let mut cycle = 0u32;
+        while !stop_spawn.load(Ordering::Relaxed) {
+            match receiver.recv_timeout(APK_WATCH_POLL) {
+                Ok(Ok(event)) => {
+                    if event_touches_apk(&event, &apk_spawn) {
+                        pending = Some(Instant::now());
                     }
                 }
-            })
-        },
-    )?;
+                Ok(Err(err)) => {
+                    warn!(trace_id = %trace_spawn, error = %err, "apk file watcher error");
+                }
+                Err(std::sync::mpsc::RecvTimeoutError::Timeout) => {}
+                Err(std::sync::mpsc::RecvTimeoutError::Disconnected) => {
+                    warn!(trace_id = %trace_spawn, "apk file watcher disconnected");
+                    break;
+                }
+            }
+            let Some(changed_at) = pending else {
+                continue;
+            };
+            let quiet_for = changed_at.elapsed();
+            if quiet_for < APK_WATCH_DEBOUNCE {
+                continue;
+            }
+            pending = None;
+            let current = file_stamp(&apk_spawn);
+            if !should_install(quiet_for, current, last_installed) {
+                continue;
+            }
+            last_installed = current;
+            cycle += 1;
+            run_apk_watch_cycle(&app, &apk_spawn, &serials, &options, cycle, &trace_spawn);
+        }
+        emit_apk_watch_event(
+            &app,
+            ApkWatchEvent {
+                path: apk_spawn.to_string_lossy().to_string(),
+                cycle,
+                phase: "stopped".to_string(),
+                result: None,
+                relaunched: Vec::new(),
+                error: None,
+                trace_id: trace_spawn.clone(),
+            },
+        );
+    });
+    guard.insert(key.clone(), ApkWatchHandle { stop_flag, join });
+    info!(trace_id = %trace_id, path = %key, "apk watch started");
 
     Ok(CommandResponse {
         trace_id,
@@ -5469,50 +19142,64 @@ pub fn start_net_profiler(
     })
 }
 
-fn sleep_with_stop(duration: Duration, stop_flag: &Arc<AtomicBool>) {
-    let mut remaining = duration;
-    let chunk = Duration::from_millis(50);
-    while remaining > Duration::from_millis(0) {
-        if stop_flag.load(Ordering::Relaxed) {
-            break;
-        }
-        let step = if remaining > chunk { chunk } else { remaining };
-        std::thread::sleep(step);
-        remaining = remaining.saturating_sub(step);
-    }
-}
+#[tauri::command(async)]
+pub fn stop_watch_apk(
+    path: String,
+    state: State<'_, AppState>,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<bool>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&path, "path", &trace_id)?;
+    let key = normalize_apk_path(path.trim())
+        .to_string_lossy()
+        .to_string();
 
-fn parse_surfaceflinger_latency_ns(output: &str) -> Option<u64> {
-    output
-        .lines()
-        .next()
-        .map(str::trim)
-        .filter(|line| !line.is_empty())
-        .and_then(|line| line.parse::<u64>().ok())
-        .filter(|value| *value > 0)
-}
+    let handle = state
+        .apk_watchers
+        .lock()
+        .map_err(|_| AppError::system("APK watch registry locked", &trace_id))?
+        .remove(&key);
+    let Some(handle) = handle else {
+        return Err(AppError::validation(
+            Message::keyed("not_running").with("subject", "APK watch"),
+            &trace_id,
+        ));
+    };
+    handle.stop_flag.store(true, Ordering::Relaxed);
+    handle
+        .join
+        .join()
+        .map_err(|_| AppError::system("APK watch thread panicked", &trace_id))?;
 
-fn compute_refresh_hz_x100(period_ns: u64) -> Option<u16> {
-    if period_ns == 0 {
-        return None;
-    }
-    let hz_x100 = (100_000_000_000u128 / (period_ns as u128)).min(u16::MAX as u128) as u16;
-    if hz_x100 == 0 {
-        return None;
-    }
-    Some(hz_x100)
+    Ok(CommandResponse {
+        trace_id,
+        data: true,
+    })
 }
 
-// SurfaceFlinger output parsing is intentionally done via shell pipelines for missed frame totals.
+#[tauri::command(async)]
+pub fn stop_net_profiler(
+    serial: String,
+    state: State<'_, AppState>,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<bool>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    stop_net_profiler_inner(serial, &state.net_profilers, &trace_id)?;
+    Ok(CommandResponse {
+        trace_id,
+        data: true,
+    })
+}
 
 #[tauri::command(async)]
-pub fn stop_perf_monitor(
+pub fn set_net_profiler_pinned_uids(
     serial: String,
+    pinned_uids: Option<Vec<u32>>,
     state: State<'_, AppState>,
     trace_id: Option<String>,
 ) -> Result<CommandResponse<bool>, AppError> {
     let trace_id = resolve_trace_id(trace_id);
-    stop_perf_monitor_inner(serial, &state.perf_monitors, &trace_id)?;
+    set_net_profiler_pinned_uids_inner(serial, pinned_uids, &state.net_profilers, &trace_id)?;
     Ok(CommandResponse {
         trace_id,
         data: true,
@@ -5520,13 +19207,32 @@ pub fn stop_perf_monitor(
 }
 
 #[tauri::command(async)]
-pub fn stop_net_profiler(
+pub fn start_logcat(
     serial: String,
+    filter: Option<String>,
+    app: AppHandle,
     state: State<'_, AppState>,
     trace_id: Option<String>,
 ) -> Result<CommandResponse<bool>, AppError> {
     let trace_id = resolve_trace_id(trace_id);
-    stop_net_profiler_inner(serial, &state.net_profilers, &trace_id)?;
+    let adb_program = get_adb_program(&trace_id)?;
+    let trace_emit = trace_id.clone();
+    let emitter: LogcatEmitter = Arc::new(move |event: LogcatEvent| {
+        if let Err(err) = emit_tracked(&app, "logcat-line", event) {
+            warn!(trace_id = %trace_emit, error = %err, "failed to emit logcat line");
+        }
+    });
+
+    start_logcat_inner(
+        serial,
+        filter,
+        &adb_program,
+        &state.logcat_processes,
+        emitter,
+        &trace_id,
+        spawn_logcat_source,
+    )?;
+
     Ok(CommandResponse {
         trace_id,
         data: true,
@@ -5534,58 +19240,255 @@ pub fn stop_net_profiler(
 }
 
 #[tauri::command(async)]
-pub fn set_net_profiler_pinned_uids(
+pub fn stop_logcat(
     serial: String,
-    pinned_uids: Option<Vec<u32>>,
     state: State<'_, AppState>,
     trace_id: Option<String>,
 ) -> Result<CommandResponse<bool>, AppError> {
     let trace_id = resolve_trace_id(trace_id);
-    set_net_profiler_pinned_uids_inner(serial, pinned_uids, &state.net_profilers, &trace_id)?;
+    stop_logcat_inner(serial, &state.logcat_processes, &trace_id)?;
+
+    Ok(CommandResponse {
+        trace_id,
+        data: true,
+    })
+}
+
+#[tauri::command(async)]
+pub fn clear_logcat(
+    serial: String,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<bool>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+
+    let adb_program = get_adb_program(&trace_id)?;
+    let args = vec![
+        "-s".to_string(),
+        serial.clone(),
+        "logcat".to_string(),
+        "-b".to_string(),
+        "all".to_string(),
+        "-c".to_string(),
+    ];
+    let output = run_adb(&adb_program, &args, &trace_id)?;
+    if output.exit_code.unwrap_or_default() != 0 {
+        return Err(AppError::dependency(
+            Message::keyed("action_failed")
+                .with("action", "Logcat clear")
+                .with("detail", &output.stderr),
+            &trace_id,
+        ));
+    }
+
     Ok(CommandResponse {
         trace_id,
         data: true,
     })
 }
 
+fn forward_dmesg_lines(
+    reader: impl std::io::Read + Send + 'static,
+    is_stderr: bool,
+    serial: String,
+    stop_flag: Arc<AtomicBool>,
+    emitter: Arc<dyn Fn(DmesgEvent) + Send + Sync>,
+    trace_id: String,
+) {
+    std::thread::spawn(move || {
+        let batch_limit = 50usize;
+        let batch_delay = Duration::from_millis(60);
+        let mut pending: Vec<DmesgLine> = Vec::new();
+        let mut last_emit = Instant::now();
+        for line_result in BufReader::new(reader).lines() {
+            if stop_flag.load(Ordering::Relaxed) {
+                break;
+            }
+            let line = match line_result {
+                Ok(line) => line,
+                Err(err) => {
+                    warn!(trace_id = %trace_id, error = %err, "failed to read dmesg output");
+                    break;
+                }
+            };
+            pending.push(if is_stderr {
+                parse_dmesg_line(&format!("STDERR: {line}"))
+            } else {
+                parse_dmesg_line(&line)
+            });
+            if pending.len() >= batch_limit || last_emit.elapsed() >= batch_delay {
+                (emitter)(DmesgEvent {
+                    serial: serial.clone(),
+                    lines: std::mem::take(&mut pending),
+                    trace_id: trace_id.clone(),
+                });
+                last_emit = Instant::now();
+            }
+        }
+        if !pending.is_empty() {
+            (emitter)(DmesgEvent {
+                serial,
+                lines: pending,
+                trace_id,
+            });
+        }
+    });
+}
+
+/// Follow the kernel log with `dmesg -w`, falling back to `su -c` on rooted builds where the
+/// shell user cannot read it. Lines arrive in batches on `dmesg-line`.
+#[tauri::command(async)]
+pub fn start_dmesg_stream(
+    serial: String,
+    app: AppHandle,
+    state: State<'_, AppState>,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<DmesgStreamInfo>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+    let running = state
+        .dmesg_streams
+        .lock()
+        .map_err(|_| AppError::system("Dmesg registry locked", &trace_id))?
+        .contains_key(&serial);
+    if running {
+        return Err(AppError::validation(
+            Message::keyed("already_running").with("subject", "Dmesg stream"),
+            &trace_id,
+        ));
+    }
+    let adb_program = get_adb_program(&trace_id)?;
+
+    let shell_args = |extra: Vec<String>| {
+        let mut args = vec!["-s".to_string(), serial.clone(), "shell".to_string()];
+        args.extend(extra);
+        args
+    };
+    let mode = [DMESG_MODE_DIRECT, DMESG_MODE_SU]
+        .into_iter()
+        .find(|mode| {
+            match run_command_with_timeout(
+                &adb_program,
+                &shell_args(dmesg_probe_args(mode)),
+                command_timeout(TimeoutKind::DeviceCommand, &trace_id),
+                &trace_id,
+            ) {
+                Ok(output) => dmesg_probe_succeeded(&output),
+                Err(err) => {
+                    warn!(trace_id = %trace_id, serial = %serial, mode = %mode, error = %err.error, "dmesg probe failed");
+                    false
+                }
+            }
+        })
+        .ok_or_else(|| {
+            AppError::dependency(
+                Message::keyed("dmesg_needs_root"),
+                &trace_id,
+            )
+        })?;
+
+    let mut child = spawn_logged(
+        adb_command_for_serial(&adb_program, &serial)
+            .args(shell_args(dmesg_stream_args(mode)))
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped()),
+        &trace_id,
+    )
+    .map_err(|err| {
+        AppError::dependency(
+            Message::keyed("start_failed")
+                .with("subject", "dmesg")
+                .with("detail", &err),
+            &trace_id,
+        )
+    })?;
+    let stdout = child
+        .stdout
+        .take()
+        .ok_or_else(|| AppError::system("Failed to capture dmesg stdout", &trace_id))?;
+    let stderr = child
+        .stderr
+        .take()
+        .ok_or_else(|| AppError::system("Failed to capture dmesg stderr", &trace_id))?;
+
+    let mut guard = state
+        .dmesg_streams
+        .lock()
+        .map_err(|_| AppError::system("Dmesg registry locked", &trace_id))?;
+    if guard.contains_key(&serial) {
+        let _ = child.kill();
+        let _ = child.wait();
+        return Err(AppError::validation(
+            Message::keyed("already_running").with("subject", "Dmesg stream"),
+            &trace_id,
+        ));
+    }
+
+    let stop_flag = Arc::new(AtomicBool::new(false));
+    let trace_emit = trace_id.clone();
+    let emitter: Arc<dyn Fn(DmesgEvent) + Send + Sync> = Arc::new(move |event: DmesgEvent| {
+        if let Err(err) = emit_tracked(&app, DMESG_EVENT_NAME, event) {
+            warn!(trace_id = %trace_emit, error = %err, "failed to emit dmesg lines");
+        }
+    });
+    forward_dmesg_lines(
+        stdout,
+        false,
+        serial.clone(),
+        Arc::clone(&stop_flag),
+        Arc::clone(&emitter),
+        trace_id.clone(),
+    );
+    forward_dmesg_lines(
+        stderr,
+        true,
+        serial.clone(),
+        Arc::clone(&stop_flag),
+        emitter,
+        trace_id.clone(),
+    );
+    guard.insert(
+        serial.clone(),
+        DmesgHandle {
+            child,
+            stop_flag,
+            mode: mode.to_string(),
+        },
+    );
+    info!(trace_id = %trace_id, serial = %serial, mode = %mode, "dmesg stream started");
+
+    Ok(CommandResponse {
+        trace_id,
+        data: DmesgStreamInfo {
+            serial,
+            mode: mode.to_string(),
+        },
+    })
+}
+
 #[tauri::command(async)]
-pub fn start_logcat(
+pub fn stop_dmesg_stream(
     serial: String,
-    filter: Option<String>,
-    app: AppHandle,
     state: State<'_, AppState>,
     trace_id: Option<String>,
 ) -> Result<CommandResponse<bool>, AppError> {
     let trace_id = resolve_trace_id(trace_id);
-    let adb_program = get_adb_program(&trace_id)?;
-    let trace_emit = trace_id.clone();
-    let emitter: LogcatEmitter = Arc::new(move |event: LogcatEvent| {
-        if let Err(err) = app.emit("logcat-line", event) {
-            warn!(trace_id = %trace_emit, error = %err, "failed to emit logcat line");
-        }
-    });
-
-    start_logcat_inner(
-        serial,
-        filter,
-        &adb_program,
-        &state.logcat_processes,
-        emitter,
-        &trace_id,
-        |program, serial, filter, trace_id| {
-            let mut cmd = Command::new(program);
-            cmd.args(["-s", serial, "logcat"]);
-            if let Some(filter) = filter {
-                cmd.args(filter.split_whitespace());
-            }
-            cmd.stdout(Stdio::piped())
-                .stderr(Stdio::piped())
-                .spawn()
-                .map_err(|err| {
-                    AppError::dependency(format!("Failed to start logcat: {err}"), trace_id)
-                })
-        },
-    )?;
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+    let mut handle = state
+        .dmesg_streams
+        .lock()
+        .map_err(|_| AppError::system("Dmesg registry locked", &trace_id))?
+        .remove(&serial)
+        .ok_or_else(|| {
+            AppError::validation(
+                Message::keyed("not_running").with("subject", "Dmesg stream"),
+                &trace_id,
+            )
+        })?;
+    handle.stop_flag.store(true, Ordering::Relaxed);
+    let _ = handle.child.kill();
+    let _ = handle.child.wait();
 
     Ok(CommandResponse {
         trace_id,
@@ -5593,44 +19496,210 @@ pub fn start_logcat(
     })
 }
 
+fn resolve_process_package(
+    adb_program: &str,
+    serial: &str,
+    pid: u32,
+    trace_id: &str,
+) -> Option<String> {
+    let args = vec![
+        "-s".to_string(),
+        serial.to_string(),
+        "shell".to_string(),
+        "cat".to_string(),
+        format!("/proc/{pid}/cmdline"),
+    ];
+    match run_command_with_timeout(
+        adb_program,
+        &args,
+        command_timeout(TimeoutKind::Probe, trace_id),
+        trace_id,
+    ) {
+        Ok(output) => package_from_cmdline(&output.stdout),
+        Err(err) => {
+            warn!(trace_id = %trace_id, serial = %serial, pid, error = %err.error, "failed to resolve lifecycle process");
+            None
+        }
+    }
+}
+
+/// Follow activity lifecycle callbacks (and, with `include_fragments`, AndroidX fragment state
+/// moves) as `activity-lifecycle` events. Resumed activities are also added to the device
+/// timeline so screen changes line up with crashes, installs and perf alerts.
 #[tauri::command(async)]
-pub fn stop_logcat(
+pub fn start_activity_lifecycle_stream(
     serial: String,
+    include_fragments: Option<bool>,
+    app: AppHandle,
     state: State<'_, AppState>,
     trace_id: Option<String>,
-) -> Result<CommandResponse<bool>, AppError> {
+) -> Result<CommandResponse<LifecycleStreamInfo>, AppError> {
     let trace_id = resolve_trace_id(trace_id);
-    stop_logcat_inner(serial, &state.logcat_processes, &trace_id)?;
+    ensure_non_empty(&serial, "serial", &trace_id)?;
+    let include_fragments = include_fragments.unwrap_or(false);
+    let running = state
+        .lifecycle_streams
+        .lock()
+        .map_err(|_| AppError::system("Lifecycle registry locked", &trace_id))?
+        .contains_key(&serial);
+    if running {
+        return Err(AppError::validation(
+            Message::keyed("already_running").with("subject", "Lifecycle stream"),
+            &trace_id,
+        ));
+    }
+    let adb_program = get_adb_program(&trace_id)?;
+    if include_fragments {
+        run_device_shell(
+            &adb_program,
+            &serial,
+            &["setprop", FRAGMENT_LOG_PROPERTY, "DEBUG"],
+            command_timeout(TimeoutKind::DeviceCommand, &trace_id),
+            &trace_id,
+        )?;
+    }
+
+    let mut child = spawn_logged(
+        adb_command_for_serial(&adb_program, &serial)
+            .args(["-s", serial.as_str(), "shell"])
+            .args(lifecycle_logcat_args(include_fragments))
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped()),
+        &trace_id,
+    )
+    .map_err(|err| {
+        AppError::dependency(
+            Message::keyed("start_failed")
+                .with("subject", "lifecycle stream")
+                .with("detail", &err),
+            &trace_id,
+        )
+    })?;
+    let stdout = child
+        .stdout
+        .take()
+        .ok_or_else(|| AppError::system("Failed to capture lifecycle stdout", &trace_id))?;
+    let stderr = child
+        .stderr
+        .take()
+        .ok_or_else(|| AppError::system("Failed to capture lifecycle stderr", &trace_id))?;
+
+    let mut guard = state
+        .lifecycle_streams
+        .lock()
+        .map_err(|_| AppError::system("Lifecycle registry locked", &trace_id))?;
+    if guard.contains_key(&serial) {
+        let _ = child.kill();
+        let _ = child.wait();
+        return Err(AppError::validation(
+            Message::keyed("already_running").with("subject", "Lifecycle stream"),
+            &trace_id,
+        ));
+    }
+
+    let stop_flag = Arc::new(AtomicBool::new(false));
+    let stop_reader = Arc::clone(&stop_flag);
+    let (serial_reader, trace_reader) = (serial.clone(), trace_id.clone());
+    std::thread::spawn(move || {
+        let parser = LifecycleLineParser::new();
+        let mut packages: HashMap<u32, Option<String>> = HashMap::new();
+        for line in BufReader::new(stdout).lines() {
+            if stop_reader.load(Ordering::Relaxed) {
+                break;
+            }
+            let line = match line {
+                Ok(line) => line,
+                Err(err) => {
+                    warn!(trace_id = %trace_reader, error = %err, "failed to read lifecycle output");
+                    break;
+                }
+            };
+            let Some(mut event) = parser.parse(&serial_reader, &line, &trace_reader) else {
+                continue;
+            };
+            if let Some(pid) = event.pid {
+                event.package = packages
+                    .entry(pid)
+                    .or_insert_with(|| {
+                        resolve_process_package(&adb_program, &serial_reader, pid, &trace_reader)
+                    })
+                    .clone();
+            }
+            if event.kind == LIFECYCLE_KIND_ACTIVITY && event.state == "resumed" {
+                let summary = match &event.package {
+                    Some(package) => format!("Resumed {} ({package})", event.component),
+                    None => format!("Resumed {}", event.component),
+                };
+                record_timeline_event(&serial_reader, TIMELINE_SCREEN, &summary, &trace_reader);
+            }
+            if let Err(err) = emit_tracked(&app, LIFECYCLE_EVENT_NAME, event) {
+                warn!(trace_id = %trace_reader, error = %err, "failed to emit lifecycle event");
+            }
+        }
+    });
+    let trace_stderr = trace_id.clone();
+    std::thread::spawn(move || {
+        for line in BufReader::new(stderr).lines().map_while(Result::ok) {
+            if !line.trim().is_empty() {
+                warn!(trace_id = %trace_stderr, stderr = %line.trim(), "lifecycle logcat reported an error");
+            }
+        }
+    });
+    guard.insert(
+        serial.clone(),
+        LifecycleStreamHandle {
+            child,
+            stop_flag,
+            include_fragments,
+        },
+    );
+    info!(trace_id = %trace_id, serial = %serial, include_fragments, "lifecycle stream started");
 
     Ok(CommandResponse {
         trace_id,
-        data: true,
+        data: LifecycleStreamInfo {
+            serial,
+            include_fragments,
+        },
     })
 }
 
 #[tauri::command(async)]
-pub fn clear_logcat(
+pub fn stop_activity_lifecycle_stream(
     serial: String,
+    state: State<'_, AppState>,
     trace_id: Option<String>,
 ) -> Result<CommandResponse<bool>, AppError> {
     let trace_id = resolve_trace_id(trace_id);
     ensure_non_empty(&serial, "serial", &trace_id)?;
-
-    let adb_program = get_adb_program(&trace_id)?;
-    let args = vec![
-        "-s".to_string(),
-        serial.clone(),
-        "logcat".to_string(),
-        "-b".to_string(),
-        "all".to_string(),
-        "-c".to_string(),
-    ];
-    let output = run_adb(&adb_program, &args, &trace_id)?;
-    if output.exit_code.unwrap_or_default() != 0 {
-        return Err(AppError::dependency(
-            format!("Logcat clear failed: {}", output.stderr),
-            &trace_id,
-        ));
+    let mut handle = state
+        .lifecycle_streams
+        .lock()
+        .map_err(|_| AppError::system("Lifecycle registry locked", &trace_id))?
+        .remove(&serial)
+        .ok_or_else(|| {
+            AppError::validation(
+                Message::keyed("not_running").with("subject", "Lifecycle stream"),
+                &trace_id,
+            )
+        })?;
+    handle.stop_flag.store(true, Ordering::Relaxed);
+    let _ = handle.child.kill();
+    let _ = handle.child.wait();
+    if handle.include_fragments {
+        let reset = get_adb_program(&trace_id).and_then(|adb_program| {
+            run_device_shell(
+                &adb_program,
+                &serial,
+                &["setprop", FRAGMENT_LOG_PROPERTY, "''"],
+                command_timeout(TimeoutKind::DeviceCommand, &trace_id),
+                &trace_id,
+            )
+        });
+        if let Err(err) = reset {
+            warn!(trace_id = %trace_id, serial = %serial, error = %err.error, "failed to reset fragment logging");
+        }
     }
 
     Ok(CommandResponse {
@@ -5650,27 +19719,19 @@ pub fn export_logcat(
     ensure_non_empty(&serial, "serial", &trace_id)?;
 
     let config = load_config(&trace_id)?;
-    let resolved_dir = output_dir
-        .filter(|value| !value.trim().is_empty())
-        .unwrap_or_else(|| {
-            if !config.output_path.trim().is_empty() {
-                config.output_path.clone()
-            } else {
-                config.file_gen_output_path.clone()
-            }
-        });
-    ensure_non_empty(&resolved_dir, "output_dir", &trace_id)?;
-
-    fs::create_dir_all(&resolved_dir).map_err(|err| {
-        AppError::system(format!("Failed to create output dir: {err}"), &trace_id)
-    })?;
+    let resolved_dir =
+        prepare_artifact_dir(&config, ArtifactKind::Log, output_dir.as_deref(), &trace_id)?;
 
     let timestamp = Utc::now().format("%Y%m%d_%H%M%S");
-    let output_path =
-        PathBuf::from(&resolved_dir).join(format!("logcat_{}_{}.txt", serial, timestamp));
+    let output_path = resolved_dir.join(format!("logcat_{}_{}.txt", serial, timestamp));
     let payload = lines.join("\n");
     fs::write(&output_path, payload).map_err(|err| {
-        AppError::system(format!("Failed to write logcat file: {err}"), &trace_id)
+        AppError::system(
+            Message::keyed("operation_failed")
+                .with("action", "write logcat file")
+                .with("detail", &err),
+            &trace_id,
+        )
     })?;
 
     Ok(CommandResponse {
@@ -5700,7 +19761,7 @@ pub fn start_bluetooth_monitor(
         .map_err(|_| AppError::system("Bluetooth monitor registry locked", &trace_id))?;
     if guard.contains_key(&serial) {
         return Err(AppError::validation(
-            "Bluetooth monitor already running",
+            Message::keyed("already_running").with("subject", "Bluetooth monitor"),
             &trace_id,
         ));
     }
@@ -5732,7 +19793,7 @@ pub fn stop_bluetooth_monitor(
         Some(handle) => handle,
         None => {
             return Err(AppError::validation(
-                "Bluetooth monitor not running",
+                Message::keyed("not_running").with("subject", "Bluetooth monitor"),
                 &trace_id,
             ))
         }
@@ -5748,21 +19809,36 @@ pub fn stop_bluetooth_monitor(
 #[tauri::command(async)]
 pub fn generate_bugreport(
     serial: String,
-    output_dir: String,
+    output_dir: Option<String>,
     app: AppHandle,
     state: State<'_, AppState>,
     trace_id: Option<String>,
 ) -> Result<CommandResponse<BugreportResult>, AppError> {
     let trace_id = resolve_trace_id(trace_id);
+    let config = load_config(&trace_id)?;
+    // Resolve first so the configured directory gets its dated subfolder; the resolved path is
+    // then created as is.
+    let output_dir = resolve_artifact_dir(
+        &config,
+        ArtifactKind::Bugreport,
+        output_dir.as_deref(),
+        Local::now().date_naive(),
+    )
+    .map(|dir| dir.to_string_lossy().to_string())
+    .unwrap_or_default();
     validate_generate_bugreport_inputs(&serial, &output_dir, &trace_id)?;
 
     let adb_program = get_adb_program(&trace_id)?;
-    fs::create_dir_all(&output_dir).map_err(|err| {
-        AppError::system(format!("Failed to create output dir: {err}"), &trace_id)
-    })?;
+    let output_dir = prepare_artifact_dir(
+        &config,
+        ArtifactKind::Bugreport,
+        Some(output_dir.as_str()),
+        &trace_id,
+    )?;
+    ensure_host_storage(&output_dir, BUGREPORT_ESTIMATED_BYTES, &trace_id)?;
     let timestamp = Utc::now().format("%Y%m%d_%H%M%S");
     let filename = format!("bugreport_{}_{}.zip", serial, timestamp);
-    let output_path = PathBuf::from(output_dir).join(filename);
+    let output_path = output_dir.join(filename);
 
     let (cancel_flag, child) = reserve_bugreport_handle(&serial, &state, &trace_id)?;
 
@@ -5782,21 +19858,34 @@ pub fn generate_bugreport(
     match stream_result {
         Ok(Some(remote_path)) => {
             result.stream_supported = true;
-            let args = vec![
-                "-s".to_string(),
-                serial.clone(),
-                "pull".to_string(),
-                remote_path,
-                output_path.to_string_lossy().to_string(),
-            ];
-            let pull =
-                run_command_with_timeout(&adb_program, &args, Duration::from_secs(300), &trace_id)?;
-            if pull.exit_code.unwrap_or_default() != 0 {
-                result.error = Some(format!("Failed to pull bugreport: {}", pull.stderr));
+            let storage = remote_artifact_bytes(&adb_program, &serial, &remote_path, &trace_id)
+                .map_or(Ok(()), |estimated| {
+                    ensure_host_storage(&output_dir, estimated, &trace_id)
+                });
+            if let Err(err) = storage {
+                result.error = Some(err.error);
+                allow_fallback = false;
             } else {
-                result.success = true;
-                result.output_path = Some(output_path.to_string_lossy().to_string());
-                result.error = None;
+                let args = vec![
+                    "-s".to_string(),
+                    serial.clone(),
+                    "pull".to_string(),
+                    remote_path,
+                    output_path.to_string_lossy().to_string(),
+                ];
+                let pull = run_command_with_timeout(
+                    &adb_program,
+                    &args,
+                    command_timeout(TimeoutKind::Transfer, &trace_id),
+                    &trace_id,
+                )?;
+                if pull.exit_code.unwrap_or_default() != 0 {
+                    result.error = Some(format!("Failed to pull bugreport: {}", pull.stderr));
+                } else {
+                    result.success = true;
+                    result.output_path = Some(output_path.to_string_lossy().to_string());
+                    result.error = None;
+                }
             }
         }
         Ok(None) => {
@@ -5840,7 +19929,50 @@ pub fn generate_bugreport(
         guard.remove(&serial);
     }
 
-    let _ = app.emit(
+    let cancelled = result
+        .error
+        .as_deref()
+        .is_some_and(|error| error.to_lowercase().contains("cancelled"));
+    if result.success {
+        let summary = format!(
+            "Bugreport saved to {}",
+            result.output_path.as_deref().unwrap_or_default()
+        );
+        notify_webhooks(
+            NOTIFY_BUGREPORT_COMPLETED,
+            &serial,
+            true,
+            &summary,
+            &trace_id,
+        );
+    } else if !cancelled {
+        let summary = format!(
+            "Bugreport failed: {}",
+            result.error.as_deref().unwrap_or("unknown error")
+        );
+        notify_webhooks(NOTIFY_BUGREPORT_FAILED, &serial, false, &summary, &trace_id);
+    }
+    run_hooks(
+        HOOK_BUGREPORT_FINISHED,
+        &serial,
+        result.success,
+        if result.success {
+            "Bugreport finished"
+        } else if cancelled {
+            "Bugreport cancelled"
+        } else {
+            "Bugreport failed"
+        },
+        serde_json::json!({
+            "output_path": result.output_path,
+            "error": result.error,
+            "cancelled": cancelled,
+        }),
+        &trace_id,
+    );
+
+    let _ = emit_tracked(
+        &app,
         "bugreport-complete",
         serde_json::json!({
             "trace_id": trace_id,
@@ -5869,7 +20001,12 @@ pub fn cancel_bugreport(
         .map_err(|_| AppError::system("Bugreport registry locked", &trace_id))?;
     let handle = match guard.get(&serial) {
         Some(handle) => handle,
-        None => return Err(AppError::validation("Bugreport not running", &trace_id)),
+        None => {
+            return Err(AppError::validation(
+                Message::keyed("not_running").with("subject", "Bugreport"),
+                &trace_id,
+            ))
+        }
     };
     handle.cancel_flag.store(true, Ordering::Relaxed);
     if let Ok(mut child_guard) = handle.child.lock() {
@@ -5907,7 +20044,12 @@ pub async fn prepare_bugreport_logcat(
         prepare_bugreport_logcat_inner(&source_for_worker, &trace_for_worker)
     })
     .await
-    .map_err(|_| AppError::system("Bugreport log index thread failed", &trace_id))??;
+    .map_err(|_| {
+        AppError::system(
+            Message::keyed("action_failed_plain").with("action", "Bugreport log index thread"),
+            &trace_id,
+        )
+    })??;
 
     Ok(CommandResponse {
         trace_id,
@@ -5932,7 +20074,12 @@ pub async fn query_bugreport_logcat(
         bugreport_logcat::query_bugreport_logcat(&report_id, filters, offset, limit)
     })
     .await
-    .map_err(|_| AppError::system("Bugreport log query thread failed", &trace_id))?
+    .map_err(|_| {
+        AppError::system(
+            Message::keyed("action_failed_plain").with("action", "Bugreport log query thread"),
+            &trace_id,
+        )
+    })?
     .map_err(|err| map_bugreport_log_query_error(err, &trace_id))?;
 
     Ok(CommandResponse {
@@ -5978,7 +20125,12 @@ pub async fn search_bugreport_logcat(
         )
     })
     .await
-    .map_err(|_| AppError::system("Bugreport log search thread failed", &trace_id))??;
+    .map_err(|_| {
+        AppError::system(
+            Message::keyed("action_failed_plain").with("action", "Bugreport log search thread"),
+            &trace_id,
+        )
+    })??;
 
     Ok(CommandResponse {
         trace_id,
@@ -5997,7 +20149,7 @@ fn query_bugreport_logcat_around_inner(
     ensure_non_empty(report_id, "report_id", trace_id)?;
     if anchor_id <= 0 {
         return Err(AppError::validation(
-            "anchor_id must be a positive integer",
+            Message::keyed("positive_integer").with("field", "anchor_id"),
             trace_id,
         ));
     }
@@ -6031,7 +20183,39 @@ pub async fn query_bugreport_logcat_around(
         )
     })
     .await
-    .map_err(|_| AppError::system("Bugreport log around thread failed", &trace_id))??;
+    .map_err(|_| {
+        AppError::system(
+            Message::keyed("action_failed_plain").with("action", "Bugreport log around thread"),
+            &trace_id,
+        )
+    })??;
+
+    Ok(CommandResponse {
+        trace_id,
+        data: result,
+    })
+}
+
+/// Index a saved `adb logcat` text file into the bugreport log viewer.
+#[tauri::command(async)]
+pub async fn import_logcat_file(
+    source_path: String,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<BugreportLogSummary>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&source_path, "source_path", &trace_id)?;
+    let trace_for_worker = trace_id.clone();
+    let result = tauri::async_runtime::spawn_blocking(move || {
+        bugreport_logcat::import_logcat_file(Path::new(&source_path), &trace_for_worker)
+    })
+    .await
+    .map_err(|_| {
+        AppError::system(
+            Message::keyed("action_failed_plain").with("action", "Logcat import thread"),
+            &trace_id,
+        )
+    })?
+    .map_err(|err| map_bugreport_log_query_error(err, &trace_id))?;
 
     Ok(CommandResponse {
         trace_id,
@@ -6039,6 +20223,52 @@ pub async fn query_bugreport_logcat_around(
     })
 }
 
+/// Export the rows of an indexed bugreport or logcat file matching `filters` as threadtime
+/// text or an Android Studio `.logcat` file. `year` dates lines without one (default: now).
+#[tauri::command(async)]
+pub async fn export_bugreport_logcat(
+    report_id: String,
+    filters: BugreportLogFilters,
+    format: String,
+    output_path: String,
+    year: Option<i32>,
+    trace_id: Option<String>,
+) -> Result<CommandResponse<LogcatFileExportResult>, AppError> {
+    let trace_id = resolve_trace_id(trace_id);
+    ensure_non_empty(&report_id, "report_id", &trace_id)?;
+    ensure_non_empty(&output_path, "output_path", &trace_id)?;
+    let format = format.trim().to_ascii_lowercase();
+    let year = year.unwrap_or_else(|| Local::now().year());
+    let (format_for_worker, path_for_worker) = (format.clone(), output_path.clone());
+    let rows = tauri::async_runtime::spawn_blocking(move || {
+        bugreport_logcat::export_bugreport_logcat(
+            &report_id,
+            filters,
+            &format_for_worker,
+            Path::new(&path_for_worker),
+            year,
+        )
+    })
+    .await
+    .map_err(|_| {
+        AppError::system(
+            Message::keyed("action_failed_plain").with("action", "Logcat export thread"),
+            &trace_id,
+        )
+    })?
+    .map_err(|err| map_bugreport_log_query_error(err, &trace_id))?;
+    info!(trace_id = %trace_id, format = %format, rows, "exported logcat");
+
+    Ok(CommandResponse {
+        trace_id,
+        data: LogcatFileExportResult {
+            output_path,
+            format,
+            rows,
+        },
+    })
+}
+
 fn map_bugreport_log_query_error(err: String, trace_id: &str) -> AppError {
     if let Some(message) = err.strip_prefix("VALIDATION:") {
         return AppError::validation(message.trim(), trace_id);
@@ -6054,12 +20284,14 @@ fn run_bugreport_streaming(
     cancel_flag: &Arc<AtomicBool>,
     child_holder: &Arc<std::sync::Mutex<Option<std::process::Child>>>,
 ) -> Result<Option<String>, String> {
-    let child = Command::new(adb_program)
-        .args(["-s", serial, "shell", "bugreportz", "-p"])
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .spawn()
-        .map_err(|err| format!("Failed to start bugreportz: {err}"))?;
+    let child = spawn_logged(
+        adb_command_for_serial(adb_program, serial)
+            .args(["-s", serial, "shell", "bugreportz", "-p"])
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped()),
+        trace_id,
+    )
+    .map_err(|err| format!("Failed to start bugreportz: {err}"))?;
 
     {
         let mut guard = child_holder
@@ -6083,7 +20315,7 @@ fn run_bugreport_streaming(
 
     let mut reader = BufReader::new(stdout);
     let start = std::time::Instant::now();
-    let timeout = Duration::from_secs(300);
+    let timeout = command_timeout(TimeoutKind::Transfer, trace_id);
     let mut remote_path = None;
     let mut progress = None;
 
@@ -6121,7 +20353,8 @@ fn run_bugreport_streaming(
                     BugreportzPayload::Progress { percent } => {
                         if progress != Some(percent) {
                             progress = Some(percent);
-                            let _ = app.emit(
+                            let _ = emit_tracked(
+                                app,
                                 "bugreport-progress",
                                 serde_json::json!({
                                     "trace_id": trace_id,
@@ -50,7 +50,9 @@ fn start_logcat_inner_rejects_empty_serial() {
         &registry,
         emitter,
         "trace-1",
-        |_program, _serial, _filter, _trace| Ok(spawn_long_running_piped_child()),
+        |_program, _serial, _filter, _trace| {
+            Ok(LogcatSource::Process(spawn_long_running_piped_child()))
+        },
     )
     .expect_err("expected error");
 
@@ -311,7 +313,7 @@ fn start_logcat_inner_rejects_when_already_running() {
         guard.insert(
             "ABC".to_string(),
             LogcatHandle {
-                child: spawn_long_running_piped_child(),
+                source: LogcatSource::Process(spawn_long_running_piped_child()),
                 stop_flag: Arc::new(AtomicBool::new(false)),
             },
         );
@@ -324,7 +326,9 @@ fn start_logcat_inner_rejects_when_already_running() {
         &registry,
         emitter,
         "trace-2",
-        |_program, _serial, _filter, _trace| Ok(spawn_long_running_piped_child()),
+        |_program, _serial, _filter, _trace| {
+            Ok(LogcatSource::Process(spawn_long_running_piped_child()))
+        },
     )
     .expect_err("expected already running");
 
@@ -348,7 +352,7 @@ fn stop_logcat_inner_removes_handle() {
         guard.insert(
             "ABC".to_string(),
             LogcatHandle {
-                child: spawn_long_running_piped_child(),
+                source: LogcatSource::Process(spawn_long_running_piped_child()),
                 stop_flag: Arc::new(AtomicBool::new(false)),
             },
         );
@@ -511,13 +515,25 @@ fn load_device_detail_continues_when_non_getprop_steps_fail() {
             "df" => Ok(ok(
                 "Filesystem 1K-blocks Used Available Use% Mounted on\n/dev/block/dm-0 1000 0 0 0% /data\n",
             )),
+            "features" => Ok(ok("shell_v2\ncmd\n")),
+            "usb" => Err(AppError::dependency("dumpsys usb fails".to_string(), trace_id)),
+            "udc_speed" => Ok(ok("high-speed\n")),
             "meminfo" => Ok(ok("MemTotal: 2048 kB\n")),
+            "lock_state" => Ok(ok(
+                "crypto_type=file\nce_available=true\n@@keyguard\n  showing=false\n",
+            )),
+            "telephony" => Err(AppError::dependency("telephony fails".to_string(), trace_id)),
             other => panic!("unexpected step {other}"),
         }
     };
 
-    let detail = load_device_detail(serial, trace_id, false, 0, run);
-    assert!(detail.is_some());
+    let detail = load_device_detail(serial, trace_id, false, 0, run).unwrap();
+    assert_eq!(detail.adb_features, vec!["cmd", "shell_v2"]);
+    assert_eq!(detail.usb_speed_mbps, Some(480));
+    let lock_state = detail.lock_state.unwrap();
+    assert_eq!(lock_state.locked, Some(false));
+    assert!(lock_state.ready);
+    assert_eq!(detail.telephony, None);
     assert_eq!(
         called_steps,
         vec![
@@ -530,7 +546,77 @@ fn load_device_detail_continues_when_non_getprop_steps_fail() {
             "gms",
             "wm_size",
             "df",
-            "meminfo"
+            "features",
+            "usb",
+            "udc_speed",
+            "meminfo",
+            "lock_state",
+            "telephony"
         ]
     );
 }
+
+fn ui_capture_record(serial: &str, capture_id: &str, xml: &str) -> UiCaptureRecord {
+    UiCaptureRecord {
+        capture_id: capture_id.to_string(),
+        serial: serial.to_string(),
+        captured_at: "2024-01-01T00:00:00Z".to_string(),
+        backend: "uiautomator".to_string(),
+        node_count: 0,
+        xml: xml.to_string(),
+        html: String::new(),
+        diff: None,
+    }
+}
+
+#[test]
+fn record_ui_capture_inner_diffs_against_previous_and_trims_history() {
+    let registry = Mutex::new(std::collections::HashMap::new());
+    let first = "<hierarchy><node class=\"A\" text=\"one\" /></hierarchy>";
+    let second = "<hierarchy><node class=\"A\" text=\"two\" /></hierarchy>";
+
+    let diff = record_ui_capture_inner(
+        &registry,
+        ui_capture_record("serial-1", "cap-1", first),
+        2,
+        "trace-ui-1",
+    )
+    .expect("record first");
+    assert!(diff.is_none());
+
+    let diff = record_ui_capture_inner(
+        &registry,
+        ui_capture_record("serial-1", "cap-2", second),
+        2,
+        "trace-ui-1",
+    )
+    .expect("record second")
+    .expect("diff");
+    assert_eq!(diff.previous_capture_id, "cap-1");
+    assert_eq!(diff.changed, 1);
+
+    record_ui_capture_inner(
+        &registry,
+        ui_capture_record("serial-1", "cap-3", second),
+        2,
+        "trace-ui-1",
+    )
+    .expect("record third");
+
+    let captures = list_ui_captures_inner("serial-1", &registry, "trace-ui-1").expect("list");
+    let ids: Vec<&str> = captures
+        .iter()
+        .map(|item| item.capture_id.as_str())
+        .collect();
+    assert_eq!(ids, vec!["cap-3", "cap-2"]);
+    assert!(list_ui_captures_inner("serial-2", &registry, "trace-ui-1")
+        .expect("list other")
+        .is_empty());
+}
+
+#[test]
+fn list_ui_captures_inner_rejects_empty_serial() {
+    let registry = Mutex::new(std::collections::HashMap::new());
+    let err = list_ui_captures_inner(" ", &registry, "trace-ui-2").expect_err("expected error");
+    assert_eq!(err.code, "ERR_VALIDATION");
+}
@@ -3,7 +3,26 @@ use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 
+use crate::app::adb::frida::validate_agent_remote_path;
+use crate::app::adb::locator::{
+    invalidate_adb_program_cache, normalize_command_path, ADB_TRANSPORTS,
+};
+use crate::app::custom_tools::{
+    normalize_custom_tool, DEFAULT_CUSTOM_TOOL_TIMEOUT_SECS, INPUT_TEXT,
+};
+use crate::app::device_farm::is_valid_project_id;
 use crate::app::error::AppError;
+use crate::app::hooks::{normalize_hook, DEFAULT_HOOK_TIMEOUT_SECS};
+use crate::app::i18n::{normalize_locale, set_message_locale, Message, LOCALE_EN};
+use crate::app::issues::{normalize_github_settings, normalize_jira_settings};
+use crate::app::logging::normalize_log_level;
+use crate::app::notifications::normalize_webhook;
+use crate::app::on_connect::{is_valid_locale, ACTION_SET_LOCALE, ON_CONNECT_ACTIONS};
+use crate::app::timeouts::{
+    invalidate_timeout_cache, normalize_timeouts, DEFAULT_DEVICE_COMMAND_TIMEOUT_SECS,
+    DEFAULT_INSTALL_TIMEOUT_SECS, DEFAULT_PROBE_TIMEOUT_SECS, DEFAULT_TRANSFER_TIMEOUT_SECS,
+};
+use crate::app::upload::normalize_upload_target;
 use tracing::warn;
 use uuid::Uuid;
 
@@ -34,10 +53,30 @@ fn default_device_refresh_interval() -> i32 {
     5
 }
 
+const MAX_AUTO_RECONNECT_WINDOW_SECS: u64 = 3600;
+const MAX_LOG_FILES: i32 = 50;
+const MAX_LOG_FILE_SIZE_MB: i32 = 500;
+
+fn default_auto_reconnect_window_secs() -> u64 {
+    300
+}
+
 fn default_true() -> bool {
     true
 }
 
+fn default_build_module() -> String {
+    "app".to_string()
+}
+
+fn default_convert_max_width() -> i32 {
+    480
+}
+
+fn default_convert_fps() -> i32 {
+    10
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct UiSettings {
     pub window_width: i32,
@@ -50,6 +89,13 @@ pub struct UiSettings {
     pub show_console_panel: bool,
     pub single_selection: bool,
     pub default_output_path: String,
+    /// Locale for backend messages (`en` or `zh-TW`).
+    #[serde(default = "default_language")]
+    pub language: String,
+}
+
+fn default_language() -> String {
+    LOCALE_EN.to_string()
 }
 
 impl Default for UiSettings {
@@ -65,6 +111,7 @@ impl Default for UiSettings {
             show_console_panel: false,
             single_selection: true,
             default_output_path: default_output_dir(),
+            language: default_language(),
         }
     }
 }
@@ -81,6 +128,14 @@ pub struct DeviceSettings {
     pub show_offline_devices: bool,
     #[serde(default)]
     pub preferred_devices: Vec<String>,
+    /// Retry `adb connect` when a wireless device drops off.
+    #[serde(default)]
+    pub auto_reconnect: bool,
+    #[serde(default = "default_auto_reconnect_window_secs")]
+    pub auto_reconnect_window_secs: u64,
+    /// Name used for device reservations; empty means the OS account name.
+    #[serde(default)]
+    pub reservation_user: String,
 }
 
 impl Default for DeviceSettings {
@@ -91,10 +146,38 @@ impl Default for DeviceSettings {
             auto_connect: true,
             show_offline_devices: false,
             preferred_devices: Vec::new(),
+            auto_reconnect: false,
+            auto_reconnect_window_secs: default_auto_reconnect_window_secs(),
+            reservation_user: String::new(),
         }
     }
 }
 
+/// Actions applied automatically when an opted-in device comes online.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct OnConnectSettings {
+    #[serde(default)]
+    pub profiles: Vec<OnConnectProfile>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct OnConnectProfile {
+    pub serial: String,
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub actions: Vec<OnConnectAction>,
+}
+
+/// `kind` is one of `stay_awake`, `disable_animations`, `set_locale` (value: locale tag) or
+/// `start_logcat` (value: optional filter).
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct OnConnectAction {
+    pub kind: String,
+    #[serde(default)]
+    pub value: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct CommandSettings {
     pub max_history_size: usize,
@@ -114,9 +197,84 @@ impl Default for CommandSettings {
     }
 }
 
-#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+fn default_adb_server_port() -> u16 {
+    5037
+}
+
+fn default_ssh_port() -> u16 {
+    22
+}
+
+fn default_tunnel_local_port() -> u16 {
+    15037
+}
+
+/// Reach a remote adb server through `ssh -L` instead of exposing it on the lab network.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SshTunnelSettings {
+    pub user: String,
+    #[serde(default)]
+    pub key_path: String,
+    #[serde(default = "default_ssh_port")]
+    pub ssh_port: u16,
+    #[serde(default = "default_adb_server_port")]
+    pub remote_port: u16,
+    #[serde(default = "default_tunnel_local_port")]
+    pub local_port: u16,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AdbServerHost {
+    pub name: String,
+    pub host: String,
+    #[serde(default = "default_adb_server_port")]
+    pub port: u16,
+    #[serde(default)]
+    pub ssh_tunnel: Option<SshTunnelSettings>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct AdbSettings {
     pub command_path: String,
+    /// Serve `adb shell` and file stats over the adb server socket instead of spawning adb.
+    #[serde(default = "default_true")]
+    pub socket_client: bool,
+    /// Gzip pulls from TCP/IP devices on the device and inflate them on the host.
+    #[serde(default = "default_true")]
+    pub compress_wireless_pulls: bool,
+    #[serde(default)]
+    pub server_hosts: Vec<AdbServerHost>,
+    /// Name of the entry in `server_hosts` to talk to; empty means the local adb server.
+    #[serde(default)]
+    pub active_server: String,
+    #[serde(default)]
+    pub path_overrides: Vec<AdbPathOverride>,
+}
+
+impl Default for AdbSettings {
+    fn default() -> Self {
+        Self {
+            command_path: String::new(),
+            socket_client: true,
+            compress_wireless_pulls: true,
+            server_hosts: Vec::new(),
+            active_server: String::new(),
+            path_overrides: Vec::new(),
+        }
+    }
+}
+
+/// Alternate adb binary (e.g. a vendor-patched build) for one device `serial`, or for every
+/// device on a `transport` (`usb`, `tcp` or `emulator`). A serial match wins over a transport
+/// match; devices with no match use `command_path`. All binaries share one adb server, so an
+/// override must report the same `adb version` protocol as `command_path`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct AdbPathOverride {
+    #[serde(default)]
+    pub serial: String,
+    #[serde(default)]
+    pub transport: String,
+    pub command_path: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -168,6 +326,11 @@ pub struct ScrcpySettings {
     pub bitrate: String,
     pub max_size: i32,
     pub extra_args: String,
+    #[serde(default = "default_scrcpy_presets")]
+    pub presets: Vec<ScrcpyPreset>,
+    /// Serial to preset name; devices without an entry use the options above.
+    #[serde(default)]
+    pub device_presets: HashMap<String, String>,
 }
 
 impl Default for ScrcpySettings {
@@ -180,10 +343,94 @@ impl Default for ScrcpySettings {
             bitrate: "8M".to_string(),
             max_size: 0,
             extra_args: String::new(),
+            presets: default_scrcpy_presets(),
+            device_presets: HashMap::new(),
+        }
+    }
+}
+
+/// Launch options shared by the scrcpy settings and named presets. An empty `bitrate`
+/// leaves scrcpy's default.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct ScrcpyOptions {
+    #[serde(default)]
+    pub stay_awake: bool,
+    #[serde(default)]
+    pub turn_screen_off: bool,
+    #[serde(default)]
+    pub disable_screensaver: bool,
+    #[serde(default)]
+    pub enable_audio_playback: bool,
+    #[serde(default)]
+    pub bitrate: String,
+    #[serde(default)]
+    pub max_size: i32,
+    #[serde(default)]
+    pub extra_args: String,
+}
+
+impl ScrcpyOptions {
+    pub fn to_settings(&self) -> ScrcpySettings {
+        ScrcpySettings {
+            stay_awake: self.stay_awake,
+            turn_screen_off: self.turn_screen_off,
+            disable_screensaver: self.disable_screensaver,
+            enable_audio_playback: self.enable_audio_playback,
+            bitrate: self.bitrate.clone(),
+            max_size: self.max_size,
+            extra_args: self.extra_args.clone(),
+            presets: Vec::new(),
+            device_presets: HashMap::new(),
         }
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ScrcpyPreset {
+    pub name: String,
+    #[serde(flatten)]
+    pub options: ScrcpyOptions,
+}
+
+fn default_scrcpy_presets() -> Vec<ScrcpyPreset> {
+    let preset = |name: &str, options: ScrcpyOptions| ScrcpyPreset {
+        name: name.to_string(),
+        options,
+    };
+    vec![
+        preset(
+            "low-latency",
+            ScrcpyOptions {
+                stay_awake: true,
+                disable_screensaver: true,
+                bitrate: "4M".to_string(),
+                max_size: 1024,
+                extra_args: "--max-fps=60 --video-buffer=0".to_string(),
+                ..Default::default()
+            },
+        ),
+        preset(
+            "recording",
+            ScrcpyOptions {
+                stay_awake: true,
+                disable_screensaver: true,
+                enable_audio_playback: true,
+                bitrate: "16M".to_string(),
+                extra_args: "--record=scrcpy-recording.mp4".to_string(),
+                ..Default::default()
+            },
+        ),
+        // OTG mode only forwards keyboard and mouse; video and audio options do not apply.
+        preset(
+            "otg",
+            ScrcpyOptions {
+                extra_args: "--otg".to_string(),
+                ..Default::default()
+            },
+        ),
+    ]
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct ApkInstallSettings {
     pub replace_existing: bool,
@@ -191,6 +438,11 @@ pub struct ApkInstallSettings {
     pub grant_permissions: bool,
     pub allow_test_packages: bool,
     pub extra_args: String,
+    /// Gradle project root scanned for `<module>/build/outputs/apk`.
+    #[serde(default)]
+    pub build_project_dir: String,
+    #[serde(default = "default_build_module")]
+    pub build_module: String,
 }
 
 impl Default for ApkInstallSettings {
@@ -201,6 +453,8 @@ impl Default for ApkInstallSettings {
             grant_permissions: true,
             allow_test_packages: false,
             extra_args: String::new(),
+            build_project_dir: String::new(),
+            build_module: default_build_module(),
         }
     }
 }
@@ -230,6 +484,14 @@ pub struct ScreenRecordSettings {
     pub bugreport: bool,
     pub verbose: bool,
     pub display_id: i32,
+    #[serde(default)]
+    pub convert_format: String,
+    #[serde(default = "default_convert_max_width")]
+    pub convert_max_width: i32,
+    #[serde(default = "default_convert_fps")]
+    pub convert_fps: i32,
+    #[serde(default)]
+    pub ffmpeg_path: String,
 }
 
 impl Default for ScreenRecordSettings {
@@ -245,10 +507,30 @@ impl Default for ScreenRecordSettings {
             bugreport: false,
             verbose: false,
             display_id: -1,
+            convert_format: String::new(),
+            convert_max_width: default_convert_max_width(),
+            convert_fps: default_convert_fps(),
+            ffmpeg_path: String::new(),
         }
     }
 }
 
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct OutputSettings {
+    #[serde(default)]
+    pub screenshots_dir: String,
+    #[serde(default)]
+    pub recordings_dir: String,
+    #[serde(default)]
+    pub bugreports_dir: String,
+    #[serde(default)]
+    pub logs_dir: String,
+    #[serde(default)]
+    pub ui_dumps_dir: String,
+    #[serde(default)]
+    pub dated_subfolders: bool,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct LogcatViewerSettings {
     pub compact_mode: bool,
@@ -288,6 +570,8 @@ pub struct NotificationsSettings {
     pub desktop_on_error: bool,
     #[serde(default)]
     pub desktop_on_cancelled: bool,
+    #[serde(default)]
+    pub webhooks: Vec<WebhookTarget>,
 }
 
 impl Default for NotificationsSettings {
@@ -299,6 +583,7 @@ impl Default for NotificationsSettings {
             desktop_on_success: false,
             desktop_on_error: true,
             desktop_on_cancelled: false,
+            webhooks: Vec::new(),
         }
     }
 }
@@ -326,6 +611,259 @@ pub fn clamp_terminal_buffer_lines(lines: &mut Vec<String>) {
     }
 }
 
+pub const DEFAULT_DEBUG_AGENT_REMOTE_PATH: &str = "/data/local/tmp/frida-server";
+pub const DEFAULT_DEBUG_AGENT_PORT: u16 = 27042;
+
+/// On-device debug agent such as frida-server. `local_path` is the host binary to push;
+/// the agent listens on `device_port` and is forwarded to `local_port` on the host.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DebugAgentSettings {
+    #[serde(default)]
+    pub local_path: String,
+    #[serde(default)]
+    pub remote_path: String,
+    #[serde(default)]
+    pub device_port: u16,
+    #[serde(default)]
+    pub local_port: u16,
+}
+
+impl Default for DebugAgentSettings {
+    fn default() -> Self {
+        Self {
+            local_path: String::new(),
+            remote_path: DEFAULT_DEBUG_AGENT_REMOTE_PATH.to_string(),
+            device_port: DEFAULT_DEBUG_AGENT_PORT,
+            local_port: DEFAULT_DEBUG_AGENT_PORT,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct UploadHeader {
+    pub name: String,
+    pub value: String,
+    /// When set, `value` names an environment variable holding the header value.
+    #[serde(default)]
+    pub value_from_env: bool,
+}
+
+/// Artifact upload destination (`s3`, `gcs` or `http`). Credentials are never stored in the
+/// config: the `*_env` fields name environment variables that hold them.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct UploadTarget {
+    pub name: String,
+    pub kind: String,
+    /// HTTP endpoint, or a custom S3-compatible endpoint (path-style) when set for `s3`.
+    #[serde(default)]
+    pub url: String,
+    #[serde(default)]
+    pub bucket: String,
+    #[serde(default)]
+    pub region: String,
+    #[serde(default)]
+    pub prefix: String,
+    #[serde(default)]
+    pub method: String,
+    #[serde(default)]
+    pub headers: Vec<UploadHeader>,
+    #[serde(default)]
+    pub access_key_env: String,
+    #[serde(default)]
+    pub secret_key_env: String,
+    #[serde(default)]
+    pub token_env: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct UploadSettings {
+    #[serde(default)]
+    pub curl_path: String,
+    #[serde(default)]
+    pub targets: Vec<UploadTarget>,
+}
+
+/// Firebase Test Lab submissions through the gcloud CLI, which holds its own credentials.
+/// An empty `project_id` uses the gcloud default project.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct DeviceFarmSettings {
+    #[serde(default)]
+    pub gcloud_path: String,
+    #[serde(default)]
+    pub project_id: String,
+    #[serde(default)]
+    pub results_bucket: String,
+}
+
+/// Outgoing webhook (Slack incoming webhook or any JSON endpoint). `template` is the JSON body
+/// with `{{event}}`, `{{serial}}`, `{{status}}`, `{{summary}}` and `{{timestamp}}` placeholders.
+/// `url_env` names an environment variable holding the URL, for webhooks whose URL is a secret.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WebhookTarget {
+    pub name: String,
+    #[serde(default)]
+    pub url: String,
+    #[serde(default)]
+    pub url_env: String,
+    /// Events this webhook fires on; empty means all.
+    #[serde(default)]
+    pub events: Vec<String>,
+    #[serde(default)]
+    pub template: String,
+    #[serde(default)]
+    pub headers: Vec<UploadHeader>,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+}
+
+/// Host-side scripts run on lifecycle events with a JSON payload on stdin.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct HooksSettings {
+    #[serde(default)]
+    pub scripts: Vec<HookScript>,
+}
+
+/// `executable` is an absolute path, run without arguments. Empty `events` means every event.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct HookScript {
+    pub name: String,
+    pub executable: String,
+    #[serde(default)]
+    pub events: Vec<String>,
+    #[serde(default = "default_hook_timeout_secs")]
+    pub timeout_secs: u64,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+}
+
+fn default_hook_timeout_secs() -> u64 {
+    DEFAULT_HOOK_TIMEOUT_SECS
+}
+
+/// Seconds allowed per class of adb command, see `app::timeouts`. Slow devices and wireless
+/// connections often need more than the defaults.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct TimeoutSettings {
+    pub probe_secs: u64,
+    pub device_command_secs: u64,
+    pub install_secs: u64,
+    pub transfer_secs: u64,
+}
+
+impl Default for TimeoutSettings {
+    fn default() -> Self {
+        Self {
+            probe_secs: DEFAULT_PROBE_TIMEOUT_SECS,
+            device_command_secs: DEFAULT_DEVICE_COMMAND_TIMEOUT_SECS,
+            install_secs: DEFAULT_INSTALL_TIMEOUT_SECS,
+            transfer_secs: DEFAULT_TRANSFER_TIMEOUT_SECS,
+        }
+    }
+}
+
+/// Team-defined actions run through `run_custom_tool`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct CustomToolsSettings {
+    #[serde(default)]
+    pub tools: Vec<CustomTool>,
+}
+
+/// `kind` is `adb` (`args` run as `adb -s <serial> ...`) or `host` (`program` with `args`).
+/// Arguments may reference `${serial}` and `${<input name>}`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CustomTool {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub kind: String,
+    #[serde(default)]
+    pub program: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    #[serde(default)]
+    pub inputs: Vec<CustomToolInput>,
+    /// Whether the tool may run on several devices at once.
+    #[serde(default)]
+    pub multi_device: bool,
+    #[serde(default = "default_custom_tool_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+/// `kind` is `text`, `number`, `boolean` or `choice`. `pattern` must match the whole value.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CustomToolInput {
+    pub name: String,
+    #[serde(default)]
+    pub label: String,
+    #[serde(default = "default_custom_tool_input_kind")]
+    pub kind: String,
+    #[serde(default)]
+    pub required: bool,
+    #[serde(default)]
+    pub default: Option<String>,
+    #[serde(default)]
+    pub choices: Vec<String>,
+    #[serde(default)]
+    pub pattern: Option<String>,
+}
+
+fn default_custom_tool_timeout_secs() -> u64 {
+    DEFAULT_CUSTOM_TOOL_TIMEOUT_SECS
+}
+
+fn default_custom_tool_input_kind() -> String {
+    INPUT_TEXT.to_string()
+}
+
+/// GitHub has no API for issue attachments, so artifacts are uploaded to
+/// `attachment_upload_target` (an entry in `upload.targets`) and linked from the issue body.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct GithubIssueSettings {
+    #[serde(default)]
+    pub api_url: String,
+    #[serde(default)]
+    pub repo: String,
+    #[serde(default)]
+    pub token_env: String,
+    #[serde(default)]
+    pub attachment_upload_target: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct JiraIssueSettings {
+    #[serde(default)]
+    pub base_url: String,
+    #[serde(default)]
+    pub project_key: String,
+    #[serde(default)]
+    pub issue_type: String,
+    #[serde(default)]
+    pub email: String,
+    #[serde(default)]
+    pub token_env: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct IssueTrackerSettings {
+    #[serde(default)]
+    pub github: GithubIssueSettings,
+    #[serde(default)]
+    pub jira: JiraIssueSettings,
+}
+
+/// Lab-station lock that blocks destructive commands in the backend.
+///
+/// `password_hash` is PBKDF2-HMAC-SHA256 (`pbkdf2-sha256$<iterations>$<salt>$<hex>`), or a legacy
+/// salted SHA-256 (`sha256$<salt>$<hex>`); empty means no password. `get_config` never returns it.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct RestrictedModeSettings {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub password_hash: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct AppConfig {
     #[serde(default)]
@@ -333,6 +871,8 @@ pub struct AppConfig {
     #[serde(default)]
     pub device: DeviceSettings,
     #[serde(default)]
+    pub on_connect: OnConnectSettings,
+    #[serde(default)]
     pub command: CommandSettings,
     #[serde(default)]
     pub adb: AdbSettings,
@@ -363,6 +903,24 @@ pub struct AppConfig {
     #[serde(default)]
     pub file_gen_output_path: String,
     #[serde(default)]
+    pub output: OutputSettings,
+    #[serde(default)]
+    pub restricted_mode: RestrictedModeSettings,
+    #[serde(default)]
+    pub debug_agent: DebugAgentSettings,
+    #[serde(default)]
+    pub upload: UploadSettings,
+    #[serde(default)]
+    pub issues: IssueTrackerSettings,
+    #[serde(default)]
+    pub device_farm: DeviceFarmSettings,
+    #[serde(default)]
+    pub hooks: HooksSettings,
+    #[serde(default)]
+    pub custom_tools: CustomToolsSettings,
+    #[serde(default)]
+    pub timeouts: TimeoutSettings,
+    #[serde(default)]
     pub version: String,
 }
 
@@ -372,6 +930,7 @@ impl Default for AppConfig {
         Self {
             ui: UiSettings::default(),
             device: DeviceSettings::default(),
+            on_connect: OnConnectSettings::default(),
             command: CommandSettings::default(),
             adb: AdbSettings::default(),
             logging: LoggingSettings::default(),
@@ -387,6 +946,15 @@ impl Default for AppConfig {
             device_groups: HashMap::new(),
             output_path: output_dir.clone(),
             file_gen_output_path: output_dir,
+            output: OutputSettings::default(),
+            restricted_mode: RestrictedModeSettings::default(),
+            debug_agent: DebugAgentSettings::default(),
+            upload: UploadSettings::default(),
+            issues: IssueTrackerSettings::default(),
+            device_farm: DeviceFarmSettings::default(),
+            hooks: HooksSettings::default(),
+            custom_tools: CustomToolsSettings::default(),
+            timeouts: TimeoutSettings::default(),
             version: "0.0.50".to_string(),
         }
     }
@@ -420,11 +988,16 @@ pub fn backup_config_path() -> PathBuf {
 }
 
 pub fn load_config(trace_id: &str) -> Result<AppConfig, AppError> {
-    load_config_from_path(&config_path(), trace_id)
+    let config = load_config_from_path(&config_path(), trace_id)?;
+    set_message_locale(&config.ui.language);
+    Ok(config)
 }
 
 pub fn save_config(config: &AppConfig, trace_id: &str) -> Result<(), AppError> {
-    save_config_to_path(config, &config_path(), &backup_config_path(), trace_id)
+    let result = save_config_to_path(config, &config_path(), &backup_config_path(), trace_id);
+    invalidate_adb_program_cache();
+    invalidate_timeout_cache();
+    result
 }
 
 pub fn load_config_from_path(path: &Path, trace_id: &str) -> Result<AppConfig, AppError> {
@@ -432,13 +1005,28 @@ pub fn load_config_from_path(path: &Path, trace_id: &str) -> Result<AppConfig, A
     if !path.exists() {
         return Ok(AppConfig::default());
     }
-    let raw = fs::read_to_string(path)
-        .map_err(|err| AppError::system(format!("Failed to read config: {err}"), &trace_id))?;
+    let raw = fs::read_to_string(path).map_err(|err| {
+        AppError::system(
+            Message::keyed("operation_failed")
+                .with("action", "read config")
+                .with("detail", &err),
+            &trace_id,
+        )
+    })?;
     let value: serde_json::Value = serde_json::from_str(&raw).map_err(|err| {
-        AppError::validation(format!("Failed to parse config JSON: {err}"), &trace_id)
+        AppError::validation(
+            Message::keyed("operation_failed")
+                .with("action", "parse config JSON")
+                .with("detail", &err),
+            &trace_id,
+        )
+    })?;
+    let mut config: AppConfig = serde_json::from_value(value.clone()).map_err(|err| {
+        AppError::validation(
+            Message::keyed("config_invalid").with("detail", &err),
+            &trace_id,
+        )
     })?;
-    let mut config: AppConfig = serde_json::from_value(value.clone())
-        .map_err(|err| AppError::validation(format!("Config file is invalid: {err}"), &trace_id))?;
     config = apply_legacy_overrides(config, &value);
     Ok(validate_config(config))
 }
@@ -452,7 +1040,12 @@ pub fn save_config_to_path(
     let trace_id = normalize_trace_id(trace_id);
     if let Some(parent) = path.parent() {
         fs::create_dir_all(parent).map_err(|err| {
-            AppError::system(format!("Failed to create config dir: {err}"), &trace_id)
+            AppError::system(
+                Message::keyed("operation_failed")
+                    .with("action", "create config dir")
+                    .with("detail", &err),
+                &trace_id,
+            )
         })?;
     }
     if path.exists() {
@@ -464,10 +1057,22 @@ pub fn save_config_to_path(
             );
         }
     }
-    let payload = serde_json::to_string_pretty(config)
-        .map_err(|err| AppError::system(format!("Failed to serialize config: {err}"), &trace_id))?;
-    fs::write(path, payload)
-        .map_err(|err| AppError::system(format!("Failed to write config: {err}"), &trace_id))?;
+    let payload = serde_json::to_string_pretty(config).map_err(|err| {
+        AppError::system(
+            Message::keyed("operation_failed")
+                .with("action", "serialize config")
+                .with("detail", &err),
+            &trace_id,
+        )
+    })?;
+    fs::write(path, payload).map_err(|err| {
+        AppError::system(
+            Message::keyed("operation_failed")
+                .with("action", "write config")
+                .with("detail", &err),
+            &trace_id,
+        )
+    })?;
     Ok(())
 }
 
@@ -518,6 +1123,22 @@ fn validate_config(mut config: AppConfig) -> AppConfig {
     if config.device.refresh_interval < 1 {
         config.device.refresh_interval = default_device_refresh_interval();
     }
+    if config.device.auto_reconnect_window_secs == 0 {
+        config.device.auto_reconnect_window_secs = default_auto_reconnect_window_secs();
+    }
+    config.device.auto_reconnect_window_secs = config
+        .device
+        .auto_reconnect_window_secs
+        .min(MAX_AUTO_RECONNECT_WINDOW_SECS);
+    config.device.reservation_user = config.device.reservation_user.trim().to_string();
+    if normalize_log_level(&config.logging.log_level).is_err() {
+        config.logging.log_level = LoggingSettings::default().log_level;
+    }
+    config.logging.max_log_files = config.logging.max_log_files.clamp(1, MAX_LOG_FILES);
+    config.logging.log_file_size_mb = config
+        .logging
+        .log_file_size_mb
+        .clamp(1, MAX_LOG_FILE_SIZE_MB);
     if config.logcat.max_lines < 100 {
         config.logcat.max_lines = 1000;
     }
@@ -548,6 +1169,18 @@ fn validate_config(mut config: AppConfig) -> AppConfig {
     if config.scrcpy.max_size < 0 {
         config.scrcpy.max_size = 0;
     }
+    let mut seen_presets = std::collections::HashSet::new();
+    config.scrcpy.presets.retain_mut(|preset| {
+        preset.name = preset.name.trim().to_string();
+        preset.options.bitrate = preset.options.bitrate.trim().to_string();
+        preset.options.max_size = preset.options.max_size.max(0);
+        !preset.name.is_empty() && seen_presets.insert(preset.name.clone())
+    });
+    config.scrcpy.device_presets = std::mem::take(&mut config.scrcpy.device_presets)
+        .into_iter()
+        .map(|(serial, preset)| (serial.trim().to_string(), preset.trim().to_string()))
+        .filter(|(serial, preset)| !serial.is_empty() && seen_presets.contains(preset))
+        .collect();
     if config.screenshot.display_id < -1 {
         config.screenshot.display_id = -1;
     }
@@ -564,6 +1197,140 @@ fn validate_config(mut config: AppConfig) -> AppConfig {
     if config.screen_record.time_limit_sec > 180 {
         config.screen_record.time_limit_sec = 180;
     }
+    config.screen_record.convert_format = config.screen_record.convert_format.trim().to_lowercase();
+    if !matches!(
+        config.screen_record.convert_format.as_str(),
+        "" | "gif" | "webp"
+    ) {
+        config.screen_record.convert_format = String::new();
+    }
+    config.screen_record.convert_max_width = config.screen_record.convert_max_width.clamp(64, 1920);
+    config.screen_record.convert_fps = config.screen_record.convert_fps.clamp(1, 30);
+    let mut seen_hosts = std::collections::HashSet::new();
+    config.adb.server_hosts.retain_mut(|entry| {
+        entry.name = entry.name.trim().to_string();
+        entry.host = entry.host.trim().to_string();
+        if entry.port == 0 {
+            entry.port = default_adb_server_port();
+        }
+        if let Some(tunnel) = entry.ssh_tunnel.as_mut() {
+            tunnel.user = tunnel.user.trim().to_string();
+            tunnel.key_path = tunnel.key_path.trim().to_string();
+            if tunnel.ssh_port == 0 {
+                tunnel.ssh_port = default_ssh_port();
+            }
+            if tunnel.remote_port == 0 {
+                tunnel.remote_port = default_adb_server_port();
+            }
+            if tunnel.local_port == 0 {
+                tunnel.local_port = default_tunnel_local_port();
+            }
+        }
+        if entry
+            .ssh_tunnel
+            .as_ref()
+            .is_some_and(|tunnel| tunnel.user.is_empty())
+        {
+            entry.ssh_tunnel = None;
+        }
+        !entry.name.is_empty() && !entry.host.is_empty() && seen_hosts.insert(entry.name.clone())
+    });
+    config.debug_agent.local_path = config.debug_agent.local_path.trim().to_string();
+    config.debug_agent.remote_path = validate_agent_remote_path(&config.debug_agent.remote_path)
+        .unwrap_or_else(|_| DEFAULT_DEBUG_AGENT_REMOTE_PATH.to_string());
+    if config.debug_agent.device_port == 0 {
+        config.debug_agent.device_port = DEFAULT_DEBUG_AGENT_PORT;
+    }
+    if config.debug_agent.local_port == 0 {
+        config.debug_agent.local_port = DEFAULT_DEBUG_AGENT_PORT;
+    }
+    config.ui.language = normalize_locale(&config.ui.language);
+    config.apk_install.build_project_dir = config.apk_install.build_project_dir.trim().to_string();
+    config.apk_install.build_module = config
+        .apk_install
+        .build_module
+        .trim()
+        .trim_matches(['/', '\\'])
+        .to_string();
+    if config.apk_install.build_module.is_empty() {
+        config.apk_install.build_module = default_build_module();
+    }
+    config.upload.curl_path = config.upload.curl_path.trim().to_string();
+    let mut seen_targets = std::collections::HashSet::new();
+    config.upload.targets.retain_mut(|target| {
+        normalize_upload_target(target) && seen_targets.insert(target.name.clone())
+    });
+    config.device_farm.gcloud_path = config.device_farm.gcloud_path.trim().to_string();
+    config.device_farm.project_id = config.device_farm.project_id.trim().to_string();
+    if !config.device_farm.project_id.is_empty()
+        && !is_valid_project_id(&config.device_farm.project_id)
+    {
+        warn!(project_id = %config.device_farm.project_id, "invalid device farm project id, using gcloud default");
+        config.device_farm.project_id.clear();
+    }
+    config.device_farm.results_bucket = config
+        .device_farm
+        .results_bucket
+        .trim()
+        .trim_start_matches("gs://")
+        .trim_end_matches('/')
+        .to_string();
+    normalize_github_settings(&mut config.issues.github);
+    normalize_jira_settings(&mut config.issues.jira);
+    let mut seen_webhooks = std::collections::HashSet::new();
+    config.notifications.webhooks.retain_mut(|webhook| {
+        normalize_webhook(webhook) && seen_webhooks.insert(webhook.name.clone())
+    });
+    config.adb.path_overrides.retain_mut(|entry| {
+        entry.serial = entry.serial.trim().to_string();
+        entry.transport = entry.transport.trim().to_ascii_lowercase();
+        entry.command_path = normalize_command_path(&entry.command_path);
+        if !entry.serial.is_empty() {
+            entry.transport = String::new();
+        }
+        !entry.command_path.is_empty()
+            && (!entry.serial.is_empty() || ADB_TRANSPORTS.contains(&entry.transport.as_str()))
+    });
+    let mut seen_hooks = std::collections::HashSet::new();
+    config.hooks.scripts.retain_mut(|hook| {
+        if let Err(err) = normalize_hook(hook) {
+            warn!(hook = %hook.name, error = %err, "dropping invalid hook");
+            return false;
+        }
+        if !seen_hooks.insert(hook.name.clone()) {
+            warn!(hook = %hook.name, "dropping duplicate hook");
+            return false;
+        }
+        true
+    });
+    let mut seen_tools = std::collections::HashSet::new();
+    config
+        .custom_tools
+        .tools
+        .retain_mut(|tool| normalize_custom_tool(tool) && seen_tools.insert(tool.name.clone()));
+    normalize_timeouts(&mut config.timeouts);
+    let mut seen_profiles = std::collections::HashSet::new();
+    config.on_connect.profiles.retain_mut(|profile| {
+        profile.serial = profile.serial.trim().to_string();
+        let mut seen_actions = std::collections::HashSet::new();
+        profile.actions.retain_mut(|action| {
+            action.kind = action.kind.trim().to_ascii_lowercase();
+            action.value = action.value.trim().to_string();
+            ON_CONNECT_ACTIONS.contains(&action.kind.as_str())
+                && (action.kind != ACTION_SET_LOCALE || is_valid_locale(&action.value))
+                && seen_actions.insert(action.kind.clone())
+        });
+        !profile.serial.is_empty() && seen_profiles.insert(profile.serial.clone())
+    });
+    config.adb.active_server = config.adb.active_server.trim().to_string();
+    if !config
+        .adb
+        .server_hosts
+        .iter()
+        .any(|entry| entry.name == config.adb.active_server)
+    {
+        config.adb.active_server = String::new();
+    }
     config
 }
 
@@ -605,11 +1372,19 @@ mod tests {
         config.device.refresh_interval = 0;
         config.logcat.max_lines = 10;
         config.command.max_history_size = 0;
+        config.device.auto_reconnect_window_secs = 86_400;
+        config.logging.log_level = "verbose".to_string();
+        config.logging.max_log_files = 0;
+        config.logging.log_file_size_mb = 10_000;
         let validated = validate_config(config);
         assert_eq!(validated.ui.ui_scale, 1.0);
         assert_eq!(validated.device.refresh_interval, 5);
+        assert_eq!(validated.device.auto_reconnect_window_secs, 3600);
         assert_eq!(validated.logcat.max_lines, 1000);
         assert_eq!(validated.command.max_history_size, 50);
+        assert_eq!(validated.logging.log_level, "INFO");
+        assert_eq!(validated.logging.max_log_files, 1);
+        assert_eq!(validated.logging.log_file_size_mb, 500);
     }
 
     #[test]
@@ -631,6 +1406,225 @@ mod tests {
         assert_eq!(validated.screen_record.time_limit_sec, 180);
     }
 
+    #[test]
+    fn normalizes_screen_record_conversion_settings() {
+        let mut config = AppConfig::default();
+        config.screen_record.convert_format = " GIF ".to_string();
+        config.screen_record.convert_max_width = 10_000;
+        config.screen_record.convert_fps = 0;
+        let validated = validate_config(config);
+        assert_eq!(validated.screen_record.convert_format, "gif");
+        assert_eq!(validated.screen_record.convert_max_width, 1920);
+        assert_eq!(validated.screen_record.convert_fps, 1);
+
+        let mut config = AppConfig::default();
+        config.screen_record.convert_format = "avi".to_string();
+        let validated = validate_config(config);
+        assert_eq!(validated.screen_record.convert_format, "");
+
+        let parsed: AppConfig = serde_json::from_value(serde_json::json!({
+            "screen_record": {
+                "bit_rate": "4000000",
+                "time_limit_sec": 180,
+                "size": "",
+                "extra_args": "",
+                "use_hevc": false,
+                "bugreport": false,
+                "verbose": false,
+                "display_id": -1
+            }
+        }))
+        .expect("config should deserialize");
+        assert_eq!(parsed.screen_record.convert_max_width, 480);
+        assert_eq!(parsed.screen_record.convert_fps, 10);
+    }
+
+    #[test]
+    fn normalizes_adb_server_hosts() {
+        let mut config = AppConfig::default();
+        config.adb.server_hosts = vec![
+            AdbServerHost {
+                name: " lab ".to_string(),
+                host: " 10.0.0.5 ".to_string(),
+                port: 0,
+                ssh_tunnel: None,
+            },
+            AdbServerHost {
+                name: "lab".to_string(),
+                host: "10.0.0.6".to_string(),
+                port: 5037,
+                ssh_tunnel: None,
+            },
+            AdbServerHost {
+                name: "empty".to_string(),
+                host: " ".to_string(),
+                port: 5037,
+                ssh_tunnel: None,
+            },
+        ];
+        config.adb.active_server = "lab".to_string();
+        let normalized = validate_config(config);
+        assert_eq!(normalized.adb.server_hosts.len(), 1);
+        assert_eq!(normalized.adb.server_hosts[0].host, "10.0.0.5");
+        assert_eq!(normalized.adb.server_hosts[0].port, 5037);
+        assert_eq!(normalized.adb.active_server, "lab");
+
+        let mut config = normalized;
+        config.adb.active_server = "missing".to_string();
+        assert_eq!(validate_config(config).adb.active_server, "");
+    }
+
+    #[test]
+    fn normalizes_adb_path_overrides() {
+        let mut config = AppConfig::default();
+        let entry = |serial: &str, transport: &str, command_path: &str| AdbPathOverride {
+            serial: serial.to_string(),
+            transport: transport.to_string(),
+            command_path: command_path.to_string(),
+        };
+        config.adb.path_overrides = vec![
+            entry(" R58M123 ", "usb", " \"/opt/vendor/adb\" "),
+            entry("", " TCP ", "/opt/wifi/adb"),
+            entry("", "bluetooth", "/opt/bt/adb"),
+            entry("", "", "/opt/none/adb"),
+            entry("R58M999", "", " "),
+        ];
+        let normalized = validate_config(config);
+        assert_eq!(
+            normalized.adb.path_overrides,
+            vec![
+                entry("R58M123", "", "/opt/vendor/adb"),
+                entry("", "tcp", "/opt/wifi/adb"),
+            ]
+        );
+    }
+
+    #[test]
+    fn drops_unnamed_scrcpy_presets_and_stale_assignments() {
+        let mut config = AppConfig::default();
+        config.scrcpy.presets.push(ScrcpyPreset {
+            name: " ".to_string(),
+            options: ScrcpyOptions::default(),
+        });
+        config.scrcpy.presets.push(ScrcpyPreset {
+            name: "otg".to_string(),
+            options: ScrcpyOptions::default(),
+        });
+        config
+            .scrcpy
+            .device_presets
+            .insert(" A1 ".to_string(), " otg ".to_string());
+        config
+            .scrcpy
+            .device_presets
+            .insert("B2".to_string(), "missing".to_string());
+        let scrcpy = validate_config(config).scrcpy;
+        let names: Vec<&str> = scrcpy
+            .presets
+            .iter()
+            .map(|preset| preset.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["low-latency", "recording", "otg"]);
+        assert_eq!(scrcpy.presets[2].options.extra_args, "--otg");
+        assert_eq!(scrcpy.device_presets.len(), 1);
+        assert_eq!(
+            scrcpy.device_presets.get("A1").map(String::as_str),
+            Some("otg")
+        );
+    }
+
+    #[test]
+    fn normalizes_on_connect_profiles() {
+        let value = serde_json::json!({
+            "on_connect": {"profiles": [
+                {"serial": " A1 ", "enabled": true, "actions": [
+                    {"kind": " Stay_Awake "},
+                    {"kind": "set_locale", "value": "en-US; reboot"},
+                    {"kind": "set_locale", "value": " fr-FR "},
+                    {"kind": "set_locale", "value": "de-DE"},
+                    {"kind": "factory_reset"}
+                ]},
+                {"serial": "A1", "enabled": false},
+                {"serial": " ", "enabled": true}
+            ]}
+        });
+        let config: AppConfig = serde_json::from_value(value).unwrap();
+        let profiles = validate_config(config).on_connect.profiles;
+        assert_eq!(profiles.len(), 1);
+        assert_eq!(profiles[0].serial, "A1");
+        assert!(profiles[0].enabled);
+        assert_eq!(
+            profiles[0].actions,
+            vec![
+                OnConnectAction {
+                    kind: "stay_awake".to_string(),
+                    value: String::new(),
+                },
+                OnConnectAction {
+                    kind: "set_locale".to_string(),
+                    value: "fr-FR".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn drops_unusable_upload_targets() {
+        let value = serde_json::json!({
+            "upload": {"targets": [
+                {"name": " bucket ", "kind": "S3", "bucket": "qa"},
+                {"name": "bucket", "kind": "gcs", "bucket": "dup"},
+                {"name": "web", "kind": "http"},
+                {"name": "ftp", "kind": "ftp", "url": "ftp://x"}
+            ]}
+        });
+        let config: AppConfig = serde_json::from_value(value).unwrap();
+        let normalized = normalize_config_for_save(config);
+        assert_eq!(normalized.upload.targets.len(), 1);
+        assert_eq!(normalized.upload.targets[0].name, "bucket");
+        assert_eq!(normalized.upload.targets[0].kind, "s3");
+    }
+
+    #[test]
+    fn normalizes_debug_agent_settings() {
+        let value = serde_json::json!({
+            "debug_agent": {"local_path": " /opt/frida-server ", "remote_path": "/sdcard/x;y", "device_port": 0}
+        });
+        let parsed: AppConfig = serde_json::from_value(value).expect("config should deserialize");
+        let normalized = validate_config(parsed);
+        assert_eq!(normalized.debug_agent.local_path, "/opt/frida-server");
+        assert_eq!(
+            normalized.debug_agent.remote_path,
+            DEFAULT_DEBUG_AGENT_REMOTE_PATH
+        );
+        assert_eq!(normalized.debug_agent.device_port, 27042);
+        assert_eq!(normalized.debug_agent.local_port, 27042);
+    }
+
+    #[test]
+    fn normalizes_ssh_tunnel_settings() {
+        let value = serde_json::json!({
+            "adb": {
+                "command_path": "",
+                "server_hosts": [
+                    {"name": "lab", "host": "lab.local", "ssh_tunnel": {"user": " qa ", "ssh_port": 0}},
+                    {"name": "bare", "host": "10.0.0.7", "ssh_tunnel": {"user": " "}}
+                ]
+            }
+        });
+        let parsed: AppConfig = serde_json::from_value(value).expect("config should deserialize");
+        let normalized = validate_config(parsed);
+        let tunnel = normalized.adb.server_hosts[0]
+            .ssh_tunnel
+            .as_ref()
+            .expect("tunnel kept");
+        assert_eq!(tunnel.user, "qa");
+        assert_eq!(tunnel.ssh_port, 22);
+        assert_eq!(tunnel.remote_port, 5037);
+        assert_eq!(tunnel.local_port, 15037);
+        assert!(normalized.adb.server_hosts[1].ssh_tunnel.is_none());
+    }
+
     #[test]
     fn loads_device_settings_without_new_fields() {
         let value = serde_json::json!({
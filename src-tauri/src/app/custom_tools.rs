@@ -0,0 +1,459 @@
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::time::Duration;
+
+use regex::{Regex, RegexBuilder};
+use tracing::warn;
+
+use crate::app::adb::runner::CommandOutput;
+use crate::app::config::CustomTool;
+use crate::app::error::AppError;
+use crate::app::models::CustomToolRunResult;
+use crate::app::shell_pipeline::{
+    is_valid_variable_name, quote_shell_value, render_template, truncate_output,
+};
+
+pub const TOOL_KIND_ADB: &str = "adb";
+pub const TOOL_KIND_HOST: &str = "host";
+pub const TOOL_KINDS: [&str; 2] = [TOOL_KIND_ADB, TOOL_KIND_HOST];
+
+pub const INPUT_TEXT: &str = "text";
+pub const INPUT_NUMBER: &str = "number";
+pub const INPUT_BOOLEAN: &str = "boolean";
+pub const INPUT_CHOICE: &str = "choice";
+pub const INPUT_KINDS: [&str; 4] = [INPUT_TEXT, INPUT_NUMBER, INPUT_BOOLEAN, INPUT_CHOICE];
+
+pub const DEFAULT_CUSTOM_TOOL_TIMEOUT_SECS: u64 = 60;
+pub const MAX_CUSTOM_TOOL_TIMEOUT_SECS: u64 = 30 * 60;
+pub const MAX_INPUT_VALUE_LEN: usize = 1024;
+
+/// Always available to templates; inputs may not reuse the name.
+pub const SERIAL_PLACEHOLDER: &str = "serial";
+
+const MAX_PATTERN_LEN: usize = 512;
+const PATTERN_SIZE_LIMIT: usize = 1 << 20;
+
+/// `pattern` must match the whole value, not just a substring of it.
+fn compile_pattern(pattern: &str) -> Result<Regex, String> {
+    if pattern.len() > MAX_PATTERN_LEN {
+        return Err(format!(
+            "pattern is longer than {MAX_PATTERN_LEN} characters"
+        ));
+    }
+    RegexBuilder::new(&format!("^(?:{pattern})$"))
+        .size_limit(PATTERN_SIZE_LIMIT)
+        .build()
+        .map_err(|err| format!("invalid pattern: {err}"))
+}
+
+pub fn check_custom_tool(tool: &CustomTool) -> Result<(), String> {
+    if tool.name.is_empty() {
+        return Err("name is required".to_string());
+    }
+    match tool.kind.as_str() {
+        TOOL_KIND_ADB => match tool.args.first() {
+            None => return Err("adb tools need at least one argument".to_string()),
+            // `-s`, `-H` and friends would override the device the tool was run on.
+            Some(first) if first.starts_with('-') => {
+                return Err("adb tools may not start with an adb option".to_string())
+            }
+            Some(_) => {}
+        },
+        TOOL_KIND_HOST if !Path::new(&tool.program).is_absolute() => {
+            return Err("host tools need an absolute program path".to_string())
+        }
+        TOOL_KIND_HOST => {}
+        other => return Err(format!("unknown kind {other:?}")),
+    }
+    let mut names = HashSet::new();
+    for input in &tool.inputs {
+        if !is_valid_variable_name(&input.name) || input.name == SERIAL_PLACEHOLDER {
+            return Err(format!("invalid input name {:?}", input.name));
+        }
+        if !names.insert(input.name.as_str()) {
+            return Err(format!("duplicate input {}", input.name));
+        }
+        if !INPUT_KINDS.contains(&input.kind.as_str()) {
+            return Err(format!(
+                "input {} has unknown kind {:?}",
+                input.name, input.kind
+            ));
+        }
+        if input.kind == INPUT_CHOICE && input.choices.is_empty() {
+            return Err(format!("choice input {} has no choices", input.name));
+        }
+        if let Some(pattern) = &input.pattern {
+            compile_pattern(pattern).map_err(|err| format!("input {}: {err}", input.name))?;
+        }
+    }
+    Ok(())
+}
+
+/// Trim and default a configured tool; returns false (with a warning) when it cannot be used.
+pub fn normalize_custom_tool(tool: &mut CustomTool) -> bool {
+    tool.name = tool.name.trim().to_string();
+    tool.description = tool.description.trim().to_string();
+    tool.kind = tool.kind.trim().to_ascii_lowercase();
+    tool.program = tool.program.trim().to_string();
+    tool.timeout_secs = tool.timeout_secs.clamp(1, MAX_CUSTOM_TOOL_TIMEOUT_SECS);
+    for input in &mut tool.inputs {
+        input.name = input.name.trim().to_string();
+        input.label = input.label.trim().to_string();
+        input.kind = input.kind.trim().to_ascii_lowercase();
+        let mut choices = Vec::new();
+        for choice in input.choices.iter().map(|choice| choice.trim()) {
+            if !choice.is_empty() && !choices.iter().any(|seen| seen == choice) {
+                choices.push(choice.to_string());
+            }
+        }
+        input.choices = choices;
+        input.pattern = input
+            .pattern
+            .as_deref()
+            .map(str::trim)
+            .filter(|pattern| !pattern.is_empty())
+            .map(str::to_string);
+    }
+    match check_custom_tool(tool) {
+        Ok(()) => true,
+        Err(reason) => {
+            warn!(tool = %tool.name, reason = %reason, "ignoring invalid custom tool");
+            false
+        }
+    }
+}
+
+/// Check `provided` against the tool's input schema. Missing optional inputs resolve to their
+/// default, or to an empty string.
+pub fn resolve_inputs(
+    tool: &CustomTool,
+    provided: &HashMap<String, String>,
+) -> Result<HashMap<String, String>, String> {
+    if let Some(unknown) = provided
+        .keys()
+        .find(|name| !tool.inputs.iter().any(|input| &input.name == *name))
+    {
+        return Err(format!("Unknown input: {unknown}"));
+    }
+    let mut values = HashMap::new();
+    for input in &tool.inputs {
+        let value = provided
+            .get(&input.name)
+            .filter(|value| !value.is_empty())
+            .or(input.default.as_ref());
+        let Some(value) = value else {
+            if input.required {
+                return Err(format!("{} is required", input.name));
+            }
+            values.insert(input.name.clone(), String::new());
+            continue;
+        };
+        if value.len() > MAX_INPUT_VALUE_LEN {
+            return Err(format!(
+                "{} is longer than {MAX_INPUT_VALUE_LEN} characters",
+                input.name
+            ));
+        }
+        if value.chars().any(char::is_control) {
+            return Err(format!("{} may not contain control characters", input.name));
+        }
+        let value = match input.kind.as_str() {
+            INPUT_NUMBER => {
+                let value = value.trim();
+                if !value.parse::<f64>().is_ok_and(f64::is_finite) {
+                    return Err(format!("{} must be a number", input.name));
+                }
+                value.to_string()
+            }
+            INPUT_BOOLEAN => match value.trim().to_ascii_lowercase().as_str() {
+                flag @ ("true" | "false") => flag.to_string(),
+                _ => return Err(format!("{} must be true or false", input.name)),
+            },
+            INPUT_CHOICE if !input.choices.contains(value) => {
+                return Err(format!(
+                    "{} must be one of {}",
+                    input.name,
+                    input.choices.join(", ")
+                ))
+            }
+            _ => value.clone(),
+        };
+        if let Some(pattern) = &input.pattern {
+            if !compile_pattern(pattern)?.is_match(&value) {
+                return Err(format!("{} does not match {pattern}", input.name));
+            }
+        }
+        values.insert(input.name.clone(), value);
+    }
+    Ok(values)
+}
+
+/// Where the tool runs: once per selected device, or once without a device for host tools
+/// when none is selected.
+pub fn tool_targets(tool: &CustomTool, serials: &[String]) -> Result<Vec<Option<String>>, String> {
+    let mut targets: Vec<String> = Vec::new();
+    for serial in serials.iter().map(|serial| serial.trim()) {
+        if !serial.is_empty() && !targets.iter().any(|seen| seen == serial) {
+            targets.push(serial.to_string());
+        }
+    }
+    if targets.len() > 1 && !tool.multi_device {
+        return Err(format!("{} runs on one device at a time", tool.name));
+    }
+    if targets.is_empty() {
+        if tool.kind == TOOL_KIND_ADB {
+            return Err(format!("{} needs a device", tool.name));
+        }
+        return Ok(vec![None]);
+    }
+    Ok(targets.into_iter().map(Some).collect())
+}
+
+/// Argument list for one run. Values are passed verbatim as their own arguments, except after
+/// `adb shell` / `adb exec-out`, where the device shell re-parses them and they are quoted.
+pub fn build_invocation(
+    tool: &CustomTool,
+    serial: Option<&str>,
+    values: &HashMap<String, String>,
+) -> Result<Vec<String>, String> {
+    let mut variables = values.clone();
+    if let Some(serial) = serial {
+        variables.insert(SERIAL_PLACEHOLDER.to_string(), serial.to_string());
+    }
+    let mut args = Vec::new();
+    let mut quoted = false;
+    if tool.kind == TOOL_KIND_ADB {
+        let serial = serial.ok_or_else(|| format!("{} needs a device", tool.name))?;
+        args.extend(["-s".to_string(), serial.to_string()]);
+        quoted = matches!(
+            tool.args.first().map(String::as_str),
+            Some("shell" | "exec-out")
+        );
+    }
+    for (index, template) in tool.args.iter().enumerate() {
+        if quoted && index > 0 {
+            args.push(render_template(template, &variables, quote_shell_value)?);
+            continue;
+        }
+        let rendered = render_template(template, &variables, str::to_string)?;
+        // An empty optional input drops its argument instead of passing "".
+        if !rendered.is_empty() {
+            args.push(rendered);
+        }
+    }
+    Ok(args)
+}
+
+pub fn tool_timeout(tool: &CustomTool) -> Duration {
+    Duration::from_secs(tool.timeout_secs)
+}
+
+pub fn run_result(
+    serial: Option<String>,
+    result: Result<CommandOutput, AppError>,
+    duration_ms: u64,
+) -> CustomToolRunResult {
+    match result {
+        Ok(output) => CustomToolRunResult {
+            serial,
+            success: output.exit_code == Some(0),
+            exit_code: output.exit_code,
+            stdout: truncate_output(output.stdout),
+            stderr: truncate_output(output.stderr),
+            error: None,
+            duration_ms,
+        },
+        Err(err) => CustomToolRunResult {
+            serial,
+            success: false,
+            exit_code: None,
+            stdout: String::new(),
+            stderr: String::new(),
+            error: Some(err.error),
+            duration_ms,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::config::CustomToolInput;
+
+    fn input(name: &str, kind: &str) -> CustomToolInput {
+        CustomToolInput {
+            name: name.to_string(),
+            label: String::new(),
+            kind: kind.to_string(),
+            required: false,
+            default: None,
+            choices: Vec::new(),
+            pattern: None,
+        }
+    }
+
+    fn tool(kind: &str, args: &[&str], inputs: Vec<CustomToolInput>) -> CustomTool {
+        CustomTool {
+            name: "Open deeplink".to_string(),
+            description: String::new(),
+            kind: kind.to_string(),
+            program: String::new(),
+            args: args.iter().map(|arg| arg.to_string()).collect(),
+            inputs,
+            multi_device: false,
+            timeout_secs: DEFAULT_CUSTOM_TOOL_TIMEOUT_SECS,
+        }
+    }
+
+    fn provided(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn normalizes_and_rejects_unusable_tools() {
+        let mut valid = tool(
+            " ADB ",
+            &["shell", "am", "start", "-d", "${url}"],
+            vec![CustomToolInput {
+                choices: vec![" a ".to_string(), "a".to_string(), " ".to_string()],
+                pattern: Some("  ".to_string()),
+                ..input(" url ", " Choice ")
+            }],
+        );
+        valid.timeout_secs = 0;
+        assert!(normalize_custom_tool(&mut valid));
+        assert_eq!(valid.kind, TOOL_KIND_ADB);
+        assert_eq!(valid.timeout_secs, 1);
+        assert_eq!(valid.inputs[0].name, "url");
+        assert_eq!(valid.inputs[0].choices, ["a"]);
+        assert_eq!(valid.inputs[0].pattern, None);
+
+        let reject = |mut tool: CustomTool| !normalize_custom_tool(&mut tool);
+        assert!(reject(tool("adb", &[], Vec::new())));
+        assert!(reject(tool("adb", &["-s", "other", "reboot"], Vec::new())));
+        assert!(reject(tool("host", &["--version"], Vec::new())));
+        assert!(reject(tool("script", &["x"], Vec::new())));
+        assert!(reject(tool(
+            "adb",
+            &["reboot"],
+            vec![input("serial", INPUT_TEXT)]
+        )));
+        assert!(reject(tool(
+            "adb",
+            &["reboot"],
+            vec![input("mode", INPUT_CHOICE)]
+        )));
+        assert!(reject(tool(
+            "adb",
+            &["reboot"],
+            vec![input("a", INPUT_TEXT), input("a", INPUT_NUMBER)]
+        )));
+        assert!(reject(tool(
+            "adb",
+            &["reboot"],
+            vec![CustomToolInput {
+                pattern: Some("(".to_string()),
+                ..input("a", INPUT_TEXT)
+            }]
+        )));
+        let mut host = tool("host", &["${serial}"], Vec::new());
+        host.program = "/usr/local/bin/flash.sh".to_string();
+        assert!(normalize_custom_tool(&mut host));
+    }
+
+    #[test]
+    fn validates_inputs_against_the_schema() {
+        let tool = tool(
+            TOOL_KIND_ADB,
+            &["shell", "true"],
+            vec![
+                CustomToolInput {
+                    required: true,
+                    pattern: Some("[a-z]+://\\S+".to_string()),
+                    ..input("url", INPUT_TEXT)
+                },
+                CustomToolInput {
+                    default: Some("1".to_string()),
+                    ..input("count", INPUT_NUMBER)
+                },
+                input("wait", INPUT_BOOLEAN),
+                CustomToolInput {
+                    choices: vec!["debug".to_string(), "release".to_string()],
+                    ..input("variant", INPUT_CHOICE)
+                },
+            ],
+        );
+        let values = resolve_inputs(
+            &tool,
+            &provided(&[("url", "app://home"), ("wait", " TRUE ")]),
+        )
+        .unwrap();
+        assert_eq!(values["count"], "1");
+        assert_eq!(values["wait"], "true");
+        assert_eq!(values["variant"], "");
+
+        let error = |pairs: &[(&str, &str)]| resolve_inputs(&tool, &provided(pairs)).unwrap_err();
+        assert_eq!(error(&[]), "url is required");
+        assert!(error(&[("url", "app://home"), ("extra", "1")]).contains("Unknown input"));
+        assert!(error(&[("url", "home")]).contains("does not match"));
+        // The pattern must match the whole value.
+        assert!(error(&[("url", "app://home; reboot")]).contains("does not match"));
+        assert!(error(&[("url", "app://home"), ("count", "NaN")]).contains("number"));
+        assert!(error(&[("url", "app://home"), ("wait", "yes")]).contains("true or false"));
+        assert!(error(&[("url", "app://home"), ("variant", "beta")]).contains("one of"));
+        assert!(error(&[("url", "app://a\nb")]).contains("control"));
+    }
+
+    #[test]
+    fn builds_invocations_per_target() {
+        let mut shell = tool(
+            TOOL_KIND_ADB,
+            &["shell", "am", "start", "-d", "${url}"],
+            vec![input("url", INPUT_TEXT)],
+        );
+        let values = provided(&[("url", "app://x'; reboot")]);
+        assert_eq!(
+            build_invocation(&shell, Some("emulator-5554"), &values).unwrap(),
+            [
+                "-s",
+                "emulator-5554",
+                "shell",
+                "am",
+                "start",
+                "-d",
+                "'app://x'\\''; reboot'"
+            ]
+        );
+        assert!(build_invocation(&shell, None, &values).is_err());
+        assert!(tool_targets(&shell, &[]).is_err());
+        assert!(tool_targets(&shell, &["a".to_string(), "b".to_string()]).is_err());
+        shell.multi_device = true;
+        assert_eq!(
+            tool_targets(
+                &shell,
+                &[" a ".to_string(), "b".to_string(), "a".to_string()]
+            )
+            .unwrap(),
+            [Some("a".to_string()), Some("b".to_string())]
+        );
+
+        let mut host = tool(
+            TOOL_KIND_HOST,
+            &["--device=${serial}", "${flavor}", "${apk}"],
+            vec![input("flavor", INPUT_TEXT), input("apk", INPUT_TEXT)],
+        );
+        host.program = "/usr/local/bin/flash.sh".to_string();
+        let values = provided(&[("flavor", ""), ("apk", "/tmp/my app.apk")]);
+        assert_eq!(
+            build_invocation(&host, Some("R58M"), &values).unwrap(),
+            ["--device=R58M", "/tmp/my app.apk"]
+        );
+        assert!(build_invocation(&host, None, &values)
+            .unwrap_err()
+            .contains("serial"));
+        assert_eq!(tool_targets(&host, &[]).unwrap(), [None]);
+    }
+}
@@ -0,0 +1,449 @@
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde_json::Value;
+
+use crate::app::config::AppConfig;
+use crate::app::models::{DeviceFarmDevice, DeviceFarmOutcome, DeviceFarmRequest};
+
+pub const DEFAULT_GCLOUD_PATH: &str = "gcloud";
+
+pub const FARM_TEST_ROBO: &str = "robo";
+pub const FARM_TEST_INSTRUMENTATION: &str = "instrumentation";
+
+pub const DEFAULT_FARM_TIMEOUT_MINUTES: u32 = 15;
+pub const MAX_FARM_TIMEOUT_MINUTES: u32 = 60;
+pub const MAX_FARM_DEVICES: usize = 10;
+
+/// The bundle is self-contained: gcloud runs with the bundle as its working directory and
+/// the arg file refers to the copied files by name, so the folder can be re-submitted from CI.
+pub const FARM_ARGFILE_NAME: &str = "lazy_blacktea_farm.yaml";
+pub const FARM_ARGFILE_GROUP: &str = "lazy_blacktea";
+pub const FARM_APP_FILE: &str = "app.apk";
+pub const FARM_TEST_FILE: &str = "test.apk";
+pub const FARM_ROBO_SCRIPT_FILE: &str = "robo_script.json";
+
+const RESULTS_URL_PREFIX: &str = "https://console.firebase.google.com/";
+const STORAGE_URL_PREFIX: &str = "https://console.developers.google.com/storage/";
+/// Matrix setup, APK upload and queueing on top of the per-device test timeout.
+const FARM_HOST_TIMEOUT_SLACK_SECS: u64 = 30 * 60;
+
+/// Validated submission with local source files; `render_farm_argfile` writes bundle names.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FarmSpec {
+    pub test_type: String,
+    pub app_apk: PathBuf,
+    pub test_apk: Option<PathBuf>,
+    pub robo_script: Option<PathBuf>,
+    pub test_targets: Vec<String>,
+    pub devices: Vec<DeviceFarmDevice>,
+    pub timeout_minutes: u32,
+}
+
+pub fn resolve_gcloud_program(config: &AppConfig) -> String {
+    if config.device_farm.gcloud_path.is_empty() {
+        DEFAULT_GCLOUD_PATH.to_string()
+    } else {
+        config.device_farm.gcloud_path.clone()
+    }
+}
+
+/// GCP project ids: 6-30 lowercase letters, digits and hyphens, starting with a letter.
+pub fn is_valid_project_id(value: &str) -> bool {
+    (6..=30).contains(&value.len())
+        && value.starts_with(|ch: char| ch.is_ascii_lowercase())
+        && !value.ends_with('-')
+        && value
+            .chars()
+            .all(|ch| ch.is_ascii_lowercase() || ch.is_ascii_digit() || ch == '-')
+}
+
+fn is_plain_token(value: &str) -> bool {
+    !value.is_empty()
+        && value.len() <= 64
+        && value
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '_' | '-' | '.'))
+}
+
+/// Device models and versions are Test Lab catalog ids (`oriole`, `33`); locale and
+/// orientation are optional.
+pub fn normalize_farm_device(device: &DeviceFarmDevice) -> Result<DeviceFarmDevice, String> {
+    let model = device.model.trim();
+    let version = device.version.trim();
+    if !is_plain_token(model) {
+        return Err(format!("Invalid device model: {model}"));
+    }
+    if !is_plain_token(version) {
+        return Err(format!("Invalid device version: {version}"));
+    }
+    let locale = device.locale.trim();
+    if !locale.is_empty() && !is_plain_token(locale) {
+        return Err(format!("Invalid device locale: {locale}"));
+    }
+    let orientation = device.orientation.trim().to_lowercase();
+    if !matches!(orientation.as_str(), "" | "portrait" | "landscape") {
+        return Err(format!("Invalid device orientation: {orientation}"));
+    }
+    Ok(DeviceFarmDevice {
+        model: model.to_string(),
+        version: version.to_string(),
+        locale: locale.to_string(),
+        orientation,
+    })
+}
+
+fn has_extension(path: &Path, extension: &str) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case(extension))
+}
+
+/// Checks the request shape; file existence is checked when the bundle is copied.
+pub fn build_farm_spec(request: &DeviceFarmRequest) -> Result<FarmSpec, String> {
+    let test_type = request.test_type.trim().to_lowercase();
+    if test_type != FARM_TEST_ROBO && test_type != FARM_TEST_INSTRUMENTATION {
+        return Err(format!(
+            "test_type must be {FARM_TEST_ROBO} or {FARM_TEST_INSTRUMENTATION}"
+        ));
+    }
+    let optional_path = |value: &Option<String>| {
+        value
+            .as_deref()
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(PathBuf::from)
+    };
+
+    let app_apk = PathBuf::from(request.apk_path.trim());
+    if !has_extension(&app_apk, "apk") {
+        return Err("apk_path must point to an .apk file".to_string());
+    }
+    let test_apk = optional_path(&request.test_apk_path);
+    let robo_script = optional_path(&request.robo_script_path);
+    let test_targets: Vec<String> = request
+        .test_targets
+        .iter()
+        .map(|target| target.trim().to_string())
+        .filter(|target| !target.is_empty())
+        .collect();
+    if test_targets
+        .iter()
+        .any(|target| target.contains(['\n', '\r']))
+    {
+        return Err("test_targets must be single-line entries".to_string());
+    }
+
+    if test_type == FARM_TEST_INSTRUMENTATION {
+        match &test_apk {
+            Some(path) if has_extension(path, "apk") => {}
+            Some(_) => return Err("test_apk_path must point to an .apk file".to_string()),
+            None => return Err("Instrumentation runs require test_apk_path".to_string()),
+        }
+        if robo_script.is_some() {
+            return Err("robo_script_path only applies to robo runs".to_string());
+        }
+    } else {
+        if test_apk.is_some() || !test_targets.is_empty() {
+            return Err(
+                "test_apk_path and test_targets only apply to instrumentation runs".to_string(),
+            );
+        }
+        if robo_script
+            .as_ref()
+            .is_some_and(|path| !has_extension(path, "json"))
+        {
+            return Err("robo_script_path must point to a .json Robo script".to_string());
+        }
+    }
+
+    if request.devices.is_empty() {
+        return Err("At least one device is required".to_string());
+    }
+    if request.devices.len() > MAX_FARM_DEVICES {
+        return Err(format!("At most {MAX_FARM_DEVICES} devices per run"));
+    }
+    let devices = request
+        .devices
+        .iter()
+        .map(normalize_farm_device)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let timeout_minutes = request
+        .timeout_minutes
+        .unwrap_or(DEFAULT_FARM_TIMEOUT_MINUTES);
+    if !(1..=MAX_FARM_TIMEOUT_MINUTES).contains(&timeout_minutes) {
+        return Err(format!(
+            "timeout_minutes must be between 1 and {MAX_FARM_TIMEOUT_MINUTES}"
+        ));
+    }
+
+    Ok(FarmSpec {
+        test_type,
+        app_apk,
+        test_apk,
+        robo_script,
+        test_targets,
+        devices,
+        timeout_minutes,
+    })
+}
+
+fn yaml_string(value: &str) -> String {
+    // JSON strings are valid YAML double-quoted scalars.
+    Value::String(value.to_string()).to_string()
+}
+
+/// gcloud arg file (`gcloud topic arg-files`) with a single group.
+pub fn render_farm_argfile(spec: &FarmSpec, results_bucket: &str) -> String {
+    let mut lines = vec![
+        format!("{FARM_ARGFILE_GROUP}:"),
+        format!("  type: {}", yaml_string(&spec.test_type)),
+        format!("  app: {}", yaml_string(FARM_APP_FILE)),
+    ];
+    if spec.test_apk.is_some() {
+        lines.push(format!("  test: {}", yaml_string(FARM_TEST_FILE)));
+    }
+    if spec.robo_script.is_some() {
+        lines.push(format!(
+            "  robo-script: {}",
+            yaml_string(FARM_ROBO_SCRIPT_FILE)
+        ));
+    }
+    if !spec.test_targets.is_empty() {
+        lines.push("  test-targets:".to_string());
+        for target in &spec.test_targets {
+            lines.push(format!("    - {}", yaml_string(target)));
+        }
+    }
+    lines.push(format!(
+        "  timeout: {}",
+        yaml_string(&format!("{}m", spec.timeout_minutes))
+    ));
+    if !results_bucket.is_empty() {
+        lines.push(format!("  results-bucket: {}", yaml_string(results_bucket)));
+    }
+    lines.push("  device:".to_string());
+    for device in &spec.devices {
+        let mut fields = vec![
+            format!("model: {}", yaml_string(&device.model)),
+            format!("version: {}", yaml_string(&device.version)),
+        ];
+        if !device.locale.is_empty() {
+            fields.push(format!("locale: {}", yaml_string(&device.locale)));
+        }
+        if !device.orientation.is_empty() {
+            fields.push(format!("orientation: {}", yaml_string(&device.orientation)));
+        }
+        lines.push(format!("    - {{{}}}", fields.join(", ")));
+    }
+    let mut rendered = lines.join("\n");
+    rendered.push('\n');
+    rendered
+}
+
+/// Arguments after the gcloud program; run from the bundle directory.
+pub fn build_gcloud_run_args(project_id: &str) -> Vec<String> {
+    let mut args = vec![
+        "firebase".to_string(),
+        "test".to_string(),
+        "android".to_string(),
+        "run".to_string(),
+        format!("{FARM_ARGFILE_NAME}:{FARM_ARGFILE_GROUP}"),
+        "--format=json".to_string(),
+        "--quiet".to_string(),
+    ];
+    if !project_id.is_empty() {
+        args.push("--project".to_string());
+        args.push(project_id.to_string());
+    }
+    args
+}
+
+fn bracketed_url(line: &str, prefix: &str) -> Option<String> {
+    let start = line.find(&format!("[{prefix}"))? + 1;
+    let end = line[start..].find(']')? + start;
+    Some(line[start..end].to_string())
+}
+
+/// gcloud announces `Test results will be streamed to [<console url>].`
+pub fn parse_results_url(line: &str) -> Option<String> {
+    bracketed_url(line, RESULTS_URL_PREFIX)
+}
+
+/// gcloud announces `Raw results will be stored in your GCS bucket at [<url>]`.
+pub fn parse_storage_url(line: &str) -> Option<String> {
+    bracketed_url(line, STORAGE_URL_PREFIX)
+}
+
+/// Per-device outcomes from `--format=json` stdout; empty when the run never finished.
+pub fn parse_farm_outcomes(stdout: &str) -> Vec<DeviceFarmOutcome> {
+    let Ok(Value::Array(entries)) = serde_json::from_str::<Value>(stdout.trim()) else {
+        return Vec::new();
+    };
+    let text = |entry: &Value, key: &str| {
+        entry
+            .get(key)
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string()
+    };
+    entries
+        .iter()
+        .map(|entry| DeviceFarmOutcome {
+            axis: text(entry, "axis_value"),
+            outcome: text(entry, "outcome"),
+            details: text(entry, "test_details"),
+        })
+        .collect()
+}
+
+/// Overall outcome from the gcloud exit code (`gcloud topic exit-codes` for Test Lab).
+pub fn farm_exit_outcome(exit_code: Option<i32>) -> &'static str {
+    match exit_code {
+        Some(0) => "passed",
+        Some(10) => "failed",
+        Some(15) => "inconclusive",
+        Some(18) => "incompatible",
+        Some(19) => "cancelled",
+        Some(20) => "infrastructure_error",
+        _ => "error",
+    }
+}
+
+pub fn farm_host_timeout(timeout_minutes: u32) -> Duration {
+    Duration::from_secs(u64::from(timeout_minutes) * 60 + FARM_HOST_TIMEOUT_SLACK_SECS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn device(model: &str, version: &str) -> DeviceFarmDevice {
+        DeviceFarmDevice {
+            model: model.to_string(),
+            version: version.to_string(),
+            locale: String::new(),
+            orientation: String::new(),
+        }
+    }
+
+    fn request(test_type: &str) -> DeviceFarmRequest {
+        DeviceFarmRequest {
+            apk_path: "/builds/app-debug.apk".to_string(),
+            test_type: test_type.to_string(),
+            test_apk_path: None,
+            robo_script_path: None,
+            test_targets: Vec::new(),
+            devices: vec![device("oriole", "33")],
+            timeout_minutes: None,
+            output_dir: None,
+        }
+    }
+
+    #[test]
+    fn validates_requests() {
+        let spec = build_farm_spec(&request(" Robo ")).unwrap();
+        assert_eq!(spec.test_type, FARM_TEST_ROBO);
+        assert_eq!(spec.timeout_minutes, DEFAULT_FARM_TIMEOUT_MINUTES);
+
+        assert!(build_farm_spec(&request("monkey")).is_err());
+        assert!(build_farm_spec(&request(FARM_TEST_INSTRUMENTATION)).is_err());
+
+        let mut instrumentation = request(FARM_TEST_INSTRUMENTATION);
+        instrumentation.test_apk_path = Some("/builds/app-debug-androidTest.apk".to_string());
+        instrumentation.test_targets = vec![" class com.example.LoginTest ".to_string()];
+        let spec = build_farm_spec(&instrumentation).unwrap();
+        assert_eq!(spec.test_targets, vec!["class com.example.LoginTest"]);
+
+        let mut robo = request(FARM_TEST_ROBO);
+        robo.robo_script_path = Some("/macros/login.txt".to_string());
+        assert!(build_farm_spec(&robo).is_err());
+        robo.robo_script_path = None;
+        robo.devices = vec![device("oriole;rm", "33")];
+        assert!(build_farm_spec(&robo).is_err());
+        robo.devices = Vec::new();
+        assert!(build_farm_spec(&robo).is_err());
+        robo.devices = vec![device("oriole", "33")];
+        robo.timeout_minutes = Some(MAX_FARM_TIMEOUT_MINUTES + 1);
+        assert!(build_farm_spec(&robo).is_err());
+    }
+
+    #[test]
+    fn validates_project_ids() {
+        assert!(is_valid_project_id("my-project-123"));
+        assert!(!is_valid_project_id("short"));
+        assert!(!is_valid_project_id("1project"));
+        assert!(!is_valid_project_id("My-Project"));
+        assert!(!is_valid_project_id("project-"));
+    }
+
+    #[test]
+    fn renders_argfile_and_args() {
+        let mut instrumentation = request(FARM_TEST_INSTRUMENTATION);
+        instrumentation.test_apk_path = Some("/builds/test.apk".to_string());
+        instrumentation.test_targets = vec!["class com.example.\"Quoted\"".to_string()];
+        instrumentation.devices = vec![DeviceFarmDevice {
+            model: "oriole".to_string(),
+            version: "33".to_string(),
+            locale: "en_US".to_string(),
+            orientation: "Landscape".to_string(),
+        }];
+        instrumentation.timeout_minutes = Some(20);
+        let spec = build_farm_spec(&instrumentation).unwrap();
+        assert_eq!(
+            render_farm_argfile(&spec, "qa-results"),
+            "lazy_blacktea:\n  type: \"instrumentation\"\n  app: \"app.apk\"\n  test: \"test.apk\"\n  test-targets:\n    - \"class com.example.\\\"Quoted\\\"\"\n  timeout: \"20m\"\n  results-bucket: \"qa-results\"\n  device:\n    - {model: \"oriole\", version: \"33\", locale: \"en_US\", orientation: \"landscape\"}\n"
+        );
+        assert_eq!(
+            build_gcloud_run_args("my-project-123"),
+            vec![
+                "firebase",
+                "test",
+                "android",
+                "run",
+                "lazy_blacktea_farm.yaml:lazy_blacktea",
+                "--format=json",
+                "--quiet",
+                "--project",
+                "my-project-123",
+            ]
+        );
+        assert_eq!(build_gcloud_run_args("").len(), 7);
+    }
+
+    #[test]
+    fn parses_gcloud_output() {
+        assert_eq!(
+            parse_results_url(
+                "Test results will be streamed to [https://console.firebase.google.com/project/p/testlab/histories/bh.1/matrices/7]."
+            )
+            .as_deref(),
+            Some("https://console.firebase.google.com/project/p/testlab/histories/bh.1/matrices/7")
+        );
+        assert_eq!(
+            parse_results_url("Uploading [app.apk] to Firebase Test Lab..."),
+            None
+        );
+        assert_eq!(
+            parse_storage_url(
+                "Raw results will be stored in your GCS bucket at [https://console.developers.google.com/storage/browser/test-lab-x/2024/]"
+            )
+            .as_deref(),
+            Some("https://console.developers.google.com/storage/browser/test-lab-x/2024/")
+        );
+
+        let outcomes = parse_farm_outcomes(
+            r#"[{"axis_value": "oriole-33-en-portrait", "outcome": "Failed", "test_details": "1 test cases failed"}]"#,
+        );
+        assert_eq!(outcomes.len(), 1);
+        assert_eq!(outcomes[0].axis, "oriole-33-en-portrait");
+        assert_eq!(outcomes[0].outcome, "Failed");
+        assert!(parse_farm_outcomes("ERROR: (gcloud) not logged in").is_empty());
+
+        assert_eq!(farm_exit_outcome(Some(0)), "passed");
+        assert_eq!(farm_exit_outcome(Some(10)), "failed");
+        assert_eq!(farm_exit_outcome(None), "error");
+        assert_eq!(farm_host_timeout(15), Duration::from_secs(45 * 60));
+    }
+}
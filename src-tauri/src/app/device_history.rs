@@ -0,0 +1,307 @@
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use tracing::warn;
+
+use crate::app::adb::locator::transport_for_serial;
+use crate::app::config::config_path;
+use crate::app::error::AppError;
+use crate::app::i18n::Message;
+use crate::app::models::{DeviceHistory, DeviceSession, DeviceSummary};
+
+/// Older sessions are dropped per device; totals keep counting them.
+pub const MAX_SESSIONS_PER_DEVICE: usize = 100;
+
+pub type DeviceHistoryStore = BTreeMap<String, DeviceHistory>;
+
+pub fn device_history_path() -> PathBuf {
+    config_path().with_file_name(".lazy_blacktea_device_history.json")
+}
+
+pub fn load_device_history(path: &Path) -> DeviceHistoryStore {
+    let raw = match fs::read_to_string(path) {
+        Ok(raw) => raw,
+        Err(_) => return DeviceHistoryStore::new(),
+    };
+    match serde_json::from_str(&raw) {
+        Ok(store) => store,
+        Err(err) => {
+            warn!(path = %path.display(), error = %err, "device history is invalid, starting over");
+            DeviceHistoryStore::new()
+        }
+    }
+}
+
+pub fn save_device_history(
+    path: &Path,
+    store: &DeviceHistoryStore,
+    trace_id: &str,
+) -> Result<(), AppError> {
+    let payload = serde_json::to_string_pretty(store).map_err(|err| {
+        AppError::system(
+            Message::keyed("operation_failed")
+                .with("action", "serialize device history")
+                .with("detail", &err),
+            trace_id,
+        )
+    })?;
+    fs::write(path, payload).map_err(|err| {
+        AppError::system(
+            Message::keyed("operation_failed")
+                .with("action", "save device history")
+                .with("detail", &err),
+            trace_id,
+        )
+    })
+}
+
+fn close_session(history: &mut DeviceHistory, ended_at_ms: i64, interrupted: bool) {
+    let Some(session) = history
+        .sessions
+        .last_mut()
+        .filter(|session| session.disconnected_at_ms.is_none())
+    else {
+        return;
+    };
+    let duration_ms = ended_at_ms.saturating_sub(session.connected_at_ms).max(0) as u64;
+    session.disconnected_at_ms = Some(ended_at_ms);
+    session.duration_ms = duration_ms;
+    session.interrupted = interrupted;
+    history.total_connected_ms += duration_ms;
+}
+
+fn is_open(history: &DeviceHistory) -> bool {
+    history
+        .sessions
+        .last()
+        .is_some_and(|session| session.disconnected_at_ms.is_none())
+}
+
+/// Folds a track-devices snapshot into the store: ready devices open a session, devices that
+/// left the `device` state close theirs. Returns true when sessions changed.
+pub fn record_snapshot(
+    store: &mut DeviceHistoryStore,
+    snapshot: &[DeviceSummary],
+    now_ms: i64,
+) -> bool {
+    let mut changed = false;
+    let mut online = HashSet::new();
+    for device in snapshot.iter().filter(|device| device.state == "device") {
+        online.insert(device.serial.as_str());
+        let connection_type = transport_for_serial(&device.serial).to_string();
+        let history = store
+            .entry(device.serial.clone())
+            .or_insert_with(|| DeviceHistory {
+                serial: device.serial.clone(),
+                model: None,
+                connection_type: connection_type.clone(),
+                first_seen_ms: now_ms,
+                last_seen_ms: now_ms,
+                total_connected_ms: 0,
+                session_count: 0,
+                sessions: Vec::new(),
+            });
+        history.last_seen_ms = now_ms;
+        if device.model.is_some() {
+            history.model = device.model.clone();
+        }
+        if !is_open(history) {
+            history.connection_type = connection_type.clone();
+            history.session_count += 1;
+            history.sessions.push(DeviceSession {
+                connected_at_ms: now_ms,
+                disconnected_at_ms: None,
+                duration_ms: 0,
+                connection_type,
+                interrupted: false,
+            });
+            if history.sessions.len() > MAX_SESSIONS_PER_DEVICE {
+                let excess = history.sessions.len() - MAX_SESSIONS_PER_DEVICE;
+                history.sessions.drain(..excess);
+            }
+            changed = true;
+        }
+    }
+    for history in store.values_mut() {
+        if !online.contains(history.serial.as_str()) && is_open(history) {
+            close_session(history, now_ms, false);
+            history.last_seen_ms = now_ms;
+            changed = true;
+        }
+    }
+    changed
+}
+
+/// Closes every open session, e.g. because tracking stopped or the app exited without
+/// seeing the disconnect. Such sessions are flagged as interrupted.
+pub fn close_open_sessions(store: &mut DeviceHistoryStore, at_ms: impl Fn(&DeviceHistory) -> i64) {
+    for history in store.values_mut() {
+        if is_open(history) {
+            let ended_at_ms = at_ms(history);
+            close_session(history, ended_at_ms, true);
+        }
+    }
+}
+
+/// Copy of `history` whose totals include the still-open session up to `now_ms`.
+pub fn with_open_time(mut history: DeviceHistory, now_ms: i64) -> DeviceHistory {
+    if let Some(session) = history
+        .sessions
+        .last_mut()
+        .filter(|session| session.disconnected_at_ms.is_none())
+    {
+        session.duration_ms = now_ms.saturating_sub(session.connected_at_ms).max(0) as u64;
+        history.total_connected_ms += session.duration_ms;
+        history.last_seen_ms = now_ms;
+    }
+    history
+}
+
+/// Tracker-side recorder that persists whenever a session opens or closes.
+pub struct DeviceHistoryRecorder {
+    path: PathBuf,
+    store: Mutex<DeviceHistoryStore>,
+}
+
+impl DeviceHistoryRecorder {
+    /// Sessions left open by a previous run end at the time they were last seen.
+    pub fn open(path: PathBuf, trace_id: &str) -> Self {
+        let mut store = load_device_history(&path);
+        close_open_sessions(&mut store, |history| history.last_seen_ms);
+        if let Err(err) = save_device_history(&path, &store, trace_id) {
+            warn!(trace_id = %trace_id, error = %err, "failed to save device history");
+        }
+        Self {
+            path,
+            store: Mutex::new(store),
+        }
+    }
+
+    pub fn observe(&self, snapshot: &[DeviceSummary], now_ms: i64, trace_id: &str) {
+        let Ok(mut store) = self.store.lock() else {
+            warn!(trace_id = %trace_id, "device history store poisoned");
+            return;
+        };
+        if record_snapshot(&mut store, snapshot, now_ms) {
+            if let Err(err) = save_device_history(&self.path, &store, trace_id) {
+                warn!(trace_id = %trace_id, error = %err, "failed to save device history");
+            }
+        }
+    }
+
+    pub fn close(&self, now_ms: i64, trace_id: &str) {
+        let Ok(mut store) = self.store.lock() else {
+            warn!(trace_id = %trace_id, "device history store poisoned");
+            return;
+        };
+        close_open_sessions(&mut store, |_| now_ms);
+        if let Err(err) = save_device_history(&self.path, &store, trace_id) {
+            warn!(trace_id = %trace_id, error = %err, "failed to save device history");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn device(serial: &str, state: &str) -> DeviceSummary {
+        DeviceSummary {
+            serial: serial.to_string(),
+            state: state.to_string(),
+            model: Some("Pixel_7".to_string()),
+            product: None,
+            device: None,
+            transport_id: None,
+            connection_type: crate::app::adb::locator::transport_for_serial(serial).to_string(),
+            usb_port: None,
+        }
+    }
+
+    #[test]
+    fn records_sessions_from_snapshots() {
+        let mut store = DeviceHistoryStore::new();
+        assert!(record_snapshot(
+            &mut store,
+            &[
+                device("A1", "device"),
+                device("10.0.0.2:5555", "unauthorized")
+            ],
+            1_000
+        ));
+        assert!(!record_snapshot(
+            &mut store,
+            &[device("A1", "device")],
+            2_000
+        ));
+        assert!(record_snapshot(
+            &mut store,
+            &[device("A1", "offline")],
+            5_000
+        ));
+        assert!(record_snapshot(
+            &mut store,
+            &[device("A1", "device")],
+            6_000
+        ));
+
+        assert_eq!(store.len(), 1);
+        let history = &store["A1"];
+        assert_eq!(history.connection_type, "usb");
+        assert_eq!(history.model.as_deref(), Some("Pixel_7"));
+        assert_eq!(history.first_seen_ms, 1_000);
+        assert_eq!(history.session_count, 2);
+        assert_eq!(history.total_connected_ms, 4_000);
+        assert_eq!(history.sessions[0].disconnected_at_ms, Some(5_000));
+        assert!(!history.sessions[0].interrupted);
+        assert_eq!(history.sessions[1].disconnected_at_ms, None);
+
+        let live = with_open_time(history.clone(), 9_000);
+        assert_eq!(live.total_connected_ms, 7_000);
+        assert_eq!(live.sessions[1].duration_ms, 3_000);
+    }
+
+    #[test]
+    fn caps_sessions_but_keeps_totals() {
+        let mut store = DeviceHistoryStore::new();
+        for index in 0..(MAX_SESSIONS_PER_DEVICE as i64 + 5) {
+            record_snapshot(&mut store, &[device("A1", "device")], index * 10);
+            record_snapshot(&mut store, &[], index * 10 + 5);
+        }
+        let history = &store["A1"];
+        assert_eq!(history.sessions.len(), MAX_SESSIONS_PER_DEVICE);
+        assert_eq!(history.session_count, MAX_SESSIONS_PER_DEVICE as u64 + 5);
+        assert_eq!(
+            history.total_connected_ms,
+            5 * (MAX_SESSIONS_PER_DEVICE as u64 + 5)
+        );
+    }
+
+    #[test]
+    fn recorder_closes_sessions_left_open() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("history.json");
+        let recorder = DeviceHistoryRecorder::open(path.clone(), "trace");
+        recorder.observe(&[device("emulator-5554", "device")], 1_000, "trace");
+        drop(recorder);
+
+        let mut saved = load_device_history(&path);
+        saved.get_mut("emulator-5554").unwrap().last_seen_ms = 4_000;
+        save_device_history(&path, &saved, "trace").unwrap();
+
+        let recorder = DeviceHistoryRecorder::open(path.clone(), "trace");
+        let history = load_device_history(&path).remove("emulator-5554").unwrap();
+        assert_eq!(history.connection_type, "emulator");
+        assert_eq!(history.sessions[0].disconnected_at_ms, Some(4_000));
+        assert!(history.sessions[0].interrupted);
+        assert_eq!(history.total_connected_ms, 3_000);
+
+        recorder.observe(&[device("emulator-5554", "device")], 10_000, "trace");
+        recorder.close(12_000, "trace");
+        let history = load_device_history(&path).remove("emulator-5554").unwrap();
+        assert_eq!(history.session_count, 2);
+        assert_eq!(history.total_connected_ms, 5_000);
+    }
+}
@@ -0,0 +1,204 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::app::config::config_path;
+use crate::app::error::AppError;
+use crate::app::i18n::Message;
+use crate::app::models::{ChecklistItem, ChecklistItemInput, DeviceNotes};
+
+pub const MAX_NOTES_MARKDOWN_BYTES: usize = 64 * 1024;
+pub const MAX_CHECKLIST_ITEMS: usize = 200;
+pub const MAX_CHECKLIST_TEXT_CHARS: usize = 500;
+
+pub type DeviceNotesStore = BTreeMap<String, DeviceNotes>;
+
+/// Edits from separate windows read-modify-write the same file.
+static NOTES_LOCK: Mutex<()> = Mutex::new(());
+
+pub fn device_notes_path() -> PathBuf {
+    config_path().with_file_name(".lazy_blacktea_device_notes.json")
+}
+
+pub fn load_device_notes(path: &Path) -> DeviceNotesStore {
+    let raw = match fs::read_to_string(path) {
+        Ok(raw) => raw,
+        Err(_) => return DeviceNotesStore::new(),
+    };
+    match serde_json::from_str(&raw) {
+        Ok(store) => store,
+        Err(err) => {
+            warn!(path = %path.display(), error = %err, "device notes are invalid, starting over");
+            DeviceNotesStore::new()
+        }
+    }
+}
+
+fn save_device_notes_store(
+    path: &Path,
+    store: &DeviceNotesStore,
+    trace_id: &str,
+) -> Result<(), AppError> {
+    let payload = serde_json::to_string_pretty(store).map_err(|err| {
+        AppError::system(
+            Message::keyed("operation_failed")
+                .with("action", "serialize device notes")
+                .with("detail", &err),
+            trace_id,
+        )
+    })?;
+    fs::write(path, payload).map_err(|err| {
+        AppError::system(
+            Message::keyed("operation_failed")
+                .with("action", "save device notes")
+                .with("detail", &err),
+            trace_id,
+        )
+    })
+}
+
+/// Validated notes for `serial`, or `None` when both the text and the checklist are empty.
+/// Items without an id (new ones) get a fresh id; existing ids are kept so the UI can track
+/// rows across saves.
+pub fn build_device_notes(
+    serial: &str,
+    markdown: &str,
+    checklist: Vec<ChecklistItemInput>,
+    updated_at: &str,
+) -> Result<Option<DeviceNotes>, String> {
+    if markdown.len() > MAX_NOTES_MARKDOWN_BYTES {
+        return Err(format!(
+            "Notes must be at most {} KB",
+            MAX_NOTES_MARKDOWN_BYTES / 1024
+        ));
+    }
+    if checklist.len() > MAX_CHECKLIST_ITEMS {
+        return Err(format!(
+            "At most {MAX_CHECKLIST_ITEMS} checklist items are allowed"
+        ));
+    }
+    let mut items: Vec<ChecklistItem> = Vec::with_capacity(checklist.len());
+    for item in checklist {
+        let text = item.text.trim();
+        if text.is_empty() {
+            continue;
+        }
+        if text.chars().count() > MAX_CHECKLIST_TEXT_CHARS {
+            return Err(format!(
+                "Checklist items must be at most {MAX_CHECKLIST_TEXT_CHARS} characters"
+            ));
+        }
+        let id = item
+            .id
+            .map(|id| id.trim().to_string())
+            .filter(|id| !id.is_empty() && !items.iter().any(|existing| existing.id == *id))
+            .unwrap_or_else(|| Uuid::new_v4().to_string());
+        items.push(ChecklistItem {
+            id,
+            text: text.to_string(),
+            done: item.done,
+        });
+    }
+    let markdown = markdown.trim_end();
+    if markdown.trim().is_empty() && items.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(DeviceNotes {
+        serial: serial.to_string(),
+        markdown: markdown.to_string(),
+        checklist: items,
+        updated_at: updated_at.to_string(),
+    }))
+}
+
+/// Replace the notes for `serial`; `None` removes them.
+pub fn store_device_notes(
+    path: &Path,
+    serial: &str,
+    notes: Option<DeviceNotes>,
+    trace_id: &str,
+) -> Result<(), AppError> {
+    let _guard = NOTES_LOCK
+        .lock()
+        .map_err(|_| AppError::system("Device notes locked", trace_id))?;
+    let mut store = load_device_notes(path);
+    let changed = match notes {
+        Some(notes) => {
+            store.insert(serial.to_string(), notes);
+            true
+        }
+        None => store.remove(serial).is_some(),
+    };
+    if changed {
+        save_device_notes_store(path, &store, trace_id)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(id: Option<&str>, text: &str, done: bool) -> ChecklistItemInput {
+        ChecklistItemInput {
+            id: id.map(str::to_string),
+            text: text.to_string(),
+            done,
+        }
+    }
+
+    #[test]
+    fn builds_and_validates_notes() {
+        let notes = build_device_notes(
+            "A1",
+            "Cracked digitizer, **no touch tests**\n\n",
+            vec![
+                item(Some("keep"), " Replace screen ", false),
+                item(Some("keep"), "Duplicate id", true),
+                item(None, "   ", true),
+            ],
+            "2024-05-01T00:00:00Z",
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(notes.markdown, "Cracked digitizer, **no touch tests**");
+        assert_eq!(notes.checklist.len(), 2);
+        assert_eq!(notes.checklist[0].id, "keep");
+        assert_eq!(notes.checklist[0].text, "Replace screen");
+        assert_ne!(notes.checklist[1].id, "keep");
+        assert!(notes.checklist[1].done);
+
+        assert_eq!(build_device_notes("A1", "  ", vec![], "now").unwrap(), None);
+        assert!(build_device_notes(
+            "A1",
+            &"x".repeat(MAX_NOTES_MARKDOWN_BYTES + 1),
+            vec![],
+            "now"
+        )
+        .is_err());
+        assert!(build_device_notes(
+            "A1",
+            "",
+            vec![item(None, &"x".repeat(MAX_CHECKLIST_TEXT_CHARS + 1), false)],
+            "now"
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn stores_and_removes_notes() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("notes.json");
+        let notes = build_device_notes("A1", "Battery swollen", vec![], "now")
+            .unwrap()
+            .unwrap();
+        store_device_notes(&path, "A1", Some(notes.clone()), "trace").unwrap();
+        assert_eq!(load_device_notes(&path).get("A1"), Some(&notes));
+        store_device_notes(&path, "A1", None, "trace").unwrap();
+        assert!(load_device_notes(&path).is_empty());
+    }
+}
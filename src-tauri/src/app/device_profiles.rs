@@ -0,0 +1,342 @@
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use sha2::{Digest, Sha256};
+use tracing::warn;
+
+use crate::app::adb::paths::sanitize_filename_component;
+use crate::app::config::config_path;
+use crate::app::error::AppError;
+use crate::app::i18n::Message;
+use crate::app::models::{
+    DeviceProfile, DeviceProfileApplyReport, DeviceProfileSetting, DeviceProfileStep,
+};
+use crate::app::shell_pipeline::quote_shell_value;
+
+pub const STEP_APPLIED: &str = "applied";
+pub const STEP_SKIPPED: &str = "skipped";
+pub const STEP_FAILED: &str = "failed";
+
+pub const STEP_KIND_APP: &str = "app";
+pub const STEP_KIND_SETTING: &str = "setting";
+pub const STEP_KIND_FILE: &str = "file";
+
+pub const SETTINGS_NAMESPACES: [&str; 3] = ["system", "secure", "global"];
+
+const PROFILE_FILE: &str = "profile.json";
+pub const PROFILE_APPS_DIR: &str = "apps";
+pub const PROFILE_FILES_DIR: &str = "files";
+
+/// Profiles live at `<store>/<name>/` with `profile.json`, the APKs under `apps/<package>/`
+/// and pushed assets under `files/`.
+pub fn device_profiles_dir() -> PathBuf {
+    config_path().with_file_name(".lazy_blacktea_device_profiles")
+}
+
+/// Profile names become directory names: 1-64 of `[A-Za-z0-9_.-]`, not starting with a dot.
+pub fn validate_profile_name(value: &str) -> Result<String, String> {
+    let name = value.trim();
+    if name.is_empty() || name.len() > 64 {
+        return Err("name must be 1 to 64 characters".to_string());
+    }
+    if name.starts_with('.')
+        || !name
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '_' | '-' | '.'))
+    {
+        return Err("name may only contain letters, digits, '_', '-' and '.'".to_string());
+    }
+    Ok(name.to_string())
+}
+
+pub fn validate_setting_key(namespace: &str, key: &str) -> Result<(), String> {
+    if !SETTINGS_NAMESPACES.contains(&namespace) {
+        return Err(format!(
+            "Settings namespace must be one of {}",
+            SETTINGS_NAMESPACES.join(", ")
+        ));
+    }
+    if key.is_empty()
+        || key.len() > 128
+        || !key
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '_' | '.' | '-' | ':'))
+    {
+        return Err(format!("Invalid settings key: {key}"));
+    }
+    Ok(())
+}
+
+/// Device paths are stored flat as `<index>_<file name>` so two assets with the same name in
+/// different directories do not collide.
+pub fn stored_file_name(index: usize, device_path: &str) -> String {
+    let name = device_path
+        .trim_end_matches('/')
+        .rsplit('/')
+        .next()
+        .unwrap_or_default();
+    format!("{index:03}_{}", sanitize_filename_component(name))
+}
+
+/// `settings get` prints `null` for keys that are not set.
+pub fn parse_settings_value(stdout: &str) -> Option<String> {
+    let value = stdout.trim_end_matches(['\r', '\n']);
+    if value == "null" {
+        None
+    } else {
+        Some(value.to_string())
+    }
+}
+
+/// `settings put`, or `settings delete` for a key that was unset on the source device.
+pub fn setting_shell_command(setting: &DeviceProfileSetting) -> String {
+    match &setting.value {
+        Some(value) => format!(
+            "settings put {} {} {}",
+            setting.namespace,
+            setting.key,
+            quote_shell_value(value)
+        ),
+        None => format!("settings delete {} {}", setting.namespace, setting.key),
+    }
+}
+
+/// First hex digest in `sha256sum` output, or `None` when the file or the tool is missing.
+pub fn parse_sha256sum(stdout: &str) -> Option<String> {
+    stdout
+        .split_whitespace()
+        .next()
+        .filter(|digest| digest.len() == 64 && digest.chars().all(|ch| ch.is_ascii_hexdigit()))
+        .map(str::to_ascii_lowercase)
+}
+
+pub fn sha256_file(path: &Path) -> std::io::Result<String> {
+    let mut file = File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; 64 * 1024];
+    loop {
+        let count = file.read(&mut buffer)?;
+        if count == 0 {
+            break;
+        }
+        hasher.update(&buffer[..count]);
+    }
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect())
+}
+
+pub fn profile_step(kind: &str, target: &str, status: &str, detail: String) -> DeviceProfileStep {
+    DeviceProfileStep {
+        kind: kind.to_string(),
+        target: target.to_string(),
+        status: status.to_string(),
+        detail,
+    }
+}
+
+pub fn build_apply_report(
+    profile_name: &str,
+    serial: &str,
+    steps: Vec<DeviceProfileStep>,
+) -> DeviceProfileApplyReport {
+    let count = |status: &str| steps.iter().filter(|step| step.status == status).count();
+    DeviceProfileApplyReport {
+        profile_name: profile_name.to_string(),
+        serial: serial.to_string(),
+        applied: count(STEP_APPLIED),
+        skipped: count(STEP_SKIPPED),
+        failed: count(STEP_FAILED),
+        steps,
+    }
+}
+
+pub fn save_profile(dir: &Path, profile: &DeviceProfile, trace_id: &str) -> Result<(), AppError> {
+    let payload = serde_json::to_string_pretty(profile).map_err(|err| {
+        AppError::system(
+            Message::keyed("operation_failed")
+                .with("action", "serialize device profile")
+                .with("detail", &err),
+            trace_id,
+        )
+    })?;
+    fs::write(dir.join(PROFILE_FILE), payload).map_err(|err| {
+        AppError::system(
+            Message::keyed("operation_failed")
+                .with("action", "save device profile")
+                .with("detail", &err),
+            trace_id,
+        )
+    })
+}
+
+pub fn load_profile(store: &Path, name: &str, trace_id: &str) -> Result<DeviceProfile, AppError> {
+    let path = store.join(name).join(PROFILE_FILE);
+    let raw = fs::read_to_string(&path).map_err(|_| {
+        AppError::validation(
+            Message::keyed("not_found_value")
+                .with("subject", "Device profile")
+                .with("value", name),
+            trace_id,
+        )
+    })?;
+    serde_json::from_str(&raw).map_err(|err| {
+        AppError::system(format!("Device profile {name} is invalid: {err}"), trace_id)
+    })
+}
+
+/// Every readable profile, sorted by name. Half-written captures (hidden directories) and
+/// invalid profiles are skipped.
+pub fn list_profiles(store: &Path) -> Vec<DeviceProfile> {
+    let Ok(entries) = fs::read_dir(store) else {
+        return Vec::new();
+    };
+    let mut profiles: Vec<DeviceProfile> = entries
+        .flatten()
+        .filter(|entry| !entry.file_name().to_string_lossy().starts_with('.'))
+        .filter_map(|entry| {
+            let path = entry.path().join(PROFILE_FILE);
+            let raw = fs::read_to_string(&path).ok()?;
+            serde_json::from_str(&raw)
+                .map_err(|err| {
+                    warn!(path = %path.display(), error = %err, "device profile is invalid");
+                })
+                .ok()
+        })
+        .collect();
+    profiles.sort_by(|a, b| a.name.cmp(&b.name));
+    profiles
+}
+
+/// Captures are written to a hidden sibling directory first and swapped in on success, so a
+/// failed capture never destroys the previous profile of the same name.
+pub fn staging_dir(store: &Path, name: &str) -> PathBuf {
+    store.join(format!(".{name}.capture"))
+}
+
+pub fn commit_staged_profile(store: &Path, name: &str, trace_id: &str) -> Result<(), AppError> {
+    let target = store.join(name);
+    if target.exists() {
+        fs::remove_dir_all(&target).map_err(|err| {
+            AppError::system(
+                Message::keyed("operation_failed")
+                    .with("action", "replace device profile")
+                    .with("detail", &err),
+                trace_id,
+            )
+        })?;
+    }
+    fs::rename(staging_dir(store, name), &target).map_err(|err| {
+        AppError::system(
+            Message::keyed("operation_failed")
+                .with("action", "store device profile")
+                .with("detail", &err),
+            trace_id,
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::models::DeviceProfileApp;
+
+    #[test]
+    fn validates_names_and_settings_keys() {
+        assert_eq!(
+            validate_profile_name(" demo-unit_1.2 ").unwrap(),
+            "demo-unit_1.2"
+        );
+        assert!(validate_profile_name(".hidden").is_err());
+        assert!(validate_profile_name("../escape").is_err());
+        assert!(validate_profile_name("").is_err());
+
+        assert!(validate_setting_key("global", "window_animation_scale").is_ok());
+        assert!(validate_setting_key("vendor", "x").is_err());
+        assert!(validate_setting_key("secure", "key; reboot").is_err());
+    }
+
+    #[test]
+    fn builds_setting_commands_and_parses_device_output() {
+        let put = DeviceProfileSetting {
+            namespace: "system".to_string(),
+            key: "font_scale".to_string(),
+            value: Some("1.3".to_string()),
+        };
+        assert_eq!(
+            setting_shell_command(&put),
+            "settings put system font_scale '1.3'"
+        );
+        let delete = DeviceProfileSetting { value: None, ..put };
+        assert_eq!(
+            setting_shell_command(&delete),
+            "settings delete system font_scale"
+        );
+
+        assert_eq!(parse_settings_value("null\n"), None);
+        assert_eq!(parse_settings_value("0.5\r\n").as_deref(), Some("0.5"));
+        assert_eq!(parse_settings_value("\n").as_deref(), Some(""));
+
+        let digest = "E3B0C44298FC1C149AFBF4C8996FB92427AE41E4649B934CA495991B7852B855";
+        assert_eq!(
+            parse_sha256sum(&format!("{digest}  /sdcard/a.bin\n")),
+            Some(digest.to_ascii_lowercase())
+        );
+        assert_eq!(
+            parse_sha256sum("sha256sum: /sdcard/a.bin: No such file"),
+            None
+        );
+        assert_eq!(
+            stored_file_name(2, "/sdcard/Demo Assets/intro.mp4"),
+            "002_intro.mp4"
+        );
+    }
+
+    #[test]
+    fn stores_profiles_and_summarizes_apply_steps() {
+        let store = std::env::temp_dir().join(format!("lb_profiles_{}", uuid::Uuid::new_v4()));
+        let profile = DeviceProfile {
+            name: "demo".to_string(),
+            source_serial: "R58M12345".to_string(),
+            created_at: "2026-01-01T00:00:00Z".to_string(),
+            apps: vec![DeviceProfileApp {
+                package_name: "com.example.demo".to_string(),
+                version_name: Some("1.0".to_string()),
+                version_code: Some("10".to_string()),
+                apk_files: vec!["base.apk".to_string()],
+            }],
+            settings: Vec::new(),
+            files: Vec::new(),
+        };
+        fs::create_dir_all(staging_dir(&store, "demo")).unwrap();
+        save_profile(&staging_dir(&store, "demo"), &profile, "trace").unwrap();
+        assert!(list_profiles(&store).is_empty());
+        commit_staged_profile(&store, "demo", "trace").unwrap();
+        assert_eq!(list_profiles(&store), vec![profile.clone()]);
+        assert_eq!(load_profile(&store, "demo", "trace").unwrap(), profile);
+        assert!(load_profile(&store, "other", "trace").is_err());
+
+        let hashed = store.join("demo").join(PROFILE_FILE);
+        assert_eq!(sha256_file(&hashed).unwrap().len(), 64);
+        fs::remove_dir_all(&store).unwrap();
+
+        let report = build_apply_report(
+            "demo",
+            "emulator-5554",
+            vec![
+                profile_step(
+                    STEP_KIND_APP,
+                    "com.example.demo",
+                    STEP_APPLIED,
+                    String::new(),
+                ),
+                profile_step(STEP_KIND_SETTING, "global/x", STEP_SKIPPED, String::new()),
+                profile_step(STEP_KIND_FILE, "/sdcard/a", STEP_SKIPPED, String::new()),
+            ],
+        );
+        assert_eq!((report.applied, report.skipped, report.failed), (1, 2, 0));
+    }
+}
@@ -0,0 +1,308 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use chrono::{DateTime, Duration, Utc};
+use tracing::warn;
+
+use crate::app::config::config_path;
+use crate::app::error::AppError;
+use crate::app::i18n::Message;
+use crate::app::models::DeviceReservation;
+
+pub const DEVICE_RESERVATION_CONFLICT_EVENT: &str = "device-reservation-conflict";
+pub const MAX_RESERVATION_DAYS: i64 = 30;
+pub const MAX_RESERVATION_USER_CHARS: usize = 64;
+
+pub type DeviceReservationStore = BTreeMap<String, DeviceReservation>;
+
+/// Several windows (or people on one workstation) read-modify-write the same file.
+static RESERVATIONS_LOCK: Mutex<()> = Mutex::new(());
+
+pub fn device_reservations_path() -> PathBuf {
+    config_path().with_file_name(".lazy_blacktea_device_reservations.json")
+}
+
+pub fn load_device_reservations(path: &Path) -> DeviceReservationStore {
+    let raw = match fs::read_to_string(path) {
+        Ok(raw) => raw,
+        Err(_) => return DeviceReservationStore::new(),
+    };
+    match serde_json::from_str(&raw) {
+        Ok(store) => store,
+        Err(err) => {
+            warn!(path = %path.display(), error = %err, "device reservations are invalid, starting over");
+            DeviceReservationStore::new()
+        }
+    }
+}
+
+fn save_device_reservations_store(
+    path: &Path,
+    store: &DeviceReservationStore,
+    trace_id: &str,
+) -> Result<(), AppError> {
+    let payload = serde_json::to_string_pretty(store).map_err(|err| {
+        AppError::system(
+            Message::keyed("operation_failed")
+                .with("action", "serialize device reservations")
+                .with("detail", &err),
+            trace_id,
+        )
+    })?;
+    fs::write(path, payload).map_err(|err| {
+        AppError::system(
+            Message::keyed("operation_failed")
+                .with("action", "save device reservations")
+                .with("detail", &err),
+            trace_id,
+        )
+    })
+}
+
+pub fn normalize_reservation_user(user: &str) -> Result<String, String> {
+    let user = user.trim();
+    if user.is_empty() {
+        return Err("user is required".to_string());
+    }
+    if user.chars().count() > MAX_RESERVATION_USER_CHARS {
+        return Err(format!(
+            "user must be at most {MAX_RESERVATION_USER_CHARS} characters"
+        ));
+    }
+    if user.chars().any(char::is_control) {
+        return Err("user must not contain control characters".to_string());
+    }
+    Ok(user.to_string())
+}
+
+/// `until` as RFC 3339; it must be in the future and at most `MAX_RESERVATION_DAYS` ahead.
+pub fn parse_reservation_until(until: &str, now: DateTime<Utc>) -> Result<DateTime<Utc>, String> {
+    let until = DateTime::parse_from_rfc3339(until.trim())
+        .map_err(|err| format!("until must be an RFC 3339 timestamp: {err}"))?
+        .with_timezone(&Utc);
+    if until <= now {
+        return Err("until must be in the future".to_string());
+    }
+    if until > now + Duration::days(MAX_RESERVATION_DAYS) {
+        return Err(format!(
+            "Reservations can last at most {MAX_RESERVATION_DAYS} days"
+        ));
+    }
+    Ok(until)
+}
+
+pub fn is_reservation_active(reservation: &DeviceReservation, now: DateTime<Utc>) -> bool {
+    DateTime::parse_from_rfc3339(&reservation.until)
+        .is_ok_and(|until| until.with_timezone(&Utc) > now)
+}
+
+pub fn same_reservation_user(left: &str, right: &str) -> bool {
+    left.trim().eq_ignore_ascii_case(right.trim())
+}
+
+pub fn active_reservation<'a>(
+    store: &'a DeviceReservationStore,
+    serial: &str,
+    now: DateTime<Utc>,
+) -> Option<&'a DeviceReservation> {
+    store
+        .get(serial)
+        .filter(|reservation| is_reservation_active(reservation, now))
+}
+
+/// Active reservations on `serials` held by someone other than `user`.
+pub fn reservation_conflicts(
+    store: &DeviceReservationStore,
+    serials: &[String],
+    user: &str,
+    now: DateTime<Utc>,
+) -> Vec<DeviceReservation> {
+    serials
+        .iter()
+        .filter_map(|serial| active_reservation(store, serial, now))
+        .filter(|reservation| !same_reservation_user(&reservation.user, user))
+        .cloned()
+        .collect()
+}
+
+/// Who this workstation acts as: the configured name, else the OS account.
+pub fn current_reservation_user(configured: &str) -> String {
+    let configured = configured.trim();
+    if !configured.is_empty() {
+        return configured.to_string();
+    }
+    ["USER", "USERNAME"]
+        .iter()
+        .filter_map(|key| std::env::var(key).ok())
+        .map(|value| value.trim().to_string())
+        .find(|value| !value.is_empty())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+fn held_by_other<'a>(
+    store: &'a DeviceReservationStore,
+    serial: &str,
+    user: &str,
+    now: DateTime<Utc>,
+) -> Option<&'a DeviceReservation> {
+    active_reservation(store, serial, now)
+        .filter(|reservation| !same_reservation_user(&reservation.user, user))
+}
+
+fn conflict_message(reservation: &DeviceReservation) -> String {
+    format!(
+        "{} is reserved by {} until {}",
+        reservation.serial, reservation.user, reservation.until
+    )
+}
+
+/// Create or extend a reservation. Someone else's active reservation is only replaced with
+/// `force`; expired entries are dropped on every write.
+pub fn store_device_reservation(
+    path: &Path,
+    reservation: DeviceReservation,
+    force: bool,
+    now: DateTime<Utc>,
+    trace_id: &str,
+) -> Result<DeviceReservation, AppError> {
+    let _guard = RESERVATIONS_LOCK
+        .lock()
+        .map_err(|_| AppError::system("Device reservations locked", trace_id))?;
+    let mut store = load_device_reservations(path);
+    store.retain(|_, existing| is_reservation_active(existing, now));
+    if let Some(existing) = held_by_other(&store, &reservation.serial, &reservation.user, now) {
+        if !force {
+            return Err(AppError::validation(conflict_message(existing), trace_id));
+        }
+        warn!(trace_id = %trace_id, serial = %existing.serial, holder = %existing.user, "overriding device reservation");
+    }
+    store.insert(reservation.serial.clone(), reservation.clone());
+    save_device_reservations_store(path, &store, trace_id)?;
+    Ok(reservation)
+}
+
+/// Drop the reservation on `serial`. Returns whether an active one was removed.
+pub fn remove_device_reservation(
+    path: &Path,
+    serial: &str,
+    user: &str,
+    force: bool,
+    now: DateTime<Utc>,
+    trace_id: &str,
+) -> Result<bool, AppError> {
+    let _guard = RESERVATIONS_LOCK
+        .lock()
+        .map_err(|_| AppError::system("Device reservations locked", trace_id))?;
+    let mut store = load_device_reservations(path);
+    if let Some(existing) = held_by_other(&store, serial, user, now) {
+        if !force {
+            return Err(AppError::validation(conflict_message(existing), trace_id));
+        }
+        warn!(trace_id = %trace_id, serial = %serial, holder = %existing.user, "releasing another user's device reservation");
+    }
+    let removed = store
+        .remove(serial)
+        .is_some_and(|existing| is_reservation_active(&existing, now));
+    store.retain(|_, existing| is_reservation_active(existing, now));
+    save_device_reservations_store(path, &store, trace_id)?;
+    Ok(removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(value: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(value)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    fn reservation(serial: &str, user: &str, until: &str) -> DeviceReservation {
+        DeviceReservation {
+            serial: serial.to_string(),
+            user: user.to_string(),
+            until: until.to_string(),
+            reserved_at: "2024-05-01T08:00:00Z".to_string(),
+        }
+    }
+
+    #[test]
+    fn validates_user_and_until() {
+        let now = at("2024-05-01T09:00:00Z");
+        assert_eq!(normalize_reservation_user(" alice ").unwrap(), "alice");
+        assert!(normalize_reservation_user("  ").is_err());
+        assert!(normalize_reservation_user("a\nb").is_err());
+        assert_eq!(
+            parse_reservation_until("2024-05-01T19:00:00+08:00", now).unwrap(),
+            at("2024-05-01T11:00:00Z")
+        );
+        assert!(parse_reservation_until("2024-05-01T08:00:00Z", now).is_err());
+        assert!(parse_reservation_until("2024-07-01T08:00:00Z", now).is_err());
+        assert!(parse_reservation_until("tomorrow", now).is_err());
+    }
+
+    #[test]
+    fn reports_conflicts_for_other_users_only() {
+        let now = at("2024-05-01T09:00:00Z");
+        let store = DeviceReservationStore::from([
+            (
+                "A1".to_string(),
+                reservation("A1", "Alice", "2024-05-01T12:00:00Z"),
+            ),
+            (
+                "B2".to_string(),
+                reservation("B2", "bob", "2024-05-01T12:00:00Z"),
+            ),
+            (
+                "C3".to_string(),
+                reservation("C3", "bob", "2024-05-01T08:30:00Z"),
+            ),
+        ]);
+        let serials = ["A1", "B2", "C3", "D4"].map(str::to_string);
+        let conflicts = reservation_conflicts(&store, &serials, "alice", now);
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].serial, "B2");
+        assert_eq!(current_reservation_user(" lab-7 "), "lab-7");
+    }
+
+    #[test]
+    fn reserves_and_releases_with_conflict_checks() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("reservations.json");
+        let now = at("2024-05-01T09:00:00Z");
+        store_device_reservation(
+            &path,
+            reservation("A1", "alice", "2024-05-01T12:00:00Z"),
+            false,
+            now,
+            "trace",
+        )
+        .unwrap();
+        assert!(store_device_reservation(
+            &path,
+            reservation("A1", "bob", "2024-05-01T13:00:00Z"),
+            false,
+            now,
+            "trace",
+        )
+        .is_err());
+        assert!(remove_device_reservation(&path, "A1", "bob", false, now, "trace").is_err());
+
+        // Once Alice's slot is over, Bob can take the device without forcing.
+        let later = at("2024-05-01T12:30:00Z");
+        store_device_reservation(
+            &path,
+            reservation("A1", "bob", "2024-05-01T15:00:00Z"),
+            false,
+            later,
+            "trace",
+        )
+        .unwrap();
+        assert_eq!(load_device_reservations(&path)["A1"].user, "bob");
+        assert!(remove_device_reservation(&path, "A1", "alice", true, later, "trace").unwrap());
+        assert!(load_device_reservations(&path).is_empty());
+    }
+}
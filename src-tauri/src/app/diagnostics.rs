@@ -1,9 +1,11 @@
 use crate::app::adb::parse::parse_adb_devices;
 use crate::app::adb::paths::sanitize_filename_component;
 use crate::app::adb::runner::run_adb;
+use crate::app::app_cache::{icon_cache_dirs, icon_cache_stats, ICON_CACHE_MAX_BYTES};
 use crate::app::config::{load_config, AppConfig};
 use crate::app::error::AppError;
-use crate::app::models::DeviceSummary;
+use crate::app::i18n::Message;
+use crate::app::models::{DeviceSummary, IconCacheStats};
 use chrono::Utc;
 use serde::Serialize;
 use std::fs;
@@ -35,6 +37,7 @@ struct DiagnosticsPayload {
     manifest: DiagnosticsManifest,
     command_history: Vec<String>,
     devices: DevicesPayload,
+    icon_cache: IconCacheStats,
 }
 
 fn resolve_output_dir(
@@ -77,8 +80,14 @@ pub fn export_diagnostics_bundle(
 
     let resolved_dir = resolve_output_dir(config.as_ref(), output_dir)
         .map_err(|message| AppError::validation(message, trace_id))?;
-    fs::create_dir_all(&resolved_dir)
-        .map_err(|err| AppError::system(format!("Failed to create output dir: {err}"), trace_id))?;
+    fs::create_dir_all(&resolved_dir).map_err(|err| {
+        AppError::system(
+            Message::keyed("operation_failed")
+                .with("action", "create output dir")
+                .with("detail", &err),
+            trace_id,
+        )
+    })?;
 
     let timestamp = Utc::now().format("%Y%m%d_%H%M%S").to_string();
     let safe_trace = sanitize_filename_component(trace_id);
@@ -129,24 +138,52 @@ pub fn export_diagnostics_bundle(
         manifest,
         command_history,
         devices: devices_payload,
+        icon_cache: icon_cache_stats(&icon_cache_dirs(), ICON_CACHE_MAX_BYTES),
     };
 
     let json = serde_json::to_vec_pretty(&payload).map_err(|err| {
         AppError::system(
-            format!("Failed to serialize diagnostics payload: {err}"),
+            Message::keyed("operation_failed")
+                .with("action", "serialize diagnostics payload")
+                .with("detail", &err),
             trace_id,
         )
     })?;
 
-    let file = fs::File::create(&bundle_path)
-        .map_err(|err| AppError::system(format!("Failed to create bundle: {err}"), trace_id))?;
+    let file = fs::File::create(&bundle_path).map_err(|err| {
+        AppError::system(
+            Message::keyed("operation_failed")
+                .with("action", "create bundle")
+                .with("detail", &err),
+            trace_id,
+        )
+    })?;
     let mut zip = zip::ZipWriter::new(file);
     zip.start_file("diagnostics.json", FileOptions::<()>::default())
-        .map_err(|err| AppError::system(format!("Failed to write bundle: {err}"), trace_id))?;
-    zip.write_all(&json)
-        .map_err(|err| AppError::system(format!("Failed to write bundle: {err}"), trace_id))?;
-    zip.finish()
-        .map_err(|err| AppError::system(format!("Failed to finalize bundle: {err}"), trace_id))?;
+        .map_err(|err| {
+            AppError::system(
+                Message::keyed("operation_failed")
+                    .with("action", "write bundle")
+                    .with("detail", &err),
+                trace_id,
+            )
+        })?;
+    zip.write_all(&json).map_err(|err| {
+        AppError::system(
+            Message::keyed("operation_failed")
+                .with("action", "write bundle")
+                .with("detail", &err),
+            trace_id,
+        )
+    })?;
+    zip.finish().map_err(|err| {
+        AppError::system(
+            Message::keyed("operation_failed")
+                .with("action", "finalize bundle")
+                .with("detail", &err),
+            trace_id,
+        )
+    })?;
 
     Ok(bundle_path)
 }
@@ -192,6 +229,7 @@ mod tests {
         assert!(content.contains("\"command_history\""));
         assert!(content.contains("echo 1"));
         assert!(content.contains("\"trace_id\""));
+        assert!(content.contains("\"icon_cache\""));
 
         std::env::remove_var("LAZY_BLACKTEA_CONFIG_PATH");
     }
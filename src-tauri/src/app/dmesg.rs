@@ -0,0 +1,122 @@
+use crate::app::adb::runner::CommandOutput;
+use crate::app::models::DmesgLine;
+
+pub const DMESG_EVENT_NAME: &str = "dmesg-line";
+
+/// `dmesg` readable by the shell user, i.e. `adb root` or `kernel.dmesg_restrict=0`.
+pub const DMESG_MODE_DIRECT: &str = "direct";
+/// Rooted builds where only `su` can read the kernel log.
+pub const DMESG_MODE_SU: &str = "su";
+
+/// Shell words for a cheap "can we read the kernel log" check. Output goes to /dev/null so a
+/// large ring buffer is not copied over adb.
+pub fn dmesg_probe_args(mode: &str) -> Vec<String> {
+    match mode {
+        DMESG_MODE_SU => vec![
+            "su".to_string(),
+            "-c".to_string(),
+            "'dmesg >/dev/null'".to_string(),
+        ],
+        _ => vec!["dmesg >/dev/null".to_string()],
+    }
+}
+
+/// `dmesg -w` prints the existing buffer and then follows new messages.
+pub fn dmesg_stream_args(mode: &str) -> Vec<String> {
+    match mode {
+        DMESG_MODE_SU => vec!["su".to_string(), "-c".to_string(), "'dmesg -w'".to_string()],
+        _ => vec!["dmesg".to_string(), "-w".to_string()],
+    }
+}
+
+/// A probe passed when it exited cleanly without `klogctl: Permission denied` (or `su`
+/// refusing) in its output.
+pub fn dmesg_probe_succeeded(output: &CommandOutput) -> bool {
+    let combined = format!("{}{}", output.stdout, output.stderr).to_lowercase();
+    output.exit_code == Some(0)
+        && !combined.contains("permission denied")
+        && !combined.contains("not found")
+        && !combined.contains("not allowed")
+}
+
+/// `[  123.456789] msg` or `<6>[  123.456789] msg`; lines without a timestamp (wrapped
+/// continuation lines, stderr) are kept with only the message.
+pub fn parse_dmesg_line(line: &str) -> DmesgLine {
+    let mut rest = line.trim_end();
+    let mut level = None;
+    if let Some(after) = rest.strip_prefix('<') {
+        if let Some((value, tail)) = after.split_once('>') {
+            if let Ok(value) = value.parse::<u8>() {
+                level = Some(value & 0x7);
+                rest = tail;
+            }
+        }
+    }
+    let mut timestamp_secs = None;
+    if let Some(after) = rest.strip_prefix('[') {
+        if let Some((value, tail)) = after.split_once(']') {
+            if let Ok(value) = value.trim().parse::<f64>() {
+                timestamp_secs = Some(value);
+                rest = tail.strip_prefix(' ').unwrap_or(tail);
+            }
+        }
+    }
+    DmesgLine {
+        timestamp_secs,
+        level,
+        message: rest.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn output(stdout: &str, stderr: &str, exit_code: i32) -> CommandOutput {
+        CommandOutput {
+            stdout: stdout.to_string(),
+            stderr: stderr.to_string(),
+            exit_code: Some(exit_code),
+        }
+    }
+
+    #[test]
+    fn parses_timestamps_and_levels() {
+        let line =
+            parse_dmesg_line("<3>[ 5123.456789] usb 1-1: device descriptor read/64, error -71");
+        assert_eq!(line.level, Some(3));
+        assert_eq!(line.timestamp_secs, Some(5123.456789));
+        assert_eq!(
+            line.message,
+            "usb 1-1: device descriptor read/64, error -71"
+        );
+
+        let plain = parse_dmesg_line("[    0.000000] Booting Linux on physical CPU 0x0");
+        assert_eq!(plain.level, None);
+        assert_eq!(plain.message, "Booting Linux on physical CPU 0x0");
+
+        let continuation = parse_dmesg_line("  continuation without timestamp");
+        assert_eq!(continuation.timestamp_secs, None);
+        assert_eq!(continuation.message, "  continuation without timestamp");
+    }
+
+    #[test]
+    fn builds_args_and_reads_probes() {
+        assert_eq!(dmesg_stream_args(DMESG_MODE_DIRECT), vec!["dmesg", "-w"]);
+        assert_eq!(
+            dmesg_stream_args(DMESG_MODE_SU),
+            vec!["su", "-c", "'dmesg -w'"]
+        );
+        assert!(dmesg_probe_succeeded(&output("", "", 0)));
+        assert!(!dmesg_probe_succeeded(&output(
+            "",
+            "dmesg: klogctl: Permission denied\n",
+            1
+        )));
+        assert!(!dmesg_probe_succeeded(&output(
+            "",
+            "/system/bin/sh: su: not found\n",
+            127
+        )));
+    }
+}
@@ -0,0 +1,297 @@
+use std::collections::BTreeMap;
+
+use serde_json::{json, Value};
+
+use crate::app::adb::apps::{
+    parse_dumpsys_debuggable, parse_dumpsys_first_install_time, parse_dumpsys_granted_permissions,
+    parse_dumpsys_installer_package_name, parse_dumpsys_last_update_time, parse_dumpsys_min_sdk,
+    parse_dumpsys_requested_permissions, parse_dumpsys_target_sdk, parse_dumpsys_user_id,
+    parse_dumpsys_version_code, parse_dumpsys_version_name,
+};
+use crate::app::adb::parse::{parse_audio_summary, parse_bluetooth_manager_state};
+use crate::app::leak_watch::parse_total_pss_kb;
+use crate::app::net_profiler::parse::parse_dumpsys_netstats_app_uid_stats;
+use crate::app::perf::parse::{parse_battery_totals, parse_gfxinfo_summary};
+use crate::app::thermal::parse::{
+    parse_thermal_service_temperatures, parse_thermal_status, throttle_status_label,
+};
+
+const MAX_DUMPSYS_ARGS: usize = 8;
+const MAX_DUMPSYS_TOKEN_LEN: usize = 128;
+
+/// A structured view of one dumpsys service. `parse` returns `None` when the output does not
+/// look like what the parser expects (wrong args, older format), and the caller falls back to
+/// raw text.
+pub struct DumpsysParser {
+    pub service: &'static str,
+    pub description: &'static str,
+    pub parse: fn(&str) -> Option<Value>,
+}
+
+pub const DUMPSYS_PARSERS: &[DumpsysParser] = &[
+    DumpsysParser {
+        service: "activity",
+        description: "Resumed activity and focused app (use args: activities)",
+        parse: parse_activity,
+    },
+    DumpsysParser {
+        service: "audio",
+        description: "Audio mode, ringer and routing summary",
+        parse: parse_audio,
+    },
+    DumpsysParser {
+        service: "battery",
+        description: "Battery level, temperature and status fields",
+        parse: parse_battery,
+    },
+    DumpsysParser {
+        service: "bluetooth_manager",
+        description: "Bluetooth adapter state",
+        parse: parse_bluetooth,
+    },
+    DumpsysParser {
+        service: "gfxinfo",
+        description: "Frame statistics for a package (args: <package>)",
+        parse: parse_gfxinfo,
+    },
+    DumpsysParser {
+        service: "meminfo",
+        description: "Total PSS for a package (args: <package>)",
+        parse: parse_meminfo,
+    },
+    DumpsysParser {
+        service: "netstats",
+        description: "Per-UID received and transmitted bytes",
+        parse: parse_netstats,
+    },
+    DumpsysParser {
+        service: "package",
+        description: "Version, SDK levels and permissions for a package (args: <package>)",
+        parse: parse_package,
+    },
+    DumpsysParser {
+        service: "thermalservice",
+        description: "Throttling status and HAL temperatures",
+        parse: parse_thermal,
+    },
+];
+
+pub fn find_dumpsys_parser(service: &str) -> Option<&'static DumpsysParser> {
+    DUMPSYS_PARSERS
+        .iter()
+        .find(|parser| parser.service == service)
+}
+
+/// Service names as listed by `dumpsys -l` (`SurfaceFlinger`, `android.hardware.power...`).
+pub fn is_valid_dumpsys_service(service: &str) -> bool {
+    !service.is_empty()
+        && service.len() <= MAX_DUMPSYS_TOKEN_LEN
+        && service
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '_' | '.' | '-' | '/' | '@'))
+}
+
+/// Arguments reach the device shell as separate words, so each must be a plain token: flags,
+/// package names and section names, nothing the shell would interpret.
+pub fn validate_dumpsys_args(args: &[String]) -> Result<Vec<String>, String> {
+    if args.len() > MAX_DUMPSYS_ARGS {
+        return Err(format!(
+            "At most {MAX_DUMPSYS_ARGS} dumpsys arguments are allowed"
+        ));
+    }
+    args.iter()
+        .map(|arg| arg.trim())
+        .filter(|arg| !arg.is_empty())
+        .map(|arg| {
+            let valid = arg.len() <= MAX_DUMPSYS_TOKEN_LEN
+                && arg.chars().all(|ch| {
+                    ch.is_ascii_alphanumeric()
+                        || matches!(ch, '_' | '.' | '-' | '/' | ':' | '=' | ',' | '@')
+                });
+            if valid {
+                Ok(arg.to_string())
+            } else {
+                Err(format!("Invalid dumpsys argument: {arg}"))
+            }
+        })
+        .collect()
+}
+
+pub fn build_dumpsys_shell_args(service: &str, args: &[String]) -> Vec<String> {
+    let mut shell_args = vec!["dumpsys".to_string(), service.to_string()];
+    shell_args.extend(args.iter().cloned());
+    shell_args
+}
+
+/// `dumpsys <service>` prints this when the service is not registered on the device.
+pub fn is_missing_service_output(output: &str) -> bool {
+    output.trim_start().starts_with("Can't find service:")
+}
+
+fn parse_activity(output: &str) -> Option<Value> {
+    let component_after = |marker: &str| {
+        output.lines().find_map(|line| {
+            let (_, rest) = line.trim().split_once(marker)?;
+            rest.split_whitespace()
+                .find(|token| token.contains('/'))
+                .map(|token| token.trim_end_matches('}').to_string())
+        })
+    };
+    let resumed = component_after("mResumedActivity:")
+        .or_else(|| component_after("topResumedActivity="))
+        .or_else(|| component_after("ResumedActivity:"));
+    let focused_app = component_after("mFocusedApp=");
+    if resumed.is_none() && focused_app.is_none() {
+        return None;
+    }
+    Some(json!({
+        "resumed_activity": resumed,
+        "focused_app": focused_app,
+    }))
+}
+
+fn parse_audio(output: &str) -> Option<Value> {
+    let summary = parse_audio_summary(output)?;
+    let fields: BTreeMap<&str, &str> = summary
+        .split(" | ")
+        .filter_map(|part| part.split_once('='))
+        .collect();
+    Some(json!(fields))
+}
+
+/// Top-level `key: value` lines; nested sections are indented further and skipped.
+fn parse_battery(output: &str) -> Option<Value> {
+    let totals = parse_battery_totals(output).ok()?;
+    totals.level?;
+    let fields: BTreeMap<&str, &str> = output
+        .lines()
+        .filter(|line| line.starts_with("  ") && !line.starts_with("   "))
+        .filter_map(|line| line.trim().split_once(':'))
+        .map(|(key, value)| (key.trim(), value.trim()))
+        .filter(|(_, value)| !value.is_empty())
+        .collect();
+    Some(json!({
+        "level": totals.level,
+        "temperature_decic": totals.temperature_decic,
+        "fields": fields,
+    }))
+}
+
+fn parse_bluetooth(output: &str) -> Option<Value> {
+    parse_bluetooth_manager_state(output).map(|state| json!({ "state": state }))
+}
+
+fn parse_gfxinfo(output: &str) -> Option<Value> {
+    let summary = parse_gfxinfo_summary(output);
+    summary.total_frames?;
+    Some(json!({
+        "total_frames": summary.total_frames,
+        "janky_frames": summary.janky_frames,
+        "p50_ms": summary.p50_ms,
+        "p90_ms": summary.p90_ms,
+        "p95_ms": summary.p95_ms,
+        "p99_ms": summary.p99_ms,
+        "missed_vsync": summary.missed_vsync,
+        "slow_ui_thread": summary.slow_ui_thread,
+        "frame_deadline_missed": summary.frame_deadline_missed,
+    }))
+}
+
+fn parse_meminfo(output: &str) -> Option<Value> {
+    parse_total_pss_kb(output).map(|total| json!({ "total_pss_kb": total }))
+}
+
+fn parse_netstats(output: &str) -> Option<Value> {
+    let stats = parse_dumpsys_netstats_app_uid_stats(output).ok()?;
+    if stats.is_empty() {
+        return None;
+    }
+    let by_uid: BTreeMap<String, Value> = stats
+        .into_iter()
+        .map(|(uid, (rx_bytes, tx_bytes))| {
+            (
+                uid.to_string(),
+                json!({ "rx_bytes": rx_bytes, "tx_bytes": tx_bytes }),
+            )
+        })
+        .collect();
+    Some(json!({ "uid_stats": by_uid }))
+}
+
+fn parse_package(output: &str) -> Option<Value> {
+    let version_name = parse_dumpsys_version_name(output);
+    let version_code = parse_dumpsys_version_code(output);
+    if version_name.is_none() && version_code.is_none() {
+        return None;
+    }
+    Some(json!({
+        "version_name": version_name,
+        "version_code": version_code,
+        "target_sdk": parse_dumpsys_target_sdk(output),
+        "min_sdk": parse_dumpsys_min_sdk(output),
+        "user_id": parse_dumpsys_user_id(output),
+        "debuggable": parse_dumpsys_debuggable(output),
+        "installer": parse_dumpsys_installer_package_name(output),
+        "first_install_time": parse_dumpsys_first_install_time(output),
+        "last_update_time": parse_dumpsys_last_update_time(output),
+        "requested_permissions": parse_dumpsys_requested_permissions(output),
+        "granted_permissions": parse_dumpsys_granted_permissions(output),
+    }))
+}
+
+fn parse_thermal(output: &str) -> Option<Value> {
+    let status = parse_thermal_status(output)?;
+    Some(json!({
+        "status": status,
+        "status_label": throttle_status_label(status),
+        "temperatures": parse_thermal_service_temperatures(output),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validates_services_and_args() {
+        assert!(is_valid_dumpsys_service("battery"));
+        assert!(is_valid_dumpsys_service(
+            "android.hardware.power.IPower/default"
+        ));
+        assert!(!is_valid_dumpsys_service("battery; reboot"));
+        assert!(!is_valid_dumpsys_service(""));
+        assert_eq!(
+            validate_dumpsys_args(&[" com.example.app ".to_string(), "".to_string()]).unwrap(),
+            vec!["com.example.app"]
+        );
+        assert!(validate_dumpsys_args(&["--checkin".to_string()]).is_ok());
+        assert!(validate_dumpsys_args(&["a|b".to_string()]).is_err());
+        assert!(validate_dumpsys_args(&vec!["x".to_string(); 9]).is_err());
+        assert_eq!(
+            build_dumpsys_shell_args("meminfo", &["com.example.app".to_string()]),
+            vec!["dumpsys", "meminfo", "com.example.app"]
+        );
+        assert!(is_missing_service_output("Can't find service: nope\n"));
+    }
+
+    #[test]
+    fn parses_registered_services() {
+        let battery = "Current Battery Service state:\n  AC powered: false\n  USB powered: true\n  status: 2\n  level: 85\n  temperature: 301\n";
+        let parsed = (find_dumpsys_parser("battery").unwrap().parse)(battery).unwrap();
+        assert_eq!(parsed["level"], 85);
+        assert_eq!(parsed["temperature_decic"], 301);
+        assert_eq!(parsed["fields"]["USB powered"], "true");
+
+        let activity =
+            "  mResumedActivity: ActivityRecord{1a2b u0 com.example.app/.MainActivity t42}\n";
+        let parsed = (find_dumpsys_parser("activity").unwrap().parse)(activity).unwrap();
+        assert_eq!(parsed["resumed_activity"], "com.example.app/.MainActivity");
+
+        let meminfo = "** MEMINFO in pid 123 [com.example.app] **\n        TOTAL PSS:    45678            TOTAL RSS:   90000\n";
+        let parsed = (find_dumpsys_parser("meminfo").unwrap().parse)(meminfo).unwrap();
+        assert_eq!(parsed["total_pss_kb"], 45678);
+
+        assert!((find_dumpsys_parser("battery").unwrap().parse)("no data").is_none());
+        assert!(find_dumpsys_parser("SurfaceFlinger").is_none());
+    }
+}
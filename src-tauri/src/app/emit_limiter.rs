@@ -0,0 +1,171 @@
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use serde_json::Value;
+
+/// Identical payloads are suppressed, but re-sent this often so a late listener still sees
+/// the current state.
+pub const COALESCE_REFRESH: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EmitPolicy {
+    pub min_interval: Duration,
+    pub coalesce: bool,
+}
+
+/// Channels that can flood the IPC bridge. Anything else (logcat lines, terminal output,
+/// install results) is delivered unchanged because every payload matters.
+pub fn emit_policy(event: &str) -> Option<EmitPolicy> {
+    match event {
+        "file-transfer-progress" => Some(EmitPolicy {
+            min_interval: Duration::from_millis(100),
+            coalesce: true,
+        }),
+        "perf-snapshot" | "thermal-snapshot" | "net-profiler-snapshot" => Some(EmitPolicy {
+            min_interval: Duration::from_millis(200),
+            coalesce: true,
+        }),
+        _ => None,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EmitDecision {
+    Emit,
+    RateLimited,
+    Coalesced,
+}
+
+/// Final progress and terminal statuses always pass the rate limit so the UI never misses
+/// the end of an operation.
+pub fn is_terminal_payload(payload: &Value) -> bool {
+    payload.get("progress").and_then(Value::as_u64) == Some(100)
+        || matches!(
+            payload.get("status").and_then(Value::as_str),
+            Some("completed" | "failed" | "cancelled")
+        )
+}
+
+pub fn payload_serial(payload: &Value) -> String {
+    payload
+        .get("serial")
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_string()
+}
+
+struct ChannelState {
+    last_emit: Instant,
+    last_payload: String,
+}
+
+/// Per `(channel, serial)` rate limit plus suppression of identical consecutive payloads.
+#[derive(Default)]
+pub struct EmitLimiter {
+    channels: HashMap<(String, String), ChannelState>,
+}
+
+impl EmitLimiter {
+    pub fn decide(
+        &mut self,
+        event: &str,
+        payload: &Value,
+        policy: EmitPolicy,
+        now: Instant,
+    ) -> EmitDecision {
+        let key = (event.to_string(), payload_serial(payload));
+        let encoded = payload.to_string();
+        if let Some(state) = self.channels.get(&key) {
+            let since = now.saturating_duration_since(state.last_emit);
+            if policy.coalesce && state.last_payload == encoded && since < COALESCE_REFRESH {
+                return EmitDecision::Coalesced;
+            }
+            if since < policy.min_interval && !is_terminal_payload(payload) {
+                return EmitDecision::RateLimited;
+            }
+        }
+        self.channels.insert(
+            key,
+            ChannelState {
+                last_emit: now,
+                last_payload: encoded,
+            },
+        );
+        EmitDecision::Emit
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn policy() -> EmitPolicy {
+        emit_policy("file-transfer-progress").expect("policy")
+    }
+
+    #[test]
+    fn only_flooding_channels_have_policies() {
+        assert!(emit_policy("perf-snapshot").is_some());
+        assert!(emit_policy("logcat-line").is_none());
+        assert!(emit_policy("terminal-event").is_none());
+    }
+
+    #[test]
+    fn rate_limits_per_channel_and_serial() {
+        let mut limiter = EmitLimiter::default();
+        let start = Instant::now();
+        let event = "file-transfer-progress";
+        let progress = |serial: &str, percent: u64| json!({"serial": serial, "progress": percent});
+
+        assert_eq!(
+            limiter.decide(event, &progress("a", 10), policy(), start),
+            EmitDecision::Emit
+        );
+        let soon = start + Duration::from_millis(20);
+        assert_eq!(
+            limiter.decide(event, &progress("a", 11), policy(), soon),
+            EmitDecision::RateLimited
+        );
+        assert_eq!(
+            limiter.decide(event, &progress("b", 11), policy(), soon),
+            EmitDecision::Emit
+        );
+        assert_eq!(
+            limiter.decide(event, &progress("a", 100), policy(), soon),
+            EmitDecision::Emit
+        );
+        let later = start + Duration::from_millis(150);
+        assert_eq!(
+            limiter.decide(event, &progress("b", 12), policy(), later),
+            EmitDecision::Emit
+        );
+    }
+
+    #[test]
+    fn coalesces_identical_payloads_until_refresh() {
+        let mut limiter = EmitLimiter::default();
+        let start = Instant::now();
+        let error = json!({"serial": "a", "error": "device offline"});
+        let event = "perf-snapshot";
+        let policy = emit_policy(event).expect("policy");
+
+        assert_eq!(
+            limiter.decide(event, &error, policy, start),
+            EmitDecision::Emit
+        );
+        assert_eq!(
+            limiter.decide(event, &error, policy, start + Duration::from_secs(2)),
+            EmitDecision::Coalesced
+        );
+        assert_eq!(
+            limiter.decide(event, &error, policy, start + COALESCE_REFRESH),
+            EmitDecision::Emit
+        );
+        let other = json!({"serial": "a", "error": "timeout"});
+        assert_eq!(
+            limiter.decide(event, &other, policy, start + COALESCE_REFRESH * 2),
+            EmitDecision::Emit
+        );
+    }
+}
@@ -0,0 +1,356 @@
+use std::path::{Path, PathBuf};
+
+use crate::app::adb::runner::CommandOutput;
+use crate::app::config::AppConfig;
+use crate::app::error::AppError;
+use crate::app::models::DependencyStatus;
+
+pub const STATUS_OK: &str = "ok";
+pub const STATUS_WARNING: &str = "warning";
+pub const STATUS_MISSING: &str = "missing";
+pub const STATUS_ERROR: &str = "error";
+
+const SPAWN_FAILURE_PREFIX: &str = "Failed to spawn command";
+
+pub const HINT_ADB: &str = "Install Android SDK platform-tools or set the adb path in Settings.";
+pub const HINT_ADB_SERVER_LOCAL: &str =
+    "Run `adb kill-server` and retry; make sure no other program holds port 5037.";
+pub const HINT_ADB_SERVER_REMOTE: &str =
+    "Make sure the remote host runs `adb -a nodaemon server start` and is reachable.";
+pub const HINT_SCRCPY: &str = "Install scrcpy to enable screen mirroring.";
+pub const HINT_FFMPEG: &str =
+    "Install ffmpeg or set the ffmpeg path in Settings to convert recordings.";
+pub const HINT_AAPT: &str =
+    "Install Android SDK build-tools or set ANDROID_HOME to read APK metadata.";
+pub const HINT_EMULATOR: &str =
+    "Install the Android Emulator with the SDK manager or set ANDROID_HOME.";
+pub const HINT_BUNDLETOOL: &str = "Install bundletool and put it on PATH to work with .aab files.";
+pub const HINT_CURL: &str =
+    "Install curl or set the curl path in Settings to use uploads, issues and webhooks.";
+pub const HINT_CONFIG: &str = "Fix the listed settings in Settings.";
+
+fn first_line(text: &str) -> Option<String> {
+    text.lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .map(str::to_string)
+}
+
+/// First non-empty line of stdout, falling back to stderr (some tools print versions there).
+pub fn version_line(output: &CommandOutput) -> Option<String> {
+    first_line(&output.stdout).or_else(|| first_line(&output.stderr))
+}
+
+/// Status for a tool probed by running it; a spawn failure means the tool is missing.
+pub fn probe_status(
+    name: &str,
+    required: bool,
+    program: &str,
+    result: Result<CommandOutput, AppError>,
+    hint: &str,
+) -> DependencyStatus {
+    let mut status = DependencyStatus {
+        name: name.to_string(),
+        status: STATUS_OK.to_string(),
+        required,
+        path: Some(program.to_string()),
+        version: None,
+        detail: None,
+        hint: None,
+    };
+    match result {
+        Ok(output) if output.exit_code == Some(0) => {
+            status.version = version_line(&output);
+        }
+        Ok(output) => {
+            status.status = STATUS_ERROR.to_string();
+            status.detail = Some(
+                first_line(&output.stderr)
+                    .or_else(|| first_line(&output.stdout))
+                    .unwrap_or_else(|| format!("Exited with code {:?}", output.exit_code)),
+            );
+            status.hint = Some(hint.to_string());
+        }
+        Err(err) => {
+            // Anything other than a spawn failure (e.g. a timeout) means the tool exists but
+            // is unhealthy.
+            status.status = if err.error.starts_with(SPAWN_FAILURE_PREFIX) {
+                STATUS_MISSING.to_string()
+            } else {
+                STATUS_ERROR.to_string()
+            };
+            status.detail = Some(err.error);
+            status.hint = Some(hint.to_string());
+        }
+    }
+    status
+}
+
+pub fn missing_status(name: &str, required: bool, detail: &str, hint: &str) -> DependencyStatus {
+    DependencyStatus {
+        name: name.to_string(),
+        status: STATUS_MISSING.to_string(),
+        required,
+        path: None,
+        version: None,
+        detail: Some(detail.to_string()),
+        hint: Some(hint.to_string()),
+    }
+}
+
+/// Ready when every required dependency is usable; optional ones only degrade features.
+pub fn environment_ready(items: &[DependencyStatus]) -> bool {
+    items
+        .iter()
+        .filter(|item| item.required)
+        .all(|item| item.status == STATUS_OK || item.status == STATUS_WARNING)
+}
+
+fn executable_name(name: &str) -> String {
+    if cfg!(windows) {
+        format!("{name}.exe")
+    } else {
+        name.to_string()
+    }
+}
+
+pub fn find_emulator_in_sdk(sdk_root: &Path) -> Option<PathBuf> {
+    let path = sdk_root.join("emulator").join(executable_name("emulator"));
+    path.is_file().then_some(path)
+}
+
+/// Settings that will make a feature fail at use time. Only environment variable names are
+/// reported, never their values.
+pub fn config_issues(config: &AppConfig, env_present: impl Fn(&str) -> bool) -> Vec<String> {
+    let mut issues = Vec::new();
+    let dirs = [
+        ("output_path", &config.output_path),
+        ("file_gen_output_path", &config.file_gen_output_path),
+        ("output.screenshots_dir", &config.output.screenshots_dir),
+        ("output.recordings_dir", &config.output.recordings_dir),
+        ("output.bugreports_dir", &config.output.bugreports_dir),
+        ("output.logs_dir", &config.output.logs_dir),
+        ("output.ui_dumps_dir", &config.output.ui_dumps_dir),
+    ];
+    for (key, dir) in dirs {
+        let dir = dir.trim();
+        if !dir.is_empty() && Path::new(dir).exists() && !Path::new(dir).is_dir() {
+            issues.push(format!("{key} points to a file, not a folder: {dir}"));
+        }
+    }
+
+    let project_dir = config.apk_install.build_project_dir.trim();
+    if !project_dir.is_empty() && !Path::new(project_dir).is_dir() {
+        issues.push(format!(
+            "apk_install.build_project_dir does not exist: {project_dir}"
+        ));
+    }
+
+    let mut require_env = |owner: String, env: &str| {
+        let env = env.trim();
+        if !env.is_empty() && !env_present(env) {
+            issues.push(format!(
+                "{owner} needs environment variable {env}, which is not set"
+            ));
+        }
+    };
+    for target in &config.upload.targets {
+        let owner = format!("Upload target `{}`", target.name);
+        require_env(owner.clone(), &target.token_env);
+        require_env(owner.clone(), &target.access_key_env);
+        require_env(owner, &target.secret_key_env);
+    }
+    for webhook in config
+        .notifications
+        .webhooks
+        .iter()
+        .filter(|webhook| webhook.enabled)
+    {
+        require_env(format!("Webhook `{}`", webhook.name), &webhook.url_env);
+    }
+    if !config.issues.github.repo.trim().is_empty() {
+        require_env("GitHub issues".to_string(), &config.issues.github.token_env);
+    }
+    if !config.issues.jira.base_url.trim().is_empty() {
+        require_env("Jira issues".to_string(), &config.issues.jira.token_env);
+    }
+
+    for webhook in &config.notifications.webhooks {
+        if webhook.enabled && webhook.url.trim().is_empty() && webhook.url_env.trim().is_empty() {
+            issues.push(format!("Webhook `{}` has no URL", webhook.name));
+        }
+    }
+    issues
+}
+
+pub fn config_status(load_error: Option<String>, issues: Vec<String>) -> DependencyStatus {
+    let mut status = DependencyStatus {
+        name: "config".to_string(),
+        status: STATUS_OK.to_string(),
+        required: true,
+        path: None,
+        version: None,
+        detail: None,
+        hint: None,
+    };
+    if let Some(error) = load_error {
+        status.status = STATUS_ERROR.to_string();
+        status.detail = Some(error);
+        status.hint = Some(HINT_CONFIG.to_string());
+    } else if !issues.is_empty() {
+        status.status = STATUS_WARNING.to_string();
+        status.detail = Some(issues.join("; "));
+        status.hint = Some(HINT_CONFIG.to_string());
+    }
+    status
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::config::{UploadTarget, WebhookTarget};
+
+    fn output(stdout: &str, stderr: &str, exit_code: Option<i32>) -> CommandOutput {
+        CommandOutput {
+            stdout: stdout.to_string(),
+            stderr: stderr.to_string(),
+            exit_code,
+        }
+    }
+
+    #[test]
+    fn reads_version_from_stdout_or_stderr() {
+        assert_eq!(
+            version_line(&output(
+                "\nAndroid Debug Bridge version 1.0.41\nVersion 35\n",
+                "",
+                Some(0)
+            ))
+            .as_deref(),
+            Some("Android Debug Bridge version 1.0.41")
+        );
+        assert_eq!(
+            version_line(&output("", "scrcpy 2.4\n", Some(0))).as_deref(),
+            Some("scrcpy 2.4")
+        );
+        assert_eq!(version_line(&output(" \n", "", Some(0))), None);
+    }
+
+    #[test]
+    fn classifies_probe_results() {
+        let ok = probe_status(
+            "adb",
+            true,
+            "adb",
+            Ok(output("adb 1.0\n", "", Some(0))),
+            HINT_ADB,
+        );
+        assert_eq!(ok.status, STATUS_OK);
+        assert_eq!(ok.version.as_deref(), Some("adb 1.0"));
+        assert_eq!(ok.hint, None);
+
+        let failed = probe_status(
+            "ffmpeg",
+            false,
+            "ffmpeg",
+            Ok(output("", "bad flag\n", Some(1))),
+            HINT_FFMPEG,
+        );
+        assert_eq!(failed.status, STATUS_ERROR);
+        assert_eq!(failed.detail.as_deref(), Some("bad flag"));
+        assert_eq!(failed.hint.as_deref(), Some(HINT_FFMPEG));
+
+        let missing = probe_status(
+            "scrcpy",
+            false,
+            "scrcpy",
+            Err(AppError::system(
+                "Failed to spawn command: not found",
+                "trace",
+            )),
+            HINT_SCRCPY,
+        );
+        assert_eq!(missing.status, STATUS_MISSING);
+        let hung = probe_status(
+            "emulator",
+            false,
+            "emulator",
+            Err(AppError::system("Command timed out", "trace")),
+            HINT_EMULATOR,
+        );
+        assert_eq!(hung.status, STATUS_ERROR);
+    }
+
+    #[test]
+    fn readiness_ignores_optional_dependencies() {
+        let ok = probe_status(
+            "adb",
+            true,
+            "adb",
+            Ok(output("adb\n", "", Some(0))),
+            HINT_ADB,
+        );
+        let optional = missing_status("bundletool", false, "not found", HINT_BUNDLETOOL);
+        assert!(environment_ready(&[ok.clone(), optional]));
+        let required = missing_status("adb", true, "not found", HINT_ADB);
+        assert!(!environment_ready(&[ok, required]));
+        assert!(environment_ready(&[config_status(
+            None,
+            vec!["x".to_string()]
+        )]));
+        assert!(!environment_ready(&[config_status(
+            Some("invalid".to_string()),
+            Vec::new()
+        )]));
+    }
+
+    #[test]
+    fn reports_config_issues() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("not-a-dir");
+        std::fs::write(&file, b"x").unwrap();
+
+        let mut config = AppConfig::default();
+        config.output.logs_dir = file.to_string_lossy().to_string();
+        config.output.screenshots_dir = dir.path().to_string_lossy().to_string();
+        config.apk_install.build_project_dir =
+            dir.path().join("missing").to_string_lossy().to_string();
+        config.upload.targets.push(UploadTarget {
+            name: "bucket".to_string(),
+            kind: "s3".to_string(),
+            access_key_env: "SET_KEY".to_string(),
+            secret_key_env: "UNSET_SECRET".to_string(),
+            ..Default::default()
+        });
+        config.notifications.webhooks.push(WebhookTarget {
+            name: "slack".to_string(),
+            url: String::new(),
+            url_env: String::new(),
+            events: Vec::new(),
+            template: String::new(),
+            headers: Vec::new(),
+            enabled: true,
+        });
+
+        let issues = config_issues(&config, |name| name == "SET_KEY");
+        assert_eq!(issues.len(), 4, "{issues:?}");
+        assert!(issues[0].starts_with("output.logs_dir points to a file"));
+        assert!(issues[1].starts_with("apk_install.build_project_dir does not exist"));
+        assert!(issues[2].contains("UNSET_SECRET"));
+        assert_eq!(issues[3], "Webhook `slack` has no URL");
+        assert!(config_issues(&AppConfig::default(), |_| false).is_empty());
+    }
+
+    #[test]
+    fn finds_emulator_in_sdk() {
+        let sdk = tempfile::tempdir().unwrap();
+        assert_eq!(find_emulator_in_sdk(sdk.path()), None);
+        std::fs::create_dir_all(sdk.path().join("emulator")).unwrap();
+        let binary = sdk
+            .path()
+            .join("emulator")
+            .join(executable_name("emulator"));
+        std::fs::write(&binary, b"").unwrap();
+        assert_eq!(find_emulator_in_sdk(sdk.path()), Some(binary));
+    }
+}
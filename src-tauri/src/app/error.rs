@@ -1,39 +1,80 @@
-use serde::Serialize;
+use serde::ser::SerializeStruct;
+use serde::{Serialize, Serializer};
+use std::collections::BTreeMap;
 use std::fmt;
 
-#[derive(Debug, Clone, Serialize)]
+use crate::app::i18n::{localize, message_locale, Message};
+
+/// `error` stays in English inside the backend (logs, tests); it is rendered in the configured
+/// locale only when sent to the frontend, from the catalog `message_key` and `params` the error
+/// was created with, which are sent along so the UI can localize on its own.
+#[derive(Debug, Clone)]
 pub struct AppError {
     pub error: String,
     pub code: String,
     pub trace_id: String,
+    pub message_key: Option<&'static str>,
+    pub params: BTreeMap<String, String>,
 }
 
 impl AppError {
     pub fn new(
         code: impl Into<String>,
-        message: impl Into<String>,
+        message: impl Into<Message>,
         trace_id: impl Into<String>,
     ) -> Self {
+        let message = message.into();
         Self {
-            error: message.into(),
+            error: message.text,
             code: code.into(),
             trace_id: trace_id.into(),
+            message_key: message.key,
+            params: message.params,
         }
     }
 
-    pub fn validation(message: impl Into<String>, trace_id: impl Into<String>) -> Self {
+    pub fn validation(message: impl Into<Message>, trace_id: impl Into<String>) -> Self {
         Self::new("ERR_VALIDATION", message, trace_id)
     }
 
-    pub fn dependency(message: impl Into<String>, trace_id: impl Into<String>) -> Self {
+    pub fn dependency(message: impl Into<Message>, trace_id: impl Into<String>) -> Self {
         Self::new("ERR_DEPENDENCY", message, trace_id)
     }
 
-    pub fn system(message: impl Into<String>, trace_id: impl Into<String>) -> Self {
+    pub fn insufficient_host_storage(
+        message: impl Into<Message>,
+        trace_id: impl Into<String>,
+    ) -> Self {
+        Self::new("ERR_INSUFFICIENT_HOST_STORAGE", message, trace_id)
+    }
+
+    pub fn restricted(message: impl Into<Message>, trace_id: impl Into<String>) -> Self {
+        Self::new("ERR_RESTRICTED_MODE", message, trace_id)
+    }
+
+    pub fn system(message: impl Into<Message>, trace_id: impl Into<String>) -> Self {
         Self::new("ERR_SYSTEM", message, trace_id)
     }
 }
 
+impl Serialize for AppError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let localized = localize(
+            self.message_key,
+            &self.params,
+            &self.error,
+            &message_locale(),
+        );
+        let mut state = serializer.serialize_struct("AppError", 5)?;
+        state.serialize_field("error", &localized)?;
+        state.serialize_field("code", &self.code)?;
+        state.serialize_field("trace_id", &self.trace_id)?;
+        state.serialize_field("message_key", &self.message_key)?;
+        state.serialize_field("params", &self.params)?;
+        state.end()
+    }
+}
+
 impl fmt::Display for AppError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{} ({})", self.error, self.code)
@@ -41,3 +82,27 @@ impl fmt::Display for AppError {
 }
 
 impl std::error::Error for AppError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serializes_message_key_and_params() {
+        let value = serde_json::to_value(AppError::validation(
+            Message::keyed("required").with("field", "serials"),
+            "trace",
+        ))
+        .unwrap();
+        assert_eq!(value["error"], "serials is required");
+        assert_eq!(value["code"], "ERR_VALIDATION");
+        assert_eq!(value["trace_id"], "trace");
+        assert_eq!(value["message_key"], "required");
+        assert_eq!(value["params"]["field"], "serials");
+
+        let value =
+            serde_json::to_value(AppError::system("device lock poisoned", "trace")).unwrap();
+        assert!(value["message_key"].is_null());
+        assert_eq!(value["params"], serde_json::json!({}));
+    }
+}
@@ -0,0 +1,208 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::{OnceLock, RwLock};
+
+use tracing::warn;
+
+/// Subscribable channels and the frontend event each one gates.
+pub const EVENT_CHANNELS: [(&str, &str); 6] = [
+    ("logcat", "logcat-line"),
+    ("perf", "perf-snapshot"),
+    ("net", "net-profiler-snapshot"),
+    ("thermal", "thermal-snapshot"),
+    ("dmesg", "dmesg-line"),
+    ("lifecycle", "activity-lifecycle"),
+];
+
+/// Distinct on both light and dark themes; a device keeps its color for the whole session.
+const DEVICE_COLORS: [&str; 12] = [
+    "#e6194b", "#3cb44b", "#4363d8", "#f58231", "#911eb4", "#42d4f4", "#f032e6", "#bfef45",
+    "#469990", "#9a6324", "#800000", "#000075",
+];
+
+pub fn normalize_channels(channels: &[String]) -> Result<Vec<String>, String> {
+    let mut normalized: Vec<String> = Vec::new();
+    for channel in channels {
+        let channel = channel.trim().to_ascii_lowercase();
+        if !EVENT_CHANNELS.iter().any(|(name, _)| *name == channel) {
+            return Err(format!(
+                "Unknown event channel: {channel} (expected one of {})",
+                EVENT_CHANNELS
+                    .iter()
+                    .map(|(name, _)| *name)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+        if !normalized.contains(&channel) {
+            normalized.push(channel);
+        }
+    }
+    if normalized.is_empty() {
+        return Err("At least one event channel is required".to_string());
+    }
+    Ok(normalized)
+}
+
+/// A stable color per serial so every stream from one device can be drawn the same way.
+pub fn device_color(serial: &str) -> String {
+    // FNV-1a: stable across runs, unlike `DefaultHasher`.
+    let hash = serial.bytes().fold(0xcbf2_9ce4_8422_2325u64, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    });
+    DEVICE_COLORS[(hash % DEVICE_COLORS.len() as u64) as usize].to_string()
+}
+
+/// Serials subscribed per channel. A channel nobody has subscribed to is delivered for every
+/// device, so views that never subscribe keep working; once any device subscribes, the
+/// channel is only emitted for subscribed devices.
+#[derive(Debug, Default)]
+pub struct EventSubscriptions {
+    channels: HashMap<String, HashSet<String>>,
+}
+
+impl EventSubscriptions {
+    pub fn subscribe(&mut self, serial: &str, channels: &[String]) {
+        for channel in channels {
+            self.channels
+                .entry(channel.clone())
+                .or_default()
+                .insert(serial.to_string());
+        }
+    }
+
+    /// `None` drops every subscription of the device.
+    pub fn unsubscribe(&mut self, serial: &str, channels: Option<&[String]>) {
+        self.channels.retain(|channel, serials| {
+            if channels.is_none_or(|channels| channels.contains(channel)) {
+                serials.remove(serial);
+            }
+            !serials.is_empty()
+        });
+    }
+
+    pub fn channels_for(&self, serial: &str) -> Vec<String> {
+        EVENT_CHANNELS
+            .iter()
+            .map(|(channel, _)| *channel)
+            .filter(|channel| {
+                self.channels
+                    .get(*channel)
+                    .is_some_and(|serials| serials.contains(serial))
+            })
+            .map(str::to_string)
+            .collect()
+    }
+
+    /// Whether `event` is currently filtered by subscription.
+    pub fn gates(&self, event: &str) -> bool {
+        channel_for_event(event).is_some_and(|channel| self.channels.contains_key(channel))
+    }
+
+    pub fn allows(&self, event: &str, serial: &str) -> bool {
+        match channel_for_event(event).and_then(|channel| self.channels.get(channel)) {
+            Some(serials) => serials.contains(serial),
+            None => true,
+        }
+    }
+}
+
+fn channel_for_event(event: &str) -> Option<&'static str> {
+    EVENT_CHANNELS
+        .iter()
+        .find(|(_, name)| *name == event)
+        .map(|(channel, _)| *channel)
+}
+
+fn store() -> &'static RwLock<EventSubscriptions> {
+    static STORE: OnceLock<RwLock<EventSubscriptions>> = OnceLock::new();
+    STORE.get_or_init(|| RwLock::new(EventSubscriptions::default()))
+}
+
+pub fn subscribe_device_events(serial: &str, channels: &[String]) -> Vec<String> {
+    match store().write() {
+        Ok(mut guard) => {
+            guard.subscribe(serial, channels);
+            guard.channels_for(serial)
+        }
+        Err(_) => {
+            warn!("event subscription store poisoned");
+            Vec::new()
+        }
+    }
+}
+
+pub fn unsubscribe_device_events(serial: &str, channels: Option<&[String]>) -> Vec<String> {
+    match store().write() {
+        Ok(mut guard) => {
+            guard.unsubscribe(serial, channels);
+            guard.channels_for(serial)
+        }
+        Err(_) => {
+            warn!("event subscription store poisoned");
+            Vec::new()
+        }
+    }
+}
+
+pub fn device_event_channels(serial: &str) -> Vec<String> {
+    store()
+        .read()
+        .map(|guard| guard.channels_for(serial))
+        .unwrap_or_default()
+}
+
+pub fn event_gated(event: &str) -> bool {
+    store().read().is_ok_and(|guard| guard.gates(event))
+}
+
+/// Fails open: a poisoned store never hides events.
+pub fn event_subscribed(event: &str, serial: &str) -> bool {
+    store()
+        .read()
+        .map(|guard| guard.allows(event, serial))
+        .unwrap_or(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validates_channels_and_colors_devices() {
+        assert_eq!(
+            normalize_channels(&[
+                " Perf ".to_string(),
+                "logcat".to_string(),
+                "perf".to_string()
+            ])
+            .unwrap(),
+            ["perf", "logcat"]
+        );
+        assert!(normalize_channels(&["bogus".to_string()]).is_err());
+        assert!(normalize_channels(&[]).is_err());
+        assert_eq!(device_color("R58M"), device_color("R58M"));
+        assert!(device_color("emulator-5554").starts_with('#'));
+    }
+
+    #[test]
+    fn gates_channels_only_after_a_subscription() {
+        let mut subscriptions = EventSubscriptions::default();
+        assert!(!subscriptions.gates("perf-snapshot"));
+        assert!(subscriptions.allows("perf-snapshot", "b"));
+
+        subscriptions.subscribe("a", &["perf".to_string(), "logcat".to_string()]);
+        assert!(subscriptions.gates("perf-snapshot"));
+        assert!(subscriptions.allows("perf-snapshot", "a"));
+        assert!(!subscriptions.allows("perf-snapshot", "b"));
+        assert!(subscriptions.allows("thermal-snapshot", "b"));
+        assert!(subscriptions.allows("terminal-event", "b"));
+        assert_eq!(subscriptions.channels_for("a"), ["logcat", "perf"]);
+
+        subscriptions.unsubscribe("a", Some(&["perf".to_string()]));
+        assert!(!subscriptions.gates("perf-snapshot"));
+        assert_eq!(subscriptions.channels_for("a"), ["logcat"]);
+        subscriptions.unsubscribe("a", None);
+        assert!(subscriptions.channels_for("a").is_empty());
+        assert!(!subscriptions.gates("logcat-line"));
+    }
+}
@@ -0,0 +1,272 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::app::adb::runner::CommandOutput;
+use crate::app::config::config_path;
+use crate::app::error::AppError;
+use crate::app::i18n::Message;
+use crate::app::models::FactoryResetAuditEntry;
+
+/// How long a confirmation token from `prepare_factory_reset` stays redeemable.
+pub const FACTORY_RESET_TOKEN_TTL: Duration = Duration::from_secs(120);
+
+/// Wiping and booting back up routinely takes several minutes.
+pub const FACTORY_RESET_BOOT_TIMEOUT_SECS: u64 = 900;
+
+/// One way of starting a wipe from the shell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WipeMethod {
+    pub name: &'static str,
+    pub command: &'static [&'static str],
+}
+
+impl WipeMethod {
+    pub fn is_broadcast(&self) -> bool {
+        self.command.first() == Some(&"am")
+    }
+}
+
+/// Tried in order until one gets under way. Running the recovery binary directly only works
+/// on engineering, userdebug and emulator images; the broadcasts ask the system server to
+/// reboot into recovery and wipe, which builds granting the shell `MASTER_CLEAR` allow.
+pub const WIPE_METHODS: [WipeMethod; 3] = [
+    WipeMethod {
+        name: "recovery",
+        command: &["recovery", "--wipe_data"],
+    },
+    WipeMethod {
+        name: "factory_reset_broadcast",
+        command: &[
+            "am",
+            "broadcast",
+            "-a",
+            "android.intent.action.FACTORY_RESET",
+            "-p",
+            "android",
+            "--receiver-foreground",
+        ],
+    },
+    WipeMethod {
+        name: "master_clear_broadcast",
+        command: &[
+            "am",
+            "broadcast",
+            "-a",
+            "android.intent.action.MASTER_CLEAR",
+            "-p",
+            "android",
+            "--receiver-foreground",
+        ],
+    },
+];
+
+/// A broadcast that went out is only known to have worked once the device drops off adb.
+pub const BROADCAST_WIPE_REBOOT_WAIT: Duration = Duration::from_secs(30);
+
+pub const RESET_STAGE_REQUESTED: &str = "requested";
+pub const RESET_STAGE_WIPE: &str = "wipe";
+pub const RESET_STAGE_BOOT: &str = "boot";
+pub const RESET_STAGE_PROVISION: &str = "provision";
+
+pub const AUDIT_LIMIT: usize = 500;
+
+static AUDIT_LOCK: Mutex<()> = Mutex::new(());
+
+/// Single-use confirmation tokens, one per device.
+#[derive(Debug, Default)]
+pub struct ResetChallenges {
+    tokens: HashMap<String, (String, Instant)>,
+}
+
+impl ResetChallenges {
+    pub fn issue(&mut self, serial: &str, now: Instant) -> String {
+        let token = Uuid::new_v4().simple().to_string();
+        self.tokens.insert(serial.to_string(), (token.clone(), now));
+        token
+    }
+
+    /// Consumes the device's token whether or not it matches, so a wrong guess forces a new
+    /// confirmation.
+    pub fn redeem(&mut self, serial: &str, token: &str, now: Instant) -> Result<(), String> {
+        let (expected, issued_at) = self
+            .tokens
+            .remove(serial)
+            .ok_or_else(|| format!("No factory reset was prepared for {serial}"))?;
+        if now.saturating_duration_since(issued_at) > FACTORY_RESET_TOKEN_TTL {
+            return Err("Factory reset confirmation expired; prepare it again".to_string());
+        }
+        if expected != token.trim() {
+            return Err("Factory reset confirmation token does not match".to_string());
+        }
+        Ok(())
+    }
+}
+
+fn challenges() -> &'static Mutex<ResetChallenges> {
+    static CHALLENGES: OnceLock<Mutex<ResetChallenges>> = OnceLock::new();
+    CHALLENGES.get_or_init(|| Mutex::new(ResetChallenges::default()))
+}
+
+pub fn issue_reset_token(serial: &str, trace_id: &str) -> Result<String, AppError> {
+    challenges()
+        .lock()
+        .map(|mut guard| guard.issue(serial, Instant::now()))
+        .map_err(|_| AppError::system("Factory reset confirmations locked", trace_id))
+}
+
+pub fn redeem_reset_token(serial: &str, token: &str, trace_id: &str) -> Result<(), AppError> {
+    let mut guard = challenges()
+        .lock()
+        .map_err(|_| AppError::system("Factory reset confirmations locked", trace_id))?;
+    guard
+        .redeem(serial, token, Instant::now())
+        .map_err(|message| AppError::validation(message, trace_id))
+}
+
+/// Whether `method` got the wipe under way. The device reboots into recovery mid-command, so
+/// a dropped connection counts as started; a missing or forbidden `recovery` binary, or a
+/// broadcast the shell may not send, does not.
+pub fn wipe_started(method: &WipeMethod, output: &CommandOutput) -> Result<(), String> {
+    let combined = format!("{}\n{}", output.stdout, output.stderr);
+    let lower = combined.to_lowercase();
+    if lower.contains("not found")
+        || lower.contains("permission denied")
+        || lower.contains("permission denial")
+        || lower.contains("security exception")
+        || lower.contains("inaccessible")
+        || (method.is_broadcast() && lower.contains("error:") && !lower.contains("error: closed"))
+    {
+        return Err(format!(
+            "This build does not allow a shell-triggered wipe: {}",
+            combined.trim()
+        ));
+    }
+    if output.exit_code == Some(0)
+        || lower.contains("error: closed")
+        || lower.contains("device offline")
+    {
+        return Ok(());
+    }
+    Err(format!(
+        "Wipe command failed (exit {:?}): {}",
+        output.exit_code,
+        combined.trim()
+    ))
+}
+
+pub fn factory_reset_audit_path() -> PathBuf {
+    config_path().with_file_name(".lazy_blacktea_factory_reset_audit.json")
+}
+
+pub fn load_reset_audit(path: &Path) -> Vec<FactoryResetAuditEntry> {
+    let raw = match fs::read_to_string(path) {
+        Ok(raw) => raw,
+        Err(_) => return Vec::new(),
+    };
+    match serde_json::from_str(&raw) {
+        Ok(entries) => entries,
+        Err(err) => {
+            warn!(path = %path.display(), error = %err, "factory reset audit is invalid, starting over");
+            Vec::new()
+        }
+    }
+}
+
+pub fn append_reset_audit(
+    path: &Path,
+    entry: FactoryResetAuditEntry,
+    trace_id: &str,
+) -> Result<(), AppError> {
+    let _guard = AUDIT_LOCK
+        .lock()
+        .map_err(|_| AppError::system("Factory reset audit locked", trace_id))?;
+    let mut audit = load_reset_audit(path);
+    audit.push(entry);
+    if audit.len() > AUDIT_LIMIT {
+        audit.drain(..audit.len() - AUDIT_LIMIT);
+    }
+    let payload = serde_json::to_string_pretty(&audit).map_err(|err| {
+        AppError::system(
+            Message::keyed("operation_failed")
+                .with("action", "serialize factory reset audit")
+                .with("detail", &err),
+            trace_id,
+        )
+    })?;
+    fs::write(path, payload).map_err(|err| {
+        AppError::system(
+            Message::keyed("operation_failed")
+                .with("action", "save factory reset audit")
+                .with("detail", &err),
+            trace_id,
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokens_are_single_use_and_expire() {
+        let mut challenges = ResetChallenges::default();
+        let now = Instant::now();
+        let token = challenges.issue("R58M", now);
+        assert!(challenges.redeem("emulator-5554", &token, now).is_err());
+        assert!(challenges.redeem("R58M", "wrong", now).is_err());
+        // The failed attempt burned the token.
+        assert!(challenges.redeem("R58M", &token, now).is_err());
+
+        let token = challenges.issue("R58M", now);
+        assert!(challenges
+            .redeem(
+                "R58M",
+                &token,
+                now + FACTORY_RESET_TOKEN_TTL + Duration::from_secs(1)
+            )
+            .is_err());
+        let token = challenges.issue("R58M", now);
+        assert!(challenges
+            .redeem("R58M", &format!(" {token} "), now)
+            .is_ok());
+        assert!(challenges.redeem("R58M", &token, now).is_err());
+    }
+
+    #[test]
+    fn classifies_wipe_output() {
+        let output = |stdout: &str, stderr: &str, exit_code: Option<i32>| CommandOutput {
+            stdout: stdout.to_string(),
+            stderr: stderr.to_string(),
+            exit_code,
+        };
+        let [recovery, broadcast, _] = &WIPE_METHODS;
+        assert!(wipe_started(recovery, &output("", "", Some(0))).is_ok());
+        assert!(wipe_started(recovery, &output("", "error: closed", Some(1))).is_ok());
+        assert!(wipe_started(
+            recovery,
+            &output(
+                "",
+                "/system/bin/sh: recovery: inaccessible or not found",
+                Some(127)
+            )
+        )
+        .is_err());
+        assert!(wipe_started(
+            recovery,
+            &output("", "recovery: Permission denied", Some(1))
+        )
+        .is_err());
+        assert!(wipe_started(recovery, &output("", "boom", Some(2))).is_err());
+
+        let sent = "Broadcasting: Intent { act=android.intent.action.FACTORY_RESET }\nBroadcast completed: result=0";
+        assert!(wipe_started(broadcast, &output(sent, "", Some(0))).is_ok());
+        let denied = "Security exception: Permission Denial: not allowed to send broadcast android.intent.action.FACTORY_RESET";
+        assert!(wipe_started(broadcast, &output("", denied, Some(255))).is_err());
+    }
+}
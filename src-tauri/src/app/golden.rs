@@ -0,0 +1,292 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use tracing::warn;
+
+use crate::app::config::config_path;
+use crate::app::error::AppError;
+use crate::app::i18n::Message;
+use crate::app::models::{GoldenCheckResult, GoldenImage};
+
+pub const GOLDEN_PASSED: &str = "passed";
+pub const GOLDEN_FAILED: &str = "failed";
+pub const GOLDEN_MISSING: &str = "missing_golden";
+
+/// Oldest check results are dropped once the report grows past this.
+pub const GOLDEN_RESULTS_LIMIT: usize = 500;
+
+const META_EXTENSION: &str = "json";
+const RESULTS_DIR: &str = "results";
+
+static RESULTS_LOCK: Mutex<()> = Mutex::new(());
+
+/// Goldens live at `<store>/<screen_name>/<width>x<height>.png`, so one screen can hold a
+/// golden per device resolution.
+pub fn golden_store_dir() -> PathBuf {
+    config_path().with_file_name(".lazy_blacktea_goldens")
+}
+
+pub fn golden_results_path() -> PathBuf {
+    config_path().with_file_name(".lazy_blacktea_golden_results.json")
+}
+
+/// Screen names become directory names: 1-64 of `[A-Za-z0-9_.-]`, not starting with a dot.
+pub fn validate_screen_name(value: &str) -> Result<String, String> {
+    let name = value.trim();
+    if name.is_empty() || name.len() > 64 {
+        return Err("screen_name must be 1 to 64 characters".to_string());
+    }
+    if name.starts_with('.')
+        || !name
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '_' | '-' | '.'))
+    {
+        return Err("screen_name may only contain letters, digits, '_', '-' and '.'".to_string());
+    }
+    Ok(name.to_string())
+}
+
+pub fn resolution_key(width: u32, height: u32) -> String {
+    format!("{width}x{height}")
+}
+
+pub fn golden_image_path(store: &Path, screen_name: &str, resolution: &str) -> PathBuf {
+    store.join(screen_name).join(format!("{resolution}.png"))
+}
+
+/// Latest capture and diff for a device live under `<screen_name>/results/`.
+pub fn golden_check_paths(
+    store: &Path,
+    screen_name: &str,
+    safe_serial: &str,
+) -> (PathBuf, PathBuf) {
+    let dir = store.join(screen_name).join(RESULTS_DIR);
+    (
+        dir.join(format!("{safe_serial}.png")),
+        dir.join(format!("{safe_serial}_diff.png")),
+    )
+}
+
+pub fn save_golden_meta(image: &GoldenImage, trace_id: &str) -> Result<(), AppError> {
+    let path = Path::new(&image.path).with_extension(META_EXTENSION);
+    let payload = serde_json::to_string_pretty(image).map_err(|err| {
+        AppError::system(
+            Message::keyed("operation_failed")
+                .with("action", "serialize golden metadata")
+                .with("detail", &err),
+            trace_id,
+        )
+    })?;
+    fs::write(path, payload).map_err(|err| {
+        AppError::system(
+            Message::keyed("operation_failed")
+                .with("action", "save golden metadata")
+                .with("detail", &err),
+            trace_id,
+        )
+    })
+}
+
+/// Every golden in the store, sorted by screen name then resolution. Images without (or
+/// with unreadable) metadata are still listed.
+pub fn list_golden_images(store: &Path, screen_name: Option<&str>) -> Vec<GoldenImage> {
+    let Ok(screens) = fs::read_dir(store) else {
+        return Vec::new();
+    };
+    let mut images = Vec::new();
+    for screen in screens.flatten() {
+        let name = screen.file_name().to_string_lossy().to_string();
+        if !screen.path().is_dir() || screen_name.is_some_and(|wanted| wanted != name) {
+            continue;
+        }
+        let Ok(files) = fs::read_dir(screen.path()) else {
+            continue;
+        };
+        for file in files.flatten() {
+            let path = file.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("png") {
+                continue;
+            }
+            let resolution = path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+                .unwrap_or_default();
+            // Hidden files are in-flight captures from `save_golden`.
+            if resolution.starts_with('.') {
+                continue;
+            }
+            let meta_path = path.with_extension(META_EXTENSION);
+            let meta = fs::read_to_string(&meta_path).ok().and_then(|raw| {
+                serde_json::from_str::<GoldenImage>(&raw)
+                    .map_err(|err| {
+                        warn!(path = %meta_path.display(), error = %err, "golden metadata is invalid");
+                    })
+                    .ok()
+            });
+            images.push(GoldenImage {
+                screen_name: name.clone(),
+                resolution,
+                path: path.to_string_lossy().to_string(),
+                serial: meta
+                    .as_ref()
+                    .map(|meta| meta.serial.clone())
+                    .unwrap_or_default(),
+                saved_at: meta.map(|meta| meta.saved_at).unwrap_or_default(),
+            });
+        }
+    }
+    images.sort_by(|a, b| {
+        a.screen_name
+            .cmp(&b.screen_name)
+            .then_with(|| a.resolution.cmp(&b.resolution))
+    });
+    images
+}
+
+pub fn load_golden_results(path: &Path) -> Vec<GoldenCheckResult> {
+    let raw = match fs::read_to_string(path) {
+        Ok(raw) => raw,
+        Err(_) => return Vec::new(),
+    };
+    match serde_json::from_str(&raw) {
+        Ok(results) => results,
+        Err(err) => {
+            warn!(path = %path.display(), error = %err, "golden results are invalid, starting over");
+            Vec::new()
+        }
+    }
+}
+
+pub fn append_golden_result(
+    path: &Path,
+    result: &GoldenCheckResult,
+    trace_id: &str,
+) -> Result<(), AppError> {
+    let _guard = RESULTS_LOCK
+        .lock()
+        .map_err(|_| AppError::system("Golden results locked", trace_id))?;
+    let mut results = load_golden_results(path);
+    results.push(result.clone());
+    if results.len() > GOLDEN_RESULTS_LIMIT {
+        results.drain(..results.len() - GOLDEN_RESULTS_LIMIT);
+    }
+    let payload = serde_json::to_string_pretty(&results).map_err(|err| {
+        AppError::system(
+            Message::keyed("operation_failed")
+                .with("action", "serialize golden results")
+                .with("detail", &err),
+            trace_id,
+        )
+    })?;
+    fs::write(path, payload).map_err(|err| {
+        AppError::system(
+            Message::keyed("operation_failed")
+                .with("action", "save golden results")
+                .with("detail", &err),
+            trace_id,
+        )
+    })
+}
+
+/// Most recent result per (screen, device), newest first.
+pub fn latest_golden_results(
+    results: Vec<GoldenCheckResult>,
+    screen_name: Option<&str>,
+) -> Vec<GoldenCheckResult> {
+    let mut latest: Vec<GoldenCheckResult> = Vec::new();
+    for result in results.into_iter().rev() {
+        if screen_name.is_some_and(|wanted| wanted != result.screen_name) {
+            continue;
+        }
+        let seen = latest.iter().any(|existing| {
+            existing.screen_name == result.screen_name && existing.serial == result.serial
+        });
+        if !seen {
+            latest.push(result);
+        }
+    }
+    latest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(screen: &str, serial: &str, status: &str) -> GoldenCheckResult {
+        GoldenCheckResult {
+            serial: serial.to_string(),
+            screen_name: screen.to_string(),
+            resolution: "1080x2400".to_string(),
+            status: status.to_string(),
+            golden_path: None,
+            capture_path: String::new(),
+            comparison: None,
+            checked_at: "2024-01-01T00:00:00Z".to_string(),
+            trace_id: "trace".to_string(),
+        }
+    }
+
+    #[test]
+    fn validates_screen_names() {
+        assert_eq!(validate_screen_name(" login ").unwrap(), "login");
+        assert!(validate_screen_name("home_v2.1-dark").is_ok());
+        assert!(validate_screen_name("").is_err());
+        assert!(validate_screen_name("..").is_err());
+        assert!(validate_screen_name("a/b").is_err());
+        assert!(validate_screen_name(&"x".repeat(65)).is_err());
+    }
+
+    #[test]
+    fn lists_goldens_per_resolution() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = dir.path();
+        let login = golden_image_path(store, "login", &resolution_key(1080, 2400));
+        fs::create_dir_all(login.parent().unwrap()).unwrap();
+        fs::write(&login, b"png").unwrap();
+        save_golden_meta(
+            &GoldenImage {
+                screen_name: "login".to_string(),
+                resolution: "1080x2400".to_string(),
+                path: login.to_string_lossy().to_string(),
+                serial: "A1".to_string(),
+                saved_at: "2024-01-01T00:00:00Z".to_string(),
+            },
+            "trace",
+        )
+        .unwrap();
+        fs::write(golden_image_path(store, "login", "720x1280"), b"png").unwrap();
+        fs::write(store.join("login").join(".capture_A1.png"), b"png").unwrap();
+        let (capture, _) = golden_check_paths(store, "login", "A1");
+        fs::create_dir_all(capture.parent().unwrap()).unwrap();
+        fs::write(&capture, b"png").unwrap();
+
+        let images = list_golden_images(store, None);
+        assert_eq!(images.len(), 2);
+        assert_eq!(images[0].resolution, "1080x2400");
+        assert_eq!(images[0].serial, "A1");
+        assert_eq!(images[1].resolution, "720x1280");
+        assert_eq!(images[1].serial, "");
+        assert!(list_golden_images(store, Some("home")).is_empty());
+    }
+
+    #[test]
+    fn keeps_latest_result_per_device() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("results.json");
+        append_golden_result(&path, &result("login", "A1", GOLDEN_FAILED), "trace").unwrap();
+        append_golden_result(&path, &result("login", "B2", GOLDEN_MISSING), "trace").unwrap();
+        append_golden_result(&path, &result("login", "A1", GOLDEN_PASSED), "trace").unwrap();
+        append_golden_result(&path, &result("home", "A1", GOLDEN_PASSED), "trace").unwrap();
+
+        let latest = latest_golden_results(load_golden_results(&path), Some("login"));
+        assert_eq!(latest.len(), 2);
+        assert_eq!(latest[0].serial, "A1");
+        assert_eq!(latest[0].status, GOLDEN_PASSED);
+        assert_eq!(latest[1].status, GOLDEN_MISSING);
+        assert_eq!(
+            latest_golden_results(load_golden_results(&path), None).len(),
+            3
+        );
+    }
+}
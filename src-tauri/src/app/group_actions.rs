@@ -0,0 +1,286 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::app::models::{ApkInstallResult, CommandResult, GroupActionDeviceResult, RebootResult};
+
+pub const GROUP_ACTION_REBOOT: &str = "reboot";
+pub const GROUP_ACTION_WIFI: &str = "wifi";
+pub const GROUP_ACTION_INSTALL: &str = "install";
+pub const GROUP_ACTION_SCREENSHOT: &str = "screenshot";
+pub const GROUP_ACTIONS: [&str; 4] = [
+    GROUP_ACTION_REBOOT,
+    GROUP_ACTION_WIFI,
+    GROUP_ACTION_INSTALL,
+    GROUP_ACTION_SCREENSHOT,
+];
+
+/// Parameters of the multi-serial command each action maps to, minus the serial list.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GroupAction {
+    Reboot {
+        mode: Option<String>,
+        wait_for_boot: bool,
+        boot_timeout_secs: Option<u64>,
+    },
+    Wifi {
+        enable: bool,
+    },
+    Install {
+        apk_path: String,
+        replace: bool,
+        allow_downgrade: bool,
+        grant: bool,
+        allow_test_packages: bool,
+        extra_args: Option<String>,
+    },
+    Screenshot {
+        output_dir: Option<String>,
+    },
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+struct RebootParams {
+    mode: Option<String>,
+    wait_for_boot: bool,
+    boot_timeout_secs: Option<u64>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct WifiParams {
+    enable: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct InstallParams {
+    apk_path: String,
+    #[serde(default = "default_true")]
+    replace: bool,
+    #[serde(default)]
+    allow_downgrade: bool,
+    #[serde(default)]
+    grant: bool,
+    #[serde(default)]
+    allow_test_packages: bool,
+    #[serde(default)]
+    extra_args: Option<String>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+struct ScreenshotParams {
+    output_dir: Option<String>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn params_of<T: for<'de> Deserialize<'de>>(action: &str, params: &Value) -> Result<T, String> {
+    let params = if params.is_null() {
+        Value::Object(Default::default())
+    } else {
+        params.clone()
+    };
+    serde_json::from_value(params).map_err(|err| format!("Invalid {action} params: {err}"))
+}
+
+pub fn parse_group_action(action: &str, params: &Value) -> Result<GroupAction, String> {
+    match action.trim() {
+        GROUP_ACTION_REBOOT => {
+            let params: RebootParams = params_of(GROUP_ACTION_REBOOT, params)?;
+            Ok(GroupAction::Reboot {
+                mode: params.mode,
+                wait_for_boot: params.wait_for_boot,
+                boot_timeout_secs: params.boot_timeout_secs,
+            })
+        }
+        GROUP_ACTION_WIFI => {
+            let params: WifiParams = params_of(GROUP_ACTION_WIFI, params)?;
+            Ok(GroupAction::Wifi {
+                enable: params.enable,
+            })
+        }
+        GROUP_ACTION_INSTALL => {
+            let params: InstallParams = params_of(GROUP_ACTION_INSTALL, params)?;
+            Ok(GroupAction::Install {
+                apk_path: params.apk_path,
+                replace: params.replace,
+                allow_downgrade: params.allow_downgrade,
+                grant: params.grant,
+                allow_test_packages: params.allow_test_packages,
+                extra_args: params.extra_args,
+            })
+        }
+        GROUP_ACTION_SCREENSHOT => {
+            let params: ScreenshotParams = params_of(GROUP_ACTION_SCREENSHOT, params)?;
+            Ok(GroupAction::Screenshot {
+                output_dir: params.output_dir,
+            })
+        }
+        other => Err(format!(
+            "Unknown group action: {other} (expected one of {})",
+            GROUP_ACTIONS.join(", ")
+        )),
+    }
+}
+
+/// Members of `group` in saved order, trimmed and de-duplicated. An exact name wins over a
+/// case-insensitive match so "Pixel shelf" and "pixel shelf" can coexist.
+pub fn resolve_group_serials(
+    groups: &HashMap<String, Vec<String>>,
+    group: &str,
+) -> Result<Vec<String>, String> {
+    let group = group.trim();
+    let members = groups
+        .get(group)
+        .or_else(|| {
+            groups
+                .iter()
+                .find(|(name, _)| name.eq_ignore_ascii_case(group))
+                .map(|(_, members)| members)
+        })
+        .ok_or_else(|| format!("Device group not found: {group}"))?;
+    let mut serials: Vec<String> = Vec::new();
+    for serial in members.iter().map(|serial| serial.trim()) {
+        if !serial.is_empty() && !serials.iter().any(|existing| existing == serial) {
+            serials.push(serial.to_string());
+        }
+    }
+    if serials.is_empty() {
+        return Err(format!("Device group {group} has no devices"));
+    }
+    Ok(serials)
+}
+
+pub fn command_result_to_group(result: CommandResult) -> GroupActionDeviceResult {
+    let success = result.exit_code == Some(0);
+    GroupActionDeviceResult {
+        serial: result.serial,
+        success,
+        output: Some(result.stdout.trim().to_string()).filter(|value| !value.is_empty()),
+        error: (!success).then(|| {
+            let stderr = result.stderr.trim();
+            if stderr.is_empty() {
+                format!("Exited with code {:?}", result.exit_code)
+            } else {
+                stderr.to_string()
+            }
+        }),
+    }
+}
+
+/// A reboot the caller waited on only succeeds once the device finished booting.
+pub fn reboot_result_to_group(result: RebootResult) -> GroupActionDeviceResult {
+    let mut group = command_result_to_group(result.result);
+    if group.success && result.boot_completed == Some(false) {
+        group.success = false;
+        group.error = Some("Device did not finish booting before the timeout".to_string());
+    }
+    group
+}
+
+pub fn install_result_to_group(result: ApkInstallResult) -> GroupActionDeviceResult {
+    GroupActionDeviceResult {
+        serial: result.serial.clone(),
+        success: result.success,
+        output: None,
+        error: (!result.success).then(|| result.error_message()),
+    }
+}
+
+/// Results in group order; serials the underlying command did not report are failures.
+pub fn order_group_results(
+    serials: &[String],
+    mut results: HashMap<String, GroupActionDeviceResult>,
+) -> Vec<GroupActionDeviceResult> {
+    serials
+        .iter()
+        .map(|serial| {
+            results
+                .remove(serial)
+                .unwrap_or_else(|| GroupActionDeviceResult {
+                    serial: serial.clone(),
+                    success: false,
+                    output: None,
+                    error: Some("No result reported for this device".to_string()),
+                })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn parses_actions_and_params() {
+        assert_eq!(
+            parse_group_action("reboot", &Value::Null).unwrap(),
+            GroupAction::Reboot {
+                mode: None,
+                wait_for_boot: false,
+                boot_timeout_secs: None,
+            }
+        );
+        assert_eq!(
+            parse_group_action("reboot", &json!({ "wait_for_boot": true })).unwrap(),
+            GroupAction::Reboot {
+                mode: None,
+                wait_for_boot: true,
+                boot_timeout_secs: None,
+            }
+        );
+        assert_eq!(
+            parse_group_action("wifi", &json!({ "enable": false })).unwrap(),
+            GroupAction::Wifi { enable: false }
+        );
+        assert!(parse_group_action("wifi", &Value::Null).is_err());
+        match parse_group_action("install", &json!({ "apk_path": "/tmp/app.apk" })).unwrap() {
+            GroupAction::Install { replace, grant, .. } => assert!(replace && !grant),
+            other => panic!("unexpected action {other:?}"),
+        }
+        assert!(parse_group_action("screenshot", &json!({ "bogus": 1 })).is_err());
+        assert!(parse_group_action("factory_reset", &Value::Null).is_err());
+    }
+
+    #[test]
+    fn resolves_groups() {
+        let groups = HashMap::from([
+            (
+                "Pixel shelf".to_string(),
+                vec![" A1 ".to_string(), "B2".to_string(), "A1".to_string()],
+            ),
+            ("empty".to_string(), vec![" ".to_string()]),
+        ]);
+        assert_eq!(
+            resolve_group_serials(&groups, "pixel SHELF").unwrap(),
+            vec!["A1", "B2"]
+        );
+        assert!(resolve_group_serials(&groups, "empty").is_err());
+        assert!(resolve_group_serials(&groups, "missing").is_err());
+    }
+
+    #[test]
+    fn maps_results_in_group_order() {
+        let failed = command_result_to_group(CommandResult {
+            serial: "B2".to_string(),
+            stdout: String::new(),
+            stderr: "error: device offline\n".to_string(),
+            exit_code: Some(1),
+        });
+        assert_eq!(failed.error.as_deref(), Some("error: device offline"));
+        let ordered = order_group_results(
+            &["A1".to_string(), "B2".to_string()],
+            HashMap::from([("B2".to_string(), failed)]),
+        );
+        assert_eq!(ordered[0].serial, "A1");
+        assert!(!ordered[0].success);
+        assert_eq!(ordered[1].serial, "B2");
+    }
+}
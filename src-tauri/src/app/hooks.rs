@@ -0,0 +1,368 @@
+use std::io::{Read, Write};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use chrono::{SecondsFormat, Utc};
+use serde::Serialize;
+use tracing::{info, warn};
+
+use crate::app::config::{load_config, HookScript, HooksSettings};
+use crate::app::error::AppError;
+use crate::app::i18n::Message;
+
+pub const HOOK_DEVICE_CONNECTED: &str = "device_connected";
+pub const HOOK_INSTALL_COMPLETE: &str = "install_complete";
+pub const HOOK_BUGREPORT_FINISHED: &str = "bugreport_finished";
+pub const HOOK_TEST: &str = "test";
+
+pub const HOOK_EVENTS: [&str; 4] = [
+    HOOK_DEVICE_CONNECTED,
+    HOOK_INSTALL_COMPLETE,
+    HOOK_BUGREPORT_FINISHED,
+    HOOK_TEST,
+];
+
+pub const DEFAULT_HOOK_TIMEOUT_SECS: u64 = 30;
+pub const MAX_HOOK_TIMEOUT_SECS: u64 = 600;
+
+/// Hook stdout/stderr beyond this is dropped; it is only kept for the log.
+const HOOK_OUTPUT_MAX_BYTES: usize = 4096;
+
+/// JSON written to the hook's stdin. `data` carries event-specific fields.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct HookPayload {
+    pub event: String,
+    pub serial: String,
+    pub success: bool,
+    pub summary: String,
+    pub timestamp: String,
+    pub trace_id: String,
+    pub data: serde_json::Value,
+}
+
+impl HookPayload {
+    pub fn new(
+        event: &str,
+        serial: &str,
+        success: bool,
+        summary: &str,
+        data: serde_json::Value,
+        trace_id: &str,
+    ) -> Self {
+        Self {
+            event: event.to_string(),
+            serial: serial.to_string(),
+            success,
+            summary: summary.to_string(),
+            timestamp: Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true),
+            trace_id: trace_id.to_string(),
+            data,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct HookRunResult {
+    pub hook: String,
+    pub exit_code: Option<i32>,
+    pub stdout: String,
+    pub stderr: String,
+    pub duration_ms: u64,
+}
+
+/// Trim and default a configured hook, or explain why it cannot be used. The executable must
+/// be an absolute path so hooks never depend on the app's `PATH`. An unknown event is an error
+/// rather than being dropped: a hook left with no events would otherwise fire on every event.
+pub fn normalize_hook(hook: &mut HookScript) -> Result<(), String> {
+    hook.name = hook.name.trim().to_string();
+    hook.executable = hook.executable.trim().to_string();
+    hook.timeout_secs = hook.timeout_secs.clamp(1, MAX_HOOK_TIMEOUT_SECS);
+    if hook.name.is_empty() {
+        return Err("Hook name is required".to_string());
+    }
+    if !Path::new(&hook.executable).is_absolute() {
+        return Err(format!(
+            "Hook {} must use an absolute executable path",
+            hook.name
+        ));
+    }
+    let mut events = Vec::new();
+    for event in &hook.events {
+        let event = event.trim().to_lowercase();
+        if event.is_empty() || events.contains(&event) {
+            continue;
+        }
+        if !HOOK_EVENTS.contains(&event.as_str()) {
+            return Err(format!(
+                "Hook {} has unknown event {event} (expected one of {})",
+                hook.name,
+                HOOK_EVENTS.join(", ")
+            ));
+        }
+        events.push(event);
+    }
+    if events.is_empty() && !hook.events.is_empty() {
+        return Err(format!("Hook {} has no valid events", hook.name));
+    }
+    hook.events = events;
+    Ok(())
+}
+
+/// Check every hook before saving so a bad entry is reported instead of being dropped.
+pub fn validate_hooks(settings: &HooksSettings) -> Result<(), String> {
+    let mut names = Vec::new();
+    for hook in &settings.scripts {
+        let mut hook = hook.clone();
+        normalize_hook(&mut hook)?;
+        if names.contains(&hook.name) {
+            return Err(format!("Duplicate hook name: {}", hook.name));
+        }
+        names.push(hook.name);
+    }
+    Ok(())
+}
+
+pub fn hooks_for_event<'a>(settings: &'a HooksSettings, event: &str) -> Vec<&'a HookScript> {
+    settings
+        .scripts
+        .iter()
+        .filter(|hook| hook.enabled)
+        .filter(|hook| {
+            event == HOOK_TEST
+                || hook.events.is_empty()
+                || hook.events.iter().any(|entry| entry == event)
+        })
+        .collect()
+}
+
+fn drain<R: Read + Send + 'static>(reader: Option<R>) -> thread::JoinHandle<Vec<u8>> {
+    thread::spawn(move || {
+        let mut buffer = Vec::new();
+        if let Some(mut reader) = reader {
+            let mut chunk = [0u8; 4096];
+            while let Ok(count) = reader.read(&mut chunk) {
+                if count == 0 {
+                    break;
+                }
+                if buffer.len() < HOOK_OUTPUT_MAX_BYTES {
+                    buffer.extend_from_slice(&chunk[..count]);
+                }
+            }
+        }
+        buffer.truncate(HOOK_OUTPUT_MAX_BYTES);
+        buffer
+    })
+}
+
+/// Run one hook with `payload` as JSON on stdin, killing it after its timeout.
+pub fn run_hook(
+    hook: &HookScript,
+    payload: &HookPayload,
+    trace_id: &str,
+) -> Result<HookRunResult, AppError> {
+    let body = serde_json::to_vec(payload).map_err(|err| {
+        AppError::system(
+            Message::keyed("operation_failed")
+                .with("action", "serialize hook payload")
+                .with("detail", &err),
+            trace_id,
+        )
+    })?;
+    let started = Instant::now();
+    let mut child = Command::new(&hook.executable)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|err| {
+            AppError::dependency(
+                Message::keyed("start_failed")
+                    .with("subject", format!("hook {}", hook.name))
+                    .with("detail", &err),
+                trace_id,
+            )
+        })?;
+    let stdout = drain(child.stdout.take());
+    let stderr = drain(child.stderr.take());
+    // Written from a thread so a hook that never reads stdin still hits its timeout.
+    if let Some(mut stdin) = child.stdin.take() {
+        let (trace_id, name) = (trace_id.to_string(), hook.name.clone());
+        thread::spawn(move || {
+            if let Err(err) = stdin.write_all(&body) {
+                warn!(trace_id = %trace_id, hook = %name, error = %err, "hook did not read its payload");
+            }
+        });
+    }
+
+    let timeout = Duration::from_secs(hook.timeout_secs);
+    let exit_code = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status.code(),
+            Ok(None) if started.elapsed() > timeout => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(AppError::system(
+                    format!("Hook {} timed out after {}s", hook.name, hook.timeout_secs),
+                    trace_id,
+                ));
+            }
+            Ok(None) => thread::sleep(Duration::from_millis(50)),
+            Err(err) => {
+                let _ = child.kill();
+                return Err(AppError::system(
+                    Message::keyed("operation_failed")
+                        .with("action", format!("poll hook {}", hook.name))
+                        .with("detail", &err),
+                    trace_id,
+                ));
+            }
+        }
+    };
+    Ok(HookRunResult {
+        hook: hook.name.clone(),
+        exit_code,
+        stdout: String::from_utf8_lossy(&stdout.join().unwrap_or_default()).to_string(),
+        stderr: String::from_utf8_lossy(&stderr.join().unwrap_or_default()).to_string(),
+        duration_ms: started.elapsed().as_millis() as u64,
+    })
+}
+
+pub fn log_hook_result(
+    result: &Result<HookRunResult, AppError>,
+    hook: &str,
+    event: &str,
+    trace_id: &str,
+) {
+    match result {
+        Ok(result) if result.exit_code == Some(0) => {
+            info!(trace_id = %trace_id, hook = %hook, event = %event, duration_ms = result.duration_ms, "hook finished")
+        }
+        Ok(result) => {
+            warn!(trace_id = %trace_id, hook = %hook, event = %event, exit_code = ?result.exit_code, stderr = %result.stderr.trim(), "hook failed")
+        }
+        Err(err) => {
+            warn!(trace_id = %trace_id, hook = %hook, event = %event, error = %err.error, "hook failed")
+        }
+    }
+}
+
+/// Run matching hooks one after another on a background thread; failures are logged, never
+/// surfaced to the operation that triggered them.
+pub fn run_hooks(
+    event: &str,
+    serial: &str,
+    success: bool,
+    summary: &str,
+    data: serde_json::Value,
+    trace_id: &str,
+) {
+    let config = match load_config(trace_id) {
+        Ok(config) => config,
+        Err(err) => {
+            warn!(trace_id = %trace_id, error = %err.error, "failed to load config for hooks");
+            return;
+        }
+    };
+    let hooks: Vec<HookScript> = hooks_for_event(&config.hooks, event)
+        .into_iter()
+        .cloned()
+        .collect();
+    if hooks.is_empty() {
+        return;
+    }
+    let payload = HookPayload::new(event, serial, success, summary, data, trace_id);
+    let trace_id = trace_id.to_string();
+    thread::spawn(move || {
+        for hook in &hooks {
+            let result = run_hook(hook, &payload, &trace_id);
+            log_hook_result(&result, &hook.name, &payload.event, &trace_id);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hook(name: &str, executable: &str, events: &[&str]) -> HookScript {
+        HookScript {
+            name: name.to_string(),
+            executable: executable.to_string(),
+            events: events.iter().map(|event| event.to_string()).collect(),
+            timeout_secs: DEFAULT_HOOK_TIMEOUT_SECS,
+            enabled: true,
+        }
+    }
+
+    #[test]
+    fn normalizes_hooks_and_selects_them_by_event() {
+        let mut valid = hook(
+            " notify ",
+            "/usr/local/bin/notify.sh",
+            &[" Install_Complete ", "reboot", "install_complete"],
+        );
+        assert!(normalize_hook(&mut valid.clone())
+            .unwrap_err()
+            .contains("unknown event reboot"));
+        valid.events.retain(|event| event != "reboot");
+        valid.timeout_secs = 0;
+        assert_eq!(normalize_hook(&mut valid), Ok(()));
+        assert_eq!(valid.name, "notify");
+        assert_eq!(valid.events, vec![HOOK_INSTALL_COMPLETE]);
+        assert_eq!(valid.timeout_secs, 1);
+        assert!(normalize_hook(&mut hook("relative", "notify.sh", &[])).is_err());
+        assert!(normalize_hook(&mut hook(" ", "/bin/true", &[])).is_err());
+        // Only unknown events must not turn into "every event".
+        assert!(normalize_hook(&mut hook("typo", "/bin/true", &["install_done"])).is_err());
+        assert!(normalize_hook(&mut hook("blank", "/bin/true", &[" "])).is_err());
+        assert!(validate_hooks(&HooksSettings {
+            scripts: vec![hook("a", "/bin/true", &[]), hook("a", "/bin/false", &[])],
+        })
+        .is_err());
+
+        let mut disabled = hook("off", "/bin/true", &[]);
+        disabled.enabled = false;
+        let settings = HooksSettings {
+            scripts: vec![valid, hook("all", "/bin/true", &[]), disabled],
+        };
+        let names = |event: &str| -> Vec<String> {
+            hooks_for_event(&settings, event)
+                .iter()
+                .map(|hook| hook.name.clone())
+                .collect()
+        };
+        assert_eq!(names(HOOK_INSTALL_COMPLETE), ["notify", "all"]);
+        assert_eq!(names(HOOK_DEVICE_CONNECTED), ["all"]);
+        assert_eq!(names(HOOK_TEST), ["notify", "all"]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn runs_hooks_with_the_payload_on_stdin() {
+        let payload = HookPayload::new(
+            HOOK_BUGREPORT_FINISHED,
+            "emulator-5554",
+            true,
+            "Bugreport saved",
+            serde_json::json!({ "output_path": "/tmp/bugreport.zip" }),
+            "trace-1",
+        );
+        let result = run_hook(&hook("cat", "/bin/cat", &[]), &payload, "trace-1").unwrap();
+        assert_eq!(result.exit_code, Some(0));
+        let echoed: serde_json::Value = serde_json::from_str(&result.stdout).unwrap();
+        assert_eq!(echoed["event"], HOOK_BUGREPORT_FINISHED);
+        assert_eq!(echoed["serial"], "emulator-5554");
+        assert_eq!(echoed["data"]["output_path"], "/tmp/bugreport.zip");
+
+        let failing = run_hook(&hook("false", "/bin/false", &[]), &payload, "trace-1").unwrap();
+        assert_ne!(failing.exit_code, Some(0));
+        assert!(run_hook(
+            &hook("missing", "/nonexistent/hook", &[]),
+            &payload,
+            "trace-1"
+        )
+        .is_err());
+    }
+}
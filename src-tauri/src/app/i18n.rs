@@ -0,0 +1,622 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::{OnceLock, RwLock};
+
+pub const LOCALE_EN: &str = "en";
+pub const LOCALE_ZH_TW: &str = "zh-TW";
+pub const SUPPORTED_LOCALES: [&str; 2] = [LOCALE_EN, LOCALE_ZH_TW];
+
+/// A message shape emitted by commands. `en` is the English text with `{name}` placeholders;
+/// errors are created through [`Message::keyed`] so the key and params travel with them.
+struct CatalogEntry {
+    key: &'static str,
+    en: &'static str,
+    zh_tw: &'static str,
+}
+
+const fn entry(key: &'static str, en: &'static str, zh_tw: &'static str) -> CatalogEntry {
+    CatalogEntry { key, en, zh_tw }
+}
+
+const CATALOG: &[CatalogEntry] = &[
+    entry("required", "{field} is required", "必須提供 {field}"),
+    entry(
+        "already_running",
+        "{subject} already running",
+        "{subject} 已在執行中",
+    ),
+    entry("not_running", "{subject} not running", "{subject} 未在執行"),
+    entry(
+        "already_active",
+        "{subject} already active",
+        "{subject} 已在進行中",
+    ),
+    entry(
+        "capture_context_active",
+        "A capture context is already active for this device",
+        "此裝置已有進行中的擷取情境",
+    ),
+    entry(
+        "no_recording",
+        "No recording in progress",
+        "目前沒有進行中的錄影",
+    ),
+    entry(
+        "no_gesture_recording",
+        "No gesture recording in progress",
+        "目前沒有進行中的手勢錄製",
+    ),
+    entry("no_touches", "No touches were recorded", "沒有錄到任何觸控"),
+    entry(
+        "no_touchscreen",
+        "No touchscreen input device found",
+        "找不到觸控螢幕輸入裝置",
+    ),
+    entry(
+        "screen_size_unreadable",
+        "Unable to read device screen size",
+        "無法讀取裝置螢幕尺寸",
+    ),
+    entry(
+        "no_launcher_activity",
+        "App has no launcher activity",
+        "此 App 沒有啟動器 Activity",
+    ),
+    entry(
+        "snapshots_emulator_only",
+        "Snapshots are only available for emulators",
+        "快照僅支援模擬器",
+    ),
+    entry(
+        "local_path_not_file",
+        "Local path must be a file",
+        "本機路徑必須是檔案",
+    ),
+    entry(
+        "paths_must_differ",
+        "from_path and to_path must be different",
+        "from_path 與 to_path 不可相同",
+    ),
+    entry(
+        "launch_mode",
+        "mode must be cold or warm",
+        "mode 必須是 cold 或 warm",
+    ),
+    entry(
+        "port_in_use",
+        "Local port {port} is already in use; choose another tunnel port",
+        "本機連接埠 {port} 已被占用，請改用其他通道連接埠",
+    ),
+    entry(
+        "perf_aggregation_devices",
+        "Perf aggregation needs at least two devices",
+        "效能彙整至少需要兩台裝置",
+    ),
+    entry(
+        "debug_agent_path_missing",
+        "Configure the debug agent binary path first",
+        "請先設定除錯代理程式的路徑",
+    ),
+    entry(
+        "debug_agent_not_pushed",
+        "Debug agent is not on the device; push it first",
+        "裝置上沒有除錯代理程式，請先推送",
+    ),
+    entry(
+        "debug_agent_exited",
+        "Debug agent exited right after start",
+        "除錯代理程式啟動後立即結束",
+    ),
+    entry(
+        "build_project_missing",
+        "No build project directory configured",
+        "尚未設定建置專案目錄",
+    ),
+    entry(
+        "jira_not_configured",
+        "Jira base_url and project_key must be configured",
+        "必須設定 Jira 的 base_url 與 project_key",
+    ),
+    entry(
+        "report_extension",
+        "Report path must end with .html",
+        "報告路徑必須以 .html 結尾",
+    ),
+    entry(
+        "step_not_skippable",
+        "Setup step `{step}` cannot be skipped",
+        "設定步驟 `{step}` 無法略過",
+    ),
+    entry(
+        "step_order",
+        "Complete the `{step}` step first",
+        "請先完成 `{step}` 步驟",
+    ),
+    entry(
+        "restricted_action",
+        "{action} is disabled in restricted mode",
+        "受限模式下無法使用 {action}",
+    ),
+    entry(
+        "max_length",
+        "{field} must be at most {max} characters",
+        "{field} 最多 {max} 個字元",
+    ),
+    entry(
+        "too_many_artifacts",
+        "At most {max} artifacts can be attached",
+        "最多只能附加 {max} 個檔案",
+    ),
+    entry(
+        "too_many_uploads",
+        "At most {max} files can be uploaded at once",
+        "一次最多只能上傳 {max} 個檔案",
+    ),
+    entry(
+        "too_many_pinned_uids",
+        "Too many pinned UIDs (max {max})",
+        "釘選的 UID 過多（上限 {max}）",
+    ),
+    entry(
+        "field_count",
+        "Provide 1 to {max} fields",
+        "請提供 1 到 {max} 個欄位",
+    ),
+    entry(
+        "gesture_count",
+        "Scenario needs 1 to {max} gestures",
+        "情境需要 1 到 {max} 個手勢",
+    ),
+    entry(
+        "range",
+        "{field} must be between {min} and {max}",
+        "{field} 必須介於 {min} 與 {max} 之間",
+    ),
+    entry(
+        "insufficient_host_storage",
+        "Insufficient host storage: {required} required, {available} available",
+        "主機儲存空間不足：需要 {required}，可用 {available}",
+    ),
+    entry(
+        "tunnel_port_timeout",
+        "SSH tunnel timed out waiting for the forwarded port",
+        "SSH 通道等待轉送連接埠逾時",
+    ),
+    entry("device_farm_running", "A device farm run is already in progress", "已有進行中的裝置農場測試"),
+    entry("no_device_farm_run", "No device farm run in progress", "目前沒有進行中的裝置農場測試"),
+    entry("device_not_connected", "Device {serial} is not connected", "裝置 {serial} 未連線"),
+    entry(
+        "package_not_installed",
+        "{package} is not installed on {serial}",
+        "{serial} 上未安裝 {package}",
+    ),
+    entry("not_regular_file", "{path} is not a regular file", "{path} 不是一般檔案"),
+    entry(
+        "device_path_not_absolute",
+        "Device file paths must be absolute: {path}",
+        "裝置檔案路徑必須是絕對路徑：{path}",
+    ),
+    entry(
+        "profile_selection_empty",
+        "Select at least one app, setting or file",
+        "請至少選擇一個 App、設定或檔案",
+    ),
+    entry("tcpip_needs_usb", "adb tcpip needs a USB-connected device", "adb tcpip 需要以 USB 連線的裝置"),
+    entry(
+        "no_wifi_address",
+        "Device has no Wi-Fi address; connect it to the same network first",
+        "裝置沒有 Wi-Fi 位址，請先連上同一個網路",
+    ),
+    entry("service_not_found", "Service not found on device: {service}", "裝置上找不到服務：{service}"),
+    entry(
+        "boot_wait_unsupported",
+        "Cannot wait for boot when rebooting to {mode}",
+        "重新開機至 {mode} 時無法等待開機完成",
+    ),
+    entry(
+        "no_on_connect_profile",
+        "No enabled on-connect profile for {serial}",
+        "{serial} 沒有已啟用的連線設定檔",
+    ),
+    entry(
+        "root_shell_failed",
+        "Root shell failed (is the device rooted?): {detail}",
+        "Root shell 執行失敗（裝置是否已 root？）：{detail}",
+    ),
+    entry(
+        "locale_change_rejected",
+        "The device did not accept the locale change (requires an emulator or userdebug build)",
+        "裝置未接受語系變更（需要模擬器或 userdebug 版本）",
+    ),
+    entry(
+        "no_saved_i18n_settings",
+        "No saved i18n settings to restore for this device",
+        "此裝置沒有可還原的語系設定",
+    ),
+    entry("soak_test_running", "A soak test is already running for this device", "此裝置已有進行中的長時間測試"),
+    entry("no_apk_path", "pm path returned no APK path", "pm path 沒有回傳 APK 路徑"),
+    entry("apk_icon_missing", "Failed to locate icon in APK", "在 APK 中找不到圖示"),
+    entry("icon_too_large", "Icon file too large to preview", "圖示檔過大，無法預覽"),
+    entry("overlay_not_mutable", "Overlay {package} cannot be toggled", "覆蓋層 {package} 無法切換"),
+    entry("volume_change_failed", "Failed to change any stream volume", "無法變更任何音訊串流的音量"),
+    entry(
+        "no_gfxinfo_frames",
+        "gfxinfo reported no frames; is the app in the foreground?",
+        "gfxinfo 沒有回報任何影格，App 是否在前景？",
+    ),
+    entry(
+        "monkey_running",
+        "A monkey run is already in progress on this device",
+        "此裝置已有進行中的 Monkey 測試",
+    ),
+    entry(
+        "text_needs_adb_keyboard",
+        "Text contains characters `input text` cannot type; install ADBKeyboard (com.android.adbkeyboard) to send Unicode",
+        "文字含有 `input text` 無法輸入的字元，請安裝 ADBKeyboard（com.android.adbkeyboard）以傳送 Unicode",
+    ),
+    entry(
+        "input_broadcast_running",
+        "Input broadcast already running for this leader",
+        "此主控裝置已有進行中的輸入廣播",
+    ),
+    entry(
+        "dmesg_needs_root",
+        "Reading the kernel log needs root: run adb root or use a build with su",
+        "讀取核心日誌需要 root：請執行 adb root 或使用含 su 的版本",
+    ),
+    entry("not_installed", "{subject} {name} is not installed", "未安裝{subject} {name}"),
+    entry("not_active", "{subject} not active", "{subject} 未在進行中"),
+    entry("at_least", "{field} must be at least {min}", "{field} 至少須為 {min}"),
+    entry("one_of", "{field} must be one of {values}", "{field} 必須是下列其中之一：{values}"),
+    entry(
+        "length_range",
+        "{field} must be {min} to {max} characters",
+        "{field} 必須為 {min} 到 {max} 個字元",
+    ),
+    entry("positive_integer", "{field} must be a positive integer", "{field} 必須是正整數"),
+    entry("adb_empty", "ADB command is empty", "ADB 指令為空"),
+    entry(
+        "adb_not_executable",
+        "ADB path must point to an executable file",
+        "ADB 路徑必須指向可執行檔",
+    ),
+    entry(
+        "adb_not_found",
+        "ADB executable not found at the configured path",
+        "在設定的路徑找不到 ADB 執行檔",
+    ),
+    entry(
+        "not_available",
+        "{subject} is not available",
+        "{subject} 無法使用",
+    ),
+    entry(
+        "screenshot_failed",
+        "Screenshot failed (exec-out): {detail}. Fallback failed: {fallback}",
+        "截圖失敗（exec-out）：{detail}。備援方式也失敗：{fallback}",
+    ),
+    entry(
+        "unknown_webhook",
+        "Unknown or disabled webhook: {name}",
+        "未知或已停用的 Webhook：{name}",
+    ),
+    entry(
+        "invalid_value",
+        "Invalid {subject}: {value}",
+        "無效的{subject}：{value}",
+    ),
+    entry("invalid", "Invalid {subject}", "無效的{subject}"),
+    entry(
+        "unknown_value",
+        "Unknown {subject}: {value}",
+        "未知的{subject}：{value}",
+    ),
+    entry(
+        "unsupported_value",
+        "Unsupported {subject}: {value}",
+        "不支援的{subject}：{value}",
+    ),
+    entry(
+        "not_found_value",
+        "{subject} not found: {value}",
+        "找不到{subject}：{value}",
+    ),
+    entry("not_found", "{subject} not found", "找不到{subject}"),
+    entry(
+        "does_not_exist",
+        "{subject} does not exist",
+        "{subject}不存在",
+    ),
+    entry(
+        "config_invalid",
+        "Config file is invalid: {detail}",
+        "設定檔無效：{detail}",
+    ),
+    entry(
+        "unexpected_response",
+        "Unexpected response from {service}",
+        "{service} 回應格式不符預期",
+    ),
+    entry(
+        "start_failed",
+        "Failed to start {subject}: {detail}",
+        "無法啟動 {subject}：{detail}",
+    ),
+    entry(
+        "operation_failed",
+        "Failed to {action}: {detail}",
+        "操作失敗（{action}）：{detail}",
+    ),
+    entry(
+        "action_failed",
+        "{action} failed: {detail}",
+        "{action} 失敗：{detail}",
+    ),
+    entry("action_failed_plain", "{action} failed", "{action} 失敗"),
+];
+
+/// Nouns substituted into `{subject}` and `{field}` when they are known.
+const TERMS_ZH_TW: &[(&str, &str)] = &[
+    ("APK directory", "APK 目錄"),
+    ("APK path", "APK 路徑"),
+    ("APK watch", "APK 監看"),
+    ("Accessibility service", "無障礙服務"),
+    ("Accuracy", "精確度"),
+    ("Artifact", "產出檔案"),
+    ("Bluetooth monitor", "藍牙監控"),
+    ("Bugreport", "錯誤報告"),
+    ("Capture context", "擷取情境"),
+    ("Debug agent binary", "除錯代理程式"),
+    ("File", "檔案"),
+    ("Gesture recording", "手勢錄製"),
+    ("Input method", "輸入法"),
+    ("Label", "標籤"),
+    ("Latitude", "緯度"),
+    ("Local file", "本機檔案"),
+    ("Longitude", "經度"),
+    ("Memory leak watch", "記憶體洩漏監看"),
+    ("Monkey run", "Monkey 測試"),
+    ("Net profiler", "網路分析"),
+    ("Perf aggregation", "效能彙整"),
+    ("Overlay", "覆蓋層"),
+    ("Perf monitor", "效能監控"),
+    ("Project directory", "專案目錄"),
+    ("Recording", "錄影"),
+    ("Recording file", "錄影檔"),
+    ("Terminal session", "終端機工作階段"),
+    ("Thermal monitor", "溫度監控"),
+    ("Title", "標題"),
+    ("UI dump backend", "UI 擷取後端"),
+    ("adb server host", "adb 伺服器主機"),
+    ("gesture file", "手勢檔"),
+    ("issue provider", "議題追蹤服務"),
+    ("module name", "模組名稱"),
+    ("package name", "套件名稱"),
+    ("upload target", "上傳目標"),
+];
+
+const TRANSLATED_PARAMS: [&str; 2] = ["subject", "field"];
+
+/// A user-facing message: the catalog `key` and `params` it was created from, and its English
+/// `text`. Plain strings convert into a message without a key and are shown as they are.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Message {
+    pub key: Option<&'static str>,
+    pub params: BTreeMap<String, String>,
+    pub text: String,
+}
+
+impl From<String> for Message {
+    fn from(text: String) -> Self {
+        Self {
+            key: None,
+            params: BTreeMap::new(),
+            text,
+        }
+    }
+}
+
+impl From<&String> for Message {
+    fn from(text: &String) -> Self {
+        text.clone().into()
+    }
+}
+
+impl From<&str> for Message {
+    fn from(text: &str) -> Self {
+        text.to_string().into()
+    }
+}
+
+fn catalog_entry(key: &str) -> Option<&'static CatalogEntry> {
+    CATALOG.iter().find(|entry| entry.key == key)
+}
+
+fn render(template: &str, params: &BTreeMap<String, String>, translate: bool) -> String {
+    params
+        .iter()
+        .fold(template.to_string(), |text, (name, value)| {
+            let value = if translate && TRANSLATED_PARAMS.contains(&name.as_str()) {
+                translate_term(value)
+            } else {
+                value.clone()
+            };
+            text.replace(&format!("{{{name}}}"), &value)
+        })
+}
+
+impl Message {
+    /// The catalog message `key`; fill its placeholders with [`Message::with`].
+    pub fn keyed(key: &'static str) -> Self {
+        debug_assert!(catalog_entry(key).is_some(), "unknown message key {key}");
+        Self {
+            key: Some(key),
+            params: BTreeMap::new(),
+            text: catalog_entry(key).map_or(key, |entry| entry.en).to_string(),
+        }
+    }
+
+    pub fn with(mut self, name: &str, value: impl fmt::Display) -> Self {
+        self.params.insert(name.to_string(), value.to_string());
+        if let Some(entry) = self.key.and_then(catalog_entry) {
+            self.text = render(entry.en, &self.params, false);
+        }
+        self
+    }
+}
+
+fn locale_slot() -> &'static RwLock<String> {
+    static SLOT: OnceLock<RwLock<String>> = OnceLock::new();
+    SLOT.get_or_init(|| RwLock::new(LOCALE_EN.to_string()))
+}
+
+/// Map user-provided language tags (`zh_TW`, `zh-Hant`, ...) to a supported locale.
+pub fn normalize_locale(value: &str) -> String {
+    let lowered = value.trim().replace('_', "-").to_ascii_lowercase();
+    if lowered == "zh-tw" || lowered.starts_with("zh-hant") {
+        LOCALE_ZH_TW.to_string()
+    } else {
+        LOCALE_EN.to_string()
+    }
+}
+
+pub fn set_message_locale(locale: &str) {
+    if let Ok(mut guard) = locale_slot().write() {
+        *guard = normalize_locale(locale);
+    }
+}
+
+pub fn message_locale() -> String {
+    locale_slot()
+        .read()
+        .map(|guard| guard.clone())
+        .unwrap_or_else(|_| LOCALE_EN.to_string())
+}
+
+fn translate_term(value: &str) -> String {
+    TERMS_ZH_TW
+        .iter()
+        .find(|(en, _)| *en == value)
+        .map(|(_, zh_tw)| zh_tw.to_string())
+        .unwrap_or_else(|| value.to_string())
+}
+
+/// Render a message in `locale` from its key and params; messages without a key keep their
+/// English `text`.
+pub fn localize(
+    key: Option<&str>,
+    params: &BTreeMap<String, String>,
+    text: &str,
+    locale: &str,
+) -> String {
+    match key.and_then(catalog_entry) {
+        Some(entry) if locale == LOCALE_ZH_TW => render(entry.zh_tw, params, true),
+        _ => text.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use regex::Regex;
+
+    fn zh_tw(message: Message) -> String {
+        localize(message.key, &message.params, &message.text, LOCALE_ZH_TW)
+    }
+
+    #[test]
+    fn normalizes_locale_tags() {
+        assert_eq!(normalize_locale("zh_TW"), LOCALE_ZH_TW);
+        assert_eq!(normalize_locale(" zh-Hant-TW "), LOCALE_ZH_TW);
+        assert_eq!(normalize_locale("fr"), LOCALE_EN);
+        assert_eq!(normalize_locale(""), LOCALE_EN);
+    }
+
+    #[test]
+    fn renders_english_from_the_template() {
+        let required = Message::keyed("required").with("field", "serials");
+        assert_eq!(required.key, Some("required"));
+        assert_eq!(
+            required.params.get("field").map(String::as_str),
+            Some("serials")
+        );
+        assert_eq!(required.text, "serials is required");
+
+        let failed = Message::keyed("action_failed")
+            .with("action", "Pull")
+            .with("detail", "remote object does not exist");
+        assert_eq!(failed.text, "Pull failed: remote object does not exist");
+        assert_eq!(
+            localize(failed.key, &failed.params, &failed.text, LOCALE_EN),
+            failed.text
+        );
+    }
+
+    #[test]
+    fn renders_traditional_chinese() {
+        assert_eq!(
+            zh_tw(Message::keyed("already_running").with("subject", "Perf monitor")),
+            "效能監控 已在執行中"
+        );
+        assert_eq!(
+            zh_tw(
+                Message::keyed("invalid_value")
+                    .with("subject", "package name")
+                    .with("value", "com..x")
+            ),
+            "無效的套件名稱：com..x"
+        );
+        assert_eq!(
+            zh_tw(
+                Message::keyed("range")
+                    .with("field", "Latitude")
+                    .with("min", -90)
+                    .with("max", 90)
+            ),
+            "緯度 必須介於 -90 與 90 之間"
+        );
+        assert_eq!(
+            zh_tw(
+                Message::keyed("screenshot_failed")
+                    .with("detail", "timeout")
+                    .with("fallback", "denied")
+            ),
+            "截圖失敗（exec-out）：timeout。備援方式也失敗：denied"
+        );
+    }
+
+    #[test]
+    fn passes_plain_messages_through() {
+        let plain = Message::from("device lock poisoned");
+        assert_eq!(plain.key, None);
+        assert!(plain.params.is_empty());
+        assert_eq!(zh_tw(plain), "device lock poisoned");
+    }
+
+    #[test]
+    fn catalog_keys_are_unique_and_fully_rendered() {
+        let placeholder = Regex::new(r"\{([a-z_]+)\}").expect("valid placeholder regex");
+        for (index, entry) in CATALOG.iter().enumerate() {
+            assert!(
+                CATALOG[..index].iter().all(|other| other.key != entry.key),
+                "duplicate key {}",
+                entry.key
+            );
+            let rendered = placeholder
+                .captures_iter(entry.en)
+                .fold(Message::keyed(entry.key), |message, caps| {
+                    message.with(&caps[1], "x")
+                });
+            assert!(
+                !rendered.text.contains('{'),
+                "{} left a placeholder",
+                entry.key
+            );
+            assert!(
+                !zh_tw(rendered).contains('{'),
+                "{} zh-TW left a placeholder",
+                entry.key
+            );
+        }
+    }
+}
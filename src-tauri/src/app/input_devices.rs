@@ -0,0 +1,488 @@
+use regex::Regex;
+
+use crate::app::models::InputDeviceInfo;
+
+const DUMPSYS_MARKER: &str = "@@dumpsys";
+
+/// `getevent -lp` for the kernel view of every `/dev/input` node (event types, axes, ids),
+/// then `dumpsys input` for how InputReader classified each one and which key layout and
+/// key character map files it loaded.
+pub const INPUT_DEVICES_SCRIPT: &str = concat!(
+    "getevent -lp 2>/dev/null; ",
+    "echo @@dumpsys; ",
+    "dumpsys input 2>/dev/null",
+);
+
+/// `InputDeviceClass` bits; releases before Android 12 print `Classes:` as a hex mask.
+const DEVICE_CLASS_BITS: [(u32, &str); 19] = [
+    (0x0000_0001, "KEYBOARD"),
+    (0x0000_0002, "ALPHAKEY"),
+    (0x0000_0004, "TOUCH"),
+    (0x0000_0008, "CURSOR"),
+    (0x0000_0010, "TOUCH_MT"),
+    (0x0000_0020, "DPAD"),
+    (0x0000_0040, "GAMEPAD"),
+    (0x0000_0080, "SWITCH"),
+    (0x0000_0100, "JOYSTICK"),
+    (0x0000_0200, "VIBRATOR"),
+    (0x0000_0400, "MIC"),
+    (0x0000_0800, "EXTERNAL_STYLUS"),
+    (0x0000_1000, "ROTARY_ENCODER"),
+    (0x0000_2000, "SENSOR"),
+    (0x0000_4000, "BATTERY"),
+    (0x0000_8000, "LIGHT"),
+    (0x0001_0000, "TOUCHPAD"),
+    (0x4000_0000, "VIRTUAL"),
+    (0x8000_0000, "EXTERNAL"),
+];
+
+/// One `add device` block of `getevent -lp`.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct KernelDevice {
+    path: String,
+    name: String,
+    vendor_id: Option<String>,
+    product_id: Option<String>,
+    event_types: Vec<String>,
+    keys: Vec<String>,
+    axes: Vec<String>,
+}
+
+fn hex_id(value: &str) -> Option<String> {
+    let value = value.trim().trim_start_matches("0x");
+    u32::from_str_radix(value, 16)
+        .ok()
+        .filter(|id| *id != 0)
+        .map(|id| format!("{id:04x}"))
+}
+
+fn parse_getevent_devices(output: &str) -> Vec<KernelDevice> {
+    let event_type = Regex::new(r"^([A-Z]+)\s+\([0-9a-fA-F]{4}\):\s*(.*)$")
+        .expect("getevent event type regex should compile");
+    let mut devices: Vec<KernelDevice> = Vec::new();
+    let mut in_events = false;
+    let mut current_type = String::new();
+
+    for line in output.lines() {
+        let trimmed = line.trim();
+        if let Some(rest) = trimmed.strip_prefix("add device") {
+            let path = rest
+                .split_once(':')
+                .map(|(_, path)| path.trim().to_string())
+                .unwrap_or_default();
+            devices.push(KernelDevice {
+                path,
+                ..KernelDevice::default()
+            });
+            in_events = false;
+            continue;
+        }
+        let Some(device) = devices.last_mut() else {
+            continue;
+        };
+        if trimmed == "events:" {
+            in_events = true;
+            continue;
+        }
+        if !in_events {
+            if let Some(name) = trimmed.strip_prefix("name:") {
+                device.name = name.trim().trim_matches('"').to_string();
+            } else if let Some(vendor) = trimmed.strip_prefix("vendor") {
+                device.vendor_id = hex_id(vendor);
+            } else if let Some(product) = trimmed.strip_prefix("product") {
+                device.product_id = hex_id(product);
+            }
+            continue;
+        }
+        if trimmed.ends_with(':') && !trimmed.contains('(') {
+            // `input props:` closes the event list.
+            in_events = false;
+            continue;
+        }
+        let codes = match event_type.captures(trimmed) {
+            Some(caps) => {
+                current_type = caps[1].to_string();
+                device.event_types.push(current_type.clone());
+                caps.get(2).map_or("", |codes| codes.as_str()).to_string()
+            }
+            None => trimmed.to_string(),
+        };
+        match current_type.as_str() {
+            // Axes print one per line with their ranges: `ABS_X : value 0, min 0, max 255`.
+            "ABS" => {
+                if let Some(axis) = codes.split(':').next().map(str::trim) {
+                    if !axis.is_empty() {
+                        device.axes.push(axis.to_string());
+                    }
+                }
+            }
+            "REL" => device
+                .axes
+                .extend(codes.split_whitespace().map(str::to_string)),
+            "KEY" => device
+                .keys
+                .extend(codes.split_whitespace().map(str::to_string)),
+            _ => {}
+        }
+    }
+    devices
+}
+
+fn parse_classes(value: &str) -> Vec<String> {
+    let value = value.trim();
+    if let Some(mask) = value
+        .strip_prefix("0x")
+        .and_then(|mask| u32::from_str_radix(mask, 16).ok())
+    {
+        return DEVICE_CLASS_BITS
+            .iter()
+            .filter(|(bit, _)| mask & bit != 0)
+            .map(|(_, name)| name.to_string())
+            .collect();
+    }
+    value
+        .split('|')
+        .map(str::trim)
+        .filter(|class| !class.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+fn optional(value: &str) -> Option<String> {
+    let value = value.trim();
+    (!value.is_empty() && !value.starts_with('<')).then(|| value.to_string())
+}
+
+/// The `Devices:` list of the `Event Hub State:` section of `dumpsys input`.
+fn parse_event_hub(output: &str) -> Vec<InputDeviceInfo> {
+    let header = Regex::new(r"^(-?\d+): (.*)$").expect("event hub device regex should compile");
+    let identifier = Regex::new(r"vendor=(0x[0-9a-fA-F]+), product=(0x[0-9a-fA-F]+)")
+        .expect("event hub identifier regex should compile");
+    let mut devices: Vec<InputDeviceInfo> = Vec::new();
+    let mut devices_indent: Option<usize> = None;
+
+    let mut lines = output
+        .lines()
+        .skip_while(|line| line.trim() != "Event Hub State:");
+    lines.next();
+    for line in lines {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+        let indent = line.len() - line.trim_start().len();
+        if indent == 0 {
+            break;
+        }
+        if trimmed == "Devices:" {
+            devices_indent = Some(indent);
+            continue;
+        }
+        let Some(list_indent) = devices_indent else {
+            continue;
+        };
+        if indent <= list_indent {
+            break;
+        }
+        if let Some(caps) = header.captures(trimmed) {
+            devices.push(InputDeviceInfo {
+                id: caps[1].parse().ok(),
+                name: caps[2].trim().to_string(),
+                path: None,
+                kind: String::new(),
+                classes: Vec::new(),
+                external: false,
+                enabled: None,
+                vendor_id: None,
+                product_id: None,
+                key_layout_file: None,
+                key_character_map_file: None,
+                event_types: Vec::new(),
+                axes: Vec::new(),
+            });
+            continue;
+        }
+        let (Some(device), Some((key, value))) = (devices.last_mut(), trimmed.split_once(':'))
+        else {
+            continue;
+        };
+        match key {
+            "Classes" => device.classes = parse_classes(value),
+            "Path" => device.path = optional(value),
+            "Enabled" => device.enabled = value.trim().parse().ok(),
+            "Identifier" => {
+                if let Some(caps) = identifier.captures(value) {
+                    device.vendor_id = hex_id(&caps[1]);
+                    device.product_id = hex_id(&caps[2]);
+                }
+            }
+            "KeyLayoutFile" => device.key_layout_file = optional(value),
+            "KeyCharacterMapFile" => device.key_character_map_file = optional(value),
+            _ => {}
+        }
+    }
+    devices
+}
+
+fn has_class(classes: &[String], name: &str) -> bool {
+    classes.iter().any(|class| class == name)
+}
+
+fn classify(classes: &[String]) -> &'static str {
+    let has = |name: &str| has_class(classes, name);
+    if has("GAMEPAD") || has("JOYSTICK") {
+        "gamepad"
+    } else if has("ALPHAKEY") {
+        "keyboard"
+    } else if has("TOUCHPAD") || (has("TOUCH") && has("CURSOR")) {
+        "touchpad"
+    } else if has("TOUCH") || has("TOUCH_MT") {
+        "touchscreen"
+    } else if has("EXTERNAL_STYLUS") {
+        "stylus"
+    } else if has("CURSOR") {
+        "mouse"
+    } else if has("ROTARY_ENCODER") {
+        "rotary"
+    } else if has("KEYBOARD") || has("DPAD") {
+        "keys"
+    } else if has("SENSOR") {
+        "sensor"
+    } else if has("SWITCH") {
+        "switch"
+    } else {
+        "other"
+    }
+}
+
+/// Used when InputReader did not report the node, e.g. devices it ignored or releases whose
+/// `dumpsys input` could not be read.
+fn classify_kernel(device: &KernelDevice) -> &'static str {
+    let key = |name: &str| device.keys.iter().any(|key| key == name);
+    let axis = |name: &str| device.axes.iter().any(|axis| axis == name);
+    if [
+        "BTN_GAMEPAD",
+        "BTN_SOUTH",
+        "BTN_A",
+        "BTN_JOYSTICK",
+        "BTN_TRIGGER",
+    ]
+    .iter()
+    .any(|name| key(name))
+    {
+        "gamepad"
+    } else if axis("ABS_MT_POSITION_X") {
+        "touchscreen"
+    } else if key("KEY_Q") && key("KEY_ENTER") {
+        "keyboard"
+    } else if axis("REL_X") {
+        "mouse"
+    } else if !device.keys.is_empty() {
+        "keys"
+    } else {
+        "other"
+    }
+}
+
+/// Merge InputReader's view with the kernel's, keyed by `/dev/input` path. Virtual devices
+/// (the framework's own key injector) are left out.
+pub fn parse_input_devices(output: &str) -> Vec<InputDeviceInfo> {
+    let (getevent, dumpsys) = output.split_once(DUMPSYS_MARKER).unwrap_or((output, ""));
+    let mut kernel = parse_getevent_devices(getevent);
+    let mut devices: Vec<InputDeviceInfo> = parse_event_hub(dumpsys)
+        .into_iter()
+        .filter(|device| !has_class(&device.classes, "VIRTUAL"))
+        .collect();
+
+    for device in &mut devices {
+        device.kind = classify(&device.classes).to_string();
+        device.external = has_class(&device.classes, "EXTERNAL");
+        let position = device
+            .path
+            .as_ref()
+            .and_then(|path| kernel.iter().position(|node| &node.path == path));
+        if let Some(node) = position.map(|index| kernel.remove(index)) {
+            device.vendor_id = device.vendor_id.take().or(node.vendor_id);
+            device.product_id = device.product_id.take().or(node.product_id);
+            device.event_types = node.event_types;
+            device.axes = node.axes;
+        }
+    }
+    devices.extend(kernel.into_iter().map(|node| InputDeviceInfo {
+        id: None,
+        kind: classify_kernel(&node).to_string(),
+        name: node.name,
+        path: Some(node.path),
+        classes: Vec::new(),
+        external: false,
+        enabled: None,
+        vendor_id: node.vendor_id,
+        product_id: node.product_id,
+        key_layout_file: None,
+        key_character_map_file: None,
+        event_types: node.event_types,
+        axes: node.axes,
+    }));
+    devices
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GETEVENT: &str = concat!(
+        "add device 1: /dev/input/event2\n",
+        "  bus:      0000\n",
+        "  vendor    0000\n",
+        "  product   0000\n",
+        "  version   0000\n",
+        "  name:     \"sec_touchscreen\"\n",
+        "  location: \"sec_touchscreen/input1\"\n",
+        "  id:       \"\"\n",
+        "  version:  1.0.1\n",
+        "  events:\n",
+        "    KEY (0001): BTN_TOOL_FINGER       BTN_TOUCH\n",
+        "    ABS (0003): ABS_MT_SLOT           : value 0, min 0, max 9, fuzz 0, flat 0, resolution 0\n",
+        "                ABS_MT_POSITION_X     : value 0, min 0, max 4095, fuzz 0, flat 0, resolution 0\n",
+        "                ABS_MT_POSITION_Y     : value 0, min 0, max 8191, fuzz 0, flat 0, resolution 0\n",
+        "  input props:\n",
+        "    INPUT_PROP_DIRECT\n",
+        "add device 2: /dev/input/event7\n",
+        "  bus:      0005\n",
+        "  vendor    045e\n",
+        "  product   0b13\n",
+        "  version   0509\n",
+        "  name:     \"Xbox Wireless Controller\"\n",
+        "  events:\n",
+        "    KEY (0001): KEY_BACK              BTN_A                 BTN_B\n",
+        "                BTN_X                 BTN_Y                 BTN_MODE\n",
+        "    ABS (0003): ABS_X                 : value 32768, min 0, max 65535, fuzz 255, flat 4095, resolution 0\n",
+        "                ABS_HAT0X             : value 0, min -1, max 1, fuzz 0, flat 0, resolution 0\n",
+        "    FF  (0015): FF_RUMBLE\n",
+        "add device 3: /dev/input/event9\n",
+        "  vendor    2dc8\n",
+        "  product   6101\n",
+        "  name:     \"8BitDo Pro 2\"\n",
+        "  events:\n",
+        "    KEY (0001): BTN_SOUTH             BTN_EAST\n",
+        "    ABS (0003): ABS_X                 : value 128, min 0, max 255, fuzz 0, flat 15, resolution 0\n",
+    );
+
+    const DUMPSYS: &str = concat!(
+        "INPUT MANAGER (dumpsys input)\n",
+        "\n",
+        "Event Hub State:\n",
+        "  BuiltInKeyboardId: -2\n",
+        "  Devices:\n",
+        "    -1: Virtual\n",
+        "      Classes: KEYBOARD | ALPHAKEY | VIRTUAL\n",
+        "      Path: <virtual>\n",
+        "      KeyLayoutFile: /system/usr/keylayout/Generic.kl\n",
+        "    2: sec_touchscreen\n",
+        "      Classes: TOUCH | TOUCH_MT\n",
+        "      Path: /dev/input/event2\n",
+        "      Enabled: true\n",
+        "      Identifier: bus=0x0000, vendor=0x0000, product=0x0000, version=0x0000\n",
+        "      KeyLayoutFile: \n",
+        "      KeyCharacterMapFile: \n",
+        "    6: Xbox Wireless Controller\n",
+        "      Classes: KEYBOARD | DPAD | GAMEPAD | JOYSTICK | VIBRATOR | EXTERNAL\n",
+        "      Path: /dev/input/event7\n",
+        "      Enabled: true\n",
+        "      Identifier: bus=0x0005, vendor=0x045e, product=0x0b13, version=0x0509\n",
+        "      KeyLayoutFile: /system/usr/keylayout/Vendor_045e_Product_0b13.kl\n",
+        "      KeyCharacterMapFile: /system/usr/keychars/Generic.kcm\n",
+        "  Unattached video devices:\n",
+        "    <none>\n",
+        "\n",
+        "Input Reader State (Nums of device: 2):\n",
+        "  Device 2: sec_touchscreen\n",
+        "    Path: /dev/input/event2\n",
+    );
+
+    #[test]
+    fn merges_event_hub_classes_with_kernel_capabilities() {
+        let output = format!("{GETEVENT}@@dumpsys\n{DUMPSYS}");
+        let devices = parse_input_devices(&output);
+        let summary: Vec<(&str, &str, Option<&str>)> = devices
+            .iter()
+            .map(|device| {
+                (
+                    device.name.as_str(),
+                    device.kind.as_str(),
+                    device.path.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("sec_touchscreen", "touchscreen", Some("/dev/input/event2")),
+                (
+                    "Xbox Wireless Controller",
+                    "gamepad",
+                    Some("/dev/input/event7")
+                ),
+                ("8BitDo Pro 2", "gamepad", Some("/dev/input/event9")),
+            ]
+        );
+
+        let touch = &devices[0];
+        assert_eq!(touch.id, Some(2));
+        assert_eq!(touch.enabled, Some(true));
+        assert!(!touch.external);
+        assert_eq!(
+            (touch.vendor_id.as_deref(), touch.key_layout_file.as_deref()),
+            (None, None)
+        );
+        assert_eq!(touch.event_types, ["KEY", "ABS"]);
+        assert_eq!(
+            touch.axes,
+            ["ABS_MT_SLOT", "ABS_MT_POSITION_X", "ABS_MT_POSITION_Y"]
+        );
+
+        let pad = &devices[1];
+        assert!(pad.external);
+        assert_eq!(pad.vendor_id.as_deref(), Some("045e"));
+        assert_eq!(pad.product_id.as_deref(), Some("0b13"));
+        assert_eq!(
+            pad.key_layout_file.as_deref(),
+            Some("/system/usr/keylayout/Vendor_045e_Product_0b13.kl")
+        );
+        assert_eq!(pad.event_types, ["KEY", "ABS", "FF"]);
+        assert_eq!(pad.axes, ["ABS_X", "ABS_HAT0X"]);
+
+        // Not reported by InputReader: classified from its kernel key codes alone.
+        let unknown = &devices[2];
+        assert_eq!(unknown.id, None);
+        assert!(unknown.classes.is_empty());
+        assert_eq!(unknown.vendor_id.as_deref(), Some("2dc8"));
+    }
+
+    #[test]
+    fn decodes_legacy_class_masks() {
+        let output = concat!(
+            "@@dumpsys\n",
+            "Event Hub State:\n",
+            "  BuiltInKeyboardId: 0\n",
+            "  Devices:\n",
+            "    0: Logitech USB Keyboard\n",
+            "      Classes: 0x80000003\n",
+            "      Path: /dev/input/event5\n",
+            "      Identifier: bus=0x0003, vendor=0x046d, product=0xc31c, version=0x0110\n",
+            "      KeyLayoutFile: /system/usr/keylayout/Generic.kl\n",
+            "    1: gpio-keys\n",
+            "      Classes: 0x00000001\n",
+            "      Path: /dev/input/event0\n",
+            "Input Reader State:\n",
+        );
+        let devices = parse_input_devices(output);
+        assert_eq!(devices.len(), 2);
+        assert_eq!(devices[0].classes, ["KEYBOARD", "ALPHAKEY", "EXTERNAL"]);
+        assert_eq!(devices[0].kind, "keyboard");
+        assert!(devices[0].external);
+        assert_eq!(devices[0].vendor_id.as_deref(), Some("046d"));
+        assert_eq!(devices[1].kind, "keys");
+        assert!(parse_input_devices("").is_empty());
+    }
+}
@@ -0,0 +1,239 @@
+use serde_json::{json, Value};
+
+use crate::app::config::{GithubIssueSettings, JiraIssueSettings};
+use crate::app::upload::{quote_config_value, required_env};
+
+pub const MAX_ISSUE_ATTACHMENTS: usize = 20;
+pub const MAX_ISSUE_TITLE_LEN: usize = 255;
+pub const DEFAULT_GITHUB_API_URL: &str = "https://api.github.com";
+pub const DEFAULT_GITHUB_TOKEN_ENV: &str = "GITHUB_TOKEN";
+pub const DEFAULT_JIRA_TOKEN_ENV: &str = "JIRA_API_TOKEN";
+pub const DEFAULT_JIRA_ISSUE_TYPE: &str = "Bug";
+
+/// An artifact as referenced from the issue body: its file name and, when it was uploaded
+/// somewhere reachable, the link.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IssueArtifactRef {
+    pub name: String,
+    pub url: Option<String>,
+}
+
+pub fn normalize_github_settings(settings: &mut GithubIssueSettings) {
+    settings.api_url = settings.api_url.trim().trim_end_matches('/').to_string();
+    if settings.api_url.is_empty() {
+        settings.api_url = DEFAULT_GITHUB_API_URL.to_string();
+    }
+    settings.repo = settings.repo.trim().trim_matches('/').to_string();
+    settings.token_env = settings.token_env.trim().to_string();
+    if settings.token_env.is_empty() {
+        settings.token_env = DEFAULT_GITHUB_TOKEN_ENV.to_string();
+    }
+    settings.attachment_upload_target = settings.attachment_upload_target.trim().to_string();
+}
+
+pub fn normalize_jira_settings(settings: &mut JiraIssueSettings) {
+    settings.base_url = settings.base_url.trim().trim_end_matches('/').to_string();
+    settings.project_key = settings.project_key.trim().to_uppercase();
+    settings.email = settings.email.trim().to_string();
+    settings.issue_type = settings.issue_type.trim().to_string();
+    if settings.issue_type.is_empty() {
+        settings.issue_type = DEFAULT_JIRA_ISSUE_TYPE.to_string();
+    }
+    settings.token_env = settings.token_env.trim().to_string();
+    if settings.token_env.is_empty() {
+        settings.token_env = DEFAULT_JIRA_TOKEN_ENV.to_string();
+    }
+}
+
+pub fn validate_github_repo(repo: &str) -> Result<(), String> {
+    let valid_part = |part: &str| {
+        !part.is_empty()
+            && part
+                .chars()
+                .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '-' | '_' | '.'))
+    };
+    match repo.split_once('/') {
+        Some((owner, name)) if valid_part(owner) && valid_part(name) => Ok(()),
+        _ => Err("GitHub repo must be configured as owner/name".to_string()),
+    }
+}
+
+/// Append an attachments section to the user's description.
+pub fn compose_issue_body(description: &str, artifacts: &[IssueArtifactRef]) -> String {
+    let mut body = description.trim().to_string();
+    if artifacts.is_empty() {
+        return body;
+    }
+    if !body.is_empty() {
+        body.push_str("\n\n");
+    }
+    body.push_str("Attachments:\n");
+    for artifact in artifacts {
+        match &artifact.url {
+            Some(url) => body.push_str(&format!("- [{}]({url})\n", artifact.name)),
+            None => body.push_str(&format!("- {} (not uploaded)\n", artifact.name)),
+        }
+    }
+    body
+}
+
+pub fn github_issue_payload(title: &str, body: &str) -> Value {
+    json!({ "title": title, "body": body })
+}
+
+pub fn jira_issue_payload(settings: &JiraIssueSettings, title: &str, description: &str) -> Value {
+    json!({
+        "fields": {
+            "project": { "key": settings.project_key },
+            "summary": title,
+            "description": description,
+            "issuetype": { "name": settings.issue_type },
+        }
+    })
+}
+
+pub fn github_auth_config(
+    settings: &GithubIssueSettings,
+    env: impl Fn(&str) -> Option<String>,
+) -> Result<Vec<String>, String> {
+    let token = required_env(&settings.token_env, &env)?;
+    Ok(vec![
+        format!(
+            "header = {}",
+            quote_config_value(&format!("Authorization: Bearer {token}"))?
+        ),
+        format!(
+            "header = {}",
+            quote_config_value("Accept: application/vnd.github+json")?
+        ),
+    ])
+}
+
+/// Jira Cloud uses basic auth with the account email and an API token.
+pub fn jira_auth_config(
+    settings: &JiraIssueSettings,
+    env: impl Fn(&str) -> Option<String>,
+) -> Result<Vec<String>, String> {
+    if settings.email.is_empty() {
+        return Err("Jira email is not configured".to_string());
+    }
+    let token = required_env(&settings.token_env, &env)?;
+    Ok(vec![format!(
+        "user = {}",
+        quote_config_value(&format!("{}:{token}", settings.email))?
+    )])
+}
+
+pub fn parse_github_issue_response(output: &str) -> Option<(String, String)> {
+    let value: Value = serde_json::from_str(output).ok()?;
+    let url = value.get("html_url")?.as_str()?.to_string();
+    let number = value.get("number")?.as_u64()?;
+    Some((url, format!("#{number}")))
+}
+
+pub fn parse_jira_issue_key(output: &str) -> Option<String> {
+    let value: Value = serde_json::from_str(output).ok()?;
+    value
+        .get("key")?
+        .as_str()
+        .map(|key| key.to_string())
+        .filter(|key| !key.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn env(name: &str) -> Option<String> {
+        (name == "GITHUB_TOKEN" || name == "JIRA_API_TOKEN").then(|| "tok".to_string())
+    }
+
+    #[test]
+    fn normalizes_settings_and_validates_repo() {
+        let mut github = GithubIssueSettings {
+            repo: " /acme/app/ ".to_string(),
+            ..GithubIssueSettings::default()
+        };
+        normalize_github_settings(&mut github);
+        assert_eq!(github.api_url, DEFAULT_GITHUB_API_URL);
+        assert_eq!(github.repo, "acme/app");
+        assert_eq!(github.token_env, "GITHUB_TOKEN");
+        assert!(validate_github_repo(&github.repo).is_ok());
+        assert!(validate_github_repo("acme").is_err());
+        assert!(validate_github_repo("acme/app/extra").is_err());
+
+        let mut jira = JiraIssueSettings {
+            base_url: "https://acme.atlassian.net/".to_string(),
+            project_key: " qa ".to_string(),
+            ..JiraIssueSettings::default()
+        };
+        normalize_jira_settings(&mut jira);
+        assert_eq!(jira.base_url, "https://acme.atlassian.net");
+        assert_eq!(jira.project_key, "QA");
+        assert_eq!(jira.issue_type, "Bug");
+    }
+
+    #[test]
+    fn composes_body_with_attachment_links() {
+        let body = compose_issue_body(
+            " Crash on login ",
+            &[
+                IssueArtifactRef {
+                    name: "bugreport.zip".to_string(),
+                    url: Some("https://bucket/bugreport.zip".to_string()),
+                },
+                IssueArtifactRef {
+                    name: "shot.png".to_string(),
+                    url: None,
+                },
+            ],
+        );
+        assert_eq!(
+            body,
+            "Crash on login\n\nAttachments:\n- [bugreport.zip](https://bucket/bugreport.zip)\n- shot.png (not uploaded)\n"
+        );
+        assert_eq!(compose_issue_body("x", &[]), "x");
+    }
+
+    #[test]
+    fn builds_auth_and_payloads() {
+        let github = GithubIssueSettings {
+            token_env: "GITHUB_TOKEN".to_string(),
+            ..GithubIssueSettings::default()
+        };
+        let lines = github_auth_config(&github, env).unwrap();
+        assert_eq!(lines[0], "header = \"Authorization: Bearer tok\"");
+
+        let mut jira = JiraIssueSettings::default();
+        normalize_jira_settings(&mut jira);
+        assert!(jira_auth_config(&jira, env).is_err());
+        jira.email = "qa@acme.test".to_string();
+        jira.project_key = "QA".to_string();
+        assert_eq!(
+            jira_auth_config(&jira, env).unwrap(),
+            ["user = \"qa@acme.test:tok\""]
+        );
+        let payload = jira_issue_payload(&jira, "Title", "Desc");
+        assert_eq!(payload["fields"]["project"]["key"], "QA");
+        assert_eq!(payload["fields"]["issuetype"]["name"], "Bug");
+        assert_eq!(github_issue_payload("T", "B")["title"], "T");
+    }
+
+    #[test]
+    fn parses_provider_responses() {
+        assert_eq!(
+            parse_github_issue_response(
+                r#"{"number": 42, "html_url": "https://github.com/acme/app/issues/42"}"#
+            ),
+            Some((
+                "https://github.com/acme/app/issues/42".to_string(),
+                "#42".to_string()
+            ))
+        );
+        assert_eq!(
+            parse_jira_issue_key(r#"{"id":"10001","key":"QA-7","self":"x"}"#),
+            Some("QA-7".to_string())
+        );
+        assert_eq!(parse_jira_issue_key("not json"), None);
+    }
+}
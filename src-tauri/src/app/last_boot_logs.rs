@@ -0,0 +1,297 @@
+use crate::app::adb::runner::CommandOutput;
+use crate::app::models::LastBootLogSection;
+
+/// Keep the end of each section: the lines right before a crash or reboot matter most.
+pub const MAX_SECTION_BYTES: usize = 2 * 1024 * 1024;
+pub const MAX_PANIC_LINES: usize = 50;
+
+pub const SOURCE_LOGCAT_LAST_BOOT: &str = "logcat_last_boot";
+pub const SOURCE_LAST_KMSG: &str = "last_kmsg";
+pub const SOURCE_CONSOLE_RAMOOPS: &str = "console_ramoops";
+pub const SOURCE_DROPBOX_LAST_KMSG: &str = "dropbox_last_kmsg";
+
+pub struct LastBootSource {
+    pub id: &'static str,
+    pub label: &'static str,
+    /// Shell commands tried in order; the first one that yields output wins.
+    pub candidates: &'static [&'static [&'static str]],
+    pub timeout_secs: u64,
+}
+
+pub const LAST_BOOT_SOURCES: [LastBootSource; 4] = [
+    LastBootSource {
+        id: SOURCE_LOGCAT_LAST_BOOT,
+        label: "Previous boot logcat (logcat -L)",
+        candidates: &[&["logcat", "-L", "-d"]],
+        timeout_secs: 30,
+    },
+    LastBootSource {
+        id: SOURCE_LAST_KMSG,
+        label: "Kernel log before reboot (/proc/last_kmsg)",
+        candidates: &[&["cat", "/proc/last_kmsg"]],
+        timeout_secs: 15,
+    },
+    LastBootSource {
+        id: SOURCE_CONSOLE_RAMOOPS,
+        label: "Kernel log before reboot (pstore console-ramoops)",
+        candidates: &[
+            &["cat", "/sys/fs/pstore/console-ramoops-0"],
+            &["cat", "/sys/fs/pstore/console-ramoops"],
+        ],
+        timeout_secs: 15,
+    },
+    LastBootSource {
+        id: SOURCE_DROPBOX_LAST_KMSG,
+        label: "Saved kernel panics (dumpsys dropbox SYSTEM_LAST_KMSG)",
+        candidates: &[&["dumpsys", "dropbox", "--print", "SYSTEM_LAST_KMSG"]],
+        timeout_secs: 30,
+    },
+];
+
+/// Kernel and framework lines that explain an unexpected reboot.
+const PANIC_MARKERS: [&str; 8] = [
+    "kernel panic",
+    "unable to handle kernel",
+    "internal error: oops",
+    "watchdog bark",
+    "watchdog bite",
+    "hard lockup",
+    "soft lockup",
+    "fatal exception",
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DropboxEntry {
+    pub timestamp: String,
+    pub tag: String,
+    pub body: String,
+}
+
+/// Why `output` holds no usable log, or `None` when it does.
+pub fn unavailable_reason(output: &CommandOutput) -> Option<String> {
+    let combined = format!("{}\n{}", output.stdout, output.stderr).to_lowercase();
+    if combined.contains("no such file") {
+        return Some("Not present on this device".to_string());
+    }
+    if combined.contains("permission denied") {
+        return Some("Permission denied (root is usually required)".to_string());
+    }
+    if output.exit_code != Some(0) {
+        let stderr = output.stderr.trim();
+        return Some(if stderr.is_empty() {
+            format!("Exited with code {:?}", output.exit_code)
+        } else {
+            stderr.to_string()
+        });
+    }
+    if output.stdout.trim().is_empty() {
+        return Some("No data".to_string());
+    }
+    None
+}
+
+/// The last `max_bytes` of `content`, cut at a line start when possible.
+pub fn tail_bytes(content: &str, max_bytes: usize) -> (String, bool) {
+    if content.len() <= max_bytes {
+        return (content.to_string(), false);
+    }
+    let mut start = content.len() - max_bytes;
+    while !content.is_char_boundary(start) {
+        start += 1;
+    }
+    let tail = &content[start..];
+    let tail = match tail.find('\n') {
+        Some(newline) if newline + 1 < tail.len() => &tail[newline + 1..],
+        _ => tail,
+    };
+    (tail.to_string(), true)
+}
+
+pub fn find_panic_lines(content: &str) -> Vec<String> {
+    content
+        .lines()
+        .filter(|line| {
+            let lower = line.to_lowercase();
+            PANIC_MARKERS.iter().any(|marker| lower.contains(marker))
+        })
+        .map(|line| line.trim().to_string())
+        .take(MAX_PANIC_LINES)
+        .collect()
+}
+
+/// Entries from `dumpsys dropbox --print`, oldest first. Each starts after a `====` rule with
+/// a `YYYY-MM-DD HH:MM:SS TAG (...)` header.
+pub fn parse_dropbox_entries(output: &str) -> Vec<DropboxEntry> {
+    let mut entries = Vec::new();
+    let mut current: Option<DropboxEntry> = None;
+    for line in output.lines() {
+        if line.starts_with("========") {
+            entries.extend(current.take());
+            continue;
+        }
+        if let Some(entry) = current.as_mut() {
+            entry.body.push_str(line);
+            entry.body.push('\n');
+            continue;
+        }
+        let mut parts = line.splitn(4, ' ');
+        let (Some(date), Some(time), Some(tag)) = (parts.next(), parts.next(), parts.next()) else {
+            continue;
+        };
+        let is_timestamp = date.len() == 10
+            && date.as_bytes()[4] == b'-'
+            && time.len() == 8
+            && time.as_bytes()[2] == b':';
+        if is_timestamp && !tag.is_empty() {
+            current = Some(DropboxEntry {
+                timestamp: format!("{date} {time}"),
+                tag: tag.to_string(),
+                body: String::new(),
+            });
+        }
+    }
+    entries.extend(current);
+    entries
+        .into_iter()
+        .filter(|entry| !entry.body.trim().is_empty())
+        .collect()
+}
+
+/// Turn the first usable candidate output into a section; `outputs` holds one result per
+/// candidate that was tried.
+pub fn build_section(
+    source: &LastBootSource,
+    outputs: Vec<Result<CommandOutput, String>>,
+) -> LastBootLogSection {
+    let mut section = LastBootLogSection {
+        source: source.id.to_string(),
+        label: source.label.to_string(),
+        available: false,
+        content: None,
+        truncated: false,
+        entry_time: None,
+        panic_lines: Vec::new(),
+        error: None,
+    };
+    let mut last_error = None;
+    for output in outputs {
+        let output = match output {
+            Ok(output) => output,
+            Err(err) => {
+                last_error = Some(err);
+                continue;
+            }
+        };
+        if let Some(reason) = unavailable_reason(&output) {
+            last_error = Some(reason);
+            continue;
+        }
+        let mut content = output.stdout;
+        if source.id == SOURCE_DROPBOX_LAST_KMSG {
+            match parse_dropbox_entries(&content).pop() {
+                Some(entry) => {
+                    section.entry_time = Some(entry.timestamp);
+                    content = entry.body;
+                }
+                None => {
+                    last_error = Some("No SYSTEM_LAST_KMSG entries".to_string());
+                    continue;
+                }
+            }
+        }
+        let (content, truncated) = tail_bytes(&content, MAX_SECTION_BYTES);
+        section.panic_lines = find_panic_lines(&content);
+        section.available = true;
+        section.truncated = truncated;
+        section.content = Some(content);
+        return section;
+    }
+    section.error = last_error;
+    section
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn output(stdout: &str, stderr: &str, exit_code: i32) -> CommandOutput {
+        CommandOutput {
+            stdout: stdout.to_string(),
+            stderr: stderr.to_string(),
+            exit_code: Some(exit_code),
+        }
+    }
+
+    #[test]
+    fn explains_missing_sources() {
+        assert_eq!(
+            unavailable_reason(&output(
+                "",
+                "cat: /proc/last_kmsg: No such file or directory\n",
+                1
+            ))
+            .as_deref(),
+            Some("Not present on this device")
+        );
+        assert!(unavailable_reason(&output(
+            "",
+            "cat: /sys/fs/pstore/console-ramoops-0: Permission denied\n",
+            1
+        ))
+        .unwrap()
+        .starts_with("Permission denied"));
+        assert_eq!(
+            unavailable_reason(&output("\n", "", 0)).as_deref(),
+            Some("No data")
+        );
+        assert_eq!(
+            unavailable_reason(&output("[    0.000] Booting\n", "", 0)),
+            None
+        );
+    }
+
+    #[test]
+    fn keeps_the_tail_on_line_boundaries() {
+        let (tail, truncated) = tail_bytes("first line\nsecond line\nthird\n", 15);
+        assert!(truncated);
+        assert_eq!(tail, "third\n");
+        assert_eq!(tail_bytes("short", 15), ("short".to_string(), false));
+    }
+
+    #[test]
+    fn parses_dropbox_and_falls_back_between_candidates() {
+        let dropbox = "Drop box contents: 2 entries\nMax entries: 1000\n\n========================================\n2024-04-30 22:10:01 SYSTEM_LAST_KMSG (text, 120 bytes)\n[  100.1] old boot\n\n========================================\n2024-05-01 09:15:42 SYSTEM_LAST_KMSG (text, 240 bytes)\n[ 5123.4] Kernel panic - not syncing: Watchdog bark! Now = 5123\n[ 5123.5] CPU: 3 PID: 0 Comm: swapper\n";
+        let entries = parse_dropbox_entries(dropbox);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[1].timestamp, "2024-05-01 09:15:42");
+        assert_eq!(entries[1].tag, "SYSTEM_LAST_KMSG");
+
+        let section = build_section(&LAST_BOOT_SOURCES[3], vec![Ok(output(dropbox, "", 0))]);
+        assert!(section.available);
+        assert_eq!(section.entry_time.as_deref(), Some("2024-05-01 09:15:42"));
+        assert_eq!(section.panic_lines.len(), 1);
+        assert!(!section.content.unwrap().contains("old boot"));
+
+        let ramoops = build_section(
+            &LAST_BOOT_SOURCES[2],
+            vec![
+                Ok(output(
+                    "",
+                    "cat: console-ramoops-0: No such file or directory",
+                    1,
+                )),
+                Ok(output(
+                    "[ 42.0] Unable to handle kernel NULL pointer\n",
+                    "",
+                    0,
+                )),
+            ],
+        );
+        assert!(ramoops.available && ramoops.error.is_none());
+        assert_eq!(ramoops.panic_lines.len(), 1);
+
+        let missing = build_section(&LAST_BOOT_SOURCES[1], vec![Err("timed out".to_string())]);
+        assert!(!missing.available);
+        assert_eq!(missing.error.as_deref(), Some("timed out"));
+    }
+}
@@ -0,0 +1,153 @@
+use std::collections::VecDeque;
+
+pub const DEFAULT_INTERVAL_SECS: u64 = 10;
+pub const DEFAULT_WINDOW_SAMPLES: usize = 30;
+pub const DEFAULT_SLOPE_KB_PER_MIN: f64 = 512.0;
+
+pub fn clamp_interval_secs(input: Option<u64>) -> u64 {
+    input.unwrap_or(DEFAULT_INTERVAL_SECS).clamp(2, 600)
+}
+
+pub fn clamp_window_samples(input: Option<usize>) -> usize {
+    input.unwrap_or(DEFAULT_WINDOW_SAMPLES).clamp(5, 360)
+}
+
+pub fn normalize_slope_kb_per_min(input: Option<f64>) -> f64 {
+    input
+        .filter(|value| value.is_finite() && *value > 0.0)
+        .unwrap_or(DEFAULT_SLOPE_KB_PER_MIN)
+}
+
+/// Read the total PSS (KB) from `dumpsys meminfo <package>`. Newer builds print
+/// `TOTAL PSS: N`, older ones a `TOTAL` row whose first column is PSS.
+pub fn parse_total_pss_kb(output: &str) -> Option<u64> {
+    let first_number = |value: &str| {
+        value
+            .split_whitespace()
+            .next()
+            .and_then(|token| token.parse::<u64>().ok())
+    };
+    output
+        .lines()
+        .find_map(|line| first_number(line.trim().strip_prefix("TOTAL PSS:")?))
+        .or_else(|| {
+            output
+                .lines()
+                .find_map(|line| first_number(line.trim().strip_prefix("TOTAL ")?))
+        })
+}
+
+/// Least-squares slope of `(seconds, kb)` samples, in KB per minute.
+pub fn pss_slope_kb_per_min(samples: &[(f64, u64)]) -> Option<f64> {
+    if samples.len() < 2 {
+        return None;
+    }
+    let n = samples.len() as f64;
+    let mean_x = samples.iter().map(|(x, _)| x).sum::<f64>() / n;
+    let mean_y = samples.iter().map(|(_, y)| *y as f64).sum::<f64>() / n;
+    let mut numerator = 0.0;
+    let mut denominator = 0.0;
+    for (x, y) in samples {
+        numerator += (x - mean_x) * (*y as f64 - mean_y);
+        denominator += (x - mean_x).powi(2);
+    }
+    if denominator == 0.0 {
+        return None;
+    }
+    Some(numerator / denominator * 60.0)
+}
+
+/// Sliding window of PSS samples that reports a leak once per excursion above the slope limit.
+pub struct LeakTrend {
+    samples: VecDeque<(f64, u64)>,
+    window: usize,
+    slope_limit: f64,
+    suspected: bool,
+}
+
+impl LeakTrend {
+    pub fn new(window: usize, slope_limit: f64) -> Self {
+        Self {
+            samples: VecDeque::with_capacity(window),
+            window,
+            slope_limit,
+            suspected: false,
+        }
+    }
+
+    /// Record a sample; returns the slope when the window first exceeds the limit.
+    pub fn push(&mut self, elapsed_secs: f64, pss_kb: u64) -> Option<f64> {
+        if self.samples.len() == self.window {
+            self.samples.pop_front();
+        }
+        self.samples.push_back((elapsed_secs, pss_kb));
+        if self.samples.len() < self.window {
+            return None;
+        }
+        let slope = pss_slope_kb_per_min(self.samples.make_contiguous())?;
+        if slope >= self.slope_limit {
+            if self.suspected {
+                return None;
+            }
+            self.suspected = true;
+            return Some(slope);
+        }
+        self.suspected = false;
+        None
+    }
+
+    pub fn window_secs(&self) -> f64 {
+        match (self.samples.front(), self.samples.back()) {
+            (Some(first), Some(last)) => last.0 - first.0,
+            _ => 0.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_total_pss_in_both_layouts() {
+        let modern = "** MEMINFO in pid 1234 [com.example] **\n App Summary\n        TOTAL PSS:   123456            TOTAL RSS:   234567       TOTAL SWAP PSS:       12\n";
+        assert_eq!(parse_total_pss_kb(modern), Some(123_456));
+        let legacy = "                   Pss  Private\n         TOTAL    98765    80000\n";
+        assert_eq!(parse_total_pss_kb(legacy), Some(98_765));
+        assert_eq!(
+            parse_total_pss_kb("No process found for: com.example"),
+            None
+        );
+    }
+
+    #[test]
+    fn computes_slope_per_minute() {
+        let samples = [(0.0, 1000), (30.0, 1500), (60.0, 2000)];
+        let slope = pss_slope_kb_per_min(&samples).expect("slope");
+        assert!((slope - 1000.0).abs() < 1e-6);
+        assert!(pss_slope_kb_per_min(&[(0.0, 1)]).is_none());
+    }
+
+    #[test]
+    fn leak_trend_reports_once_per_excursion() {
+        let mut trend = LeakTrend::new(5, 500.0);
+        let mut reports = Vec::new();
+        for step in 0..10u64 {
+            if let Some(slope) = trend.push(step as f64 * 10.0, 10_000 + step * 200) {
+                reports.push(slope);
+            }
+        }
+        assert_eq!(reports.len(), 1);
+        assert!((reports[0] - 1200.0).abs() < 1e-6);
+        assert!((trend.window_secs() - 40.0).abs() < 1e-6);
+
+        // Flat memory clears the latch so a later climb is reported again.
+        for step in 10..16u64 {
+            assert!(trend.push(step as f64 * 10.0, 11_800).is_none());
+        }
+        let reported = (16..22u64)
+            .filter_map(|step| trend.push(step as f64 * 10.0, 11_800 + (step - 15) * 300))
+            .count();
+        assert_eq!(reported, 1);
+    }
+}
@@ -0,0 +1,229 @@
+use chrono::{DateTime, SecondsFormat};
+use regex::Regex;
+
+use crate::app::models::ActivityLifecycleEvent;
+
+pub const LIFECYCLE_EVENT_NAME: &str = "activity-lifecycle";
+
+pub const LIFECYCLE_KIND_ACTIVITY: &str = "activity";
+pub const LIFECYCLE_KIND_FRAGMENT: &str = "fragment";
+
+/// AndroidX `FragmentManager` only logs state moves when its tag is loggable at DEBUG.
+pub const FRAGMENT_LOG_PROPERTY: &str = "log.tag.FragmentManager";
+const FRAGMENT_TAG: &str = "FragmentManager";
+
+/// Event-log tags written by the activity manager when it delivers lifecycle callbacks:
+/// `wm_*` since Android 9, `am_*` before.
+const ACTIVITY_TAGS: [(&str, &str); 13] = [
+    ("wm_on_create_called", "created"),
+    ("wm_on_start_called", "started"),
+    ("wm_on_restart_called", "restarted"),
+    ("wm_on_resume_called", "resumed"),
+    ("wm_on_top_resumed_gained_called", "top_resumed"),
+    ("wm_on_top_resumed_lost_called", "top_resumed_lost"),
+    ("wm_on_paused_called", "paused"),
+    ("wm_on_stop_called", "stopped"),
+    ("wm_on_destroy_called", "destroyed"),
+    ("am_on_resume_called", "resumed"),
+    ("am_on_paused_called", "paused"),
+    ("am_on_stop_called", "stopped"),
+    ("am_on_destroy_called", "destroyed"),
+];
+
+/// `logcat` reading only the lifecycle tags from now on; `-v epoch` keeps timestamps
+/// unambiguous across midnight and year boundaries.
+pub fn lifecycle_logcat_args(include_fragments: bool) -> Vec<String> {
+    let mut args: Vec<String> = ["logcat", "-v", "epoch", "-T", "1", "-b", "events"]
+        .into_iter()
+        .map(str::to_string)
+        .collect();
+    if include_fragments {
+        args.extend(["-b".to_string(), "main".to_string()]);
+        args.push(format!("{FRAGMENT_TAG}:D"));
+    }
+    args.extend(ACTIVITY_TAGS.iter().map(|(tag, _)| format!("{tag}:I")));
+    args.push("*:S".to_string());
+    args
+}
+
+pub struct LifecycleLineParser {
+    line: Regex,
+    fragment: Regex,
+}
+
+impl Default for LifecycleLineParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LifecycleLineParser {
+    pub fn new() -> Self {
+        Self {
+            line: Regex::new(r"^\s*(\d+\.\d+)\s+(\d+)\s+\d+\s+[VDIWEF]\s+(\S+)\s*:\s(.*)$")
+                .expect("lifecycle line regex should compile"),
+            fragment: Regex::new(r"^moveto (\w+): ([\w$.]+)\{")
+                .expect("fragment move regex should compile"),
+        }
+    }
+
+    /// One `logcat -v epoch` line, e.g.
+    /// `1700000000.123  4321  4321 I wm_on_resume_called: [93829380,com.example.MainActivity,RESUME_ACTIVITY]`
+    /// or, for fragments, `... D FragmentManager: moveto RESUMED: HomeFragment{c0ffee} (...)`.
+    /// The callbacks are logged from the app process with the activity class name only, so
+    /// `package` is usually left for the caller to resolve from `pid`.
+    pub fn parse(
+        &self,
+        serial: &str,
+        line: &str,
+        trace_id: &str,
+    ) -> Option<ActivityLifecycleEvent> {
+        let caps = self.line.captures(line)?;
+        let timestamp = epoch_timestamp(&caps[1])?;
+        let pid = caps[2].parse().ok();
+        let tag = &caps[3];
+        let message = caps[4].trim();
+
+        let (kind, state, package, component, reason) = if tag == FRAGMENT_TAG {
+            let moved = self.fragment.captures(message)?;
+            (
+                LIFECYCLE_KIND_FRAGMENT,
+                moved[1].to_ascii_lowercase(),
+                None,
+                moved[2].to_string(),
+                None,
+            )
+        } else {
+            let state = ACTIVITY_TAGS
+                .iter()
+                .find(|(name, _)| *name == tag)
+                .map(|(_, state)| state.to_string())?;
+            let fields: Vec<&str> = message
+                .trim_start_matches('[')
+                .trim_end_matches(']')
+                .split(',')
+                .map(str::trim)
+                .collect();
+            // `[token or user, class name, reason, ...]`.
+            let class = fields.get(1).filter(|class| !class.is_empty())?;
+            let reason = fields
+                .get(2)
+                .filter(|reason| !reason.is_empty())
+                .map(|reason| reason.to_string());
+            (
+                LIFECYCLE_KIND_ACTIVITY,
+                state,
+                None,
+                class.to_string(),
+                reason,
+            )
+        };
+
+        Some(ActivityLifecycleEvent {
+            serial: serial.to_string(),
+            timestamp,
+            pid,
+            kind: kind.to_string(),
+            state,
+            package,
+            component,
+            reason,
+            trace_id: trace_id.to_string(),
+        })
+    }
+}
+
+fn epoch_timestamp(value: &str) -> Option<String> {
+    let (secs, fraction) = value.split_once('.')?;
+    let millis: u32 = format!("{fraction:0<3}")[..3].parse().ok()?;
+    DateTime::from_timestamp(secs.parse().ok()?, millis * 1_000_000)
+        .map(|at| at.to_rfc3339_opts(SecondsFormat::Millis, true))
+}
+
+/// The package behind a process from its `/proc/<pid>/cmdline`: the first NUL-separated word
+/// without a `:remote`-style process suffix.
+pub fn package_from_cmdline(cmdline: &str) -> Option<String> {
+    let process = cmdline.split('\0').next()?.trim();
+    let package = process.split(':').next()?.trim();
+    (!package.is_empty() && !package.starts_with('/')).then(|| package.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_activity_lifecycle_tags() {
+        let parser = LifecycleLineParser::new();
+        let resumed = parser
+            .parse(
+                "R58M",
+                "1700000000.123  4321  4321 I wm_on_resume_called: [93829380,com.example.ui.MainActivity,RESUME_ACTIVITY]",
+                "trace-1",
+            )
+            .expect("resume event");
+        assert_eq!(resumed.timestamp, "2023-11-14T22:13:20.123Z");
+        assert_eq!(resumed.pid, Some(4321));
+        assert_eq!(resumed.kind, LIFECYCLE_KIND_ACTIVITY);
+        assert_eq!(resumed.state, "resumed");
+        assert_eq!(resumed.package, None);
+        assert_eq!(resumed.component, "com.example.ui.MainActivity");
+        assert_eq!(resumed.reason.as_deref(), Some("RESUME_ACTIVITY"));
+
+        // Android 8 and older put the user id first.
+        let legacy = parser
+            .parse(
+                "old",
+                "1500000000.5  900  900 I am_on_paused_called: [0,com.android.settings.Settings,handlePauseActivity]",
+                "trace-1",
+            )
+            .expect("legacy pause");
+        assert_eq!(legacy.state, "paused");
+        assert_eq!(legacy.timestamp, "2017-07-14T02:40:00.500Z");
+        assert_eq!(legacy.component, "com.android.settings.Settings");
+        assert_eq!(legacy.reason.as_deref(), Some("handlePauseActivity"));
+
+        assert!(parser
+            .parse("R58M", "--------- beginning of events", "trace-1")
+            .is_none());
+        assert!(parser
+            .parse(
+                "R58M",
+                "1700000000.123  1  1 I am_proc_start: [0,4321,10123,com.example,activity,{com.example/.Main}]",
+                "trace-1"
+            )
+            .is_none());
+    }
+
+    #[test]
+    fn parses_fragment_moves_and_builds_args() {
+        let parser = LifecycleLineParser::new();
+        let fragment = parser
+            .parse(
+                "R58M",
+                "1700000001.000  4321  4321 D FragmentManager: moveto RESUMED: HomeFragment{c0ffee} (8f1b id=0x7f0a0012 tag=home)",
+                "trace-1",
+            )
+            .expect("fragment event");
+        assert_eq!(fragment.kind, LIFECYCLE_KIND_FRAGMENT);
+        assert_eq!(fragment.state, "resumed");
+        assert_eq!(fragment.component, "HomeFragment");
+        assert_eq!(fragment.package, None);
+
+        let args = lifecycle_logcat_args(true);
+        assert_eq!(
+            &args[..7],
+            ["logcat", "-v", "epoch", "-T", "1", "-b", "events"]
+        );
+        assert!(args.contains(&"FragmentManager:D".to_string()));
+        assert_eq!(args.last().map(String::as_str), Some("*:S"));
+        assert!(!lifecycle_logcat_args(false).contains(&"main".to_string()));
+
+        assert_eq!(
+            package_from_cmdline("com.example:remote\0").as_deref(),
+            Some("com.example")
+        );
+        assert_eq!(package_from_cmdline("/system/bin/app_process64\0"), None);
+        assert_eq!(package_from_cmdline(""), None);
+    }
+}
@@ -0,0 +1,151 @@
+use std::collections::HashMap;
+
+use crate::app::models::DeviceLockState;
+use crate::app::readiness::parse_keyguard_locked;
+
+const KEYGUARD_MARKER: &str = "@@keyguard";
+
+/// One round trip: `key=value` lines for the properties and settings, then the window policy
+/// dump, which carries the keyguard delegate's `showing=` and `secure=` flags.
+pub const LOCK_STATE_SCRIPT: &str = concat!(
+    "echo \"crypto_state=$(getprop ro.crypto.state)\"; ",
+    "echo \"crypto_type=$(getprop ro.crypto.type)\"; ",
+    "user=$(cmd activity get-current-user 2>/dev/null); ",
+    "echo \"ce_available=$(getprop sys.user.${user:-0}.ce_available)\"; ",
+    "echo \"user_setup_complete=$(settings get secure user_setup_complete 2>/dev/null)\"; ",
+    "echo \"device_provisioned=$(settings get global device_provisioned 2>/dev/null)\"; ",
+    "echo @@keyguard; ",
+    "dumpsys window policy 2>/dev/null",
+);
+
+/// `key=true|false` as a whitespace-separated token, so `secure=` does not match `mSimSecure=`.
+fn token_flag(output: &str, key: &str) -> Option<bool> {
+    output
+        .split_whitespace()
+        .find_map(|token| match token.strip_prefix(key)? {
+            "true" => Some(true),
+            "false" => Some(false),
+            _ => None,
+        })
+}
+
+fn settings_flag(value: Option<&String>) -> Option<bool> {
+    match value.map(|value| value.trim())? {
+        "1" => Some(true),
+        "0" => Some(false),
+        _ => None,
+    }
+}
+
+fn non_empty(value: Option<&String>) -> Option<String> {
+    value
+        .map(|value| value.trim())
+        .filter(|value| !value.is_empty() && *value != "null")
+        .map(str::to_string)
+}
+
+pub fn parse_lock_state(serial: &str, output: &str) -> DeviceLockState {
+    let (values, keyguard) = output.split_once(KEYGUARD_MARKER).unwrap_or((output, ""));
+    let values: HashMap<String, String> = values
+        .lines()
+        .filter_map(|line| line.trim().split_once('='))
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect();
+
+    let locked = parse_keyguard_locked(keyguard).or_else(|| token_flag(keyguard, "showing="));
+    let encryption_type = non_empty(values.get("crypto_type"));
+    // Before the first unlock after boot, file-based encryption keeps credential storage
+    // sealed; `sys.user.N.ce_available` flips to true once it opens.
+    let user_unlocked = match values.get("ce_available").map(|value| value.trim()) {
+        Some("true") => Some(true),
+        Some("") | None if encryption_type.as_deref() == Some("file") => Some(false),
+        _ => None,
+    };
+    let setup_complete = settings_flag(values.get("user_setup_complete"));
+    let provisioned = settings_flag(values.get("device_provisioned"));
+    let in_setup_wizard = match (setup_complete, provisioned) {
+        (Some(false), _) | (_, Some(false)) => Some(true),
+        (None, None) => None,
+        _ => Some(false),
+    };
+
+    DeviceLockState {
+        serial: serial.to_string(),
+        locked,
+        secure: token_flag(keyguard, "secure="),
+        encryption_state: non_empty(values.get("crypto_state")),
+        file_based_encryption: encryption_type.as_deref().map(|kind| kind == "file"),
+        encryption_type,
+        user_unlocked,
+        in_setup_wizard,
+        ready: locked != Some(true)
+            && in_setup_wizard != Some(true)
+            && user_unlocked != Some(false),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_a_locked_secure_device_before_first_unlock() {
+        let output = concat!(
+            "crypto_state=encrypted\n",
+            "crypto_type=file\n",
+            "ce_available=\n",
+            "user_setup_complete=1\n",
+            "device_provisioned=1\n",
+            "@@keyguard\n",
+            "WINDOW MANAGER POLICY STATE (dumpsys window policy)\n",
+            "    KeyguardServiceDelegate\n",
+            "      showing=true\n",
+            "      secure=true\n",
+            "      KeyguardStateMonitor\n",
+            "        mIsShowing=true\n",
+            "        mSimSecure=false\n",
+        );
+        let state = parse_lock_state("R58M", output);
+        assert_eq!(state.locked, Some(true));
+        assert_eq!(state.secure, Some(true));
+        assert_eq!(state.encryption_state.as_deref(), Some("encrypted"));
+        assert_eq!(state.file_based_encryption, Some(true));
+        assert_eq!(state.user_unlocked, Some(false));
+        assert_eq!(state.in_setup_wizard, Some(false));
+        assert!(!state.ready);
+    }
+
+    #[test]
+    fn parses_unlocked_and_setup_wizard_devices() {
+        let unlocked = concat!(
+            "crypto_state=encrypted\n",
+            "crypto_type=file\n",
+            "ce_available=true\n",
+            "user_setup_complete=1\n",
+            "device_provisioned=1\n",
+            "@@keyguard\n",
+            "    mShowingLockscreen=false mShowingDream=false\n",
+            "      secure=false\n",
+        );
+        let state = parse_lock_state("emulator-5554", unlocked);
+        assert_eq!((state.locked, state.secure), (Some(false), Some(false)));
+        assert_eq!(state.user_unlocked, Some(true));
+        assert!(state.ready);
+
+        let wizard = concat!(
+            "crypto_state=unencrypted\n",
+            "crypto_type=\n",
+            "ce_available=\n",
+            "user_setup_complete=0\n",
+            "device_provisioned=null\n",
+            "@@keyguard\n",
+        );
+        let state = parse_lock_state("emulator-5554", wizard);
+        assert_eq!(state.locked, None);
+        assert_eq!(state.encryption_type, None);
+        assert_eq!(state.file_based_encryption, None);
+        assert_eq!(state.user_unlocked, None);
+        assert_eq!(state.in_setup_wizard, Some(true));
+        assert!(!state.ready);
+    }
+}
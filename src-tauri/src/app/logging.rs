@@ -1,18 +1,480 @@
-use tracing_subscriber::EnvFilter;
+use std::collections::{BTreeMap, VecDeque};
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
 
-pub fn init_logging() {
-    let env_filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+use tracing::warn;
+use tracing_subscriber::fmt::{self, MakeWriter};
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{reload, EnvFilter, Registry};
+
+use crate::app::config::{config_path, load_config, LoggingSettings};
+use crate::app::models::AppLogEntry;
+
+pub const APP_LOG_MEMORY_LIMIT: usize = 2000;
+const APP_LOG_DEFAULT_QUERY_LIMIT: usize = 200;
+pub const LOG_LEVELS: [&str; 6] = ["trace", "debug", "info", "warn", "error", "off"];
+const APP_CRATE: &str = "lazy_blacktea_rust_lib";
+
+pub fn app_log_path() -> PathBuf {
+    config_path().with_file_name(".lazy_blacktea_app.log")
+}
+
+/// Default directive plus per-module overrides, rendered into an `EnvFilter`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogLevels {
+    pub default: String,
+    pub modules: BTreeMap<String, String>,
+}
+
+impl LogLevels {
+    pub fn directives(&self) -> String {
+        std::iter::once(self.default.clone())
+            .chain(
+                self.modules
+                    .iter()
+                    .map(|(module, level)| format!("{module}={level}")),
+            )
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+}
+
+pub fn normalize_log_level(level: &str) -> Result<String, String> {
+    let level = level.trim().to_lowercase();
+    let level = if level == "warning" {
+        "warn".to_string()
+    } else {
+        level
+    };
+    if LOG_LEVELS.contains(&level.as_str()) {
+        Ok(level)
+    } else {
+        Err(format!(
+            "Invalid log level: {level} (expected one of {})",
+            LOG_LEVELS.join(", ")
+        ))
+    }
+}
 
-    if cfg!(debug_assertions) {
-        let _ = tracing_subscriber::fmt()
-            .with_env_filter(env_filter)
-            .with_target(false)
-            .try_init();
+/// Tracing target prefix for `module`. `app::adb::runner` is resolved inside this crate;
+/// anything else (`tauri`, a full `lazy_blacktea_rust_lib::...` path) is used as given.
+pub fn normalize_log_module(module: &str) -> Result<String, String> {
+    let module = module.trim();
+    let valid = !module.is_empty()
+        && module.split("::").all(|segment| {
+            !segment.is_empty()
+                && segment
+                    .chars()
+                    .all(|ch| ch.is_ascii_alphanumeric() || ch == '_' || ch == '-')
+        });
+    if !valid {
+        return Err(format!("Invalid log module: {module}"));
+    }
+    if module == "app" || module.starts_with("app::") {
+        Ok(format!("{APP_CRATE}::{module}"))
     } else {
-        let _ = tracing_subscriber::fmt()
-            .with_env_filter(env_filter)
-            .json()
-            .with_target(false)
-            .try_init();
+        Ok(module.to_string())
+    }
+}
+
+/// Size-capped log file that keeps `max_files` files in total: `path`, `path.1` (newest
+/// rotated) .. `path.{max_files - 1}`.
+pub struct RotatingLogFile {
+    path: PathBuf,
+    max_bytes: u64,
+    max_files: usize,
+    written: u64,
+    file: Option<File>,
+}
+
+impl RotatingLogFile {
+    pub fn new(path: PathBuf, max_bytes: u64, max_files: usize) -> Self {
+        let written = fs::metadata(&path).map(|meta| meta.len()).unwrap_or(0);
+        Self {
+            path,
+            max_bytes: max_bytes.max(1),
+            max_files: max_files.max(1),
+            written,
+            file: None,
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn rotated_path(&self, index: usize) -> PathBuf {
+        PathBuf::from(format!("{}.{index}", self.path.display()))
+    }
+
+    fn rotate(&mut self) -> io::Result<()> {
+        self.file = None;
+        let remove = |path: &Path| match fs::remove_file(path) {
+            Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err),
+            _ => Ok(()),
+        };
+        if self.max_files <= 1 {
+            remove(&self.path)?;
+        } else {
+            remove(&self.rotated_path(self.max_files - 1))?;
+            for index in (1..self.max_files - 1).rev() {
+                let from = self.rotated_path(index);
+                if from.exists() {
+                    fs::rename(from, self.rotated_path(index + 1))?;
+                }
+            }
+            fs::rename(&self.path, self.rotated_path(1))?;
+        }
+        self.written = 0;
+        Ok(())
+    }
+
+    pub fn write_line(&mut self, line: &[u8]) -> io::Result<()> {
+        if self.written > 0 && self.written + line.len() as u64 > self.max_bytes {
+            self.rotate()?;
+        }
+        if self.file.is_none() {
+            self.file = Some(
+                OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(&self.path)?,
+            );
+        }
+        if let Some(file) = self.file.as_mut() {
+            file.write_all(line)?;
+        }
+        self.written += line.len() as u64;
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+struct AppLogSink {
+    recent: VecDeque<String>,
+    file: Option<RotatingLogFile>,
+    file_error: Option<String>,
+}
+
+fn sink() -> &'static Mutex<AppLogSink> {
+    static SINK: OnceLock<Mutex<AppLogSink>> = OnceLock::new();
+    SINK.get_or_init(|| Mutex::new(AppLogSink::default()))
+}
+
+/// Runs inside the subscriber, so failures are recorded for `app_log_file_status` rather
+/// than logged (which would re-enter the sink).
+fn record_app_log_line(line: &[u8]) {
+    let Ok(mut sink) = sink().lock() else {
+        return;
+    };
+    let result = sink.file.as_mut().map(|file| file.write_line(line));
+    if let Some(Err(err)) = result {
+        sink.file_error = Some(err.to_string());
+    }
+    sink.recent
+        .push_back(String::from_utf8_lossy(line).trim_end().to_string());
+    while sink.recent.len() > APP_LOG_MEMORY_LIMIT {
+        sink.recent.pop_front();
+    }
+}
+
+/// Buffers one formatted event and hands it to the sink as a single line.
+struct SinkWriter {
+    buffer: Vec<u8>,
+}
+
+impl Write for SinkWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.buffer.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Drop for SinkWriter {
+    fn drop(&mut self) {
+        if !self.buffer.is_empty() {
+            record_app_log_line(&self.buffer);
+        }
+    }
+}
+
+struct SinkMakeWriter;
+
+impl<'a> MakeWriter<'a> for SinkMakeWriter {
+    type Writer = SinkWriter;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        SinkWriter { buffer: Vec::new() }
+    }
+}
+
+struct LogControl {
+    levels: Mutex<LogLevels>,
+    handle: reload::Handle<EnvFilter, Registry>,
+}
+
+static LOG_CONTROL: OnceLock<LogControl> = OnceLock::new();
+
+/// `RUST_LOG` wins at startup; otherwise the configured level is used. Either can be changed
+/// later with `set_log_level`.
+pub fn init_logging() {
+    let settings = load_config("logging-init").map(|config| config.logging);
+    let configured_level = settings
+        .as_ref()
+        .ok()
+        .and_then(|settings| normalize_log_level(&settings.log_level).ok())
+        .unwrap_or_else(|| "info".to_string());
+    let default = std::env::var("RUST_LOG")
+        .ok()
+        .filter(|value| EnvFilter::try_new(value).is_ok())
+        .unwrap_or(configured_level);
+    let levels = LogLevels {
+        default,
+        modules: BTreeMap::new(),
+    };
+    let filter = EnvFilter::try_new(levels.directives()).unwrap_or_else(|_| EnvFilter::new("info"));
+    let (filter, handle) = reload::Layer::new(filter);
+
+    let debug = cfg!(debug_assertions);
+    let initialized = tracing_subscriber::registry()
+        .with(filter)
+        .with(debug.then(|| fmt::layer().with_target(false)))
+        .with((!debug).then(|| fmt::layer().json().with_target(false)))
+        .with(
+            fmt::layer()
+                .json()
+                .with_ansi(false)
+                .with_writer(SinkMakeWriter),
+        )
+        .try_init()
+        .is_ok();
+    if initialized {
+        let _ = LOG_CONTROL.set(LogControl {
+            levels: Mutex::new(levels),
+            handle,
+        });
+    }
+
+    match settings {
+        Ok(settings) => configure_app_log_file(&settings),
+        Err(err) => {
+            warn!(error = %err.error, "failed to load logging settings, using defaults");
+            configure_app_log_file(&LoggingSettings::default());
+        }
+    }
+}
+
+pub fn configure_app_log_file(settings: &LoggingSettings) {
+    let file = settings.log_to_file.then(|| {
+        RotatingLogFile::new(
+            app_log_path(),
+            settings.log_file_size_mb.max(1) as u64 * 1024 * 1024,
+            settings.max_log_files.max(1) as usize,
+        )
+    });
+    match sink().lock() {
+        Ok(mut sink) => {
+            sink.file = file;
+            sink.file_error = None;
+        }
+        Err(_) => warn!("app log sink poisoned; log file settings not applied"),
+    }
+}
+
+/// Change the default level (`module` = `None`) or one module's level at runtime. `off` on a
+/// module silences it; setting a module back to the default level removes the override.
+pub fn set_log_level(module: Option<&str>, level: &str) -> Result<LogLevels, String> {
+    let level = normalize_log_level(level)?;
+    let control = LOG_CONTROL
+        .get()
+        .ok_or_else(|| "Logging is not initialized".to_string())?;
+    let mut levels = control
+        .levels
+        .lock()
+        .map_err(|_| "Log levels locked".to_string())?;
+    let mut next = levels.clone();
+    match module.map(str::trim).filter(|module| !module.is_empty()) {
+        Some(module) => {
+            let module = normalize_log_module(module)?;
+            if level == next.default {
+                next.modules.remove(&module);
+            } else {
+                next.modules.insert(module, level);
+            }
+        }
+        None => next.default = level,
+    }
+    let filter = EnvFilter::try_new(next.directives())
+        .map_err(|err| format!("Invalid log filter: {err}"))?;
+    control
+        .handle
+        .reload(filter)
+        .map_err(|err| format!("Failed to apply log level: {err}"))?;
+    *levels = next.clone();
+    Ok(next)
+}
+
+pub fn current_log_levels() -> Option<LogLevels> {
+    let control = LOG_CONTROL.get()?;
+    control.levels.lock().ok().map(|levels| levels.clone())
+}
+
+/// Path of the active log file and the last error writing it.
+pub fn app_log_file_status() -> (Option<PathBuf>, Option<String>) {
+    match sink().lock() {
+        Ok(sink) => (
+            sink.file.as_ref().map(|file| file.path().to_path_buf()),
+            sink.file_error.clone(),
+        ),
+        Err(_) => (None, Some("App log sink poisoned".to_string())),
+    }
+}
+
+fn level_rank(level: &str) -> usize {
+    let level = level.to_lowercase();
+    LOG_LEVELS
+        .iter()
+        .position(|candidate| *candidate == level)
+        .unwrap_or(0)
+}
+
+/// One JSON line from the sink. Lines that are not JSON are kept as the message.
+pub fn parse_app_log_line(line: &str) -> AppLogEntry {
+    let Ok(serde_json::Value::Object(mut value)) = serde_json::from_str(line) else {
+        return AppLogEntry {
+            timestamp: String::new(),
+            level: "INFO".to_string(),
+            target: String::new(),
+            message: line.to_string(),
+            fields: serde_json::Value::Null,
+        };
+    };
+    let mut text = |key: &str| {
+        value
+            .remove(key)
+            .and_then(|value| value.as_str().map(str::to_string))
+            .unwrap_or_default()
+    };
+    let timestamp = text("timestamp");
+    let level = text("level");
+    let target = text("target");
+    let mut fields = value
+        .remove("fields")
+        .unwrap_or(serde_json::Value::Object(Default::default()));
+    let message = fields
+        .as_object_mut()
+        .and_then(|fields| fields.remove("message"))
+        .and_then(|message| message.as_str().map(str::to_string))
+        .unwrap_or_default();
+    AppLogEntry {
+        timestamp,
+        level,
+        target,
+        message,
+        fields,
+    }
+}
+
+/// Newest `limit` entries at or above `min_level`, oldest first.
+pub fn filter_app_logs<'a>(
+    lines: impl DoubleEndedIterator<Item = &'a String>,
+    limit: Option<usize>,
+    min_level: Option<&str>,
+) -> Vec<AppLogEntry> {
+    let limit = limit
+        .unwrap_or(APP_LOG_DEFAULT_QUERY_LIMIT)
+        .clamp(1, APP_LOG_MEMORY_LIMIT);
+    let min_rank = min_level.map(level_rank).unwrap_or(0);
+    let mut entries: Vec<AppLogEntry> = lines
+        .rev()
+        .map(|line| parse_app_log_line(line))
+        .filter(|entry| level_rank(&entry.level) >= min_rank)
+        .take(limit)
+        .collect();
+    entries.reverse();
+    entries
+}
+
+pub fn recent_app_logs(limit: Option<usize>, min_level: Option<&str>) -> Vec<AppLogEntry> {
+    match sink().lock() {
+        Ok(sink) => filter_app_logs(sink.recent.iter(), limit, min_level),
+        Err(_) => {
+            warn!("app log sink poisoned; returning no entries");
+            Vec::new()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes_levels_and_modules() {
+        assert_eq!(normalize_log_level(" WARNING ").unwrap(), "warn");
+        assert_eq!(normalize_log_level("Debug").unwrap(), "debug");
+        assert!(normalize_log_level("verbose").is_err());
+        assert_eq!(
+            normalize_log_module("app::adb::runner").unwrap(),
+            "lazy_blacktea_rust_lib::app::adb::runner"
+        );
+        assert_eq!(normalize_log_module("tauri").unwrap(), "tauri");
+        assert!(normalize_log_module("app::").is_err());
+        assert!(normalize_log_module("app=debug").is_err());
+
+        let levels = LogLevels {
+            default: "info".to_string(),
+            modules: BTreeMap::from([("tauri".to_string(), "warn".to_string())]),
+        };
+        assert_eq!(levels.directives(), "info,tauri=warn");
+        assert!(EnvFilter::try_new(levels.directives()).is_ok());
+    }
+
+    #[test]
+    fn parses_and_filters_sink_lines() {
+        let lines = [
+            r#"{"timestamp":"2024-05-01T10:00:00Z","level":"DEBUG","fields":{"message":"probe"},"target":"a"}"#.to_string(),
+            r#"{"timestamp":"2024-05-01T10:00:01Z","level":"WARN","fields":{"message":"slow","trace_id":"t1"},"target":"lazy_blacktea_rust_lib::app::adb"}"#.to_string(),
+            "not json".to_string(),
+        ];
+        let entry = parse_app_log_line(&lines[1]);
+        assert_eq!(entry.level, "WARN");
+        assert_eq!(entry.message, "slow");
+        assert_eq!(entry.fields["trace_id"], "t1");
+        assert_eq!(parse_app_log_line("not json").message, "not json");
+
+        let warnings = filter_app_logs(lines.iter(), None, Some("warn"));
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].message, "slow");
+        let newest = filter_app_logs(lines.iter(), Some(2), None);
+        assert_eq!(newest[0].message, "slow");
+        assert_eq!(newest[1].message, "not json");
+    }
+
+    #[test]
+    fn rotates_by_size_and_keeps_max_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("app.log");
+        let mut file = RotatingLogFile::new(path.clone(), 10, 3);
+        for line in ["first\n", "second\n", "third\n", "fourth\n"] {
+            file.write_line(line.as_bytes()).unwrap();
+        }
+        assert_eq!(fs::read_to_string(&path).unwrap(), "fourth\n");
+        assert_eq!(
+            fs::read_to_string(dir.path().join("app.log.1")).unwrap(),
+            "third\n"
+        );
+        assert_eq!(
+            fs::read_to_string(dir.path().join("app.log.2")).unwrap(),
+            "second\n"
+        );
+        assert!(!dir.path().join("app.log.3").exists());
     }
 }
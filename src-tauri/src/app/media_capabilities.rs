@@ -0,0 +1,260 @@
+use regex::Regex;
+
+use crate::app::models::{CameraInfo, MediaCodecInfo};
+
+/// `HAL_PIXEL_FORMAT_BLOB`: the JPEG output sizes of a camera.
+const FORMAT_JPEG: u32 = 33;
+
+const STREAM_CONFIGS_TAG: &str = "android.scaler.availableStreamConfigurations (";
+const HARDWARE_LEVEL_TAG: &str = "android.info.supportedHardwareLevel (";
+const LENS_FACING_TAG: &str = "android.lens.facing (";
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum PendingTag {
+    None,
+    StreamConfigs,
+    HardwareLevel,
+    LensFacing,
+}
+
+/// Camera ids from the static-information headers of `dumpsys media.camera`, e.g.
+/// `== Camera HAL device device@3.5/internal/0 (v3.5) static information: ==` or
+/// `== Camera device 1 static information: ==` on older releases.
+fn camera_header_regex() -> Regex {
+    Regex::new(r"^== Camera (?:HAL device \S*/(\S+)|device (\S+)) .*static information")
+        .expect("camera header regex should compile")
+}
+
+fn finish_camera(camera: &mut CameraInfo, sizes: &mut Vec<(u32, u32)>) {
+    sizes.sort_by(|a, b| (b.0 * b.1).cmp(&(a.0 * a.1)).then(b.0.cmp(&a.0)));
+    sizes.dedup();
+    camera.jpeg_resolutions = sizes
+        .iter()
+        .map(|(width, height)| format!("{width}x{height}"))
+        .collect();
+    camera.max_jpeg_resolution = camera.jpeg_resolutions.first().cloned();
+    sizes.clear();
+}
+
+/// `[33 4032 3024 OUTPUT ]` rows: (format, width, height, direction) quadruples.
+fn collect_jpeg_sizes(tokens: &[String], sizes: &mut Vec<(u32, u32)>) {
+    for entry in tokens.chunks_exact(4) {
+        let parsed = (
+            entry[0].parse::<u32>(),
+            entry[1].parse::<u32>(),
+            entry[2].parse::<u32>(),
+        );
+        if let (Ok(FORMAT_JPEG), Ok(width), Ok(height)) = parsed {
+            if entry[3] == "OUTPUT" {
+                sizes.push((width, height));
+            }
+        }
+    }
+}
+
+pub fn parse_camera_dump(output: &str) -> Vec<CameraInfo> {
+    let header = camera_header_regex();
+    let mut cameras: Vec<CameraInfo> = Vec::new();
+    let mut sizes: Vec<(u32, u32)> = Vec::new();
+    let mut tokens: Vec<String> = Vec::new();
+    let mut pending = PendingTag::None;
+
+    for line in output.lines() {
+        let trimmed = line.trim();
+        if let Some(caps) = header.captures(trimmed) {
+            if let Some(camera) = cameras.last_mut() {
+                collect_jpeg_sizes(&tokens, &mut sizes);
+                finish_camera(camera, &mut sizes);
+            }
+            tokens.clear();
+            pending = PendingTag::None;
+            let id = caps.get(1).or_else(|| caps.get(2)).map(|id| id.as_str());
+            cameras.push(CameraInfo {
+                id: id.unwrap_or_default().to_string(),
+                facing: None,
+                orientation: None,
+                hardware_level: None,
+                max_jpeg_resolution: None,
+                jpeg_resolutions: Vec::new(),
+            });
+            continue;
+        }
+        let Some(camera) = cameras.last_mut() else {
+            continue;
+        };
+        if let Some(values) = trimmed.strip_prefix('[') {
+            let values = values.trim_end_matches(']');
+            match pending {
+                PendingTag::StreamConfigs => {
+                    tokens.extend(values.split_whitespace().map(str::to_string))
+                }
+                PendingTag::HardwareLevel if camera.hardware_level.is_none() => {
+                    camera.hardware_level = Some(values.trim().to_string())
+                }
+                PendingTag::LensFacing if camera.facing.is_none() => {
+                    camera.facing = Some(values.trim().to_ascii_lowercase())
+                }
+                _ => {}
+            }
+            continue;
+        }
+        if pending == PendingTag::StreamConfigs {
+            collect_jpeg_sizes(&tokens, &mut sizes);
+            tokens.clear();
+        }
+        pending = if trimmed.starts_with(STREAM_CONFIGS_TAG) {
+            PendingTag::StreamConfigs
+        } else if trimmed.starts_with(HARDWARE_LEVEL_TAG) {
+            PendingTag::HardwareLevel
+        } else if trimmed.starts_with(LENS_FACING_TAG) {
+            PendingTag::LensFacing
+        } else {
+            PendingTag::None
+        };
+        if let Some(facing) = trimmed.strip_prefix("Facing:") {
+            camera.facing = Some(facing.trim().to_ascii_lowercase());
+        } else if let Some(orientation) = trimmed.strip_prefix("Orientation:") {
+            camera.orientation = orientation.trim().parse().ok();
+        }
+    }
+    if let Some(camera) = cameras.last_mut() {
+        collect_jpeg_sizes(&tokens, &mut sizes);
+        finish_camera(camera, &mut sizes);
+    }
+    cameras
+}
+
+/// Software codecs ship under these prefixes on every device.
+fn is_software_codec_name(name: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    name.starts_with("omx.google.") || name.starts_with("c2.android.")
+}
+
+/// The MediaCodecList section of `dumpsys media.player`:
+/// `Media type 'video/avc':` followed by `Encoder "c2.qti.avc.encoder" supports` entries with
+/// `software-only` / `hw-accelerated` attributes (Android 10+). Older releases lack the
+/// attributes, so the codec name decides.
+pub fn parse_codec_dump(output: &str) -> Vec<MediaCodecInfo> {
+    let entry = Regex::new(r#"^(Encoder|Decoder) "([^"]+)" supports"#)
+        .expect("codec entry regex should compile");
+    let mut codecs: Vec<MediaCodecInfo> = Vec::new();
+    let mut media_type: Option<String> = None;
+    for line in output.lines().map(str::trim) {
+        if let Some(rest) = line.strip_prefix("Media type '") {
+            media_type = rest.split('\'').next().map(str::to_string);
+        } else if let Some(caps) = entry.captures(line) {
+            let Some(media_type) = media_type.clone() else {
+                continue;
+            };
+            let name = caps[2].to_string();
+            if codecs
+                .iter()
+                .any(|codec| codec.name == name && codec.media_type == media_type)
+            {
+                continue;
+            }
+            codecs.push(MediaCodecInfo {
+                hardware: !is_software_codec_name(&name),
+                name,
+                media_type,
+                encoder: &caps[1] == "Encoder",
+            });
+        } else if let Some(codec) = codecs.last_mut() {
+            match line.trim_end_matches(',').trim_end_matches(" ]") {
+                "hw-accelerated: 1" => codec.hardware = true,
+                "software-only: 1" => codec.hardware = false,
+                _ => {}
+            }
+        }
+    }
+    codecs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_camera_static_information() {
+        let output = concat!(
+            "== Service global info: ==\n",
+            "Number of camera devices: 2\n",
+            "== Camera HAL device device@3.5/internal/0 (v3.5) static information: ==\n",
+            "  Resource cost: 100\n",
+            "  Facing: Back\n",
+            "  Orientation: 90\n",
+            "  Camera characteristics:\n",
+            "      android.info.supportedHardwareLevel (150000): byte[1]\n",
+            "        [FULL ]\n",
+            "      android.scaler.availableStreamConfigurations (d000a): int32[16]\n",
+            "        [34 4032 3024 OUTPUT ]\n",
+            "        [33 1920 1080 OUTPUT ]\n",
+            "        [33 4032 3024 OUTPUT ]\n",
+            "        [33 640 480 INPUT ]\n",
+            "      android.scaler.availableStreamConfigurationsMaximumResolution (d0012): int32[4]\n",
+            "        [33 8064 6048 OUTPUT ]\n",
+            "== Camera device 1 static information: ==\n",
+            "      android.lens.facing (80005): byte[1]\n",
+            "        [FRONT ]\n",
+            "      android.scaler.availableStreamConfigurations (d000a): int32[8]\n",
+            "        [33 2592 1944 OUTPUT 35 2592 1944 OUTPUT ]\n",
+            "== Dynamic device info ==\n",
+        );
+        let cameras = parse_camera_dump(output);
+        assert_eq!(cameras.len(), 2);
+        assert_eq!(cameras[0].id, "0");
+        assert_eq!(cameras[0].facing.as_deref(), Some("back"));
+        assert_eq!(cameras[0].orientation, Some(90));
+        assert_eq!(cameras[0].hardware_level.as_deref(), Some("FULL"));
+        assert_eq!(cameras[0].jpeg_resolutions, ["4032x3024", "1920x1080"]);
+        assert_eq!(cameras[0].max_jpeg_resolution.as_deref(), Some("4032x3024"));
+        assert_eq!(cameras[1].id, "1");
+        assert_eq!(cameras[1].facing.as_deref(), Some("front"));
+        assert_eq!(cameras[1].jpeg_resolutions, ["2592x1944"]);
+        assert!(parse_camera_dump("Camera module API version: 2.5\n").is_empty());
+    }
+
+    #[test]
+    fn parses_codec_list_with_and_without_attributes() {
+        let output = concat!(
+            "Media Codec List:\n",
+            " Decoders:\n",
+            "  Media type 'video/avc':\n",
+            "    Decoder \"c2.qti.avc.decoder\" supports\n",
+            "      attributes: 0x6: [\n",
+            "        encoder: 0,\n",
+            "        vendor: 1,\n",
+            "        software-only: 0,\n",
+            "        hw-accelerated: 1 ]\n",
+            "    Decoder \"c2.android.avc.decoder\" supports\n",
+            "      attributes: 0x4: [\n",
+            "        software-only: 1,\n",
+            "        hw-accelerated: 0 ]\n",
+            " Encoders:\n",
+            "  Media type 'video/hevc':\n",
+            "    Encoder \"OMX.qcom.video.encoder.hevc\" supports\n",
+            "    Encoder \"OMX.google.hevc.encoder\" supports\n",
+        );
+        let codecs = parse_codec_dump(output);
+        let summary: Vec<(&str, &str, bool, bool)> = codecs
+            .iter()
+            .map(|codec| {
+                (
+                    codec.name.as_str(),
+                    codec.media_type.as_str(),
+                    codec.encoder,
+                    codec.hardware,
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("c2.qti.avc.decoder", "video/avc", false, true),
+                ("c2.android.avc.decoder", "video/avc", false, false),
+                ("OMX.qcom.video.encoder.hevc", "video/hevc", true, true),
+                ("OMX.google.hevc.encoder", "video/hevc", true, false),
+            ]
+        );
+    }
+}
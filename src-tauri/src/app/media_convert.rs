@@ -0,0 +1,147 @@
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+pub const SUPPORTED_CONVERT_FORMATS: [&str; 2] = ["gif", "webp"];
+
+pub fn normalize_convert_format(value: &str) -> Result<String, String> {
+    let format = value.trim().to_lowercase();
+    if SUPPORTED_CONVERT_FORMATS.contains(&format.as_str()) {
+        Ok(format)
+    } else {
+        Err(format!("Unsupported conversion format: {}", value.trim()))
+    }
+}
+
+pub fn converted_output_path(input: &Path, format: &str) -> PathBuf {
+    input.with_extension(format)
+}
+
+/// Build ffmpeg arguments that convert an MP4 recording into an animated GIF or WebP.
+///
+/// Output is bounded by `max_width` (never upscaled) and `fps`. GIFs use a generated
+/// palette to keep file size and banding down.
+pub fn build_ffmpeg_convert_args(
+    input: &str,
+    output: &str,
+    format: &str,
+    max_width: i32,
+    fps: i32,
+) -> Result<Vec<String>, String> {
+    let format = normalize_convert_format(format)?;
+    let max_width = max_width.clamp(64, 1920);
+    let fps = fps.clamp(1, 30);
+    let scale = format!("fps={fps},scale='min({max_width},iw)':-2:flags=lanczos");
+
+    let mut args = vec![
+        "-hide_banner".to_string(),
+        "-loglevel".to_string(),
+        "error".to_string(),
+        "-y".to_string(),
+        // `key=value` progress blocks on stdout; see `parse_ffmpeg_progress`.
+        "-progress".to_string(),
+        "pipe:1".to_string(),
+        "-nostats".to_string(),
+        "-i".to_string(),
+        input.to_string(),
+        "-an".to_string(),
+    ];
+    if format == "gif" {
+        args.push("-filter_complex".to_string());
+        args.push(format!(
+            "{scale},split[a][b];[a]palettegen=stats_mode=diff[p];[b][p]paletteuse=dither=bayer"
+        ));
+    } else {
+        args.extend([
+            "-vf".to_string(),
+            scale,
+            "-c:v".to_string(),
+            "libwebp".to_string(),
+            "-lossless".to_string(),
+            "0".to_string(),
+            "-q:v".to_string(),
+            "60".to_string(),
+        ]);
+    }
+    args.extend(["-loop".to_string(), "0".to_string(), output.to_string()]);
+    Ok(args)
+}
+
+/// Media time converted so far, from an ffmpeg `-progress` line such as `out_time_us=1500000`.
+/// `out_time_ms` is also microseconds despite its name.
+pub fn parse_ffmpeg_progress(line: &str) -> Option<Duration> {
+    let (key, value) = line.trim().split_once('=')?;
+    match key {
+        "out_time_us" | "out_time_ms" => value.parse::<u64>().ok().map(Duration::from_micros),
+        _ => None,
+    }
+}
+
+/// Share of `total` converted, capped at 100; `None` without a usable total.
+pub fn conversion_percent(done: Duration, total: Duration) -> Option<f32> {
+    if total.is_zero() {
+        return None;
+    }
+    Some((done.as_secs_f32() / total.as_secs_f32() * 100.0).min(100.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_convert_format_accepts_known_formats() {
+        assert_eq!(normalize_convert_format(" GIF ").unwrap(), "gif");
+        assert_eq!(normalize_convert_format("webp").unwrap(), "webp");
+        assert!(normalize_convert_format("avi").is_err());
+        assert!(normalize_convert_format("").is_err());
+    }
+
+    #[test]
+    fn parses_ffmpeg_progress_lines() {
+        assert_eq!(
+            parse_ffmpeg_progress("out_time_us=1500000"),
+            Some(Duration::from_millis(1500))
+        );
+        assert_eq!(parse_ffmpeg_progress("out_time=00:00:01.500000"), None);
+        assert_eq!(parse_ffmpeg_progress("out_time_us=N/A"), None);
+        assert_eq!(
+            conversion_percent(Duration::from_secs(5), Duration::from_secs(10)),
+            Some(50.0)
+        );
+        assert_eq!(
+            conversion_percent(Duration::from_secs(12), Duration::from_secs(10)),
+            Some(100.0)
+        );
+        assert_eq!(
+            conversion_percent(Duration::from_secs(1), Duration::ZERO),
+            None
+        );
+    }
+
+    #[test]
+    fn converted_output_path_swaps_extension() {
+        let path = converted_output_path(Path::new("/tmp/out/record.mp4"), "gif");
+        assert_eq!(path, PathBuf::from("/tmp/out/record.gif"));
+    }
+
+    #[test]
+    fn build_ffmpeg_convert_args_gif_uses_palette() {
+        let args = build_ffmpeg_convert_args("in.mp4", "out.gif", "gif", 480, 10).unwrap();
+        let filter_index = args
+            .iter()
+            .position(|item| item == "-filter_complex")
+            .expect("filter");
+        assert!(args[filter_index + 1].starts_with("fps=10,scale='min(480,iw)'"));
+        assert!(args[filter_index + 1].contains("palettegen"));
+        assert_eq!(args.last().map(String::as_str), Some("out.gif"));
+    }
+
+    #[test]
+    fn build_ffmpeg_convert_args_webp_clamps_bounds() {
+        let args = build_ffmpeg_convert_args("in.mp4", "out.webp", "webp", 99_999, 0).unwrap();
+        assert!(args.iter().any(|item| item == "libwebp"));
+        assert!(args
+            .iter()
+            .any(|item| item.starts_with("fps=1,scale='min(1920,iw)'")));
+    }
+}
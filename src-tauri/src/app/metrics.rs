@@ -0,0 +1,444 @@
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+use tracing::warn;
+
+use crate::app::emit_limiter::{emit_policy, payload_serial, EmitDecision, EmitLimiter};
+use crate::app::event_subscriptions::{event_gated, event_subscribed};
+use crate::app::models::{
+    CommandMetric, EventMetric, InternalMetrics, SchedulerMetric, TimingSummary,
+};
+
+#[derive(Debug, Clone, Default)]
+struct Timing {
+    count: u64,
+    total_ms: u64,
+    max_ms: u64,
+}
+
+impl Timing {
+    fn observe(&mut self, elapsed: Duration) {
+        let ms = elapsed.as_millis() as u64;
+        self.count += 1;
+        self.total_ms = self.total_ms.saturating_add(ms);
+        self.max_ms = self.max_ms.max(ms);
+    }
+
+    fn summary(&self) -> TimingSummary {
+        TimingSummary {
+            count: self.count,
+            total_ms: self.total_ms,
+            max_ms: self.max_ms,
+            avg_ms: if self.count == 0 {
+                0.0
+            } else {
+                self.total_ms as f64 / self.count as f64
+            },
+        }
+    }
+}
+
+struct MetricsStore {
+    started: Instant,
+    commands: HashMap<String, (Timing, u64)>,
+    scheduler_wait: Timing,
+    events: HashMap<String, EventCounts>,
+    limiter: EmitLimiter,
+}
+
+#[derive(Debug, Clone, Default)]
+struct EventCounts {
+    emitted: u64,
+    rate_limited: u64,
+    coalesced: u64,
+    unsubscribed: u64,
+}
+
+fn store() -> &'static Mutex<MetricsStore> {
+    static STORE: OnceLock<Mutex<MetricsStore>> = OnceLock::new();
+    STORE.get_or_init(|| {
+        Mutex::new(MetricsStore {
+            started: Instant::now(),
+            commands: HashMap::new(),
+            scheduler_wait: Timing::default(),
+            events: HashMap::new(),
+            limiter: EmitLimiter::default(),
+        })
+    })
+}
+
+fn with_store(update: impl FnOnce(&mut MetricsStore)) {
+    match store().lock() {
+        Ok(mut guard) => update(&mut guard),
+        Err(_) => warn!("metrics store poisoned"),
+    }
+}
+
+/// Group commands by program and adb subcommand (`adb shell`, `adb pull`, ...), skipping
+/// `-s <serial>` so per-device invocations share one bucket.
+pub fn command_metric_key(program: &str, args: &[String]) -> String {
+    let mut iter = args.iter();
+    let mut subcommand = None;
+    while let Some(arg) = iter.next() {
+        if arg == "-s" || arg == "-H" || arg == "-P" {
+            iter.next();
+            continue;
+        }
+        if arg.starts_with('-') {
+            continue;
+        }
+        subcommand = Some(arg.as_str());
+        break;
+    }
+    match subcommand {
+        Some(subcommand) => format!("{program} {subcommand}"),
+        None => program.to_string(),
+    }
+}
+
+pub fn record_command_metric(key: &str, elapsed: Duration, success: bool) {
+    with_store(|store| {
+        let (timing, failures) = store.commands.entry(key.to_string()).or_default();
+        timing.observe(elapsed);
+        if !success {
+            *failures += 1;
+        }
+    });
+}
+
+pub fn record_scheduler_wait(elapsed: Duration) {
+    with_store(|store| store.scheduler_wait.observe(elapsed));
+}
+
+pub fn record_event_emit(event: &str) {
+    with_store(|store| store.events.entry(event.to_string()).or_default().emitted += 1);
+}
+
+fn decide_emit(event: &str, payload: &serde_json::Value) -> EmitDecision {
+    let Some(policy) = emit_policy(event) else {
+        return EmitDecision::Emit;
+    };
+    let mut guard = match store().lock() {
+        Ok(guard) => guard,
+        Err(_) => {
+            warn!("metrics store poisoned");
+            return EmitDecision::Emit;
+        }
+    };
+    let decision = guard.limiter.decide(event, payload, policy, Instant::now());
+    let counts = guard.events.entry(event.to_string()).or_default();
+    match decision {
+        EmitDecision::Emit => counts.emitted += 1,
+        EmitDecision::RateLimited => counts.rate_limited += 1,
+        EmitDecision::Coalesced => counts.coalesced += 1,
+    }
+    decision
+}
+
+/// Emit a frontend event and count it for the event rate metrics. Channels with an emit
+/// policy are rate-limited per serial and skip identical consecutive payloads; subscribable
+/// channels skip devices the UI has not subscribed to.
+pub fn emit_tracked<S: Serialize + Clone>(
+    app: &AppHandle,
+    event: &str,
+    payload: S,
+) -> tauri::Result<()> {
+    let gated = event_gated(event);
+    if emit_policy(event).is_none() && !gated {
+        record_event_emit(event);
+        return app.emit(event, payload);
+    }
+    let value = serde_json::to_value(&payload)?;
+    if gated && !event_subscribed(event, &payload_serial(&value)) {
+        with_store(|store| {
+            store
+                .events
+                .entry(event.to_string())
+                .or_default()
+                .unsubscribed += 1
+        });
+        return Ok(());
+    }
+    if emit_policy(event).is_none() {
+        record_event_emit(event);
+        return app.emit(event, value);
+    }
+    match decide_emit(event, &value) {
+        EmitDecision::Emit => app.emit(event, value),
+        EmitDecision::RateLimited | EmitDecision::Coalesced => Ok(()),
+    }
+}
+
+pub fn snapshot_metrics(global_limit: usize, global_in_use: usize) -> InternalMetrics {
+    let guard = match store().lock() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    let uptime = guard.started.elapsed();
+    let uptime_minutes = (uptime.as_secs_f64() / 60.0).max(1.0 / 60.0);
+
+    let mut commands: Vec<CommandMetric> = guard
+        .commands
+        .iter()
+        .map(|(key, (timing, failures))| CommandMetric {
+            command: key.clone(),
+            failures: *failures,
+            timing: timing.summary(),
+        })
+        .collect();
+    commands.sort_by(|a, b| a.command.cmp(&b.command));
+
+    let mut events: Vec<EventMetric> = guard
+        .events
+        .iter()
+        .map(|(name, counts)| EventMetric {
+            event: name.clone(),
+            count: counts.emitted,
+            per_minute: counts.emitted as f64 / uptime_minutes,
+            rate_limited: counts.rate_limited,
+            coalesced: counts.coalesced,
+            unsubscribed: counts.unsubscribed,
+        })
+        .collect();
+    events.sort_by(|a, b| a.event.cmp(&b.event));
+
+    InternalMetrics {
+        uptime_ms: uptime.as_millis() as u64,
+        commands,
+        scheduler: SchedulerMetric {
+            global_limit,
+            global_in_use,
+            wait: guard.scheduler_wait.summary(),
+        },
+        events,
+    }
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+pub fn render_prometheus(metrics: &InternalMetrics) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "# TYPE lazy_blacktea_uptime_seconds gauge");
+    let _ = writeln!(
+        out,
+        "lazy_blacktea_uptime_seconds {:.3}",
+        metrics.uptime_ms as f64 / 1000.0
+    );
+
+    let _ = writeln!(out, "# TYPE lazy_blacktea_command_total counter");
+    let _ = writeln!(out, "# TYPE lazy_blacktea_command_failures_total counter");
+    let _ = writeln!(
+        out,
+        "# TYPE lazy_blacktea_command_duration_seconds_sum counter"
+    );
+    let _ = writeln!(
+        out,
+        "# TYPE lazy_blacktea_command_duration_seconds_max gauge"
+    );
+    for command in &metrics.commands {
+        let label = escape_label(&command.command);
+        let timing = &command.timing;
+        let _ = writeln!(
+            out,
+            "lazy_blacktea_command_total{{command=\"{label}\"}} {}",
+            timing.count
+        );
+        let _ = writeln!(
+            out,
+            "lazy_blacktea_command_failures_total{{command=\"{label}\"}} {}",
+            command.failures
+        );
+        let _ = writeln!(
+            out,
+            "lazy_blacktea_command_duration_seconds_sum{{command=\"{label}\"}} {:.3}",
+            timing.total_ms as f64 / 1000.0
+        );
+        let _ = writeln!(
+            out,
+            "lazy_blacktea_command_duration_seconds_max{{command=\"{label}\"}} {:.3}",
+            timing.max_ms as f64 / 1000.0
+        );
+    }
+
+    let scheduler = &metrics.scheduler;
+    let _ = writeln!(out, "# TYPE lazy_blacktea_scheduler_global_limit gauge");
+    let _ = writeln!(
+        out,
+        "lazy_blacktea_scheduler_global_limit {}",
+        scheduler.global_limit
+    );
+    let _ = writeln!(out, "# TYPE lazy_blacktea_scheduler_global_in_use gauge");
+    let _ = writeln!(
+        out,
+        "lazy_blacktea_scheduler_global_in_use {}",
+        scheduler.global_in_use
+    );
+    let _ = writeln!(out, "# TYPE lazy_blacktea_scheduler_wait_total counter");
+    let _ = writeln!(
+        out,
+        "lazy_blacktea_scheduler_wait_total {}",
+        scheduler.wait.count
+    );
+    let _ = writeln!(
+        out,
+        "# TYPE lazy_blacktea_scheduler_wait_seconds_sum counter"
+    );
+    let _ = writeln!(
+        out,
+        "lazy_blacktea_scheduler_wait_seconds_sum {:.3}",
+        scheduler.wait.total_ms as f64 / 1000.0
+    );
+    let _ = writeln!(out, "# TYPE lazy_blacktea_scheduler_wait_seconds_max gauge");
+    let _ = writeln!(
+        out,
+        "lazy_blacktea_scheduler_wait_seconds_max {:.3}",
+        scheduler.wait.max_ms as f64 / 1000.0
+    );
+
+    let _ = writeln!(out, "# TYPE lazy_blacktea_events_emitted_total counter");
+    for event in &metrics.events {
+        let _ = writeln!(
+            out,
+            "lazy_blacktea_events_emitted_total{{event=\"{}\"}} {}",
+            escape_label(&event.event),
+            event.count
+        );
+    }
+    let _ = writeln!(out, "# TYPE lazy_blacktea_events_dropped_total counter");
+    for event in &metrics.events {
+        let label = escape_label(&event.event);
+        for (reason, count) in [
+            ("rate_limited", event.rate_limited),
+            ("coalesced", event.coalesced),
+            ("unsubscribed", event.unsubscribed),
+        ] {
+            if count > 0 {
+                let _ = writeln!(
+                    out,
+                    "lazy_blacktea_events_dropped_total{{event=\"{label}\",reason=\"{reason}\"}} {count}"
+                );
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn command_metric_key_skips_serial_and_flags() {
+        assert_eq!(
+            command_metric_key("adb", &args(&["-s", "serial", "shell", "getprop"])),
+            "adb shell"
+        );
+        assert_eq!(
+            command_metric_key("adb", &args(&["devices", "-l"])),
+            "adb devices"
+        );
+        assert_eq!(command_metric_key("ffmpeg", &args(&["-y"])), "ffmpeg");
+    }
+
+    #[test]
+    fn snapshot_metrics_aggregates_commands_and_events() {
+        record_command_metric("test-metrics pull", Duration::from_millis(40), true);
+        record_command_metric("test-metrics pull", Duration::from_millis(20), false);
+        record_event_emit("test-metrics-event");
+
+        let metrics = snapshot_metrics(8, 3);
+        let command = metrics
+            .commands
+            .iter()
+            .find(|command| command.command == "test-metrics pull")
+            .expect("command metric");
+        assert_eq!(command.timing.count, 2);
+        assert_eq!(command.failures, 1);
+        assert_eq!(command.timing.max_ms, 40);
+        assert!((command.timing.avg_ms - 30.0).abs() < f64::EPSILON);
+        assert_eq!(metrics.scheduler.global_limit, 8);
+        assert!(metrics
+            .events
+            .iter()
+            .any(|event| event.event == "test-metrics-event" && event.count >= 1));
+    }
+
+    #[test]
+    fn decide_emit_counts_drops() {
+        let payload = serde_json::json!({"serial": "test-metrics-drop", "progress": 5});
+        assert_eq!(
+            decide_emit("file-transfer-progress", &payload),
+            EmitDecision::Emit
+        );
+        assert_eq!(
+            decide_emit("file-transfer-progress", &payload),
+            EmitDecision::Coalesced
+        );
+        let metrics = snapshot_metrics(1, 0);
+        let event = metrics
+            .events
+            .iter()
+            .find(|event| event.event == "file-transfer-progress")
+            .expect("event metric");
+        assert!(event.count >= 1);
+        assert!(event.coalesced >= 1);
+    }
+
+    #[test]
+    fn render_prometheus_escapes_labels() {
+        let metrics = InternalMetrics {
+            uptime_ms: 1500,
+            commands: vec![CommandMetric {
+                command: "adb \"shell\"".to_string(),
+                failures: 1,
+                timing: TimingSummary {
+                    count: 2,
+                    total_ms: 250,
+                    max_ms: 200,
+                    avg_ms: 125.0,
+                },
+            }],
+            scheduler: SchedulerMetric {
+                global_limit: 8,
+                global_in_use: 0,
+                wait: TimingSummary::default(),
+            },
+            events: vec![EventMetric {
+                event: "logcat-line".to_string(),
+                count: 7,
+                per_minute: 7.0,
+                rate_limited: 3,
+                coalesced: 0,
+                unsubscribed: 2,
+            }],
+        };
+        let text = render_prometheus(&metrics);
+        assert!(text.contains("lazy_blacktea_uptime_seconds 1.500"));
+        assert!(text.contains("lazy_blacktea_command_total{command=\"adb \\\"shell\\\"\"} 2"));
+        assert!(text.contains(
+            "lazy_blacktea_command_duration_seconds_sum{command=\"adb \\\"shell\\\"\"} 0.250"
+        ));
+        assert!(text.contains("lazy_blacktea_events_emitted_total{event=\"logcat-line\"} 7"));
+        assert!(text.contains(
+            "lazy_blacktea_events_dropped_total{event=\"logcat-line\",reason=\"rate_limited\"} 3"
+        ));
+        assert!(!text.contains("reason=\"coalesced\""));
+        assert!(text.contains(
+            "lazy_blacktea_events_dropped_total{event=\"logcat-line\",reason=\"unsubscribed\"} 2"
+        ));
+        assert!(text.contains("lazy_blacktea_scheduler_global_limit 8"));
+    }
+}
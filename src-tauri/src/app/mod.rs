@@ -1,16 +1,68 @@
+pub mod accessibility_audit;
 pub mod adb;
+pub mod apk_watch;
+pub mod app_cache;
 pub mod bluetooth;
 pub mod bugreport_logcat;
+pub mod build_outputs;
+pub mod capture_context;
+pub mod command_log;
 pub mod commands;
 pub mod config;
+pub mod custom_tools;
+pub mod device_farm;
+pub mod device_history;
+pub mod device_notes;
+pub mod device_profiles;
+pub mod device_reservations;
 pub mod diagnostics;
+pub mod dmesg;
+pub mod dumpsys;
+pub mod emit_limiter;
+pub mod environment;
 pub mod error;
+pub mod event_subscriptions;
+pub mod factory_reset;
+pub mod golden;
+pub mod group_actions;
+pub mod hooks;
+pub mod i18n;
+pub mod input_devices;
+pub mod issues;
+pub mod last_boot_logs;
+pub mod leak_watch;
+pub mod lifecycle;
+pub mod lock_state;
 pub mod logging;
+pub mod media_capabilities;
+pub mod media_convert;
+pub mod metrics;
 pub mod models;
 pub mod net_profiler;
+pub mod notifications;
+pub mod on_connect;
+pub mod output;
 pub mod perf;
+pub mod read_cache;
+pub mod readiness;
+pub mod restricted;
 pub mod scheduler;
+pub mod screenshot_diff;
+pub mod session_report;
+pub mod setup_wizard;
+pub mod shell_pipeline;
+pub mod shell_stream;
+pub mod soak_test;
 pub mod state;
+pub mod storage;
+pub mod telephony;
 pub mod terminal;
+pub mod thermal;
+pub mod timeline;
+pub mod timeouts;
+pub mod triage;
 pub mod ui_capture;
+pub mod ui_diff;
+pub mod ui_view_hierarchy;
 pub mod ui_xml;
+pub mod upload;
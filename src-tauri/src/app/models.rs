@@ -1,5 +1,5 @@
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct DeviceSummary {
@@ -9,6 +9,12 @@ pub struct DeviceSummary {
     pub product: Option<String>,
     pub device: Option<String>,
     pub transport_id: Option<String>,
+    /// `usb`, `tcp` or `emulator`.
+    #[serde(default)]
+    pub connection_type: String,
+    /// Host USB port path from `adb devices -l`, e.g. `1-4.2`.
+    #[serde(default)]
+    pub usb_port: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -32,12 +38,372 @@ pub struct DeviceDetail {
     pub memory_total_bytes: Option<u64>,
     pub audio_state: Option<String>,
     pub bluetooth_manager_state: Option<String>,
+    /// Negotiated USB speed as reported by the device; `None` over TCP or when unknown.
+    pub usb_speed_mbps: Option<u32>,
+    pub usb_speed: Option<String>,
+    #[serde(default)]
+    pub adb_features: Vec<String>,
+    #[serde(default)]
+    pub lock_state: Option<DeviceLockState>,
+    #[serde(default)]
+    pub telephony: Option<TelephonyInfo>,
+}
+
+/// One SIM slot; `None` fields were not reported by the device.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TelephonySlot {
+    pub slot: usize,
+    /// `gsm.sim.state`, e.g. `LOADED`, `ABSENT`, `PIN_REQUIRED`.
+    pub sim_state: Option<String>,
+    /// Carrier that issued the SIM.
+    pub sim_operator: Option<String>,
+    /// Network currently registered on; differs from `sim_operator` when roaming.
+    pub network_operator: Option<String>,
+    pub network_type: Option<String>,
+    pub roaming: Option<bool>,
+    /// Voice registration, e.g. `IN_SERVICE`, `OUT_OF_SERVICE`, `EMERGENCY_ONLY`.
+    pub service_state: Option<String>,
+    pub data_state: Option<String>,
+    /// 0 (none) to 4 (great), as shown in the status bar.
+    pub signal_level: Option<u8>,
+    pub signal_dbm: Option<i32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TelephonyInfo {
+    pub serial: String,
+    pub slots: Vec<TelephonySlot>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BootInfo {
+    pub serial: String,
+    /// `a` or `b`; `None` on devices without A/B partitions.
+    pub current_slot: Option<String>,
+    pub ab_update: bool,
+    pub virtual_ab: bool,
+    /// OTAs install in the background to the inactive slot.
+    pub seamless_updates: bool,
+    /// `green`, `yellow`, `orange` or `red`.
+    pub verified_boot_state: Option<String>,
+    pub verity_mode: Option<String>,
+    pub bootloader_locked: Option<bool>,
+    pub bootloader_version: Option<String>,
+    pub boot_reason: Option<String>,
+    pub build_fingerprint: Option<String>,
+    pub security_patch: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CameraInfo {
+    pub id: String,
+    /// `back`, `front` or `external`.
+    pub facing: Option<String>,
+    pub orientation: Option<u32>,
+    /// `LEGACY`, `LIMITED`, `FULL`, `LEVEL_3` or `EXTERNAL`.
+    pub hardware_level: Option<String>,
+    pub max_jpeg_resolution: Option<String>,
+    /// JPEG output sizes as `WxH`, largest first.
+    pub jpeg_resolutions: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct MediaCodecInfo {
+    pub name: String,
+    pub media_type: String,
+    pub encoder: bool,
+    pub hardware: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct MediaCapabilities {
+    pub serial: String,
+    pub cameras: Vec<CameraInfo>,
+    pub codecs: Vec<MediaCodecInfo>,
+}
+
+/// One `/dev/input` device. `classes` are InputReader's `InputDeviceClass` names; `kind`
+/// summarizes them as `touchscreen`, `touchpad`, `gamepad`, `keyboard`, `keys`, `mouse`,
+/// `stylus`, `rotary`, `sensor`, `switch` or `other`. A gamepad without a key layout file
+/// falls back to `Generic.kl` mappings.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct InputDeviceInfo {
+    pub id: Option<i32>,
+    pub name: String,
+    pub path: Option<String>,
+    pub kind: String,
+    pub classes: Vec<String>,
+    pub external: bool,
+    pub enabled: Option<bool>,
+    /// USB/Bluetooth ids as four lowercase hex digits.
+    pub vendor_id: Option<String>,
+    pub product_id: Option<String>,
+    pub key_layout_file: Option<String>,
+    pub key_character_map_file: Option<String>,
+    /// Kernel event types (`KEY`, `ABS`, `REL`, `FF`, ...) and the absolute/relative axes.
+    pub event_types: Vec<String>,
+    pub axes: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct InputDeviceInventory {
+    pub serial: String,
+    pub devices: Vec<InputDeviceInfo>,
+}
+
+/// Why a device may silently reject automation: keyguard, sealed credential storage or an
+/// unfinished setup wizard. `None` fields could not be read.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DeviceLockState {
+    pub serial: String,
+    pub locked: Option<bool>,
+    /// A PIN, pattern or password protects the keyguard.
+    pub secure: Option<bool>,
+    /// `ro.crypto.state`: `encrypted`, `unencrypted` or `unsupported`.
+    pub encryption_state: Option<String>,
+    /// `ro.crypto.type`: `file` or `block`.
+    pub encryption_type: Option<String>,
+    pub file_based_encryption: Option<bool>,
+    /// Credential-encrypted storage has been unlocked since boot.
+    pub user_unlocked: Option<bool>,
+    pub in_setup_wizard: Option<bool>,
+    /// Nothing above is known to block UI automation.
+    pub ready: bool,
+}
+
+/// A classified change in a device's adb state, emitted by the device tracker.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DeviceTransition {
+    pub serial: String,
+    pub kind: String,
+    pub previous_state: Option<String>,
+    pub state: Option<String>,
+    pub model: Option<String>,
+}
+
+/// One continuous stretch in the `device` state.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DeviceSession {
+    pub connected_at_ms: i64,
+    pub disconnected_at_ms: Option<i64>,
+    pub duration_ms: u64,
+    pub connection_type: String,
+    /// Ended because tracking stopped rather than an observed disconnect.
+    #[serde(default)]
+    pub interrupted: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DeviceHistory {
+    pub serial: String,
+    pub model: Option<String>,
+    pub connection_type: String,
+    pub first_seen_ms: i64,
+    pub last_seen_ms: i64,
+    pub total_connected_ms: u64,
+    pub session_count: u64,
+    pub sessions: Vec<DeviceSession>,
+}
+
+/// One on-connect action applied to a device, kept in the on-connect audit log.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct OnConnectAuditEntry {
+    pub serial: String,
+    pub action: String,
+    pub value: String,
+    pub success: bool,
+    pub error: Option<String>,
+    pub applied_at: String,
+    pub trace_id: String,
+}
+
+/// Progress of an automatic `adb connect` retry for a wireless device that dropped off.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DeviceReconnectStatus {
+    pub serial: String,
+    pub status: String,
+    pub attempt: u32,
+    pub next_retry_ms: Option<u64>,
+    pub error: Option<String>,
+    pub trace_id: String,
+}
+
+/// Progress of waiting for a rebooted device to report `sys.boot_completed`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RebootProgress {
+    pub serial: String,
+    pub stage: String,
+    pub elapsed_ms: u64,
+    pub timeout_ms: u64,
+    pub error: Option<String>,
+    pub trace_id: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct DeviceInfo {
     pub summary: DeviceSummary,
     pub detail: Option<DeviceDetail>,
+    #[serde(default)]
+    pub notes: Option<DeviceNotes>,
+    #[serde(default)]
+    pub reservation: Option<DeviceReservation>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ChecklistItem {
+    pub id: String,
+    pub text: String,
+    pub done: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ChecklistItemInput {
+    pub id: Option<String>,
+    pub text: String,
+    #[serde(default)]
+    pub done: bool,
+}
+
+/// Free-form markdown and a checklist kept per serial, e.g. known hardware quirks.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DeviceNotes {
+    pub serial: String,
+    pub markdown: String,
+    pub checklist: Vec<ChecklistItem>,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DeviceProfileApp {
+    pub package_name: String,
+    pub version_name: Option<String>,
+    pub version_code: Option<String>,
+    /// Base and split APK file names under `apps/<package>/` in the profile directory.
+    pub apk_files: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DeviceProfileSettingKey {
+    pub namespace: String,
+    pub key: String,
+}
+
+/// `value` is `None` when the key was unset on the source device; applying deletes it.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DeviceProfileSetting {
+    pub namespace: String,
+    pub key: String,
+    pub value: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DeviceProfileFile {
+    pub device_path: String,
+    /// File name under `files/` in the profile directory.
+    pub stored_file: String,
+    pub size_bytes: u64,
+    pub sha256: String,
+}
+
+/// Named snapshot of app versions, settings and asset files taken from one device, to be
+/// re-applied onto others.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DeviceProfile {
+    pub name: String,
+    pub source_serial: String,
+    pub created_at: String,
+    pub apps: Vec<DeviceProfileApp>,
+    pub settings: Vec<DeviceProfileSetting>,
+    pub files: Vec<DeviceProfileFile>,
+}
+
+/// `kind` is `app`, `setting` or `file`; `status` is `applied`, `skipped` or `failed`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DeviceProfileStep {
+    pub kind: String,
+    pub target: String,
+    pub status: String,
+    pub detail: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DeviceProfileApplyReport {
+    pub profile_name: String,
+    pub serial: String,
+    pub steps: Vec<DeviceProfileStep>,
+    pub applied: usize,
+    pub skipped: usize,
+    pub failed: usize,
+}
+
+/// A prepared factory reset: `token` must be passed back to `factory_reset_device` before it
+/// expires.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct FactoryResetChallenge {
+    pub serial: String,
+    pub model: Option<String>,
+    pub token: String,
+    pub expires_in_ms: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct FactoryResetReport {
+    pub serial: String,
+    pub wipe_started: bool,
+    /// Name of the `WIPE_METHODS` entry that started the wipe.
+    pub wipe_method: String,
+    /// Only set when a provisioning profile made the reset wait for the device to come back.
+    pub boot_completed: Option<bool>,
+    pub provisioning: Option<DeviceProfileApplyReport>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct FactoryResetAuditEntry {
+    pub serial: String,
+    pub stage: String,
+    /// Wipe method tried, on `wipe` entries.
+    #[serde(default)]
+    pub method: Option<String>,
+    pub success: bool,
+    pub detail: Option<String>,
+    pub at: String,
+    pub trace_id: String,
+}
+
+/// One run of a custom tool; `serial` is `None` for host tools run without a device.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CustomToolRunResult {
+    pub serial: Option<String>,
+    pub success: bool,
+    pub exit_code: Option<i32>,
+    pub stdout: String,
+    pub stderr: String,
+    pub error: Option<String>,
+    pub duration_ms: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CustomToolResult {
+    pub tool: String,
+    pub results: Vec<CustomToolRunResult>,
+}
+
+/// A lightweight booking so people sharing a workstation know who is using a device.
+/// `until` and `reserved_at` are RFC 3339.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DeviceReservation {
+    pub serial: String,
+    pub user: String,
+    pub until: String,
+    pub reserved_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReservationConflictEvent {
+    pub action: String,
+    pub user: String,
+    pub conflicts: Vec<DeviceReservation>,
+    pub trace_id: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -49,6 +415,41 @@ pub struct DeviceFileEntry {
     pub modified_at: Option<String>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct GroupActionDeviceResult {
+    pub serial: String,
+    pub success: bool,
+    /// Screenshot path or command stdout, when there is any.
+    pub output: Option<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct GroupActionResult {
+    pub group: String,
+    pub action: String,
+    pub serials: Vec<String>,
+    pub results: Vec<GroupActionDeviceResult>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct WirelessFlowInfo {
+    pub serial: String,
+    pub api_level: Option<u32>,
+    /// `pairing`, `tcpip`, `already_wireless` or `unsupported`.
+    pub flow: String,
+    pub wlan_ip: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TcpipConnectResult {
+    pub serial: String,
+    /// `ip:port` to use as the wireless serial.
+    pub address: String,
+    pub attempts: u32,
+    pub stdout: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct CommandResult {
     pub serial: String,
@@ -57,6 +458,17 @@ pub struct CommandResult {
     pub exit_code: Option<i32>,
 }
 
+/// A reboot's command result plus, when the caller waited for it, how the boot went.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RebootResult {
+    #[serde(flatten)]
+    pub result: CommandResult,
+    #[serde(default)]
+    pub boot_completed: Option<bool>,
+    #[serde(default)]
+    pub boot_elapsed_ms: Option<u64>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct HostCommandResult {
     pub stdout: String,
@@ -72,12 +484,190 @@ pub struct AdbInfo {
     pub error: Option<String>,
 }
 
+/// Crash context collected into `path` the moment stress tooling saw a crash marker.
+/// `errors` lists the pieces that could not be captured.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TriageBundle {
+    pub serial: String,
+    /// `crash`, `anr` or `native_crash`.
+    pub reason: String,
+    pub detail: String,
+    pub path: String,
+    pub files: Vec<String>,
+    pub errors: Vec<String>,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct MonkeyStressResult {
+    pub serial: String,
+    pub package_name: String,
+    pub events_requested: u32,
+    pub events_injected: Option<u64>,
+    pub seed: Option<u64>,
+    pub crashed: bool,
+    pub crash_kind: Option<String>,
+    pub triage: Option<TriageBundle>,
+    pub exit_code: Option<i32>,
+    pub cancelled: bool,
+    pub duration_ms: u64,
+}
+
+/// `kind` is `missing_label`, `small_touch_target` or `low_contrast`; `severity` is
+/// `error`, `warning` or `info`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AccessibilityFinding {
+    pub kind: String,
+    pub severity: String,
+    pub path: String,
+    pub class_name: String,
+    pub resource_id: String,
+    pub text: String,
+    pub bounds: String,
+    pub message: String,
+    pub contrast_ratio: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DumpsysResult {
+    pub serial: String,
+    pub service: String,
+    pub args: Vec<String>,
+    /// Registered parser that produced `parsed`, if any.
+    pub parser: Option<String>,
+    pub parsed: Option<serde_json::Value>,
+    /// Always set when nothing was parsed; otherwise only on request.
+    pub raw: Option<String>,
+    pub duration_ms: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct LastBootLogSection {
+    pub source: String,
+    pub label: String,
+    pub available: bool,
+    /// Tail of the log, capped per section.
+    pub content: Option<String>,
+    pub truncated: bool,
+    /// Dropbox entry time for sources that keep history.
+    pub entry_time: Option<String>,
+    pub panic_lines: Vec<String>,
+    /// Why the section is unavailable (missing on this device, root required, ...).
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct LastBootLogs {
+    pub serial: String,
+    pub sections: Vec<LastBootLogSection>,
+    pub panic_detected: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DumpsysParserInfo {
+    pub service: String,
+    pub description: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AccessibilityAudit {
+    pub serial: String,
+    pub density_dpi: u32,
+    pub node_count: usize,
+    pub clickable_count: usize,
+    /// False when the screenshot for contrast estimates was skipped or failed.
+    pub contrast_checked: bool,
+    pub findings: Vec<AccessibilityFinding>,
+    pub captured_at: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct UiHierarchyCaptureResult {
     pub html: String,
     pub xml: String,
     pub screenshot_data_url: Option<String>,
     pub screenshot_error: Option<String>,
+    #[serde(default)]
+    pub backend: String,
+    #[serde(default)]
+    pub capture_id: String,
+    #[serde(default)]
+    pub diff: Option<UiHierarchyDiff>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct UiNodeChange {
+    pub kind: String,
+    pub path: String,
+    pub class_name: String,
+    pub resource_id: String,
+    pub details: String,
+}
+
+/// Pixel diff of two PNG screenshots. `similarity` is the share of matching pixels over the
+/// union of both sizes; `passed` when it reaches `threshold`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ScreenshotComparison {
+    pub path_a: String,
+    pub path_b: String,
+    pub width: u32,
+    pub height: u32,
+    pub diff_pixels: u64,
+    pub total_pixels: u64,
+    pub similarity: f64,
+    pub threshold: f64,
+    pub passed: bool,
+    pub size_mismatch: bool,
+    pub diff_path: String,
+}
+
+/// A stored reference screenshot for one screen at one device resolution.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct GoldenImage {
+    pub screen_name: String,
+    pub resolution: String,
+    pub path: String,
+    #[serde(default)]
+    pub serial: String,
+    #[serde(default)]
+    pub saved_at: String,
+}
+
+/// `status` is `passed`, `failed` or `missing_golden` (no golden at this resolution yet).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GoldenCheckResult {
+    pub serial: String,
+    pub screen_name: String,
+    pub resolution: String,
+    pub status: String,
+    pub golden_path: Option<String>,
+    pub capture_path: String,
+    pub comparison: Option<ScreenshotComparison>,
+    pub checked_at: String,
+    pub trace_id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct UiHierarchyDiff {
+    pub previous_capture_id: String,
+    pub added: usize,
+    pub removed: usize,
+    pub changed: usize,
+    #[serde(default)]
+    pub changes: Vec<UiNodeChange>,
+    pub truncated: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct UiCaptureRecord {
+    pub capture_id: String,
+    pub serial: String,
+    pub captured_at: String,
+    pub backend: String,
+    pub node_count: usize,
+    pub xml: String,
+    pub html: String,
+    pub diff: Option<UiHierarchyDiff>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -111,6 +701,15 @@ pub struct PerfSnapshot {
     pub battery_temp_decic: Option<i32>,
     pub display_refresh_hz_x100: Option<u16>,
     pub missed_frames_per_sec_x100: Option<u16>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cpu_clusters: Vec<CpuCluster>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CpuCluster {
+    pub label: String,
+    pub cores: Vec<usize>,
+    pub max_freq_khz: Option<u32>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -157,23 +756,192 @@ pub struct TerminalEvent {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
-pub struct AppInfo {
-    pub package_name: String,
-    pub version_name: Option<String>,
-    pub version_code: Option<String>,
-    pub is_system: bool,
-    pub apk_path: Option<String>,
+pub struct PipelineCapture {
+    pub variable: String,
+    /// First capture group, or the whole match when the pattern has none.
+    pub pattern: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
-pub struct AppBasicInfo {
-    pub package_name: String,
-    pub version_name: Option<String>,
-    pub version_code: Option<String>,
-    pub first_install_time: Option<String>,
-    pub last_update_time: Option<String>,
-    pub installer_package_name: Option<String>,
-    pub installing_package_name: Option<String>,
+pub struct ShellPipelineStep {
+    pub name: Option<String>,
+    /// May reference earlier captures as `${name}`; values are substituted shell-quoted.
+    pub command: String,
+    #[serde(default)]
+    pub continue_on_error: bool,
+    #[serde(default)]
+    pub captures: Vec<PipelineCapture>,
+    pub timeout_secs: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ShellPipelineStepResult {
+    pub index: usize,
+    pub name: Option<String>,
+    pub command: Option<String>,
+    /// `ok`, `failed` or `skipped`.
+    pub status: String,
+    pub exit_code: Option<i32>,
+    pub stdout: String,
+    pub stderr: String,
+    pub captured: HashMap<String, String>,
+    pub error: Option<String>,
+    pub duration_ms: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ShellPipelineResult {
+    pub serial: String,
+    pub succeeded: bool,
+    /// Index of the failing step that stopped the pipeline.
+    pub stopped_at: Option<usize>,
+    pub variables: HashMap<String, String>,
+    pub steps: Vec<ShellPipelineStepResult>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DmesgLine {
+    /// Seconds since boot from the `[  123.456789]` prefix.
+    pub timestamp_secs: Option<f64>,
+    /// Syslog level (0 = emergency .. 7 = debug) when the kernel prints `<N>`.
+    pub level: Option<u8>,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DmesgEvent {
+    pub serial: String,
+    pub lines: Vec<DmesgLine>,
+    pub trace_id: String,
+}
+
+/// An activity lifecycle callback from the events buffer or, when fragment logging is on, an
+/// AndroidX fragment state move. `timestamp` is the device clock (RFC 3339, UTC); `package`
+/// is resolved from the process id and is `None` once the process is gone.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ActivityLifecycleEvent {
+    pub serial: String,
+    pub timestamp: String,
+    pub pid: Option<u32>,
+    /// `activity` or `fragment`.
+    pub kind: String,
+    /// `created`, `started`, `resumed`, `paused`, `stopped`, `destroyed`, ... for activities;
+    /// the lowercased FragmentManager state (`resumed`, `view_created`, ...) for fragments.
+    pub state: String,
+    pub package: Option<String>,
+    /// Activity class name or fragment class name.
+    pub component: String,
+    pub reason: Option<String>,
+    pub trace_id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct LifecycleStreamInfo {
+    pub serial: String,
+    pub include_fragments: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DmesgStreamInfo {
+    pub serial: String,
+    pub mode: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ShellStreamInfo {
+    pub serial: String,
+    pub stream_id: String,
+    pub timeout_secs: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ShellStreamEvent {
+    pub serial: String,
+    pub stream_id: String,
+    /// `output` chunks, then a single `exit`.
+    pub event: String,
+    pub stream: Option<String>,
+    pub chunk: Option<String>,
+    pub exit_code: Option<i32>,
+    pub cancelled: bool,
+    pub timed_out: bool,
+    pub trace_id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AppInfo {
+    pub package_name: String,
+    pub version_name: Option<String>,
+    pub version_code: Option<String>,
+    pub is_system: bool,
+    pub apk_path: Option<String>,
+    /// `None` when `pm list packages -d` could not be read.
+    #[serde(default)]
+    pub enabled: Option<bool>,
+    /// Only known when versions were fetched.
+    #[serde(default)]
+    pub target_sdk: Option<i64>,
+}
+
+/// Filters, sort and page for `list_apps`, evaluated on the backend. `sort_by` is
+/// `package_name` (default), `version_code` or `target_sdk`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AppListQuery {
+    #[serde(default)]
+    pub name_contains: Option<String>,
+    #[serde(default)]
+    pub enabled: Option<bool>,
+    #[serde(default)]
+    pub system: Option<bool>,
+    #[serde(default)]
+    pub min_target_sdk: Option<i64>,
+    #[serde(default)]
+    pub sort_by: Option<String>,
+    #[serde(default)]
+    pub descending: bool,
+    #[serde(default)]
+    pub offset: Option<usize>,
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AppListPage {
+    pub apps: Vec<AppInfo>,
+    /// Apps matching the filters, before paging.
+    pub total: usize,
+    pub has_more: bool,
+    pub next_offset: usize,
+}
+
+/// One row of the enriched app list. `metadata_pending` means label/icon were not resolved in
+/// this call (per-call fetch budget exhausted or pull failed); ask again to fill them in.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AppListEntry {
+    pub package_name: String,
+    pub label: Option<String>,
+    pub version_name: Option<String>,
+    pub version_code: Option<String>,
+    pub is_system: bool,
+    pub apk_path: Option<String>,
+    pub icon_path: Option<String>,
+    pub app_size_bytes: Option<u64>,
+    pub data_size_bytes: Option<u64>,
+    pub cache_size_bytes: Option<u64>,
+    pub first_install_time: Option<String>,
+    pub last_update_time: Option<String>,
+    pub metadata_pending: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AppBasicInfo {
+    pub package_name: String,
+    pub version_name: Option<String>,
+    pub version_code: Option<String>,
+    pub first_install_time: Option<String>,
+    pub last_update_time: Option<String>,
+    pub installer_package_name: Option<String>,
+    pub installing_package_name: Option<String>,
     pub originating_package_name: Option<String>,
     pub initiating_package_name: Option<String>,
     pub uid: Option<i64>,
@@ -227,6 +995,13 @@ pub struct BugreportLogSummary {
     pub buffers: HashMap<String, usize>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct LogcatFileExportResult {
+    pub output_path: String,
+    pub format: String,
+    pub rows: usize,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
 pub struct BugreportLogFilters {
     pub levels: Vec<String>,
@@ -310,6 +1085,148 @@ pub struct ScrcpyInfo {
     pub command_path: String,
 }
 
+/// An audio-only scrcpy session forwarding device audio to this machine.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AudioForwardSession {
+    pub serial: String,
+    pub codec: String,
+    pub source: String,
+    pub started_at: String,
+    pub running: bool,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ScrcpyOptionIssue {
+    pub flag: String,
+    pub message: String,
+}
+
+/// Dry-build of a scrcpy command line checked against the detected scrcpy version.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ScrcpyValidation {
+    pub available: bool,
+    pub version: Option<String>,
+    pub command: Vec<String>,
+    pub issues: Vec<ScrcpyOptionIssue>,
+    pub valid: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RecordingConversionStatus {
+    pub serial: String,
+    pub source_path: String,
+    pub format: String,
+    pub elapsed_ms: u64,
+}
+
+/// `percent` is estimated from how long the recording ran; `None` when that is unknown.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ScreenRecordConversionProgress {
+    pub serial: String,
+    pub source_path: String,
+    pub format: String,
+    pub converted_ms: u64,
+    pub percent: Option<f32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ScreenRecordConversionResult {
+    pub source_path: String,
+    pub output_path: Option<String>,
+    pub format: String,
+    pub size_bytes: Option<u64>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PointerGesture {
+    pub kind: String,
+    pub x: u32,
+    pub y: u32,
+    pub end_x: Option<u32>,
+    pub end_y: Option<u32>,
+    pub duration_ms: Option<u32>,
+    pub source_width: u32,
+    pub source_height: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PointerGestureResult {
+    pub serial: String,
+    pub kind: String,
+    pub device_width: u32,
+    pub device_height: u32,
+    pub x: u32,
+    pub y: u32,
+    pub end_x: Option<u32>,
+    pub end_y: Option<u32>,
+    pub duration_ms: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct GesturePoint {
+    pub t_ms: u32,
+    pub x: u32,
+    pub y: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct GestureStroke {
+    pub start_ms: u64,
+    pub points: Vec<GesturePoint>,
+}
+
+/// Portable gesture file: points are in the source device's natural-orientation pixels.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct GestureRecording {
+    pub version: u32,
+    pub source_width: u32,
+    pub source_height: u32,
+    pub strokes: Vec<GestureStroke>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct GestureRecordingSummary {
+    pub serial: String,
+    pub path: String,
+    pub stroke_count: usize,
+    pub duration_ms: u64,
+    pub source_width: u32,
+    pub source_height: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct InputBroadcastDeviceResult {
+    pub serial: String,
+    pub success: bool,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct InputBroadcastInfo {
+    pub leader: String,
+    pub followers: Vec<String>,
+    pub touch_device: String,
+}
+
+/// One leader stroke mirrored to the followers; `kind` is `tap`, `long_press` or `swipe`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct InputBroadcastEvent {
+    pub leader: String,
+    pub kind: String,
+    pub results: Vec<InputBroadcastDeviceResult>,
+    pub trace_id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct GestureReplayResult {
+    pub serial: String,
+    pub strokes_played: usize,
+    pub device_width: u32,
+    pub device_height: u32,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub enum ApkInstallErrorCode {
     Success,
@@ -491,7 +1408,6 @@ pub struct ApkInstallResult {
 }
 
 impl ApkInstallResult {
-    #[allow(dead_code)]
     pub fn error_message(&self) -> String {
         if self.success {
             String::new()
@@ -501,12 +1417,161 @@ impl ApkInstallResult {
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CapturePerfSample {
+    pub timestamp: String,
+    pub cpu_percent_x100: Option<u16>,
+    pub mem_total_bytes: Option<u64>,
+    pub mem_used_bytes: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CaptureContextInfo {
+    pub context_id: String,
+    pub serial: String,
+    pub label: String,
+    pub started_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CaptureContextResult {
+    pub context_id: String,
+    pub serial: String,
+    pub label: String,
+    pub path: String,
+    pub log_lines: usize,
+    pub dropped_log_lines: usize,
+    pub perf_samples: usize,
+    pub installs: Vec<ApkInstallResult>,
+}
+
+/// Soak test settings; unset fields take the defaults in `app::soak_test`. A zero capture
+/// interval turns that capture off.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct SoakProfile {
+    pub label: Option<String>,
+    pub sample_interval_secs: Option<u64>,
+    pub screenshot_interval_secs: Option<u64>,
+    pub logcat_interval_secs: Option<u64>,
+    pub logcat_lines: Option<u32>,
+    pub cpu_threshold_percent: Option<u8>,
+    pub mem_threshold_percent: Option<u8>,
+    pub temp_threshold_decic: Option<i32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SoakSample {
+    pub timestamp: String,
+    pub cpu_percent_x100: Option<u16>,
+    pub mem_total_bytes: Option<u64>,
+    pub mem_used_bytes: Option<u64>,
+    pub max_temp_decic: Option<i32>,
+    pub thermal_status: Option<u8>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SoakAlert {
+    pub timestamp: String,
+    /// `cpu`, `memory` or `temperature`.
+    pub metric: String,
+    pub value: i64,
+    pub threshold: i64,
+    pub summary: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SoakTestInfo {
+    pub serial: String,
+    pub label: String,
+    pub started_at: String,
+    pub path: String,
+    pub profile: SoakProfile,
+    /// Monitors this soak test turned on and will turn off again; ones already running are
+    /// left alone.
+    pub started_monitors: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SoakTestReport {
+    pub serial: String,
+    pub label: String,
+    pub started_at: String,
+    pub ended_at: String,
+    pub duration_secs: i64,
+    pub path: String,
+    pub samples: usize,
+    pub screenshots: usize,
+    pub logcat_snapshots: usize,
+    pub capture_errors: usize,
+    pub alerts: Vec<SoakAlert>,
+    pub cpu_avg_percent_x100: Option<u16>,
+    pub cpu_max_percent_x100: Option<u16>,
+    pub mem_max_used_bytes: Option<u64>,
+    pub temp_max_decic: Option<i32>,
+    /// Bytes each uid moved during the soak, busiest first.
+    pub net_usage: Vec<SoakNetUsage>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SoakNetUsage {
+    pub uid: u32,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub packages: Vec<String>,
+    pub rx_bytes: u64,
+    pub tx_bytes: u64,
+}
+
+/// An APK found in a Gradle `build/outputs/apk` tree. Metadata fields come from
+/// `output-metadata.json` when the plugin wrote one.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BuildVariantApk {
+    pub variant: String,
+    pub path: String,
+    pub application_id: Option<String>,
+    pub version_code: Option<i64>,
+    pub version_name: Option<String>,
+    pub output_type: Option<String>,
+    pub filters: Vec<String>,
+    pub size_bytes: u64,
+    pub modified_at: Option<String>,
+    pub apk_info: Option<ApkInfo>,
+}
+
+/// Install flags applied on every watch-mode cycle; `relaunch` starts the app afterwards.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct ApkWatchOptions {
+    pub replace: bool,
+    pub allow_downgrade: bool,
+    pub grant: bool,
+    pub allow_test_packages: bool,
+    pub extra_args: Option<String>,
+    pub relaunch: bool,
+}
+
+impl Default for ApkWatchOptions {
+    fn default() -> Self {
+        Self {
+            replace: true,
+            allow_downgrade: false,
+            grant: false,
+            allow_test_packages: false,
+            extra_args: None,
+            relaunch: false,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct ApkBatchInstallResult {
     pub apk_path: String,
     pub apk_info: Option<ApkInfo>,
     pub results: HashMap<String, ApkInstallResult>,
     pub total_duration_seconds: f64,
+    /// Capture folders written for this install, keyed by serial.
+    #[serde(default)]
+    pub capture_paths: HashMap<String, String>,
 }
 
 impl ApkBatchInstallResult {
@@ -516,6 +1581,656 @@ impl ApkBatchInstallResult {
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CommandLogEntry {
+    pub id: u64,
+    pub trace_id: String,
+    pub serial: Option<String>,
+    pub program: String,
+    pub args: Vec<String>,
+    pub started_at: String,
+    pub duration_ms: u64,
+    pub exit_code: Option<i32>,
+    pub outcome: String,
+}
+
+/// One backend log event from the in-memory ring; `fields` holds structured fields such as
+/// `trace_id`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AppLogEntry {
+    pub timestamp: String,
+    pub level: String,
+    pub target: String,
+    pub message: String,
+    pub fields: serde_json::Value,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AppLogStatus {
+    pub default_level: String,
+    pub module_levels: BTreeMap<String, String>,
+    pub log_to_file: bool,
+    /// Active log file, `None` when file logging is off.
+    pub log_file: Option<String>,
+    pub max_log_files: i32,
+    pub log_file_size_mb: i32,
+    pub file_error: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CommandLogQuery {
+    #[serde(default)]
+    pub serial: Option<String>,
+    #[serde(default)]
+    pub trace_id: Option<String>,
+    #[serde(default)]
+    pub contains: Option<String>,
+    #[serde(default)]
+    pub failed_only: bool,
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TimelineEvent {
+    pub id: u64,
+    pub serial: String,
+    pub kind: String,
+    pub timestamp: String,
+    pub summary: String,
+    pub trace_id: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TimelineQuery {
+    pub serial: String,
+    #[serde(default)]
+    pub since: Option<String>,
+    #[serde(default)]
+    pub until: Option<String>,
+    #[serde(default)]
+    pub kinds: Vec<String>,
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ArtifactUploadResult {
+    pub path: String,
+    pub url: Option<String>,
+    pub bytes: u64,
+    pub success: bool,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DeviceFarmDevice {
+    pub model: String,
+    pub version: String,
+    #[serde(default)]
+    pub locale: String,
+    #[serde(default)]
+    pub orientation: String,
+}
+
+/// Submission to Firebase Test Lab: a robo run (optionally driven by a Robo script) or an
+/// instrumentation run with a test APK.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DeviceFarmRequest {
+    pub apk_path: String,
+    pub test_type: String,
+    #[serde(default)]
+    pub test_apk_path: Option<String>,
+    #[serde(default)]
+    pub robo_script_path: Option<String>,
+    #[serde(default)]
+    pub test_targets: Vec<String>,
+    pub devices: Vec<DeviceFarmDevice>,
+    #[serde(default)]
+    pub timeout_minutes: Option<u32>,
+    #[serde(default)]
+    pub output_dir: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DeviceFarmBundle {
+    pub bundle_dir: String,
+    pub argfile_path: String,
+    /// gcloud invocation to run from `bundle_dir`.
+    pub command: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DeviceFarmOutcome {
+    pub axis: String,
+    pub outcome: String,
+    pub details: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DeviceFarmRunResult {
+    pub bundle: DeviceFarmBundle,
+    pub success: bool,
+    /// `passed`, `failed`, `inconclusive`, `incompatible`, `cancelled`,
+    /// `infrastructure_error` or `error`.
+    pub outcome: String,
+    pub exit_code: Option<i32>,
+    pub results_url: Option<String>,
+    pub storage_url: Option<String>,
+    pub outcomes: Vec<DeviceFarmOutcome>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CreatedIssue {
+    pub provider: String,
+    pub key: String,
+    pub url: String,
+    pub attached: Vec<String>,
+    pub failed_attachments: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SessionTimeRange {
+    #[serde(default)]
+    pub since: Option<String>,
+    #[serde(default)]
+    pub until: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SessionReportResult {
+    pub path: String,
+    pub device_count: usize,
+    pub event_count: usize,
+    pub capture_count: usize,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct TimingSummary {
+    pub count: u64,
+    pub total_ms: u64,
+    pub max_ms: u64,
+    pub avg_ms: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CommandMetric {
+    pub command: String,
+    pub failures: u64,
+    pub timing: TimingSummary,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SchedulerMetric {
+    pub global_limit: usize,
+    pub global_in_use: usize,
+    pub wait: TimingSummary,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct EventMetric {
+    pub event: String,
+    pub count: u64,
+    pub per_minute: f64,
+    /// Payloads dropped by the channel's rate limit.
+    #[serde(default)]
+    pub rate_limited: u64,
+    /// Identical consecutive payloads that were not re-sent.
+    #[serde(default)]
+    pub coalesced: u64,
+    /// Payloads for devices the UI had not subscribed to on that channel.
+    #[serde(default)]
+    pub unsubscribed: u64,
+}
+
+/// `channels` the device is subscribed to and the color the UI draws its streams with.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct EventSubscriptionInfo {
+    pub serial: String,
+    pub channels: Vec<String>,
+    pub color: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct InternalMetrics {
+    pub uptime_ms: u64,
+    pub commands: Vec<CommandMetric>,
+    pub scheduler: SchedulerMetric,
+    pub events: Vec<EventMetric>,
+}
+
+fn default_readiness_min_battery() -> u8 {
+    50
+}
+
+fn default_readiness_min_storage_mb() -> u64 {
+    1024
+}
+
+fn default_readiness_max_clock_skew() -> i64 {
+    60
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ReadinessThresholds {
+    #[serde(default = "default_readiness_min_battery")]
+    pub min_battery_percent: u8,
+    #[serde(default = "default_readiness_min_storage_mb")]
+    pub min_free_storage_mb: u64,
+    #[serde(default = "default_readiness_max_clock_skew")]
+    pub max_clock_skew_secs: i64,
+}
+
+impl Default for ReadinessThresholds {
+    fn default() -> Self {
+        Self {
+            min_battery_percent: default_readiness_min_battery(),
+            min_free_storage_mb: default_readiness_min_storage_mb(),
+            max_clock_skew_secs: default_readiness_max_clock_skew(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ReadinessCheck {
+    pub name: String,
+    pub status: String,
+    pub detail: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DeviceReadinessResult {
+    pub serial: String,
+    pub ready: bool,
+    pub checks: Vec<ReadinessCheck>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DeviceUnlockResult {
+    pub serial: String,
+    pub pin_entered: bool,
+    pub keyguard_locked: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct MockLocationResult {
+    pub serial: String,
+    pub method: String,
+    pub helper_package: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct EmulatorSnapshot {
+    pub name: String,
+    pub size: String,
+    pub created_at: String,
+    pub vm_clock: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AdbTunnelStatus {
+    pub name: String,
+    pub host: String,
+    pub local_port: u16,
+    pub state: String,
+    pub message: Option<String>,
+    pub trace_id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AppUninstallResult {
+    pub serial: String,
+    pub package_name: String,
+    pub success: bool,
+    pub skipped: bool,
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct IconCacheStats {
+    pub file_count: usize,
+    pub total_bytes: u64,
+    pub max_bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DebugAgentStatus {
+    pub serial: String,
+    pub remote_path: String,
+    pub running: bool,
+    pub pids: Vec<u32>,
+    pub device_port: u16,
+    pub local_port: u16,
+    pub forwarded: bool,
+}
+
+/// An inspectable page from a DevTools socket. `devtools_url` opens the inspector in a
+/// desktop Chrome; it is absent while another debugger is attached.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DevtoolsTarget {
+    pub socket: String,
+    pub local_port: u16,
+    pub id: String,
+    pub target_type: String,
+    pub title: String,
+    pub url: String,
+    pub websocket_url: Option<String>,
+    pub devtools_url: Option<String>,
+}
+
+/// A Chrome or WebView DevTools socket and the local port forwarded to it.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DevtoolsSocket {
+    pub socket: String,
+    pub package_name: Option<String>,
+    pub pid: Option<u32>,
+    pub local_port: Option<u16>,
+    pub targets: Vec<DevtoolsTarget>,
+    pub error: Option<String>,
+}
+
+/// A runtime resource overlay (RRO) from `cmd overlay list`. `state` is `enabled`,
+/// `disabled` or `unavailable` (cannot be toggled).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct OverlayPackage {
+    pub package_name: String,
+    pub target_package: String,
+    pub state: String,
+}
+
+/// One stream from `dumpsys audio`; `devices` are the outputs it currently plays to.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AudioStreamState {
+    pub stream: String,
+    pub volume: Option<u32>,
+    pub min: Option<u32>,
+    pub max: Option<u32>,
+    pub muted: bool,
+    pub devices: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AudioRouteInfo {
+    pub serial: String,
+    /// Audio mode, ringer mode and activity, as shown in device details.
+    pub summary: Option<String>,
+    /// Where media currently plays, e.g. `speaker` or `bt_a2dp`.
+    pub media_route: Vec<String>,
+    pub streams: Vec<AudioStreamState>,
+}
+
+/// A media session from `dumpsys media_session`. `state` is the `PlaybackState` name, e.g.
+/// `playing` or `paused`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct MediaSessionInfo {
+    pub tag: String,
+    pub package_name: Option<String>,
+    pub active: bool,
+    pub state: Option<String>,
+    pub position_ms: Option<i64>,
+    /// Title, artist and album as the session publishes them.
+    pub description: Option<String>,
+}
+
+/// An accessibility service; `installed` is false for an enabled service whose app is gone.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AccessibilityServiceInfo {
+    pub component: String,
+    pub package_name: String,
+    pub enabled: bool,
+    pub installed: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct InputMethodInfo {
+    pub id: String,
+    pub package_name: String,
+    pub enabled: bool,
+    pub selected: bool,
+}
+
+/// `current` is the selected USB function (`mtp`, `ptp`, `rndis`, `midi`, `ncm` or `none` for
+/// charging only); `None` when it could not be read. `functions` lists every active gadget
+/// function other than `adb`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct UsbFunctionState {
+    pub serial: String,
+    pub current: Option<String>,
+    pub functions: Vec<String>,
+    pub adb_enabled: bool,
+}
+
+/// `proxy` is the active global HTTP proxy (`host:port`), if any.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ProxyStatus {
+    pub serial: String,
+    pub proxy: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CaCertificateInstall {
+    pub serial: String,
+    pub device_path: String,
+    pub installer_launched: bool,
+    pub note: String,
+}
+
+/// `method` is `emulator_console` or `tc` (netem through a root shell).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct NetworkShapingResult {
+    pub serial: String,
+    pub method: String,
+    pub interface: Option<String>,
+    pub rate_kbps: u32,
+    pub latency_ms: u32,
+    pub loss_pct: f64,
+    pub active: bool,
+}
+
+/// `locale` and `force_rtl` are the values now in effect, `None` when left unchanged.
+/// `restart_required` means apps show the change only after a UI restart or reboot.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct I18nToggleResult {
+    pub serial: String,
+    pub success: bool,
+    pub locale: Option<String>,
+    pub force_rtl: Option<bool>,
+    pub restart_required: bool,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct NativeLibAbi {
+    pub abi: String,
+    pub libraries: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AppBinaryInspection {
+    pub serial: String,
+    pub package_name: String,
+    pub apk_path: String,
+    /// `apk_path` followed by any split APKs; sizes and contents cover all of them.
+    pub apk_paths: Vec<String>,
+    pub apk_size_bytes: u64,
+    pub dex_count: usize,
+    pub native_libs: Vec<NativeLibAbi>,
+    pub min_sdk: Option<i64>,
+    pub target_sdk: Option<i64>,
+    pub debuggable: bool,
+    pub primary_cpu_abi: Option<String>,
+    pub device_abis: Vec<String>,
+    pub device_sdk: Option<i64>,
+    pub issues: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ThermalReading {
+    pub name: String,
+    pub kind: String,
+    pub temp_decic: i32,
+    pub throttle_status: Option<u8>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ThermalCrossing {
+    pub name: String,
+    pub temp_decic: i32,
+    pub threshold_decic: i32,
+    pub rising: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ThermalSnapshot {
+    pub ts_ms: i64,
+    pub thermal_status: Option<u8>,
+    pub throttle_label: Option<String>,
+    pub max_temp_decic: Option<i32>,
+    pub readings: Vec<ThermalReading>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PerfFrameDevice {
+    pub serial: String,
+    pub snapshot: Option<PerfSnapshot>,
+}
+
+/// Snapshots from several devices aligned to the same wall-clock bucket.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PerfMultiFrame {
+    pub bucket_ts_ms: i64,
+    pub bucket_ms: u64,
+    pub devices: Vec<PerfFrameDevice>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct StartupTimeStats {
+    pub min_ms: u32,
+    pub median_ms: u32,
+    pub max_ms: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct StartupSample {
+    pub total_time_ms: Option<u32>,
+    pub wait_time_ms: Option<u32>,
+    pub launch_state: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AppStartupResult {
+    pub serial: String,
+    pub package_name: String,
+    pub activity: String,
+    pub mode: String,
+    pub samples: Vec<StartupSample>,
+    pub total_time: Option<StartupTimeStats>,
+    pub wait_time: Option<StartupTimeStats>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct JankScenarioResult {
+    pub serial: String,
+    pub package_name: String,
+    pub gestures_played: usize,
+    pub total_frames: Option<u64>,
+    pub janky_frames: Option<u64>,
+    pub jank_percent_x100: Option<u16>,
+    pub p50_ms: Option<u32>,
+    pub p90_ms: Option<u32>,
+    pub p95_ms: Option<u32>,
+    pub p99_ms: Option<u32>,
+    pub missed_vsync: Option<u64>,
+    pub slow_ui_thread: Option<u64>,
+    pub frame_deadline_missed: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CapturedPushToken {
+    pub token: String,
+    pub first_seen: Option<String>,
+    pub last_seen: Option<String>,
+    pub occurrences: u32,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct UiSelector {
+    #[serde(default)]
+    pub resource_id: Option<String>,
+    #[serde(default)]
+    pub text: Option<String>,
+    #[serde(default)]
+    pub content_desc: Option<String>,
+}
+
+/// A field to fill: tap device coordinates (`x`/`y`) or a node matched by `selector`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TextFieldEntry {
+    #[serde(default)]
+    pub x: Option<u32>,
+    #[serde(default)]
+    pub y: Option<u32>,
+    #[serde(default)]
+    pub selector: Option<UiSelector>,
+    pub value: String,
+    #[serde(default)]
+    pub clear: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TextFieldFillResult {
+    pub index: usize,
+    pub success: bool,
+    pub error: Option<String>,
+}
+
+/// `method` is `input_text` (chunked `adb shell input text`) or `adb_keyboard`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TypeTextResult {
+    pub serial: String,
+    pub method: String,
+    pub characters: usize,
+    pub chunks: usize,
+}
+
+/// One row of `check_environment`. `status` is `ok`, `warning`, `missing` or `error`; `hint`
+/// tells the user how to fix anything that is not `ok`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DependencyStatus {
+    pub name: String,
+    pub status: String,
+    pub required: bool,
+    pub path: Option<String>,
+    pub version: Option<String>,
+    pub detail: Option<String>,
+    pub hint: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct EnvironmentReport {
+    pub ready: bool,
+    pub items: Vec<DependencyStatus>,
+}
+
+/// One onboarding step; `status` is `pending`, `completed`, `failed` or `skipped`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SetupWizardStep {
+    pub id: String,
+    pub status: String,
+    pub detail: Option<String>,
+    pub error: Option<String>,
+    pub hint: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SetupWizardState {
+    pub steps: Vec<SetupWizardStep>,
+    pub current_step: Option<String>,
+    pub completed: bool,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
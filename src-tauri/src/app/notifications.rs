@@ -0,0 +1,362 @@
+use std::collections::HashSet;
+use std::thread;
+use std::time::Duration;
+
+use chrono::{SecondsFormat, Utc};
+use tracing::{info, warn};
+
+use crate::app::config::{load_config, NotificationsSettings, WebhookTarget};
+use crate::app::error::AppError;
+use crate::app::models::DeviceSummary;
+use crate::app::upload::{
+    quote_config_value, required_env, resolve_curl_program, run_curl, write_temp_json,
+};
+
+pub const NOTIFY_BUGREPORT_COMPLETED: &str = "bugreport_completed";
+pub const NOTIFY_BUGREPORT_FAILED: &str = "bugreport_failed";
+pub const NOTIFY_INSTALL_FINISHED: &str = "install_batch_finished";
+pub const NOTIFY_PERF_ALERT: &str = "perf_alert";
+pub const NOTIFY_DEVICE_OFFLINE: &str = "device_offline";
+pub const NOTIFY_TEST: &str = "test";
+
+pub const NOTIFICATION_EVENTS: [&str; 6] = [
+    NOTIFY_BUGREPORT_COMPLETED,
+    NOTIFY_BUGREPORT_FAILED,
+    NOTIFY_INSTALL_FINISHED,
+    NOTIFY_PERF_ALERT,
+    NOTIFY_DEVICE_OFFLINE,
+    NOTIFY_TEST,
+];
+
+/// Slack incoming webhooks accept `{"text": ...}`; most chat tools understand it too.
+pub const DEFAULT_WEBHOOK_TEMPLATE: &str = r#"{"text": "[{{event}}] {{serial}}: {{summary}}"}"#;
+
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(15);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NotificationContext {
+    pub event: String,
+    pub serial: String,
+    pub status: String,
+    pub summary: String,
+    pub timestamp: String,
+}
+
+impl NotificationContext {
+    pub fn new(event: &str, serial: &str, success: bool, summary: &str) -> Self {
+        Self {
+            event: event.to_string(),
+            serial: serial.to_string(),
+            status: if success { "success" } else { "failure" }.to_string(),
+            summary: summary.to_string(),
+            timestamp: Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true),
+        }
+    }
+}
+
+/// Trim and default a configured webhook; returns false when it cannot be used.
+pub fn normalize_webhook(webhook: &mut WebhookTarget) -> bool {
+    webhook.name = webhook.name.trim().to_string();
+    webhook.url = webhook.url.trim().to_string();
+    webhook.url_env = webhook.url_env.trim().to_string();
+    webhook.template = webhook.template.trim().to_string();
+    if webhook.template.is_empty() {
+        webhook.template = DEFAULT_WEBHOOK_TEMPLATE.to_string();
+    }
+    let mut events = Vec::new();
+    for event in &webhook.events {
+        let event = event.trim().to_lowercase();
+        if NOTIFICATION_EVENTS.contains(&event.as_str()) && !events.contains(&event) {
+            events.push(event);
+        } else if !event.is_empty() {
+            warn!(webhook = %webhook.name, event = %event, "ignoring unknown webhook event");
+        }
+    }
+    webhook.events = events;
+    webhook.headers.retain_mut(|header| {
+        header.name = header.name.trim().to_string();
+        header.value = header.value.trim().to_string();
+        !header.name.is_empty()
+    });
+    let has_url = webhook.url.starts_with("https://")
+        || webhook.url.starts_with("http://")
+        || !webhook.url_env.is_empty();
+    !webhook.name.is_empty() && has_url
+}
+
+pub fn webhooks_for_event<'a>(
+    settings: &'a NotificationsSettings,
+    event: &str,
+) -> Vec<&'a WebhookTarget> {
+    settings
+        .webhooks
+        .iter()
+        .filter(|webhook| webhook.enabled)
+        .filter(|webhook| {
+            event == NOTIFY_TEST
+                || webhook.events.is_empty()
+                || webhook.events.iter().any(|entry| entry == event)
+        })
+        .collect()
+}
+
+fn json_escape(value: &str) -> String {
+    let quoted = serde_json::Value::String(value.to_string()).to_string();
+    quoted[1..quoted.len() - 1].to_string()
+}
+
+/// Substitute placeholders with JSON-escaped values and check the result is valid JSON.
+pub fn render_webhook_payload(
+    template: &str,
+    context: &NotificationContext,
+) -> Result<serde_json::Value, String> {
+    let rendered = template
+        .replace("{{event}}", &json_escape(&context.event))
+        .replace("{{serial}}", &json_escape(&context.serial))
+        .replace("{{status}}", &json_escape(&context.status))
+        .replace("{{summary}}", &json_escape(&context.summary))
+        .replace("{{timestamp}}", &json_escape(&context.timestamp));
+    serde_json::from_str(&rendered)
+        .map_err(|err| format!("Webhook template is not valid JSON: {err}"))
+}
+
+/// curl config lines for a webhook. The URL goes in the config file as well, since Slack
+/// webhook URLs are credentials.
+pub fn webhook_config_lines(
+    webhook: &WebhookTarget,
+    env: impl Fn(&str) -> Option<String>,
+) -> Result<Vec<String>, String> {
+    let url = if webhook.url_env.is_empty() {
+        webhook.url.clone()
+    } else {
+        required_env(&webhook.url_env, &env)?
+    };
+    let mut lines = vec![format!("url = {}", quote_config_value(&url)?)];
+    for header in &webhook.headers {
+        if header.name.contains([':', '\n', '\r']) {
+            return Err(format!("Invalid header name: {}", header.name));
+        }
+        let value = if header.value_from_env {
+            required_env(&header.value, &env)?
+        } else {
+            header.value.clone()
+        };
+        lines.push(format!(
+            "header = {}",
+            quote_config_value(&format!("{}: {value}", header.name))?
+        ));
+    }
+    Ok(lines)
+}
+
+pub fn send_webhook(
+    curl_program: &str,
+    webhook: &WebhookTarget,
+    context: &NotificationContext,
+    trace_id: &str,
+) -> Result<(), AppError> {
+    let payload = render_webhook_payload(&webhook.template, context)
+        .map_err(|message| AppError::validation(message, trace_id))?;
+    let config_lines = webhook_config_lines(webhook, |name| std::env::var(name).ok())
+        .map_err(|message| AppError::validation(message, trace_id))?;
+    let body = write_temp_json(&payload, trace_id)?;
+    let args = vec![
+        "-X".to_string(),
+        "POST".to_string(),
+        "-H".to_string(),
+        "Content-Type: application/json".to_string(),
+        "--data-binary".to_string(),
+        format!("@{}", body.path().to_string_lossy()),
+    ];
+    run_curl(
+        curl_program,
+        &config_lines,
+        args,
+        WEBHOOK_TIMEOUT,
+        &format!("Webhook {}", webhook.name),
+        trace_id,
+    )?;
+    Ok(())
+}
+
+/// Fire matching webhooks on a background thread; failures are logged, never surfaced to the
+/// operation that triggered them.
+pub fn notify_webhooks(event: &str, serial: &str, success: bool, summary: &str, trace_id: &str) {
+    let config = match load_config(trace_id) {
+        Ok(config) => config,
+        Err(err) => {
+            warn!(trace_id = %trace_id, error = %err.error, "failed to load config for webhooks");
+            return;
+        }
+    };
+    let webhooks: Vec<WebhookTarget> = webhooks_for_event(&config.notifications, event)
+        .into_iter()
+        .cloned()
+        .collect();
+    if webhooks.is_empty() {
+        return;
+    }
+    let curl_program = resolve_curl_program(&config);
+    let context = NotificationContext::new(event, serial, success, summary);
+    let trace_id = trace_id.to_string();
+    thread::spawn(move || {
+        for webhook in &webhooks {
+            match send_webhook(&curl_program, webhook, &context, &trace_id) {
+                Ok(()) => {
+                    info!(trace_id = %trace_id, webhook = %webhook.name, event = %context.event, "webhook sent")
+                }
+                Err(err) => {
+                    warn!(trace_id = %trace_id, webhook = %webhook.name, event = %context.event, error = %err.error, "webhook failed")
+                }
+            }
+        }
+    });
+}
+
+/// Serials that were online in `previous` and are now missing or in another state; updates
+/// `previous` to the current online set.
+pub fn devices_gone_offline(
+    previous: &mut HashSet<String>,
+    current: &[DeviceSummary],
+) -> Vec<String> {
+    let online: HashSet<String> = current
+        .iter()
+        .filter(|device| device.state == "device")
+        .map(|device| device.serial.clone())
+        .collect();
+    let mut gone: Vec<String> = previous.difference(&online).cloned().collect();
+    gone.sort();
+    *previous = online;
+    gone
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::config::UploadHeader;
+
+    fn webhook(name: &str, events: &[&str]) -> WebhookTarget {
+        WebhookTarget {
+            name: name.to_string(),
+            url: "https://hooks.example.com/abc".to_string(),
+            url_env: String::new(),
+            events: events.iter().map(|event| event.to_string()).collect(),
+            template: String::new(),
+            headers: Vec::new(),
+            enabled: true,
+        }
+    }
+
+    fn context() -> NotificationContext {
+        NotificationContext {
+            event: NOTIFY_BUGREPORT_COMPLETED.to_string(),
+            serial: "emulator-5554".to_string(),
+            status: "success".to_string(),
+            summary: "Saved to \"C:\\out\\bug.zip\"\nDone".to_string(),
+            timestamp: "2024-05-01T10:00:00Z".to_string(),
+        }
+    }
+
+    #[test]
+    fn normalizes_webhooks() {
+        let mut hook = webhook(" ci ", &[" Perf_Alert ", "unknown", "perf_alert"]);
+        assert!(normalize_webhook(&mut hook));
+        assert_eq!(hook.name, "ci");
+        assert_eq!(hook.events, ["perf_alert"]);
+        assert_eq!(hook.template, DEFAULT_WEBHOOK_TEMPLATE);
+
+        let mut no_url = webhook("x", &[]);
+        no_url.url = "ftp://nope".to_string();
+        assert!(!normalize_webhook(&mut no_url));
+        no_url.url_env = "SLACK_WEBHOOK_URL".to_string();
+        assert!(normalize_webhook(&mut no_url));
+    }
+
+    #[test]
+    fn selects_webhooks_by_event() {
+        let mut disabled = webhook("off", &[]);
+        disabled.enabled = false;
+        let settings = NotificationsSettings {
+            webhooks: vec![
+                webhook("all", &[]),
+                webhook("perf", &[NOTIFY_PERF_ALERT]),
+                disabled,
+            ],
+            ..NotificationsSettings::default()
+        };
+        let names = |event: &str| {
+            webhooks_for_event(&settings, event)
+                .iter()
+                .map(|hook| hook.name.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(names(NOTIFY_PERF_ALERT), ["all", "perf"]);
+        assert_eq!(names(NOTIFY_DEVICE_OFFLINE), ["all"]);
+        assert_eq!(names(NOTIFY_TEST), ["all", "perf"]);
+    }
+
+    #[test]
+    fn renders_templates_with_escaped_values() {
+        let payload = render_webhook_payload(DEFAULT_WEBHOOK_TEMPLATE, &context()).unwrap();
+        assert_eq!(
+            payload["text"],
+            "[bugreport_completed] emulator-5554: Saved to \"C:\\out\\bug.zip\"\nDone"
+        );
+        let custom = render_webhook_payload(
+            r#"{"status": "{{status}}", "at": "{{timestamp}}"}"#,
+            &context(),
+        )
+        .unwrap();
+        assert_eq!(custom["status"], "success");
+        assert_eq!(custom["at"], "2024-05-01T10:00:00Z");
+        assert!(render_webhook_payload("{{summary}}", &context()).is_err());
+    }
+
+    #[test]
+    fn keeps_url_and_secret_headers_in_config_lines() {
+        let mut hook = webhook("ci", &[]);
+        hook.url_env = "SLACK_WEBHOOK_URL".to_string();
+        hook.headers = vec![UploadHeader {
+            name: "X-Token".to_string(),
+            value: "HOOK_TOKEN".to_string(),
+            value_from_env: true,
+        }];
+        let env = |name: &str| match name {
+            "SLACK_WEBHOOK_URL" => Some("https://hooks.slack.com/services/T/B/x".to_string()),
+            "HOOK_TOKEN" => Some("t0k".to_string()),
+            _ => None,
+        };
+        assert_eq!(
+            webhook_config_lines(&hook, env).unwrap(),
+            [
+                "url = \"https://hooks.slack.com/services/T/B/x\"",
+                "header = \"X-Token: t0k\""
+            ]
+        );
+        assert!(webhook_config_lines(&hook, |_| None).is_err());
+    }
+
+    #[test]
+    fn detects_devices_going_offline() {
+        let device = |serial: &str, state: &str| DeviceSummary {
+            serial: serial.to_string(),
+            state: state.to_string(),
+            model: None,
+            product: None,
+            device: None,
+            transport_id: None,
+            connection_type: crate::app::adb::locator::transport_for_serial(serial).to_string(),
+            usb_port: None,
+        };
+        let mut online = HashSet::new();
+        assert!(
+            devices_gone_offline(&mut online, &[device("a", "device"), device("b", "device")])
+                .is_empty()
+        );
+        assert_eq!(
+            devices_gone_offline(&mut online, &[device("a", "offline")]),
+            ["a", "b"]
+        );
+        assert!(devices_gone_offline(&mut online, &[device("a", "device")]).is_empty());
+    }
+}
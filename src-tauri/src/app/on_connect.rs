@@ -0,0 +1,216 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use tracing::warn;
+
+use crate::app::config::{config_path, OnConnectAction, OnConnectSettings};
+use crate::app::error::AppError;
+use crate::app::i18n::Message;
+use crate::app::models::OnConnectAuditEntry;
+
+pub const ON_CONNECT_APPLIED_EVENT: &str = "on-connect-applied";
+
+pub const ACTION_STAY_AWAKE: &str = "stay_awake";
+pub const ACTION_DISABLE_ANIMATIONS: &str = "disable_animations";
+pub const ACTION_SET_LOCALE: &str = "set_locale";
+pub const ACTION_START_LOGCAT: &str = "start_logcat";
+pub const ON_CONNECT_ACTIONS: [&str; 4] = [
+    ACTION_STAY_AWAKE,
+    ACTION_DISABLE_ANIMATIONS,
+    ACTION_SET_LOCALE,
+    ACTION_START_LOGCAT,
+];
+
+/// Oldest entries are dropped once the audit log grows past this.
+pub const AUDIT_LIMIT: usize = 500;
+
+/// Devices coming online together append from separate threads.
+static AUDIT_LOCK: Mutex<()> = Mutex::new(());
+
+const ANIMATION_SCALES: [&str; 3] = [
+    "window_animation_scale",
+    "transition_animation_scale",
+    "animator_duration_scale",
+];
+
+/// BCP 47 style tag such as `en-US`, `zh-Hant-TW` or `fr`.
+pub fn is_valid_locale(tag: &str) -> bool {
+    let mut parts = tag.split('-');
+    let language_ok = parts.next().is_some_and(|language| {
+        (2..=3).contains(&language.len()) && language.chars().all(|ch| ch.is_ascii_alphabetic())
+    });
+    language_ok
+        && parts.all(|part| {
+            (2..=8).contains(&part.len()) && part.chars().all(|ch| ch.is_ascii_alphanumeric())
+        })
+}
+
+/// Actions to run for `serial`, or `None` when the device has not opted in.
+pub fn profile_actions<'a>(
+    settings: &'a OnConnectSettings,
+    serial: &str,
+) -> Option<&'a [OnConnectAction]> {
+    settings
+        .profiles
+        .iter()
+        .find(|profile| profile.enabled && profile.serial == serial)
+        .map(|profile| profile.actions.as_slice())
+        .filter(|actions| !actions.is_empty())
+}
+
+/// `adb shell` invocations for an action. `start_logcat` is not a shell action and yields
+/// none. Setting the locale needs an emulator or userdebug build on most devices.
+pub fn action_shell_commands(action: &OnConnectAction) -> Vec<Vec<String>> {
+    let command = |parts: &[&str]| parts.iter().map(|part| part.to_string()).collect();
+    match action.kind.as_str() {
+        ACTION_STAY_AWAKE => vec![command(&["svc", "power", "stayon", "true"])],
+        ACTION_DISABLE_ANIMATIONS => ANIMATION_SCALES
+            .iter()
+            .map(|key| command(&["settings", "put", "global", key, "0"]))
+            .collect(),
+        ACTION_SET_LOCALE => vec![command(&["setprop", "persist.sys.locale", &action.value])],
+        _ => Vec::new(),
+    }
+}
+
+pub fn on_connect_audit_path() -> PathBuf {
+    config_path().with_file_name(".lazy_blacktea_on_connect_audit.json")
+}
+
+pub fn load_audit(path: &Path) -> Vec<OnConnectAuditEntry> {
+    let raw = match fs::read_to_string(path) {
+        Ok(raw) => raw,
+        Err(_) => return Vec::new(),
+    };
+    match serde_json::from_str(&raw) {
+        Ok(entries) => entries,
+        Err(err) => {
+            warn!(path = %path.display(), error = %err, "on-connect audit is invalid, starting over");
+            Vec::new()
+        }
+    }
+}
+
+pub fn append_audit(
+    path: &Path,
+    entries: &[OnConnectAuditEntry],
+    trace_id: &str,
+) -> Result<(), AppError> {
+    let _guard = AUDIT_LOCK
+        .lock()
+        .map_err(|_| AppError::system("On-connect audit locked", trace_id))?;
+    let mut audit = load_audit(path);
+    audit.extend_from_slice(entries);
+    if audit.len() > AUDIT_LIMIT {
+        audit.drain(..audit.len() - AUDIT_LIMIT);
+    }
+    let payload = serde_json::to_string_pretty(&audit).map_err(|err| {
+        AppError::system(
+            Message::keyed("operation_failed")
+                .with("action", "serialize on-connect audit")
+                .with("detail", &err),
+            trace_id,
+        )
+    })?;
+    fs::write(path, payload).map_err(|err| {
+        AppError::system(
+            Message::keyed("operation_failed")
+                .with("action", "save on-connect audit")
+                .with("detail", &err),
+            trace_id,
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::config::OnConnectProfile;
+
+    fn action(kind: &str, value: &str) -> OnConnectAction {
+        OnConnectAction {
+            kind: kind.to_string(),
+            value: value.to_string(),
+        }
+    }
+
+    fn entry(serial: &str, action: &str) -> OnConnectAuditEntry {
+        OnConnectAuditEntry {
+            serial: serial.to_string(),
+            action: action.to_string(),
+            value: String::new(),
+            success: true,
+            error: None,
+            applied_at: "2024-01-01T00:00:00Z".to_string(),
+            trace_id: "trace".to_string(),
+        }
+    }
+
+    #[test]
+    fn validates_locale_tags() {
+        assert!(is_valid_locale("en"));
+        assert!(is_valid_locale("en-US"));
+        assert!(is_valid_locale("zh-Hant-TW"));
+        assert!(!is_valid_locale(""));
+        assert!(!is_valid_locale("english"));
+        assert!(!is_valid_locale("en_US"));
+        assert!(!is_valid_locale("en-US; reboot"));
+    }
+
+    #[test]
+    fn only_enabled_profiles_apply() {
+        let settings = OnConnectSettings {
+            profiles: vec![
+                OnConnectProfile {
+                    serial: "A1".to_string(),
+                    enabled: true,
+                    actions: vec![action(ACTION_STAY_AWAKE, "")],
+                },
+                OnConnectProfile {
+                    serial: "B2".to_string(),
+                    enabled: false,
+                    actions: vec![action(ACTION_STAY_AWAKE, "")],
+                },
+            ],
+        };
+        assert_eq!(profile_actions(&settings, "A1").map(<[_]>::len), Some(1));
+        assert!(profile_actions(&settings, "B2").is_none());
+        assert!(profile_actions(&settings, "C3").is_none());
+    }
+
+    #[test]
+    fn builds_shell_commands() {
+        assert_eq!(
+            action_shell_commands(&action(ACTION_STAY_AWAKE, "")),
+            vec![vec!["svc", "power", "stayon", "true"]]
+        );
+        let animations = action_shell_commands(&action(ACTION_DISABLE_ANIMATIONS, ""));
+        assert_eq!(animations.len(), 3);
+        assert_eq!(
+            animations[2],
+            vec!["settings", "put", "global", "animator_duration_scale", "0"]
+        );
+        assert_eq!(
+            action_shell_commands(&action(ACTION_SET_LOCALE, "fr-FR")),
+            vec![vec!["setprop", "persist.sys.locale", "fr-FR"]]
+        );
+        assert!(action_shell_commands(&action(ACTION_START_LOGCAT, "*:E")).is_empty());
+    }
+
+    #[test]
+    fn audit_is_capped() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("audit.json");
+        assert!(load_audit(&path).is_empty());
+        let batch: Vec<_> = (0..AUDIT_LIMIT)
+            .map(|index| entry(&format!("S{index}"), ACTION_STAY_AWAKE))
+            .collect();
+        append_audit(&path, &batch, "trace").unwrap();
+        append_audit(&path, &[entry("last", ACTION_SET_LOCALE)], "trace").unwrap();
+        let audit = load_audit(&path);
+        assert_eq!(audit.len(), AUDIT_LIMIT);
+        assert_eq!(audit[0].serial, "S1");
+        assert_eq!(audit[AUDIT_LIMIT - 1].serial, "last");
+    }
+}
@@ -0,0 +1,160 @@
+use std::fs;
+use std::path::PathBuf;
+
+use chrono::{Local, NaiveDate};
+
+use crate::app::config::AppConfig;
+use crate::app::error::AppError;
+use crate::app::i18n::Message;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArtifactKind {
+    Screenshot,
+    Recording,
+    Bugreport,
+    Log,
+    UiDump,
+}
+
+pub fn configured_artifact_dir(config: &AppConfig, kind: ArtifactKind) -> String {
+    let per_type = match kind {
+        ArtifactKind::Screenshot => &config.output.screenshots_dir,
+        ArtifactKind::Recording => &config.output.recordings_dir,
+        ArtifactKind::Bugreport => &config.output.bugreports_dir,
+        ArtifactKind::Log => &config.output.logs_dir,
+        ArtifactKind::UiDump => &config.output.ui_dumps_dir,
+    };
+    if !per_type.trim().is_empty() {
+        return per_type.trim().to_string();
+    }
+    let (primary, secondary) = match kind {
+        ArtifactKind::UiDump => (&config.file_gen_output_path, &config.output_path),
+        _ => (&config.output_path, &config.file_gen_output_path),
+    };
+    if !primary.trim().is_empty() {
+        primary.trim().to_string()
+    } else {
+        secondary.trim().to_string()
+    }
+}
+
+/// Resolve the directory an artifact should be written to.
+///
+/// An explicit `requested` directory wins over the configured per-type directory and is used
+/// as given. When dated subfolders are enabled, a `YYYY-MM-DD` folder is appended to the
+/// configured directory only.
+pub fn resolve_artifact_dir(
+    config: &AppConfig,
+    kind: ArtifactKind,
+    requested: Option<&str>,
+    today: NaiveDate,
+) -> Option<PathBuf> {
+    if let Some(requested) = requested
+        .map(|value| value.trim())
+        .filter(|value| !value.is_empty())
+    {
+        return Some(PathBuf::from(requested));
+    }
+    let base = configured_artifact_dir(config, kind);
+    if base.is_empty() {
+        return None;
+    }
+    let mut path = PathBuf::from(base);
+    if config.output.dated_subfolders {
+        path.push(today.format("%Y-%m-%d").to_string());
+    }
+    Some(path)
+}
+
+pub fn prepare_artifact_dir(
+    config: &AppConfig,
+    kind: ArtifactKind,
+    requested: Option<&str>,
+    trace_id: &str,
+) -> Result<PathBuf, AppError> {
+    let dir = resolve_artifact_dir(config, kind, requested, Local::now().date_naive()).ok_or_else(
+        || {
+            AppError::validation(
+                Message::keyed("required").with("field", "output_dir"),
+                trace_id,
+            )
+        },
+    )?;
+    fs::create_dir_all(&dir).map_err(|err| {
+        AppError::system(
+            Message::keyed("operation_failed")
+                .with("action", "create output dir")
+                .with("detail", &err),
+            trace_id,
+        )
+    })?;
+    Ok(dir)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn base_config() -> AppConfig {
+        AppConfig {
+            output_path: "/tmp/out".to_string(),
+            file_gen_output_path: "/tmp/files".to_string(),
+            ..AppConfig::default()
+        }
+    }
+
+    fn day() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 3, 9).expect("date")
+    }
+
+    #[test]
+    fn configured_artifact_dir_prefers_per_type_dir() {
+        let mut config = base_config();
+        assert_eq!(
+            configured_artifact_dir(&config, ArtifactKind::Screenshot),
+            "/tmp/out"
+        );
+        assert_eq!(
+            configured_artifact_dir(&config, ArtifactKind::UiDump),
+            "/tmp/files"
+        );
+        config.output.screenshots_dir = " /tmp/shots ".to_string();
+        assert_eq!(
+            configured_artifact_dir(&config, ArtifactKind::Screenshot),
+            "/tmp/shots"
+        );
+    }
+
+    #[test]
+    fn resolve_artifact_dir_dates_only_the_configured_dir() {
+        let mut config = base_config();
+        config.output.dated_subfolders = true;
+        let resolved =
+            resolve_artifact_dir(&config, ArtifactKind::Bugreport, None, day()).expect("dir");
+        assert_eq!(resolved, PathBuf::from("/tmp/out").join("2024-03-09"));
+
+        let explicit =
+            resolve_artifact_dir(&config, ArtifactKind::Bugreport, Some("/tmp/custom"), day())
+                .expect("dir");
+        assert_eq!(explicit, PathBuf::from("/tmp/custom"));
+    }
+
+    #[test]
+    fn resolve_artifact_dir_returns_none_when_unconfigured() {
+        let mut config = base_config();
+        config.output_path = String::new();
+        config.file_gen_output_path = String::new();
+        assert!(resolve_artifact_dir(&config, ArtifactKind::Log, Some("  "), day()).is_none());
+    }
+
+    #[test]
+    fn prepare_artifact_dir_creates_directory() {
+        let dir = TempDir::new().expect("tmp");
+        let mut config = base_config();
+        config.output.logs_dir = dir.path().join("logs").to_string_lossy().to_string();
+        let prepared =
+            prepare_artifact_dir(&config, ArtifactKind::Log, None, "trace-output").expect("dir");
+        assert!(prepared.is_dir());
+    }
+}
@@ -0,0 +1,163 @@
+use std::collections::{BTreeMap, HashMap};
+
+use crate::app::models::{PerfFrameDevice, PerfMultiFrame, PerfSnapshot};
+
+pub const DEFAULT_BUCKET_MS: u64 = 1000;
+/// Buckets still waiting for slow devices; older ones are flushed with gaps.
+const MAX_PENDING_BUCKETS: usize = 3;
+
+pub fn clamp_bucket_ms(input: Option<u64>) -> u64 {
+    input.unwrap_or(DEFAULT_BUCKET_MS).clamp(500, 10_000)
+}
+
+/// Aligns per-device perf snapshots into wall-clock buckets and yields one frame per bucket.
+pub struct PerfFrameAggregator {
+    serials: Vec<String>,
+    bucket_ms: u64,
+    pending: BTreeMap<i64, HashMap<String, PerfSnapshot>>,
+    last_emitted: Option<i64>,
+}
+
+impl PerfFrameAggregator {
+    pub fn new(serials: Vec<String>, bucket_ms: u64) -> Self {
+        Self {
+            serials,
+            bucket_ms: bucket_ms.max(1),
+            pending: BTreeMap::new(),
+            last_emitted: None,
+        }
+    }
+
+    fn bucket_of(&self, ts_ms: i64) -> i64 {
+        ts_ms - ts_ms.rem_euclid(self.bucket_ms as i64)
+    }
+
+    /// Add a snapshot; returns frames that are complete or too old to wait for.
+    pub fn push(&mut self, serial: &str, snapshot: PerfSnapshot) -> Vec<PerfMultiFrame> {
+        if !self.serials.iter().any(|value| value == serial) {
+            return Vec::new();
+        }
+        let bucket = self.bucket_of(snapshot.ts_ms);
+        if self.last_emitted.is_some_and(|last| bucket <= last) {
+            return Vec::new();
+        }
+        self.pending
+            .entry(bucket)
+            .or_default()
+            .insert(serial.to_string(), snapshot);
+
+        // A complete bucket releases every older one too, so frames always go out in order.
+        let complete = self
+            .pending
+            .iter()
+            .rev()
+            .find(|(_, devices)| devices.len() == self.serials.len())
+            .map(|(key, _)| *key);
+        let mut frames = Vec::new();
+        while let Some(&oldest) = self.pending.keys().next() {
+            let release = complete.is_some_and(|key| oldest <= key)
+                || self.pending.len() > MAX_PENDING_BUCKETS;
+            if !release {
+                break;
+            }
+            let devices = self.pending.remove(&oldest).unwrap_or_default();
+            self.last_emitted = Some(oldest);
+            frames.push(self.build_frame(oldest, devices));
+        }
+        frames
+    }
+
+    /// Emit everything still pending, e.g. when aggregation stops.
+    pub fn flush(&mut self) -> Vec<PerfMultiFrame> {
+        let pending = std::mem::take(&mut self.pending);
+        pending
+            .into_iter()
+            .map(|(bucket, devices)| {
+                self.last_emitted = Some(bucket);
+                self.build_frame(bucket, devices)
+            })
+            .collect()
+    }
+
+    fn build_frame(
+        &self,
+        bucket: i64,
+        mut devices: HashMap<String, PerfSnapshot>,
+    ) -> PerfMultiFrame {
+        PerfMultiFrame {
+            bucket_ts_ms: bucket,
+            bucket_ms: self.bucket_ms,
+            devices: self
+                .serials
+                .iter()
+                .map(|serial| PerfFrameDevice {
+                    serial: serial.clone(),
+                    snapshot: devices.remove(serial),
+                })
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(ts_ms: i64) -> PerfSnapshot {
+        PerfSnapshot {
+            ts_ms,
+            cpu_total_percent_x100: Some(1000),
+            cpu_cores_percent_x100: Vec::new(),
+            cpu_cores_freq_khz: Vec::new(),
+            mem_total_bytes: None,
+            mem_used_bytes: None,
+            net_rx_bps: None,
+            net_tx_bps: None,
+            battery_level: None,
+            battery_temp_decic: None,
+            display_refresh_hz_x100: None,
+            missed_frames_per_sec_x100: None,
+            cpu_clusters: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn emits_frame_once_all_devices_report() {
+        let mut aggregator = PerfFrameAggregator::new(vec!["a".to_string(), "b".to_string()], 1000);
+        assert!(aggregator.push("a", snapshot(10_100)).is_empty());
+        assert!(aggregator.push("c", snapshot(10_200)).is_empty());
+        let frames = aggregator.push("b", snapshot(10_900));
+        assert_eq!(frames.len(), 1);
+        assert_eq!(frames[0].bucket_ts_ms, 10_000);
+        assert_eq!(frames[0].devices[0].serial, "a");
+        assert!(frames[0]
+            .devices
+            .iter()
+            .all(|device| device.snapshot.is_some()));
+
+        // Late samples for an emitted bucket are dropped.
+        assert!(aggregator.push("a", snapshot(10_950)).is_empty());
+    }
+
+    #[test]
+    fn flushes_stale_buckets_with_gaps_in_order() {
+        let mut aggregator = PerfFrameAggregator::new(vec!["a".to_string(), "b".to_string()], 1000);
+        for ts in [1_000, 2_000, 3_000] {
+            assert!(aggregator.push("a", snapshot(ts)).is_empty());
+        }
+        let frames = aggregator.push("a", snapshot(4_000));
+        assert_eq!(frames.len(), 1);
+        assert_eq!(frames[0].bucket_ts_ms, 1_000);
+        assert!(frames[0].devices[1].snapshot.is_none());
+
+        let frames = aggregator.push("b", snapshot(3_500));
+        assert_eq!(
+            frames
+                .iter()
+                .map(|frame| frame.bucket_ts_ms)
+                .collect::<Vec<_>>(),
+            vec![2_000, 3_000]
+        );
+        assert_eq!(aggregator.flush().len(), 1);
+    }
+}
@@ -1 +1,2 @@
+pub mod aggregate;
 pub mod parse;
@@ -1,4 +1,6 @@
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
+
+use crate::app::models::CpuCluster;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct CpuTotals {
@@ -30,6 +32,9 @@ pub const MARK_NETDEV: &str = "__LBT_PERF_NETDEV__";
 pub const MARK_CPUFREQ: &str = "__LBT_PERF_CPUFREQ__";
 pub const MARK_BATTERY: &str = "__LBT_PERF_BATTERY__";
 
+/// One line per core: `cpu<N>:<related_cpus>:<cpuinfo_max_freq>`.
+pub const CPU_TOPOLOGY_SCRIPT: &str = r#"for cpu in /sys/devices/system/cpu/cpu[0-9]*; do idx=${cpu##*/cpu}; echo "cpu${idx}:$(cat "$cpu/cpufreq/related_cpus" 2>/dev/null):$(cat "$cpu/cpufreq/cpuinfo_max_freq" 2>/dev/null)"; done"#;
+
 pub fn build_perf_script() -> String {
     [
         format!("echo {MARK_PROC_STAT}"),
@@ -317,6 +322,131 @@ pub fn parse_battery_totals(battery: &str) -> Result<BatteryTotals, String> {
     })
 }
 
+/// Group cores by `related_cpus` (cores sharing a frequency domain) and label the clusters by
+/// max frequency: little, big, then prime for the fastest cluster on tri-cluster SoCs.
+pub fn parse_cpu_clusters(output: &str) -> Vec<CpuCluster> {
+    let mut clusters: Vec<(BTreeSet<usize>, Option<u32>)> = Vec::new();
+    for line in output.lines() {
+        let mut parts = line.trim().splitn(3, ':');
+        let (Some(label), Some(related), Some(max_freq)) =
+            (parts.next(), parts.next(), parts.next())
+        else {
+            continue;
+        };
+        let Some(index) = label
+            .strip_prefix("cpu")
+            .and_then(|value| value.parse::<usize>().ok())
+        else {
+            continue;
+        };
+        let mut cores: BTreeSet<usize> = related
+            .split_whitespace()
+            .filter_map(|value| value.parse::<usize>().ok())
+            .collect();
+        // Offline cores or kernels without cpufreq expose no domain; leave them ungrouped.
+        if cores.is_empty() {
+            continue;
+        }
+        cores.insert(index);
+        let max_freq_khz = max_freq.trim().parse::<u32>().ok();
+        match clusters
+            .iter_mut()
+            .find(|(existing, _)| existing.contains(&index))
+        {
+            Some((existing, freq)) => {
+                existing.extend(cores);
+                *freq = (*freq).max(max_freq_khz);
+            }
+            None => clusters.push((cores, max_freq_khz)),
+        }
+    }
+
+    clusters.sort_by_key(|(cores, freq)| (*freq, cores.iter().next().copied()));
+    let count = clusters.len();
+    clusters
+        .into_iter()
+        .enumerate()
+        .map(|(position, (cores, max_freq_khz))| CpuCluster {
+            label: cluster_label(position, count).to_string(),
+            cores: cores.into_iter().collect(),
+            max_freq_khz,
+        })
+        .collect()
+}
+
+fn cluster_label(position: usize, count: usize) -> &'static str {
+    match (count, position) {
+        (1, _) => "all",
+        (_, 0) => "little",
+        (2, _) => "big",
+        (_, p) if p + 1 == count => "prime",
+        (4.., 1) => "mid",
+        _ => "big",
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GfxInfoSummary {
+    pub total_frames: Option<u64>,
+    pub janky_frames: Option<u64>,
+    pub p50_ms: Option<u32>,
+    pub p90_ms: Option<u32>,
+    pub p95_ms: Option<u32>,
+    pub p99_ms: Option<u32>,
+    pub missed_vsync: Option<u64>,
+    pub slow_ui_thread: Option<u64>,
+    pub frame_deadline_missed: Option<u64>,
+}
+
+/// Parse the summary block of `dumpsys gfxinfo <package>`. Newer builds repeat some keys in
+/// per-window sections, so the first occurrence (the app-wide summary) wins.
+pub fn parse_gfxinfo_summary(output: &str) -> GfxInfoSummary {
+    fn leading_number<T: std::str::FromStr>(value: &str) -> Option<T> {
+        let digits: String = value
+            .trim()
+            .chars()
+            .take_while(|ch| ch.is_ascii_digit())
+            .collect();
+        digits.parse().ok()
+    }
+
+    let mut summary = GfxInfoSummary::default();
+    for line in output.lines() {
+        let Some((key, value)) = line.trim().split_once(':') else {
+            continue;
+        };
+        match key.trim() {
+            "Total frames rendered" if summary.total_frames.is_none() => {
+                summary.total_frames = leading_number(value)
+            }
+            "Janky frames" if summary.janky_frames.is_none() => {
+                summary.janky_frames = leading_number(value)
+            }
+            "50th percentile" if summary.p50_ms.is_none() => summary.p50_ms = leading_number(value),
+            "90th percentile" if summary.p90_ms.is_none() => summary.p90_ms = leading_number(value),
+            "95th percentile" if summary.p95_ms.is_none() => summary.p95_ms = leading_number(value),
+            "99th percentile" if summary.p99_ms.is_none() => summary.p99_ms = leading_number(value),
+            "Number Missed Vsync" if summary.missed_vsync.is_none() => {
+                summary.missed_vsync = leading_number(value)
+            }
+            "Number Slow UI thread" if summary.slow_ui_thread.is_none() => {
+                summary.slow_ui_thread = leading_number(value)
+            }
+            "Number Frame deadline missed" if summary.frame_deadline_missed.is_none() => {
+                summary.frame_deadline_missed = leading_number(value)
+            }
+            _ => {}
+        }
+    }
+    summary
+}
+
+pub fn compute_jank_percent_x100(summary: &GfxInfoSummary) -> Option<u16> {
+    let total = summary.total_frames.filter(|total| *total > 0)?;
+    let janky = summary.janky_frames?.min(total);
+    Some((janky * 10_000 / total) as u16)
+}
+
 pub fn parse_cpu_freq_khz(section: &str) -> HashMap<usize, u32> {
     let mut map: HashMap<usize, u32> = HashMap::new();
     for raw in section.lines() {
@@ -478,4 +608,69 @@ Inter-|   Receive                                                |  Transmit
         assert!(sections.get(MARK_CPUFREQ).unwrap().contains("cpu0:"));
         assert!(sections.get(MARK_BATTERY).unwrap().contains("level:"));
     }
+
+    #[test]
+    fn parses_cpu_clusters_with_labels() {
+        let output = "\
+cpu0:0 1 2 3:1804800
+cpu1:0 1 2 3:1804800
+cpu2:0 1 2 3:1804800
+cpu3:0 1 2 3:1804800
+cpu4:4 5 6:2419200
+cpu5:4 5 6:2419200
+cpu6:4 5 6:2419200
+cpu7:7:3187200
+";
+        let clusters = parse_cpu_clusters(output);
+        assert_eq!(clusters.len(), 3);
+        assert_eq!(clusters[0].label, "little");
+        assert_eq!(clusters[0].cores, vec![0, 1, 2, 3]);
+        assert_eq!(clusters[1].label, "big");
+        assert_eq!(clusters[1].max_freq_khz, Some(2_419_200));
+        assert_eq!(clusters[2].label, "prime");
+        assert_eq!(clusters[2].cores, vec![7]);
+
+        let uniform = parse_cpu_clusters("cpu0:0 1:2000000\ncpu1:0 1:2000000\n");
+        assert_eq!(uniform.len(), 1);
+        assert_eq!(uniform[0].label, "all");
+        assert!(parse_cpu_clusters("cpu0::\ncpu1::\n").is_empty());
+    }
+
+    #[test]
+    fn parses_gfxinfo_summary() {
+        let output = "\
+Applications Graphics Acceleration Info:
+Uptime: 1000 Realtime: 1000
+
+** Graphics info for pid 1234 [com.example] **
+
+Stats since: 123ns
+Total frames rendered: 240
+Janky frames: 18 (7.50%)
+Janky frames (legacy): 30 (12.50%)
+50th percentile: 7ms
+90th percentile: 13ms
+95th percentile: 19ms
+99th percentile: 42ms
+Number Missed Vsync: 3
+Number High input latency: 0
+Number Slow UI thread: 9
+Number Slow bitmap uploads: 0
+Number Slow issue draw commands: 2
+Number Frame deadline missed: 12
+
+Window: com.example/.MainActivity
+Total frames rendered: 200
+Janky frames: 10 (5.00%)
+";
+        let summary = parse_gfxinfo_summary(output);
+        assert_eq!(summary.total_frames, Some(240));
+        assert_eq!(summary.janky_frames, Some(18));
+        assert_eq!(summary.p50_ms, Some(7));
+        assert_eq!(summary.p99_ms, Some(42));
+        assert_eq!(summary.slow_ui_thread, Some(9));
+        assert_eq!(summary.frame_deadline_missed, Some(12));
+        assert_eq!(compute_jank_percent_x100(&summary), Some(750));
+        assert_eq!(compute_jank_percent_x100(&GfxInfoSummary::default()), None);
+    }
 }
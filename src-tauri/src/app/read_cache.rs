@@ -0,0 +1,206 @@
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+use tracing::warn;
+
+/// `get_app_basic_info` results per package.
+pub const CACHE_SCOPE_APP_INFO: &str = "app_info";
+/// `list_device_files` listings per directory.
+pub const CACHE_SCOPE_DEVICE_FILES: &str = "device_files";
+/// The versionCode `get_app_icon` keys its icon cache with.
+pub const CACHE_SCOPE_APP_ICON: &str = "app_icon";
+
+/// Every cached scope and how long its entries stay fresh.
+pub const CACHE_SCOPES: [(&str, Duration); 3] = [
+    (CACHE_SCOPE_APP_INFO, Duration::from_secs(30)),
+    (CACHE_SCOPE_DEVICE_FILES, Duration::from_secs(10)),
+    (CACHE_SCOPE_APP_ICON, Duration::from_secs(300)),
+];
+
+const MAX_CACHE_ENTRIES: usize = 2_000;
+
+pub fn scope_ttl(scope: &str) -> Option<Duration> {
+    CACHE_SCOPES
+        .iter()
+        .find(|(name, _)| *name == scope)
+        .map(|(_, ttl)| *ttl)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct CacheKey {
+    scope: String,
+    serial: String,
+    key: String,
+}
+
+#[derive(Debug)]
+struct CacheEntry {
+    value: Value,
+    stored_at: Instant,
+}
+
+/// Results of idempotent device reads, kept for their scope's TTL so re-rendered panels do
+/// not re-run the same adb commands.
+#[derive(Debug, Default)]
+pub struct ReadCache {
+    entries: HashMap<CacheKey, CacheEntry>,
+}
+
+impl ReadCache {
+    pub fn get(&self, scope: &str, serial: &str, key: &str, now: Instant) -> Option<&Value> {
+        let ttl = scope_ttl(scope)?;
+        let entry = self.entries.get(&CacheKey {
+            scope: scope.to_string(),
+            serial: serial.to_string(),
+            key: key.to_string(),
+        })?;
+        (now.saturating_duration_since(entry.stored_at) < ttl).then_some(&entry.value)
+    }
+
+    pub fn put(&mut self, scope: &str, serial: &str, key: &str, value: Value, now: Instant) {
+        if self.entries.len() >= MAX_CACHE_ENTRIES {
+            self.entries.retain(|key, entry| {
+                scope_ttl(&key.scope)
+                    .is_some_and(|ttl| now.saturating_duration_since(entry.stored_at) < ttl)
+            });
+        }
+        if self.entries.len() >= MAX_CACHE_ENTRIES {
+            if let Some(oldest) = self
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.stored_at)
+                .map(|(key, _)| key.clone())
+            {
+                self.entries.remove(&oldest);
+            }
+        }
+        self.entries.insert(
+            CacheKey {
+                scope: scope.to_string(),
+                serial: serial.to_string(),
+                key: key.to_string(),
+            },
+            CacheEntry {
+                value,
+                stored_at: now,
+            },
+        );
+    }
+
+    /// Drop entries matching `scope` and `serial`; `None` matches everything. Returns how many
+    /// were removed.
+    pub fn bust(&mut self, scope: Option<&str>, serial: Option<&str>) -> usize {
+        let before = self.entries.len();
+        self.entries.retain(|key, _| {
+            !(scope.is_none_or(|scope| key.scope == scope)
+                && serial.is_none_or(|serial| key.serial == serial))
+        });
+        before - self.entries.len()
+    }
+}
+
+fn store() -> &'static Mutex<ReadCache> {
+    static STORE: OnceLock<Mutex<ReadCache>> = OnceLock::new();
+    STORE.get_or_init(|| Mutex::new(ReadCache::default()))
+}
+
+pub fn cached_read<T: DeserializeOwned>(
+    scope: &str,
+    serial: &str,
+    key: &str,
+    trace_id: &str,
+) -> Option<T> {
+    let Ok(guard) = store().lock() else {
+        warn!(trace_id = %trace_id, "read cache poisoned");
+        return None;
+    };
+    let value = guard.get(scope, serial, key, Instant::now())?;
+    match serde_json::from_value(value.clone()) {
+        Ok(value) => Some(value),
+        Err(err) => {
+            warn!(trace_id = %trace_id, scope, error = %err, "discarding unreadable cache entry");
+            None
+        }
+    }
+}
+
+pub fn store_read<T: Serialize>(scope: &str, serial: &str, key: &str, value: &T, trace_id: &str) {
+    let value = match serde_json::to_value(value) {
+        Ok(value) => value,
+        Err(err) => {
+            warn!(trace_id = %trace_id, scope, error = %err, "failed to cache read result");
+            return;
+        }
+    };
+    match store().lock() {
+        Ok(mut guard) => guard.put(scope, serial, key, value, Instant::now()),
+        Err(_) => warn!(trace_id = %trace_id, "read cache poisoned; result not cached"),
+    }
+}
+
+pub fn bust_read_cache(scope: Option<&str>, serial: Option<&str>) -> usize {
+    store()
+        .lock()
+        .map(|mut guard| guard.bust(scope, serial))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn expires_entries_per_scope() {
+        let mut cache = ReadCache::default();
+        let start = Instant::now();
+        cache.put(CACHE_SCOPE_DEVICE_FILES, "a", "/sdcard", json!([1]), start);
+        cache.put(
+            CACHE_SCOPE_APP_INFO,
+            "a",
+            "com.example",
+            json!({"v": 1}),
+            start,
+        );
+
+        let later = start + Duration::from_secs(15);
+        assert_eq!(
+            cache.get(CACHE_SCOPE_APP_INFO, "a", "com.example", later),
+            Some(&json!({"v": 1}))
+        );
+        assert_eq!(
+            cache.get(CACHE_SCOPE_DEVICE_FILES, "a", "/sdcard", later),
+            None
+        );
+        assert_eq!(
+            cache.get(CACHE_SCOPE_APP_INFO, "b", "com.example", later),
+            None
+        );
+        assert_eq!(cache.get("unknown", "a", "/sdcard", start), None);
+    }
+
+    #[test]
+    fn busts_by_scope_and_serial() {
+        let mut cache = ReadCache::default();
+        let now = Instant::now();
+        for serial in ["a", "b"] {
+            cache.put(CACHE_SCOPE_DEVICE_FILES, serial, "/", json!([]), now);
+            cache.put(
+                CACHE_SCOPE_APP_ICON,
+                serial,
+                "com.example",
+                json!("42"),
+                now,
+            );
+        }
+        assert_eq!(cache.bust(Some(CACHE_SCOPE_DEVICE_FILES), Some("a")), 1);
+        assert!(cache.get(CACHE_SCOPE_DEVICE_FILES, "b", "/", now).is_some());
+        assert_eq!(cache.bust(None, Some("b")), 2);
+        assert_eq!(cache.bust(None, None), 1);
+        assert_eq!(cache.bust(None, None), 0);
+    }
+}
@@ -0,0 +1,339 @@
+use crate::app::models::{DeviceReadinessResult, ReadinessCheck, ReadinessThresholds};
+
+pub const READINESS_STATUS_PASS: &str = "pass";
+pub const READINESS_STATUS_FAIL: &str = "fail";
+pub const READINESS_STATUS_UNKNOWN: &str = "unknown";
+
+/// Raw per-device readings; `None` means the value could not be read from the device.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReadinessSignals {
+    pub screen_on: Option<bool>,
+    pub keyguard_locked: Option<bool>,
+    pub battery_level: Option<u8>,
+    pub free_storage_kb: Option<u64>,
+    pub anr_windows: Option<usize>,
+    pub wifi_connected: Option<bool>,
+    pub device_epoch_secs: Option<i64>,
+}
+
+pub fn normalize_readiness_thresholds(input: Option<ReadinessThresholds>) -> ReadinessThresholds {
+    let mut thresholds = input.unwrap_or_default();
+    thresholds.min_battery_percent = thresholds.min_battery_percent.min(100);
+    thresholds.max_clock_skew_secs = thresholds.max_clock_skew_secs.clamp(1, 3600);
+    thresholds
+}
+
+fn flag_value(output: &str, key: &str) -> Option<bool> {
+    let mut rest = output;
+    let mut found = None;
+    while let Some(index) = rest.find(key) {
+        let value = &rest[index + key.len()..];
+        if value.starts_with("true") {
+            return Some(true);
+        }
+        if value.starts_with("false") {
+            found = Some(false);
+        }
+        rest = &rest[index + key.len()..];
+    }
+    found
+}
+
+/// Read keyguard state from `dumpsys window`. Different Android releases expose it under
+/// different field names, so any `true` wins over `false`.
+pub fn parse_keyguard_locked(output: &str) -> Option<bool> {
+    let mut result = None;
+    for key in [
+        "mShowingLockscreen=",
+        "mDreamingLockscreen=",
+        "isStatusBarKeyguard=",
+        "mKeyguardShowing=",
+        "KeyguardShowing=",
+    ] {
+        match flag_value(output, key) {
+            Some(true) => return Some(true),
+            Some(false) => result = Some(false),
+            None => {}
+        }
+    }
+    result
+}
+
+pub fn parse_screen_on(output: &str) -> Option<bool> {
+    for line in output.lines() {
+        let trimmed = line.trim();
+        if let Some(value) = trimmed.strip_prefix("mWakefulness=") {
+            return Some(value.trim() == "Awake");
+        }
+        if let Some(value) = trimmed.strip_prefix("Display Power: state=") {
+            return Some(value.trim() == "ON");
+        }
+    }
+    None
+}
+
+pub fn count_anr_windows(window_dump: &str) -> usize {
+    window_dump
+        .lines()
+        .filter(|line| line.trim_start().starts_with("Window #"))
+        .filter(|line| line.contains("Application Not Responding"))
+        .count()
+}
+
+/// Parse `cmd wifi status` (Android 11+) or the legacy `dumpsys wifi` output.
+pub fn parse_wifi_connected(output: &str) -> Option<bool> {
+    let lower = output.to_lowercase();
+    if lower.contains("wifi is connected to") {
+        return Some(true);
+    }
+    if lower.contains("wifi is not connected") || lower.contains("wifi is disabled") {
+        return Some(false);
+    }
+    for line in output.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("mNetworkInfo") {
+            return Some(trimmed.contains("state: CONNECTED/CONNECTED"));
+        }
+    }
+    if lower.contains("supplicant state: completed") {
+        return Some(true);
+    }
+    None
+}
+
+pub fn parse_epoch_secs(output: &str) -> Option<i64> {
+    output
+        .lines()
+        .find_map(|line| line.trim().parse::<i64>().ok())
+}
+
+fn check(name: &str, status: &str, detail: String) -> ReadinessCheck {
+    ReadinessCheck {
+        name: name.to_string(),
+        status: status.to_string(),
+        detail,
+    }
+}
+
+fn status_for(passed: bool) -> &'static str {
+    if passed {
+        READINESS_STATUS_PASS
+    } else {
+        READINESS_STATUS_FAIL
+    }
+}
+
+pub fn evaluate_readiness(
+    serial: &str,
+    signals: &ReadinessSignals,
+    thresholds: &ReadinessThresholds,
+    host_epoch_secs: i64,
+) -> DeviceReadinessResult {
+    let unknown = |name: &str, what: &str| {
+        check(
+            name,
+            READINESS_STATUS_UNKNOWN,
+            format!("Unable to read {what}"),
+        )
+    };
+    let mut checks = Vec::new();
+
+    checks.push(match (signals.screen_on, signals.keyguard_locked) {
+        (Some(false), _) => check(
+            "screen_unlocked",
+            READINESS_STATUS_FAIL,
+            "Screen is off".to_string(),
+        ),
+        (_, Some(true)) => check(
+            "screen_unlocked",
+            READINESS_STATUS_FAIL,
+            "Keyguard is showing".to_string(),
+        ),
+        (Some(true), Some(false)) => check(
+            "screen_unlocked",
+            READINESS_STATUS_PASS,
+            "Screen on and unlocked".to_string(),
+        ),
+        _ => unknown("screen_unlocked", "screen or keyguard state"),
+    });
+
+    checks.push(match signals.battery_level {
+        Some(level) => check(
+            "battery",
+            status_for(level >= thresholds.min_battery_percent),
+            format!("{level}% (min {}%)", thresholds.min_battery_percent),
+        ),
+        None => unknown("battery", "battery level"),
+    });
+
+    checks.push(match signals.free_storage_kb {
+        Some(kb) => {
+            let free_mb = kb / 1024;
+            check(
+                "storage",
+                status_for(free_mb >= thresholds.min_free_storage_mb),
+                format!(
+                    "{free_mb} MB free (min {} MB)",
+                    thresholds.min_free_storage_mb
+                ),
+            )
+        }
+        None => unknown("storage", "free storage"),
+    });
+
+    checks.push(match signals.anr_windows {
+        Some(0) => check(
+            "no_pending_anr",
+            READINESS_STATUS_PASS,
+            "No ANR dialogs".to_string(),
+        ),
+        Some(count) => check(
+            "no_pending_anr",
+            READINESS_STATUS_FAIL,
+            format!("{count} ANR dialog(s) showing"),
+        ),
+        None => unknown("no_pending_anr", "window state"),
+    });
+
+    checks.push(match signals.wifi_connected {
+        Some(true) => check(
+            "wifi_connected",
+            READINESS_STATUS_PASS,
+            "Connected".to_string(),
+        ),
+        Some(false) => check(
+            "wifi_connected",
+            READINESS_STATUS_FAIL,
+            "Not connected".to_string(),
+        ),
+        None => unknown("wifi_connected", "Wi-Fi state"),
+    });
+
+    checks.push(match signals.device_epoch_secs {
+        Some(device_secs) => {
+            let skew = (device_secs - host_epoch_secs).abs();
+            check(
+                "clock_in_sync",
+                status_for(skew <= thresholds.max_clock_skew_secs),
+                format!("{skew}s skew (max {}s)", thresholds.max_clock_skew_secs),
+            )
+        }
+        None => unknown("clock_in_sync", "device time"),
+    });
+
+    let ready = checks
+        .iter()
+        .all(|item| item.status == READINESS_STATUS_PASS);
+    DeviceReadinessResult {
+        serial: serial.to_string(),
+        ready,
+        checks,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ready_signals() -> ReadinessSignals {
+        ReadinessSignals {
+            screen_on: Some(true),
+            keyguard_locked: Some(false),
+            battery_level: Some(80),
+            free_storage_kb: Some(4 * 1024 * 1024),
+            anr_windows: Some(0),
+            wifi_connected: Some(true),
+            device_epoch_secs: Some(1_700_000_010),
+        }
+    }
+
+    #[test]
+    fn parse_keyguard_locked_prefers_true() {
+        let output = "mDreamingLockscreen=false\n  mShowingLockscreen=true mShowingDream=false\n";
+        assert_eq!(parse_keyguard_locked(output), Some(true));
+        assert_eq!(
+            parse_keyguard_locked("isStatusBarKeyguard=false mShowingLockscreen=false"),
+            Some(false)
+        );
+        assert_eq!(parse_keyguard_locked("nothing here"), None);
+    }
+
+    #[test]
+    fn parse_screen_on_reads_wakefulness() {
+        assert_eq!(parse_screen_on("  mWakefulness=Awake\n"), Some(true));
+        assert_eq!(parse_screen_on("  mWakefulness=Asleep\n"), Some(false));
+        assert_eq!(parse_screen_on(""), None);
+    }
+
+    #[test]
+    fn count_anr_windows_matches_dialog_titles() {
+        let output = "  Window #0 Window{a1 u0 NavigationBar0}:\n  Window #1 Window{b2 u0 Application Not Responding: com.example}:\n  mCurrentFocus=Window{b2 u0 Application Not Responding: com.example}\n";
+        assert_eq!(count_anr_windows(output), 1);
+    }
+
+    #[test]
+    fn parse_wifi_connected_handles_cmd_and_dumpsys() {
+        assert_eq!(
+            parse_wifi_connected("Wifi is enabled\nWifi is connected to \"Lab\"\n"),
+            Some(true)
+        );
+        assert_eq!(parse_wifi_connected("Wifi is disabled\n"), Some(false));
+        assert_eq!(
+            parse_wifi_connected(
+                "mNetworkInfo [type: WIFI[], state: CONNECTED/CONNECTED, reason: (unspecified)]"
+            ),
+            Some(true)
+        );
+        assert_eq!(parse_wifi_connected("unrelated"), None);
+    }
+
+    #[test]
+    fn evaluate_readiness_passes_when_all_checks_pass() {
+        let thresholds = ReadinessThresholds::default();
+        let result = evaluate_readiness("serial-1", &ready_signals(), &thresholds, 1_700_000_000);
+        assert!(result.ready);
+        assert_eq!(result.checks.len(), 6);
+        assert!(result
+            .checks
+            .iter()
+            .all(|item| item.status == READINESS_STATUS_PASS));
+    }
+
+    #[test]
+    fn evaluate_readiness_reports_failures_and_unknowns() {
+        let signals = ReadinessSignals {
+            keyguard_locked: Some(true),
+            battery_level: Some(10),
+            wifi_connected: None,
+            device_epoch_secs: Some(1_700_001_000),
+            ..ready_signals()
+        };
+        let thresholds = ReadinessThresholds::default();
+        let result = evaluate_readiness("serial-1", &signals, &thresholds, 1_700_000_000);
+        assert!(!result.ready);
+        let status = |name: &str| {
+            result
+                .checks
+                .iter()
+                .find(|item| item.name == name)
+                .map(|item| item.status.clone())
+                .unwrap_or_default()
+        };
+        assert_eq!(status("screen_unlocked"), READINESS_STATUS_FAIL);
+        assert_eq!(status("battery"), READINESS_STATUS_FAIL);
+        assert_eq!(status("storage"), READINESS_STATUS_PASS);
+        assert_eq!(status("wifi_connected"), READINESS_STATUS_UNKNOWN);
+        assert_eq!(status("clock_in_sync"), READINESS_STATUS_FAIL);
+    }
+
+    #[test]
+    fn normalize_readiness_thresholds_clamps_values() {
+        let thresholds = normalize_readiness_thresholds(Some(ReadinessThresholds {
+            min_battery_percent: 250,
+            min_free_storage_mb: 10,
+            max_clock_skew_secs: 0,
+        }));
+        assert_eq!(thresholds.min_battery_percent, 100);
+        assert_eq!(thresholds.max_clock_skew_secs, 1);
+    }
+}
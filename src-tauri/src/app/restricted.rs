@@ -0,0 +1,265 @@
+use pbkdf2::pbkdf2_hmac;
+use sha2::{Digest, Sha256};
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::app::config::{load_config, AppConfig, RestrictedModeSettings};
+use crate::app::error::AppError;
+use crate::app::i18n::Message;
+
+const HASH_SCHEME: &str = "pbkdf2-sha256";
+/// Salted single-round SHA-256 written by earlier versions; still accepted so an existing
+/// lock keeps working, but never written again.
+const LEGACY_HASH_SCHEME: &str = "sha256";
+const PBKDF2_ITERATIONS: u32 = 600_000;
+const MIN_PASSWORD_LEN: usize = 8;
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+fn legacy_digest_hex(salt: &str, password: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(salt.as_bytes());
+    hasher.update(b":");
+    hasher.update(password.as_bytes());
+    to_hex(&hasher.finalize())
+}
+
+fn pbkdf2_hex(salt: &str, password: &str, iterations: u32) -> String {
+    let mut key = [0u8; 32];
+    pbkdf2_hmac::<Sha256>(password.as_bytes(), salt.as_bytes(), iterations, &mut key);
+    to_hex(&key)
+}
+
+fn hash_with_iterations(password: &str, iterations: u32) -> String {
+    let salt = Uuid::new_v4().simple().to_string();
+    format!(
+        "{HASH_SCHEME}${iterations}${salt}${}",
+        pbkdf2_hex(&salt, password, iterations)
+    )
+}
+
+/// PBKDF2-HMAC-SHA256 as `pbkdf2-sha256$<iterations>$<salt>$<hex>`.
+pub fn hash_restricted_password(password: &str) -> String {
+    hash_with_iterations(password, PBKDF2_ITERATIONS)
+}
+
+pub fn verify_restricted_password(hash: &str, password: &str) -> bool {
+    let parts: Vec<&str> = hash.split('$').collect();
+    match parts.as_slice() {
+        [HASH_SCHEME, iterations, salt, expected] => iterations
+            .parse::<u32>()
+            .ok()
+            .filter(|iterations| *iterations > 0)
+            .is_some_and(|iterations| pbkdf2_hex(salt, password, iterations) == *expected),
+        [LEGACY_HASH_SCHEME, salt, expected] => legacy_digest_hex(salt, password) == *expected,
+        _ => false,
+    }
+}
+
+/// Apply a restricted mode change. Leaving restricted mode, or changing its password while it
+/// is on, requires the current password when one is set.
+pub fn apply_restricted_mode_change(
+    current: &RestrictedModeSettings,
+    enabled: bool,
+    password: Option<&str>,
+    new_password: Option<&str>,
+) -> Result<RestrictedModeSettings, String> {
+    if current.enabled && !current.password_hash.is_empty() {
+        let verified = password
+            .is_some_and(|value| verify_restricted_password(&current.password_hash, value.trim()));
+        if !verified {
+            return Err("Incorrect restricted mode password".to_string());
+        }
+    }
+    if !enabled {
+        return Ok(RestrictedModeSettings::default());
+    }
+
+    let password_hash = match new_password.map(str::trim) {
+        Some("") => String::new(),
+        Some(value) if value.chars().count() < MIN_PASSWORD_LEN => {
+            return Err(format!(
+                "Restricted mode password must be at least {MIN_PASSWORD_LEN} characters"
+            ));
+        }
+        Some(value) => hash_restricted_password(value),
+        None => current.password_hash.clone(),
+    };
+    Ok(RestrictedModeSettings {
+        enabled: true,
+        password_hash,
+    })
+}
+
+/// `save_app_config` must not be a way around the lock, so the stored settings always win.
+/// Hooks, custom tools, adb (default and per-device), ffmpeg, curl and gcloud paths all choose
+/// host executables, so they are frozen while the lock is on as well.
+pub fn preserve_restricted_mode(current: &AppConfig, mut incoming: AppConfig) -> AppConfig {
+    incoming.restricted_mode = current.restricted_mode.clone();
+    if current.restricted_mode.enabled {
+        incoming.hooks = current.hooks.clone();
+        incoming.custom_tools = current.custom_tools.clone();
+        incoming.adb.command_path = current.adb.command_path.clone();
+        incoming.adb.path_overrides = current.adb.path_overrides.clone();
+        incoming.screen_record.ffmpeg_path = current.screen_record.ffmpeg_path.clone();
+        incoming.upload.curl_path = current.upload.curl_path.clone();
+        incoming.device_farm.gcloud_path = current.device_farm.gcloud_path.clone();
+    }
+    incoming
+}
+
+/// The config as handed to the frontend: the password hash never leaves the backend.
+pub fn redact_restricted_mode(mut config: AppConfig) -> AppConfig {
+    config.restricted_mode.password_hash.clear();
+    config
+}
+
+/// Reject a destructive action while restricted mode is on.
+pub fn ensure_action_allowed(action: &str, trace_id: &str) -> Result<(), AppError> {
+    let config = load_config(trace_id)?;
+    if config.restricted_mode.enabled {
+        warn!(trace_id = %trace_id, action = %action, "blocked by restricted mode");
+        return Err(AppError::restricted(
+            Message::keyed("restricted_action").with("action", action),
+            trace_id,
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::config::{AdbPathOverride, HookScript};
+
+    #[test]
+    fn restricted_password_hash_round_trips() {
+        let hash = hash_with_iterations("lab-1234", 1_000);
+        assert!(hash.starts_with("pbkdf2-sha256$1000$"));
+        assert!(!hash.contains("lab-1234"));
+        assert!(verify_restricted_password(&hash, "lab-1234"));
+        assert!(!verify_restricted_password(&hash, "lab-12345"));
+        assert!(!verify_restricted_password("plain", "plain"));
+        assert!(!verify_restricted_password(
+            &hash.replacen("$1000$", "$0$", 1),
+            "lab-1234"
+        ));
+
+        let legacy = format!("sha256$salt${}", legacy_digest_hex("salt", "lab-1234"));
+        assert!(verify_restricted_password(&legacy, "lab-1234"));
+        assert!(!verify_restricted_password(&legacy, "lab-4321"));
+    }
+
+    #[test]
+    fn apply_restricted_mode_change_requires_password_to_leave() {
+        let locked = apply_restricted_mode_change(
+            &RestrictedModeSettings::default(),
+            true,
+            None,
+            Some("secret-pass"),
+        )
+        .expect("enable");
+        assert!(locked.enabled);
+
+        assert!(apply_restricted_mode_change(&locked, false, None, None).is_err());
+        assert!(apply_restricted_mode_change(&locked, false, Some("wrong"), None).is_err());
+        let unlocked = apply_restricted_mode_change(&locked, false, Some("secret-pass"), None)
+            .expect("disable");
+        assert_eq!(unlocked, RestrictedModeSettings::default());
+
+        assert!(apply_restricted_mode_change(
+            &RestrictedModeSettings::default(),
+            true,
+            None,
+            Some("short")
+        )
+        .is_err());
+    }
+
+    fn locked_config() -> AppConfig {
+        AppConfig {
+            restricted_mode: RestrictedModeSettings {
+                enabled: true,
+                password_hash: String::new(),
+            },
+            ..AppConfig::default()
+        }
+    }
+
+    #[test]
+    fn preserve_restricted_mode_keeps_adb_command_path() {
+        let mut incoming = AppConfig::default();
+        incoming.adb.command_path = "/tmp/fake-adb".to_string();
+        let saved = preserve_restricted_mode(&locked_config(), incoming);
+        assert_eq!(
+            saved.adb.command_path,
+            AppConfig::default().adb.command_path
+        );
+    }
+
+    #[test]
+    fn preserve_restricted_mode_keeps_ffmpeg_path() {
+        let mut incoming = AppConfig::default();
+        incoming.screen_record.ffmpeg_path = "/tmp/fake-ffmpeg".to_string();
+        let saved = preserve_restricted_mode(&locked_config(), incoming);
+        assert!(saved.screen_record.ffmpeg_path.is_empty());
+    }
+
+    #[test]
+    fn preserve_restricted_mode_keeps_curl_path() {
+        let mut incoming = AppConfig::default();
+        incoming.upload.curl_path = "/tmp/fake-curl".to_string();
+        let saved = preserve_restricted_mode(&locked_config(), incoming);
+        assert!(saved.upload.curl_path.is_empty());
+    }
+
+    #[test]
+    fn preserve_restricted_mode_keeps_gcloud_path() {
+        let mut incoming = AppConfig::default();
+        incoming.device_farm.gcloud_path = "/tmp/fake-gcloud".to_string();
+        let saved = preserve_restricted_mode(&locked_config(), incoming);
+        assert!(saved.device_farm.gcloud_path.is_empty());
+
+        // Unlocked configs take the incoming path.
+        let mut incoming = AppConfig::default();
+        incoming.device_farm.gcloud_path = "/opt/gcloud".to_string();
+        let saved = preserve_restricted_mode(&AppConfig::default(), incoming);
+        assert_eq!(saved.device_farm.gcloud_path, "/opt/gcloud");
+    }
+
+    #[test]
+    fn preserve_restricted_mode_ignores_incoming_settings() {
+        let current = AppConfig {
+            restricted_mode: RestrictedModeSettings {
+                enabled: true,
+                password_hash: String::new(),
+            },
+            ..AppConfig::default()
+        };
+        let mut incoming = AppConfig::default();
+        incoming.hooks.scripts.push(HookScript {
+            name: "inject".to_string(),
+            executable: "/tmp/inject.sh".to_string(),
+            events: Vec::new(),
+            timeout_secs: 30,
+            enabled: true,
+        });
+        incoming.adb.path_overrides.push(AdbPathOverride {
+            serial: "R58M".to_string(),
+            transport: String::new(),
+            command_path: "/tmp/fake-adb".to_string(),
+        });
+        let saved = preserve_restricted_mode(&current, incoming);
+        assert!(saved.restricted_mode.enabled);
+        assert!(saved.hooks.scripts.is_empty());
+        assert!(saved.adb.path_overrides.is_empty());
+
+        let mut hashed = current.clone();
+        hashed.restricted_mode.password_hash = "sha256$salt$abc".to_string();
+        let redacted = redact_restricted_mode(hashed);
+        assert!(redacted.restricted_mode.enabled);
+        assert!(redacted.restricted_mode.password_hash.is_empty());
+    }
+}
@@ -1,5 +1,8 @@
 use std::collections::HashMap;
 use std::sync::{Arc, Condvar, Mutex};
+use std::time::Instant;
+
+use crate::app::metrics::record_scheduler_wait;
 
 pub struct GlobalSemaphore {
     limit: usize,
@@ -17,16 +20,26 @@ impl GlobalSemaphore {
     }
 
     pub fn acquire(self: &Arc<Self>) -> GlobalPermit {
+        let started = Instant::now();
         let mut used = self.used.lock().expect("semaphore lock poisoned");
         while *used >= self.limit {
             used = self.cv.wait(used).expect("semaphore lock poisoned");
         }
         *used += 1;
+        record_scheduler_wait(started.elapsed());
         GlobalPermit {
             semaphore: Arc::clone(self),
         }
     }
 
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+
+    pub fn in_use(&self) -> usize {
+        self.used.lock().map(|used| *used).unwrap_or_default()
+    }
+
     fn release(&self) {
         let mut used = self.used.lock().expect("semaphore lock poisoned");
         *used = used.saturating_sub(1);
@@ -61,6 +74,14 @@ impl TaskScheduler {
         self.global.acquire()
     }
 
+    pub fn global_limit(&self) -> usize {
+        self.global.limit()
+    }
+
+    pub fn global_in_use(&self) -> usize {
+        self.global.in_use()
+    }
+
     pub fn device_lock(&self, serial: &str) -> Arc<Mutex<()>> {
         let mut guard = self.device_locks.lock().expect("device locks poisoned");
         guard
@@ -0,0 +1,229 @@
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+
+use png::{BitDepth, ColorType, Decoder, Encoder, Transformations};
+
+/// Per-channel difference at or below this counts as equal, absorbing dithering and
+/// anti-aliasing noise between otherwise identical renders.
+pub const PIXEL_TOLERANCE: u8 = 8;
+pub const DEFAULT_SIMILARITY_THRESHOLD: f64 = 0.99;
+
+const DIFF_COLOR: [u8; 4] = [255, 0, 64, 255];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RgbaImage {
+    pub width: u32,
+    pub height: u32,
+    pub pixels: Vec<u8>,
+}
+
+impl RgbaImage {
+    pub fn pixel(&self, x: u32, y: u32) -> Option<&[u8]> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        let offset = ((y as usize) * (self.width as usize) + x as usize) * 4;
+        self.pixels.get(offset..offset + 4)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImageDiff {
+    pub width: u32,
+    pub height: u32,
+    pub diff_pixels: u64,
+    pub total_pixels: u64,
+    pub similarity: f64,
+    pub size_mismatch: bool,
+    pub diff_image: RgbaImage,
+}
+
+/// Threshold is the minimum similarity (0.0-1.0) for a comparison to pass.
+pub fn normalize_similarity_threshold(threshold: Option<f64>) -> Result<f64, String> {
+    let threshold = threshold.unwrap_or(DEFAULT_SIMILARITY_THRESHOLD);
+    if !(0.0..=1.0).contains(&threshold) {
+        return Err("threshold must be between 0.0 and 1.0".to_string());
+    }
+    Ok(threshold)
+}
+
+pub fn read_png_rgba(path: &Path) -> Result<RgbaImage, String> {
+    let file =
+        File::open(path).map_err(|err| format!("Failed to open {}: {err}", path.display()))?;
+    let mut decoder = Decoder::new(file);
+    decoder.set_transformations(Transformations::normalize_to_color8());
+    let mut reader = decoder
+        .read_info()
+        .map_err(|err| format!("Failed to read PNG {}: {err}", path.display()))?;
+    let mut buffer = vec![0; reader.output_buffer_size()];
+    let info = reader
+        .next_frame(&mut buffer)
+        .map_err(|err| format!("Failed to decode PNG {}: {err}", path.display()))?;
+    buffer.truncate(info.buffer_size());
+    let pixels = match info.color_type {
+        ColorType::Rgba => buffer,
+        ColorType::Rgb => buffer
+            .chunks_exact(3)
+            .flat_map(|px| [px[0], px[1], px[2], 255])
+            .collect(),
+        ColorType::Grayscale => buffer.iter().flat_map(|&v| [v, v, v, 255]).collect(),
+        ColorType::GrayscaleAlpha => buffer
+            .chunks_exact(2)
+            .flat_map(|px| [px[0], px[0], px[0], px[1]])
+            .collect(),
+        ColorType::Indexed => {
+            return Err(format!("Unsupported PNG color type in {}", path.display()))
+        }
+    };
+    Ok(RgbaImage {
+        width: info.width,
+        height: info.height,
+        pixels,
+    })
+}
+
+pub fn write_png_rgba(path: &Path, image: &RgbaImage) -> Result<(), String> {
+    let file =
+        File::create(path).map_err(|err| format!("Failed to create {}: {err}", path.display()))?;
+    let mut encoder = Encoder::new(BufWriter::new(file), image.width, image.height);
+    encoder.set_color(ColorType::Rgba);
+    encoder.set_depth(BitDepth::Eight);
+    let mut writer = encoder
+        .write_header()
+        .map_err(|err| format!("Failed to write PNG {}: {err}", path.display()))?;
+    writer
+        .write_image_data(&image.pixels)
+        .map_err(|err| format!("Failed to write PNG {}: {err}", path.display()))
+}
+
+fn pixels_match(a: &[u8], b: &[u8]) -> bool {
+    a.iter()
+        .zip(b)
+        .all(|(x, y)| x.abs_diff(*y) <= PIXEL_TOLERANCE)
+}
+
+/// Compares two images over the union of their sizes; pixels present in only one image
+/// count as different. The diff image shows differing pixels in red over a faded copy
+/// of `a`.
+pub fn diff_images(a: &RgbaImage, b: &RgbaImage) -> ImageDiff {
+    let width = a.width.max(b.width);
+    let height = a.height.max(b.height);
+    let mut pixels = Vec::with_capacity(width as usize * height as usize * 4);
+    let mut diff_pixels = 0u64;
+    for y in 0..height {
+        for x in 0..width {
+            match (a.pixel(x, y), b.pixel(x, y)) {
+                (Some(left), Some(right)) if pixels_match(left, right) => {
+                    let luma = (u32::from(left[0]) * 299
+                        + u32::from(left[1]) * 587
+                        + u32::from(left[2]) * 114)
+                        / 1000;
+                    let faded = (170 + luma / 3) as u8;
+                    pixels.extend_from_slice(&[faded, faded, faded, 255]);
+                }
+                _ => {
+                    diff_pixels += 1;
+                    pixels.extend_from_slice(&DIFF_COLOR);
+                }
+            }
+        }
+    }
+    let total_pixels = u64::from(width) * u64::from(height);
+    let similarity = if total_pixels == 0 {
+        1.0
+    } else {
+        1.0 - diff_pixels as f64 / total_pixels as f64
+    };
+    ImageDiff {
+        width,
+        height,
+        diff_pixels,
+        total_pixels,
+        similarity,
+        size_mismatch: a.width != b.width || a.height != b.height,
+        diff_image: RgbaImage {
+            width,
+            height,
+            pixels,
+        },
+    }
+}
+
+/// `<a>_vs_<b>_diff.png` next to `a`.
+pub fn default_diff_path(path_a: &Path, path_b: &Path) -> PathBuf {
+    let stem = |path: &Path| {
+        path.file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_else(|| "image".to_string())
+    };
+    path_a.with_file_name(format!("{}_vs_{}_diff.png", stem(path_a), stem(path_b)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solid(width: u32, height: u32, rgba: [u8; 4]) -> RgbaImage {
+        RgbaImage {
+            width,
+            height,
+            pixels: rgba.repeat((width * height) as usize),
+        }
+    }
+
+    #[test]
+    fn identical_images_are_fully_similar() {
+        let image = solid(4, 4, [10, 20, 30, 255]);
+        let mut noisy = image.clone();
+        noisy.pixels[0] = 15;
+        let diff = diff_images(&image, &noisy);
+        assert_eq!(diff.diff_pixels, 0);
+        assert_eq!(diff.similarity, 1.0);
+        assert!(!diff.size_mismatch);
+    }
+
+    #[test]
+    fn counts_changed_and_missing_pixels() {
+        let a = solid(4, 4, [0, 0, 0, 255]);
+        let mut b = a.clone();
+        b.pixels[0..4].copy_from_slice(&[200, 0, 0, 255]);
+        let diff = diff_images(&a, &b);
+        assert_eq!(diff.diff_pixels, 1);
+        assert_eq!(diff.total_pixels, 16);
+        assert_eq!(&diff.diff_image.pixels[0..4], &DIFF_COLOR);
+
+        let taller = solid(4, 5, [0, 0, 0, 255]);
+        let diff = diff_images(&a, &taller);
+        assert!(diff.size_mismatch);
+        assert_eq!((diff.width, diff.height), (4, 5));
+        assert_eq!(diff.diff_pixels, 4);
+        assert_eq!(diff.similarity, 0.8);
+    }
+
+    #[test]
+    fn round_trips_png_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let a_path = dir.path().join("home_a.png");
+        let b_path = dir.path().join("home_b.png");
+        let image = solid(3, 2, [1, 2, 3, 255]);
+        write_png_rgba(&a_path, &image).unwrap();
+        assert_eq!(read_png_rgba(&a_path).unwrap(), image);
+        assert!(read_png_rgba(&b_path).is_err());
+        assert_eq!(
+            default_diff_path(&a_path, &b_path),
+            dir.path().join("home_a_vs_home_b_diff.png")
+        );
+    }
+
+    #[test]
+    fn validates_threshold() {
+        assert_eq!(
+            normalize_similarity_threshold(None).unwrap(),
+            DEFAULT_SIMILARITY_THRESHOLD
+        );
+        assert_eq!(normalize_similarity_threshold(Some(0.5)).unwrap(), 0.5);
+        assert!(normalize_similarity_threshold(Some(1.5)).is_err());
+        assert!(normalize_similarity_threshold(Some(f64::NAN)).is_err());
+    }
+}
@@ -0,0 +1,269 @@
+use std::fmt::Write;
+
+use chrono::{DateTime, Utc};
+
+use crate::app::models::{TimelineEvent, UiCaptureRecord};
+use crate::app::timeline::{
+    TIMELINE_ANR, TIMELINE_CRASH, TIMELINE_INSTALL, TIMELINE_PERF_ALERT, TIMELINE_REBOOT,
+    TIMELINE_SCREEN_RECORD,
+};
+use crate::app::ui_xml::escape_html;
+
+const REPORT_STYLE: &str = "\
+body { margin: 0; padding: 24px; font-family: -apple-system, BlinkMacSystemFont, \"Segoe UI\", Helvetica, Arial, sans-serif; font-size: 13px; color: #0f172a; background: #f7f7fb; }\n\
+h1 { font-size: 20px; margin: 0 0 4px; }\n\
+h2 { font-size: 16px; margin: 28px 0 8px; }\n\
+h3 { font-size: 13px; margin: 16px 0 6px; color: #475569; }\n\
+.meta { color: #475569; margin-bottom: 16px; }\n\
+.device { background: #ffffff; border: 1px solid #d3d7e0; border-radius: 8px; padding: 4px 16px 16px; margin-bottom: 16px; }\n\
+.counts { display: flex; flex-wrap: wrap; gap: 8px; }\n\
+.count { border: 1px solid #d3d7e0; border-radius: 6px; padding: 6px 10px; }\n\
+.count b { display: block; font-size: 16px; }\n\
+table { border-collapse: collapse; width: 100%; }\n\
+th, td { text-align: left; padding: 4px 8px; border-bottom: 1px solid #e2e8f0; vertical-align: top; }\n\
+td.time { white-space: nowrap; color: #475569; font-family: ui-monospace, Menlo, Consolas, monospace; font-size: 12px; }\n\
+.empty { color: #475569; font-style: italic; }\n";
+
+pub struct SessionCapture {
+    pub capture_id: String,
+    pub captured_at: String,
+    pub backend: String,
+    pub node_count: usize,
+    pub diff: Option<(usize, usize, usize)>,
+}
+
+impl From<&UiCaptureRecord> for SessionCapture {
+    fn from(record: &UiCaptureRecord) -> Self {
+        Self {
+            capture_id: record.capture_id.clone(),
+            captured_at: record.captured_at.clone(),
+            backend: record.backend.clone(),
+            node_count: record.node_count,
+            diff: record
+                .diff
+                .as_ref()
+                .map(|diff| (diff.added, diff.removed, diff.changed)),
+        }
+    }
+}
+
+pub struct SessionDeviceReport {
+    pub serial: String,
+    pub events: Vec<TimelineEvent>,
+    pub captures: Vec<SessionCapture>,
+    pub commands_run: usize,
+    pub commands_failed: usize,
+}
+
+pub struct SessionReport {
+    pub generated_at: String,
+    pub since: Option<String>,
+    pub until: Option<String>,
+    pub devices: Vec<SessionDeviceReport>,
+}
+
+pub fn in_time_range(
+    timestamp: &str,
+    since: Option<DateTime<Utc>>,
+    until: Option<DateTime<Utc>>,
+) -> bool {
+    match DateTime::parse_from_rfc3339(timestamp) {
+        Ok(at) => {
+            let at = at.with_timezone(&Utc);
+            since.is_none_or(|since| at >= since) && until.is_none_or(|until| at <= until)
+        }
+        Err(_) => false,
+    }
+}
+
+fn render_event_table(output: &mut String, events: &[&TimelineEvent]) {
+    if events.is_empty() {
+        output.push_str("<p class=\"empty\">None recorded.</p>\n");
+        return;
+    }
+    output.push_str("<table><tr><th>Time</th><th>Kind</th><th>Details</th></tr>\n");
+    for event in events {
+        let _ = writeln!(
+            output,
+            "<tr><td class=\"time\">{}</td><td>{}</td><td>{}</td></tr>",
+            escape_html(&event.timestamp),
+            escape_html(&event.kind),
+            escape_html(&event.summary)
+        );
+    }
+    output.push_str("</table>\n");
+}
+
+fn render_device(output: &mut String, device: &SessionDeviceReport) {
+    let count = |kinds: &[&str]| {
+        device
+            .events
+            .iter()
+            .filter(|event| kinds.contains(&event.kind.as_str()))
+            .count()
+    };
+    let _ = writeln!(
+        output,
+        "<section class=\"device\"><h2>{}</h2>",
+        escape_html(&device.serial)
+    );
+    output.push_str("<div class=\"counts\">\n");
+    for (label, value) in [
+        ("Installs", count(&[TIMELINE_INSTALL])),
+        ("Crashes", count(&[TIMELINE_CRASH])),
+        ("ANRs", count(&[TIMELINE_ANR])),
+        ("Perf alerts", count(&[TIMELINE_PERF_ALERT])),
+        ("UI captures", device.captures.len()),
+        ("Commands", device.commands_run),
+        ("Failed commands", device.commands_failed),
+    ] {
+        let _ = writeln!(
+            output,
+            "<div class=\"count\"><b>{value}</b>{}</div>",
+            escape_html(label)
+        );
+    }
+    output.push_str("</div>\n");
+
+    let sections: [(&str, &[&str]); 4] = [
+        ("Install results", &[TIMELINE_INSTALL]),
+        ("Crashes and ANRs", &[TIMELINE_CRASH, TIMELINE_ANR]),
+        ("Performance alerts", &[TIMELINE_PERF_ALERT]),
+        (
+            "Recordings and reboots",
+            &[TIMELINE_SCREEN_RECORD, TIMELINE_REBOOT],
+        ),
+    ];
+    for (title, kinds) in sections {
+        let _ = writeln!(output, "<h3>{}</h3>", escape_html(title));
+        let events: Vec<&TimelineEvent> = device
+            .events
+            .iter()
+            .filter(|event| kinds.contains(&event.kind.as_str()))
+            .collect();
+        render_event_table(output, &events);
+    }
+
+    output.push_str("<h3>UI captures</h3>\n");
+    if device.captures.is_empty() {
+        output.push_str("<p class=\"empty\">None recorded.</p>\n");
+    } else {
+        output.push_str(
+            "<table><tr><th>Time</th><th>Capture</th><th>Backend</th><th>Nodes</th><th>Diff vs previous</th></tr>\n",
+        );
+        for capture in &device.captures {
+            let diff = capture
+                .diff
+                .map(|(added, removed, changed)| format!("+{added} / -{removed} / ~{changed}"))
+                .unwrap_or_else(|| "-".to_string());
+            let _ = writeln!(
+                output,
+                "<tr><td class=\"time\">{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                escape_html(&capture.captured_at),
+                escape_html(&capture.capture_id),
+                escape_html(&capture.backend),
+                capture.node_count,
+                escape_html(&diff)
+            );
+        }
+        output.push_str("</table>\n");
+    }
+
+    output.push_str("<h3>Full timeline</h3>\n");
+    let all: Vec<&TimelineEvent> = device.events.iter().collect();
+    render_event_table(output, &all);
+    output.push_str("</section>\n");
+}
+
+/// Render a single self-contained HTML page (inline styles, no external assets).
+pub fn render_session_report_html(report: &SessionReport) -> String {
+    let mut output = String::new();
+    output.push_str("<!doctype html>\n<html>\n<head>\n<meta charset=\"utf-8\" />\n");
+    output.push_str("<title>Session report</title>\n<style>\n");
+    output.push_str(REPORT_STYLE);
+    output.push_str("</style>\n</head>\n<body>\n<h1>Session report</h1>\n");
+    let range = format!(
+        "{} to {}",
+        report.since.as_deref().unwrap_or("start of session"),
+        report.until.as_deref().unwrap_or("now")
+    );
+    let _ = writeln!(
+        output,
+        "<div class=\"meta\">Generated {} &middot; Range: {} &middot; Devices: {}</div>",
+        escape_html(&report.generated_at),
+        escape_html(&range),
+        report.devices.len()
+    );
+    for device in &report.devices {
+        render_device(&mut output, device);
+    }
+    output.push_str("</body>\n</html>\n");
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(kind: &str, summary: &str) -> TimelineEvent {
+        TimelineEvent {
+            id: 1,
+            serial: "emulator-5554".to_string(),
+            kind: kind.to_string(),
+            timestamp: "2024-05-01T10:00:00.000Z".to_string(),
+            summary: summary.to_string(),
+            trace_id: "trace".to_string(),
+        }
+    }
+
+    #[test]
+    fn renders_sections_and_escapes_content() {
+        let report = SessionReport {
+            generated_at: "2024-05-01T11:00:00Z".to_string(),
+            since: Some("2024-05-01T09:00:00Z".to_string()),
+            until: None,
+            devices: vec![SessionDeviceReport {
+                serial: "emulator-5554".to_string(),
+                events: vec![
+                    event(TIMELINE_INSTALL, "Installed com.example"),
+                    event(TIMELINE_CRASH, "Fatal exception on thread <main>"),
+                ],
+                captures: vec![SessionCapture {
+                    capture_id: "cap-1".to_string(),
+                    captured_at: "2024-05-01T10:05:00Z".to_string(),
+                    backend: "uiautomator".to_string(),
+                    node_count: 42,
+                    diff: Some((1, 2, 3)),
+                }],
+                commands_run: 10,
+                commands_failed: 1,
+            }],
+        };
+        let html = render_session_report_html(&report);
+        assert!(html.starts_with("<!doctype html>"));
+        assert!(html.contains("<h2>emulator-5554</h2>"));
+        assert!(html.contains("Installed com.example"));
+        assert!(html.contains("thread &lt;main&gt;"));
+        assert!(html.contains("+1 / -2 / ~3"));
+        assert!(html.contains("2024-05-01T09:00:00Z to now"));
+        assert!(html.contains("<h3>Performance alerts</h3>\n<p class=\"empty\">"));
+        assert!(!html.contains("<script") && !html.contains("src=\"http"));
+    }
+
+    #[test]
+    fn checks_time_range() {
+        let at = |value: &str| {
+            Some(
+                DateTime::parse_from_rfc3339(value)
+                    .unwrap()
+                    .with_timezone(&Utc),
+            )
+        };
+        let since = at("2024-05-01T10:00:00Z");
+        let until = at("2024-05-01T11:00:00Z");
+        assert!(in_time_range("2024-05-01T10:30:00+00:00", since, until));
+        assert!(!in_time_range("2024-05-01T11:30:00Z", since, until));
+        assert!(in_time_range("2024-05-01T08:00:00Z", None, until));
+        assert!(!in_time_range("not a date", None, None));
+    }
+}
@@ -0,0 +1,397 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use tracing::warn;
+
+use crate::app::config::config_path;
+use crate::app::environment::STATUS_OK;
+use crate::app::error::AppError;
+use crate::app::i18n::Message;
+use crate::app::models::{DependencyStatus, DeviceSummary, SetupWizardState, SetupWizardStep};
+
+pub const STEP_ADB_LOCATED: &str = "adb_located";
+pub const STEP_DEVICE_AUTHORIZED: &str = "device_authorized";
+pub const STEP_OUTPUT_DIR_CHOSEN: &str = "output_dir_chosen";
+pub const STEP_OPTIONAL_TOOLS_DETECTED: &str = "optional_tools_detected";
+/// Onboarding order; a step can only run once every earlier step is done.
+pub const SETUP_STEPS: [&str; 4] = [
+    STEP_ADB_LOCATED,
+    STEP_DEVICE_AUTHORIZED,
+    STEP_OUTPUT_DIR_CHOSEN,
+    STEP_OPTIONAL_TOOLS_DETECTED,
+];
+pub const SKIPPABLE_STEPS: [&str; 1] = [STEP_OPTIONAL_TOOLS_DETECTED];
+
+pub const STEP_PENDING: &str = "pending";
+pub const STEP_COMPLETED: &str = "completed";
+pub const STEP_FAILED: &str = "failed";
+pub const STEP_SKIPPED: &str = "skipped";
+
+const WRITE_PROBE_FILE: &str = ".lazy_blacktea_write_test";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StepFailure {
+    pub error: String,
+    pub hint: Option<String>,
+}
+
+impl StepFailure {
+    pub fn new(error: impl Into<String>, hint: &str) -> Self {
+        Self {
+            error: error.into(),
+            hint: Some(hint.to_string()),
+        }
+    }
+}
+
+pub fn setup_wizard_path() -> PathBuf {
+    config_path().with_file_name(".lazy_blacktea_setup_wizard.json")
+}
+
+fn pending_step(id: &str) -> SetupWizardStep {
+    SetupWizardStep {
+        id: id.to_string(),
+        status: STEP_PENDING.to_string(),
+        detail: None,
+        error: None,
+        hint: None,
+        updated_at: None,
+    }
+}
+
+fn step_done(step: &SetupWizardStep) -> bool {
+    step.status == STEP_COMPLETED || step.status == STEP_SKIPPED
+}
+
+/// Rebuild the step list in canonical order (keeping saved progress for known steps) and
+/// recompute the derived fields.
+pub fn normalize_wizard_state(state: SetupWizardState) -> SetupWizardState {
+    let steps: Vec<SetupWizardStep> = SETUP_STEPS
+        .iter()
+        .map(|id| {
+            state
+                .steps
+                .iter()
+                .find(|step| step.id == *id)
+                .cloned()
+                .unwrap_or_else(|| pending_step(id))
+        })
+        .collect();
+    let current_step = steps
+        .iter()
+        .find(|step| !step_done(step))
+        .map(|step| step.id.clone());
+    SetupWizardState {
+        completed: current_step.is_none(),
+        current_step,
+        steps,
+    }
+}
+
+pub fn default_wizard_state() -> SetupWizardState {
+    normalize_wizard_state(SetupWizardState {
+        steps: Vec::new(),
+        current_step: None,
+        completed: false,
+    })
+}
+
+pub fn ensure_step_reachable(state: &SetupWizardState, step: &str) -> Result<(), String> {
+    let Some(index) = SETUP_STEPS.iter().position(|id| *id == step) else {
+        return Err(format!("Unknown setup step: {step}"));
+    };
+    match state.steps[..index]
+        .iter()
+        .find(|earlier| !step_done(earlier))
+    {
+        Some(earlier) => Err(format!("Complete the `{}` step first", earlier.id)),
+        None => Ok(()),
+    }
+}
+
+/// Record a step result. A failed step resets every later step, since those were validated
+/// against the state that no longer holds.
+pub fn apply_step_outcome(
+    state: SetupWizardState,
+    step: &str,
+    outcome: Result<String, StepFailure>,
+    now: String,
+) -> SetupWizardState {
+    let mut state = normalize_wizard_state(state);
+    let Some(index) = state.steps.iter().position(|entry| entry.id == step) else {
+        return state;
+    };
+    let failed = outcome.is_err();
+    let entry = &mut state.steps[index];
+    entry.updated_at = Some(now);
+    match outcome {
+        Ok(detail) => {
+            entry.status = STEP_COMPLETED.to_string();
+            entry.detail = Some(detail);
+            entry.error = None;
+            entry.hint = None;
+        }
+        Err(failure) => {
+            entry.status = STEP_FAILED.to_string();
+            entry.detail = None;
+            entry.error = Some(failure.error);
+            entry.hint = failure.hint;
+        }
+    }
+    if failed {
+        for later in state.steps.iter_mut().skip(index + 1) {
+            *later = pending_step(&later.id);
+        }
+    }
+    normalize_wizard_state(state)
+}
+
+pub fn skip_step(state: SetupWizardState, step: &str, now: String) -> SetupWizardState {
+    let mut state = normalize_wizard_state(state);
+    if let Some(entry) = state.steps.iter_mut().find(|entry| entry.id == step) {
+        entry.status = STEP_SKIPPED.to_string();
+        entry.detail = None;
+        entry.error = None;
+        entry.hint = None;
+        entry.updated_at = Some(now);
+    }
+    normalize_wizard_state(state)
+}
+
+pub fn device_authorization_outcome(devices: &[DeviceSummary]) -> Result<String, StepFailure> {
+    let authorized: Vec<&str> = devices
+        .iter()
+        .filter(|device| device.state == "device")
+        .map(|device| device.serial.as_str())
+        .collect();
+    if !authorized.is_empty() {
+        return Ok(format!("Authorized: {}", authorized.join(", ")));
+    }
+    if let Some(device) = devices.iter().find(|device| device.state == "unauthorized") {
+        return Err(StepFailure::new(
+            format!("{} has not authorized this computer", device.serial),
+            "Unlock the device and accept the USB debugging prompt. If no prompt appears, revoke USB debugging authorizations in Developer options and reconnect.",
+        ));
+    }
+    if let Some(device) = devices.first() {
+        return Err(StepFailure::new(
+            format!("{} is {}", device.serial, device.state),
+            "Reconnect the cable (or run `adb reconnect`) and wait for the device to come online.",
+        ));
+    }
+    Err(StepFailure::new(
+        "No devices connected",
+        "Connect a device with USB debugging enabled in Developer options, or start an emulator.",
+    ))
+}
+
+/// Optional tools never block onboarding; the summary tells the user what is unavailable.
+pub fn optional_tools_summary(items: &[DependencyStatus]) -> String {
+    let (found, missing): (Vec<&DependencyStatus>, Vec<&DependencyStatus>) = items
+        .iter()
+        .filter(|item| !item.required)
+        .partition(|item| item.status == STATUS_OK);
+    let names = |items: Vec<&DependencyStatus>| {
+        if items.is_empty() {
+            "none".to_string()
+        } else {
+            items
+                .iter()
+                .map(|item| item.name.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        }
+    };
+    format!("Found: {}. Missing: {}.", names(found), names(missing))
+}
+
+/// Create `dir` if needed and prove it is writable.
+pub fn check_output_dir(dir: &Path) -> Result<(), StepFailure> {
+    const HINT: &str = "Choose a folder you can write to.";
+    fs::create_dir_all(dir)
+        .map_err(|err| StepFailure::new(format!("Cannot create {}: {err}", dir.display()), HINT))?;
+    let probe = dir.join(WRITE_PROBE_FILE);
+    fs::write(&probe, b"ok").map_err(|err| {
+        StepFailure::new(format!("Cannot write to {}: {err}", dir.display()), HINT)
+    })?;
+    if let Err(err) = fs::remove_file(&probe) {
+        warn!(path = %probe.display(), error = %err, "failed to remove output folder write probe");
+    }
+    Ok(())
+}
+
+pub fn load_wizard_state(path: &Path) -> SetupWizardState {
+    let raw = match fs::read_to_string(path) {
+        Ok(raw) => raw,
+        Err(_) => return default_wizard_state(),
+    };
+    match serde_json::from_str::<SetupWizardState>(&raw) {
+        Ok(state) => normalize_wizard_state(state),
+        Err(err) => {
+            warn!(path = %path.display(), error = %err, "setup wizard state is invalid, starting over");
+            default_wizard_state()
+        }
+    }
+}
+
+pub fn save_wizard_state(
+    path: &Path,
+    state: &SetupWizardState,
+    trace_id: &str,
+) -> Result<(), AppError> {
+    let payload = serde_json::to_string_pretty(state).map_err(|err| {
+        AppError::system(
+            Message::keyed("operation_failed")
+                .with("action", "serialize setup state")
+                .with("detail", &err),
+            trace_id,
+        )
+    })?;
+    fs::write(path, payload).map_err(|err| {
+        AppError::system(
+            Message::keyed("operation_failed")
+                .with("action", "save setup state")
+                .with("detail", &err),
+            trace_id,
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn device(serial: &str, state: &str) -> DeviceSummary {
+        DeviceSummary {
+            serial: serial.to_string(),
+            state: state.to_string(),
+            model: None,
+            product: None,
+            device: None,
+            transport_id: None,
+            connection_type: crate::app::adb::locator::transport_for_serial(serial).to_string(),
+            usb_port: None,
+        }
+    }
+
+    fn now() -> String {
+        "2024-05-01T09:00:00Z".to_string()
+    }
+
+    #[test]
+    fn walks_steps_in_order() {
+        let state = default_wizard_state();
+        assert_eq!(state.current_step.as_deref(), Some(STEP_ADB_LOCATED));
+        assert!(ensure_step_reachable(&state, STEP_ADB_LOCATED).is_ok());
+        assert_eq!(
+            ensure_step_reachable(&state, STEP_OUTPUT_DIR_CHOSEN).unwrap_err(),
+            "Complete the `adb_located` step first"
+        );
+        assert!(ensure_step_reachable(&state, "bogus").is_err());
+
+        let mut state = state;
+        for step in &SETUP_STEPS[..3] {
+            state = apply_step_outcome(state, step, Ok("ok".to_string()), now());
+        }
+        assert_eq!(
+            state.current_step.as_deref(),
+            Some(STEP_OPTIONAL_TOOLS_DETECTED)
+        );
+        let state = skip_step(state, STEP_OPTIONAL_TOOLS_DETECTED, now());
+        assert!(state.completed);
+        assert_eq!(state.current_step, None);
+    }
+
+    #[test]
+    fn failure_resets_later_steps() {
+        let mut state = default_wizard_state();
+        for step in &SETUP_STEPS[..3] {
+            state = apply_step_outcome(state, step, Ok("ok".to_string()), now());
+        }
+        let state = apply_step_outcome(
+            state,
+            STEP_DEVICE_AUTHORIZED,
+            Err(StepFailure::new("No devices connected", "Connect one")),
+            now(),
+        );
+        let failed = &state.steps[1];
+        assert_eq!(failed.status, STEP_FAILED);
+        assert_eq!(failed.error.as_deref(), Some("No devices connected"));
+        assert_eq!(failed.hint.as_deref(), Some("Connect one"));
+        assert_eq!(state.steps[2].status, STEP_PENDING);
+        assert_eq!(state.steps[0].status, STEP_COMPLETED);
+        assert_eq!(state.current_step.as_deref(), Some(STEP_DEVICE_AUTHORIZED));
+    }
+
+    #[test]
+    fn classifies_device_authorization() {
+        assert_eq!(
+            device_authorization_outcome(&[device("A", "unauthorized"), device("B", "device")]),
+            Ok("Authorized: B".to_string())
+        );
+        let unauthorized =
+            device_authorization_outcome(&[device("A", "unauthorized")]).unwrap_err();
+        assert_eq!(unauthorized.error, "A has not authorized this computer");
+        let offline = device_authorization_outcome(&[device("A", "offline")]).unwrap_err();
+        assert_eq!(offline.error, "A is offline");
+        let none = device_authorization_outcome(&[]).unwrap_err();
+        assert_eq!(none.error, "No devices connected");
+    }
+
+    #[test]
+    fn summarizes_optional_tools() {
+        let item = |name: &str, status: &str, required: bool| DependencyStatus {
+            name: name.to_string(),
+            status: status.to_string(),
+            required,
+            path: None,
+            version: None,
+            detail: None,
+            hint: None,
+        };
+        assert_eq!(
+            optional_tools_summary(&[
+                item("adb", "ok", true),
+                item("scrcpy", "ok", false),
+                item("ffmpeg", "ok", false),
+                item("bundletool", "missing", false),
+            ]),
+            "Found: scrcpy, ffmpeg. Missing: bundletool."
+        );
+        assert_eq!(optional_tools_summary(&[]), "Found: none. Missing: none.");
+    }
+
+    #[test]
+    fn persists_and_normalizes_state() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("wizard.json");
+        assert_eq!(load_wizard_state(&path), default_wizard_state());
+
+        let state = apply_step_outcome(
+            default_wizard_state(),
+            STEP_ADB_LOCATED,
+            Ok("adb 1.0.41".to_string()),
+            now(),
+        );
+        save_wizard_state(&path, &state, "trace").unwrap();
+        assert_eq!(load_wizard_state(&path), state);
+
+        fs::write(&path, r#"{"steps":[{"id":"old_step","status":"completed","detail":null,"error":null,"hint":null,"updated_at":null}],"current_step":null,"completed":true}"#).unwrap();
+        assert_eq!(load_wizard_state(&path), default_wizard_state());
+        fs::write(&path, "not json").unwrap();
+        assert_eq!(load_wizard_state(&path), default_wizard_state());
+    }
+
+    #[test]
+    fn checks_output_dir_is_writable() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("nested").join("out");
+        assert!(check_output_dir(&target).is_ok());
+        assert!(target.is_dir());
+        assert!(!target.join(WRITE_PROBE_FILE).exists());
+
+        let file = dir.path().join("file");
+        fs::write(&file, b"x").unwrap();
+        assert!(check_output_dir(&file).is_err());
+    }
+}
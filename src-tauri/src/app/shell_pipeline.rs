@@ -0,0 +1,349 @@
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use regex::{Regex, RegexBuilder};
+
+use crate::app::adb::runner::CommandOutput;
+use crate::app::models::{ShellPipelineResult, ShellPipelineStep, ShellPipelineStepResult};
+
+pub const MAX_PIPELINE_STEPS: usize = 50;
+pub const DEFAULT_STEP_TIMEOUT_SECS: u64 = 60;
+pub const MAX_STEP_TIMEOUT_SECS: u64 = 30 * 60;
+/// Per-stream output kept in a step result; captures still see the full stdout.
+pub const MAX_STEP_OUTPUT_BYTES: usize = 64 * 1024;
+
+pub const STEP_OK: &str = "ok";
+pub const STEP_FAILED: &str = "failed";
+pub const STEP_SKIPPED: &str = "skipped";
+
+const MAX_PATTERN_LEN: usize = 512;
+const PATTERN_SIZE_LIMIT: usize = 1 << 20;
+
+pub struct CompiledStep {
+    pub step: ShellPipelineStep,
+    pub captures: Vec<(String, Regex)>,
+    pub timeout: Duration,
+}
+
+pub fn is_valid_variable_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(ch) if ch.is_ascii_alphabetic() || ch == '_')
+        && chars.all(|ch| ch.is_ascii_alphanumeric() || ch == '_')
+}
+
+/// Validate every step up front so a bad pattern in step 5 does not surface after steps 1-4
+/// already changed the device.
+pub fn compile_pipeline(steps: &[ShellPipelineStep]) -> Result<Vec<CompiledStep>, String> {
+    if steps.is_empty() {
+        return Err("At least one step is required".to_string());
+    }
+    if steps.len() > MAX_PIPELINE_STEPS {
+        return Err(format!("At most {MAX_PIPELINE_STEPS} steps are allowed"));
+    }
+    steps
+        .iter()
+        .enumerate()
+        .map(|(index, step)| {
+            if step.command.trim().is_empty() {
+                return Err(format!("Step {} has an empty command", index + 1));
+            }
+            let captures = step
+                .captures
+                .iter()
+                .map(|capture| {
+                    if !is_valid_variable_name(&capture.variable) {
+                        return Err(format!(
+                            "Step {}: invalid variable name {:?}",
+                            index + 1,
+                            capture.variable
+                        ));
+                    }
+                    if capture.pattern.len() > MAX_PATTERN_LEN {
+                        return Err(format!("Step {}: capture pattern is too long", index + 1));
+                    }
+                    let regex = RegexBuilder::new(&capture.pattern)
+                        .multi_line(true)
+                        .size_limit(PATTERN_SIZE_LIMIT)
+                        .build()
+                        .map_err(|err| {
+                            format!("Step {}: invalid capture pattern: {err}", index + 1)
+                        })?;
+                    Ok((capture.variable.clone(), regex))
+                })
+                .collect::<Result<Vec<_>, String>>()?;
+            let timeout_secs = step
+                .timeout_secs
+                .unwrap_or(DEFAULT_STEP_TIMEOUT_SECS)
+                .clamp(1, MAX_STEP_TIMEOUT_SECS);
+            Ok(CompiledStep {
+                step: step.clone(),
+                captures,
+                timeout: Duration::from_secs(timeout_secs),
+            })
+        })
+        .collect()
+}
+
+pub fn quote_shell_value(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+/// Replace `${name}` with the quoted captured value. Plain `$NAME` is left for the device
+/// shell, so environment variables keep working.
+pub fn render_command(
+    template: &str,
+    variables: &HashMap<String, String>,
+) -> Result<String, String> {
+    render_template(template, variables, quote_shell_value)
+}
+
+/// `render_command` with the substituted values passed through `escape`.
+pub fn render_template(
+    template: &str,
+    variables: &HashMap<String, String>,
+    escape: fn(&str) -> String,
+) -> Result<String, String> {
+    let mut rendered = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("${") {
+        rendered.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let Some(end) = after.find('}') else {
+            return Err("Unterminated ${ in command".to_string());
+        };
+        let name = &after[..end];
+        if !is_valid_variable_name(name) {
+            // Not one of ours (e.g. `${#array[@]}`); pass it through untouched.
+            rendered.push_str(&rest[start..start + 2 + end + 1]);
+        } else {
+            let value = variables
+                .get(name)
+                .ok_or_else(|| format!("Undefined variable: {name}"))?;
+            rendered.push_str(&escape(value));
+        }
+        rest = &after[end + 1..];
+    }
+    rendered.push_str(rest);
+    Ok(rendered)
+}
+
+pub fn extract_capture(regex: &Regex, stdout: &str) -> Option<String> {
+    let captures = regex.captures(stdout)?;
+    captures
+        .get(1)
+        .or_else(|| captures.get(0))
+        .map(|value| value.as_str().trim().to_string())
+}
+
+pub fn truncate_output(mut value: String) -> String {
+    if value.len() > MAX_STEP_OUTPUT_BYTES {
+        let mut end = MAX_STEP_OUTPUT_BYTES;
+        while !value.is_char_boundary(end) {
+            end -= 1;
+        }
+        value.truncate(end);
+        value.push_str("\n[truncated]");
+    }
+    value
+}
+
+fn skipped_step(index: usize, step: &ShellPipelineStep) -> ShellPipelineStepResult {
+    ShellPipelineStepResult {
+        index,
+        name: step.name.clone(),
+        command: None,
+        status: STEP_SKIPPED.to_string(),
+        exit_code: None,
+        stdout: String::new(),
+        stderr: String::new(),
+        captured: HashMap::new(),
+        error: None,
+        duration_ms: 0,
+    }
+}
+
+/// Run steps in order with `run_step(command, timeout)`. A failed step (error, non-zero exit
+/// or a capture that did not match) stops the pipeline unless it set `continue_on_error`;
+/// the remaining steps are reported as skipped.
+pub fn run_pipeline(
+    serial: &str,
+    steps: &[CompiledStep],
+    mut run_step: impl FnMut(&str, Duration) -> Result<CommandOutput, String>,
+) -> ShellPipelineResult {
+    let mut variables: HashMap<String, String> = HashMap::new();
+    let mut results = Vec::with_capacity(steps.len());
+    let mut stopped_at = None;
+
+    for (index, compiled) in steps.iter().enumerate() {
+        let step = &compiled.step;
+        if stopped_at.is_some() {
+            results.push(skipped_step(index, step));
+            continue;
+        }
+        let started = Instant::now();
+        let mut result = skipped_step(index, step);
+        let mut error = None;
+        match render_command(&step.command, &variables) {
+            Ok(command) => {
+                match run_step(&command, compiled.timeout) {
+                    Ok(output) => {
+                        result.exit_code = output.exit_code;
+                        if output.exit_code != Some(0) {
+                            error = Some(match output.exit_code {
+                                Some(code) => format!("Exited with code {code}"),
+                                None => "Terminated without an exit code".to_string(),
+                            });
+                        }
+                        for (variable, regex) in &compiled.captures {
+                            match extract_capture(regex, &output.stdout) {
+                                Some(value) => {
+                                    result.captured.insert(variable.clone(), value);
+                                }
+                                None if error.is_none() => {
+                                    error = Some(format!("Capture {variable} did not match"));
+                                }
+                                None => {}
+                            }
+                        }
+                        result.stdout = truncate_output(output.stdout);
+                        result.stderr = truncate_output(output.stderr);
+                    }
+                    Err(err) => error = Some(err),
+                }
+                result.command = Some(command);
+            }
+            Err(err) => error = Some(err),
+        }
+        variables.extend(result.captured.clone());
+        result.status = if error.is_some() {
+            STEP_FAILED
+        } else {
+            STEP_OK
+        }
+        .to_string();
+        result.error = error;
+        result.duration_ms = started.elapsed().as_millis() as u64;
+        if result.error.is_some() && !step.continue_on_error {
+            stopped_at = Some(index);
+        }
+        results.push(result);
+    }
+
+    ShellPipelineResult {
+        serial: serial.to_string(),
+        succeeded: stopped_at.is_none(),
+        stopped_at,
+        variables,
+        steps: results,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::models::PipelineCapture;
+
+    fn step(
+        command: &str,
+        continue_on_error: bool,
+        captures: &[(&str, &str)],
+    ) -> ShellPipelineStep {
+        ShellPipelineStep {
+            name: None,
+            command: command.to_string(),
+            continue_on_error,
+            captures: captures
+                .iter()
+                .map(|(variable, pattern)| PipelineCapture {
+                    variable: variable.to_string(),
+                    pattern: pattern.to_string(),
+                })
+                .collect(),
+            timeout_secs: None,
+        }
+    }
+
+    fn output(stdout: &str, exit_code: i32) -> CommandOutput {
+        CommandOutput {
+            stdout: stdout.to_string(),
+            stderr: String::new(),
+            exit_code: Some(exit_code),
+        }
+    }
+
+    #[test]
+    fn renders_variables_quoted() {
+        let variables = HashMap::from([("pid".to_string(), "12'3".to_string())]);
+        assert_eq!(
+            render_command("kill ${pid}; echo $HOME ${#x}", &variables).unwrap(),
+            "kill '12'\\''3'; echo $HOME ${#x}"
+        );
+        assert!(render_command("echo ${missing}", &variables).is_err());
+        assert!(render_command("echo ${pid", &variables).is_err());
+    }
+
+    #[test]
+    fn validates_steps() {
+        assert!(compile_pipeline(&[]).is_err());
+        assert!(compile_pipeline(&[step(" ", false, &[])]).is_err());
+        assert!(compile_pipeline(&[step("ls", false, &[("1bad", ".*")])]).is_err());
+        assert!(compile_pipeline(&[step("ls", false, &[("ok", "(")])]).is_err());
+        let compiled = compile_pipeline(&[step("ls", false, &[("pid", r"pid=(\d+)")])]).unwrap();
+        assert_eq!(
+            compiled[0].timeout,
+            Duration::from_secs(DEFAULT_STEP_TIMEOUT_SECS)
+        );
+    }
+
+    #[test]
+    fn captures_and_stops_on_failure() {
+        let steps = compile_pipeline(&[
+            step("pidof app", false, &[("pid", r"^(\d+)")]),
+            step("false", true, &[]),
+            step(
+                "cat /proc/${pid}/status",
+                false,
+                &[("state", r"State:\s+(\S+)")],
+            ),
+            step("echo never", false, &[]),
+            step("echo skipped", false, &[]),
+        ])
+        .unwrap();
+        let mut commands = Vec::new();
+        let result = run_pipeline("A1", &steps, |command, _| {
+            commands.push(command.to_string());
+            Ok(match command {
+                "pidof app" => output("4242\n", 0),
+                "false" => output("", 1),
+                _ => output("Name: app\n", 0),
+            })
+        });
+
+        assert_eq!(
+            commands,
+            vec!["pidof app", "false", "cat /proc/'4242'/status"]
+        );
+        let statuses: Vec<&str> = result
+            .steps
+            .iter()
+            .map(|step| step.status.as_str())
+            .collect();
+        assert_eq!(
+            statuses,
+            vec![
+                STEP_OK,
+                STEP_FAILED,
+                STEP_FAILED,
+                STEP_SKIPPED,
+                STEP_SKIPPED
+            ]
+        );
+        assert_eq!(result.stopped_at, Some(2));
+        assert!(!result.succeeded);
+        assert_eq!(result.variables["pid"], "4242");
+        assert_eq!(
+            result.steps[2].error.as_deref(),
+            Some("Capture state did not match")
+        );
+    }
+}
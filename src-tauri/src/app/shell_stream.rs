@@ -0,0 +1,290 @@
+use crate::app::adb::locator::adb_command_for_serial;
+use crate::app::command_log::spawn_logged;
+use crate::app::models::ShellStreamEvent;
+use std::io::Read;
+use std::process::{Child, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+use tracing::warn;
+
+pub const SHELL_STREAM_EVENT_NAME: &str = "shell-stream-event";
+pub const DEFAULT_SHELL_STREAM_TIMEOUT_SECS: u64 = 30 * 60;
+pub const MAX_SHELL_STREAM_TIMEOUT_SECS: u64 = 4 * 60 * 60;
+
+const CHUNK_FLUSH_BYTES: usize = 16_384;
+const CHUNK_FLUSH_DELAY: Duration = Duration::from_millis(50);
+
+pub type ShellStreamEmitter = Arc<dyn Fn(ShellStreamEvent) + Send + Sync>;
+
+pub fn clamp_stream_timeout_secs(input: Option<u64>) -> u64 {
+    input
+        .unwrap_or(DEFAULT_SHELL_STREAM_TIMEOUT_SECS)
+        .clamp(1, MAX_SHELL_STREAM_TIMEOUT_SECS)
+}
+
+/// Decode the valid UTF-8 in `pending`, keeping a multi-byte character split across reads
+/// for the next call. Invalid bytes become U+FFFD.
+pub fn drain_utf8(pending: &mut Vec<u8>) -> String {
+    let mut decoded = String::new();
+    let mut start = 0;
+    while start < pending.len() {
+        match std::str::from_utf8(&pending[start..]) {
+            Ok(text) => {
+                decoded.push_str(text);
+                start = pending.len();
+            }
+            Err(err) => {
+                let valid_end = start + err.valid_up_to();
+                decoded.push_str(&String::from_utf8_lossy(&pending[start..valid_end]));
+                match err.error_len() {
+                    Some(len) => {
+                        decoded.push(char::REPLACEMENT_CHARACTER);
+                        start = valid_end + len;
+                    }
+                    None => {
+                        start = valid_end;
+                        break;
+                    }
+                }
+            }
+        }
+    }
+    pending.drain(..start);
+    decoded
+}
+
+/// A one-shot `adb shell` command whose stdout and stderr are emitted as they arrive.
+pub struct ShellStream {
+    pub serial: String,
+    pub stream_id: String,
+    child: Arc<Mutex<Child>>,
+    cancel_flag: Arc<AtomicBool>,
+}
+
+struct StreamContext {
+    serial: String,
+    stream_id: String,
+    trace_id: String,
+    emitter: ShellStreamEmitter,
+}
+
+impl StreamContext {
+    fn output(&self, stream: &str, chunk: String) {
+        (self.emitter)(ShellStreamEvent {
+            serial: self.serial.clone(),
+            stream_id: self.stream_id.clone(),
+            event: "output".to_string(),
+            stream: Some(stream.to_string()),
+            chunk: Some(chunk),
+            exit_code: None,
+            cancelled: false,
+            timed_out: false,
+            trace_id: self.trace_id.clone(),
+        });
+    }
+}
+
+fn forward_chunks(
+    mut reader: impl Read + Send + 'static,
+    stream: &'static str,
+    context: Arc<StreamContext>,
+) -> JoinHandle<()> {
+    std::thread::spawn(move || {
+        let mut temp = [0u8; 4096];
+        let mut pending = Vec::new();
+        let mut last_emit = Instant::now();
+        loop {
+            let read_count = match reader.read(&mut temp) {
+                Ok(0) => break,
+                Ok(count) => count,
+                Err(err) => {
+                    warn!(trace_id = %context.trace_id, stream = %stream, error = %err, "failed to read shell stream");
+                    break;
+                }
+            };
+            pending.extend_from_slice(&temp[..read_count]);
+            // Blocking reads return at the end of a burst, so a short read is a flush point
+            // just like a full buffer or an elapsed delay.
+            if pending.len() >= CHUNK_FLUSH_BYTES
+                || read_count < temp.len()
+                || last_emit.elapsed() >= CHUNK_FLUSH_DELAY
+            {
+                let chunk = drain_utf8(&mut pending);
+                if !chunk.is_empty() {
+                    context.output(stream, chunk);
+                }
+                last_emit = Instant::now();
+            }
+        }
+        if !pending.is_empty() {
+            context.output(stream, String::from_utf8_lossy(&pending).to_string());
+        }
+    })
+}
+
+impl ShellStream {
+    pub fn spawn(
+        program: &str,
+        args: &[String],
+        serial: String,
+        stream_id: String,
+        trace_id: String,
+        timeout: Duration,
+        emitter: ShellStreamEmitter,
+    ) -> Result<Self, std::io::Error> {
+        let mut child = spawn_logged(
+            adb_command_for_serial(program, &serial)
+                .args(args)
+                .stdin(Stdio::null())
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped()),
+            &trace_id,
+        )?;
+        let stdout = child
+            .stdout
+            .take()
+            .ok_or_else(|| std::io::Error::other("Failed to capture stdout"))?;
+        let stderr = child
+            .stderr
+            .take()
+            .ok_or_else(|| std::io::Error::other("Failed to capture stderr"))?;
+
+        let context = Arc::new(StreamContext {
+            serial: serial.clone(),
+            stream_id: stream_id.clone(),
+            trace_id,
+            emitter,
+        });
+        let readers = vec![
+            forward_chunks(stdout, "stdout", Arc::clone(&context)),
+            forward_chunks(stderr, "stderr", Arc::clone(&context)),
+        ];
+
+        let child = Arc::new(Mutex::new(child));
+        let cancel_flag = Arc::new(AtomicBool::new(false));
+        let child_watch = Arc::clone(&child);
+        let cancel_watch = Arc::clone(&cancel_flag);
+        let deadline = Instant::now() + timeout;
+        std::thread::spawn(move || {
+            let mut timed_out = false;
+            let exit_code = loop {
+                let status = match child_watch.lock() {
+                    Ok(mut guard) => {
+                        if !timed_out && Instant::now() > deadline {
+                            warn!(trace_id = %context.trace_id, serial = %context.serial, "shell stream timed out");
+                            timed_out = true;
+                            let _ = guard.kill();
+                        }
+                        guard.try_wait()
+                    }
+                    Err(_) => break None,
+                };
+                match status {
+                    Ok(Some(status)) => break status.code(),
+                    Ok(None) => std::thread::sleep(Duration::from_millis(100)),
+                    Err(err) => {
+                        warn!(trace_id = %context.trace_id, error = %err, "failed to poll shell stream process");
+                        break None;
+                    }
+                }
+            };
+            // Drain the pipes first so the exit event is the last one for this stream.
+            for reader in readers {
+                let _ = reader.join();
+            }
+            (context.emitter)(ShellStreamEvent {
+                serial: context.serial.clone(),
+                stream_id: context.stream_id.clone(),
+                event: "exit".to_string(),
+                stream: None,
+                chunk: None,
+                exit_code,
+                cancelled: cancel_watch.load(Ordering::Relaxed),
+                timed_out,
+                trace_id: context.trace_id.clone(),
+            });
+        });
+
+        Ok(Self {
+            serial,
+            stream_id,
+            child,
+            cancel_flag,
+        })
+    }
+
+    /// Kill the adb client; the exit event still follows from the watcher thread.
+    pub fn cancel(&self) {
+        self.cancel_flag.store(true, Ordering::Relaxed);
+        if let Ok(mut guard) = self.child.lock() {
+            let _ = guard.kill();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+
+    #[test]
+    fn keeps_split_utf8_for_next_read() {
+        let bytes = "héllo".as_bytes();
+        let mut pending = bytes[..2].to_vec();
+        assert_eq!(drain_utf8(&mut pending), "h");
+        assert_eq!(pending, vec![0xC3]);
+        pending.extend_from_slice(&bytes[2..]);
+        assert_eq!(drain_utf8(&mut pending), "éllo");
+        assert!(pending.is_empty());
+
+        let mut invalid = vec![b'a', 0xFF, b'b'];
+        assert_eq!(drain_utf8(&mut invalid), "a\u{FFFD}b");
+        assert_eq!(
+            clamp_stream_timeout_secs(None),
+            DEFAULT_SHELL_STREAM_TIMEOUT_SECS
+        );
+        assert_eq!(clamp_stream_timeout_secs(Some(0)), 1);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn streams_output_then_exit() {
+        let (tx, rx) = mpsc::channel::<ShellStreamEvent>();
+        let emitter: ShellStreamEmitter = Arc::new(move |event| {
+            let _ = tx.send(event);
+        });
+        let _stream = ShellStream::spawn(
+            "sh",
+            &[
+                "-c".to_string(),
+                "echo out; echo err >&2; exit 3".to_string(),
+            ],
+            "test-serial".to_string(),
+            "test-stream".to_string(),
+            "test-trace".to_string(),
+            Duration::from_secs(5),
+            emitter,
+        )
+        .expect("spawn shell stream");
+
+        let mut stdout = String::new();
+        let mut stderr = String::new();
+        let exit = loop {
+            let event = rx
+                .recv_timeout(Duration::from_secs(5))
+                .expect("shell stream event");
+            match (event.event.as_str(), event.stream.as_deref()) {
+                ("output", Some("stdout")) => stdout.push_str(&event.chunk.unwrap_or_default()),
+                ("output", Some("stderr")) => stderr.push_str(&event.chunk.unwrap_or_default()),
+                _ => break event,
+            }
+        };
+        assert_eq!(stdout, "out\n");
+        assert_eq!(stderr, "err\n");
+        assert_eq!(exit.event, "exit");
+        assert_eq!(exit.exit_code, Some(3));
+        assert!(!exit.cancelled && !exit.timed_out);
+    }
+}
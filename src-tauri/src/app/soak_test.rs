@@ -0,0 +1,646 @@
+use std::collections::HashMap;
+use std::io::Write;
+use std::time::Duration;
+
+use chrono::{TimeZone, Utc};
+
+use crate::app::capture_context::MAX_CAPTURE_LABEL_LEN;
+use crate::app::models::{
+    NetProfilerSnapshot, PerfSnapshot, SoakAlert, SoakNetUsage, SoakProfile, SoakSample,
+    ThermalSnapshot,
+};
+
+pub const DEFAULT_SOAK_LABEL: &str = "soak";
+pub const SOAK_SAMPLES_FILE: &str = "samples.jsonl";
+
+const DEFAULT_SAMPLE_INTERVAL_SECS: u64 = 5;
+const DEFAULT_SCREENSHOT_INTERVAL_SECS: u64 = 300;
+const DEFAULT_LOGCAT_INTERVAL_SECS: u64 = 600;
+const DEFAULT_LOGCAT_LINES: u32 = 5_000;
+const DEFAULT_CPU_THRESHOLD_PERCENT: u8 = 90;
+const DEFAULT_MEM_THRESHOLD_PERCENT: u8 = 90;
+const DEFAULT_TEMP_THRESHOLD_DECIC: i32 = 450;
+
+const SAMPLE_INTERVAL_BOUNDS: (u64, u64) = (2, 60);
+const CAPTURE_INTERVAL_BOUNDS: (u64, u64) = (30, 3_600);
+const LOGCAT_LINES_BOUNDS: (u32, u32) = (100, 50_000);
+const PERCENT_THRESHOLD_BOUNDS: (u8, u8) = (10, 100);
+const TEMP_THRESHOLD_BOUNDS: (i32, i32) = (200, 1_200);
+
+pub const SOAK_MONITOR_PERF: &str = "perf";
+pub const SOAK_MONITOR_NET: &str = "net";
+pub const SOAK_MONITOR_THERMAL: &str = "thermal";
+
+pub const SOAK_METRIC_CPU: &str = "cpu";
+pub const SOAK_METRIC_MEMORY: &str = "memory";
+pub const SOAK_METRIC_TEMPERATURE: &str = "temperature";
+
+/// A validated soak profile; `None` intervals and thresholds are turned off.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SoakPlan {
+    pub label: String,
+    pub sample_interval: Duration,
+    pub screenshot_interval: Option<Duration>,
+    pub logcat_interval: Option<Duration>,
+    pub logcat_lines: u32,
+    pub cpu_threshold_percent: Option<u8>,
+    pub mem_threshold_percent: Option<u8>,
+    pub temp_threshold_decic: Option<i32>,
+}
+
+impl SoakPlan {
+    pub fn from_profile(profile: &SoakProfile) -> Result<Self, String> {
+        let label = profile
+            .label
+            .as_deref()
+            .map(str::trim)
+            .filter(|label| !label.is_empty())
+            .unwrap_or(DEFAULT_SOAK_LABEL)
+            .to_string();
+        if label.chars().count() > MAX_CAPTURE_LABEL_LEN {
+            return Err(format!(
+                "Label must be at most {MAX_CAPTURE_LABEL_LEN} characters"
+            ));
+        }
+        let capture_interval = |value: Option<u64>, default: u64| match value.unwrap_or(default) {
+            0 => None,
+            secs => Some(Duration::from_secs(
+                secs.clamp(CAPTURE_INTERVAL_BOUNDS.0, CAPTURE_INTERVAL_BOUNDS.1),
+            )),
+        };
+        let percent = |value: Option<u8>, default: u8| match value.unwrap_or(default) {
+            0 => None,
+            percent => Some(percent.clamp(PERCENT_THRESHOLD_BOUNDS.0, PERCENT_THRESHOLD_BOUNDS.1)),
+        };
+        Ok(Self {
+            label,
+            sample_interval: Duration::from_secs(
+                profile
+                    .sample_interval_secs
+                    .unwrap_or(DEFAULT_SAMPLE_INTERVAL_SECS)
+                    .clamp(SAMPLE_INTERVAL_BOUNDS.0, SAMPLE_INTERVAL_BOUNDS.1),
+            ),
+            screenshot_interval: capture_interval(
+                profile.screenshot_interval_secs,
+                DEFAULT_SCREENSHOT_INTERVAL_SECS,
+            ),
+            logcat_interval: capture_interval(
+                profile.logcat_interval_secs,
+                DEFAULT_LOGCAT_INTERVAL_SECS,
+            ),
+            logcat_lines: profile
+                .logcat_lines
+                .unwrap_or(DEFAULT_LOGCAT_LINES)
+                .clamp(LOGCAT_LINES_BOUNDS.0, LOGCAT_LINES_BOUNDS.1),
+            cpu_threshold_percent: percent(
+                profile.cpu_threshold_percent,
+                DEFAULT_CPU_THRESHOLD_PERCENT,
+            ),
+            mem_threshold_percent: percent(
+                profile.mem_threshold_percent,
+                DEFAULT_MEM_THRESHOLD_PERCENT,
+            ),
+            temp_threshold_decic: match profile
+                .temp_threshold_decic
+                .unwrap_or(DEFAULT_TEMP_THRESHOLD_DECIC)
+            {
+                0 => None,
+                decic => Some(decic.clamp(TEMP_THRESHOLD_BOUNDS.0, TEMP_THRESHOLD_BOUNDS.1)),
+            },
+        })
+    }
+
+    /// The plan as a fully populated profile, zero marking what is turned off.
+    pub fn profile(&self) -> SoakProfile {
+        SoakProfile {
+            label: Some(self.label.clone()),
+            sample_interval_secs: Some(self.sample_interval.as_secs()),
+            screenshot_interval_secs: Some(self.screenshot_interval.map_or(0, |d| d.as_secs())),
+            logcat_interval_secs: Some(self.logcat_interval.map_or(0, |d| d.as_secs())),
+            logcat_lines: Some(self.logcat_lines),
+            cpu_threshold_percent: Some(self.cpu_threshold_percent.unwrap_or(0)),
+            mem_threshold_percent: Some(self.mem_threshold_percent.unwrap_or(0)),
+            temp_threshold_decic: Some(self.temp_threshold_decic.unwrap_or(0)),
+        }
+    }
+}
+
+/// `logcat -d` of the newest `lines` entries; snapshots may overlap when the log is quiet.
+pub fn logcat_snapshot_args(serial: &str, lines: u32) -> Vec<String> {
+    [
+        "-s",
+        serial,
+        "logcat",
+        "-d",
+        "-v",
+        "threadtime",
+        "-t",
+        &lines.to_string(),
+    ]
+    .into_iter()
+    .map(str::to_string)
+    .collect()
+}
+
+/// A reading one of the monitors took while a soak test runs on the device.
+#[derive(Debug, Clone, Copy)]
+pub enum SoakReading<'a> {
+    Perf(&'a PerfSnapshot),
+    Thermal(&'a ThermalSnapshot),
+    Net(&'a NetProfilerSnapshot),
+}
+
+/// What recording one reading produced.
+#[derive(Debug, Default)]
+pub struct SoakUpdate {
+    pub alerts: Vec<SoakAlert>,
+    pub write_error: Option<String>,
+}
+
+/// What the soak test has collected so far; read back when the test stops. The perf,
+/// thermal and net monitors feed it, so the soak takes no samples of its own. Samples are
+/// appended to `samples.jsonl` as they arrive and only running aggregates stay in memory.
+pub struct SoakProgress {
+    plan: SoakPlan,
+    tracker: SoakAlertTracker,
+    samples_out: Option<Box<dyn Write + Send>>,
+    /// Latest `(max_temp_decic, thermal_status)`, paired with the next perf sample.
+    thermal: Option<(Option<i32>, Option<u8>)>,
+    pub stats: SoakStats,
+    pub net: SoakNetTotals,
+    pub alerts: Vec<SoakAlert>,
+    pub screenshots: usize,
+    pub logcat_snapshots: usize,
+    pub capture_errors: usize,
+}
+
+impl SoakProgress {
+    pub fn new(plan: SoakPlan, samples_out: Box<dyn Write + Send>) -> Self {
+        Self {
+            plan,
+            tracker: SoakAlertTracker::default(),
+            samples_out: Some(samples_out),
+            thermal: None,
+            stats: SoakStats::default(),
+            net: SoakNetTotals::default(),
+            alerts: Vec::new(),
+            screenshots: 0,
+            logcat_snapshots: 0,
+            capture_errors: 0,
+        }
+    }
+
+    pub fn label(&self) -> &str {
+        &self.plan.label
+    }
+
+    /// Perf snapshots become samples, checked against the thresholds with the latest thermal
+    /// reading; thermal and net readings are only remembered. A failed write stops further
+    /// writes, but the aggregates keep counting.
+    pub fn record(&mut self, reading: SoakReading<'_>) -> SoakUpdate {
+        let perf = match reading {
+            SoakReading::Perf(perf) => perf,
+            SoakReading::Thermal(snapshot) => {
+                self.thermal = Some((snapshot.max_temp_decic, snapshot.thermal_status));
+                return SoakUpdate::default();
+            }
+            SoakReading::Net(snapshot) => {
+                self.net.record(snapshot);
+                return SoakUpdate::default();
+            }
+        };
+        let (max_temp_decic, thermal_status) = self.thermal.unwrap_or_default();
+        let sample = SoakSample {
+            timestamp: Utc
+                .timestamp_millis_opt(perf.ts_ms)
+                .single()
+                .unwrap_or_else(Utc::now)
+                .to_rfc3339(),
+            cpu_percent_x100: perf.cpu_total_percent_x100,
+            mem_total_bytes: perf.mem_total_bytes,
+            mem_used_bytes: perf.mem_used_bytes,
+            max_temp_decic,
+            thermal_status,
+        };
+        let alerts = self.tracker.check(&self.plan, &sample);
+        self.stats.record(&sample);
+        self.alerts.extend(alerts.iter().cloned());
+
+        let mut write_error = None;
+        if let Some(out) = self.samples_out.as_mut() {
+            let written = serde_json::to_string(&sample)
+                .map_err(|err| err.to_string())
+                .and_then(|line| writeln!(out, "{line}").map_err(|err| err.to_string()));
+            if let Err(err) = written {
+                self.samples_out = None;
+                self.capture_errors += 1;
+                write_error = Some(err);
+            }
+        }
+        SoakUpdate {
+            alerts,
+            write_error,
+        }
+    }
+
+    /// Flush the samples file; called once the monitors stopped feeding the test.
+    pub fn finish(&mut self) -> std::io::Result<()> {
+        match self.samples_out.take() {
+            Some(mut out) => out.flush(),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Remembers which thresholds are exceeded so only the sample that crosses one raises an
+/// alert, not every sample while it stays above.
+#[derive(Debug, Default)]
+pub struct SoakAlertTracker {
+    cpu_over: bool,
+    mem_over: bool,
+    temp_over: bool,
+}
+
+impl SoakAlertTracker {
+    pub fn check(&mut self, plan: &SoakPlan, sample: &SoakSample) -> Vec<SoakAlert> {
+        let mut alerts = Vec::new();
+        let crossed = |over: &mut bool, value: Option<i64>, threshold: Option<i64>| {
+            let (Some(value), Some(threshold)) = (value, threshold) else {
+                return None;
+            };
+            let was_over = std::mem::replace(over, value >= threshold);
+            (*over && !was_over).then_some((value, threshold))
+        };
+
+        let cpu_x100 = sample.cpu_percent_x100.map(i64::from);
+        if let Some((value, threshold)) = crossed(
+            &mut self.cpu_over,
+            cpu_x100,
+            plan.cpu_threshold_percent
+                .map(|percent| i64::from(percent) * 100),
+        ) {
+            alerts.push(alert(
+                sample,
+                SOAK_METRIC_CPU,
+                value,
+                threshold,
+                format!(
+                    "CPU {:.1}% reached the {}% soak threshold",
+                    value as f64 / 100.0,
+                    threshold / 100
+                ),
+            ));
+        }
+
+        let mem_x100 = match (sample.mem_used_bytes, sample.mem_total_bytes) {
+            (Some(used), Some(total)) if total > 0 => {
+                Some((u128::from(used) * 10_000 / u128::from(total)) as i64)
+            }
+            _ => None,
+        };
+        if let Some((value, threshold)) = crossed(
+            &mut self.mem_over,
+            mem_x100,
+            plan.mem_threshold_percent
+                .map(|percent| i64::from(percent) * 100),
+        ) {
+            alerts.push(alert(
+                sample,
+                SOAK_METRIC_MEMORY,
+                value,
+                threshold,
+                format!(
+                    "Memory {:.1}% reached the {}% soak threshold",
+                    value as f64 / 100.0,
+                    threshold / 100
+                ),
+            ));
+        }
+
+        if let Some((value, threshold)) = crossed(
+            &mut self.temp_over,
+            sample.max_temp_decic.map(i64::from),
+            plan.temp_threshold_decic.map(i64::from),
+        ) {
+            alerts.push(alert(
+                sample,
+                SOAK_METRIC_TEMPERATURE,
+                value,
+                threshold,
+                format!(
+                    "Temperature {:.1} C reached the {:.1} C soak threshold",
+                    value as f64 / 10.0,
+                    threshold as f64 / 10.0
+                ),
+            ));
+        }
+        alerts
+    }
+}
+
+fn alert(
+    sample: &SoakSample,
+    metric: &str,
+    value: i64,
+    threshold: i64,
+    summary: String,
+) -> SoakAlert {
+    SoakAlert {
+        timestamp: sample.timestamp.clone(),
+        metric: metric.to_string(),
+        value,
+        threshold,
+        summary,
+    }
+}
+
+/// Running aggregates over every sample of a soak test.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SoakStats {
+    pub samples: usize,
+    cpu_sum_x100: u64,
+    cpu_samples: u64,
+    pub cpu_max_percent_x100: Option<u16>,
+    pub mem_max_used_bytes: Option<u64>,
+    pub temp_max_decic: Option<i32>,
+}
+
+impl SoakStats {
+    pub fn record(&mut self, sample: &SoakSample) {
+        self.samples += 1;
+        if let Some(cpu) = sample.cpu_percent_x100 {
+            self.cpu_sum_x100 += u64::from(cpu);
+            self.cpu_samples += 1;
+        }
+        self.cpu_max_percent_x100 = self.cpu_max_percent_x100.max(sample.cpu_percent_x100);
+        self.mem_max_used_bytes = self.mem_max_used_bytes.max(sample.mem_used_bytes);
+        self.temp_max_decic = self.temp_max_decic.max(sample.max_temp_decic);
+    }
+
+    pub fn cpu_avg_percent_x100(&self) -> Option<u16> {
+        (self.cpu_samples > 0).then(|| (self.cpu_sum_x100 / self.cpu_samples) as u16)
+    }
+}
+
+/// Bytes each uid moved during the soak, from the net profiler's cumulative counters. A
+/// counter that goes backwards was reset (reboot, netd restart) and counts from zero again.
+#[derive(Debug, Default)]
+pub struct SoakNetTotals {
+    uids: HashMap<u32, NetUidTotals>,
+}
+
+#[derive(Debug, Default)]
+struct NetUidTotals {
+    packages: Vec<String>,
+    last: (u64, u64),
+    rx_bytes: u64,
+    tx_bytes: u64,
+}
+
+impl SoakNetTotals {
+    pub fn record(&mut self, snapshot: &NetProfilerSnapshot) {
+        if snapshot.unsupported {
+            return;
+        }
+        for row in &snapshot.rows {
+            let delta = |total: u64, last: u64| {
+                if total >= last {
+                    total - last
+                } else {
+                    total
+                }
+            };
+            match self.uids.get_mut(&row.uid) {
+                Some(uid) => {
+                    uid.rx_bytes += delta(row.rx_bytes, uid.last.0);
+                    uid.tx_bytes += delta(row.tx_bytes, uid.last.1);
+                    uid.last = (row.rx_bytes, row.tx_bytes);
+                    if !row.packages.is_empty() {
+                        uid.packages = row.packages.clone();
+                    }
+                }
+                // The first reading is the baseline: traffic before the soak does not count.
+                None => {
+                    self.uids.insert(
+                        row.uid,
+                        NetUidTotals {
+                            packages: row.packages.clone(),
+                            last: (row.rx_bytes, row.tx_bytes),
+                            ..NetUidTotals::default()
+                        },
+                    );
+                }
+            }
+        }
+    }
+
+    /// Uids that moved any data, busiest first.
+    pub fn usage(&self) -> Vec<SoakNetUsage> {
+        let mut usage: Vec<SoakNetUsage> = self
+            .uids
+            .iter()
+            .filter(|(_, totals)| totals.rx_bytes > 0 || totals.tx_bytes > 0)
+            .map(|(uid, totals)| SoakNetUsage {
+                uid: *uid,
+                packages: totals.packages.clone(),
+                rx_bytes: totals.rx_bytes,
+                tx_bytes: totals.tx_bytes,
+            })
+            .collect();
+        usage.sort_by(|a, b| {
+            (b.rx_bytes + b.tx_bytes)
+                .cmp(&(a.rx_bytes + a.tx_bytes))
+                .then(a.uid.cmp(&b.uid))
+        });
+        usage
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::models::NetUsageRow;
+
+    fn sample(cpu: Option<u16>, mem_used: Option<u64>, temp: Option<i32>) -> SoakSample {
+        SoakSample {
+            timestamp: "2026-01-01T00:00:00Z".to_string(),
+            cpu_percent_x100: cpu,
+            mem_total_bytes: Some(1_000),
+            mem_used_bytes: mem_used,
+            max_temp_decic: temp,
+            thermal_status: None,
+        }
+    }
+
+    #[test]
+    fn normalizes_profiles() {
+        let defaults = SoakPlan::from_profile(&SoakProfile::default()).expect("defaults");
+        assert_eq!(defaults.label, DEFAULT_SOAK_LABEL);
+        assert_eq!(defaults.sample_interval, Duration::from_secs(5));
+        assert_eq!(defaults.screenshot_interval, Some(Duration::from_secs(300)));
+        assert_eq!(defaults.temp_threshold_decic, Some(450));
+
+        let custom = SoakPlan::from_profile(&SoakProfile {
+            label: Some("  overnight  ".to_string()),
+            sample_interval_secs: Some(1),
+            screenshot_interval_secs: Some(0),
+            logcat_interval_secs: Some(5),
+            logcat_lines: Some(1_000_000),
+            cpu_threshold_percent: Some(0),
+            mem_threshold_percent: Some(150),
+            temp_threshold_decic: Some(100),
+        })
+        .expect("custom");
+        assert_eq!(custom.label, "overnight");
+        assert_eq!(custom.sample_interval, Duration::from_secs(2));
+        assert_eq!(custom.screenshot_interval, None);
+        assert_eq!(custom.logcat_interval, Some(Duration::from_secs(30)));
+        assert_eq!(custom.logcat_lines, 50_000);
+        assert_eq!(custom.cpu_threshold_percent, None);
+        assert_eq!(custom.mem_threshold_percent, Some(100));
+        assert_eq!(custom.temp_threshold_decic, Some(200));
+        assert_eq!(custom.profile().screenshot_interval_secs, Some(0));
+        assert_eq!(custom.profile().cpu_threshold_percent, Some(0));
+
+        assert!(SoakPlan::from_profile(&SoakProfile {
+            label: Some("x".repeat(MAX_CAPTURE_LABEL_LEN + 1)),
+            ..SoakProfile::default()
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn alerts_on_crossings_and_summarizes() {
+        let plan = SoakPlan::from_profile(&SoakProfile::default()).expect("defaults");
+        let mut tracker = SoakAlertTracker::default();
+        let samples = [
+            sample(None, Some(500), Some(400)),
+            sample(Some(9_500), Some(950), Some(460)),
+            sample(Some(9_800), Some(960), Some(470)),
+            sample(Some(1_000), Some(500), Some(300)),
+            sample(Some(9_000), Some(500), Some(300)),
+        ];
+        let metrics: Vec<Vec<String>> = samples
+            .iter()
+            .map(|sample| {
+                tracker
+                    .check(&plan, sample)
+                    .into_iter()
+                    .map(|alert| alert.metric)
+                    .collect()
+            })
+            .collect();
+        assert_eq!(
+            metrics,
+            [
+                vec![],
+                vec![
+                    SOAK_METRIC_CPU.to_string(),
+                    SOAK_METRIC_MEMORY.to_string(),
+                    SOAK_METRIC_TEMPERATURE.to_string()
+                ],
+                vec![],
+                vec![],
+                vec![SOAK_METRIC_CPU.to_string()],
+            ]
+        );
+
+        let mut stats = SoakStats::default();
+        for sample in &samples {
+            stats.record(sample);
+        }
+        assert_eq!(stats.samples, 5);
+        assert_eq!(stats.cpu_avg_percent_x100(), Some(7_325));
+        assert_eq!(stats.cpu_max_percent_x100, Some(9_800));
+        assert_eq!(stats.mem_max_used_bytes, Some(960));
+        assert_eq!(stats.temp_max_decic, Some(470));
+        assert_eq!(SoakStats::default().cpu_avg_percent_x100(), None);
+    }
+
+    #[derive(Clone, Default)]
+    struct SharedBuffer(std::sync::Arc<std::sync::Mutex<Vec<u8>>>);
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().expect("buffer").write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn records_monitor_readings() {
+        let plan = SoakPlan::from_profile(&SoakProfile::default()).expect("defaults");
+        let buffer = SharedBuffer::default();
+        let mut progress = SoakProgress::new(plan, Box::new(buffer.clone()));
+        let perf = |cpu: u16| PerfSnapshot {
+            ts_ms: 1_767_225_600_000,
+            cpu_total_percent_x100: Some(cpu),
+            mem_total_bytes: Some(1_000),
+            mem_used_bytes: Some(400),
+            net_rx_bps: None,
+            net_tx_bps: None,
+            battery_level: None,
+            battery_temp_decic: None,
+            display_refresh_hz_x100: None,
+            missed_frames_per_sec_x100: None,
+            cpu_cores_percent_x100: Vec::new(),
+            cpu_cores_freq_khz: Vec::new(),
+            cpu_clusters: Vec::new(),
+        };
+        let thermal = ThermalSnapshot {
+            ts_ms: 0,
+            thermal_status: Some(2),
+            throttle_label: None,
+            max_temp_decic: Some(470),
+            readings: Vec::new(),
+        };
+        let net = |rx: u64, tx: u64| NetProfilerSnapshot {
+            ts_ms: 0,
+            dt_ms: None,
+            rows: vec![NetUsageRow {
+                uid: 10_123,
+                packages: vec!["com.example".to_string()],
+                rx_bytes: rx,
+                tx_bytes: tx,
+                rx_bps: None,
+                tx_bps: None,
+            }],
+            unsupported: false,
+        };
+
+        assert!(progress
+            .record(SoakReading::Thermal(&thermal))
+            .alerts
+            .is_empty());
+        let update = progress.record(SoakReading::Perf(&perf(1_000)));
+        assert_eq!(update.alerts.len(), 1);
+        assert_eq!(update.alerts[0].metric, SOAK_METRIC_TEMPERATURE);
+        assert!(update.write_error.is_none());
+        progress.record(SoakReading::Perf(&perf(3_000)));
+        progress.record(SoakReading::Net(&net(5_000, 100)));
+        progress.record(SoakReading::Net(&net(7_000, 300)));
+        // The device rebooted and its counters started over.
+        progress.record(SoakReading::Net(&net(500, 50)));
+        progress.finish().expect("flush");
+
+        assert_eq!(progress.stats.samples, 2);
+        assert_eq!(progress.stats.cpu_avg_percent_x100(), Some(2_000));
+        assert_eq!(progress.alerts.len(), 1);
+        let usage = progress.net.usage();
+        assert_eq!(usage.len(), 1);
+        assert_eq!((usage[0].rx_bytes, usage[0].tx_bytes), (2_500, 250));
+
+        let written = String::from_utf8(buffer.0.lock().expect("buffer").clone()).expect("utf8");
+        let lines: Vec<SoakSample> = written
+            .lines()
+            .map(|line| serde_json::from_str(line).expect("sample"))
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].timestamp, "2026-01-01T00:00:00+00:00");
+        assert_eq!(lines[0].max_temp_decic, Some(470));
+        assert_eq!(lines[1].cpu_percent_x100, Some(3_000));
+    }
+}
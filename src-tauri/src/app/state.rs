@@ -1,17 +1,80 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
+use std::io::{self, Read};
+use std::net::{Shutdown, TcpStream};
+use std::path::PathBuf;
 use std::process::Child;
 use std::sync::atomic::AtomicBool;
 use std::sync::{Arc, Mutex, RwLock};
 use std::thread::JoinHandle;
+use std::time::Instant;
+
+use chrono::{DateTime, Local};
 
 use crate::app::adb::device_tracking::DeviceTrackerHandle;
+use crate::app::adb::getevent::TouchDevice;
+use crate::app::adb::tunnel::SshTunnelHandle;
 use crate::app::bluetooth::service::BluetoothMonitorHandle;
+use crate::app::capture_context::CaptureLineBuffer;
+use crate::app::models::{ApkInstallResult, AppInfo, CapturePerfSample, UiCaptureRecord};
+use crate::app::perf::aggregate::PerfFrameAggregator;
 use crate::app::scheduler::TaskScheduler;
+use crate::app::shell_stream::ShellStream;
+use crate::app::soak_test::{SoakPlan, SoakProgress};
 use crate::app::terminal::TerminalSession;
 
+/// Where a logcat stream reads from: a spawned adb binary, or a `shell:logcat` service opened
+/// directly on the adb server socket.
+pub enum LogcatSource {
+    Process(Child),
+    Socket(TcpStream),
+}
+
+impl LogcatSource {
+    /// Readers for stdout and stderr; the socket carries both on one stream, so its stderr
+    /// reader is empty.
+    pub fn take_output(&mut self) -> std::io::Result<(Box<dyn Read + Send>, Box<dyn Read + Send>)> {
+        match self {
+            LogcatSource::Process(child) => match (child.stdout.take(), child.stderr.take()) {
+                (Some(stdout), Some(stderr)) => Ok((Box::new(stdout), Box::new(stderr))),
+                _ => Err(std::io::Error::other("logcat output is not piped")),
+            },
+            LogcatSource::Socket(stream) => {
+                Ok((Box::new(stream.try_clone()?), Box::new(io::empty())))
+            }
+        }
+    }
+
+    pub fn stop(&mut self) {
+        match self {
+            LogcatSource::Process(child) => {
+                let _ = child.kill();
+                let _ = child.wait();
+            }
+            // Closing the connection makes adbd end the command.
+            LogcatSource::Socket(stream) => {
+                let _ = stream.shutdown(Shutdown::Both);
+            }
+        }
+    }
+}
+
 pub struct LogcatHandle {
+    pub source: LogcatSource,
+    pub stop_flag: Arc<AtomicBool>,
+}
+
+pub struct DmesgHandle {
+    pub child: Child,
+    pub stop_flag: Arc<AtomicBool>,
+    /// `direct` or `su`, see `app::dmesg`.
+    pub mode: String,
+}
+
+pub struct LifecycleStreamHandle {
     pub child: Child,
     pub stop_flag: Arc<AtomicBool>,
+    /// Whether `log.tag.FragmentManager` was raised and must be reset on stop.
+    pub include_fragments: bool,
 }
 
 pub struct PerfMonitorHandle {
@@ -19,15 +82,91 @@ pub struct PerfMonitorHandle {
     pub join: JoinHandle<()>,
 }
 
+pub struct ThermalMonitorHandle {
+    pub stop_flag: Arc<AtomicBool>,
+    pub join: JoinHandle<()>,
+}
+
+pub struct LeakWatchHandle {
+    pub stop_flag: Arc<AtomicBool>,
+    pub join: JoinHandle<()>,
+}
+
+pub struct ApkWatchHandle {
+    pub stop_flag: Arc<AtomicBool>,
+    pub join: JoinHandle<()>,
+}
+
+pub struct CaptureContextHandle {
+    pub serial: String,
+    pub label: String,
+    pub started_at: DateTime<Local>,
+    pub stop_flag: Arc<AtomicBool>,
+    pub logcat: Child,
+    pub log_buffer: Arc<Mutex<CaptureLineBuffer>>,
+    pub perf_samples: Arc<Mutex<Vec<CapturePerfSample>>>,
+    pub installs: Vec<ApkInstallResult>,
+    pub joins: Vec<JoinHandle<()>>,
+}
+
+/// The collector of each device's running soak test, which its monitors feed.
+pub type SoakFeeds = Mutex<HashMap<String, Arc<Mutex<SoakProgress>>>>;
+
+pub struct SoakTestHandle {
+    pub plan: SoakPlan,
+    pub started_at: DateTime<Local>,
+    pub dir: PathBuf,
+    pub stop_flag: Arc<AtomicBool>,
+    pub progress: Arc<Mutex<SoakProgress>>,
+    pub join: JoinHandle<()>,
+    /// `perf`, `net` and/or `thermal`: the monitors to stop with the soak test.
+    pub started_monitors: Vec<String>,
+}
+
 pub struct NetProfilerHandle {
     pub stop_flag: Arc<AtomicBool>,
     pub pinned_uids: Arc<RwLock<Vec<u32>>>,
     pub join: JoinHandle<()>,
 }
 
+/// A background conversion started by `stop_screen_record`.
+pub struct RecordingConversionHandle {
+    pub serial: String,
+    pub format: String,
+    pub started_at: Instant,
+    pub join: JoinHandle<()>,
+}
+
 pub struct RecordingHandle {
     pub child: Child,
     pub remote_path: String,
+    /// Recording length estimate for conversion progress.
+    pub started_at: Instant,
+}
+
+pub struct GestureRecorderHandle {
+    pub child: Child,
+    pub output: Arc<Mutex<String>>,
+    pub join: JoinHandle<()>,
+    pub device: TouchDevice,
+    pub screen_width: u32,
+    pub screen_height: u32,
+}
+
+/// Live `getevent` stream on the leader; each finished stroke is replayed on `followers`.
+pub struct InputBroadcastHandle {
+    pub child: Child,
+    pub join: JoinHandle<()>,
+    pub followers: Vec<String>,
+}
+
+pub struct AudioForwardHandle {
+    pub child: Child,
+    pub codec: String,
+    pub source: String,
+    pub started_at: DateTime<Local>,
+    pub stderr_tail: Arc<Mutex<String>>,
+    pub stderr_join: Option<JoinHandle<()>>,
 }
 
 pub struct BugreportHandle {
@@ -35,16 +174,63 @@ pub struct BugreportHandle {
     pub child: Arc<Mutex<Option<Child>>>,
 }
 
+/// Keyed by the transfer's trace id so the file manager can cancel one of several
+/// concurrent pulls or pushes.
+pub struct FileTransferHandle {
+    pub serial: String,
+    pub direction: String,
+    pub cancel_flag: Arc<AtomicBool>,
+}
+
+/// Last full `list_apps` result per device, so filter and page queries skip adb.
+pub struct AppListSnapshot {
+    pub third_party_only: Option<bool>,
+    pub with_versions: bool,
+    pub apps: Vec<AppInfo>,
+    pub fetched_at: Instant,
+}
+
+pub struct MonkeyRunHandle {
+    pub cancel_flag: Arc<AtomicBool>,
+    pub child: Arc<Mutex<Option<Child>>>,
+}
+
+pub struct DeviceFarmHandle {
+    pub cancel_flag: Arc<AtomicBool>,
+    pub child: Arc<Mutex<Option<Child>>>,
+}
+
 pub struct AppState {
     pub scheduler: Arc<TaskScheduler>,
     pub recording_processes: Mutex<HashMap<String, RecordingHandle>>,
+    /// Background conversions started by `stop_screen_record`, keyed by source path.
+    pub recording_conversions: Mutex<HashMap<String, RecordingConversionHandle>>,
+    pub gesture_recorders: Mutex<HashMap<String, GestureRecorderHandle>>,
+    pub input_broadcasts: Mutex<HashMap<String, InputBroadcastHandle>>,
     pub logcat_processes: Mutex<HashMap<String, LogcatHandle>>,
+    pub dmesg_streams: Mutex<HashMap<String, DmesgHandle>>,
+    pub lifecycle_streams: Mutex<HashMap<String, LifecycleStreamHandle>>,
     pub perf_monitors: Mutex<HashMap<String, PerfMonitorHandle>>,
+    pub perf_aggregator: Arc<Mutex<Option<PerfFrameAggregator>>>,
+    pub thermal_monitors: Mutex<HashMap<String, ThermalMonitorHandle>>,
     pub net_profilers: Mutex<HashMap<String, NetProfilerHandle>>,
+    pub leak_watches: Mutex<HashMap<String, LeakWatchHandle>>,
+    pub apk_watchers: Mutex<HashMap<String, ApkWatchHandle>>,
+    pub capture_contexts: Mutex<HashMap<String, CaptureContextHandle>>,
+    pub soak_tests: Mutex<HashMap<String, SoakTestHandle>>,
+    pub soak_feeds: Arc<SoakFeeds>,
     pub bugreport_processes: Mutex<HashMap<String, BugreportHandle>>,
+    pub file_transfers: Mutex<HashMap<String, FileTransferHandle>>,
+    pub app_lists: Mutex<HashMap<String, AppListSnapshot>>,
+    pub audio_forwards: Mutex<HashMap<String, AudioForwardHandle>>,
+    pub monkey_runs: Mutex<HashMap<String, MonkeyRunHandle>>,
     pub bluetooth_monitors: Mutex<HashMap<String, BluetoothMonitorHandle>>,
     pub device_tracker: Mutex<Option<DeviceTrackerHandle>>,
+    pub adb_tunnel: Mutex<Option<SshTunnelHandle>>,
+    pub device_farm_run: Mutex<Option<DeviceFarmHandle>>,
     pub terminal_sessions: Mutex<HashMap<String, TerminalSession>>,
+    pub shell_streams: Mutex<HashMap<String, ShellStream>>,
+    pub ui_captures: Mutex<HashMap<String, VecDeque<UiCaptureRecord>>>,
 }
 
 impl AppState {
@@ -52,13 +238,33 @@ impl AppState {
         Self {
             scheduler: Arc::new(TaskScheduler::new(8)),
             recording_processes: Mutex::new(HashMap::new()),
+            recording_conversions: Mutex::new(HashMap::new()),
+            gesture_recorders: Mutex::new(HashMap::new()),
+            input_broadcasts: Mutex::new(HashMap::new()),
             logcat_processes: Mutex::new(HashMap::new()),
+            dmesg_streams: Mutex::new(HashMap::new()),
+            lifecycle_streams: Mutex::new(HashMap::new()),
             perf_monitors: Mutex::new(HashMap::new()),
+            perf_aggregator: Arc::new(Mutex::new(None)),
+            thermal_monitors: Mutex::new(HashMap::new()),
             net_profilers: Mutex::new(HashMap::new()),
+            leak_watches: Mutex::new(HashMap::new()),
+            apk_watchers: Mutex::new(HashMap::new()),
+            capture_contexts: Mutex::new(HashMap::new()),
+            soak_tests: Mutex::new(HashMap::new()),
+            soak_feeds: Arc::new(Mutex::new(HashMap::new())),
             bugreport_processes: Mutex::new(HashMap::new()),
+            file_transfers: Mutex::new(HashMap::new()),
+            app_lists: Mutex::new(HashMap::new()),
+            audio_forwards: Mutex::new(HashMap::new()),
+            monkey_runs: Mutex::new(HashMap::new()),
             bluetooth_monitors: Mutex::new(HashMap::new()),
             device_tracker: Mutex::new(None),
+            adb_tunnel: Mutex::new(None),
+            device_farm_run: Mutex::new(None),
             terminal_sessions: Mutex::new(HashMap::new()),
+            shell_streams: Mutex::new(HashMap::new()),
+            ui_captures: Mutex::new(HashMap::new()),
         }
     }
 }
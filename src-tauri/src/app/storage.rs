@@ -0,0 +1,127 @@
+use std::path::Path;
+use std::time::Duration;
+
+use tracing::warn;
+
+use crate::app::adb::parse::parse_df_available_kb;
+use crate::app::adb::runner::run_command_with_timeout;
+use crate::app::error::AppError;
+
+/// Upper end of typical `bugreportz` archive sizes; the real size is only known once the
+/// report has been generated on the device.
+pub const BUGREPORT_ESTIMATED_BYTES: u64 = 256 * 1024 * 1024;
+
+const STORAGE_HEADROOM_BYTES: u64 = 32 * 1024 * 1024;
+const HOST_FREE_SPACE_TIMEOUT: Duration = Duration::from_secs(10);
+
+pub fn required_host_bytes(estimated_bytes: u64) -> u64 {
+    estimated_bytes.saturating_add(STORAGE_HEADROOM_BYTES)
+}
+
+pub fn check_host_storage(available_bytes: u64, estimated_bytes: u64) -> Result<(), String> {
+    let required = required_host_bytes(estimated_bytes);
+    if available_bytes >= required {
+        return Ok(());
+    }
+    Err(format!(
+        "Insufficient host storage: {} required, {} available",
+        format_megabytes(required),
+        format_megabytes(available_bytes)
+    ))
+}
+
+pub fn format_megabytes(bytes: u64) -> String {
+    format!("{:.1} MB", bytes as f64 / (1024.0 * 1024.0))
+}
+
+pub fn windows_drive_letter(path: &Path) -> Option<char> {
+    let text = path.to_string_lossy();
+    let mut chars = text.chars();
+    let letter = chars.next()?;
+    if letter.is_ascii_alphabetic() && chars.next() == Some(':') {
+        Some(letter.to_ascii_uppercase())
+    } else {
+        None
+    }
+}
+
+pub fn host_available_bytes(dir: &Path, trace_id: &str) -> Result<u64, String> {
+    if cfg!(windows) {
+        let letter = windows_drive_letter(dir)
+            .ok_or_else(|| "Free space lookup requires a drive letter path".to_string())?;
+        let args = vec![
+            "-NoProfile".to_string(),
+            "-Command".to_string(),
+            format!("(Get-PSDrive -Name {letter}).Free"),
+        ];
+        let output =
+            run_command_with_timeout("powershell", &args, HOST_FREE_SPACE_TIMEOUT, trace_id)
+                .map_err(|err| err.error)?;
+        if output.exit_code.unwrap_or_default() != 0 {
+            return Err(format!("Get-PSDrive failed: {}", output.stderr.trim()));
+        }
+        output
+            .stdout
+            .trim()
+            .parse::<u64>()
+            .map_err(|_| "Invalid Get-PSDrive free space value".to_string())
+    } else {
+        let args = vec!["-Pk".to_string(), dir.to_string_lossy().to_string()];
+        let output = run_command_with_timeout("df", &args, HOST_FREE_SPACE_TIMEOUT, trace_id)
+            .map_err(|err| err.error)?;
+        if output.exit_code.unwrap_or_default() != 0 {
+            return Err(format!("df failed: {}", output.stderr.trim()));
+        }
+        parse_df_available_kb(&output.stdout).map(|kb| kb.saturating_mul(1024))
+    }
+}
+
+/// Fail early with `ERR_INSUFFICIENT_HOST_STORAGE` when `dir` cannot hold an artifact of
+/// roughly `estimated_bytes`. If free space cannot be determined the check is skipped.
+pub fn ensure_host_storage(
+    dir: &Path,
+    estimated_bytes: u64,
+    trace_id: &str,
+) -> Result<(), AppError> {
+    let available = match host_available_bytes(dir, trace_id) {
+        Ok(value) => value,
+        Err(err) => {
+            warn!(trace_id = %trace_id, error = %err, "skipping host storage preflight");
+            return Ok(());
+        }
+    };
+    check_host_storage(available, estimated_bytes)
+        .map_err(|message| AppError::insufficient_host_storage(message, trace_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn check_host_storage_requires_headroom() {
+        let estimated = 100 * 1024 * 1024;
+        assert!(check_host_storage(required_host_bytes(estimated), estimated).is_ok());
+        let err = check_host_storage(estimated, estimated).unwrap_err();
+        assert!(err.starts_with("Insufficient host storage: 132.0 MB required"));
+    }
+
+    #[test]
+    fn windows_drive_letter_only_accepts_drive_paths() {
+        assert_eq!(windows_drive_letter(Path::new("c:\\out")), Some('C'));
+        assert_eq!(windows_drive_letter(Path::new("\\\\server\\share")), None);
+        assert_eq!(windows_drive_letter(Path::new("/tmp/out")), None);
+    }
+
+    #[test]
+    fn ensure_host_storage_accepts_small_artifacts() {
+        if cfg!(windows) {
+            return;
+        }
+        let dir = TempDir::new().expect("tmp");
+        ensure_host_storage(dir.path(), 1024, "trace-storage").expect("storage");
+        let err = ensure_host_storage(dir.path(), u64::MAX / 2, "trace-storage").unwrap_err();
+        assert_eq!(err.code, "ERR_INSUFFICIENT_HOST_STORAGE");
+    }
+}
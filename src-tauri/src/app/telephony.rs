@@ -0,0 +1,295 @@
+use std::collections::HashMap;
+
+use regex::Regex;
+
+use crate::app::models::{TelephonyInfo, TelephonySlot};
+
+const REGISTRY_MARKER: &str = "@@registry";
+
+/// Per-slot `gsm.*` properties (comma-separated, one entry per SIM slot) followed by
+/// `dumpsys telephony.registry` for service, signal and data state. Subscriber identifiers
+/// (`service call iphonesubinfo`) are never read: IMSI and phone numbers are personal data.
+pub const TELEPHONY_SCRIPT: &str = concat!(
+    "for p in gsm.sim.state gsm.sim.operator.alpha gsm.operator.alpha gsm.network.type ",
+    "gsm.operator.isroaming; do echo \"$p=$(getprop $p)\"; done; ",
+    "echo @@registry; ",
+    "dumpsys telephony.registry 2>/dev/null",
+);
+
+/// `TelephonyManager.NETWORK_TYPE_*`, indexed by value.
+const NETWORK_TYPES: [&str; 21] = [
+    "Unknown", "GPRS", "EDGE", "UMTS", "CDMA", "EVDO_0", "EVDO_A", "1xRTT", "HSDPA", "HSUPA",
+    "HSPA", "iDEN", "EVDO_B", "LTE", "eHRPD", "HSPA+", "GSM", "TD_SCDMA", "IWLAN", "LTE_CA", "NR",
+];
+
+/// `TelephonyManager.DATA_*` connection states.
+const DATA_STATES: [&str; 5] = [
+    "disconnected",
+    "connecting",
+    "connected",
+    "suspended",
+    "disconnecting",
+];
+
+/// Integer fields in `CellSignalStrength*` dumps use `Integer.MAX_VALUE` for "unavailable".
+const SIGNAL_UNAVAILABLE: i32 = i32::MAX;
+
+#[derive(Debug, Default, PartialEq, Eq)]
+struct RegistryPhone {
+    service_state: Option<String>,
+    operator: Option<String>,
+    roaming: Option<bool>,
+    signal_level: Option<u8>,
+    signal_dbm: Option<i32>,
+    data_state: Option<String>,
+    network_type: Option<String>,
+}
+
+fn slot_values(value: Option<&String>) -> Vec<Option<String>> {
+    value
+        .filter(|value| !value.trim().is_empty())
+        .map(|value| {
+            value
+                .split(',')
+                .map(|entry| {
+                    let entry = entry.trim();
+                    (!entry.is_empty()).then(|| entry.to_string())
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn capture(regex: &Regex, text: &str) -> Option<String> {
+    regex
+        .captures(text)
+        .and_then(|caps| caps.get(1))
+        .map(|value| value.as_str().trim().to_string())
+        .filter(|value| !value.is_empty() && value != "null")
+}
+
+/// The strongest reported level, and the first usable dBm reading (RSRP for LTE/NR, RSSI
+/// otherwise) of the technology with that level.
+fn parse_signal(signal: &str) -> (Option<u8>, Option<i32>) {
+    let level_re = Regex::new(r"\blevel=(\d)").expect("signal level regex should compile");
+    let dbm_re = Regex::new(r"\b(?:ssRsrp|rsrp|rscp|rssi|mDbm)=(-?\d+)")
+        .expect("signal dbm regex should compile");
+    let mut best: (Option<u8>, Option<i32>) = (None, None);
+    // Each technology is its own `CellSignalStrength...:` section of the dump.
+    for section in signal.split("CellSignalStrength").skip(1) {
+        let Some(level) = level_re
+            .captures(section)
+            .and_then(|caps| caps[1].parse::<u8>().ok())
+        else {
+            continue;
+        };
+        let dbm = dbm_re
+            .captures_iter(section)
+            .filter_map(|caps| caps[1].parse::<i32>().ok())
+            .find(|value| *value != SIGNAL_UNAVAILABLE && *value < 0);
+        if best.0.is_none_or(|current| level > current) {
+            best = (Some(level), dbm);
+        }
+    }
+    best
+}
+
+fn parse_registry_phone(block: &str) -> RegistryPhone {
+    let service_re =
+        Regex::new(r"mVoiceRegState=\d+\((\w+)\)").expect("service state regex should compile");
+    let operator_re =
+        Regex::new(r"mOperatorAlphaLong=([^,}]*)").expect("operator regex should compile");
+    let roaming_re = Regex::new(r"mVoiceRoamingType=(\w+)").expect("roaming regex should compile");
+    let mut phone = RegistryPhone::default();
+    for line in block.lines().map(str::trim) {
+        if let Some(state) = line.strip_prefix("mServiceState=") {
+            phone.service_state = capture(&service_re, state);
+            phone.operator = capture(&operator_re, state);
+            phone.roaming = capture(&roaming_re, state).and_then(|kind| match kind.as_str() {
+                "home" => Some(false),
+                "unknown" => None,
+                _ => Some(true),
+            });
+        } else if let Some(signal) = line.strip_prefix("mSignalStrength=") {
+            (phone.signal_level, phone.signal_dbm) = parse_signal(signal);
+        } else if let Some(state) = line.strip_prefix("mDataConnectionState=") {
+            phone.data_state = state
+                .trim()
+                .parse::<usize>()
+                .ok()
+                .and_then(|index| DATA_STATES.get(index))
+                .map(|state| state.to_string());
+        } else if let Some(kind) = line.strip_prefix("mDataConnectionNetworkType=") {
+            phone.network_type = kind
+                .trim()
+                .parse::<usize>()
+                .ok()
+                .and_then(|index| NETWORK_TYPES.get(index))
+                .map(|kind| kind.to_string());
+        }
+    }
+    phone
+}
+
+/// Blocks per `Phone Id=N`; releases before multi-SIM support print a single unnamed block.
+fn parse_registry(registry: &str) -> Vec<RegistryPhone> {
+    let mut blocks: Vec<String> = Vec::new();
+    for line in registry.lines() {
+        if line.trim().starts_with("Phone Id=") {
+            blocks.push(String::new());
+        } else if let Some(block) = blocks.last_mut() {
+            block.push_str(line);
+            block.push('\n');
+        }
+    }
+    if blocks.is_empty() {
+        let phone = parse_registry_phone(registry);
+        return if phone == RegistryPhone::default() {
+            Vec::new()
+        } else {
+            vec![phone]
+        };
+    }
+    blocks
+        .iter()
+        .map(|block| parse_registry_phone(block))
+        .collect()
+}
+
+pub fn parse_telephony_info(serial: &str, output: &str) -> TelephonyInfo {
+    let (props, registry) = output.split_once(REGISTRY_MARKER).unwrap_or((output, ""));
+    let props: HashMap<String, String> = props
+        .lines()
+        .filter_map(|line| line.trim().split_once('='))
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect();
+    let sim_states = slot_values(props.get("gsm.sim.state"));
+    let sim_operators = slot_values(props.get("gsm.sim.operator.alpha"));
+    let operators = slot_values(props.get("gsm.operator.alpha"));
+    let network_types = slot_values(props.get("gsm.network.type"));
+    let roaming = slot_values(props.get("gsm.operator.isroaming"));
+    let phones = parse_registry(registry);
+
+    let count = [
+        sim_states.len(),
+        sim_operators.len(),
+        operators.len(),
+        network_types.len(),
+        phones.len(),
+    ]
+    .into_iter()
+    .max()
+    .unwrap_or(0);
+    let at = |values: &[Option<String>], index: usize| values.get(index).cloned().flatten();
+    let slots = (0..count)
+        .map(|slot| {
+            let phone = phones.get(slot);
+            let network_type = at(&network_types, slot)
+                .filter(|kind| !kind.eq_ignore_ascii_case("unknown"))
+                .or_else(|| phone.and_then(|phone| phone.network_type.clone()));
+            TelephonySlot {
+                slot,
+                sim_state: at(&sim_states, slot),
+                sim_operator: at(&sim_operators, slot),
+                network_operator: at(&operators, slot)
+                    .or_else(|| phone.and_then(|phone| phone.operator.clone())),
+                network_type,
+                roaming: at(&roaming, slot)
+                    .map(|value| value == "true")
+                    .or_else(|| phone.and_then(|phone| phone.roaming)),
+                service_state: phone.and_then(|phone| phone.service_state.clone()),
+                data_state: phone.and_then(|phone| phone.data_state.clone()),
+                signal_level: phone.and_then(|phone| phone.signal_level),
+                signal_dbm: phone.and_then(|phone| phone.signal_dbm),
+            }
+        })
+        .collect();
+
+    TelephonyInfo {
+        serial: serial.to_string(),
+        slots,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DUAL_SIM: &str = concat!(
+        "gsm.sim.state=LOADED,ABSENT\n",
+        "gsm.sim.operator.alpha=T-Mobile,\n",
+        "gsm.operator.alpha=T-Mobile,\n",
+        "gsm.network.type=Unknown,Unknown\n",
+        "gsm.operator.isroaming=false,false\n",
+        "@@registry\n",
+        "last known state:\n",
+        "  Phone Id=0\n",
+        "  mCallState=0\n",
+        "  mServiceState={mVoiceRegState=0(IN_SERVICE), mDataRegState=0(IN_SERVICE), ",
+        "mOperatorAlphaLong=T-Mobile, mOperatorAlphaShort=TMO, mVoiceRoamingType=home}\n",
+        "  mSignalStrength=SignalStrength:{mGsm=CellSignalStrengthGsm: rssi=2147483647 ber=2147483647 ",
+        "mTa=2147483647 mLevel=0, mLte=CellSignalStrengthLte: rssi=-65 rsrp=-98 rsrq=-11 ",
+        "rssnr=2147483647 level=3, mNr=CellSignalStrengthNr:{ csiRsrp = 2147483647 ssRsrp = ",
+        "2147483647 level = 0 }, primary=CellSignalStrengthLte}\n",
+        "  mDataConnectionState=2\n",
+        "  mDataConnectionNetworkType=13\n",
+        "  Phone Id=1\n",
+        "  mServiceState={mVoiceRegState=1(OUT_OF_SERVICE), mOperatorAlphaLong=null, ",
+        "mVoiceRoamingType=unknown}\n",
+        "  mDataConnectionState=0\n",
+        "  mDataConnectionNetworkType=0\n",
+    );
+
+    #[test]
+    fn merges_properties_and_registry_per_slot() {
+        let info = parse_telephony_info("R58M", DUAL_SIM);
+        assert_eq!(info.slots.len(), 2);
+        let primary = &info.slots[0];
+        assert_eq!(primary.sim_state.as_deref(), Some("LOADED"));
+        assert_eq!(primary.network_operator.as_deref(), Some("T-Mobile"));
+        assert_eq!(primary.network_type.as_deref(), Some("LTE"));
+        assert_eq!(primary.roaming, Some(false));
+        assert_eq!(primary.service_state.as_deref(), Some("IN_SERVICE"));
+        assert_eq!(primary.data_state.as_deref(), Some("connected"));
+        assert_eq!(
+            (primary.signal_level, primary.signal_dbm),
+            (Some(3), Some(-65))
+        );
+
+        let secondary = &info.slots[1];
+        assert_eq!(secondary.sim_state.as_deref(), Some("ABSENT"));
+        assert_eq!(secondary.sim_operator, None);
+        assert_eq!(secondary.network_operator, None);
+        assert_eq!(secondary.network_type.as_deref(), Some("Unknown"));
+        assert_eq!(secondary.service_state.as_deref(), Some("OUT_OF_SERVICE"));
+        assert_eq!(secondary.data_state.as_deref(), Some("disconnected"));
+        assert_eq!(secondary.signal_level, None);
+    }
+
+    #[test]
+    fn handles_wifi_only_devices_and_single_block_registries() {
+        let wifi_only = parse_telephony_info(
+            "tablet",
+            "gsm.sim.state=\ngsm.operator.alpha=\n@@registry\n",
+        );
+        assert!(wifi_only.slots.is_empty());
+
+        let legacy = parse_telephony_info(
+            "old",
+            concat!(
+                "@@registry\n",
+                "last known state:\n",
+                "  mServiceState={mVoiceRegState=0(IN_SERVICE), mOperatorAlphaLong=Vodafone, ",
+                "mVoiceRoamingType=international}\n",
+                "  mDataConnectionNetworkType=3\n",
+            ),
+        );
+        assert_eq!(legacy.slots.len(), 1);
+        assert_eq!(
+            legacy.slots[0].network_operator.as_deref(),
+            Some("Vodafone")
+        );
+        assert_eq!(legacy.slots[0].network_type.as_deref(), Some("UMTS"));
+        assert_eq!(legacy.slots[0].roaming, Some(true));
+    }
+}
@@ -0,0 +1,258 @@
+use crate::app::models::{ThermalCrossing, ThermalReading};
+
+pub const MARK_THERMAL_SERVICE: &str = "__LBT_THERMAL_SERVICE__";
+pub const MARK_THERMAL_ZONES: &str = "__LBT_THERMAL_ZONES__";
+
+pub fn build_thermal_script() -> String {
+    [
+        format!("echo {MARK_THERMAL_SERVICE}"),
+        "dumpsys thermalservice 2>/dev/null".to_string(),
+        format!("echo {MARK_THERMAL_ZONES}"),
+        r#"for zone in /sys/class/thermal/thermal_zone*; do echo "$(cat "$zone/type" 2>/dev/null) $(cat "$zone/temp" 2>/dev/null)"; done"#.to_string(),
+    ]
+    .join("; ")
+}
+
+/// Split the script output into the `dumpsys thermalservice` and thermal zone sections.
+pub fn split_thermal_sections(output: &str) -> (String, String) {
+    let mut service = String::new();
+    let mut zones = String::new();
+    let mut current: Option<&mut String> = None;
+    for line in output.lines() {
+        match line.trim() {
+            MARK_THERMAL_SERVICE => current = Some(&mut service),
+            MARK_THERMAL_ZONES => current = Some(&mut zones),
+            _ => {
+                if let Some(buf) = current.as_deref_mut() {
+                    buf.push_str(line);
+                    buf.push('\n');
+                }
+            }
+        }
+    }
+    (service, zones)
+}
+
+/// `Thermal Status: N` is the framework-level throttling state (0 = none .. 6 = shutdown).
+pub fn parse_thermal_status(dumpsys: &str) -> Option<u8> {
+    dumpsys.lines().find_map(|line| {
+        line.trim()
+            .strip_prefix("Thermal Status:")
+            .and_then(|value| value.trim().parse::<u8>().ok())
+    })
+}
+
+pub fn throttle_status_label(status: u8) -> &'static str {
+    match status {
+        0 => "none",
+        1 => "light",
+        2 => "moderate",
+        3 => "severe",
+        4 => "critical",
+        5 => "emergency",
+        6 => "shutdown",
+        _ => "unknown",
+    }
+}
+
+/// Map `Temperature.mType`; the BCL types report voltage/current/percentage, not temperatures.
+fn temperature_kind(kind: i32) -> Option<&'static str> {
+    match kind {
+        0 => Some("cpu"),
+        1 => Some("gpu"),
+        2 => Some("battery"),
+        3 => Some("skin"),
+        4 => Some("usb_port"),
+        5 => Some("power_amplifier"),
+        9 => Some("npu"),
+        6..=8 => None,
+        _ => Some("unknown"),
+    }
+}
+
+fn parse_hal_temperature(line: &str) -> Option<ThermalReading> {
+    let body = line
+        .trim()
+        .strip_prefix("Temperature{")?
+        .strip_suffix('}')?;
+    let mut value = None;
+    let mut kind = None;
+    let mut name = None;
+    let mut status = None;
+    for field in body.split(',') {
+        let (key, raw) = field.trim().split_once('=')?;
+        match key {
+            "mValue" => value = raw.parse::<f64>().ok(),
+            "mType" => kind = raw.parse::<i32>().ok(),
+            "mName" => name = Some(raw.to_string()),
+            "mStatus" => status = raw.parse::<u8>().ok(),
+            _ => {}
+        }
+    }
+    let value = value.filter(|value| value.is_finite())?;
+    Some(ThermalReading {
+        name: name?,
+        kind: temperature_kind(kind?)?.to_string(),
+        temp_decic: (value * 10.0).round() as i32,
+        throttle_status: status,
+    })
+}
+
+/// Read temperatures reported by the thermal HAL, preferring the live section over the cache.
+pub fn parse_thermal_service_temperatures(dumpsys: &str) -> Vec<ThermalReading> {
+    let mut current_hal = Vec::new();
+    let mut cached = Vec::new();
+    let mut section: Option<bool> = None;
+    for line in dumpsys.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("Temperature{") {
+            match section {
+                Some(true) => current_hal.extend(parse_hal_temperature(trimmed)),
+                Some(false) => cached.extend(parse_hal_temperature(trimmed)),
+                None => {}
+            }
+            continue;
+        }
+        section = match trimmed {
+            "Current temperatures from HAL:" => Some(true),
+            "Cached temperatures:" => Some(false),
+            _ => None,
+        };
+    }
+    if current_hal.is_empty() {
+        cached
+    } else {
+        current_hal
+    }
+}
+
+/// Parse `<type> <temp>` lines from `/sys/class/thermal/thermal_zone*`. Most kernels report
+/// millidegrees, some report whole degrees.
+pub fn parse_thermal_zones(output: &str) -> Vec<ThermalReading> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (name, raw) = line.trim().rsplit_once(' ')?;
+            let name = name.trim();
+            let raw = raw.trim().parse::<i64>().ok()?;
+            if name.is_empty() {
+                return None;
+            }
+            let temp_decic = if raw.abs() >= 1000 {
+                raw / 100
+            } else {
+                raw * 10
+            };
+            // Disabled zones often report large negative sentinels.
+            if !(-400..=2000).contains(&temp_decic) {
+                return None;
+            }
+            Some(ThermalReading {
+                name: name.to_string(),
+                kind: "zone".to_string(),
+                temp_decic: temp_decic as i32,
+                throttle_status: None,
+            })
+        })
+        .collect()
+}
+
+/// Compare two samples and report sensors that moved across `threshold_decic`.
+pub fn detect_threshold_crossings(
+    previous: &[ThermalReading],
+    current: &[ThermalReading],
+    threshold_decic: i32,
+) -> Vec<ThermalCrossing> {
+    current
+        .iter()
+        .filter_map(|reading| {
+            let before = previous.iter().find(|prev| prev.name == reading.name)?;
+            let was_above = before.temp_decic >= threshold_decic;
+            let is_above = reading.temp_decic >= threshold_decic;
+            (was_above != is_above).then(|| ThermalCrossing {
+                name: reading.name.clone(),
+                temp_decic: reading.temp_decic,
+                threshold_decic,
+                rising: is_above,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const THERMALSERVICE: &str = "\
+IsStatusOverride: false
+ThermalEventListeners:
+	callbacks: 2
+Thermal Status: 2
+Cached temperatures:
+	Temperature{mValue=40.1, mType=3, mName=skin, mStatus=1}
+HAL Ready: true
+HAL connection:
+	ThermalHAL 2.0 connected: yes
+Current temperatures from HAL:
+	Temperature{mValue=36.8, mType=2, mName=battery, mStatus=0}
+	Temperature{mValue=47.250004, mType=3, mName=skin, mStatus=2}
+	Temperature{mValue=3950.0, mType=6, mName=vbat, mStatus=0}
+Current cooling devices from HAL:
+	CoolingDevice{mValue=0, mType=2, mName=cpu0}
+";
+
+    #[test]
+    fn parses_thermal_service_status_and_temperatures() {
+        assert_eq!(parse_thermal_status(THERMALSERVICE), Some(2));
+        assert_eq!(throttle_status_label(2), "moderate");
+        let readings = parse_thermal_service_temperatures(THERMALSERVICE);
+        assert_eq!(readings.len(), 2);
+        assert_eq!(readings[0].name, "battery");
+        assert_eq!(readings[0].temp_decic, 368);
+        assert_eq!(readings[1].kind, "skin");
+        assert_eq!(readings[1].temp_decic, 473);
+        assert_eq!(readings[1].throttle_status, Some(2));
+
+        let cached_only =
+            "Cached temperatures:\n\tTemperature{mValue=40.1, mType=3, mName=skin, mStatus=1}\n";
+        assert_eq!(
+            parse_thermal_service_temperatures(cached_only)[0].temp_decic,
+            401
+        );
+    }
+
+    #[test]
+    fn parses_thermal_zones_and_sections() {
+        let output = format!(
+            "{MARK_THERMAL_SERVICE}\nThermal Status: 0\n{MARK_THERMAL_ZONES}\ncpu-0-0 45200\nbattery 31\nxo-therm -273000\n broken\n"
+        );
+        let (service, zones) = split_thermal_sections(&output);
+        assert_eq!(parse_thermal_status(&service), Some(0));
+        let readings = parse_thermal_zones(&zones);
+        assert_eq!(readings.len(), 2);
+        assert_eq!(readings[0].name, "cpu-0-0");
+        assert_eq!(readings[0].temp_decic, 452);
+        assert_eq!(readings[1].temp_decic, 310);
+    }
+
+    #[test]
+    fn detects_threshold_crossings_both_ways() {
+        let reading = |name: &str, temp_decic: i32| ThermalReading {
+            name: name.to_string(),
+            kind: "skin".to_string(),
+            temp_decic,
+            throttle_status: None,
+        };
+        let previous = vec![reading("skin", 440), reading("cpu", 470)];
+        let current = vec![
+            reading("skin", 455),
+            reading("cpu", 430),
+            reading("gpu", 500),
+        ];
+        let crossings = detect_threshold_crossings(&previous, &current, 450);
+        assert_eq!(crossings.len(), 2);
+        assert!(crossings[0].rising);
+        assert_eq!(crossings[0].name, "skin");
+        assert!(!crossings[1].rising);
+    }
+}
@@ -0,0 +1,217 @@
+use std::collections::VecDeque;
+use std::sync::{Mutex, OnceLock};
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use tracing::warn;
+
+use crate::app::error::AppError;
+use crate::app::models::{TimelineEvent, TimelineQuery};
+
+pub const TIMELINE_MEMORY_LIMIT: usize = 5000;
+const TIMELINE_DEFAULT_QUERY_LIMIT: usize = 500;
+
+pub const TIMELINE_INSTALL: &str = "install";
+pub const TIMELINE_CRASH: &str = "crash";
+pub const TIMELINE_ANR: &str = "anr";
+pub const TIMELINE_PERF_ALERT: &str = "perf_alert";
+pub const TIMELINE_SCREEN_RECORD: &str = "screen_record";
+pub const TIMELINE_REBOOT: &str = "reboot";
+pub const TIMELINE_SCREEN: &str = "screen";
+
+struct StoredEvent {
+    at: DateTime<Utc>,
+    event: TimelineEvent,
+}
+
+struct TimelineStore {
+    events: VecDeque<StoredEvent>,
+    next_id: u64,
+}
+
+fn store() -> &'static Mutex<TimelineStore> {
+    static STORE: OnceLock<Mutex<TimelineStore>> = OnceLock::new();
+    STORE.get_or_init(|| {
+        Mutex::new(TimelineStore {
+            events: VecDeque::new(),
+            next_id: 1,
+        })
+    })
+}
+
+/// Append an event to the in-memory device timeline shared by all subsystems.
+pub fn record_timeline_event(serial: &str, kind: &str, summary: &str, trace_id: &str) {
+    let at = Utc::now();
+    let Ok(mut guard) = store().lock() else {
+        warn!(trace_id = %trace_id, "timeline store poisoned");
+        return;
+    };
+    let event = TimelineEvent {
+        id: guard.next_id,
+        serial: serial.to_string(),
+        kind: kind.to_string(),
+        timestamp: at.to_rfc3339_opts(SecondsFormat::Millis, true),
+        summary: summary.to_string(),
+        trace_id: trace_id.to_string(),
+    };
+    guard.next_id += 1;
+    guard.events.push_back(StoredEvent { at, event });
+    while guard.events.len() > TIMELINE_MEMORY_LIMIT {
+        guard.events.pop_front();
+    }
+}
+
+/// Recognize crash and ANR markers in a streamed logcat line.
+pub fn classify_logcat_line(line: &str) -> Option<(&'static str, String)> {
+    if let Some((_, rest)) = line.split_once("FATAL EXCEPTION:") {
+        return Some((
+            TIMELINE_CRASH,
+            format!("Fatal exception on thread {}", rest.trim()),
+        ));
+    }
+    if let Some((_, rest)) = line.split_once(">>> ") {
+        if let Some((process, _)) = rest.split_once(" <<<") {
+            return Some((
+                TIMELINE_CRASH,
+                format!("Native crash in {}", process.trim()),
+            ));
+        }
+    }
+    if let Some((_, rest)) = line.split_once("ANR in ") {
+        let process = rest.split_whitespace().next().unwrap_or_default();
+        if !process.is_empty() {
+            return Some((TIMELINE_ANR, format!("ANR in {process}")));
+        }
+    }
+    None
+}
+
+pub fn parse_time_bound(value: Option<&str>, field: &str) -> Result<Option<DateTime<Utc>>, String> {
+    match value.map(str::trim).filter(|value| !value.is_empty()) {
+        Some(value) => DateTime::parse_from_rfc3339(value)
+            .map(|parsed| Some(parsed.with_timezone(&Utc)))
+            .map_err(|_| format!("{field} must be an RFC 3339 timestamp")),
+        None => Ok(None),
+    }
+}
+
+/// Matching events in chronological order; when more than `limit` match, the newest are kept.
+fn filter_timeline<'a>(
+    events: impl DoubleEndedIterator<Item = &'a StoredEvent>,
+    query: &TimelineQuery,
+) -> Result<Vec<TimelineEvent>, String> {
+    let serial = query.serial.trim();
+    if serial.is_empty() {
+        return Err("serial is required".to_string());
+    }
+    let since = parse_time_bound(query.since.as_deref(), "since")?;
+    let until = parse_time_bound(query.until.as_deref(), "until")?;
+    let limit = query
+        .limit
+        .unwrap_or(TIMELINE_DEFAULT_QUERY_LIMIT)
+        .clamp(1, TIMELINE_MEMORY_LIMIT);
+
+    let mut matched: Vec<TimelineEvent> = events
+        .rev()
+        .filter(|stored| stored.event.serial == serial)
+        .filter(|stored| since.is_none_or(|since| stored.at >= since))
+        .filter(|stored| until.is_none_or(|until| stored.at <= until))
+        .filter(|stored| query.kinds.is_empty() || query.kinds.contains(&stored.event.kind))
+        .take(limit)
+        .map(|stored| stored.event.clone())
+        .collect();
+    matched.reverse();
+    Ok(matched)
+}
+
+pub fn query_timeline_events(
+    query: &TimelineQuery,
+    trace_id: &str,
+) -> Result<Vec<TimelineEvent>, AppError> {
+    let guard = store()
+        .lock()
+        .map_err(|_| AppError::system("Timeline locked", trace_id))?;
+    filter_timeline(guard.events.iter(), query)
+        .map_err(|message| AppError::validation(message, trace_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stored(id: u64, serial: &str, kind: &str, at: &str) -> StoredEvent {
+        let at = DateTime::parse_from_rfc3339(at)
+            .unwrap()
+            .with_timezone(&Utc);
+        StoredEvent {
+            at,
+            event: TimelineEvent {
+                id,
+                serial: serial.to_string(),
+                kind: kind.to_string(),
+                timestamp: at.to_rfc3339_opts(SecondsFormat::Millis, true),
+                summary: format!("event {id}"),
+                trace_id: "trace".to_string(),
+            },
+        }
+    }
+
+    fn query(serial: &str) -> TimelineQuery {
+        TimelineQuery {
+            serial: serial.to_string(),
+            ..TimelineQuery::default()
+        }
+    }
+
+    #[test]
+    fn filters_by_serial_range_and_kind_in_order() {
+        let events = [
+            stored(1, "a", TIMELINE_INSTALL, "2024-05-01T10:00:00Z"),
+            stored(2, "b", TIMELINE_CRASH, "2024-05-01T10:01:00Z"),
+            stored(3, "a", TIMELINE_CRASH, "2024-05-01T10:02:00Z"),
+            stored(4, "a", TIMELINE_REBOOT, "2024-05-01T10:03:00Z"),
+        ];
+        let ids = |result: Vec<TimelineEvent>| result.iter().map(|e| e.id).collect::<Vec<_>>();
+
+        assert_eq!(
+            ids(filter_timeline(events.iter(), &query("a")).unwrap()),
+            [1, 3, 4]
+        );
+
+        let mut ranged = query("a");
+        ranged.since = Some("2024-05-01T10:01:30Z".to_string());
+        ranged.until = Some("2024-05-01T12:02:30+02:00".to_string());
+        assert_eq!(ids(filter_timeline(events.iter(), &ranged).unwrap()), [3]);
+
+        let mut kinds = query("a");
+        kinds.kinds = vec![TIMELINE_INSTALL.to_string(), TIMELINE_REBOOT.to_string()];
+        kinds.limit = Some(1);
+        assert_eq!(ids(filter_timeline(events.iter(), &kinds).unwrap()), [4]);
+    }
+
+    #[test]
+    fn rejects_missing_serial_and_bad_bounds() {
+        assert!(filter_timeline(std::iter::empty(), &query(" ")).is_err());
+        let mut bad = query("a");
+        bad.since = Some("yesterday".to_string());
+        assert!(filter_timeline(std::iter::empty(), &bad).is_err());
+    }
+
+    #[test]
+    fn classifies_crash_and_anr_lines() {
+        assert_eq!(
+            classify_logcat_line("E AndroidRuntime: FATAL EXCEPTION: main"),
+            Some((TIMELINE_CRASH, "Fatal exception on thread main".to_string()))
+        );
+        assert_eq!(
+            classify_logcat_line(
+                "F DEBUG   : pid: 4242, tid: 4242, name: app  >>> com.example <<<"
+            ),
+            Some((TIMELINE_CRASH, "Native crash in com.example".to_string()))
+        );
+        assert_eq!(
+            classify_logcat_line("E ActivityManager: ANR in com.example (com.example/.Main)"),
+            Some((TIMELINE_ANR, "ANR in com.example".to_string()))
+        );
+        assert_eq!(classify_logcat_line("I ActivityManager: Start proc"), None);
+    }
+}
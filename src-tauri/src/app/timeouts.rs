@@ -0,0 +1,139 @@
+use std::path::PathBuf;
+use std::sync::{OnceLock, RwLock};
+use std::time::Duration;
+
+use tracing::warn;
+
+use crate::app::config::{config_path, load_config, TimeoutSettings};
+
+pub const DEFAULT_PROBE_TIMEOUT_SECS: u64 = 5;
+pub const DEFAULT_DEVICE_COMMAND_TIMEOUT_SECS: u64 = 10;
+pub const DEFAULT_INSTALL_TIMEOUT_SECS: u64 = 180;
+pub const DEFAULT_TRANSFER_TIMEOUT_SECS: u64 = 600;
+
+const PROBE_TIMEOUT_BOUNDS: (u64, u64) = (1, 120);
+const DEVICE_COMMAND_TIMEOUT_BOUNDS: (u64, u64) = (1, 600);
+const INSTALL_TIMEOUT_BOUNDS: (u64, u64) = (30, 3600);
+const TRANSFER_TIMEOUT_BOUNDS: (u64, u64) = (30, 14_400);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeoutKind {
+    /// Quick reads: getprop, single-service dumpsys, `load_device_detail` steps, stat.
+    Probe,
+    /// Ordinary device commands: toggles, `am`/`pm`/`settings`/`svc` calls.
+    DeviceCommand,
+    /// APK installs.
+    Install,
+    /// `adb push` / `adb pull`.
+    Transfer,
+}
+
+impl TimeoutSettings {
+    pub fn duration(&self, kind: TimeoutKind) -> Duration {
+        Duration::from_secs(match kind {
+            TimeoutKind::Probe => self.probe_secs,
+            TimeoutKind::DeviceCommand => self.device_command_secs,
+            TimeoutKind::Install => self.install_secs,
+            TimeoutKind::Transfer => self.transfer_secs,
+        })
+    }
+}
+
+/// Clamp each timeout into its bounds; zero (unset) falls back to the default.
+pub fn normalize_timeouts(settings: &mut TimeoutSettings) {
+    let clamp = |value: u64, default: u64, (min, max): (u64, u64)| {
+        if value == 0 {
+            default
+        } else {
+            value.clamp(min, max)
+        }
+    };
+    settings.probe_secs = clamp(
+        settings.probe_secs,
+        DEFAULT_PROBE_TIMEOUT_SECS,
+        PROBE_TIMEOUT_BOUNDS,
+    );
+    settings.device_command_secs = clamp(
+        settings.device_command_secs,
+        DEFAULT_DEVICE_COMMAND_TIMEOUT_SECS,
+        DEVICE_COMMAND_TIMEOUT_BOUNDS,
+    );
+    settings.install_secs = clamp(
+        settings.install_secs,
+        DEFAULT_INSTALL_TIMEOUT_SECS,
+        INSTALL_TIMEOUT_BOUNDS,
+    );
+    settings.transfer_secs = clamp(
+        settings.transfer_secs,
+        DEFAULT_TRANSFER_TIMEOUT_SECS,
+        TRANSFER_TIMEOUT_BOUNDS,
+    );
+}
+
+/// Timeouts from the last config read, keyed by the config file they came from, so the hot
+/// path does not re-read the config for every adb call. Saving the config clears it.
+fn timeouts_slot() -> &'static RwLock<Option<(PathBuf, TimeoutSettings)>> {
+    static SLOT: OnceLock<RwLock<Option<(PathBuf, TimeoutSettings)>>> = OnceLock::new();
+    SLOT.get_or_init(|| RwLock::new(None))
+}
+
+pub fn invalidate_timeout_cache() {
+    if let Ok(mut guard) = timeouts_slot().write() {
+        *guard = None;
+    }
+}
+
+fn configured_timeouts(trace_id: &str) -> TimeoutSettings {
+    let path = config_path();
+    if let Ok(guard) = timeouts_slot().read() {
+        if let Some((cached_path, settings)) = guard.as_ref() {
+            if *cached_path == path {
+                return settings.clone();
+            }
+        }
+    }
+    let settings = match load_config(trace_id) {
+        Ok(config) => config.timeouts,
+        Err(err) => {
+            warn!(trace_id = %trace_id, error = %err.error, "failed to load timeouts, using defaults");
+            return TimeoutSettings::default();
+        }
+    };
+    if let Ok(mut guard) = timeouts_slot().write() {
+        *guard = Some((path, settings.clone()));
+    }
+    settings
+}
+
+/// The configured timeout for a class of adb command.
+pub fn command_timeout(kind: TimeoutKind, trace_id: &str) -> Duration {
+    configured_timeouts(trace_id).duration(kind)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clamps_timeouts_and_maps_kinds() {
+        let mut settings = TimeoutSettings {
+            probe_secs: 0,
+            device_command_secs: 45,
+            install_secs: 5,
+            transfer_secs: 100_000,
+        };
+        normalize_timeouts(&mut settings);
+        assert_eq!(settings.probe_secs, DEFAULT_PROBE_TIMEOUT_SECS);
+        assert_eq!(settings.device_command_secs, 45);
+        assert_eq!(settings.install_secs, 30);
+        assert_eq!(settings.transfer_secs, 14_400);
+        assert_eq!(
+            settings.duration(TimeoutKind::DeviceCommand),
+            Duration::from_secs(45)
+        );
+        assert_eq!(
+            TimeoutSettings::default().duration(TimeoutKind::Install),
+            Duration::from_secs(DEFAULT_INSTALL_TIMEOUT_SECS)
+        );
+    }
+}
@@ -0,0 +1,72 @@
+use chrono::{DateTime, Local};
+
+use crate::app::adb::paths::sanitize_filename_component;
+
+pub const TRIAGE_BUNDLE_READY_EVENT: &str = "triage-bundle-ready";
+
+pub const TRIAGE_SCREENSHOT_FILE: &str = "screenshot.png";
+pub const TRIAGE_LOGCAT_FILE: &str = "logcat.txt";
+pub const TRIAGE_DEVICE_STATE_FILE: &str = "device_state.txt";
+pub const TRIAGE_OUTPUT_FILE: &str = "tool_output.txt";
+pub const TRIAGE_META_FILE: &str = "triage.json";
+
+/// `(section, device shell command)` snapshots written to `device_state.txt`. Each runs as a
+/// single `adb shell` word so the device shell handles the pipe.
+pub const TRIAGE_STATE_COMMANDS: [(&str, &str); 5] = [
+    ("build", "getprop ro.build.fingerprint"),
+    ("uptime", "uptime"),
+    (
+        "focus",
+        "dumpsys window | grep -E 'mCurrentFocus|mFocusedApp'",
+    ),
+    ("battery", "dumpsys battery"),
+    ("memory", "cat /proc/meminfo"),
+];
+
+pub fn triage_dir_name(serial: &str, now: DateTime<Local>) -> String {
+    format!(
+        "triage_{}_{}",
+        sanitize_filename_component(serial),
+        now.format("%Y%m%d_%H%M%S")
+    )
+}
+
+/// `== section ==` blocks; failed snapshots keep their error so the file shows what is missing.
+pub fn render_device_state(sections: &[(String, Result<String, String>)]) -> String {
+    let mut rendered = String::new();
+    for (label, output) in sections {
+        rendered.push_str(&format!("== {label} ==\n"));
+        match output {
+            Ok(output) => rendered.push_str(output.trim_end()),
+            Err(err) => rendered.push_str(&format!("(failed: {err})")),
+        }
+        rendered.push_str("\n\n");
+    }
+    rendered
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn names_dirs_and_renders_state() {
+        let now = Local.with_ymd_and_hms(2024, 5, 1, 10, 30, 0).unwrap();
+        assert_eq!(
+            triage_dir_name("192.168.1.5:5555", now),
+            format!(
+                "triage_{}_20240501_103000",
+                sanitize_filename_component("192.168.1.5:5555")
+            )
+        );
+        let rendered = render_device_state(&[
+            ("build".to_string(), Ok("google/oriole\n".to_string())),
+            ("battery".to_string(), Err("timed out".to_string())),
+        ]);
+        assert_eq!(
+            rendered,
+            "== build ==\ngoogle/oriole\n\n== battery ==\n(failed: timed out)\n\n"
+        );
+    }
+}
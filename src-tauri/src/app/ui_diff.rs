@@ -0,0 +1,284 @@
+use std::collections::HashMap;
+
+use crate::app::models::{UiHierarchyDiff, UiNodeChange};
+
+const MAX_DIFF_CHANGES: usize = 200;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UiNodeSnapshot {
+    pub path: String,
+    pub class_name: String,
+    pub resource_id: String,
+    pub text: String,
+    pub content_desc: String,
+    pub bounds: String,
+    pub hint: String,
+    /// `clickable` or `long-clickable`.
+    pub clickable: bool,
+    pub enabled: bool,
+}
+
+/// Flatten uiautomator-style XML into `<node>` snapshots keyed by a structural path.
+///
+/// The path is built from `index:class` segments, so a node keeps its key when only its
+/// text or bounds change.
+pub fn extract_ui_nodes(xml: &str) -> Vec<UiNodeSnapshot> {
+    let mut nodes = Vec::new();
+    let mut path_stack: Vec<String> = Vec::new();
+    let mut sibling_stack: Vec<usize> = vec![0];
+    let mut rest = xml;
+
+    while let Some(start) = rest.find('<') {
+        rest = &rest[start..];
+        let end = match find_tag_end(rest) {
+            Some(end) => end,
+            None => break,
+        };
+        let tag = &rest[1..end];
+        rest = &rest[end + 1..];
+
+        if let Some(name) = tag.strip_prefix('/') {
+            if name.trim() == "node" {
+                path_stack.pop();
+                sibling_stack.pop();
+            }
+            continue;
+        }
+        if tag.starts_with('?') || tag.starts_with('!') {
+            continue;
+        }
+        let self_closing = tag.ends_with('/');
+        let tag = tag.trim_end_matches('/');
+        let (name, attrs) = tag.split_once(char::is_whitespace).unwrap_or((tag, ""));
+        if name != "node" {
+            continue;
+        }
+
+        let attrs = parse_attributes(attrs);
+        let class_name = attrs.get("class").cloned().unwrap_or_default();
+        let sibling_index = sibling_stack.last_mut().map_or(0, |value| {
+            let current = *value;
+            *value += 1;
+            current
+        });
+        let segment = format!("{sibling_index}:{class_name}");
+        let path = if path_stack.is_empty() {
+            segment.clone()
+        } else {
+            format!("{}/{}", path_stack.join("/"), segment)
+        };
+        nodes.push(UiNodeSnapshot {
+            path,
+            class_name,
+            resource_id: attrs.get("resource-id").cloned().unwrap_or_default(),
+            text: attrs.get("text").cloned().unwrap_or_default(),
+            content_desc: attrs.get("content-desc").cloned().unwrap_or_default(),
+            bounds: attrs.get("bounds").cloned().unwrap_or_default(),
+            hint: attrs.get("hint").cloned().unwrap_or_default(),
+            clickable: ["clickable", "long-clickable"]
+                .iter()
+                .any(|key| attrs.get(*key).is_some_and(|value| value == "true")),
+            enabled: attrs.get("enabled").is_none_or(|value| value == "true"),
+        });
+        if !self_closing {
+            path_stack.push(segment);
+            sibling_stack.push(0);
+        }
+    }
+
+    nodes
+}
+
+pub fn diff_ui_nodes(
+    previous: &[UiNodeSnapshot],
+    current: &[UiNodeSnapshot],
+) -> (usize, usize, usize, Vec<UiNodeChange>) {
+    let previous_by_path: HashMap<&str, &UiNodeSnapshot> = previous
+        .iter()
+        .map(|node| (node.path.as_str(), node))
+        .collect();
+    let current_by_path: HashMap<&str, &UiNodeSnapshot> = current
+        .iter()
+        .map(|node| (node.path.as_str(), node))
+        .collect();
+
+    let mut added = 0;
+    let mut removed = 0;
+    let mut changed = 0;
+    let mut changes = Vec::new();
+
+    for node in current {
+        match previous_by_path.get(node.path.as_str()) {
+            None => {
+                added += 1;
+                push_change(&mut changes, "added", node, String::new());
+            }
+            Some(before) => {
+                let details = describe_node_changes(before, node);
+                if !details.is_empty() {
+                    changed += 1;
+                    push_change(&mut changes, "changed", node, details);
+                }
+            }
+        }
+    }
+    for node in previous {
+        if !current_by_path.contains_key(node.path.as_str()) {
+            removed += 1;
+            push_change(&mut changes, "removed", node, String::new());
+        }
+    }
+
+    (added, removed, changed, changes)
+}
+
+pub fn build_ui_hierarchy_diff(
+    previous_capture_id: &str,
+    previous_xml: &str,
+    current_xml: &str,
+) -> UiHierarchyDiff {
+    let previous = extract_ui_nodes(previous_xml);
+    let current = extract_ui_nodes(current_xml);
+    let (added, removed, changed, changes) = diff_ui_nodes(&previous, &current);
+    let truncated = added + removed + changed > changes.len();
+    UiHierarchyDiff {
+        previous_capture_id: previous_capture_id.to_string(),
+        added,
+        removed,
+        changed,
+        changes,
+        truncated,
+    }
+}
+
+fn push_change(
+    changes: &mut Vec<UiNodeChange>,
+    kind: &str,
+    node: &UiNodeSnapshot,
+    details: String,
+) {
+    if changes.len() >= MAX_DIFF_CHANGES {
+        return;
+    }
+    changes.push(UiNodeChange {
+        kind: kind.to_string(),
+        path: node.path.clone(),
+        class_name: node.class_name.clone(),
+        resource_id: node.resource_id.clone(),
+        details,
+    });
+}
+
+fn describe_node_changes(before: &UiNodeSnapshot, after: &UiNodeSnapshot) -> String {
+    let mut parts = Vec::new();
+    for (label, old, new) in [
+        ("resource-id", &before.resource_id, &after.resource_id),
+        ("text", &before.text, &after.text),
+        ("content-desc", &before.content_desc, &after.content_desc),
+        ("bounds", &before.bounds, &after.bounds),
+    ] {
+        if old != new {
+            parts.push(format!("{label}: \"{old}\" -> \"{new}\""));
+        }
+    }
+    parts.join("; ")
+}
+
+fn find_tag_end(input: &str) -> Option<usize> {
+    let mut quote: Option<char> = None;
+    for (index, ch) in input.char_indices().skip(1) {
+        match quote {
+            Some(active) if ch == active => quote = None,
+            Some(_) => {}
+            None if ch == '"' || ch == '\'' => quote = Some(ch),
+            None if ch == '>' => return Some(index),
+            None => {}
+        }
+    }
+    None
+}
+
+fn parse_attributes(input: &str) -> HashMap<String, String> {
+    let mut attrs = HashMap::new();
+    let mut rest = input.trim();
+    while let Some(eq) = rest.find('=') {
+        let name = rest[..eq].trim().to_string();
+        let after = rest[eq + 1..].trim_start();
+        let quote = match after.chars().next() {
+            Some(ch @ ('"' | '\'')) => ch,
+            _ => break,
+        };
+        let value_end = match after[1..].find(quote) {
+            Some(end) => end + 1,
+            None => break,
+        };
+        attrs.insert(name, unescape_xml(&after[1..value_end]));
+        rest = after[value_end + 1..].trim_start();
+    }
+    attrs
+}
+
+fn unescape_xml(value: &str) -> String {
+    value
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BEFORE: &str = "<?xml version='1.0' ?><hierarchy rotation=\"0\">\
+<node index=\"0\" class=\"android.widget.FrameLayout\" text=\"\" bounds=\"[0,0][1080,2400]\">\
+<node index=\"0\" class=\"android.widget.TextView\" resource-id=\"app:id/title\" text=\"Hello\" bounds=\"[0,0][100,50]\" />\
+<node index=\"1\" class=\"android.widget.Button\" text=\"OK\" bounds=\"[0,60][100,110]\" />\
+</node></hierarchy>";
+
+    const AFTER: &str = "<?xml version='1.0' ?><hierarchy rotation=\"0\">\
+<node index=\"0\" class=\"android.widget.FrameLayout\" text=\"\" bounds=\"[0,0][1080,2400]\">\
+<node index=\"0\" class=\"android.widget.TextView\" resource-id=\"app:id/title\" text=\"Hello &amp; bye\" bounds=\"[0,0][100,50]\" />\
+<node index=\"1\" class=\"android.widget.ImageView\" content-desc=\"icon\" bounds=\"[0,60][100,110]\" />\
+</node></hierarchy>";
+
+    #[test]
+    fn extract_ui_nodes_builds_structural_paths() {
+        let nodes = extract_ui_nodes(BEFORE);
+        assert_eq!(nodes.len(), 3);
+        assert_eq!(nodes[0].path, "0:android.widget.FrameLayout");
+        assert_eq!(
+            nodes[2].path,
+            "0:android.widget.FrameLayout/1:android.widget.Button"
+        );
+        assert_eq!(nodes[1].resource_id, "app:id/title");
+        assert_eq!(nodes[2].text, "OK");
+        assert!(!nodes[2].clickable);
+        assert!(nodes[2].enabled);
+    }
+
+    #[test]
+    fn diff_reports_added_removed_and_changed_nodes() {
+        let diff = build_ui_hierarchy_diff("cap-1", BEFORE, AFTER);
+        assert_eq!(diff.previous_capture_id, "cap-1");
+        assert_eq!(diff.added, 1);
+        assert_eq!(diff.removed, 1);
+        assert_eq!(diff.changed, 1);
+        assert!(!diff.truncated);
+        let changed = diff
+            .changes
+            .iter()
+            .find(|change| change.kind == "changed")
+            .expect("changed entry");
+        assert_eq!(changed.details, "text: \"Hello\" -> \"Hello & bye\"");
+    }
+
+    #[test]
+    fn diff_of_identical_captures_is_empty() {
+        let diff = build_ui_hierarchy_diff("cap-1", BEFORE, BEFORE);
+        assert_eq!((diff.added, diff.removed, diff.changed), (0, 0, 0));
+        assert!(diff.changes.is_empty());
+    }
+}
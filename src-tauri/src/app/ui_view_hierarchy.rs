@@ -0,0 +1,464 @@
+use std::fmt::Write;
+
+use crate::app::ui_xml::escape_html;
+
+/// Parse the `View Hierarchy:` section of `dumpsys activity top` into uiautomator-style XML.
+///
+/// Expected lines (bounds are relative to the parent view):
+/// - `android.widget.TextView{c2d7e3a V.ED..C.. ........ 42,63-500,120 #7f0a0123 app:id/title}`
+///
+/// The view dump is still available on screens where uiautomator cannot attach, so the
+/// result can be rendered by `render_device_ui_html` like a regular dump.
+pub fn parse_view_hierarchy_to_xml(output: &str) -> Result<String, String> {
+    let section = last_view_hierarchy_section(output)
+        .ok_or_else(|| "View hierarchy not found in dumpsys output".to_string())?;
+
+    let mut nodes: Vec<ViewNode> = Vec::new();
+    for line in section {
+        let indent = line.len() - line.trim_start().len();
+        if let Some(node) = parse_view_line(line.trim(), indent) {
+            nodes.push(node);
+        }
+    }
+    if nodes.is_empty() {
+        return Err("View hierarchy is empty".to_string());
+    }
+    Ok(nodes_to_xml(&nodes))
+}
+
+/// Parse a ViewServer `DUMP -1` capture into uiautomator-style XML. Unlike
+/// `dumpsys activity top` it carries each view's exported properties, so text and content
+/// descriptions are filled in.
+///
+/// Each view is one line, indented one space per level, with length-prefixed values since
+/// they may contain spaces:
+/// - `android.widget.TextView@c2d7e3a mID=8,id/title text:mText=5,Hello layout:mLeft=2,42 ...`
+pub fn parse_view_server_dump_to_xml(output: &str) -> Result<String, String> {
+    let nodes: Vec<ViewNode> = output
+        .lines()
+        .take_while(|line| line.trim() != "DONE.")
+        .filter_map(parse_view_server_line)
+        .collect();
+    if nodes.is_empty() {
+        return Err("ViewServer dump is empty".to_string());
+    }
+    Ok(nodes_to_xml(&nodes))
+}
+
+/// Parse the window list of `dumpsys accessibility` into uiautomator-style XML, one node per
+/// window. The service only dumps windows, not their view nodes, so this gives the title,
+/// type, bounds and focus of each window (including secure ones) but no per-view properties.
+///
+/// Expected lines (`AccessibilityWindowInfo[` on older releases):
+/// - `Window[displayId=0, title=Settings, id=12, type=TYPE_APPLICATION, layer=3, bounds=Rect(0, 0 - 1080, 2400), focused=true, active=true, ...]`
+pub fn parse_accessibility_windows_to_xml(output: &str) -> Result<String, String> {
+    let nodes: Vec<ViewNode> = output
+        .lines()
+        .filter_map(|line| parse_accessibility_window_line(line.trim()))
+        .collect();
+    if nodes.is_empty() {
+        return Err("No accessibility windows in dumpsys output".to_string());
+    }
+    Ok(nodes_to_xml(&nodes))
+}
+
+fn parse_accessibility_window_line(line: &str) -> Option<ViewNode> {
+    let body = line
+        .strip_prefix("Window[")
+        .or_else(|| line.strip_prefix("AccessibilityWindowInfo["))?;
+    let bounds = window_field(body, "bounds")?;
+    let (left, top, right, bottom) = bounds
+        .strip_prefix("Rect(")
+        .and_then(|rect| rect.strip_suffix(')'))
+        .and_then(|rect| parse_view_bounds(&rect.replace(' ', "")))?;
+    // Titles may contain commas, so they run up to the `id=` field that always follows.
+    let title = body
+        .find("title=")
+        .map(|start| &body[start + "title=".len()..])
+        .and_then(|rest| rest.split_once(", id=").map(|(title, _)| title))
+        .filter(|title| *title != "null")
+        .unwrap_or_default();
+    let flag = |key: &str| window_field(body, key) == Some("true");
+
+    Some(ViewNode {
+        indent: 0,
+        class_name: window_field(body, "type").unwrap_or_default().to_string(),
+        resource_id: String::new(),
+        text: title.to_string(),
+        content_desc: String::new(),
+        left,
+        top,
+        right,
+        bottom,
+        visible: true,
+        focusable: false,
+        enabled: true,
+        scrollable: false,
+        clickable: false,
+        long_clickable: false,
+        focused: flag("focused"),
+        selected: flag("active"),
+    })
+}
+
+/// Value of `key=` in a window record, up to the next `, ` or `]`. `bounds` keeps its
+/// parenthesised `Rect(l, t - r, b)` whole.
+fn window_field<'a>(body: &'a str, key: &str) -> Option<&'a str> {
+    let pattern = format!("{key}=");
+    let start = body
+        .match_indices(&pattern)
+        .find(|(index, _)| *index == 0 || body[..*index].ends_with(' '))
+        .map(|(index, _)| index + pattern.len())?;
+    let rest = &body[start..];
+    let end = if rest.starts_with("Rect(") {
+        rest.find(')').map(|index| index + 1)?
+    } else {
+        rest.find([',', ']']).unwrap_or(rest.len())
+    };
+    Some(&rest[..end])
+}
+
+fn nodes_to_xml(nodes: &[ViewNode]) -> String {
+    let mut xml = String::from("<?xml version='1.0' encoding='UTF-8' standalone='yes' ?>");
+    xml.push_str("<hierarchy rotation=\"0\">");
+    // Each stack entry holds (indent, absolute left, absolute top).
+    let mut stack: Vec<(usize, i32, i32)> = Vec::new();
+    let mut index_stack: Vec<usize> = vec![0];
+    for node in nodes {
+        while let Some((indent, _, _)) = stack.last() {
+            if *indent >= node.indent {
+                stack.pop();
+                index_stack.pop();
+                xml.push_str("</node>");
+            } else {
+                break;
+            }
+        }
+        let (origin_left, origin_top) = stack
+            .last()
+            .map(|(_, left, top)| (*left, *top))
+            .unwrap_or((0, 0));
+        let left = origin_left + node.left;
+        let top = origin_top + node.top;
+        let right = origin_left + node.right;
+        let bottom = origin_top + node.bottom;
+
+        let sibling_index = index_stack.last_mut().map_or(0, |value| {
+            let current = *value;
+            *value += 1;
+            current
+        });
+
+        let _ = write!(
+            xml,
+            "<node index=\"{}\" text=\"{}\" resource-id=\"{}\" class=\"{}\" package=\"\" content-desc=\"{}\" \
+checkable=\"false\" checked=\"false\" clickable=\"{}\" enabled=\"{}\" focusable=\"{}\" focused=\"{}\" \
+scrollable=\"{}\" long-clickable=\"{}\" password=\"false\" selected=\"{}\" visible-to-user=\"{}\" \
+bounds=\"[{},{}][{},{}]\">",
+            sibling_index,
+            escape_html(&node.text),
+            escape_html(&node.resource_id),
+            escape_html(&node.class_name),
+            escape_html(&node.content_desc),
+            node.clickable,
+            node.enabled,
+            node.focusable,
+            node.focused,
+            node.scrollable,
+            node.long_clickable,
+            node.selected,
+            node.visible,
+            left,
+            top,
+            right,
+            bottom,
+        );
+        stack.push((node.indent, left, top));
+        index_stack.push(0);
+    }
+    for _ in stack {
+        xml.push_str("</node>");
+    }
+    xml.push_str("</hierarchy>");
+    xml
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ViewNode {
+    indent: usize,
+    class_name: String,
+    resource_id: String,
+    text: String,
+    content_desc: String,
+    left: i32,
+    top: i32,
+    right: i32,
+    bottom: i32,
+    visible: bool,
+    focusable: bool,
+    enabled: bool,
+    scrollable: bool,
+    clickable: bool,
+    long_clickable: bool,
+    focused: bool,
+    selected: bool,
+}
+
+fn last_view_hierarchy_section(output: &str) -> Option<Vec<&str>> {
+    let lines: Vec<&str> = output.lines().collect();
+    let start = lines
+        .iter()
+        .rposition(|line| line.trim() == "View Hierarchy:")?;
+    let header_indent = lines[start].len() - lines[start].trim_start().len();
+    let mut section = Vec::new();
+    for line in &lines[start + 1..] {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let indent = line.len() - line.trim_start().len();
+        if indent <= header_indent {
+            break;
+        }
+        section.push(*line);
+    }
+    Some(section)
+}
+
+fn parse_view_line(line: &str, indent: usize) -> Option<ViewNode> {
+    let (class_name, rest) = line.split_once('{')?;
+    let body = rest.strip_suffix('}')?;
+    if class_name.is_empty() || class_name.contains(' ') {
+        return None;
+    }
+    let tokens: Vec<&str> = body.split_whitespace().collect();
+    if tokens.len() < 4 {
+        return None;
+    }
+    let view_flags: Vec<char> = tokens[1].chars().collect();
+    let private_flags: Vec<char> = tokens[2].chars().collect();
+    if view_flags.len() < 9 || private_flags.len() < 8 {
+        return None;
+    }
+    let (left, top, right, bottom) = parse_view_bounds(tokens[3])?;
+    let resource_id = tokens
+        .iter()
+        .skip(4)
+        .find(|token| token.contains(":id/"))
+        .map(|token| token.to_string())
+        .unwrap_or_default();
+
+    Some(ViewNode {
+        indent,
+        class_name: class_name.to_string(),
+        resource_id,
+        text: String::new(),
+        content_desc: String::new(),
+        left,
+        top,
+        right,
+        bottom,
+        visible: view_flags[0] == 'V',
+        focusable: view_flags[1] == 'F',
+        enabled: view_flags[2] == 'E',
+        scrollable: view_flags[4] == 'H' || view_flags[5] == 'V',
+        clickable: view_flags[6] == 'C',
+        long_clickable: view_flags[7] == 'L',
+        focused: private_flags[1] == 'F',
+        selected: private_flags[2] == 'S',
+    })
+}
+
+fn parse_view_server_line(line: &str) -> Option<ViewNode> {
+    let indent = line.len() - line.trim_start_matches(' ').len();
+    let (header, rest) = line.trim().split_once(' ').unwrap_or((line.trim(), ""));
+    let (class_name, hash) = header.split_once('@')?;
+    if class_name.is_empty() || hash.is_empty() || !hash.chars().all(|ch| ch.is_ascii_hexdigit()) {
+        return None;
+    }
+    let properties = parse_view_properties(rest);
+    // Keys carry an optional `category:` prefix that varies between releases.
+    let get = |names: &[&str]| {
+        properties.iter().find_map(|(key, value)| {
+            let name = key.rsplit_once(':').map_or(key.as_str(), |(_, name)| name);
+            names.contains(&name).then_some(value.as_str())
+        })
+    };
+    let number = |names: &[&str]| get(names).and_then(|value| value.parse::<i32>().ok());
+    let flag = |names: &[&str], default: bool| get(names).map_or(default, |value| value == "true");
+    let text = |names: &[&str]| {
+        get(names)
+            .filter(|value| *value != "null")
+            .unwrap_or_default()
+            .to_string()
+    };
+    let left = number(&["mLeft"]).unwrap_or(0);
+    let top = number(&["mTop"]).unwrap_or(0);
+    // `id/title` for the app's own ids, `android:id/title` for framework ones; spelled like
+    // the `app:id/` ids of the dumpsys view hierarchy.
+    let resource_id = get(&["mID"])
+        .filter(|id| *id != "NO_ID")
+        .map(|id| match id.strip_prefix("id/") {
+            Some(name) => format!("app:id/{name}"),
+            None => id.to_string(),
+        })
+        .unwrap_or_default();
+
+    Some(ViewNode {
+        indent,
+        class_name: class_name.to_string(),
+        resource_id,
+        text: text(&["mText", "getText()"]),
+        content_desc: text(&["getContentDescription()", "mContentDescription"]),
+        left,
+        top,
+        right: number(&["mRight"]).unwrap_or(left + number(&["getWidth()"]).unwrap_or(0)),
+        bottom: number(&["mBottom"]).unwrap_or(top + number(&["getHeight()"]).unwrap_or(0)),
+        visible: get(&["getVisibility()"]).is_none_or(|value| value == "VISIBLE"),
+        focusable: flag(&["isFocusable()"], false),
+        enabled: flag(&["isEnabled()"], true),
+        scrollable: flag(&["isScrollContainer()"], false),
+        clickable: flag(&["isClickable()"], false),
+        long_clickable: flag(&["isLongClickable()"], false),
+        focused: flag(&["isFocused()", "hasFocus()"], false),
+        selected: flag(&["isSelected()"], false),
+    })
+}
+
+/// `key=len,value` pairs separated by spaces. `len` counts UTF-16 units, as Java wrote it.
+fn parse_view_properties(mut rest: &str) -> Vec<(String, String)> {
+    let mut properties = Vec::new();
+    loop {
+        rest = rest.trim_start_matches(' ');
+        let Some((key, after_key)) = rest.split_once('=') else {
+            break;
+        };
+        let Some((len, after_len)) = after_key.split_once(',') else {
+            break;
+        };
+        let Ok(len) = len.parse::<usize>() else {
+            break;
+        };
+        let mut units = 0;
+        let end = after_len
+            .char_indices()
+            .find(|(_, ch)| {
+                let done = units >= len;
+                units += ch.len_utf16();
+                done
+            })
+            .map_or(after_len.len(), |(index, _)| index);
+        properties.push((key.to_string(), after_len[..end].to_string()));
+        rest = &after_len[end..];
+    }
+    properties
+}
+
+fn parse_view_bounds(value: &str) -> Option<(i32, i32, i32, i32)> {
+    let (start, end) = value.split_once('-')?;
+    let (left, top) = start.split_once(',')?;
+    let (right, bottom) = end.split_once(',')?;
+    Some((
+        left.parse().ok()?,
+        top.parse().ok()?,
+        right.parse().ok()?,
+        bottom.parse().ok()?,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "\
+TASK 10 id=12 userId=0
+  ACTIVITY com.example/.MainActivity 1a2b3c pid=4321
+    View Hierarchy:
+      DecorView@5c4d1a5[MainActivity]
+      com.android.internal.policy.DecorView{5c4d1a5 V.E...... R....... 0,0-1080,2400}
+        android.widget.LinearLayout{8f1e2d V.E...... ......I. 0,0-1080,2400}
+          android.widget.Button{c2d7e3a VFED..CL. .F...... 40,100-540,220 #7f0a0123 app:id/submit}
+          android.widget.ScrollView{d3e4f5 VFED.V... ........ 0,300-1080,2400}
+            android.widget.TextView{e4f5a6 V.ED..... ......ID 10,20-500,80 #7f0a0456 app:id/title}
+    Looper (main, tid 1) {6f8e7d}
+";
+
+    #[test]
+    fn parses_view_hierarchy_with_absolute_bounds() {
+        let xml = parse_view_hierarchy_to_xml(SAMPLE).expect("parse");
+        assert!(xml.starts_with("<?xml"));
+        assert!(xml.contains("class=\"android.widget.Button\""));
+        assert!(xml.contains("resource-id=\"app:id/submit\""));
+        assert!(
+            xml.contains("clickable=\"true\" enabled=\"true\" focusable=\"true\" focused=\"true\"")
+        );
+        assert!(xml.contains("long-clickable=\"true\""));
+        assert!(xml.contains("bounds=\"[40,100][540,220]\""));
+        assert!(xml.contains("bounds=\"[10,320][500,380]\""));
+        assert!(xml.contains("scrollable=\"true\""));
+        assert_eq!(xml.matches("<node ").count(), 5);
+        assert_eq!(xml.matches("</node>").count(), 5);
+    }
+
+    #[test]
+    fn parses_view_server_dump_with_text() {
+        let dump = concat!(
+            "com.android.internal.policy.DecorView@5c4d1a5 mID=5,NO_ID layout:mLeft=1,0 layout:mTop=1,0 layout:mRight=4,1080 layout:mBottom=4,2400 getVisibility()=7,VISIBLE\n",
+            " android.widget.LinearLayout@8f1e2d mID=5,NO_ID layout:mLeft=1,0 layout:mTop=2,80 layout:getWidth()=4,1080 layout:getHeight()=4,2320\n",
+            "  android.widget.Button@c2d7e3a mID=9,id/submit text:mText=9,Send now! accessibility:getContentDescription()=11,Send <form> layout:mLeft=2,40 layout:mTop=3,100 layout:mRight=3,540 layout:mBottom=3,220 isClickable()=4,true\n",
+            "  android.widget.TextView@e4f5a6 mID=18,android:id/summary text:mText=7,Caf\u{e9} \u{1f375} accessibility:getContentDescription()=4,null layout:mLeft=2,10 layout:mTop=3,300 layout:mRight=3,500 layout:mBottom=3,380 getVisibility()=4,GONE\n",
+            "DONE.\n",
+        );
+        let xml = parse_view_server_dump_to_xml(dump).expect("parse");
+        assert!(xml.contains(
+            "text=\"Send now!\" resource-id=\"app:id/submit\" class=\"android.widget.Button\" package=\"\" content-desc=\"Send &lt;form&gt;\""
+        ));
+        assert!(xml.contains("clickable=\"true\" enabled=\"true\""));
+        assert!(xml.contains("bounds=\"[40,180][540,300]\""));
+        assert!(xml.contains("bounds=\"[0,80][1080,2400]\""));
+        // The emoji is two UTF-16 units, so the length prefix still ends at the value.
+        assert!(xml.contains("text=\"Caf\u{e9} \u{1f375}\" resource-id=\"android:id/summary\""));
+        assert!(xml.contains("content-desc=\"\" checkable"));
+        assert!(xml.contains("visible-to-user=\"false\""));
+        assert_eq!(xml.matches("<node ").count(), 4);
+        assert_eq!(xml.matches("</node>").count(), 4);
+        assert!(parse_view_server_dump_to_xml("DONE.\n").is_err());
+    }
+
+    #[test]
+    fn parses_accessibility_windows() {
+        let dump = "\
+ACCESSIBILITY MANAGER (dumpsys accessibility)
+
+User state[
+     attributes:{id=0, touchExplorationEnabled=false}
+]
+Windows:
+  Window[displayId=0, title=Status bar, id=44, type=TYPE_SYSTEM, layer=0, region=SkRegion((0,0,1080,136)), bounds=Rect(0, 0 - 1080, 136), focused=false, active=false, pictureInPicture=false, hasParent=false, hasChildren=false]
+  Window[displayId=0, title=Bank, Login, id=52, type=TYPE_APPLICATION, layer=3, bounds=Rect(0, 136 - 1080, 2400), focused=true, active=true, hasParent=false, hasChildren=false]
+  AccessibilityWindowInfo[title=null, id=60, type=TYPE_INPUT_METHOD, layer=4, bounds=Rect(0, 1600 - 1080, 2400), focused=false, active=false]
+";
+        let xml = parse_accessibility_windows_to_xml(dump).expect("parse");
+        assert_eq!(xml.matches("<node ").count(), 3);
+        assert!(xml.contains("text=\"Status bar\" resource-id=\"\" class=\"TYPE_SYSTEM\""));
+        assert!(xml.contains("text=\"Bank, Login\""));
+        assert!(xml.contains("focused=\"true\" scrollable=\"false\" long-clickable=\"false\" password=\"false\" selected=\"true\""));
+        assert!(xml.contains("bounds=\"[0,136][1080,2400]\""));
+        assert!(xml.contains("text=\"\" resource-id=\"\" class=\"TYPE_INPUT_METHOD\""));
+        assert!(xml.contains("bounds=\"[0,1600][1080,2400]\""));
+        assert!(parse_accessibility_windows_to_xml("Windows:\n").is_err());
+    }
+
+    #[test]
+    fn rejects_output_without_view_hierarchy() {
+        assert!(parse_view_hierarchy_to_xml("ACTIVITY MANAGER TOP\n").is_err());
+        assert!(parse_view_hierarchy_to_xml("    View Hierarchy:\n  other\n").is_err());
+    }
+
+    #[test]
+    fn parsed_xml_renders_to_html() {
+        let xml = parse_view_hierarchy_to_xml(SAMPLE).expect("parse");
+        let html = crate::app::ui_xml::render_device_ui_html(&xml).expect("render");
+        assert!(html.contains("data-ui-node-index=\"4\""));
+        assert!(html.contains("#app:id/title"));
+    }
+}
@@ -0,0 +1,437 @@
+use std::io::Write;
+use std::path::Path;
+use std::time::Duration;
+
+use crate::app::adb::runner::{run_command_with_timeout, CommandOutput};
+use crate::app::config::{AppConfig, UploadTarget};
+use crate::app::error::AppError;
+use crate::app::i18n::Message;
+
+pub const DEFAULT_CURL_PATH: &str = "curl";
+pub const MAX_UPLOAD_FILES: usize = 50;
+
+const MIN_UPLOAD_TIMEOUT_SECS: u64 = 60;
+const MAX_UPLOAD_TIMEOUT_SECS: u64 = 2 * 60 * 60;
+/// Conservative uplink estimate used to size per-file timeouts.
+const ASSUMED_UPLOAD_BYTES_PER_SEC: u64 = 256 * 1024;
+
+/// One curl invocation. `config_lines` carry credentials and headers and are written to a
+/// private curl config file, so secrets never show up in argv or the command log.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UploadRequest {
+    pub url: String,
+    pub args: Vec<String>,
+    pub config_lines: Vec<String>,
+}
+
+/// Trim and default a configured target; returns false when it cannot be used.
+pub fn normalize_upload_target(target: &mut UploadTarget) -> bool {
+    target.name = target.name.trim().to_string();
+    target.kind = target.kind.trim().to_lowercase();
+    target.url = target.url.trim().trim_end_matches('/').to_string();
+    target.bucket = target.bucket.trim().to_string();
+    target.region = target.region.trim().to_string();
+    target.prefix = target.prefix.trim().trim_matches('/').to_string();
+    target.method = target.method.trim().to_uppercase();
+    target.access_key_env = target.access_key_env.trim().to_string();
+    target.secret_key_env = target.secret_key_env.trim().to_string();
+    target.token_env = target.token_env.trim().to_string();
+    target.headers.retain_mut(|header| {
+        header.name = header.name.trim().to_string();
+        header.value = header.value.trim().to_string();
+        !header.name.is_empty()
+    });
+
+    match target.kind.as_str() {
+        "s3" => {
+            if target.region.is_empty() {
+                target.region = "us-east-1".to_string();
+            }
+            if target.access_key_env.is_empty() {
+                target.access_key_env = "AWS_ACCESS_KEY_ID".to_string();
+            }
+            if target.secret_key_env.is_empty() {
+                target.secret_key_env = "AWS_SECRET_ACCESS_KEY".to_string();
+            }
+            if target.token_env.is_empty() {
+                target.token_env = "AWS_SESSION_TOKEN".to_string();
+            }
+        }
+        "gcs" => {
+            if target.token_env.is_empty() {
+                target.token_env = "GCS_ACCESS_TOKEN".to_string();
+            }
+        }
+        "http" => {
+            if target.method != "POST" {
+                target.method = "PUT".to_string();
+            }
+        }
+        _ => return false,
+    }
+    let has_destination = match target.kind.as_str() {
+        "http" => !target.url.is_empty(),
+        _ => !target.bucket.is_empty(),
+    };
+    !target.name.is_empty() && has_destination
+}
+
+pub fn object_key(prefix: &str, file_name: &str) -> String {
+    if prefix.is_empty() {
+        file_name.to_string()
+    } else {
+        format!("{prefix}/{file_name}")
+    }
+}
+
+/// Percent-encode an object key for use in a URL path, keeping `/` separators.
+pub fn encode_object_key(key: &str) -> String {
+    let mut encoded = String::with_capacity(key.len());
+    for byte in key.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' | b'/' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    encoded
+}
+
+/// Quote a value for a curl `--config` file; multi-line values are rejected.
+pub fn quote_config_value(value: &str) -> Result<String, String> {
+    if value.contains(['\n', '\r']) {
+        return Err("Credentials and header values must be single-line".to_string());
+    }
+    Ok(format!(
+        "\"{}\"",
+        value.replace('\\', "\\\\").replace('"', "\\\"")
+    ))
+}
+
+pub fn required_env(name: &str, env: &impl Fn(&str) -> Option<String>) -> Result<String, String> {
+    env(name)
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+        .ok_or_else(|| format!("Environment variable {name} is not set"))
+}
+
+/// Build the curl arguments for uploading `file_path` to `target`. `env` resolves the
+/// environment variables named in the target.
+pub fn build_upload_request(
+    target: &UploadTarget,
+    file_path: &Path,
+    env: impl Fn(&str) -> Option<String>,
+) -> Result<UploadRequest, String> {
+    let file_name = file_path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .ok_or_else(|| "Artifact path has no file name".to_string())?;
+    let key = encode_object_key(&object_key(&target.prefix, &file_name));
+    let local = file_path.to_string_lossy().to_string();
+    let mut config_lines = Vec::new();
+
+    let (url, args) = match target.kind.as_str() {
+        "s3" => {
+            let access_key = required_env(&target.access_key_env, &env)?;
+            let secret_key = required_env(&target.secret_key_env, &env)?;
+            config_lines.push(format!(
+                "user = {}",
+                quote_config_value(&format!("{access_key}:{secret_key}"))?
+            ));
+            if let Some(token) = env(&target.token_env).filter(|value| !value.trim().is_empty()) {
+                config_lines.push(format!(
+                    "header = {}",
+                    quote_config_value(&format!("x-amz-security-token: {}", token.trim()))?
+                ));
+            }
+            let url = if target.url.is_empty() {
+                format!(
+                    "https://{}.s3.{}.amazonaws.com/{key}",
+                    target.bucket, target.region
+                )
+            } else {
+                format!("{}/{}/{key}", target.url, target.bucket)
+            };
+            let args = vec![
+                "--aws-sigv4".to_string(),
+                format!("aws:amz:{}:s3", target.region),
+                "-T".to_string(),
+                local,
+                url.clone(),
+            ];
+            (url, args)
+        }
+        "gcs" => {
+            let token = required_env(&target.token_env, &env)?;
+            config_lines.push(format!(
+                "header = {}",
+                quote_config_value(&format!("Authorization: Bearer {token}"))?
+            ));
+            let url = format!("https://storage.googleapis.com/{}/{key}", target.bucket);
+            (url.clone(), vec!["-T".to_string(), local, url])
+        }
+        "http" => {
+            if target.method == "POST" {
+                let args = vec![
+                    "-F".to_string(),
+                    format!("file=@{}", quote_config_value(&local)?),
+                    target.url.clone(),
+                ];
+                (target.url.clone(), args)
+            } else {
+                let url = format!("{}/{key}", target.url);
+                (url.clone(), vec!["-T".to_string(), local, url])
+            }
+        }
+        other => return Err(format!("Unsupported upload target kind: {other}")),
+    };
+
+    for header in &target.headers {
+        if header.name.contains([':', '\n', '\r']) {
+            return Err(format!("Invalid header name: {}", header.name));
+        }
+        let value = if header.value_from_env {
+            required_env(&header.value, &env)?
+        } else {
+            header.value.clone()
+        };
+        config_lines.push(format!(
+            "header = {}",
+            quote_config_value(&format!("{}: {value}", header.name))?
+        ));
+    }
+
+    Ok(UploadRequest {
+        url,
+        args,
+        config_lines,
+    })
+}
+
+pub fn upload_timeout(bytes: u64) -> Duration {
+    Duration::from_secs(
+        (MIN_UPLOAD_TIMEOUT_SECS + bytes / ASSUMED_UPLOAD_BYTES_PER_SEC)
+            .min(MAX_UPLOAD_TIMEOUT_SECS),
+    )
+}
+
+pub fn resolve_curl_program(config: &AppConfig) -> String {
+    if config.upload.curl_path.is_empty() {
+        DEFAULT_CURL_PATH.to_string()
+    } else {
+        config.upload.curl_path.clone()
+    }
+}
+
+/// Run curl with `config_lines` (credentials, headers) in a private `--config` file so they
+/// stay out of argv and the command log. `action` prefixes the error on HTTP failure.
+pub fn run_curl(
+    curl_program: &str,
+    config_lines: &[String],
+    args: Vec<String>,
+    timeout: Duration,
+    action: &str,
+    trace_id: &str,
+) -> Result<CommandOutput, AppError> {
+    let mut config_file = tempfile::NamedTempFile::new().map_err(|err| {
+        AppError::system(
+            Message::keyed("operation_failed")
+                .with("action", "create curl config")
+                .with("detail", &err),
+            trace_id,
+        )
+    })?;
+    for line in config_lines {
+        writeln!(config_file, "{line}").map_err(|err| {
+            AppError::system(
+                Message::keyed("operation_failed")
+                    .with("action", "write curl config")
+                    .with("detail", &err),
+                trace_id,
+            )
+        })?;
+    }
+    let mut full_args = vec![
+        "--fail".to_string(),
+        "--silent".to_string(),
+        "--show-error".to_string(),
+        "--config".to_string(),
+        config_file.path().to_string_lossy().to_string(),
+    ];
+    full_args.extend(args);
+
+    let output = run_command_with_timeout(curl_program, &full_args, timeout, trace_id)?;
+    if output.exit_code != Some(0) {
+        return Err(AppError::dependency(
+            Message::keyed("action_failed")
+                .with("action", action)
+                .with("detail", output.stderr.trim()),
+            trace_id,
+        ));
+    }
+    Ok(output)
+}
+
+pub fn write_temp_json(
+    value: &serde_json::Value,
+    trace_id: &str,
+) -> Result<tempfile::NamedTempFile, AppError> {
+    let mut file = tempfile::NamedTempFile::new().map_err(|err| {
+        AppError::system(
+            Message::keyed("operation_failed")
+                .with("action", "create request body")
+                .with("detail", &err),
+            trace_id,
+        )
+    })?;
+    serde_json::to_writer(&mut file, value).map_err(|err| {
+        AppError::system(
+            Message::keyed("operation_failed")
+                .with("action", "write request body")
+                .with("detail", &err),
+            trace_id,
+        )
+    })?;
+    Ok(file)
+}
+
+pub fn post_json_with_curl(
+    curl_program: &str,
+    auth: &[String],
+    url: &str,
+    payload: &serde_json::Value,
+    action: &str,
+    trace_id: &str,
+) -> Result<String, AppError> {
+    let body = write_temp_json(payload, trace_id)?;
+    let args = vec![
+        "-X".to_string(),
+        "POST".to_string(),
+        "-H".to_string(),
+        "Content-Type: application/json".to_string(),
+        "--data-binary".to_string(),
+        format!("@{}", body.path().to_string_lossy()),
+        url.to_string(),
+    ];
+    let output = run_curl(
+        curl_program,
+        auth,
+        args,
+        Duration::from_secs(30),
+        action,
+        trace_id,
+    )?;
+    Ok(output.stdout)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::config::UploadHeader;
+    use std::path::PathBuf;
+
+    fn target(kind: &str) -> UploadTarget {
+        UploadTarget {
+            name: " team ".to_string(),
+            kind: kind.to_uppercase(),
+            url: String::new(),
+            bucket: "qa-artifacts".to_string(),
+            region: String::new(),
+            prefix: "/runs/nightly/".to_string(),
+            method: String::new(),
+            headers: Vec::new(),
+            access_key_env: String::new(),
+            secret_key_env: String::new(),
+            token_env: String::new(),
+        }
+    }
+
+    fn env(name: &str) -> Option<String> {
+        match name {
+            "AWS_ACCESS_KEY_ID" => Some("AKIA".to_string()),
+            "AWS_SECRET_ACCESS_KEY" => Some("se\"cret".to_string()),
+            "GCS_ACCESS_TOKEN" => Some("ya29".to_string()),
+            "API_KEY" => Some("k-123".to_string()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn normalizes_targets() {
+        let mut s3 = target("s3");
+        assert!(normalize_upload_target(&mut s3));
+        assert_eq!(s3.name, "team");
+        assert_eq!(s3.kind, "s3");
+        assert_eq!(s3.prefix, "runs/nightly");
+        assert_eq!(s3.region, "us-east-1");
+        assert_eq!(s3.access_key_env, "AWS_ACCESS_KEY_ID");
+
+        let mut http = target("http");
+        assert!(!normalize_upload_target(&mut http));
+        http.url = "https://upload.example.com/".to_string();
+        assert!(normalize_upload_target(&mut http));
+        assert_eq!(http.method, "PUT");
+
+        let mut unknown = target("ftp");
+        assert!(!normalize_upload_target(&mut unknown));
+    }
+
+    #[test]
+    fn builds_s3_request_with_credentials_in_config() {
+        let mut s3 = target("s3");
+        normalize_upload_target(&mut s3);
+        let request =
+            build_upload_request(&s3, &PathBuf::from("/tmp/bug report.zip"), env).unwrap();
+        assert_eq!(
+            request.url,
+            "https://qa-artifacts.s3.us-east-1.amazonaws.com/runs/nightly/bug%20report.zip"
+        );
+        assert_eq!(request.args[..2], ["--aws-sigv4", "aws:amz:us-east-1:s3"]);
+        assert!(!request.args.iter().any(|arg| arg.contains("AKIA")));
+        assert_eq!(request.config_lines, ["user = \"AKIA:se\\\"cret\""]);
+
+        let missing = build_upload_request(&s3, &PathBuf::from("/tmp/a.zip"), |_| None);
+        assert!(missing.unwrap_err().contains("AWS_ACCESS_KEY_ID"));
+    }
+
+    #[test]
+    fn builds_gcs_and_http_requests() {
+        let mut gcs = target("gcs");
+        normalize_upload_target(&mut gcs);
+        let request = build_upload_request(&gcs, &PathBuf::from("/tmp/log.txt"), env).unwrap();
+        assert_eq!(
+            request.url,
+            "https://storage.googleapis.com/qa-artifacts/runs/nightly/log.txt"
+        );
+        assert_eq!(
+            request.config_lines,
+            ["header = \"Authorization: Bearer ya29\""]
+        );
+
+        let mut http = target("http");
+        http.url = "https://upload.example.com".to_string();
+        http.prefix = String::new();
+        http.method = "post".to_string();
+        http.headers = vec![UploadHeader {
+            name: "X-Api-Key".to_string(),
+            value: "API_KEY".to_string(),
+            value_from_env: true,
+        }];
+        normalize_upload_target(&mut http);
+        let request = build_upload_request(&http, &PathBuf::from("/tmp/a.mp4"), env).unwrap();
+        assert_eq!(request.url, "https://upload.example.com");
+        assert_eq!(
+            request.args,
+            ["-F", "file=@\"/tmp/a.mp4\"", "https://upload.example.com"]
+        );
+        assert_eq!(request.config_lines, ["header = \"X-Api-Key: k-123\""]);
+    }
+
+    #[test]
+    fn scales_timeout_with_size() {
+        assert_eq!(upload_timeout(0), Duration::from_secs(60));
+        assert_eq!(upload_timeout(256 * 1024 * 60), Duration::from_secs(120));
+        assert_eq!(upload_timeout(u64::MAX), Duration::from_secs(7200));
+    }
+}
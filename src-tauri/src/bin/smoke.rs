@@ -8,8 +8,8 @@ use lazy_blacktea_rust_lib::app::adb::locator::resolve_adb_program;
 use lazy_blacktea_rust_lib::app::adb::parse::parse_adb_devices;
 use lazy_blacktea_rust_lib::app::adb::runner::{run_adb, run_command_with_timeout};
 use lazy_blacktea_rust_lib::app::commands::{
-    capture_screenshot, check_adb, check_scrcpy, delete_device_path, export_ui_hierarchy,
-    list_device_files, mkdir_device_dir, rename_device_path, smoke_install_apk_batch,
+    capture_screenshot, check_adb, check_scrcpy, export_ui_hierarchy, list_device_files,
+    mkdir_device_dir, rename_device_path, smoke_delete_device_path, smoke_install_apk_batch,
     smoke_launch_app, smoke_start_logcat_stream, smoke_start_perf_monitor,
     smoke_stop_logcat_stream, smoke_stop_perf_monitor, LogcatEvent, PerfEvent,
 };
@@ -440,7 +440,7 @@ fn main() {
     if run_check(&mut checks, "capture_screenshot", || {
         let resp = capture_screenshot(
             serial.clone(),
-            out_dir.to_string_lossy().to_string(),
+            Some(out_dir.to_string_lossy().to_string()),
             Some(trace_id.clone()),
         )
         .map_err(|err| ("ERR_SCREENSHOT", err.to_string()))?;
@@ -650,7 +650,7 @@ fn main() {
                 .map_err(|err| ("ERR_MKDIR", err.to_string()))?;
             rename_device_path(serial.clone(), a.clone(), b.clone(), Some(trace_id.clone()))
                 .map_err(|err| ("ERR_RENAME", err.to_string()))?;
-            delete_device_path(serial.clone(), base.clone(), true, Some(trace_id.clone()))
+            smoke_delete_device_path(serial.clone(), base.clone(), true, &trace_id)
                 .map_err(|err| ("ERR_DELETE", err.to_string()))?;
 
             Ok((vec![], None, None))
@@ -841,6 +841,7 @@ fn main() {
             let resp = export_ui_hierarchy(
                 serial.clone(),
                 Some(out_dir.to_string_lossy().to_string()),
+                None,
                 Some(trace_id.clone()),
             )
             .map_err(|err| ("ERR_UI_EXPORT", err.to_string()))?;
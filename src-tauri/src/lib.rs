@@ -1,26 +1,66 @@
 pub mod app;
 
+use app::command_log::{command_log_path, init_command_log_disk};
 use app::commands::{
-    adb_connect, adb_pair, cancel_bugreport, capture_screenshot, capture_ui_hierarchy, check_adb,
-    check_scrcpy, clear_app_data, clear_logcat, delete_device_path, export_diagnostics_bundle,
-    export_logcat, export_ui_hierarchy, force_stop_app, generate_bugreport, get_app_basic_info,
-    get_app_icon, get_config, install_apk_batch, launch_app, launch_scrcpy, list_apps,
-    list_device_files, list_devices, mkdir_device_dir, open_app_info, persist_terminal_state,
-    prepare_bugreport_logcat, preview_local_file, pull_device_file, push_device_file,
-    query_bugreport_logcat, query_bugreport_logcat_around, reboot_devices, rename_device_path,
-    reset_config, run_shell, save_app_config, search_bugreport_logcat, set_app_enabled,
-    set_bluetooth_state, set_net_profiler_pinned_uids, set_wifi_state, start_bluetooth_monitor,
-    start_device_tracking, start_logcat, start_net_profiler, start_perf_monitor,
-    start_screen_record, start_terminal_session, stop_bluetooth_monitor, stop_device_tracking,
-    stop_logcat, stop_net_profiler, stop_perf_monitor, stop_screen_record, stop_terminal_session,
-    uninstall_app, write_terminal_session,
+    adb_connect, adb_pair, apply_device_profile, apply_on_connect_profile, begin_capture_context,
+    broadcast_pointer_gesture, broadcast_text, bust_cache, cancel_bugreport,
+    cancel_device_farm_test, cancel_file_transfer, cancel_shell_streaming, capture_device_profile,
+    capture_screenshot, capture_ui_hierarchy, check_adb, check_against_golden, check_environment,
+    check_scrcpy, clear_app_data, clear_global_proxy, clear_icon_cache, clear_logcat,
+    clear_mock_location, clear_network_shaping, compare_screenshots, convert_screen_recording,
+    create_issue_from_artifacts, delete_device_notes, delete_device_path, delete_device_profile,
+    detect_wireless_flow, enable_tcpip_and_connect, end_capture_context, export_bugreport_logcat,
+    export_device_farm_bundle, export_diagnostics_bundle, export_internal_metrics_prometheus,
+    export_logcat, export_session_report, export_ui_hierarchy, factory_reset_device,
+    fill_text_fields, force_stop_app, generate_bugreport, get_adb_tunnel_status,
+    get_app_basic_info, get_app_icon, get_app_log_status, get_audio_route, get_boot_info,
+    get_captured_push_tokens, get_config, get_debug_agent_status, get_device_detail,
+    get_device_history, get_device_notes, get_event_subscriptions, get_factory_reset_audit,
+    get_global_proxy, get_golden_results, get_internal_metrics, get_last_boot_logs, get_lock_state,
+    get_media_capabilities, get_media_sessions, get_on_connect_audit, get_recent_app_logs,
+    get_setup_wizard_state, get_telephony_info, get_usb_functions, import_logcat_file,
+    init_active_adb_server, inspect_app_binaries, install_apk_batch, install_ca_certificate,
+    join_recording_conversions, launch_app, launch_scrcpy, list_accessibility_services, list_apps,
+    list_apps_enriched, list_audio_forwards, list_build_variants, list_device_files,
+    list_device_profiles, list_devices, list_devtools_targets, list_dumpsys_parsers,
+    list_emulator_snapshots, list_goldens, list_input_devices, list_input_methods, list_overlays,
+    list_recording_conversions, list_ui_captures, load_emulator_snapshot, measure_app_startup,
+    mkdir_device_dir, mute_all, open_app_info, persist_terminal_state, prepare_bugreport_logcat,
+    prepare_factory_reset, preview_local_file, pull_device_file, push_debug_agent,
+    push_device_file, query_bugreport_logcat, query_bugreport_logcat_around, query_command_log,
+    query_device_timeline, reboot_devices, release_device, reload_adb_program,
+    remove_devtools_forwards, rename_device_path, replay_gesture_file, reserve_device,
+    reset_config, reset_setup_wizard, restore_adb_tunnel, restore_i18n_toggles,
+    run_accessibility_audit, run_custom_tool, run_device_farm_test, run_device_readiness_check,
+    run_dumpsys, run_group_action, run_jank_scenario, run_monkey_stress, run_setup_wizard_step,
+    run_shell, run_shell_pipeline, run_shell_streaming, run_test_hook, save_app_config,
+    save_device_notes, save_emulator_snapshot, save_golden, search_bugreport_logcat,
+    send_media_key, send_pointer_gesture, send_test_notification, set_accessibility_service,
+    set_app_enabled, set_bluetooth_state, set_global_proxy, set_i18n_toggles, set_input_method,
+    set_log_level, set_log_rotation, set_mock_location, set_net_profiler_pinned_uids,
+    set_network_shaping, set_overlay_state, set_restricted_mode, set_stay_awake, set_usb_function,
+    set_volume, set_wifi_state, skip_setup_wizard_step, start_activity_lifecycle_stream,
+    start_audio_forward, start_bluetooth_monitor, start_debug_agent, start_device_tracking,
+    start_dmesg_stream, start_gesture_recording, start_input_broadcast, start_logcat,
+    start_memory_leak_watch, start_net_profiler, start_perf_aggregation, start_perf_monitor,
+    start_screen_record, start_soak_test, start_terminal_session, start_thermal_monitor,
+    stop_activity_lifecycle_stream, stop_audio_forward, stop_bluetooth_monitor, stop_debug_agent,
+    stop_device_tracking, stop_dmesg_stream, stop_gesture_recording, stop_input_broadcast,
+    stop_logcat, stop_memory_leak_watch, stop_monkey_stress, stop_net_profiler,
+    stop_perf_aggregation, stop_perf_monitor, stop_screen_record, stop_soak_test,
+    stop_terminal_session, stop_thermal_monitor, stop_watch_apk, subscribe_events,
+    switch_adb_server, type_to_device, uninstall_app, uninstall_apps_batch, unlock_device,
+    unsubscribe_events, upload_artifacts, validate_scrcpy_options, wake_device, watch_apk,
+    write_terminal_session,
 };
 use app::logging::init_logging;
 use app::state::AppState;
+use tauri::Manager;
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     init_logging();
+    init_command_log_disk(command_log_path());
     tauri::Builder::default()
         .plugin(tauri_plugin_notification::init())
         .plugin(tauri_plugin_dialog::init())
@@ -29,29 +69,134 @@ pub fn run() {
         .plugin(tauri_plugin_process::init())
         .plugin(tauri_plugin_updater::Builder::new().build())
         .manage(AppState::new())
+        .setup(|app| {
+            init_active_adb_server();
+            restore_adb_tunnel(app.handle().clone());
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             get_config,
             save_app_config,
             reset_config,
+            set_restricted_mode,
             check_adb,
+            reload_adb_program,
             export_diagnostics_bundle,
+            query_command_log,
+            get_recent_app_logs,
+            set_log_rotation,
+            set_log_level,
+            get_app_log_status,
+            query_device_timeline,
+            export_session_report,
+            upload_artifacts,
+            export_device_farm_bundle,
+            run_device_farm_test,
+            cancel_device_farm_test,
+            create_issue_from_artifacts,
+            send_test_notification,
+            run_test_hook,
+            run_custom_tool,
+            watch_apk,
+            stop_watch_apk,
+            list_build_variants,
+            begin_capture_context,
+            end_capture_context,
+            start_soak_test,
+            stop_soak_test,
+            check_environment,
+            get_setup_wizard_state,
+            run_setup_wizard_step,
+            skip_setup_wizard_step,
+            reset_setup_wizard,
+            apply_on_connect_profile,
+            get_on_connect_audit,
+            prepare_factory_reset,
+            factory_reset_device,
+            get_factory_reset_audit,
+            get_internal_metrics,
+            export_internal_metrics_prometheus,
+            subscribe_events,
+            unsubscribe_events,
+            get_event_subscriptions,
             list_devices,
+            get_device_detail,
             start_device_tracking,
+            get_device_history,
+            get_device_notes,
+            save_device_notes,
+            delete_device_notes,
+            capture_device_profile,
+            list_device_profiles,
+            apply_device_profile,
+            delete_device_profile,
+            reserve_device,
+            release_device,
             stop_device_tracking,
+            switch_adb_server,
+            get_adb_tunnel_status,
             adb_pair,
             adb_connect,
+            detect_wireless_flow,
+            enable_tcpip_and_connect,
             run_shell,
+            run_shell_streaming,
+            cancel_shell_streaming,
+            run_shell_pipeline,
+            run_dumpsys,
+            list_dumpsys_parsers,
+            get_last_boot_logs,
             start_terminal_session,
             write_terminal_session,
             stop_terminal_session,
             persist_terminal_state,
             reboot_devices,
+            run_device_readiness_check,
+            wake_device,
+            unlock_device,
+            get_lock_state,
+            get_telephony_info,
+            get_boot_info,
+            get_audio_route,
+            set_volume,
+            mute_all,
+            send_media_key,
+            get_media_sessions,
+            get_media_capabilities,
+            list_overlays,
+            list_accessibility_services,
+            set_accessibility_service,
+            list_input_methods,
+            set_input_method,
+            set_overlay_state,
+            get_usb_functions,
+            set_usb_function,
+            list_input_devices,
+            set_stay_awake,
+            set_mock_location,
+            clear_mock_location,
+            list_emulator_snapshots,
+            save_emulator_snapshot,
+            load_emulator_snapshot,
             set_wifi_state,
+            set_i18n_toggles,
+            restore_i18n_toggles,
+            get_global_proxy,
+            set_global_proxy,
+            clear_global_proxy,
+            install_ca_certificate,
             set_bluetooth_state,
             install_apk_batch,
             capture_screenshot,
+            run_group_action,
+            compare_screenshots,
+            save_golden,
+            check_against_golden,
+            list_goldens,
+            get_golden_results,
             start_screen_record,
             stop_screen_record,
+            convert_screen_recording,
             list_device_files,
             pull_device_file,
             push_device_file,
@@ -60,22 +205,62 @@ pub fn run() {
             delete_device_path,
             preview_local_file,
             capture_ui_hierarchy,
+            run_accessibility_audit,
             export_ui_hierarchy,
+            send_pointer_gesture,
+            start_gesture_recording,
+            stop_gesture_recording,
+            replay_gesture_file,
+            broadcast_pointer_gesture,
+            broadcast_text,
+            start_input_broadcast,
+            stop_input_broadcast,
+            run_jank_scenario,
+            run_monkey_stress,
+            stop_monkey_stress,
+            fill_text_fields,
+            type_to_device,
+            list_ui_captures,
             start_perf_monitor,
             stop_perf_monitor,
+            start_perf_aggregation,
+            stop_perf_aggregation,
+            start_thermal_monitor,
+            stop_thermal_monitor,
+            start_memory_leak_watch,
+            stop_memory_leak_watch,
             start_net_profiler,
             stop_net_profiler,
             set_net_profiler_pinned_uids,
             start_logcat,
             stop_logcat,
             clear_logcat,
+            start_dmesg_stream,
+            stop_dmesg_stream,
+            start_activity_lifecycle_stream,
+            stop_activity_lifecycle_stream,
             export_logcat,
             start_bluetooth_monitor,
             stop_bluetooth_monitor,
             list_apps,
+            list_apps_enriched,
             get_app_basic_info,
+            bust_cache,
             get_app_icon,
+            inspect_app_binaries,
+            measure_app_startup,
+            get_captured_push_tokens,
+            clear_icon_cache,
+            push_debug_agent,
+            start_debug_agent,
+            stop_debug_agent,
+            get_debug_agent_status,
+            list_devtools_targets,
+            remove_devtools_forwards,
+            set_network_shaping,
+            clear_network_shaping,
             uninstall_app,
+            uninstall_apps_batch,
             force_stop_app,
             clear_app_data,
             set_app_enabled,
@@ -83,13 +268,26 @@ pub fn run() {
             launch_app,
             check_scrcpy,
             launch_scrcpy,
+            validate_scrcpy_options,
+            start_audio_forward,
+            stop_audio_forward,
+            list_audio_forwards,
+            list_recording_conversions,
             generate_bugreport,
             cancel_bugreport,
+            cancel_file_transfer,
             prepare_bugreport_logcat,
             query_bugreport_logcat,
             search_bugreport_logcat,
+            import_logcat_file,
+            export_bugreport_logcat,
             query_bugreport_logcat_around
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while running tauri application")
+        .run(|app, event| {
+            if let tauri::RunEvent::Exit = event {
+                join_recording_conversions(app.state::<AppState>().inner());
+            }
+        });
 }
@@ -34,6 +34,7 @@ import type {
   AppConfig,
   AppBasicInfo,
   AppInfo,
+  AppListProgress,
   BugreportLogFilters,
   BugreportLogMatch,
   BugreportLogRow,
@@ -41,6 +42,9 @@ import type {
   BugreportResult,
   DeviceFileEntry,
   DeviceInfo,
+  DeviceReconnectStatus,
+  DeviceDetailUpdatedPayload,
+  DeviceTransitionPayload,
   FilePreview,
   LogcatEvent,
   NetProfilerEvent,
@@ -54,6 +58,7 @@ import {
   adbConnect,
   adbPair,
   cancelBugreport,
+  cancelFileTransfer,
   captureScreenshot,
   captureUiHierarchy,
   checkAdb,
@@ -145,7 +150,9 @@ import {
 import {
   createInitialTaskState,
   createTask,
+  fileTransferFailureStatus,
   inflateStoredTaskState,
+  isCancellableFileTransfer,
   parseStoredTaskState,
   sanitizeTaskStateForStorage,
   summarizeTask,
@@ -159,12 +166,15 @@ import {
   applyDeviceDetailPatch,
   filterDevicesBySearch,
   formatDeviceInfoMarkdown,
+  isSlowUsbConnection,
   mergeDeviceDetails,
   reduceSelectionToOne,
   resolveSelectedSerials,
   selectSerialsForGroup,
+  upsertDeviceInfo,
 } from "./deviceUtils";
 import { clampRefreshIntervalSec } from "./deviceAutoRefresh";
+import { mergeAppListProgress } from "./appList";
 import { bugreportLogLineMatches, buildBugreportLogFindPattern } from "./bugreportLogFind";
 import {
   findRunningBugreportTaskIdForSerial,
@@ -194,6 +204,8 @@ type FileTransferProgress = {
   direction: string;
   progress?: number | null;
   message?: string | null;
+  bytes_transferred?: number | null;
+  total_bytes?: number | null;
   trace_id: string;
 };
 type ApkInstallEvent = {
@@ -205,7 +217,8 @@ type ApkInstallEvent = {
   raw_output?: string | null;
   trace_id: string;
 };
-type DeviceTrackingSnapshotPayload = { trace_id: string; devices: DeviceInfo[] };
+// `changed` lists the serials whose details the backend pushes as `device-detail-updated`.
+type DeviceTrackingSnapshotPayload = { trace_id: string; devices: DeviceInfo[]; changed?: string[] };
 type LogcatLineEntry = { id: number; text: string };
 type PerfMonitorState = {
   running: boolean;
@@ -1441,6 +1454,9 @@ function App() {
   const [filesPath, setFilesPath] = useState("/sdcard");
   const [files, setFiles] = useState<DeviceFileEntry[]>([]);
   const [filePreview, setFilePreview] = useState<FilePreview | null>(null);
+  // "auto" leaves it to the backend, which compresses pulls from TCP/IP devices.
+  const [filePullCompression, setFilePullCompression] = useState<"auto" | "on" | "off">("auto");
+  const filePullCompress = filePullCompression === "auto" ? undefined : filePullCompression === "on";
   const [filePreviewDevicePath, setFilePreviewDevicePath] = useState<string | null>(null);
   const [filesSelectedPaths, setFilesSelectedPaths] = useState<string[]>([]);
   const [filesSearchQuery, setFilesSearchQuery] = useState("");
@@ -1600,13 +1616,14 @@ function App() {
   const prevTaskItemsRef = useRef<TaskItem[] | null>(null);
   const notifiedTaskIdsRef = useRef<Set<string>>(new Set());
   const appsDetailsSeqRef = useRef(0);
+  const appsLoadTraceIdRef = useRef<string | null>(null);
   const refreshSeqRef = useRef(0);
   const detailRefreshSeqRef = useRef(0);
   const detailRefreshTimerRef = useRef<number | null>(null);
   const deviceAutoRefreshLastWarnAtRef = useRef(0);
   const deviceTrackingLastSnapshotAtRef = useRef<number>(0);
   const deviceTrackingLastFallbackAtRef = useRef<number>(0);
-  const deviceTrackingPendingSnapshotRef = useRef<DeviceInfo[] | null>(null);
+  const deviceTrackingPendingSnapshotRef = useRef<{ devices: DeviceInfo[]; delta: boolean } | null>(null);
   const deviceTrackingRestartInFlightRef = useRef(false);
   const deviceTrackingFallbackInFlightRef = useRef(false);
   const deviceTrackingStartedAtRef = useRef<number>(0);
@@ -2835,7 +2852,10 @@ function App() {
     }, delayMs);
   };
 
-  const applyDeviceTrackingSnapshot = (nextDevices: DeviceInfo[], options: { allowDetailRefresh: boolean }) => {
+  const applyDeviceTrackingSnapshot = (
+    nextDevices: DeviceInfo[],
+    options: { allowDetailRefresh: boolean; deltaDetail?: boolean },
+  ) => {
     const prevBySerial = new Map(
       devicesRef.current.map((device) => [device.summary.serial, device.summary.state] as const),
     );
@@ -2850,7 +2870,8 @@ function App() {
     // Tracking snapshots contain summaries only; keep the last known detail to avoid UI flicker.
     setDevices((prev) => mergeDeviceDetails(prev, nextDevices, { preserveMissingDetail: true }));
     setSelectedSerials((prev) => resolveSelectedSerials(prev, nextDevices));
-    if (options.allowDetailRefresh && shouldRefreshDetail) {
+    // Delta snapshots are followed by per-device detail events; only older payloads need a full refresh.
+    if (options.allowDetailRefresh && shouldRefreshDetail && !options.deltaDetail) {
       scheduleDeviceDetailRefresh(800, { notifyOnError: false });
     }
   };
@@ -2861,7 +2882,7 @@ function App() {
       return;
     }
     deviceTrackingPendingSnapshotRef.current = null;
-    applyDeviceTrackingSnapshot(pending, options);
+    applyDeviceTrackingSnapshot(pending.devices, { ...options, deltaDetail: pending.delta });
   };
 
   const refreshDeviceSummaryOnce = async (notifyOnError = false) => {
@@ -3060,14 +3081,71 @@ function App() {
       }
       deviceTrackingLastSnapshotAtRef.current = Date.now();
       deviceTrackingLastFallbackAtRef.current = Date.now();
+      const pending = { devices: nextDevices, delta: Array.isArray(event.payload?.changed) };
       if (busyRef.current) {
-        deviceTrackingPendingSnapshotRef.current = nextDevices;
+        // Detail events for new devices may arrive before a held snapshot lands, so refresh fully later.
+        deviceTrackingPendingSnapshotRef.current = { ...pending, delta: false };
         return;
       }
-      deviceTrackingPendingSnapshotRef.current = nextDevices;
+      deviceTrackingPendingSnapshotRef.current = pending;
       flushPendingDeviceTrackingSnapshot({ allowDetailRefresh: true });
 	    });
 
+    const unlistenDetail = listen<DeviceDetailUpdatedPayload>("device-detail-updated", (event) => {
+      const device = event.payload?.device;
+      if (!device) {
+        return;
+      }
+      setDevices((prev) => upsertDeviceInfo(prev, device));
+    });
+
+    const unlistenTransition = listen<DeviceTransitionPayload>("device-transition", (event) => {
+      const transition = event.payload?.transition;
+      if (!transition) {
+        return;
+      }
+      const label = transition.model ? `${transition.model} (${transition.serial})` : transition.serial;
+      switch (transition.kind) {
+        case "connected":
+          pushToastRef.current(
+            transition.state === "unauthorized"
+              ? `${label} connected. Allow USB debugging on the device.`
+              : `${label} connected.`,
+            "info",
+          );
+          break;
+        case "disconnected":
+          pushToastRef.current(`${label} disconnected.`, "info");
+          break;
+        case "authorized":
+          pushToastRef.current(`${label} authorized.`, "info");
+          break;
+        case "online":
+          pushToastRef.current(`${label} is back online.`, "info");
+          break;
+        case "unauthorized":
+          pushToastRef.current(`${label} needs USB debugging authorization.`, "error");
+          break;
+        case "offline":
+          pushToastRef.current(`${label} went offline.`, "error");
+          break;
+        default:
+          break;
+      }
+    });
+
+    const unlistenReconnect = listen<DeviceReconnectStatus>("device-reconnect", (event) => {
+      const status = event.payload;
+      if (status?.status === "reconnected") {
+        pushToastRef.current(`Reconnected ${status.serial}.`, "info");
+      } else if (status?.status === "gave_up") {
+        pushToastRef.current(
+          `Could not reconnect ${status.serial}${status.error ? `: ${status.error}` : "."}`,
+          "error",
+        );
+      }
+    });
+
     deviceTrackingStartedAtRef.current = Date.now();
     deviceTrackingLastSnapshotAtRef.current = 0;
     deviceTrackingLastFallbackAtRef.current = 0;
@@ -3075,9 +3153,17 @@ function App() {
     void refreshDeviceSummaryOnce(false);
     return () => {
       void unlisten.then((unlisten) => unlisten());
+      void unlistenDetail.then((unlisten) => unlisten());
+      void unlistenTransition.then((unlisten) => unlisten());
+      void unlistenReconnect.then((unlisten) => unlisten());
       void stopDeviceTracking().catch(() => null);
     };
-  }, [config?.device.auto_refresh_enabled, config?.device.refresh_interval]);
+  }, [
+    config?.device.auto_refresh_enabled,
+    config?.device.refresh_interval,
+    config?.device.auto_reconnect,
+    config?.device.auto_reconnect_window_secs,
+  ]);
 
   useEffect(() => {
     if (!config?.device.auto_refresh_enabled) {
@@ -3557,6 +3643,13 @@ function App() {
         });
       }
     });
+    const unlistenAppListProgress = listen<AppListProgress>("app-list-progress", (event) => {
+      const payload = event.payload;
+      if (payload.trace_id !== appsLoadTraceIdRef.current) {
+        return;
+      }
+      setApps((prev) => mergeAppListProgress(prev, payload.apps));
+    });
     const unlistenFileTransferProgress = listen<FileTransferProgress>("file-transfer-progress", (event) => {
       const payload = event.payload;
       const taskId = fileTransferTaskByTraceIdRef.current[payload.trace_id];
@@ -3564,9 +3657,23 @@ function App() {
         return;
       }
       const progress = payload.progress ?? null;
+      if (progress == null && payload.message) {
+        // Status-only updates, e.g. a chunked pull waiting for the device to come back.
+        dispatchTasks({
+          type: "TASK_UPDATE_DEVICE",
+          id: taskId,
+          serial: payload.serial,
+          patch: { message: payload.message },
+        });
+        return;
+      }
+      const message =
+        payload.bytes_transferred != null && payload.total_bytes != null
+          ? `${formatBytes(payload.bytes_transferred)} / ${formatBytes(payload.total_bytes)}`
+          : (payload.message ?? null);
       const patch = {
         progress,
-        ...(progress != null && progress < 100 ? { message: payload.message ?? null } : {}),
+        ...(progress != null && progress < 100 ? { message } : {}),
       };
       dispatchTasks({
         type: "TASK_UPDATE_DEVICE",
@@ -3686,6 +3793,7 @@ function App() {
         terminalFlushTimerRef.current = null;
       }
       terminalPendingRef.current = {};
+      void unlistenAppListProgress.then((unlisten) => unlisten());
       void unlistenFileTransferProgress.then((unlisten) => unlisten());
       void unlistenApkInstallEvent.then((unlisten) => unlisten());
       void unlistenBugreportProgress.then((unlisten) => unlisten());
@@ -4356,8 +4464,9 @@ function App() {
 	      pushToast("Select at least one device for bugreport.", "error");
 	      return;
 	    }
-	    let outputDir = config?.output_path || "";
-	    if (!outputDir) {
+	    // The configured folder is resolved by the backend so it can add the dated subfolder.
+	    let outputDir: string | undefined;
+	    if (!(config?.output?.bugreports_dir || config?.output_path)) {
       const selected = await openDialog({
         title: "Select output folder",
         directory: true,
@@ -4419,6 +4528,25 @@ function App() {
 	    }
 	  };
 
+  const handleCancelFileTransfer = async (task: TaskItem) => {
+    if (!task.trace_id) {
+      return;
+    }
+    try {
+      await cancelFileTransfer(task.trace_id);
+      Object.keys(task.devices).forEach((serial) => {
+        dispatchTasks({
+          type: "TASK_UPDATE_DEVICE",
+          id: task.id,
+          serial,
+          patch: { message: "Cancel requested." },
+        });
+      });
+    } catch (error) {
+      pushToast(formatError(error), "error");
+    }
+  };
+
   const handleCancelBugreport = async () => {
     if (!selectedSerials.length) {
       pushToast("Select at least one device to cancel bugreport.", "error");
@@ -4693,7 +4821,6 @@ function App() {
       const response = await exportLogcat(
         serial,
         logcatFiltered.lines.map((entry) => entry.text),
-        config?.file_gen_output_path || config?.output_path,
       );
       setLogcatLastExport(response.data.output_path);
       pushToast("Logcat exported.", "info");
@@ -5345,7 +5472,7 @@ function App() {
         dispatchTasks({ type: "TASK_SET_TRACE", id: taskId, trace_id: traceId });
         fileTransferTaskByTraceIdRef.current[traceId] = taskId;
         try {
-          const response = await pullDeviceFile(serial, entry.path, outputDir, traceId);
+          const response = await pullDeviceFile(serial, entry.path, outputDir, traceId, true, filePullCompress);
           dispatchTasks({
             type: "TASK_UPDATE_DEVICE",
             id: taskId,
@@ -5359,13 +5486,14 @@ function App() {
           });
           dispatchTasks({ type: "TASK_SET_STATUS", id: taskId, status: "success" });
         } catch (error) {
+          const status = fileTransferFailureStatus(formatError(error));
           dispatchTasks({
             type: "TASK_UPDATE_DEVICE",
             id: taskId,
             serial,
-            patch: { status: "error", message: formatError(error), progress: null },
+            patch: { status, message: formatError(error), progress: null },
           });
-          dispatchTasks({ type: "TASK_SET_STATUS", id: taskId, status: "error" });
+          dispatchTasks({ type: "TASK_SET_STATUS", id: taskId, status });
         } finally {
           delete fileTransferTaskByTraceIdRef.current[traceId];
         }
@@ -5606,7 +5734,7 @@ function App() {
     fileTransferTaskByTraceIdRef.current[traceId] = taskId;
     setBusy(true);
     try {
-      const response = await pushDeviceFile(serial, selected, remotePath, traceId);
+      const response = await pushDeviceFile(serial, selected, remotePath, traceId, undefined, true);
       dispatchTasks({
         type: "TASK_UPDATE_DEVICE",
         id: taskId,
@@ -5623,13 +5751,14 @@ function App() {
         pushToast(`Uploaded. Refresh failed: ${formatError(error)}`, "error");
       }
     } catch (error) {
+      const status = fileTransferFailureStatus(formatError(error));
       dispatchTasks({
         type: "TASK_UPDATE_DEVICE",
         id: taskId,
         serial: activeSerial,
-        patch: { status: "error", message: formatError(error), progress: null },
+        patch: { status, message: formatError(error), progress: null },
       });
-      dispatchTasks({ type: "TASK_SET_STATUS", id: taskId, status: "error" });
+      dispatchTasks({ type: "TASK_SET_STATUS", id: taskId, status });
       pushToast(formatError(error), "error");
     } finally {
       delete fileTransferTaskByTraceIdRef.current[traceId];
@@ -5702,7 +5831,7 @@ function App() {
               dispatchTasks({ type: "TASK_SET_TRACE", id: taskId, trace_id: traceId });
               fileTransferTaskByTraceIdRef.current[traceId] = taskId;
               try {
-                const response = await pushDeviceFile(filesCtx.serial, path, remotePath, traceId);
+                const response = await pushDeviceFile(filesCtx.serial, path, remotePath, traceId, undefined, true);
                 dispatchTasks({
                   type: "TASK_UPDATE_DEVICE",
                   id: taskId,
@@ -5712,13 +5841,14 @@ function App() {
                 dispatchTasks({ type: "TASK_SET_STATUS", id: taskId, status: "success" });
                 existing.add(filename);
               } catch (error) {
+                const status = fileTransferFailureStatus(formatError(error));
                 dispatchTasks({
                   type: "TASK_UPDATE_DEVICE",
                   id: taskId,
                   serial: filesCtx.serial,
-                  patch: { status: "error", message: formatError(error), progress: null },
+                  patch: { status, message: formatError(error), progress: null },
                 });
-                dispatchTasks({ type: "TASK_SET_STATUS", id: taskId, status: "error" });
+                dispatchTasks({ type: "TASK_SET_STATUS", id: taskId, status });
                 pushToast(`Upload failed: ${filename} (${formatError(error)})`, "error");
               } finally {
                 delete fileTransferTaskByTraceIdRef.current[traceId];
@@ -5788,7 +5918,7 @@ function App() {
 		    fileTransferTaskByTraceIdRef.current[traceId] = taskId;
 		    setBusy(true);
 		    try {
-		      const response = await pullDeviceFile(serial, entry.path, outputDir, traceId);
+		      const response = await pullDeviceFile(serial, entry.path, outputDir, traceId, true, filePullCompress);
 		      dispatchTasks({
 		        type: "TASK_UPDATE_DEVICE",
 		        id: taskId,
@@ -5815,13 +5945,14 @@ function App() {
 	        });
 	      }
 		    } catch (error) {
+		      const status = fileTransferFailureStatus(formatError(error));
 		      dispatchTasks({
 		        type: "TASK_UPDATE_DEVICE",
 		        id: taskId,
 		        serial,
-		        patch: { status: "error", message: formatError(error), progress: null },
+		        patch: { status, message: formatError(error), progress: null },
 		      });
-		      dispatchTasks({ type: "TASK_SET_STATUS", id: taskId, status: "error" });
+		      dispatchTasks({ type: "TASK_SET_STATUS", id: taskId, status });
 		      pushToast(formatError(error), "error");
 		    } finally {
 		      delete fileTransferTaskByTraceIdRef.current[traceId];
@@ -5861,7 +5992,7 @@ function App() {
         fileTransferTaskByTraceIdRef.current[traceId] = taskId;
         setBusy(true);
         try {
-          const response = await pullDeviceFile(serial, entry.path, outputDir, traceId);
+          const response = await pullDeviceFile(serial, entry.path, outputDir, traceId, true, filePullCompress);
           dispatchTasks({
             type: "TASK_UPDATE_DEVICE",
             id: taskId,
@@ -5888,13 +6019,14 @@ function App() {
             pushToast(`Preview failed: ${formatError(error)}`, "error");
           }
         } catch (error) {
+          const status = fileTransferFailureStatus(formatError(error));
           dispatchTasks({
             type: "TASK_UPDATE_DEVICE",
             id: taskId,
             serial,
-            patch: { status: "error", message: formatError(error), progress: null },
+            patch: { status, message: formatError(error), progress: null },
           });
-          dispatchTasks({ type: "TASK_SET_STATUS", id: taskId, status: "error" });
+          dispatchTasks({ type: "TASK_SET_STATUS", id: taskId, status });
           pushToast(formatError(error), "error");
         } finally {
           delete fileTransferTaskByTraceIdRef.current[traceId];
@@ -5943,7 +6075,7 @@ function App() {
     }
     setBusy(true);
     try {
-      const response = await exportUiHierarchy(serial, config?.file_gen_output_path || config?.output_path);
+      const response = await exportUiHierarchy(serial);
       setUiExportResult(response.data.html_path);
       pushToast("UI inspector export completed.", "info");
     } catch (error) {
@@ -5959,13 +6091,21 @@ function App() {
       return;
     }
     setBusy(true);
+    const traceId = crypto.randomUUID();
+    if (appsIncludeVersions) {
+      appsLoadTraceIdRef.current = traceId;
+      setApps([]);
+    }
     try {
       const response = await listApps(
         serial,
         appsThirdPartyOnly ? true : undefined,
         appsIncludeVersions,
+        traceId,
+        undefined,
+        true,
       );
-      setApps(response.data);
+      setApps(response.data.apps);
       setAppsVisibleCount(APPS_PAGE_SIZE);
       setSelectedApp(null);
       setSelectedAppDetails(null);
@@ -5978,6 +6118,9 @@ function App() {
     } catch (error) {
       pushToast(formatError(error), "error");
     } finally {
+      if (appsLoadTraceIdRef.current === traceId) {
+        appsLoadTraceIdRef.current = null;
+      }
       setBusy(false);
     }
   };
@@ -6039,7 +6182,12 @@ function App() {
       }));
       void (async () => {
         try {
-          const response = await getAppIcon(next.serial, next.app.package_name, next.app.apk_path ?? undefined);
+          const response = await getAppIcon(
+            next.serial,
+            next.app.package_name,
+            next.app.apk_path ?? undefined,
+            next.app.version_code ?? undefined,
+          );
           setAppIconsByKey((prev) => ({
             ...prev,
             [next.key]: { status: "ready", dataUrl: response.data.data_url },
@@ -6353,8 +6501,7 @@ function App() {
       pushToast("Select at least one device.", "error");
       return;
     }
-    const outputDir = (config?.output_path ?? "").trim();
-    if (!outputDir) {
+    if (!(config?.output?.screenshots_dir || config?.output_path || "").trim()) {
       pushToast("Set an output folder in Settings to save screenshots.", "error");
       return;
     }
@@ -6372,7 +6519,7 @@ function App() {
       await Promise.all(
         serials.map(async (serial) => {
           try {
-            const response = await captureScreenshot(serial, outputDir);
+            const response = await captureScreenshot(serial);
             if (!traceSet && response.trace_id) {
               traceSet = true;
               dispatchTasks({ type: "TASK_SET_TRACE", id: taskId, trace_id: response.trace_id });
@@ -6415,14 +6562,13 @@ function App() {
     setBusy(true);
     try {
       if (screenRecordRemote) {
-        const outputDir = (config?.output_path ?? "").trim() || undefined;
         const taskId = beginTask({
           kind: "screen_record_stop",
           title: `Screen Record Stop: ${singleSerial}`,
           serials: [singleSerial],
         });
         try {
-          const response = await stopScreenRecord(singleSerial, outputDir);
+          const response = await stopScreenRecord(singleSerial);
           const savedPath = response.data?.trim();
           setScreenRecordRemote(null);
           dispatchTasks({ type: "TASK_SET_TRACE", id: taskId, trace_id: response.trace_id });
@@ -9177,7 +9323,14 @@ function App() {
                                   {task.trace_id ? ` • ${task.trace_id}` : ""}
                                 </p>
                               </div>
-                              <span className={`status-pill ${statusTone}`}>{task.status}</span>
+                              <div className="button-row">
+                                {isCancellableFileTransfer(task) && (
+                                  <button className="ghost" onClick={() => void handleCancelFileTransfer(task)}>
+                                    Cancel
+                                  </button>
+                                )}
+                                <span className={`status-pill ${statusTone}`}>{task.status}</span>
+                              </div>
                             </div>
                             <div className="task-summary">
                               <span className="badge">{summary.serials.length} devices</span>
@@ -9357,6 +9510,11 @@ function App() {
                               </div>
                               <div className="device-tags">
                                 {groupMap[serial] && <span className="group-tag">{groupMap[serial]}</span>}
+                                {isSlowUsbConnection(device) && (
+                                  <span className="group-tag" title={detail?.usb_speed ?? "USB 2.0"}>
+                                    USB 2.0
+                                  </span>
+                                )}
                               </div>
                             </div>
                             <div className="device-cell device-serial">{serial}</div>
@@ -10469,6 +10627,16 @@ function App() {
                         >
                           Clear selection
                         </button>
+                        <select
+                          value={filePullCompression}
+                          onChange={(event) => setFilePullCompression(event.target.value as "auto" | "on" | "off")}
+                          aria-label="Download compression"
+                          title="Gzip files on the device before downloading; helps text-heavy files on slow links"
+                        >
+                          <option value="auto">Compress: auto (Wi-Fi)</option>
+                          <option value="on">Compress: on</option>
+                          <option value="off">Compress: off</option>
+                        </select>
                         <button
                           onClick={handleFilesPullSelected}
                           disabled={busy || selectedSerials.length !== 1 || !hasFileSelection}
@@ -12273,6 +12441,32 @@ function App() {
                             Leave blank to use <code>adb</code> from your PATH. Otherwise select the{" "}
                             <code>adb</code> executable from Android platform-tools.
                           </div>
+                          <label className="toggle">
+                            <input
+                              type="checkbox"
+                              checked={config.adb.socket_client ?? true}
+                              onChange={(event) =>
+                                setConfig((prev) =>
+                                  prev ? { ...prev, adb: { ...prev.adb, socket_client: event.target.checked } } : prev,
+                                )
+                              }
+                            />
+                            Talk to the adb server directly for shell commands (faster monitors)
+                          </label>
+                          <label className="toggle">
+                            <input
+                              type="checkbox"
+                              checked={config.adb.compress_wireless_pulls ?? true}
+                              onChange={(event) =>
+                                setConfig((prev) =>
+                                  prev
+                                    ? { ...prev, adb: { ...prev.adb, compress_wireless_pulls: event.target.checked } }
+                                    : prev,
+                                )
+                              }
+                            />
+                            Compress downloads from Wi-Fi devices (gzip on device)
+                          </label>
                           <div className="button-row">
                             <button type="button" className="ghost" onClick={handleBrowseAdbPath} disabled={busy}>
                               Browse
@@ -12682,6 +12876,47 @@ function App() {
                             see flaky ADB/USB behavior.
                           </div>
                         </div>
+                        <div className="settings-group">
+                          <h3>Timeouts</h3>
+                          {(
+                            [
+                              ["probe_secs", "Device probes (sec)", 5],
+                              ["device_command_secs", "Device commands (sec)", 10],
+                              ["install_secs", "APK installs (sec)", 180],
+                              ["transfer_secs", "File transfers (sec)", 600],
+                            ] as const
+                          ).map(([key, label, fallback]) => (
+                            <label key={key}>
+                              {label}
+                              <input
+                                type="number"
+                                min={1}
+                                value={config.timeouts?.[key] ?? fallback}
+                                onChange={(event) =>
+                                  setConfig((prev) =>
+                                    prev
+                                      ? {
+                                          ...prev,
+                                          timeouts: {
+                                            probe_secs: 5,
+                                            device_command_secs: 10,
+                                            install_secs: 180,
+                                            transfer_secs: 600,
+                                            ...prev.timeouts,
+                                            [key]: Number(event.target.value),
+                                          },
+                                        }
+                                      : prev,
+                                  )
+                                }
+                              />
+                            </label>
+                          ))}
+                          <div className="muted settings-hint">
+                            Raise these for slow devices or wireless adb. Probes cover device details and quick reads;
+                            out-of-range values are clamped when saved.
+                          </div>
+                        </div>
                         <div className="settings-group">
                           <h3>Screenshot</h3>
                           <label>
@@ -1,28 +1,141 @@
 import { invoke, type InvokeArgs } from "@tauri-apps/api/core";
 import type {
+  AccessibilityAudit,
+  AccessibilityServiceInfo,
   AdbInfo,
+  AdbTunnelStatus,
   ApkBatchInstallResult,
+  ApkWatchOptions,
   AppConfig,
   AppBasicInfo,
+  AppBinaryInspection,
+  AppLogEntry,
+  AppLogLevel,
+  AppLogStatus,
+  AppStartupResult,
   AppIcon,
-  AppInfo,
+  AppUninstallResult,
+  AppListPage,
+  AppListQuery,
+  AppListEntry,
+  ArtifactUploadResult,
+  AudioForwardCodec,
+  AudioForwardSession,
+  AudioForwardSource,
+  AudioRouteInfo,
+  AudioStream,
+  BootInfo,
   BugreportLogAroundPage,
   BugreportLogFilters,
   BugreportLogPage,
   BugreportLogSearchResult,
   BugreportLogSummary,
   BugreportResult,
+  BuildVariantApk,
+  CaCertificateInstall,
+  CacheScope,
+  CaptureContextInfo,
+  CaptureContextResult,
+  CapturedPushToken,
+  ChecklistItemInput,
+  CommandLogEntry,
+  CommandLogQuery,
   CommandResponse,
   CommandResult,
+  CreatedIssue,
+  CustomToolResult,
+  DeviceFarmBundle,
+  DeviceFarmRequest,
+  DeviceFarmRunResult,
   DeviceFileEntry,
+  DeviceHistory,
+  DeviceLockState,
+  DeviceNotes,
+  DeviceProfile,
+  DeviceProfileApplyReport,
+  DeviceProfileSettingKey,
+  DeviceReadinessResult,
+  DeviceReservation,
+  DeviceUnlockResult,
+  DevtoolsSocket,
+  DmesgStreamInfo,
+  DumpsysParserInfo,
+  DumpsysResult,
+  EmulatorSnapshot,
   DeviceInfo,
+  EnvironmentReport,
+  EventChannel,
+  EventSubscriptionInfo,
+  FactoryResetAuditEntry,
+  FactoryResetChallenge,
+  FactoryResetReport,
   FilePreview,
+  GestureRecordingSummary,
+  GestureReplayResult,
+  GoldenCheckResult,
+  GoldenImage,
+  GroupActionParams,
+  GroupActionResult,
+  HookRunResult,
   HostCommandResult,
+  DebugAgentStatus,
+  I18nToggleResult,
+  IconCacheStats,
+  InputBroadcastDeviceResult,
+  InputBroadcastInfo,
+  InputDeviceInventory,
+  InputMethodInfo,
+  InternalMetrics,
+  JankScenarioResult,
+  LastBootLogs,
+  LifecycleStreamInfo,
   LogcatExportResult,
+  LogcatFileExportFormat,
+  LogcatFileExportResult,
+  MediaCapabilities,
+  MediaKey,
+  MediaSessionInfo,
+  MockLocationResult,
+  MonkeyStressResult,
+  NetworkShapingResult,
+  OnConnectAuditEntry,
+  OverlayPackage,
+  PointerGesture,
+  PointerGestureResult,
+  ProxyStatus,
+  PseudoLocale,
+  ReadinessThresholds,
+  RebootResult,
+  RecordingConversionStatus,
   ScrcpyInfo,
+  ScrcpyOptions,
+  ScrcpyValidation,
+  ScreenRecordConversionResult,
+  ScreenshotComparison,
+  SessionReportResult,
+  SessionTimeRange,
+  SetupWizardState,
+  SetupWizardStepId,
+  ShellPipelineResult,
+  ShellPipelineStep,
+  ShellStreamInfo,
+  SoakProfile,
+  SoakTestInfo,
+  SoakTestReport,
+  TcpipConnectResult,
+  TelephonyInfo,
   TerminalSessionInfo,
+  TextFieldEntry,
+  TextFieldFillResult,
+  TimelineEvent,
+  TimelineQuery,
+  TypeTextResult,
+  UiCaptureRecord,
+  UiDumpBackend,
   UiHierarchyCaptureResult,
   UiHierarchyExportResult,
+  UsbFunctionState,
+  WirelessFlowInfo,
 } from "./types";
 import { isTauriRuntime } from "./tauriEnv";
 
@@ -58,6 +171,281 @@ export const checkAdb = async (commandPath?: string) => {
   return tauriInvoke<CommandResponse<AdbInfo>>("check_adb", payload);
 };
 
+export const reloadAdbProgram = async () => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<string>>("reload_adb_program", {
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const checkEnvironment = async () => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<EnvironmentReport>>("check_environment", {
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const getSetupWizardState = async () => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<SetupWizardState>>("get_setup_wizard_state", {
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const runSetupWizardStep = async (step: SetupWizardStepId, outputDir?: string) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<SetupWizardState>>("run_setup_wizard_step", {
+    step,
+    output_dir: outputDir ?? null,
+    outputDir: outputDir ?? null,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const skipSetupWizardStep = async (step: SetupWizardStepId) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<SetupWizardState>>("skip_setup_wizard_step", {
+    step,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const resetSetupWizard = async () => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<SetupWizardState>>("reset_setup_wizard", {
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const queryCommandLog = async (query?: CommandLogQuery) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<CommandLogEntry[]>>("query_command_log", {
+    query: query ?? null,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const getAppLogStatus = async () => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<AppLogStatus>>("get_app_log_status", {
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const setLogLevel = async (level: AppLogLevel, module?: string) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<AppLogStatus>>("set_log_level", {
+    module: module ?? null,
+    level,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const setLogRotation = async (
+  logToFile: boolean,
+  maxLogFiles: number,
+  logFileSizeMb: number,
+) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<AppLogStatus>>("set_log_rotation", {
+    log_to_file: logToFile,
+    logToFile,
+    max_log_files: maxLogFiles,
+    maxLogFiles,
+    log_file_size_mb: logFileSizeMb,
+    logFileSizeMb,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const getRecentAppLogs = async (limit?: number, minLevel?: AppLogLevel) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<AppLogEntry[]>>("get_recent_app_logs", {
+    limit: limit ?? null,
+    min_level: minLevel ?? null,
+    minLevel: minLevel ?? null,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const queryDeviceTimeline = async (query: TimelineQuery) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<TimelineEvent[]>>("query_device_timeline", {
+    query,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const exportSessionReport = async (
+  serials: string[],
+  path: string,
+  timeRange?: SessionTimeRange,
+) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<SessionReportResult>>("export_session_report", {
+    serials,
+    time_range: timeRange ?? null,
+    timeRange: timeRange ?? null,
+    path,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const uploadArtifacts = async (target: string, paths: string[]) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<ArtifactUploadResult[]>>("upload_artifacts", {
+    target,
+    paths,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const exportDeviceFarmBundle = async (request: DeviceFarmRequest) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<DeviceFarmBundle>>("export_device_farm_bundle", {
+    request,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const runDeviceFarmTest = async (request: DeviceFarmRequest) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<DeviceFarmRunResult>>("run_device_farm_test", {
+    request,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const cancelDeviceFarmTest = async () => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<boolean>>("cancel_device_farm_test", {
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const createIssueFromArtifacts = async (
+  provider: "github" | "jira",
+  title: string,
+  description: string,
+  artifactPaths: string[],
+) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<CreatedIssue>>("create_issue_from_artifacts", {
+    provider,
+    title,
+    description,
+    artifact_paths: artifactPaths,
+    artifactPaths,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const sendTestNotification = async (webhook: string) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<boolean>>("send_test_notification", {
+    webhook,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const runTestHook = async (hook: string) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<HookRunResult>>("run_test_hook", {
+    hook,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const runCustomTool = async (
+  name: string,
+  serials: string[],
+  inputs: Record<string, string>,
+) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<CustomToolResult>>("run_custom_tool", {
+    name,
+    serials,
+    inputs,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const getInternalMetrics = async () => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<InternalMetrics>>("get_internal_metrics", {
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const exportInternalMetricsPrometheus = async () => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<string>>("export_internal_metrics_prometheus", {
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const subscribeEvents = async (serial: string, channels: EventChannel[]) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<EventSubscriptionInfo>>("subscribe_events", {
+    serial,
+    channels,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const unsubscribeEvents = async (serial: string, channels?: EventChannel[]) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<EventSubscriptionInfo>>("unsubscribe_events", {
+    serial,
+    channels: channels ?? null,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const getEventSubscriptions = async (serial: string) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<EventSubscriptionInfo>>("get_event_subscriptions", {
+    serial,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const bustCache = async (scope?: CacheScope, serial?: string) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<number>>("bust_cache", {
+    scope: scope ?? null,
+    serial: serial ?? null,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
 export const exportDiagnosticsBundle = async (outputDir?: string) => {
   const traceId = createTraceId();
   const payload: Record<string, unknown> = {
@@ -89,6 +477,31 @@ export const resetConfig = async () => {
   });
 };
 
+export const setRestrictedMode = async (
+  enabled: boolean,
+  password?: string,
+  newPassword?: string,
+) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<AppConfig>>("set_restricted_mode", {
+    enabled,
+    password: password ?? null,
+    new_password: newPassword ?? null,
+    newPassword: newPassword ?? null,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const getDeviceDetail = async (serial: string) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<DeviceInfo>>("get_device_detail", {
+    serial,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
 export const listDevices = async (detailed = true) => {
   const traceId = createTraceId();
   return tauriInvoke<CommandResponse<DeviceInfo[]>>("list_devices", {
@@ -106,128 +519,721 @@ export const startDeviceTracking = async () => {
   });
 };
 
-export const stopDeviceTracking = async () => {
+export const applyOnConnectProfile = async (serial: string) => {
   const traceId = createTraceId();
-  return tauriInvoke<CommandResponse<boolean>>("stop_device_tracking", {
+  return tauriInvoke<CommandResponse<OnConnectAuditEntry[]>>("apply_on_connect_profile", {
+    serial,
     trace_id: traceId,
     traceId,
   });
 };
 
-export const adbPair = async (address: string, pairingCode: string) => {
+export const getOnConnectAudit = async (serial?: string) => {
   const traceId = createTraceId();
-  return tauriInvoke<CommandResponse<HostCommandResult>>("adb_pair", {
-    address,
-    pairing_code: pairingCode,
-    pairingCode,
+  return tauriInvoke<CommandResponse<OnConnectAuditEntry[]>>("get_on_connect_audit", {
+    serial: serial ?? null,
     trace_id: traceId,
     traceId,
   });
 };
 
-export const adbConnect = async (address: string) => {
+export const prepareFactoryReset = async (serial: string) => {
   const traceId = createTraceId();
-  return tauriInvoke<CommandResponse<HostCommandResult>>("adb_connect", {
-    address,
+  return tauriInvoke<CommandResponse<FactoryResetChallenge>>("prepare_factory_reset", {
+    serial,
     trace_id: traceId,
     traceId,
   });
 };
 
-export const runShell = async (
-  serials: string[],
-  command: string,
-  parallel?: boolean,
+export const factoryResetDevice = async (
+  serial: string,
+  confirmToken: string,
+  provisioningProfile?: string,
 ) => {
   const traceId = createTraceId();
-  return tauriInvoke<CommandResponse<CommandResult[]>>("run_shell", {
-    serials,
-    command,
-    parallel,
+  return tauriInvoke<CommandResponse<FactoryResetReport>>("factory_reset_device", {
+    serial,
+    confirm_token: confirmToken,
+    confirmToken,
+    provisioning_profile: provisioningProfile ?? null,
+    provisioningProfile: provisioningProfile ?? null,
     trace_id: traceId,
     traceId,
   });
 };
 
-export const startTerminalSession = async (serial: string) => {
+export const getFactoryResetAudit = async (serial?: string) => {
   const traceId = createTraceId();
-  return tauriInvoke<CommandResponse<TerminalSessionInfo>>("start_terminal_session", {
-    serial,
+  return tauriInvoke<CommandResponse<FactoryResetAuditEntry[]>>("get_factory_reset_audit", {
+    serial: serial ?? null,
     trace_id: traceId,
     traceId,
   });
 };
 
-export const writeTerminalSession = async (
+export const getDeviceHistory = async (serial?: string) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<DeviceHistory[]>>("get_device_history", {
+    serial: serial ?? null,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const getDeviceNotes = async (serial?: string) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<DeviceNotes[]>>("get_device_notes", {
+    serial: serial ?? null,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const saveDeviceNotes = async (
   serial: string,
-  data: string,
-  newline: boolean,
+  markdown: string,
+  checklist: ChecklistItemInput[],
 ) => {
   const traceId = createTraceId();
-  return tauriInvoke<CommandResponse<boolean>>("write_terminal_session", {
+  return tauriInvoke<CommandResponse<DeviceNotes | null>>("save_device_notes", {
     serial,
-    data,
-    newline,
+    markdown,
+    checklist,
     trace_id: traceId,
     traceId,
   });
 };
 
-export const stopTerminalSession = async (serial: string) => {
+export const deleteDeviceNotes = async (serial: string) => {
   const traceId = createTraceId();
-  return tauriInvoke<CommandResponse<boolean>>("stop_terminal_session", {
+  return tauriInvoke<CommandResponse<boolean>>("delete_device_notes", {
     serial,
     trace_id: traceId,
     traceId,
   });
 };
 
-export const persistTerminalState = async (
-  restore_sessions: string[],
-  buffers: Record<string, string[]>,
+export const captureDeviceProfile = async (
+  serial: string,
+  name: string,
+  packages: string[],
+  settings: DeviceProfileSettingKey[],
+  files: string[],
 ) => {
   const traceId = createTraceId();
-  return tauriInvoke<CommandResponse<boolean>>("persist_terminal_state", {
-    restore_sessions,
-    restoreSessions: restore_sessions,
-    buffers,
+  return tauriInvoke<CommandResponse<DeviceProfile>>("capture_device_profile", {
+    serial,
+    name,
+    packages,
+    settings,
+    files,
     trace_id: traceId,
     traceId,
   });
 };
 
-export const rebootDevices = async (serials: string[], mode?: string) => {
+export const listDeviceProfiles = async () => {
   const traceId = createTraceId();
-  return tauriInvoke<CommandResponse<CommandResult[]>>("reboot_devices", {
-    serials,
-    mode,
+  return tauriInvoke<CommandResponse<DeviceProfile[]>>("list_device_profiles", {
     trace_id: traceId,
     traceId,
   });
 };
 
-export const setWifiState = async (serials: string[], enable: boolean) => {
+export const applyDeviceProfile = async (serial: string, name: string) => {
   const traceId = createTraceId();
-  return tauriInvoke<CommandResponse<CommandResult[]>>("set_wifi_state", {
-    serials,
-    enable,
+  return tauriInvoke<CommandResponse<DeviceProfileApplyReport>>("apply_device_profile", {
+    serial,
+    name,
     trace_id: traceId,
     traceId,
   });
 };
 
-export const setBluetoothState = async (serials: string[], enable: boolean) => {
+export const deleteDeviceProfile = async (name: string) => {
   const traceId = createTraceId();
-  return tauriInvoke<CommandResponse<CommandResult[]>>("set_bluetooth_state", {
-    serials,
-    enable,
+  return tauriInvoke<CommandResponse<boolean>>("delete_device_profile", {
+    name,
     trace_id: traceId,
     traceId,
   });
 };
 
-export const installApkBatch = async (
-  serials: string[],
+export const reserveDevice = async (
+  serial: string,
+  user: string,
+  until: string,
+  force?: boolean,
+) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<DeviceReservation>>("reserve_device", {
+    serial,
+    user,
+    until,
+    force: force ?? null,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const releaseDevice = async (serial: string, user?: string, force?: boolean) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<boolean>>("release_device", {
+    serial,
+    user: user ?? null,
+    force: force ?? null,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const stopDeviceTracking = async () => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<boolean>>("stop_device_tracking", {
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const switchAdbServer = async (name?: string) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<AppConfig>>("switch_adb_server", {
+    name: name ?? null,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const getAdbTunnelStatus = async () => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<AdbTunnelStatus | null>>("get_adb_tunnel_status", {
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const adbPair = async (address: string, pairingCode: string) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<HostCommandResult>>("adb_pair", {
+    address,
+    pairing_code: pairingCode,
+    pairingCode,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const adbConnect = async (address: string) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<HostCommandResult>>("adb_connect", {
+    address,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const detectWirelessFlow = async (serial: string) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<WirelessFlowInfo>>("detect_wireless_flow", {
+    serial,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const enableTcpipAndConnect = async (serial: string, port?: number) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<TcpipConnectResult>>("enable_tcpip_and_connect", {
+    serial,
+    port: port ?? null,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const runShell = async (
+  serials: string[],
+  command: string,
+  parallel?: boolean,
+) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<CommandResult[]>>("run_shell", {
+    serials,
+    command,
+    parallel,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const runShellStreaming = async (serial: string, command: string, timeoutSecs?: number) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<ShellStreamInfo>>("run_shell_streaming", {
+    serial,
+    command,
+    timeout_secs: timeoutSecs ?? null,
+    timeoutSecs: timeoutSecs ?? null,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const runShellPipeline = async (serial: string, steps: ShellPipelineStep[]) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<ShellPipelineResult>>("run_shell_pipeline", {
+    serial,
+    steps,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const cancelShellStreaming = async (streamId: string) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<boolean>>("cancel_shell_streaming", {
+    stream_id: streamId,
+    streamId,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const startTerminalSession = async (serial: string) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<TerminalSessionInfo>>("start_terminal_session", {
+    serial,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const writeTerminalSession = async (
+  serial: string,
+  data: string,
+  newline: boolean,
+) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<boolean>>("write_terminal_session", {
+    serial,
+    data,
+    newline,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const stopTerminalSession = async (serial: string) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<boolean>>("stop_terminal_session", {
+    serial,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const persistTerminalState = async (
+  restore_sessions: string[],
+  buffers: Record<string, string[]>,
+) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<boolean>>("persist_terminal_state", {
+    restore_sessions,
+    restoreSessions: restore_sessions,
+    buffers,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const rebootDevices = async (
+  serials: string[],
+  mode?: string,
+  waitForBoot?: boolean,
+  bootTimeoutSecs?: number,
+) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<RebootResult[]>>("reboot_devices", {
+    serials,
+    mode,
+    wait_for_boot: waitForBoot ?? null,
+    waitForBoot: waitForBoot ?? null,
+    boot_timeout_secs: bootTimeoutSecs ?? null,
+    bootTimeoutSecs: bootTimeoutSecs ?? null,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const runDeviceReadinessCheck = async (
+  serials: string[],
+  thresholds?: ReadinessThresholds,
+) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<DeviceReadinessResult[]>>("run_device_readiness_check", {
+    serials,
+    thresholds: thresholds ?? null,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const wakeDevice = async (serial: string) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<boolean>>("wake_device", {
+    serial,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const unlockDevice = async (serial: string, pin?: string) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<DeviceUnlockResult>>("unlock_device", {
+    serial,
+    pin: pin ?? null,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const getLockState = async (serial: string) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<DeviceLockState>>("get_lock_state", {
+    serial,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const getMediaCapabilities = async (serial: string) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<MediaCapabilities>>("get_media_capabilities", {
+    serial,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const listInputDevices = async (serial: string) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<InputDeviceInventory>>("list_input_devices", {
+    serial,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const listOverlays = async (serial: string) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<OverlayPackage[]>>("list_overlays", {
+    serial,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const setOverlayState = async (serial: string, overlayPackage: string, enable: boolean) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<OverlayPackage>>("set_overlay_state", {
+    serial,
+    overlay_package: overlayPackage,
+    overlayPackage,
+    enable,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const listAccessibilityServices = async (serial: string) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<AccessibilityServiceInfo[]>>("list_accessibility_services", {
+    serial,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const setAccessibilityService = async (
+  serial: string,
+  component: string,
+  enable: boolean,
+) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<AccessibilityServiceInfo[]>>("set_accessibility_service", {
+    serial,
+    component,
+    enable,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const listInputMethods = async (serial: string) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<InputMethodInfo[]>>("list_input_methods", {
+    serial,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const setInputMethod = async (serial: string, imeId: string) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<InputMethodInfo[]>>("set_input_method", {
+    serial,
+    ime_id: imeId,
+    imeId,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const getAudioRoute = async (serial: string) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<AudioRouteInfo>>("get_audio_route", {
+    serial,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const setVolume = async (serial: string, stream: AudioStream, level: number) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<AudioRouteInfo>>("set_volume", {
+    serial,
+    stream,
+    level,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const muteAll = async (serial: string) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<AudioRouteInfo>>("mute_all", {
+    serial,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const sendMediaKey = async (serial: string, key: MediaKey) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<boolean>>("send_media_key", {
+    serial,
+    key,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const getMediaSessions = async (serial: string) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<MediaSessionInfo[]>>("get_media_sessions", {
+    serial,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const getUsbFunctions = async (serial: string) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<UsbFunctionState>>("get_usb_functions", {
+    serial,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const setUsbFunction = async (serial: string, usbFunction: string) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<UsbFunctionState>>("set_usb_function", {
+    serial,
+    function: usbFunction,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const getTelephonyInfo = async (serial: string) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<TelephonyInfo>>("get_telephony_info", {
+    serial,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const getBootInfo = async (serial: string) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<BootInfo>>("get_boot_info", {
+    serial,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const setStayAwake = async (serial: string, enable: boolean) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<boolean>>("set_stay_awake", {
+    serial,
+    enable,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const setMockLocation = async (
+  serial: string,
+  latitude: number,
+  longitude: number,
+  accuracy?: number,
+  helperPackage?: string,
+) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<MockLocationResult>>("set_mock_location", {
+    serial,
+    latitude,
+    longitude,
+    accuracy: accuracy ?? null,
+    helper_package: helperPackage ?? null,
+    helperPackage: helperPackage ?? null,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const clearMockLocation = async (serial: string, helperPackage?: string) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<boolean>>("clear_mock_location", {
+    serial,
+    helper_package: helperPackage ?? null,
+    helperPackage: helperPackage ?? null,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const listEmulatorSnapshots = async (serial: string) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<EmulatorSnapshot[]>>("list_emulator_snapshots", {
+    serial,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const saveEmulatorSnapshot = async (serial: string, name: string) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<EmulatorSnapshot[]>>("save_emulator_snapshot", {
+    serial,
+    name,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const loadEmulatorSnapshot = async (serial: string, name: string) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<boolean>>("load_emulator_snapshot", {
+    serial,
+    name,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const setWifiState = async (serials: string[], enable: boolean) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<CommandResult[]>>("set_wifi_state", {
+    serials,
+    enable,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const setI18nToggles = async (
+  serials: string[],
+  options: { pseudoLocale?: PseudoLocale; forceRtl?: boolean; restartUi?: boolean },
+) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<I18nToggleResult[]>>("set_i18n_toggles", {
+    serials,
+    pseudo_locale: options.pseudoLocale ?? null,
+    pseudoLocale: options.pseudoLocale ?? null,
+    force_rtl: options.forceRtl ?? null,
+    forceRtl: options.forceRtl ?? null,
+    restart_ui: options.restartUi ?? null,
+    restartUi: options.restartUi ?? null,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const restoreI18nToggles = async (serials: string[], restartUi?: boolean) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<I18nToggleResult[]>>("restore_i18n_toggles", {
+    serials,
+    restart_ui: restartUi ?? null,
+    restartUi: restartUi ?? null,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const getGlobalProxy = async (serial: string) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<ProxyStatus>>("get_global_proxy", {
+    serial,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const setGlobalProxy = async (serial: string, address: string) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<ProxyStatus>>("set_global_proxy", {
+    serial,
+    address,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const clearGlobalProxy = async (serial: string) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<ProxyStatus>>("clear_global_proxy", {
+    serial,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const installCaCertificate = async (serial: string, certPath: string) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<CaCertificateInstall>>("install_ca_certificate", {
+    serial,
+    cert_path: certPath,
+    certPath,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const setBluetoothState = async (serials: string[], enable: boolean) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<CommandResult[]>>("set_bluetooth_state", {
+    serials,
+    enable,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const installApkBatch = async (
+  serials: string[],
   apkPath: string,
   replace: boolean,
   allowDowngrade: boolean,
@@ -235,318 +1241,1009 @@ export const installApkBatch = async (
   allowTestPackages: boolean,
   extraArgs?: string,
   traceIdOverride?: string,
+  captureLabel?: string,
+) => {
+  const traceId = traceIdOverride ?? createTraceId();
+  return tauriInvoke<CommandResponse<ApkBatchInstallResult>>("install_apk_batch", {
+    serials,
+    apk_path: apkPath,
+    apkPath,
+    replace,
+    allow_downgrade: allowDowngrade,
+    allowDowngrade,
+    grant,
+    allow_test_packages: allowTestPackages,
+    allowTestPackages,
+    extra_args: extraArgs,
+    extraArgs,
+    capture_label: captureLabel ?? null,
+    captureLabel: captureLabel ?? null,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const beginCaptureContext = async (serial: string, label: string) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<CaptureContextInfo>>("begin_capture_context", {
+    serial,
+    label,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const endCaptureContext = async (contextId: string, outputDir?: string) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<CaptureContextResult>>("end_capture_context", {
+    context_id: contextId,
+    contextId,
+    output_dir: outputDir ?? null,
+    outputDir: outputDir ?? null,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const startSoakTest = async (serial: string, profile?: SoakProfile, outputDir?: string) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<SoakTestInfo>>("start_soak_test", {
+    serial,
+    profile: profile ?? null,
+    output_dir: outputDir ?? null,
+    outputDir: outputDir ?? null,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const stopSoakTest = async (serial: string) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<SoakTestReport>>("stop_soak_test", {
+    serial,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const listBuildVariants = async (projectDir?: string, module?: string) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<BuildVariantApk[]>>("list_build_variants", {
+    project_dir: projectDir ?? null,
+    projectDir: projectDir ?? null,
+    module: module ?? null,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const watchApk = async (
+  path: string,
+  serials: string[],
+  installOptions?: ApkWatchOptions,
+) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<boolean>>("watch_apk", {
+    path,
+    serials,
+    install_options: installOptions ?? null,
+    installOptions: installOptions ?? null,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const stopWatchApk = async (path: string) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<boolean>>("stop_watch_apk", {
+    path,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const captureScreenshot = async (serial: string, outputDir?: string) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<string>>("capture_screenshot", {
+    serial,
+    output_dir: outputDir,
+    outputDir,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const runGroupAction = async (group: string, request: GroupActionParams) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<GroupActionResult>>("run_group_action", {
+    group,
+    action: request.action,
+    params: request.params ?? null,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const compareScreenshots = async (pathA: string, pathB: string, threshold?: number) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<ScreenshotComparison>>("compare_screenshots", {
+    path_a: pathA,
+    path_b: pathB,
+    threshold: threshold ?? null,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const saveGolden = async (serial: string, screenName: string) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<GoldenImage>>("save_golden", {
+    serial,
+    screen_name: screenName,
+    screenName,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const checkAgainstGolden = async (
+  serial: string,
+  screenName: string,
+  threshold?: number,
+) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<GoldenCheckResult>>("check_against_golden", {
+    serial,
+    screen_name: screenName,
+    screenName,
+    threshold: threshold ?? null,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const listGoldens = async (screenName?: string) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<GoldenImage[]>>("list_goldens", {
+    screen_name: screenName ?? null,
+    screenName: screenName ?? null,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const getGoldenResults = async (screenName?: string) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<GoldenCheckResult[]>>("get_golden_results", {
+    screen_name: screenName ?? null,
+    screenName: screenName ?? null,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const startScreenRecord = async (serial: string) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<string>>("start_screen_record", {
+    serial,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const stopScreenRecord = async (
+  serial: string,
+  outputDir?: string,
+  convertFormat?: "gif" | "webp",
+) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<string>>("stop_screen_record", {
+    serial,
+    output_dir: outputDir,
+    outputDir,
+    convert_format: convertFormat,
+    convertFormat,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const convertScreenRecording = async (inputPath: string, format: "gif" | "webp") => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<ScreenRecordConversionResult>>("convert_screen_recording", {
+    input_path: inputPath,
+    inputPath,
+    format,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const listRecordingConversions = async () => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<RecordingConversionStatus[]>>("list_recording_conversions", {
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const listDeviceFiles = async (serial: string, path: string) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<DeviceFileEntry[]>>("list_device_files", {
+    serial,
+    path,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const pullDeviceFile = async (
+  serial: string,
+  devicePath: string,
+  outputDir: string,
+  traceId?: string,
+  resume?: boolean,
+  compress?: boolean,
+) => {
+  const resolvedTraceId = traceId ?? createTraceId();
+  return tauriInvoke<CommandResponse<string>>("pull_device_file", {
+    serial,
+    device_path: devicePath,
+    output_dir: outputDir,
+    devicePath,
+    outputDir,
+    resume,
+    compress,
+    trace_id: resolvedTraceId,
+    traceId: resolvedTraceId,
+  });
+};
+
+export const pushDeviceFile = async (
+  serial: string,
+  localPath: string,
+  devicePath: string,
+  traceId?: string,
+  mediaScan?: boolean,
+  resume?: boolean,
+) => {
+  const resolvedTraceId = traceId ?? createTraceId();
+  return tauriInvoke<CommandResponse<string>>("push_device_file", {
+    serial,
+    local_path: localPath,
+    localPath,
+    device_path: devicePath,
+    devicePath,
+    media_scan: mediaScan,
+    mediaScan,
+    resume,
+    trace_id: resolvedTraceId,
+    traceId: resolvedTraceId,
+  });
+};
+
+/** `transferId` is the trace id the pull or push was started with. */
+export const cancelFileTransfer = async (transferId: string) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<boolean>>("cancel_file_transfer", {
+    transfer_id: transferId,
+    transferId,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const mkdirDeviceDir = async (serial: string, devicePath: string, traceId?: string) => {
+  const resolvedTraceId = traceId ?? createTraceId();
+  return tauriInvoke<CommandResponse<string>>("mkdir_device_dir", {
+    serial,
+    device_path: devicePath,
+    devicePath,
+    trace_id: resolvedTraceId,
+    traceId: resolvedTraceId,
+  });
+};
+
+export const renameDevicePath = async (
+  serial: string,
+  fromPath: string,
+  toPath: string,
+  traceId?: string,
+) => {
+  const resolvedTraceId = traceId ?? createTraceId();
+  return tauriInvoke<CommandResponse<string>>("rename_device_path", {
+    serial,
+    from_path: fromPath,
+    to_path: toPath,
+    fromPath,
+    toPath,
+    trace_id: resolvedTraceId,
+    traceId: resolvedTraceId,
+  });
+};
+
+export const deleteDevicePath = async (
+  serial: string,
+  devicePath: string,
+  recursive: boolean,
+  traceId?: string,
+) => {
+  const resolvedTraceId = traceId ?? createTraceId();
+  return tauriInvoke<CommandResponse<string>>("delete_device_path", {
+    serial,
+    device_path: devicePath,
+    devicePath,
+    recursive,
+    trace_id: resolvedTraceId,
+    traceId: resolvedTraceId,
+  });
+};
+
+export const previewLocalFile = async (localPath: string) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<FilePreview>>("preview_local_file", {
+    local_path: localPath,
+    localPath,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const captureUiHierarchy = async (serial: string, backend?: UiDumpBackend) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<UiHierarchyCaptureResult>>("capture_ui_hierarchy", {
+    serial,
+    backend,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const runAccessibilityAudit = async (serial: string, includeContrast?: boolean) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<AccessibilityAudit>>("run_accessibility_audit", {
+    serial,
+    include_contrast: includeContrast ?? null,
+    includeContrast: includeContrast ?? null,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const sendPointerGesture = async (serial: string, gesture: PointerGesture) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<PointerGestureResult>>("send_pointer_gesture", {
+    serial,
+    gesture,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const startGestureRecording = async (serial: string) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<boolean>>("start_gesture_recording", {
+    serial,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const stopGestureRecording = async (serial: string, outputDir?: string) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<GestureRecordingSummary>>("stop_gesture_recording", {
+    serial,
+    output_dir: outputDir ?? null,
+    outputDir: outputDir ?? null,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const replayGestureFile = async (serial: string, path: string) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<GestureReplayResult>>("replay_gesture_file", {
+    serial,
+    path,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const broadcastPointerGesture = async (serials: string[], gesture: PointerGesture) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<InputBroadcastDeviceResult[]>>("broadcast_pointer_gesture", {
+    serials,
+    gesture,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const broadcastText = async (serials: string[], text: string) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<InputBroadcastDeviceResult[]>>("broadcast_text", {
+    serials,
+    text,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const startInputBroadcast = async (leader: string, followers: string[]) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<InputBroadcastInfo>>("start_input_broadcast", {
+    leader,
+    followers,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const stopInputBroadcast = async (leader: string) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<boolean>>("stop_input_broadcast", {
+    leader,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const runJankScenario = async (
+  serial: string,
+  packageName: string,
+  gestures: PointerGesture[],
+  repeat?: number,
+  pauseMs?: number,
 ) => {
-  const traceId = traceIdOverride ?? createTraceId();
-  return tauriInvoke<CommandResponse<ApkBatchInstallResult>>("install_apk_batch", {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<JankScenarioResult>>("run_jank_scenario", {
+    serial,
+    package_name: packageName,
+    packageName,
+    gestures,
+    repeat: repeat ?? null,
+    pause_ms: pauseMs ?? null,
+    pauseMs: pauseMs ?? null,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const runDumpsys = async (
+  serial: string,
+  service: string,
+  args?: string[],
+  includeRaw?: boolean,
+) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<DumpsysResult>>("run_dumpsys", {
+    serial,
+    service,
+    args: args ?? null,
+    include_raw: includeRaw ?? null,
+    includeRaw: includeRaw ?? null,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const listDumpsysParsers = async () => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<DumpsysParserInfo[]>>("list_dumpsys_parsers", {
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const getLastBootLogs = async (serial: string) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<LastBootLogs>>("get_last_boot_logs", {
+    serial,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const runMonkeyStress = async (
+  serial: string,
+  packageName: string,
+  events: number,
+  throttleMs?: number,
+  seed?: number,
+) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<MonkeyStressResult>>("run_monkey_stress", {
+    serial,
+    package_name: packageName,
+    packageName,
+    events,
+    throttle_ms: throttleMs ?? null,
+    throttleMs: throttleMs ?? null,
+    seed: seed ?? null,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const stopMonkeyStress = async (serial: string) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<boolean>>("stop_monkey_stress", {
+    serial,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const fillTextFields = async (serial: string, entries: TextFieldEntry[]) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<TextFieldFillResult[]>>("fill_text_fields", {
+    serial,
+    entries,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const typeToDevice = async (serial: string, text: string) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<TypeTextResult>>("type_to_device", {
+    serial,
+    text,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const listUiCaptures = async (serial: string) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<UiCaptureRecord[]>>("list_ui_captures", {
+    serial,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const exportUiHierarchy = async (
+  serial: string,
+  outputDir?: string,
+  backend?: UiDumpBackend,
+) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<UiHierarchyExportResult>>("export_ui_hierarchy", {
+    serial,
+    output_dir: outputDir,
+    outputDir,
+    backend,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const startPerfMonitor = async (serial: string, intervalMs?: number) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<boolean>>("start_perf_monitor", {
+    serial,
+    interval_ms: intervalMs,
+    intervalMs,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const stopPerfMonitor = async (serial: string) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<boolean>>("stop_perf_monitor", {
+    serial,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const startPerfAggregation = async (serials: string[], bucketMs?: number) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<boolean>>("start_perf_aggregation", {
     serials,
-    apk_path: apkPath,
-    apkPath,
-    replace,
-    allow_downgrade: allowDowngrade,
-    allowDowngrade,
-    grant,
-    allow_test_packages: allowTestPackages,
-    allowTestPackages,
-    extra_args: extraArgs,
-    extraArgs,
+    bucket_ms: bucketMs,
+    bucketMs,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const stopPerfAggregation = async () => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<boolean>>("stop_perf_aggregation", {
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const startThermalMonitor = async (
+  serial: string,
+  intervalMs?: number,
+  thresholdDecic?: number,
+) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<boolean>>("start_thermal_monitor", {
+    serial,
+    interval_ms: intervalMs,
+    intervalMs,
+    threshold_decic: thresholdDecic,
+    thresholdDecic,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const stopThermalMonitor = async (serial: string) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<boolean>>("stop_thermal_monitor", {
+    serial,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const startMemoryLeakWatch = async (
+  serial: string,
+  packageName: string,
+  options: { intervalSecs?: number; windowSamples?: number; slopeKbPerMin?: number } = {},
+) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<boolean>>("start_memory_leak_watch", {
+    serial,
+    package_name: packageName,
+    packageName,
+    interval_secs: options.intervalSecs ?? null,
+    intervalSecs: options.intervalSecs ?? null,
+    window_samples: options.windowSamples ?? null,
+    windowSamples: options.windowSamples ?? null,
+    slope_kb_per_min: options.slopeKbPerMin ?? null,
+    slopeKbPerMin: options.slopeKbPerMin ?? null,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const stopMemoryLeakWatch = async (serial: string, packageName: string) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<boolean>>("stop_memory_leak_watch", {
+    serial,
+    package_name: packageName,
+    packageName,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const startNetProfiler = async (
+  serial: string,
+  intervalMs?: number,
+  topN?: number,
+  pinnedUids?: number[],
+) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<boolean>>("start_net_profiler", {
+    serial,
+    interval_ms: intervalMs,
+    intervalMs,
+    top_n: topN,
+    topN,
+    pinned_uids: pinnedUids,
+    pinnedUids,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const stopNetProfiler = async (serial: string) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<boolean>>("stop_net_profiler", {
+    serial,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const setNetProfilerPinnedUids = async (serial: string, pinnedUids?: number[]) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<boolean>>("set_net_profiler_pinned_uids", {
+    serial,
+    pinned_uids: pinnedUids,
+    pinnedUids,
     trace_id: traceId,
     traceId,
   });
 };
 
-export const captureScreenshot = async (serial: string, outputDir: string) => {
+export const startLogcat = async (serial: string, filter?: string) => {
   const traceId = createTraceId();
-  return tauriInvoke<CommandResponse<string>>("capture_screenshot", {
+  return tauriInvoke<CommandResponse<boolean>>("start_logcat", {
     serial,
-    output_dir: outputDir,
-    outputDir,
+    filter,
     trace_id: traceId,
     traceId,
   });
 };
 
-export const startScreenRecord = async (serial: string) => {
+export const stopLogcat = async (serial: string) => {
   const traceId = createTraceId();
-  return tauriInvoke<CommandResponse<string>>("start_screen_record", {
+  return tauriInvoke<CommandResponse<boolean>>("stop_logcat", {
     serial,
     trace_id: traceId,
     traceId,
   });
 };
 
-export const stopScreenRecord = async (serial: string, outputDir?: string) => {
+export const clearLogcat = async (serial: string) => {
   const traceId = createTraceId();
-  return tauriInvoke<CommandResponse<string>>("stop_screen_record", {
+  return tauriInvoke<CommandResponse<boolean>>("clear_logcat", {
     serial,
-    output_dir: outputDir,
-    outputDir,
     trace_id: traceId,
     traceId,
   });
 };
 
-export const listDeviceFiles = async (serial: string, path: string) => {
+export const startDmesgStream = async (serial: string) => {
   const traceId = createTraceId();
-  return tauriInvoke<CommandResponse<DeviceFileEntry[]>>("list_device_files", {
+  return tauriInvoke<CommandResponse<DmesgStreamInfo>>("start_dmesg_stream", {
     serial,
-    path,
     trace_id: traceId,
     traceId,
   });
 };
 
-export const pullDeviceFile = async (
-  serial: string,
-  devicePath: string,
-  outputDir: string,
-  traceId?: string,
-) => {
-  const resolvedTraceId = traceId ?? createTraceId();
-  return tauriInvoke<CommandResponse<string>>("pull_device_file", {
+export const stopDmesgStream = async (serial: string) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<boolean>>("stop_dmesg_stream", {
     serial,
-    device_path: devicePath,
-    output_dir: outputDir,
-    devicePath,
-    outputDir,
-    trace_id: resolvedTraceId,
-    traceId: resolvedTraceId,
+    trace_id: traceId,
+    traceId,
   });
 };
 
-export const pushDeviceFile = async (
+export const startActivityLifecycleStream = async (
   serial: string,
-  localPath: string,
-  devicePath: string,
-  traceId?: string,
+  includeFragments?: boolean,
 ) => {
-  const resolvedTraceId = traceId ?? createTraceId();
-  return tauriInvoke<CommandResponse<string>>("push_device_file", {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<LifecycleStreamInfo>>("start_activity_lifecycle_stream", {
     serial,
-    local_path: localPath,
-    localPath,
-    device_path: devicePath,
-    devicePath,
-    trace_id: resolvedTraceId,
-    traceId: resolvedTraceId,
+    include_fragments: includeFragments ?? null,
+    includeFragments: includeFragments ?? null,
+    trace_id: traceId,
+    traceId,
   });
 };
 
-export const mkdirDeviceDir = async (serial: string, devicePath: string, traceId?: string) => {
-  const resolvedTraceId = traceId ?? createTraceId();
-  return tauriInvoke<CommandResponse<string>>("mkdir_device_dir", {
+export const stopActivityLifecycleStream = async (serial: string) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<boolean>>("stop_activity_lifecycle_stream", {
     serial,
-    device_path: devicePath,
-    devicePath,
-    trace_id: resolvedTraceId,
-    traceId: resolvedTraceId,
+    trace_id: traceId,
+    traceId,
   });
 };
 
-export const renameDevicePath = async (
+export const exportLogcat = async (
   serial: string,
-  fromPath: string,
-  toPath: string,
-  traceId?: string,
+  lines: string[],
+  outputDir?: string,
 ) => {
-  const resolvedTraceId = traceId ?? createTraceId();
-  return tauriInvoke<CommandResponse<string>>("rename_device_path", {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<LogcatExportResult>>("export_logcat", {
     serial,
-    from_path: fromPath,
-    to_path: toPath,
-    fromPath,
-    toPath,
-    trace_id: resolvedTraceId,
-    traceId: resolvedTraceId,
+    lines,
+    output_dir: outputDir,
+    outputDir,
+    trace_id: traceId,
+    traceId,
   });
 };
 
-export const deleteDevicePath = async (
+export const listApps = async (
   serial: string,
-  devicePath: string,
-  recursive: boolean,
+  thirdPartyOnly?: boolean,
+  includeVersions?: boolean,
   traceId?: string,
+  query?: AppListQuery,
+  refresh?: boolean,
 ) => {
   const resolvedTraceId = traceId ?? createTraceId();
-  return tauriInvoke<CommandResponse<string>>("delete_device_path", {
+  return tauriInvoke<CommandResponse<AppListPage>>("list_apps", {
     serial,
-    device_path: devicePath,
-    devicePath,
-    recursive,
+    third_party_only: thirdPartyOnly,
+    thirdPartyOnly,
+    include_versions: includeVersions,
+    includeVersions,
+    query,
+    refresh,
     trace_id: resolvedTraceId,
     traceId: resolvedTraceId,
   });
 };
 
-export const previewLocalFile = async (localPath: string) => {
+export const listAppsEnriched = async (
+  serial: string,
+  thirdPartyOnly?: boolean,
+  maxMetadataFetch?: number,
+) => {
   const traceId = createTraceId();
-  return tauriInvoke<CommandResponse<FilePreview>>("preview_local_file", {
-    local_path: localPath,
-    localPath,
+  return tauriInvoke<CommandResponse<AppListEntry[]>>("list_apps_enriched", {
+    serial,
+    third_party_only: thirdPartyOnly,
+    thirdPartyOnly,
+    max_metadata_fetch: maxMetadataFetch ?? null,
+    maxMetadataFetch: maxMetadataFetch ?? null,
     trace_id: traceId,
     traceId,
   });
 };
 
-export const captureUiHierarchy = async (serial: string) => {
+export const getAppBasicInfo = async (serial: string, packageName: string) => {
   const traceId = createTraceId();
-  return tauriInvoke<CommandResponse<UiHierarchyCaptureResult>>("capture_ui_hierarchy", {
+  return tauriInvoke<CommandResponse<AppBasicInfo>>("get_app_basic_info", {
     serial,
+    package_name: packageName,
+    packageName,
     trace_id: traceId,
     traceId,
   });
 };
 
-export const exportUiHierarchy = async (serial: string, outputDir?: string) => {
+export const getAppIcon = async (
+  serial: string,
+  packageName: string,
+  apkPath?: string,
+  versionCode?: string,
+) => {
   const traceId = createTraceId();
-  return tauriInvoke<CommandResponse<UiHierarchyExportResult>>("export_ui_hierarchy", {
+  return tauriInvoke<CommandResponse<AppIcon>>("get_app_icon", {
     serial,
-    output_dir: outputDir,
-    outputDir,
+    package_name: packageName,
+    packageName,
+    apk_path: apkPath,
+    apkPath,
+    version_code: versionCode ?? null,
+    versionCode: versionCode ?? null,
     trace_id: traceId,
     traceId,
   });
 };
 
-export const startPerfMonitor = async (serial: string, intervalMs?: number) => {
+export const clearIconCache = async (serial?: string) => {
   const traceId = createTraceId();
-  return tauriInvoke<CommandResponse<boolean>>("start_perf_monitor", {
-    serial,
-    interval_ms: intervalMs,
-    intervalMs,
+  return tauriInvoke<CommandResponse<IconCacheStats>>("clear_icon_cache", {
+    serial: serial ?? null,
     trace_id: traceId,
     traceId,
   });
 };
 
-export const stopPerfMonitor = async (serial: string) => {
+export const pushDebugAgent = async (serial: string) => {
   const traceId = createTraceId();
-  return tauriInvoke<CommandResponse<boolean>>("stop_perf_monitor", {
+  return tauriInvoke<CommandResponse<DebugAgentStatus>>("push_debug_agent", {
     serial,
     trace_id: traceId,
     traceId,
   });
 };
 
-export const startNetProfiler = async (
-  serial: string,
-  intervalMs?: number,
-  topN?: number,
-  pinnedUids?: number[],
-) => {
+export const startDebugAgent = async (serial: string) => {
   const traceId = createTraceId();
-  return tauriInvoke<CommandResponse<boolean>>("start_net_profiler", {
+  return tauriInvoke<CommandResponse<DebugAgentStatus>>("start_debug_agent", {
     serial,
-    interval_ms: intervalMs,
-    intervalMs,
-    top_n: topN,
-    topN,
-    pinned_uids: pinnedUids,
-    pinnedUids,
     trace_id: traceId,
     traceId,
   });
 };
 
-export const stopNetProfiler = async (serial: string) => {
+export const stopDebugAgent = async (serial: string) => {
   const traceId = createTraceId();
-  return tauriInvoke<CommandResponse<boolean>>("stop_net_profiler", {
+  return tauriInvoke<CommandResponse<DebugAgentStatus>>("stop_debug_agent", {
     serial,
     trace_id: traceId,
     traceId,
   });
 };
 
-export const setNetProfilerPinnedUids = async (serial: string, pinnedUids?: number[]) => {
+export const getDebugAgentStatus = async (serial: string) => {
   const traceId = createTraceId();
-  return tauriInvoke<CommandResponse<boolean>>("set_net_profiler_pinned_uids", {
+  return tauriInvoke<CommandResponse<DebugAgentStatus>>("get_debug_agent_status", {
     serial,
-    pinned_uids: pinnedUids,
-    pinnedUids,
     trace_id: traceId,
     traceId,
   });
 };
 
-export const startLogcat = async (serial: string, filter?: string) => {
+export const listDevtoolsTargets = async (serial: string) => {
   const traceId = createTraceId();
-  return tauriInvoke<CommandResponse<boolean>>("start_logcat", {
+  return tauriInvoke<CommandResponse<DevtoolsSocket[]>>("list_devtools_targets", {
     serial,
-    filter,
     trace_id: traceId,
     traceId,
   });
 };
 
-export const stopLogcat = async (serial: string) => {
+export const removeDevtoolsForwards = async (serial: string) => {
   const traceId = createTraceId();
-  return tauriInvoke<CommandResponse<boolean>>("stop_logcat", {
+  return tauriInvoke<CommandResponse<number>>("remove_devtools_forwards", {
     serial,
     trace_id: traceId,
     traceId,
   });
 };
 
-export const clearLogcat = async (serial: string) => {
+export const setNetworkShaping = async (
+  serial: string,
+  rateKbps: number,
+  latencyMs: number,
+  lossPct: number,
+) => {
   const traceId = createTraceId();
-  return tauriInvoke<CommandResponse<boolean>>("clear_logcat", {
+  return tauriInvoke<CommandResponse<NetworkShapingResult>>("set_network_shaping", {
     serial,
+    rate_kbps: rateKbps,
+    rateKbps,
+    latency_ms: latencyMs,
+    latencyMs,
+    loss_pct: lossPct,
+    lossPct,
     trace_id: traceId,
     traceId,
   });
 };
 
-export const exportLogcat = async (
-  serial: string,
-  lines: string[],
-  outputDir?: string,
-) => {
+export const clearNetworkShaping = async (serial: string) => {
   const traceId = createTraceId();
-  return tauriInvoke<CommandResponse<LogcatExportResult>>("export_logcat", {
+  return tauriInvoke<CommandResponse<NetworkShapingResult>>("clear_network_shaping", {
     serial,
-    lines,
-    output_dir: outputDir,
-    outputDir,
     trace_id: traceId,
     traceId,
   });
 };
 
-export const listApps = async (
-  serial: string,
-  thirdPartyOnly?: boolean,
-  includeVersions?: boolean,
-) => {
+export const inspectAppBinaries = async (serial: string, packageName: string) => {
   const traceId = createTraceId();
-  return tauriInvoke<CommandResponse<AppInfo[]>>("list_apps", {
+  return tauriInvoke<CommandResponse<AppBinaryInspection>>("inspect_app_binaries", {
     serial,
-    third_party_only: thirdPartyOnly,
-    thirdPartyOnly,
-    include_versions: includeVersions,
-    includeVersions,
+    package_name: packageName,
+    packageName,
     trace_id: traceId,
     traceId,
   });
 };
 
-export const getAppBasicInfo = async (serial: string, packageName: string) => {
+export const measureAppStartup = async (
+  serial: string,
+  packageName: string,
+  iterations?: number,
+  mode?: "cold" | "warm",
+) => {
   const traceId = createTraceId();
-  return tauriInvoke<CommandResponse<AppBasicInfo>>("get_app_basic_info", {
+  return tauriInvoke<CommandResponse<AppStartupResult>>("measure_app_startup", {
     serial,
     package_name: packageName,
     packageName,
+    iterations: iterations ?? null,
+    mode: mode ?? null,
     trace_id: traceId,
     traceId,
   });
 };
 
-export const getAppIcon = async (serial: string, packageName: string, apkPath?: string) => {
+export const getCapturedPushTokens = async (
+  serial: string,
+  packageName: string,
+  pattern?: string,
+) => {
   const traceId = createTraceId();
-  return tauriInvoke<CommandResponse<AppIcon>>("get_app_icon", {
+  return tauriInvoke<CommandResponse<CapturedPushToken[]>>("get_captured_push_tokens", {
     serial,
     package_name: packageName,
     packageName,
-    apk_path: apkPath,
-    apkPath,
+    pattern: pattern ?? null,
     trace_id: traceId,
     traceId,
   });
@@ -569,6 +2266,22 @@ export const uninstallApp = async (
   });
 };
 
+export const uninstallAppsBatch = async (
+  serials: string[],
+  packages: string[],
+  keepData: boolean,
+) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<AppUninstallResult[]>>("uninstall_apps_batch", {
+    serials,
+    packages,
+    keep_data: keepData,
+    keepData,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
 export const forceStopApp = async (serial: string, packageName: string) => {
   const traceId = createTraceId();
   return tauriInvoke<CommandResponse<boolean>>("force_stop_app", {
@@ -640,10 +2353,53 @@ export const checkScrcpy = async () => {
   });
 };
 
-export const launchScrcpy = async (serials: string[]) => {
+export const launchScrcpy = async (serials: string[], preset?: string) => {
   const traceId = createTraceId();
   return tauriInvoke<CommandResponse<CommandResult[]>>("launch_scrcpy", {
     serials,
+    preset: preset ?? null,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const validateScrcpyOptions = async (options: ScrcpyOptions, serial?: string) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<ScrcpyValidation>>("validate_scrcpy_options", {
+    options,
+    serial: serial ?? null,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const startAudioForward = async (
+  serial: string,
+  codec?: AudioForwardCodec,
+  source?: AudioForwardSource,
+) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<AudioForwardSession>>("start_audio_forward", {
+    serial,
+    codec: codec ?? null,
+    source: source ?? null,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const stopAudioForward = async (serial: string) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<boolean>>("stop_audio_forward", {
+    serial,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const listAudioForwards = async () => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<AudioForwardSession[]>>("list_audio_forwards", {
     trace_id: traceId,
     traceId,
   });
@@ -667,7 +2423,7 @@ export const stopBluetoothMonitor = async (serial: string) => {
   });
 };
 
-export const generateBugreport = async (serial: string, outputDir: string) => {
+export const generateBugreport = async (serial: string, outputDir?: string) => {
   const traceId = createTraceId();
   return tauriInvoke<CommandResponse<BugreportResult>>("generate_bugreport", {
     serial,
@@ -697,6 +2453,37 @@ export const prepareBugreportLogcat = async (sourcePath: string) => {
   });
 };
 
+export const importLogcatFile = async (sourcePath: string) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<BugreportLogSummary>>("import_logcat_file", {
+    source_path: sourcePath,
+    sourcePath,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
+export const exportBugreportLogcat = async (
+  reportId: string,
+  filters: BugreportLogFilters,
+  format: LogcatFileExportFormat,
+  outputPath: string,
+  year?: number,
+) => {
+  const traceId = createTraceId();
+  return tauriInvoke<CommandResponse<LogcatFileExportResult>>("export_bugreport_logcat", {
+    report_id: reportId,
+    reportId,
+    filters,
+    format,
+    output_path: outputPath,
+    outputPath,
+    year: year ?? null,
+    trace_id: traceId,
+    traceId,
+  });
+};
+
 export const queryBugreportLogcat = async (
   reportId: string,
   filters: BugreportLogFilters,
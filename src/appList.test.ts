@@ -0,0 +1,23 @@
+import { describe, expect, it } from "vitest";
+import { mergeAppListProgress } from "./appList";
+import type { AppInfo } from "./types";
+
+describe("appList", () => {
+  it("merges streamed version batches into the app list", () => {
+    const listed: AppInfo[] = [
+      { package_name: "com.b", is_system: false },
+      { package_name: "com.a", is_system: true },
+    ];
+    const first = mergeAppListProgress([], listed);
+    expect(first.map((app) => app.package_name)).toEqual(["com.a", "com.b"]);
+
+    const next = mergeAppListProgress(first, [
+      { package_name: "com.b", is_system: false, version_name: "2.0", version_code: "20" },
+    ]);
+    expect(next).toEqual([
+      { package_name: "com.a", is_system: true },
+      { package_name: "com.b", is_system: false, version_name: "2.0", version_code: "20" },
+    ]);
+    expect(mergeAppListProgress(next, [])).toBe(next);
+  });
+});
@@ -0,0 +1,15 @@
+import type { AppInfo } from "./types";
+
+/** Replace apps by package name and add new ones, keeping the list sorted like `list_apps`. */
+export const mergeAppListProgress = (current: AppInfo[], incoming: AppInfo[]): AppInfo[] => {
+  if (!incoming.length) {
+    return current;
+  }
+  const byPackage = new Map(current.map((app) => [app.package_name, app]));
+  for (const app of incoming) {
+    byPackage.set(app.package_name, app);
+  }
+  return Array.from(byPackage.values()).sort((a, b) =>
+    a.package_name < b.package_name ? -1 : a.package_name > b.package_name ? 1 : 0,
+  );
+};
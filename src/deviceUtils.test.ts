@@ -3,7 +3,9 @@ import {
   applyDeviceDetailPatch,
   filterDevicesBySearch,
   formatDeviceInfoMarkdown,
+  isSlowUsbConnection,
   mergeDeviceDetails,
+  upsertDeviceInfo,
   reduceSelectionToOne,
   resolveSelectedSerials,
   selectSerialsForGroup,
@@ -62,6 +64,25 @@ describe("deviceUtils", () => {
     expect(merged[0].detail?.wifi_is_on).toBe(true);
   });
 
+  it("replaces only the device pushed by a delta refresh", () => {
+    const devices: DeviceInfo[] = [
+      { summary: { serial: "alpha", state: "device" }, detail: null },
+      { summary: { serial: "bravo", state: "device" }, detail: { serial: "bravo", wifi_is_on: true } },
+    ];
+    const updated: DeviceInfo = {
+      summary: { serial: "alpha", state: "device" },
+      detail: { serial: "alpha", wifi_is_on: false },
+    };
+
+    const merged = upsertDeviceInfo(devices, updated);
+
+    expect(merged[0].detail?.wifi_is_on).toBe(false);
+    expect(merged[1]).toBe(devices[1]);
+    expect(
+      upsertDeviceInfo(devices, { summary: { serial: "gone", state: "device" }, detail: null }),
+    ).toBe(devices);
+  });
+
   it("applies detail patches only to targeted devices", () => {
     const devices: DeviceInfo[] = [
       {
@@ -127,6 +148,28 @@ describe("deviceUtils", () => {
     expect(markdown).toContain("- **Bluetooth:** Off");
   });
 
+  it("flags devices negotiated at USB 2.0 speed", () => {
+    const device: DeviceInfo = {
+      summary: { serial: "alpha", state: "device", connection_type: "usb", usb_port: "1-4.2" },
+      detail: {
+        serial: "alpha",
+        usb_speed_mbps: 480,
+        usb_speed: "USB 2.0 High Speed (480 Mbps)",
+        adb_features: ["cmd", "shell_v2"],
+      },
+    };
+
+    const markdown = formatDeviceInfoMarkdown(device);
+
+    expect(isSlowUsbConnection(device)).toBe(true);
+    expect(markdown).toContain("- **Connection:** USB, port 1-4.2");
+    expect(markdown).toContain("(slow link");
+    expect(markdown).toContain("- **adb Features:** cmd, shell_v2");
+    expect(
+      isSlowUsbConnection({ ...device, detail: { serial: "alpha", usb_speed_mbps: 5000 } }),
+    ).toBe(false);
+  });
+
   it("reduces selection to one device while keeping the primary when possible", () => {
     const devices: DeviceInfo[] = [
       { summary: { serial: "alpha", state: "offline" }, detail: null },
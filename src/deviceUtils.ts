@@ -40,6 +40,29 @@ export const reduceSelectionToOne = (previous: string[], devices: DeviceInfo[]):
   return preferred ? [preferred.summary.serial] : [];
 };
 
+// USB 2.0 (480 Mbps) or slower links make large installs and pulls crawl.
+export const SLOW_USB_SPEED_MBPS = 480;
+
+export const isSlowUsbConnection = (device: DeviceInfo): boolean => {
+  const mbps = device.detail?.usb_speed_mbps;
+  return mbps != null && mbps <= SLOW_USB_SPEED_MBPS;
+};
+
+const formatConnection = (device: DeviceInfo): string => {
+  const { connection_type: type, usb_port: port, transport_id: transportId } = device.summary;
+  if (!type) {
+    return "Unknown";
+  }
+  const parts = [type.toUpperCase()];
+  if (port) {
+    parts.push(`port ${port}`);
+  }
+  if (transportId) {
+    parts.push(`transport ${transportId}`);
+  }
+  return parts.join(", ");
+};
+
 export const formatDeviceInfoMarkdown = (device: DeviceInfo): string => {
   const detail = device.detail;
   const lines = [
@@ -63,6 +86,11 @@ export const formatDeviceInfoMarkdown = (device: DeviceInfo): string => {
     `- **Bluetooth:** ${formatDeviceValue(detail?.bt_is_on)}`,
     `- **GMS:** ${formatDeviceValue(detail?.gms_version)}`,
     `- **Fingerprint:** ${formatDeviceValue(detail?.build_fingerprint)}`,
+    `- **Connection:** ${formatConnection(device)}`,
+    `- **USB Speed:** ${formatDeviceValue(detail?.usb_speed)}${
+      isSlowUsbConnection(device) ? " (slow link: installs and transfers will be slower)" : ""
+    }`,
+    `- **adb Features:** ${detail?.adb_features?.length ? detail.adb_features.join(", ") : "Unknown"}`,
   ];
   return lines.join("\n");
 };
@@ -84,13 +112,27 @@ export const mergeDeviceDetails = (
 
   return incoming.map((device) => {
     const existing = currentBySerial.get(device.summary.serial);
+    const notes = device.notes ?? (preserveMissingDetail ? existing?.notes : null);
+    const reservation =
+      device.reservation ?? (preserveMissingDetail ? existing?.reservation : null);
     return {
       summary: device.summary,
       detail: device.detail ?? (preserveMissingDetail ? existing?.detail : null) ?? null,
+      ...(notes ? { notes } : {}),
+      ...(reservation ? { reservation } : {}),
     };
   });
 };
 
+// Replaces one device pushed by a delta refresh; devices that left the list meanwhile stay gone.
+export const upsertDeviceInfo = (devices: DeviceInfo[], updated: DeviceInfo): DeviceInfo[] => {
+  const serial = updated.summary.serial;
+  if (!devices.some((device) => device.summary.serial === serial)) {
+    return devices;
+  }
+  return devices.map((device) => (device.summary.serial === serial ? updated : device));
+};
+
 export const applyDeviceDetailPatch = (
   devices: DeviceInfo[],
   serials: string[],
@@ -2,7 +2,9 @@ import { describe, expect, it } from "vitest";
 import {
   createInitialTaskState,
   createTask,
+  fileTransferFailureStatus,
   inflateStoredTaskState,
+  isCancellableFileTransfer,
   parseStoredTaskState,
   sanitizeTaskStateForStorage,
   tasksReducer,
@@ -93,6 +95,15 @@ describe("tasksReducer", () => {
     expect(final.items[0].status).toBe("error");
   });
 
+  it("treats cancelled file transfers as cancelled, not failed", () => {
+    const task = createTask({ id: "1", kind: "file_pull", title: "Pull", serials: ["A"], trace_id: "t-1" });
+    expect(isCancellableFileTransfer(task)).toBe(true);
+    expect(isCancellableFileTransfer({ ...task, trace_id: null })).toBe(false);
+    expect(isCancellableFileTransfer({ ...task, kind: "shell" })).toBe(false);
+    expect(fileTransferFailureStatus("Cancelled by user")).toBe("cancelled");
+    expect(fileTransferFailureStatus("Pull failed: adb: remote object does not exist")).toBe("error");
+  });
+
   it("sanitizes and inflates stored tasks", () => {
     const state = createInitialTaskState();
     const longMessage = "x".repeat(500);
@@ -187,6 +187,15 @@ export const createTask = (params: {
   };
 };
 
+/** Matches the backend error of a pull or push stopped with `cancelFileTransfer`. */
+export const FILE_TRANSFER_CANCELLED_MESSAGE = "Cancelled by user";
+
+export const isCancellableFileTransfer = (task: TaskItem) =>
+  task.status === "running" && Boolean(task.trace_id) && (task.kind === "file_pull" || task.kind === "file_push");
+
+export const fileTransferFailureStatus = (message: string): TaskStatus =>
+  message.includes(FILE_TRANSFER_CANCELLED_MESSAGE) ? "cancelled" : "error";
+
 export const summarizeTask = (task: TaskItem) => {
   const serials = Object.keys(task.devices);
   const counts = { running: 0, success: 0, error: 0, cancelled: 0 } as Record<TaskStatus, number>;
@@ -5,6 +5,8 @@ export type DeviceSummary = {
   product?: string | null;
   device?: string | null;
   transport_id?: string | null;
+  connection_type?: "usb" | "tcp" | "emulator" | string;
+  usb_port?: string | null;
 };
 
 export type DeviceDetail = {
@@ -27,46 +29,1265 @@ export type DeviceDetail = {
   memory_total_bytes?: number | null;
   audio_state?: string | null;
   bluetooth_manager_state?: string | null;
+  usb_speed_mbps?: number | null;
+  usb_speed?: string | null;
+  adb_features?: string[];
+  lock_state?: DeviceLockState | null;
+  telephony?: TelephonyInfo | null;
+};
+
+export type TelephonySlot = {
+  slot: number;
+  sim_state?: string | null;
+  sim_operator?: string | null;
+  network_operator?: string | null;
+  network_type?: string | null;
+  roaming?: boolean | null;
+  service_state?: string | null;
+  data_state?: string | null;
+  signal_level?: number | null;
+  signal_dbm?: number | null;
+};
+
+export type BootInfo = {
+  serial: string;
+  current_slot?: string | null;
+  ab_update: boolean;
+  virtual_ab: boolean;
+  seamless_updates: boolean;
+  verified_boot_state?: string | null;
+  verity_mode?: string | null;
+  bootloader_locked?: boolean | null;
+  bootloader_version?: string | null;
+  boot_reason?: string | null;
+  build_fingerprint?: string | null;
+  security_patch?: string | null;
+};
+
+export type TelephonyInfo = {
+  serial: string;
+  slots: TelephonySlot[];
+};
+
+export type CameraInfo = {
+  id: string;
+  facing?: string | null;
+  orientation?: number | null;
+  hardware_level?: string | null;
+  max_jpeg_resolution?: string | null;
+  jpeg_resolutions: string[];
+};
+
+export type MediaCodecInfo = {
+  name: string;
+  media_type: string;
+  encoder: boolean;
+  hardware: boolean;
+};
+
+export type MediaCapabilities = {
+  serial: string;
+  cameras: CameraInfo[];
+  codecs: MediaCodecInfo[];
+};
+
+export type InputDeviceInfo = {
+  id?: number | null;
+  name: string;
+  path?: string | null;
+  kind: string;
+  classes: string[];
+  external: boolean;
+  enabled?: boolean | null;
+  vendor_id?: string | null;
+  product_id?: string | null;
+  key_layout_file?: string | null;
+  key_character_map_file?: string | null;
+  event_types: string[];
+  axes: string[];
+};
+
+export type InputDeviceInventory = {
+  serial: string;
+  devices: InputDeviceInfo[];
+};
+
+export type DeviceLockState = {
+  serial: string;
+  locked?: boolean | null;
+  secure?: boolean | null;
+  encryption_state?: string | null;
+  encryption_type?: string | null;
+  file_based_encryption?: boolean | null;
+  user_unlocked?: boolean | null;
+  in_setup_wizard?: boolean | null;
+  ready: boolean;
+};
+
+export type DeviceTransitionKind =
+  | "connected"
+  | "disconnected"
+  | "authorized"
+  | "online"
+  | "unauthorized"
+  | "offline"
+  | "state_changed";
+
+export type DeviceTransition = {
+  serial: string;
+  kind: DeviceTransitionKind;
+  previous_state?: string | null;
+  state?: string | null;
+  model?: string | null;
+};
+
+export type DeviceDetailUpdatedPayload = {
+  trace_id: string;
+  device: DeviceInfo;
+};
+
+export type DeviceTransitionPayload = {
+  trace_id: string;
+  transition: DeviceTransition;
+};
+
+export type RebootProgress = {
+  serial: string;
+  stage: "rebooting" | "waiting" | "booted" | "timed_out";
+  elapsed_ms: number;
+  timeout_ms: number;
+  error?: string | null;
+  trace_id: string;
+};
+
+export type DeviceReconnectStatus = {
+  serial: string;
+  status: "retrying" | "reconnected" | "gave_up";
+  attempt: number;
+  next_retry_ms?: number | null;
+  error?: string | null;
+  trace_id: string;
 };
 
 export type DeviceInfo = {
   summary: DeviceSummary;
   detail?: DeviceDetail | null;
+  notes?: DeviceNotes | null;
+  reservation?: DeviceReservation | null;
+};
+
+export type ChecklistItem = {
+  id: string;
+  text: string;
+  done: boolean;
+};
+
+export type ChecklistItemInput = {
+  id?: string | null;
+  text: string;
+  done?: boolean;
+};
+
+export type DeviceNotes = {
+  serial: string;
+  markdown: string;
+  checklist: ChecklistItem[];
+  updated_at: string;
+};
+
+export type DeviceProfileApp = {
+  package_name: string;
+  version_name?: string | null;
+  version_code?: string | null;
+  apk_files: string[];
+};
+
+export type DeviceProfileSettingKey = {
+  namespace: "system" | "secure" | "global";
+  key: string;
+};
+
+export type DeviceProfileSetting = DeviceProfileSettingKey & {
+  value?: string | null;
+};
+
+export type DeviceProfileFile = {
+  device_path: string;
+  stored_file: string;
+  size_bytes: number;
+  sha256: string;
+};
+
+export type DeviceProfile = {
+  name: string;
+  source_serial: string;
+  created_at: string;
+  apps: DeviceProfileApp[];
+  settings: DeviceProfileSetting[];
+  files: DeviceProfileFile[];
+};
+
+export type DeviceProfileStep = {
+  kind: "app" | "setting" | "file";
+  target: string;
+  status: "applied" | "skipped" | "failed";
+  detail: string;
+};
+
+export type DeviceProfileApplyReport = {
+  profile_name: string;
+  serial: string;
+  steps: DeviceProfileStep[];
+  applied: number;
+  skipped: number;
+  failed: number;
+};
+
+export type DeviceReservation = {
+  serial: string;
+  user: string;
+  until: string;
+  reserved_at: string;
+};
+
+export type ReservationConflictEvent = {
+  action: string;
+  user: string;
+  conflicts: DeviceReservation[];
+  trace_id: string;
+};
+
+export type DeviceFileEntry = {
+  name: string;
+  path: string;
+  is_dir: boolean;
+  size_bytes?: number | null;
+  modified_at?: string | null;
+};
+
+export type GroupActionParams =
+  | { action: "reboot"; params?: { mode?: string | null } }
+  | { action: "wifi"; params: { enable: boolean } }
+  | {
+      action: "install";
+      params: {
+        apk_path: string;
+        replace?: boolean;
+        allow_downgrade?: boolean;
+        grant?: boolean;
+        allow_test_packages?: boolean;
+        extra_args?: string | null;
+      };
+    }
+  | { action: "screenshot"; params?: { output_dir?: string | null } };
+
+export type GroupActionDeviceResult = {
+  serial: string;
+  success: boolean;
+  output?: string | null;
+  error?: string | null;
+};
+
+export type GroupActionResult = {
+  group: string;
+  action: string;
+  serials: string[];
+  results: GroupActionDeviceResult[];
+};
+
+export type WirelessFlow = "pairing" | "tcpip" | "already_wireless" | "unsupported";
+
+export type WirelessFlowInfo = {
+  serial: string;
+  api_level?: number | null;
+  flow: WirelessFlow;
+  wlan_ip?: string | null;
+};
+
+export type TcpipConnectResult = {
+  serial: string;
+  address: string;
+  attempts: number;
+  stdout: string;
+};
+
+export type CommandResult = {
+  serial: string;
+  stdout: string;
+  stderr: string;
+  exit_code?: number | null;
+};
+
+export type RebootResult = CommandResult & {
+  boot_completed?: boolean | null;
+  boot_elapsed_ms?: number | null;
+};
+
+export type HostCommandResult = {
+  stdout: string;
+  stderr: string;
+  exit_code?: number | null;
+};
+
+export type AdbInfo = {
+  available: boolean;
+  version_output: string;
+  command_path: string;
+  error?: string | null;
+};
+
+export type DependencyStatusKind = "ok" | "warning" | "missing" | "error";
+
+export type DependencyStatus = {
+  name: string;
+  status: DependencyStatusKind;
+  required: boolean;
+  path?: string | null;
+  version?: string | null;
+  detail?: string | null;
+  hint?: string | null;
+};
+
+export type EnvironmentReport = {
+  ready: boolean;
+  items: DependencyStatus[];
+};
+
+export type SetupWizardStepId =
+  | "adb_located"
+  | "device_authorized"
+  | "output_dir_chosen"
+  | "optional_tools_detected";
+
+export type SetupWizardStep = {
+  id: SetupWizardStepId;
+  status: "pending" | "completed" | "failed" | "skipped";
+  detail?: string | null;
+  error?: string | null;
+  hint?: string | null;
+  updated_at?: string | null;
+};
+
+export type SetupWizardState = {
+  steps: SetupWizardStep[];
+  current_step?: SetupWizardStepId | null;
+  completed: boolean;
+};
+
+export type UiDumpBackend =
+  | "auto"
+  | "uiautomator"
+  | "compressed"
+  | "view_hierarchy"
+  | "accessibility";
+
+export type DumpsysResult = {
+  serial: string;
+  service: string;
+  args: string[];
+  parser?: string | null;
+  parsed?: Record<string, unknown> | null;
+  raw?: string | null;
+  duration_ms: number;
+};
+
+export type LastBootLogSection = {
+  source: string;
+  label: string;
+  available: boolean;
+  content?: string | null;
+  truncated: boolean;
+  entry_time?: string | null;
+  panic_lines: string[];
+  error?: string | null;
+};
+
+export type LastBootLogs = {
+  serial: string;
+  sections: LastBootLogSection[];
+  panic_detected: boolean;
+};
+
+export type DumpsysParserInfo = {
+  service: string;
+  description: string;
+};
+
+export type CrashKind = "crash" | "anr" | "native_crash";
+
+export type TriageBundle = {
+  serial: string;
+  reason: CrashKind;
+  detail: string;
+  path: string;
+  files: string[];
+  errors: string[];
+  created_at: string;
+};
+
+export type TriageBundleReadyEvent = {
+  serial: string;
+  reason: CrashKind;
+  path: string;
+  trace_id: string;
+};
+
+export type MonkeyStressResult = {
+  serial: string;
+  package_name: string;
+  events_requested: number;
+  events_injected?: number | null;
+  seed?: number | null;
+  crashed: boolean;
+  crash_kind?: CrashKind | null;
+  triage?: TriageBundle | null;
+  exit_code?: number | null;
+  cancelled: boolean;
+  duration_ms: number;
+};
+
+export type AccessibilityFinding = {
+  kind: "missing_label" | "small_touch_target" | "low_contrast";
+  severity: "error" | "warning" | "info";
+  path: string;
+  class_name: string;
+  resource_id: string;
+  text: string;
+  bounds: string;
+  message: string;
+  contrast_ratio?: number | null;
+};
+
+export type AccessibilityAudit = {
+  serial: string;
+  density_dpi: number;
+  node_count: number;
+  clickable_count: number;
+  contrast_checked: boolean;
+  findings: AccessibilityFinding[];
+  captured_at: string;
+};
+
+export type UiHierarchyCaptureResult = {
+  html: string;
+  xml: string;
+  screenshot_data_url?: string | null;
+  screenshot_error?: string | null;
+  backend?: string;
+  capture_id?: string;
+  diff?: UiHierarchyDiff | null;
+};
+
+export type UiNodeChange = {
+  kind: "added" | "removed" | "changed";
+  path: string;
+  class_name: string;
+  resource_id: string;
+  details: string;
+};
+
+export type ScreenshotComparison = {
+  path_a: string;
+  path_b: string;
+  width: number;
+  height: number;
+  diff_pixels: number;
+  total_pixels: number;
+  similarity: number;
+  threshold: number;
+  passed: boolean;
+  size_mismatch: boolean;
+  diff_path: string;
+};
+
+export type GoldenImage = {
+  screen_name: string;
+  resolution: string;
+  path: string;
+  serial: string;
+  saved_at: string;
+};
+
+export type GoldenCheckStatus = "passed" | "failed" | "missing_golden";
+
+export type GoldenCheckResult = {
+  serial: string;
+  screen_name: string;
+  resolution: string;
+  status: GoldenCheckStatus;
+  golden_path?: string | null;
+  capture_path: string;
+  comparison?: ScreenshotComparison | null;
+  checked_at: string;
+  trace_id: string;
+};
+
+export type UiHierarchyDiff = {
+  previous_capture_id: string;
+  added: number;
+  removed: number;
+  changed: number;
+  changes: UiNodeChange[];
+  truncated: boolean;
+};
+
+export type CommandLogEntry = {
+  id: number;
+  trace_id: string;
+  serial?: string | null;
+  program: string;
+  args: string[];
+  started_at: string;
+  duration_ms: number;
+  exit_code?: number | null;
+  outcome: "ok" | "started" | "failed" | "timeout" | "error";
+};
+
+export type AppLogLevel = "trace" | "debug" | "info" | "warn" | "error" | "off";
+
+export type AppLogEntry = {
+  timestamp: string;
+  level: string;
+  target: string;
+  message: string;
+  fields: Record<string, unknown> | null;
+};
+
+export type AppLogStatus = {
+  default_level: string;
+  module_levels: Record<string, string>;
+  log_to_file: boolean;
+  log_file?: string | null;
+  max_log_files: number;
+  log_file_size_mb: number;
+  file_error?: string | null;
+};
+
+export type CommandLogQuery = {
+  serial?: string | null;
+  trace_id?: string | null;
+  contains?: string | null;
+  failed_only?: boolean;
+  limit?: number | null;
+};
+
+export type TimelineEvent = {
+  id: number;
+  serial: string;
+  kind: "install" | "crash" | "anr" | "perf_alert" | "screen_record" | "reboot" | "screen" | string;
+  timestamp: string;
+  summary: string;
+  trace_id: string;
+};
+
+export type TimelineQuery = {
+  serial: string;
+  since?: string | null;
+  until?: string | null;
+  kinds?: string[];
+  limit?: number | null;
+};
+
+export type SessionTimeRange = {
+  since?: string | null;
+  until?: string | null;
+};
+
+export type SessionReportResult = {
+  path: string;
+  device_count: number;
+  event_count: number;
+  capture_count: number;
+};
+
+export type TimingSummary = {
+  count: number;
+  total_ms: number;
+  max_ms: number;
+  avg_ms: number;
+};
+
+export type EventChannel = "logcat" | "perf" | "net" | "thermal" | "dmesg" | "lifecycle";
+
+export type CacheScope = "app_info" | "device_files" | "app_icon" | "app_list" | "all";
+
+export type EventSubscriptionInfo = {
+  serial: string;
+  channels: EventChannel[];
+  color: string;
+};
+
+export type InternalMetrics = {
+  uptime_ms: number;
+  commands: { command: string; failures: number; timing: TimingSummary }[];
+  scheduler: { global_limit: number; global_in_use: number; wait: TimingSummary };
+  events: {
+    event: string;
+    count: number;
+    per_minute: number;
+    rate_limited?: number;
+    coalesced?: number;
+    unsubscribed?: number;
+  }[];
+};
+
+export type ReadinessThresholds = {
+  min_battery_percent?: number;
+  min_free_storage_mb?: number;
+  max_clock_skew_secs?: number;
+};
+
+export type ReadinessCheck = {
+  name:
+    | "screen_unlocked"
+    | "battery"
+    | "storage"
+    | "no_pending_anr"
+    | "wifi_connected"
+    | "clock_in_sync";
+  status: "pass" | "fail" | "unknown";
+  detail: string;
+};
+
+export type DeviceReadinessResult = {
+  serial: string;
+  ready: boolean;
+  checks: ReadinessCheck[];
+};
+
+export type DeviceUnlockResult = {
+  serial: string;
+  pin_entered: boolean;
+  keyguard_locked?: boolean | null;
+};
+
+export type MockLocationResult = {
+  serial: string;
+  method: "emulator_geo_fix" | "helper_app";
+  helper_package?: string | null;
+};
+
+export type EmulatorSnapshot = {
+  name: string;
+  size: string;
+  created_at: string;
+  vm_clock?: string | null;
+};
+
+export type AdbTunnelStatus = {
+  name: string;
+  host: string;
+  local_port: number;
+  state: "up" | "down";
+  message?: string | null;
+  trace_id: string;
+};
+
+export type AppUninstallResult = {
+  serial: string;
+  package_name: string;
+  success: boolean;
+  skipped: boolean;
+  reason?: string | null;
+};
+
+export type AppListEntry = {
+  package_name: string;
+  label?: string | null;
+  version_name?: string | null;
+  version_code?: string | null;
+  is_system: boolean;
+  apk_path?: string | null;
+  icon_path?: string | null;
+  app_size_bytes?: number | null;
+  data_size_bytes?: number | null;
+  cache_size_bytes?: number | null;
+  first_install_time?: string | null;
+  last_update_time?: string | null;
+  metadata_pending: boolean;
+};
+
+export type IconCacheStats = {
+  file_count: number;
+  total_bytes: number;
+  max_bytes: number;
+};
+
+export type DebugAgentSettings = {
+  local_path: string;
+  remote_path: string;
+  device_port: number;
+  local_port: number;
+};
+
+export type UploadHeader = {
+  name: string;
+  value: string;
+  value_from_env?: boolean;
+};
+
+export type UploadTarget = {
+  name: string;
+  kind: "s3" | "gcs" | "http";
+  url?: string;
+  bucket?: string;
+  region?: string;
+  prefix?: string;
+  method?: string;
+  headers?: UploadHeader[];
+  access_key_env?: string;
+  secret_key_env?: string;
+  token_env?: string;
+};
+
+export type UploadSettings = {
+  curl_path?: string;
+  targets: UploadTarget[];
+};
+
+export type NotificationEventKind =
+  | "bugreport_completed"
+  | "bugreport_failed"
+  | "install_batch_finished"
+  | "perf_alert"
+  | "device_offline"
+  | "test";
+
+export type WebhookTarget = {
+  name: string;
+  url?: string;
+  url_env?: string;
+  events?: NotificationEventKind[];
+  template?: string;
+  headers?: UploadHeader[];
+  enabled?: boolean;
+};
+
+export type HookEventKind = "device_connected" | "install_complete" | "bugreport_finished" | "test";
+
+export type HookScript = {
+  name: string;
+  executable: string;
+  events?: HookEventKind[];
+  timeout_secs?: number;
+  enabled?: boolean;
+};
+
+export type HooksSettings = {
+  scripts?: HookScript[];
+};
+
+export type HookRunResult = {
+  hook: string;
+  exit_code?: number | null;
+  stdout: string;
+  stderr: string;
+  duration_ms: number;
+};
+
+export type CustomToolKind = "adb" | "host";
+
+export type CustomToolInputKind = "text" | "number" | "boolean" | "choice";
+
+export type CustomToolInput = {
+  name: string;
+  label?: string;
+  kind?: CustomToolInputKind;
+  required?: boolean;
+  default?: string | null;
+  choices?: string[];
+  pattern?: string | null;
+};
+
+export type CustomTool = {
+  name: string;
+  description?: string;
+  kind: CustomToolKind;
+  program?: string;
+  args?: string[];
+  inputs?: CustomToolInput[];
+  multi_device?: boolean;
+  timeout_secs?: number;
+};
+
+export type CustomToolsSettings = {
+  tools?: CustomTool[];
+};
+
+export type CustomToolRunResult = {
+  serial?: string | null;
+  success: boolean;
+  exit_code?: number | null;
+  stdout: string;
+  stderr: string;
+  error?: string | null;
+  duration_ms: number;
+};
+
+export type CustomToolResult = {
+  tool: string;
+  results: CustomToolRunResult[];
+};
+
+export type GithubIssueSettings = {
+  api_url?: string;
+  repo?: string;
+  token_env?: string;
+  attachment_upload_target?: string;
+};
+
+export type JiraIssueSettings = {
+  base_url?: string;
+  project_key?: string;
+  issue_type?: string;
+  email?: string;
+  token_env?: string;
+};
+
+export type IssueTrackerSettings = {
+  github?: GithubIssueSettings;
+  jira?: JiraIssueSettings;
+};
+
+export type CreatedIssue = {
+  provider: string;
+  key: string;
+  url: string;
+  attached: string[];
+  failed_attachments: string[];
+};
+
+export type ArtifactUploadResult = {
+  path: string;
+  url?: string | null;
+  bytes: number;
+  success: boolean;
+  error?: string | null;
+};
+
+export type DeviceFarmDevice = {
+  model: string;
+  version: string;
+  locale?: string;
+  orientation?: "" | "portrait" | "landscape";
+};
+
+export type DeviceFarmRequest = {
+  apk_path: string;
+  test_type: "robo" | "instrumentation";
+  test_apk_path?: string | null;
+  robo_script_path?: string | null;
+  test_targets?: string[];
+  devices: DeviceFarmDevice[];
+  timeout_minutes?: number | null;
+  output_dir?: string | null;
+};
+
+export type DeviceFarmBundle = {
+  bundle_dir: string;
+  argfile_path: string;
+  command: string[];
+};
+
+export type DeviceFarmOutcome = {
+  axis: string;
+  outcome: string;
+  details: string;
+};
+
+export type DeviceFarmRunResult = {
+  bundle: DeviceFarmBundle;
+  success: boolean;
+  outcome:
+    | "passed"
+    | "failed"
+    | "inconclusive"
+    | "incompatible"
+    | "cancelled"
+    | "infrastructure_error"
+    | "error";
+  exit_code?: number | null;
+  results_url?: string | null;
+  storage_url?: string | null;
+  outcomes: DeviceFarmOutcome[];
+  error?: string | null;
+};
+
+export type DeviceFarmProgressEvent = {
+  line: string;
+  results_url?: string;
+  trace_id: string;
+};
+
+export type DeviceFarmSettings = {
+  gcloud_path?: string;
+  project_id?: string;
+  results_bucket?: string;
+};
+
+export type UploadProgressEvent = {
+  target: string;
+  path: string;
+  index: number;
+  total: number;
+  status: "started" | "completed" | "failed";
+  bytes: number;
+  url?: string;
+  error?: string;
+  trace_id: string;
+};
+
+export type DebugAgentStatus = {
+  serial: string;
+  remote_path: string;
+  running: boolean;
+  pids: number[];
+  device_port: number;
+  local_port: number;
+  forwarded: boolean;
+};
+
+export type DevtoolsTarget = {
+  socket: string;
+  local_port: number;
+  id: string;
+  target_type: string;
+  title: string;
+  url: string;
+  websocket_url?: string | null;
+  devtools_url?: string | null;
+};
+
+export type DevtoolsSocket = {
+  socket: string;
+  package_name?: string | null;
+  pid?: number | null;
+  local_port?: number | null;
+  targets: DevtoolsTarget[];
+  error?: string | null;
+};
+
+export type OverlayPackage = {
+  package_name: string;
+  target_package: string;
+  state: "enabled" | "disabled" | "unavailable" | string;
+};
+
+export type AudioStream =
+  | "voice_call"
+  | "system"
+  | "ring"
+  | "music"
+  | "alarm"
+  | "notification"
+  | "accessibility";
+
+export type AudioStreamState = {
+  stream: string;
+  volume?: number | null;
+  min?: number | null;
+  max?: number | null;
+  muted: boolean;
+  devices: string[];
+};
+
+export type AudioRouteInfo = {
+  serial: string;
+  summary?: string | null;
+  media_route: string[];
+  streams: AudioStreamState[];
+};
+
+export type MediaKey =
+  | "play"
+  | "pause"
+  | "play_pause"
+  | "stop"
+  | "next"
+  | "previous"
+  | "fast_forward"
+  | "rewind";
+
+export type MediaSessionInfo = {
+  tag: string;
+  package_name?: string | null;
+  active: boolean;
+  state?: string | null;
+  position_ms?: number | null;
+  description?: string | null;
+};
+
+export type AccessibilityServiceInfo = {
+  component: string;
+  package_name: string;
+  enabled: boolean;
+  installed: boolean;
+};
+
+export type InputMethodInfo = {
+  id: string;
+  package_name: string;
+  enabled: boolean;
+  selected: boolean;
+};
+
+export type UsbFunctionState = {
+  serial: string;
+  current?: string | null;
+  functions: string[];
+  adb_enabled: boolean;
+};
+
+export type ProxyStatus = {
+  serial: string;
+  proxy?: string | null;
+};
+
+export type CaCertificateInstall = {
+  serial: string;
+  device_path: string;
+  installer_launched: boolean;
+  note: string;
+};
+
+export type PseudoLocale = "en-XA" | "ar-XB";
+
+export type I18nToggleResult = {
+  serial: string;
+  success: boolean;
+  locale?: string | null;
+  force_rtl?: boolean | null;
+  restart_required: boolean;
+  error?: string | null;
+};
+
+export type NetworkShapingResult = {
+  serial: string;
+  method: "emulator_console" | "tc";
+  interface?: string | null;
+  rate_kbps: number;
+  latency_ms: number;
+  loss_pct: number;
+  active: boolean;
+};
+
+export type NativeLibAbi = {
+  abi: string;
+  libraries: string[];
+};
+
+export type AppBinaryInspection = {
+  serial: string;
+  package_name: string;
+  apk_path: string;
+  apk_paths: string[];
+  apk_size_bytes: number;
+  dex_count: number;
+  native_libs: NativeLibAbi[];
+  min_sdk?: number | null;
+  target_sdk?: number | null;
+  debuggable: boolean;
+  primary_cpu_abi?: string | null;
+  device_abis: string[];
+  device_sdk?: number | null;
+  issues: string[];
+};
+
+export type ThermalReading = {
+  name: string;
+  kind: string;
+  temp_decic: number;
+  throttle_status?: number | null;
+};
+
+export type ThermalCrossing = {
+  name: string;
+  temp_decic: number;
+  threshold_decic: number;
+  rising: boolean;
+};
+
+export type ThermalSnapshot = {
+  ts_ms: number;
+  thermal_status?: number | null;
+  throttle_label?: string | null;
+  max_temp_decic?: number | null;
+  readings: ThermalReading[];
+};
+
+export type ThermalEvent = {
+  serial: string;
+  snapshot?: ThermalSnapshot;
+  crossings?: ThermalCrossing[];
+  error?: string;
+  trace_id: string;
 };
 
-export type DeviceFileEntry = {
-  name: string;
-  path: string;
-  is_dir: boolean;
-  size_bytes?: number | null;
-  modified_at?: string | null;
+export type PerfFrameDevice = {
+  serial: string;
+  snapshot?: PerfSnapshot | null;
 };
 
-export type CommandResult = {
+export type PerfMultiFrame = {
+  bucket_ts_ms: number;
+  bucket_ms: number;
+  devices: PerfFrameDevice[];
+};
+
+export type PerfFrameEvent = {
+  frame: PerfMultiFrame;
+  trace_id: string;
+};
+
+export type MemoryLeakEvent = {
   serial: string;
-  stdout: string;
-  stderr: string;
-  exit_code?: number | null;
+  package_name: string;
+  pss_kb: number;
+  slope_kb_per_min: number;
+  threshold_kb_per_min: number;
+  window_secs: number;
+  trace_id: string;
 };
 
-export type HostCommandResult = {
-  stdout: string;
-  stderr: string;
-  exit_code?: number | null;
+export type StartupTimeStats = {
+  min_ms: number;
+  median_ms: number;
+  max_ms: number;
 };
 
-export type AdbInfo = {
-  available: boolean;
-  version_output: string;
-  command_path: string;
+export type StartupSample = {
+  total_time_ms?: number | null;
+  wait_time_ms?: number | null;
+  launch_state?: string | null;
+};
+
+export type AppStartupResult = {
+  serial: string;
+  package_name: string;
+  activity: string;
+  mode: "cold" | "warm";
+  samples: StartupSample[];
+  total_time?: StartupTimeStats | null;
+  wait_time?: StartupTimeStats | null;
+};
+
+export type JankScenarioResult = {
+  serial: string;
+  package_name: string;
+  gestures_played: number;
+  total_frames?: number | null;
+  janky_frames?: number | null;
+  jank_percent_x100?: number | null;
+  p50_ms?: number | null;
+  p90_ms?: number | null;
+  p95_ms?: number | null;
+  p99_ms?: number | null;
+  missed_vsync?: number | null;
+  slow_ui_thread?: number | null;
+  frame_deadline_missed?: number | null;
+};
+
+export type CapturedPushToken = {
+  token: string;
+  first_seen?: string | null;
+  last_seen?: string | null;
+  occurrences: number;
+};
+
+export type UiSelector = {
+  resource_id?: string | null;
+  text?: string | null;
+  content_desc?: string | null;
+};
+
+export type TextFieldEntry = {
+  x?: number | null;
+  y?: number | null;
+  selector?: UiSelector | null;
+  value: string;
+  clear?: boolean;
+};
+
+export type TextFieldFillResult = {
+  index: number;
+  success: boolean;
   error?: string | null;
 };
 
-export type UiHierarchyCaptureResult = {
-  html: string;
+export type TypeTextResult = {
+  serial: string;
+  method: "input_text" | "adb_keyboard";
+  characters: number;
+  chunks: number;
+};
+
+export type UiCaptureRecord = {
+  capture_id: string;
+  serial: string;
+  captured_at: string;
+  backend: string;
+  node_count: number;
   xml: string;
-  screenshot_data_url?: string | null;
-  screenshot_error?: string | null;
+  html: string;
+  diff?: UiHierarchyDiff | null;
+};
+
+export type PointerGesture = {
+  kind: "tap" | "long_press" | "swipe";
+  x: number;
+  y: number;
+  end_x?: number | null;
+  end_y?: number | null;
+  duration_ms?: number | null;
+  source_width: number;
+  source_height: number;
+};
+
+export type PointerGestureResult = {
+  serial: string;
+  kind: string;
+  device_width: number;
+  device_height: number;
+  x: number;
+  y: number;
+  end_x?: number | null;
+  end_y?: number | null;
+  duration_ms?: number | null;
+};
+
+export type GestureRecordingSummary = {
+  serial: string;
+  path: string;
+  stroke_count: number;
+  duration_ms: number;
+  source_width: number;
+  source_height: number;
+};
+
+export type GestureReplayResult = {
+  serial: string;
+  strokes_played: number;
+  device_width: number;
+  device_height: number;
+};
+
+export type InputBroadcastDeviceResult = {
+  serial: string;
+  success: boolean;
+  error?: string | null;
+};
+
+export type InputBroadcastInfo = {
+  leader: string;
+  followers: string[];
+  touch_device: string;
+};
+
+export type InputBroadcastEvent = {
+  leader: string;
+  kind: "tap" | "long_press" | "swipe";
+  results: InputBroadcastDeviceResult[];
+  trace_id: string;
 };
 
 export type UiHierarchyExportResult = {
@@ -87,6 +1308,16 @@ export type CommandResponse<T> = {
   data: T;
 };
 
+/** Rejection payload of a command. `error` is already in the configured language;
+ * `message_key` and `params` identify the message for client-side localization. */
+export type AppErrorPayload = {
+  error: string;
+  code: string;
+  trace_id: string;
+  message_key?: string | null;
+  params?: Record<string, string>;
+};
+
 export type BluetoothState =
   | "Idle"
   | "Scanning"
@@ -182,6 +1413,46 @@ export type LogcatEvent = {
   trace_id: string;
 };
 
+export type DmesgLine = {
+  timestamp_secs?: number | null;
+  level?: number | null;
+  message: string;
+};
+
+export type DmesgEvent = {
+  serial: string;
+  lines: DmesgLine[];
+  trace_id: string;
+};
+
+export type DmesgStreamInfo = {
+  serial: string;
+  mode: "direct" | "su";
+};
+
+export type ActivityLifecycleEvent = {
+  serial: string;
+  timestamp: string;
+  pid?: number | null;
+  kind: "activity" | "fragment";
+  state: string;
+  package?: string | null;
+  component: string;
+  reason?: string | null;
+  trace_id: string;
+};
+
+export type LifecycleStreamInfo = {
+  serial: string;
+  include_fragments: boolean;
+};
+
+export type CpuCluster = {
+  label: string;
+  cores: number[];
+  max_freq_khz?: number | null;
+};
+
 export type PerfSnapshot = {
   ts_ms: number;
   cpu_total_percent_x100?: number | null;
@@ -195,6 +1466,7 @@ export type PerfSnapshot = {
   battery_temp_decic?: number | null;
   display_refresh_hz_x100?: number | null;
   missed_frames_per_sec_x100?: number | null;
+  cpu_clusters?: CpuCluster[];
 };
 
 export type PerfEvent = {
@@ -242,6 +1514,58 @@ export type TerminalEvent = {
   trace_id: string;
 };
 
+export type PipelineCapture = {
+  variable: string;
+  pattern: string;
+};
+
+export type ShellPipelineStep = {
+  name?: string | null;
+  command: string;
+  continue_on_error?: boolean;
+  captures?: PipelineCapture[];
+  timeout_secs?: number | null;
+};
+
+export type ShellPipelineStepResult = {
+  index: number;
+  name?: string | null;
+  command?: string | null;
+  status: "ok" | "failed" | "skipped";
+  exit_code?: number | null;
+  stdout: string;
+  stderr: string;
+  captured: Record<string, string>;
+  error?: string | null;
+  duration_ms: number;
+};
+
+export type ShellPipelineResult = {
+  serial: string;
+  succeeded: boolean;
+  stopped_at?: number | null;
+  variables: Record<string, string>;
+  steps: ShellPipelineStepResult[];
+};
+
+export type ShellStreamInfo = {
+  serial: string;
+  stream_id: string;
+  timeout_secs: number;
+};
+
+export type ShellStreamEvent = {
+  serial: string;
+  stream_id: string;
+  event: "output" | "exit";
+  stream?: "stdout" | "stderr" | null;
+  chunk?: string | null;
+  exit_code?: number | null;
+  cancelled: boolean;
+  timed_out: boolean;
+  trace_id: string;
+};
+
 export type TerminalSettings = {
   restore_sessions: string[];
   buffers: Record<string, string[]>;
@@ -274,6 +1598,111 @@ export type ApkBatchInstallResult = {
   apk_info?: ApkInfo | null;
   results: Record<string, ApkInstallResult>;
   total_duration_seconds: number;
+  capture_paths?: Record<string, string>;
+};
+
+export type CaptureContextInfo = {
+  context_id: string;
+  serial: string;
+  label: string;
+  started_at: string;
+};
+
+export type CaptureContextResult = {
+  context_id: string;
+  serial: string;
+  label: string;
+  path: string;
+  log_lines: number;
+  dropped_log_lines: number;
+  perf_samples: number;
+  installs: ApkInstallResult[];
+};
+
+export type SoakProfile = {
+  label?: string | null;
+  sample_interval_secs?: number | null;
+  screenshot_interval_secs?: number | null;
+  logcat_interval_secs?: number | null;
+  logcat_lines?: number | null;
+  cpu_threshold_percent?: number | null;
+  mem_threshold_percent?: number | null;
+  temp_threshold_decic?: number | null;
+};
+
+export type SoakAlert = {
+  timestamp: string;
+  metric: "cpu" | "memory" | "temperature";
+  value: number;
+  threshold: number;
+  summary: string;
+};
+
+export type SoakTestInfo = {
+  serial: string;
+  label: string;
+  started_at: string;
+  path: string;
+  profile: SoakProfile;
+  started_monitors: string[];
+};
+
+export type SoakTestReport = {
+  serial: string;
+  label: string;
+  started_at: string;
+  ended_at: string;
+  duration_secs: number;
+  path: string;
+  samples: number;
+  screenshots: number;
+  logcat_snapshots: number;
+  capture_errors: number;
+  alerts: SoakAlert[];
+  cpu_avg_percent_x100?: number | null;
+  cpu_max_percent_x100?: number | null;
+  mem_max_used_bytes?: number | null;
+  temp_max_decic?: number | null;
+  net_usage: SoakNetUsage[];
+};
+
+export type SoakNetUsage = {
+  uid: number;
+  packages?: string[];
+  rx_bytes: number;
+  tx_bytes: number;
+};
+
+export type BuildVariantApk = {
+  variant: string;
+  path: string;
+  application_id?: string | null;
+  version_code?: number | null;
+  version_name?: string | null;
+  output_type?: string | null;
+  filters: string[];
+  size_bytes: number;
+  modified_at?: string | null;
+  apk_info?: ApkInfo | null;
+};
+
+export type ApkWatchOptions = {
+  replace?: boolean;
+  allow_downgrade?: boolean;
+  grant?: boolean;
+  allow_test_packages?: boolean;
+  extra_args?: string | null;
+  relaunch?: boolean;
+};
+
+export type ApkWatchEvent = {
+  path: string;
+  cycle: number;
+  phase: "detected" | "installed" | "failed" | "stopped";
+  result?: ApkBatchInstallResult;
+  relaunched: string[];
+  error?: string;
+  trace_id: string;
 };
 
 export type AppInfo = {
@@ -282,6 +1711,36 @@ export type AppInfo = {
   version_code?: string | null;
   is_system: boolean;
   apk_path?: string | null;
+  enabled?: boolean | null;
+  target_sdk?: number | null;
+};
+
+export type AppListSortKey = "package_name" | "version_code" | "target_sdk";
+
+export type AppListQuery = {
+  name_contains?: string | null;
+  enabled?: boolean | null;
+  system?: boolean | null;
+  min_target_sdk?: number | null;
+  sort_by?: AppListSortKey | null;
+  descending?: boolean;
+  offset?: number | null;
+  limit?: number | null;
+};
+
+export type AppListPage = {
+  apps: AppInfo[];
+  total: number;
+  has_more: boolean;
+  next_offset: number;
+};
+
+export type AppListProgress = {
+  serial: string;
+  apps: AppInfo[];
+  resolved: number;
+  total: number;
+  trace_id: string;
 };
 
 export type AppBasicInfo = {
@@ -369,6 +1828,14 @@ export type BugreportLogSummary = {
   buffers: Record<string, number>;
 };
 
+export type LogcatFileExportFormat = "threadtime" | "android_studio";
+
+export type LogcatFileExportResult = {
+  output_path: string;
+  format: LogcatFileExportFormat;
+  rows: number;
+};
+
 export type BugreportLogFilters = {
   levels: string[];
   buffer?: string | null;
@@ -450,6 +1917,82 @@ export type UiSettings = {
   show_console_panel: boolean;
   single_selection: boolean;
   default_output_path: string;
+  language?: "en" | "zh-TW";
+};
+
+export type DeviceSession = {
+  connected_at_ms: number;
+  disconnected_at_ms?: number | null;
+  duration_ms: number;
+  connection_type: AdbTransport;
+  interrupted: boolean;
+};
+
+export type DeviceHistory = {
+  serial: string;
+  model?: string | null;
+  connection_type: AdbTransport;
+  first_seen_ms: number;
+  last_seen_ms: number;
+  total_connected_ms: number;
+  session_count: number;
+  sessions: DeviceSession[];
+};
+
+export type OnConnectActionKind =
+  | "stay_awake"
+  | "disable_animations"
+  | "set_locale"
+  | "start_logcat";
+
+export type OnConnectAction = {
+  kind: OnConnectActionKind;
+  value?: string;
+};
+
+export type OnConnectProfile = {
+  serial: string;
+  enabled: boolean;
+  actions: OnConnectAction[];
+};
+
+export type OnConnectSettings = {
+  profiles: OnConnectProfile[];
+};
+
+export type OnConnectAuditEntry = {
+  serial: string;
+  action: OnConnectActionKind;
+  value: string;
+  success: boolean;
+  error?: string | null;
+  applied_at: string;
+  trace_id: string;
+};
+
+export type FactoryResetChallenge = {
+  serial: string;
+  model?: string | null;
+  token: string;
+  expires_in_ms: number;
+};
+
+export type FactoryResetReport = {
+  serial: string;
+  wipe_started: boolean;
+  wipe_method: "recovery" | "factory_reset_broadcast" | "master_clear_broadcast";
+  boot_completed?: boolean | null;
+  provisioning?: DeviceProfileApplyReport | null;
+};
+
+export type FactoryResetAuditEntry = {
+  serial: string;
+  stage: "requested" | "wipe" | "boot" | "provision";
+  method?: string | null;
+  success: boolean;
+  detail?: string | null;
+  at: string;
+  trace_id: string;
 };
 
 export type DeviceSettings = {
@@ -458,6 +2001,9 @@ export type DeviceSettings = {
   auto_connect: boolean;
   show_offline_devices: boolean;
   preferred_devices: string[];
+  auto_reconnect?: boolean;
+  auto_reconnect_window_secs?: number;
+  reservation_user?: string;
 };
 
 export type CommandSettings = {
@@ -467,8 +2013,36 @@ export type CommandSettings = {
   parallel_execution: boolean;
 };
 
+export type SshTunnelSettings = {
+  user: string;
+  key_path: string;
+  ssh_port: number;
+  remote_port: number;
+  local_port: number;
+};
+
+export type AdbServerHost = {
+  name: string;
+  host: string;
+  port: number;
+  ssh_tunnel?: SshTunnelSettings | null;
+};
+
+export type AdbTransport = "usb" | "tcp" | "emulator";
+
+export type AdbPathOverride = {
+  serial?: string;
+  transport?: AdbTransport | "";
+  command_path: string;
+};
+
 export type AdbSettings = {
   command_path: string;
+  socket_client?: boolean;
+  compress_wireless_pulls?: boolean;
+  server_hosts?: AdbServerHost[];
+  active_server?: string;
+  path_overrides?: AdbPathOverride[];
 };
 
 export type LoggingSettings = {
@@ -486,7 +2060,7 @@ export type LogcatSettings = {
   max_buffer_size: number;
 };
 
-export type ScrcpySettings = {
+export type ScrcpyOptions = {
   stay_awake: boolean;
   turn_screen_off: boolean;
   disable_screensaver: boolean;
@@ -496,12 +2070,48 @@ export type ScrcpySettings = {
   extra_args: string;
 };
 
+export type ScrcpyPreset = ScrcpyOptions & {
+  name: string;
+};
+
+export type ScrcpySettings = ScrcpyOptions & {
+  presets?: ScrcpyPreset[];
+  device_presets?: Record<string, string>;
+};
+
+export type ScrcpyOptionIssue = {
+  flag: string;
+  message: string;
+};
+
+export type ScrcpyValidation = {
+  available: boolean;
+  version?: string | null;
+  command: string[];
+  issues: ScrcpyOptionIssue[];
+  valid: boolean;
+};
+
+export type AudioForwardCodec = "opus" | "aac" | "flac" | "raw";
+export type AudioForwardSource = "output" | "mic";
+
+export type AudioForwardSession = {
+  serial: string;
+  codec: AudioForwardCodec;
+  source: AudioForwardSource;
+  started_at: string;
+  running: boolean;
+  error?: string | null;
+};
+
 export type ApkInstallSettings = {
   replace_existing: boolean;
   allow_downgrade: boolean;
   grant_permissions: boolean;
   allow_test_packages: boolean;
   extra_args: string;
+  build_project_dir?: string;
+  build_module?: string;
 };
 
 export type ScreenshotSettings = {
@@ -518,6 +2128,42 @@ export type ScreenRecordSettings = {
   bugreport: boolean;
   verbose: boolean;
   display_id: number;
+  convert_format?: "" | "gif" | "webp";
+  convert_max_width?: number;
+  convert_fps?: number;
+  ffmpeg_path?: string;
+};
+
+export type RecordingConversionStatus = {
+  serial: string;
+  source_path: string;
+  format: string;
+  elapsed_ms: number;
+};
+
+export type ScreenRecordConversionProgress = {
+  serial: string;
+  source_path: string;
+  format: string;
+  converted_ms: number;
+  percent?: number | null;
+};
+
+export type ScreenRecordConversionResult = {
+  source_path: string;
+  output_path?: string | null;
+  format: string;
+  size_bytes?: number | null;
+  error?: string | null;
+};
+
+export type OutputSettings = {
+  screenshots_dir: string;
+  recordings_dir: string;
+  bugreports_dir: string;
+  logs_dir: string;
+  ui_dumps_dir: string;
+  dated_subfolders: boolean;
 };
 
 export type LogcatViewerSettings = {
@@ -537,11 +2183,25 @@ export type NotificationsSettings = {
   desktop_on_success: boolean;
   desktop_on_error: boolean;
   desktop_on_cancelled: boolean;
+  webhooks?: WebhookTarget[];
+};
+
+export type RestrictedModeSettings = {
+  enabled: boolean;
+  password_hash: string;
+};
+
+export type TimeoutSettings = {
+  probe_secs: number;
+  device_command_secs: number;
+  install_secs: number;
+  transfer_secs: number;
 };
 
 export type AppConfig = {
   ui: UiSettings;
   device: DeviceSettings;
+  on_connect?: OnConnectSettings;
   command: CommandSettings;
   adb: AdbSettings;
   logging: LoggingSettings;
@@ -557,5 +2217,14 @@ export type AppConfig = {
   device_groups: Record<string, string[]>;
   output_path: string;
   file_gen_output_path: string;
+  output?: OutputSettings;
+  restricted_mode?: RestrictedModeSettings;
+  debug_agent?: DebugAgentSettings;
+  upload?: UploadSettings;
+  issues?: IssueTrackerSettings;
+  device_farm?: DeviceFarmSettings;
+  hooks?: HooksSettings;
+  custom_tools?: CustomToolsSettings;
+  timeouts?: TimeoutSettings;
   version: string;
 };